@@ -4,18 +4,38 @@
 //! - JWT token validation
 //! - API key authentication
 //! - Role-based access control
+//!
+//! API keys and bearer tokens resolve to a [`Principal`] with a role
+//! (read-only, trader or admin) that may be scoped to a set of strategies.
+//! Reads need read-only access, mutating routes need trader access and key
+//! management needs admin access. A scoped principal can only modify its own
+//! strategies. Bearer tokens are validated as described in [`crate::jwt`].
+//!
+//! Until an API key or bearer tokens are configured nobody can
+//! authenticate, so only reads are served.
 
+use crate::jwt::{JwtConfig, JwtValidator};
 use axum::{
     body::Body,
-    extract::Request,
-    http::{HeaderMap, StatusCode},
+    extract::{Request, State},
+    http::{HeaderMap, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{EncodingKey, Header, encode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tracing::{debug, warn};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// Prefix of generated API key secrets.
+const API_KEY_PREFIX: &str = "clmm_";
+
+/// Number of secret characters kept to identify a key.
+const API_KEY_DISPLAY_LEN: usize = 12;
 
 /// JWT claims structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,14 +84,14 @@ impl Claims {
     }
 }
 
-/// User roles for access control.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// User roles for access control, from least to most privileged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Role {
     /// Read-only access.
     ReadOnly,
-    /// Can execute operations.
-    Execute,
-    /// Full administrative access.
+    /// Can open, close and rebalance positions and manage strategies.
+    Trader,
+    /// Full administrative access, including key management.
     Admin,
 }
 
@@ -81,17 +101,23 @@ impl Role {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::ReadOnly => "read_only",
-            Self::Execute => "execute",
+            Self::Trader => "trader",
             Self::Admin => "admin",
         }
     }
 
+    /// Checks whether this role grants the access of `required`.
+    #[must_use]
+    pub fn allows(self, required: Role) -> bool {
+        self >= required
+    }
+
     /// Parses role from string.
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "read_only" | "readonly" => Some(Self::ReadOnly),
-            "execute" => Some(Self::Execute),
+            "trader" | "execute" => Some(Self::Trader),
             "admin" => Some(Self::Admin),
             _ => None,
         }
@@ -103,12 +129,16 @@ impl Role {
 pub struct AuthConfig {
     /// JWT secret key.
     pub jwt_secret: String,
-    /// Valid API keys.
+    /// Bootstrap API keys, granted the admin role.
     pub api_keys: HashSet<String>,
-    /// Whether authentication is required.
+    /// Whether authentication is required even when no API keys exist.
+    ///
+    /// Authentication is always enforced once any API key exists.
     pub require_auth: bool,
     /// Token expiration time in seconds.
     pub token_expiry_secs: u64,
+    /// Bearer token validation; tokens are rejected when unset.
+    pub jwt: Option<JwtConfig>,
 }
 
 impl Default for AuthConfig {
@@ -118,53 +148,268 @@ impl Default for AuthConfig {
             api_keys: HashSet::new(),
             require_auth: false,
             token_expiry_secs: 3600, // 1 hour
+            jwt: None,
+        }
+    }
+}
+
+/// A managed API key.
+///
+/// Only a SHA-256 hash of the secret is stored; the secret itself is
+/// returned once, when the key is created.
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    /// Key ID.
+    pub id: String,
+    /// Human-readable name.
+    pub name: String,
+    /// Granted role.
+    pub role: Role,
+    /// Strategy IDs the key may modify; empty for all strategies.
+    pub strategies: Vec<String>,
+    /// Tenant the key acts for; `None` for the operator.
+    pub tenant: Option<String>,
+    /// Leading characters of the secret, for identification.
+    pub prefix: String,
+    /// Creation time.
+    pub created_at: DateTime<Utc>,
+    /// Last successful authentication.
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Returns the principal authenticated by this key.
+    #[must_use]
+    pub fn principal(&self) -> Principal {
+        Principal {
+            subject: self.id.clone(),
+            role: self.role,
+            strategies: self.strategies.clone(),
+            tenant: self.tenant.clone(),
+        }
+    }
+}
+
+/// An authenticated caller.
+///
+/// Added to the request extensions by [`auth_middleware`].
+#[derive(Debug, Clone)]
+pub struct Principal {
+    /// API key ID or token subject.
+    pub subject: String,
+    /// Granted role.
+    pub role: Role,
+    /// Strategy IDs the caller may modify; empty for all strategies.
+    pub strategies: Vec<String>,
+    /// Tenant the caller acts for; `None` for the operator, who sees every
+    /// tenant's resources.
+    pub tenant: Option<String>,
+}
+
+impl Principal {
+    /// Checks whether the caller may see a resource owned by a tenant.
+    ///
+    /// The operator sees everything; tenants only see their own resources.
+    #[must_use]
+    pub fn can_access(&self, owner: Option<&str>) -> bool {
+        match &self.tenant {
+            None => true,
+            Some(tenant) => owner == Some(tenant.as_str()),
+        }
+    }
+
+    /// Checks whether the caller is restricted to specific strategies.
+    #[must_use]
+    pub fn is_scoped(&self) -> bool {
+        !self.strategies.is_empty()
+    }
+
+    /// Checks whether the caller may modify a strategy.
+    #[must_use]
+    pub fn can_modify_strategy(&self, strategy_id: &str) -> bool {
+        !self.is_scoped() || self.strategies.iter().any(|s| s == strategy_id)
+    }
+}
+
+/// In-memory store of API keys indexed by secret hash.
+#[derive(Debug, Default)]
+pub struct ApiKeyStore {
+    /// Keys by SHA-256 hash of their secret.
+    keys: RwLock<HashMap<String, ApiKey>>,
+}
+
+impl ApiKeyStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store holding the given secrets as admin keys.
+    pub fn with_bootstrap_keys(secrets: impl IntoIterator<Item = String>) -> Self {
+        let keys = secrets
+            .into_iter()
+            .filter(|secret| !secret.is_empty())
+            .map(|secret| {
+                let key = new_key(&secret, "bootstrap", Role::Admin, Vec::new());
+                (hash_secret(&secret), key)
+            })
+            .collect();
+        Self {
+            keys: RwLock::new(keys),
+        }
+    }
+
+    /// Creates an operator key and returns it with its secret.
+    pub async fn create(
+        &self,
+        name: impl Into<String>,
+        role: Role,
+        strategies: Vec<String>,
+    ) -> (ApiKey, String) {
+        self.create_for_tenant(name, role, strategies, None).await
+    }
+
+    /// Creates a key acting for a tenant and returns it with its secret.
+    pub async fn create_for_tenant(
+        &self,
+        name: impl Into<String>,
+        role: Role,
+        strategies: Vec<String>,
+        tenant: Option<String>,
+    ) -> (ApiKey, String) {
+        let secret = format!("{}{}", API_KEY_PREFIX, uuid::Uuid::new_v4().simple());
+        let mut key = new_key(&secret, name, role, strategies);
+        key.tenant = tenant;
+        self.keys
+            .write()
+            .await
+            .insert(hash_secret(&secret), key.clone());
+
+        info!(id = %key.id, name = %key.name, role = key.role.as_str(), "API key created");
+        (key, secret)
+    }
+
+    /// Looks up the key for a secret and records its use.
+    pub async fn authenticate(&self, secret: &str) -> Option<ApiKey> {
+        let mut keys = self.keys.write().await;
+        let key = keys.get_mut(&hash_secret(secret))?;
+        key.last_used_at = Some(Utc::now());
+        Some(key.clone())
+    }
+
+    /// Lists all keys, oldest first.
+    pub async fn list(&self) -> Vec<ApiKey> {
+        let mut keys: Vec<ApiKey> = self.keys.read().await.values().cloned().collect();
+        keys.sort_by_key(|k| k.created_at);
+        keys
+    }
+
+    /// Revokes a key by ID and returns it.
+    ///
+    /// The last admin key is kept, so the store can always be managed.
+    ///
+    /// # Errors
+    /// Returns an error if the key does not exist or is the last admin key.
+    pub async fn revoke(&self, id: &str) -> Result<ApiKey, RevokeError> {
+        let mut keys = self.keys.write().await;
+        let (hash, key) = keys
+            .iter()
+            .find(|(_, k)| k.id == id)
+            .ok_or(RevokeError::NotFound)?;
+        if key.role == Role::Admin && keys.values().filter(|k| k.role == Role::Admin).count() == 1 {
+            return Err(RevokeError::LastAdmin);
         }
+        let hash = hash.clone();
+        let key = keys.remove(&hash).ok_or(RevokeError::NotFound)?;
+        info!(id = %key.id, name = %key.name, "API key revoked");
+        Ok(key)
+    }
+
+    /// Checks whether the store holds no keys.
+    pub async fn is_empty(&self) -> bool {
+        self.keys.read().await.is_empty()
+    }
+}
+
+/// Reasons an API key cannot be revoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum RevokeError {
+    /// No key has the ID.
+    #[error("API key not found")]
+    NotFound,
+    /// The key is the last admin key.
+    #[error("Cannot revoke the last admin key")]
+    LastAdmin,
+}
+
+/// Builds a key record for a secret.
+fn new_key(secret: &str, name: impl Into<String>, role: Role, strategies: Vec<String>) -> ApiKey {
+    ApiKey {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.into(),
+        role,
+        strategies,
+        tenant: None,
+        prefix: secret.chars().take(API_KEY_DISPLAY_LEN).collect(),
+        created_at: Utc::now(),
+        last_used_at: None,
     }
 }
 
+/// Hashes an API key secret for storage.
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
 /// Authentication state shared across handlers.
 #[derive(Clone)]
 pub struct AuthState {
     config: Arc<AuthConfig>,
+    keys: Arc<ApiKeyStore>,
+    jwt: Option<Arc<JwtValidator>>,
 }
 
 impl AuthState {
     /// Creates a new authentication state.
+    ///
+    /// The configured API keys are registered as admin keys.
     pub fn new(config: AuthConfig) -> Self {
+        let keys = ApiKeyStore::with_bootstrap_keys(config.api_keys.iter().cloned());
+        let jwt = config
+            .jwt
+            .clone()
+            .map(|jwt| Arc::new(JwtValidator::new(jwt)));
         Self {
             config: Arc::new(config),
+            keys: Arc::new(keys),
+            jwt,
         }
     }
 
-    /// Validates an API key.
+    /// Gets the API key store.
     #[must_use]
-    pub fn validate_api_key(&self, key: &str) -> bool {
-        self.config.api_keys.contains(key)
+    pub fn keys(&self) -> &Arc<ApiKeyStore> {
+        &self.keys
     }
 
-    /// Validates a JWT token.
-    pub fn validate_jwt(&self, token: &str) -> Result<Claims, AuthError> {
-        // Simple JWT validation (in production, use a proper JWT library)
-        // This is a simplified implementation for demonstration
-        let parts: Vec<&str> = token.split('.').collect();
-        if parts.len() != 3 {
-            return Err(AuthError::InvalidToken);
-        }
-
-        // Decode payload (base64)
-        let payload = parts[1];
-        let decoded = base64_decode(payload).map_err(|_| AuthError::InvalidToken)?;
-        let claims: Claims =
-            serde_json::from_slice(&decoded).map_err(|_| AuthError::InvalidToken)?;
+    /// Validates an API key.
+    pub async fn validate_api_key(&self, key: &str) -> bool {
+        self.keys.authenticate(key).await.is_some()
+    }
 
-        if claims.is_expired() {
-            return Err(AuthError::TokenExpired);
+    /// Validates a JWT bearer token.
+    ///
+    /// # Errors
+    /// Returns an error if bearer tokens are not configured or the token is
+    /// invalid.
+    pub async fn validate_jwt(&self, token: &str) -> Result<Principal, AuthError> {
+        match &self.jwt {
+            Some(jwt) => jwt.validate(token).await,
+            None => Err(AuthError::InvalidToken),
         }
-
-        Ok(claims)
     }
 
-    /// Creates a JWT token for a user.
+    /// Creates an HS256 JWT token for a user, signed with the JWT secret.
     pub fn create_token(&self, user_id: &str, roles: Vec<String>) -> Result<String, AuthError> {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -173,14 +418,12 @@ impl AuthState {
 
         let claims = Claims::new(user_id, now + self.config.token_expiry_secs, roles);
 
-        // Simple JWT creation (in production, use proper signing)
-        let header = base64_encode(b"{\"alg\":\"HS256\",\"typ\":\"JWT\"}");
-        let payload = base64_encode(
-            &serde_json::to_vec(&claims).map_err(|_| AuthError::TokenCreationFailed)?,
-        );
-        let signature = base64_encode(b"signature"); // Simplified
-
-        Ok(format!("{}.{}.{}", header, payload, signature))
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.config.jwt_secret.as_bytes()),
+        )
+        .map_err(|_| AuthError::TokenCreationFailed)
     }
 
     /// Checks if authentication is required.
@@ -188,6 +431,20 @@ impl AuthState {
     pub fn require_auth(&self) -> bool {
         self.config.require_auth
     }
+
+    /// Checks whether requests must authenticate.
+    ///
+    /// True when required by configuration, when bearer tokens are
+    /// configured, or once any API key exists.
+    pub async fn is_enforced(&self) -> bool {
+        self.config.require_auth || self.jwt.is_some() || !self.keys.is_empty().await
+    }
+}
+
+impl Default for AuthState {
+    fn default() -> Self {
+        Self::new(AuthConfig::default())
+    }
 }
 
 /// Authentication errors.
@@ -211,6 +468,9 @@ pub enum AuthError {
     /// Token creation failed.
     #[error("Failed to create token")]
     TokenCreationFailed,
+    /// Mutating request while no API key or bearer tokens are configured.
+    #[error("Authentication is not configured; set API_KEYS or bearer tokens to use this route")]
+    NotConfigured,
 }
 
 impl IntoResponse for AuthError {
@@ -221,6 +481,7 @@ impl IntoResponse for AuthError {
             Self::TokenExpired => StatusCode::UNAUTHORIZED,
             Self::InvalidApiKey => StatusCode::UNAUTHORIZED,
             Self::InsufficientPermissions => StatusCode::FORBIDDEN,
+            Self::NotConfigured => StatusCode::FORBIDDEN,
             Self::TokenCreationFailed => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -262,170 +523,116 @@ pub enum AuthMethod {
     ApiKey(String),
 }
 
-/// Authentication middleware.
-pub async fn auth_middleware(
-    headers: HeaderMap,
-    request: Request<Body>,
-    next: Next,
-) -> Result<Response, AuthError> {
-    // Extract auth state from extensions if available
-    // For now, we'll do a simple check
-
-    match extract_auth(&headers) {
-        Some(AuthMethod::Bearer(token)) => {
-            debug!("Bearer token authentication");
-            // Validate token (simplified)
-            if token.is_empty() {
-                warn!("Empty bearer token");
-                return Err(AuthError::InvalidToken);
-            }
-        }
-        Some(AuthMethod::ApiKey(key)) => {
-            debug!("API key authentication");
-            if key.is_empty() {
-                warn!("Empty API key");
-                return Err(AuthError::InvalidApiKey);
-            }
-        }
-        None => {
-            // Allow unauthenticated requests for now (can be configured)
-            debug!("No authentication provided");
-        }
-    }
-
-    Ok(next.run(request).await)
+/// Checks whether a path is served without authentication.
+pub(crate) fn is_public(path: &str) -> bool {
+    path.starts_with("/health") || path == "/metrics"
 }
 
-/// Requires a specific role.
-pub async fn require_role(
-    required_role: Role,
-    headers: HeaderMap,
-    request: Request<Body>,
-    next: Next,
-) -> Result<Response, AuthError> {
-    match extract_auth(&headers) {
-        Some(AuthMethod::Bearer(token)) => {
-            // Parse claims and check role
-            let parts: Vec<&str> = token.split('.').collect();
-            if parts.len() == 3
-                && let Ok(decoded) = base64_decode(parts[1])
-                && let Ok(claims) = serde_json::from_slice::<Claims>(&decoded)
-                && claims.has_role(required_role.as_str())
-            {
-                return Ok(next.run(request).await);
-            }
-            Err(AuthError::InsufficientPermissions)
-        }
-        Some(AuthMethod::ApiKey(_)) => {
-            // API keys have full access for now
-            Ok(next.run(request).await)
-        }
-        None => Err(AuthError::MissingAuth),
+/// Returns the role required for a request.
+///
+/// Reads (including GraphQL queries) need read-only access, key, webhook
+/// and wallet management, the request audit log and engaging or releasing
+/// the kill switch or portfolio stop need admin access, and everything else
+/// needs trader access.
+#[must_use]
+pub fn required_role(method: &Method, path: &str) -> Role {
+    if path.starts_with("/auth")
+        || path.starts_with("/webhooks")
+        || path.starts_with("/wallets")
+        || path.starts_with("/audit/requests")
+    {
+        return Role::Admin;
     }
-}
-
-// Helper functions for base64 encoding/decoding
-
-fn base64_encode(data: &[u8]) -> String {
-    use std::io::Write;
-    let mut buf = Vec::new();
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || path == "/analytics/simulate"
+        || path == "/graphql"
     {
-        let mut encoder = Base64Encoder::new(&mut buf);
-        encoder.write_all(data).ok();
+        return Role::ReadOnly;
     }
-    String::from_utf8(buf).unwrap_or_default()
-}
-
-fn base64_decode(data: &str) -> Result<Vec<u8>, ()> {
-    // Simple base64 URL-safe decoding
-    let data = data.replace('-', "+").replace('_', "/");
-    let padding = (4 - data.len() % 4) % 4;
-    let padded = format!("{}{}", data, "=".repeat(padding));
-
-    base64_decode_standard(&padded)
-}
-
-fn base64_decode_standard(data: &str) -> Result<Vec<u8>, ()> {
-    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-
-    let mut result = Vec::new();
-    let mut buffer = 0u32;
-    let mut bits = 0;
-
-    for c in data.bytes() {
-        if c == b'=' {
-            break;
-        }
-        let value = ALPHABET.iter().position(|&x| x == c).ok_or(())? as u32;
-        buffer = (buffer << 6) | value;
-        bits += 6;
-
-        if bits >= 8 {
-            bits -= 8;
-            result.push((buffer >> bits) as u8);
-            buffer &= (1 << bits) - 1;
-        }
+    if path.starts_with("/emergency") {
+        return Role::Admin;
     }
-
-    Ok(result)
+    Role::Trader
 }
 
-/// Simple base64 encoder.
-struct Base64Encoder<'a> {
-    output: &'a mut Vec<u8>,
-    buffer: u32,
-    bits: u8,
+/// Extracts the strategy ID from a `/strategies/{id}/...` path.
+fn strategy_id(path: &str) -> Option<&str> {
+    path.strip_prefix("/strategies/")?
+        .split('/')
+        .next()
+        .filter(|id| !id.is_empty())
 }
 
-impl<'a> Base64Encoder<'a> {
-    fn new(output: &'a mut Vec<u8>) -> Self {
-        Self {
-            output,
-            buffer: 0,
-            bits: 0,
-        }
+/// Authentication middleware.
+///
+/// Resolves the API key or bearer token to a [`Principal`], checks its role
+/// against the route and, for scoped principals, that mutating requests
+/// target one of its strategies. The principal is added to the request
+/// extensions for handlers.
+///
+/// While authentication is not enforced, reads are served anonymously and
+/// every other route is refused, so nobody can mint the first admin key.
+pub async fn auth_middleware(
+    State(auth): State<AuthState>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let path = request.uri().path().to_string();
+    if is_public(&path) {
+        return Ok(next.run(request).await);
     }
-}
-
-impl std::io::Write for Base64Encoder<'_> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-
-        for &byte in buf {
-            self.buffer = (self.buffer << 8) | byte as u32;
-            self.bits += 8;
-
-            while self.bits >= 6 {
-                self.bits -= 6;
-                let index = ((self.buffer >> self.bits) & 0x3F) as usize;
-                self.output.push(ALPHABET[index]);
-            }
+    if !auth.is_enforced().await {
+        if required_role(request.method(), &path) != Role::ReadOnly {
+            warn!(path = %path, "Refusing mutating request, authentication is not configured");
+            return Err(AuthError::NotConfigured);
         }
-
-        Ok(buf.len())
+        return Ok(next.run(request).await);
     }
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        if self.bits > 0 {
-            const ALPHABET: &[u8] =
-                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-            let index = ((self.buffer << (6 - self.bits)) & 0x3F) as usize;
-            self.output.push(ALPHABET[index]);
-
-            // Add padding
-            let padding = (3 - (self.bits / 8 + 1) % 3) % 3;
-            for _ in 0..padding {
-                self.output.push(b'=');
+    let principal = match extract_auth(request.headers()) {
+        Some(AuthMethod::ApiKey(secret)) => match auth.keys.authenticate(&secret).await {
+            Some(key) => key.principal(),
+            None => {
+                warn!(path = %path, "Invalid API key");
+                return Err(AuthError::InvalidApiKey);
+            }
+        },
+        Some(AuthMethod::Bearer(token)) => match auth.validate_jwt(&token).await {
+            Ok(principal) => principal,
+            Err(e) => {
+                warn!(path = %path, error = %e, "Invalid bearer token");
+                return Err(e);
             }
+        },
+        None => {
+            debug!(path = %path, "No authentication provided");
+            return Err(AuthError::MissingAuth);
         }
-        Ok(())
+    };
+
+    let required = required_role(request.method(), &path);
+    if !principal.role.allows(required) {
+        warn!(
+            subject = %principal.subject,
+            role = principal.role.as_str(),
+            required = required.as_str(),
+            path = %path,
+            "Insufficient role"
+        );
+        return Err(AuthError::InsufficientPermissions);
     }
-}
 
-impl Drop for Base64Encoder<'_> {
-    fn drop(&mut self) {
-        let _ = std::io::Write::flush(self);
+    // Scoped principals may only modify their own strategies
+    if required != Role::ReadOnly
+        && principal.is_scoped()
+        && !strategy_id(&path).is_some_and(|id| principal.can_modify_strategy(id))
+    {
+        warn!(subject = %principal.subject, path = %path, "Request outside scope");
+        return Err(AuthError::InsufficientPermissions);
     }
+
+    request.extensions_mut().insert(principal);
+    Ok(next.run(request).await)
 }
 
 #[cfg(test)]
@@ -462,17 +669,175 @@ mod tests {
     #[test]
     fn test_role_parsing() {
         assert_eq!(Role::from_str("admin"), Some(Role::Admin));
-        assert_eq!(Role::from_str("execute"), Some(Role::Execute));
+        assert_eq!(Role::from_str("trader"), Some(Role::Trader));
+        assert_eq!(Role::from_str("execute"), Some(Role::Trader));
         assert_eq!(Role::from_str("read_only"), Some(Role::ReadOnly));
         assert_eq!(Role::from_str("readonly"), Some(Role::ReadOnly));
         assert_eq!(Role::from_str("unknown"), None);
     }
 
     #[test]
-    fn test_base64_roundtrip() {
-        let original = b"Hello, World!";
-        let encoded = base64_encode(original);
-        let decoded = base64_decode(&encoded).unwrap();
-        assert_eq!(decoded, original);
+    fn test_required_role() {
+        assert_eq!(required_role(&Method::GET, "/positions"), Role::ReadOnly);
+        assert_eq!(
+            required_role(&Method::POST, "/analytics/simulate"),
+            Role::ReadOnly
+        );
+        assert_eq!(
+            required_role(&Method::POST, "/strategies/s1/start"),
+            Role::Trader
+        );
+        assert_eq!(
+            required_role(&Method::GET, "/emergency/kill-switch"),
+            Role::ReadOnly
+        );
+        assert_eq!(
+            required_role(&Method::POST, "/emergency/kill-switch"),
+            Role::Admin
+        );
+        assert_eq!(
+            required_role(&Method::POST, "/emergency/portfolio-stop"),
+            Role::Admin
+        );
+        assert_eq!(
+            required_role(&Method::DELETE, "/emergency/kill-switch"),
+            Role::Admin
+        );
+        assert_eq!(
+            required_role(&Method::DELETE, "/emergency/portfolio-stop"),
+            Role::Admin
+        );
+        assert_eq!(required_role(&Method::GET, "/auth/keys"), Role::Admin);
+        assert_eq!(required_role(&Method::GET, "/webhooks"), Role::Admin);
+        assert_eq!(
+            required_role(&Method::GET, "/wallets/hot/balance"),
+            Role::Admin
+        );
+        assert_eq!(required_role(&Method::POST, "/graphql"), Role::ReadOnly);
+        assert_eq!(required_role(&Method::GET, "/audit"), Role::ReadOnly);
+        assert_eq!(required_role(&Method::GET, "/audit/requests"), Role::Admin);
+
+        assert!(Role::Admin.allows(Role::Trader));
+        assert!(!Role::ReadOnly.allows(Role::Trader));
+        assert_eq!(strategy_id("/strategies/s1/exits/p1"), Some("s1"));
+        assert_eq!(strategy_id("/strategies"), None);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_store() {
+        let store = ApiKeyStore::with_bootstrap_keys(vec!["root".to_string()]);
+        assert_eq!(store.authenticate("root").await.unwrap().role, Role::Admin);
+
+        let (key, secret) = store
+            .create("bot", Role::Trader, vec!["s1".to_string()])
+            .await;
+        assert!(secret.starts_with(API_KEY_PREFIX));
+        assert!(secret.starts_with(&key.prefix));
+        assert!(key.principal().can_modify_strategy("s1"));
+        assert!(!key.principal().can_modify_strategy("s2"));
+
+        let authenticated = store.authenticate(&secret).await.unwrap();
+        assert_eq!(authenticated.id, key.id);
+        assert!(authenticated.last_used_at.is_some());
+        assert!(store.authenticate("wrong").await.is_none());
+
+        assert_eq!(store.list().await.len(), 2);
+        assert_eq!(store.revoke(&key.id).await.unwrap().id, key.id);
+        assert!(store.authenticate(&secret).await.is_none());
+        assert_eq!(
+            store.revoke(&key.id).await.unwrap_err(),
+            RevokeError::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revoke_keeps_last_admin() {
+        let store = ApiKeyStore::with_bootstrap_keys(vec!["root".to_string()]);
+        let root = store.authenticate("root").await.unwrap();
+        assert_eq!(
+            store.revoke(&root.id).await.unwrap_err(),
+            RevokeError::LastAdmin
+        );
+
+        let (admin, _) = store.create("ops", Role::Admin, Vec::new()).await;
+        let store = Arc::new(store);
+        let revokes = [root.id.clone(), admin.id.clone()].map(|id| {
+            let store = store.clone();
+            tokio::spawn(async move { store.revoke(&id).await })
+        });
+        let mut revoked = 0;
+        for revoke in revokes {
+            if revoke.await.unwrap().is_ok() {
+                revoked += 1;
+            }
+        }
+        assert_eq!(revoked, 1);
+        assert_eq!(store.list().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_auth_serves_reads_only() {
+        use axum::{Router, routing::get};
+        use tower::ServiceExt;
+
+        let auth = AuthState::default();
+        assert!(!auth.is_enforced().await);
+        let app = Router::new()
+            .route("/positions", get(|| async { "ok" }))
+            .route("/auth/keys", get(|| async { "ok" }).post(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(auth, auth_middleware));
+
+        let send = |method: Method, uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let request = axum::http::Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap();
+                app.oneshot(request).await.unwrap().status()
+            }
+        };
+        assert_eq!(send(Method::GET, "/positions").await, StatusCode::OK);
+        assert_eq!(
+            send(Method::POST, "/auth/keys").await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(send(Method::GET, "/auth/keys").await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_keys() {
+        let store = ApiKeyStore::new();
+        let (operator, _) = store.create("ops", Role::Trader, Vec::new()).await;
+        let (tenant, _) = store
+            .create_for_tenant("alice", Role::Trader, Vec::new(), Some("alice".to_string()))
+            .await;
+
+        assert!(operator.principal().can_access(None));
+        assert!(operator.principal().can_access(Some("alice")));
+        assert!(tenant.principal().can_access(Some("alice")));
+        assert!(!tenant.principal().can_access(Some("bob")));
+        assert!(!tenant.principal().can_access(None));
+    }
+
+    #[tokio::test]
+    async fn test_token_roundtrip() {
+        let auth = AuthState::new(AuthConfig {
+            jwt_secret: "s3cret".to_string(),
+            jwt: Some(JwtConfig::secret("s3cret")),
+            ..Default::default()
+        });
+        assert!(auth.is_enforced().await);
+
+        let token = auth
+            .create_token("user1", vec!["trader".to_string()])
+            .unwrap();
+        let principal = auth.validate_jwt(&token).await.unwrap();
+        assert_eq!(principal.subject, "user1");
+        assert_eq!(principal.role, Role::Trader);
+
+        // Tokens are rejected when bearer auth is not configured
+        assert!(AuthState::default().validate_jwt(&token).await.is_err());
     }
 }
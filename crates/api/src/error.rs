@@ -5,6 +5,9 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use clmm_lp_data::prelude::DataError;
+use clmm_lp_execution::prelude::ExecutionError;
+use clmm_lp_protocols::prelude::ProtocolError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use utoipa::ToSchema;
@@ -43,6 +46,10 @@ pub enum ApiError {
     /// Service unavailable.
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
+
+    /// Rate limit exceeded.
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
 }
 
 impl ApiError {
@@ -77,6 +84,7 @@ impl ApiError {
             Self::Validation(_) => "VALIDATION_ERROR",
             Self::Internal(_) => "INTERNAL_ERROR",
             Self::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
+            Self::TooManyRequests(_) => "RATE_LIMITED",
         }
     }
 
@@ -91,6 +99,7 @@ impl ApiError {
             Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
@@ -108,8 +117,94 @@ impl IntoResponse for ApiError {
     }
 }
 
+impl From<&DataError> for ApiError {
+    fn from(err: &DataError) -> Self {
+        match err {
+            DataError::RateLimited {
+                retry_after_secs: Some(secs),
+                ..
+            } => Self::TooManyRequests(format!("{}, retry after {}s", err, secs)),
+            DataError::RateLimited { .. } => Self::TooManyRequests(err.to_string()),
+            DataError::NotFound(what) => Self::NotFound(what.clone()),
+            DataError::Io(_) => Self::Internal(err.to_string()),
+            DataError::Unauthorized { .. }
+            | DataError::Provider { .. }
+            | DataError::Http(_)
+            | DataError::InvalidData(_) => {
+                Self::ServiceUnavailable(format!("Market data unavailable: {}", err))
+            }
+        }
+    }
+}
+
+impl From<&ProtocolError> for ApiError {
+    fn from(err: &ProtocolError) -> Self {
+        match err {
+            ProtocolError::InvalidAddress(_)
+            | ProtocolError::UnsupportedChain(_)
+            | ProtocolError::UnsupportedProgram(_)
+            | ProtocolError::Unsupported(_) => Self::BadRequest(err.to_string()),
+            ProtocolError::AccountNotFound(_) => Self::NotFound(err.to_string()),
+            ProtocolError::RpcUnavailable { .. } => Self::ServiceUnavailable(err.to_string()),
+            ProtocolError::InvalidAccountData { .. }
+            | ProtocolError::Transaction(_)
+            | ProtocolError::ContractCall { .. } => Self::Internal(err.to_string()),
+        }
+    }
+}
+
+impl From<&ExecutionError> for ApiError {
+    fn from(err: &ExecutionError) -> Self {
+        match err {
+            ExecutionError::Protocol(e) => e.into(),
+            ExecutionError::PositionNotFound(_)
+            | ExecutionError::NoPendingExit(_)
+            | ExecutionError::ShadowRecordNotFound(_) => Self::NotFound(err.to_string()),
+            ExecutionError::InvalidPositionState { .. }
+            | ExecutionError::PriceDivergence { .. }
+            | ExecutionError::PriceImpactTooHigh { .. }
+            | ExecutionError::SimulationFailed(_) => Self::Conflict(err.to_string()),
+            ExecutionError::InvalidRange(_) => Self::Validation(err.to_string()),
+            ExecutionError::NotPositionOwner { .. } => Self::Forbidden(err.to_string()),
+            ExecutionError::CircuitBreakerOpen
+            | ExecutionError::ConfirmationTimeout(_)
+            | ExecutionError::OracleUnavailable(_) => Self::ServiceUnavailable(err.to_string()),
+            ExecutionError::TransactionFailed(_) => Self::Internal(err.to_string()),
+        }
+    }
+}
+
+impl From<DataError> for ApiError {
+    fn from(err: DataError) -> Self {
+        Self::from(&err)
+    }
+}
+
+impl From<ProtocolError> for ApiError {
+    fn from(err: ProtocolError) -> Self {
+        Self::from(&err)
+    }
+}
+
+impl From<ExecutionError> for ApiError {
+    fn from(err: ExecutionError) -> Self {
+        Self::from(&err)
+    }
+}
+
+/// Maps typed library errors found in the chain and falls back to an
+/// internal error.
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
+        if let Some(e) = ExecutionError::find(&err) {
+            return e.into();
+        }
+        if let Some(e) = ProtocolError::find(&err) {
+            return e.into();
+        }
+        if let Some(e) = err.chain().find_map(|e| e.downcast_ref::<DataError>()) {
+            return e.into();
+        }
         Self::Internal(err.to_string())
     }
 }
@@ -128,3 +223,45 @@ pub struct ErrorResponse {
 
 /// Result type for API handlers.
 pub type ApiResult<T> = Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_data_errors_map_to_status_codes() {
+        let rate_limited = ApiError::from(DataError::RateLimited {
+            provider: "Birdeye",
+            retry_after_secs: Some(10),
+        });
+        assert_eq!(rate_limited.status_code(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(rate_limited.to_string().contains("retry after 10s"));
+
+        let not_found = ApiError::from(DataError::NotFound("Price for SOL".to_string()));
+        assert_eq!(not_found.status_code(), StatusCode::NOT_FOUND);
+
+        let unauthorized = ApiError::from(DataError::Unauthorized {
+            provider: "Birdeye",
+        });
+        assert_eq!(unauthorized.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn test_anyhow_errors_are_classified() {
+        let err = anyhow::Error::from(ProtocolError::AccountNotFound("abc".to_string()))
+            .context("Failed to fetch pool");
+        assert_eq!(ApiError::from(err).status_code(), StatusCode::NOT_FOUND);
+
+        let err = anyhow::Error::from(ExecutionError::Protocol(ProtocolError::InvalidAddress(
+            "xyz".to_string(),
+        )));
+        assert_eq!(ApiError::from(err).status_code(), StatusCode::BAD_REQUEST);
+
+        let err = anyhow::anyhow!("boom");
+        assert_eq!(
+            ApiError::from(err).status_code(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
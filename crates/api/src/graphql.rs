@@ -0,0 +1,237 @@
+//! GraphQL endpoint over positions, pools, and analytics.
+//!
+//! Enabled with the `graphql` feature. The schema is read-only and resolves
+//! through the REST handlers, so a dashboard can fetch positions with their
+//! pool, PnL, and history in one request:
+//!
+//! ```graphql
+//! {
+//!   positions {
+//!     address
+//!     pnl { netPnlUsd feesEarnedUsd }
+//!     pool { price feeRateBps }
+//!   }
+//!   portfolio { totalValueUsd positionsInRange }
+//! }
+//! ```
+//!
+//! `POST /graphql` executes queries; `GET /graphql` serves GraphiQL.
+//! Queries see the positions and analytics of the caller's tenant (see
+//! [`crate::tenancy`]).
+
+use crate::error::ApiError;
+use crate::handlers;
+use crate::models::{
+    PoolResponse, PortfolioAnalyticsResponse, PortfolioHistoryQuery, PortfolioHistoryResponse,
+    PositionHistoryQuery, PositionHistoryResponse, PositionResponse,
+};
+use crate::state::AppState;
+use crate::tenancy::TenantScope;
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Schema};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{
+    Extension, Router,
+    extract::{Path, Query, State},
+    response::{Html, IntoResponse},
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+
+/// Maximum nesting depth of a query.
+const MAX_QUERY_DEPTH: usize = 8;
+
+/// The API's GraphQL schema.
+pub type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Maps a not-found error to `None`.
+fn optional<T>(result: Result<T, ApiError>) -> async_graphql::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ApiError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Returns the tenant scope of a query.
+///
+/// Queries executed outside an HTTP request run as an operator.
+fn scope(ctx: &Context<'_>) -> TenantScope {
+    ctx.data_opt::<TenantScope>()
+        .cloned()
+        .unwrap_or_else(TenantScope::operator)
+}
+
+/// Root of GraphQL queries.
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Monitored positions.
+    async fn positions(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<PositionResponse>> {
+        let state = ctx.data::<AppState>()?.clone();
+        Ok(
+            handlers::list_positions(State(state), Extension(scope(ctx)))
+                .await?
+                .0
+                .positions,
+        )
+    }
+
+    /// A monitored position by address.
+    async fn position(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> async_graphql::Result<Option<PositionResponse>> {
+        let state = ctx.data::<AppState>()?.clone();
+        if !scope(ctx).can_see_position_at(&state, &address).await {
+            return Ok(None);
+        }
+        optional(
+            handlers::get_position(State(state), Path(address))
+                .await
+                .map(|r| r.0),
+        )
+    }
+
+    /// A pool by address.
+    async fn pool(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> async_graphql::Result<Option<PoolResponse>> {
+        let state = ctx.data::<AppState>()?.clone();
+        optional(
+            handlers::get_pool(State(state), Path(address))
+                .await
+                .map(|r| r.0),
+        )
+    }
+
+    /// Portfolio analytics.
+    async fn portfolio(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<PortfolioAnalyticsResponse> {
+        let state = ctx.data::<AppState>()?.clone();
+        Ok(
+            handlers::get_portfolio_analytics(State(state), Extension(scope(ctx)))
+                .await?
+                .0,
+        )
+    }
+
+    /// Portfolio PnL history over a window such as `24h`, `7d` or `30d`.
+    async fn portfolio_history(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = "30d")] window: String,
+    ) -> async_graphql::Result<PortfolioHistoryResponse> {
+        let state = ctx.data::<AppState>()?.clone();
+        let query = PortfolioHistoryQuery { window };
+        Ok(
+            handlers::get_portfolio_history(State(state), Extension(scope(ctx)), Query(query))
+                .await?
+                .0,
+        )
+    }
+}
+
+#[ComplexObject]
+impl PositionResponse {
+    /// Pool the position belongs to.
+    async fn pool(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<PoolResponse>> {
+        let state = ctx.data::<AppState>()?.clone();
+        optional(
+            handlers::get_pool(State(state), Path(self.pool_address.clone()))
+                .await
+                .map(|r| r.0),
+        )
+    }
+
+    /// Lifecycle events and PnL snapshots, optionally within a time range.
+    async fn history(
+        &self,
+        ctx: &Context<'_>,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> async_graphql::Result<PositionHistoryResponse> {
+        let state = ctx.data::<AppState>()?.clone();
+        let query = PositionHistoryQuery { since, until };
+        Ok(
+            handlers::get_position_history(State(state), Path(self.address.clone()), Query(query))
+                .await?
+                .0,
+        )
+    }
+}
+
+/// Builds the GraphQL schema over the application state.
+pub fn build_schema(state: AppState) -> ApiSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+/// Serves GraphiQL.
+async fn graphiql() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/api/v1/graphql").finish())
+}
+
+/// Executes a query in the caller's tenant scope.
+async fn execute(
+    schema: ApiSchema,
+    scope: TenantScope,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema
+        .execute(request.into_inner().data(scope))
+        .await
+        .into()
+}
+
+/// Creates the GraphQL routes.
+pub fn graphql_routes(state: &AppState) -> Router<AppState> {
+    let schema = build_schema(state.clone());
+    Router::new().route(
+        "/graphql",
+        get(graphiql).post(
+            move |Extension(scope): Extension<TenantScope>, request: GraphQLRequest| {
+                execute(schema.clone(), scope, request)
+            },
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ApiConfig;
+    use clmm_lp_protocols::prelude::RpcConfig;
+
+    fn schema() -> ApiSchema {
+        build_schema(AppState::new(RpcConfig::default(), ApiConfig::default()))
+    }
+
+    #[test]
+    fn test_schema_nests_pool_and_pnl() {
+        let sdl = schema().sdl();
+        assert!(sdl.contains("type PositionResponse"));
+        assert!(sdl.contains("pool: PoolResponse"));
+        assert!(sdl.contains("pnl: PnLResponse!"));
+    }
+
+    #[tokio::test]
+    async fn test_query_portfolio() {
+        let response = schema()
+            .execute("{ positions { address } portfolio { activePositions } }")
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["positions"], serde_json::json!([]));
+        assert_eq!(data["portfolio"]["activePositions"], 0);
+    }
+}
@@ -0,0 +1,337 @@
+//! Alert handlers.
+//!
+//! Exposes alerts tracked by the alert dispatcher:
+//! - List recent and unacknowledged alerts
+//! - Acknowledge an alert to stop its escalation
+//! - Manage custom expression-based alert rules
+//! - Send test alerts to verify notification channels
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    AcknowledgeAlertRequest, AlertResponse, AlertRuleResponse, AlertSeverity, AlertsQuery,
+    ChannelTestResponse, CreateAlertRuleRequest, ListAlertRulesResponse, ListAlertsResponse,
+    MessageResponse, TestAlertRequest, TestAlertResponse,
+};
+use crate::state::AppState;
+use crate::tenancy::TenantScope;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use clmm_lp_execution::prelude::{
+    Alert, AlertLevel, AlertRule, AlertType, Expression, RuleCondition, TrackedAlert, TriggerMode,
+};
+
+/// Maps a tracked alert to its API representation.
+pub(crate) fn alert_response(tracked: TrackedAlert) -> AlertResponse {
+    let alert = tracked.alert;
+    AlertResponse {
+        id: alert.id,
+        level: alert.level.name().to_lowercase(),
+        alert_type: alert.alert_type.name().to_string(),
+        message: alert.message,
+        position_address: alert.position,
+        pool_address: alert.pool,
+        timestamp: alert.timestamp,
+        acknowledged: alert.acknowledged,
+        acknowledged_at: tracked.acknowledged_at,
+        acknowledged_by: tracked.acknowledged_by,
+        escalation_channel: tracked.policy.as_ref().map(|p| p.channel.clone()),
+        escalate_after_secs: tracked.policy.as_ref().map(|p| p.after_secs),
+        escalated_at: tracked.escalated_at,
+    }
+}
+
+/// List alerts.
+///
+/// Tenants only see alerts about their positions.
+#[utoipa::path(
+    get,
+    path = "/alerts",
+    tag = "Alerts",
+    params(AlertsQuery),
+    responses(
+        (status = 200, description = "List of alerts", body = ListAlertsResponse)
+    )
+)]
+pub async fn list_alerts(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Query(query): Query<AlertsQuery>,
+) -> ApiResult<Json<ListAlertsResponse>> {
+    let mut alerts: Vec<AlertResponse> = Vec::new();
+    for tracked in state.alerts.list(query.unacknowledged).await {
+        if scope.can_see_alert(&state, &tracked.alert).await {
+            alerts.push(alert_response(tracked));
+        }
+    }
+
+    let total = alerts.len();
+    Ok(Json(ListAlertsResponse { alerts, total }))
+}
+
+/// Get an alert.
+///
+/// Alerts about other tenants' positions answer 404.
+#[utoipa::path(
+    get,
+    path = "/alerts/{id}",
+    tag = "Alerts",
+    params(
+        ("id" = String, Path, description = "Alert ID")
+    ),
+    responses(
+        (status = 200, description = "Alert details", body = AlertResponse),
+        (status = 404, description = "Alert not found")
+    )
+)]
+pub async fn get_alert(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<AlertResponse>> {
+    let tracked = visible_alert(&state, &scope, &id).await?;
+
+    Ok(Json(alert_response(tracked)))
+}
+
+/// Acknowledge an alert.
+///
+/// Stops any pending escalation for the alert. Alerts about other tenants'
+/// positions answer 404.
+#[utoipa::path(
+    post,
+    path = "/alerts/{id}/acknowledge",
+    tag = "Alerts",
+    params(
+        ("id" = String, Path, description = "Alert ID")
+    ),
+    request_body = AcknowledgeAlertRequest,
+    responses(
+        (status = 200, description = "Alert acknowledged", body = AlertResponse),
+        (status = 404, description = "Alert not found")
+    )
+)]
+pub async fn acknowledge_alert(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Path(id): Path<String>,
+    Json(request): Json<AcknowledgeAlertRequest>,
+) -> ApiResult<Json<AlertResponse>> {
+    visible_alert(&state, &scope, &id).await?;
+    let tracked = state
+        .alerts
+        .acknowledge(&id, request.acknowledged_by)
+        .await
+        .ok_or_else(|| ApiError::not_found("Alert not found"))?;
+
+    Ok(Json(alert_response(tracked)))
+}
+
+/// Gets an alert visible to the caller.
+async fn visible_alert(state: &AppState, scope: &TenantScope, id: &str) -> ApiResult<TrackedAlert> {
+    let tracked = state
+        .alerts
+        .get(id)
+        .await
+        .ok_or_else(|| ApiError::not_found("Alert not found"))?;
+    if !scope.can_see_alert(state, &tracked.alert).await {
+        return Err(ApiError::not_found("Alert not found"));
+    }
+    Ok(tracked)
+}
+
+/// Maps an API severity to an alert level.
+fn alert_level(severity: AlertSeverity) -> AlertLevel {
+    match severity {
+        AlertSeverity::Info => AlertLevel::Info,
+        AlertSeverity::Warning => AlertLevel::Warning,
+        AlertSeverity::Critical => AlertLevel::Critical,
+    }
+}
+
+/// Maps an alert rule to its API representation.
+fn rule_response(rule: AlertRule) -> AlertRuleResponse {
+    let condition = match &rule.condition {
+        RuleCondition::Expression(expr) => expr.source().to_string(),
+        other => format!("{:?}", other),
+    };
+    AlertRuleResponse {
+        name: rule.name,
+        condition,
+        level: rule.level.name().to_lowercase(),
+        message: rule.message_template,
+        enabled: rule.enabled,
+        cooldown_secs: rule.cooldown_secs,
+        on_change: rule.trigger_mode == TriggerMode::OnChange,
+        dedup_key: rule.dedup_key,
+    }
+}
+
+/// List alert rules.
+///
+/// Rules are evaluated over every monitored position, so they are reserved
+/// for operators.
+#[utoipa::path(
+    get,
+    path = "/alerts/rules",
+    tag = "Alerts",
+    responses(
+        (status = 200, description = "Configured alert rules", body = ListAlertRulesResponse),
+        (status = 403, description = "Reserved for operators")
+    )
+)]
+pub async fn list_alert_rules(
+    State(state): State<AppState>,
+) -> ApiResult<Json<ListAlertRulesResponse>> {
+    let rules: Vec<AlertRuleResponse> = state
+        .monitor
+        .alert_rules()
+        .await
+        .into_iter()
+        .map(rule_response)
+        .collect();
+
+    let total = rules.len();
+    Ok(Json(ListAlertRulesResponse { rules, total }))
+}
+
+/// Create an alert rule.
+///
+/// The condition is an expression over monitor state, evaluated on every
+/// monitor poll. A rule with the same name is replaced.
+#[utoipa::path(
+    post,
+    path = "/alerts/rules",
+    tag = "Alerts",
+    request_body = CreateAlertRuleRequest,
+    responses(
+        (status = 200, description = "Rule created", body = AlertRuleResponse),
+        (status = 400, description = "Invalid condition expression"),
+        (status = 403, description = "Reserved for operators")
+    )
+)]
+pub async fn create_alert_rule(
+    State(state): State<AppState>,
+    Json(request): Json<CreateAlertRuleRequest>,
+) -> ApiResult<Json<AlertRuleResponse>> {
+    if request.name.trim().is_empty() {
+        return Err(ApiError::bad_request("Rule name must not be empty"));
+    }
+
+    let expr = Expression::parse(&request.condition)
+        .map_err(|e| ApiError::bad_request(format!("Invalid condition: {}", e)))?;
+
+    let level = alert_level(request.level);
+    let message = request
+        .message
+        .unwrap_or_else(|| format!("{} triggered for {{position}}", request.name));
+
+    let mut rule = AlertRule::new(
+        &request.name,
+        RuleCondition::Expression(expr),
+        level,
+        AlertType::Custom(request.name.clone()),
+    )
+    .with_message(message);
+    if let Some(secs) = request.cooldown_secs {
+        rule = rule.with_cooldown(secs);
+    }
+    if request.on_change {
+        rule = rule.on_change();
+    }
+    if let Some(key) = request.dedup_key {
+        rule = rule.with_dedup_key(key);
+    }
+
+    state.monitor.add_alert_rule(rule.clone()).await;
+
+    Ok(Json(rule_response(rule)))
+}
+
+/// Delete an alert rule.
+#[utoipa::path(
+    delete,
+    path = "/alerts/rules/{name}",
+    tag = "Alerts",
+    params(
+        ("name" = String, Path, description = "Rule name")
+    ),
+    responses(
+        (status = 200, description = "Rule deleted", body = MessageResponse),
+        (status = 403, description = "Reserved for operators"),
+        (status = 404, description = "Rule not found")
+    )
+)]
+pub async fn delete_alert_rule(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    if !state.monitor.remove_alert_rule(&name).await {
+        return Err(ApiError::not_found("Alert rule not found"));
+    }
+
+    Ok(Json(MessageResponse::new(format!(
+        "Alert rule {} deleted",
+        name
+    ))))
+}
+
+/// Send a test alert.
+///
+/// Sends an alert through every configured channel, including escalation
+/// channels, or only the named one, and reports each delivery. Test alerts
+/// are not tracked and never escalate. The channels are the operator's, so
+/// tenants cannot send test alerts.
+#[utoipa::path(
+    post,
+    path = "/alerts/test",
+    tag = "Alerts",
+    request_body = TestAlertRequest,
+    responses(
+        (status = 200, description = "Delivery results", body = TestAlertResponse),
+        (status = 403, description = "Reserved for operators"),
+        (status = 404, description = "No such channel, or no channels configured")
+    )
+)]
+pub async fn test_alert_channels(
+    State(state): State<AppState>,
+    Json(request): Json<TestAlertRequest>,
+) -> ApiResult<Json<TestAlertResponse>> {
+    let message = request
+        .message
+        .unwrap_or_else(|| "Test alert from clmm-lp: this channel is configured".to_string());
+    let alert = Alert::new(
+        alert_level(request.level),
+        AlertType::Custom("Test".to_string()),
+        message,
+    );
+
+    let results = state
+        .alerts
+        .test_channels(request.channel.as_deref(), &alert)
+        .await;
+    if results.is_empty() {
+        return Err(ApiError::not_found(match request.channel {
+            Some(channel) => format!("Alert channel {} not configured", channel),
+            None => "No alert channels configured".to_string(),
+        }));
+    }
+
+    let channels: Vec<ChannelTestResponse> = results
+        .into_iter()
+        .map(|result| ChannelTestResponse {
+            channel: result.channel,
+            primary: result.primary,
+            delivered: result.error.is_none(),
+            error: result.error,
+        })
+        .collect();
+    let delivered = channels.iter().filter(|c| c.delivered).count();
+
+    Ok(Json(TestAlertResponse {
+        failed: channels.len() - delivered,
+        delivered,
+        channels,
+    }))
+}
@@ -1,12 +1,27 @@
 //! Analytics handlers.
 
 use crate::error::{ApiError, ApiResult};
-use crate::models::{PortfolioAnalyticsResponse, SimulationRequest, SimulationResponse};
+use crate::models::{
+    LeaderboardEntryResponse, LeaderboardQuery, LeaderboardResponse, PortfolioAnalyticsResponse,
+    PortfolioHistoryQuery, PortfolioHistoryResponse, PortfolioRebalanceEvent, SimulationRequest,
+    SimulationResponse,
+};
+use crate::services::{
+    LEADERBOARD_WINDOW_DAYS, MAX_HISTORY_DAYS, aggregate_history, history_interval, parse_window,
+};
 use crate::state::AppState;
-use axum::{Json, extract::State};
+use crate::tenancy::TenantScope;
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
 use rust_decimal::Decimal;
+use std::collections::HashSet;
+use tracing::warn;
 
 /// Get portfolio analytics.
+///
+/// Covers the positions visible to the caller.
 #[utoipa::path(
     get,
     path = "/analytics/portfolio",
@@ -17,8 +32,18 @@ use rust_decimal::Decimal;
 )]
 pub async fn get_portfolio_analytics(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
 ) -> ApiResult<Json<PortfolioAnalyticsResponse>> {
-    let positions = state.monitor.get_positions().await;
+    Ok(Json(portfolio_analytics(&state, &scope).await))
+}
+
+/// Aggregates the monitored positions visible to a caller into portfolio
+/// analytics.
+pub(crate) async fn portfolio_analytics(
+    state: &AppState,
+    scope: &TenantScope,
+) -> PortfolioAnalyticsResponse {
+    let positions = scope.filter_positions(state.monitor.get_positions().await);
 
     let mut total_value = Decimal::ZERO;
     let mut total_pnl = Decimal::ZERO;
@@ -64,7 +89,7 @@ pub async fn get_portfolio_analytics(
         Decimal::ZERO
     };
 
-    let response = PortfolioAnalyticsResponse {
+    PortfolioAnalyticsResponse {
         total_value_usd: total_value,
         total_pnl_usd: total_pnl,
         total_pnl_pct,
@@ -74,9 +99,161 @@ pub async fn get_portfolio_analytics(
         positions_in_range: in_range_count,
         best_position,
         worst_position,
+    }
+}
+
+/// Get portfolio PnL history.
+///
+/// Returns the equity curve, fee accrual, and IL over the window, built
+/// from the monitor snapshots recorded while the server was running, with
+/// the rebalances and fee collections executed in it. Tenants get the
+/// history of their currently monitored positions.
+#[utoipa::path(
+    get,
+    path = "/analytics/portfolio/history",
+    tag = "Analytics",
+    params(PortfolioHistoryQuery),
+    responses(
+        (status = 200, description = "Portfolio history", body = PortfolioHistoryResponse),
+        (status = 400, description = "Invalid window"),
+        (status = 503, description = "History requires a database")
+    )
+)]
+pub async fn get_portfolio_history(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Query(query): Query<PortfolioHistoryQuery>,
+) -> ApiResult<Json<PortfolioHistoryResponse>> {
+    let window = parse_window(&query.window).ok_or_else(|| {
+        ApiError::Validation(format!(
+            "window must be a positive number of hours (h), days (d) or weeks (w), up to {} days",
+            MAX_HISTORY_DAYS
+        ))
+    })?;
+    let history = state.position_history.clone().ok_or_else(|| {
+        ApiError::ServiceUnavailable("Portfolio history requires a database".to_string())
+    })?;
+
+    let since = chrono::Utc::now() - window;
+    let load_failed = |e: anyhow::Error| {
+        warn!(error = %e, "Failed to load portfolio history");
+        ApiError::internal(format!("Failed to load portfolio history: {}", e))
     };
+    let mut points = history
+        .load_history(since, None)
+        .await
+        .map_err(load_failed)?;
+    let mut rebalances = history
+        .load_rebalances(since, None)
+        .await
+        .map_err(load_failed)?;
+    let mut fee_collections = history
+        .load_fee_collections(since, None)
+        .await
+        .map_err(load_failed)?;
+    if !scope.is_operator() {
+        let visible: HashSet<String> = scope
+            .filter_positions(state.monitor.get_positions().await)
+            .iter()
+            .map(|p| p.address.to_string())
+            .collect();
+        points.retain(|p| visible.contains(&p.position_address));
+        rebalances.retain(|r| visible.contains(&r.position_address));
+        fee_collections.retain(|f| visible.contains(&f.position_address));
+    }
+    let interval_secs = history_interval(window);
 
-    Ok(Json(response))
+    Ok(Json(PortfolioHistoryResponse {
+        window: query.window,
+        interval_secs,
+        points: aggregate_history(&points, interval_secs),
+        rebalances: rebalances
+            .into_iter()
+            .map(|rebalance| PortfolioRebalanceEvent {
+                position_address: rebalance.position_address,
+                timestamp: rebalance.timestamp,
+                old_tick_lower: rebalance.old_ticks.0,
+                old_tick_upper: rebalance.old_ticks.1,
+                new_tick_lower: rebalance.new_ticks.0,
+                new_tick_upper: rebalance.new_ticks.1,
+                tx_cost_lamports: rebalance.tx_cost_lamports,
+                reason: rebalance.reason,
+            })
+            .collect(),
+        fee_collections: fee_collections.len() as u32,
+        fees_collected_usd: fee_collections.iter().map(|f| f.fees_usd).sum(),
+    }))
+}
+
+/// Maximum number of leaderboard entries per request.
+const MAX_LEADERBOARD_ENTRIES: usize = 100;
+
+/// Get the pool or strategy leaderboard.
+///
+/// Ranks pools or stored strategies by the fee APR their positions actually
+/// realized over the window, alongside their return against holding the
+/// tokens. Rankings are recomputed periodically from the recorded position
+/// snapshots of every tenant, so leaderboards are reserved for operators.
+#[utoipa::path(
+    get,
+    path = "/analytics/leaderboard",
+    tag = "Analytics",
+    params(LeaderboardQuery),
+    responses(
+        (status = 200, description = "Leaderboard", body = LeaderboardResponse),
+        (status = 400, description = "Invalid window or limit"),
+        (status = 403, description = "Reserved for operators"),
+        (status = 503, description = "Leaderboards require a database")
+    )
+)]
+pub async fn get_leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> ApiResult<Json<LeaderboardResponse>> {
+    if !LEADERBOARD_WINDOW_DAYS.contains(&query.window_days) {
+        return Err(ApiError::Validation(format!(
+            "window_days must be one of {:?}",
+            LEADERBOARD_WINDOW_DAYS
+        )));
+    }
+    if query.limit == 0 || query.limit > MAX_LEADERBOARD_ENTRIES {
+        return Err(ApiError::Validation(format!(
+            "limit must be between 1 and {}",
+            MAX_LEADERBOARD_ENTRIES
+        )));
+    }
+    let leaderboard = state.leaderboard.clone().ok_or_else(|| {
+        ApiError::ServiceUnavailable("Leaderboards require a database".to_string())
+    })?;
+
+    let entries = leaderboard
+        .load_leaderboard(query.kind, query.window_days, query.limit)
+        .await
+        .map_err(|e| {
+            warn!(error = %e, "Failed to load leaderboard");
+            ApiError::internal(format!("Failed to load leaderboard: {}", e))
+        })?;
+
+    Ok(Json(LeaderboardResponse {
+        kind: query.kind,
+        window_days: query.window_days,
+        computed_at: entries.first().map(|entry| entry.computed_at),
+        entries: entries
+            .into_iter()
+            .map(|entry| LeaderboardEntryResponse {
+                rank: entry.rank,
+                id: entry.subject,
+                name: entry.name,
+                pool_address: entry.pool_address,
+                positions: entry.performance.positions,
+                avg_value_usd: entry.performance.avg_value_usd,
+                fees_usd: entry.performance.fees_usd,
+                fee_apr_pct: entry.performance.fee_apr_pct,
+                vs_hodl_pct: entry.performance.vs_hodl_pct,
+                time_in_range_pct: entry.performance.time_in_range_pct,
+            })
+            .collect(),
+    }))
 }
 
 /// Run a simulation.
@@ -0,0 +1,152 @@
+//! API key management handlers.
+//!
+//! Admin-only endpoints to create, list and revoke API keys:
+//! - Keys carry a read-only, trader or admin role
+//! - Keys may be scoped to specific strategies
+//! - Keys may act for a tenant, seeing only the tenant's resources
+//! - Secrets are only returned when a key is created
+
+use crate::auth::{ApiKey, RevokeError, Role};
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    ApiKeyResponse, ApiKeyRole, CreateApiKeyRequest, CreateApiKeyResponse, ListApiKeysResponse,
+    MessageResponse,
+};
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+/// Maps an auth role to its API representation.
+fn role_response(role: Role) -> ApiKeyRole {
+    match role {
+        Role::ReadOnly => ApiKeyRole::ReadOnly,
+        Role::Trader => ApiKeyRole::Trader,
+        Role::Admin => ApiKeyRole::Admin,
+    }
+}
+
+/// Maps an API key to its API representation.
+fn api_key_response(key: ApiKey) -> ApiKeyResponse {
+    ApiKeyResponse {
+        id: key.id,
+        name: key.name,
+        role: role_response(key.role),
+        strategies: key.strategies,
+        tenant: key.tenant,
+        prefix: key.prefix,
+        created_at: key.created_at,
+        last_used_at: key.last_used_at,
+    }
+}
+
+/// List API keys.
+#[utoipa::path(
+    get,
+    path = "/auth/keys",
+    tag = "Auth",
+    responses(
+        (status = 200, description = "API keys", body = ListApiKeysResponse),
+        (status = 403, description = "Admin role required")
+    )
+)]
+pub async fn list_api_keys(State(state): State<AppState>) -> ApiResult<Json<ListApiKeysResponse>> {
+    let keys: Vec<ApiKeyResponse> = state
+        .auth
+        .keys()
+        .list()
+        .await
+        .into_iter()
+        .map(api_key_response)
+        .collect();
+    let total = keys.len();
+
+    Ok(Json(ListApiKeysResponse { keys, total }))
+}
+
+/// Create an API key.
+///
+/// The secret is only returned in this response.
+#[utoipa::path(
+    post,
+    path = "/auth/keys",
+    tag = "Auth",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "API key created", body = CreateApiKeyResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Admin role required")
+    )
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> ApiResult<Json<CreateApiKeyResponse>> {
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::bad_request("API key name must not be empty"));
+    }
+
+    let role = match request.role {
+        ApiKeyRole::ReadOnly => Role::ReadOnly,
+        ApiKeyRole::Trader => Role::Trader,
+        ApiKeyRole::Admin => Role::Admin,
+    };
+    if role == Role::Admin && !request.strategies.is_empty() {
+        return Err(ApiError::bad_request(
+            "Admin keys cannot be scoped to strategies",
+        ));
+    }
+    let tenant = request
+        .tenant
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    if role == Role::Admin && tenant.is_some() {
+        return Err(ApiError::bad_request("Admin keys cannot act for a tenant"));
+    }
+
+    let (key, secret) = state
+        .auth
+        .keys()
+        .create_for_tenant(name, role, request.strategies, tenant)
+        .await;
+
+    Ok(Json(CreateApiKeyResponse {
+        key: api_key_response(key),
+        secret,
+    }))
+}
+
+/// Revoke an API key.
+///
+/// The last admin key cannot be revoked.
+#[utoipa::path(
+    delete,
+    path = "/auth/keys/{id}",
+    tag = "Auth",
+    params(
+        ("id" = String, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 200, description = "API key revoked", body = MessageResponse),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "API key not found"),
+        (status = 409, description = "Last admin key")
+    )
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    match state.auth.keys().revoke(&id).await {
+        Ok(_) => {}
+        Err(e @ RevokeError::NotFound) => return Err(ApiError::not_found(e.to_string())),
+        Err(e @ RevokeError::LastAdmin) => return Err(ApiError::Conflict(e.to_string())),
+    }
+
+    Ok(Json(MessageResponse::new(format!(
+        "API key {} revoked",
+        id
+    ))))
+}
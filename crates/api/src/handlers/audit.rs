@@ -0,0 +1,152 @@
+//! Audit log handlers.
+//!
+//! Exposes the append-only audit log of decision-engine evaluations,
+//! proposed actions, transactions and outcomes for post-mortems, and the
+//! log of the mutating API requests behind them.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    AuditEntryResponse, AuditLogQuery, ListAuditEntriesResponse, ListRequestAuditEntriesResponse,
+    RequestAuditEntryResponse, RequestAuditLogQuery,
+};
+use crate::request_audit::{RequestAuditEntry, RequestAuditQuery};
+use crate::state::AppState;
+use crate::tenancy::TenantScope;
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
+use clmm_lp_execution::prelude::{AuditEntry, AuditKind, AuditQuery};
+
+/// Maximum number of entries returned per request.
+const MAX_AUDIT_ENTRIES: usize = 1000;
+
+/// Maps an audit entry to its API representation.
+fn audit_entry_response(entry: AuditEntry) -> AuditEntryResponse {
+    AuditEntryResponse {
+        id: entry.id,
+        timestamp: entry.timestamp,
+        kind: entry.kind.name().to_string(),
+        action: entry.action,
+        strategy_id: entry.strategy_id,
+        position_address: entry.position,
+        signature: entry.signature,
+        success: entry.success,
+        error: entry.error,
+        details: entry.details,
+    }
+}
+
+/// Query the audit log.
+///
+/// Tenants only see entries about their strategies and positions.
+#[utoipa::path(
+    get,
+    path = "/audit",
+    tag = "Audit",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Audit log entries, newest first", body = ListAuditEntriesResponse),
+        (status = 400, description = "Invalid filter")
+    )
+)]
+pub async fn list_audit_entries(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Query(query): Query<AuditLogQuery>,
+) -> ApiResult<Json<ListAuditEntriesResponse>> {
+    let kind =
+        match query.kind.as_deref() {
+            Some(name) => Some(AuditKind::from_name(name).ok_or_else(|| {
+                ApiError::bad_request(format!("Unknown audit entry kind: {}", name))
+            })?),
+            None => None,
+        };
+
+    // Tenants' entries are filtered after the query, so scan the maximum
+    let limit = query.limit.min(MAX_AUDIT_ENTRIES);
+    let matching = state
+        .audit
+        .query(&AuditQuery {
+            strategy_id: query.strategy_id,
+            position: query.position_address,
+            kind,
+            since: query.since,
+            until: query.until,
+            limit: if scope.is_operator() {
+                limit
+            } else {
+                MAX_AUDIT_ENTRIES
+            },
+        })
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to query audit log: {}", e)))?;
+
+    let mut entries: Vec<AuditEntryResponse> = Vec::new();
+    for entry in matching {
+        if entries.len() == limit {
+            break;
+        }
+        if scope.can_see_audit_entry(&state, &entry).await {
+            entries.push(audit_entry_response(entry));
+        }
+    }
+
+    let total = entries.len();
+    Ok(Json(ListAuditEntriesResponse { entries, total }))
+}
+
+/// Maps a request audit entry to its API representation.
+fn request_audit_entry_response(entry: RequestAuditEntry) -> RequestAuditEntryResponse {
+    RequestAuditEntryResponse {
+        id: entry.id,
+        timestamp: entry.timestamp,
+        subject: entry.subject,
+        role: entry.role,
+        tenant: entry.tenant,
+        method: entry.method,
+        path: entry.path,
+        payload_hash: entry.payload_hash,
+        status: entry.status,
+        duration_ms: entry.duration_ms,
+    }
+}
+
+/// Query the request audit log.
+///
+/// Lists mutating API calls with their caller, payload hash and result.
+#[utoipa::path(
+    get,
+    path = "/audit/requests",
+    tag = "Audit",
+    params(RequestAuditLogQuery),
+    responses(
+        (status = 200, description = "Requests, newest first", body = ListRequestAuditEntriesResponse),
+        (status = 403, description = "Admin role required")
+    )
+)]
+pub async fn list_request_audit_entries(
+    State(state): State<AppState>,
+    Query(query): Query<RequestAuditLogQuery>,
+) -> ApiResult<Json<ListRequestAuditEntriesResponse>> {
+    let entries: Vec<RequestAuditEntryResponse> = state
+        .request_audit
+        .query(&RequestAuditQuery {
+            subject: query.subject,
+            tenant: query.tenant,
+            method: query.method.map(|m| m.to_uppercase()),
+            path_prefix: query.path_prefix,
+            status: query.status,
+            since: query.since,
+            until: query.until,
+            limit: query.limit.min(MAX_AUDIT_ENTRIES),
+        })
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to query request audit log: {}", e)))?
+        .into_iter()
+        .map(request_audit_entry_response)
+        .collect();
+
+    let total = entries.len();
+    Ok(Json(ListRequestAuditEntriesResponse { entries, total }))
+}
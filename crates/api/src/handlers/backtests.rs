@@ -0,0 +1,158 @@
+//! Backtest handlers.
+//!
+//! Runs backtests on the background job queue against stored price
+//! history:
+//! - Submit a backtest and receive a job ID immediately
+//! - Poll the job for its status and results
+//! - Repeating a backtest over unchanged history returns the cached result
+//!
+//! Backtests can also be tracked and cancelled through the generic
+//! `/jobs/{id}` endpoints.
+
+use super::jobs::{job_state, visible_job};
+use crate::error::{ApiError, ApiResult};
+use crate::models::{BacktestJobResponse, BacktestRequest, BacktestResultResponse};
+use crate::services::{Job, run_backtest_job};
+use crate::state::AppState;
+use crate::tenancy::TenantScope;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::NaiveDate;
+use clmm_lp_data::prelude::ResultKey;
+use rust_decimal::Decimal;
+use tracing::info;
+
+/// Job kind of backtests.
+pub(crate) const BACKTEST_JOB: &str = "backtest";
+
+/// Converts an inclusive date range to Unix timestamps.
+pub(crate) fn date_window(start: NaiveDate, end: NaiveDate) -> (i64, i64) {
+    let start = start
+        .and_hms_opt(0, 0, 0)
+        .map(|t| t.and_utc().timestamp())
+        .unwrap_or_default();
+    let end = end
+        .and_hms_opt(23, 59, 59)
+        .map(|t| t.and_utc().timestamp())
+        .unwrap_or_default();
+    (start, end)
+}
+
+/// Maps a backtest job to its API representation.
+fn backtest_job_response(job: Job) -> ApiResult<BacktestJobResponse> {
+    let request: BacktestRequest = serde_json::from_value(job.params)
+        .map_err(|e| ApiError::internal(format!("Invalid stored backtest request: {}", e)))?;
+    let result: Option<BacktestResultResponse> = job
+        .result
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| ApiError::internal(format!("Invalid stored backtest result: {}", e)))?;
+
+    Ok(BacktestJobResponse {
+        id: job.id,
+        status: job_state(job.status),
+        progress_pct: job.progress_pct,
+        request,
+        created_at: job.created_at,
+        started_at: job.started_at,
+        finished_at: job.finished_at,
+        result,
+        error: job.error,
+    })
+}
+
+/// Submit a backtest.
+///
+/// The backtest is queued and runs in the background against the pool's
+/// stored price history; poll `GET /backtests/{id}` for the results.
+#[utoipa::path(
+    post,
+    path = "/backtests",
+    tag = "Backtests",
+    request_body = BacktestRequest,
+    responses(
+        (status = 202, description = "Backtest queued", body = BacktestJobResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 503, description = "No price history database configured")
+    )
+)]
+pub async fn submit_backtest(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Json(request): Json<BacktestRequest>,
+) -> ApiResult<(StatusCode, Json<BacktestJobResponse>)> {
+    if request.lower_price <= Decimal::ZERO || request.lower_price >= request.upper_price {
+        return Err(ApiError::Validation(
+            "lower_price must be positive and below upper_price".to_string(),
+        ));
+    }
+    if request.start_date > request.end_date {
+        return Err(ApiError::Validation(
+            "start_date must not be after end_date".to_string(),
+        ));
+    }
+    if request.initial_capital_usd <= Decimal::ZERO {
+        return Err(ApiError::Validation(
+            "initial_capital_usd must be positive".to_string(),
+        ));
+    }
+
+    let Some(source) = state.price_history.clone() else {
+        return Err(ApiError::ServiceUnavailable(
+            "Backtests require a price history database".to_string(),
+        ));
+    };
+
+    let (start, end) = date_window(request.start_date, request.end_date);
+
+    let params = serde_json::to_value(&request)
+        .map_err(|e| ApiError::internal(format!("Failed to encode request: {}", e)))?;
+    let results = state.results.clone();
+    let owner = scope.tenant_id();
+    let job = state
+        .jobs
+        .submit(BACKTEST_JOB, owner, params, |job| async move {
+            let history = source.load(&request.pool_address, start, end).await?;
+            // Backtests are deterministic, so the same request over the same
+            // history always produces the same result
+            let key = ResultKey::for_inputs(BACKTEST_JOB, &history, &request)?;
+            let result = tokio::task::spawn_blocking(move || {
+                results.get_or_compute(&key, || run_backtest_job(&request, &history, &job))
+            })
+            .await??;
+            Ok(serde_json::to_value(result)?)
+        })
+        .await;
+
+    info!(id = %job.id, "Backtest queued");
+    Ok((StatusCode::ACCEPTED, Json(backtest_job_response(job)?)))
+}
+
+/// Get a backtest job.
+#[utoipa::path(
+    get,
+    path = "/backtests/{id}",
+    tag = "Backtests",
+    params(
+        ("id" = String, Path, description = "Backtest job ID")
+    ),
+    responses(
+        (status = 200, description = "Backtest job", body = BacktestJobResponse),
+        (status = 404, description = "Backtest not found")
+    )
+)]
+pub async fn get_backtest(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<BacktestJobResponse>> {
+    let job = visible_job(&state, &scope, &id)
+        .await
+        .filter(|job| job.kind == BACKTEST_JOB)
+        .ok_or_else(|| ApiError::not_found("Backtest not found"))?;
+
+    Ok(Json(backtest_job_response(job)?))
+}
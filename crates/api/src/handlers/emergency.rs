@@ -0,0 +1,252 @@
+//! Emergency control handlers.
+//!
+//! Exposes the global circuit breaker shared by all strategy executors:
+//! - Inspect the kill switch and failure breaker state
+//! - Engage the kill switch to halt automated execution
+//! - Release the kill switch and reset the breaker
+//! - Inspect, engage and release the portfolio stop enforcing the
+//!   portfolio drawdown and token exposure limits
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    CircuitBreakerStatus, KillSwitchRequest, KillSwitchResponse, PortfolioRiskResponse,
+    PortfolioStopRequest, TokenExposureResponse,
+};
+use crate::state::{AlertUpdate, AppState};
+use axum::{Json, extract::State};
+use clmm_lp_execution::prelude::{CircuitState, PortfolioRiskManager};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Maps the execution circuit state to its API representation.
+pub(crate) fn circuit_status(state: CircuitState) -> CircuitBreakerStatus {
+    match state {
+        CircuitState::Closed => CircuitBreakerStatus::Closed,
+        CircuitState::Open => CircuitBreakerStatus::Open,
+        CircuitState::HalfOpen => CircuitBreakerStatus::HalfOpen,
+    }
+}
+
+/// Builds the kill switch status from the shared circuit breaker.
+async fn kill_switch_status(state: &AppState) -> KillSwitchResponse {
+    let execution_allowed = state.circuit_breaker.is_allowed().await;
+    let stats = state.circuit_breaker.stats().await;
+
+    KillSwitchResponse {
+        circuit_breaker: circuit_status(stats.state),
+        kill_switch_engaged: stats.manually_tripped,
+        execution_allowed,
+        failure_count: stats.failure_count,
+        max_failures: state.circuit_breaker.config().max_failures,
+        reason: stats.trip_reason,
+        tripped_at: stats.tripped_at,
+    }
+}
+
+/// Get kill switch status.
+#[utoipa::path(
+    get,
+    path = "/emergency/kill-switch",
+    tag = "Emergency",
+    responses(
+        (status = 200, description = "Kill switch status", body = KillSwitchResponse)
+    )
+)]
+pub async fn get_kill_switch(State(state): State<AppState>) -> ApiResult<Json<KillSwitchResponse>> {
+    Ok(Json(kill_switch_status(&state).await))
+}
+
+/// Engage the kill switch.
+///
+/// Halts all automated execution until the kill switch is released.
+#[utoipa::path(
+    post,
+    path = "/emergency/kill-switch",
+    tag = "Emergency",
+    request_body = KillSwitchRequest,
+    responses(
+        (status = 200, description = "Kill switch engaged", body = KillSwitchResponse),
+        (status = 400, description = "Invalid request")
+    )
+)]
+pub async fn engage_kill_switch(
+    State(state): State<AppState>,
+    Json(request): Json<KillSwitchRequest>,
+) -> ApiResult<Json<KillSwitchResponse>> {
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::bad_request(
+            "Kill switch reason must not be empty",
+        ));
+    }
+
+    warn!(reason = %reason, "Kill switch engaged");
+    state.circuit_breaker.manual_trip(reason).await;
+
+    state.broadcast_alert(AlertUpdate {
+        level: "critical".to_string(),
+        message: format!("Kill switch engaged: {}", reason),
+        timestamp: chrono::Utc::now(),
+        position_address: None,
+    });
+
+    Ok(Json(kill_switch_status(&state).await))
+}
+
+/// Release the kill switch.
+///
+/// Resets the circuit breaker and resumes automated execution, releasing
+/// the portfolio stop if it is engaged.
+#[utoipa::path(
+    delete,
+    path = "/emergency/kill-switch",
+    tag = "Emergency",
+    responses(
+        (status = 200, description = "Kill switch released", body = KillSwitchResponse)
+    )
+)]
+pub async fn release_kill_switch(
+    State(state): State<AppState>,
+) -> ApiResult<Json<KillSwitchResponse>> {
+    info!("Kill switch released");
+    state.circuit_breaker.reset().await;
+    if let Some(risk) = &state.risk {
+        risk.resume().await;
+    }
+
+    state.broadcast_alert(AlertUpdate {
+        level: "info".to_string(),
+        message: "Kill switch released, automated execution resumed".to_string(),
+        timestamp: chrono::Utc::now(),
+        position_address: None,
+    });
+
+    Ok(Json(kill_switch_status(&state).await))
+}
+
+/// Gets the portfolio risk manager, if risk limits are configured.
+fn risk_manager(state: &AppState) -> ApiResult<Arc<PortfolioRiskManager>> {
+    state.risk.clone().ok_or_else(|| {
+        ApiError::ServiceUnavailable(
+            "Portfolio risk limits are not configured \
+             (set execution.max_portfolio_drawdown_pct or execution.max_token_exposure_usd)"
+                .to_string(),
+        )
+    })
+}
+
+/// Builds the portfolio risk status from the risk manager.
+async fn portfolio_risk_status(risk: &PortfolioRiskManager) -> PortfolioRiskResponse {
+    let status = risk.status().await;
+    let config = risk.config();
+
+    PortfolioRiskResponse {
+        value_usd: status.value_usd,
+        peak_value_usd: status.peak_value_usd,
+        drawdown_pct: status.drawdown_pct.round_dp(4),
+        max_drawdown_pct: config.max_drawdown_pct,
+        max_token_exposure_usd: config.max_token_exposure_usd,
+        unwind_on_stop: config.unwind_on_stop,
+        exposures: status
+            .exposures
+            .iter()
+            .map(|exposure| TokenExposureResponse {
+                mint: exposure.mint.to_string(),
+                amount: exposure.amount,
+                value_usd: exposure.value_usd,
+                share_pct: exposure.share_pct.round_dp(4),
+                limit_usd: exposure.limit_usd,
+            })
+            .collect(),
+        breaches: status.breaches.iter().map(ToString::to_string).collect(),
+        stopped: status.stopped,
+        stop_reason: status.stop_reason,
+        stopped_at: status.stopped_at,
+        unwound_positions: status.unwound_positions,
+        checked_at: status.checked_at,
+    }
+}
+
+/// Get portfolio risk status.
+///
+/// Reports the drawdown and token exposures at the last check against the
+/// configured limits, and whether the portfolio stop is engaged.
+#[utoipa::path(
+    get,
+    path = "/emergency/portfolio-stop",
+    tag = "Emergency",
+    responses(
+        (status = 200, description = "Portfolio risk status", body = PortfolioRiskResponse),
+        (status = 503, description = "Risk limits not configured")
+    )
+)]
+pub async fn get_portfolio_risk(
+    State(state): State<AppState>,
+) -> ApiResult<Json<PortfolioRiskResponse>> {
+    let risk = risk_manager(&state)?;
+    Ok(Json(portfolio_risk_status(&risk).await))
+}
+
+/// Engage the portfolio stop.
+///
+/// Pauses all strategies, and closes all positions if the stop is
+/// configured to unwind, until the stop is released.
+#[utoipa::path(
+    post,
+    path = "/emergency/portfolio-stop",
+    tag = "Emergency",
+    request_body = PortfolioStopRequest,
+    responses(
+        (status = 200, description = "Portfolio stop engaged", body = PortfolioRiskResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 503, description = "Risk limits not configured")
+    )
+)]
+pub async fn engage_portfolio_stop(
+    State(state): State<AppState>,
+    Json(request): Json<PortfolioStopRequest>,
+) -> ApiResult<Json<PortfolioRiskResponse>> {
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::bad_request(
+            "Portfolio stop reason must not be empty",
+        ));
+    }
+    let risk = risk_manager(&state)?;
+
+    warn!(reason = %reason, "Portfolio stop engaged by operator");
+    risk.stop(&format!("operator: {}", reason)).await;
+
+    Ok(Json(portfolio_risk_status(&risk).await))
+}
+
+/// Release the portfolio stop.
+///
+/// Resets the circuit breaker and resumes strategies. The drawdown is
+/// measured from the portfolio value at the time of release.
+#[utoipa::path(
+    delete,
+    path = "/emergency/portfolio-stop",
+    tag = "Emergency",
+    responses(
+        (status = 200, description = "Portfolio stop released", body = PortfolioRiskResponse),
+        (status = 503, description = "Risk limits not configured")
+    )
+)]
+pub async fn release_portfolio_stop(
+    State(state): State<AppState>,
+) -> ApiResult<Json<PortfolioRiskResponse>> {
+    let risk = risk_manager(&state)?;
+
+    info!("Portfolio stop released");
+    risk.resume().await;
+
+    state.broadcast_alert(AlertUpdate {
+        level: "info".to_string(),
+        message: "Portfolio stop released, strategies resumed".to_string(),
+        timestamp: chrono::Utc::now(),
+        position_address: None,
+    });
+
+    Ok(Json(portfolio_risk_status(&risk).await))
+}
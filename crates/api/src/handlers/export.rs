@@ -0,0 +1,262 @@
+//! Data export handlers.
+//!
+//! Downloads of positions, portfolio analytics and simulation results as
+//! CSV or Parquet files, for analysis in pandas or spreadsheets:
+//! - Tables are built with the same formatters as the CLI exports
+//! - Decimals are exported as doubles in Parquet files
+
+use super::analytics::portfolio_analytics;
+use super::backtests::BACKTEST_JOB;
+use super::jobs::visible_job;
+use super::optimize::OPTIMIZATION_JOB;
+use super::positions::position_response;
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    BacktestResultResponse, ExportFileFormat, ExportQuery, OptimizeResultResponse,
+};
+use crate::services::JobStatus;
+use crate::state::AppState;
+use crate::tenancy::TenantScope;
+use axum::{
+    Extension,
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use clmm_lp_data::prelude::{ExportTable, ExportValue, TableFormat};
+
+/// Maps an API export format to the table format.
+fn table_format(format: ExportFileFormat) -> TableFormat {
+    match format {
+        ExportFileFormat::Csv => TableFormat::Csv,
+        ExportFileFormat::Parquet => TableFormat::Parquet,
+    }
+}
+
+/// Renders a table as a file download.
+fn download(table: &ExportTable, format: ExportFileFormat, name: &str) -> ApiResult<Response> {
+    let format = table_format(format);
+    if format == TableFormat::Parquet && !cfg!(feature = "parquet") {
+        return Err(ApiError::bad_request(
+            "Parquet export is not enabled on this server",
+        ));
+    }
+
+    let body = table
+        .render(format)
+        .map_err(|e| ApiError::internal(format!("Failed to encode export: {}", e)))?;
+    let disposition = format!("attachment; filename=\"{}.{}\"", name, format.extension());
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Builds the metric table of a backtest result.
+fn backtest_table(result: &BacktestResultResponse) -> ExportTable {
+    ExportTable::metrics([
+        ("total_steps", ExportValue::from(result.total_steps)),
+        ("entry_price", result.entry_price.into()),
+        ("final_price", result.final_price.into()),
+        ("final_value_usd", result.final_value_usd.into()),
+        ("final_pnl_usd", result.final_pnl_usd.into()),
+        ("final_il_pct", result.final_il_pct.into()),
+        ("total_fees_usd", result.total_fees_usd.into()),
+        ("time_in_range_pct", result.time_in_range_pct.into()),
+        ("rebalance_count", result.rebalance_count.into()),
+        (
+            "total_rebalance_cost_usd",
+            result.total_rebalance_cost_usd.into(),
+        ),
+        ("max_drawdown_pct", result.max_drawdown_pct.into()),
+        ("hodl_value_usd", result.hodl_value_usd.into()),
+        ("vs_hodl_usd", result.vs_hodl_usd.into()),
+    ])
+}
+
+/// Builds the range candidate table of an optimization result.
+fn optimization_table(result: &OptimizeResultResponse) -> ExportTable {
+    let mut table = ExportTable::new([
+        "rank",
+        "width_pct",
+        "lower",
+        "upper",
+        "expected_fees",
+        "expected_il",
+        "expected_pnl",
+        "score",
+    ]);
+    for c in &result.range_candidates {
+        table.push_row(vec![
+            c.rank.into(),
+            c.range_width_pct.into(),
+            c.lower_price.into(),
+            c.upper_price.into(),
+            c.expected_fees.into(),
+            c.expected_il.into(),
+            c.expected_pnl.into(),
+            c.score.into(),
+        ]);
+    }
+    table
+}
+
+/// Export positions.
+///
+/// One row per monitored position visible to the caller, with its PnL
+/// breakdown.
+#[utoipa::path(
+    get,
+    path = "/export/positions",
+    tag = "Export",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "Positions file", content_type = "text/csv"),
+        (status = 400, description = "Unsupported format")
+    )
+)]
+pub async fn export_positions(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Query(query): Query<ExportQuery>,
+) -> ApiResult<Response> {
+    let mut table = ExportTable::new([
+        "address",
+        "pool_address",
+        "owner",
+        "tick_lower",
+        "tick_upper",
+        "liquidity",
+        "in_range",
+        "value_usd",
+        "unrealized_pnl_usd",
+        "unrealized_pnl_pct",
+        "fees_earned_a",
+        "fees_earned_b",
+        "fees_earned_usd",
+        "il_pct",
+        "net_pnl_usd",
+        "net_pnl_pct",
+    ]);
+    for position in scope
+        .filter_positions(state.monitor.get_positions().await)
+        .iter()
+    {
+        let p = position_response(position);
+        table.push_row(vec![
+            p.address.into(),
+            p.pool_address.into(),
+            p.owner.into(),
+            p.tick_lower.into(),
+            p.tick_upper.into(),
+            p.liquidity.into(),
+            p.in_range.into(),
+            p.value_usd.into(),
+            p.pnl.unrealized_pnl_usd.into(),
+            p.pnl.unrealized_pnl_pct.into(),
+            p.pnl.fees_earned_a.into(),
+            p.pnl.fees_earned_b.into(),
+            p.pnl.fees_earned_usd.into(),
+            p.pnl.il_pct.into(),
+            p.pnl.net_pnl_usd.into(),
+            p.pnl.net_pnl_pct.into(),
+        ]);
+    }
+
+    download(&table, query.format, "positions")
+}
+
+/// Export portfolio analytics.
+///
+/// A `metric,value` table of the totals of the positions visible to the
+/// caller.
+#[utoipa::path(
+    get,
+    path = "/export/analytics",
+    tag = "Export",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "Analytics file", content_type = "text/csv"),
+        (status = 400, description = "Unsupported format")
+    )
+)]
+pub async fn export_analytics(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Query(query): Query<ExportQuery>,
+) -> ApiResult<Response> {
+    let analytics = portfolio_analytics(&state, &scope).await;
+    let table = ExportTable::metrics([
+        (
+            "total_value_usd",
+            ExportValue::from(analytics.total_value_usd),
+        ),
+        ("total_pnl_usd", analytics.total_pnl_usd.into()),
+        ("total_pnl_pct", analytics.total_pnl_pct.into()),
+        ("total_fees_usd", analytics.total_fees_usd.into()),
+        ("total_il_pct", analytics.total_il_pct.into()),
+        ("active_positions", analytics.active_positions.into()),
+        ("positions_in_range", analytics.positions_in_range.into()),
+        ("best_position", analytics.best_position.into()),
+        ("worst_position", analytics.worst_position.into()),
+    ]);
+
+    download(&table, query.format, "analytics")
+}
+
+/// Export a simulation result.
+///
+/// Backtests export their result metrics; optimizations export their
+/// ranked range candidates.
+#[utoipa::path(
+    get,
+    path = "/export/simulations/{id}",
+    tag = "Export",
+    params(
+        ("id" = String, Path, description = "Backtest or optimization job ID"),
+        ExportQuery
+    ),
+    responses(
+        (status = 200, description = "Simulation file", content_type = "text/csv"),
+        (status = 400, description = "Unsupported format"),
+        (status = 404, description = "Simulation not found"),
+        (status = 409, description = "Simulation has not completed")
+    )
+)]
+pub async fn export_simulation(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> ApiResult<Response> {
+    let job = visible_job(&state, &scope, &id)
+        .await
+        .filter(|job| job.kind == BACKTEST_JOB || job.kind == OPTIMIZATION_JOB)
+        .ok_or_else(|| ApiError::not_found("Simulation not found"))?;
+    if job.status != JobStatus::Completed {
+        return Err(ApiError::Conflict(format!(
+            "Simulation {} has not completed",
+            id
+        )));
+    }
+    let result = job
+        .result
+        .ok_or_else(|| ApiError::internal("Completed simulation has no result"))?;
+
+    let table = if job.kind == BACKTEST_JOB {
+        let result: BacktestResultResponse = serde_json::from_value(result)
+            .map_err(|e| ApiError::internal(format!("Invalid stored backtest result: {}", e)))?;
+        backtest_table(&result)
+    } else {
+        let result: OptimizeResultResponse = serde_json::from_value(result)
+            .map_err(|e| ApiError::internal(format!("Invalid stored optimize result: {}", e)))?;
+        optimization_table(&result)
+    };
+
+    download(&table, query.format, &format!("{}-{}", job.kind, id))
+}
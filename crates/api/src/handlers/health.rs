@@ -1,13 +1,16 @@
 //! Health check handlers.
 
 use crate::error::ApiResult;
+use crate::handlers::emergency::circuit_status;
 use crate::models::{
-    CircuitBreakerStatus, ComponentHealth, HealthResponse, MetricsResponse, ServiceStatus,
+    CheckStatus, ComponentCheck, ComponentHealth, ExecutorHealth, HealthResponse, MetricsResponse,
+    RpcEndpointHealth, ServiceStatus,
 };
 use crate::state::AppState;
 use axum::{Json, extract::State};
 use clmm_lp_execution::prelude::CircuitState;
-use std::time::Instant;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
 /// Start time for uptime calculation.
 static START_TIME: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
@@ -33,9 +36,104 @@ pub fn increment_error_count() {
     ERROR_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 }
 
+/// Time allowed for each dependency check.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Slots an RPC endpoint may trail the most advanced one before the service
+/// counts as degraded (about a minute).
+const MAX_SLOT_LAG: u64 = 150;
+
+/// Runs a dependency check with a timeout and measures its latency.
+async fn timed_check<F, E>(check: F) -> ComponentCheck
+where
+    F: Future<Output = Result<(), E>>,
+    E: std::fmt::Display,
+{
+    let start = Instant::now();
+    let (status, error) = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(Ok(())) => (CheckStatus::Up, None),
+        Ok(Err(e)) => (CheckStatus::Down, Some(e.to_string())),
+        Err(_) => (CheckStatus::Down, Some("Check timed out".to_string())),
+    };
+    ComponentCheck {
+        status,
+        latency_ms: Some(start.elapsed().as_secs_f64() * 1000.0),
+        error,
+    }
+}
+
+/// Probes every RPC endpoint.
+async fn rpc_endpoints(state: &AppState) -> Vec<RpcEndpointHealth> {
+    state
+        .provider
+        .probe_endpoints()
+        .await
+        .into_iter()
+        .map(|probe| RpcEndpointHealth {
+            healthy: probe.is_reachable(),
+            url: probe.endpoint,
+            latency_ms: probe.latency_ms,
+            slot: probe.slot,
+            slot_lag: probe.slot_lag,
+            error: probe.error,
+        })
+        .collect()
+}
+
+/// Checks database connectivity.
+async fn database_check(state: &AppState) -> ComponentCheck {
+    match &state.database {
+        Some(db) => timed_check(db.ping()).await,
+        None => ComponentCheck::not_configured(),
+    }
+}
+
+/// Checks market data provider reachability.
+async fn market_data_check(state: &AppState) -> ComponentCheck {
+    match &state.market_data {
+        Some(market_data) => timed_check(market_data.health_check()).await,
+        None => ComponentCheck::not_configured(),
+    }
+}
+
+/// Compares running strategies with their executors' loops.
+async fn executor_health(state: &AppState) -> ExecutorHealth {
+    let running: Vec<String> = state
+        .strategies
+        .read()
+        .await
+        .values()
+        .filter(|s| s.running)
+        .map(|s| s.id.clone())
+        .collect();
+
+    let mut live = HashSet::new();
+    for (id, executor) in state.executors.read().await.iter() {
+        if executor.read().await.is_running() {
+            live.insert(id.clone());
+        }
+    }
+
+    let mut stalled: Vec<String> = running
+        .iter()
+        .filter(|id| !live.contains(*id))
+        .cloned()
+        .collect();
+    stalled.sort();
+
+    ExecutorHealth {
+        strategies_running: running.len() as u32,
+        executors_running: live.len() as u32,
+        stalled,
+    }
+}
+
 /// Health check endpoint.
 ///
-/// Returns the current health status of the service.
+/// Probes every RPC endpoint for latency and slot lag, pings the database
+/// and market data provider, and reports circuit breaker and executor
+/// state. The service is unhealthy when no RPC endpoint answers, and
+/// degraded when any other check fails.
 #[utoipa::path(
     get,
     path = "/health",
@@ -47,22 +145,32 @@ pub fn increment_error_count() {
 pub async fn health_check(State(state): State<AppState>) -> ApiResult<Json<HealthResponse>> {
     let uptime = START_TIME.get().map(|t| t.elapsed().as_secs()).unwrap_or(0);
 
-    let circuit_state = state.circuit_breaker.state().await;
-    let circuit_status = match circuit_state {
-        CircuitState::Closed => CircuitBreakerStatus::Closed,
-        CircuitState::Open => CircuitBreakerStatus::Open,
-        CircuitState::HalfOpen => CircuitBreakerStatus::HalfOpen,
-    };
+    let (rpc_endpoints, database, market_data, executors, breaker) = tokio::join!(
+        rpc_endpoints(&state),
+        database_check(&state),
+        market_data_check(&state),
+        executor_health(&state),
+        state.circuit_breaker.stats(),
+    );
+    let circuit_state = breaker.state;
 
-    // Check RPC health
-    let rpc_healthy = state.provider.get_slot().await.is_ok();
+    let rpc_healthy = rpc_endpoints.iter().any(|e| e.healthy);
+    let rpc_degraded = rpc_endpoints
+        .iter()
+        .any(|e| !e.healthy || e.slot_lag.is_some_and(|lag| lag > MAX_SLOT_LAG));
+    let degraded = rpc_degraded
+        || circuit_state != CircuitState::Closed
+        || breaker.manually_tripped
+        || database.status == CheckStatus::Down
+        || market_data.status == CheckStatus::Down
+        || !executors.stalled.is_empty();
 
-    let status = if rpc_healthy && circuit_state == CircuitState::Closed {
-        ServiceStatus::Healthy
-    } else if rpc_healthy {
+    let status = if !rpc_healthy {
+        ServiceStatus::Unhealthy
+    } else if degraded {
         ServiceStatus::Degraded
     } else {
-        ServiceStatus::Unhealthy
+        ServiceStatus::Healthy
     };
 
     let response = HealthResponse {
@@ -71,8 +179,14 @@ pub async fn health_check(State(state): State<AppState>) -> ApiResult<Json<Healt
         uptime_secs: uptime,
         components: ComponentHealth {
             rpc: rpc_healthy,
-            database: true, // Placeholder - no DB yet
-            circuit_breaker: circuit_status,
+            database: database.status != CheckStatus::Down,
+            circuit_breaker: circuit_status(circuit_state),
+            kill_switch_engaged: breaker.manually_tripped,
+            circuit_breaker_reason: breaker.trip_reason,
+            rpc_endpoints,
+            database_check: database,
+            market_data,
+            executors,
         },
     };
 
@@ -107,8 +221,9 @@ pub async fn liveness() -> &'static str {
     )
 )]
 pub async fn readiness(State(state): State<AppState>) -> Result<&'static str, &'static str> {
-    // Check if RPC is available
-    if state.provider.get_slot().await.is_ok() {
+    // Ready once any RPC endpoint answers
+    let probes = state.provider.probe_endpoints().await;
+    if probes.iter().any(|p| p.is_reachable()) {
         Ok("OK")
     } else {
         Err("NOT READY")
@@ -0,0 +1,125 @@
+//! Background job handlers.
+//!
+//! Generic status and control of queued work such as backtests:
+//! - Get a job's status, progress, and results
+//! - Cancel a queued or running job
+//!
+//! Tenants only see the jobs they submitted; other jobs answer 404.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{JobProgress, JobResponse, JobState};
+use crate::services::{Job, JobStatus};
+use crate::state::AppState;
+use crate::tenancy::TenantScope;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
+use tracing::info;
+
+/// Maps the job queue status to its API representation.
+pub(crate) fn job_state(status: JobStatus) -> JobState {
+    match status {
+        JobStatus::Queued => JobState::Queued,
+        JobStatus::Running => JobState::Running,
+        JobStatus::Completed => JobState::Completed,
+        JobStatus::Failed => JobState::Failed,
+        JobStatus::Cancelled => JobState::Cancelled,
+    }
+}
+
+/// Gets a job visible to the caller.
+pub(crate) async fn visible_job(state: &AppState, scope: &TenantScope, id: &str) -> Option<Job> {
+    state
+        .jobs
+        .get(id)
+        .await
+        .filter(|job| scope.can_see_job(job))
+}
+
+/// Maps a job to its API representation.
+fn job_response(job: Job, progress: Option<JobProgress>) -> JobResponse {
+    JobResponse {
+        id: job.id,
+        kind: job.kind,
+        status: job_state(job.status),
+        progress_pct: job.progress_pct,
+        progress,
+        params: job.params,
+        created_at: job.created_at,
+        started_at: job.started_at,
+        finished_at: job.finished_at,
+        result: job.result,
+        error: job.error,
+    }
+}
+
+/// Get a job.
+///
+/// Results of finished jobs remain available after completion.
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    tag = "Jobs",
+    params(
+        ("id" = String, Path, description = "Job ID")
+    ),
+    responses(
+        (status = 200, description = "Job", body = JobResponse),
+        (status = 404, description = "Job not found")
+    )
+)]
+pub async fn get_job(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<JobResponse>> {
+    let job = visible_job(&state, &scope, &id)
+        .await
+        .ok_or_else(|| ApiError::not_found("Job not found"))?;
+    let progress = state.jobs.progress(&id).await;
+
+    Ok(Json(job_response(job, progress)))
+}
+
+/// Cancel a job.
+///
+/// Queued jobs never start; running jobs are stopped at their next
+/// checkpoint. Cancelling an already cancelled job is a no-op.
+#[utoipa::path(
+    delete,
+    path = "/jobs/{id}",
+    tag = "Jobs",
+    params(
+        ("id" = String, Path, description = "Job ID")
+    ),
+    responses(
+        (status = 200, description = "Job cancelled", body = JobResponse),
+        (status = 404, description = "Job not found"),
+        (status = 409, description = "Job already finished")
+    )
+)]
+pub async fn cancel_job(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<JobResponse>> {
+    if visible_job(&state, &scope, &id).await.is_none() {
+        return Err(ApiError::not_found("Job not found"));
+    }
+    let job = state
+        .jobs
+        .cancel(&id)
+        .await
+        .ok_or_else(|| ApiError::not_found("Job not found"))?;
+
+    if matches!(job.status, JobStatus::Completed | JobStatus::Failed) {
+        return Err(ApiError::Conflict(format!(
+            "Job already {}",
+            job.status.name()
+        )));
+    }
+
+    info!(id = %id, kind = %job.kind, "Job cancelled via API");
+    Ok(Json(job_response(job, None)))
+}
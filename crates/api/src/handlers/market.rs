@@ -0,0 +1,131 @@
+//! Market data handlers.
+//!
+//! Serves cached market data for token pairs:
+//! - Price candles
+//! - Volatility, volume, and suggested range analysis
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{CandleResponse, CandlesResponse, MarketAnalysisResponse, MarketQuery};
+use crate::services::{analyze_candles, parse_pair};
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use clmm_lp_domain::entities::price_candle::PriceCandle;
+use tracing::warn;
+
+/// Candle resolutions supported by the market data providers, in seconds.
+const RESOLUTIONS: &[u64] = &[60, 180, 300, 900, 1800, 3600, 7200, 14400, 43200, 86400];
+
+/// Maximum days of history per request.
+const MAX_DAYS: u64 = 365;
+
+/// Validates the query and fetches candles for a pair.
+async fn fetch_candles(
+    state: &AppState,
+    pair: &str,
+    query: &MarketQuery,
+) -> ApiResult<Vec<PriceCandle>> {
+    if !RESOLUTIONS.contains(&query.resolution) {
+        return Err(ApiError::Validation(format!(
+            "resolution must be one of {:?}",
+            RESOLUTIONS
+        )));
+    }
+    if query.days == 0 || query.days > MAX_DAYS {
+        return Err(ApiError::Validation(format!(
+            "days must be between 1 and {}",
+            MAX_DAYS
+        )));
+    }
+
+    let (base, quote) = parse_pair(pair).ok_or_else(|| {
+        ApiError::bad_request("Pair must be BASE-QUOTE with known symbols or mint addresses")
+    })?;
+    let market = state.market_data.clone().ok_or_else(|| {
+        ApiError::ServiceUnavailable("No market data provider configured".to_string())
+    })?;
+
+    market
+        .candles(&base, &quote, query.resolution, query.days)
+        .await
+        .map_err(|e| {
+            warn!(pair = %pair, error = %e, "Failed to fetch candles");
+            ApiError::from(e)
+        })
+}
+
+/// Get price candles for a pair.
+#[utoipa::path(
+    get,
+    path = "/market/{pair}/candles",
+    tag = "Market",
+    params(
+        ("pair" = String, Path, description = "Pair as BASE-QUOTE, e.g. SOL-USDC"),
+        MarketQuery
+    ),
+    responses(
+        (status = 200, description = "Price candles", body = CandlesResponse),
+        (status = 400, description = "Invalid pair or query"),
+        (status = 404, description = "Pair not known to the provider"),
+        (status = 429, description = "Provider rate limit exceeded"),
+        (status = 503, description = "Market data unavailable")
+    )
+)]
+pub async fn get_market_candles(
+    State(state): State<AppState>,
+    Path(pair): Path<String>,
+    Query(query): Query<MarketQuery>,
+) -> ApiResult<Json<CandlesResponse>> {
+    let candles = fetch_candles(&state, &pair, &query).await?;
+
+    Ok(Json(CandlesResponse {
+        pair,
+        resolution: query.resolution,
+        candles: candles
+            .into_iter()
+            .map(|c| CandleResponse {
+                timestamp: c.start_timestamp,
+                open: c.open.value,
+                high: c.high.value,
+                low: c.low.value,
+                close: c.close.value,
+                volume: c.volume_token_a.to_decimal(),
+            })
+            .collect(),
+    }))
+}
+
+/// Get market analysis for a pair.
+///
+/// Returns the same price, volatility, volume, and suggested range
+/// statistics as the CLI `analyze` command.
+#[utoipa::path(
+    get,
+    path = "/market/{pair}/analysis",
+    tag = "Market",
+    params(
+        ("pair" = String, Path, description = "Pair as BASE-QUOTE, e.g. SOL-USDC"),
+        MarketQuery
+    ),
+    responses(
+        (status = 200, description = "Market analysis", body = MarketAnalysisResponse),
+        (status = 400, description = "Invalid pair or query"),
+        (status = 404, description = "No market data for the period"),
+        (status = 429, description = "Provider rate limit exceeded"),
+        (status = 503, description = "Market data unavailable")
+    )
+)]
+pub async fn get_market_analysis(
+    State(state): State<AppState>,
+    Path(pair): Path<String>,
+    Query(query): Query<MarketQuery>,
+) -> ApiResult<Json<MarketAnalysisResponse>> {
+    let candles = fetch_candles(&state, &pair, &query).await?;
+
+    let analysis = analyze_candles(&pair, &candles, query.resolution, query.days)
+        .ok_or_else(|| ApiError::not_found("No market data for the requested period"))?;
+
+    Ok(Json(analysis))
+}
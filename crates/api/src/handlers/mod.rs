@@ -1,13 +1,35 @@
 //! Request handlers for API endpoints.
 
+pub mod alerts;
 pub mod analytics;
+pub mod api_keys;
+pub mod audit;
+pub mod backtests;
+pub mod emergency;
+pub mod export;
 pub mod health;
+pub mod jobs;
+pub mod market;
+pub mod optimize;
 pub mod pools;
 pub mod positions;
 pub mod strategies;
+pub mod wallets;
+pub mod webhooks;
 
+pub use alerts::*;
 pub use analytics::*;
+pub use api_keys::*;
+pub use audit::*;
+pub use backtests::*;
+pub use emergency::*;
+pub use export::*;
 pub use health::*;
+pub use jobs::*;
+pub use market::*;
+pub use optimize::*;
 pub use pools::*;
 pub use positions::*;
 pub use strategies::*;
+pub use wallets::*;
+pub use webhooks::*;
@@ -0,0 +1,170 @@
+//! Optimization handlers.
+//!
+//! Runs the range and parameter optimizers on the background job queue
+//! against stored price history:
+//! - Submit an optimization and receive a job ID immediately
+//! - Poll the job for its status and ranked candidates
+//! - Repeating a seeded optimization over unchanged history returns the
+//!   cached result
+
+use super::backtests::date_window;
+use super::jobs::{job_state, visible_job};
+use crate::error::{ApiError, ApiResult};
+use crate::models::{OptimizeJobResponse, OptimizeRequest, OptimizeResultResponse};
+use crate::services::{Job, run_optimization};
+use crate::state::AppState;
+use crate::tenancy::TenantScope;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use clmm_lp_data::prelude::ResultKey;
+use tracing::info;
+
+/// Job kind of optimizations.
+pub(crate) const OPTIMIZATION_JOB: &str = "optimization";
+
+/// Maximum Monte Carlo iterations per candidate.
+const MAX_ITERATIONS: usize = 10_000;
+
+/// Maximum simulated horizon in days.
+const MAX_HORIZON_DAYS: u32 = 365;
+
+/// Maps an optimization job to its API representation.
+fn optimize_job_response(job: Job) -> ApiResult<OptimizeJobResponse> {
+    let request: OptimizeRequest = serde_json::from_value(job.params)
+        .map_err(|e| ApiError::internal(format!("Invalid stored optimize request: {}", e)))?;
+    let result: Option<OptimizeResultResponse> = job
+        .result
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| ApiError::internal(format!("Invalid stored optimize result: {}", e)))?;
+
+    Ok(OptimizeJobResponse {
+        id: job.id,
+        status: job_state(job.status),
+        progress_pct: job.progress_pct,
+        request,
+        created_at: job.created_at,
+        started_at: job.started_at,
+        finished_at: job.finished_at,
+        result,
+        error: job.error,
+    })
+}
+
+/// Submit an optimization.
+///
+/// Estimates volatility and volume from the pool's stored price history,
+/// ranks candidate ranges by Monte Carlo simulation and then rebalancing
+/// parameters for the best range. Poll `GET /optimize/{id}` for the results.
+#[utoipa::path(
+    post,
+    path = "/optimize",
+    tag = "Optimization",
+    request_body = OptimizeRequest,
+    responses(
+        (status = 202, description = "Optimization queued", body = OptimizeJobResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 503, description = "No price history database configured")
+    )
+)]
+pub async fn submit_optimization(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Json(request): Json<OptimizeRequest>,
+) -> ApiResult<(StatusCode, Json<OptimizeJobResponse>)> {
+    if request.iterations == 0 || request.iterations > MAX_ITERATIONS {
+        return Err(ApiError::Validation(format!(
+            "iterations must be between 1 and {}",
+            MAX_ITERATIONS
+        )));
+    }
+    if request.horizon_days == 0 || request.horizon_days > MAX_HORIZON_DAYS {
+        return Err(ApiError::Validation(format!(
+            "horizon_days must be between 1 and {}",
+            MAX_HORIZON_DAYS
+        )));
+    }
+    if request.top_n == 0 {
+        return Err(ApiError::Validation("top_n must be positive".to_string()));
+    }
+    if request.start_date > request.end_date {
+        return Err(ApiError::Validation(
+            "start_date must not be after end_date".to_string(),
+        ));
+    }
+    if let (Some(min), Some(max)) = (
+        request.constraints.min_range_width,
+        request.constraints.max_range_width,
+    ) && min > max
+    {
+        return Err(ApiError::Validation(
+            "min_range_width must not exceed max_range_width".to_string(),
+        ));
+    }
+
+    let Some(source) = state.price_history.clone() else {
+        return Err(ApiError::ServiceUnavailable(
+            "Optimization requires a price history database".to_string(),
+        ));
+    };
+
+    let (start, end) = date_window(request.start_date, request.end_date);
+    let params = serde_json::to_value(&request)
+        .map_err(|e| ApiError::internal(format!("Failed to encode request: {}", e)))?;
+    let results = state.results.clone();
+    let owner = scope.tenant_id();
+    let job = state
+        .jobs
+        .submit(OPTIMIZATION_JOB, owner, params, |job| async move {
+            let history = source.load(&request.pool_address, start, end).await?;
+            let result = match request.seed {
+                // Only seeded runs are reproducible and worth caching
+                Some(seed) => {
+                    let key = ResultKey::for_inputs(OPTIMIZATION_JOB, &history, &request)?
+                        .with_seed(seed);
+                    tokio::task::spawn_blocking(move || {
+                        results.get_or_compute(&key, || run_optimization(&request, &history, &job))
+                    })
+                    .await??
+                }
+                None => {
+                    tokio::task::spawn_blocking(move || run_optimization(&request, &history, &job))
+                        .await??
+                }
+            };
+            Ok(serde_json::to_value(result)?)
+        })
+        .await;
+
+    info!(id = %job.id, "Optimization queued");
+    Ok((StatusCode::ACCEPTED, Json(optimize_job_response(job)?)))
+}
+
+/// Get an optimization job.
+#[utoipa::path(
+    get,
+    path = "/optimize/{id}",
+    tag = "Optimization",
+    params(
+        ("id" = String, Path, description = "Optimization job ID")
+    ),
+    responses(
+        (status = 200, description = "Optimization job", body = OptimizeJobResponse),
+        (status = 404, description = "Optimization not found")
+    )
+)]
+pub async fn get_optimization(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<OptimizeJobResponse>> {
+    let job = visible_job(&state, &scope, &id)
+        .await
+        .filter(|job| job.kind == OPTIMIZATION_JOB)
+        .ok_or_else(|| ApiError::not_found("Optimization not found"))?;
+
+    Ok(Json(optimize_job_response(job)?))
+}
@@ -1,16 +1,31 @@
 //! Pool handlers.
 
 use crate::error::{ApiError, ApiResult};
-use crate::models::{ListPoolsResponse, PoolResponse, PoolStateResponse};
+use crate::models::{
+    ListPoolsResponse, PoolResponse, PoolSearchQuery, PoolSearchResponse, PoolStateResponse,
+};
+use crate::services::MAX_PAGE_SIZE;
 use crate::state::AppState;
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
 };
-use clmm_lp_protocols::prelude::WhirlpoolReader;
+use clmm_lp_domain::enums::Protocol;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
+/// Name of a protocol in pool responses.
+fn protocol_name(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::OrcaWhirlpools => "orca_whirlpool",
+        Protocol::OrcaLegacy => "orca_legacy",
+        Protocol::Raydium => "raydium_clmm",
+        Protocol::MeteoraDLMM => "meteora_dlmm",
+        Protocol::MeteoraStable => "meteora_stable",
+        Protocol::UniswapV3 => "uniswap_v3",
+    }
+}
+
 /// List available pools.
 #[utoipa::path(
     get,
@@ -29,6 +44,47 @@ pub async fn list_pools(State(_state): State<AppState>) -> ApiResult<Json<ListPo
     }))
 }
 
+/// Search pools.
+///
+/// Filters stored pools by token, protocol, TVL, volume, and estimated fee
+/// APR, sorted and paginated. APR estimates use the last 24 hours of volume.
+#[utoipa::path(
+    get,
+    path = "/pools/search",
+    tag = "Pools",
+    params(PoolSearchQuery),
+    responses(
+        (status = 200, description = "Matching pools", body = PoolSearchResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 503, description = "No pool database configured")
+    )
+)]
+pub async fn search_pools(
+    State(state): State<AppState>,
+    Query(query): Query<PoolSearchQuery>,
+) -> ApiResult<Json<PoolSearchResponse>> {
+    if query.page == 0 {
+        return Err(ApiError::Validation("page must be at least 1".to_string()));
+    }
+    if query.per_page == 0 || query.per_page > MAX_PAGE_SIZE {
+        return Err(ApiError::Validation(format!(
+            "per_page must be between 1 and {}",
+            MAX_PAGE_SIZE
+        )));
+    }
+
+    let screener = state.pool_screener.clone().ok_or_else(|| {
+        ApiError::ServiceUnavailable("Pool search requires a database".to_string())
+    })?;
+
+    let response = screener
+        .search(&query)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to search pools: {}", e)))?;
+
+    Ok(Json(response))
+}
+
 /// Get pool details.
 #[utoipa::path(
     get,
@@ -46,26 +102,25 @@ pub async fn get_pool(
     State(state): State<AppState>,
     Path(address): Path<String>,
 ) -> ApiResult<Json<PoolResponse>> {
-    let _pubkey =
+    let pubkey =
         Pubkey::from_str(&address).map_err(|_| ApiError::bad_request("Invalid pool address"))?;
 
-    let reader = WhirlpoolReader::new(state.provider.clone());
-
-    let pool_state = reader
-        .get_pool_state(&address)
+    let pool_state = state
+        .protocols
+        .fetch_pool_state(&pubkey)
         .await
         .map_err(|e| ApiError::not_found(format!("Pool not found: {}", e)))?;
 
     let response = PoolResponse {
-        address: pool_state.address,
-        protocol: "orca_whirlpool".to_string(),
+        address,
+        protocol: protocol_name(pool_state.protocol).to_string(),
         token_mint_a: pool_state.token_mint_a.to_string(),
         token_mint_b: pool_state.token_mint_b.to_string(),
         current_tick: pool_state.tick_current,
         tick_spacing: pool_state.tick_spacing as i32,
         price: pool_state.price,
         liquidity: pool_state.liquidity.to_string(),
-        fee_rate_bps: pool_state.fee_rate_bps,
+        fee_rate_bps: u16::try_from(pool_state.fee_rate / 100).unwrap_or(u16::MAX),
         volume_24h_usd: None,
         tvl_usd: None,
         apy_estimate: None,
@@ -91,18 +146,17 @@ pub async fn get_pool_state(
     State(state): State<AppState>,
     Path(address): Path<String>,
 ) -> ApiResult<Json<PoolStateResponse>> {
-    let _pubkey =
+    let pubkey =
         Pubkey::from_str(&address).map_err(|_| ApiError::bad_request("Invalid pool address"))?;
 
-    let reader = WhirlpoolReader::new(state.provider.clone());
-
-    let pool_state = reader
-        .get_pool_state(&address)
+    let pool_state = state
+        .protocols
+        .fetch_pool_state(&pubkey)
         .await
         .map_err(|e| ApiError::not_found(format!("Pool not found: {}", e)))?;
 
     let response = PoolStateResponse {
-        address: pool_state.address,
+        address,
         current_tick: pool_state.tick_current,
         sqrt_price: pool_state.sqrt_price.to_string(),
         price: pool_state.price,
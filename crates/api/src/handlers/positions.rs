@@ -2,21 +2,75 @@
 
 use crate::error::{ApiError, ApiResult};
 use crate::models::{
-    ListPositionsResponse, MessageResponse, OpenPositionRequest, PnLResponse, PositionResponse,
-    PositionStatus, RebalanceRequest,
+    BatchItemResult, BatchOperation, BatchPositionItem, BatchPositionRequest,
+    BatchPositionResponse, ExpectedImpactResponse, LifecycleEventResponse, ListPositionsResponse,
+    MessageResponse, OpenPositionRequest, PnLResponse, PositionHistoryQuery,
+    PositionHistoryResponse, PositionResponse, PositionSnapshotResponse, PositionStatus,
+    RebalanceRequest, RecommendationQuery, RecommendationResponse,
 };
-use crate::state::{AlertUpdate, AppState, PositionUpdate};
+use crate::services::{
+    MAX_HISTORY_DAYS, PositionService, estimate_fee_apr_pct, estimate_volatility,
+};
+use crate::state::{AppState, PositionUpdate};
+use crate::tenancy::TenantScope;
 use axum::{
-    Json,
-    extract::{Path, State},
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use clmm_lp_execution::prelude::{
+    DecisionConfig, EventData, LifecycleEvent, MonitoredPosition, Recommendation,
+    RecommendationConfig, Recommender,
 };
-use clmm_lp_execution::prelude::{RebalanceData, RebalanceReason};
 use clmm_lp_protocols::prelude::WhirlpoolReader;
+use futures::stream::{self, StreamExt};
+use rust_decimal::Decimal;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use tracing::{info, warn};
 
+/// Maximum operations per batch request.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Operations of a batch run concurrently.
+const BATCH_CONCURRENCY: usize = 8;
+
+/// Days of price history used to estimate volatility and fee APR for
+/// recommendations.
+const RECOMMENDATION_HISTORY_DAYS: i64 = 7;
+
+/// Maps a monitored position to its API representation.
+pub(crate) fn position_response(p: &MonitoredPosition) -> PositionResponse {
+    PositionResponse {
+        address: p.address.to_string(),
+        pool_address: p.pool.to_string(),
+        owner: p.on_chain.owner.to_string(),
+        tick_lower: p.on_chain.tick_lower,
+        tick_upper: p.on_chain.tick_upper,
+        liquidity: p.on_chain.liquidity.to_string(),
+        in_range: p.in_range,
+        value_usd: p.pnl.current_value_usd,
+        pnl: PnLResponse {
+            unrealized_pnl_usd: p.pnl.net_pnl_usd,
+            unrealized_pnl_pct: p.pnl.net_pnl_pct,
+            fees_earned_a: p.pnl.fees_earned_a,
+            fees_earned_b: p.pnl.fees_earned_b,
+            fees_earned_usd: p.pnl.fees_usd,
+            il_pct: p.pnl.il_pct,
+            net_pnl_usd: p.pnl.net_pnl_usd,
+            net_pnl_pct: p.pnl.net_pnl_pct,
+        },
+        status: if p.in_range {
+            PositionStatus::Active
+        } else {
+            PositionStatus::OutOfRange
+        },
+        created_at: None,
+    }
+}
+
 /// List all positions.
+///
+/// Tenants only see positions owned by their wallets.
 #[utoipa::path(
     get,
     path = "/positions",
@@ -27,38 +81,11 @@ use tracing::{info, warn};
 )]
 pub async fn list_positions(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
 ) -> ApiResult<Json<ListPositionsResponse>> {
-    let positions = state.monitor.get_positions().await;
+    let positions = scope.filter_positions(state.monitor.get_positions().await);
 
-    let responses: Vec<PositionResponse> = positions
-        .iter()
-        .map(|p| PositionResponse {
-            address: p.address.to_string(),
-            pool_address: p.pool.to_string(),
-            owner: p.on_chain.owner.to_string(),
-            tick_lower: p.on_chain.tick_lower,
-            tick_upper: p.on_chain.tick_upper,
-            liquidity: p.on_chain.liquidity.to_string(),
-            in_range: p.in_range,
-            value_usd: p.pnl.current_value_usd,
-            pnl: PnLResponse {
-                unrealized_pnl_usd: p.pnl.net_pnl_usd,
-                unrealized_pnl_pct: p.pnl.net_pnl_pct,
-                fees_earned_a: p.pnl.fees_earned_a,
-                fees_earned_b: p.pnl.fees_earned_b,
-                fees_earned_usd: p.pnl.fees_usd,
-                il_pct: p.pnl.il_pct,
-                net_pnl_usd: p.pnl.net_pnl_usd,
-                net_pnl_pct: p.pnl.net_pnl_pct,
-            },
-            status: if p.in_range {
-                PositionStatus::Active
-            } else {
-                PositionStatus::OutOfRange
-            },
-            created_at: None,
-        })
-        .collect();
+    let responses: Vec<PositionResponse> = positions.iter().map(position_response).collect();
 
     Ok(Json(ListPositionsResponse {
         total: responses.len(),
@@ -92,34 +119,7 @@ pub async fn get_position(
         .find(|p| p.address == pubkey)
         .ok_or_else(|| ApiError::not_found("Position not found"))?;
 
-    let response = PositionResponse {
-        address: position.address.to_string(),
-        pool_address: position.pool.to_string(),
-        owner: position.on_chain.owner.to_string(),
-        tick_lower: position.on_chain.tick_lower,
-        tick_upper: position.on_chain.tick_upper,
-        liquidity: position.on_chain.liquidity.to_string(),
-        in_range: position.in_range,
-        value_usd: position.pnl.current_value_usd,
-        pnl: PnLResponse {
-            unrealized_pnl_usd: position.pnl.net_pnl_usd,
-            unrealized_pnl_pct: position.pnl.net_pnl_pct,
-            fees_earned_a: position.pnl.fees_earned_a,
-            fees_earned_b: position.pnl.fees_earned_b,
-            fees_earned_usd: position.pnl.fees_usd,
-            il_pct: position.pnl.il_pct,
-            net_pnl_usd: position.pnl.net_pnl_usd,
-            net_pnl_pct: position.pnl.net_pnl_pct,
-        },
-        status: if position.in_range {
-            PositionStatus::Active
-        } else {
-            PositionStatus::OutOfRange
-        },
-        created_at: None,
-    };
-
-    Ok(Json(response))
+    Ok(Json(position_response(position)))
 }
 
 /// Open a new position.
@@ -130,11 +130,13 @@ pub async fn get_position(
     request_body = OpenPositionRequest,
     responses(
         (status = 201, description = "Position opened", body = PositionResponse),
-        (status = 400, description = "Invalid request")
+        (status = 400, description = "Invalid request"),
+        (status = 409, description = "Pool price diverges from the oracle")
     )
 )]
 pub async fn open_position(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
     Json(request): Json<OpenPositionRequest>,
 ) -> ApiResult<Json<MessageResponse>> {
     info!(
@@ -153,9 +155,11 @@ pub async fn open_position(
     }
 
     // Validate pool exists
-    let pool_reader = WhirlpoolReader::new(state.provider.clone());
-    let pool_state = pool_reader
-        .get_pool_state(&request.pool_address)
+    let pool_pubkey = Pubkey::from_str(&request.pool_address)
+        .map_err(|_| ApiError::bad_request("Invalid pool address"))?;
+    let pool_state = state
+        .protocols
+        .fetch_pool_state(&pool_pubkey)
         .await
         .map_err(|e| ApiError::not_found(format!("Pool not found: {}", e)))?;
 
@@ -168,6 +172,11 @@ pub async fn open_position(
         )));
     }
 
+    // Refuse pools priced away from the oracle
+    if let Some(guard) = &state.price_guard {
+        guard.check_pool(&pool_state).await?;
+    }
+
     if state.dry_run {
         info!("Dry-run mode: would open position");
         return Ok(Json(MessageResponse::new(format!(
@@ -176,11 +185,24 @@ pub async fn open_position(
         ))));
     }
 
-    // Actual execution requires wallet configuration
-    warn!("Position opening requires wallet configuration");
-    Ok(Json(MessageResponse::new(
-        "Position opening requires wallet configuration. Set up wallet first.",
-    )))
+    let mut service = PositionService::new(state.clone());
+    service.set_dry_run(false);
+    service.set_tenant(scope.tenant_id().map(str::to_string));
+    let result = service.open_position(&request).await?.confirmed()?;
+
+    let position = result
+        .data
+        .as_ref()
+        .and_then(|data| data["position"].as_str())
+        .unwrap_or_default();
+    Ok(Json(MessageResponse::new(format!(
+        "Opened position {} in pool {} with range [{}, {}] (signature {})",
+        position,
+        request.pool_address,
+        request.tick_lower,
+        request.tick_upper,
+        result.signature.unwrap_or_default()
+    ))))
 }
 
 /// Close a position.
@@ -198,10 +220,20 @@ pub async fn open_position(
 )]
 pub async fn close_position(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
     Path(address): Path<String>,
 ) -> ApiResult<Json<MessageResponse>> {
-    let pubkey = Pubkey::from_str(&address)
-        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    close_one(&state, &scope, &address).await.map(Json)
+}
+
+/// Closes a position, or simulates closing it in dry-run mode.
+async fn close_one(
+    state: &AppState,
+    scope: &TenantScope,
+    address: &str,
+) -> ApiResult<MessageResponse> {
+    let pubkey =
+        Pubkey::from_str(address).map_err(|_| ApiError::bad_request("Invalid position address"))?;
 
     info!(position = %address, dry_run = state.dry_run, "Closing position");
 
@@ -218,7 +250,8 @@ pub async fn close_position(
         // Broadcast simulated update
         state.broadcast_position_update(PositionUpdate {
             update_type: "close_simulated".to_string(),
-            position_address: address.clone(),
+            position_address: address.to_string(),
+            pool_address: Some(position.pool.to_string()),
             timestamp: chrono::Utc::now(),
             data: serde_json::json!({
                 "liquidity": position.on_chain.liquidity.to_string(),
@@ -226,16 +259,27 @@ pub async fn close_position(
             }),
         });
 
-        return Ok(Json(MessageResponse::new(format!(
+        return Ok(MessageResponse::new(format!(
             "[DRY-RUN] Would close position {} with liquidity {}",
             address, position.on_chain.liquidity
-        ))));
+        )));
     }
 
-    // Actual execution requires wallet configuration
-    warn!("Position closing requires wallet configuration");
-    Ok(Json(MessageResponse::new(
-        "Position closing requires wallet configuration. Set up wallet first.",
+    let mut service = PositionService::new(state.clone());
+    service.set_dry_run(false);
+    service.set_tenant(scope.tenant_id().map(str::to_string));
+    let result = service.close_position(address).await?.confirmed()?;
+
+    let pnl = result
+        .data
+        .as_ref()
+        .and_then(|data| data["realized_pnl_usd"].as_str())
+        .unwrap_or("0");
+    Ok(MessageResponse::new(format!(
+        "Closed position {} with realized PnL {} USD (signature {})",
+        address,
+        pnl,
+        result.signature.unwrap_or_default()
     )))
 }
 
@@ -254,10 +298,20 @@ pub async fn close_position(
 )]
 pub async fn collect_fees(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
     Path(address): Path<String>,
 ) -> ApiResult<Json<MessageResponse>> {
-    let pubkey = Pubkey::from_str(&address)
-        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    collect_fees_one(&state, &scope, &address).await.map(Json)
+}
+
+/// Collects a position's fees, or simulates collecting them in dry-run mode.
+async fn collect_fees_one(
+    state: &AppState,
+    scope: &TenantScope,
+    address: &str,
+) -> ApiResult<MessageResponse> {
+    let pubkey =
+        Pubkey::from_str(address).map_err(|_| ApiError::bad_request("Invalid position address"))?;
 
     info!(position = %address, dry_run = state.dry_run, "Collecting fees");
 
@@ -274,7 +328,8 @@ pub async fn collect_fees(
         // Broadcast simulated update
         state.broadcast_position_update(PositionUpdate {
             update_type: "fees_collected_simulated".to_string(),
-            position_address: address.clone(),
+            position_address: address.to_string(),
+            pool_address: Some(position.pool.to_string()),
             timestamp: chrono::Utc::now(),
             data: serde_json::json!({
                 "fees_a": position.pnl.fees_earned_a,
@@ -283,16 +338,30 @@ pub async fn collect_fees(
             }),
         });
 
-        return Ok(Json(MessageResponse::new(format!(
+        return Ok(MessageResponse::new(format!(
             "[DRY-RUN] Would collect fees from position {}: {} token A, {} token B",
             address, position.pnl.fees_earned_a, position.pnl.fees_earned_b
-        ))));
+        )));
     }
 
-    // Actual execution requires wallet configuration
-    warn!("Fee collection requires wallet configuration");
-    Ok(Json(MessageResponse::new(
-        "Fee collection requires wallet configuration. Set up wallet first.",
+    let mut service = PositionService::new(state.clone());
+    service.set_dry_run(false);
+    service.set_tenant(scope.tenant_id().map(str::to_string));
+    let result = service.collect_fees(address).await?.confirmed()?;
+
+    let fee = |key: &str| {
+        result
+            .data
+            .as_ref()
+            .and_then(|data| data[key].as_u64())
+            .unwrap_or_default()
+    };
+    Ok(MessageResponse::new(format!(
+        "Collected fees from position {}: {} token A, {} token B (signature {})",
+        address,
+        fee("fees_a"),
+        fee("fees_b"),
+        result.signature.clone().unwrap_or_default()
     )))
 }
 
@@ -312,11 +381,24 @@ pub async fn collect_fees(
 )]
 pub async fn rebalance_position(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
     Path(address): Path<String>,
     Json(request): Json<RebalanceRequest>,
 ) -> ApiResult<Json<MessageResponse>> {
-    let pubkey = Pubkey::from_str(&address)
-        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    rebalance_one(&state, &scope, &address, &request)
+        .await
+        .map(Json)
+}
+
+/// Rebalances a position, or simulates rebalancing it in dry-run mode.
+async fn rebalance_one(
+    state: &AppState,
+    scope: &TenantScope,
+    address: &str,
+    request: &RebalanceRequest,
+) -> ApiResult<MessageResponse> {
+    let pubkey =
+        Pubkey::from_str(address).map_err(|_| ApiError::bad_request("Invalid position address"))?;
 
     info!(
         position = %address,
@@ -341,9 +423,9 @@ pub async fn rebalance_position(
         .ok_or_else(|| ApiError::not_found("Position not found"))?;
 
     // Fetch pool state for validation
-    let pool_reader = WhirlpoolReader::new(state.provider.clone());
-    let pool_state = pool_reader
-        .get_pool_state(&position.pool.to_string())
+    let pool_state = state
+        .protocols
+        .fetch_pool_state(&position.pool)
         .await
         .map_err(|e| ApiError::Internal(format!("Failed to fetch pool state: {}", e)))?;
 
@@ -362,7 +444,8 @@ pub async fn rebalance_position(
         // Broadcast simulated update
         state.broadcast_position_update(PositionUpdate {
             update_type: "rebalance_simulated".to_string(),
-            position_address: address.clone(),
+            position_address: address.to_string(),
+            pool_address: Some(position.pool.to_string()),
             timestamp: chrono::Utc::now(),
             data: serde_json::json!({
                 "old_range": [position.on_chain.tick_lower, position.on_chain.tick_upper],
@@ -371,60 +454,123 @@ pub async fn rebalance_position(
             }),
         });
 
-        return Ok(Json(MessageResponse::new(format!(
+        return Ok(MessageResponse::new(format!(
             "[DRY-RUN] Would rebalance position {} from [{}, {}] to [{}, {}]",
             address,
             position.on_chain.tick_lower,
             position.on_chain.tick_upper,
             request.new_tick_lower,
             request.new_tick_upper
-        ))));
+        )));
     }
 
-    // Record rebalance intent in lifecycle tracker
-    state
-        .lifecycle
-        .record_rebalance(
-            pubkey,
-            position.pool,
-            RebalanceData {
-                old_tick_lower: position.on_chain.tick_lower,
-                old_tick_upper: position.on_chain.tick_upper,
-                new_tick_lower: request.new_tick_lower,
-                new_tick_upper: request.new_tick_upper,
-                old_liquidity: position.on_chain.liquidity,
-                new_liquidity: position.on_chain.liquidity,
-                tx_cost_lamports: 0,
-                il_at_rebalance: position.pnl.il_pct,
-                reason: RebalanceReason::Manual,
+    let mut service = PositionService::new(state.clone());
+    service.set_dry_run(false);
+    service.set_tenant(scope.tenant_id().map(str::to_string));
+    let result = service
+        .rebalance_position(address, request)
+        .await?
+        .confirmed()?;
+
+    let new_position = result
+        .data
+        .as_ref()
+        .and_then(|data| data["new_position"].as_str())
+        .unwrap_or_default();
+    Ok(MessageResponse::new(format!(
+        "Rebalanced position {} into {} with range [{}, {}]",
+        address, new_position, request.new_tick_lower, request.new_tick_upper
+    )))
+}
+
+/// Applies one batch item.
+async fn apply_batch_item(
+    state: &AppState,
+    scope: &TenantScope,
+    item: BatchPositionItem,
+) -> BatchItemResult {
+    let result = if !scope.can_see_position_at(state, &item.address).await {
+        Err(ApiError::not_found("Position not found"))
+    } else {
+        match item.operation {
+            BatchOperation::CollectFees => collect_fees_one(state, scope, &item.address).await,
+            BatchOperation::Close => close_one(state, scope, &item.address).await,
+            BatchOperation::Rebalance => match &item.rebalance {
+                Some(request) => rebalance_one(state, scope, &item.address, request).await,
+                None => Err(ApiError::Validation(
+                    "rebalance requires new_tick_lower and new_tick_upper".to_string(),
+                )),
             },
-        )
+        }
+    };
+
+    match result {
+        Ok(response) => BatchItemResult {
+            address: item.address,
+            operation: item.operation,
+            success: true,
+            status: 200,
+            message: Some(response.message),
+            error_code: None,
+            error: None,
+        },
+        Err(e) => BatchItemResult {
+            address: item.address,
+            operation: item.operation,
+            success: false,
+            status: e.status_code().as_u16(),
+            message: None,
+            error_code: Some(e.code().to_string()),
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Apply operations to many positions.
+///
+/// Each item is applied independently as by its single-position endpoint;
+/// a failing item does not stop the others. Outside dry-run mode an item
+/// only succeeds once its transactions are confirmed. Results are returned in
+/// request order. Positions outside a tenant's scope fail as not found.
+#[utoipa::path(
+    post,
+    path = "/positions/batch",
+    tag = "Positions",
+    request_body = BatchPositionRequest,
+    responses(
+        (status = 200, description = "Per-item results", body = BatchPositionResponse),
+        (status = 422, description = "Empty or oversized batch")
+    )
+)]
+pub async fn batch_positions(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    Json(request): Json<BatchPositionRequest>,
+) -> ApiResult<Json<BatchPositionResponse>> {
+    if request.items.is_empty() || request.items.len() > MAX_BATCH_SIZE {
+        return Err(ApiError::Validation(format!(
+            "items must contain between 1 and {} operations",
+            MAX_BATCH_SIZE
+        )));
+    }
+
+    info!(
+        items = request.items.len(),
+        "Applying batch position operations"
+    );
+
+    let results: Vec<BatchItemResult> = stream::iter(request.items)
+        .map(|item| apply_batch_item(&state, &scope, item))
+        .buffered(BATCH_CONCURRENCY)
+        .collect()
         .await;
+    let succeeded = results.iter().filter(|r| r.success).count();
 
-    // Broadcast update
-    state.broadcast_position_update(PositionUpdate {
-        update_type: "rebalance_initiated".to_string(),
-        position_address: address.clone(),
-        timestamp: chrono::Utc::now(),
-        data: serde_json::json!({
-            "old_range": [position.on_chain.tick_lower, position.on_chain.tick_upper],
-            "new_range": [request.new_tick_lower, request.new_tick_upper]
-        }),
-    });
-
-    // Broadcast alert
-    state.broadcast_alert(AlertUpdate {
-        level: "info".to_string(),
-        message: format!("Rebalance initiated for position {}", address),
-        timestamp: chrono::Utc::now(),
-        position_address: Some(address.clone()),
-    });
-
-    // Actual execution requires wallet configuration
-    warn!("Rebalance recorded - actual execution requires wallet configuration");
-    Ok(Json(MessageResponse::new(
-        "Rebalance recorded. Actual execution requires wallet configuration.",
-    )))
+    Ok(Json(BatchPositionResponse {
+        failed: results.len() - succeeded,
+        succeeded,
+        results,
+    }))
 }
 
 /// Get position PnL details.
@@ -466,3 +612,242 @@ pub async fn get_position_pnl(
 
     Ok(Json(response))
 }
+
+/// Get a recommended action for a position.
+///
+/// Reads the current pool state, runs the decision engine without its
+/// confirmation and cooldown gates, and compares the current range with a
+/// recentered or wider one. Volatility and fee APR are estimated from the
+/// pool's recent price history unless given.
+#[utoipa::path(
+    get,
+    path = "/positions/{address}/recommendation",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address"),
+        RecommendationQuery
+    ),
+    responses(
+        (status = 200, description = "Recommended action", body = RecommendationResponse),
+        (status = 400, description = "Invalid address or parameters"),
+        (status = 404, description = "Position not found")
+    )
+)]
+pub async fn get_position_recommendation(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<RecommendationQuery>,
+) -> ApiResult<Json<RecommendationResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    if query.horizon_hours == Some(0) {
+        return Err(ApiError::Validation(
+            "horizon_hours must be positive".to_string(),
+        ));
+    }
+    if query.volatility.is_some_and(|v| !v.is_finite() || v <= 0.0) {
+        return Err(ApiError::Validation(
+            "volatility must be positive".to_string(),
+        ));
+    }
+    if let Some(width) = query.range_width_pct
+        && width <= Decimal::ZERO
+    {
+        return Err(ApiError::Validation(
+            "range_width_pct must be positive".to_string(),
+        ));
+    }
+
+    let position = state
+        .monitor
+        .get_position(&pubkey)
+        .await
+        .ok_or_else(|| ApiError::not_found("Position not found"))?;
+
+    let pool_reader = WhirlpoolReader::new(state.provider.clone());
+    let pool_state = pool_reader
+        .get_pool_state(&position.pool.to_string())
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to fetch pool state: {}", e)))?;
+
+    let mut config = RecommendationConfig::default();
+    if let Some(width) = query.range_width_pct {
+        config = config.with_decision(DecisionConfig {
+            range_width_pct: width,
+            ..DecisionConfig::default()
+        });
+    }
+    if let Some(horizon_hours) = query.horizon_hours {
+        config = config.with_horizon_hours(horizon_hours);
+    }
+
+    let mut volatility = query.volatility;
+    let mut fee_apr_pct = query.fee_apr_pct;
+    if (volatility.is_none() || fee_apr_pct.is_none())
+        && let Some(source) = state.price_history.clone()
+    {
+        let end = chrono::Utc::now().timestamp();
+        let start = end - RECOMMENDATION_HISTORY_DAYS * 86_400;
+        match source.load(&position.pool.to_string(), start, end).await {
+            Ok(history) => {
+                volatility = volatility.or_else(|| estimate_volatility(&history));
+                fee_apr_pct = fee_apr_pct.or_else(|| estimate_fee_apr_pct(&history));
+            }
+            Err(e) => {
+                warn!(
+                    pool = %position.pool,
+                    error = %e,
+                    "Failed to load pool history for recommendation"
+                );
+            }
+        }
+    }
+    if let Some(volatility) = volatility {
+        config = config.with_volatility(volatility);
+    }
+    if let Some(fee_apr_pct) = fee_apr_pct {
+        config = config.with_fee_apr_pct(fee_apr_pct);
+    }
+
+    let recommendation = Recommender::new(config).recommend(&position, &pool_state);
+    info!(
+        position = %address,
+        action = %recommendation.action,
+        "Recommendation computed"
+    );
+
+    Ok(Json(recommendation_response(&recommendation)))
+}
+
+/// Maps a recommendation to its API representation.
+fn recommendation_response(recommendation: &Recommendation) -> RecommendationResponse {
+    let new_prices = recommendation.new_price_range();
+    let impact = &recommendation.impact;
+    RecommendationResponse {
+        address: recommendation.position.to_string(),
+        pool_address: recommendation.pool.to_string(),
+        action: recommendation.action.to_string(),
+        reason: recommendation.reason.clone(),
+        tick_current: recommendation.tick_current,
+        price: recommendation.price,
+        in_range: recommendation.in_range,
+        tick_lower: recommendation.tick_lower,
+        tick_upper: recommendation.tick_upper,
+        new_tick_lower: recommendation.new_tick_lower,
+        new_tick_upper: recommendation.new_tick_upper,
+        new_price_lower: new_prices.map(|(lower, _)| lower),
+        new_price_upper: new_prices.map(|(_, upper)| upper),
+        impact: ExpectedImpactResponse {
+            horizon_hours: impact.horizon_hours,
+            volatility: impact.volatility,
+            current_time_in_range_pct: impact.current_time_in_range_pct,
+            proposed_time_in_range_pct: impact.proposed_time_in_range_pct,
+            current_fee_multiplier: impact.current_fee_multiplier,
+            proposed_fee_multiplier: impact.proposed_fee_multiplier,
+            current_fees_usd: impact.current_fees_usd,
+            proposed_fees_usd: impact.proposed_fees_usd,
+            cost_usd: impact.cost_usd,
+            net_benefit_usd: impact.net_benefit_usd,
+        },
+    }
+}
+
+/// Maps a lifecycle event to its API representation.
+fn lifecycle_event_response(event: &LifecycleEvent) -> LifecycleEventResponse {
+    let data = match &event.data {
+        EventData::PositionOpened(data) => serde_json::to_value(data),
+        EventData::LiquidityChange(data) => serde_json::to_value(data),
+        EventData::Rebalance(data) => serde_json::to_value(data),
+        EventData::FeesCollected(data) => serde_json::to_value(data),
+        EventData::PositionClosed(data) => serde_json::to_value(data),
+        EventData::HedgeFunding(data) => serde_json::to_value(data),
+    };
+    LifecycleEventResponse {
+        id: event.id.clone(),
+        event_type: format!("{:?}", event.event_type),
+        timestamp: event.timestamp,
+        signature: event.signature.map(|s| s.to_string()),
+        data: data.unwrap_or_default(),
+    }
+}
+
+/// Get position history.
+///
+/// Returns the position's lifecycle events (opening, rebalances, fee
+/// collections, ...) and the PnL snapshots recorded by the monitor,
+/// optionally limited to a time range.
+#[utoipa::path(
+    get,
+    path = "/positions/{address}/history",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address"),
+        PositionHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Position history", body = PositionHistoryResponse),
+        (status = 400, description = "Invalid address or time range"),
+        (status = 404, description = "Position not found")
+    )
+)]
+pub async fn get_position_history(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<PositionHistoryQuery>,
+) -> ApiResult<Json<PositionHistoryResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    if let (Some(since), Some(until)) = (query.since, query.until)
+        && since >= until
+    {
+        return Err(ApiError::Validation(
+            "since must be before until".to_string(),
+        ));
+    }
+    let in_window = |time: &chrono::DateTime<chrono::Utc>| {
+        query.since.is_none_or(|since| *time >= since)
+            && query.until.is_none_or(|until| *time < until)
+    };
+
+    let mut snapshots = Vec::new();
+    if let Some(history) = state.position_history.clone() {
+        let since = query
+            .since
+            .unwrap_or_else(|| chrono::Utc::now() - chrono::Duration::days(MAX_HISTORY_DAYS));
+        let points = history
+            .load_history(since, Some(&address))
+            .await
+            .map_err(|e| {
+                warn!(position = %address, error = %e, "Failed to load position history");
+                ApiError::internal(format!("Failed to load position history: {}", e))
+            })?;
+        snapshots = points
+            .into_iter()
+            .filter(|point| in_window(&point.timestamp))
+            .map(|point| PositionSnapshotResponse {
+                timestamp: point.timestamp,
+                in_range: point.in_range,
+                value_usd: point.value_usd,
+                fees_usd: point.fees_usd,
+                il_pct: point.il_pct,
+                net_pnl_usd: point.net_pnl_usd,
+            })
+            .collect();
+    }
+
+    let events = state.lifecycle.get_events(&pubkey).await;
+    let monitored = state.monitor.get_position(&pubkey).await.is_some();
+    if events.is_empty() && snapshots.is_empty() && !monitored {
+        return Err(ApiError::not_found("Position not found"));
+    }
+
+    Ok(Json(PositionHistoryResponse {
+        address,
+        events: events
+            .iter()
+            .filter(|event| in_window(&event.timestamp))
+            .map(lifecycle_event_response)
+            .collect(),
+        snapshots,
+    }))
+}
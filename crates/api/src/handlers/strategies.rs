@@ -2,21 +2,149 @@
 
 use crate::error::{ApiError, ApiResult};
 use crate::models::{
-    CreateStrategyRequest, ListStrategiesResponse, MessageResponse, StrategyParameters,
-    StrategyPerformanceResponse, StrategyResponse, StrategyType,
+    AutoCompoundSettings, CreateStrategyRequest, ListPendingExitsResponse, ListStrategiesResponse,
+    MessageResponse, PendingExitResponse, PlannedInstructionResponse, ReconcileShadowRequest,
+    ShadowDecisionResponse, ShadowReconciliationResponse, ShadowReportResponse,
+    ShadowSummaryResponse, StopLossSettings, StrategyParameters, StrategyPerformanceResponse,
+    StrategyResponse, StrategyType, TranscriptQuery, TranscriptResponse, TranscriptStepResponse,
 };
-use crate::state::{AlertUpdate, AppState, StrategyState};
+use crate::services::StrategyDocument;
+use crate::state::{AlertUpdate, AppState, StrategyState, StrategyUpdate};
+use crate::tenancy::TenantScope;
 use axum::{
-    Json,
-    extract::{Path, State},
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use clmm_lp_data::prelude::MAX_STRATEGY_NAME_LEN;
+use clmm_lp_execution::prelude::{
+    ActualOutcome, AuditKind, AuditQuery, AutoCompoundConfig, CronSchedule, DecisionConfig,
+    ExecutorConfig, PaperExecutor, PaperTradingConfig, ShadowReconciliation, StopLossConfig,
+    StrategyExecutor, TranscriptStep, reconcile_shadow, shadow_report,
 };
-use clmm_lp_execution::prelude::{DecisionConfig, ExecutorConfig, StrategyExecutor};
 use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::info;
 
+/// Parses the evaluation and auto-compound cron schedules of a strategy.
+///
+/// Expressions are evaluated in the strategy's `timezone` (UTC by default).
+pub(crate) fn strategy_schedules(
+    parameters: Option<&serde_json::Value>,
+) -> ApiResult<(Option<CronSchedule>, Option<CronSchedule>)> {
+    let Some(params) = parameters else {
+        return Ok((None, None));
+    };
+    let timezone = params
+        .get("timezone")
+        .and_then(|v| v.as_str())
+        .unwrap_or("UTC");
+
+    let parse = |expression: Option<&serde_json::Value>| {
+        expression
+            .and_then(|v| v.as_str())
+            .map(|expr| {
+                CronSchedule::parse_in(expr, timezone)
+                    .map_err(|e| ApiError::bad_request(format!("Invalid cron schedule: {}", e)))
+            })
+            .transpose()
+    };
+
+    Ok((
+        parse(params.get("eval_cron"))?,
+        parse(params.get("auto_compound").and_then(|a| a.get("cron")))?,
+    ))
+}
+
+/// Checks that a strategy name is present and fits the strategies table.
+fn validate_strategy_name(name: &str) -> ApiResult<()> {
+    if name.trim().is_empty() {
+        return Err(ApiError::bad_request("Strategy name must not be empty"));
+    }
+    if name.chars().count() > MAX_STRATEGY_NAME_LEN {
+        return Err(ApiError::bad_request(format!(
+            "Strategy name must be at most {} characters",
+            MAX_STRATEGY_NAME_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Maps a strategy to its API representation.
+pub(crate) fn strategy_response(strategy: &StrategyState) -> StrategyResponse {
+    let params: StrategyParameters = strategy
+        .config
+        .get("parameters")
+        .and_then(|p| serde_json::from_value(p.clone()).ok())
+        .unwrap_or(StrategyParameters {
+            tick_width: None,
+            rebalance_threshold_pct: None,
+            max_il_pct: None,
+            eval_interval_secs: None,
+            eval_cron: None,
+            timezone: None,
+            min_rebalance_interval_hours: None,
+            hysteresis_margin_pct: None,
+            confirm_evaluations: None,
+            rebalance_cooldown_secs: None,
+            auto_compound: None,
+            stop_loss: None,
+        });
+
+    StrategyResponse {
+        id: strategy.id.clone(),
+        name: strategy.name.clone(),
+        pool_address: strategy
+            .config
+            .get("pool_address")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string(),
+        strategy_type: strategy
+            .config
+            .get("strategy_type")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or(StrategyType::StaticRange),
+        parameters: params,
+        running: strategy.running,
+        dry_run: strategy
+            .config
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        paper_trading: strategy
+            .config
+            .get("paper_trading")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        shadow: strategy
+            .config
+            .get("shadow")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        tenant: strategy.owner.clone(),
+        created_at: strategy.created_at,
+        updated_at: strategy.updated_at,
+    }
+}
+
+/// Broadcasts a strategy update to WebSocket subscribers.
+fn broadcast_strategy(state: &AppState, id: &str, update_type: &str, data: serde_json::Value) {
+    state.broadcast_strategy_update(StrategyUpdate {
+        update_type: update_type.to_string(),
+        strategy_id: id.to_string(),
+        timestamp: chrono::Utc::now(),
+        data,
+    });
+}
+
 /// List all strategies.
+///
+/// Tenants only see their own strategies.
 #[utoipa::path(
     get,
     path = "/strategies",
@@ -27,46 +155,14 @@ use tracing::info;
 )]
 pub async fn list_strategies(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
 ) -> ApiResult<Json<ListStrategiesResponse>> {
     let strategies = state.strategies.read().await;
 
     let responses: Vec<StrategyResponse> = strategies
         .values()
-        .map(|s| {
-            let params: StrategyParameters =
-                serde_json::from_value(s.config.clone()).unwrap_or(StrategyParameters {
-                    tick_width: None,
-                    rebalance_threshold_pct: None,
-                    max_il_pct: None,
-                    eval_interval_secs: None,
-                    min_rebalance_interval_hours: None,
-                });
-
-            StrategyResponse {
-                id: s.id.clone(),
-                name: s.name.clone(),
-                pool_address: s
-                    .config
-                    .get("pool_address")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
-                strategy_type: s
-                    .config
-                    .get("strategy_type")
-                    .and_then(|v| serde_json::from_value(v.clone()).ok())
-                    .unwrap_or(StrategyType::StaticRange),
-                parameters: params,
-                running: s.running,
-                dry_run: s
-                    .config
-                    .get("dry_run")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false),
-                created_at: s.created_at,
-                updated_at: s.updated_at,
-            }
-        })
+        .filter(|s| scope.can_see_strategy(s))
+        .map(strategy_response)
         .collect();
 
     Ok(Json(ListStrategiesResponse {
@@ -97,44 +193,12 @@ pub async fn get_strategy(
         .get(&id)
         .ok_or_else(|| ApiError::not_found("Strategy not found"))?;
 
-    let params: StrategyParameters =
-        serde_json::from_value(strategy.config.clone()).unwrap_or(StrategyParameters {
-            tick_width: None,
-            rebalance_threshold_pct: None,
-            max_il_pct: None,
-            eval_interval_secs: None,
-            min_rebalance_interval_hours: None,
-        });
-
-    let response = StrategyResponse {
-        id: strategy.id.clone(),
-        name: strategy.name.clone(),
-        pool_address: strategy
-            .config
-            .get("pool_address")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
-        strategy_type: strategy
-            .config
-            .get("strategy_type")
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
-            .unwrap_or(StrategyType::StaticRange),
-        parameters: params,
-        running: strategy.running,
-        dry_run: strategy
-            .config
-            .get("dry_run")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false),
-        created_at: strategy.created_at,
-        updated_at: strategy.updated_at,
-    };
-
-    Ok(Json(response))
+    Ok(Json(strategy_response(strategy)))
 }
 
 /// Create a new strategy.
+///
+/// Strategies created by a tenant belong to that tenant.
 #[utoipa::path(
     post,
     path = "/strategies",
@@ -147,8 +211,10 @@ pub async fn get_strategy(
 )]
 pub async fn create_strategy(
     State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
     Json(request): Json<CreateStrategyRequest>,
 ) -> ApiResult<Json<StrategyResponse>> {
+    validate_strategy_name(&request.name)?;
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
 
@@ -158,13 +224,17 @@ pub async fn create_strategy(
         "parameters": request.parameters,
         "auto_execute": request.auto_execute,
         "dry_run": request.dry_run,
+        "paper_trading": request.paper_trading,
+        "shadow": request.shadow,
     });
+    strategy_schedules(config.get("parameters"))?;
 
     let strategy_state = StrategyState {
         id: id.clone(),
         name: request.name.clone(),
         running: false,
         config: config.clone(),
+        owner: scope.tenant_id().map(str::to_string),
         created_at: now,
         updated_at: now,
     };
@@ -174,6 +244,7 @@ pub async fn create_strategy(
         .write()
         .await
         .insert(id.clone(), strategy_state);
+    state.persist_strategy(&id).await;
 
     info!(id = %id, name = %request.name, "Strategy created");
 
@@ -185,9 +256,18 @@ pub async fn create_strategy(
         parameters: request.parameters,
         running: false,
         dry_run: request.dry_run,
+        paper_trading: request.paper_trading,
+        shadow: request.shadow,
+        tenant: scope.tenant_id().map(str::to_string),
         created_at: now,
         updated_at: now,
     };
+    broadcast_strategy(
+        &state,
+        &response.id,
+        "created",
+        serde_json::to_value(&response).unwrap_or_default(),
+    );
 
     Ok(Json(response))
 }
@@ -203,6 +283,7 @@ pub async fn create_strategy(
     request_body = CreateStrategyRequest,
     responses(
         (status = 200, description = "Strategy updated", body = StrategyResponse),
+        (status = 400, description = "Invalid request"),
         (status = 404, description = "Strategy not found")
     )
 )]
@@ -211,6 +292,7 @@ pub async fn update_strategy(
     Path(id): Path<String>,
     Json(request): Json<CreateStrategyRequest>,
 ) -> ApiResult<Json<StrategyResponse>> {
+    validate_strategy_name(&request.name)?;
     let mut strategies = state.strategies.write().await;
     let strategy = strategies
         .get_mut(&id)
@@ -224,7 +306,10 @@ pub async fn update_strategy(
         "parameters": request.parameters,
         "auto_execute": request.auto_execute,
         "dry_run": request.dry_run,
+        "paper_trading": request.paper_trading,
+        "shadow": request.shadow,
     });
+    strategy_schedules(config.get("parameters"))?;
 
     strategy.name = request.name.clone();
     strategy.config = config;
@@ -240,9 +325,20 @@ pub async fn update_strategy(
         parameters: request.parameters,
         running: strategy.running,
         dry_run: request.dry_run,
+        paper_trading: request.paper_trading,
+        shadow: request.shadow,
+        tenant: strategy.owner.clone(),
         created_at: strategy.created_at,
         updated_at: now,
     };
+    drop(strategies);
+    state.persist_strategy(&response.id).await;
+    broadcast_strategy(
+        &state,
+        &response.id,
+        "updated",
+        serde_json::to_value(&response).unwrap_or_default(),
+    );
 
     Ok(Json(response))
 }
@@ -270,70 +366,147 @@ pub async fn delete_strategy(
         return Err(ApiError::not_found("Strategy not found"));
     }
 
+    drop(strategies);
+    state.forget_strategy(&id).await;
+
     info!(id = %id, "Strategy deleted");
+    broadcast_strategy(&state, &id, "deleted", serde_json::Value::Null);
 
     Ok(Json(MessageResponse::new("Strategy deleted")))
 }
 
-/// Start a strategy.
+/// Export a strategy as a versioned YAML document.
+///
+/// The document can be committed to git and imported into another
+/// deployment with `POST /strategies/import`.
 #[utoipa::path(
-    post,
-    path = "/strategies/{id}/start",
+    get,
+    path = "/strategies/{id}/export",
     tag = "Strategies",
     params(
         ("id" = String, Path, description = "Strategy ID")
     ),
     responses(
-        (status = 200, description = "Strategy started", body = MessageResponse),
+        (status = 200, description = "Strategy document", body = String, content_type = "application/yaml"),
         (status = 404, description = "Strategy not found")
     )
 )]
-pub async fn start_strategy(
+pub async fn export_strategy(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> ApiResult<Json<MessageResponse>> {
-    // Get strategy configuration
-    let strategy_config = {
-        let mut strategies = state.strategies.write().await;
-        let strategy = strategies
-            .get_mut(&id)
-            .ok_or_else(|| ApiError::not_found("Strategy not found"))?;
+) -> ApiResult<Response> {
+    let strategies = state.strategies.read().await;
+    let strategy = strategies
+        .get(&id)
+        .ok_or_else(|| ApiError::not_found("Strategy not found"))?;
 
-        if strategy.running {
-            return Err(ApiError::Conflict(
-                "Strategy is already running".to_string(),
-            ));
-        }
+    let response = strategy_response(strategy);
+    let document = StrategyDocument::new(CreateStrategyRequest {
+        name: response.name,
+        pool_address: response.pool_address,
+        strategy_type: response.strategy_type,
+        parameters: response.parameters,
+        auto_execute: strategy
+            .config
+            .get("auto_execute")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        dry_run: response.dry_run,
+        paper_trading: response.paper_trading,
+        shadow: response.shadow,
+    });
+    let yaml = document
+        .to_yaml()
+        .map_err(|e| ApiError::internal(e.to_string()))?;
 
-        strategy.running = true;
-        strategy.updated_at = chrono::Utc::now();
-        strategy.config.clone()
-    };
+    let disposition = format!("attachment; filename=\"strategy-{}.yaml\"", id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/yaml".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        yaml,
+    )
+        .into_response())
+}
+
+/// Import a strategy from a YAML document.
+///
+/// The strategy is created stopped, with a new ID, like `POST /strategies`.
+#[utoipa::path(
+    post,
+    path = "/strategies/import",
+    tag = "Strategies",
+    request_body(content = String, content_type = "application/yaml", description = "Strategy document"),
+    responses(
+        (status = 200, description = "Strategy created", body = StrategyResponse),
+        (status = 400, description = "Invalid or unsupported document")
+    )
+)]
+pub async fn import_strategy(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TenantScope>,
+    body: String,
+) -> ApiResult<Json<StrategyResponse>> {
+    let document =
+        StrategyDocument::from_yaml(&body).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    info!(
+        name = %document.strategy.name,
+        version = document.version,
+        "Importing strategy"
+    );
+
+    create_strategy(State(state), Extension(scope), Json(document.strategy)).await
+}
+
+/// Creates and starts the executor of a strategy.
+///
+/// Returns the strategy's `(dry_run, auto_execute, paper_trading, shadow)` flags.
+pub(crate) async fn launch_executor(
+    state: &AppState,
+    id: &str,
+    strategy_config: &serde_json::Value,
+) -> ApiResult<(bool, bool, bool, bool)> {
+    let defaults = &state.config.executor;
 
     // Parse configuration
     let dry_run = strategy_config
         .get("dry_run")
         .and_then(|v| v.as_bool())
-        .unwrap_or(true);
+        .unwrap_or(defaults.dry_run);
 
     let auto_execute = strategy_config
         .get("auto_execute")
         .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.auto_execute);
+
+    let paper_trading = strategy_config
+        .get("paper_trading")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let shadow = strategy_config
+        .get("shadow")
+        .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
     let eval_interval_secs = strategy_config
         .get("parameters")
         .and_then(|p| p.get("eval_interval_secs"))
         .and_then(|v| v.as_u64())
-        .unwrap_or(300);
+        .unwrap_or(defaults.eval_interval_secs);
+
+    let (eval_cron, compound_cron) = strategy_schedules(strategy_config.get("parameters"))?;
 
     // Create executor configuration
     let executor_config = ExecutorConfig {
         eval_interval_secs,
+        eval_cron,
         auto_execute,
         require_confirmation: !auto_execute,
-        max_slippage_pct: Decimal::new(5, 3), // 0.5%
+        max_slippage_pct: defaults.max_slippage_pct,
         dry_run,
+        shadow,
     };
 
     // Create strategy executor
@@ -344,6 +517,70 @@ pub async fn start_strategy(
         executor_config,
     );
 
+    // Share the global circuit breaker so the kill switch halts every executor
+    executor.set_circuit_breaker(state.circuit_breaker.clone());
+
+    // Record history in the shared (and possibly persisted) lifecycle tracker
+    executor.set_lifecycle(state.lifecycle.clone());
+
+    // Abort rebalances into pools priced away from the oracle
+    if let Some(guard) = &state.price_guard {
+        executor.set_price_guard(guard.clone());
+    }
+
+    // Record every evaluation and outcome in the audit log
+    executor.set_audit_log(state.audit.clone(), Some(id.to_string()));
+
+    // Sign with the wallet bound to this strategy, if any
+    let owner = state
+        .strategies
+        .read()
+        .await
+        .get(id)
+        .and_then(|strategy| strategy.owner.clone());
+    if let Some(wallet) = state
+        .wallets
+        .wallet_for_strategy(id, owner.as_deref())
+        .await
+    {
+        info!(strategy_id = %id, wallet = %wallet.label(), "Using bound wallet");
+        executor.set_wallet(wallet);
+    }
+
+    // Fill decisions against live quotes without sending transactions
+    if paper_trading {
+        let paper_config = PaperTradingConfig {
+            ledger_path: Some(state.config.paper_ledger_dir.join(format!("{}.json", id))),
+            ..Default::default()
+        };
+        let paper = PaperExecutor::new(
+            state.provider.clone(),
+            executor.lifecycle().clone(),
+            paper_config,
+        )
+        .map_err(|e| ApiError::internal(format!("Failed to load paper ledger: {}", e)))?;
+        executor.set_backend(Arc::new(paper));
+    }
+
+    // Schedule fee compounding if enabled for this strategy
+    if let Some(settings) = strategy_config
+        .get("parameters")
+        .and_then(|p| p.get("auto_compound"))
+        .and_then(|v| serde_json::from_value::<AutoCompoundSettings>(v.clone()).ok())
+        && settings.enabled
+    {
+        let defaults = AutoCompoundConfig::default();
+        executor.set_auto_compound(AutoCompoundConfig {
+            interval_secs: settings.interval_secs.unwrap_or(defaults.interval_secs),
+            cron: compound_cron,
+            min_fees_usd: settings.min_fees_usd.unwrap_or(defaults.min_fees_usd),
+            min_profit_multiplier: settings
+                .min_profit_multiplier
+                .unwrap_or(defaults.min_profit_multiplier),
+            tx_cost_usd: settings.tx_cost_usd.unwrap_or(defaults.tx_cost_usd),
+        });
+    }
+
     // Configure decision engine if parameters provided
     if let Some(params) = strategy_config.get("parameters") {
         let mut decision_config = DecisionConfig::default();
@@ -368,20 +605,69 @@ pub async fn start_strategy(
             decision_config.min_rebalance_interval_hours = val;
         }
 
+        if let Some(margin) = params
+            .get("hysteresis_margin_pct")
+            .and_then(|v| serde_json::from_value::<Decimal>(v.clone()).ok())
+        {
+            decision_config.hysteresis_margin_pct = margin / Decimal::ONE_HUNDRED;
+        }
+
+        if let Some(evaluations) = params.get("confirm_evaluations")
+            && let Some(val) = evaluations.as_u64()
+        {
+            decision_config.confirm_evaluations = val.max(1) as u32;
+        }
+
+        if let Some(cooldown) = params.get("rebalance_cooldown_secs")
+            && let Some(val) = cooldown.as_u64()
+        {
+            decision_config.rebalance_cooldown_secs = val;
+        }
+
+        if let Some(settings) = params
+            .get("stop_loss")
+            .and_then(|v| serde_json::from_value::<StopLossSettings>(v.clone()).ok())
+            && settings.enabled
+        {
+            let hundred = Decimal::ONE_HUNDRED;
+            let defaults = StopLossConfig::default();
+            decision_config.stop_loss = Some(StopLossConfig {
+                max_il_pct: settings
+                    .max_il_pct
+                    .map(|v| v / hundred)
+                    .or(defaults.max_il_pct),
+                max_drawdown_pct: settings
+                    .max_drawdown_pct
+                    .map(|v| v / hundred)
+                    .or(defaults.max_drawdown_pct),
+                min_price: settings.min_price,
+                max_price: settings.max_price,
+                swap_to_quote: settings.swap_to_quote,
+                require_confirmation: settings.require_confirmation,
+                escalate_after_secs: settings
+                    .escalate_after_secs
+                    .unwrap_or(defaults.escalate_after_secs),
+            });
+        }
+
         executor.set_decision_config(decision_config);
     }
 
+    // Forward stop-loss alerts to subscribers and notification channels
+    let alert_state = state.clone();
+    executor.set_alert_callback(move |alert| alert_state.raise_alert(alert));
+
     let executor = Arc::new(RwLock::new(executor));
 
     // Store executor
     {
         let mut executors = state.executors.write().await;
-        executors.insert(id.clone(), executor.clone());
+        executors.insert(id.to_string(), executor.clone());
     }
 
     // Start executor in background task
     let executor_clone = executor.clone();
-    let id_clone = id.clone();
+    let id_clone = id.to_string();
     let alert_sender = state.alert_updates.clone();
 
     tokio::spawn(async move {
@@ -399,6 +685,56 @@ pub async fn start_strategy(
         });
     });
 
+    Ok((dry_run, auto_execute, paper_trading, shadow))
+}
+
+/// Start a strategy.
+#[utoipa::path(
+    post,
+    path = "/strategies/{id}/start",
+    tag = "Strategies",
+    params(
+        ("id" = String, Path, description = "Strategy ID")
+    ),
+    responses(
+        (status = 200, description = "Strategy started", body = MessageResponse),
+        (status = 404, description = "Strategy not found")
+    )
+)]
+pub async fn start_strategy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    // Get strategy configuration
+    let strategy_config = {
+        let mut strategies = state.strategies.write().await;
+        let strategy = strategies
+            .get_mut(&id)
+            .ok_or_else(|| ApiError::not_found("Strategy not found"))?;
+
+        if strategy.running {
+            return Err(ApiError::Conflict(
+                "Strategy is already running".to_string(),
+            ));
+        }
+
+        strategy.running = true;
+        strategy.updated_at = chrono::Utc::now();
+        strategy.config.clone()
+    };
+
+    let (dry_run, auto_execute, paper_trading, shadow) =
+        match launch_executor(&state, &id, &strategy_config).await {
+            Ok(flags) => flags,
+            Err(e) => {
+                if let Some(strategy) = state.strategies.write().await.get_mut(&id) {
+                    strategy.running = false;
+                }
+                return Err(e);
+            }
+        };
+    state.persist_strategy(&id).await;
+
     // Broadcast alert
     state.broadcast_alert(AlertUpdate {
         level: "info".to_string(),
@@ -407,11 +743,30 @@ pub async fn start_strategy(
         position_address: None,
     });
 
-    info!(id = %id, dry_run = dry_run, auto_execute = auto_execute, "Strategy started");
+    broadcast_strategy(
+        &state,
+        &id,
+        "started",
+        serde_json::json!({
+            "dry_run": dry_run,
+            "auto_execute": auto_execute,
+            "paper_trading": paper_trading,
+            "shadow": shadow
+        }),
+    );
+
+    info!(
+        id = %id,
+        dry_run = dry_run,
+        auto_execute = auto_execute,
+        paper_trading = paper_trading,
+        shadow = shadow,
+        "Strategy started"
+    );
 
     Ok(Json(MessageResponse::new(format!(
-        "Strategy started (dry_run={}, auto_execute={})",
-        dry_run, auto_execute
+        "Strategy started (dry_run={}, auto_execute={}, paper_trading={}, shadow={})",
+        dry_run, auto_execute, paper_trading, shadow
     ))))
 }
 
@@ -471,6 +826,9 @@ pub async fn stop_strategy(
         position_address: None,
     });
 
+    state.persist_strategy(&id).await;
+    broadcast_strategy(&state, &id, "stopped", serde_json::Value::Null);
+
     info!(id = %id, "Strategy stopped");
 
     Ok(Json(MessageResponse::new("Strategy stopped")))
@@ -509,8 +867,351 @@ pub async fn get_strategy_performance(
         total_il_pct: Decimal::ZERO, // Would need to track per strategy
         rebalance_count: stats.total_rebalances,
         total_tx_costs_lamports: stats.total_tx_costs_lamports,
+        total_funding_usd: stats.total_funding_usd,
         win_rate_pct: Decimal::ZERO, // Would need to track per strategy
     };
 
     Ok(Json(response))
 }
+
+/// Maximum number of transcript steps returned per request.
+const MAX_TRANSCRIPT_STEPS: usize = 1000;
+
+/// Get a strategy's dry-run transcript.
+///
+/// Lists every action the strategy planned while running in dry run mode,
+/// with the instructions it would have sent, expected amounts and estimated
+/// costs. Set `download=true` to receive it as a JSON file.
+#[utoipa::path(
+    get,
+    path = "/strategies/{id}/transcript",
+    tag = "Strategies",
+    params(
+        ("id" = String, Path, description = "Strategy ID"),
+        TranscriptQuery
+    ),
+    responses(
+        (status = 200, description = "Dry-run transcript, newest first", body = TranscriptResponse),
+        (status = 404, description = "Strategy not found")
+    )
+)]
+pub async fn get_strategy_transcript(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<TranscriptQuery>,
+) -> ApiResult<Response> {
+    if !state.strategies.read().await.contains_key(&id) {
+        return Err(ApiError::not_found("Strategy not found"));
+    }
+
+    let entries = state
+        .audit
+        .query(&AuditQuery {
+            strategy_id: Some(id.clone()),
+            kind: Some(AuditKind::DryRun),
+            since: query.since,
+            until: query.until,
+            limit: query.limit.min(MAX_TRANSCRIPT_STEPS),
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to query transcript: {}", e)))?;
+
+    let steps: Vec<TranscriptStepResponse> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let step: TranscriptStep = serde_json::from_value(entry.details).ok()?;
+            Some(TranscriptStepResponse {
+                id: entry.id,
+                timestamp: entry.timestamp,
+                position_address: entry.position,
+                action: step.action,
+                description: step.description,
+                pool_tick: step.pool_tick,
+                instructions: step
+                    .instructions
+                    .into_iter()
+                    .map(|i| PlannedInstructionResponse {
+                        program: i.program,
+                        instruction: i.instruction,
+                        args: i.args,
+                    })
+                    .collect(),
+                expected_amount_a: step.expected_amount_a,
+                expected_amount_b: step.expected_amount_b,
+                position_value_usd: step.position_value_usd,
+                estimated_cost_lamports: step.estimated_cost_lamports,
+            })
+        })
+        .collect();
+
+    let response = TranscriptResponse {
+        strategy_id: id.clone(),
+        total: steps.len(),
+        total_estimated_cost_lamports: steps.iter().map(|s| s.estimated_cost_lamports).sum(),
+        steps,
+    };
+
+    if query.download {
+        let disposition = format!("attachment; filename=\"transcript-{}.json\"", id);
+        return Ok(([(header::CONTENT_DISPOSITION, disposition)], Json(response)).into_response());
+    }
+    Ok(Json(response).into_response())
+}
+
+/// Get a strategy's shadow-mode decisions.
+///
+/// Lists every decision the strategy recorded while running in shadow mode,
+/// with its simulated outcome and, once reconciled, the actual outcome.
+/// The summary quantifies the model's slippage over reconciled decisions.
+#[utoipa::path(
+    get,
+    path = "/strategies/{id}/shadow",
+    tag = "Strategies",
+    params(
+        ("id" = String, Path, description = "Strategy ID")
+    ),
+    responses(
+        (status = 200, description = "Shadow decisions, newest first", body = ShadowReportResponse),
+        (status = 404, description = "Strategy not found")
+    )
+)]
+pub async fn get_strategy_shadow(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ShadowReportResponse>> {
+    if !state.strategies.read().await.contains_key(&id) {
+        return Err(ApiError::not_found("Strategy not found"));
+    }
+
+    let report = shadow_report(state.audit.as_ref(), Some(&id))
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to query shadow decisions: {}", e)))?;
+
+    let decisions = report
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let planned = entry.record.planned;
+            ShadowDecisionResponse {
+                id: entry.id,
+                timestamp: entry.timestamp,
+                position_address: entry.position,
+                action: planned.action,
+                description: planned.description,
+                pool_price: entry.record.pool_price,
+                expected_amount_a: planned.expected_amount_a,
+                expected_amount_b: planned.expected_amount_b,
+                expected_value_usd: planned.position_value_usd,
+                estimated_cost_lamports: planned.estimated_cost_lamports,
+                reconciliation: entry.reconciliation.map(reconciliation_response),
+            }
+        })
+        .collect();
+
+    let summary = report.summary;
+    Ok(Json(ShadowReportResponse {
+        strategy_id: id,
+        decisions,
+        summary: ShadowSummaryResponse {
+            decisions: summary.decisions,
+            reconciled: summary.reconciled,
+            mean_abs_value_slippage_pct: summary.mean_abs_value_slippage_pct,
+            max_abs_value_slippage_pct: summary.max_abs_value_slippage_pct,
+            total_value_slippage_usd: summary.total_value_slippage_usd,
+            cost_difference_lamports: summary.cost_difference_lamports,
+        },
+    }))
+}
+
+/// Reconcile a shadow decision with its actual outcome.
+///
+/// Records what happened on-chain after an operator carried out (or skipped)
+/// a decision recorded in shadow mode.
+#[utoipa::path(
+    post,
+    path = "/strategies/{id}/shadow/{record_id}/reconcile",
+    tag = "Strategies",
+    params(
+        ("id" = String, Path, description = "Strategy ID"),
+        ("record_id" = String, Path, description = "Shadow record ID")
+    ),
+    request_body = ReconcileShadowRequest,
+    responses(
+        (status = 200, description = "Decision reconciled", body = ShadowReconciliationResponse),
+        (status = 404, description = "Strategy or shadow record not found")
+    )
+)]
+pub async fn reconcile_shadow_decision(
+    State(state): State<AppState>,
+    Path((id, record_id)): Path<(String, String)>,
+    Json(request): Json<ReconcileShadowRequest>,
+) -> ApiResult<Json<ShadowReconciliationResponse>> {
+    if !state.strategies.read().await.contains_key(&id) {
+        return Err(ApiError::not_found("Strategy not found"));
+    }
+
+    let outcome = ActualOutcome {
+        signature: request.signature,
+        amount_a: request.amount_a,
+        amount_b: request.amount_b,
+        value_usd: request.value_usd,
+        cost_lamports: request.cost_lamports,
+    };
+    let reconciliation = reconcile_shadow(state.audit.as_ref(), Some(&id), &record_id, &outcome)
+        .await
+        .map_err(ApiError::from)?;
+
+    info!(
+        id = %id,
+        record_id = %record_id,
+        value_slippage_pct = %reconciliation.value_slippage_pct,
+        "Shadow decision reconciled"
+    );
+
+    Ok(Json(reconciliation_response(reconciliation)))
+}
+
+/// Converts a shadow reconciliation to its response model.
+fn reconciliation_response(reconciliation: ShadowReconciliation) -> ShadowReconciliationResponse {
+    ShadowReconciliationResponse {
+        actual_amount_a: reconciliation.actual_amount_a,
+        actual_amount_b: reconciliation.actual_amount_b,
+        slippage_a_pct: reconciliation.slippage_a_pct,
+        slippage_b_pct: reconciliation.slippage_b_pct,
+        actual_value_usd: reconciliation.actual_value_usd,
+        value_slippage_usd: reconciliation.value_slippage_usd,
+        value_slippage_pct: reconciliation.value_slippage_pct,
+        actual_cost_lamports: reconciliation.actual_cost_lamports,
+        signature: reconciliation.signature,
+    }
+}
+
+/// Looks up the executor of a running strategy.
+async fn running_executor(state: &AppState, id: &str) -> ApiResult<Arc<RwLock<StrategyExecutor>>> {
+    if !state.strategies.read().await.contains_key(id) {
+        return Err(ApiError::not_found("Strategy not found"));
+    }
+
+    state
+        .executors
+        .read()
+        .await
+        .get(id)
+        .cloned()
+        .ok_or_else(|| ApiError::Conflict("Strategy is not running".to_string()))
+}
+
+/// List stop-loss exits awaiting confirmation.
+#[utoipa::path(
+    get,
+    path = "/strategies/{id}/exits",
+    tag = "Strategies",
+    params(
+        ("id" = String, Path, description = "Strategy ID")
+    ),
+    responses(
+        (status = 200, description = "Pending exits", body = ListPendingExitsResponse),
+        (status = 404, description = "Strategy not found"),
+        (status = 409, description = "Strategy is not running")
+    )
+)]
+pub async fn list_pending_exits(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ListPendingExitsResponse>> {
+    let executor = running_executor(&state, &id).await?;
+    let pending = executor.read().await.pending_exits().await;
+
+    let exits: Vec<PendingExitResponse> = pending
+        .into_iter()
+        .map(|exit| PendingExitResponse {
+            position_address: exit.position.to_string(),
+            trigger: exit.trigger.description(),
+            swap_to_quote: exit.swap_to_quote,
+            detected_at: exit.detected_at,
+            escalated: exit.escalated,
+        })
+        .collect();
+
+    let total = exits.len();
+    Ok(Json(ListPendingExitsResponse { exits, total }))
+}
+
+/// Confirm a pending stop-loss exit.
+///
+/// Closes the position (and swaps to quote if configured) immediately.
+#[utoipa::path(
+    post,
+    path = "/strategies/{id}/exits/{position}/confirm",
+    tag = "Strategies",
+    params(
+        ("id" = String, Path, description = "Strategy ID"),
+        ("position" = String, Path, description = "Position address")
+    ),
+    responses(
+        (status = 200, description = "Exit executed", body = MessageResponse),
+        (status = 400, description = "Invalid position address or no pending exit"),
+        (status = 404, description = "Strategy not found"),
+        (status = 409, description = "Strategy is not running")
+    )
+)]
+pub async fn confirm_exit(
+    State(state): State<AppState>,
+    Path((id, position)): Path<(String, String)>,
+) -> ApiResult<Json<MessageResponse>> {
+    let pubkey = Pubkey::from_str(&position)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    let executor = running_executor(&state, &id).await?;
+
+    executor
+        .read()
+        .await
+        .confirm_exit(&pubkey)
+        .await
+        .map_err(|e| ApiError::bad_request(format!("Exit failed: {}", e)))?;
+
+    state.broadcast_alert(AlertUpdate {
+        level: "critical".to_string(),
+        message: format!("Stop-loss exit confirmed for {}", position),
+        timestamp: chrono::Utc::now(),
+        position_address: Some(position.clone()),
+    });
+
+    info!(id = %id, position = %position, "Stop-loss exit confirmed");
+
+    Ok(Json(MessageResponse::new("Exit executed")))
+}
+
+/// Cancel a pending stop-loss exit.
+#[utoipa::path(
+    delete,
+    path = "/strategies/{id}/exits/{position}",
+    tag = "Strategies",
+    params(
+        ("id" = String, Path, description = "Strategy ID"),
+        ("position" = String, Path, description = "Position address")
+    ),
+    responses(
+        (status = 200, description = "Exit cancelled", body = MessageResponse),
+        (status = 400, description = "Invalid position address"),
+        (status = 404, description = "Strategy or pending exit not found"),
+        (status = 409, description = "Strategy is not running")
+    )
+)]
+pub async fn cancel_exit(
+    State(state): State<AppState>,
+    Path((id, position)): Path<(String, String)>,
+) -> ApiResult<Json<MessageResponse>> {
+    let pubkey = Pubkey::from_str(&position)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    let executor = running_executor(&state, &id).await?;
+
+    if !executor.read().await.cancel_exit(&pubkey).await {
+        return Err(ApiError::not_found("No pending exit for position"));
+    }
+
+    info!(id = %id, position = %position, "Stop-loss exit cancelled");
+
+    Ok(Json(MessageResponse::new("Exit cancelled")))
+}
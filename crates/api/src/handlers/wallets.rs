@@ -0,0 +1,287 @@
+//! Wallet handlers.
+//!
+//! Admin-only endpoints to register, inspect and bind signing wallets:
+//! - Wallets load from encrypted keystores or delegate to remote signers
+//! - Passphrases and signer tokens are never returned
+//! - A strategy's executor uses its bound wallet from its next start
+//! - Wallets of a tenant own that tenant's positions and only sign for its
+//!   strategies
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    BindWalletRequest, KeystoreSource, ListWalletsResponse, MessageResponse, RegisterWalletRequest,
+    RemoteSignerSource, WalletBalanceResponse, WalletKind, WalletResponse,
+};
+use crate::services::{RegisteredWallet, WalletRegistry, is_valid_webhook_url};
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use clmm_lp_execution::prelude::{RemoteSigner, Wallet};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use zeroize::Zeroizing;
+
+/// Lamports per SOL.
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Maps a registered wallet to its API representation.
+async fn wallet_response(registry: &WalletRegistry, wallet: &RegisteredWallet) -> WalletResponse {
+    WalletResponse {
+        label: wallet.label.clone(),
+        pubkey: wallet.wallet.pubkey().to_string(),
+        kind: wallet.kind,
+        source: wallet.source.clone(),
+        tenant: wallet.tenant.clone(),
+        strategies: registry.strategies(&wallet.label).await,
+        created_at: wallet.created_at,
+    }
+}
+
+/// Loads a wallet from an encrypted keystore.
+async fn load_keystore(label: String, source: KeystoreSource) -> ApiResult<Wallet> {
+    let passphrase = match (source.passphrase, source.passphrase_env) {
+        (Some(passphrase), _) => passphrase,
+        (None, Some(var)) => std::env::var(&var)
+            .map_err(|_| ApiError::bad_request(format!("Environment variable {} not set", var)))?,
+        (None, None) => {
+            return Err(ApiError::bad_request(
+                "Keystore requires passphrase or passphrase_env",
+            ));
+        }
+    };
+    let passphrase = Zeroizing::new(passphrase);
+
+    // Key derivation is deliberately slow
+    tokio::task::spawn_blocking(move || {
+        Wallet::from_encrypted_file(&source.path, &passphrase, label)
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Keystore task failed: {}", e)))?
+    .map_err(|e| ApiError::bad_request(format!("Failed to load keystore: {:#}", e)))
+}
+
+/// Creates a wallet delegating to a remote signer.
+fn remote_wallet(label: String, source: RemoteSignerSource) -> ApiResult<Wallet> {
+    if !is_valid_webhook_url(&source.url) {
+        return Err(ApiError::bad_request(
+            "Signer URL must be an absolute http or https URL",
+        ));
+    }
+    let pubkey: Pubkey = source
+        .pubkey
+        .parse()
+        .map_err(|_| ApiError::bad_request("Invalid signer public key"))?;
+
+    let mut signer = RemoteSigner::new(source.url, pubkey);
+    if let Some(token) = source.auth_token {
+        signer = signer.with_auth_token(token);
+    }
+    Ok(Wallet::from_remote_signer(signer, label))
+}
+
+/// List wallets.
+#[utoipa::path(
+    get,
+    path = "/wallets",
+    tag = "Wallets",
+    responses(
+        (status = 200, description = "Wallets", body = ListWalletsResponse),
+        (status = 403, description = "Admin role required")
+    )
+)]
+pub async fn list_wallets(State(state): State<AppState>) -> ApiResult<Json<ListWalletsResponse>> {
+    let mut wallets = Vec::new();
+    for wallet in state.wallets.list().await {
+        wallets.push(wallet_response(&state.wallets, &wallet).await);
+    }
+    let total = wallets.len();
+
+    Ok(Json(ListWalletsResponse { wallets, total }))
+}
+
+/// Register a wallet.
+///
+/// Loads an encrypted keystore or registers a remote signer.
+#[utoipa::path(
+    post,
+    path = "/wallets",
+    tag = "Wallets",
+    request_body = RegisterWalletRequest,
+    responses(
+        (status = 200, description = "Wallet registered", body = WalletResponse),
+        (status = 400, description = "Invalid request or keystore"),
+        (status = 403, description = "Admin role required"),
+        (status = 409, description = "Label already registered")
+    )
+)]
+pub async fn register_wallet(
+    State(state): State<AppState>,
+    Json(request): Json<RegisterWalletRequest>,
+) -> ApiResult<Json<WalletResponse>> {
+    if request.label.trim().is_empty() {
+        return Err(ApiError::bad_request("Wallet label is required"));
+    }
+    if state.wallets.get(&request.label).await.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Wallet {} is already registered",
+            request.label
+        )));
+    }
+
+    let (wallet, kind, source) = match (request.keystore, request.remote_signer) {
+        (Some(keystore), None) => {
+            let path = keystore.path.clone();
+            let wallet = load_keystore(request.label, keystore).await?;
+            (wallet, WalletKind::Keystore, path)
+        }
+        (None, Some(remote)) => {
+            let url = remote.url.clone();
+            let wallet = remote_wallet(request.label, remote)?;
+            (wallet, WalletKind::RemoteSigner, url)
+        }
+        _ => {
+            return Err(ApiError::bad_request(
+                "Exactly one of keystore and remote_signer is required",
+            ));
+        }
+    };
+
+    let label = wallet.label().to_string();
+    let registered = state
+        .wallets
+        .register_for_tenant(wallet, kind, source, request.tenant)
+        .await
+        .ok_or_else(|| ApiError::Conflict(format!("Wallet {} is already registered", label)))?;
+
+    Ok(Json(wallet_response(&state.wallets, &registered).await))
+}
+
+/// Remove a wallet.
+///
+/// Strategies bound to the wallet are unbound.
+#[utoipa::path(
+    delete,
+    path = "/wallets/{label}",
+    tag = "Wallets",
+    params(
+        ("label" = String, Path, description = "Wallet label")
+    ),
+    responses(
+        (status = 200, description = "Wallet removed", body = MessageResponse),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn delete_wallet(
+    State(state): State<AppState>,
+    Path(label): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    let bound = state.wallets.strategies(&label).await;
+    if !state.wallets.delete(&label).await {
+        return Err(ApiError::not_found("Wallet not found"));
+    }
+    for strategy_id in bound {
+        state.persist_strategy(&strategy_id).await;
+    }
+
+    Ok(Json(MessageResponse::new(format!(
+        "Wallet {} removed",
+        label
+    ))))
+}
+
+/// Get a wallet's SOL balance.
+#[utoipa::path(
+    get,
+    path = "/wallets/{label}/balance",
+    tag = "Wallets",
+    params(
+        ("label" = String, Path, description = "Wallet label")
+    ),
+    responses(
+        (status = 200, description = "Wallet balance", body = WalletBalanceResponse),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Wallet not found"),
+        (status = 503, description = "RPC unavailable")
+    )
+)]
+pub async fn get_wallet_balance(
+    State(state): State<AppState>,
+    Path(label): Path<String>,
+) -> ApiResult<Json<WalletBalanceResponse>> {
+    let wallet = state
+        .wallets
+        .get(&label)
+        .await
+        .ok_or_else(|| ApiError::not_found("Wallet not found"))?;
+    let pubkey = wallet.wallet.pubkey();
+
+    let lamports = state
+        .provider
+        .get_balance(&pubkey)
+        .await
+        .map_err(|e| ApiError::ServiceUnavailable(format!("Failed to fetch balance: {}", e)))?;
+
+    Ok(Json(WalletBalanceResponse {
+        label,
+        pubkey: pubkey.to_string(),
+        lamports,
+        sol: Decimal::from(lamports) / Decimal::from(LAMPORTS_PER_SOL),
+    }))
+}
+
+/// Bind a wallet to a strategy.
+///
+/// The strategy signs with the wallet from its next start. The wallet and
+/// strategy must belong to the same tenant.
+#[utoipa::path(
+    post,
+    path = "/wallets/{label}/bind",
+    tag = "Wallets",
+    params(
+        ("label" = String, Path, description = "Wallet label")
+    ),
+    request_body = BindWalletRequest,
+    responses(
+        (status = 200, description = "Wallet bound", body = WalletResponse),
+        (status = 400, description = "Wallet and strategy belong to different tenants"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Wallet or strategy not found")
+    )
+)]
+pub async fn bind_wallet(
+    State(state): State<AppState>,
+    Path(label): Path<String>,
+    Json(request): Json<BindWalletRequest>,
+) -> ApiResult<Json<WalletResponse>> {
+    let owner = state
+        .strategies
+        .read()
+        .await
+        .get(&request.strategy_id)
+        .map(|strategy| strategy.owner.clone())
+        .ok_or_else(|| ApiError::not_found("Strategy not found"))?;
+    let wallet = state
+        .wallets
+        .get(&label)
+        .await
+        .ok_or_else(|| ApiError::not_found("Wallet not found"))?;
+    if wallet.tenant != owner {
+        return Err(ApiError::bad_request(format!(
+            "Wallet {} and strategy {} belong to different tenants",
+            label, request.strategy_id
+        )));
+    }
+    if !state
+        .wallets
+        .bind(&label, request.strategy_id.clone())
+        .await
+    {
+        return Err(ApiError::not_found("Wallet not found"));
+    }
+    state.persist_strategy(&request.strategy_id).await;
+
+    Ok(Json(wallet_response(&state.wallets, &wallet).await))
+}
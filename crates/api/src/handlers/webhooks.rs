@@ -0,0 +1,141 @@
+//! Webhook subscription handlers.
+//!
+//! Admin-only endpoints to register, list and delete webhooks:
+//! - Webhooks receive position updates, executed rebalances, strategy
+//!   updates and alerts
+//! - Deliveries are signed with the webhook's secret and retried
+//! - Secrets are only returned when a webhook is registered
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    CreateWebhookRequest, CreateWebhookResponse, ListWebhooksResponse, MessageResponse,
+    WebhookResponse,
+};
+use crate::services::{WebhookSubscription, is_valid_webhook_url};
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+/// Minimum length of a client-supplied signing secret.
+const MIN_SECRET_LEN: usize = 16;
+
+/// Maps a subscription to its API representation.
+fn webhook_response(subscription: &WebhookSubscription) -> WebhookResponse {
+    WebhookResponse {
+        id: subscription.id.clone(),
+        url: subscription.url.clone(),
+        events: subscription.events.clone(),
+        description: subscription.description.clone(),
+        created_at: subscription.created_at,
+        deliveries: subscription.deliveries(),
+        failures: subscription.failures(),
+    }
+}
+
+/// List webhooks.
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "Webhooks",
+    responses(
+        (status = 200, description = "Webhooks", body = ListWebhooksResponse),
+        (status = 403, description = "Admin role required")
+    )
+)]
+pub async fn list_webhooks(State(state): State<AppState>) -> ApiResult<Json<ListWebhooksResponse>> {
+    let webhooks: Vec<WebhookResponse> = state
+        .webhooks
+        .list()
+        .await
+        .iter()
+        .map(|s| webhook_response(s))
+        .collect();
+    let total = webhooks.len();
+
+    Ok(Json(ListWebhooksResponse { webhooks, total }))
+}
+
+/// Register a webhook.
+///
+/// The signing secret is only returned in this response.
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "Webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook registered", body = CreateWebhookResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Admin role required")
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Json(mut request): Json<CreateWebhookRequest>,
+) -> ApiResult<Json<CreateWebhookResponse>> {
+    if !is_valid_webhook_url(&request.url) {
+        return Err(ApiError::bad_request(
+            "Webhook URL must be an absolute http or https URL",
+        ));
+    }
+    if request.events.is_empty() {
+        return Err(ApiError::bad_request("At least one event type is required"));
+    }
+    if request
+        .secret
+        .as_ref()
+        .is_some_and(|s| s.len() < MIN_SECRET_LEN)
+    {
+        return Err(ApiError::bad_request(format!(
+            "Webhook secret must be at least {} characters",
+            MIN_SECRET_LEN
+        )));
+    }
+    request.events.sort();
+    request.events.dedup();
+
+    let (subscription, secret) = state
+        .webhooks
+        .create(
+            request.url,
+            request.events,
+            request.description,
+            request.secret,
+        )
+        .await;
+
+    Ok(Json(CreateWebhookResponse {
+        webhook: webhook_response(&subscription),
+        secret,
+    }))
+}
+
+/// Delete a webhook.
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "Webhooks",
+    params(
+        ("id" = String, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook deleted", body = MessageResponse),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Webhook not found")
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    if !state.webhooks.delete(&id).await {
+        return Err(ApiError::not_found("Webhook not found"));
+    }
+
+    Ok(Json(MessageResponse::new(format!(
+        "Webhook {} deleted",
+        id
+    ))))
+}
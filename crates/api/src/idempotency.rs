@@ -0,0 +1,413 @@
+//! Idempotency keys for mutating position endpoints.
+//!
+//! A client may send an `Idempotency-Key` header with a request that opens,
+//! closes, rebalances, or collects fees from positions. The first response
+//! for the key is stored and replayed for retries with the same key, marked
+//! with `Idempotent-Replayed: true`, so a retry after a timeout cannot
+//! trigger the on-chain action twice.
+//!
+//! Keys are scoped to the authenticated principal and expire after
+//! [`IDEMPOTENCY_TTL_HOURS`]. Reusing a key for a different request is
+//! rejected. A key is reserved in the store before its request runs and the
+//! response is stored whatever its status, server errors included, since
+//! the action may have reached the chain anyway. Retries are rejected while
+//! the original request is running, and until the key expires if it never
+//! completed, as then its outcome is unknown.
+
+use crate::auth::Principal;
+use crate::error::ApiError;
+use async_trait::async_trait;
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Request header carrying the client's idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on replayed responses.
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Hours a stored response is replayed for.
+pub const IDEMPOTENCY_TTL_HOURS: i64 = 24;
+
+/// Maximum length of a client key.
+const MAX_KEY_LEN: usize = 255;
+
+/// Maximum request body buffered for fingerprinting.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// A stored response.
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    /// Fingerprint of the request that produced the response.
+    pub fingerprint: String,
+    /// HTTP status code; `None` while the request is running, or if it
+    /// never completed.
+    pub status: Option<u16>,
+    /// Content type.
+    pub content_type: Option<String>,
+    /// Body.
+    pub body: Vec<u8>,
+    /// When the response was stored.
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredResponse {
+    /// Creates the reservation of a key for a request about to run.
+    #[must_use]
+    pub fn pending(fingerprint: impl Into<String>) -> Self {
+        Self {
+            fingerprint: fingerprint.into(),
+            status: None,
+            content_type: None,
+            body: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Checks whether the response is past its replay window.
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now - self.created_at >= Duration::hours(IDEMPOTENCY_TTL_HOURS)
+    }
+
+    /// Rebuilds the response for a replay.
+    fn replay(&self) -> Response {
+        let mut response = Response::new(Body::from(self.body.clone()));
+        *response.status_mut() = self
+            .status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .unwrap_or(StatusCode::OK);
+        if let Some(content_type) = self
+            .content_type
+            .as_deref()
+            .and_then(|c| HeaderValue::from_str(c).ok())
+        {
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, content_type);
+        }
+        response
+            .headers_mut()
+            .insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+/// Store of responses by scoped idempotency key.
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Reserves a key for a request, replacing any expired response.
+    ///
+    /// Returns the unexpired response already stored under the key, if
+    /// any, in which case nothing is reserved. Reservations are atomic, so
+    /// concurrent requests with the same key reserve it at most once.
+    async fn reserve(
+        &self,
+        key: &str,
+        pending: &StoredResponse,
+    ) -> anyhow::Result<Option<StoredResponse>>;
+
+    /// Stores the response to the request that reserved a key.
+    async fn put(&self, key: &str, response: &StoredResponse) -> anyhow::Result<()>;
+}
+
+/// In-memory idempotency store.
+///
+/// Stored responses are lost when the process exits.
+#[derive(Default)]
+pub struct MemoryIdempotencyStore {
+    /// Responses by key.
+    responses: RwLock<HashMap<String, StoredResponse>>,
+}
+
+#[async_trait]
+impl IdempotencyStore for MemoryIdempotencyStore {
+    async fn reserve(
+        &self,
+        key: &str,
+        pending: &StoredResponse,
+    ) -> anyhow::Result<Option<StoredResponse>> {
+        let mut responses = self.responses.write().await;
+        let now = Utc::now();
+        responses.retain(|_, stored| !stored.is_expired(now));
+        if let Some(stored) = responses.get(key) {
+            return Ok(Some(stored.clone()));
+        }
+        responses.insert(key.to_string(), pending.clone());
+        Ok(None)
+    }
+
+    async fn put(&self, key: &str, response: &StoredResponse) -> anyhow::Result<()> {
+        self.responses
+            .write()
+            .await
+            .insert(key.to_string(), response.clone());
+        Ok(())
+    }
+}
+
+/// Idempotency state shared by the middleware.
+#[derive(Clone)]
+pub struct IdempotencyState {
+    /// Stored responses.
+    store: Arc<dyn IdempotencyStore>,
+}
+
+impl Default for IdempotencyState {
+    fn default() -> Self {
+        Self::new(Arc::new(MemoryIdempotencyStore::default()))
+    }
+}
+
+impl IdempotencyState {
+    /// Creates idempotency state backed by a store.
+    pub fn new(store: Arc<dyn IdempotencyStore>) -> Self {
+        Self { store }
+    }
+}
+
+/// Hashes parts into a hex SHA-256 digest, separating them unambiguously.
+fn digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Checks that a client key is 1-255 visible ASCII characters.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LEN && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Idempotency middleware.
+///
+/// Requests without an `Idempotency-Key` header pass through unchanged.
+pub async fn idempotency_middleware(
+    State(idempotency): State<IdempotencyState>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(request).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .filter(|key| is_valid_key(key))
+        .ok_or_else(|| {
+            ApiError::bad_request("Idempotency-Key must be 1 to 255 visible ASCII characters")
+        })?;
+    let subject = request
+        .extensions()
+        .get::<Principal>()
+        .map(|principal| principal.subject.clone())
+        .unwrap_or_default();
+    let scoped_key = digest(&[subject.as_bytes(), key.as_bytes()]);
+
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| ApiError::bad_request("Request body too large"))?;
+    let fingerprint = digest(&[
+        parts.method.as_str().as_bytes(),
+        parts.uri.path().as_bytes(),
+        &body,
+    ]);
+
+    let stored = idempotency
+        .store
+        .reserve(&scoped_key, &StoredResponse::pending(fingerprint.clone()))
+        .await
+        .map_err(|e| {
+            warn!(error = %e, "Failed to reserve idempotency key");
+            ApiError::ServiceUnavailable("Idempotency store unavailable".to_string())
+        })?;
+    if let Some(stored) = stored {
+        if stored.fingerprint != fingerprint {
+            return Err(ApiError::Validation(
+                "Idempotency-Key was already used for a different request".to_string(),
+            ));
+        }
+        if stored.status.is_none() {
+            return Err(ApiError::Conflict(
+                "A request with this Idempotency-Key is in progress or its outcome is unknown"
+                    .to_string(),
+            ));
+        }
+        debug!(path = %parts.uri.path(), "Replaying stored response");
+        return Ok(stored.replay());
+    }
+
+    // The key stays reserved if the request never completes, so a retry
+    // cannot repeat an action whose outcome is unknown
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to read response: {}", e)))?;
+    let stored = StoredResponse {
+        fingerprint,
+        status: Some(parts.status.as_u16()),
+        content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body: body.to_vec(),
+        created_at: Utc::now(),
+    };
+    if let Err(e) = idempotency.store.put(&scoped_key, &stored).await {
+        warn!(error = %e, "Failed to store idempotent response");
+    }
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware, routing::post};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tower::ServiceExt;
+
+    fn router(idempotency: IdempotencyState, calls: Arc<AtomicU32>) -> Router {
+        Router::new()
+            .route(
+                "/positions",
+                post(move |body: String| {
+                    let calls = calls.clone();
+                    async move {
+                        let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                        let status = if body == "fail" {
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        } else {
+                            StatusCode::OK
+                        };
+                        (status, format!("{}:{}", body, n))
+                    }
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                idempotency,
+                idempotency_middleware,
+            ))
+    }
+
+    fn request(key: Option<&str>, body: &str) -> Request<Body> {
+        let mut builder = axum::http::Request::post("/positions");
+        if let Some(key) = key {
+            builder = builder.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    async fn body_text(response: Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replays_stored_response() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let app = router(IdempotencyState::default(), calls.clone());
+
+        let first = app.clone().oneshot(request(Some("k1"), "a")).await.unwrap();
+        assert!(first.headers().get(REPLAYED_HEADER).is_none());
+        assert_eq!(body_text(first).await, "a:1");
+
+        let retry = app.clone().oneshot(request(Some("k1"), "a")).await.unwrap();
+        assert_eq!(retry.headers().get(REPLAYED_HEADER).unwrap(), "true");
+        assert_eq!(body_text(retry).await, "a:1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Requests without a key always run
+        app.clone().oneshot(request(None, "a")).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_replays_server_errors() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let app = router(IdempotencyState::default(), calls.clone());
+
+        let first = app
+            .clone()
+            .oneshot(request(Some("k1"), "fail"))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        // The action may have gone through, so it is not run again
+        let retry = app.oneshot(request(Some("k1"), "fail")).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(retry.headers().get(REPLAYED_HEADER).unwrap(), "true");
+        assert_eq!(body_text(retry).await, "fail:1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_reused_key() {
+        let app = router(IdempotencyState::default(), Arc::new(AtomicU32::new(0)));
+        app.clone().oneshot(request(Some("k1"), "a")).await.unwrap();
+
+        let reused = app.clone().oneshot(request(Some("k1"), "b")).await.unwrap();
+        assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let invalid = app.oneshot(request(Some("bad key"), "a")).await.unwrap();
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_retry_of_unfinished_request() {
+        let store = Arc::new(MemoryIdempotencyStore::default());
+        let calls = Arc::new(AtomicU32::new(0));
+        let app = router(IdempotencyState::new(store.clone()), calls.clone());
+
+        // A request that is still running, or never completed
+        let key = digest(&["".as_bytes(), "k1".as_bytes()]);
+        let fingerprint = digest(&["POST".as_bytes(), "/positions".as_bytes(), "a".as_bytes()]);
+        store
+            .reserve(&key, &StoredResponse::pending(fingerprint))
+            .await
+            .unwrap();
+
+        let retry = app.oneshot(request(Some("k1"), "a")).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::CONFLICT);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_reserves_once() {
+        let store = MemoryIdempotencyStore::default();
+        let pending = StoredResponse::pending("f");
+        assert!(store.reserve("k1", &pending).await.unwrap().is_none());
+
+        let existing = store.reserve("k1", &pending).await.unwrap().unwrap();
+        assert!(existing.status.is_none());
+
+        let mut done = pending.clone();
+        done.status = Some(200);
+        store.put("k1", &done).await.unwrap();
+        let existing = store.reserve("k1", &pending).await.unwrap().unwrap();
+        assert_eq!(existing.status, Some(200));
+
+        // Expired responses are replaced
+        done.created_at = Utc::now() - Duration::hours(IDEMPOTENCY_TTL_HOURS);
+        store.put("k1", &done).await.unwrap();
+        assert!(store.reserve("k1", &pending).await.unwrap().is_none());
+    }
+}
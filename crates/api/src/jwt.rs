@@ -0,0 +1,426 @@
+//! JWT bearer token validation.
+//!
+//! Tokens are verified either with a shared HMAC secret or, for OIDC
+//! providers, against the issuer's JWKS. Role claims are mapped onto the
+//! same [`Role`] model as API keys.
+
+use crate::auth::{AuthError, Principal, Role};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, Header, Validation, decode, decode_header, errors::ErrorKind,
+    jwk::JwkSet,
+};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// How long fetched JWKS keys are trusted before refetching.
+const JWKS_TTL: Duration = Duration::from_secs(3600);
+
+/// Minimum interval between refetches triggered by an unknown key ID.
+const JWKS_MIN_REFRESH: Duration = Duration::from_secs(60);
+
+/// JWT validation configuration.
+///
+/// Keys are resolved from `jwks_url` if set, otherwise from the issuer's
+/// OIDC discovery document unless a shared `secret` is configured, in which
+/// case HS256/384/512 tokens are verified with it.
+#[derive(Debug, Clone)]
+pub struct JwtConfig {
+    /// Shared HMAC secret.
+    pub secret: Option<String>,
+    /// Expected `iss` claim; also used for OIDC discovery.
+    pub issuer: Option<String>,
+    /// Expected `aud` claim.
+    pub audience: Option<String>,
+    /// JWKS endpoint.
+    pub jwks_url: Option<String>,
+    /// Claim holding the roles; dotted paths reach nested claims
+    /// (e.g. `realm_access.roles`).
+    pub roles_claim: String,
+    /// Claim values mapped to roles, checked before role names.
+    pub role_mapping: HashMap<String, Role>,
+    /// Allowed clock skew in seconds.
+    pub leeway_secs: u64,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            issuer: None,
+            audience: None,
+            jwks_url: None,
+            roles_claim: "roles".to_string(),
+            role_mapping: HashMap::new(),
+            leeway_secs: 60,
+        }
+    }
+}
+
+impl JwtConfig {
+    /// Creates a configuration verifying tokens with a shared secret.
+    pub fn secret(secret: impl Into<String>) -> Self {
+        Self {
+            secret: Some(secret.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a configuration for an OIDC issuer, discovering its JWKS.
+    pub fn oidc(issuer: impl Into<String>) -> Self {
+        Self {
+            issuer: Some(issuer.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the expected audience.
+    #[must_use]
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Sets the JWKS endpoint, skipping discovery.
+    #[must_use]
+    pub fn with_jwks_url(mut self, url: impl Into<String>) -> Self {
+        self.jwks_url = Some(url.into());
+        self
+    }
+
+    /// Sets the claim holding the roles.
+    #[must_use]
+    pub fn with_roles_claim(mut self, claim: impl Into<String>) -> Self {
+        self.roles_claim = claim.into();
+        self
+    }
+
+    /// Maps a claim value (e.g. a group name) to a role.
+    #[must_use]
+    pub fn with_role_mapping(mut self, value: impl Into<String>, role: Role) -> Self {
+        self.role_mapping.insert(value.into(), role);
+        self
+    }
+
+    /// Checks whether keys come from a JWKS rather than the shared secret.
+    fn uses_jwks(&self) -> bool {
+        self.jwks_url.is_some() || self.secret.is_none()
+    }
+}
+
+/// Fetched JWKS keys.
+struct CachedJwks {
+    /// Key set.
+    keys: JwkSet,
+    /// When the key set was fetched.
+    fetched_at: Instant,
+}
+
+/// Validates bearer tokens and maps their claims to a [`Principal`].
+pub struct JwtValidator {
+    /// Configuration.
+    config: JwtConfig,
+    /// HTTP client for JWKS and discovery requests.
+    client: reqwest::Client,
+    /// Cached JWKS keys.
+    jwks: RwLock<Option<CachedJwks>>,
+}
+
+impl JwtValidator {
+    /// Creates a new validator.
+    pub fn new(config: JwtConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            jwks: RwLock::new(None),
+        }
+    }
+
+    /// Gets the configuration.
+    #[must_use]
+    pub fn config(&self) -> &JwtConfig {
+        &self.config
+    }
+
+    /// Validates a token and returns its principal.
+    ///
+    /// # Errors
+    /// Returns an error if the token is malformed, has an invalid signature,
+    /// issuer or audience, is expired, or carries no recognised role.
+    pub async fn validate(&self, token: &str) -> Result<Principal, AuthError> {
+        let header = decode_header(token).map_err(|_| AuthError::InvalidToken)?;
+        let key = self.decoding_key(&header).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.config.leeway_secs;
+        if let Some(issuer) = &self.config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.config.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        let data =
+            decode::<Map<String, Value>>(token, &key, &validation).map_err(|e| match e.kind() {
+                ErrorKind::ExpiredSignature => AuthError::TokenExpired,
+                _ => {
+                    debug!(error = %e, "Rejected bearer token");
+                    AuthError::InvalidToken
+                }
+            })?;
+
+        self.principal(&data.claims)
+    }
+
+    /// Maps verified claims to a principal.
+    fn principal(&self, claims: &Map<String, Value>) -> Result<Principal, AuthError> {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or(AuthError::InvalidToken)?;
+
+        let role = claim_strings(claims, &self.config.roles_claim)
+            .into_iter()
+            .filter_map(|value| {
+                self.config
+                    .role_mapping
+                    .get(value)
+                    .copied()
+                    .or_else(|| Role::from_str(value))
+            })
+            .max()
+            .ok_or(AuthError::InsufficientPermissions)?;
+
+        let strategies = claim_strings(claims, "strategies")
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+        let tenant = claims
+            .get("tenant")
+            .and_then(Value::as_str)
+            .filter(|tenant| !tenant.is_empty())
+            .map(str::to_string);
+
+        Ok(Principal {
+            subject: subject.to_string(),
+            role,
+            strategies,
+            tenant,
+        })
+    }
+
+    /// Resolves the key to verify a token with.
+    async fn decoding_key(&self, header: &Header) -> Result<DecodingKey, AuthError> {
+        let hmac = matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        );
+
+        if !self.config.uses_jwks() {
+            let secret = self.config.secret.as_deref().unwrap_or_default();
+            if !hmac {
+                return Err(AuthError::InvalidToken);
+            }
+            return Ok(DecodingKey::from_secret(secret.as_bytes()));
+        }
+
+        // Never accept a public JWKS key as an HMAC secret
+        if hmac {
+            return Err(AuthError::InvalidToken);
+        }
+
+        if let Some(key) = self.cached_key(header.kid.as_deref(), false).await {
+            return Ok(key);
+        }
+        self.refresh_jwks().await;
+        self.cached_key(header.kid.as_deref(), true)
+            .await
+            .ok_or(AuthError::InvalidToken)
+    }
+
+    /// Looks up a key in the cached JWKS.
+    ///
+    /// Without a key ID, a key set holding a single key is used. Unless
+    /// `allow_stale`, keys older than the TTL are not returned.
+    async fn cached_key(&self, kid: Option<&str>, allow_stale: bool) -> Option<DecodingKey> {
+        let jwks = self.jwks.read().await;
+        let cached = jwks.as_ref()?;
+        if !allow_stale && cached.fetched_at.elapsed() > JWKS_TTL {
+            return None;
+        }
+
+        let jwk = match kid {
+            Some(kid) => cached.keys.find(kid)?,
+            None if cached.keys.keys.len() == 1 => &cached.keys.keys[0],
+            None => return None,
+        };
+        DecodingKey::from_jwk(jwk).ok()
+    }
+
+    /// Refetches the JWKS unless it was fetched very recently.
+    async fn refresh_jwks(&self) {
+        let mut jwks = self.jwks.write().await;
+        if jwks
+            .as_ref()
+            .is_some_and(|c| c.fetched_at.elapsed() < JWKS_MIN_REFRESH)
+        {
+            return;
+        }
+
+        match self.fetch_jwks().await {
+            Ok(keys) => {
+                info!(keys = keys.keys.len(), "Fetched JWKS");
+                *jwks = Some(CachedJwks {
+                    keys,
+                    fetched_at: Instant::now(),
+                });
+            }
+            Err(e) => warn!(error = %e, "Failed to fetch JWKS"),
+        }
+    }
+
+    /// Fetches the JWKS, discovering its URL from the issuer if needed.
+    async fn fetch_jwks(&self) -> anyhow::Result<JwkSet> {
+        let url = match (&self.config.jwks_url, &self.config.issuer) {
+            (Some(url), _) => url.clone(),
+            (None, Some(issuer)) => self.discover_jwks_url(issuer).await?,
+            (None, None) => anyhow::bail!("no JWKS URL or issuer configured"),
+        };
+
+        Ok(self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Reads the JWKS URL from the issuer's OIDC discovery document.
+    async fn discover_jwks_url(&self, issuer: &str) -> anyhow::Result<String> {
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        );
+        let document: Value = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        document
+            .get("jwks_uri")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("discovery document has no jwks_uri"))
+    }
+}
+
+/// Reads a string or string-array claim, following dotted paths.
+///
+/// Space-separated strings (as in OAuth `scope`) yield each value.
+fn claim_strings<'a>(claims: &'a Map<String, Value>, path: &str) -> Vec<&'a str> {
+    let mut parts = path.split('.');
+    let Some(mut value) = parts.next().and_then(|first| claims.get(first)) else {
+        return Vec::new();
+    };
+    for part in parts {
+        match value.get(part) {
+            Some(next) => value = next,
+            None => return Vec::new(),
+        }
+    }
+
+    match value {
+        Value::String(s) => s.split_whitespace().collect(),
+        Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, encode};
+    use serde_json::json;
+
+    fn token(claims: Value, secret: &str) -> String {
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    fn exp() -> u64 {
+        chrono::Utc::now().timestamp() as u64 + 3600
+    }
+
+    #[tokio::test]
+    async fn test_validate_secret_token() {
+        let validator = JwtValidator::new(
+            JwtConfig::secret("s3cret")
+                .with_audience("clmm")
+                .with_role_mapping("lp-admins", Role::Admin),
+        );
+
+        let claims = json!({"sub": "alice", "aud": "clmm", "exp": exp(), "roles": ["read_only", "lp-admins"]});
+        let principal = validator
+            .validate(&token(claims.clone(), "s3cret"))
+            .await
+            .unwrap();
+        assert_eq!(principal.subject, "alice");
+        assert_eq!(principal.role, Role::Admin);
+
+        assert!(matches!(
+            validator.validate(&token(claims, "wrong")).await,
+            Err(AuthError::InvalidToken)
+        ));
+
+        let expired = json!({"sub": "alice", "aud": "clmm", "exp": 1, "roles": ["admin"]});
+        assert!(matches!(
+            validator.validate(&token(expired, "s3cret")).await,
+            Err(AuthError::TokenExpired)
+        ));
+
+        let no_role = json!({"sub": "bob", "aud": "clmm", "exp": exp(), "roles": ["guest"]});
+        assert!(matches!(
+            validator.validate(&token(no_role, "s3cret")).await,
+            Err(AuthError::InsufficientPermissions)
+        ));
+    }
+
+    #[test]
+    fn test_nested_role_claims() {
+        let validator = JwtValidator::new(
+            JwtConfig::oidc("https://issuer").with_roles_claim("realm_access.roles"),
+        );
+        let claims = json!({
+            "sub": "carol",
+            "realm_access": {"roles": ["offline_access", "trader"]},
+            "strategies": ["s1"],
+            "tenant": "acme"
+        });
+
+        let principal = validator.principal(claims.as_object().unwrap()).unwrap();
+        assert_eq!(principal.role, Role::Trader);
+        assert_eq!(principal.tenant.as_deref(), Some("acme"));
+        assert!(principal.can_modify_strategy("s1"));
+        assert!(!principal.can_modify_strategy("s2"));
+
+        let scope = json!({"scope": "openid read_only"});
+        assert_eq!(
+            claim_strings(scope.as_object().unwrap(), "scope"),
+            vec!["openid", "read_only"]
+        );
+    }
+}
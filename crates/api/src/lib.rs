@@ -4,9 +4,13 @@
 //! - Position management endpoints
 //! - Strategy configuration and execution
 //! - Pool information and analytics
-//! - Real-time WebSocket updates
+//! - Real-time WebSocket and Server-Sent Events updates
 //! - OpenAPI documentation with Swagger UI
+//! - Optional GraphQL endpoint (`graphql` feature)
 //! - JWT and API key authentication
+//! - Audit log of mutating API requests
+//! - Tenant scoping of strategies, positions and analytics
+//! - Optional TLS with certificate files or ACME
 
 /// Prelude module for convenient imports.
 pub mod prelude;
@@ -15,27 +19,47 @@ pub mod prelude;
 pub mod auth;
 /// Error types.
 pub mod error;
+/// GraphQL schema and endpoint.
+#[cfg(feature = "graphql")]
+pub mod graphql;
 /// Request handlers.
 pub mod handlers;
+/// Idempotency keys for mutating endpoints.
+pub mod idempotency;
+/// JWT and OIDC bearer token validation.
+pub mod jwt;
 /// Middleware components.
 pub mod middleware;
 /// API request/response models.
 pub mod models;
 /// OpenAPI documentation.
 pub mod openapi;
+/// Request audit logging of mutating API calls.
+pub mod request_audit;
 /// Route definitions.
 pub mod routes;
 /// Server configuration and startup.
 pub mod server;
 /// Service layer for API operations.
 pub mod services;
+/// Server-Sent Events stream.
+pub mod sse;
 /// Application state.
 pub mod state;
+/// Tracing and OpenTelemetry export.
+pub mod telemetry;
+/// Tenant scoping of strategies and positions.
+pub mod tenancy;
+/// TLS termination.
+pub mod tls;
+/// API versions and deprecation headers.
+pub mod versioning;
 /// WebSocket handlers.
 pub mod websocket;
 
-pub use auth::{AuthConfig, AuthError, AuthState, Claims, Role};
+pub use auth::{ApiKey, ApiKeyStore, AuthConfig, AuthError, AuthState, Claims, Principal, Role};
 pub use error::ApiError;
+pub use jwt::{JwtConfig, JwtValidator};
 pub use openapi::ApiDoc;
 pub use server::{ApiServer, ServerConfig};
 pub use services::{PositionService, StrategyService};
@@ -3,21 +3,46 @@
 //! This binary starts the REST API server with WebSocket support.
 
 use anyhow::Result;
+use clmm_lp_api::auth::Role;
+use clmm_lp_api::jwt::JwtConfig;
+use clmm_lp_api::middleware::{RateLimitConfig, RateLimits};
 use clmm_lp_api::server::{ApiServer, ServerConfig, shutdown_signal};
-use clmm_lp_api::state::ApiConfig;
+use clmm_lp_api::services::{
+    DbAuditLog, DbHistoryStore, DbIdempotencyStore, DbJobStore, DbPoolStats, DbPositionHistory,
+    DbPriceHistory, DbRequestAuditLog, DbStateStore, DbStrategyStore, LeaderboardService,
+    MarketDataService, PoolScreener, restore_strategies,
+};
+use clmm_lp_api::state::{ApiConfig, AppState, StrategyUpdate};
+use clmm_lp_api::telemetry::init_tracing;
+use clmm_lp_api::tls::{AcmeSettings, TlsConfig};
+use clmm_lp_config::prelude::{AppConfig, ConfigLoader};
+use clmm_lp_data::prelude::{BirdeyeProvider, Database};
+use clmm_lp_execution::prelude::{
+    CronSchedule, DigestConfig, DigestJob, DiscordNotifier, EmergencyExitConfig,
+    EmergencyExitManager, EscalationPolicy, ExecutorConfig, PortfolioRiskManager, PriceGuard,
+    PriceGuardConfig, PythOracle, RiskLimitsConfig, Schedule, SlackConfig, SlackNotifier,
+    SnapshotRecorder, TelegramConfig, TelegramNotifier, TransactionConfig, WebhookConfig,
+    WebhookNotifier,
+};
 use clmm_lp_protocols::prelude::RpcConfig;
 use std::env;
-use tracing::info;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
+    // Initialize logging and trace export
+    let _telemetry = init_tracing("clmm-lp-api")?;
 
     info!("Starting CLMM Liquidity Provider API Server");
 
-    // Load configuration from environment
-    let config = load_config_from_env();
+    // Layer the config file (CLMM_CONFIG, CLMM_PROFILE) under the environment
+    let settings = ConfigLoader::new()
+        .with_file(env::var("CLMM_CONFIG").ok())
+        .with_profile(env::var("CLMM_PROFILE").ok())
+        .load()?;
+    let config = server_config(&settings);
 
     info!(
         host = %config.host,
@@ -26,47 +51,436 @@ async fn main() -> Result<()> {
     );
 
     // Create and run server
-    let server = ApiServer::new(config);
+    let mut state = AppState::new(config.rpc_config.clone(), config.api_config.clone());
+    restore_state(&mut state, &settings).await?;
+    configure_market_data(&mut state, &settings);
+    configure_price_guard(&mut state, &settings);
+    configure_risk_limits(&mut state, &settings);
+    let server = ApiServer::with_state(config, state);
+    configure_alert_channels(server.state()).await;
+    configure_daily_digest(server.state());
     server.run_with_shutdown(shutdown_signal()).await?;
 
     Ok(())
 }
 
-/// Loads server configuration from environment variables.
-fn load_config_from_env() -> ServerConfig {
-    let host = env::var("API_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = env::var("API_PORT")
-        .ok()
-        .and_then(|p| p.parse().ok())
-        .unwrap_or(8080);
+/// Builds the server configuration from the shared settings.
+///
+/// Rate limits, TLS and bearer token validation are still read from their
+/// own environment variables.
+fn server_config(settings: &AppConfig) -> ServerConfig {
+    let api = &settings.api;
+    let api_config = ApiConfig {
+        host: api.host.clone(),
+        port: api.port,
+        enable_cors: api.cors_allow_all,
+        rate_limits: load_rate_limits_from_env(),
+        request_timeout_secs: api.request_timeout_secs,
+        api_keys: api.api_keys.clone(),
+        require_auth: api.require_auth,
+        jwt: load_jwt_config_from_env(),
+        paper_ledger_dir: api.paper_ledger_dir.clone(),
+        executor: ExecutorConfig::from_settings(&settings.execution),
+        transaction: TransactionConfig::from_settings(&settings.execution),
+    };
 
-    let rpc_url = env::var("SOLANA_RPC_URL")
-        .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
+    ServerConfig {
+        host: api.host.clone(),
+        port: api.port,
+        rpc_config: RpcConfig::from_settings(&settings.rpc),
+        api_config,
+        tls: load_tls_config_from_env(),
+    }
+}
 
-    let rpc_config = RpcConfig {
-        primary_url: rpc_url,
+/// Loads rate limits from environment variables.
+///
+/// `API_RATE_LIMIT_READ_RPM`, `API_RATE_LIMIT_MUTATION_RPM` and
+/// `API_RATE_LIMIT_BACKTEST_RPM` set the per-caller defaults.
+/// `API_RATE_LIMIT_KEYS` overrides them per API key ID or token subject,
+/// e.g. `<key-id>=1200/120/20`.
+fn load_rate_limits_from_env() -> RateLimitConfig {
+    let rpm = |var: &str, default: u32| {
+        env::var(var)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    let defaults = RateLimits::default();
+    let mut config = RateLimitConfig {
+        default: RateLimits {
+            reads_per_minute: rpm("API_RATE_LIMIT_READ_RPM", defaults.reads_per_minute),
+            mutations_per_minute: rpm("API_RATE_LIMIT_MUTATION_RPM", defaults.mutations_per_minute),
+            backtests_per_minute: rpm("API_RATE_LIMIT_BACKTEST_RPM", defaults.backtests_per_minute),
+        },
         ..Default::default()
     };
+    if let Ok(overrides) = env::var("API_RATE_LIMIT_KEYS") {
+        for entry in overrides.split(',') {
+            match entry
+                .split_once('=')
+                .and_then(|(key, limits)| Some((key.trim(), RateLimits::parse(limits)?)))
+            {
+                Some((key, limits)) => config = config.with_key_limits(key, limits),
+                None => warn!(entry = %entry, "Ignoring invalid API_RATE_LIMIT_KEYS entry"),
+            }
+        }
+    }
+    config
+}
 
-    let api_config = ApiConfig {
-        enable_cors: env::var("API_CORS_ALLOW_ALL")
+/// Loads TLS settings from environment variables.
+///
+/// `API_TLS_CERT` and `API_TLS_KEY` serve PEM files. Otherwise
+/// `API_ACME_DOMAINS` (comma-separated) obtains certificates over ACME,
+/// with `API_ACME_CONTACT`, `API_ACME_CACHE_DIR` and `API_ACME_PRODUCTION`
+/// (staging unless `true`).
+fn load_tls_config_from_env() -> Option<TlsConfig> {
+    if let (Ok(cert), Ok(key)) = (env::var("API_TLS_CERT"), env::var("API_TLS_KEY")) {
+        return Some(TlsConfig::files(cert, key));
+    }
+
+    let domains: Vec<String> = env::var("API_ACME_DOMAINS")
+        .ok()?
+        .split(',')
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+        .collect();
+    if domains.is_empty() {
+        return None;
+    }
+
+    let mut acme = AcmeSettings::new(domains).with_production(
+        env::var("API_ACME_PRODUCTION")
             .map(|v| v == "true")
-            .unwrap_or(true),
-        rate_limit_per_minute: env::var("API_RATE_LIMIT_RPM")
-            .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(100),
-        request_timeout_secs: env::var("API_REQUEST_TIMEOUT_SECS")
+            .unwrap_or(false),
+    );
+    if let Ok(contact) = env::var("API_ACME_CONTACT") {
+        acme = acme.with_contact(contact);
+    }
+    if let Ok(dir) = env::var("API_ACME_CACHE_DIR") {
+        acme = acme.with_cache_dir(dir);
+    }
+    Some(TlsConfig::Acme(acme))
+}
+
+/// Enables the market data endpoints when a Birdeye API key is configured.
+///
+/// `providers.market_cache_ttl_secs` sets how long fetched candles stay
+/// cached.
+fn configure_market_data(state: &mut AppState, settings: &AppConfig) {
+    let Some(api_key) = settings.providers.birdeye_api_key.clone() else {
+        info!("BIRDEYE_API_KEY not set, market data endpoints are disabled");
+        return;
+    };
+
+    let ttl = Duration::from_secs(settings.providers.market_cache_ttl_secs);
+    let service = MarketDataService::new(Arc::new(BirdeyeProvider::new(api_key))).with_ttl(ttl);
+    state.set_market_data(Arc::new(service));
+}
+
+/// Checks pool prices against Pyth before strategies rebalance and before
+/// positions are opened.
+///
+/// Disabled when `execution.max_oracle_divergence_bps` is 0. Pools whose tokens
+/// have no Pyth feed are not checked.
+fn configure_price_guard(state: &mut AppState, settings: &AppConfig) {
+    if settings.execution.max_oracle_divergence_bps == 0 {
+        info!("Oracle price guard disabled");
+        return;
+    }
+
+    let guard = PriceGuard::new(
+        state.provider.clone(),
+        Arc::new(PythOracle::new()),
+        PriceGuardConfig::from_settings(&settings.execution),
+    );
+    state.set_price_guard(Arc::new(guard));
+}
+
+/// Enforces the portfolio drawdown and token exposure limits.
+///
+/// Disabled unless `execution.max_portfolio_drawdown_pct` or
+/// `execution.max_token_exposure_usd` is set. A breach engages the portfolio
+/// stop, which pauses all strategies through the shared circuit breaker and,
+/// with `execution.unwind_on_portfolio_stop`, closes all positions.
+fn configure_risk_limits(state: &mut AppState, settings: &AppConfig) {
+    let config = RiskLimitsConfig::from_settings(&settings.execution);
+    if !config.is_enabled() {
+        return;
+    }
+
+    let mut risk = PortfolioRiskManager::new(
+        state.provider.clone(),
+        state.monitor.clone(),
+        Arc::new(PythOracle::new()),
+        state.circuit_breaker.clone(),
+        config,
+    );
+    risk.set_exit_manager(Arc::new(EmergencyExitManager::new(
+        state.monitor.clone(),
+        state.tx_manager.clone(),
+        EmergencyExitConfig::default(),
+    )));
+    let alert_state = state.clone();
+    risk.set_alert_callback(move |alert| alert_state.raise_alert(alert));
+    let strategy_state = state.clone();
+    risk.set_stop_callback(move |status| {
+        let state = strategy_state.clone();
+        let reason = status.stop_reason.clone();
+        tokio::spawn(async move {
+            let running: Vec<String> = state
+                .strategies
+                .read()
+                .await
+                .values()
+                .filter(|strategy| strategy.running)
+                .map(|strategy| strategy.id.clone())
+                .collect();
+            for id in running {
+                state.broadcast_strategy_update(StrategyUpdate {
+                    update_type: "paused".to_string(),
+                    strategy_id: id,
+                    timestamp: chrono::Utc::now(),
+                    data: serde_json::json!({ "reason": reason }),
+                });
+            }
+        });
+    });
+
+    let risk = Arc::new(risk);
+    state.set_risk_manager(risk.clone());
+    tokio::spawn(async move { risk.start().await });
+    info!(
+        max_drawdown_pct = settings.execution.max_portfolio_drawdown_pct,
+        max_token_exposure_usd = settings.execution.max_token_exposure_usd,
+        unwind = settings.execution.unwind_on_portfolio_stop,
+        "Portfolio risk limits enabled"
+    );
+}
+
+/// Loads bearer token validation settings from environment variables.
+///
+/// Enabled when `JWT_JWKS_URL`, `JWT_ISSUER` or `JWT_SECRET` is set.
+/// `JWT_ROLE_MAPPING` maps claim values to roles, e.g.
+/// `lp-admins=admin,lp-traders=trader`.
+fn load_jwt_config_from_env() -> Option<JwtConfig> {
+    let jwks_url = env::var("JWT_JWKS_URL").ok();
+    let issuer = env::var("JWT_ISSUER").ok();
+    let secret = env::var("JWT_SECRET").ok();
+    if jwks_url.is_none() && issuer.is_none() && secret.is_none() {
+        return None;
+    }
+
+    let mut config = JwtConfig {
+        secret,
+        issuer,
+        audience: env::var("JWT_AUDIENCE").ok(),
+        jwks_url,
+        ..Default::default()
+    };
+    if let Ok(claim) = env::var("JWT_ROLES_CLAIM") {
+        config = config.with_roles_claim(claim);
+    }
+    if let Ok(mapping) = env::var("JWT_ROLE_MAPPING") {
+        for entry in mapping.split(',') {
+            if let Some((value, role)) = entry.split_once('=')
+                && let Some(role) = Role::from_str(role.trim())
+            {
+                config = config.with_role_mapping(value.trim(), role);
+            }
+        }
+    }
+
+    info!(
+        issuer = ?config.issuer,
+        jwks_url = ?config.jwks_url,
+        "Bearer token authentication enabled"
+    );
+    Some(config)
+}
+
+/// Registers alert notification channels from environment variables.
+///
+/// Critical alerts escalate through `ESCALATION_WEBHOOK_URL` when they are
+/// not acknowledged within `ALERT_ESCALATION_MINUTES`.
+async fn configure_alert_channels(state: &AppState) {
+    let alerts = &state.alerts;
+
+    if let (Ok(token), Ok(chat_id)) = (env::var("TELEGRAM_BOT_TOKEN"), env::var("TELEGRAM_CHAT_ID"))
+    {
+        let notifier = TelegramNotifier::new(TelegramConfig::new(token, chat_id));
+        alerts.add_primary("telegram", Arc::new(notifier)).await;
+        info!("Telegram alert channel enabled");
+    }
+
+    if let Ok(url) = env::var("SLACK_WEBHOOK_URL") {
+        let notifier = SlackNotifier::new(SlackConfig::webhook(url));
+        alerts.add_primary("slack", Arc::new(notifier)).await;
+        info!("Slack alert channel enabled");
+    }
+
+    if let Ok(url) = env::var("DISCORD_WEBHOOK_URL") {
+        alerts
+            .add_primary("discord", Arc::new(DiscordNotifier::new(url)))
+            .await;
+        info!("Discord alert channel enabled");
+    }
+
+    if let Ok(url) = env::var("ALERT_WEBHOOK_URL") {
+        let notifier = WebhookNotifier::with_config(webhook_config(url, "ALERT_WEBHOOK_SECRET"));
+        alerts.add_primary("webhook", Arc::new(notifier)).await;
+        info!("Webhook alert channel enabled");
+    }
+
+    if let Ok(url) = env::var("ESCALATION_WEBHOOK_URL") {
+        let notifier =
+            WebhookNotifier::with_config(webhook_config(url, "ESCALATION_WEBHOOK_SECRET"));
+        alerts
+            .add_escalation_channel("escalation", Arc::new(notifier))
+            .await;
+
+        let minutes: u64 = env::var("ALERT_ESCALATION_MINUTES")
             .ok()
             .and_then(|v| v.parse().ok())
-            .unwrap_or(30),
-        ..Default::default()
+            .unwrap_or(15);
+        alerts
+            .set_default_policy(EscalationPolicy::new(minutes * 60, "escalation"))
+            .await;
+        info!(minutes = minutes, "Critical alert escalation enabled");
+    }
+
+    tokio::spawn(alerts.clone().run(Duration::from_secs(30)));
+}
+
+/// Schedules the daily portfolio digest from environment variables.
+///
+/// `DAILY_DIGEST_CRON` (e.g. `0 8 * * *`) enables the digest, evaluated in
+/// `DAILY_DIGEST_TIMEZONE` (UTC by default). `DAILY_DIGEST_EDGE_PCT` sets how
+/// close to a range edge, in percent of the price, a position is reported.
+/// Digests are sent through the alert channels.
+fn configure_daily_digest(state: &AppState) {
+    let Ok(expression) = env::var("DAILY_DIGEST_CRON") else {
+        return;
+    };
+    let timezone = env::var("DAILY_DIGEST_TIMEZONE").unwrap_or_else(|_| "UTC".to_string());
+    let cron = match CronSchedule::parse_in(&expression, &timezone) {
+        Ok(cron) => cron,
+        Err(e) => {
+            warn!(error = %e, "Ignoring invalid DAILY_DIGEST_CRON");
+            return;
+        }
     };
 
-    ServerConfig {
-        host,
-        port,
-        rpc_config,
-        api_config,
+    let mut config = DigestConfig::default().with_schedule(Schedule::Cron(cron));
+    if let Some(pct) = env::var("DAILY_DIGEST_EDGE_PCT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+    {
+        config = config.with_edge_threshold_pct(pct);
+    }
+
+    let mut job = DigestJob::new(
+        state.provider.clone(),
+        state.monitor.clone(),
+        state.lifecycle.clone(),
+        config,
+    );
+    let alert_state = state.clone();
+    job.set_alert_callback(move |alert| alert_state.raise_alert(alert));
+    tokio::spawn(async move { job.start().await });
+    info!(cron = %expression, timezone = %timezone, "Daily digest enabled");
+}
+
+/// Persists monitor and lifecycle state and strategies to the configured
+/// database (`DATABASE_URL`) and restores them, restarting strategies that were running.
+///
+/// The audit logs are written to the same database, and backtests read pool
+/// price history from it. Monitored positions are recorded every
+/// `database.snapshot_interval_secs` for the historical portfolio endpoints,
+/// and pool and strategy leaderboards are recomputed from those snapshots every
+/// `database.leaderboard_interval_secs`.
+/// Without a database, state and recent audit
+/// entries are kept in memory and lost on restart, and backtests are
+/// unavailable.
+async fn restore_state(state: &mut AppState, settings: &AppConfig) -> Result<()> {
+    let Some(database_url) = settings.database.url.as_deref() else {
+        info!(
+            "DATABASE_URL not set, position state, strategies, audit log and jobs will not survive restarts"
+        );
+        return Ok(());
+    };
+
+    let db = Database::connect(database_url).await?;
+    db.migrate().await?;
+    state.set_database(db.clone());
+
+    state
+        .set_audit_log(Arc::new(DbAuditLog::new(db.audit())))
+        .await;
+    state.set_request_audit_log(Arc::new(DbRequestAuditLog::new(db.request_audit())));
+
+    let idempotency = DbIdempotencyStore::new(db.idempotency_keys());
+    let expired = idempotency.purge_expired().await?;
+    if expired > 0 {
+        info!(keys = expired, "Purged expired idempotency keys");
+    }
+    state.set_idempotency_store(Arc::new(idempotency));
+
+    state.set_price_history(Arc::new(DbPriceHistory::new(db.pools(), db.prices())));
+    state.set_pool_screener(Arc::new(PoolScreener::new(Arc::new(DbPoolStats::new(
+        db.pools(),
+    )))));
+
+    // Jobs do not survive a restart; mark those left unfinished as failed
+    let jobs = db.jobs();
+    let interrupted = jobs
+        .fail_unfinished("interrupted by server restart")
+        .await?;
+    if interrupted > 0 {
+        info!(jobs = interrupted, "Marked interrupted jobs as failed");
+    }
+    state.jobs.set_store(Arc::new(DbJobStore::new(jobs))).await;
+
+    let store = Arc::new(DbStateStore::new(db.position_states(), db.positions()));
+    state.monitor.set_state_store(store.clone()).await;
+    state.lifecycle.set_state_store(store).await;
+    state.set_position_history(Arc::new(DbPositionHistory::new(db.positions())));
+
+    let positions = state.monitor.restore().await?;
+    let events = state.lifecycle.restore().await?;
+    info!(
+        positions = positions,
+        events = events,
+        "Restored persisted position state"
+    );
+
+    // Record monitored positions for the historical portfolio endpoints
+    let recorder = SnapshotRecorder::new(
+        state.monitor.clone(),
+        Arc::new(DbHistoryStore::new(db.positions())),
+        Duration::from_secs(settings.database.snapshot_interval_secs.max(1)),
+    );
+    tokio::spawn(recorder.run());
+
+    let leaderboard = Arc::new(LeaderboardService::new(&db));
+    state.set_leaderboard(leaderboard.clone());
+    tokio::spawn(leaderboard.run(Duration::from_secs(
+        settings.database.leaderboard_interval_secs.max(1),
+    )));
+
+    // Restart executors only once the positions they evaluate are restored
+    state.set_strategy_store(Arc::new(DbStrategyStore::new(db.strategies())));
+    restore_strategies(state).await?;
+
+    Ok(())
+}
+
+/// Builds a webhook configuration, signing with the secret in `secret_var` if set.
+fn webhook_config(url: String, secret_var: &str) -> WebhookConfig {
+    let config = WebhookConfig::new(url).with_dead_letter("data/webhook_dead_letter.jsonl");
+    match env::var(secret_var) {
+        Ok(secret) => config.with_secret(secret),
+        Err(_) => config,
     }
 }
@@ -1,44 +1,19 @@
 //! Middleware components.
 
+use crate::auth::{Principal, is_public};
+use crate::error::ApiError;
 use crate::handlers::health::{increment_error_count, increment_request_count};
-use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
-use std::collections::HashSet;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, Method, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 
-/// API key authentication middleware.
-pub async fn api_key_auth(
-    api_keys: Arc<HashSet<String>>,
-    request: Request,
-    next: Next,
-) -> Result<Response, StatusCode> {
-    // Skip auth for health endpoints
-    let path = request.uri().path();
-    if path.starts_with("/health") || path == "/metrics" {
-        return Ok(next.run(request).await);
-    }
-
-    // Check for API key in header
-    let api_key = request
-        .headers()
-        .get("X-API-Key")
-        .and_then(|v| v.to_str().ok());
-
-    match api_key {
-        Some(key) if api_keys.is_empty() || api_keys.contains(key) => Ok(next.run(request).await),
-        Some(_) => {
-            warn!("Invalid API key");
-            Err(StatusCode::UNAUTHORIZED)
-        }
-        None if api_keys.is_empty() => Ok(next.run(request).await),
-        None => {
-            warn!("Missing API key");
-            Err(StatusCode::UNAUTHORIZED)
-        }
-    }
-}
-
 /// Request logging middleware.
 pub async fn request_logging(request: Request, next: Next) -> Response {
     let method = request.method().clone();
@@ -67,61 +42,399 @@ pub async fn request_logging(request: Request, next: Next) -> Response {
     response
 }
 
-/// Rate limiting state.
+/// Response header with the bucket's requests per minute.
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "x-ratelimit-limit";
+
+/// Response header with the requests left in the bucket.
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// Number of tracked buckets above which idle ones are dropped.
+const MAX_TRACKED_BUCKETS: usize = 10_000;
+
+/// Class of route, each with its own rate limit bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    /// Reads, including GraphQL queries.
+    Read,
+    /// Requests that change state.
+    Mutation,
+    /// Backtests, optimizations and simulations.
+    Backtest,
+}
+
+impl RouteClass {
+    /// Classifies a request.
+    #[must_use]
+    pub fn of(method: &Method, path: &str) -> Self {
+        if *method == Method::POST
+            && (path.starts_with("/backtests")
+                || path.starts_with("/optimize")
+                || path == "/analytics/simulate")
+        {
+            return Self::Backtest;
+        }
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) || path == "/graphql" {
+            Self::Read
+        } else {
+            Self::Mutation
+        }
+    }
+
+    /// Returns the name of this class.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Mutation => "mutation",
+            Self::Backtest => "backtest",
+        }
+    }
+}
+
+/// Requests per minute for each route class; zero disables a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimits {
+    /// Reads per minute.
+    pub reads_per_minute: u32,
+    /// Mutations per minute.
+    pub mutations_per_minute: u32,
+    /// Backtests, optimizations and simulations per minute.
+    pub backtests_per_minute: u32,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            reads_per_minute: 600,
+            mutations_per_minute: 60,
+            backtests_per_minute: 10,
+        }
+    }
+}
+
+impl RateLimits {
+    /// Returns the limit for a route class.
+    #[must_use]
+    pub fn for_class(&self, class: RouteClass) -> u32 {
+        match class {
+            RouteClass::Read => self.reads_per_minute,
+            RouteClass::Mutation => self.mutations_per_minute,
+            RouteClass::Backtest => self.backtests_per_minute,
+        }
+    }
+
+    /// Parses limits written as `reads/mutations/backtests`, e.g. `600/60/10`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split('/').map(|p| p.trim().parse::<u32>());
+        let limits = Self {
+            reads_per_minute: parts.next()?.ok()?,
+            mutations_per_minute: parts.next()?.ok()?,
+            backtests_per_minute: parts.next()?.ok()?,
+        };
+        parts.next().is_none().then_some(limits)
+    }
+}
+
+/// Rate limit configuration.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitConfig {
+    /// Limits for callers without an override.
+    pub default: RateLimits,
+    /// Limits by API key ID or token subject.
+    pub per_key: HashMap<String, RateLimits>,
+}
+
+impl RateLimitConfig {
+    /// Overrides the limits for an API key ID or token subject.
+    #[must_use]
+    pub fn with_key_limits(mut self, subject: impl Into<String>, limits: RateLimits) -> Self {
+        self.per_key.insert(subject.into(), limits);
+        self
+    }
+
+    /// Returns the limits for a caller.
+    #[must_use]
+    pub fn limits_for(&self, subject: Option<&str>) -> RateLimits {
+        subject
+            .and_then(|s| self.per_key.get(s))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// Outcome of a rate limit check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    /// The request may proceed.
+    Allowed {
+        /// Requests per minute of the bucket (zero if unlimited).
+        limit: u32,
+        /// Requests left in the bucket.
+        remaining: u32,
+    },
+    /// The bucket is empty.
+    Limited {
+        /// Requests per minute of the bucket.
+        limit: u32,
+        /// Seconds until a request will be allowed.
+        retry_after_secs: u64,
+    },
+}
+
+/// A token bucket refilling at its per-minute limit.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Refills the bucket and takes a token if one is available.
+    fn take(&mut self, limit: u32, now: Instant) -> RateDecision {
+        let capacity = f64::from(limit);
+        let per_sec = capacity / 60.0;
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * per_sec).min(capacity);
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            RateDecision::Allowed {
+                limit,
+                remaining: self.tokens as u32,
+            }
+        } else {
+            RateDecision::Limited {
+                limit,
+                retry_after_secs: ((1.0 - self.tokens) * 60.0 / capacity).ceil() as u64,
+            }
+        }
+    }
+}
+
+/// Per-caller, per-route-class rate limiter.
+///
+/// Authenticated callers are limited per API key or token subject; other
+/// callers per client IP from `X-Forwarded-For`.
 #[derive(Clone)]
 pub struct RateLimiter {
-    /// Request counts per IP.
-    counts: Arc<RwLock<std::collections::HashMap<String, (u32, std::time::Instant)>>>,
-    /// Maximum requests per minute.
-    max_requests: u32,
+    /// Buckets by caller and route class.
+    buckets: Arc<Mutex<HashMap<(String, RouteClass), Bucket>>>,
+    /// Configured limits.
+    config: Arc<RateLimitConfig>,
 }
 
 impl RateLimiter {
     /// Creates a new rate limiter.
-    pub fn new(max_requests: u32) -> Self {
+    pub fn new(config: RateLimitConfig) -> Self {
         Self {
-            counts: Arc::new(RwLock::new(std::collections::HashMap::new())),
-            max_requests,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+            config: Arc::new(config),
         }
     }
 
-    /// Checks if a request is allowed.
-    pub async fn check(&self, ip: &str) -> bool {
-        let mut counts = self.counts.write().await;
-        let now = std::time::Instant::now();
-
-        // Clean up old entries
-        counts.retain(|_, (_, time)| now.duration_since(*time).as_secs() < 60);
-
-        let entry = counts.entry(ip.to_string()).or_insert((0, now));
+    /// Checks whether a caller may make a request of a route class.
+    pub fn check(&self, client: &str, subject: Option<&str>, class: RouteClass) -> RateDecision {
+        self.check_at(client, subject, class, Instant::now())
+    }
 
-        // Reset if more than a minute has passed
-        if now.duration_since(entry.1).as_secs() >= 60 {
-            *entry = (0, now);
+    fn check_at(
+        &self,
+        client: &str,
+        subject: Option<&str>,
+        class: RouteClass,
+        now: Instant,
+    ) -> RateDecision {
+        let limit = self.config.limits_for(subject).for_class(class);
+        if limit == 0 {
+            return RateDecision::Allowed {
+                limit: 0,
+                remaining: u32::MAX,
+            };
         }
 
-        if entry.0 >= self.max_requests {
-            false
-        } else {
-            entry.0 += 1;
-            true
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_BUCKETS {
+            // An idle bucket has refilled, so dropping it changes nothing
+            buckets
+                .retain(|_, b| now.saturating_duration_since(b.updated) < Duration::from_secs(60));
         }
+        buckets
+            .entry((client.to_string(), class))
+            .or_insert_with(|| Bucket {
+                tokens: f64::from(limit),
+                updated: now,
+            })
+            .take(limit, now)
+    }
+}
+
+/// Identifies the caller for rate limiting.
+fn client_id(subject: Option<&str>, headers: &HeaderMap) -> String {
+    if let Some(subject) = subject {
+        return format!("key:{}", subject);
     }
+    headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(|ip| format!("ip:{}", ip.trim()))
+        .unwrap_or_else(|| "anonymous".to_string())
 }
 
 /// Rate limiting middleware.
+///
+/// Runs after authentication so callers are limited per key. Limited
+/// requests get a 429 with a `Retry-After` header.
 pub async fn rate_limit(
-    rate_limiter: Arc<RateLimiter>,
+    State(limiter): State<RateLimiter>,
     request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    // Get client IP (simplified - in production would check X-Forwarded-For)
-    let ip = "default".to_string();
-
-    if rate_limiter.check(&ip).await {
-        Ok(next.run(request).await)
-    } else {
-        warn!(ip = %ip, "Rate limit exceeded");
-        Err(StatusCode::TOO_MANY_REQUESTS)
+) -> Response {
+    let path = request.uri().path();
+    if is_public(path) {
+        return next.run(request).await;
+    }
+
+    let class = RouteClass::of(request.method(), path);
+    let subject = request
+        .extensions()
+        .get::<Principal>()
+        .map(|p| p.subject.clone());
+    let client = client_id(subject.as_deref(), request.headers());
+
+    match limiter.check(&client, subject.as_deref(), class) {
+        RateDecision::Allowed { limit, remaining } => {
+            let mut response = next.run(request).await;
+            if limit > 0 {
+                let headers = response.headers_mut();
+                headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(limit));
+                headers.insert(RATE_LIMIT_REMAINING_HEADER, HeaderValue::from(remaining));
+            }
+            response
+        }
+        RateDecision::Limited {
+            limit,
+            retry_after_secs,
+        } => {
+            warn!(client = %client, class = class.as_str(), "Rate limit exceeded");
+            let mut response = ApiError::TooManyRequests(format!(
+                "{} limit of {} requests per minute exceeded, retry in {} seconds",
+                class.as_str(),
+                limit,
+                retry_after_secs
+            ))
+            .into_response();
+            let headers = response.headers_mut();
+            headers.insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+            headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(limit));
+            headers.insert(RATE_LIMIT_REMAINING_HEADER, HeaderValue::from(0u32));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter() -> RateLimiter {
+        let limits = RateLimits {
+            reads_per_minute: 2,
+            mutations_per_minute: 1,
+            backtests_per_minute: 0,
+        };
+        RateLimiter::new(RateLimitConfig {
+            default: limits,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_route_class() {
+        assert_eq!(RouteClass::of(&Method::GET, "/positions"), RouteClass::Read);
+        assert_eq!(RouteClass::of(&Method::POST, "/graphql"), RouteClass::Read);
+        assert_eq!(
+            RouteClass::of(&Method::POST, "/positions"),
+            RouteClass::Mutation
+        );
+        assert_eq!(
+            RouteClass::of(&Method::POST, "/backtests"),
+            RouteClass::Backtest
+        );
+        assert_eq!(
+            RouteClass::of(&Method::GET, "/backtests/1"),
+            RouteClass::Read
+        );
+    }
+
+    #[test]
+    fn test_buckets_per_client_and_class() {
+        let limiter = limiter();
+        let now = Instant::now();
+        let check = |client, class| limiter.check_at(client, None, class, now);
+
+        assert!(matches!(
+            check("a", RouteClass::Read),
+            RateDecision::Allowed { remaining: 1, .. }
+        ));
+        assert!(matches!(
+            check("a", RouteClass::Read),
+            RateDecision::Allowed { .. }
+        ));
+        assert_eq!(
+            check("a", RouteClass::Read),
+            RateDecision::Limited {
+                limit: 2,
+                retry_after_secs: 30
+            }
+        );
+        // Other classes and clients have their own buckets
+        assert!(matches!(
+            check("a", RouteClass::Mutation),
+            RateDecision::Allowed { .. }
+        ));
+        assert!(matches!(
+            check("b", RouteClass::Read),
+            RateDecision::Allowed { .. }
+        ));
+        // A zero limit is unlimited
+        for _ in 0..10 {
+            assert!(matches!(
+                check("a", RouteClass::Backtest),
+                RateDecision::Allowed { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_bucket_refills() {
+        let limiter = limiter();
+        let now = Instant::now();
+        limiter.check_at("a", None, RouteClass::Mutation, now);
+        assert!(matches!(
+            limiter.check_at("a", None, RouteClass::Mutation, now),
+            RateDecision::Limited { .. }
+        ));
+        assert!(matches!(
+            limiter.check_at(
+                "a",
+                None,
+                RouteClass::Mutation,
+                now + Duration::from_secs(60)
+            ),
+            RateDecision::Allowed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_per_key_limits() {
+        let config = RateLimitConfig::default()
+            .with_key_limits("k1", RateLimits::parse("1200/120/20").unwrap());
+        assert_eq!(config.limits_for(Some("k1")).mutations_per_minute, 120);
+        assert_eq!(config.limits_for(Some("k2")), RateLimits::default());
+        assert_eq!(RateLimits::parse("1/2"), None);
+        assert_eq!(RateLimits::parse("1/2/3/4"), None);
     }
 }
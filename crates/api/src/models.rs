@@ -2,7 +2,7 @@
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 // ============================================================================
 // Position Models
@@ -24,6 +24,9 @@ pub struct OpenPositionRequest {
     /// Slippage tolerance in basis points.
     #[serde(default = "default_slippage")]
     pub slippage_tolerance_bps: u16,
+    /// Label of the signing wallet; defaults to the caller's only wallet.
+    #[serde(default)]
+    pub wallet: Option<String>,
 }
 
 fn default_slippage() -> u16 {
@@ -42,8 +45,74 @@ pub struct RebalanceRequest {
     pub slippage_tolerance_bps: u16,
 }
 
+/// Operation applied to a position in a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOperation {
+    /// Collect fees.
+    CollectFees,
+    /// Close the position.
+    Close,
+    /// Rebalance to a new range.
+    Rebalance,
+}
+
+/// A single operation in a batch.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchPositionItem {
+    /// Position address.
+    pub address: String,
+    /// Operation to apply.
+    pub operation: BatchOperation,
+    /// New range, required for rebalance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebalance: Option<RebalanceRequest>,
+}
+
+/// Request to apply operations to many positions.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchPositionRequest {
+    /// Operations, applied independently.
+    pub items: Vec<BatchPositionItem>,
+}
+
+/// Result of a single batch operation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchItemResult {
+    /// Position address.
+    pub address: String,
+    /// Operation applied.
+    pub operation: BatchOperation,
+    /// Whether the operation succeeded.
+    pub success: bool,
+    /// HTTP status the operation would have returned on its own.
+    pub status: u16,
+    /// Result message on success.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Error code on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Error message on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Results of a batch, in request order.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchPositionResponse {
+    /// Per-item results.
+    pub results: Vec<BatchItemResult>,
+    /// Number of operations that succeeded.
+    pub succeeded: usize,
+    /// Number of operations that failed.
+    pub failed: usize,
+}
+
 /// Position response.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct PositionResponse {
     /// Position address.
     pub address: String,
@@ -73,6 +142,7 @@ pub struct PositionResponse {
 
 /// PnL response.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct PnLResponse {
     /// Unrealized PnL in USD.
     #[schema(value_type = String)]
@@ -98,8 +168,162 @@ pub struct PnLResponse {
     pub net_pnl_pct: Decimal,
 }
 
-/// Position status.
+/// Query parameters for a position recommendation.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct RecommendationQuery {
+    /// Horizon the current and proposed ranges are compared over, in hours
+    /// (default 24).
+    pub horizon_hours: Option<u64>,
+    /// Annualized volatility (0.8 = 80%). Estimated from the pool's price
+    /// history when omitted.
+    pub volatility: Option<f64>,
+    /// Full-range fee APR of the pool (as percentage). Estimated from the
+    /// pool's price history when omitted.
+    #[param(value_type = Option<String>)]
+    pub fee_apr_pct: Option<Decimal>,
+    /// Width of a recentered range (0.1 = 10%).
+    #[param(value_type = Option<String>)]
+    pub range_width_pct: Option<Decimal>,
+}
+
+/// Recommended action for a position at the current pool state.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecommendationResponse {
+    /// Position address.
+    pub address: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Recommended action: hold, recenter, widen or close.
+    pub action: String,
+    /// Why the action is recommended.
+    pub reason: String,
+    /// Current pool tick.
+    pub tick_current: i32,
+    /// Current pool price.
+    #[schema(value_type = String)]
+    pub price: Decimal,
+    /// Whether the position is in range.
+    pub in_range: bool,
+    /// Current lower tick.
+    pub tick_lower: i32,
+    /// Current upper tick.
+    pub tick_upper: i32,
+    /// Proposed lower tick for recenter and widen.
+    pub new_tick_lower: Option<i32>,
+    /// Proposed upper tick for recenter and widen.
+    pub new_tick_upper: Option<i32>,
+    /// Proposed lower price for recenter and widen.
+    #[schema(value_type = Option<String>)]
+    pub new_price_lower: Option<Decimal>,
+    /// Proposed upper price for recenter and widen.
+    #[schema(value_type = Option<String>)]
+    pub new_price_upper: Option<Decimal>,
+    /// Expected impact over the horizon.
+    pub impact: ExpectedImpactResponse,
+}
+
+/// Expected effect of following a recommendation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExpectedImpactResponse {
+    /// Horizon in hours.
+    pub horizon_hours: u64,
+    /// Annualized volatility used.
+    pub volatility: f64,
+    /// Expected time in range of the current range (as percentage).
+    #[schema(value_type = String)]
+    pub current_time_in_range_pct: Decimal,
+    /// Expected time in range after the action (as percentage).
+    #[schema(value_type = String)]
+    pub proposed_time_in_range_pct: Decimal,
+    /// Expected fee yield of the current range relative to full range.
+    #[schema(value_type = String)]
+    pub current_fee_multiplier: Decimal,
+    /// Expected fee yield after the action relative to full range.
+    #[schema(value_type = String)]
+    pub proposed_fee_multiplier: Decimal,
+    /// Expected fees of the current range in USD, if the pool APR is known.
+    #[schema(value_type = Option<String>)]
+    pub current_fees_usd: Option<Decimal>,
+    /// Expected fees after the action in USD, if the pool APR is known.
+    #[schema(value_type = Option<String>)]
+    pub proposed_fees_usd: Option<Decimal>,
+    /// Estimated cost of the action in USD.
+    #[schema(value_type = String)]
+    pub cost_usd: Decimal,
+    /// Expected fees gained minus cost in USD, if the pool APR is known.
+    #[schema(value_type = Option<String>)]
+    pub net_benefit_usd: Option<Decimal>,
+}
+
+/// Query parameters for a position's history.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct PositionHistoryQuery {
+    /// Only entries at or after this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only entries before this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A lifecycle event of a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct LifecycleEventResponse {
+    /// Event ID.
+    pub id: String,
+    /// Event type (PositionOpened, Rebalanced, FeesCollected, ...).
+    pub event_type: String,
+    /// When the event occurred.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Transaction signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Event-specific data.
+    #[schema(value_type = Object)]
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub data: serde_json::Value,
+}
+
+/// A recorded PnL snapshot of a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct PositionSnapshotResponse {
+    /// When the snapshot was taken.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Whether the position was in range.
+    pub in_range: bool,
+    /// Position value in USD.
+    #[schema(value_type = String)]
+    pub value_usd: Decimal,
+    /// Fees earned in USD.
+    #[schema(value_type = String)]
+    pub fees_usd: Decimal,
+    /// IL percentage.
+    #[schema(value_type = String)]
+    pub il_pct: Decimal,
+    /// Net PnL in USD.
+    #[schema(value_type = String)]
+    pub net_pnl_usd: Decimal,
+}
+
+/// History of a position.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct PositionHistoryResponse {
+    /// Position address.
+    pub address: String,
+    /// Lifecycle events in chronological order.
+    pub events: Vec<LifecycleEventResponse>,
+    /// PnL snapshots in chronological order (empty without a database).
+    pub snapshots: Vec<PositionSnapshotResponse>,
+}
+
+/// Position status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 #[serde(rename_all = "snake_case")]
 pub enum PositionStatus {
     /// Position is active.
@@ -142,6 +366,12 @@ pub struct CreateStrategyRequest {
     /// Whether to run in dry-run mode.
     #[serde(default)]
     pub dry_run: bool,
+    /// Whether to fill decisions against live quotes without sending transactions.
+    #[serde(default)]
+    pub paper_trading: bool,
+    /// Whether to record decisions for manual execution instead of executing them.
+    #[serde(default)]
+    pub shadow: bool,
 }
 
 /// Strategy type.
@@ -175,9 +405,97 @@ pub struct StrategyParameters {
     /// Evaluation interval in seconds.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eval_interval_secs: Option<u64>,
+    /// Cron expression for evaluations, overriding the interval
+    /// (e.g. `5 0,8,16 * * *`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eval_cron: Option<String>,
+    /// IANA timezone cron expressions are evaluated in (default UTC).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
     /// Minimum rebalance interval in hours.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_rebalance_interval_hours: Option<u64>,
+    /// Percentage the price must move beyond the range before rebalancing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub hysteresis_margin_pct: Option<Decimal>,
+    /// Consecutive evaluations a rebalance condition must hold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_evaluations: Option<u32>,
+    /// Hard cooldown after a rebalance in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebalance_cooldown_secs: Option<u64>,
+    /// Auto-compounding settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_compound: Option<AutoCompoundSettings>,
+    /// Stop-loss settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<StopLossSettings>,
+}
+
+/// Auto-compounding settings for a strategy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct AutoCompoundSettings {
+    /// Whether auto-compounding is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interval between compounding checks in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u64>,
+    /// Cron expression for compounding checks, overriding the interval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    /// Minimum accrued fees in USD before compounding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub min_fees_usd: Option<Decimal>,
+    /// Minimum ratio of accrued fees to transaction cost.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub min_profit_multiplier: Option<Decimal>,
+    /// Estimated cost per transaction in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub tx_cost_usd: Option<Decimal>,
+}
+
+/// Stop-loss settings for a strategy.
+///
+/// Percentages are given as whole numbers (e.g. `20` for 20%).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StopLossSettings {
+    /// Whether stop-loss exits are enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum impermanent loss percentage before exiting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub max_il_pct: Option<Decimal>,
+    /// Maximum drawdown percentage from the peak position value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub max_drawdown_pct: Option<Decimal>,
+    /// Exit if the pool price falls below this level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub min_price: Option<Decimal>,
+    /// Exit if the pool price rises above this level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub max_price: Option<Decimal>,
+    /// Whether to swap withdrawn tokens to the quote token.
+    #[serde(default)]
+    pub swap_to_quote: bool,
+    /// Whether exits must be confirmed via the API before executing.
+    #[serde(default = "default_true")]
+    pub require_confirmation: bool,
+    /// Seconds an exit may await confirmation before escalating.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub escalate_after_secs: Option<u64>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// Strategy response.
@@ -197,6 +515,12 @@ pub struct StrategyResponse {
     pub running: bool,
     /// Whether in dry-run mode.
     pub dry_run: bool,
+    /// Whether in paper trading mode.
+    pub paper_trading: bool,
+    /// Whether in shadow mode.
+    pub shadow: bool,
+    /// Owning tenant; absent for operator strategies.
+    pub tenant: Option<String>,
     /// Created timestamp.
     #[schema(value_type = String)]
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -235,17 +559,46 @@ pub struct StrategyPerformanceResponse {
     pub rebalance_count: u32,
     /// Total transaction costs in lamports.
     pub total_tx_costs_lamports: u64,
+    /// Total hedge funding paid in USD (negative if received).
+    #[schema(value_type = String)]
+    pub total_funding_usd: Decimal,
     /// Win rate percentage.
     #[schema(value_type = String)]
     pub win_rate_pct: Decimal,
 }
 
+/// Emergency exit awaiting confirmation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PendingExitResponse {
+    /// Position address.
+    pub position_address: String,
+    /// Limit that triggered the exit.
+    pub trigger: String,
+    /// Whether proceeds will be swapped to the quote token.
+    pub swap_to_quote: bool,
+    /// When the breach was first detected.
+    #[schema(value_type = String)]
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+    /// Whether the alert has been escalated.
+    pub escalated: bool,
+}
+
+/// List of pending exits response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListPendingExitsResponse {
+    /// Pending exits.
+    pub exits: Vec<PendingExitResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
 // ============================================================================
 // Pool Models
 // ============================================================================
 
 /// Pool response.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct PoolResponse {
     /// Pool address.
     pub address: String,
@@ -289,6 +642,115 @@ pub struct ListPoolsResponse {
     pub total: usize,
 }
 
+/// Field to sort pool search results by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolSortField {
+    /// Estimated fee APR.
+    #[default]
+    FeeApr,
+    /// Total value locked.
+    Tvl,
+    /// 24h volume.
+    Volume,
+    /// Fee tier.
+    FeeTier,
+}
+
+/// Sort order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Ascending.
+    Asc,
+    /// Descending.
+    #[default]
+    Desc,
+}
+
+/// Query parameters for pool search.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct PoolSearchQuery {
+    /// Token symbol or mint on either side of the pool.
+    pub token_a: Option<String>,
+    /// Second token symbol or mint on either side of the pool.
+    pub token_b: Option<String>,
+    /// Protocol (e.g. orca, raydium).
+    pub protocol: Option<String>,
+    /// Minimum TVL in USD.
+    #[param(value_type = Option<String>)]
+    pub min_tvl: Option<Decimal>,
+    /// Minimum 24h volume in USD.
+    #[param(value_type = Option<String>)]
+    pub min_volume: Option<Decimal>,
+    /// Minimum estimated fee APR, as a fraction.
+    #[param(value_type = Option<String>)]
+    pub min_fee_apr: Option<Decimal>,
+    /// Maximum fee tier in basis points.
+    pub max_fee_bps: Option<u32>,
+    /// Sort field (default fee_apr).
+    #[serde(default)]
+    pub sort: PoolSortField,
+    /// Sort order (default desc).
+    #[serde(default)]
+    pub order: SortOrder,
+    /// Page number, starting at 1.
+    #[serde(default = "default_page")]
+    pub page: usize,
+    /// Results per page.
+    #[serde(default = "default_per_page")]
+    pub per_page: usize,
+}
+
+fn default_page() -> usize {
+    1
+}
+
+fn default_per_page() -> usize {
+    20
+}
+
+/// Pool search result.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolSearchResultResponse {
+    /// Pool address.
+    pub address: String,
+    /// Protocol name.
+    pub protocol: String,
+    /// Token A mint.
+    pub token_mint_a: String,
+    /// Token B mint.
+    pub token_mint_b: String,
+    /// Token A symbol.
+    pub symbol_a: String,
+    /// Token B symbol.
+    pub symbol_b: String,
+    /// Fee tier in basis points.
+    pub fee_rate_bps: u32,
+    /// TVL in USD, if recorded.
+    #[schema(value_type = Option<String>)]
+    pub tvl_usd: Option<Decimal>,
+    /// 24h volume in USD.
+    #[schema(value_type = String)]
+    pub volume_24h_usd: Decimal,
+    /// Estimated fee APR from 24h volume, as a fraction.
+    #[schema(value_type = Option<String>)]
+    pub fee_apr: Option<Decimal>,
+}
+
+/// Pool search response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolSearchResponse {
+    /// Pools on this page.
+    pub pools: Vec<PoolSearchResultResponse>,
+    /// Total matching pools.
+    pub total: usize,
+    /// Page number.
+    pub page: usize,
+    /// Results per page.
+    pub per_page: usize,
+}
+
 /// Pool state response.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PoolStateResponse {
@@ -318,6 +780,7 @@ pub struct PoolStateResponse {
 
 /// Portfolio analytics response.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct PortfolioAnalyticsResponse {
     /// Total value in USD.
     #[schema(value_type = String)]
@@ -346,6 +809,174 @@ pub struct PortfolioAnalyticsResponse {
     pub worst_position: Option<String>,
 }
 
+/// Query parameters for portfolio history.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct PortfolioHistoryQuery {
+    /// Window of history, e.g. `24h`, `7d` or `30d` (default `30d`).
+    #[serde(default = "default_history_window")]
+    pub window: String,
+}
+
+fn default_history_window() -> String {
+    "30d".to_string()
+}
+
+/// A point in the portfolio history.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct PortfolioHistoryPoint {
+    /// Start of the interval.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Total value in USD.
+    #[schema(value_type = String)]
+    pub total_value_usd: Decimal,
+    /// Net PnL in USD.
+    #[schema(value_type = String)]
+    pub net_pnl_usd: Decimal,
+    /// Fees earned in USD.
+    #[schema(value_type = String)]
+    pub fees_usd: Decimal,
+    /// Value-weighted IL percentage.
+    #[schema(value_type = String)]
+    pub il_pct: Decimal,
+    /// Number of positions.
+    pub positions: u32,
+    /// Number of positions in range.
+    pub positions_in_range: u32,
+}
+
+/// A rebalance in the portfolio history.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct PortfolioRebalanceEvent {
+    /// Position address.
+    pub position_address: String,
+    /// When the rebalance happened.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Lower tick before the rebalance.
+    pub old_tick_lower: i32,
+    /// Upper tick before the rebalance.
+    pub old_tick_upper: i32,
+    /// Lower tick after the rebalance.
+    pub new_tick_lower: i32,
+    /// Upper tick after the rebalance.
+    pub new_tick_upper: i32,
+    /// Transaction cost in lamports.
+    pub tx_cost_lamports: i64,
+    /// Reason for the rebalance.
+    pub reason: String,
+}
+
+/// Portfolio history response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct PortfolioHistoryResponse {
+    /// Requested window.
+    pub window: String,
+    /// Interval between points in seconds.
+    pub interval_secs: i64,
+    /// Points in chronological order.
+    pub points: Vec<PortfolioHistoryPoint>,
+    /// Rebalances in the window in chronological order.
+    pub rebalances: Vec<PortfolioRebalanceEvent>,
+    /// Number of fee collections in the window.
+    pub fee_collections: u32,
+    /// Fees collected in the window in USD.
+    #[schema(value_type = String)]
+    pub fees_collected_usd: Decimal,
+}
+
+/// What a leaderboard ranks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardKind {
+    /// Pools with recorded positions.
+    #[default]
+    Pool,
+    /// Stored strategies.
+    Strategy,
+}
+
+impl LeaderboardKind {
+    /// Gets the stored name of the kind.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pool => "pool",
+            Self::Strategy => "strategy",
+        }
+    }
+}
+
+/// Query parameters for a leaderboard.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct LeaderboardQuery {
+    /// What to rank (default `pool`).
+    #[serde(default)]
+    pub kind: LeaderboardKind,
+    /// Window in days: 7 or 30 (default 30).
+    #[serde(default = "default_leaderboard_window")]
+    pub window_days: u32,
+    /// Maximum number of entries (default 20).
+    #[serde(default = "default_leaderboard_limit")]
+    pub limit: usize,
+}
+
+fn default_leaderboard_window() -> u32 {
+    30
+}
+
+fn default_leaderboard_limit() -> usize {
+    20
+}
+
+/// A ranked pool or strategy.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardEntryResponse {
+    /// Rank, starting at 1.
+    pub rank: u32,
+    /// Pool address or strategy ID.
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Number of positions measured.
+    pub positions: usize,
+    /// Average capital deployed in USD.
+    #[schema(value_type = String)]
+    pub avg_value_usd: Decimal,
+    /// Fees earned over the window in USD.
+    #[schema(value_type = String)]
+    pub fees_usd: Decimal,
+    /// Realized fee APR (as percentage).
+    #[schema(value_type = String)]
+    pub fee_apr_pct: Decimal,
+    /// Fees net of the change in impermanent loss over the window, relative
+    /// to average capital (as percentage).
+    #[schema(value_type = String)]
+    pub vs_hodl_pct: Decimal,
+    /// Share of snapshots in range (as percentage).
+    #[schema(value_type = String)]
+    pub time_in_range_pct: Decimal,
+}
+
+/// Leaderboard response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardResponse {
+    /// What is ranked.
+    pub kind: LeaderboardKind,
+    /// Window in days.
+    pub window_days: u32,
+    /// When the ranking was computed; absent before the first run.
+    #[schema(value_type = Option<String>)]
+    pub computed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Entries in rank order.
+    pub entries: Vec<LeaderboardEntryResponse>,
+}
+
 /// Simulation request.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SimulationRequest {
@@ -437,12 +1068,86 @@ pub enum ServiceStatus {
 /// Component health status.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ComponentHealth {
-    /// RPC connection status.
+    /// Whether any RPC endpoint is reachable.
     pub rpc: bool,
-    /// Database status.
+    /// Whether the database is reachable or not configured.
     pub database: bool,
     /// Circuit breaker status.
     pub circuit_breaker: CircuitBreakerStatus,
+    /// Whether the operator kill switch is engaged.
+    pub kill_switch_engaged: bool,
+    /// Reason for the most recent circuit breaker trip.
+    pub circuit_breaker_reason: Option<String>,
+    /// Probe of each RPC endpoint.
+    pub rpc_endpoints: Vec<RpcEndpointHealth>,
+    /// Database connectivity check.
+    pub database_check: ComponentCheck,
+    /// Market data provider reachability check.
+    pub market_data: ComponentCheck,
+    /// Strategy executor state.
+    pub executors: ExecutorHealth,
+}
+
+/// Probe of an RPC endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RpcEndpointHealth {
+    /// Endpoint URL.
+    pub url: String,
+    /// Whether the endpoint answered.
+    pub healthy: bool,
+    /// Round-trip latency in milliseconds.
+    pub latency_ms: Option<f64>,
+    /// Slot reported by the endpoint.
+    pub slot: Option<u64>,
+    /// Slots behind the most advanced endpoint.
+    pub slot_lag: Option<u64>,
+    /// Error of a failed probe.
+    pub error: Option<String>,
+}
+
+/// Status of a checked component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Component answered.
+    Up,
+    /// Component failed or timed out.
+    Down,
+    /// Component is not configured.
+    NotConfigured,
+}
+
+/// Result of checking a component.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ComponentCheck {
+    /// Check status.
+    pub status: CheckStatus,
+    /// Check latency in milliseconds.
+    pub latency_ms: Option<f64>,
+    /// Error of a failed check.
+    pub error: Option<String>,
+}
+
+impl ComponentCheck {
+    /// A component that is not configured.
+    pub fn not_configured() -> Self {
+        Self {
+            status: CheckStatus::NotConfigured,
+            latency_ms: None,
+            error: None,
+        }
+    }
+}
+
+/// Strategy executor state.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutorHealth {
+    /// Strategies marked as running.
+    pub strategies_running: u32,
+    /// Executors whose evaluation loop is running.
+    pub executors_running: u32,
+    /// IDs of running strategies whose executor has stopped.
+    pub stalled: Vec<String>,
 }
 
 /// Circuit breaker status.
@@ -474,6 +1179,1381 @@ pub struct MetricsResponse {
     pub strategies_running: u32,
 }
 
+// ============================================================================
+// Emergency Models
+// ============================================================================
+
+/// Request to engage the kill switch.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KillSwitchRequest {
+    /// Reason for halting automated execution.
+    pub reason: String,
+}
+
+/// Kill switch and circuit breaker status response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KillSwitchResponse {
+    /// Circuit breaker status.
+    pub circuit_breaker: CircuitBreakerStatus,
+    /// Whether the operator kill switch is engaged.
+    pub kill_switch_engaged: bool,
+    /// Whether automated execution is currently allowed.
+    pub execution_allowed: bool,
+    /// Current consecutive failure count.
+    pub failure_count: u32,
+    /// Consecutive failures before the circuit opens.
+    pub max_failures: u32,
+    /// Reason for the most recent trip.
+    pub reason: Option<String>,
+    /// When the circuit was last tripped.
+    pub tripped_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Request to engage the portfolio stop.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioStopRequest {
+    /// Reason for stopping the portfolio.
+    pub reason: String,
+}
+
+/// Exposure to one token across all positions.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TokenExposureResponse {
+    /// Token mint.
+    pub mint: String,
+    /// Amount in whole tokens.
+    #[schema(value_type = String)]
+    pub amount: Decimal,
+    /// Value in USD.
+    #[schema(value_type = String)]
+    pub value_usd: Decimal,
+    /// Share of the portfolio value (as percentage).
+    #[schema(value_type = String)]
+    pub share_pct: Decimal,
+    /// Maximum exposure in USD, if limited.
+    #[schema(value_type = Option<String>)]
+    pub limit_usd: Option<Decimal>,
+}
+
+/// Portfolio risk limits and stop status response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioRiskResponse {
+    /// Portfolio value in USD.
+    #[schema(value_type = String)]
+    pub value_usd: Decimal,
+    /// Peak portfolio value in USD, adjusted for positions added and removed.
+    #[schema(value_type = String)]
+    pub peak_value_usd: Decimal,
+    /// Drawdown from the peak (as percentage).
+    #[schema(value_type = String)]
+    pub drawdown_pct: Decimal,
+    /// Maximum drawdown (as percentage), if limited.
+    #[schema(value_type = Option<String>)]
+    pub max_drawdown_pct: Option<Decimal>,
+    /// Default maximum exposure to a single token in USD, if limited.
+    #[schema(value_type = Option<String>)]
+    pub max_token_exposure_usd: Option<Decimal>,
+    /// Whether the portfolio stop closes all positions.
+    pub unwind_on_stop: bool,
+    /// Exposure by token, largest first.
+    pub exposures: Vec<TokenExposureResponse>,
+    /// Limits breached at the last check.
+    pub breaches: Vec<String>,
+    /// Whether the portfolio stop is engaged.
+    pub stopped: bool,
+    /// Why the portfolio stop was engaged.
+    pub stop_reason: Option<String>,
+    /// When the portfolio stop was engaged.
+    pub stopped_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Positions closed by the portfolio stop, if it unwound them.
+    pub unwound_positions: Option<usize>,
+    /// When the limits were last checked.
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// ============================================================================
+// Alert Models
+// ============================================================================
+
+/// Query parameters for listing alerts.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct AlertsQuery {
+    /// Only return alerts that have not been acknowledged.
+    #[serde(default)]
+    pub unacknowledged: bool,
+}
+
+/// Alert response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AlertResponse {
+    /// Alert ID.
+    pub id: String,
+    /// Alert level.
+    pub level: String,
+    /// Alert type.
+    pub alert_type: String,
+    /// Alert message.
+    pub message: String,
+    /// Position address (if applicable).
+    pub position_address: Option<String>,
+    /// Pool address (if applicable).
+    pub pool_address: Option<String>,
+    /// When the alert was raised.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Whether the alert has been acknowledged.
+    pub acknowledged: bool,
+    /// When the alert was acknowledged.
+    #[schema(value_type = Option<String>)]
+    pub acknowledged_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Who acknowledged the alert.
+    pub acknowledged_by: Option<String>,
+    /// Channel the alert escalates through if unacknowledged.
+    pub escalation_channel: Option<String>,
+    /// Seconds before escalation.
+    pub escalate_after_secs: Option<u64>,
+    /// When the alert was escalated.
+    #[schema(value_type = Option<String>)]
+    pub escalated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// List alerts response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListAlertsResponse {
+    /// Alerts, newest first.
+    pub alerts: Vec<AlertResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+/// Request to acknowledge an alert.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct AcknowledgeAlertRequest {
+    /// Who is acknowledging the alert.
+    #[serde(default)]
+    pub acknowledged_by: Option<String>,
+}
+
+/// Alert severity.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    /// Informational.
+    Info,
+    /// Warning.
+    #[default]
+    Warning,
+    /// Critical.
+    Critical,
+}
+
+/// Request to create or replace a custom alert rule.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateAlertRuleRequest {
+    /// Rule name (unique; an existing rule with this name is replaced).
+    pub name: String,
+    /// Condition expression, e.g. `il_pct > 3% && time_out_of_range > 2h`.
+    pub condition: String,
+    /// Alert severity.
+    #[serde(default)]
+    pub level: AlertSeverity,
+    /// Message template (`{position}`, `{il_pct}`, `{pnl_pct}`, `{pnl_usd}`,
+    /// `{fees_usd}` and `{in_range}` placeholders).
+    pub message: Option<String>,
+    /// Cooldown between alerts in seconds.
+    pub cooldown_secs: Option<u64>,
+    /// Fire only when the condition starts or stops holding.
+    #[serde(default)]
+    pub on_change: bool,
+    /// Deduplication key template.
+    pub dedup_key: Option<String>,
+}
+
+/// Alert rule response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AlertRuleResponse {
+    /// Rule name.
+    pub name: String,
+    /// Condition expression, or a description of a built-in condition.
+    pub condition: String,
+    /// Alert severity.
+    pub level: String,
+    /// Message template.
+    pub message: String,
+    /// Whether the rule is enabled.
+    pub enabled: bool,
+    /// Cooldown between alerts in seconds.
+    pub cooldown_secs: u64,
+    /// Whether the rule fires only on condition changes.
+    pub on_change: bool,
+    /// Deduplication key template.
+    pub dedup_key: Option<String>,
+}
+
+/// List alert rules response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListAlertRulesResponse {
+    /// Configured rules.
+    pub rules: Vec<AlertRuleResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+/// Request to send a test alert through the configured channels.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct TestAlertRequest {
+    /// Only test this channel (e.g. `telegram`); all channels by default.
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Alert severity.
+    #[serde(default)]
+    pub level: AlertSeverity,
+    /// Message text.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Delivery result for one channel.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChannelTestResponse {
+    /// Channel name.
+    pub channel: String,
+    /// Whether the channel receives every alert (false for escalation-only
+    /// channels).
+    pub primary: bool,
+    /// Whether the test alert was delivered.
+    pub delivered: bool,
+    /// Delivery error.
+    pub error: Option<String>,
+}
+
+/// Test alert response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TestAlertResponse {
+    /// Results per channel, sorted by name.
+    pub channels: Vec<ChannelTestResponse>,
+    /// Number of channels the alert was delivered to.
+    pub delivered: usize,
+    /// Number of channels that failed.
+    pub failed: usize,
+}
+
+// ============================================================================
+// Audit Models
+// ============================================================================
+
+/// Query parameters for the audit log.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct AuditLogQuery {
+    /// Only entries for this strategy.
+    pub strategy_id: Option<String>,
+    /// Only entries for this position.
+    pub position_address: Option<String>,
+    /// Only entries of this kind (evaluation, proposal, transaction, outcome, dry_run, shadow, reconciliation).
+    pub kind: Option<String>,
+    /// Only entries at or after this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only entries before this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Maximum number of entries returned.
+    #[serde(default = "default_audit_limit")]
+    pub limit: usize,
+}
+
+fn default_audit_limit() -> usize {
+    100
+}
+
+/// Audit log entry response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntryResponse {
+    /// Entry ID.
+    pub id: String,
+    /// When the event occurred.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Event kind.
+    pub kind: String,
+    /// Action name.
+    pub action: String,
+    /// Strategy the event belongs to.
+    pub strategy_id: Option<String>,
+    /// Position the event concerns.
+    pub position_address: Option<String>,
+    /// Transaction signature.
+    pub signature: Option<String>,
+    /// Whether the action succeeded.
+    pub success: Option<bool>,
+    /// Error message if the action failed.
+    pub error: Option<String>,
+    /// Event-specific details.
+    #[schema(value_type = Object)]
+    pub details: serde_json::Value,
+}
+
+/// List of audit log entries.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListAuditEntriesResponse {
+    /// Entries, newest first.
+    pub entries: Vec<AuditEntryResponse>,
+    /// Number of entries returned.
+    pub total: usize,
+}
+
+/// Query parameters for the request audit log.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct RequestAuditLogQuery {
+    /// Only requests by this API key ID or token subject.
+    pub subject: Option<String>,
+    /// Only requests by this tenant.
+    pub tenant: Option<String>,
+    /// Only requests with this method (POST, PUT, PATCH, DELETE).
+    pub method: Option<String>,
+    /// Only requests whose path starts with this prefix, e.g. `/api/v1/positions`.
+    pub path_prefix: Option<String>,
+    /// Only requests answered with this status code.
+    pub status: Option<u16>,
+    /// Only requests at or after this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only requests before this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Maximum number of entries returned.
+    #[serde(default = "default_audit_limit")]
+    pub limit: usize,
+}
+
+/// Request audit log entry response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RequestAuditEntryResponse {
+    /// Entry ID.
+    pub id: String,
+    /// When the request was received.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// API key ID or token subject; empty when unauthenticated.
+    pub subject: String,
+    /// Caller's role.
+    pub role: Option<String>,
+    /// Caller's tenant.
+    pub tenant: Option<String>,
+    /// HTTP method.
+    pub method: String,
+    /// Request path.
+    pub path: String,
+    /// Hex SHA-256 of the request body; absent when empty.
+    pub payload_hash: Option<String>,
+    /// Response status code.
+    pub status: u16,
+    /// Time to respond in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// List of request audit log entries.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListRequestAuditEntriesResponse {
+    /// Entries, newest first.
+    pub entries: Vec<RequestAuditEntryResponse>,
+    /// Number of entries returned.
+    pub total: usize,
+}
+
+// ============================================================================
+// Dry-Run Transcript Models
+// ============================================================================
+
+/// Query parameters for a strategy's dry-run transcript.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct TranscriptQuery {
+    /// Only steps at or after this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only steps before this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Maximum number of steps returned.
+    #[serde(default = "default_audit_limit")]
+    pub limit: usize,
+    /// Return the transcript as a file attachment.
+    #[serde(default)]
+    pub download: bool,
+}
+
+/// An instruction a dry run would have sent.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PlannedInstructionResponse {
+    /// Program the instruction targets.
+    pub program: String,
+    /// Instruction name.
+    pub instruction: String,
+    /// Instruction arguments.
+    #[schema(value_type = Object)]
+    pub args: serde_json::Value,
+}
+
+/// One action in a dry-run transcript.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TranscriptStepResponse {
+    /// Entry ID.
+    pub id: String,
+    /// When the action was planned.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Position the action concerns.
+    pub position_address: Option<String>,
+    /// Action name.
+    pub action: String,
+    /// Human-readable description.
+    pub description: String,
+    /// Pool tick when the action was planned.
+    pub pool_tick: i32,
+    /// Instructions that would have been sent, in order.
+    pub instructions: Vec<PlannedInstructionResponse>,
+    /// Expected raw token A amount.
+    pub expected_amount_a: u64,
+    /// Expected raw token B amount.
+    pub expected_amount_b: u64,
+    /// Position value in USD when planned.
+    #[schema(value_type = String)]
+    pub position_value_usd: Decimal,
+    /// Estimated transaction cost in lamports.
+    pub estimated_cost_lamports: u64,
+}
+
+/// Dry-run transcript of a strategy.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TranscriptResponse {
+    /// Strategy ID.
+    pub strategy_id: String,
+    /// Planned actions, newest first.
+    pub steps: Vec<TranscriptStepResponse>,
+    /// Number of steps returned.
+    pub total: usize,
+    /// Total estimated transaction cost of the returned steps in lamports.
+    pub total_estimated_cost_lamports: u64,
+}
+
+// ============================================================================
+// Shadow Mode Models
+// ============================================================================
+
+/// Comparison of a shadow decision with its actual outcome.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShadowReconciliationResponse {
+    /// Actual raw token A amount.
+    pub actual_amount_a: u64,
+    /// Actual raw token B amount.
+    pub actual_amount_b: u64,
+    /// Token A difference from the expected amount as a percentage.
+    #[schema(value_type = String)]
+    pub slippage_a_pct: Decimal,
+    /// Token B difference from the expected amount as a percentage.
+    #[schema(value_type = String)]
+    pub slippage_b_pct: Decimal,
+    /// Position value in USD after the action.
+    #[schema(value_type = String)]
+    pub actual_value_usd: Decimal,
+    /// Difference between the actual and expected value in USD.
+    #[schema(value_type = String)]
+    pub value_slippage_usd: Decimal,
+    /// Value difference as a percentage of the expected value.
+    #[schema(value_type = String)]
+    pub value_slippage_pct: Decimal,
+    /// Transaction cost paid in lamports.
+    pub actual_cost_lamports: u64,
+    /// Signature of the transaction that carried out the action.
+    pub signature: Option<String>,
+}
+
+/// A decision recorded in shadow mode.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShadowDecisionResponse {
+    /// Record ID, used to reconcile the decision.
+    pub id: String,
+    /// When the decision was recorded.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Position the decision concerns.
+    pub position_address: Option<String>,
+    /// Action name.
+    pub action: String,
+    /// Human-readable description.
+    pub description: String,
+    /// Pool price when the decision was made.
+    #[schema(value_type = String)]
+    pub pool_price: Decimal,
+    /// Expected raw token A amount.
+    pub expected_amount_a: u64,
+    /// Expected raw token B amount.
+    pub expected_amount_b: u64,
+    /// Position value in USD when the decision was made.
+    #[schema(value_type = String)]
+    pub expected_value_usd: Decimal,
+    /// Estimated transaction cost in lamports.
+    pub estimated_cost_lamports: u64,
+    /// Comparison with the actual outcome, once reconciled.
+    pub reconciliation: Option<ShadowReconciliationResponse>,
+}
+
+/// Aggregate model slippage over reconciled shadow decisions.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShadowSummaryResponse {
+    /// Number of recorded decisions.
+    pub decisions: usize,
+    /// Number of decisions reconciled against an outcome.
+    pub reconciled: usize,
+    /// Mean absolute value slippage as a percentage.
+    #[schema(value_type = String)]
+    pub mean_abs_value_slippage_pct: Decimal,
+    /// Largest absolute value slippage as a percentage.
+    #[schema(value_type = String)]
+    pub max_abs_value_slippage_pct: Decimal,
+    /// Sum of value differences in USD.
+    #[schema(value_type = String)]
+    pub total_value_slippage_usd: Decimal,
+    /// Sum of actual minus estimated transaction costs in lamports.
+    pub cost_difference_lamports: i64,
+}
+
+/// Shadow-mode decisions of a strategy with their reconciliations.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShadowReportResponse {
+    /// Strategy ID.
+    pub strategy_id: String,
+    /// Recorded decisions, newest first.
+    pub decisions: Vec<ShadowDecisionResponse>,
+    /// Aggregate model slippage.
+    pub summary: ShadowSummaryResponse,
+}
+
+/// Request to reconcile a shadow decision with its actual outcome.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReconcileShadowRequest {
+    /// Signature of the transaction that carried out the action.
+    pub signature: Option<String>,
+    /// Actual raw token A amount withdrawn, deposited or collected.
+    #[serde(default)]
+    pub amount_a: u64,
+    /// Actual raw token B amount withdrawn, deposited or collected.
+    #[serde(default)]
+    pub amount_b: u64,
+    /// Position value in USD after the action.
+    #[schema(value_type = String)]
+    pub value_usd: Decimal,
+    /// Transaction cost paid in lamports.
+    #[serde(default)]
+    pub cost_lamports: u64,
+}
+
+// ============================================================================
+// Backtest Models
+// ============================================================================
+
+/// Request to run a backtest against stored price history.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BacktestRequest {
+    /// Pool address.
+    pub pool_address: String,
+    /// Lower price of the initial range.
+    #[schema(value_type = String)]
+    pub lower_price: Decimal,
+    /// Upper price of the initial range.
+    #[schema(value_type = String)]
+    pub upper_price: Decimal,
+    /// Rebalancing strategy (default static range).
+    #[serde(default)]
+    pub strategy_type: Option<StrategyType>,
+    /// Start date.
+    #[schema(value_type = String)]
+    pub start_date: chrono::NaiveDate,
+    /// End date (inclusive).
+    #[schema(value_type = String)]
+    pub end_date: chrono::NaiveDate,
+    /// Initial capital in USD.
+    #[schema(value_type = String)]
+    pub initial_capital_usd: Decimal,
+    /// Cost per rebalance in USD.
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub tx_cost_usd: Decimal,
+    /// Candles between rebalances (periodic strategy).
+    #[serde(default = "default_rebalance_interval")]
+    pub rebalance_interval: u64,
+    /// Price move from the range center that triggers a rebalance, as a
+    /// fraction (threshold strategy).
+    #[serde(default = "default_backtest_threshold")]
+    #[schema(value_type = String)]
+    pub threshold_pct: Decimal,
+    /// Impermanent loss that triggers a rebalance, as a fraction (IL limit
+    /// strategy).
+    #[serde(default = "default_backtest_threshold")]
+    #[schema(value_type = String)]
+    pub max_il_pct: Decimal,
+    /// Share of pool liquidity held, as a fraction. Defaults to capital
+    /// divided by the recorded pool liquidity of each candle.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub liquidity_share: Option<Decimal>,
+}
+
+fn default_rebalance_interval() -> u64 {
+    24
+}
+
+fn default_backtest_threshold() -> Decimal {
+    Decimal::new(5, 2)
+}
+
+/// Backtest results.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BacktestResultResponse {
+    /// Number of simulated candles.
+    pub total_steps: u64,
+    /// Price at the first candle.
+    #[schema(value_type = String)]
+    pub entry_price: Decimal,
+    /// Price at the last candle.
+    #[schema(value_type = String)]
+    pub final_price: Decimal,
+    /// Final position value in USD.
+    #[schema(value_type = String)]
+    pub final_value_usd: Decimal,
+    /// Net PnL in USD.
+    #[schema(value_type = String)]
+    pub final_pnl_usd: Decimal,
+    /// Final impermanent loss, as a fraction.
+    #[schema(value_type = String)]
+    pub final_il_pct: Decimal,
+    /// Fees earned in USD.
+    #[schema(value_type = String)]
+    pub total_fees_usd: Decimal,
+    /// Share of candles in range, as a fraction.
+    #[schema(value_type = String)]
+    pub time_in_range_pct: Decimal,
+    /// Number of rebalances.
+    pub rebalance_count: u32,
+    /// Total rebalance cost in USD.
+    #[schema(value_type = String)]
+    pub total_rebalance_cost_usd: Decimal,
+    /// Maximum drawdown, as a fraction.
+    #[schema(value_type = String)]
+    pub max_drawdown_pct: Decimal,
+    /// Value of holding the initial tokens in USD.
+    #[schema(value_type = String)]
+    pub hodl_value_usd: Decimal,
+    /// Final value minus the HODL value in USD.
+    #[schema(value_type = String)]
+    pub vs_hodl_usd: Decimal,
+}
+
+/// Background job state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// Waiting for a free worker.
+    Queued,
+    /// Running.
+    Running,
+    /// Finished successfully.
+    Completed,
+    /// Finished with an error.
+    Failed,
+    /// Cancelled before finishing.
+    Cancelled,
+}
+
+/// Backtest job response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BacktestJobResponse {
+    /// Job ID.
+    pub id: String,
+    /// Job state.
+    pub status: JobState,
+    /// Progress percentage.
+    pub progress_pct: u8,
+    /// Submitted request.
+    pub request: BacktestRequest,
+    /// Submitted timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Started timestamp.
+    #[schema(value_type = Option<String>)]
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Finished timestamp.
+    #[schema(value_type = Option<String>)]
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Results, once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<BacktestResultResponse>,
+    /// Error, if failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Optimization Models
+// ============================================================================
+
+/// Objective to optimize for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectiveType {
+    /// Maximize net PnL.
+    #[default]
+    NetPnl,
+    /// Maximize fees earned.
+    Fees,
+    /// Maximize the Sharpe ratio.
+    Sharpe,
+    /// Minimize impermanent loss.
+    MinIl,
+    /// Maximize time in range.
+    TimeInRange,
+    /// Maximize return net of weighted impermanent loss.
+    RiskAdjusted,
+}
+
+/// Constraints limiting the optimization search space.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct OptimizationConstraintsRequest {
+    /// Minimum range width as a fraction of the price.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub min_range_width: Option<Decimal>,
+    /// Maximum range width as a fraction of the price.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub max_range_width: Option<Decimal>,
+    /// Highest impermanent loss threshold searched by the threshold and IL
+    /// limit strategies, as a fraction.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub max_il_threshold: Option<Decimal>,
+    /// Minimum rebalance interval in hours.
+    #[serde(default)]
+    pub min_rebalance_interval: Option<u64>,
+    /// Maximum rebalance interval in hours.
+    #[serde(default)]
+    pub max_rebalance_interval: Option<u64>,
+    /// Maximum number of rebalances over the horizon.
+    #[serde(default)]
+    pub max_rebalances: Option<u32>,
+}
+
+/// Request to optimize a position's range and rebalancing parameters.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizeRequest {
+    /// Pool address.
+    pub pool_address: String,
+    /// Objective to optimize for.
+    #[serde(default)]
+    pub objective: ObjectiveType,
+    /// Search constraints.
+    #[serde(default)]
+    pub constraints: OptimizationConstraintsRequest,
+    /// Monte Carlo iterations per candidate range.
+    #[serde(default = "default_optimize_iterations")]
+    pub iterations: usize,
+    /// Simulated horizon in days.
+    #[serde(default = "default_optimize_horizon_days")]
+    pub horizon_days: u32,
+    /// Start of the price history window used for volatility and volume.
+    #[schema(value_type = String)]
+    pub start_date: chrono::NaiveDate,
+    /// End of the price history window (inclusive).
+    #[schema(value_type = String)]
+    pub end_date: chrono::NaiveDate,
+    /// Cost per rebalance in USD.
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub tx_cost_usd: Decimal,
+    /// Number of ranked candidates to return.
+    #[serde(default = "default_optimize_top_n")]
+    pub top_n: usize,
+    /// Seed of the Monte Carlo price paths. Seeded runs are reproducible,
+    /// and repeating one over unchanged history returns the cached result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+fn default_optimize_iterations() -> usize {
+    100
+}
+
+fn default_optimize_horizon_days() -> u32 {
+    30
+}
+
+fn default_optimize_top_n() -> usize {
+    10
+}
+
+/// Ranked price range candidate.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RangeCandidateResponse {
+    /// Rank, starting at 1.
+    pub rank: usize,
+    /// Range width as a fraction of the current price.
+    #[schema(value_type = String)]
+    pub range_width_pct: Decimal,
+    /// Lower price of the range.
+    #[schema(value_type = String)]
+    pub lower_price: Decimal,
+    /// Upper price of the range.
+    #[schema(value_type = String)]
+    pub upper_price: Decimal,
+    /// Expected fees.
+    #[schema(value_type = String)]
+    pub expected_fees: Decimal,
+    /// Expected impermanent loss.
+    #[schema(value_type = String)]
+    pub expected_il: Decimal,
+    /// Expected net PnL.
+    #[schema(value_type = String)]
+    pub expected_pnl: Decimal,
+    /// Objective score.
+    #[schema(value_type = String)]
+    pub score: Decimal,
+}
+
+/// Ranked rebalancing parameter candidate.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ParameterCandidateResponse {
+    /// Rank, starting at 1.
+    pub rank: usize,
+    /// Rebalancing strategy.
+    pub strategy_type: StrategyType,
+    /// Strategy parameters.
+    #[schema(value_type = Object)]
+    pub parameters: serde_json::Value,
+    /// Expected fees net of rebalance costs.
+    #[schema(value_type = String)]
+    pub expected_fees: Decimal,
+    /// Expected impermanent loss.
+    #[schema(value_type = String)]
+    pub expected_il: Decimal,
+    /// Expected number of rebalances.
+    pub expected_rebalances: u32,
+    /// Objective score.
+    #[schema(value_type = String)]
+    pub score: Decimal,
+}
+
+/// Optimization results.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizeResultResponse {
+    /// Latest price in the data window.
+    #[schema(value_type = String)]
+    pub current_price: Decimal,
+    /// Annualized volatility estimated from the data window.
+    #[schema(value_type = String)]
+    pub volatility: Decimal,
+    /// Average daily volume in USD over the data window.
+    #[schema(value_type = String)]
+    pub daily_volume_usd: Decimal,
+    /// Pool fee rate.
+    #[schema(value_type = String)]
+    pub fee_rate: Decimal,
+    /// Range candidates, best first.
+    pub range_candidates: Vec<RangeCandidateResponse>,
+    /// Rebalancing parameter candidates for the best range, best first.
+    pub parameter_candidates: Vec<ParameterCandidateResponse>,
+}
+
+/// Optimization job response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizeJobResponse {
+    /// Job ID.
+    pub id: String,
+    /// Job state.
+    pub status: JobState,
+    /// Progress percentage.
+    pub progress_pct: u8,
+    /// Submitted request.
+    pub request: OptimizeRequest,
+    /// Submitted timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Started timestamp.
+    #[schema(value_type = Option<String>)]
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Finished timestamp.
+    #[schema(value_type = Option<String>)]
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Results, once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<OptimizeResultResponse>,
+    /// Error, if failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Market Data Models
+// ============================================================================
+
+/// Query parameters for market data.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct MarketQuery {
+    /// Candle resolution in seconds (60, 300, 900, 3600, 14400 or 86400).
+    #[serde(default = "default_market_resolution")]
+    pub resolution: u64,
+    /// Number of days of history.
+    #[serde(default = "default_market_days")]
+    pub days: u64,
+}
+
+fn default_market_resolution() -> u64 {
+    3600
+}
+
+fn default_market_days() -> u64 {
+    30
+}
+
+/// Price candle.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CandleResponse {
+    /// Candle start as a Unix timestamp.
+    pub timestamp: u64,
+    /// Opening price.
+    #[schema(value_type = String)]
+    pub open: Decimal,
+    /// Highest price.
+    #[schema(value_type = String)]
+    pub high: Decimal,
+    /// Lowest price.
+    #[schema(value_type = String)]
+    pub low: Decimal,
+    /// Closing price.
+    #[schema(value_type = String)]
+    pub close: Decimal,
+    /// Volume in the base token.
+    #[schema(value_type = String)]
+    pub volume: Decimal,
+}
+
+/// Candles response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CandlesResponse {
+    /// Pair (base-quote).
+    pub pair: String,
+    /// Candle resolution in seconds.
+    pub resolution: u64,
+    /// Candles, oldest first.
+    pub candles: Vec<CandleResponse>,
+}
+
+/// Suggested LP range.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SuggestedRangeResponse {
+    /// Range name (conservative, moderate, wide).
+    pub name: String,
+    /// How the range was derived.
+    pub description: String,
+    /// Lower price.
+    #[schema(value_type = String)]
+    pub lower_price: Decimal,
+    /// Upper price.
+    #[schema(value_type = String)]
+    pub upper_price: Decimal,
+}
+
+/// Market analysis of a pair.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MarketAnalysisResponse {
+    /// Pair (base-quote).
+    pub pair: String,
+    /// Analyzed period in days.
+    pub period_days: u64,
+    /// Candle resolution in seconds.
+    pub resolution: u64,
+    /// Number of candles analyzed.
+    pub data_points: usize,
+    /// Latest price.
+    #[schema(value_type = String)]
+    pub current_price: Decimal,
+    /// Price at the start of the period.
+    #[schema(value_type = String)]
+    pub start_price: Decimal,
+    /// Highest close in the period.
+    #[schema(value_type = String)]
+    pub high_price: Decimal,
+    /// Lowest close in the period.
+    #[schema(value_type = String)]
+    pub low_price: Decimal,
+    /// Average close in the period.
+    #[schema(value_type = String)]
+    pub avg_price: Decimal,
+    /// Price change over the period in percent.
+    #[schema(value_type = String)]
+    pub price_change_pct: Decimal,
+    /// Annualized volatility.
+    #[schema(value_type = String)]
+    pub volatility_annual: Decimal,
+    /// Daily volatility.
+    #[schema(value_type = String)]
+    pub volatility_daily: Decimal,
+    /// Total volume in the base token.
+    #[schema(value_type = String)]
+    pub total_volume: Decimal,
+    /// Average daily volume in the base token.
+    #[schema(value_type = String)]
+    pub avg_daily_volume: Decimal,
+    /// Suggested LP ranges.
+    pub suggested_ranges: Vec<SuggestedRangeResponse>,
+}
+
+// ============================================================================
+// Job Models
+// ============================================================================
+
+/// Position value at a step of a running job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct EquityPoint {
+    /// Step number.
+    pub step: u64,
+    /// Position value in USD.
+    #[schema(value_type = String)]
+    pub value_usd: Decimal,
+}
+
+/// Detailed progress of a running job.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct JobProgress {
+    /// Steps completed.
+    pub step: u64,
+    /// Total steps.
+    pub total_steps: u64,
+    /// Best candidate found so far, for optimizations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub best_candidate: Option<serde_json::Value>,
+    /// Equity curve so far, for backtests; one point per percent.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub equity_curve: Vec<EquityPoint>,
+}
+
+/// Background job response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobResponse {
+    /// Job ID.
+    pub id: String,
+    /// Job kind (e.g. `backtest`).
+    pub kind: String,
+    /// Job state.
+    pub status: JobState,
+    /// Progress percentage.
+    pub progress_pct: u8,
+    /// Detailed progress, while running.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<JobProgress>,
+    /// Submitted parameters.
+    #[schema(value_type = Object)]
+    pub params: serde_json::Value,
+    /// Submitted timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Started timestamp.
+    #[schema(value_type = Option<String>)]
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Finished timestamp.
+    #[schema(value_type = Option<String>)]
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Results, once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<serde_json::Value>,
+    /// Error, if failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Export Models
+// ============================================================================
+
+/// File format of a data export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFileFormat {
+    /// Comma-separated values.
+    #[default]
+    Csv,
+    /// Apache Parquet.
+    Parquet,
+}
+
+/// Query parameters of data exports.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    /// File format (default `csv`).
+    #[serde(default)]
+    pub format: ExportFileFormat,
+}
+
+// ============================================================================
+// API Key Models
+// ============================================================================
+
+/// API key role.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyRole {
+    /// Read-only access.
+    #[default]
+    ReadOnly,
+    /// Can open, close and rebalance positions and manage strategies.
+    Trader,
+    /// Full access, including key management.
+    Admin,
+}
+
+/// Request to create an API key.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Human-readable name.
+    pub name: String,
+    /// Granted role.
+    #[serde(default)]
+    pub role: ApiKeyRole,
+    /// Strategy IDs the key may modify; empty for all strategies.
+    ///
+    /// Scoped keys cannot use mutating routes outside these strategies.
+    #[serde(default)]
+    pub strategies: Vec<String>,
+    /// Tenant the key acts for; absent for operator keys.
+    ///
+    /// Tenant keys only see the tenant's strategies and positions.
+    #[serde(default)]
+    pub tenant: Option<String>,
+}
+
+/// API key response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyResponse {
+    /// Key ID.
+    pub id: String,
+    /// Human-readable name.
+    pub name: String,
+    /// Granted role.
+    pub role: ApiKeyRole,
+    /// Strategy IDs the key may modify; empty for all strategies.
+    pub strategies: Vec<String>,
+    /// Tenant the key acts for; absent for operator keys.
+    pub tenant: Option<String>,
+    /// Leading characters of the secret.
+    pub prefix: String,
+    /// Created timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Last used timestamp.
+    #[schema(value_type = Option<String>)]
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Created API key with its secret.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    /// Created key.
+    pub key: ApiKeyResponse,
+    /// Secret to send in the `X-API-Key` header. Only returned once.
+    pub secret: String,
+}
+
+/// List API keys response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListApiKeysResponse {
+    /// API keys.
+    pub keys: Vec<ApiKeyResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+// ============================================================================
+// Webhook Models
+// ============================================================================
+
+/// Event type delivered to webhooks.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    /// Any position update.
+    PositionUpdate,
+    /// A rebalance was executed (not simulated).
+    RebalanceExecuted,
+    /// Strategy started, stopped, or updated.
+    StrategyUpdate,
+    /// Alert raised.
+    Alert,
+}
+
+/// Request to register a webhook.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// Callback URL.
+    pub url: String,
+    /// Event types to deliver.
+    pub events: Vec<WebhookEventType>,
+    /// Optional description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Signing secret; generated if omitted.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// Webhook response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookResponse {
+    /// Webhook ID.
+    pub id: String,
+    /// Callback URL.
+    pub url: String,
+    /// Subscribed event types.
+    pub events: Vec<WebhookEventType>,
+    /// Optional description.
+    pub description: Option<String>,
+    /// Created timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Successful deliveries.
+    pub deliveries: u64,
+    /// Deliveries that failed after all retries.
+    pub failures: u64,
+}
+
+/// Registered webhook with its signing secret.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateWebhookResponse {
+    /// Registered webhook.
+    pub webhook: WebhookResponse,
+    /// Secret used to sign deliveries. Only returned once.
+    pub secret: String,
+}
+
+/// List webhooks response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListWebhooksResponse {
+    /// Webhooks.
+    pub webhooks: Vec<WebhookResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+// ============================================================================
+// Wallet Models
+// ============================================================================
+
+/// How a wallet signs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletKind {
+    /// Passphrase-encrypted keystore file.
+    Keystore,
+    /// External signing service.
+    RemoteSigner,
+}
+
+/// Encrypted keystore to load a wallet from.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeystoreSource {
+    /// Keystore file path on the server.
+    pub path: String,
+    /// Passphrase.
+    #[serde(default)]
+    pub passphrase: Option<String>,
+    /// Environment variable holding the passphrase, preferred over
+    /// sending it in the request.
+    #[serde(default)]
+    pub passphrase_env: Option<String>,
+}
+
+/// Remote signing service a wallet delegates to.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemoteSignerSource {
+    /// Signing endpoint URL.
+    pub url: String,
+    /// Public key the service signs for.
+    pub pubkey: String,
+    /// Bearer token for the service.
+    #[serde(default)]
+    pub auth_token: Option<String>,
+}
+
+/// Request to register a wallet.
+///
+/// Exactly one of `keystore` and `remote_signer` must be set.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RegisterWalletRequest {
+    /// Unique wallet label.
+    pub label: String,
+    /// Encrypted keystore.
+    #[serde(default)]
+    pub keystore: Option<KeystoreSource>,
+    /// Remote signer.
+    #[serde(default)]
+    pub remote_signer: Option<RemoteSignerSource>,
+    /// Owning tenant; the positions the wallet owns are visible only to it.
+    #[serde(default)]
+    pub tenant: Option<String>,
+}
+
+/// Wallet response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WalletResponse {
+    /// Wallet label.
+    pub label: String,
+    /// Public key.
+    pub pubkey: String,
+    /// How the wallet signs.
+    pub kind: WalletKind,
+    /// Keystore path or signer URL.
+    pub source: String,
+    /// Owning tenant; absent for operator wallets.
+    pub tenant: Option<String>,
+    /// Strategies bound to the wallet.
+    pub strategies: Vec<String>,
+    /// Registered timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// List wallets response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListWalletsResponse {
+    /// Wallets.
+    pub wallets: Vec<WalletResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+/// Wallet balance response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WalletBalanceResponse {
+    /// Wallet label.
+    pub label: String,
+    /// Public key.
+    pub pubkey: String,
+    /// Balance in lamports.
+    pub lamports: u64,
+    /// Balance in SOL.
+    #[schema(value_type = String)]
+    pub sol: Decimal,
+}
+
+/// Request to bind a wallet to a strategy.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BindWalletRequest {
+    /// Strategy ID.
+    pub strategy_id: String,
+}
+
 // ============================================================================
 // Common Models
 // ============================================================================
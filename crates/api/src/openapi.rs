@@ -4,11 +4,32 @@
 
 use crate::handlers;
 use crate::models::{
-    CreateStrategyRequest, HealthResponse, ListPoolsResponse, ListPositionsResponse,
-    ListStrategiesResponse, MessageResponse, MetricsResponse, OpenPositionRequest, PnLResponse,
-    PoolResponse, PoolStateResponse, PortfolioAnalyticsResponse, PositionResponse,
-    RebalanceRequest, SimulationRequest, SimulationResponse, StrategyPerformanceResponse,
-    StrategyResponse,
+    AcknowledgeAlertRequest, AlertResponse, AlertRuleResponse, AlertSeverity, ApiKeyResponse,
+    ApiKeyRole, AuditEntryResponse, AutoCompoundSettings, BacktestJobResponse, BacktestRequest,
+    BacktestResultResponse, BatchItemResult, BatchOperation, BatchPositionItem,
+    BatchPositionRequest, BatchPositionResponse, BindWalletRequest, CandleResponse,
+    CandlesResponse, ChannelTestResponse, CreateAlertRuleRequest, CreateApiKeyRequest,
+    CreateApiKeyResponse, CreateStrategyRequest, CreateWebhookRequest, CreateWebhookResponse,
+    EquityPoint, ExpectedImpactResponse, ExportFileFormat, HealthResponse, JobProgress,
+    JobResponse, JobState, KeystoreSource, KillSwitchRequest, KillSwitchResponse,
+    LeaderboardEntryResponse, LeaderboardKind, LeaderboardResponse, LifecycleEventResponse,
+    ListAlertRulesResponse, ListAlertsResponse, ListApiKeysResponse, ListAuditEntriesResponse,
+    ListPendingExitsResponse, ListPoolsResponse, ListPositionsResponse,
+    ListRequestAuditEntriesResponse, ListStrategiesResponse, ListWalletsResponse,
+    ListWebhooksResponse, MarketAnalysisResponse, MessageResponse, MetricsResponse, ObjectiveType,
+    OpenPositionRequest, OptimizationConstraintsRequest, OptimizeJobResponse, OptimizeRequest,
+    OptimizeResultResponse, ParameterCandidateResponse, PendingExitResponse,
+    PlannedInstructionResponse, PnLResponse, PoolResponse, PoolSearchResponse,
+    PoolSearchResultResponse, PoolSortField, PoolStateResponse, PortfolioAnalyticsResponse,
+    PortfolioHistoryPoint, PortfolioHistoryResponse, PortfolioRebalanceEvent,
+    PortfolioRiskResponse, PortfolioStopRequest, PositionHistoryResponse, PositionResponse,
+    PositionSnapshotResponse, RangeCandidateResponse, RebalanceRequest, RecommendationResponse,
+    ReconcileShadowRequest, RegisterWalletRequest, RemoteSignerSource, RequestAuditEntryResponse,
+    ShadowDecisionResponse, ShadowReconciliationResponse, ShadowReportResponse,
+    ShadowSummaryResponse, SimulationRequest, SimulationResponse, SortOrder, StopLossSettings,
+    StrategyPerformanceResponse, StrategyResponse, SuggestedRangeResponse, TestAlertRequest,
+    TestAlertResponse, TokenExposureResponse, TranscriptResponse, TranscriptStepResponse,
+    WalletBalanceResponse, WalletKind, WalletResponse, WebhookEventType, WebhookResponse,
 };
 use utoipa::OpenApi;
 
@@ -38,7 +59,18 @@ use utoipa::OpenApi;
         (name = "Positions", description = "LP position management"),
         (name = "Strategies", description = "Automated strategy management"),
         (name = "Pools", description = "Pool information and state"),
-        (name = "Analytics", description = "Portfolio analytics and simulations")
+        (name = "Analytics", description = "Portfolio analytics and simulations"),
+        (name = "Market", description = "Cached market data and pair analysis"),
+        (name = "Backtests", description = "Background backtests against stored price history"),
+        (name = "Optimization", description = "Background range and parameter optimization"),
+        (name = "Jobs", description = "Background job status and cancellation"),
+        (name = "Emergency", description = "Kill switch and circuit breaker controls"),
+        (name = "Alerts", description = "Alert history and acknowledgement"),
+        (name = "Audit", description = "Audit logs of decisions, transactions and API requests"),
+        (name = "Auth", description = "API key management"),
+        (name = "Webhooks", description = "Outbound webhook subscriptions"),
+        (name = "Wallets", description = "Signing wallets and strategy bindings"),
+        (name = "Export", description = "CSV and Parquet downloads of positions and results")
     ),
     paths(
         // Health endpoints
@@ -54,6 +86,9 @@ use utoipa::OpenApi;
         handlers::collect_fees,
         handlers::rebalance_position,
         handlers::get_position_pnl,
+        handlers::get_position_history,
+        handlers::get_position_recommendation,
+        handlers::batch_positions,
         // Strategy endpoints
         handlers::list_strategies,
         handlers::get_strategy,
@@ -62,14 +97,73 @@ use utoipa::OpenApi;
         handlers::delete_strategy,
         handlers::start_strategy,
         handlers::stop_strategy,
+        handlers::export_strategy,
+        handlers::import_strategy,
         handlers::get_strategy_performance,
+        handlers::get_strategy_transcript,
+        handlers::get_strategy_shadow,
+        handlers::reconcile_shadow_decision,
+        handlers::list_pending_exits,
+        handlers::confirm_exit,
+        handlers::cancel_exit,
         // Pool endpoints
         handlers::list_pools,
+        handlers::search_pools,
         handlers::get_pool,
         handlers::get_pool_state,
         // Analytics endpoints
         handlers::get_portfolio_analytics,
+        handlers::get_portfolio_history,
+        handlers::get_leaderboard,
         handlers::run_simulation,
+        // Market data endpoints
+        handlers::get_market_candles,
+        handlers::get_market_analysis,
+        // Backtest endpoints
+        handlers::submit_backtest,
+        handlers::get_backtest,
+        // Optimization endpoints
+        handlers::submit_optimization,
+        handlers::get_optimization,
+        // Job endpoints
+        handlers::get_job,
+        handlers::cancel_job,
+        // Emergency endpoints
+        handlers::get_kill_switch,
+        handlers::engage_kill_switch,
+        handlers::release_kill_switch,
+        handlers::get_portfolio_risk,
+        handlers::engage_portfolio_stop,
+        handlers::release_portfolio_stop,
+        // Alert endpoints
+        handlers::list_alerts,
+        handlers::get_alert,
+        handlers::acknowledge_alert,
+        handlers::list_alert_rules,
+        handlers::create_alert_rule,
+        handlers::delete_alert_rule,
+        handlers::test_alert_channels,
+        // Audit endpoints
+        handlers::list_audit_entries,
+        handlers::list_request_audit_entries,
+        // API key endpoints
+        handlers::list_api_keys,
+        handlers::create_api_key,
+        handlers::revoke_api_key,
+        // Webhook endpoints
+        handlers::list_webhooks,
+        handlers::create_webhook,
+        handlers::delete_webhook,
+        // Wallet endpoints
+        handlers::list_wallets,
+        handlers::register_wallet,
+        handlers::delete_wallet,
+        handlers::get_wallet_balance,
+        handlers::bind_wallet,
+        // Export endpoints
+        handlers::export_positions,
+        handlers::export_analytics,
+        handlers::export_simulation,
     ),
     components(
         schemas(
@@ -80,6 +174,16 @@ use utoipa::OpenApi;
             ListPositionsResponse,
             PositionResponse,
             PnLResponse,
+            PositionHistoryResponse,
+            LifecycleEventResponse,
+            PositionSnapshotResponse,
+            RecommendationResponse,
+            ExpectedImpactResponse,
+            BatchOperation,
+            BatchPositionItem,
+            BatchPositionRequest,
+            BatchItemResult,
+            BatchPositionResponse,
             OpenPositionRequest,
             RebalanceRequest,
             MessageResponse,
@@ -88,14 +192,103 @@ use utoipa::OpenApi;
             StrategyResponse,
             StrategyPerformanceResponse,
             CreateStrategyRequest,
+            AutoCompoundSettings,
+            StopLossSettings,
+            PendingExitResponse,
+            ListPendingExitsResponse,
+            PlannedInstructionResponse,
+            TranscriptStepResponse,
+            TranscriptResponse,
+            ShadowDecisionResponse,
+            ShadowReconciliationResponse,
+            ShadowSummaryResponse,
+            ShadowReportResponse,
+            ReconcileShadowRequest,
             // Pools
             ListPoolsResponse,
             PoolResponse,
             PoolStateResponse,
+            PoolSearchResultResponse,
+            PoolSearchResponse,
+            PoolSortField,
+            SortOrder,
             // Analytics
             PortfolioAnalyticsResponse,
+            PortfolioHistoryPoint,
+            PortfolioHistoryResponse,
+            PortfolioRebalanceEvent,
+            LeaderboardKind,
+            LeaderboardResponse,
+            LeaderboardEntryResponse,
             SimulationRequest,
             SimulationResponse,
+            // Market data
+            CandleResponse,
+            CandlesResponse,
+            SuggestedRangeResponse,
+            MarketAnalysisResponse,
+            // Backtests
+            BacktestRequest,
+            BacktestResultResponse,
+            BacktestJobResponse,
+            JobState,
+            // Optimization
+            ObjectiveType,
+            OptimizationConstraintsRequest,
+            OptimizeRequest,
+            RangeCandidateResponse,
+            ParameterCandidateResponse,
+            OptimizeResultResponse,
+            OptimizeJobResponse,
+            // Jobs
+            EquityPoint,
+            JobProgress,
+            JobResponse,
+            // Emergency
+            KillSwitchRequest,
+            KillSwitchResponse,
+            PortfolioStopRequest,
+            PortfolioRiskResponse,
+            TokenExposureResponse,
+            // Alerts
+            AlertResponse,
+            ListAlertsResponse,
+            AcknowledgeAlertRequest,
+            AlertSeverity,
+            CreateAlertRuleRequest,
+            AlertRuleResponse,
+            ListAlertRulesResponse,
+            TestAlertRequest,
+            ChannelTestResponse,
+            TestAlertResponse,
+            // Audit
+            AuditEntryResponse,
+            ListAuditEntriesResponse,
+            RequestAuditEntryResponse,
+            ListRequestAuditEntriesResponse,
+            // API keys
+            ApiKeyRole,
+            CreateApiKeyRequest,
+            ApiKeyResponse,
+            CreateApiKeyResponse,
+            ListApiKeysResponse,
+            // Webhooks
+            WebhookEventType,
+            CreateWebhookRequest,
+            WebhookResponse,
+            CreateWebhookResponse,
+            ListWebhooksResponse,
+            // Wallets
+            WalletKind,
+            KeystoreSource,
+            RemoteSignerSource,
+            RegisterWalletRequest,
+            WalletResponse,
+            ListWalletsResponse,
+            WalletBalanceResponse,
+            BindWalletRequest,
+            // Export
+            ExportFileFormat,
         )
     ),
     modifiers(&SecurityAddon)
@@ -152,6 +345,153 @@ mod tests {
         assert!(json.contains("CLMM LP Strategy Optimizer API"));
     }
 
+    #[test]
+    fn test_openapi_includes_kill_switch() {
+        let json = openapi_json();
+        assert!(json.contains("/emergency/kill-switch"));
+        assert!(json.contains("/emergency/portfolio-stop"));
+        assert!(json.contains("KillSwitchResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_alerts() {
+        let json = openapi_json();
+        assert!(json.contains("/alerts/{id}/acknowledge"));
+        assert!(json.contains("/alerts/rules/{name}"));
+        assert!(json.contains("/alerts/test"));
+        assert!(json.contains("AlertResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_audit() {
+        let json = openapi_json();
+        assert!(json.contains("/audit"));
+        assert!(json.contains("ListAuditEntriesResponse"));
+        assert!(json.contains("/audit/requests"));
+        assert!(json.contains("ListRequestAuditEntriesResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_transcript() {
+        let json = openapi_json();
+        assert!(json.contains("/strategies/{id}/transcript"));
+        assert!(json.contains("TranscriptStepResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_strategy_documents() {
+        let json = openapi_json();
+        assert!(json.contains("/strategies/{id}/export"));
+        assert!(json.contains("/strategies/import"));
+    }
+
+    #[test]
+    fn test_openapi_includes_shadow() {
+        let json = openapi_json();
+        assert!(json.contains("/strategies/{id}/shadow/{record_id}/reconcile"));
+        assert!(json.contains("ShadowReportResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_api_keys() {
+        let json = openapi_json();
+        assert!(json.contains("/auth/keys/{id}"));
+        assert!(json.contains("CreateApiKeyResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_webhooks() {
+        let json = openapi_json();
+        assert!(json.contains("/webhooks/{id}"));
+        assert!(json.contains("CreateWebhookResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_wallets() {
+        let json = openapi_json();
+        assert!(json.contains("/wallets/{label}/balance"));
+        assert!(json.contains("RegisterWalletRequest"));
+    }
+
+    #[test]
+    fn test_openapi_includes_export() {
+        let json = openapi_json();
+        assert!(json.contains("/export/simulations/{id}"));
+        assert!(json.contains("ExportFileFormat"));
+    }
+
+    #[test]
+    fn test_openapi_includes_pool_search() {
+        let json = openapi_json();
+        assert!(json.contains("/pools/search"));
+        assert!(json.contains("PoolSearchResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_batch_positions() {
+        let json = openapi_json();
+        assert!(json.contains("/positions/batch"));
+        assert!(json.contains("BatchPositionResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_position_history() {
+        let json = openapi_json();
+        assert!(json.contains("/positions/{address}/history"));
+        assert!(json.contains("PositionHistoryResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_position_recommendation() {
+        let json = openapi_json();
+        assert!(json.contains("/positions/{address}/recommendation"));
+        assert!(json.contains("RecommendationResponse"));
+        assert!(json.contains("ExpectedImpactResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_portfolio_history() {
+        let json = openapi_json();
+        assert!(json.contains("/analytics/portfolio/history"));
+        assert!(json.contains("PortfolioHistoryResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_leaderboard() {
+        let json = openapi_json();
+        assert!(json.contains("/analytics/leaderboard"));
+        assert!(json.contains("LeaderboardResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_market() {
+        let json = openapi_json();
+        assert!(json.contains("/market/{pair}/analysis"));
+        assert!(json.contains("MarketAnalysisResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_backtests() {
+        let json = openapi_json();
+        assert!(json.contains("/backtests/{id}"));
+        assert!(json.contains("BacktestJobResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_optimization() {
+        let json = openapi_json();
+        assert!(json.contains("/optimize/{id}"));
+        assert!(json.contains("OptimizeJobResponse"));
+    }
+
+    #[test]
+    fn test_openapi_includes_jobs() {
+        let json = openapi_json();
+        assert!(json.contains("/jobs/{id}"));
+        assert!(json.contains("JobResponse"));
+        assert!(json.contains("JobProgress"));
+    }
+
     #[test]
     fn test_openapi_yaml() {
         let yaml = openapi_yaml();
@@ -13,31 +13,85 @@ pub use crate::error::{ApiError, ApiResult, ErrorResponse};
 
 // Models
 pub use crate::models::{
-    CircuitBreakerStatus, ComponentHealth, CreateStrategyRequest, HealthResponse,
-    ListPoolsResponse, ListPositionsResponse, ListStrategiesResponse, MessageResponse,
-    MetricsResponse, OpenPositionRequest, PnLResponse, PoolResponse, PoolStateResponse,
-    PortfolioAnalyticsResponse, PositionResponse, PositionStatus, RebalanceRequest, ServiceStatus,
-    SimulationRequest, SimulationResponse, StrategyParameters, StrategyPerformanceResponse,
-    StrategyResponse, StrategyType, SuccessResponse,
+    AcknowledgeAlertRequest, AlertResponse, AlertRuleResponse, AlertSeverity, AlertsQuery,
+    ApiKeyResponse, ApiKeyRole, AuditEntryResponse, AuditLogQuery, AutoCompoundSettings,
+    BacktestJobResponse, BacktestRequest, BacktestResultResponse, BatchItemResult, BatchOperation,
+    BatchPositionItem, BatchPositionRequest, BatchPositionResponse, BindWalletRequest,
+    CandleResponse, CandlesResponse, CheckStatus, CircuitBreakerStatus, ComponentCheck,
+    ComponentHealth, CreateAlertRuleRequest, CreateApiKeyRequest, CreateApiKeyResponse,
+    CreateStrategyRequest, CreateWebhookRequest, CreateWebhookResponse, EquityPoint,
+    ExecutorHealth, ExportFileFormat, ExportQuery, HealthResponse, JobProgress, JobResponse,
+    JobState, KeystoreSource, LeaderboardEntryResponse, LeaderboardKind, LeaderboardQuery,
+    LeaderboardResponse, LifecycleEventResponse, ListAlertRulesResponse, ListAlertsResponse,
+    ListApiKeysResponse, ListAuditEntriesResponse, ListPendingExitsResponse, ListPoolsResponse,
+    ListPositionsResponse, ListRequestAuditEntriesResponse, ListStrategiesResponse,
+    ListWalletsResponse, ListWebhooksResponse, MarketAnalysisResponse, MarketQuery,
+    MessageResponse, MetricsResponse, ObjectiveType, OpenPositionRequest,
+    OptimizationConstraintsRequest, OptimizeJobResponse, OptimizeRequest, OptimizeResultResponse,
+    ParameterCandidateResponse, PendingExitResponse, PlannedInstructionResponse, PnLResponse,
+    PoolResponse, PoolSearchQuery, PoolSearchResponse, PoolSearchResultResponse, PoolSortField,
+    PoolStateResponse, PortfolioAnalyticsResponse, PortfolioHistoryPoint, PortfolioHistoryQuery,
+    PortfolioHistoryResponse, PortfolioRebalanceEvent, PositionHistoryQuery,
+    PositionHistoryResponse, PositionResponse, PositionSnapshotResponse, PositionStatus,
+    RangeCandidateResponse, RebalanceRequest, RegisterWalletRequest, RemoteSignerSource,
+    RequestAuditEntryResponse, RequestAuditLogQuery, RpcEndpointHealth, ServiceStatus,
+    SimulationRequest, SimulationResponse, SortOrder, StopLossSettings, StrategyParameters,
+    StrategyPerformanceResponse, StrategyResponse, StrategyType, SuccessResponse,
+    SuggestedRangeResponse, TranscriptQuery, TranscriptResponse, TranscriptStepResponse,
+    WalletBalanceResponse, WalletKind, WalletResponse, WebhookEventType, WebhookResponse,
 };
 
 // Server
 pub use crate::server::{ApiServer, ServerConfig, shutdown_signal};
+pub use crate::tls::{AcmeSettings, TlsConfig};
 
 // State
-pub use crate::state::{AlertUpdate, ApiConfig, AppState, PositionUpdate, StrategyState};
+pub use crate::state::{
+    AlertUpdate, ApiConfig, AppState, PositionUpdate, StrategyState, StrategyUpdate,
+};
 
 // Middleware
-pub use crate::middleware::RateLimiter;
+pub use crate::middleware::{RateDecision, RateLimitConfig, RateLimiter, RateLimits, RouteClass};
 
 // Routes
-pub use crate::routes::{create_router, create_versioned_router};
+pub use crate::routes::{create_router, create_router_for, create_versioned_router};
+
+// Versioning
+pub use crate::versioning::{ApiVersion, Deprecation, VersionPolicy};
 
 // Services
-pub use crate::services::{PositionService, StrategyService};
+pub use crate::services::{
+    DbHistoryStore, DbIdempotencyStore, DbJobStore, DbPoolStats, DbPositionHistory, DbPriceHistory,
+    DbRequestAuditLog, DbStrategyStore, Job, JobContext, JobQueue, JobStatus, JobStore, JobUpdate,
+    LeaderboardService, LeaderboardSource, MarketDataService, PoolScreener, PoolStatsSource,
+    PositionHistorySource, PositionService, PriceHistorySource, RegisteredWallet, StoredStrategy,
+    StrategyDocument, StrategyService, StrategyStore, WalletRegistry, WebhookRegistry,
+    WebhookSubscription, restore_strategies, run_backtest, run_optimization,
+    spawn_webhook_dispatcher,
+};
 
 // Authentication
-pub use crate::auth::{AuthConfig, AuthError, AuthState, Claims, Role};
+pub use crate::auth::{
+    ApiKey, ApiKeyStore, AuthConfig, AuthError, AuthState, Claims, Principal, Role,
+};
+pub use crate::jwt::{JwtConfig, JwtValidator};
+
+// Request audit
+pub use crate::request_audit::{
+    MemoryRequestAuditLog, RequestAuditEntry, RequestAuditLog, RequestAuditQuery,
+};
+
+// Tenancy
+pub use crate::tenancy::TenantScope;
+
+// Idempotency
+pub use crate::idempotency::{
+    IdempotencyState, IdempotencyStore, MemoryIdempotencyStore, StoredResponse,
+};
+
+// GraphQL
+#[cfg(feature = "graphql")]
+pub use crate::graphql::{ApiSchema, QueryRoot, build_schema};
 
 // OpenAPI
 pub use crate::openapi::ApiDoc;
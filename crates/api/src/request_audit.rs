@@ -0,0 +1,303 @@
+//! Request audit logging of mutating API calls.
+//!
+//! Every `POST`, `PUT`, `PATCH` and `DELETE` request is recorded with its
+//! caller (subject, role and tenant), method and path, a SHA-256 hash of
+//! the request body, the response status and the time taken. Bodies are
+//! hashed rather than stored, so secrets such as keystore passphrases never
+//! reach the log, while a known payload can still be matched to its entry.
+//!
+//! This complements the execution audit log of decisions and transactions:
+//! together they trace an on-chain action back to the API call behind it.
+//! Entries are written after the response is produced; failing to persist
+//! one is logged and does not fail the request.
+
+use crate::auth::Principal;
+use crate::error::ApiError;
+use async_trait::async_trait;
+use axum::{
+    body::{Body, to_bytes},
+    extract::{OriginalUri, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+/// Maximum request body hashed, matching axum's default body limit.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// A recorded API request.
+#[derive(Debug, Clone)]
+pub struct RequestAuditEntry {
+    /// Entry ID.
+    pub id: String,
+    /// When the request was received.
+    pub timestamp: DateTime<Utc>,
+    /// API key ID or token subject; empty when unauthenticated.
+    pub subject: String,
+    /// Caller's role.
+    pub role: Option<String>,
+    /// Caller's tenant.
+    pub tenant: Option<String>,
+    /// HTTP method.
+    pub method: String,
+    /// Request path, including any version prefix.
+    pub path: String,
+    /// Hex SHA-256 of the request body; `None` when empty.
+    pub payload_hash: Option<String>,
+    /// Response status code.
+    pub status: u16,
+    /// Time to respond in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Filter for request audit queries.
+///
+/// Unset fields match every entry.
+#[derive(Debug, Clone)]
+pub struct RequestAuditQuery {
+    /// Only requests by this subject.
+    pub subject: Option<String>,
+    /// Only requests by this tenant.
+    pub tenant: Option<String>,
+    /// Only requests with this method.
+    pub method: Option<String>,
+    /// Only requests whose path starts with this prefix.
+    pub path_prefix: Option<String>,
+    /// Only requests answered with this status code.
+    pub status: Option<u16>,
+    /// Only requests at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only requests before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Maximum number of entries returned.
+    pub limit: usize,
+}
+
+impl Default for RequestAuditQuery {
+    fn default() -> Self {
+        Self {
+            subject: None,
+            tenant: None,
+            method: None,
+            path_prefix: None,
+            status: None,
+            since: None,
+            until: None,
+            limit: 100,
+        }
+    }
+}
+
+impl RequestAuditQuery {
+    /// Checks whether an entry matches the filter.
+    #[must_use]
+    pub fn matches(&self, entry: &RequestAuditEntry) -> bool {
+        self.subject.as_ref().is_none_or(|s| *s == entry.subject)
+            && self
+                .tenant
+                .as_ref()
+                .is_none_or(|t| entry.tenant.as_ref() == Some(t))
+            && self.method.as_ref().is_none_or(|m| *m == entry.method)
+            && self
+                .path_prefix
+                .as_ref()
+                .is_none_or(|p| entry.path.starts_with(p.as_str()))
+            && self.status.is_none_or(|s| s == entry.status)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp < until)
+    }
+}
+
+/// Append-only log of API requests.
+#[async_trait]
+pub trait RequestAuditLog: Send + Sync {
+    /// Appends an entry.
+    async fn append(&self, entry: &RequestAuditEntry) -> anyhow::Result<()>;
+
+    /// Queries entries, newest first.
+    async fn query(&self, query: &RequestAuditQuery) -> anyhow::Result<Vec<RequestAuditEntry>>;
+}
+
+/// In-memory request audit log keeping the most recent entries.
+pub struct MemoryRequestAuditLog {
+    /// Entries, oldest first.
+    entries: RwLock<VecDeque<RequestAuditEntry>>,
+    /// Maximum number of entries kept.
+    max_entries: usize,
+}
+
+impl MemoryRequestAuditLog {
+    /// Creates an in-memory log keeping up to `max_entries` entries.
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: RwLock::new(VecDeque::new()),
+            max_entries,
+        }
+    }
+}
+
+impl Default for MemoryRequestAuditLog {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+#[async_trait]
+impl RequestAuditLog for MemoryRequestAuditLog {
+    async fn append(&self, entry: &RequestAuditEntry) -> anyhow::Result<()> {
+        let mut entries = self.entries.write().await;
+        if entries.len() >= self.max_entries {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+        Ok(())
+    }
+
+    async fn query(&self, query: &RequestAuditQuery) -> anyhow::Result<Vec<RequestAuditEntry>> {
+        Ok(self
+            .entries
+            .read()
+            .await
+            .iter()
+            .rev()
+            .filter(|e| query.matches(e))
+            .take(query.limit)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Checks whether a request method mutates state.
+fn is_mutating(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+/// Request audit middleware.
+///
+/// Reads pass through unrecorded. Mutating requests are recorded once the
+/// inner service has responded, including requests it rejected.
+pub async fn request_audit_middleware(
+    State(log): State<Arc<dyn RequestAuditLog>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if !is_mutating(request.method()) {
+        return Ok(next.run(request).await);
+    }
+
+    let started = Instant::now();
+    let timestamp = Utc::now();
+    let principal = request.extensions().get::<Principal>().cloned();
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or(request.uri().path(), |uri| uri.path())
+        .to_string();
+
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| ApiError::bad_request("Request body too large"))?;
+    let payload_hash = (!body.is_empty()).then(|| hex::encode(Sha256::digest(&body)));
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let entry = RequestAuditEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp,
+        subject: principal
+            .as_ref()
+            .map(|p| p.subject.clone())
+            .unwrap_or_default(),
+        role: principal.as_ref().map(|p| p.role.as_str().to_string()),
+        tenant: principal.and_then(|p| p.tenant),
+        method,
+        path,
+        payload_hash,
+        status: response.status().as_u16(),
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    };
+    tokio::spawn(async move {
+        if let Err(e) = log.append(&entry).await {
+            warn!(path = %entry.path, error = %e, "Failed to record request audit entry");
+        }
+    });
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(subject: &str, method: &str, path: &str, status: u16) -> RequestAuditEntry {
+        RequestAuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            subject: subject.to_string(),
+            role: Some("trader".to_string()),
+            tenant: None,
+            method: method.to_string(),
+            path: path.to_string(),
+            payload_hash: None,
+            status,
+            duration_ms: 3,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_request_audit_log_query() {
+        let log = MemoryRequestAuditLog::new(3);
+        log.append(&entry("a", "POST", "/positions", 200))
+            .await
+            .unwrap();
+        log.append(&entry("b", "DELETE", "/strategies/s1", 404))
+            .await
+            .unwrap();
+        log.append(&entry("a", "POST", "/strategies", 200))
+            .await
+            .unwrap();
+        log.append(&entry("a", "PUT", "/strategies/s2", 200))
+            .await
+            .unwrap();
+
+        let all = log.query(&RequestAuditQuery::default()).await.unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].method, "PUT");
+
+        let query = RequestAuditQuery {
+            subject: Some("a".to_string()),
+            path_prefix: Some("/strategies".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(log.query(&query).await.unwrap().len(), 2);
+
+        let query = RequestAuditQuery {
+            status: Some(404),
+            ..Default::default()
+        };
+        let failed = log.query(&query).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].subject, "b");
+    }
+
+    #[test]
+    fn test_only_mutating_methods_are_recorded() {
+        assert!(is_mutating(&Method::POST));
+        assert!(is_mutating(&Method::DELETE));
+        assert!(!is_mutating(&Method::GET));
+        assert!(!is_mutating(&Method::OPTIONS));
+    }
+}
@@ -1,14 +1,57 @@
 //! Route definitions.
 
+use crate::auth::auth_middleware;
 use crate::handlers;
+use crate::idempotency::idempotency_middleware;
+use crate::middleware::rate_limit;
+use crate::request_audit::request_audit_middleware;
 use crate::state::AppState;
-use crate::websocket;
+use crate::tenancy::tenant_scope;
+use crate::versioning::{ApiVersion, version_headers};
+use crate::{sse, websocket};
 use axum::{
-    Router,
+    Router, middleware,
     routing::{delete, get, post, put},
 };
 
+/// Creates the routes that act on positions on-chain.
+///
+/// These honor the `Idempotency-Key` header (see [`crate::idempotency`]).
+fn position_action_routes(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/positions", post(handlers::open_position))
+        .route("/positions/batch", post(handlers::batch_positions))
+        .route("/positions/{address}", delete(handlers::close_position))
+        .route("/positions/{address}/collect", post(handlers::collect_fees))
+        .route(
+            "/positions/{address}/rebalance",
+            post(handlers::rebalance_position),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.idempotency.clone(),
+            idempotency_middleware,
+        ))
+}
+
+/// Creates the GraphQL routes.
+#[cfg(feature = "graphql")]
+fn graphql_routes(state: &AppState) -> Router<AppState> {
+    crate::graphql::graphql_routes(state)
+}
+
+/// Creates no routes when the `graphql` feature is disabled.
+#[cfg(not(feature = "graphql"))]
+fn graphql_routes(_state: &AppState) -> Router<AppState> {
+    Router::new()
+}
+
 /// Creates the API router with all routes.
+///
+/// Every route except health checks and metrics is authenticated once any
+/// API key exists (see [`crate::auth`]), scoped to the caller's tenant (see
+/// [`crate::tenancy`]) and rate limited per caller (see
+/// [`crate::middleware::rate_limit`]). Mutating requests are recorded in the
+/// request audit log (see [`crate::request_audit`]).
 pub fn create_router(state: AppState) -> Router {
     Router::new()
         // Health routes
@@ -17,30 +60,56 @@ pub fn create_router(state: AppState) -> Router {
         .route("/health/ready", get(handlers::readiness))
         .route("/metrics", get(handlers::metrics))
         // Position routes
+        .merge(position_action_routes(&state))
         .route("/positions", get(handlers::list_positions))
-        .route("/positions", post(handlers::open_position))
         .route("/positions/{address}", get(handlers::get_position))
-        .route("/positions/{address}", delete(handlers::close_position))
-        .route("/positions/{address}/collect", post(handlers::collect_fees))
+        .route("/positions/{address}/pnl", get(handlers::get_position_pnl))
         .route(
-            "/positions/{address}/rebalance",
-            post(handlers::rebalance_position),
+            "/positions/{address}/history",
+            get(handlers::get_position_history),
+        )
+        .route(
+            "/positions/{address}/recommendation",
+            get(handlers::get_position_recommendation),
         )
-        .route("/positions/{address}/pnl", get(handlers::get_position_pnl))
         // Strategy routes
         .route("/strategies", get(handlers::list_strategies))
         .route("/strategies", post(handlers::create_strategy))
+        .route("/strategies/import", post(handlers::import_strategy))
         .route("/strategies/{id}", get(handlers::get_strategy))
         .route("/strategies/{id}", put(handlers::update_strategy))
         .route("/strategies/{id}", delete(handlers::delete_strategy))
         .route("/strategies/{id}/start", post(handlers::start_strategy))
         .route("/strategies/{id}/stop", post(handlers::stop_strategy))
+        .route("/strategies/{id}/export", get(handlers::export_strategy))
         .route(
             "/strategies/{id}/performance",
             get(handlers::get_strategy_performance),
         )
+        .route(
+            "/strategies/{id}/transcript",
+            get(handlers::get_strategy_transcript),
+        )
+        .route(
+            "/strategies/{id}/shadow",
+            get(handlers::get_strategy_shadow),
+        )
+        .route(
+            "/strategies/{id}/shadow/{record_id}/reconcile",
+            post(handlers::reconcile_shadow_decision),
+        )
+        .route("/strategies/{id}/exits", get(handlers::list_pending_exits))
+        .route(
+            "/strategies/{id}/exits/{position}/confirm",
+            post(handlers::confirm_exit),
+        )
+        .route(
+            "/strategies/{id}/exits/{position}",
+            delete(handlers::cancel_exit),
+        )
         // Pool routes
         .route("/pools", get(handlers::list_pools))
+        .route("/pools/search", get(handlers::search_pools))
         .route("/pools/{address}", get(handlers::get_pool))
         .route("/pools/{address}/state", get(handlers::get_pool_state))
         // Analytics routes
@@ -48,15 +117,127 @@ pub fn create_router(state: AppState) -> Router {
             "/analytics/portfolio",
             get(handlers::get_portfolio_analytics),
         )
+        .route(
+            "/analytics/portfolio/history",
+            get(handlers::get_portfolio_history),
+        )
+        .route("/analytics/leaderboard", get(handlers::get_leaderboard))
         .route("/analytics/simulate", post(handlers::run_simulation))
+        // Market data routes
+        .route("/market/{pair}/candles", get(handlers::get_market_candles))
+        .route(
+            "/market/{pair}/analysis",
+            get(handlers::get_market_analysis),
+        )
+        // Backtest routes
+        .route("/backtests", post(handlers::submit_backtest))
+        .route("/backtests/{id}", get(handlers::get_backtest))
+        // Optimization routes
+        .route("/optimize", post(handlers::submit_optimization))
+        .route("/optimize/{id}", get(handlers::get_optimization))
+        // Job routes
+        .route("/jobs/{id}", get(handlers::get_job))
+        .route("/jobs/{id}", delete(handlers::cancel_job))
+        // Emergency routes
+        .route("/emergency/kill-switch", get(handlers::get_kill_switch))
+        .route("/emergency/kill-switch", post(handlers::engage_kill_switch))
+        .route(
+            "/emergency/kill-switch",
+            delete(handlers::release_kill_switch),
+        )
+        .route(
+            "/emergency/portfolio-stop",
+            get(handlers::get_portfolio_risk),
+        )
+        .route(
+            "/emergency/portfolio-stop",
+            post(handlers::engage_portfolio_stop),
+        )
+        .route(
+            "/emergency/portfolio-stop",
+            delete(handlers::release_portfolio_stop),
+        )
+        // Alert routes
+        .route("/alerts", get(handlers::list_alerts))
+        .route("/alerts/rules", get(handlers::list_alert_rules))
+        .route("/alerts/rules", post(handlers::create_alert_rule))
+        .route("/alerts/rules/{name}", delete(handlers::delete_alert_rule))
+        .route("/alerts/test", post(handlers::test_alert_channels))
+        .route("/alerts/{id}", get(handlers::get_alert))
+        .route(
+            "/alerts/{id}/acknowledge",
+            post(handlers::acknowledge_alert),
+        )
+        // Audit routes
+        .route("/audit", get(handlers::list_audit_entries))
+        .route("/audit/requests", get(handlers::list_request_audit_entries))
+        // API key routes
+        .route("/auth/keys", get(handlers::list_api_keys))
+        .route("/auth/keys", post(handlers::create_api_key))
+        .route("/auth/keys/{id}", delete(handlers::revoke_api_key))
+        // Webhook routes
+        .route("/webhooks", get(handlers::list_webhooks))
+        .route("/webhooks", post(handlers::create_webhook))
+        .route("/webhooks/{id}", delete(handlers::delete_webhook))
+        // Wallet routes
+        .route("/wallets", get(handlers::list_wallets))
+        .route("/wallets", post(handlers::register_wallet))
+        .route("/wallets/{label}", delete(handlers::delete_wallet))
+        .route(
+            "/wallets/{label}/balance",
+            get(handlers::get_wallet_balance),
+        )
+        .route("/wallets/{label}/bind", post(handlers::bind_wallet))
+        // Export routes
+        .route("/export/positions", get(handlers::export_positions))
+        .route("/export/analytics", get(handlers::export_analytics))
+        .route("/export/simulations/{id}", get(handlers::export_simulation))
         // WebSocket routes
+        .route("/ws", get(websocket::subscribe_ws))
         .route("/ws/positions", get(websocket::positions_ws))
         .route("/ws/alerts", get(websocket::alerts_ws))
+        // Server-Sent Events routes
+        .route("/events", get(sse::events_sse))
+        // GraphQL routes
+        .merge(graphql_routes(&state))
+        // Scope to the caller's tenant, after authentication
+        .layer(middleware::from_fn_with_state(state.clone(), tenant_scope))
+        // Record mutating requests with their caller
+        .layer(middleware::from_fn_with_state(
+            state.request_audit.clone(),
+            request_audit_middleware,
+        ))
+        // Rate limit per caller, after authentication
+        .layer(middleware::from_fn_with_state(
+            state.rate_limiter.clone(),
+            rate_limit,
+        ))
+        // Authenticate requests
+        .layer(middleware::from_fn_with_state(
+            state.auth.clone(),
+            auth_middleware,
+        ))
         // Add state
         .with_state(state)
 }
 
-/// Creates the API router with versioning prefix.
+/// Creates the API router for one version.
+///
+/// Versions share the routes of [`create_router`]; handlers whose response
+/// model changes in a newer version read the [`ApiVersion`] from the
+/// request extensions (see [`crate::versioning`]).
+pub fn create_router_for(version: ApiVersion, state: AppState) -> Router {
+    create_router(state).layer(middleware::from_fn_with_state(
+        version.policy(),
+        version_headers,
+    ))
+}
+
+/// Creates the API router with every version under its prefix.
 pub fn create_versioned_router(state: AppState) -> Router {
-    Router::new().nest("/api/v1", create_router(state))
+    ApiVersion::ALL
+        .iter()
+        .fold(Router::new(), |router, version| {
+            router.nest(version.prefix(), create_router_for(*version, state.clone()))
+        })
 }
@@ -1,15 +1,18 @@
 //! Server configuration and startup.
 
 use crate::handlers::health::init_start_time;
-use crate::middleware::{RateLimiter, request_logging};
+use crate::middleware::request_logging;
 use crate::openapi::ApiDoc;
 use crate::routes::create_versioned_router;
+use crate::services::spawn_webhook_dispatcher;
 use crate::state::{ApiConfig, AppState};
+use crate::telemetry::make_request_span;
+use crate::tls::{TlsConfig, serve_tls};
+use axum::body::Body;
+use axum::http::Request;
 use axum::{Router, middleware};
 use clmm_lp_protocols::prelude::RpcConfig;
-use std::collections::HashSet;
 use std::net::SocketAddr;
-use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
 use tower_http::{
@@ -32,6 +35,8 @@ pub struct ServerConfig {
     pub rpc_config: RpcConfig,
     /// API configuration.
     pub api_config: ApiConfig,
+    /// TLS termination; plaintext HTTP if unset.
+    pub tls: Option<TlsConfig>,
 }
 
 impl Default for ServerConfig {
@@ -41,10 +46,20 @@ impl Default for ServerConfig {
             port: 8080,
             rpc_config: RpcConfig::default(),
             api_config: ApiConfig::default(),
+            tls: None,
         }
     }
 }
 
+impl ServerConfig {
+    /// Serves HTTPS with the given TLS configuration.
+    #[must_use]
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+}
+
 /// API server.
 pub struct ApiServer {
     /// Server configuration.
@@ -72,11 +87,6 @@ impl ApiServer {
 
     /// Builds the router with all middleware.
     pub fn build_router(&self) -> Router {
-        let _api_keys: HashSet<String> = self.config.api_config.api_keys.iter().cloned().collect();
-        let _rate_limiter = Arc::new(RateLimiter::new(
-            self.config.api_config.rate_limit_per_minute,
-        ));
-
         let mut router = create_versioned_router(self.state.clone());
 
         // Add Swagger UI at /docs
@@ -103,8 +113,11 @@ impl ApiServer {
             )));
         }
 
-        // Add tracing
-        router = router.layer(TraceLayer::new_for_http());
+        // Add tracing, continuing traces from incoming requests
+        router = router.layer(
+            TraceLayer::new_for_http()
+                .make_span_with(|request: &Request<Body>| make_request_span(request)),
+        );
 
         router
     }
@@ -112,11 +125,17 @@ impl ApiServer {
     /// Starts the server.
     pub async fn run(self) -> anyhow::Result<()> {
         init_start_time();
+        spawn_webhook_dispatcher(&self.state);
 
         let addr: SocketAddr = format!("{}:{}", self.config.host, self.config.port).parse()?;
 
         let router = self.build_router();
 
+        if let Some(tls) = &self.config.tls {
+            info!(address = %addr, "Starting API server with TLS");
+            return serve_tls(addr, router, tls, std::future::pending()).await;
+        }
+
         info!(address = %addr, "Starting API server");
 
         let listener = TcpListener::bind(addr).await?;
@@ -131,11 +150,19 @@ impl ApiServer {
         shutdown_signal: impl std::future::Future<Output = ()> + Send + 'static,
     ) -> anyhow::Result<()> {
         init_start_time();
+        spawn_webhook_dispatcher(&self.state);
 
         let addr: SocketAddr = format!("{}:{}", self.config.host, self.config.port).parse()?;
 
         let router = self.build_router();
 
+        if let Some(tls) = &self.config.tls {
+            info!(address = %addr, "Starting API server with TLS and graceful shutdown");
+            serve_tls(addr, router, tls, shutdown_signal).await?;
+            info!("API server stopped");
+            return Ok(());
+        }
+
         info!(address = %addr, "Starting API server with graceful shutdown");
 
         let listener = TcpListener::bind(addr).await?;
@@ -0,0 +1,73 @@
+//! Database-backed audit log.
+
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{AuditFilter, AuditRecord, AuditRepository};
+use clmm_lp_execution::prelude::{AuditEntry, AuditKind, AuditLog, AuditQuery};
+use uuid::Uuid;
+
+/// Audit log that appends entries to PostgreSQL.
+#[derive(Clone)]
+pub struct DbAuditLog {
+    /// Audit repository.
+    repo: AuditRepository,
+}
+
+impl DbAuditLog {
+    /// Creates a new database audit log.
+    pub fn new(repo: AuditRepository) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl AuditLog for DbAuditLog {
+    async fn append(&self, entry: &AuditEntry) -> anyhow::Result<()> {
+        let record = AuditRecord {
+            id: Uuid::parse_str(&entry.id)?,
+            timestamp: entry.timestamp,
+            kind: entry.kind.name().to_string(),
+            action: entry.action.clone(),
+            strategy_id: entry.strategy_id.clone(),
+            position_address: entry.position.clone(),
+            tx_signature: entry.signature.clone(),
+            success: entry.success,
+            error: entry.error.clone(),
+            details: entry.details.clone(),
+        };
+        self.repo.append(&record).await?;
+        Ok(())
+    }
+
+    async fn query(&self, query: &AuditQuery) -> anyhow::Result<Vec<AuditEntry>> {
+        let filter = AuditFilter {
+            strategy_id: query.strategy_id.clone(),
+            position_address: query.position.clone(),
+            kind: query.kind.map(|k| k.name().to_string()),
+            tx_signature: None,
+            since: query.since,
+            until: query.until,
+        };
+
+        self.repo
+            .query(&filter, query.limit as i64)
+            .await?
+            .into_iter()
+            .map(|record| {
+                let kind = AuditKind::from_name(&record.kind)
+                    .ok_or_else(|| anyhow::anyhow!("unknown audit entry kind: {}", record.kind))?;
+                Ok(AuditEntry {
+                    id: record.id.to_string(),
+                    timestamp: record.timestamp,
+                    kind,
+                    action: record.action,
+                    strategy_id: record.strategy_id,
+                    position: record.position_address,
+                    signature: record.tx_signature,
+                    success: record.success,
+                    error: record.error,
+                    details: record.details,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,306 @@
+//! Backtest service running strategies against stored price history.
+
+use super::jobs::JobContext;
+use crate::models::{BacktestRequest, BacktestResultResponse, StrategyType};
+use anyhow::Context;
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{PoolRepository, PriceRepository};
+use clmm_lp_domain::prelude::{Price, PriceRange};
+use clmm_lp_simulation::prelude::{
+    ILLimitStrategy, PeriodicRebalance, PositionTracker, RebalanceStrategy, StaticRange,
+    ThresholdRebalance,
+};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// A historical price candle of a pool.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoricalCandle {
+    /// Timestamp in seconds.
+    pub timestamp: i64,
+    /// Closing price.
+    pub price: Decimal,
+    /// Traded volume in USD.
+    pub volume_usd: Decimal,
+    /// Pool liquidity in USD, if recorded.
+    pub liquidity_usd: Option<Decimal>,
+}
+
+/// Price history of a pool over a period.
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolHistory {
+    /// Pool fee rate as a fraction (0.003 = 0.3%).
+    pub fee_rate: Decimal,
+    /// Candles, oldest first.
+    pub candles: Vec<HistoricalCandle>,
+}
+
+/// Source of pool price history for backtests.
+#[async_trait]
+pub trait PriceHistorySource: Send + Sync {
+    /// Loads a pool's price history between two Unix timestamps.
+    async fn load(&self, pool_address: &str, start: i64, end: i64) -> anyhow::Result<PoolHistory>;
+}
+
+/// Price history source backed by the pool and price repositories.
+#[derive(Clone)]
+pub struct DbPriceHistory {
+    /// Pool repository.
+    pools: PoolRepository,
+    /// Price history repository.
+    prices: PriceRepository,
+}
+
+impl DbPriceHistory {
+    /// Creates a new database price history source.
+    pub fn new(pools: PoolRepository, prices: PriceRepository) -> Self {
+        Self { pools, prices }
+    }
+}
+
+#[async_trait]
+impl PriceHistorySource for DbPriceHistory {
+    async fn load(&self, pool_address: &str, start: i64, end: i64) -> anyhow::Result<PoolHistory> {
+        let pool = self
+            .pools
+            .find_by_address(pool_address)
+            .await?
+            .with_context(|| format!("unknown pool {}", pool_address))?;
+
+        let candles = self
+            .prices
+            .find_by_pool_and_range(pool.id, start, end)
+            .await?
+            .into_iter()
+            .map(|record| HistoricalCandle {
+                timestamp: record.timestamp,
+                price: record.close_price,
+                volume_usd: record.volume.unwrap_or_default(),
+                liquidity_usd: record.liquidity,
+            })
+            .collect();
+
+        Ok(PoolHistory {
+            // Fee tiers are stored in basis points
+            fee_rate: Decimal::from(pool.fee_tier) / Decimal::from(10_000),
+            candles,
+        })
+    }
+}
+
+/// Runs a backtest over a pool's price history.
+///
+/// Each candle earns the pool fee on its volume, scaled by the position's
+/// share of liquidity, while the price is within the current range.
+///
+/// # Errors
+/// Returns an error if the history holds no candles.
+pub fn run_backtest(
+    request: &BacktestRequest,
+    history: &PoolHistory,
+) -> anyhow::Result<BacktestResultResponse> {
+    run_backtest_job(request, history, &JobContext::default())
+}
+
+/// Runs a backtest as a job, reporting progress as candles are replayed.
+///
+/// # Errors
+/// Returns an error if the history holds no candles or the job is
+/// cancelled.
+pub fn run_backtest_job(
+    request: &BacktestRequest,
+    history: &PoolHistory,
+    job: &JobContext,
+) -> anyhow::Result<BacktestResultResponse> {
+    let (first, last) = match (history.candles.first(), history.candles.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => anyhow::bail!(
+            "no price history for {} in the requested period",
+            request.pool_address
+        ),
+    };
+
+    let range = PriceRange::new(
+        Price::new(request.lower_price),
+        Price::new(request.upper_price),
+    );
+    let center = (request.lower_price + request.upper_price) / Decimal::TWO;
+    let range_width_pct = (request.upper_price - request.lower_price) / center;
+
+    let mut tracker = PositionTracker::new(
+        request.initial_capital_usd,
+        Price::new(first.price),
+        range,
+        request.tx_cost_usd,
+    )
+    .with_capacity(history.candles.len());
+
+    match request
+        .strategy_type
+        .as_ref()
+        .unwrap_or(&StrategyType::StaticRange)
+    {
+        StrategyType::StaticRange => {
+            replay(&mut tracker, request, history, job, &StaticRange::new())
+        }
+        StrategyType::Periodic => replay(
+            &mut tracker,
+            request,
+            history,
+            job,
+            &PeriodicRebalance::new(request.rebalance_interval, range_width_pct),
+        ),
+        StrategyType::Threshold => replay(
+            &mut tracker,
+            request,
+            history,
+            job,
+            &ThresholdRebalance::new(request.threshold_pct, range_width_pct),
+        ),
+        StrategyType::IlLimit => replay(
+            &mut tracker,
+            request,
+            history,
+            job,
+            &ILLimitStrategy::new(request.max_il_pct, range_width_pct),
+        ),
+    }?;
+
+    let summary = tracker.summary();
+    Ok(BacktestResultResponse {
+        total_steps: summary.total_steps,
+        entry_price: first.price,
+        final_price: last.price,
+        final_value_usd: summary.final_value,
+        final_pnl_usd: summary.final_pnl,
+        final_il_pct: summary.final_il_pct,
+        total_fees_usd: summary.total_fees,
+        time_in_range_pct: summary.time_in_range_pct,
+        rebalance_count: summary.rebalance_count,
+        total_rebalance_cost_usd: summary.total_rebalance_cost,
+        max_drawdown_pct: summary.max_drawdown,
+        hodl_value_usd: summary.hodl_value,
+        vs_hodl_usd: summary.vs_hodl,
+    })
+}
+
+/// Steps the tracker through every candle with a strategy.
+fn replay<S: RebalanceStrategy>(
+    tracker: &mut PositionTracker,
+    request: &BacktestRequest,
+    history: &PoolHistory,
+    job: &JobContext,
+    strategy: &S,
+) -> anyhow::Result<()> {
+    let total = history.candles.len();
+    for (i, candle) in history.candles.iter().enumerate() {
+        if job.is_cancelled() {
+            anyhow::bail!("backtest cancelled");
+        }
+        let in_range = candle.price >= tracker.current_range.lower_price.value
+            && candle.price <= tracker.current_range.upper_price.value;
+
+        let fees = if in_range {
+            candle.volume_usd * history.fee_rate * liquidity_share(request, candle)
+        } else {
+            Decimal::ZERO
+        };
+
+        tracker.record_step(Price::new(candle.price), fees, Some(strategy));
+
+        // Extend the partial equity curve once per percent
+        let pct = ((i + 1) * 100 / total) as u8;
+        job.set_steps((i + 1) as u64, total as u64);
+        if pct != job.progress()
+            && let Some(value) = tracker.equity_curve().last()
+        {
+            job.push_equity(tracker.len() as u64, *value);
+        }
+        job.set_progress(pct);
+    }
+    Ok(())
+}
+
+/// Returns the position's share of pool liquidity during a candle.
+fn liquidity_share(request: &BacktestRequest, candle: &HistoricalCandle) -> Decimal {
+    if let Some(share) = request.liquidity_share {
+        return share;
+    }
+    match candle.liquidity_usd {
+        Some(liquidity) if liquidity > Decimal::ZERO => {
+            (request.initial_capital_usd / liquidity).min(Decimal::ONE)
+        }
+        _ => Decimal::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(strategy_type: StrategyType) -> BacktestRequest {
+        serde_json::from_value(serde_json::json!({
+            "pool_address": "pool",
+            "lower_price": "90",
+            "upper_price": "110",
+            "strategy_type": strategy_type,
+            "start_date": "2024-01-01",
+            "end_date": "2024-01-02",
+            "initial_capital_usd": "1000",
+            "tx_cost_usd": "1",
+            "threshold_pct": "0.05"
+        }))
+        .unwrap()
+    }
+
+    fn history(prices: &[Decimal]) -> PoolHistory {
+        PoolHistory {
+            fee_rate: Decimal::new(3, 3),
+            candles: prices
+                .iter()
+                .enumerate()
+                .map(|(i, price)| HistoricalCandle {
+                    timestamp: i as i64 * 3600,
+                    price: *price,
+                    volume_usd: Decimal::from(100000),
+                    liquidity_usd: Some(Decimal::from(100000)),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_run_backtest() {
+        let prices = [
+            Decimal::from(100),
+            Decimal::from(101),
+            Decimal::from(120),
+            Decimal::from(121),
+        ];
+
+        let result = run_backtest(&request(StrategyType::StaticRange), &history(&prices)).unwrap();
+        assert_eq!(result.total_steps, 4);
+        assert_eq!(result.entry_price, Decimal::from(100));
+        assert_eq!(result.final_price, Decimal::from(121));
+        assert_eq!(result.rebalance_count, 0);
+        // 1% of the pool earns 0.3% of 100k volume on each in-range candle
+        assert_eq!(result.total_fees_usd, Decimal::from(6));
+        assert_eq!(result.time_in_range_pct, Decimal::new(5, 1));
+
+        let result = run_backtest(&request(StrategyType::Threshold), &history(&prices)).unwrap();
+        assert!(result.rebalance_count > 0);
+        assert!(result.total_rebalance_cost_usd > Decimal::ZERO);
+
+        assert!(run_backtest(&request(StrategyType::StaticRange), &history(&[])).is_err());
+    }
+
+    #[test]
+    fn test_run_backtest_job_progress() {
+        let prices = [Decimal::from(100), Decimal::from(105)];
+        let request = request(StrategyType::StaticRange);
+
+        let job = JobContext::default();
+        run_backtest_job(&request, &history(&prices), &job).unwrap();
+        assert_eq!(job.progress(), 100);
+    }
+}
@@ -0,0 +1,75 @@
+//! Database-backed idempotency store.
+
+use crate::idempotency::{IDEMPOTENCY_TTL_HOURS, IdempotencyStore, StoredResponse};
+use anyhow::Context;
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{IdempotencyRecord, IdempotencyRepository};
+
+/// Idempotency store that persists responses to PostgreSQL.
+#[derive(Clone)]
+pub struct DbIdempotencyStore {
+    /// Idempotency repository.
+    repo: IdempotencyRepository,
+}
+
+impl DbIdempotencyStore {
+    /// Creates a new database idempotency store.
+    pub fn new(repo: IdempotencyRepository) -> Self {
+        Self { repo }
+    }
+
+    /// Deletes expired responses. Returns the number deleted.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn purge_expired(&self) -> anyhow::Result<u64> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::hours(IDEMPOTENCY_TTL_HOURS);
+        Ok(self.repo.delete_before(cutoff).await?)
+    }
+}
+
+/// Converts a stored response to its record.
+fn to_record(key: &str, response: &StoredResponse) -> IdempotencyRecord {
+    IdempotencyRecord {
+        key: key.to_string(),
+        fingerprint: response.fingerprint.clone(),
+        status: response.status.map(i32::from),
+        content_type: response.content_type.clone(),
+        body: response.body.clone(),
+        created_at: response.created_at,
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for DbIdempotencyStore {
+    async fn reserve(
+        &self,
+        key: &str,
+        pending: &StoredResponse,
+    ) -> anyhow::Result<Option<StoredResponse>> {
+        let cutoff = pending.created_at - chrono::Duration::hours(IDEMPOTENCY_TTL_HOURS);
+        if self.repo.reserve(&to_record(key, pending), cutoff).await? {
+            return Ok(None);
+        }
+
+        let record = self
+            .repo
+            .find(key)
+            .await?
+            .context("idempotency key released while reserving it")?;
+        Ok(Some(StoredResponse {
+            fingerprint: record.fingerprint,
+            status: record
+                .status
+                .map(|status| u16::try_from(status).unwrap_or(200)),
+            content_type: record.content_type,
+            body: record.body,
+            created_at: record.created_at,
+        }))
+    }
+
+    async fn put(&self, key: &str, response: &StoredResponse) -> anyhow::Result<()> {
+        self.repo.upsert(&to_record(key, response)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+//! Database-backed store for background jobs and their results.
+
+use super::jobs::{Job, JobStatus, JobStore};
+use anyhow::Context;
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{JobRecord, JobRepository};
+use uuid::Uuid;
+
+/// Job store that persists jobs to PostgreSQL.
+#[derive(Clone)]
+pub struct DbJobStore {
+    /// Job repository.
+    repo: JobRepository,
+}
+
+impl DbJobStore {
+    /// Creates a new database job store.
+    pub fn new(repo: JobRepository) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl JobStore for DbJobStore {
+    async fn save(&self, job: &Job) -> anyhow::Result<()> {
+        let record = JobRecord {
+            id: Uuid::parse_str(&job.id)?,
+            kind: job.kind.clone(),
+            status: job.status.name().to_string(),
+            progress_pct: i16::from(job.progress_pct),
+            params: job.params.clone(),
+            result: job.result.clone(),
+            error: job.error.clone(),
+            tenant: job.owner.clone(),
+            created_at: job.created_at,
+            started_at: job.started_at,
+            finished_at: job.finished_at,
+        };
+        self.repo.upsert(&record).await?;
+        Ok(())
+    }
+
+    async fn load(&self, id: &str) -> anyhow::Result<Option<Job>> {
+        // Job IDs are UUIDs, so anything else is simply unknown
+        let Ok(uuid) = Uuid::parse_str(id) else {
+            return Ok(None);
+        };
+        let Some(record) = self.repo.find_by_id(uuid).await? else {
+            return Ok(None);
+        };
+
+        let status = JobStatus::from_name(&record.status)
+            .with_context(|| format!("invalid stored status for job {}", id))?;
+        Ok(Some(Job {
+            id: record.id.to_string(),
+            kind: record.kind,
+            status,
+            progress_pct: u8::try_from(record.progress_pct.clamp(0, 100)).unwrap_or_default(),
+            params: record.params,
+            owner: record.tenant,
+            created_at: record.created_at,
+            started_at: record.started_at,
+            finished_at: record.finished_at,
+            result: record.result,
+            error: record.error,
+        }))
+    }
+}
@@ -0,0 +1,645 @@
+//! Background job queue for long-running API operations.
+//!
+//! Jobs are accepted immediately and run on background tasks, with at most
+//! a fixed number running at once. Running jobs report progress and can be
+//! cancelled. Status changes and progress are broadcast as [`JobUpdate`]s
+//! for live progress bars. With a [`JobStore`], jobs and their results are
+//! persisted and remain retrievable after they leave memory.
+
+use crate::models::{EquityPoint, JobProgress};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Notify, RwLock, Semaphore, broadcast};
+use tracing::{info, warn};
+
+/// Default number of jobs run concurrently.
+pub const DEFAULT_MAX_CONCURRENT_JOBS: usize = 2;
+
+/// Finished jobs kept in memory; older ones are served from the store.
+const MAX_FINISHED_JOBS: usize = 500;
+
+/// Capacity of the job update channel.
+const UPDATE_CHANNEL_CAPACITY: usize = 1000;
+
+/// Job status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    /// Waiting for a free slot.
+    Queued,
+    /// Running.
+    Running,
+    /// Finished successfully.
+    Completed,
+    /// Finished with an error.
+    Failed,
+    /// Cancelled before finishing.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Gets the status name.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Parses a status name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "queued" => Some(Self::Queued),
+            "running" => Some(Self::Running),
+            "completed" => Some(Self::Completed),
+            "failed" => Some(Self::Failed),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the job has finished.
+    #[must_use]
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// A background job.
+#[derive(Debug, Clone)]
+pub struct Job {
+    /// Job ID.
+    pub id: String,
+    /// Job kind (e.g. `backtest`).
+    pub kind: String,
+    /// Current status.
+    pub status: JobStatus,
+    /// Progress percentage.
+    pub progress_pct: u8,
+    /// Job input, for display.
+    pub params: serde_json::Value,
+    /// Tenant that submitted the job, if any.
+    pub owner: Option<String>,
+    /// When the job was submitted.
+    pub created_at: DateTime<Utc>,
+    /// When the job started running.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the job finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Result of a completed job.
+    pub result: Option<serde_json::Value>,
+    /// Error of a failed job.
+    pub error: Option<String>,
+}
+
+/// Job status or progress change, broadcast to subscribers.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobUpdate {
+    /// Job ID.
+    pub job_id: String,
+    /// Job kind.
+    pub kind: String,
+    /// Status name.
+    pub status: String,
+    /// Progress percentage.
+    pub progress_pct: u8,
+    /// Detailed progress, while running.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<JobProgress>,
+    /// Timestamp.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl JobUpdate {
+    /// Creates an update from a job's state.
+    #[must_use]
+    pub fn new(job: &Job, progress: Option<JobProgress>) -> Self {
+        Self {
+            job_id: job.id.clone(),
+            kind: job.kind.clone(),
+            status: job.status.name().to_string(),
+            progress_pct: job.progress_pct,
+            progress,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Persistence for jobs and their results.
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// Saves a job, replacing any previous version.
+    async fn save(&self, job: &Job) -> anyhow::Result<()>;
+
+    /// Loads a job by ID.
+    async fn load(&self, id: &str) -> anyhow::Result<Option<Job>>;
+}
+
+/// Handle given to a running job to report progress and observe
+/// cancellation.
+#[derive(Clone, Default)]
+pub struct JobContext {
+    /// Shared progress and cancellation state.
+    inner: Arc<JobSignals>,
+}
+
+/// Progress and cancellation state of a job.
+#[derive(Default)]
+struct JobSignals {
+    /// Progress percentage.
+    progress: AtomicU8,
+    /// Detailed progress.
+    detail: Mutex<JobProgress>,
+    /// Whether cancellation was requested.
+    cancelled: AtomicBool,
+    /// Wakes tasks waiting for cancellation.
+    notify: Notify,
+    /// Job ID, kind, and channel progress is published to.
+    publisher: Option<(String, String, broadcast::Sender<JobUpdate>)>,
+}
+
+impl JobContext {
+    /// Creates a context publishing the progress of a job.
+    fn for_job(job: &Job, updates: broadcast::Sender<JobUpdate>) -> Self {
+        Self {
+            inner: Arc::new(JobSignals {
+                publisher: Some((job.id.clone(), job.kind.clone(), updates)),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Reports progress, clamped to 100%.
+    ///
+    /// Publishes an update whenever the percentage changes.
+    pub fn set_progress(&self, pct: u8) {
+        let pct = pct.min(100);
+        let previous = self.inner.progress.swap(pct, Ordering::Relaxed);
+        if previous != pct {
+            self.publish();
+        }
+    }
+
+    /// Records completed steps out of a total.
+    pub fn set_steps(&self, step: u64, total_steps: u64) {
+        let mut detail = self.detail_mut();
+        detail.step = step;
+        detail.total_steps = total_steps;
+    }
+
+    /// Records the best candidate found so far and publishes an update.
+    pub fn set_best_candidate(&self, candidate: serde_json::Value) {
+        self.detail_mut().best_candidate = Some(candidate);
+        self.publish();
+    }
+
+    /// Appends a point to the partial equity curve.
+    pub fn push_equity(&self, step: u64, value_usd: Decimal) {
+        self.detail_mut()
+            .equity_curve
+            .push(EquityPoint { step, value_usd });
+    }
+
+    /// Gets the detailed progress.
+    #[must_use]
+    pub fn detail(&self) -> JobProgress {
+        self.detail_mut().clone()
+    }
+
+    /// Locks the detailed progress.
+    fn detail_mut(&self) -> std::sync::MutexGuard<'_, JobProgress> {
+        self.inner.detail.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Publishes the running job's progress.
+    fn publish(&self) {
+        let Some((id, kind, updates)) = &self.inner.publisher else {
+            return;
+        };
+        let _ = updates.send(JobUpdate {
+            job_id: id.clone(),
+            kind: kind.clone(),
+            status: JobStatus::Running.name().to_string(),
+            progress_pct: self.progress(),
+            progress: Some(self.detail()),
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// Gets the reported progress.
+    #[must_use]
+    pub fn progress(&self) -> u8 {
+        self.inner.progress.load(Ordering::Relaxed)
+    }
+
+    /// Checks whether cancellation was requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Requests cancellation.
+    fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Waits until cancellation is requested.
+    async fn cancelled(&self) {
+        let notified = self.inner.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Queue running jobs on background tasks.
+pub struct JobQueue {
+    /// Jobs by ID.
+    jobs: RwLock<HashMap<String, Job>>,
+    /// Contexts of unfinished jobs by ID.
+    contexts: RwLock<HashMap<String, JobContext>>,
+    /// Slots limiting concurrently running jobs.
+    slots: Arc<Semaphore>,
+    /// Optional persistent store.
+    store: RwLock<Option<Arc<dyn JobStore>>>,
+    /// Broadcast channel for job updates.
+    updates: broadcast::Sender<JobUpdate>,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_JOBS)
+    }
+}
+
+impl JobQueue {
+    /// Creates a queue running at most `max_concurrent` jobs at once.
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            jobs: RwLock::new(HashMap::new()),
+            contexts: RwLock::new(HashMap::new()),
+            slots: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            store: RwLock::new(None),
+            updates: broadcast::channel(UPDATE_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Subscribes to job status and progress updates.
+    pub fn subscribe(&self) -> broadcast::Receiver<JobUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Sets the persistent store.
+    pub async fn set_store(&self, store: Arc<dyn JobStore>) {
+        *self.store.write().await = Some(store);
+    }
+
+    /// Enqueues a job for a tenant, or the operator, and returns its
+    /// initial state.
+    ///
+    /// The task runs once a slot is free and receives a [`JobContext`] to
+    /// report progress; its output is stored as the job result. Cancelling
+    /// the job drops the task at its next await point.
+    pub async fn submit<F, Fut>(
+        self: &Arc<Self>,
+        kind: &str,
+        owner: Option<&str>,
+        params: serde_json::Value,
+        task: F,
+    ) -> Job
+    where
+        F: FnOnce(JobContext) -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<serde_json::Value>> + Send + 'static,
+    {
+        let job = Job {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            status: JobStatus::Queued,
+            progress_pct: 0,
+            params,
+            owner: owner.map(str::to_string),
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+            result: None,
+            error: None,
+        };
+        let context = JobContext::for_job(&job, self.updates.clone());
+        self.jobs.write().await.insert(job.id.clone(), job.clone());
+        self.contexts
+            .write()
+            .await
+            .insert(job.id.clone(), context.clone());
+        self.persist(&job).await;
+        let _ = self.updates.send(JobUpdate::new(&job, None));
+        info!(id = %job.id, kind = %job.kind, "Job queued");
+
+        let queue = self.clone();
+        let id = job.id.clone();
+        tokio::spawn(async move {
+            let Ok(_permit) = queue.slots.clone().acquire_owned().await else {
+                return;
+            };
+            if context.is_cancelled() {
+                return;
+            }
+            queue
+                .update(&id, |job| {
+                    job.status = JobStatus::Running;
+                    job.started_at = Some(Utc::now());
+                })
+                .await;
+
+            let outcome = tokio::select! {
+                outcome = task(context.clone()) => Some(outcome),
+                _ = context.cancelled() => None,
+            };
+
+            queue.contexts.write().await.remove(&id);
+            let Some(outcome) = outcome else {
+                return;
+            };
+            queue
+                .update(&id, |job| {
+                    // Cancellation already finished the job
+                    if job.status.is_finished() {
+                        return;
+                    }
+                    job.finished_at = Some(Utc::now());
+                    match outcome {
+                        Ok(result) => {
+                            job.status = JobStatus::Completed;
+                            job.progress_pct = 100;
+                            job.result = Some(result);
+                        }
+                        Err(e) => {
+                            warn!(id = %job.id, kind = %job.kind, error = %e, "Job failed");
+                            job.status = JobStatus::Failed;
+                            job.progress_pct = context.progress();
+                            job.error = Some(e.to_string());
+                        }
+                    }
+                })
+                .await;
+            queue.evict_finished().await;
+        });
+
+        job
+    }
+
+    /// Gets a job by ID, falling back to the store.
+    pub async fn get(&self, id: &str) -> Option<Job> {
+        let job = self.jobs.read().await.get(id).cloned();
+        match job {
+            Some(mut job) => {
+                if job.status == JobStatus::Running
+                    && let Some(context) = self.contexts.read().await.get(id)
+                {
+                    job.progress_pct = context.progress();
+                }
+                Some(job)
+            }
+            None => {
+                let store = self.store.read().await.clone()?;
+                match store.load(id).await {
+                    Ok(job) => job,
+                    Err(e) => {
+                        warn!(id = %id, error = %e, "Failed to load job");
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gets the detailed progress of an unfinished job.
+    pub async fn progress(&self, id: &str) -> Option<JobProgress> {
+        self.contexts.read().await.get(id).map(JobContext::detail)
+    }
+
+    /// Lists unfinished jobs, oldest first.
+    pub async fn active(&self) -> Vec<Job> {
+        let ids: Vec<String> = self.contexts.read().await.keys().cloned().collect();
+        let mut jobs = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(job) = self.get(&id).await
+                && !job.status.is_finished()
+            {
+                jobs.push(job);
+            }
+        }
+        jobs.sort_by_key(|job| job.created_at);
+        jobs
+    }
+
+    /// Cancels a queued or running job.
+    ///
+    /// Returns the job's state afterwards, or `None` if it is unknown.
+    /// Finished jobs are returned unchanged.
+    pub async fn cancel(&self, id: &str) -> Option<Job> {
+        let job = self.get(id).await?;
+        if job.status.is_finished() {
+            return Some(job);
+        }
+
+        if let Some(context) = self.contexts.write().await.remove(id) {
+            context.cancel();
+        }
+        let progress = job.progress_pct;
+        self.update(id, |job| {
+            job.status = JobStatus::Cancelled;
+            job.progress_pct = progress;
+            job.finished_at = Some(Utc::now());
+        })
+        .await;
+        info!(id = %id, "Job cancelled");
+
+        self.get(id).await
+    }
+
+    /// Applies an update to a job, persists it, and broadcasts it.
+    async fn update(&self, id: &str, f: impl FnOnce(&mut Job)) {
+        let job = {
+            let mut jobs = self.jobs.write().await;
+            let Some(job) = jobs.get_mut(id) else {
+                return;
+            };
+            f(job);
+            job.clone()
+        };
+        self.persist(&job).await;
+        let _ = self.updates.send(JobUpdate::new(&job, None));
+    }
+
+    /// Saves a job to the store, if any.
+    async fn persist(&self, job: &Job) {
+        let store = self.store.read().await.clone();
+        if let Some(store) = store
+            && let Err(e) = store.save(job).await
+        {
+            warn!(id = %job.id, error = %e, "Failed to persist job");
+        }
+    }
+
+    /// Drops the oldest finished jobs from memory beyond the limit.
+    async fn evict_finished(&self) {
+        let mut jobs = self.jobs.write().await;
+        let mut finished: Vec<(DateTime<Utc>, String)> = jobs
+            .values()
+            .filter(|job| job.status.is_finished())
+            .map(|job| (job.finished_at.unwrap_or(job.created_at), job.id.clone()))
+            .collect();
+        if finished.len() <= MAX_FINISHED_JOBS {
+            return;
+        }
+
+        finished.sort();
+        let excess = finished.len() - MAX_FINISHED_JOBS;
+        for (_, id) in finished.into_iter().take(excess) {
+            jobs.remove(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn wait_finished(queue: &JobQueue, id: &str) -> Job {
+        for _ in 0..100 {
+            let job = queue.get(id).await.unwrap();
+            if job.status.is_finished() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not finish", id);
+    }
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let queue = Arc::new(JobQueue::new(1));
+
+        let ok = queue
+            .submit("test", None, serde_json::json!({}), |ctx| async move {
+                ctx.set_progress(50);
+                Ok(serde_json::json!({"answer": 42}))
+            })
+            .await;
+        assert_eq!(ok.status, JobStatus::Queued);
+
+        let failing = queue
+            .submit("test", None, serde_json::json!({}), |_| async {
+                anyhow::bail!("boom")
+            })
+            .await;
+
+        let ok = wait_finished(&queue, &ok.id).await;
+        assert_eq!(ok.status, JobStatus::Completed);
+        assert_eq!(ok.progress_pct, 100);
+        assert_eq!(ok.result.unwrap()["answer"], 42);
+        assert!(ok.started_at.is_some());
+
+        let failing = wait_finished(&queue, &failing.id).await;
+        assert_eq!(failing.status, JobStatus::Failed);
+        assert_eq!(failing.error.as_deref(), Some("boom"));
+
+        assert!(queue.get("missing").await.is_none());
+        assert!(queue.cancel("missing").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_job_updates() {
+        let queue = Arc::new(JobQueue::new(1));
+        let mut updates = queue.subscribe();
+
+        let job = queue
+            .submit("test", None, serde_json::json!({}), |ctx| async move {
+                ctx.set_steps(1, 2);
+                ctx.push_equity(1, Decimal::ONE_HUNDRED);
+                ctx.set_progress(50);
+                ctx.set_best_candidate(serde_json::json!({"score": 1}));
+                Ok(serde_json::Value::Null)
+            })
+            .await;
+        wait_finished(&queue, &job.id).await;
+
+        let mut received = Vec::new();
+        while let Ok(update) = updates.try_recv() {
+            assert_eq!(update.job_id, job.id);
+            received.push(update);
+        }
+        let statuses: Vec<&str> = received.iter().map(|u| u.status.as_str()).collect();
+        assert_eq!(
+            statuses,
+            vec!["queued", "running", "running", "running", "completed"]
+        );
+
+        let progress = received[2].progress.as_ref().unwrap();
+        assert_eq!(received[2].progress_pct, 50);
+        assert_eq!((progress.step, progress.total_steps), (1, 2));
+        assert_eq!(progress.equity_curve.len(), 1);
+        assert!(
+            received[3]
+                .progress
+                .as_ref()
+                .unwrap()
+                .best_candidate
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_job() {
+        let queue = Arc::new(JobQueue::new(1));
+
+        let running = queue
+            .submit("test", None, serde_json::json!({}), |ctx| async move {
+                ctx.set_progress(10);
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(serde_json::Value::Null)
+            })
+            .await;
+        let queued = queue
+            .submit("test", None, serde_json::json!({}), |_| async {
+                Ok(serde_json::Value::Null)
+            })
+            .await;
+
+        // Wait for the first job to start
+        for _ in 0..100 {
+            if queue.get(&running.id).await.unwrap().status == JobStatus::Running {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let cancelled = queue.cancel(&queued.id).await.unwrap();
+        assert_eq!(cancelled.status, JobStatus::Cancelled);
+
+        let cancelled = queue.cancel(&running.id).await.unwrap();
+        assert_eq!(cancelled.status, JobStatus::Cancelled);
+        assert!(cancelled.finished_at.is_some());
+
+        // The freed slot does not run the cancelled job
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let queued = queue.get(&queued.id).await.unwrap();
+        assert_eq!(queued.status, JobStatus::Cancelled);
+        assert!(queued.started_at.is_none());
+    }
+}
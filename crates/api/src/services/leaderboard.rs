@@ -0,0 +1,534 @@
+//! Leaderboard of pools and strategies by realized performance.
+//!
+//! Rankings are computed from the recorded position snapshots: fees earned
+//! relative to the capital deployed give the realized fee APR, and fees net
+//! of the change in impermanent loss give the return against holding the
+//! tokens. Strategies are credited with the positions recorded in their pool
+//! since they were created.
+
+use crate::models::LeaderboardKind;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use clmm_lp_data::prelude::{
+    Database, LeaderboardRecord, LeaderboardRepository, PoolRepository, PositionSnapshotRecord,
+    PositionSnapshotRepository, StrategyRepository,
+};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+/// Windows the leaderboard is computed for, in days.
+pub const LEADERBOARD_WINDOW_DAYS: [u32; 2] = [7, 30];
+
+/// Seconds in a year, for annualizing fee returns.
+const SECONDS_PER_YEAR: i64 = 365 * 86_400;
+
+/// A ranked pool or strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    /// Rank, starting at 1.
+    pub rank: u32,
+    /// Pool address or strategy ID.
+    pub subject: String,
+    /// Display name.
+    pub name: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Realized performance over the window.
+    pub performance: RealizedPerformance,
+    /// When the ranking was computed.
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Realized performance of a group of positions over a window.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RealizedPerformance {
+    /// Number of positions.
+    pub positions: usize,
+    /// Average capital deployed in USD.
+    pub avg_value_usd: Decimal,
+    /// Fees earned in USD.
+    pub fees_usd: Decimal,
+    /// Fees earned per year relative to capital deployed (as percentage).
+    pub fee_apr_pct: Decimal,
+    /// Fees net of the change in impermanent loss, relative to average
+    /// capital (as percentage).
+    pub vs_hodl_pct: Decimal,
+    /// Share of snapshots in range (as percentage).
+    pub time_in_range_pct: Decimal,
+}
+
+/// A stored strategy to rank.
+#[derive(Debug, Clone)]
+pub struct LeaderboardStrategy {
+    /// Strategy ID.
+    pub id: String,
+    /// Strategy name.
+    pub name: String,
+    /// Pool the strategy manages.
+    pub pool_address: String,
+    /// When the strategy was created.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Totals accumulated over the snapshots of one or more positions.
+#[derive(Debug, Clone, Default)]
+struct Totals {
+    /// Number of positions.
+    positions: usize,
+    /// Fees earned in USD.
+    fees_usd: Decimal,
+    /// Capital deployed times seconds, in USD-seconds.
+    capital_secs: Decimal,
+    /// Change in impermanent loss in USD.
+    il_change_usd: Decimal,
+    /// Snapshots in range.
+    in_range: usize,
+    /// Snapshots.
+    snapshots: usize,
+    /// First snapshot time.
+    start: Option<DateTime<Utc>>,
+    /// Last snapshot time.
+    end: Option<DateTime<Utc>>,
+}
+
+impl Totals {
+    /// Accumulates the chronologically ordered snapshots of one position.
+    ///
+    /// Positions with fewer than two snapshots are skipped. A drop in fees
+    /// is taken as a collection, after which fees accrue again from zero.
+    fn add_position(&mut self, snapshots: &[&PositionSnapshotRecord]) {
+        let (Some(first), Some(last)) = (snapshots.first(), snapshots.last()) else {
+            return;
+        };
+        if last.recorded_at <= first.recorded_at {
+            return;
+        }
+
+        let mut capital_secs = Decimal::ZERO;
+        for pair in snapshots.windows(2) {
+            let secs = (pair[1].recorded_at - pair[0].recorded_at).num_seconds();
+            capital_secs += pair[0].value_usd * Decimal::from(secs);
+            self.fees_usd += if pair[1].fees_usd >= pair[0].fees_usd {
+                pair[1].fees_usd - pair[0].fees_usd
+            } else {
+                pair[1].fees_usd
+            };
+        }
+        let span_secs = Decimal::from((last.recorded_at - first.recorded_at).num_seconds());
+        let avg_value = capital_secs / span_secs;
+
+        self.positions += 1;
+        self.capital_secs += capital_secs;
+        self.il_change_usd += (last.il_pct.abs() - first.il_pct.abs()) * avg_value;
+        self.in_range += snapshots.iter().filter(|s| s.in_range).count();
+        self.snapshots += snapshots.len();
+        self.start = Some(
+            self.start
+                .map_or(first.recorded_at, |t| t.min(first.recorded_at)),
+        );
+        self.end = Some(
+            self.end
+                .map_or(last.recorded_at, |t| t.max(last.recorded_at)),
+        );
+    }
+
+    /// Converts the totals to performance metrics.
+    fn performance(&self) -> Option<RealizedPerformance> {
+        let span_secs = (self.end? - self.start?).num_seconds();
+        if span_secs <= 0 || self.capital_secs <= Decimal::ZERO {
+            return None;
+        }
+        let avg_value = self.capital_secs / Decimal::from(span_secs);
+        let hundred = Decimal::ONE_HUNDRED;
+
+        Some(RealizedPerformance {
+            positions: self.positions,
+            avg_value_usd: avg_value.round_dp(2),
+            fees_usd: self.fees_usd.round_dp(2),
+            fee_apr_pct: (self.fees_usd * Decimal::from(SECONDS_PER_YEAR) / self.capital_secs
+                * hundred)
+                .round_dp(2),
+            vs_hodl_pct: ((self.fees_usd - self.il_change_usd) / avg_value * hundred).round_dp(2),
+            time_in_range_pct: (Decimal::from(self.in_range) / Decimal::from(self.snapshots)
+                * hundred)
+                .round_dp(2),
+        })
+    }
+}
+
+/// Groups snapshots by position in chronological order.
+fn by_position(
+    snapshots: &[PositionSnapshotRecord],
+) -> BTreeMap<&str, Vec<&PositionSnapshotRecord>> {
+    let mut positions: BTreeMap<&str, Vec<&PositionSnapshotRecord>> = BTreeMap::new();
+    for snapshot in snapshots {
+        positions
+            .entry(snapshot.position_address.as_str())
+            .or_default()
+            .push(snapshot);
+    }
+    for history in positions.values_mut() {
+        history.sort_by_key(|s| s.recorded_at);
+    }
+    positions
+}
+
+/// Sorts entries by fee APR, then return against holding, and numbers them.
+fn rank(mut entries: Vec<LeaderboardEntry>) -> Vec<LeaderboardEntry> {
+    entries.sort_by(|a, b| {
+        b.performance
+            .fee_apr_pct
+            .cmp(&a.performance.fee_apr_pct)
+            .then(b.performance.vs_hodl_pct.cmp(&a.performance.vs_hodl_pct))
+            .then(a.subject.cmp(&b.subject))
+    });
+    for (i, entry) in entries.iter_mut().enumerate() {
+        entry.rank = i as u32 + 1;
+    }
+    entries
+}
+
+/// Ranks pools by the realized performance of their recorded positions.
+///
+/// `names` maps pool addresses to display names; unknown pools are named
+/// by address.
+#[must_use]
+pub fn rank_pools(
+    snapshots: &[PositionSnapshotRecord],
+    names: &HashMap<String, String>,
+    computed_at: DateTime<Utc>,
+) -> Vec<LeaderboardEntry> {
+    let positions = by_position(snapshots);
+    let mut pools: BTreeMap<&str, Totals> = BTreeMap::new();
+    for history in positions.values() {
+        pools
+            .entry(history[0].pool_address.as_str())
+            .or_default()
+            .add_position(history);
+    }
+
+    let entries = pools
+        .into_iter()
+        .filter_map(|(pool, totals)| {
+            Some(LeaderboardEntry {
+                rank: 0,
+                subject: pool.to_string(),
+                name: names.get(pool).cloned().unwrap_or_else(|| pool.to_string()),
+                pool_address: pool.to_string(),
+                performance: totals.performance()?,
+                computed_at,
+            })
+        })
+        .collect();
+    rank(entries)
+}
+
+/// Ranks strategies by the realized performance of the positions recorded
+/// in their pool since they were created.
+#[must_use]
+pub fn rank_strategies(
+    snapshots: &[PositionSnapshotRecord],
+    strategies: &[LeaderboardStrategy],
+    computed_at: DateTime<Utc>,
+) -> Vec<LeaderboardEntry> {
+    let positions = by_position(snapshots);
+
+    let entries = strategies
+        .iter()
+        .filter_map(|strategy| {
+            let mut totals = Totals::default();
+            for history in positions.values() {
+                if history[0].pool_address != strategy.pool_address {
+                    continue;
+                }
+                let managed: Vec<&PositionSnapshotRecord> = history
+                    .iter()
+                    .copied()
+                    .filter(|s| s.recorded_at >= strategy.created_at)
+                    .collect();
+                totals.add_position(&managed);
+            }
+            Some(LeaderboardEntry {
+                rank: 0,
+                subject: strategy.id.clone(),
+                name: strategy.name.clone(),
+                pool_address: strategy.pool_address.clone(),
+                performance: totals.performance()?,
+                computed_at,
+            })
+        })
+        .collect();
+    rank(entries)
+}
+
+/// Source of computed leaderboards.
+#[async_trait]
+pub trait LeaderboardSource: Send + Sync {
+    /// Loads the top entries of a kind and window in rank order.
+    async fn load_leaderboard(
+        &self,
+        kind: LeaderboardKind,
+        window_days: u32,
+        limit: usize,
+    ) -> anyhow::Result<Vec<LeaderboardEntry>>;
+}
+
+/// Computes leaderboards from the database and persists the rankings.
+pub struct LeaderboardService {
+    /// Position snapshot repository.
+    snapshots: PositionSnapshotRepository,
+    /// Strategy repository.
+    strategies: StrategyRepository,
+    /// Pool repository, for display names.
+    pools: PoolRepository,
+    /// Leaderboard repository.
+    leaderboard: LeaderboardRepository,
+}
+
+impl LeaderboardService {
+    /// Creates a leaderboard service over a database.
+    #[must_use]
+    pub fn new(db: &Database) -> Self {
+        Self {
+            snapshots: db.position_snapshots(),
+            strategies: db.strategies(),
+            pools: db.pools(),
+            leaderboard: db.leaderboard(),
+        }
+    }
+
+    /// Recomputes and persists every leaderboard.
+    ///
+    /// Returns the number of entries written.
+    ///
+    /// # Errors
+    /// Returns an error if loading snapshots or writing rankings fails.
+    pub async fn refresh(&self) -> anyhow::Result<usize> {
+        let now = Utc::now();
+        let names: HashMap<String, String> = self
+            .pools
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|pool| {
+                let name = format!("{}/{} ({})", pool.symbol_a, pool.symbol_b, pool.protocol);
+                (pool.address, name)
+            })
+            .collect();
+        let strategies: Vec<LeaderboardStrategy> = self
+            .strategies
+            .find_all()
+            .await?
+            .into_iter()
+            .filter_map(|record| {
+                let pool_address = record.config.get("pool_address")?.as_str()?.to_string();
+                Some(LeaderboardStrategy {
+                    id: record.id.to_string(),
+                    name: record.name,
+                    pool_address,
+                    created_at: record.created_at,
+                })
+            })
+            .collect();
+
+        let mut written = 0;
+        for window_days in LEADERBOARD_WINDOW_DAYS {
+            let since = now - Duration::days(i64::from(window_days));
+            let snapshots = self.snapshots.find_since(since, None).await?;
+
+            for (kind, entries) in [
+                (LeaderboardKind::Pool, rank_pools(&snapshots, &names, now)),
+                (
+                    LeaderboardKind::Strategy,
+                    rank_strategies(&snapshots, &strategies, now),
+                ),
+            ] {
+                let records: Vec<LeaderboardRecord> = entries
+                    .iter()
+                    .map(|entry| leaderboard_record(kind, window_days, entry))
+                    .collect();
+                self.leaderboard
+                    .replace(kind.as_str(), window_days as i32, &records)
+                    .await?;
+                written += records.len();
+            }
+        }
+        Ok(written)
+    }
+
+    /// Runs the refresh loop.
+    pub async fn run(self: Arc<Self>, interval: std::time::Duration) {
+        info!(
+            interval_secs = interval.as_secs(),
+            "Starting leaderboard aggregation"
+        );
+
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.refresh().await {
+                Ok(count) => debug!(entries = count, "Refreshed leaderboards"),
+                Err(e) => error!(error = %e, "Failed to refresh leaderboards"),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderboardSource for LeaderboardService {
+    async fn load_leaderboard(
+        &self,
+        kind: LeaderboardKind,
+        window_days: u32,
+        limit: usize,
+    ) -> anyhow::Result<Vec<LeaderboardEntry>> {
+        let records = self
+            .leaderboard
+            .find_ranked(kind.as_str(), window_days as i32, limit as i64)
+            .await?;
+        Ok(records
+            .into_iter()
+            .map(|record| LeaderboardEntry {
+                rank: record.rank.max(0) as u32,
+                subject: record.subject,
+                name: record.name,
+                pool_address: record.pool_address,
+                performance: RealizedPerformance {
+                    positions: record.positions.max(0) as usize,
+                    avg_value_usd: record.avg_value_usd,
+                    fees_usd: record.fees_usd,
+                    fee_apr_pct: record.fee_apr_pct,
+                    vs_hodl_pct: record.vs_hodl_pct,
+                    time_in_range_pct: record.time_in_range_pct,
+                },
+                computed_at: record.computed_at,
+            })
+            .collect())
+    }
+}
+
+/// Maps an entry to its database record.
+fn leaderboard_record(
+    kind: LeaderboardKind,
+    window_days: u32,
+    entry: &LeaderboardEntry,
+) -> LeaderboardRecord {
+    let performance = &entry.performance;
+    LeaderboardRecord {
+        kind: kind.as_str().to_string(),
+        window_days: window_days as i32,
+        subject: entry.subject.clone(),
+        name: entry.name.clone(),
+        pool_address: entry.pool_address.clone(),
+        rank: entry.rank as i32,
+        positions: performance.positions as i32,
+        avg_value_usd: performance.avg_value_usd,
+        fees_usd: performance.fees_usd,
+        fee_apr_pct: performance.fee_apr_pct,
+        vs_hodl_pct: performance.vs_hodl_pct,
+        time_in_range_pct: performance.time_in_range_pct,
+        computed_at: entry.computed_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn snapshot(
+        position: &str,
+        pool: &str,
+        day: u32,
+        value: i64,
+        fees: i64,
+        il_pct: Decimal,
+    ) -> PositionSnapshotRecord {
+        PositionSnapshotRecord {
+            position_address: position.to_string(),
+            pool_address: pool.to_string(),
+            recorded_at: Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap(),
+            tick_lower: Some(-100),
+            tick_upper: Some(100),
+            in_range: day % 2 == 1,
+            entry_value_usd: Some(Decimal::from(value)),
+            value_usd: Decimal::from(value),
+            fees_usd: Decimal::from(fees),
+            il_pct,
+            net_pnl_usd: Decimal::from(fees),
+        }
+    }
+
+    fn history() -> Vec<PositionSnapshotRecord> {
+        vec![
+            // Pool "a": $10k earning $10/day, with 1% IL appearing
+            snapshot("p1", "a", 1, 10_000, 0, Decimal::ZERO),
+            snapshot("p1", "a", 2, 10_000, 10, Decimal::ZERO),
+            snapshot("p1", "a", 3, 10_000, 20, Decimal::new(-1, 2)),
+            // Pool "b": $1k earning $2/day, fees collected on day 3
+            snapshot("p2", "b", 3, 1_000, 4, Decimal::ZERO),
+            snapshot("p2", "b", 1, 1_000, 0, Decimal::ZERO),
+            snapshot("p2", "b", 2, 1_000, 2, Decimal::ZERO),
+            snapshot("p2", "b", 4, 1_000, 2, Decimal::ZERO),
+            // A single snapshot cannot be measured
+            snapshot("p3", "c", 1, 5_000, 0, Decimal::ZERO),
+        ]
+    }
+
+    #[test]
+    fn test_rank_pools() {
+        let names = HashMap::from([("a".to_string(), "SOL/USDC (orca)".to_string())]);
+        let now = Utc.with_ymd_and_hms(2024, 5, 5, 0, 0, 0).unwrap();
+        let pools = rank_pools(&history(), &names, now);
+
+        assert_eq!(pools.len(), 2);
+        let (b, a) = (&pools[0], &pools[1]);
+        assert_eq!((b.rank, b.subject.as_str()), (1, "b"));
+        assert_eq!((a.rank, a.name.as_str()), (2, "SOL/USDC (orca)"));
+
+        // $2/day on $1k, across the collection before day 4
+        assert_eq!(b.performance.fees_usd, Decimal::from(6));
+        assert_eq!(b.performance.fee_apr_pct, Decimal::from(73));
+        assert_eq!(b.performance.vs_hodl_pct, Decimal::new(6, 1));
+
+        // $10/day on $10k, with $100 of IL against $20 of fees
+        assert_eq!(a.performance.avg_value_usd, Decimal::from(10_000));
+        assert_eq!(a.performance.fee_apr_pct, Decimal::new(3650, 2));
+        assert_eq!(a.performance.vs_hodl_pct, Decimal::new(-8, 1));
+        assert_eq!(a.performance.time_in_range_pct, Decimal::new(6667, 2));
+    }
+
+    #[test]
+    fn test_rank_strategies() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 5, 0, 0, 0).unwrap();
+        let strategy = |id: &str, pool: &str, day: u32| LeaderboardStrategy {
+            id: id.to_string(),
+            name: format!("strategy {}", id),
+            pool_address: pool.to_string(),
+            created_at: Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap(),
+        };
+        let strategies = vec![
+            strategy("s1", "a", 1),
+            // Created on day 2, so only days 2 and 3 count
+            strategy("s2", "a", 2),
+            // No recorded positions
+            strategy("s3", "z", 1),
+        ];
+        let ranked = rank_strategies(&history(), &strategies, now);
+
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked.iter().all(|e| e.pool_address == "a"));
+        // Same fee APR; the later start carries the same IL on fewer fees
+        assert_eq!(ranked[0].subject, "s1");
+        assert_eq!(ranked[0].performance.fees_usd, Decimal::from(20));
+        assert_eq!(ranked[1].subject, "s2");
+        assert_eq!(ranked[1].performance.fees_usd, Decimal::from(10));
+        assert_eq!(
+            ranked[1].performance.fee_apr_pct,
+            ranked[0].performance.fee_apr_pct
+        );
+        assert_eq!(ranked[1].performance.vs_hodl_pct, Decimal::new(-9, 1));
+    }
+}
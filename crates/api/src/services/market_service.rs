@@ -0,0 +1,293 @@
+//! Market data service serving cached candles and pair analysis.
+
+use crate::models::{MarketAnalysisResponse, SuggestedRangeResponse};
+use clmm_lp_data::MarketDataProvider;
+use clmm_lp_data::prelude::DataResult;
+use clmm_lp_data::prelude::{Cache, CacheKeyBuilder, MemoryCache};
+use clmm_lp_data::providers::jupiter::known_mints;
+use clmm_lp_domain::entities::price_candle::PriceCandle;
+use clmm_lp_domain::entities::token::Token;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Default time candles stay cached.
+pub const DEFAULT_MARKET_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Maximum number of cached candle series.
+const MAX_CACHE_ENTRIES: usize = 1000;
+
+/// Seconds in a day.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Well-known tokens as (symbol, mint, decimals).
+const KNOWN_TOKENS: &[(&str, &str, u8)] = &[
+    ("SOL", known_mints::SOL, 9),
+    ("USDC", known_mints::USDC, 6),
+    ("USDT", known_mints::USDT, 6),
+    ("RAY", known_mints::RAY, 6),
+    ("ORCA", known_mints::ORCA, 6),
+    ("JUP", known_mints::JUP, 6),
+    ("BONK", known_mints::BONK, 5),
+];
+
+/// Parses a pair such as `SOL-USDC` into its base and quote tokens.
+///
+/// Each side is either a well-known symbol or a mint address.
+#[must_use]
+pub fn parse_pair(pair: &str) -> Option<(Token, Token)> {
+    let (base, quote) = pair.split_once(['-', '_'])?;
+    Some((resolve_token(base)?, resolve_token(quote)?))
+}
+
+/// Resolves a token symbol or mint address.
+fn resolve_token(token: &str) -> Option<Token> {
+    if let Some((symbol, mint, decimals)) = KNOWN_TOKENS
+        .iter()
+        .find(|(symbol, _, _)| symbol.eq_ignore_ascii_case(token))
+    {
+        return Some(Token::new(*mint, *symbol, *decimals, *symbol));
+    }
+
+    // Mint addresses are base58-encoded 32-byte keys
+    let is_mint =
+        (32..=44).contains(&token.len()) && token.chars().all(|c| c.is_ascii_alphanumeric());
+    is_mint.then(|| Token::new(token, token, 9, token))
+}
+
+/// Market data service fetching candles through a provider with caching.
+pub struct MarketDataService {
+    /// Underlying market data provider.
+    provider: Arc<dyn MarketDataProvider + Send + Sync>,
+    /// Cache of serialized candle series.
+    cache: MemoryCache,
+    /// Time candles stay cached.
+    ttl: Duration,
+}
+
+impl MarketDataService {
+    /// Creates a new market data service.
+    pub fn new(provider: Arc<dyn MarketDataProvider + Send + Sync>) -> Self {
+        Self {
+            provider,
+            cache: MemoryCache::with_max_entries(MAX_CACHE_ENTRIES),
+            ttl: DEFAULT_MARKET_CACHE_TTL,
+        }
+    }
+
+    /// Sets the time candles stay cached.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Checks that the underlying provider is reachable.
+    ///
+    /// # Errors
+    /// Returns an error if the provider cannot be reached.
+    pub async fn health_check(&self) -> DataResult<()> {
+        self.provider.health_check().await
+    }
+
+    /// Gets candles for a pair over the last `days`.
+    ///
+    /// The window is aligned to the resolution, so requests within the same
+    /// candle share a cache entry.
+    ///
+    /// # Errors
+    /// Returns an error if the provider request fails.
+    pub async fn candles(
+        &self,
+        base: &Token,
+        quote: &Token,
+        resolution: u64,
+        days: u64,
+    ) -> DataResult<Vec<PriceCandle>> {
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        let end = now - now % resolution.max(1);
+        let start = end.saturating_sub(days * SECONDS_PER_DAY);
+
+        let key = CacheKeyBuilder::new()
+            .with("candles")
+            .with(&base.mint_address)
+            .with(&quote.mint_address)
+            .with(resolution.to_string())
+            .with(start.to_string())
+            .with(end.to_string())
+            .build();
+        if let Some(data) = self.cache.get(&key)
+            && let Ok(candles) = serde_json::from_slice(&data)
+        {
+            debug!(key = %key, "Market data cache hit");
+            return Ok(candles);
+        }
+
+        let candles = self
+            .provider
+            .get_price_history(base, quote, start, end, resolution)
+            .await?;
+        if let Ok(data) = serde_json::to_vec(&candles) {
+            self.cache.set(&key, data, self.ttl);
+        }
+        Ok(candles)
+    }
+}
+
+/// Analyzes candles the same way as the CLI `analyze` command.
+///
+/// Returns `None` if there are no candles.
+#[must_use]
+pub fn analyze_candles(
+    pair: &str,
+    candles: &[PriceCandle],
+    resolution: u64,
+    days: u64,
+) -> Option<MarketAnalysisResponse> {
+    let prices: Vec<Decimal> = candles.iter().map(|c| c.close.value).collect();
+    let current_price = *prices.last()?;
+    let start_price = *prices.first()?;
+    let high_price = prices.iter().copied().max()?;
+    let low_price = prices.iter().copied().min()?;
+    let avg_price = prices.iter().copied().sum::<Decimal>() / Decimal::from(prices.len());
+
+    let price_change_pct = if start_price.is_zero() {
+        Decimal::ZERO
+    } else {
+        (current_price - start_price) / start_price * Decimal::ONE_HUNDRED
+    };
+
+    // Annualized volatility of log returns
+    let closes: Vec<f64> = prices.iter().filter_map(|p| p.to_f64()).collect();
+    let returns: Vec<f64> = closes
+        .windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect();
+    let volatility_annual = if returns.is_empty() {
+        0.0
+    } else {
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance =
+            returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64;
+        let periods_per_year = 365.0 * SECONDS_PER_DAY as f64 / resolution.max(1) as f64;
+        variance.sqrt() * periods_per_year.sqrt()
+    };
+    let volatility_daily = volatility_annual / 365.0_f64.sqrt();
+
+    let total_volume: Decimal = candles.iter().map(|c| c.volume_token_a.to_decimal()).sum();
+    let candles_per_day = Decimal::from(SECONDS_PER_DAY) / Decimal::from(resolution.max(1));
+    let avg_daily_volume = total_volume / Decimal::from(candles.len()) * candles_per_day;
+
+    // Suggested ranges based on volatility
+    let sigma = current_price * Decimal::from_f64(volatility_daily).unwrap_or_default();
+    let wide_margin = Decimal::new(5, 2);
+    let suggested_ranges = vec![
+        SuggestedRangeResponse {
+            name: "conservative".to_string(),
+            description: "1σ daily move".to_string(),
+            lower_price: (current_price - sigma).round_dp(6),
+            upper_price: (current_price + sigma).round_dp(6),
+        },
+        SuggestedRangeResponse {
+            name: "moderate".to_string(),
+            description: "2σ daily move".to_string(),
+            lower_price: (current_price - sigma * Decimal::TWO).round_dp(6),
+            upper_price: (current_price + sigma * Decimal::TWO).round_dp(6),
+        },
+        SuggestedRangeResponse {
+            name: "wide".to_string(),
+            description: "Period range with a 5% margin".to_string(),
+            lower_price: (low_price * (Decimal::ONE - wide_margin)).round_dp(6),
+            upper_price: (high_price * (Decimal::ONE + wide_margin)).round_dp(6),
+        },
+    ];
+
+    Some(MarketAnalysisResponse {
+        pair: pair.to_string(),
+        period_days: days,
+        resolution,
+        data_points: candles.len(),
+        current_price,
+        start_price,
+        high_price,
+        low_price,
+        avg_price: avg_price.round_dp(6),
+        price_change_pct: price_change_pct.round_dp(4),
+        volatility_annual: Decimal::from_f64(volatility_annual)
+            .unwrap_or_default()
+            .round_dp(6),
+        volatility_daily: Decimal::from_f64(volatility_daily)
+            .unwrap_or_default()
+            .round_dp(6),
+        total_volume: total_volume.round_dp(6),
+        avg_daily_volume: avg_daily_volume.round_dp(6),
+        suggested_ranges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_domain::value_objects::amount::Amount;
+    use clmm_lp_domain::value_objects::price::Price;
+
+    fn candles(prices: &[i64]) -> Vec<PriceCandle> {
+        let (base, quote) = parse_pair("SOL-USDC").unwrap();
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, price)| {
+                let price = Price::new(Decimal::from(*price));
+                PriceCandle {
+                    token_a: base.clone(),
+                    token_b: quote.clone(),
+                    start_timestamp: i as u64 * 3600,
+                    duration_seconds: 3600,
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    volume_token_a: Amount::from_decimal(Decimal::from(10), 9),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        let (base, quote) = parse_pair("sol-USDC").unwrap();
+        assert_eq!(base.symbol, "SOL");
+        assert_eq!(base.mint_address, known_mints::SOL);
+        assert_eq!(quote.decimals, 6);
+
+        let (base, _) = parse_pair("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN_USDC").unwrap();
+        assert_eq!(base.mint_address, known_mints::JUP);
+
+        assert!(parse_pair("SOL").is_none());
+        assert!(parse_pair("SOL-NOPE").is_none());
+    }
+
+    #[test]
+    fn test_analyze_candles() {
+        let analysis =
+            analyze_candles("SOL-USDC", &candles(&[100, 110, 90, 120]), 3600, 1).unwrap();
+
+        assert_eq!(analysis.data_points, 4);
+        assert_eq!(analysis.current_price, Decimal::from(120));
+        assert_eq!(analysis.high_price, Decimal::from(120));
+        assert_eq!(analysis.low_price, Decimal::from(90));
+        assert_eq!(analysis.price_change_pct, Decimal::from(20));
+        assert!(analysis.volatility_annual > analysis.volatility_daily);
+        // 10 per hourly candle
+        assert_eq!(analysis.avg_daily_volume, Decimal::from(240));
+        assert_eq!(analysis.suggested_ranges.len(), 3);
+        assert!(
+            analysis.suggested_ranges[0].lower_price > analysis.suggested_ranges[1].lower_price
+        );
+
+        assert!(analyze_candles("SOL-USDC", &[], 3600, 1).is_none());
+    }
+}
@@ -3,8 +3,57 @@
 //! This module provides services that bridge API handlers with
 //! the execution layer.
 
+pub mod audit_log;
+pub mod backtest_service;
+pub mod idempotency_store;
+pub mod job_store;
+pub mod jobs;
+pub mod leaderboard;
+pub mod market_service;
+pub mod optimization_service;
+pub mod pool_screener;
+pub mod portfolio_history;
 pub mod position_service;
+pub mod request_audit_store;
+pub mod state_store;
+pub mod strategy_document;
 pub mod strategy_service;
+pub mod strategy_store;
+pub mod wallets;
+pub mod webhooks;
 
+pub use audit_log::DbAuditLog;
+pub use backtest_service::{
+    DbPriceHistory, HistoricalCandle, PoolHistory, PriceHistorySource, run_backtest,
+    run_backtest_job,
+};
+pub use idempotency_store::DbIdempotencyStore;
+pub use job_store::DbJobStore;
+pub use jobs::{Job, JobContext, JobQueue, JobStatus, JobStore, JobUpdate};
+pub use leaderboard::{
+    LEADERBOARD_WINDOW_DAYS, LeaderboardEntry, LeaderboardService, LeaderboardSource,
+    LeaderboardStrategy, RealizedPerformance, rank_pools, rank_strategies,
+};
+pub use market_service::{
+    DEFAULT_MARKET_CACHE_TTL, MarketDataService, analyze_candles, parse_pair,
+};
+pub use optimization_service::{estimate_fee_apr_pct, estimate_volatility, run_optimization};
+pub use pool_screener::{
+    DbPoolStats, MAX_PAGE_SIZE, PoolScreener, PoolStatsSource, ScreenedPool, screen_pools,
+};
+pub use portfolio_history::{
+    DbHistoryStore, DbPositionHistory, FeeCollectionHistoryPoint, MAX_HISTORY_DAYS,
+    PositionHistoryPoint, PositionHistorySource, RebalanceHistoryPoint, aggregate_history,
+    history_interval, parse_window,
+};
 pub use position_service::PositionService;
+pub use request_audit_store::DbRequestAuditLog;
+pub use state_store::DbStateStore;
+pub use strategy_document::{STRATEGY_FORMAT_VERSION, StrategyDocument, StrategyDocumentError};
 pub use strategy_service::StrategyService;
+pub use strategy_store::{DbStrategyStore, StoredStrategy, StrategyStore, restore_strategies};
+pub use wallets::{RegisteredWallet, WalletRegistry};
+pub use webhooks::{
+    WebhookRegistry, WebhookSubscription, is_valid_webhook_url, spawn_webhook_dispatcher,
+    webhook_events,
+};
@@ -0,0 +1,439 @@
+//! Optimization service running the range and parameter optimizers
+//! against stored price history.
+
+use super::backtest_service::PoolHistory;
+use super::jobs::JobContext;
+use crate::models::{
+    ObjectiveType, OptimizeRequest, OptimizeResultResponse, ParameterCandidateResponse,
+    RangeCandidateResponse, StrategyType,
+};
+use anyhow::Context;
+use clmm_lp_domain::prelude::{Amount, Position, PositionId, PositionStatus};
+use clmm_lp_optimization::prelude::{
+    CandidateResult, GridSearchOptimizer, MaximizeFees, MaximizeNetPnL, MaximizeSharpeRatio,
+    MaximizeTimeInRange, MinimizeIL, ObjectiveFunction, OptimizationConfig, ParameterOptimizer,
+    PositionConstraints, RangeOptimizer, RebalanceConstraints, RiskAdjustedReturn,
+};
+use clmm_lp_simulation::prelude::ConstantVolume;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use uuid::Uuid;
+
+/// Seconds in a day.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Share of progress taken by the range search.
+const RANGE_PROGRESS_PCT: usize = 80;
+
+/// Market conditions estimated from price history.
+struct MarketEstimate {
+    /// Latest price.
+    price: Decimal,
+    /// Annualized volatility.
+    volatility: f64,
+    /// Average daily volume in USD.
+    daily_volume_usd: Decimal,
+    /// Latest recorded pool liquidity.
+    pool_liquidity: u128,
+}
+
+/// Runs an optimization over a pool's price history.
+///
+/// Candidate ranges are ranked by Monte Carlo simulation using the
+/// volatility and volume of the history, and rebalancing parameters are
+/// then ranked for the best range.
+///
+/// # Errors
+/// Returns an error if the history is too short, no candidate satisfies
+/// the constraints, or the job is cancelled.
+pub fn run_optimization(
+    request: &OptimizeRequest,
+    history: &PoolHistory,
+    job: &JobContext,
+) -> anyhow::Result<OptimizeResultResponse> {
+    match request.objective {
+        ObjectiveType::NetPnl => optimize(request, history, job, &MaximizeNetPnL),
+        ObjectiveType::Fees => optimize(request, history, job, &MaximizeFees),
+        ObjectiveType::Sharpe => optimize(request, history, job, &MaximizeSharpeRatio::default()),
+        ObjectiveType::MinIl => optimize(request, history, job, &MinimizeIL::default()),
+        ObjectiveType::TimeInRange => optimize(request, history, job, &MaximizeTimeInRange),
+        ObjectiveType::RiskAdjusted => {
+            optimize(request, history, job, &RiskAdjustedReturn::default())
+        }
+    }
+}
+
+/// Runs the range and parameter search for an objective.
+fn optimize<O: ObjectiveFunction>(
+    request: &OptimizeRequest,
+    history: &PoolHistory,
+    job: &JobContext,
+    objective: &O,
+) -> anyhow::Result<OptimizeResultResponse> {
+    let market = estimate_market(history).with_context(|| {
+        format!(
+            "not enough price history for {} in the requested period",
+            request.pool_address
+        )
+    })?;
+    let constraints = &request.constraints;
+
+    let defaults = PositionConstraints::default();
+    let position_constraints = PositionConstraints::new()
+        .with_min_range_width(
+            constraints
+                .min_range_width
+                .unwrap_or(defaults.min_range_width),
+        )
+        .with_max_range_width(
+            constraints
+                .max_range_width
+                .unwrap_or(defaults.max_range_width),
+        );
+    let widths: Vec<Decimal> = GridSearchOptimizer::new()
+        .range_widths
+        .into_iter()
+        .filter(|w| position_constraints.is_valid_range_width(*w))
+        .collect();
+    if widths.is_empty() {
+        anyhow::bail!("no candidate range widths satisfy the constraints");
+    }
+
+    // Simulate one step per day over the horizon
+    let mut optimizer = RangeOptimizer::new(
+        request.iterations,
+        request.horizon_days as usize,
+        1.0 / 365.0,
+    );
+    if let Some(seed) = request.seed {
+        optimizer = optimizer.with_seed(seed);
+    }
+    let position = base_position(&request.pool_address);
+    let volume = ConstantVolume::new(market.daily_volume_usd);
+
+    let mut ranges = Vec::with_capacity(widths.len());
+    for (i, width) in widths.iter().enumerate() {
+        if job.is_cancelled() {
+            anyhow::bail!("optimization cancelled");
+        }
+        ranges.push(optimizer.evaluate_width(
+            *width,
+            &position,
+            market.price,
+            market.volatility,
+            0.0,
+            &volume,
+            market.pool_liquidity,
+            history.fee_rate,
+            objective,
+        ));
+        job.set_steps((i + 1) as u64, widths.len() as u64);
+        if let Some(best) = ranges.iter().max_by_key(|c| c.score) {
+            job.set_best_candidate(
+                serde_json::to_value(range_candidate(1, best, market.price)).unwrap_or_default(),
+            );
+        }
+        job.set_progress(((i + 1) * RANGE_PROGRESS_PCT / widths.len()) as u8);
+    }
+    GridSearchOptimizer::rank_candidates(&mut ranges);
+    let best_width = ranges[0].range_width;
+
+    let defaults = RebalanceConstraints::default();
+    let mut rebalance_constraints = RebalanceConstraints::new()
+        .with_interval_bounds(
+            constraints
+                .min_rebalance_interval
+                .unwrap_or(defaults.min_rebalance_interval),
+            constraints
+                .max_rebalance_interval
+                .unwrap_or(defaults.max_rebalance_interval),
+        )
+        .with_il_threshold_bounds(
+            defaults.min_il_threshold,
+            constraints
+                .max_il_threshold
+                .unwrap_or(defaults.max_il_threshold),
+        );
+    if let Some(max) = constraints.max_rebalances {
+        rebalance_constraints = rebalance_constraints.with_max_rebalances(max);
+    }
+
+    // Strategy parameters are expressed in hourly steps
+    let mut config = OptimizationConfig::new()
+        .with_iterations(request.iterations)
+        .with_steps(request.horizon_days as usize * 24)
+        .with_volatility(market.volatility)
+        .with_price(market.price)
+        .with_fee_rate(history.fee_rate);
+    config.pool_liquidity = market.pool_liquidity;
+    config.tx_cost = request.tx_cost_usd;
+
+    let parameter_optimizer =
+        ParameterOptimizer::new().with_constraints(rebalance_constraints.clone());
+    let mut parameters: Vec<ParameterCandidateResponse> = Vec::new();
+    parameters.extend(
+        parameter_optimizer
+            .optimize_threshold(&config, best_width, objective)
+            .into_iter()
+            .map(|c| ParameterCandidateResponse {
+                rank: 0,
+                strategy_type: StrategyType::Threshold,
+                parameters: serde_json::json!({
+                    "price_threshold": c.params.price_threshold,
+                    "il_threshold": c.params.il_threshold,
+                    "rebalance_on_out_of_range": c.params.rebalance_on_out_of_range,
+                }),
+                expected_fees: c.expected_fees,
+                expected_il: c.expected_il,
+                expected_rebalances: c.expected_rebalances,
+                score: c.score,
+            }),
+    );
+    parameters.extend(
+        parameter_optimizer
+            .optimize_periodic(&config, best_width, objective)
+            .into_iter()
+            .map(|c| ParameterCandidateResponse {
+                rank: 0,
+                strategy_type: StrategyType::Periodic,
+                parameters: serde_json::json!({
+                    "interval": c.params.interval,
+                    "only_when_out_of_range": c.params.only_when_out_of_range,
+                }),
+                expected_fees: c.expected_fees,
+                expected_il: c.expected_il,
+                expected_rebalances: c.expected_rebalances,
+                score: c.score,
+            }),
+    );
+    parameters.extend(
+        parameter_optimizer
+            .optimize_il_limit(&config, best_width, objective)
+            .into_iter()
+            .map(|c| ParameterCandidateResponse {
+                rank: 0,
+                strategy_type: StrategyType::IlLimit,
+                parameters: serde_json::json!({
+                    "max_il": c.params.max_il,
+                    "close_il": c.params.close_il,
+                    "grace_period": c.params.grace_period,
+                }),
+                expected_fees: c.expected_fees,
+                expected_il: c.expected_il,
+                expected_rebalances: c.expected_rebalances,
+                score: c.score,
+            }),
+    );
+    if let Some(max) = rebalance_constraints.max_rebalances {
+        parameters.retain(|c| c.expected_rebalances <= max);
+    }
+    parameters.sort_by(|a, b| b.score.cmp(&a.score));
+    parameters.truncate(request.top_n);
+    for (i, candidate) in parameters.iter_mut().enumerate() {
+        candidate.rank = i + 1;
+    }
+    job.set_progress(100);
+
+    let range_candidates = ranges
+        .iter()
+        .take(request.top_n)
+        .enumerate()
+        .map(|(i, c)| range_candidate(i + 1, c, market.price))
+        .collect();
+
+    Ok(OptimizeResultResponse {
+        current_price: market.price,
+        volatility: Decimal::from_f64(market.volatility)
+            .unwrap_or_default()
+            .round_dp(6),
+        daily_volume_usd: market.daily_volume_usd,
+        fee_rate: history.fee_rate,
+        range_candidates,
+        parameter_candidates: parameters,
+    })
+}
+
+/// Maps a range candidate to its API representation around a price.
+fn range_candidate(rank: usize, c: &CandidateResult, price: Decimal) -> RangeCandidateResponse {
+    RangeCandidateResponse {
+        rank,
+        range_width_pct: c.range_width,
+        lower_price: price * (Decimal::ONE - c.range_width),
+        upper_price: price * (Decimal::ONE + c.range_width),
+        expected_fees: c.expected_fees,
+        expected_il: c.expected_il,
+        expected_pnl: c.net_pnl,
+        score: c.score,
+    }
+}
+
+/// Estimates annualized volatility from a pool's history.
+///
+/// Returns `None` if the history has fewer than two usable candles.
+pub fn estimate_volatility(history: &PoolHistory) -> Option<f64> {
+    estimate_market(history).map(|market| market.volatility)
+}
+
+/// Estimates the fee APR of a full-range position from a pool's history
+/// (as percentage).
+///
+/// Returns `None` if the history is too short or has no recorded liquidity.
+pub fn estimate_fee_apr_pct(history: &PoolHistory) -> Option<Decimal> {
+    let liquidity = history
+        .candles
+        .last()?
+        .liquidity_usd
+        .filter(|liquidity| *liquidity > Decimal::ZERO)?;
+    let market = estimate_market(history)?;
+    let yearly_fees = market.daily_volume_usd * history.fee_rate * Decimal::from(365);
+    Some((yearly_fees / liquidity * Decimal::ONE_HUNDRED).round_dp(2))
+}
+
+/// Estimates price, volatility, and volume from a pool's history.
+///
+/// Returns `None` if the history has fewer than two usable candles.
+fn estimate_market(history: &PoolHistory) -> Option<MarketEstimate> {
+    let candles = &history.candles;
+    let (first, last) = (candles.first()?, candles.last()?);
+    if candles.len() < 2 || last.timestamp <= first.timestamp {
+        return None;
+    }
+    let interval = (last.timestamp - first.timestamp) as f64 / (candles.len() - 1) as f64;
+
+    let returns: Vec<f64> = candles
+        .windows(2)
+        .filter_map(|pair| {
+            let (a, b) = (pair[0].price.to_f64()?, pair[1].price.to_f64()?);
+            (a > 0.0 && b > 0.0).then(|| (b / a).ln())
+        })
+        .collect();
+    if returns.is_empty() {
+        return None;
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64;
+    let periods_per_year = 365.0 * SECONDS_PER_DAY / interval;
+
+    let total_volume: Decimal = candles.iter().map(|c| c.volume_usd).sum();
+    let days = Decimal::from_f64(candles.len() as f64 * interval / SECONDS_PER_DAY)?;
+
+    Some(MarketEstimate {
+        price: last.price,
+        volatility: variance.sqrt() * periods_per_year.sqrt(),
+        daily_volume_usd: (total_volume / days).round_dp(2),
+        pool_liquidity: last
+            .liquidity_usd
+            .and_then(|liquidity| liquidity.to_u128())
+            .filter(|liquidity| *liquidity > 0)
+            .unwrap_or(OptimizationConfig::default().pool_liquidity),
+    })
+}
+
+/// Creates the empty position used as the simulation template.
+fn base_position(pool_address: &str) -> Position {
+    let zero = Amount::from_decimal(Decimal::ZERO, 6);
+    Position {
+        id: PositionId(Uuid::new_v4()),
+        pool_address: pool_address.to_string(),
+        owner_address: "optimizer".to_string(),
+        liquidity_amount: 0,
+        deposited_amount_a: zero,
+        deposited_amount_b: zero,
+        current_amount_a: zero,
+        current_amount_b: zero,
+        unclaimed_fees_a: zero,
+        unclaimed_fees_b: zero,
+        range: None,
+        opened_at: 0,
+        status: PositionStatus::Open,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::HistoricalCandle;
+
+    fn request(top_n: usize) -> OptimizeRequest {
+        serde_json::from_value(serde_json::json!({
+            "pool_address": "pool",
+            "start_date": "2024-01-01",
+            "end_date": "2024-01-02",
+            "iterations": 5,
+            "horizon_days": 5,
+            "top_n": top_n
+        }))
+        .unwrap()
+    }
+
+    fn history(len: usize) -> PoolHistory {
+        PoolHistory {
+            fee_rate: Decimal::new(3, 3),
+            candles: (0..len)
+                .map(|i| HistoricalCandle {
+                    timestamp: i as i64 * 3600,
+                    price: Decimal::from(100 + (i % 3) as i64),
+                    volume_usd: Decimal::from(10000),
+                    liquidity_usd: Some(Decimal::from(1_000_000)),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_run_optimization() {
+        let job = JobContext::default();
+        let result = run_optimization(&request(3), &history(48), &job).unwrap();
+
+        assert_eq!(result.current_price, Decimal::from(102));
+        assert!(result.volatility > Decimal::ZERO);
+        // 10k per hourly candle
+        assert_eq!(result.daily_volume_usd, Decimal::from(240_000));
+        assert_eq!(result.range_candidates.len(), 3);
+        assert_eq!(result.range_candidates[0].rank, 1);
+        assert!(result.range_candidates[0].score >= result.range_candidates[1].score);
+        assert!(!result.parameter_candidates.is_empty());
+        assert!(result.parameter_candidates.len() <= 3);
+        assert_eq!(job.progress(), 100);
+    }
+
+    #[test]
+    fn test_seeded_optimization_is_reproducible() {
+        let mut seeded = request(3);
+        seeded.seed = Some(7);
+        let runs: Vec<_> = (0..2)
+            .map(|_| {
+                let result =
+                    run_optimization(&seeded, &history(48), &JobContext::default()).unwrap();
+                serde_json::to_value(result.range_candidates).unwrap()
+            })
+            .collect();
+
+        assert_eq!(runs[0], runs[1]);
+    }
+
+    #[test]
+    fn test_estimate_market_inputs() {
+        let hourly = history(48);
+        assert!(estimate_volatility(&hourly).unwrap() > 0.0);
+        // 240k daily volume at 0.3% over 1M liquidity
+        assert_eq!(estimate_fee_apr_pct(&hourly), Some(Decimal::new(2628, 2)));
+
+        let mut unrecorded = hourly.clone();
+        for candle in &mut unrecorded.candles {
+            candle.liquidity_usd = None;
+        }
+        assert_eq!(estimate_fee_apr_pct(&unrecorded), None);
+        assert_eq!(estimate_volatility(&history(1)), None);
+    }
+
+    #[test]
+    fn test_run_optimization_requires_history() {
+        let job = JobContext::default();
+        assert!(run_optimization(&request(3), &history(1), &job).is_err());
+
+        let mut narrow = request(3);
+        narrow.constraints.min_range_width = Some(Decimal::from(2));
+        assert!(run_optimization(&narrow, &history(48), &job).is_err());
+    }
+}
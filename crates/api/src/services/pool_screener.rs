@@ -0,0 +1,307 @@
+//! Pool screener filtering and ranking stored pools by market statistics.
+
+use crate::models::{
+    PoolSearchQuery, PoolSearchResponse, PoolSearchResultResponse, PoolSortField, SortOrder,
+};
+use async_trait::async_trait;
+use clmm_lp_data::prelude::PoolRepository;
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Window over which volume is summed, in seconds.
+const VOLUME_WINDOW_SECS: i64 = 86_400;
+
+/// Maximum results per page.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// A pool with the statistics used for screening.
+#[derive(Debug, Clone)]
+pub struct ScreenedPool {
+    /// Pool address.
+    pub address: String,
+    /// Protocol name.
+    pub protocol: String,
+    /// Token A mint.
+    pub token_mint_a: String,
+    /// Token B mint.
+    pub token_mint_b: String,
+    /// Token A symbol.
+    pub symbol_a: String,
+    /// Token B symbol.
+    pub symbol_b: String,
+    /// Fee tier in basis points.
+    pub fee_rate_bps: u32,
+    /// Latest TVL in USD, if recorded.
+    pub tvl_usd: Option<Decimal>,
+    /// 24h volume in USD.
+    pub volume_24h_usd: Decimal,
+}
+
+impl ScreenedPool {
+    /// Estimates the fee APR from 24h volume, fee tier, and TVL.
+    ///
+    /// Returns `None` without a positive TVL.
+    #[must_use]
+    pub fn fee_apr(&self) -> Option<Decimal> {
+        let tvl = self
+            .tvl_usd
+            .filter(|tvl| tvl.is_sign_positive() && !tvl.is_zero())?;
+        let daily_fees =
+            self.volume_24h_usd * Decimal::from(self.fee_rate_bps) / Decimal::from(10_000);
+        Some(daily_fees * Decimal::from(365) / tvl)
+    }
+
+    /// Checks whether either side of the pool matches a symbol or mint.
+    fn has_token(&self, token: &str) -> bool {
+        self.symbol_a.eq_ignore_ascii_case(token)
+            || self.symbol_b.eq_ignore_ascii_case(token)
+            || self.token_mint_a == token
+            || self.token_mint_b == token
+    }
+}
+
+/// Source of pools and their statistics.
+#[async_trait]
+pub trait PoolStatsSource: Send + Sync {
+    /// Loads all pools with TVL and the volume since a Unix timestamp.
+    async fn load_pools(&self, since: i64) -> anyhow::Result<Vec<ScreenedPool>>;
+}
+
+/// Pool statistics source backed by the pool and price history tables.
+pub struct DbPoolStats {
+    /// Pool repository.
+    pools: PoolRepository,
+}
+
+impl DbPoolStats {
+    /// Creates a new database pool statistics source.
+    pub fn new(pools: PoolRepository) -> Self {
+        Self { pools }
+    }
+}
+
+#[async_trait]
+impl PoolStatsSource for DbPoolStats {
+    async fn load_pools(&self, since: i64) -> anyhow::Result<Vec<ScreenedPool>> {
+        let records = self.pools.find_all_with_stats(since).await?;
+        Ok(records
+            .into_iter()
+            .map(|record| ScreenedPool {
+                address: record.pool.address,
+                protocol: record.pool.protocol,
+                token_mint_a: record.pool.token_mint_a,
+                token_mint_b: record.pool.token_mint_b,
+                symbol_a: record.pool.symbol_a,
+                symbol_b: record.pool.symbol_b,
+                fee_rate_bps: record.pool.fee_tier.max(0) as u32,
+                tvl_usd: record.tvl,
+                volume_24h_usd: record.volume,
+            })
+            .collect())
+    }
+}
+
+/// Pool screener searching pools by tokens and market statistics.
+pub struct PoolScreener {
+    /// Source of pools and statistics.
+    source: Arc<dyn PoolStatsSource>,
+}
+
+impl PoolScreener {
+    /// Creates a new pool screener.
+    pub fn new(source: Arc<dyn PoolStatsSource>) -> Self {
+        Self { source }
+    }
+
+    /// Searches pools using the last 24 hours of volume.
+    ///
+    /// # Errors
+    /// Returns an error if the pools cannot be loaded.
+    pub async fn search(&self, query: &PoolSearchQuery) -> anyhow::Result<PoolSearchResponse> {
+        let since = chrono::Utc::now().timestamp() - VOLUME_WINDOW_SECS;
+        let pools = self.source.load_pools(since).await?;
+        Ok(screen_pools(pools, query))
+    }
+}
+
+/// Filters, sorts, and paginates pools.
+///
+/// Pools without a fee APR estimate sort last in either order.
+#[must_use]
+pub fn screen_pools(pools: Vec<ScreenedPool>, query: &PoolSearchQuery) -> PoolSearchResponse {
+    let mut matches: Vec<(ScreenedPool, Option<Decimal>)> = pools
+        .into_iter()
+        .filter(|pool| {
+            query.token_a.as_deref().is_none_or(|t| pool.has_token(t))
+                && query.token_b.as_deref().is_none_or(|t| pool.has_token(t))
+                && query
+                    .protocol
+                    .as_deref()
+                    .is_none_or(|p| pool.protocol.eq_ignore_ascii_case(p))
+                && query.max_fee_bps.is_none_or(|max| pool.fee_rate_bps <= max)
+                && query
+                    .min_tvl
+                    .is_none_or(|min| pool.tvl_usd.is_some_and(|tvl| tvl >= min))
+                && query
+                    .min_volume
+                    .is_none_or(|min| pool.volume_24h_usd >= min)
+        })
+        .map(|pool| {
+            let apr = pool.fee_apr();
+            (pool, apr)
+        })
+        .filter(|(_, apr)| {
+            query
+                .min_fee_apr
+                .is_none_or(|min| apr.is_some_and(|apr| apr >= min))
+        })
+        .collect();
+
+    matches.sort_by(|(a, a_apr), (b, b_apr)| {
+        let ordering = match query.sort {
+            PoolSortField::FeeApr => compare_optional(*a_apr, *b_apr, query.order),
+            PoolSortField::Tvl => compare_optional(a.tvl_usd, b.tvl_usd, query.order),
+            PoolSortField::Volume => directed(a.volume_24h_usd.cmp(&b.volume_24h_usd), query.order),
+            PoolSortField::FeeTier => directed(a.fee_rate_bps.cmp(&b.fee_rate_bps), query.order),
+        };
+        ordering.then_with(|| a.address.cmp(&b.address))
+    });
+
+    let total = matches.len();
+    let page = query.page.max(1);
+    let per_page = query.per_page.clamp(1, MAX_PAGE_SIZE);
+    let pools = matches
+        .into_iter()
+        .skip((page - 1).saturating_mul(per_page))
+        .take(per_page)
+        .map(|(pool, apr)| PoolSearchResultResponse {
+            address: pool.address,
+            protocol: pool.protocol,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            symbol_a: pool.symbol_a,
+            symbol_b: pool.symbol_b,
+            fee_rate_bps: pool.fee_rate_bps,
+            tvl_usd: pool.tvl_usd,
+            volume_24h_usd: pool.volume_24h_usd,
+            fee_apr: apr.map(|apr| apr.round_dp(6)),
+        })
+        .collect();
+
+    PoolSearchResponse {
+        pools,
+        total,
+        page,
+        per_page,
+    }
+}
+
+/// Applies the sort order to an ordering.
+fn directed(ordering: Ordering, order: SortOrder) -> Ordering {
+    match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    }
+}
+
+/// Compares optional values, placing missing values last.
+fn compare_optional(a: Option<Decimal>, b: Option<Decimal>, order: SortOrder) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => directed(a.cmp(&b), order),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(
+        address: &str,
+        symbols: (&str, &str),
+        fee: u32,
+        tvl: Option<i64>,
+        volume: i64,
+    ) -> ScreenedPool {
+        ScreenedPool {
+            address: address.to_string(),
+            protocol: "orca".to_string(),
+            token_mint_a: format!("{}-mint", symbols.0),
+            token_mint_b: format!("{}-mint", symbols.1),
+            symbol_a: symbols.0.to_string(),
+            symbol_b: symbols.1.to_string(),
+            fee_rate_bps: fee,
+            tvl_usd: tvl.map(Decimal::from),
+            volume_24h_usd: Decimal::from(volume),
+        }
+    }
+
+    fn query() -> PoolSearchQuery {
+        PoolSearchQuery {
+            page: 1,
+            per_page: 20,
+            ..Default::default()
+        }
+    }
+
+    fn pools() -> Vec<ScreenedPool> {
+        vec![
+            // 1000 * 0.003 * 365 / 100_000 = 0.01095
+            pool("a", ("SOL", "USDC"), 30, Some(100_000), 1_000),
+            // 50_000 * 0.0005 * 365 / 1_000_000 = 0.009125
+            pool("b", ("SOL", "USDT"), 5, Some(1_000_000), 50_000),
+            pool("c", ("BONK", "SOL"), 100, None, 10_000),
+            pool("d", ("JUP", "USDC"), 30, Some(10_000), 500),
+        ]
+    }
+
+    #[test]
+    fn test_fee_apr() {
+        let apr = pools()[0].fee_apr().unwrap();
+        assert_eq!(apr, Decimal::new(1095, 5));
+        assert!(pools()[2].fee_apr().is_none());
+    }
+
+    #[test]
+    fn test_screen_pools_filters_and_sorts() {
+        let mut q = query();
+        q.token_a = Some("sol".to_string());
+        let result = screen_pools(pools(), &q);
+        assert_eq!(result.total, 3);
+        // Highest APR first, pools without TVL last
+        let order: Vec<_> = result.pools.iter().map(|p| p.address.as_str()).collect();
+        assert_eq!(order, vec!["a", "b", "c"]);
+
+        q.token_b = Some("USDC-mint".to_string());
+        assert_eq!(screen_pools(pools(), &q).total, 1);
+
+        let mut q = query();
+        q.min_tvl = Some(Decimal::from(50_000));
+        q.sort = PoolSortField::Tvl;
+        q.order = SortOrder::Asc;
+        let order: Vec<_> = screen_pools(pools(), &q)
+            .pools
+            .into_iter()
+            .map(|p| p.address)
+            .collect();
+        assert_eq!(order, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_screen_pools_paginates() {
+        let mut q = query();
+        q.sort = PoolSortField::Volume;
+        q.per_page = 3;
+        q.page = 2;
+        let result = screen_pools(pools(), &q);
+        assert_eq!(result.total, 4);
+        assert_eq!(result.pools.len(), 1);
+        assert_eq!(result.pools[0].address, "d");
+
+        q.page = 5;
+        assert!(screen_pools(pools(), &q).pools.is_empty());
+    }
+}
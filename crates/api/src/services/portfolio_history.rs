@@ -0,0 +1,343 @@
+//! Historical portfolio PnL from persisted monitor snapshots.
+
+use crate::models::PortfolioHistoryPoint;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use clmm_lp_data::prelude::{PositionRepository, PositionSnapshotRecord};
+use clmm_lp_execution::prelude::{HistoryPoint, HistoryStore};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+/// Longest supported history window, in days.
+pub const MAX_HISTORY_DAYS: i64 = 365;
+
+/// A recorded snapshot of a position's PnL.
+#[derive(Debug, Clone)]
+pub struct PositionHistoryPoint {
+    /// Position address.
+    pub position_address: String,
+    /// When the snapshot was taken.
+    pub timestamp: DateTime<Utc>,
+    /// Whether the position was in range.
+    pub in_range: bool,
+    /// Position value in USD.
+    pub value_usd: Decimal,
+    /// Fees earned in USD.
+    pub fees_usd: Decimal,
+    /// Impermanent loss percentage.
+    pub il_pct: Decimal,
+    /// Net PnL in USD.
+    pub net_pnl_usd: Decimal,
+}
+
+/// A recorded rebalance of a position.
+#[derive(Debug, Clone)]
+pub struct RebalanceHistoryPoint {
+    /// Position address.
+    pub position_address: String,
+    /// When the rebalance happened.
+    pub timestamp: DateTime<Utc>,
+    /// Ticks of the range before the rebalance.
+    pub old_ticks: (i32, i32),
+    /// Ticks of the range after the rebalance.
+    pub new_ticks: (i32, i32),
+    /// Transaction cost in lamports.
+    pub tx_cost_lamports: i64,
+    /// Reason for the rebalance.
+    pub reason: String,
+}
+
+/// A recorded fee collection of a position.
+#[derive(Debug, Clone)]
+pub struct FeeCollectionHistoryPoint {
+    /// Position address.
+    pub position_address: String,
+    /// When the fees were collected.
+    pub timestamp: DateTime<Utc>,
+    /// Fees value in USD.
+    pub fees_usd: Decimal,
+}
+
+/// Source of recorded position snapshots.
+#[async_trait]
+pub trait PositionHistorySource: Send + Sync {
+    /// Loads snapshots since a time in chronological order, optionally for
+    /// a single position.
+    async fn load_history(
+        &self,
+        since: DateTime<Utc>,
+        position: Option<&str>,
+    ) -> anyhow::Result<Vec<PositionHistoryPoint>>;
+
+    /// Loads rebalances since a time in chronological order, optionally for
+    /// a single position.
+    async fn load_rebalances(
+        &self,
+        _since: DateTime<Utc>,
+        _position: Option<&str>,
+    ) -> anyhow::Result<Vec<RebalanceHistoryPoint>> {
+        Ok(Vec::new())
+    }
+
+    /// Loads fee collections since a time in chronological order, optionally
+    /// for a single position.
+    async fn load_fee_collections(
+        &self,
+        _since: DateTime<Utc>,
+        _position: Option<&str>,
+    ) -> anyhow::Result<Vec<FeeCollectionHistoryPoint>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Position history source backed by the position history tables.
+pub struct DbPositionHistory {
+    /// Position repository.
+    repo: PositionRepository,
+}
+
+impl DbPositionHistory {
+    /// Creates a new database position history source.
+    pub fn new(repo: PositionRepository) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl PositionHistorySource for DbPositionHistory {
+    async fn load_history(
+        &self,
+        since: DateTime<Utc>,
+        position: Option<&str>,
+    ) -> anyhow::Result<Vec<PositionHistoryPoint>> {
+        let records = self.repo.find_snapshots(since, position).await?;
+        Ok(records
+            .into_iter()
+            .map(|record| PositionHistoryPoint {
+                position_address: record.position_address,
+                timestamp: record.recorded_at,
+                in_range: record.in_range,
+                value_usd: record.value_usd,
+                fees_usd: record.fees_usd,
+                il_pct: record.il_pct,
+                net_pnl_usd: record.net_pnl_usd,
+            })
+            .collect())
+    }
+
+    async fn load_rebalances(
+        &self,
+        since: DateTime<Utc>,
+        position: Option<&str>,
+    ) -> anyhow::Result<Vec<RebalanceHistoryPoint>> {
+        let records = self.repo.find_rebalances(since, position).await?;
+        Ok(records
+            .into_iter()
+            .map(|record| RebalanceHistoryPoint {
+                position_address: record.position_address,
+                timestamp: record.timestamp,
+                old_ticks: (record.old_tick_lower, record.old_tick_upper),
+                new_ticks: (record.new_tick_lower, record.new_tick_upper),
+                tx_cost_lamports: record.tx_cost_lamports,
+                reason: record.reason,
+            })
+            .collect())
+    }
+
+    async fn load_fee_collections(
+        &self,
+        since: DateTime<Utc>,
+        position: Option<&str>,
+    ) -> anyhow::Result<Vec<FeeCollectionHistoryPoint>> {
+        let records = self.repo.find_fee_collections(since, position).await?;
+        Ok(records
+            .into_iter()
+            .map(|record| FeeCollectionHistoryPoint {
+                position_address: record.position_address,
+                timestamp: record.timestamp,
+                fees_usd: record.fees_usd,
+            })
+            .collect())
+    }
+}
+
+/// History store writing recorded snapshots to the position snapshots table.
+pub struct DbHistoryStore {
+    /// Position repository.
+    repo: PositionRepository,
+}
+
+impl DbHistoryStore {
+    /// Creates a new database history store.
+    pub fn new(repo: PositionRepository) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl HistoryStore for DbHistoryStore {
+    async fn record(&self, points: &[HistoryPoint]) -> anyhow::Result<()> {
+        let records: Vec<PositionSnapshotRecord> = points
+            .iter()
+            .map(|point| PositionSnapshotRecord {
+                position_address: point.address.to_string(),
+                pool_address: point.pool.to_string(),
+                recorded_at: point.recorded_at,
+                tick_lower: Some(point.tick_lower),
+                tick_upper: Some(point.tick_upper),
+                in_range: point.in_range,
+                entry_value_usd: Some(point.pnl.entry_value_usd),
+                value_usd: point.pnl.current_value_usd,
+                fees_usd: point.pnl.fees_usd,
+                il_pct: point.pnl.il_pct,
+                net_pnl_usd: point.pnl.net_pnl_usd,
+            })
+            .collect();
+        self.repo.insert_snapshots(&records).await?;
+        Ok(())
+    }
+}
+
+/// Parses a window such as `24h`, `7d` or `4w`.
+///
+/// Returns `None` for unknown units, zero, or windows over
+/// [`MAX_HISTORY_DAYS`].
+#[must_use]
+pub fn parse_window(window: &str) -> Option<Duration> {
+    let window = window.trim();
+    let unit = window.chars().last()?;
+    let amount: i64 = window[..window.len() - unit.len_utf8()].parse().ok()?;
+    let duration = match unit {
+        'h' => Duration::try_hours(amount)?,
+        'd' => Duration::try_days(amount)?,
+        'w' => Duration::try_weeks(amount)?,
+        _ => return None,
+    };
+    (amount > 0 && duration <= Duration::days(MAX_HISTORY_DAYS)).then_some(duration)
+}
+
+/// Chooses the interval between points for a window, in seconds.
+///
+/// Keeps charts to a few hundred points at most.
+#[must_use]
+pub fn history_interval(window: Duration) -> i64 {
+    if window <= Duration::days(1) {
+        300
+    } else if window <= Duration::days(7) {
+        3_600
+    } else if window <= Duration::days(30) {
+        14_400
+    } else {
+        86_400
+    }
+}
+
+/// Aggregates position snapshots into a portfolio time series.
+///
+/// Each point sums the latest snapshot of every position recorded within
+/// the interval, so intervals in which the monitor was not running are
+/// absent rather than interpolated. IL is weighted by position value.
+#[must_use]
+pub fn aggregate_history(
+    points: &[PositionHistoryPoint],
+    interval_secs: i64,
+) -> Vec<PortfolioHistoryPoint> {
+    let mut buckets: BTreeMap<i64, HashMap<&str, &PositionHistoryPoint>> = BTreeMap::new();
+    for point in points {
+        let secs = point.timestamp.timestamp();
+        let latest = buckets
+            .entry(secs - secs.rem_euclid(interval_secs))
+            .or_default()
+            .entry(point.position_address.as_str())
+            .or_insert(point);
+        if point.timestamp >= latest.timestamp {
+            *latest = point;
+        }
+    }
+
+    buckets
+        .into_iter()
+        .map(|(bucket, positions)| {
+            let mut total_value = Decimal::ZERO;
+            let mut net_pnl = Decimal::ZERO;
+            let mut fees = Decimal::ZERO;
+            let mut weighted_il = Decimal::ZERO;
+            let mut in_range = 0u32;
+            for point in positions.values() {
+                total_value += point.value_usd;
+                net_pnl += point.net_pnl_usd;
+                fees += point.fees_usd;
+                weighted_il += point.il_pct * point.value_usd;
+                if point.in_range {
+                    in_range += 1;
+                }
+            }
+            let il_pct = if total_value > Decimal::ZERO {
+                weighted_il / total_value
+            } else {
+                Decimal::ZERO
+            };
+
+            PortfolioHistoryPoint {
+                timestamp: Utc.timestamp_opt(bucket, 0).single().unwrap_or_default(),
+                total_value_usd: total_value,
+                net_pnl_usd: net_pnl,
+                fees_usd: fees,
+                il_pct: il_pct.round_dp(6),
+                positions: positions.len() as u32,
+                positions_in_range: in_range,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(address: &str, minute: u32, value: i64, fees: i64, il: i64) -> PositionHistoryPoint {
+        PositionHistoryPoint {
+            position_address: address.to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 5, 1, 12, minute, 0).unwrap(),
+            in_range: true,
+            value_usd: Decimal::from(value),
+            fees_usd: Decimal::from(fees),
+            il_pct: Decimal::from(il),
+            net_pnl_usd: Decimal::from(fees - il),
+        }
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window("24h"), Some(Duration::hours(24)));
+        assert_eq!(parse_window("30d"), Some(Duration::days(30)));
+        assert_eq!(parse_window("2w"), Some(Duration::weeks(2)));
+        assert_eq!(parse_window("0d"), None);
+        assert_eq!(parse_window("366d"), None);
+        assert_eq!(parse_window("30m"), None);
+        assert_eq!(parse_window("d"), None);
+    }
+
+    #[test]
+    fn test_aggregate_history() {
+        let points = vec![
+            point("a", 0, 1_000, 1, 2),
+            point("b", 2, 3_000, 5, 4),
+            // Later snapshot of "a" in the same interval replaces the first
+            point("a", 4, 1_000, 2, 2),
+            point("a", 6, 1_100, 3, 1),
+        ];
+        let history = aggregate_history(&points, 300);
+        assert_eq!(history.len(), 2);
+
+        assert_eq!(history[0].positions, 2);
+        assert_eq!(history[0].total_value_usd, Decimal::from(4_000));
+        assert_eq!(history[0].fees_usd, Decimal::from(7));
+        // (2 * 1000 + 4 * 3000) / 4000
+        assert_eq!(history[0].il_pct, Decimal::new(35, 1));
+
+        assert_eq!(history[1].positions, 1);
+        assert_eq!(history[1].total_value_usd, Decimal::from(1_100));
+        assert!(history[0].timestamp < history[1].timestamp);
+    }
+}
@@ -2,15 +2,25 @@
 
 use crate::error::ApiError;
 use crate::models::{OpenPositionRequest, RebalanceRequest};
+use crate::services::RegisteredWallet;
 use crate::state::{AlertUpdate, AppState, PositionUpdate};
-use clmm_lp_execution::prelude::{RebalanceParams, RebalanceReason, StrategyExecutor};
-use clmm_lp_protocols::prelude::WhirlpoolReader;
+use clmm_lp_execution::prelude::{
+    CloseReason, CloseRequest, DecreaseRequest, ExecutionError, IncreaseRequest, JupiterSwapper,
+    LiquidityAdjuster, LiquidityChange, OpenRequest, PositionCloser, PositionCollector,
+    PositionOpener, RebalanceConfig, RebalanceExecutor, RebalanceParams, RebalanceReason,
+};
+use clmm_lp_protocols::prelude::{ProtocolRegistry, find_position_mints};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+/// Slippage tolerance of manual closes in basis points.
+const CLOSE_SLIPPAGE_BPS: u16 = 50;
+
+/// Slippage tolerance of manual liquidity changes in basis points.
+const LIQUIDITY_SLIPPAGE_BPS: u16 = 50;
+
 /// Result of a position operation.
 #[derive(Debug, Clone)]
 pub struct OperationResult {
@@ -55,6 +65,18 @@ impl OperationResult {
         }
     }
 
+    /// Returns the result if the operation succeeded, and its error as a
+    /// conflict otherwise, so a failure is never reported as a success.
+    pub fn confirmed(self) -> Result<Self, ApiError> {
+        if self.success {
+            Ok(self)
+        } else {
+            Err(ApiError::Conflict(
+                self.error.unwrap_or_else(|| "Operation failed".to_string()),
+            ))
+        }
+    }
+
     /// Creates a dry-run result.
     pub fn dry_run(message: impl Into<String>) -> Self {
         Self {
@@ -73,10 +95,10 @@ impl OperationResult {
 pub struct PositionService {
     /// Application state.
     state: AppState,
-    /// Strategy executor for rebalancing.
-    executor: Option<Arc<RwLock<StrategyExecutor>>>,
-    /// Pool reader.
-    pool_reader: WhirlpoolReader,
+    /// Protocol adapters for reading pools.
+    protocols: Arc<ProtocolRegistry>,
+    /// Tenant of the caller; `None` for operators.
+    tenant: Option<String>,
     /// Whether in dry-run mode.
     dry_run: bool,
 }
@@ -84,18 +106,18 @@ pub struct PositionService {
 impl PositionService {
     /// Creates a new position service.
     pub fn new(state: AppState) -> Self {
-        let pool_reader = WhirlpoolReader::new(state.provider.clone());
+        let protocols = state.protocols.clone();
         Self {
             state,
-            executor: None,
-            pool_reader,
+            protocols,
+            tenant: None,
             dry_run: true, // Default to dry-run for safety
         }
     }
 
-    /// Sets the strategy executor.
-    pub fn set_executor(&mut self, executor: Arc<RwLock<StrategyExecutor>>) {
-        self.executor = Some(executor);
+    /// Sets the tenant of the caller, limiting the wallets it may sign with.
+    pub fn set_tenant(&mut self, tenant: Option<String>) {
+        self.tenant = tenant;
     }
 
     /// Enables or disables dry-run mode.
@@ -108,7 +130,7 @@ impl PositionService {
         &self,
         request: &OpenPositionRequest,
     ) -> Result<OperationResult, ApiError> {
-        let _pool_pubkey = Pubkey::from_str(&request.pool_address)
+        let pool_pubkey = Pubkey::from_str(&request.pool_address)
             .map_err(|_| ApiError::bad_request("Invalid pool address"))?;
 
         info!(
@@ -127,8 +149,8 @@ impl PositionService {
 
         // Fetch pool state to validate
         let pool_state = self
-            .pool_reader
-            .get_pool_state(&request.pool_address)
+            .protocols
+            .fetch_pool_state(&pool_pubkey)
             .await
             .map_err(|e| ApiError::not_found(format!("Pool not found: {}", e)))?;
 
@@ -149,17 +171,81 @@ impl PositionService {
             )));
         }
 
-        // TODO: Implement actual position opening via WhirlpoolExecutor
-        // This would require:
-        // 1. Create position NFT
-        // 2. Increase liquidity
-        // 3. Track in lifecycle
-
-        // For now, return a placeholder
-        warn!("Position opening not yet fully implemented");
-        Ok(OperationResult::failure(
-            "Position opening requires wallet configuration",
-        ))
+        if !self.state.circuit_breaker.is_allowed().await {
+            return Err(ExecutionError::CircuitBreakerOpen.into());
+        }
+
+        let wallet = self
+            .state
+            .wallets
+            .signing_wallet(request.wallet.as_deref(), self.tenant.as_deref())
+            .await
+            .ok_or_else(|| match &request.wallet {
+                Some(label) => ApiError::not_found(format!("Wallet {} not found", label)),
+                None => ApiError::Validation(
+                    "Specify the signing wallet; none or several are registered".to_string(),
+                ),
+            })?;
+
+        let mut opener = PositionOpener::new(
+            self.state.provider.clone(),
+            self.protocols.clone(),
+            self.state.tx_manager.clone(),
+        )
+        .with_lifecycle(self.state.lifecycle.clone());
+        if let Some(guard) = &self.state.price_guard {
+            opener = opener.with_price_guard(guard.clone());
+        }
+
+        let opened = opener
+            .open(
+                &OpenRequest {
+                    pool: pool_pubkey,
+                    tick_lower: request.tick_lower,
+                    tick_upper: request.tick_upper,
+                    amount_a: request.amount_a,
+                    amount_b: request.amount_b,
+                    slippage_bps: request.slippage_tolerance_bps,
+                },
+                &wallet.wallet,
+            )
+            .await?;
+
+        let data = serde_json::json!({
+            "position": opened.position.to_string(),
+            "position_mint": opened.position_mint.to_string(),
+            "wallet": wallet.label,
+            "range": [opened.tick_lower, opened.tick_upper],
+            "liquidity": opened.liquidity.to_string(),
+            "token_max_a": opened.token_max.0,
+            "token_max_b": opened.token_max.1,
+            "priority_fee_micro_lamports": opened.priority_fee,
+            "compute_units": opened.compute_units,
+            "slot": opened.slot,
+        });
+
+        // Track the new position; it is already open on chain if this fails
+        if let Err(e) = self
+            .state
+            .monitor
+            .add_position(&opened.position.to_string())
+            .await
+        {
+            warn!(position = %opened.position, error = %e, "Failed to monitor opened position");
+        }
+
+        self.state.broadcast_position_update(PositionUpdate {
+            update_type: "opened".to_string(),
+            position_address: opened.position.to_string(),
+            pool_address: Some(request.pool_address.clone()),
+            timestamp: chrono::Utc::now(),
+            data: data.clone(),
+        });
+
+        Ok(OperationResult {
+            data: Some(data),
+            ..OperationResult::success_with_signature(opened.signature.to_string())
+        })
     }
 
     /// Closes a position.
@@ -184,17 +270,74 @@ impl PositionService {
             )));
         }
 
-        // TODO: Implement actual position closing
-        // This would require:
-        // 1. Collect fees
-        // 2. Decrease liquidity to 0
-        // 3. Close position NFT
-        // 4. Record in lifecycle
-
-        warn!("Position closing not yet fully implemented");
-        Ok(OperationResult::failure(
-            "Position closing requires wallet configuration",
-        ))
+        // Closes reduce risk, so they go ahead while the circuit breaker is open
+
+        // Sign with the caller's wallet holding the position NFT
+        let wallet = self.owning_wallet(&position_pubkey).await?;
+
+        let closer = PositionCloser::new(
+            self.state.provider.clone(),
+            self.protocols.clone(),
+            self.state.tx_manager.clone(),
+        )
+        .with_lifecycle(self.state.lifecycle.clone());
+        let closed = closer
+            .close(
+                &CloseRequest {
+                    position: position_pubkey,
+                    slippage_bps: CLOSE_SLIPPAGE_BPS,
+                    reason: CloseReason::Manual,
+                    pnl: Some(position.pnl.clone()),
+                },
+                &wallet.wallet,
+            )
+            .await?;
+
+        self.state.monitor.remove_position(&position_pubkey).await;
+
+        let data = serde_json::json!({
+            "wallet": wallet.label,
+            "liquidity_removed": closed.liquidity_removed.to_string(),
+            "amount_a": closed.amounts.0,
+            "amount_b": closed.amounts.1,
+            "fees_a": closed.fees_collected.0,
+            "fees_b": closed.fees_collected.1,
+            "realized_pnl_usd": closed.realized_pnl_usd,
+            "realized_pnl_pct": closed.realized_pnl_pct,
+            "priority_fee_micro_lamports": closed.priority_fee,
+            "compute_units": closed.compute_units,
+            "slot": closed.slot,
+        });
+
+        self.state.broadcast_position_update(PositionUpdate {
+            update_type: "closed".to_string(),
+            position_address: address.to_string(),
+            pool_address: Some(closed.pool.to_string()),
+            timestamp: chrono::Utc::now(),
+            data: data.clone(),
+        });
+
+        Ok(OperationResult {
+            data: Some(data),
+            ..OperationResult::success_with_signature(closed.signature.to_string())
+        })
+    }
+
+    /// Finds the caller's registered wallet holding a position's NFT.
+    async fn owning_wallet(&self, position: &Pubkey) -> Result<Arc<RegisteredWallet>, ApiError> {
+        let adapter = self.protocols.adapter_for_account(position).await?;
+        let position_mint = adapter.fetch_position(position).await?.position_mint;
+
+        for wallet in self.state.wallets.usable(self.tenant.as_deref()).await {
+            let mints = find_position_mints(&self.state.provider, &wallet.wallet.pubkey()).await?;
+            if mints.contains(&position_mint) {
+                return Ok(wallet);
+            }
+        }
+        Err(ApiError::not_found(format!(
+            "No registered wallet holds position {}",
+            position
+        )))
     }
 
     /// Collects fees from a position.
@@ -219,16 +362,56 @@ impl PositionService {
             )));
         }
 
-        // TODO: Implement actual fee collection
-        // This would use the Whirlpool collect_fees instruction
-
-        warn!("Fee collection not yet fully implemented");
-        Ok(OperationResult::failure(
-            "Fee collection requires wallet configuration",
-        ))
+        // Collecting takes no liquidity, so it goes ahead while the circuit
+        // breaker is open
+        let wallet = self.owning_wallet(&position_pubkey).await?;
+
+        let collected = PositionCollector::new(
+            self.state.provider.clone(),
+            self.protocols.clone(),
+            self.state.tx_manager.clone(),
+        )
+        .with_lifecycle(self.state.lifecycle.clone())
+        .collect(&position_pubkey, &wallet.wallet)
+        .await?;
+
+        let data = serde_json::json!({
+            "wallet": wallet.label,
+            "fees_a": collected.fees.0,
+            "fees_b": collected.fees.1,
+            "rewards": collected
+                .rewards
+                .iter()
+                .map(|reward| serde_json::json!({
+                    "mint": reward.mint.to_string(),
+                    "amount": reward.amount_owed,
+                }))
+                .collect::<Vec<_>>(),
+            "priority_fee_micro_lamports": collected.priority_fee,
+            "compute_units": collected.compute_units,
+            "slot": collected.slot,
+        });
+
+        self.state.broadcast_position_update(PositionUpdate {
+            update_type: "fees_collected".to_string(),
+            position_address: address.to_string(),
+            pool_address: Some(collected.pool.to_string()),
+            timestamp: chrono::Utc::now(),
+            data: data.clone(),
+        });
+
+        Ok(OperationResult {
+            data: Some(data),
+            ..OperationResult::success_with_signature(collected.signature.to_string())
+        })
     }
 
     /// Rebalances a position.
+    ///
+    /// Collects its fees, closes it, swaps the withdrawn tokens to the new
+    /// range's ratio and opens the new range, signed by the wallet holding
+    /// the position. Fails as a conflict if nothing was changed, and as an
+    /// internal error if the position was closed but the new one not opened.
     pub async fn rebalance_position(
         &self,
         address: &str,
@@ -260,8 +443,8 @@ impl PositionService {
 
         // Fetch pool state
         let pool_state = self
-            .pool_reader
-            .get_pool_state(&position.pool.to_string())
+            .protocols
+            .fetch_pool_state(&position.pool)
             .await
             .map_err(|e| ApiError::Internal(format!("Failed to fetch pool state: {}", e)))?;
 
@@ -282,6 +465,7 @@ impl PositionService {
             self.state.broadcast_position_update(PositionUpdate {
                 update_type: "rebalance_simulated".to_string(),
                 position_address: address.to_string(),
+                pool_address: Some(position.pool.to_string()),
                 timestamp: chrono::Utc::now(),
                 data: serde_json::json!({
                     "old_range": [position.on_chain.tick_lower, position.on_chain.tick_upper],
@@ -300,9 +484,27 @@ impl PositionService {
             )));
         }
 
-        // Execute rebalance if executor is available
-        if let Some(executor) = &self.executor {
-            let _params = RebalanceParams {
+        if !self.state.circuit_breaker.is_allowed().await {
+            return Err(ExecutionError::CircuitBreakerOpen.into());
+        }
+
+        // Sign with the caller's wallet holding the position NFT
+        let wallet = self.owning_wallet(&position_pubkey).await?;
+
+        let mut executor = RebalanceExecutor::new(
+            self.state.provider.clone(),
+            self.state.tx_manager.clone(),
+            self.state.lifecycle.clone(),
+            RebalanceConfig::default(),
+        );
+        executor.set_wallet(wallet.wallet.clone());
+        executor.set_swapper(Arc::new(JupiterSwapper::new()));
+        if let Some(guard) = &self.state.price_guard {
+            executor.set_price_guard(guard.clone());
+        }
+
+        let result = executor
+            .execute(RebalanceParams {
                 position: position_pubkey,
                 pool: position.pool,
                 current_tick_lower: position.on_chain.tick_lower,
@@ -312,61 +514,82 @@ impl PositionService {
                 current_liquidity: position.on_chain.liquidity,
                 reason: RebalanceReason::Manual,
                 current_il_pct: position.pnl.il_pct,
-            };
-
-            let _executor_guard = executor.read().await;
-            // Note: RebalanceExecutor is inside StrategyExecutor, we need to access it
-            // For now, we'll use the lifecycle tracker to record the intent
-
-            drop(_executor_guard);
-
-            // Record the rebalance request
-            self.state
-                .lifecycle
-                .record_rebalance(
-                    position_pubkey,
-                    position.pool,
-                    clmm_lp_execution::prelude::RebalanceData {
-                        old_tick_lower: position.on_chain.tick_lower,
-                        old_tick_upper: position.on_chain.tick_upper,
-                        new_tick_lower: request.new_tick_lower,
-                        new_tick_upper: request.new_tick_upper,
-                        old_liquidity: position.on_chain.liquidity,
-                        new_liquidity: position.on_chain.liquidity, // Assuming same liquidity
-                        tx_cost_lamports: 0,
-                        il_at_rebalance: position.pnl.il_pct,
-                        reason: RebalanceReason::Manual,
-                    },
-                )
-                .await;
+            })
+            .await;
 
-            // Broadcast update
-            self.state.broadcast_position_update(PositionUpdate {
-                update_type: "rebalance_initiated".to_string(),
-                position_address: address.to_string(),
-                timestamp: chrono::Utc::now(),
-                data: serde_json::json!({
-                    "old_range": [position.on_chain.tick_lower, position.on_chain.tick_upper],
-                    "new_range": [request.new_tick_lower, request.new_tick_upper]
-                }),
-            });
+        // The old position is gone once closed, even if reopening failed
+        if result.old_position_closed {
+            self.state.monitor.remove_position(&position_pubkey).await;
+        }
 
-            // Broadcast alert
+        let Some(new_position) = result.new_position.filter(|_| result.success) else {
+            let error = result
+                .error
+                .unwrap_or_else(|| "Rebalance failed".to_string());
+            warn!(position = %address, error = %error, "Rebalance failed");
+            if !result.old_position_closed {
+                return Err(ApiError::Conflict(format!("Rebalance failed: {}", error)));
+            }
+
+            // The liquidity is back in the wallet, outside any position
+            let message = format!(
+                "Rebalance of position {} failed after closing it: {}",
+                address, error
+            );
             self.state.broadcast_alert(AlertUpdate {
-                level: "info".to_string(),
-                message: format!("Rebalance initiated for position {}", address),
+                level: "error".to_string(),
+                message: message.clone(),
                 timestamp: chrono::Utc::now(),
                 position_address: Some(address.to_string()),
             });
-
-            info!("Rebalance recorded - actual execution pending wallet configuration");
-            return Ok(OperationResult::success());
+            return Err(ApiError::Internal(message));
+        };
+
+        // Track the new position; it is already open on chain if this fails
+        if let Err(e) = self
+            .state
+            .monitor
+            .add_position(&new_position.to_string())
+            .await
+        {
+            warn!(position = %new_position, error = %e, "Failed to monitor rebalanced position");
         }
 
-        warn!("Rebalancing not yet fully implemented");
-        Ok(OperationResult::failure(
-            "Rebalancing requires executor configuration",
-        ))
+        let data = serde_json::json!({
+            "wallet": wallet.label,
+            "new_position": new_position.to_string(),
+            "old_range": [position.on_chain.tick_lower, position.on_chain.tick_upper],
+            "new_range": [request.new_tick_lower, request.new_tick_upper],
+            "liquidity_removed": result.liquidity_removed.to_string(),
+            "liquidity_added": result.liquidity_added.to_string(),
+            "fees_a": result.fees_collected.unwrap_or_default().0,
+            "fees_b": result.fees_collected.unwrap_or_default().1,
+            "swap_signature": result.swap.as_ref().map(|swap| swap.signature.to_string()),
+            "tx_cost_lamports": result.tx_cost_lamports,
+        });
+
+        self.state.broadcast_position_update(PositionUpdate {
+            update_type: "rebalanced".to_string(),
+            position_address: address.to_string(),
+            pool_address: Some(position.pool.to_string()),
+            timestamp: chrono::Utc::now(),
+            data: data.clone(),
+        });
+
+        self.state.broadcast_alert(AlertUpdate {
+            level: "info".to_string(),
+            message: format!(
+                "Rebalanced position {} into {} with range [{}, {}]",
+                address, new_position, request.new_tick_lower, request.new_tick_upper
+            ),
+            timestamp: chrono::Utc::now(),
+            position_address: Some(address.to_string()),
+        });
+
+        Ok(OperationResult {
+            data: Some(data),
+            ..OperationResult::success()
+        })
     }
 
     /// Increases liquidity in a position.
@@ -388,10 +611,9 @@ impl PositionService {
 
         // Verify position exists
         let positions = self.state.monitor.get_positions().await;
-        let _position = positions
-            .iter()
-            .find(|p| p.address == position_pubkey)
-            .ok_or_else(|| ApiError::not_found("Position not found"))?;
+        if !positions.iter().any(|p| p.address == position_pubkey) {
+            return Err(ApiError::not_found("Position not found"));
+        }
 
         if self.dry_run {
             return Ok(OperationResult::dry_run(format!(
@@ -400,10 +622,28 @@ impl PositionService {
             )));
         }
 
-        // TODO: Implement actual liquidity increase
-        Ok(OperationResult::failure(
-            "Liquidity increase requires wallet configuration",
-        ))
+        if !self.state.circuit_breaker.is_allowed().await {
+            return Err(ExecutionError::CircuitBreakerOpen.into());
+        }
+
+        let wallet = self.owning_wallet(&position_pubkey).await?;
+        let mut adjuster = self.liquidity_adjuster();
+        if let Some(guard) = &self.state.price_guard {
+            adjuster = adjuster.with_price_guard(guard.clone());
+        }
+        let change = adjuster
+            .increase(
+                &IncreaseRequest {
+                    position: position_pubkey,
+                    amount_a,
+                    amount_b,
+                    slippage_bps: LIQUIDITY_SLIPPAGE_BPS,
+                },
+                &wallet.wallet,
+            )
+            .await?;
+
+        Ok(self.liquidity_changed(address, &wallet, &change))
     }
 
     /// Decreases liquidity from a position.
@@ -441,9 +681,71 @@ impl PositionService {
             )));
         }
 
-        // TODO: Implement actual liquidity decrease
-        Ok(OperationResult::failure(
-            "Liquidity decrease requires wallet configuration",
-        ))
+        // Withdrawals reduce risk, so they go ahead while the circuit breaker
+        // is open
+        let wallet = self.owning_wallet(&position_pubkey).await?;
+        let change = self
+            .liquidity_adjuster()
+            .decrease(
+                &DecreaseRequest {
+                    position: position_pubkey,
+                    liquidity: liquidity_amount,
+                    slippage_bps: LIQUIDITY_SLIPPAGE_BPS,
+                },
+                &wallet.wallet,
+            )
+            .await?;
+
+        Ok(self.liquidity_changed(address, &wallet, &change))
+    }
+
+    /// Creates an adjuster of position liquidity recording in the shared
+    /// lifecycle.
+    fn liquidity_adjuster(&self) -> LiquidityAdjuster {
+        LiquidityAdjuster::new(
+            self.state.provider.clone(),
+            self.protocols.clone(),
+            self.state.tx_manager.clone(),
+        )
+        .with_lifecycle(self.state.lifecycle.clone())
+    }
+
+    /// Broadcasts a confirmed liquidity change and describes it.
+    fn liquidity_changed(
+        &self,
+        address: &str,
+        wallet: &RegisteredWallet,
+        change: &LiquidityChange,
+    ) -> OperationResult {
+        let data = serde_json::json!({
+            "wallet": wallet.label,
+            "liquidity_delta": change.liquidity_delta.to_string(),
+            "new_liquidity": change.new_liquidity.to_string(),
+            "amount_a": change.amounts.0,
+            "amount_b": change.amounts.1,
+            "limit_a": change.limits.0,
+            "limit_b": change.limits.1,
+            "priority_fee_micro_lamports": change.priority_fee,
+            "compute_units": change.compute_units,
+            "slot": change.slot,
+        });
+
+        self.state.broadcast_position_update(PositionUpdate {
+            update_type: if change.is_increase {
+                "liquidity_increased"
+            } else {
+                "liquidity_decreased"
+            }
+            .to_string(),
+            position_address: address.to_string(),
+            pool_address: Some(change.pool.to_string()),
+            timestamp: chrono::Utc::now(),
+            data: data.clone(),
+        });
+
+        OperationResult {
+            data: Some(data),
+            ..OperationResult::success_with_signature(change.signature.to_string())
+        }
     }
 }
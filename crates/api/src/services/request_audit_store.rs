@@ -0,0 +1,71 @@
+//! Database-backed request audit log.
+
+use crate::request_audit::{RequestAuditEntry, RequestAuditLog, RequestAuditQuery};
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{RequestAuditFilter, RequestAuditRecord, RequestAuditRepository};
+use uuid::Uuid;
+
+/// Request audit log that appends entries to PostgreSQL.
+#[derive(Clone)]
+pub struct DbRequestAuditLog {
+    /// Request audit repository.
+    repo: RequestAuditRepository,
+}
+
+impl DbRequestAuditLog {
+    /// Creates a new database request audit log.
+    pub fn new(repo: RequestAuditRepository) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl RequestAuditLog for DbRequestAuditLog {
+    async fn append(&self, entry: &RequestAuditEntry) -> anyhow::Result<()> {
+        let record = RequestAuditRecord {
+            id: Uuid::parse_str(&entry.id)?,
+            timestamp: entry.timestamp,
+            subject: entry.subject.clone(),
+            role: entry.role.clone(),
+            tenant: entry.tenant.clone(),
+            method: entry.method.clone(),
+            path: entry.path.clone(),
+            payload_hash: entry.payload_hash.clone(),
+            status: i32::from(entry.status),
+            duration_ms: i64::try_from(entry.duration_ms).unwrap_or(i64::MAX),
+        };
+        self.repo.append(&record).await?;
+        Ok(())
+    }
+
+    async fn query(&self, query: &RequestAuditQuery) -> anyhow::Result<Vec<RequestAuditEntry>> {
+        let filter = RequestAuditFilter {
+            subject: query.subject.clone(),
+            tenant: query.tenant.clone(),
+            method: query.method.clone(),
+            path_prefix: query.path_prefix.clone(),
+            status: query.status.map(i32::from),
+            since: query.since,
+            until: query.until,
+        };
+
+        Ok(self
+            .repo
+            .query(&filter, query.limit as i64)
+            .await?
+            .into_iter()
+            .map(|record| RequestAuditEntry {
+                id: record.id.to_string(),
+                timestamp: record.timestamp,
+                subject: record.subject,
+                role: record.role,
+                tenant: record.tenant,
+                method: record.method,
+                path: record.path,
+                payload_hash: record.payload_hash,
+                status: u16::try_from(record.status).unwrap_or_default(),
+                duration_ms: u64::try_from(record.duration_ms).unwrap_or_default(),
+            })
+            .collect())
+    }
+}
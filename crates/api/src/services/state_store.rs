@@ -0,0 +1,129 @@
+//! Database-backed state store for monitor and lifecycle persistence.
+
+use anyhow::Context;
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{
+    FeeCollectionRecord, LifecycleEventRecord, PositionRepository, PositionStateRepository,
+    RebalanceRecord,
+};
+use clmm_lp_execution::prelude::{EventData, LifecycleEvent, PositionSnapshot, StateStore};
+use solana_sdk::pubkey::Pubkey;
+use uuid::Uuid;
+
+/// State store that persists snapshots and events to PostgreSQL.
+///
+/// Rebalances and fee collections are also recorded as rows of their own
+/// for the analytics endpoints.
+#[derive(Clone)]
+pub struct DbStateStore {
+    /// Position state repository.
+    repo: PositionStateRepository,
+    /// Position history repository.
+    positions: PositionRepository,
+}
+
+impl DbStateStore {
+    /// Creates a new database state store.
+    pub fn new(repo: PositionStateRepository, positions: PositionRepository) -> Self {
+        Self { repo, positions }
+    }
+
+    /// Records a rebalance or fee collection event as a row of its own.
+    async fn record_activity(&self, event: &LifecycleEvent, id: Uuid) -> anyhow::Result<()> {
+        let tx_signature = event.signature.map(|signature| signature.to_string());
+        match &event.data {
+            EventData::Rebalance(data) => {
+                let record = RebalanceRecord {
+                    id,
+                    position_address: event.position.to_string(),
+                    pool_address: event.pool.to_string(),
+                    old_tick_lower: data.old_tick_lower,
+                    old_tick_upper: data.old_tick_upper,
+                    new_tick_lower: data.new_tick_lower,
+                    new_tick_upper: data.new_tick_upper,
+                    tx_cost_lamports: i64::try_from(data.tx_cost_lamports)?,
+                    il_pct: data.il_at_rebalance,
+                    reason: format!("{:?}", data.reason),
+                    tx_signature,
+                    timestamp: event.timestamp,
+                };
+                self.positions.insert_rebalance(&record).await?;
+            }
+            EventData::FeesCollected(data) => {
+                let record = FeeCollectionRecord {
+                    id,
+                    position_address: event.position.to_string(),
+                    pool_address: event.pool.to_string(),
+                    fees_a: i64::try_from(data.fees_a)?,
+                    fees_b: i64::try_from(data.fees_b)?,
+                    fees_usd: data.fees_usd,
+                    tx_signature,
+                    timestamp: event.timestamp,
+                };
+                self.positions.insert_fee_collection(&record).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StateStore for DbStateStore {
+    async fn save_position(&self, snapshot: &PositionSnapshot) -> anyhow::Result<()> {
+        let state = serde_json::to_value(snapshot)?;
+        self.repo
+            .upsert(
+                &snapshot.address.to_string(),
+                &snapshot.pool.to_string(),
+                snapshot.in_range,
+                &state,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_position(&self, position: &Pubkey) -> anyhow::Result<()> {
+        self.repo.delete(&position.to_string()).await?;
+        Ok(())
+    }
+
+    async fn load_positions(&self) -> anyhow::Result<Vec<PositionSnapshot>> {
+        self.repo
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|record| {
+                serde_json::from_value(record.state).with_context(|| {
+                    format!("invalid stored state for {}", record.position_address)
+                })
+            })
+            .collect()
+    }
+
+    async fn append_event(&self, event: &LifecycleEvent) -> anyhow::Result<()> {
+        let id = Uuid::parse_str(&event.id)?;
+        let record = LifecycleEventRecord {
+            id,
+            position_address: event.position.to_string(),
+            pool_address: event.pool.to_string(),
+            event_type: format!("{:?}", event.event_type),
+            event: serde_json::to_value(event)?,
+            timestamp: event.timestamp,
+        };
+        self.repo.insert_event(&record).await?;
+        self.record_activity(event, id).await
+    }
+
+    async fn load_events(&self) -> anyhow::Result<Vec<LifecycleEvent>> {
+        self.repo
+            .find_all_events()
+            .await?
+            .into_iter()
+            .map(|record| {
+                serde_json::from_value(record.event)
+                    .with_context(|| format!("invalid stored lifecycle event {}", record.id))
+            })
+            .collect()
+    }
+}
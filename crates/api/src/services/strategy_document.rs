@@ -0,0 +1,177 @@
+//! Versioned YAML format for sharing strategy definitions.
+//!
+//! A document holds everything needed to recreate a strategy elsewhere:
+//! type, pool, parameters (including schedules and risk limits) and
+//! execution mode. Runtime state such as IDs, owners and timestamps is left
+//! out, so exports diff cleanly in git.
+
+use crate::models::CreateStrategyRequest;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Current version of the strategy document format.
+pub const STRATEGY_FORMAT_VERSION: u32 = 1;
+
+/// Errors reading a strategy document.
+#[derive(Debug, Error)]
+pub enum StrategyDocumentError {
+    /// The document is not valid YAML or does not describe a strategy.
+    #[error("Invalid strategy document: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    /// The document has no `version` field.
+    #[error("Strategy document is missing its format version")]
+    MissingVersion,
+    /// The document was written by a newer release.
+    #[error("Unsupported strategy format version {0} (latest is {STRATEGY_FORMAT_VERSION})")]
+    UnsupportedVersion(u64),
+}
+
+/// A strategy definition in the shareable YAML format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyDocument {
+    /// Format version.
+    pub version: u32,
+    /// Strategy definition.
+    #[serde(flatten)]
+    pub strategy: CreateStrategyRequest,
+}
+
+impl StrategyDocument {
+    /// Wraps a strategy definition in a document of the current version.
+    #[must_use]
+    pub fn new(strategy: CreateStrategyRequest) -> Self {
+        Self {
+            version: STRATEGY_FORMAT_VERSION,
+            strategy,
+        }
+    }
+
+    /// Serializes the document to YAML.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn to_yaml(&self) -> Result<String, StrategyDocumentError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Parses a document, checking its format version first.
+    ///
+    /// # Errors
+    /// Returns an error if the YAML is invalid, the version is missing or
+    /// newer than [`STRATEGY_FORMAT_VERSION`], or fields are missing.
+    pub fn from_yaml(text: &str) -> Result<Self, StrategyDocumentError> {
+        let value: serde_yaml::Value = serde_yaml::from_str(text)?;
+        let version = value
+            .get("version")
+            .and_then(serde_yaml::Value::as_u64)
+            .ok_or(StrategyDocumentError::MissingVersion)?;
+        if version == 0 || version > u64::from(STRATEGY_FORMAT_VERSION) {
+            return Err(StrategyDocumentError::UnsupportedVersion(version));
+        }
+        Ok(serde_yaml::from_value(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{StopLossSettings, StrategyParameters, StrategyType};
+    use rust_decimal::Decimal;
+
+    fn strategy() -> CreateStrategyRequest {
+        CreateStrategyRequest {
+            name: "SOL/USDC threshold".to_string(),
+            pool_address: "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ".to_string(),
+            strategy_type: StrategyType::Threshold,
+            parameters: StrategyParameters {
+                tick_width: Some(400),
+                rebalance_threshold_pct: Some(Decimal::new(5, 2)),
+                max_il_pct: None,
+                eval_interval_secs: None,
+                eval_cron: Some("5 0,8,16 * * *".to_string()),
+                timezone: Some("Europe/Berlin".to_string()),
+                min_rebalance_interval_hours: None,
+                hysteresis_margin_pct: None,
+                confirm_evaluations: Some(2),
+                rebalance_cooldown_secs: None,
+                auto_compound: None,
+                stop_loss: Some(StopLossSettings {
+                    enabled: true,
+                    max_il_pct: None,
+                    max_drawdown_pct: Some(Decimal::from(20)),
+                    min_price: None,
+                    max_price: None,
+                    swap_to_quote: false,
+                    require_confirmation: true,
+                    escalate_after_secs: None,
+                }),
+            },
+            auto_execute: false,
+            dry_run: true,
+            paper_trading: false,
+            shadow: false,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let yaml = StrategyDocument::new(strategy()).to_yaml().unwrap();
+        assert!(yaml.starts_with("version: 1\n"));
+        assert!(yaml.contains("strategy_type: threshold"));
+
+        let document = StrategyDocument::from_yaml(&yaml).unwrap();
+        let parameters = &document.strategy.parameters;
+        assert_eq!(document.strategy.name, "SOL/USDC threshold");
+        assert_eq!(parameters.rebalance_threshold_pct, Some(Decimal::new(5, 2)));
+        assert_eq!(parameters.eval_cron.as_deref(), Some("5 0,8,16 * * *"));
+        assert_eq!(
+            parameters
+                .stop_loss
+                .as_ref()
+                .and_then(|s| s.max_drawdown_pct),
+            Some(Decimal::from(20))
+        );
+        assert!(document.strategy.dry_run);
+    }
+
+    #[test]
+    fn test_hand_written_document() {
+        let yaml = "\
+version: 1
+name: Wide range
+pool_address: HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ
+strategy_type: il_limit
+parameters:
+  max_il_pct: 2.5
+  eval_interval_secs: 600
+";
+        let document = StrategyDocument::from_yaml(yaml).unwrap();
+        assert!(matches!(
+            document.strategy.strategy_type,
+            StrategyType::IlLimit
+        ));
+        assert_eq!(
+            document.strategy.parameters.max_il_pct,
+            Some(Decimal::new(25, 1))
+        );
+        assert!(!document.strategy.auto_execute);
+    }
+
+    #[test]
+    fn test_rejects_unknown_versions() {
+        let yaml = StrategyDocument::new(strategy()).to_yaml().unwrap();
+
+        assert!(matches!(
+            StrategyDocument::from_yaml(&yaml.replace("version: 1", "version: 2")),
+            Err(StrategyDocumentError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            StrategyDocument::from_yaml(&yaml.replace("version: 1\n", "")),
+            Err(StrategyDocumentError::MissingVersion)
+        ));
+        assert!(matches!(
+            StrategyDocument::from_yaml("version: 1\nname: incomplete\n"),
+            Err(StrategyDocumentError::Yaml(_))
+        ));
+    }
+}
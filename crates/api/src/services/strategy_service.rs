@@ -1,6 +1,7 @@
 //! Strategy service for managing automated strategies.
 
 use crate::error::ApiError;
+use crate::handlers::strategy_schedules;
 use crate::state::{AlertUpdate, AppState};
 use clmm_lp_execution::prelude::{DecisionConfig, ExecutorConfig, StrategyExecutor};
 use rust_decimal::Decimal;
@@ -71,17 +72,25 @@ impl StrategyService {
             ));
         }
 
+        let defaults = &self.state.config.executor;
+
         // Parse configuration
         let dry_run = strategy
             .config
             .get("dry_run")
             .and_then(|v| v.as_bool())
-            .unwrap_or(true);
+            .unwrap_or(defaults.dry_run);
 
         let auto_execute = strategy
             .config
             .get("auto_execute")
             .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.auto_execute);
+
+        let shadow = strategy
+            .config
+            .get("shadow")
+            .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
         let eval_interval_secs = strategy
@@ -89,25 +98,43 @@ impl StrategyService {
             .get("parameters")
             .and_then(|p| p.get("eval_interval_secs"))
             .and_then(|v| v.as_u64())
-            .unwrap_or(300);
+            .unwrap_or(defaults.eval_interval_secs);
+
+        let (eval_cron, _) = strategy_schedules(strategy.config.get("parameters"))?;
 
         // Create executor configuration
         let executor_config = ExecutorConfig {
             eval_interval_secs,
+            eval_cron,
             auto_execute,
             require_confirmation: !auto_execute,
-            max_slippage_pct: Decimal::new(5, 3), // 0.5%
+            max_slippage_pct: defaults.max_slippage_pct,
             dry_run,
+            shadow,
         };
 
         // Create strategy executor
-        let executor = StrategyExecutor::new(
+        let mut executor = StrategyExecutor::new(
             self.state.provider.clone(),
             self.state.monitor.clone(),
             self.state.tx_manager.clone(),
             executor_config,
         );
 
+        // Share the global circuit breaker so the kill switch halts every executor
+        executor.set_circuit_breaker(self.state.circuit_breaker.clone());
+
+        // Record history in the shared (and possibly persisted) lifecycle tracker
+        executor.set_lifecycle(self.state.lifecycle.clone());
+
+        // Abort rebalances into pools priced away from the oracle
+        if let Some(guard) = &self.state.price_guard {
+            executor.set_price_guard(guard.clone());
+        }
+
+        // Record every evaluation and outcome in the audit log
+        executor.set_audit_log(self.state.audit.clone(), Some(strategy_id.to_string()));
+
         // Configure decision engine if parameters provided
         if let Some(params) = strategy.config.get("parameters") {
             let mut decision_config = DecisionConfig::default();
@@ -284,6 +311,7 @@ impl StrategyService {
                     "closed_positions": stats.closed_positions,
                     "total_rebalances": stats.total_rebalances,
                     "total_fees_usd": stats.total_fees_usd.to_string(),
+                    "total_funding_usd": stats.total_funding_usd.to_string(),
                     "total_pnl_usd": stats.total_pnl_usd.to_string(),
                     "avg_pnl_pct": stats.avg_pnl_pct.to_string(),
                     "total_tx_costs_lamports": stats.total_tx_costs_lamports
@@ -307,6 +335,7 @@ impl StrategyService {
                     "closed_positions": stats.closed_positions,
                     "total_rebalances": stats.total_rebalances,
                     "total_fees_usd": stats.total_fees_usd.to_string(),
+                    "total_funding_usd": stats.total_funding_usd.to_string(),
                     "total_pnl_usd": stats.total_pnl_usd.to_string(),
                     "avg_pnl_pct": stats.avg_pnl_pct.to_string(),
                     "total_tx_costs_lamports": stats.total_tx_costs_lamports
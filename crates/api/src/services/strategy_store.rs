@@ -0,0 +1,219 @@
+//! Persistence of strategy definitions, running state and wallet bindings.
+
+use crate::handlers::strategies::launch_executor;
+use crate::state::{AppState, StrategyState};
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{StrategyRecord, StrategyRepository};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// A persisted strategy.
+#[derive(Debug, Clone)]
+pub struct StoredStrategy {
+    /// The strategy.
+    pub strategy: StrategyState,
+    /// Label of the wallet bound to the strategy, if any.
+    pub wallet: Option<String>,
+}
+
+/// Store for strategies that survive a restart.
+#[async_trait]
+pub trait StrategyStore: Send + Sync {
+    /// Saves a strategy.
+    async fn save(&self, strategy: &StoredStrategy) -> anyhow::Result<()>;
+
+    /// Deletes a strategy.
+    async fn delete(&self, id: &str) -> anyhow::Result<()>;
+
+    /// Loads all strategies.
+    async fn load_all(&self) -> anyhow::Result<Vec<StoredStrategy>>;
+}
+
+/// Converts a strategy to its database record.
+///
+/// # Errors
+/// Returns an error if the ID is not a UUID or the configuration has no
+/// strategy type.
+fn strategy_record(stored: &StoredStrategy) -> anyhow::Result<StrategyRecord> {
+    let strategy = &stored.strategy;
+    let strategy_type = strategy
+        .config
+        .get("strategy_type")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Strategy {} has no strategy type", strategy.id))?;
+    Ok(StrategyRecord {
+        id: Uuid::parse_str(&strategy.id)?,
+        name: strategy.name.clone(),
+        strategy_type: strategy_type.to_string(),
+        config: strategy.config.clone(),
+        running: strategy.running,
+        tenant: strategy.owner.clone(),
+        wallet: stored.wallet.clone(),
+        created_at: strategy.created_at,
+        updated_at: strategy.updated_at,
+    })
+}
+
+/// Converts a database record back to a strategy.
+fn stored_strategy(record: StrategyRecord) -> StoredStrategy {
+    StoredStrategy {
+        strategy: StrategyState {
+            id: record.id.to_string(),
+            name: record.name,
+            running: record.running,
+            config: record.config,
+            owner: record.tenant,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        },
+        wallet: record.wallet,
+    }
+}
+
+/// Strategy store that persists strategies to PostgreSQL.
+#[derive(Clone)]
+pub struct DbStrategyStore {
+    /// Strategy repository.
+    repo: StrategyRepository,
+}
+
+impl DbStrategyStore {
+    /// Creates a new database strategy store.
+    pub fn new(repo: StrategyRepository) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl StrategyStore for DbStrategyStore {
+    async fn save(&self, strategy: &StoredStrategy) -> anyhow::Result<()> {
+        self.repo.upsert(&strategy_record(strategy)?).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> anyhow::Result<()> {
+        self.repo.delete(Uuid::parse_str(id)?).await?;
+        Ok(())
+    }
+
+    async fn load_all(&self) -> anyhow::Result<Vec<StoredStrategy>> {
+        let records = self.repo.find_all().await?;
+        Ok(records.into_iter().map(stored_strategy).collect())
+    }
+}
+
+/// Loads persisted strategies, restores their wallet bindings and restarts
+/// the executors of those that were running.
+///
+/// Strategies whose executor fails to start are restored as stopped. A
+/// bound wallet is used once it is registered again.
+/// Returns the number of strategies restored.
+///
+/// # Errors
+/// Returns an error if no store is configured or loading fails.
+pub async fn restore_strategies(state: &AppState) -> anyhow::Result<usize> {
+    let store = state
+        .strategy_store
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("No strategy store configured"))?;
+    let strategies = store.load_all().await?;
+    let total = strategies.len();
+
+    let mut running = Vec::new();
+    {
+        let mut map = state.strategies.write().await;
+        for StoredStrategy { strategy, wallet } in strategies {
+            if let Some(label) = wallet {
+                state
+                    .wallets
+                    .restore_binding(&label, strategy.id.clone())
+                    .await;
+            }
+            if strategy.running {
+                running.push((strategy.id.clone(), strategy.config.clone()));
+            }
+            map.insert(strategy.id.clone(), strategy);
+        }
+    }
+
+    let mut restarted = 0;
+    for (id, config) in running {
+        match launch_executor(state, &id, &config).await {
+            Ok(_) => restarted += 1,
+            Err(e) => {
+                warn!(id = %id, error = %e, "Failed to restart strategy, marking it stopped");
+                if let Some(strategy) = state.strategies.write().await.get_mut(&id) {
+                    strategy.running = false;
+                    strategy.updated_at = chrono::Utc::now();
+                }
+                state.persist_strategy(&id).await;
+            }
+        }
+    }
+
+    info!(
+        strategies = total,
+        restarted = restarted,
+        "Restored persisted strategies"
+    );
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(config: serde_json::Value, wallet: Option<&str>) -> StoredStrategy {
+        let now = chrono::Utc::now();
+        StoredStrategy {
+            strategy: StrategyState {
+                id: Uuid::new_v4().to_string(),
+                name: "SOL/USDC threshold".to_string(),
+                running: true,
+                config,
+                owner: Some("acme".to_string()),
+                created_at: now,
+                updated_at: now,
+            },
+            wallet: wallet.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let original = stored(
+            serde_json::json!({
+                "pool_address": "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ",
+                "strategy_type": "threshold",
+                "dry_run": true,
+            }),
+            Some("acme-hot"),
+        );
+
+        let record = strategy_record(&original).unwrap();
+        assert_eq!(record.strategy_type, "threshold");
+        assert_eq!(record.wallet.as_deref(), Some("acme-hot"));
+        assert!(record.validate().is_ok());
+
+        let restored = stored_strategy(record);
+        assert_eq!(restored.strategy.id, original.strategy.id);
+        assert_eq!(restored.strategy.name, original.strategy.name);
+        assert_eq!(restored.strategy.config, original.strategy.config);
+        assert_eq!(restored.strategy.owner, original.strategy.owner);
+        assert!(restored.strategy.running);
+        assert_eq!(restored.wallet, original.wallet);
+
+        let unbound =
+            stored_strategy(strategy_record(&stored(original.strategy.config, None)).unwrap());
+        assert!(unbound.wallet.is_none());
+    }
+
+    #[test]
+    fn test_record_requires_strategy_type() {
+        let missing = stored(serde_json::json!({ "dry_run": true }), None);
+        assert!(strategy_record(&missing).is_err());
+        let empty = stored(serde_json::json!({ "strategy_type": "" }), None);
+        assert!(strategy_record(&empty).is_err());
+    }
+}
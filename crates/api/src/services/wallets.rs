@@ -0,0 +1,391 @@
+//! Registered signing wallets.
+//!
+//! Wallets are loaded from encrypted keystores or delegate to a remote
+//! signer, and are bound to strategies by ID. A strategy's executor is
+//! given its bound wallet when it starts. Registrations are held in memory;
+//! bindings are persisted with their strategies and restored on startup,
+//! taking effect once the wallet is registered again.
+//!
+//! A wallet may belong to a tenant; the positions it owns are visible only
+//! to that tenant (see [`crate::tenancy`]).
+
+use crate::models::WalletKind;
+use chrono::{DateTime, Utc};
+use clmm_lp_execution::prelude::Wallet;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+/// A registered wallet.
+pub struct RegisteredWallet {
+    /// Wallet label.
+    pub label: String,
+    /// How the wallet signs.
+    pub kind: WalletKind,
+    /// Keystore path or signer URL.
+    pub source: String,
+    /// Owning tenant; `None` for operator wallets.
+    pub tenant: Option<String>,
+    /// Registered timestamp.
+    pub created_at: DateTime<Utc>,
+    /// The signing wallet.
+    pub wallet: Arc<Wallet>,
+}
+
+/// In-memory registry of wallets and their strategy bindings.
+#[derive(Default)]
+pub struct WalletRegistry {
+    /// Wallets by label.
+    wallets: RwLock<HashMap<String, Arc<RegisteredWallet>>>,
+    /// Wallet label by strategy ID.
+    bindings: RwLock<HashMap<String, String>>,
+}
+
+impl WalletRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an operator wallet under its label.
+    ///
+    /// Returns `None` if the label is already taken.
+    pub async fn register(
+        &self,
+        wallet: Wallet,
+        kind: WalletKind,
+        source: impl Into<String>,
+    ) -> Option<Arc<RegisteredWallet>> {
+        self.register_for_tenant(wallet, kind, source, None).await
+    }
+
+    /// Registers a wallet owned by a tenant under its label.
+    ///
+    /// Returns `None` if the label is already taken.
+    pub async fn register_for_tenant(
+        &self,
+        wallet: Wallet,
+        kind: WalletKind,
+        source: impl Into<String>,
+        tenant: Option<String>,
+    ) -> Option<Arc<RegisteredWallet>> {
+        let mut wallets = self.wallets.write().await;
+        if wallets.contains_key(wallet.label()) {
+            return None;
+        }
+
+        let registered = Arc::new(RegisteredWallet {
+            label: wallet.label().to_string(),
+            kind,
+            source: source.into(),
+            tenant,
+            created_at: Utc::now(),
+            wallet: Arc::new(wallet),
+        });
+        wallets.insert(registered.label.clone(), registered.clone());
+
+        info!(
+            label = %registered.label,
+            pubkey = %registered.wallet.pubkey(),
+            "Wallet registered"
+        );
+        Some(registered)
+    }
+
+    /// Gets a wallet by label.
+    pub async fn get(&self, label: &str) -> Option<Arc<RegisteredWallet>> {
+        self.wallets.read().await.get(label).cloned()
+    }
+
+    /// Lists all wallets, oldest first.
+    pub async fn list(&self) -> Vec<Arc<RegisteredWallet>> {
+        let mut wallets: Vec<_> = self.wallets.read().await.values().cloned().collect();
+        wallets.sort_by_key(|w| w.created_at);
+        wallets
+    }
+
+    /// Lists the wallets a caller may sign with, oldest first.
+    ///
+    /// Tenants may use their own wallets; operators every wallet.
+    pub async fn usable(&self, tenant: Option<&str>) -> Vec<Arc<RegisteredWallet>> {
+        let mut wallets = self.list().await;
+        wallets.retain(|w| tenant.is_none() || w.tenant.as_deref() == tenant);
+        wallets
+    }
+
+    /// Returns the public keys of the wallets owned by a tenant.
+    pub async fn owners(&self, tenant: &str) -> HashSet<Pubkey> {
+        self.wallets
+            .read()
+            .await
+            .values()
+            .filter(|w| w.tenant.as_deref() == Some(tenant))
+            .map(|w| w.wallet.pubkey())
+            .collect()
+    }
+
+    /// Removes a wallet and its bindings. Returns whether it existed.
+    pub async fn delete(&self, label: &str) -> bool {
+        let removed = self.wallets.write().await.remove(label).is_some();
+        if removed {
+            self.bindings
+                .write()
+                .await
+                .retain(|_, bound| bound != label);
+            info!(label = %label, "Wallet removed");
+        }
+        removed
+    }
+
+    /// Binds a wallet to a strategy, replacing any previous binding.
+    ///
+    /// Returns `false` if the wallet does not exist.
+    pub async fn bind(&self, label: &str, strategy_id: impl Into<String>) -> bool {
+        if !self.wallets.read().await.contains_key(label) {
+            return false;
+        }
+        let strategy_id = strategy_id.into();
+        info!(label = %label, strategy_id = %strategy_id, "Wallet bound to strategy");
+        self.bindings
+            .write()
+            .await
+            .insert(strategy_id, label.to_string());
+        true
+    }
+
+    /// Restores a persisted binding.
+    ///
+    /// Unlike [`Self::bind`], the wallet need not be registered yet; the
+    /// strategy signs with it once it is.
+    pub async fn restore_binding(&self, label: &str, strategy_id: impl Into<String>) {
+        self.bindings
+            .write()
+            .await
+            .insert(strategy_id.into(), label.to_string());
+    }
+
+    /// Returns the label of the wallet bound to a strategy.
+    pub async fn bound_label(&self, strategy_id: &str) -> Option<String> {
+        self.bindings.read().await.get(strategy_id).cloned()
+    }
+
+    /// Returns the IDs of the strategies bound to a wallet, sorted.
+    pub async fn strategies(&self, label: &str) -> Vec<String> {
+        let mut strategies: Vec<String> = self
+            .bindings
+            .read()
+            .await
+            .iter()
+            .filter(|(_, bound)| bound.as_str() == label)
+            .map(|(id, _)| id.clone())
+            .collect();
+        strategies.sort();
+        strategies
+    }
+
+    /// Gets the wallet with a public key.
+    pub async fn find_by_pubkey(&self, pubkey: &Pubkey) -> Option<Arc<RegisteredWallet>> {
+        self.wallets
+            .read()
+            .await
+            .values()
+            .find(|w| w.wallet.pubkey() == *pubkey)
+            .cloned()
+    }
+
+    /// Picks the wallet signing for a caller.
+    ///
+    /// A named wallet must belong to the tenant, if any; operators may use
+    /// every wallet. Without a name, the caller's only wallet is picked, and
+    /// `None` is returned if it has several.
+    pub async fn signing_wallet(
+        &self,
+        label: Option<&str>,
+        tenant: Option<&str>,
+    ) -> Option<Arc<RegisteredWallet>> {
+        let usable = |w: &RegisteredWallet| tenant.is_none() || w.tenant.as_deref() == tenant;
+        let wallets = self.wallets.read().await;
+        match label {
+            Some(label) => wallets.get(label).filter(|w| usable(w)).cloned(),
+            None => {
+                let mut candidates = wallets.values().filter(|w| usable(w));
+                match (candidates.next(), candidates.next()) {
+                    (Some(wallet), None) => Some(wallet.clone()),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Returns the wallet bound to a strategy owned by `owner`.
+    ///
+    /// A wallet registered under the bound label by another tenant is not
+    /// returned, which guards bindings restored before re-registration.
+    pub async fn wallet_for_strategy(
+        &self,
+        strategy_id: &str,
+        owner: Option<&str>,
+    ) -> Option<Arc<Wallet>> {
+        let label = self.bound_label(strategy_id).await?;
+        self.get(&label)
+            .await
+            .filter(|w| w.tenant.as_deref() == owner)
+            .map(|w| w.wallet.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+
+    fn wallet(label: &str) -> Wallet {
+        Wallet::from_keypair(Keypair::new(), label)
+    }
+
+    #[tokio::test]
+    async fn test_register_and_bind() {
+        let registry = WalletRegistry::new();
+        let hot = registry
+            .register(wallet("hot"), WalletKind::Keystore, "hot.json")
+            .await
+            .unwrap();
+        assert!(
+            registry
+                .register(wallet("hot"), WalletKind::Keystore, "other.json")
+                .await
+                .is_none()
+        );
+
+        assert!(registry.bind("hot", "strategy-1").await);
+        assert!(!registry.bind("cold", "strategy-1").await);
+        assert_eq!(registry.strategies("hot").await, vec!["strategy-1"]);
+        assert_eq!(
+            registry
+                .wallet_for_strategy("strategy-1", None)
+                .await
+                .unwrap()
+                .pubkey(),
+            hot.wallet.pubkey()
+        );
+        assert!(
+            registry
+                .wallet_for_strategy("strategy-1", Some("acme"))
+                .await
+                .is_none()
+        );
+
+        let acme = registry
+            .register_for_tenant(
+                wallet("acme"),
+                WalletKind::Keystore,
+                "acme.json",
+                Some("acme".to_string()),
+            )
+            .await
+            .unwrap();
+        let owners = registry.owners("acme").await;
+        assert_eq!(owners.len(), 1);
+        assert!(owners.contains(&acme.wallet.pubkey()));
+        assert!(registry.owners("globex").await.is_empty());
+
+        // Tenants sign only with their own wallets; operators with any
+        let found = registry.find_by_pubkey(&acme.wallet.pubkey()).await;
+        assert_eq!(found.unwrap().label, "acme");
+
+        let usable: Vec<String> = registry
+            .usable(Some("acme"))
+            .await
+            .iter()
+            .map(|w| w.label.clone())
+            .collect();
+        assert_eq!(usable, vec!["acme"]);
+        assert_eq!(registry.usable(None).await.len(), 2);
+
+        let hot_signer = registry.signing_wallet(Some("hot"), None).await;
+        assert_eq!(hot_signer.unwrap().label, "hot");
+        assert!(
+            registry
+                .signing_wallet(Some("hot"), Some("acme"))
+                .await
+                .is_none()
+        );
+        let acme_signer = registry.signing_wallet(None, Some("acme")).await;
+        assert_eq!(acme_signer.unwrap().label, "acme");
+        assert!(registry.signing_wallet(None, None).await.is_none());
+        assert!(
+            registry
+                .signing_wallet(None, Some("globex"))
+                .await
+                .is_none()
+        );
+
+        assert!(registry.delete("hot").await);
+        assert!(
+            registry
+                .wallet_for_strategy("strategy-1", None)
+                .await
+                .is_none()
+        );
+        assert!(registry.bound_label("strategy-1").await.is_none());
+        assert!(registry.delete("acme").await);
+        assert!(registry.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restore_binding() {
+        let registry = WalletRegistry::new();
+        registry.restore_binding("acme", "strategy-1").await;
+        assert_eq!(
+            registry.bound_label("strategy-1").await.as_deref(),
+            Some("acme")
+        );
+        assert!(
+            registry
+                .wallet_for_strategy("strategy-1", Some("acme"))
+                .await
+                .is_none()
+        );
+
+        // Another tenant's wallet under the label is not used
+        registry
+            .register_for_tenant(
+                wallet("acme"),
+                WalletKind::Keystore,
+                "acme.json",
+                Some("globex".to_string()),
+            )
+            .await
+            .unwrap();
+        assert!(
+            registry
+                .wallet_for_strategy("strategy-1", Some("acme"))
+                .await
+                .is_none()
+        );
+        assert!(registry.delete("acme").await);
+
+        let acme = registry
+            .register_for_tenant(
+                wallet("acme"),
+                WalletKind::Keystore,
+                "acme.json",
+                Some("acme".to_string()),
+            )
+            .await
+            .unwrap();
+        // Deleting the wallet dropped the binding
+        assert!(registry.bound_label("strategy-1").await.is_none());
+        registry.restore_binding("acme", "strategy-1").await;
+        assert_eq!(
+            registry
+                .wallet_for_strategy("strategy-1", Some("acme"))
+                .await
+                .unwrap()
+                .pubkey(),
+            acme.wallet.pubkey()
+        );
+    }
+}
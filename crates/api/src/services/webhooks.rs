@@ -0,0 +1,299 @@
+//! Outbound webhook subscriptions.
+//!
+//! Registered URLs receive the same position, strategy, and alert updates
+//! as WebSocket clients, delivered as signed `WebhookEvent`s by the
+//! execution crate's notifier, which retries failed deliveries with
+//! backoff. Subscriptions are held in memory.
+
+use crate::models::WebhookEventType;
+use crate::state::AppState;
+use crate::websocket::{ServerMessage, Subscriptions, Topic, UpdateStream};
+use chrono::{DateTime, Utc};
+use clmm_lp_execution::prelude::{WebhookConfig, WebhookEvent, WebhookEventKind, WebhookNotifier};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Prefix of generated signing secrets.
+const WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+
+/// A registered webhook.
+pub struct WebhookSubscription {
+    /// Subscription ID.
+    pub id: String,
+    /// Callback URL.
+    pub url: String,
+    /// Subscribed event types.
+    pub events: Vec<WebhookEventType>,
+    /// Optional description.
+    pub description: Option<String>,
+    /// Created timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Signing notifier.
+    notifier: WebhookNotifier,
+    /// Successful deliveries.
+    deliveries: AtomicU64,
+    /// Deliveries that failed after all retries.
+    failures: AtomicU64,
+}
+
+impl WebhookSubscription {
+    /// Checks whether the subscription wants an event type.
+    #[must_use]
+    pub fn wants(&self, event_type: WebhookEventType) -> bool {
+        self.events.contains(&event_type)
+    }
+
+    /// Returns the number of successful deliveries.
+    #[must_use]
+    pub fn deliveries(&self) -> u64 {
+        self.deliveries.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of deliveries that failed after all retries.
+    #[must_use]
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// Delivers an event, recording the outcome.
+    pub async fn deliver(&self, event: &WebhookEvent) {
+        match self.notifier.send_event(event).await {
+            Ok(()) => {
+                self.deliveries.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+                warn!(id = %self.id, url = %self.url, error = %e, "Webhook delivery failed");
+            }
+        }
+    }
+}
+
+/// In-memory registry of webhook subscriptions.
+#[derive(Default)]
+pub struct WebhookRegistry {
+    /// Subscriptions by ID.
+    subscriptions: RwLock<HashMap<String, Arc<WebhookSubscription>>>,
+}
+
+impl WebhookRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a webhook and returns it with its signing secret.
+    ///
+    /// A secret is generated unless one is supplied.
+    pub async fn create(
+        &self,
+        url: impl Into<String>,
+        events: Vec<WebhookEventType>,
+        description: Option<String>,
+        secret: Option<String>,
+    ) -> (Arc<WebhookSubscription>, String) {
+        let url = url.into();
+        let secret = secret.unwrap_or_else(|| {
+            format!("{}{}", WEBHOOK_SECRET_PREFIX, uuid::Uuid::new_v4().simple())
+        });
+        let subscription = Arc::new(WebhookSubscription {
+            id: uuid::Uuid::new_v4().to_string(),
+            notifier: WebhookNotifier::with_config(
+                WebhookConfig::new(url.clone()).with_secret(secret.clone()),
+            ),
+            url,
+            events,
+            description,
+            created_at: Utc::now(),
+            deliveries: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+        });
+        self.subscriptions
+            .write()
+            .await
+            .insert(subscription.id.clone(), subscription.clone());
+
+        info!(id = %subscription.id, url = %subscription.url, "Webhook registered");
+        (subscription, secret)
+    }
+
+    /// Lists all subscriptions, oldest first.
+    pub async fn list(&self) -> Vec<Arc<WebhookSubscription>> {
+        let mut subscriptions: Vec<_> = self.subscriptions.read().await.values().cloned().collect();
+        subscriptions.sort_by_key(|s| s.created_at);
+        subscriptions
+    }
+
+    /// Deletes a subscription. Returns whether it existed.
+    pub async fn delete(&self, id: &str) -> bool {
+        let removed = self.subscriptions.write().await.remove(id).is_some();
+        if removed {
+            info!(id = %id, "Webhook deleted");
+        }
+        removed
+    }
+
+    /// Returns the subscriptions that want an event type.
+    pub async fn matching(&self, event_type: WebhookEventType) -> Vec<Arc<WebhookSubscription>> {
+        self.subscriptions
+            .read()
+            .await
+            .values()
+            .filter(|s| s.wants(event_type))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Checks that a callback URL is an absolute HTTP(S) URL.
+#[must_use]
+pub fn is_valid_webhook_url(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .map(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+        .unwrap_or(false)
+}
+
+/// Maps a broadcast update to the webhook events it produces.
+///
+/// Executed rebalances produce both a position update and a rebalance
+/// event; simulated ones only a position update.
+#[must_use]
+pub fn webhook_events(message: &ServerMessage) -> Vec<(WebhookEventType, WebhookEvent)> {
+    match message {
+        ServerMessage::Position(update) => {
+            let data = serde_json::to_value(update).unwrap_or_default();
+            let mut events = vec![(
+                WebhookEventType::PositionUpdate,
+                WebhookEvent::new(WebhookEventKind::Position, data.clone()),
+            )];
+            if update.update_type.starts_with("rebalance")
+                && !update.update_type.ends_with("_simulated")
+            {
+                events.push((
+                    WebhookEventType::RebalanceExecuted,
+                    WebhookEvent::new(WebhookEventKind::Rebalance, data),
+                ));
+            }
+            events
+        }
+        ServerMessage::Strategy(update) => vec![(
+            WebhookEventType::StrategyUpdate,
+            WebhookEvent::new(
+                WebhookEventKind::Strategy,
+                serde_json::to_value(update).unwrap_or_default(),
+            ),
+        )],
+        ServerMessage::Alert(alert) => vec![(
+            WebhookEventType::Alert,
+            WebhookEvent::new(
+                WebhookEventKind::Alert,
+                serde_json::to_value(alert).unwrap_or_default(),
+            ),
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// Spawns the task forwarding broadcast updates to registered webhooks.
+///
+/// Each delivery runs in its own task so a slow receiver does not hold up
+/// the others.
+pub fn spawn_webhook_dispatcher(state: &AppState) -> JoinHandle<()> {
+    let registry = state.webhooks.clone();
+    let mut updates = UpdateStream::new(state);
+    let mut subscriptions = Subscriptions::default();
+    subscriptions.subscribe(Topic::Positions);
+    subscriptions.subscribe(Topic::Strategies);
+    subscriptions.subscribe(Topic::Alerts);
+
+    tokio::spawn(async move {
+        while let Some(message) = updates.next(&subscriptions).await {
+            for (event_type, event) in webhook_events(&message) {
+                for subscription in registry.matching(event_type).await {
+                    let event = event.clone();
+                    tokio::spawn(async move { subscription.deliver(&event).await });
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::PositionUpdate;
+
+    fn position_update(update_type: &str) -> ServerMessage {
+        ServerMessage::Position(PositionUpdate {
+            update_type: update_type.to_string(),
+            position_address: "pos".to_string(),
+            pool_address: None,
+            timestamp: Utc::now(),
+            data: serde_json::Value::Null,
+        })
+    }
+
+    #[test]
+    fn test_webhook_events() {
+        let types = |message: &ServerMessage| -> Vec<WebhookEventType> {
+            webhook_events(message)
+                .into_iter()
+                .map(|(t, _)| t)
+                .collect()
+        };
+        assert_eq!(
+            types(&position_update("rebalanced")),
+            vec![
+                WebhookEventType::PositionUpdate,
+                WebhookEventType::RebalanceExecuted
+            ]
+        );
+        assert_eq!(
+            types(&position_update("rebalance_simulated")),
+            vec![WebhookEventType::PositionUpdate]
+        );
+        assert!(
+            types(&ServerMessage::Pong {
+                timestamp: Utc::now()
+            })
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_is_valid_webhook_url() {
+        assert!(is_valid_webhook_url("https://example.com/hooks/clmm"));
+        assert!(is_valid_webhook_url("http://localhost:9000"));
+        assert!(!is_valid_webhook_url("ftp://example.com"));
+        assert!(!is_valid_webhook_url("not a url"));
+    }
+
+    #[tokio::test]
+    async fn test_registry() {
+        let registry = WebhookRegistry::new();
+        let (hook, secret) = registry
+            .create(
+                "https://example.com",
+                vec![WebhookEventType::Alert],
+                None,
+                None,
+            )
+            .await;
+        assert!(secret.starts_with(WEBHOOK_SECRET_PREFIX));
+        assert_eq!(registry.matching(WebhookEventType::Alert).await.len(), 1);
+        assert!(
+            registry
+                .matching(WebhookEventType::PositionUpdate)
+                .await
+                .is_empty()
+        );
+
+        assert!(registry.delete(&hook.id).await);
+        assert!(!registry.delete(&hook.id).await);
+        assert!(registry.list().await.is_empty());
+    }
+}
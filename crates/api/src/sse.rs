@@ -0,0 +1,117 @@
+//! Server-Sent Events stream for real-time updates.
+//!
+//! An alternative to the WebSocket for clients behind proxies that cannot
+//! hold WebSocket connections. Topics are the same as on the WebSocket and
+//! are chosen once per connection with `?topics=positions,alerts`.
+//!
+//! Each topic's snapshot is sent first, followed by updates. The SSE event
+//! name is the message type (`snapshot`, `position`, `strategy`, `alert`,
+//! `job`) and the data is the same JSON as on the WebSocket.
+
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use crate::websocket::{ServerMessage, Subscriptions, Topic, UpdateStream, snapshot};
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::time::Duration;
+use tracing::info;
+
+/// Interval between keep-alive comments.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Query parameters for the event stream.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventsQuery {
+    /// Comma-separated topics (default `positions,alerts`).
+    #[serde(default = "default_topics")]
+    pub topics: String,
+}
+
+fn default_topics() -> String {
+    "positions,alerts".to_string()
+}
+
+/// Parses comma-separated topics.
+///
+/// # Errors
+/// Returns the first unknown topic.
+pub fn parse_topics(topics: &str) -> Result<Vec<Topic>, String> {
+    topics
+        .split(',')
+        .map(str::trim)
+        .filter(|topic| !topic.is_empty())
+        .map(|topic| Topic::parse(topic).ok_or_else(|| topic.to_string()))
+        .collect()
+}
+
+/// Converts a message to an SSE event.
+fn event(message: &ServerMessage) -> Event {
+    Event::default()
+        .event(message.kind())
+        .json_data(message)
+        .unwrap_or_else(|_| Event::default().event("error"))
+}
+
+/// Streams position, strategy, alert, and job updates as Server-Sent Events.
+pub async fn events_sse(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
+) -> ApiResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let topics = parse_topics(&query.topics)
+        .map_err(|topic| ApiError::bad_request(format!("Unknown topic: {}", topic)))?;
+    if topics.is_empty() {
+        return Err(ApiError::bad_request("At least one topic is required"));
+    }
+
+    // Subscribe before taking snapshots so no update is missed in between
+    let updates = UpdateStream::new(&state);
+    let mut subscriptions = Subscriptions::default();
+    let mut snapshots = Vec::with_capacity(topics.len());
+    for topic in topics {
+        snapshots.push(Ok(event(&ServerMessage::Snapshot {
+            topic: topic.to_string(),
+            data: snapshot(&state, &topic).await,
+        })));
+        if !subscriptions.subscribe(topic) {
+            return Err(ApiError::bad_request("Too many topics"));
+        }
+    }
+
+    info!(topics = %query.topics, "SSE client connected");
+
+    let live = stream::unfold(
+        (updates, subscriptions),
+        |(mut updates, subscriptions)| async move {
+            let message = updates.next(&subscriptions).await?;
+            Some((Ok(event(&message)), (updates, subscriptions)))
+        },
+    );
+
+    Ok(Sse::new(stream::iter(snapshots).chain(live))
+        .keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_topics() {
+        let topics = parse_topics("positions, pools:abc,,alerts").unwrap();
+        assert_eq!(
+            topics,
+            vec![
+                Topic::Positions,
+                Topic::Pool("abc".to_string()),
+                Topic::Alerts
+            ]
+        );
+        assert!(parse_topics("").unwrap().is_empty());
+        assert_eq!(parse_topics("alerts,trades").unwrap_err(), "trades");
+    }
+}
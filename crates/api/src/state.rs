@@ -1,18 +1,37 @@
 //! Application state shared across handlers.
 
+use crate::auth::{AuthConfig, AuthState};
+use crate::idempotency::{IdempotencyState, IdempotencyStore};
+use crate::jwt::JwtConfig;
+use crate::middleware::{RateLimitConfig, RateLimiter};
+use crate::request_audit::{MemoryRequestAuditLog, RequestAuditLog};
+use crate::services::{
+    JobQueue, LeaderboardSource, MarketDataService, PoolScreener, PositionHistorySource,
+    PriceHistorySource, StoredStrategy, StrategyStore, WalletRegistry, WebhookRegistry,
+};
+use clmm_lp_config::prelude::ExecutionSettings;
+use clmm_lp_data::prelude::{Database, ResultCache};
 use clmm_lp_execution::prelude::{
-    CircuitBreaker, LifecycleTracker, PositionMonitor, StrategyExecutor, TransactionManager,
+    Alert, AlertDispatcher, AlertLevel, AuditLog, CircuitBreaker, ExecutorConfig, LifecycleTracker,
+    MemoryAuditLog, PortfolioRiskManager, PositionMonitor, PriceGuard, StrategyExecutor,
+    TransactionConfig, TransactionManager,
 };
-use clmm_lp_protocols::prelude::{RpcConfig, RpcProvider};
+use clmm_lp_protocols::prelude::{ProtocolRegistry, RpcConfig, RpcProvider};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{RwLock, broadcast};
+use tracing::warn;
+
+/// Maximum number of backtest and optimization results kept in memory.
+const MAX_CACHED_RESULTS: usize = 500;
 
 /// Application state shared across all handlers.
 #[derive(Clone)]
 pub struct AppState {
     /// RPC provider.
     pub provider: Arc<RpcProvider>,
+    /// Protocol adapters for reading pools and positions of any CLMM.
+    pub protocols: Arc<ProtocolRegistry>,
     /// Position monitor.
     pub monitor: Arc<PositionMonitor>,
     /// Transaction manager.
@@ -21,14 +40,54 @@ pub struct AppState {
     pub circuit_breaker: Arc<CircuitBreaker>,
     /// Lifecycle tracker.
     pub lifecycle: Arc<LifecycleTracker>,
+    /// Audit log of decisions and transactions.
+    pub audit: Arc<dyn AuditLog>,
     /// Active strategies.
     pub strategies: Arc<RwLock<HashMap<String, StrategyState>>>,
+    /// Strategy persistence, if a database is configured.
+    pub strategy_store: Option<Arc<dyn StrategyStore>>,
     /// WebSocket broadcast channel for position updates.
     pub position_updates: broadcast::Sender<PositionUpdate>,
     /// WebSocket broadcast channel for alerts.
     pub alert_updates: broadcast::Sender<AlertUpdate>,
+    /// WebSocket broadcast channel for strategy updates.
+    pub strategy_updates: broadcast::Sender<StrategyUpdate>,
+    /// Alert dispatcher for notification channels and escalation.
+    pub alerts: Arc<AlertDispatcher>,
+    /// Outbound webhook subscriptions.
+    pub webhooks: Arc<WebhookRegistry>,
+    /// Registered signing wallets and strategy bindings.
+    pub wallets: Arc<WalletRegistry>,
     /// API configuration.
     pub config: ApiConfig,
+    /// API key authentication.
+    pub auth: AuthState,
+    /// Stored responses for idempotent retries.
+    pub idempotency: IdempotencyState,
+    /// Log of mutating API requests.
+    pub request_audit: Arc<dyn RequestAuditLog>,
+    /// Per-caller, per-route-class rate limits.
+    pub rate_limiter: RateLimiter,
+    /// Background job queue.
+    pub jobs: Arc<JobQueue>,
+    /// Backtest and optimization results keyed by their inputs.
+    pub results: ResultCache,
+    /// Pool price history for backtests, if a database is configured.
+    pub price_history: Option<Arc<dyn PriceHistorySource>>,
+    /// Market data for candles and analysis, if a provider is configured.
+    pub market_data: Option<Arc<MarketDataService>>,
+    /// Pool screener for pool search, if a database is configured.
+    pub pool_screener: Option<Arc<PoolScreener>>,
+    /// Recorded position snapshots, if a database is configured.
+    pub position_history: Option<Arc<dyn PositionHistorySource>>,
+    /// Computed pool and strategy leaderboards, if a database is configured.
+    pub leaderboard: Option<Arc<dyn LeaderboardSource>>,
+    /// Oracle price guard for rebalances and opens, if enabled.
+    pub price_guard: Option<Arc<PriceGuard>>,
+    /// Portfolio risk limits and stop, if enabled.
+    pub risk: Option<Arc<PortfolioRiskManager>>,
+    /// Database connection, if configured.
+    pub database: Option<Database>,
     /// Strategy executors by ID.
     pub executors: Arc<RwLock<HashMap<String, Arc<RwLock<StrategyExecutor>>>>>,
     /// Whether in dry-run mode.
@@ -39,35 +98,171 @@ impl AppState {
     /// Creates a new application state.
     pub fn new(rpc_config: RpcConfig, api_config: ApiConfig) -> Self {
         let provider = Arc::new(RpcProvider::new(rpc_config));
-        let monitor = Arc::new(PositionMonitor::new(
+        let protocols = Arc::new(ProtocolRegistry::with_defaults(provider.clone()));
+        let (position_tx, _) = broadcast::channel(1000);
+        let (alert_tx, _) = broadcast::channel(1000);
+        let (strategy_tx, _) = broadcast::channel(1000);
+        let alerts = Arc::new(AlertDispatcher::new());
+
+        let mut monitor = PositionMonitor::new(
             provider.clone(),
             clmm_lp_execution::prelude::MonitorConfig::default(),
-        ));
-        let tx_manager = Arc::new(TransactionManager::new(
-            provider.clone(),
-            clmm_lp_execution::prelude::TransactionConfig::default(),
-        ));
+        );
+        let monitor_alert_tx = alert_tx.clone();
+        let monitor_alerts = alerts.clone();
+        monitor.set_alert_callback(move |alert| {
+            raise_alert(&monitor_alert_tx, &monitor_alerts, alert);
+        });
+        let monitor = Arc::new(monitor);
+
+        let audit: Arc<dyn AuditLog> = Arc::new(MemoryAuditLog::default());
+        let tx_manager = Arc::new(
+            TransactionManager::new(provider.clone(), api_config.transaction.clone())
+                .with_audit_log(audit.clone()),
+        );
         let circuit_breaker = Arc::new(CircuitBreaker::default());
         let lifecycle = Arc::new(LifecycleTracker::new());
-
-        let (position_tx, _) = broadcast::channel(1000);
-        let (alert_tx, _) = broadcast::channel(1000);
+        let mut auth_config = AuthConfig {
+            api_keys: api_config.api_keys.iter().cloned().collect(),
+            require_auth: api_config.require_auth,
+            jwt: api_config.jwt.clone(),
+            ..Default::default()
+        };
+        if let Some(secret) = api_config.jwt.as_ref().and_then(|j| j.secret.clone()) {
+            auth_config.jwt_secret = secret;
+        }
+        let auth = AuthState::new(auth_config);
+        let rate_limiter = RateLimiter::new(api_config.rate_limits.clone());
 
         Self {
             provider,
+            protocols,
             monitor,
             tx_manager,
             circuit_breaker,
             lifecycle,
+            audit,
             strategies: Arc::new(RwLock::new(HashMap::new())),
+            strategy_store: None,
             position_updates: position_tx,
             alert_updates: alert_tx,
+            strategy_updates: strategy_tx,
+            alerts,
+            webhooks: Arc::new(WebhookRegistry::new()),
+            wallets: Arc::new(WalletRegistry::new()),
             config: api_config,
+            auth,
+            idempotency: IdempotencyState::default(),
+            request_audit: Arc::new(MemoryRequestAuditLog::default()),
+            rate_limiter,
+            jobs: Arc::new(JobQueue::default()),
+            results: ResultCache::in_memory(MAX_CACHED_RESULTS),
+            price_history: None,
+            market_data: None,
+            pool_screener: None,
+            position_history: None,
+            leaderboard: None,
+            price_guard: None,
+            risk: None,
+            database: None,
             executors: Arc::new(RwLock::new(HashMap::new())),
             dry_run: true, // Default to dry-run for safety
         }
     }
 
+    /// Replaces the audit log, e.g. with a persistent one.
+    ///
+    /// Executors started afterwards and the transaction manager record to
+    /// the new log.
+    pub async fn set_audit_log(&mut self, audit: Arc<dyn AuditLog>) {
+        self.tx_manager.set_audit_log(audit.clone()).await;
+        self.audit = audit;
+    }
+
+    /// Sets the store responses for idempotent retries are kept in.
+    pub fn set_idempotency_store(&mut self, store: Arc<dyn IdempotencyStore>) {
+        self.idempotency = IdempotencyState::new(store);
+    }
+
+    /// Replaces the request audit log, e.g. with a persistent one.
+    pub fn set_request_audit_log(&mut self, log: Arc<dyn RequestAuditLog>) {
+        self.request_audit = log;
+    }
+
+    /// Sets the price history source used by backtests.
+    pub fn set_price_history(&mut self, source: Arc<dyn PriceHistorySource>) {
+        self.price_history = Some(source);
+    }
+
+    /// Sets the market data service used by the market endpoints.
+    pub fn set_market_data(&mut self, service: Arc<MarketDataService>) {
+        self.market_data = Some(service);
+    }
+
+    /// Sets the database checked by the health endpoint.
+    pub fn set_database(&mut self, database: Database) {
+        self.database = Some(database);
+    }
+
+    /// Sets the store strategies are persisted to.
+    pub fn set_strategy_store(&mut self, store: Arc<dyn StrategyStore>) {
+        self.strategy_store = Some(store);
+    }
+
+    /// Persists a strategy's definition, running state and wallet binding, if
+    /// a store is set.
+    ///
+    /// Failures are logged; the in-memory strategy remains authoritative.
+    pub async fn persist_strategy(&self, id: &str) {
+        let Some(store) = &self.strategy_store else {
+            return;
+        };
+        let Some(strategy) = self.strategies.read().await.get(id).cloned() else {
+            return;
+        };
+        let stored = StoredStrategy {
+            strategy,
+            wallet: self.wallets.bound_label(id).await,
+        };
+        if let Err(e) = store.save(&stored).await {
+            warn!(id = %id, error = %e, "Failed to persist strategy");
+        }
+    }
+
+    /// Removes a persisted strategy, if a store is set.
+    pub async fn forget_strategy(&self, id: &str) {
+        if let Some(store) = &self.strategy_store
+            && let Err(e) = store.delete(id).await
+        {
+            warn!(id = %id, error = %e, "Failed to delete persisted strategy");
+        }
+    }
+
+    /// Sets the pool screener used by pool search.
+    pub fn set_pool_screener(&mut self, screener: Arc<PoolScreener>) {
+        self.pool_screener = Some(screener);
+    }
+
+    /// Sets the source of recorded position snapshots.
+    pub fn set_position_history(&mut self, source: Arc<dyn PositionHistorySource>) {
+        self.position_history = Some(source);
+    }
+
+    /// Sets the source of computed leaderboards.
+    pub fn set_leaderboard(&mut self, source: Arc<dyn LeaderboardSource>) {
+        self.leaderboard = Some(source);
+    }
+
+    /// Sets the oracle price guard applied before rebalances and opens.
+    pub fn set_price_guard(&mut self, guard: Arc<PriceGuard>) {
+        self.price_guard = Some(guard);
+    }
+
+    /// Sets the portfolio risk manager enforcing the portfolio stop.
+    pub fn set_risk_manager(&mut self, risk: Arc<PortfolioRiskManager>) {
+        self.risk = Some(risk);
+    }
+
     /// Sets dry-run mode.
     pub fn set_dry_run(&mut self, dry_run: bool) {
         self.dry_run = dry_run;
@@ -83,6 +278,19 @@ impl AppState {
         let _ = self.alert_updates.send(alert);
     }
 
+    /// Broadcasts a strategy update.
+    pub fn broadcast_strategy_update(&self, update: StrategyUpdate) {
+        let _ = self.strategy_updates.send(update);
+    }
+
+    /// Raises an execution alert.
+    ///
+    /// Broadcasts it to WebSocket subscribers and dispatches it to the
+    /// notification channels, where it is tracked for acknowledgement.
+    pub fn raise_alert(&self, alert: Alert) {
+        raise_alert(&self.alert_updates, &self.alerts, alert);
+    }
+
     /// Subscribes to position updates.
     pub fn subscribe_positions(&self) -> broadcast::Receiver<PositionUpdate> {
         self.position_updates.subscribe()
@@ -92,6 +300,33 @@ impl AppState {
     pub fn subscribe_alerts(&self) -> broadcast::Receiver<AlertUpdate> {
         self.alert_updates.subscribe()
     }
+
+    /// Subscribes to strategy updates.
+    pub fn subscribe_strategies(&self) -> broadcast::Receiver<StrategyUpdate> {
+        self.strategy_updates.subscribe()
+    }
+}
+
+/// Broadcasts an alert and dispatches it to the notification channels.
+fn raise_alert(
+    alert_updates: &broadcast::Sender<AlertUpdate>,
+    alerts: &Arc<AlertDispatcher>,
+    alert: Alert,
+) {
+    let level = match alert.level {
+        AlertLevel::Info => "info",
+        AlertLevel::Warning => "warning",
+        AlertLevel::Critical => "critical",
+    };
+    let _ = alert_updates.send(AlertUpdate {
+        level: level.to_string(),
+        message: alert.message.clone(),
+        timestamp: alert.timestamp,
+        position_address: alert.position.clone(),
+    });
+
+    let alerts = alerts.clone();
+    tokio::spawn(async move { alerts.dispatch(alert).await });
 }
 
 /// API configuration.
@@ -101,14 +336,25 @@ pub struct ApiConfig {
     pub host: String,
     /// Server port.
     pub port: u16,
-    /// API keys for authentication.
+    /// Bootstrap admin API keys.
     pub api_keys: Vec<String>,
+    /// Whether to require authentication even when no API keys exist.
+    pub require_auth: bool,
+    /// Bearer token validation (JWT/OIDC), as an alternative to API keys.
+    pub jwt: Option<JwtConfig>,
     /// Whether to enable CORS.
     pub enable_cors: bool,
     /// Request timeout in seconds.
     pub request_timeout_secs: u64,
-    /// Rate limit per minute.
-    pub rate_limit_per_minute: u32,
+    /// Rate limits per caller and route class.
+    pub rate_limits: RateLimitConfig,
+    /// Directory for paper trading ledgers.
+    pub paper_ledger_dir: std::path::PathBuf,
+    /// Executor settings strategies start from; their own config overrides
+    /// the flags and evaluation interval.
+    pub executor: ExecutorConfig,
+    /// Transaction submission settings.
+    pub transaction: TransactionConfig,
 }
 
 impl Default for ApiConfig {
@@ -117,9 +363,14 @@ impl Default for ApiConfig {
             host: "0.0.0.0".to_string(),
             port: 8080,
             api_keys: vec![],
+            require_auth: false,
+            jwt: None,
             enable_cors: true,
             request_timeout_secs: 30,
-            rate_limit_per_minute: 100,
+            rate_limits: RateLimitConfig::default(),
+            paper_ledger_dir: std::path::PathBuf::from("data/paper"),
+            executor: ExecutorConfig::from_settings(&ExecutionSettings::default()),
+            transaction: TransactionConfig::default(),
         }
     }
 }
@@ -135,6 +386,8 @@ pub struct StrategyState {
     pub running: bool,
     /// Strategy configuration as JSON.
     pub config: serde_json::Value,
+    /// Owning tenant; `None` for operator strategies.
+    pub owner: Option<String>,
     /// Created timestamp.
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Last updated timestamp.
@@ -148,6 +401,21 @@ pub struct PositionUpdate {
     pub update_type: String,
     /// Position address.
     pub position_address: String,
+    /// Pool address (if known).
+    pub pool_address: Option<String>,
+    /// Timestamp.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Update data.
+    pub data: serde_json::Value,
+}
+
+/// Strategy update for WebSocket broadcast.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StrategyUpdate {
+    /// Update type.
+    pub update_type: String,
+    /// Strategy ID.
+    pub strategy_id: String,
     /// Timestamp.
     pub timestamp: chrono::DateTime<chrono::Utc>,
     /// Update data.
@@ -0,0 +1,138 @@
+//! Tracing setup with optional OpenTelemetry export.
+//!
+//! When `OTEL_EXPORTER_OTLP_ENDPOINT` is set, spans are exported over OTLP
+//! (gRPC) in addition to being logged. HTTP request spans continue traces
+//! from incoming W3C `traceparent` headers, and the spans of strategy
+//! execution, transaction building, and RPC calls nest beneath them.
+
+use axum::http::{HeaderMap, Request};
+use opentelemetry::global;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use std::env;
+use tracing::{Span, info_span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, fmt};
+
+/// Flushes and shuts down span export when dropped.
+pub struct TelemetryGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take()
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("Failed to shut down trace export: {}", e);
+        }
+    }
+}
+
+/// Initializes logging and, if configured, OTLP trace export.
+///
+/// The log filter is read from `RUST_LOG` (default `info`). The service name
+/// is read from `OTEL_SERVICE_NAME` (default `service_name`). Keep the
+/// returned guard alive until shutdown so buffered spans are flushed.
+///
+/// # Errors
+/// Returns an error if the exporter cannot be built or a global subscriber
+/// is already set.
+pub fn init_tracing(service_name: &str) -> anyhow::Result<TelemetryGuard> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer());
+
+    let Ok(endpoint) = env::var("OTEL_EXPORTER_OTLP_ENDPOINT") else {
+        registry.try_init()?;
+        return Ok(TelemetryGuard { provider: None });
+    };
+
+    let service_name = env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| service_name.to_string());
+    let exporter = SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+
+    let tracer = provider.tracer("clmm-lp-api");
+    registry
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()?;
+
+    Ok(TelemetryGuard {
+        provider: Some(provider),
+    })
+}
+
+/// Reads trace context from HTTP headers.
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// Creates the span of an HTTP request, continuing the caller's trace.
+pub fn make_request_span<B>(request: &Request<B>) -> Span {
+    let method = request.method();
+    let path = request.uri().path();
+    let span = info_span!(
+        "request",
+        otel.name = %format!("{} {}", method, path),
+        otel.kind = "server",
+        http.request.method = %method,
+        url.path = %path,
+    );
+
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    let _ = span.set_parent(parent);
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::trace::TraceContextExt;
+
+    #[test]
+    fn test_extracts_traceparent() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+
+        let cx = TraceContextPropagator::new().extract(&HeaderExtractor(&headers));
+        let span = cx.span();
+        let span_context = span.span_context();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+    }
+}
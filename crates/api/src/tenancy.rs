@@ -0,0 +1,522 @@
+//! Tenant scoping of strategies and positions.
+//!
+//! One deployment can serve several tenants. A caller's tenant comes from
+//! its API key or the `tenant` claim of its bearer token; callers without a
+//! tenant are operators and see everything. A tenant sees:
+//! - Strategies it created
+//! - Positions owned by wallets registered to it
+//! - Analytics and exports over those positions
+//! - Alerts and audit entries about those strategies and positions
+//! - Jobs, such as backtests and optimizations, it submitted
+//!
+//! [`tenant_scope`] resolves the caller's [`TenantScope`] into the request
+//! extensions and reports strategies and positions outside it as not found;
+//! handlers do the same for alerts and jobs. The WebSocket and Server-Sent
+//! Events streams are not filtered by tenant, the emergency controls and
+//! alert rules act on every tenant, test alerts go out through the
+//! operator's channels, and leaderboards rank every tenant's positions; all
+//! are reserved for operators.
+
+use crate::auth::Principal;
+use crate::error::ApiError;
+use crate::services::Job;
+use crate::state::{AppState, StrategyState};
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use clmm_lp_execution::prelude::{Alert, AuditEntry, MonitoredPosition};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::str::FromStr;
+use tracing::debug;
+
+/// The strategies and positions visible to a caller.
+#[derive(Debug, Clone, Default)]
+pub struct TenantScope {
+    /// Caller's tenant; `None` for operators.
+    tenant: Option<String>,
+    /// Owners of the positions visible to the tenant.
+    owners: HashSet<Pubkey>,
+}
+
+impl TenantScope {
+    /// Creates the scope of an operator, who sees everything.
+    pub fn operator() -> Self {
+        Self::default()
+    }
+
+    /// Creates the scope of a tenant owning the given wallets.
+    pub fn tenant(tenant: impl Into<String>, owners: HashSet<Pubkey>) -> Self {
+        Self {
+            tenant: Some(tenant.into()),
+            owners,
+        }
+    }
+
+    /// Resolves the scope of a principal.
+    ///
+    /// Unauthenticated requests are served as an operator, as they are only
+    /// accepted while authentication is not enforced.
+    pub async fn resolve(state: &AppState, principal: Option<&Principal>) -> Self {
+        match principal.and_then(|p| p.tenant.as_deref()) {
+            Some(tenant) => Self::tenant(tenant, state.wallets.owners(tenant).await),
+            None => Self::operator(),
+        }
+    }
+
+    /// Returns the caller's tenant, if any.
+    #[must_use]
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    /// Checks whether the caller is an operator.
+    #[must_use]
+    pub fn is_operator(&self) -> bool {
+        self.tenant.is_none()
+    }
+
+    /// Checks whether a strategy is visible to the caller.
+    #[must_use]
+    pub fn can_see_strategy(&self, strategy: &StrategyState) -> bool {
+        self.is_operator() || strategy.owner == self.tenant
+    }
+
+    /// Checks whether a job is visible to the caller.
+    #[must_use]
+    pub fn can_see_job(&self, job: &Job) -> bool {
+        self.is_operator() || job.owner == self.tenant
+    }
+
+    /// Checks whether a position is visible to the caller.
+    #[must_use]
+    pub fn can_see_position(&self, position: &MonitoredPosition) -> bool {
+        self.is_operator() || self.owners.contains(&position.on_chain.owner)
+    }
+
+    /// Checks whether the position at an address is visible to the caller.
+    ///
+    /// Tenants only see positions that are monitored.
+    pub async fn can_see_position_at(&self, state: &AppState, address: &str) -> bool {
+        if self.is_operator() {
+            return true;
+        }
+        let Ok(address) = Pubkey::from_str(address) else {
+            return false;
+        };
+        state
+            .monitor
+            .get_position(&address)
+            .await
+            .is_some_and(|position| self.can_see_position(&position))
+    }
+
+    /// Checks whether an alert is visible to the caller.
+    ///
+    /// Tenants only see alerts about their positions.
+    pub async fn can_see_alert(&self, state: &AppState, alert: &Alert) -> bool {
+        if self.is_operator() {
+            return true;
+        }
+        match &alert.position {
+            Some(address) => self.can_see_position_at(state, address).await,
+            None => false,
+        }
+    }
+
+    /// Checks whether an audit entry is visible to the caller.
+    ///
+    /// Tenants only see entries about their strategies and positions.
+    pub async fn can_see_audit_entry(&self, state: &AppState, entry: &AuditEntry) -> bool {
+        if self.is_operator() {
+            return true;
+        }
+        if let Some(id) = &entry.strategy_id {
+            return state
+                .strategies
+                .read()
+                .await
+                .get(id)
+                .is_some_and(|strategy| self.can_see_strategy(strategy));
+        }
+        match &entry.position {
+            Some(address) => self.can_see_position_at(state, address).await,
+            None => false,
+        }
+    }
+
+    /// Keeps the positions visible to the caller.
+    pub fn filter_positions(&self, positions: Vec<MonitoredPosition>) -> Vec<MonitoredPosition> {
+        if self.is_operator() {
+            return positions;
+        }
+        positions
+            .into_iter()
+            .filter(|p| self.can_see_position(p))
+            .collect()
+    }
+}
+
+/// Checks whether a path serves a real-time stream.
+fn is_stream(path: &str) -> bool {
+    path == "/ws" || path.starts_with("/ws/") || path == "/events" || path.starts_with("/events/")
+}
+
+/// Returns the segment following a collection prefix of a path.
+fn resource_id<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    path.strip_prefix(prefix)?
+        .split('/')
+        .next()
+        .filter(|id| !id.is_empty())
+}
+
+/// Tenant scoping middleware.
+///
+/// Adds the caller's [`TenantScope`] to the request extensions. Requests by
+/// tenants for a strategy or position outside their scope are rejected as
+/// not found, so they cannot learn that it exists; their requests for
+/// real-time streams, to engage or release the emergency controls, to
+/// manage alert rules, to send test alerts and for leaderboards are
+/// forbidden.
+pub async fn tenant_scope(
+    State(state): State<AppState>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let scope = TenantScope::resolve(&state, request.extensions().get::<Principal>()).await;
+
+    if !scope.is_operator() {
+        let path = request.uri().path();
+        if is_stream(path) {
+            return Err(ApiError::Forbidden(
+                "Real-time streams are not available to tenants".to_string(),
+            ));
+        }
+        if path.starts_with("/emergency") && request.method() != Method::GET {
+            return Err(ApiError::Forbidden(
+                "Emergency controls are reserved for operators".to_string(),
+            ));
+        }
+        if path == "/alerts/rules" || path.starts_with("/alerts/rules/") {
+            return Err(ApiError::Forbidden(
+                "Alert rules are reserved for operators".to_string(),
+            ));
+        }
+        if path == "/alerts/test" {
+            return Err(ApiError::Forbidden(
+                "Test alerts are reserved for operators".to_string(),
+            ));
+        }
+        if path == "/analytics/leaderboard" {
+            return Err(ApiError::Forbidden(
+                "Leaderboards are reserved for operators".to_string(),
+            ));
+        }
+        if let Some(id) = resource_id(path, "/strategies/") {
+            let hidden = state
+                .strategies
+                .read()
+                .await
+                .get(id)
+                .is_some_and(|strategy| !scope.can_see_strategy(strategy));
+            if hidden {
+                debug!(tenant = ?scope.tenant_id(), id = %id, "Strategy outside tenant scope");
+                return Err(ApiError::not_found("Strategy not found"));
+            }
+        }
+        if let Some(address) = resource_id(path, "/positions/")
+            && address != "batch"
+            && !scope.can_see_position_at(&state, address).await
+        {
+            debug!(
+                tenant = ?scope.tenant_id(),
+                address = %address,
+                "Position outside tenant scope"
+            );
+            return Err(ApiError::not_found("Position not found"));
+        }
+    }
+
+    request.extensions_mut().insert(scope);
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::handlers;
+    use crate::services::JobStatus;
+    use crate::state::ApiConfig;
+    use axum::body::to_bytes;
+    use axum::http::StatusCode;
+    use axum::routing::{get, post};
+    use axum::{Router, middleware};
+    use clmm_lp_execution::prelude::{AlertLevel, AlertType, AuditKind};
+    use clmm_lp_protocols::prelude::RpcConfig;
+    use tower::ServiceExt;
+
+    fn strategy(owner: Option<&str>) -> StrategyState {
+        StrategyState {
+            id: "s1".to_string(),
+            name: "test".to_string(),
+            running: false,
+            config: serde_json::Value::Null,
+            owner: owner.map(str::to_string),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_strategy_visibility() {
+        let operator = TenantScope::operator();
+        let acme = TenantScope::tenant("acme", HashSet::new());
+
+        assert!(operator.can_see_strategy(&strategy(None)));
+        assert!(operator.can_see_strategy(&strategy(Some("acme"))));
+        assert!(acme.can_see_strategy(&strategy(Some("acme"))));
+        assert!(!acme.can_see_strategy(&strategy(Some("globex"))));
+        assert!(!acme.can_see_strategy(&strategy(None)));
+    }
+
+    #[test]
+    fn test_resource_id() {
+        assert_eq!(
+            resource_id("/strategies/s1/start", "/strategies/"),
+            Some("s1")
+        );
+        assert_eq!(resource_id("/strategies/", "/strategies/"), None);
+        assert_eq!(resource_id("/strategies", "/strategies/"), None);
+        assert_eq!(resource_id("/positions/abc", "/positions/"), Some("abc"));
+        assert!(is_stream("/ws/positions"));
+        assert!(is_stream("/events"));
+        assert!(!is_stream("/wallets"));
+    }
+
+    /// Routes alerts, jobs, leaderboard, audit and emergency requests as the
+    /// given tenant.
+    fn app(state: AppState, tenant: Option<&str>) -> Router {
+        let principal = Principal {
+            subject: "test".to_string(),
+            role: Role::Admin,
+            strategies: Vec::new(),
+            tenant: tenant.map(str::to_string),
+        };
+        Router::new()
+            .route("/alerts", get(handlers::list_alerts))
+            .route("/alerts/{id}", get(handlers::get_alert))
+            .route(
+                "/alerts/{id}/acknowledge",
+                post(handlers::acknowledge_alert),
+            )
+            .route("/alerts/rules", get(handlers::list_alert_rules))
+            .route("/alerts/test", post(handlers::test_alert_channels))
+            .route("/analytics/leaderboard", get(handlers::get_leaderboard))
+            .route(
+                "/jobs/{id}",
+                get(handlers::get_job).delete(handlers::cancel_job),
+            )
+            .route("/audit", get(handlers::list_audit_entries))
+            .route(
+                "/emergency/kill-switch",
+                get(handlers::get_kill_switch).post(handlers::engage_kill_switch),
+            )
+            .route(
+                "/emergency/portfolio-stop",
+                post(handlers::engage_portfolio_stop),
+            )
+            .layer(middleware::from_fn_with_state(state.clone(), tenant_scope))
+            .layer(middleware::from_fn(
+                move |mut request: Request<Body>, next: Next| {
+                    request.extensions_mut().insert(principal.clone());
+                    next.run(request)
+                },
+            ))
+            .with_state(state)
+    }
+
+    async fn send(app: Router, method: Method, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tenant_alerts() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+        let position = Pubkey::new_unique();
+        let alert = Alert::new(
+            AlertLevel::Warning,
+            AlertType::Custom("Test".to_string()),
+            "out of range",
+        )
+        .with_position(&position);
+        let id = alert.id.clone();
+        state.alerts.dispatch(alert).await;
+
+        let (status, body) = send(app(state.clone(), None), Method::GET, "/alerts").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 1);
+
+        // The position is not owned by the tenant, so neither is its alert
+        let (status, body) = send(app(state.clone(), Some("acme")), Method::GET, "/alerts").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 0);
+
+        let uri = format!("/alerts/{}", id);
+        let (status, _) = send(app(state.clone(), Some("acme")), Method::GET, &uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let uri = format!("/alerts/{}/acknowledge", id);
+        let (status, _) = send(app(state.clone(), Some("acme")), Method::POST, &uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(!state.alerts.get(&id).await.unwrap().alert.acknowledged);
+
+        let (status, _) = send(app(state.clone(), None), Method::POST, &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(state.alerts.get(&id).await.unwrap().alert.acknowledged);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_alert_rules() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+
+        let (status, _) = send(
+            app(state.clone(), Some("acme")),
+            Method::GET,
+            "/alerts/rules",
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, _) = send(app(state.clone(), None), Method::GET, "/alerts/rules").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send(
+            app(state.clone(), Some("acme")),
+            Method::POST,
+            "/alerts/test",
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_leaderboard() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+
+        let (status, _) = send(
+            app(state.clone(), Some("acme")),
+            Method::GET,
+            "/analytics/leaderboard",
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Operators reach the handler, which needs a database
+        let (status, _) = send(
+            app(state.clone(), None),
+            Method::GET,
+            "/analytics/leaderboard",
+        )
+        .await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_jobs() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+        let job = state
+            .jobs
+            .submit("test", Some("acme"), serde_json::json!({}), |_| async {
+                Ok(serde_json::json!({}))
+            })
+            .await;
+        let uri = format!("/jobs/{}", job.id);
+
+        let (status, _) = send(app(state.clone(), Some("acme")), Method::GET, &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(app(state.clone(), None), Method::GET, &uri).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // Other tenants can neither see nor cancel the job
+        let (status, _) = send(app(state.clone(), Some("globex")), Method::GET, &uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = send(app(state.clone(), Some("globex")), Method::DELETE, &uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_ne!(
+            state.jobs.get(&job.id).await.unwrap().status,
+            JobStatus::Cancelled
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tenant_audit_entries() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+        for (id, owner) in [("acme-1", "acme"), ("globex-1", "globex")] {
+            let mut owned = strategy(Some(owner));
+            owned.id = id.to_string();
+            state.strategies.write().await.insert(id.to_string(), owned);
+            state
+                .audit
+                .append(&AuditEntry::new(AuditKind::Evaluation, "hold").with_strategy(Some(id)))
+                .await
+                .unwrap();
+        }
+        state
+            .audit
+            .append(
+                &AuditEntry::new(AuditKind::Outcome, "close").with_position(&Pubkey::new_unique()),
+            )
+            .await
+            .unwrap();
+
+        let (_, body) = send(app(state.clone(), None), Method::GET, "/audit").await;
+        assert_eq!(body["total"], 3);
+
+        let (status, body) = send(app(state.clone(), Some("acme")), Method::GET, "/audit").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["entries"][0]["strategy_id"], "acme-1");
+
+        let (_, body) = send(
+            app(state.clone(), Some("acme")),
+            Method::GET,
+            "/audit?strategy_id=globex-1",
+        )
+        .await;
+        assert_eq!(body["total"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_emergency_controls() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+
+        for uri in ["/emergency/kill-switch", "/emergency/portfolio-stop"] {
+            let (status, _) = send(app(state.clone(), Some("acme")), Method::POST, uri).await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{}", uri);
+        }
+        let (status, _) = send(
+            app(state.clone(), Some("acme")),
+            Method::GET,
+            "/emergency/kill-switch",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
@@ -0,0 +1,210 @@
+//! TLS termination for the API server.
+//!
+//! Certificates come either from PEM files or from an ACME CA such as
+//! Let's Encrypt, answering TLS-ALPN-01 challenges on the API port itself.
+//! ACME certificates are cached on disk and renewed in the background.
+
+use anyhow::Context;
+use axum::Router;
+use axum_server::Handle;
+use axum_server::tls_rustls::RustlsConfig;
+use futures::StreamExt;
+use rustls_acme::AcmeConfig;
+use rustls_acme::caches::DirCache;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Time in-flight requests get to finish after a shutdown signal.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(30);
+
+/// Certificate source for TLS.
+#[derive(Debug, Clone)]
+pub enum TlsConfig {
+    /// PEM certificate chain and private key files.
+    Files {
+        /// Certificate chain path.
+        cert_path: PathBuf,
+        /// Private key path.
+        key_path: PathBuf,
+    },
+    /// Certificates obtained from an ACME CA.
+    Acme(AcmeSettings),
+}
+
+impl TlsConfig {
+    /// Creates a configuration serving certificate and key files.
+    pub fn files(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self::Files {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+        }
+    }
+}
+
+/// ACME certificate settings.
+#[derive(Debug, Clone)]
+pub struct AcmeSettings {
+    /// Domains to request a certificate for.
+    pub domains: Vec<String>,
+    /// Contact email addresses for the ACME account.
+    pub contacts: Vec<String>,
+    /// Directory caching the account key and certificates.
+    pub cache_dir: PathBuf,
+    /// Whether to use the production directory rather than staging.
+    pub production: bool,
+}
+
+impl AcmeSettings {
+    /// Creates settings for domains, using the Let's Encrypt staging
+    /// directory and caching under `data/acme`.
+    pub fn new(domains: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            domains: domains.into_iter().map(Into::into).collect(),
+            contacts: Vec::new(),
+            cache_dir: PathBuf::from("data/acme"),
+            production: false,
+        }
+    }
+
+    /// Adds a contact email address.
+    #[must_use]
+    pub fn with_contact(mut self, email: impl Into<String>) -> Self {
+        self.contacts.push(email.into());
+        self
+    }
+
+    /// Sets the cache directory.
+    #[must_use]
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = dir.into();
+        self
+    }
+
+    /// Sets whether to use the production directory.
+    #[must_use]
+    pub fn with_production(mut self, production: bool) -> Self {
+        self.production = production;
+        self
+    }
+}
+
+/// Selects the rustls crypto provider.
+///
+/// Both ring and aws-lc-rs may be linked, in which case rustls cannot pick
+/// one itself.
+fn install_crypto_provider() {
+    let _ = rustls::crypto::ring::default_provider().install_default();
+}
+
+/// Loads a certificate chain and private key from PEM files.
+pub async fn load_certificate(cert_path: &Path, key_path: &Path) -> anyhow::Result<RustlsConfig> {
+    install_crypto_provider();
+    RustlsConfig::from_pem_file(cert_path, key_path)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to load TLS certificate {} and key {}",
+                cert_path.display(),
+                key_path.display()
+            )
+        })
+}
+
+/// Serves a router over TLS until the shutdown signal completes.
+pub async fn serve_tls(
+    addr: SocketAddr,
+    router: Router,
+    config: &TlsConfig,
+    shutdown_signal: impl Future<Output = ()> + Send + 'static,
+) -> anyhow::Result<()> {
+    install_crypto_provider();
+
+    let handle = Handle::new();
+    let shutdown = handle.clone();
+    tokio::spawn(async move {
+        shutdown_signal.await;
+        shutdown.graceful_shutdown(Some(SHUTDOWN_GRACE));
+    });
+
+    let service = router.into_make_service();
+    match config {
+        TlsConfig::Files {
+            cert_path,
+            key_path,
+        } => {
+            let rustls = load_certificate(cert_path, key_path).await?;
+            info!(cert = %cert_path.display(), "Serving TLS with certificate files");
+            axum_server::bind_rustls(addr, rustls)
+                .handle(handle)
+                .serve(service)
+                .await?;
+        }
+        TlsConfig::Acme(acme) => {
+            anyhow::ensure!(
+                !acme.domains.is_empty(),
+                "ACME requires at least one domain"
+            );
+            let mut state = AcmeConfig::new(&acme.domains)
+                .contact(
+                    acme.contacts
+                        .iter()
+                        .map(|email| format!("mailto:{}", email)),
+                )
+                .cache(DirCache::new(acme.cache_dir.clone()))
+                .directory_lets_encrypt(acme.production)
+                .state();
+            let acceptor = state.axum_acceptor(state.default_rustls_config());
+            tokio::spawn(async move {
+                while let Some(event) = state.next().await {
+                    match event {
+                        Ok(event) => info!(event = ?event, "ACME event"),
+                        Err(e) => warn!(error = %e, "ACME error"),
+                    }
+                }
+            });
+
+            info!(
+                domains = ?acme.domains,
+                production = acme.production,
+                "Serving TLS with ACME certificates"
+            );
+            axum_server::bind(addr)
+                .acceptor(acceptor)
+                .handle(handle)
+                .serve(service)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_missing_certificate_fails() {
+        let result = load_certificate(
+            Path::new("/nonexistent/cert.pem"),
+            Path::new("/nonexistent/key.pem"),
+        )
+        .await;
+        let message = format!("{:#}", result.err().unwrap());
+        assert!(message.contains("/nonexistent/cert.pem"));
+    }
+
+    #[test]
+    fn test_acme_settings() {
+        let acme = AcmeSettings::new(["api.example.com"])
+            .with_contact("ops@example.com")
+            .with_production(true);
+        assert_eq!(acme.domains, vec!["api.example.com".to_string()]);
+        assert_eq!(acme.contacts, vec!["ops@example.com".to_string()]);
+        assert_eq!(acme.cache_dir, PathBuf::from("data/acme"));
+        assert!(acme.production);
+    }
+}
@@ -0,0 +1,208 @@
+//! API versions and deprecation headers.
+//!
+//! Each version is served under its own prefix (`/api/v1`, ...). Responses
+//! carry an `API-Version` header, and the version is added to the request
+//! extensions so a handler can keep serving the old response model to
+//! older versions after a breaking change ships in a newer one.
+//!
+//! Deprecated versions keep working but announce their retirement with
+//! `Deprecation` (RFC 9745), `Sunset` (RFC 8594) and a `Link` to the
+//! successor version.
+
+use axum::{
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+
+/// Response header naming the version that served the request.
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// A version of the REST API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ApiVersion {
+    /// Version 1.
+    V1,
+}
+
+impl ApiVersion {
+    /// All served versions, oldest first.
+    pub const ALL: &'static [ApiVersion] = &[ApiVersion::V1];
+
+    /// The newest version.
+    pub const LATEST: ApiVersion = ApiVersion::V1;
+
+    /// Returns the version name, e.g. `v1`.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V1 => "v1",
+        }
+    }
+
+    /// Returns the route prefix, e.g. `/api/v1`.
+    #[must_use]
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::V1 => "/api/v1",
+        }
+    }
+
+    /// Returns how this version is served.
+    #[must_use]
+    pub fn policy(self) -> VersionPolicy {
+        match self {
+            Self::V1 => VersionPolicy::current(self),
+        }
+    }
+}
+
+/// Deprecation of an API version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation {
+    /// When the version was deprecated.
+    pub deprecated_at: DateTime<Utc>,
+    /// When the version will be removed, if scheduled.
+    pub sunset: Option<DateTime<Utc>>,
+    /// Version clients should move to.
+    pub successor: ApiVersion,
+}
+
+/// How a version is served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionPolicy {
+    /// The version.
+    pub version: ApiVersion,
+    /// Deprecation, if the version is deprecated.
+    pub deprecation: Option<Deprecation>,
+}
+
+impl VersionPolicy {
+    /// Creates the policy of a supported, non-deprecated version.
+    #[must_use]
+    pub fn current(version: ApiVersion) -> Self {
+        Self {
+            version,
+            deprecation: None,
+        }
+    }
+
+    /// Marks the version as deprecated.
+    #[must_use]
+    pub fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = Some(deprecation);
+        self
+    }
+}
+
+/// Formats a time as an HTTP date.
+fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Version middleware.
+///
+/// Adds the version to the request extensions and the version and any
+/// deprecation headers to the response.
+pub async fn version_headers(
+    State(policy): State<VersionPolicy>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    // Nested routers see the path without the version prefix
+    let path = request.uri().path().to_string();
+    request.extensions_mut().insert(policy.version);
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(
+        API_VERSION_HEADER,
+        HeaderValue::from_static(policy.version.as_str()),
+    );
+
+    if let Some(deprecation) = policy.deprecation {
+        let values = [
+            (
+                "deprecation",
+                format!("@{}", deprecation.deprecated_at.timestamp()),
+            ),
+            (
+                "link",
+                format!(
+                    "<{}{}>; rel=\"successor-version\"",
+                    deprecation.successor.prefix(),
+                    path
+                ),
+            ),
+        ];
+        for (name, value) in values {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(name, value);
+            }
+        }
+        if let Some(value) = deprecation
+            .sunset
+            .and_then(|sunset| HeaderValue::from_str(&http_date(sunset)).ok())
+        {
+            headers.insert("sunset", value);
+        }
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use chrono::TimeZone;
+    use tower::ServiceExt;
+
+    fn router(policy: VersionPolicy) -> Router {
+        let versioned = Router::new()
+            .route(
+                "/positions",
+                get(|version: axum::Extension<ApiVersion>| async move { version.as_str() }),
+            )
+            .layer(middleware::from_fn_with_state(policy, version_headers));
+        Router::new().nest(policy.version.prefix(), versioned)
+    }
+
+    fn request() -> Request {
+        axum::http::Request::get("/api/v1/positions")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_current_version_headers() {
+        let response = router(ApiVersion::V1.policy())
+            .oneshot(request())
+            .await
+            .unwrap();
+        assert_eq!(response.headers().get(API_VERSION_HEADER).unwrap(), "v1");
+        assert!(response.headers().get("deprecation").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deprecation_headers() {
+        let policy = VersionPolicy::current(ApiVersion::V1).with_deprecation(Deprecation {
+            deprecated_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            sunset: Some(Utc.with_ymd_and_hms(2026, 7, 1, 0, 0, 0).unwrap()),
+            successor: ApiVersion::LATEST,
+        });
+        let response = router(policy).oneshot(request()).await.unwrap();
+        let headers = response.headers();
+        assert_eq!(headers.get("deprecation").unwrap(), "@1767225600");
+        assert_eq!(
+            headers.get("sunset").unwrap(),
+            "Wed, 01 Jul 2026 00:00:00 GMT"
+        );
+        assert_eq!(
+            headers.get("link").unwrap(),
+            "</api/v1/positions>; rel=\"successor-version\""
+        );
+    }
+}
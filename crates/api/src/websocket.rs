@@ -1,6 +1,25 @@
 //! WebSocket handlers for real-time updates.
+//!
+//! Clients subscribe to the topics they care about instead of receiving
+//! every update:
+//! - `positions` - updates for all positions
+//! - `positions:<address>` - updates for one position
+//! - `pools:<address>` - updates for positions in one pool
+//! - `strategies` / `strategies:<id>` - strategy lifecycle updates
+//! - `alerts` - all alerts
+//! - `alerts:<address>` - alerts for one position
+//! - `jobs` / `jobs:<id>` - background job status and progress
+//!
+//! Messages are JSON. Clients send `{"action":"subscribe","topic":"..."}`,
+//! `{"action":"unsubscribe","topic":"..."}` or `{"action":"ping"}`. Each
+//! subscription is answered with a snapshot of the topic's current state,
+//! and the server sends a heartbeat every 30 seconds.
 
-use crate::state::AppState;
+use crate::handlers::alerts::alert_response;
+use crate::handlers::positions::position_response;
+use crate::handlers::strategies::strategy_response;
+use crate::services::JobUpdate;
+use crate::state::{AlertUpdate, AppState, PositionUpdate, StrategyUpdate};
 use axum::{
     extract::{
         State,
@@ -9,108 +28,589 @@ use axum::{
     response::Response,
 };
 use futures::{SinkExt, StreamExt};
-use tracing::{debug, error, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
 
-/// WebSocket handler for position updates.
-pub async fn positions_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
-    ws.on_upgrade(|socket| handle_positions_ws(socket, state))
+/// Interval between server heartbeats.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum topics per connection.
+const MAX_TOPICS: usize = 100;
+
+/// Subscription topic.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Topic {
+    /// All positions.
+    Positions,
+    /// A single position.
+    Position(String),
+    /// Positions in a pool.
+    Pool(String),
+    /// All strategies.
+    Strategies,
+    /// A single strategy.
+    Strategy(String),
+    /// All alerts.
+    Alerts,
+    /// Alerts for a single position.
+    Alert(String),
+    /// All background jobs.
+    Jobs,
+    /// A single background job.
+    Job(String),
 }
 
-/// Handles position WebSocket connection.
-async fn handle_positions_ws(socket: WebSocket, state: AppState) {
-    let (mut sender, mut receiver) = socket.split();
+impl Topic {
+    /// Parses a topic such as `positions:<address>`.
+    #[must_use]
+    pub fn parse(topic: &str) -> Option<Self> {
+        match topic.split_once(':') {
+            None => match topic {
+                "positions" => Some(Self::Positions),
+                "strategies" => Some(Self::Strategies),
+                "alerts" => Some(Self::Alerts),
+                "jobs" => Some(Self::Jobs),
+                _ => None,
+            },
+            Some((_, "")) => None,
+            Some(("positions", address)) => Some(Self::Position(address.to_string())),
+            Some(("pools", address)) => Some(Self::Pool(address.to_string())),
+            Some(("strategies", id)) => Some(Self::Strategy(id.to_string())),
+            Some(("alerts", address)) => Some(Self::Alert(address.to_string())),
+            Some(("jobs", id)) => Some(Self::Job(id.to_string())),
+            Some(_) => None,
+        }
+    }
+}
 
-    // Subscribe to position updates
-    let mut rx = state.subscribe_positions();
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Positions => write!(f, "positions"),
+            Self::Position(address) => write!(f, "positions:{}", address),
+            Self::Pool(address) => write!(f, "pools:{}", address),
+            Self::Strategies => write!(f, "strategies"),
+            Self::Strategy(id) => write!(f, "strategies:{}", id),
+            Self::Alerts => write!(f, "alerts"),
+            Self::Alert(address) => write!(f, "alerts:{}", address),
+            Self::Jobs => write!(f, "jobs"),
+            Self::Job(id) => write!(f, "jobs:{}", id),
+        }
+    }
+}
 
-    info!("Position WebSocket client connected");
+/// Topics a client is subscribed to.
+#[derive(Debug, Clone, Default)]
+pub struct Subscriptions {
+    topics: HashSet<Topic>,
+}
 
-    // Spawn task to forward updates to client
-    let send_task = tokio::spawn(async move {
-        while let Ok(update) = rx.recv().await {
-            let msg = serde_json::to_string(&update).unwrap_or_default();
-            if sender.send(Message::Text(msg.into())).await.is_err() {
-                break;
-            }
+impl Subscriptions {
+    /// Adds a topic. Returns false if the topic limit is reached.
+    pub fn subscribe(&mut self, topic: Topic) -> bool {
+        if self.topics.len() >= MAX_TOPICS && !self.topics.contains(&topic) {
+            return false;
         }
-    });
-
-    // Handle incoming messages (ping/pong, close)
-    let recv_task = tokio::spawn(async move {
-        while let Some(msg) = receiver.next().await {
-            match msg {
-                Ok(Message::Ping(_data)) => {
-                    debug!("Received ping");
-                    // Pong is handled automatically by axum
-                }
-                Ok(Message::Close(_)) => {
-                    debug!("Client closed connection");
-                    break;
-                }
-                Err(e) => {
-                    error!(error = %e, "WebSocket error");
-                    break;
-                }
-                _ => {}
-            }
+        self.topics.insert(topic);
+        true
+    }
+
+    /// Removes a topic. Returns whether it was subscribed.
+    pub fn unsubscribe(&mut self, topic: &Topic) -> bool {
+        self.topics.remove(topic)
+    }
+
+    /// Checks whether a position update matches a subscribed topic.
+    #[must_use]
+    pub fn wants_position(&self, update: &PositionUpdate) -> bool {
+        self.topics.iter().any(|topic| match topic {
+            Topic::Positions => true,
+            Topic::Position(address) => *address == update.position_address,
+            Topic::Pool(address) => update.pool_address.as_ref() == Some(address),
+            _ => false,
+        })
+    }
+
+    /// Checks whether a strategy update matches a subscribed topic.
+    #[must_use]
+    pub fn wants_strategy(&self, update: &StrategyUpdate) -> bool {
+        self.topics.iter().any(|topic| match topic {
+            Topic::Strategies => true,
+            Topic::Strategy(id) => *id == update.strategy_id,
+            _ => false,
+        })
+    }
+
+    /// Checks whether a job update matches a subscribed topic.
+    #[must_use]
+    pub fn wants_job(&self, update: &JobUpdate) -> bool {
+        self.topics.iter().any(|topic| match topic {
+            Topic::Jobs => true,
+            Topic::Job(id) => *id == update.job_id,
+            _ => false,
+        })
+    }
+
+    /// Checks whether an alert matches a subscribed topic.
+    #[must_use]
+    pub fn wants_alert(&self, alert: &AlertUpdate) -> bool {
+        self.topics.iter().any(|topic| match topic {
+            Topic::Alerts => true,
+            Topic::Alert(address) => alert.position_address.as_ref() == Some(address),
+            _ => false,
+        })
+    }
+}
+
+/// Receives updates from the broadcast channels in `AppState`.
+pub struct UpdateStream {
+    positions: broadcast::Receiver<PositionUpdate>,
+    strategies: broadcast::Receiver<StrategyUpdate>,
+    alerts: broadcast::Receiver<AlertUpdate>,
+    jobs: broadcast::Receiver<JobUpdate>,
+}
+
+impl UpdateStream {
+    /// Subscribes to the position, strategy, alert, and job channels.
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            positions: state.subscribe_positions(),
+            strategies: state.subscribe_strategies(),
+            alerts: state.subscribe_alerts(),
+            jobs: state.jobs.subscribe(),
         }
-    });
+    }
 
-    // Wait for either task to complete
-    tokio::select! {
-        _ = send_task => {},
-        _ = recv_task => {},
+    /// Waits for the next update matching the subscriptions.
+    ///
+    /// Updates missed by a lagging client are skipped. Returns `None` once
+    /// the channels are closed.
+    pub async fn next(&mut self, subscriptions: &Subscriptions) -> Option<ServerMessage> {
+        loop {
+            let message = tokio::select! {
+                update = self.positions.recv() => match update {
+                    Ok(update) if subscriptions.wants_position(&update) => {
+                        ServerMessage::Position(update)
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Client lagging on position updates");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+                update = self.strategies.recv() => match update {
+                    Ok(update) if subscriptions.wants_strategy(&update) => {
+                        ServerMessage::Strategy(update)
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Client lagging on strategy updates");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+                alert = self.alerts.recv() => match alert {
+                    Ok(alert) if subscriptions.wants_alert(&alert) => ServerMessage::Alert(alert),
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Client lagging on alerts");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+                update = self.jobs.recv() => match update {
+                    Ok(update) if subscriptions.wants_job(&update) => ServerMessage::Job(update),
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Client lagging on job updates");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+            };
+            return Some(message);
+        }
     }
+}
 
-    info!("Position WebSocket client disconnected");
+/// Message sent by a client.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Subscribes to a topic.
+    Subscribe {
+        /// Topic to subscribe to.
+        topic: String,
+    },
+    /// Unsubscribes from a topic.
+    Unsubscribe {
+        /// Topic to unsubscribe from.
+        topic: String,
+    },
+    /// Requests a pong.
+    Ping,
 }
 
-/// WebSocket handler for alert updates.
-pub async fn alerts_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
-    ws.on_upgrade(|socket| handle_alerts_ws(socket, state))
+/// Message sent by the server.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Subscription confirmed.
+    Subscribed {
+        /// Subscribed topic.
+        topic: String,
+    },
+    /// Unsubscription confirmed.
+    Unsubscribed {
+        /// Unsubscribed topic.
+        topic: String,
+    },
+    /// Current state of a topic, sent on subscribe.
+    Snapshot {
+        /// Topic of the snapshot.
+        topic: String,
+        /// Snapshot data.
+        data: serde_json::Value,
+    },
+    /// Position update.
+    Position(PositionUpdate),
+    /// Strategy update.
+    Strategy(StrategyUpdate),
+    /// Alert.
+    Alert(AlertUpdate),
+    /// Background job status or progress.
+    Job(JobUpdate),
+    /// Periodic heartbeat.
+    Heartbeat {
+        /// Server time.
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Reply to a ping.
+    Pong {
+        /// Server time.
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Invalid client message.
+    Error {
+        /// Error message.
+        message: String,
+    },
 }
 
-/// Handles alerts WebSocket connection.
-async fn handle_alerts_ws(socket: WebSocket, state: AppState) {
-    let (mut sender, mut receiver) = socket.split();
+impl ServerMessage {
+    /// Returns the message type, as serialized in the `type` field.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Subscribed { .. } => "subscribed",
+            Self::Unsubscribed { .. } => "unsubscribed",
+            Self::Snapshot { .. } => "snapshot",
+            Self::Position(_) => "position",
+            Self::Strategy(_) => "strategy",
+            Self::Alert(_) => "alert",
+            Self::Job(_) => "job",
+            Self::Heartbeat { .. } => "heartbeat",
+            Self::Pong { .. } => "pong",
+            Self::Error { .. } => "error",
+        }
+    }
+}
 
-    // Subscribe to alert updates
-    let mut rx = state.subscribe_alerts();
+/// WebSocket handler for topic subscriptions.
+pub async fn subscribe_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    ws.on_upgrade(|socket| handle_socket(socket, state, Vec::new()))
+}
 
-    info!("Alerts WebSocket client connected");
+/// WebSocket handler subscribed to all position updates.
+pub async fn positions_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    ws.on_upgrade(|socket| handle_socket(socket, state, vec![Topic::Positions]))
+}
 
-    // Spawn task to forward alerts to client
-    let send_task = tokio::spawn(async move {
-        while let Ok(alert) = rx.recv().await {
-            let msg = serde_json::to_string(&alert).unwrap_or_default();
-            if sender.send(Message::Text(msg.into())).await.is_err() {
-                break;
+/// WebSocket handler subscribed to alerts.
+pub async fn alerts_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    ws.on_upgrade(|socket| handle_socket(socket, state, vec![Topic::Alerts]))
+}
+
+/// Builds the current state of a topic.
+pub(crate) async fn snapshot(state: &AppState, topic: &Topic) -> serde_json::Value {
+    match topic {
+        Topic::Positions | Topic::Position(_) | Topic::Pool(_) => {
+            let positions: Vec<_> = state
+                .monitor
+                .get_positions()
+                .await
+                .iter()
+                .filter(|p| match topic {
+                    Topic::Position(address) => p.address.to_string() == *address,
+                    Topic::Pool(address) => p.pool.to_string() == *address,
+                    _ => true,
+                })
+                .map(position_response)
+                .collect();
+            serde_json::to_value(positions).unwrap_or_default()
+        }
+        Topic::Strategies | Topic::Strategy(_) => {
+            let strategies: Vec<_> = state
+                .strategies
+                .read()
+                .await
+                .values()
+                .filter(|s| !matches!(topic, Topic::Strategy(id) if s.id != *id))
+                .map(strategy_response)
+                .collect();
+            serde_json::to_value(strategies).unwrap_or_default()
+        }
+        Topic::Alerts | Topic::Alert(_) => {
+            let alerts: Vec<_> = state
+                .alerts
+                .list(true)
+                .await
+                .into_iter()
+                .filter(|tracked| match topic {
+                    Topic::Alert(address) => tracked.alert.position.as_ref() == Some(address),
+                    _ => true,
+                })
+                .map(alert_response)
+                .collect();
+            serde_json::to_value(alerts).unwrap_or_default()
+        }
+        Topic::Jobs => {
+            let mut jobs = Vec::new();
+            for job in state.jobs.active().await {
+                let progress = state.jobs.progress(&job.id).await;
+                jobs.push(JobUpdate::new(&job, progress));
+            }
+            serde_json::to_value(jobs).unwrap_or_default()
+        }
+        Topic::Job(id) => match state.jobs.get(id).await {
+            Some(job) => {
+                let progress = state.jobs.progress(id).await;
+                serde_json::to_value(JobUpdate::new(&job, progress)).unwrap_or_default()
+            }
+            None => serde_json::Value::Null,
+        },
+    }
+}
+
+/// Handles a client message, returning the replies to send.
+async fn handle_client_message(
+    state: &AppState,
+    subscriptions: &mut Subscriptions,
+    text: &str,
+) -> Vec<ServerMessage> {
+    let message = match serde_json::from_str::<ClientMessage>(text) {
+        Ok(message) => message,
+        Err(e) => {
+            return vec![ServerMessage::Error {
+                message: format!("Invalid message: {}", e),
+            }];
+        }
+    };
+
+    match message {
+        ClientMessage::Subscribe { topic } => {
+            let Some(parsed) = Topic::parse(&topic) else {
+                return vec![ServerMessage::Error {
+                    message: format!("Unknown topic: {}", topic),
+                }];
+            };
+            if !subscriptions.subscribe(parsed.clone()) {
+                return vec![ServerMessage::Error {
+                    message: format!("Too many subscriptions (max {})", MAX_TOPICS),
+                }];
             }
+            let data = snapshot(state, &parsed).await;
+            vec![
+                ServerMessage::Subscribed {
+                    topic: parsed.to_string(),
+                },
+                ServerMessage::Snapshot {
+                    topic: parsed.to_string(),
+                    data,
+                },
+            ]
         }
-    });
+        ClientMessage::Unsubscribe { topic } => match Topic::parse(&topic) {
+            Some(parsed) if subscriptions.unsubscribe(&parsed) => {
+                vec![ServerMessage::Unsubscribed {
+                    topic: parsed.to_string(),
+                }]
+            }
+            _ => vec![ServerMessage::Error {
+                message: format!("Not subscribed to {}", topic),
+            }],
+        },
+        ClientMessage::Ping => vec![ServerMessage::Pong {
+            timestamp: chrono::Utc::now(),
+        }],
+    }
+}
+
+/// Handles a WebSocket connection with its initial topics.
+async fn handle_socket(socket: WebSocket, state: AppState, initial: Vec<Topic>) {
+    let (mut sender, mut receiver) = socket.split();
+
+    let mut updates = UpdateStream::new(&state);
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    heartbeat.tick().await;
+
+    let mut subscriptions = Subscriptions::default();
+    for topic in initial {
+        subscriptions.subscribe(topic);
+    }
 
-    // Handle incoming messages
-    let recv_task = tokio::spawn(async move {
-        while let Some(msg) = receiver.next().await {
-            match msg {
-                Ok(Message::Close(_)) => {
+    info!("WebSocket client connected");
+
+    loop {
+        let outgoing = tokio::select! {
+            msg = receiver.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    handle_client_message(&state, &mut subscriptions, text.as_str()).await
+                }
+                Some(Ok(Message::Close(_))) | None => {
                     debug!("Client closed connection");
                     break;
                 }
-                Err(e) => {
+                Some(Err(e)) => {
                     error!(error = %e, "WebSocket error");
                     break;
                 }
-                _ => {}
+                // Pong is handled automatically by axum
+                Some(Ok(_)) => continue,
+            },
+            message = updates.next(&subscriptions) => match message {
+                Some(message) => vec![message],
+                None => break,
+            },
+            _ = heartbeat.tick() => vec![ServerMessage::Heartbeat {
+                timestamp: chrono::Utc::now(),
+            }],
+        };
+
+        for message in outgoing {
+            let msg = serde_json::to_string(&message).unwrap_or_default();
+            if sender.send(Message::Text(msg.into())).await.is_err() {
+                info!("WebSocket client disconnected");
+                return;
             }
         }
-    });
+    }
+
+    info!("WebSocket client disconnected");
+}
 
-    // Wait for either task to complete
-    tokio::select! {
-        _ = send_task => {},
-        _ = recv_task => {},
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position_update(position: &str, pool: Option<&str>) -> PositionUpdate {
+        PositionUpdate {
+            update_type: "rebalance_simulated".to_string(),
+            position_address: position.to_string(),
+            pool_address: pool.map(str::to_string),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::Value::Null,
+        }
+    }
+
+    fn alert_update(position: Option<&str>) -> AlertUpdate {
+        AlertUpdate {
+            level: "warning".to_string(),
+            message: "Position out of range".to_string(),
+            timestamp: chrono::Utc::now(),
+            position_address: position.map(str::to_string),
+        }
     }
 
-    info!("Alerts WebSocket client disconnected");
+    #[test]
+    fn test_parse_topic() {
+        assert_eq!(Topic::parse("positions"), Some(Topic::Positions));
+        assert_eq!(
+            Topic::parse("positions:abc"),
+            Some(Topic::Position("abc".to_string()))
+        );
+        assert_eq!(
+            Topic::parse("pools:xyz"),
+            Some(Topic::Pool("xyz".to_string()))
+        );
+        assert_eq!(
+            Topic::parse("strategies:1"),
+            Some(Topic::Strategy("1".to_string()))
+        );
+        assert_eq!(Topic::parse("alerts"), Some(Topic::Alerts));
+        assert_eq!(
+            Topic::parse("alerts:abc"),
+            Some(Topic::Alert("abc".to_string()))
+        );
+        assert_eq!(Topic::parse("jobs:j1"), Some(Topic::Job("j1".to_string())));
+        assert_eq!(Topic::parse("positions:"), None);
+        assert_eq!(Topic::parse("pools"), None);
+        assert_eq!(Topic::parse("trades:abc"), None);
+
+        let topic = Topic::Pool("xyz".to_string());
+        assert_eq!(Topic::parse(&topic.to_string()), Some(topic));
+    }
+
+    #[test]
+    fn test_subscriptions_filter_updates() {
+        let mut subscriptions = Subscriptions::default();
+        assert!(!subscriptions.wants_position(&position_update("a", Some("p"))));
+        assert!(!subscriptions.wants_alert(&alert_update(Some("a"))));
+
+        subscriptions.subscribe(Topic::Position("a".to_string()));
+        subscriptions.subscribe(Topic::Pool("p".to_string()));
+        assert!(subscriptions.wants_position(&position_update("a", None)));
+        assert!(subscriptions.wants_position(&position_update("b", Some("p"))));
+        assert!(!subscriptions.wants_position(&position_update("b", Some("q"))));
+
+        assert!(subscriptions.unsubscribe(&Topic::Position("a".to_string())));
+        assert!(!subscriptions.unsubscribe(&Topic::Position("a".to_string())));
+        assert!(!subscriptions.wants_position(&position_update("a", None)));
+
+        let update = StrategyUpdate {
+            update_type: "started".to_string(),
+            strategy_id: "s1".to_string(),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::Value::Null,
+        };
+        assert!(!subscriptions.wants_strategy(&update));
+        subscriptions.subscribe(Topic::Strategy("s1".to_string()));
+        assert!(subscriptions.wants_strategy(&update));
+    }
+
+    #[test]
+    fn test_alert_subscriptions() {
+        let mut subscriptions = Subscriptions::default();
+        subscriptions.subscribe(Topic::parse("alerts:a").unwrap());
+        assert!(subscriptions.wants_alert(&alert_update(Some("a"))));
+        assert!(!subscriptions.wants_alert(&alert_update(Some("b"))));
+        assert!(!subscriptions.wants_alert(&alert_update(None)));
+
+        subscriptions.subscribe(Topic::Alerts);
+        assert!(subscriptions.wants_alert(&alert_update(None)));
+        assert!(subscriptions.unsubscribe(&Topic::Alerts));
+        assert!(!subscriptions.wants_alert(&alert_update(Some("b"))));
+    }
+
+    #[test]
+    fn test_message_format() {
+        let message: ClientMessage =
+            serde_json::from_str(r#"{"action":"subscribe","topic":"positions:abc"}"#).unwrap();
+        assert!(matches!(message, ClientMessage::Subscribe { topic } if topic == "positions:abc"));
+
+        let json =
+            serde_json::to_value(ServerMessage::Position(position_update("a", None))).unwrap();
+        assert_eq!(json["type"], "position");
+        assert_eq!(
+            json["type"],
+            ServerMessage::Position(position_update("a", None)).kind()
+        );
+        assert_eq!(json["position_address"], "a");
+    }
 }
@@ -201,6 +201,52 @@ fn generate_mock_report(args: &AnalyzeArgs) -> AnalysisReport {
     }
 }
 
+/// Where a price sits relative to a watched range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeStatus {
+    /// Below the lower bound.
+    Below,
+    /// Inside the range, close to the lower bound.
+    NearLower,
+    /// Comfortably inside the range.
+    Inside,
+    /// Inside the range, close to the upper bound.
+    NearUpper,
+    /// Above the upper bound.
+    Above,
+}
+
+/// Position of a price within a range, from 0% at the lower bound to 100%
+/// at the upper bound. Prices outside the range fall below 0% or above 100%.
+#[must_use]
+pub fn range_position_pct(price: f64, lower: f64, upper: f64) -> f64 {
+    let width = upper - lower;
+    if width <= 0.0 {
+        return 50.0;
+    }
+    (price - lower) / width * 100.0
+}
+
+/// Classifies a price against a range, treating prices within `margin_pct`
+/// of the range width from a bound as near that edge.
+#[must_use]
+pub fn edge_status(price: f64, lower: f64, upper: f64, margin_pct: f64) -> EdgeStatus {
+    if price < lower {
+        return EdgeStatus::Below;
+    }
+    if price > upper {
+        return EdgeStatus::Above;
+    }
+    let position = range_position_pct(price, lower, upper);
+    if position <= margin_pct {
+        EdgeStatus::NearLower
+    } else if position >= 100.0 - margin_pct {
+        EdgeStatus::NearUpper
+    } else {
+        EdgeStatus::Inside
+    }
+}
+
 /// Prints the report in CSV format.
 fn print_csv_report(report: &AnalysisReport) {
     println!("metric,value");
@@ -218,3 +264,23 @@ fn print_csv_report(report: &AnalysisReport) {
     println!("estimated_time_in_range,{}", report.estimated_time_in_range);
     println!("data_points,{}", report.data_points);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_status() {
+        assert_eq!(edge_status(85.0, 90.0, 110.0, 10.0), EdgeStatus::Below);
+        assert_eq!(edge_status(91.0, 90.0, 110.0, 10.0), EdgeStatus::NearLower);
+        assert_eq!(edge_status(100.0, 90.0, 110.0, 10.0), EdgeStatus::Inside);
+        assert_eq!(edge_status(109.0, 90.0, 110.0, 10.0), EdgeStatus::NearUpper);
+        assert_eq!(edge_status(111.0, 90.0, 110.0, 10.0), EdgeStatus::Above);
+    }
+
+    #[test]
+    fn test_range_position_pct() {
+        assert_eq!(range_position_pct(95.0, 90.0, 110.0), 25.0);
+        assert_eq!(range_position_pct(100.0, 100.0, 100.0), 50.0);
+    }
+}
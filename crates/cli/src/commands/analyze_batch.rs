@@ -0,0 +1,426 @@
+//! Batch analysis command implementation.
+//!
+//! Analyzes every pair of a watchlist concurrently and ranks them by
+//! estimated fee APR, volatility, suggested range width or volume, as a
+//! starting point for portfolio construction.
+//!
+//! A watchlist lists one pair per line as `SYMBOL MINT [FEE_BPS [TVL_USD]]`,
+//! separated by whitespace or commas, quoted in USDC. Blank lines and `#`
+//! comments are ignored.
+
+use crate::output;
+use crate::status;
+use anyhow::{Context, Result, anyhow, bail};
+use clmm_lp_data::prelude::*;
+use clmm_lp_domain::prelude::*;
+use prettytable::{Table, row};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Arguments for the analyze-batch command.
+#[derive(Debug, Clone)]
+pub struct AnalyzeBatchArgs {
+    /// Watchlist file.
+    pub watchlist: PathBuf,
+    /// Number of days to analyze.
+    pub days: u64,
+    /// Fee tier in basis points for pairs that do not set one.
+    pub fee_bps: u32,
+    /// Maximum number of pairs fetched at once.
+    pub concurrency: usize,
+    /// Ranking column.
+    pub sort: RankBy,
+    /// Only show the best N pairs.
+    pub top: Option<usize>,
+    /// Birdeye API key.
+    pub api_key: Option<String>,
+}
+
+/// Column the batch summary is ranked by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum RankBy {
+    /// Highest estimated fee APR first; pairs without a TVL sort last.
+    #[default]
+    FeeApr,
+    /// Lowest volatility first.
+    Volatility,
+    /// Narrowest suggested range first.
+    Width,
+    /// Highest daily volume first.
+    Volume,
+}
+
+/// A pair of the watchlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchlistEntry {
+    /// Token A symbol.
+    pub symbol: String,
+    /// Token A mint address.
+    pub mint: String,
+    /// Fee tier in basis points, if set.
+    pub fee_bps: Option<u32>,
+    /// Pool TVL in USD, if set.
+    pub tvl_usd: Option<f64>,
+}
+
+/// Parses a watchlist.
+///
+/// # Errors
+/// Returns an error naming the line of the first malformed entry.
+pub fn parse_watchlist(contents: &str) -> Result<Vec<WatchlistEntry>> {
+    let mut entries = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|f| !f.is_empty())
+            .collect();
+        let context = || format!("watchlist line {}", index + 1);
+        if !(2..=4).contains(&fields.len()) {
+            bail!("{}: expected SYMBOL MINT [FEE_BPS [TVL_USD]]", context());
+        }
+        let fee_bps = fields
+            .get(2)
+            .map(|f| f.parse::<u32>())
+            .transpose()
+            .with_context(|| format!("{}: invalid fee tier", context()))?;
+        let tvl_usd = fields
+            .get(3)
+            .map(|f| f.parse::<f64>())
+            .transpose()
+            .with_context(|| format!("{}: invalid TVL", context()))?;
+        entries.push(WatchlistEntry {
+            symbol: fields[0].to_uppercase(),
+            mint: fields[1].to_string(),
+            fee_bps,
+            tvl_usd,
+        });
+    }
+    Ok(entries)
+}
+
+/// Summary metrics of one analyzed pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct PairAnalysis {
+    /// Pair label.
+    pub pair: String,
+    /// Token A mint address.
+    pub mint: String,
+    /// Latest close price.
+    pub current_price: f64,
+    /// Annualized volatility of hourly log returns.
+    pub volatility_annual: f64,
+    /// Daily volatility.
+    pub volatility_daily: f64,
+    /// Suggested range half-width (2σ daily) as a fraction of the price.
+    pub range_width: f64,
+    /// Suggested lower bound.
+    pub recommended_lower: f64,
+    /// Suggested upper bound.
+    pub recommended_upper: f64,
+    /// Share of the period the price spent in the suggested range.
+    pub time_in_range: f64,
+    /// Average daily volume in USD.
+    pub daily_volume_usd: f64,
+    /// Fee tier in basis points.
+    pub fee_bps: u32,
+    /// Pool TVL in USD, if known.
+    pub tvl_usd: Option<f64>,
+    /// Estimated fee APR of a position in the suggested range, if the TVL
+    /// is known.
+    pub fee_apr: Option<f64>,
+    /// Number of hourly candles analyzed.
+    pub data_points: usize,
+}
+
+/// Computes the summary metrics of a pair from hourly closes and USD volumes.
+///
+/// The fee APR estimate is the pool's fee yield (daily volume times the fee
+/// tier over TVL, annualized) scaled by the time the price spent in the
+/// suggested range.
+#[must_use]
+pub fn analyze_pair(
+    entry: &WatchlistEntry,
+    fee_bps: u32,
+    closes: &[f64],
+    volumes_usd: &[f64],
+) -> PairAnalysis {
+    let current_price = closes.last().copied().unwrap_or_default();
+
+    let returns: Vec<f64> = closes
+        .windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect();
+    let volatility_annual = if returns.is_empty() {
+        0.0
+    } else {
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance =
+            returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64;
+        variance.sqrt() * 8760.0_f64.sqrt()
+    };
+    let volatility_daily = volatility_annual / 365.0_f64.sqrt();
+
+    let range_width = volatility_daily * 2.0;
+    let recommended_lower = current_price * (1.0 - range_width);
+    let recommended_upper = current_price * (1.0 + range_width);
+    let in_range = closes
+        .iter()
+        .filter(|p| **p >= recommended_lower && **p <= recommended_upper)
+        .count();
+    let time_in_range = in_range as f64 / closes.len().max(1) as f64;
+
+    let daily_volume_usd = volumes_usd.iter().sum::<f64>() * 24.0 / volumes_usd.len().max(1) as f64;
+    let fee_bps = entry.fee_bps.unwrap_or(fee_bps);
+    let fee_apr = entry
+        .tvl_usd
+        .filter(|tvl| *tvl > 0.0)
+        .map(|tvl| daily_volume_usd * f64::from(fee_bps) / 10_000.0 * 365.0 / tvl * time_in_range);
+
+    PairAnalysis {
+        pair: format!("{}/USDC", entry.symbol),
+        mint: entry.mint.clone(),
+        current_price,
+        volatility_annual,
+        volatility_daily,
+        range_width,
+        recommended_lower,
+        recommended_upper,
+        time_in_range,
+        daily_volume_usd,
+        fee_bps,
+        tvl_usd: entry.tvl_usd,
+        fee_apr,
+        data_points: closes.len(),
+    }
+}
+
+/// Sorts analyses by a ranking column, best first.
+pub fn rank(analyses: &mut [PairAnalysis], by: RankBy) {
+    analyses.sort_by(|a, b| match by {
+        RankBy::FeeApr => match (a.fee_apr, b.fee_apr) {
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => b.daily_volume_usd.total_cmp(&a.daily_volume_usd),
+        },
+        RankBy::Volatility => a.volatility_annual.total_cmp(&b.volatility_annual),
+        RankBy::Width => a.range_width.total_cmp(&b.range_width),
+        RankBy::Volume => b.daily_volume_usd.total_cmp(&a.daily_volume_usd),
+    });
+}
+
+/// Runs the analyze-batch command.
+pub async fn run_analyze_batch(args: AnalyzeBatchArgs) -> Result<()> {
+    let api_key = args
+        .api_key
+        .clone()
+        .context("BIRDEYE_API_KEY must be set in .env, environment or config file")?;
+    let contents = std::fs::read_to_string(&args.watchlist)
+        .with_context(|| format!("Failed to read {}", args.watchlist.display()))?;
+    let entries = parse_watchlist(&contents)?;
+    if entries.is_empty() {
+        bail!("{} lists no pairs", args.watchlist.display());
+    }
+
+    status!(
+        "📊 Analyzing {} pairs over {} days ({} at a time)...",
+        entries.len(),
+        args.days,
+        args.concurrency.max(1)
+    );
+
+    let provider = Arc::new(BirdeyeProvider::new(api_key));
+    let permits = Arc::new(Semaphore::new(args.concurrency.max(1)));
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let start_time = now - (args.days * 24 * 3600);
+
+    let mut tasks = JoinSet::new();
+    for entry in entries {
+        let provider = Arc::clone(&provider);
+        let permits = Arc::clone(&permits);
+        let fee_bps = args.fee_bps;
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await?;
+            let token_a = Token::new(&entry.mint, &entry.symbol, 9, &entry.symbol);
+            let token_b = Token::new(
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "USDC",
+                6,
+                "USD Coin",
+            );
+            let result = provider
+                .get_price_history(&token_a, &token_b, start_time, now, 3600)
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|candles| {
+                    if candles.is_empty() {
+                        return Err(anyhow!("no data for the period"));
+                    }
+                    let closes: Vec<f64> = candles
+                        .iter()
+                        .filter_map(|c| c.close.value.to_f64())
+                        .collect();
+                    let volumes: Vec<f64> = candles
+                        .iter()
+                        .map(|c| {
+                            let volume = c.volume_token_a.to_decimal() * c.close.value;
+                            volume.to_f64().unwrap_or_default()
+                        })
+                        .collect();
+                    Ok(analyze_pair(&entry, fee_bps, &closes, &volumes))
+                });
+            anyhow::Ok((entry.symbol, result))
+        });
+    }
+
+    let mut analyses = Vec::new();
+    let mut failures = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined?? {
+            (symbol, Ok(analysis)) => {
+                status!("   ✅ {}", symbol);
+                analyses.push(analysis);
+            }
+            (symbol, Err(e)) => {
+                status!("   ❌ {}: {}", symbol, e);
+                failures.push(serde_json::json!({ "symbol": symbol, "error": e.to_string() }));
+            }
+        }
+    }
+
+    rank(&mut analyses, args.sort);
+    if let Some(top) = args.top {
+        analyses.truncate(top);
+    }
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "days": args.days,
+            "pairs": analyses,
+            "failed": failures,
+        }));
+    }
+
+    println!();
+    println!("🎯 BATCH ANALYSIS: {} days", args.days);
+    println!();
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "Pair",
+        "Price",
+        "Volatility",
+        "Range Width",
+        "Suggested Range",
+        "In Range",
+        "Daily Volume",
+        "Fee",
+        "Fee APR (est.)"
+    ]);
+    for (position, pair) in analyses.iter().enumerate() {
+        table.add_row(row![
+            position + 1,
+            pair.pair,
+            format!("${:.4}", pair.current_price),
+            format!("{:.1}%", pair.volatility_annual * 100.0),
+            format!("±{:.2}%", pair.range_width * 100.0),
+            format!(
+                "${:.4} - ${:.4}",
+                pair.recommended_lower, pair.recommended_upper
+            ),
+            format!("{:.1}%", pair.time_in_range * 100.0),
+            format!("${:.0}", pair.daily_volume_usd),
+            format!("{:.2}%", f64::from(pair.fee_bps) / 100.0),
+            pair.fee_apr
+                .map_or_else(|| "n/a".to_string(), |apr| format!("{:.1}%", apr * 100.0))
+        ]);
+    }
+    table.printstd();
+
+    if analyses.iter().any(|pair| pair.fee_apr.is_none()) {
+        println!();
+        println!("💡 Add a TVL_USD column to the watchlist to estimate the fee APR of a pair.");
+    }
+    if !failures.is_empty() {
+        println!();
+        println!("⚠️  {} pairs could not be analyzed", failures.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(tvl_usd: Option<f64>) -> WatchlistEntry {
+        WatchlistEntry {
+            symbol: "SOL".to_string(),
+            mint: "So11111111111111111111111111111111111111112".to_string(),
+            fee_bps: None,
+            tvl_usd,
+        }
+    }
+
+    #[test]
+    fn test_parse_watchlist() {
+        let entries = parse_watchlist(
+            "# pairs\nsol So11111111111111111111111111111111111111112\n\n\
+             JUP,JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN,30,2500000 # main pool\n",
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].symbol, "SOL");
+        assert_eq!(entries[0].fee_bps, None);
+        assert_eq!(entries[1].fee_bps, Some(30));
+        assert_eq!(entries[1].tvl_usd, Some(2_500_000.0));
+
+        let error = parse_watchlist("SOL\n").unwrap_err();
+        assert!(error.to_string().contains("line 1"));
+        assert!(parse_watchlist("SOL mint abc\n").is_err());
+    }
+
+    #[test]
+    fn test_analyze_pair() {
+        let closes: Vec<f64> = (0..48).map(|i| 100.0 + f64::from(i % 3)).collect();
+        let volumes = vec![1000.0; 48];
+        let analysis = analyze_pair(&entry(Some(876_000.0)), 30, &closes, &volumes);
+
+        assert_eq!(analysis.pair, "SOL/USDC");
+        assert_eq!(analysis.data_points, 48);
+        assert!((analysis.daily_volume_usd - 24_000.0).abs() < 1e-9);
+        assert!(analysis.volatility_annual > 0.0);
+        // 24k daily volume x 0.3% x 365 / 876k TVL = 3%, scaled by time in range
+        let apr = analysis.fee_apr.unwrap();
+        assert!((apr - 0.03 * analysis.time_in_range).abs() < 1e-9);
+
+        assert!(
+            analyze_pair(&entry(None), 30, &closes, &volumes)
+                .fee_apr
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_rank_puts_pairs_without_apr_last() {
+        let closes = [100.0, 101.0, 99.0, 100.0];
+        let mut analyses = vec![
+            analyze_pair(&entry(None), 30, &closes, &[1.0; 4]),
+            analyze_pair(&entry(Some(1_000.0)), 30, &closes, &[1.0; 4]),
+            analyze_pair(&entry(Some(100.0)), 30, &closes, &[1.0; 4]),
+        ];
+        rank(&mut analyses, RankBy::FeeApr);
+        assert_eq!(analyses[0].tvl_usd, Some(100.0));
+        assert_eq!(analyses[2].tvl_usd, None);
+    }
+}
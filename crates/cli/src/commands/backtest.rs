@@ -12,6 +12,7 @@ use clmm_lp_domain::value_objects::price_range::PriceRange;
 use clmm_lp_simulation::prelude::*;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
+use serde::Serialize;
 use tracing::info;
 
 /// Arguments for the backtest command.
@@ -46,7 +47,8 @@ pub struct BacktestArgs {
 }
 
 /// Strategy type for backtesting.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StrategyType {
     /// No rebalancing.
     #[default]
@@ -0,0 +1,176 @@
+//! Collect-fees command implementation.
+//!
+//! Collects the fees and rewards of one Orca Whirlpool position, or of every
+//! position of a wallet. Without `--execute` only the claimable amounts are
+//! previewed.
+
+use super::position::{plan_json, result_json};
+use super::wallet::load_wallet;
+use crate::output::{self, confirm};
+use crate::status;
+use anyhow::{Context, Result};
+use clmm_lp_execution::prelude::*;
+use clmm_lp_protocols::prelude::*;
+use prettytable::{Table, row};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Arguments for the collect-fees command.
+#[derive(Debug, Clone)]
+pub struct CollectArgs {
+    /// Position to collect from.
+    pub position: Option<String>,
+    /// Wallet whose positions are all collected.
+    pub wallet: Option<String>,
+    /// Solana RPC endpoint.
+    pub rpc_url: String,
+    /// Keypair or keystore file signing the transactions.
+    pub keypair: Option<PathBuf>,
+    /// Send the transactions instead of only previewing them.
+    pub execute: bool,
+    /// Skip the confirmation prompt.
+    pub yes: bool,
+}
+
+/// Formats the rewards of a plan.
+fn format_rewards(plan: &OperationPlan) -> String {
+    if plan.rewards.is_empty() {
+        return "-".to_string();
+    }
+    plan.rewards
+        .iter()
+        .map(|r| format!("#{} {} ({})", r.index, r.amount_owed, r.mint))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prints the claimable amounts of each plan.
+fn print_claimable(plans: &[OperationPlan]) {
+    let mut table = Table::new();
+    table.add_row(row!["Position", "Pool", "Fees A", "Fees B", "Rewards"]);
+    for plan in plans {
+        table.add_row(row![
+            plan.position.map(|p| p.to_string()).unwrap_or_default(),
+            plan.pool,
+            plan.fees_owed.0,
+            plan.fees_owed.1,
+            format_rewards(plan),
+        ]);
+    }
+    table.printstd();
+    println!(
+        "Amounts are in base units, as last recorded on-chain; fees earned since the last \
+         position update are added when collecting."
+    );
+}
+
+/// Runs the collect-fees command.
+pub async fn run_collect_fees(args: CollectArgs) -> Result<()> {
+    let provider = Arc::new(RpcProvider::new(RpcConfig::new(args.rpc_url.clone())));
+    let operator = PositionOperator::new(provider);
+
+    let wallet = args
+        .keypair
+        .as_ref()
+        .map(|path| load_wallet(path, None))
+        .transpose()?;
+
+    let plans = match (&args.position, &args.wallet) {
+        (Some(position), _) => vec![operator.plan_collect(position).await?],
+        (None, Some(owner)) => operator.plan_collect_all(owner).await?,
+        (None, None) => {
+            let owner = wallet
+                .as_ref()
+                .map(|w| w.pubkey().to_string())
+                .context("Pass a position, --wallet or --keypair")?;
+            operator.plan_collect_all(&owner).await?
+        }
+    };
+
+    let dry_run_json = || {
+        let plans: Vec<_> = plans
+            .iter()
+            .map(|plan| serde_json::json!({ "plan": plan_json(plan), "executed": false }))
+            .collect();
+        output::print_json(&plans)
+    };
+
+    if plans.is_empty() {
+        if output::is_json() {
+            return dry_run_json();
+        }
+        println!("No positions with fees or rewards to collect");
+        return Ok(());
+    }
+
+    if !output::is_json() {
+        print_claimable(&plans);
+    }
+
+    if !args.execute {
+        if output::is_json() {
+            return dry_run_json();
+        }
+        println!("Dry run: nothing was sent. Re-run with --execute to collect.");
+        return Ok(());
+    }
+
+    let wallet = wallet.context("--keypair (or CLMM_KEYPAIR) is required with --execute")?;
+    status!("Signing with {}", wallet.pubkey());
+
+    if !args.yes && !confirm(&format!("Collect from {} positions?", plans.len()))? {
+        status!("Aborted.");
+        return if output::is_json() {
+            dry_run_json()
+        } else {
+            Ok(())
+        };
+    }
+
+    let mut failed = 0;
+    let mut collected = Vec::with_capacity(plans.len());
+    for plan in &plans {
+        let position = plan.position.map(|p| p.to_string()).unwrap_or_default();
+        match operator.execute(plan, &wallet).await {
+            Ok(results) => {
+                for result in &results {
+                    match &result.error {
+                        None => status!(
+                            "✅ {}: collected {} A, {} B ({})",
+                            position,
+                            plan.fees_owed.0,
+                            plan.fees_owed.1,
+                            result.signature
+                        ),
+                        Some(error) => {
+                            failed += 1;
+                            status!("❌ {}: {} ({})", position, error, result.signature);
+                        }
+                    }
+                }
+                collected.push(serde_json::json!({
+                    "plan": plan_json(plan),
+                    "executed": true,
+                    "results": results.iter().map(result_json).collect::<Vec<_>>(),
+                }));
+            }
+            Err(e) => {
+                failed += 1;
+                status!("❌ {}: {:#}", position, e);
+                collected.push(serde_json::json!({
+                    "plan": plan_json(plan),
+                    "executed": false,
+                    "error": format!("{:#}", e),
+                }));
+            }
+        }
+    }
+    if output::is_json() {
+        output::print_json(&collected)?;
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} collections failed", failed, plans.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,564 @@
+//! Compare command implementation.
+//!
+//! Loads saved simulations or reruns inline configurations and prints
+//! their metrics side by side together with a relative ranking.
+
+use super::backtest::StrategyType;
+use super::sweep::{SweepCase, simulate_case};
+use crate::output;
+use crate::status;
+use anyhow::{Context, Result, anyhow, bail};
+use clmm_lp_data::prelude::*;
+use clmm_lp_domain::prelude::*;
+use clmm_lp_simulation::prelude::TrackerSummary;
+use prettytable::{Cell, Row, Table, row};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Arguments for the compare command.
+#[derive(Debug, Clone)]
+pub struct CompareArgs {
+    /// Saved simulation IDs (or ID prefixes) and inline run specs.
+    pub runs: Vec<String>,
+    /// Token A symbol used for reruns.
+    pub symbol_a: String,
+    /// Token A mint address used for reruns.
+    pub mint_a: String,
+    /// Days of history used for reruns.
+    pub days: u64,
+    /// Initial capital in USD for inline runs.
+    pub capital: Decimal,
+    /// Transaction cost per rebalance in USD for inline runs.
+    pub tx_cost: Decimal,
+    /// Rerun saved simulations instead of loading their stored results.
+    pub rerun: bool,
+    /// Database URL holding saved simulations.
+    pub database_url: String,
+    /// Birdeye API key.
+    pub api_key: Option<String>,
+}
+
+/// A run requested on the command line.
+#[derive(Debug, Clone)]
+pub enum RunSpec {
+    /// A saved simulation, by full ID or ID prefix.
+    Saved(String),
+    /// An inline configuration to simulate.
+    Inline(SweepCase),
+}
+
+/// Parses a run spec.
+///
+/// Inline runs are written `LOWER:UPPER[@STRATEGY[:PARAM]]`, where the
+/// parameter is the rebalance interval in hours for `periodic` and the
+/// threshold for `threshold` and `il-limit`. Anything else is treated as
+/// a saved simulation ID.
+pub fn parse_run_spec(spec: &str) -> Result<RunSpec> {
+    let spec = spec.trim();
+    if !spec.contains(':') {
+        if spec.is_empty() || !spec.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            bail!(
+                "'{}' is neither a simulation ID nor LOWER:UPPER[@STRATEGY[:PARAM]]",
+                spec
+            );
+        }
+        return Ok(RunSpec::Saved(spec.to_string()));
+    }
+
+    let (range, strategy) = spec.split_once('@').unwrap_or((spec, "static"));
+    let (lower, upper) = range
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected LOWER:UPPER in '{}'", spec))?;
+    let lower: Decimal = lower.trim().parse().context("invalid lower price")?;
+    let upper: Decimal = upper.trim().parse().context("invalid upper price")?;
+    if lower <= Decimal::ZERO || lower >= upper {
+        bail!(
+            "lower price must be positive and below upper price in '{}'",
+            spec
+        );
+    }
+
+    let (name, param) = match strategy.split_once(':') {
+        Some((name, param)) => (name, Some(param.trim())),
+        None => (strategy, None),
+    };
+    let strategy = strategy_type(name).ok_or_else(|| anyhow!("unknown strategy '{}'", name))?;
+
+    let mut case = SweepCase {
+        lower,
+        upper,
+        strategy,
+        interval: None,
+        threshold: None,
+    };
+    match (strategy, param) {
+        (StrategyType::Static, Some(_)) => bail!("the static strategy takes no parameter"),
+        (StrategyType::Static, None) => {}
+        (StrategyType::Periodic, param) => {
+            case.interval = Some(
+                param
+                    .map_or(Ok(24), str::parse)
+                    .context("invalid interval")?,
+            );
+        }
+        (StrategyType::Threshold | StrategyType::ILLimit, param) => {
+            case.threshold = Some(
+                param
+                    .map_or(Ok(Decimal::new(5, 2)), str::parse)
+                    .context("invalid threshold")?,
+            );
+        }
+    }
+    Ok(RunSpec::Inline(case))
+}
+
+/// Maps a strategy name to its type.
+fn strategy_type(name: &str) -> Option<StrategyType> {
+    match name.trim().to_lowercase().replace('_', "-").as_str() {
+        "static" => Some(StrategyType::Static),
+        "periodic" => Some(StrategyType::Periodic),
+        "threshold" => Some(StrategyType::Threshold),
+        "il-limit" | "illimit" => Some(StrategyType::ILLimit),
+        _ => None,
+    }
+}
+
+/// Rebuilds the configuration of a saved simulation.
+#[cfg(feature = "postgres")]
+fn saved_case(record: &SimulationRecord) -> Result<SweepCase> {
+    let strategy = strategy_type(&record.strategy_type)
+        .ok_or_else(|| anyhow!("unsupported strategy '{}'", record.strategy_type))?;
+    let config = &record.strategy_config;
+    let decimal = |key: &str| {
+        config
+            .get(key)
+            .and_then(|v| {
+                v.as_str()
+                    .map(str::to_string)
+                    .or_else(|| Some(v.to_string()))
+            })
+            .and_then(|v| v.parse::<Decimal>().ok())
+    };
+
+    Ok(SweepCase {
+        lower: record.lower_price,
+        upper: record.upper_price,
+        strategy,
+        interval: matches!(strategy, StrategyType::Periodic).then(|| {
+            config
+                .get("rebalance_interval")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(24)
+        }),
+        threshold: matches!(strategy, StrategyType::Threshold | StrategyType::ILLimit).then(|| {
+            decimal("threshold_pct")
+                .or_else(|| decimal("max_il_pct"))
+                .unwrap_or(Decimal::new(5, 2))
+        }),
+    })
+}
+
+/// Metrics of one compared run, in the units of the position tracker.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparedRun {
+    /// Column label.
+    pub label: String,
+    /// Where the metrics come from.
+    pub source: &'static str,
+    /// Strategy description.
+    pub strategy: String,
+    /// Lower price bound.
+    pub lower: Decimal,
+    /// Upper price bound.
+    pub upper: Decimal,
+    /// Initial capital.
+    pub capital: Decimal,
+    /// Final position value.
+    pub final_value: Decimal,
+    /// Net PnL.
+    pub pnl: Decimal,
+    /// Fees earned.
+    pub fees: Decimal,
+    /// Final impermanent loss as a fraction.
+    pub il_pct: Decimal,
+    /// Time in range as a fraction.
+    pub time_in_range: Decimal,
+    /// Maximum drawdown as a fraction.
+    pub max_drawdown: Decimal,
+    /// Number of rebalances.
+    pub rebalances: u32,
+    /// Total rebalance cost.
+    pub rebalance_cost: Decimal,
+    /// Difference vs HODL.
+    pub vs_hodl: Decimal,
+}
+
+impl ComparedRun {
+    fn from_summary(
+        label: String,
+        source: &'static str,
+        case: &SweepCase,
+        capital: Decimal,
+        summary: &TrackerSummary,
+    ) -> Self {
+        Self {
+            label,
+            source,
+            strategy: describe(case),
+            lower: case.lower,
+            upper: case.upper,
+            capital,
+            final_value: summary.final_value,
+            pnl: summary.final_pnl,
+            fees: summary.total_fees,
+            il_pct: summary.final_il_pct,
+            time_in_range: summary.time_in_range_pct,
+            max_drawdown: summary.max_drawdown,
+            rebalances: summary.rebalance_count,
+            rebalance_cost: summary.total_rebalance_cost,
+            vs_hodl: summary.vs_hodl,
+        }
+    }
+
+    /// Net return as a percentage of capital.
+    #[must_use]
+    pub fn return_pct(&self) -> Decimal {
+        if self.capital.is_zero() {
+            Decimal::ZERO
+        } else {
+            self.pnl / self.capital * Decimal::from(100)
+        }
+    }
+}
+
+fn describe(case: &SweepCase) -> String {
+    match (case.interval, case.threshold) {
+        (Some(hours), _) => format!("{:?} ({}h)", case.strategy, hours),
+        (_, Some(threshold)) => format!("{:?} ({})", case.strategy, threshold.normalize()),
+        _ => format!("{:?}", case.strategy),
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn from_record(record: &SimulationRecord, result: &SimulationResultRecord) -> ComparedRun {
+    let strategy = saved_case(record)
+        .map(|case| describe(&case))
+        .unwrap_or_else(|_| record.strategy_type.clone());
+    ComparedRun {
+        label: record.id.to_string()[..8].to_string(),
+        source: "saved",
+        strategy,
+        lower: record.lower_price,
+        upper: record.upper_price,
+        capital: record.initial_capital,
+        final_value: result.final_value,
+        pnl: result.final_pnl,
+        fees: result.total_fees,
+        il_pct: result.final_il_pct,
+        time_in_range: result.time_in_range_pct,
+        max_drawdown: result.max_drawdown,
+        rebalances: u32::try_from(result.rebalance_count).unwrap_or_default(),
+        rebalance_cost: result.total_rebalance_cost,
+        vs_hodl: result.vs_hodl,
+    }
+}
+
+/// Ranks runs by their average position across PnL, vs HODL, drawdown
+/// and time in range.
+///
+/// Returns `(index, score)` pairs, best first; lower scores are better.
+#[must_use]
+pub fn rank_runs(runs: &[ComparedRun]) -> Vec<(usize, Decimal)> {
+    let criteria: [fn(&ComparedRun) -> Decimal; 4] = [
+        |r| -r.pnl,
+        |r| -r.vs_hodl,
+        |r| r.max_drawdown,
+        |r| -r.time_in_range,
+    ];
+
+    let mut scores = vec![Decimal::ZERO; runs.len()];
+    for key in criteria {
+        for (i, run) in runs.iter().enumerate() {
+            // Ties share the best position.
+            let position = runs.iter().filter(|other| key(other) < key(run)).count() + 1;
+            scores[i] += Decimal::from(position);
+        }
+    }
+
+    let mut ranking: Vec<(usize, Decimal)> = scores
+        .into_iter()
+        .map(|score| score / Decimal::from(criteria.len()))
+        .enumerate()
+        .collect();
+    ranking.sort_by(|a, b| {
+        a.1.cmp(&b.1)
+            .then_with(|| runs[b.0].pnl.cmp(&runs[a.0].pnl))
+    });
+    ranking
+}
+
+/// Runs the compare command.
+pub async fn run_compare(args: CompareArgs) -> Result<()> {
+    if args.runs.len() < 2 {
+        bail!("at least two runs are required for a comparison");
+    }
+    let specs = args
+        .runs
+        .iter()
+        .map(|spec| parse_run_spec(spec))
+        .collect::<Result<Vec<_>>>()?;
+
+    #[cfg(feature = "postgres")]
+    let mut db = None;
+    let mut prices: Option<Vec<Price>> = None;
+    let mut runs = Vec::with_capacity(specs.len());
+
+    for spec in specs {
+        match spec {
+            RunSpec::Inline(case) => {
+                let prices = load_prices(&args, &mut prices).await?;
+                let summary = simulate_case(&case, prices, args.capital, args.tx_cost);
+                let label = format!("#{}", runs.len() + 1);
+                runs.push(ComparedRun::from_summary(
+                    label,
+                    "inline",
+                    &case,
+                    args.capital,
+                    &summary,
+                ));
+            }
+            #[cfg(not(feature = "postgres"))]
+            RunSpec::Saved(id) => {
+                bail!(
+                    "saved run '{}' needs the CLI built with the `postgres` feature",
+                    id
+                )
+            }
+            #[cfg(feature = "postgres")]
+            RunSpec::Saved(id) => {
+                if db.is_none() {
+                    db = Some(Database::connect(&args.database_url).await?);
+                }
+                let repo = db.as_ref().expect("connected above").simulations();
+                let mut matches = repo.find_by_id_prefix(&id).await?;
+                let record = match matches.len() {
+                    0 => bail!("no saved simulation matches '{}'", id),
+                    1 => matches.remove(0),
+                    n => bail!("'{}' matches {} simulations; use a longer prefix", id, n),
+                };
+
+                let stored = if args.rerun {
+                    None
+                } else {
+                    repo.find_result_by_simulation(record.id).await?
+                };
+                match stored {
+                    Some(result) => runs.push(from_record(&record, &result)),
+                    None => {
+                        let case = saved_case(&record)?;
+                        let prices = load_prices(&args, &mut prices).await?;
+                        let summary =
+                            simulate_case(&case, prices, record.initial_capital, record.tx_cost);
+                        runs.push(ComparedRun::from_summary(
+                            record.id.to_string()[..8].to_string(),
+                            "rerun",
+                            &case,
+                            record.initial_capital,
+                            &summary,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if output::is_json() {
+        let ranking: Vec<_> = rank_runs(&runs)
+            .into_iter()
+            .map(|(index, score)| serde_json::json!({ "run": runs[index].label, "score": score }))
+            .collect();
+        return output::print_json(&serde_json::json!({ "runs": runs, "ranking": ranking }));
+    }
+
+    print_comparison(&runs);
+    Ok(())
+}
+
+/// Fetches the rerun price history once and caches it.
+async fn load_prices<'a>(
+    args: &CompareArgs,
+    cache: &'a mut Option<Vec<Price>>,
+) -> Result<&'a [Price]> {
+    if cache.is_none() {
+        let api_key = args
+            .api_key
+            .clone()
+            .context("BIRDEYE_API_KEY must be set in .env, environment or config file")?;
+        let provider = BirdeyeProvider::new(api_key);
+        let token_a = Token::new(&args.mint_a, &args.symbol_a, 9, &args.symbol_a);
+        let token_b = Token::new(
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "USDC",
+            6,
+            "USD Coin",
+        );
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let start_time = now - (args.days * 24 * 3600);
+
+        status!(
+            "🔍 Fetching historical data for {}/USDC ({} days)...",
+            args.symbol_a,
+            args.days
+        );
+        let candles = provider
+            .get_price_history(&token_a, &token_b, start_time, now, 3600)
+            .await?;
+        if candles.is_empty() {
+            bail!("no price data found for the specified period");
+        }
+        *cache = Some(candles.iter().map(|c| c.close).collect());
+    }
+    Ok(cache.as_deref().unwrap_or_default())
+}
+
+/// A row of the comparison table: its name and how a run's cell is printed.
+type Metric = (&'static str, fn(&ComparedRun) -> String);
+
+fn print_comparison(runs: &[ComparedRun]) {
+    let metrics: [Metric; 14] = [
+        ("Source", |r| r.source.to_string()),
+        ("Strategy", |r| r.strategy.clone()),
+        ("Range", |r| format!("${:.2} - ${:.2}", r.lower, r.upper)),
+        ("Capital", |r| format!("${:.2}", r.capital)),
+        ("Final Value", |r| format!("${:.2}", r.final_value)),
+        ("Net PnL", |r| format!("${:.2}", r.pnl)),
+        ("Return", |r| format!("{:.2}%", r.return_pct())),
+        ("Fees", |r| format!("${:.2}", r.fees)),
+        ("IL", |r| format!("{:.2}%", r.il_pct * Decimal::from(100))),
+        ("Time in Range", |r| {
+            format!("{:.1}%", r.time_in_range * Decimal::from(100))
+        }),
+        ("Max Drawdown", |r| {
+            format!("{:.2}%", r.max_drawdown * Decimal::from(100))
+        }),
+        ("Rebalances", |r| r.rebalances.to_string()),
+        ("Rebalance Cost", |r| format!("${:.2}", r.rebalance_cost)),
+        ("vs HODL", |r| format!("${:.2}", r.vs_hodl)),
+    ];
+
+    println!();
+    println!("📊 SIDE-BY-SIDE COMPARISON");
+    println!();
+    let mut table = Table::new();
+    let mut header = vec![Cell::new("Metric")];
+    header.extend(runs.iter().map(|r| Cell::new(&r.label)));
+    table.add_row(Row::new(header));
+    for (name, metric) in metrics {
+        let mut cells = vec![Cell::new(name)];
+        cells.extend(runs.iter().map(|r| Cell::new(&metric(r))));
+        table.add_row(Row::new(cells));
+    }
+    table.printstd();
+
+    println!();
+    println!("🏆 RANKING (average position across PnL, vs HODL, drawdown, time in range)");
+    println!();
+    let ranking = rank_runs(runs);
+    let best_pnl = runs.iter().map(|r| r.pnl).max().unwrap_or_default();
+    let mut table = Table::new();
+    table.add_row(row!["#", "Run", "Strategy", "Score", "PnL", "Δ Best PnL"]);
+    for (position, (index, score)) in ranking.iter().enumerate() {
+        let run = &runs[*index];
+        table.add_row(row![
+            position + 1,
+            run.label,
+            run.strategy,
+            format!("{:.2}", score),
+            format!("${:.2}", run.pnl),
+            format!("{:.2}%", relative(run.pnl - best_pnl, best_pnl))
+        ]);
+    }
+    table.printstd();
+}
+
+fn relative(delta: Decimal, base: Decimal) -> Decimal {
+    if base.is_zero() {
+        Decimal::ZERO
+    } else {
+        delta / base.abs() * Decimal::from(100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(label: &str, pnl: i64, vs_hodl: i64, drawdown: i64, in_range: i64) -> ComparedRun {
+        ComparedRun {
+            label: label.to_string(),
+            source: "inline",
+            strategy: "Static".to_string(),
+            lower: Decimal::from(90),
+            upper: Decimal::from(110),
+            capital: Decimal::from(1000),
+            final_value: Decimal::from(1000 + pnl),
+            pnl: Decimal::from(pnl),
+            fees: Decimal::ZERO,
+            il_pct: Decimal::ZERO,
+            time_in_range: Decimal::new(in_range, 2),
+            max_drawdown: Decimal::new(drawdown, 2),
+            rebalances: 0,
+            rebalance_cost: Decimal::ZERO,
+            vs_hodl: Decimal::from(vs_hodl),
+        }
+    }
+
+    #[test]
+    fn test_parse_inline_specs() {
+        let RunSpec::Inline(case) = parse_run_spec("90:110@periodic:12").unwrap() else {
+            panic!("expected inline spec");
+        };
+        assert!(matches!(case.strategy, StrategyType::Periodic));
+        assert_eq!(case.interval, Some(12));
+
+        let RunSpec::Inline(case) = parse_run_spec("95.5:105").unwrap() else {
+            panic!("expected inline spec");
+        };
+        assert!(matches!(case.strategy, StrategyType::Static));
+        assert_eq!(case.lower, Decimal::new(955, 1));
+
+        let RunSpec::Inline(case) = parse_run_spec("90:110@il-limit").unwrap() else {
+            panic!("expected inline spec");
+        };
+        assert_eq!(case.threshold, Some(Decimal::new(5, 2)));
+    }
+
+    #[test]
+    fn test_parse_saved_and_invalid_specs() {
+        assert!(matches!(
+            parse_run_spec("3f2a9c1b").unwrap(),
+            RunSpec::Saved(id) if id == "3f2a9c1b"
+        ));
+        assert!(parse_run_spec("110:90").is_err());
+        assert!(parse_run_spec("90:110@static:5").is_err());
+        assert!(parse_run_spec("90:110@martingale").is_err());
+        assert!(parse_run_spec("not-an-id%").is_err());
+    }
+
+    #[test]
+    fn test_rank_runs() {
+        let runs = vec![
+            run("a", 10, 5, 10, 50),
+            run("b", 50, 20, 5, 90),
+            run("c", 30, 10, 20, 70),
+        ];
+        let ranking = rank_runs(&runs);
+        let order: Vec<&str> = ranking
+            .iter()
+            .map(|(i, _)| runs[*i].label.as_str())
+            .collect();
+        assert_eq!(order, ["b", "c", "a"]);
+        assert_eq!(ranking[0].1, Decimal::ONE);
+    }
+}
@@ -1,14 +1,34 @@
 //! Data command implementation.
 //!
 //! Provides data management functionality including fetching,
-//! caching, and exporting market data.
+//! caching, importing, and exporting market data.
 
+#[cfg(feature = "postgres")]
+use crate::output;
+#[cfg(feature = "postgres")]
+use crate::status;
 use anyhow::Result;
+#[cfg(feature = "postgres")]
+use anyhow::{Context, bail};
 use clmm_lp_data::prelude::*;
+#[cfg(feature = "postgres")]
+use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
 use rust_decimal::Decimal;
+#[cfg(feature = "postgres")]
+use std::collections::HashSet;
+#[cfg(feature = "postgres")]
+use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
-use tracing::info;
+#[cfg(feature = "postgres")]
+use std::str::FromStr;
+use tracing::{info, warn};
+#[cfg(feature = "postgres")]
+use uuid::Uuid;
+
+/// Number of invalid rows listed before the rest are summarized.
+#[cfg(feature = "postgres")]
+const MAX_REPORTED_ERRORS: usize = 10;
 
 /// Arguments for the data command.
 #[derive(Debug, Clone)]
@@ -24,6 +44,12 @@ pub enum DataAction {
     Fetch(FetchArgs),
     /// Export data to file.
     Export(ExportArgs),
+    /// Import CSV price files into the database.
+    #[cfg(feature = "postgres")]
+    Import(ImportArgs),
+    /// Export stored price history to a file.
+    #[cfg(feature = "postgres")]
+    ExportStored(ExportStoredArgs),
     /// Show cache status.
     CacheStatus,
     /// Clear cache.
@@ -66,6 +92,59 @@ pub struct ExportArgs {
     pub format: ExportFormat,
 }
 
+/// Arguments for import action.
+#[derive(Debug, Clone)]
+pub struct ImportArgs {
+    /// CSV files to import.
+    pub files: Vec<PathBuf>,
+    /// Address of the pool the prices belong to.
+    pub pool_address: String,
+    /// Protocol of the pool, used when registering it.
+    pub protocol: String,
+    /// Token A symbol, used when registering the pool.
+    pub symbol_a: String,
+    /// Token A mint address, used when registering the pool.
+    pub mint_a: String,
+    /// Fee tier in basis points, used when registering the pool.
+    pub fee_tier: i32,
+    /// Tick spacing, used when registering the pool.
+    pub tick_spacing: i32,
+    /// Import the valid rows of files that contain invalid ones.
+    pub skip_invalid: bool,
+    /// Rows written per transaction.
+    pub batch_size: usize,
+    /// Database connection URL.
+    pub database_url: String,
+}
+
+/// Arguments for exporting stored price history.
+#[derive(Debug, Clone)]
+pub struct ExportStoredArgs {
+    /// Address of a single pool to export.
+    pub pool_address: Option<String>,
+    /// Symbol or mint of one side of the pair.
+    pub token_a: String,
+    /// Symbol or mint of the other side of the pair.
+    pub token_b: String,
+    /// Days of history to export.
+    pub days: u64,
+    /// Output file path.
+    pub output: PathBuf,
+    /// Output format.
+    pub format: TableFormat,
+    /// Database connection URL.
+    pub database_url: String,
+}
+
+/// A row of a CSV price file that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Why the row was rejected.
+    pub reason: String,
+}
+
 /// Export format.
 #[derive(Debug, Clone, Copy, Default)]
 pub enum ExportFormat {
@@ -94,6 +173,10 @@ pub async fn run_data(args: DataArgs) -> Result<()> {
     match args.action {
         DataAction::Fetch(fetch_args) => run_fetch(fetch_args).await,
         DataAction::Export(export_args) => run_export(export_args).await,
+        #[cfg(feature = "postgres")]
+        DataAction::Import(import_args) => run_import(import_args).await,
+        #[cfg(feature = "postgres")]
+        DataAction::ExportStored(export_args) => run_export_stored(export_args).await,
         DataAction::CacheStatus => run_cache_status().await,
         DataAction::ClearCache => run_clear_cache().await,
     }
@@ -193,11 +276,449 @@ async fn run_export(args: ExportArgs) -> Result<()> {
     Ok(())
 }
 
+/// Parses and validates a CSV price file.
+///
+/// Expects `timestamp,open,high,low,close[,volume[,liquidity]]` rows with an
+/// optional header, the format written by `write_candles_to_csv`. Further
+/// columns, such as the pool columns of [`price_history_table`], are
+/// ignored. Rows are
+/// rejected when a field does not parse, a price is not positive, the high
+/// and low do not bound the open and close, volume or liquidity is negative,
+/// or the timestamp repeats an earlier row.
+///
+/// # Errors
+/// Returns an error if the input cannot be read.
+#[cfg(feature = "postgres")]
+pub fn parse_price_csv(reader: impl BufRead) -> Result<(Vec<PriceBar>, Vec<CsvRowError>)> {
+    let mut bars = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read line {}", index + 1))?;
+        if line.trim().is_empty() || (index == 0 && line.to_lowercase().contains("timestamp")) {
+            continue;
+        }
+
+        match parse_price_row(&line) {
+            Ok(bar) if !seen.insert(bar.timestamp) => errors.push(CsvRowError {
+                line: index + 1,
+                reason: format!("duplicate timestamp {}", bar.timestamp),
+            }),
+            Ok(bar) => bars.push(bar),
+            Err(reason) => errors.push(CsvRowError {
+                line: index + 1,
+                reason,
+            }),
+        }
+    }
+
+    bars.sort_by_key(|bar| bar.timestamp);
+    Ok((bars, errors))
+}
+
+/// Parses and validates one CSV row.
+#[cfg(feature = "postgres")]
+fn parse_price_row(line: &str) -> std::result::Result<PriceBar, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() < 5 {
+        return Err(format!(
+            "expected at least 5 columns, found {}",
+            fields.len()
+        ));
+    }
+
+    let timestamp: i64 = fields[0]
+        .parse()
+        .map_err(|_| format!("invalid timestamp '{}'", fields[0]))?;
+    if timestamp < 0 {
+        return Err(format!("negative timestamp {}", timestamp));
+    }
+    let decimal = |name: &str, value: &str| {
+        Decimal::from_str(value).map_err(|_| format!("invalid {} '{}'", name, value))
+    };
+    let optional = |name: &str, index: usize| -> std::result::Result<Option<Decimal>, String> {
+        match fields.get(index).filter(|v| !v.is_empty()) {
+            Some(value) => {
+                let value = decimal(name, value)?;
+                if value.is_sign_negative() {
+                    return Err(format!("negative {} {}", name, value));
+                }
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    };
+
+    let bar = PriceBar {
+        timestamp,
+        open: decimal("open", fields[1])?,
+        high: decimal("high", fields[2])?,
+        low: decimal("low", fields[3])?,
+        close: decimal("close", fields[4])?,
+        volume: optional("volume", 5)?,
+        liquidity: optional("liquidity", 6)?,
+    };
+
+    if [bar.open, bar.high, bar.low, bar.close]
+        .iter()
+        .any(|price| *price <= Decimal::ZERO)
+    {
+        return Err("prices must be positive".to_string());
+    }
+    if bar.high < bar.open.max(bar.close) || bar.low > bar.open.min(bar.close) {
+        return Err(format!(
+            "high {} and low {} do not bound open {} and close {}",
+            bar.high, bar.low, bar.open, bar.close
+        ));
+    }
+    Ok(bar)
+}
+
+/// Imports CSV price files into the database.
+#[cfg(feature = "postgres")]
+async fn run_import(args: ImportArgs) -> Result<()> {
+    let mut files = Vec::with_capacity(args.files.len());
+    let mut file_stats = Vec::with_capacity(args.files.len());
+    let mut invalid = 0;
+    for path in &args.files {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let (bars, errors) = parse_price_csv(BufReader::new(file))?;
+
+        status!(
+            "📄 {}: {} valid rows, {} invalid",
+            path.display(),
+            bars.len(),
+            errors.len()
+        );
+        for error in errors.iter().take(MAX_REPORTED_ERRORS) {
+            status!("   line {}: {}", error.line, error.reason);
+        }
+        if errors.len() > MAX_REPORTED_ERRORS {
+            status!("   ... and {} more", errors.len() - MAX_REPORTED_ERRORS);
+        }
+        invalid += errors.len();
+        file_stats.push(serde_json::json!({
+            "file": path,
+            "valid": bars.len(),
+            "invalid": errors.len(),
+        }));
+        files.push((path, bars));
+    }
+
+    if invalid > 0 && !args.skip_invalid {
+        bail!(
+            "{} invalid rows found; fix them or pass --skip-invalid to import the valid rows",
+            invalid
+        );
+    }
+
+    let total: usize = files.iter().map(|(_, bars)| bars.len()).sum();
+    if total == 0 {
+        if output::is_json() {
+            return output::print_json(&serde_json::json!({ "files": file_stats, "imported": 0 }));
+        }
+        println!("ℹ️  Nothing to import");
+        return Ok(());
+    }
+
+    let db = Database::connect(&args.database_url).await?;
+    let pool = match db.pools().find_by_address(&args.pool_address).await? {
+        Some(pool) => pool,
+        None => {
+            status!(
+                "➕ Registering pool {} ({} {}/USDC)",
+                args.pool_address,
+                args.protocol,
+                args.symbol_a
+            );
+            db.pools()
+                .upsert(
+                    Uuid::new_v4(),
+                    &args.protocol,
+                    &args.pool_address,
+                    &args.mint_a,
+                    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                    &args.symbol_a,
+                    "USDC",
+                    9,
+                    6,
+                    args.fee_tier,
+                    args.tick_spacing,
+                )
+                .await?
+        }
+    };
+
+    let prices = db.prices();
+    let mut imported = 0;
+    for (path, bars) in &files {
+        for chunk in bars.chunks(args.batch_size.max(1)) {
+            prices
+                .save_batch(Some(pool.id), chunk)
+                .await
+                .with_context(|| format!("Failed to import {}", path.display()))?;
+            imported += chunk.len();
+            if output::is_json() {
+                continue;
+            }
+            print!(
+                "\r⏳ Imported {}/{} rows ({:.0}%)",
+                imported,
+                total,
+                imported as f64 / total as f64 * 100.0
+            );
+            std::io::stdout().flush()?;
+        }
+    }
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "pool": pool.address,
+            "pair": format!("{}/{}", pool.symbol_a, pool.symbol_b),
+            "files": file_stats,
+            "imported": imported,
+            "skipped_invalid": invalid,
+        }));
+    }
+    println!();
+    println!(
+        "✅ Imported {} rows from {} files into pool {}/{} ({})",
+        imported,
+        files.len(),
+        pool.symbol_a,
+        pool.symbol_b,
+        pool.address
+    );
+
+    Ok(())
+}
+
+/// Builds the export table of stored price history.
+///
+/// The leading columns match the CSV import format, so exported files can be
+/// loaded back with `data import`.
+#[cfg(feature = "postgres")]
+#[must_use]
+pub fn price_history_table(rows: &[(PoolRecord, Vec<PriceRecord>)]) -> ExportTable {
+    let mut table = ExportTable::new([
+        "timestamp",
+        "open",
+        "high",
+        "low",
+        "close",
+        "volume",
+        "liquidity",
+        "time",
+        "pool_address",
+        "protocol",
+        "pair",
+        "fee_tier_bps",
+    ]);
+    for (pool, prices) in rows {
+        for price in prices {
+            table.push_row(vec![
+                price.timestamp.into(),
+                price.open_price.into(),
+                price.high_price.into(),
+                price.low_price.into(),
+                price.close_price.into(),
+                price.volume.into(),
+                price.liquidity.into(),
+                chrono::DateTime::from_timestamp(price.timestamp, 0).into(),
+                pool.address.as_str().into(),
+                pool.protocol.as_str().into(),
+                format!("{}/{}", pool.symbol_a, pool.symbol_b).into(),
+                pool.fee_tier.into(),
+            ]);
+        }
+    }
+    table
+}
+
+/// Checks whether a pool trades a token, given as a symbol or mint.
+#[cfg(feature = "postgres")]
+fn pool_has_token(pool: &PoolRecord, token: &str) -> bool {
+    pool.symbol_a.eq_ignore_ascii_case(token)
+        || pool.symbol_b.eq_ignore_ascii_case(token)
+        || pool.token_mint_a == token
+        || pool.token_mint_b == token
+}
+
+/// Loads the stored candles of a pool for an offline replay.
+///
+/// Replays the pool at `pool_address`, or else the first stored pool trading
+/// both tokens. The candle duration is the smallest gap between stored rows.
+///
+/// # Errors
+/// Returns an error if the database is unreachable or no pool matches.
+#[cfg(feature = "postgres")]
+pub async fn load_stored_candles(
+    database_url: &str,
+    pool_address: Option<&str>,
+    token_a: &Token,
+    token_b: &Token,
+    start_time: u64,
+    end_time: u64,
+) -> Result<(PoolRecord, Vec<PriceCandle>)> {
+    let db = Database::connect(database_url).await?;
+    let pool = match pool_address {
+        Some(address) => db
+            .pools()
+            .find_by_address(address)
+            .await?
+            .with_context(|| format!("Pool {} is not in the database", address))?,
+        None => {
+            let mut pools: Vec<PoolRecord> = db
+                .pools()
+                .find_all()
+                .await?
+                .into_iter()
+                .filter(|pool| {
+                    (pool_has_token(pool, &token_a.symbol)
+                        || pool_has_token(pool, &token_a.mint_address))
+                        && (pool_has_token(pool, &token_b.symbol)
+                            || pool_has_token(pool, &token_b.mint_address))
+                })
+                .collect();
+            if pools.len() > 1 {
+                status!(
+                    "ℹ️  {} stored {}/{} pools, replaying {}; pick another with --pool",
+                    pools.len(),
+                    token_a.symbol,
+                    token_b.symbol,
+                    pools[0].address
+                );
+            }
+            if pools.is_empty() {
+                bail!(
+                    "No stored {}/{} pool; load candles with `data import` first",
+                    token_a.symbol,
+                    token_b.symbol
+                );
+            }
+            pools.swap_remove(0)
+        }
+    };
+
+    let records = db
+        .prices()
+        .find_by_pool_and_range(pool.id, start_time as i64, end_time as i64)
+        .await?;
+    let duration = records
+        .windows(2)
+        .map(|w| w[1].timestamp - w[0].timestamp)
+        .filter(|gap| *gap > 0)
+        .min()
+        .map_or(3600, |gap| gap as u64);
+    let candles = records
+        .iter()
+        .map(|record| record.to_candle(token_a, token_b, duration))
+        .collect();
+    Ok((pool, candles))
+}
+
+/// Exports stored price history of a pool or pair to a file.
+#[cfg(feature = "postgres")]
+async fn run_export_stored(args: ExportStoredArgs) -> Result<()> {
+    let db = Database::connect(&args.database_url).await?;
+    let pools = match &args.pool_address {
+        Some(address) => {
+            let pool = db
+                .pools()
+                .find_by_address(address)
+                .await?
+                .with_context(|| format!("Pool {} is not in the database", address))?;
+            vec![pool]
+        }
+        None => db
+            .pools()
+            .find_all()
+            .await?
+            .into_iter()
+            .filter(|pool| {
+                pool_has_token(pool, &args.token_a) && pool_has_token(pool, &args.token_b)
+            })
+            .collect(),
+    };
+    if pools.is_empty() {
+        bail!("No stored pools for {}/{}", args.token_a, args.token_b);
+    }
+
+    let end = chrono::Utc::now().timestamp();
+    let start = end - (args.days as i64 * 24 * 3600);
+    let mut rows = Vec::with_capacity(pools.len());
+    let mut pool_stats = Vec::with_capacity(pools.len());
+    for pool in pools {
+        let prices = db
+            .prices()
+            .find_by_pool_and_range(pool.id, start, end)
+            .await?;
+        pool_stats.push(serde_json::json!({
+            "protocol": pool.protocol,
+            "pool": pool.address,
+            "pair": format!("{}/{}", pool.symbol_a, pool.symbol_b),
+            "rows": prices.len(),
+        }));
+        status!(
+            "📊 {} {}/{} ({}): {} rows",
+            pool.protocol,
+            pool.symbol_a,
+            pool.symbol_b,
+            pool.address,
+            prices.len()
+        );
+        rows.push((pool, prices));
+    }
+
+    let table = price_history_table(&rows);
+    std::fs::write(&args.output, table.render(args.format)?)
+        .with_context(|| format!("Failed to write {}", args.output.display()))?;
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "file": args.output,
+            "rows": table.rows().len(),
+            "pools": pool_stats,
+        }));
+    }
+    println!(
+        "✅ Exported {} rows to {}",
+        table.rows().len(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+/// Returns the CLI cache directory.
+#[must_use]
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("clmm-lp")
+}
+
+/// Opens the on-disk cache of backtest, sweep and optimization results.
+///
+/// Returns `None` if caching is disabled or the cache directory cannot be
+/// created, in which case results are simply recomputed.
+#[must_use]
+pub fn result_cache(enabled: bool) -> Option<ResultCache> {
+    if !enabled {
+        return None;
+    }
+    match ResultCache::on_disk(cache_dir().join("results")) {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            warn!(error = %e, "Result cache unavailable, results will be recomputed");
+            None
+        }
+    }
+}
+
 /// Shows cache status.
 async fn run_cache_status() -> Result<()> {
-    let cache_dir = dirs::cache_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("clmm-lp");
+    let cache_dir = cache_dir();
 
     println!("\n💾 Cache Status");
     println!("═══════════════════════════════════════");
@@ -218,6 +739,10 @@ async fn run_cache_status() -> Result<()> {
 
         println!("Files:           {}", file_count);
         println!("Total Size:      {} KB", total_size / 1024);
+        println!(
+            "Cached Results:  {}",
+            result_cache(true).map_or(0, |cache| cache.len())
+        );
     } else {
         println!("Status:          No cache directory found");
     }
@@ -227,9 +752,7 @@ async fn run_cache_status() -> Result<()> {
 
 /// Clears the cache.
 async fn run_clear_cache() -> Result<()> {
-    let cache_dir = dirs::cache_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("clmm-lp");
+    let cache_dir = cache_dir();
 
     if cache_dir.exists() {
         std::fs::remove_dir_all(&cache_dir)?;
@@ -240,3 +763,83 @@ async fn run_clear_cache() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_price_csv_validates_rows() {
+        let csv = "\
+timestamp,open,high,low,close,volume
+1704070800,101,102,100,101.5,900
+1704067200,100,101,99,100.5,1000
+1704074400,abc,102,100,101,0
+1704078000,101,100,99,101.5,0
+1704067200,100,101,99,100.5,1000
+1704081600,0,1,0,1
+
+1704085200,100,101,99,100,-5
+";
+        let (bars, errors) = parse_price_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].timestamp, 1704067200);
+        assert_eq!(bars[1].volume, Some(Decimal::from(900)));
+        assert_eq!(bars[1].liquidity, None);
+
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![4, 5, 6, 7, 9]);
+        assert!(errors[0].reason.contains("invalid open"));
+        assert!(errors[2].reason.contains("duplicate"));
+    }
+
+    #[test]
+    fn test_price_history_table_round_trips_through_import() {
+        let now = chrono::Utc::now();
+        let pool = PoolRecord {
+            id: Uuid::new_v4(),
+            protocol: "orca".to_string(),
+            address: "pool".to_string(),
+            token_mint_a: "mint-a".to_string(),
+            token_mint_b: "mint-b".to_string(),
+            symbol_a: "SOL".to_string(),
+            symbol_b: "USDC".to_string(),
+            decimals_a: 9,
+            decimals_b: 6,
+            fee_tier: 30,
+            tick_spacing: 64,
+            created_at: now,
+            updated_at: now,
+        };
+        let price = PriceRecord {
+            id: Uuid::new_v4(),
+            pool_id: Some(pool.id),
+            timestamp: 1704067200,
+            open_price: Decimal::from(100),
+            high_price: Decimal::from(101),
+            low_price: Decimal::from(99),
+            close_price: Decimal::from(100),
+            volume: None,
+            liquidity: Some(Decimal::from(5000)),
+            created_at: now,
+        };
+        assert!(pool_has_token(&pool, "sol") && pool_has_token(&pool, "mint-b"));
+
+        let csv = price_history_table(&[(pool, vec![price])]).to_csv();
+        assert!(csv.contains(",2024-01-01T00:00:00+00:00,pool,orca,SOL/USDC,30"));
+
+        let (bars, errors) = parse_price_csv(csv.as_bytes()).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(bars[0].volume, None);
+        assert_eq!(bars[0].liquidity, Some(Decimal::from(5000)));
+    }
+
+    #[test]
+    fn test_parse_price_csv_reads_written_candles() {
+        let (bars, errors) =
+            parse_price_csv("1704067200,100,101,99,100.5,1000,250000\n".as_bytes()).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(bars[0].liquidity, Some(Decimal::from(250_000)));
+    }
+}
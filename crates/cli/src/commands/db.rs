@@ -0,0 +1,483 @@
+//! Database maintenance command implementation.
+//!
+//! Reports table sizes, prunes rows past a retention period, vacuums
+//! tables, and backs the database up to, or restores it from, a JSON lines
+//! file: a header line followed by one `{"table": ..., "row": ...}` line per
+//! row, with tables in foreign-key order.
+
+use crate::output::{self, confirm};
+use crate::status;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use clmm_lp_data::prelude::*;
+use clmm_lp_data::repositories::{DEFAULT_PRUNE_TABLES, TABLES, retention_condition};
+use prettytable::{Table, row};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Format tag written to the header of backup files.
+const BACKUP_FORMAT: &str = "clmm-lp-backup";
+
+/// Layout version of backup files.
+const BACKUP_VERSION: u32 = 1;
+
+/// Arguments for the database maintenance commands.
+#[derive(Debug, Clone)]
+pub struct DbMaintenanceArgs {
+    /// Database connection URL.
+    pub database_url: String,
+    /// Maintenance action.
+    pub action: DbMaintenanceAction,
+}
+
+/// Database maintenance actions.
+#[derive(Debug, Clone)]
+pub enum DbMaintenanceAction {
+    /// Show table sizes and row counts.
+    Stats,
+    /// Delete rows older than a retention period.
+    Prune {
+        /// Retention period in days.
+        older_than_days: u32,
+        /// Tables to prune; the default retention tables when empty.
+        tables: Vec<String>,
+        /// Only count the rows that would be deleted.
+        dry_run: bool,
+        /// Skip the confirmation prompt.
+        yes: bool,
+    },
+    /// Vacuum and analyze tables.
+    Vacuum {
+        /// Tables to vacuum; the whole database when empty.
+        tables: Vec<String>,
+        /// Rewrite the tables to return space to the operating system.
+        full: bool,
+    },
+    /// Write every table to a backup file.
+    Backup {
+        /// Backup file.
+        output: PathBuf,
+        /// Overwrite an existing file.
+        force: bool,
+    },
+    /// Load a backup file into the database.
+    Restore {
+        /// Backup file.
+        input: PathBuf,
+        /// Rows inserted per statement.
+        batch_size: usize,
+    },
+}
+
+/// First line of a backup file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupHeader {
+    /// Always [`BACKUP_FORMAT`].
+    format: String,
+    /// Layout version.
+    version: u32,
+    /// When the backup was taken.
+    created_at: DateTime<Utc>,
+    /// Tables in the file, in restore order.
+    tables: Vec<String>,
+}
+
+/// A row line of a backup file.
+#[derive(Debug, Clone, Deserialize)]
+struct BackupRow {
+    /// Table the row belongs to.
+    table: String,
+    /// Row as a JSON object keyed by column.
+    row: serde_json::Value,
+}
+
+/// Formats a byte count with a binary unit.
+#[must_use]
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes.max(0) as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes.max(0), UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Resolves the tables to prune, defaulting to the retention tables.
+///
+/// # Errors
+/// Returns an error if a table is unknown or has no retention rule.
+pub fn prune_tables(tables: &[String]) -> Result<Vec<String>> {
+    if tables.is_empty() {
+        return Ok(DEFAULT_PRUNE_TABLES.iter().map(|t| t.to_string()).collect());
+    }
+    for table in tables {
+        if retention_condition(table).is_none() {
+            let prunable: Vec<_> = TABLES
+                .iter()
+                .filter(|t| retention_condition(t).is_some())
+                .copied()
+                .collect();
+            bail!(
+                "'{}' cannot be pruned (expected one of: {})",
+                table,
+                prunable.join(", ")
+            );
+        }
+    }
+    Ok(tables.to_vec())
+}
+
+/// Runs a database maintenance command.
+pub async fn run_db_maintenance(args: DbMaintenanceArgs) -> Result<()> {
+    let db = Database::connect(&args.database_url)
+        .await
+        .context("Failed to connect to the database")?;
+
+    match args.action {
+        DbMaintenanceAction::Stats => run_stats(&db).await,
+        DbMaintenanceAction::Prune {
+            older_than_days,
+            tables,
+            dry_run,
+            yes,
+        } => run_prune(&db, older_than_days, &tables, dry_run, yes).await,
+        DbMaintenanceAction::Vacuum { tables, full } => run_vacuum(&db, &tables, full).await,
+        DbMaintenanceAction::Backup { output, force } => run_backup(&db, output, force).await,
+        DbMaintenanceAction::Restore { input, batch_size } => {
+            run_restore(&db, input, batch_size.max(1)).await
+        }
+    }
+}
+
+/// Shows table sizes and row counts.
+async fn run_stats(db: &Database) -> Result<()> {
+    let maintenance = db.maintenance();
+    let database_bytes = maintenance.database_size().await?;
+    let stats = maintenance.table_stats().await?;
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "database_bytes": database_bytes,
+            "tables": stats,
+        }));
+    }
+
+    println!("🗄️  Database size: {}", format_bytes(database_bytes));
+    println!();
+    let mut table = Table::new();
+    table.add_row(row![
+        "Table",
+        "Rows (est.)",
+        "Dead Rows",
+        "Total Size",
+        "Indexes",
+        "Last Vacuum",
+        "Last Analyze"
+    ]);
+    let when = |time: Option<DateTime<Utc>>| {
+        time.map_or_else(
+            || "never".to_string(),
+            |t| t.format("%Y-%m-%d %H:%M").to_string(),
+        )
+    };
+    for entry in &stats {
+        table.add_row(row![
+            entry.table,
+            entry.rows,
+            entry.dead_rows,
+            format_bytes(entry.total_bytes),
+            format_bytes(entry.index_bytes),
+            when(entry.last_vacuum),
+            when(entry.last_analyze)
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+/// Deletes rows older than the retention period.
+async fn run_prune(
+    db: &Database,
+    older_than_days: u32,
+    tables: &[String],
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let tables = prune_tables(tables)?;
+    let cutoff = Utc::now() - Duration::days(i64::from(older_than_days));
+    let maintenance = db.maintenance();
+
+    let mut counts = Vec::with_capacity(tables.len());
+    for table in &tables {
+        counts.push((
+            table.as_str(),
+            maintenance.count_older_than(table, cutoff).await?,
+        ));
+    }
+    let total: u64 = counts.iter().map(|(_, count)| count).sum();
+
+    let report = |counts: &[(&str, u64)], deleted: bool| {
+        if output::is_json() {
+            let tables: Vec<_> = counts
+                .iter()
+                .map(|(table, rows)| serde_json::json!({ "table": table, "rows": rows }))
+                .collect();
+            return output::print_json(&serde_json::json!({
+                "cutoff": cutoff,
+                "deleted": deleted,
+                "tables": tables,
+            }));
+        }
+        let heading = if deleted { "Deleted" } else { "Older Rows" };
+        let mut table = Table::new();
+        table.add_row(row!["Table", heading]);
+        for (name, rows) in counts {
+            table.add_row(row![name, rows]);
+        }
+        table.printstd();
+        Ok(())
+    };
+
+    status!(
+        "🧹 Rows older than {} ({} days):",
+        cutoff.format("%Y-%m-%d %H:%M UTC"),
+        older_than_days
+    );
+    if dry_run || total == 0 {
+        report(&counts, false)?;
+        if !output::is_json() {
+            if total == 0 {
+                println!("Nothing to prune.");
+            } else {
+                println!("Dry run: nothing was deleted.");
+            }
+        }
+        return Ok(());
+    }
+
+    if !output::is_json() {
+        report(&counts, false)?;
+    }
+    if !yes && !confirm(&format!("Delete {} rows?", total))? {
+        status!("Aborted.");
+        return Ok(());
+    }
+
+    let mut deleted = Vec::with_capacity(counts.len());
+    for (table, _) in &counts {
+        let rows = maintenance.delete_older_than(table, cutoff).await?;
+        status!("🗑️  {}: {} rows deleted", table, rows);
+        deleted.push((*table, rows));
+    }
+    if output::is_json() {
+        report(&deleted, true)?;
+    } else {
+        println!("Run `db vacuum` to reclaim the space.");
+    }
+    Ok(())
+}
+
+/// Vacuums and analyzes tables.
+async fn run_vacuum(db: &Database, tables: &[String], full: bool) -> Result<()> {
+    let maintenance = db.maintenance();
+    if tables.is_empty() {
+        status!("🧽 Vacuuming the database...");
+        maintenance.vacuum(None, full).await?;
+    }
+    for table in tables {
+        status!("🧽 Vacuuming {}...", table);
+        maintenance.vacuum(Some(table), full).await?;
+    }
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({ "vacuumed": tables, "full": full }));
+    }
+    println!("✅ Vacuum complete");
+    Ok(())
+}
+
+/// Writes every table to a backup file.
+async fn run_backup(db: &Database, path: PathBuf, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        bail!(
+            "{} already exists; pass --force to overwrite",
+            path.display()
+        );
+    }
+    let file =
+        File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    let header = BackupHeader {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        created_at: Utc::now(),
+        tables: TABLES.iter().map(|t| t.to_string()).collect(),
+    };
+    writeln!(writer, "{}", serde_json::to_string(&header)?)?;
+
+    status!("💾 Backing up {} tables...", TABLES.len());
+    let result = db
+        .maintenance()
+        .export(|table, row| writeln!(writer, "{{\"table\":\"{}\",\"row\":{}}}", table, row))
+        .await;
+    let counts = match result.map_err(anyhow::Error::from).and_then(|counts| {
+        writer.flush()?;
+        Ok(counts)
+    }) {
+        Ok(counts) => counts,
+        Err(e) => {
+            // Do not leave a truncated backup behind
+            let _ = std::fs::remove_file(&path);
+            return Err(e.context("Backup failed"));
+        }
+    };
+
+    let total: u64 = counts.iter().map(|(_, rows)| rows).sum();
+    if output::is_json() {
+        let tables: Vec<_> = counts
+            .iter()
+            .map(|(table, rows)| serde_json::json!({ "table": table, "rows": rows }))
+            .collect();
+        return output::print_json(&serde_json::json!({
+            "file": path,
+            "rows": total,
+            "tables": tables,
+        }));
+    }
+    let mut table = Table::new();
+    table.add_row(row!["Table", "Rows"]);
+    for (name, rows) in &counts {
+        table.add_row(row![name, rows]);
+    }
+    table.printstd();
+    println!("✅ Backed up {} rows to {}", total, path.display());
+    Ok(())
+}
+
+/// Loads a backup file into the database.
+///
+/// Applies the migrations first, then inserts the rows table by table.
+/// Rows whose key already exists are kept as they are.
+async fn run_restore(db: &Database, path: PathBuf, batch_size: usize) -> Result<()> {
+    let file = File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header: BackupHeader = match lines.next() {
+        Some(line) => serde_json::from_str(&line?).context("Invalid backup header")?,
+        None => bail!("{} is empty", path.display()),
+    };
+    if header.format != BACKUP_FORMAT || header.version != BACKUP_VERSION {
+        bail!(
+            "{} is not a version {} {} file",
+            path.display(),
+            BACKUP_VERSION,
+            BACKUP_FORMAT
+        );
+    }
+    status!(
+        "♻️  Restoring backup taken {}",
+        header.created_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    db.migrate().await?;
+    let maintenance = db.maintenance();
+
+    // (table, rows read, rows inserted)
+    let mut counts: Vec<(String, u64, u64)> = Vec::new();
+    let mut batch: Vec<String> = Vec::with_capacity(batch_size);
+    let mut current: Option<String> = None;
+
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: BackupRow = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid row on line {}", index + 2))?;
+
+        if current.as_deref() != Some(entry.table.as_str()) || batch.len() >= batch_size {
+            if let Some(table) = &current {
+                let inserted = maintenance.import(table, &batch).await?;
+                record_batch(&mut counts, table, batch.len() as u64, inserted);
+                batch.clear();
+            }
+            if current.as_deref() != Some(entry.table.as_str()) {
+                status!("   {}...", entry.table);
+            }
+            current = Some(entry.table.clone());
+        }
+        batch.push(entry.row.to_string());
+    }
+    if let Some(table) = &current {
+        let inserted = maintenance.import(table, &batch).await?;
+        record_batch(&mut counts, table, batch.len() as u64, inserted);
+    }
+
+    if output::is_json() {
+        let tables: Vec<_> = counts
+            .iter()
+            .map(|(table, read, inserted)| {
+                serde_json::json!({ "table": table, "rows": read, "inserted": inserted })
+            })
+            .collect();
+        return output::print_json(&serde_json::json!({ "file": path, "tables": tables }));
+    }
+    let mut table = Table::new();
+    table.add_row(row!["Table", "Rows", "Inserted", "Already Present"]);
+    for (name, read, inserted) in &counts {
+        table.add_row(row![name, read, inserted, read - inserted]);
+    }
+    table.printstd();
+    println!("✅ Restored {}", path.display());
+    Ok(())
+}
+
+/// Adds an imported batch to the per-table counts.
+fn record_batch(counts: &mut Vec<(String, u64, u64)>, table: &str, read: u64, inserted: u64) {
+    match counts.iter_mut().find(|(name, _, _)| name == table) {
+        Some((_, total_read, total_inserted)) => {
+            *total_read += read;
+            *total_inserted += inserted;
+        }
+        None => counts.push((table.to_string(), read, inserted)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_prune_tables() {
+        assert_eq!(prune_tables(&[]).unwrap().len(), DEFAULT_PRUNE_TABLES.len());
+        assert_eq!(
+            prune_tables(&["audit_log".to_string()]).unwrap(),
+            vec!["audit_log"]
+        );
+        assert!(prune_tables(&["pools".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_backup_row_line() {
+        let line = r#"{"table":"pools","row":{"id":"a","fee_tier":30}}"#;
+        let row: BackupRow = serde_json::from_str(line).unwrap();
+        assert_eq!(row.table, "pools");
+        assert_eq!(row.row["fee_tier"], 30);
+    }
+}
@@ -1,14 +1,50 @@
 //! CLI command implementations.
 //!
 //! This module contains the implementation of all CLI commands,
-//! separated into logical modules for maintainability.
+//! separated into logical modules for maintainability. Commands that sign
+//! or read on-chain state need the `live` feature; database maintenance
+//! needs the `postgres` feature.
 
 pub mod analyze;
+pub mod analyze_batch;
 pub mod backtest;
+#[cfg(feature = "live")]
+pub mod collect;
+pub mod compare;
 pub mod data;
+#[cfg(feature = "postgres")]
+pub mod db;
+pub mod monitor;
 pub mod optimize;
+pub mod optimize_params;
+#[cfg(feature = "live")]
+pub mod position;
+#[cfg(feature = "live")]
+pub mod positions;
+pub mod scenario;
+#[cfg(feature = "live")]
+pub mod swaps;
+pub mod sweep;
+#[cfg(feature = "live")]
+pub mod wallet;
 
 pub use analyze::run_analyze;
+pub use analyze_batch::run_analyze_batch;
 pub use backtest::run_backtest;
+#[cfg(feature = "live")]
+pub use collect::run_collect_fees;
+pub use compare::run_compare;
 pub use data::run_data;
+#[cfg(feature = "postgres")]
+pub use db::run_db_maintenance;
+pub use monitor::run_monitor;
 pub use optimize::run_optimize;
+pub use optimize_params::run_optimize_params;
+#[cfg(feature = "live")]
+pub use position::run_position;
+#[cfg(feature = "live")]
+pub use positions::run_positions;
+pub use scenario::run_scenario;
+pub use sweep::run_sweep;
+#[cfg(feature = "live")]
+pub use wallet::run_wallet;
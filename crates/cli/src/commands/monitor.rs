@@ -0,0 +1,479 @@
+//! Monitor command implementation.
+//!
+//! A live terminal dashboard of the positions tracked by the API server:
+//! portfolio totals, each position's range status and PnL, and the most
+//! recent alerts. The dashboard polls the API at a fixed interval and keeps
+//! showing the last data while the server is unreachable.
+//!
+//! Keys: `q` or `Esc` quits, `r` refreshes immediately. Under
+//! `--output json` a single snapshot is printed instead.
+
+use crate::output;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How long to wait for a key press between redraws.
+const INPUT_POLL: Duration = Duration::from_millis(200);
+
+/// Arguments for the monitor command.
+#[derive(Debug, Clone)]
+pub struct MonitorArgs {
+    /// Base URL of the API server.
+    pub api_url: String,
+    /// API key sent in the `X-API-Key` header.
+    pub api_key: Option<String>,
+    /// Seconds between refreshes.
+    pub interval_secs: u64,
+    /// Number of recent alerts shown.
+    pub alert_limit: usize,
+}
+
+impl Default for MonitorArgs {
+    fn default() -> Self {
+        Self {
+            api_url: "http://localhost:8080/api/v1".to_string(),
+            api_key: None,
+            interval_secs: 5,
+            alert_limit: 8,
+        }
+    }
+}
+
+/// PnL of a position, as returned by the API.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct PnLRow {
+    fees_earned_usd: Decimal,
+    il_pct: Decimal,
+    net_pnl_usd: Decimal,
+    net_pnl_pct: Decimal,
+}
+
+/// A monitored position, as returned by the API.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct PositionRow {
+    address: String,
+    pool_address: String,
+    tick_lower: i32,
+    tick_upper: i32,
+    in_range: bool,
+    value_usd: Decimal,
+    pnl: PnLRow,
+}
+
+/// List of positions, as returned by the API.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct PositionList {
+    positions: Vec<PositionRow>,
+}
+
+/// Portfolio totals, as returned by the API.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct PortfolioRow {
+    total_value_usd: Decimal,
+    total_pnl_usd: Decimal,
+    total_pnl_pct: Decimal,
+    total_fees_usd: Decimal,
+    total_il_pct: Decimal,
+    active_positions: u32,
+    positions_in_range: u32,
+}
+
+/// An alert, as returned by the API.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct AlertRow {
+    level: String,
+    message: String,
+    position_address: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+    acknowledged: bool,
+}
+
+/// List of alerts, as returned by the API.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct AlertList {
+    alerts: Vec<AlertRow>,
+}
+
+/// Data shown by one frame of the dashboard.
+#[derive(Debug, Clone, Default, Serialize)]
+struct Snapshot {
+    portfolio: PortfolioRow,
+    positions: Vec<PositionRow>,
+    alerts: Vec<AlertRow>,
+    fetched_at: Option<DateTime<Local>>,
+}
+
+/// Client for the API endpoints the dashboard reads.
+struct ApiClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl ApiClient {
+    /// Fetches and decodes a JSON resource.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let request = self.client.get(format!("{}{}", self.base_url, path));
+        let request = match &self.api_key {
+            Some(key) => request.header("X-API-Key", key),
+            None => request,
+        };
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", self.base_url))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("GET {} failed ({}): {}", path, status, body);
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Fetches the portfolio, positions and recent alerts.
+    async fn snapshot(&self, alert_limit: usize) -> Result<Snapshot> {
+        let (portfolio, positions, alerts) = tokio::try_join!(
+            self.get::<PortfolioRow>("/analytics/portfolio"),
+            self.get::<PositionList>("/positions"),
+            self.get::<AlertList>("/alerts"),
+        )?;
+
+        let mut positions = positions.positions;
+        positions.sort_by_key(|p| std::cmp::Reverse(p.value_usd));
+        let mut alerts = alerts.alerts;
+        alerts.truncate(alert_limit);
+
+        Ok(Snapshot {
+            portfolio,
+            positions,
+            alerts,
+            fetched_at: Some(Local::now()),
+        })
+    }
+}
+
+/// Dashboard state between frames.
+struct Dashboard {
+    api_url: String,
+    interval: Duration,
+    snapshot: Snapshot,
+    error: Option<String>,
+}
+
+/// Shortens a base58 address to its first and last characters.
+fn short_address(address: &str) -> String {
+    if address.chars().count() <= 12 {
+        return address.to_string();
+    }
+    let head: String = address.chars().take(4).collect();
+    let tail: String = address.chars().skip(address.chars().count() - 4).collect();
+    format!("{}…{}", head, tail)
+}
+
+/// Formats a USD amount with its sign.
+fn usd(value: Decimal) -> String {
+    let cents = value.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+    if cents.is_sign_negative() && !cents.is_zero() {
+        format!("-${:.2}", cents.abs())
+    } else {
+        format!("${:.2}", cents)
+    }
+}
+
+/// Colors a value green when positive and red when negative.
+fn signed_style(value: Decimal) -> Style {
+    if value > Decimal::ZERO {
+        Style::default().fg(Color::Green)
+    } else if value < Decimal::ZERO {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default()
+    }
+}
+
+/// Colors an alert level.
+fn level_style(level: &str) -> Style {
+    match level.to_ascii_lowercase().as_str() {
+        "critical" => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        "warning" => Style::default().fg(Color::Yellow),
+        _ => Style::default().fg(Color::Cyan),
+    }
+}
+
+/// Renders the header line.
+fn render_header(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let updated = dashboard
+        .snapshot
+        .fetched_at
+        .map(|t| t.format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "never".to_string());
+    let mut spans = vec![
+        Span::styled(
+            "CLMM LP Monitor",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            "  {}  updated {} (every {}s)  q quit  r refresh",
+            dashboard.api_url,
+            updated,
+            dashboard.interval.as_secs()
+        )),
+    ];
+    if let Some(error) = &dashboard.error {
+        spans.push(Span::styled(
+            format!("  ⚠ {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).block(Block::bordered()),
+        area,
+    );
+}
+
+/// Renders the portfolio totals.
+fn render_portfolio(frame: &mut Frame, area: Rect, portfolio: &PortfolioRow) {
+    let line = Line::from(vec![
+        Span::raw(format!("Value {}   ", usd(portfolio.total_value_usd))),
+        Span::raw("PnL "),
+        Span::styled(
+            format!(
+                "{} ({:+.2}%)",
+                usd(portfolio.total_pnl_usd),
+                portfolio.total_pnl_pct
+            ),
+            signed_style(portfolio.total_pnl_usd),
+        ),
+        Span::raw(format!(
+            "   Fees {}   Avg IL {:.2}%   In range {}/{}",
+            usd(portfolio.total_fees_usd),
+            portfolio.total_il_pct,
+            portfolio.positions_in_range,
+            portfolio.active_positions
+        )),
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).block(Block::bordered().title("Portfolio")),
+        area,
+    );
+}
+
+/// Renders the positions table.
+fn render_positions(frame: &mut Frame, area: Rect, positions: &[PositionRow]) {
+    let header = Row::new([
+        "Position", "Pool", "Ticks", "Status", "Value", "Net PnL", "PnL %", "Fees", "IL %",
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows = positions.iter().map(|p| {
+        let status = if p.in_range {
+            Cell::from("In range").style(Style::default().fg(Color::Green))
+        } else {
+            Cell::from("Out of range").style(Style::default().fg(Color::Red))
+        };
+        Row::new(vec![
+            Cell::from(short_address(&p.address)),
+            Cell::from(short_address(&p.pool_address)),
+            Cell::from(format!("{}..{}", p.tick_lower, p.tick_upper)),
+            status,
+            Cell::from(usd(p.value_usd)),
+            Cell::from(usd(p.pnl.net_pnl_usd)).style(signed_style(p.pnl.net_pnl_usd)),
+            Cell::from(format!("{:+.2}%", p.pnl.net_pnl_pct))
+                .style(signed_style(p.pnl.net_pnl_pct)),
+            Cell::from(usd(p.pnl.fees_earned_usd)),
+            Cell::from(format!("{:.2}%", p.pnl.il_pct)),
+        ])
+    });
+
+    let widths = [
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Length(15),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(8),
+    ];
+    let title = format!("Positions ({})", positions.len());
+    frame.render_widget(
+        Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title(title)),
+        area,
+    );
+}
+
+/// Renders the recent alerts.
+fn render_alerts(frame: &mut Frame, area: Rect, alerts: &[AlertRow]) {
+    let lines: Vec<Line> = if alerts.is_empty() {
+        vec![Line::from("No alerts")]
+    } else {
+        alerts
+            .iter()
+            .map(|alert| {
+                let time = alert
+                    .timestamp
+                    .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+                    .unwrap_or_default();
+                let position = alert
+                    .position_address
+                    .as_deref()
+                    .map(|a| format!(" [{}]", short_address(a)))
+                    .unwrap_or_default();
+                let mut style = level_style(&alert.level);
+                if alert.acknowledged {
+                    style = style.add_modifier(Modifier::DIM);
+                }
+                Line::from(vec![
+                    Span::raw(format!("{} ", time)),
+                    Span::styled(format!("{:<8}", alert.level.to_uppercase()), style),
+                    Span::raw(format!("{}{}", alert.message, position)),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title("Recent alerts")),
+        area,
+    );
+}
+
+/// Renders one frame of the dashboard.
+fn render(frame: &mut Frame, dashboard: &Dashboard) {
+    let alert_height = dashboard.snapshot.alerts.len().max(1) as u16 + 2;
+    let [header, portfolio, positions, alerts] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(alert_height),
+    ])
+    .areas(frame.area());
+
+    render_header(frame, header, dashboard);
+    render_portfolio(frame, portfolio, &dashboard.snapshot.portfolio);
+    render_positions(frame, positions, &dashboard.snapshot.positions);
+    render_alerts(frame, alerts, &dashboard.snapshot.alerts);
+}
+
+/// Runs the dashboard until the user quits.
+async fn run_dashboard(
+    terminal: &mut DefaultTerminal,
+    client: &ApiClient,
+    args: &MonitorArgs,
+) -> Result<()> {
+    let mut dashboard = Dashboard {
+        api_url: args.api_url.clone(),
+        interval: Duration::from_secs(args.interval_secs.max(1)),
+        snapshot: Snapshot::default(),
+        error: None,
+    };
+    let mut next_refresh = Instant::now();
+
+    loop {
+        if Instant::now() >= next_refresh {
+            match client.snapshot(args.alert_limit).await {
+                Ok(snapshot) => {
+                    dashboard.snapshot = snapshot;
+                    dashboard.error = None;
+                }
+                Err(e) => dashboard.error = Some(format!("{:#}", e)),
+            }
+            next_refresh = Instant::now() + dashboard.interval;
+        }
+
+        terminal.draw(|frame| render(frame, &dashboard))?;
+
+        if event::poll(INPUT_POLL)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('r') => next_refresh = Instant::now(),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Runs the monitor command.
+pub async fn run_monitor(args: MonitorArgs) -> Result<()> {
+    let client = ApiClient {
+        client: reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?,
+        base_url: args.api_url.trim_end_matches('/').to_string(),
+        api_key: args.api_key.clone(),
+    };
+
+    if output::is_json() {
+        return output::print_json(&client.snapshot(args.alert_limit).await?);
+    }
+
+    let mut terminal = ratatui::init();
+    let result = run_dashboard(&mut terminal, &client, &args).await;
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_short_address() {
+        assert_eq!(
+            short_address("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"),
+            "7xKX…gAsU"
+        );
+        assert_eq!(short_address("short"), "short");
+    }
+
+    #[test]
+    fn test_usd() {
+        assert_eq!(usd(dec!(12.345)), "$12.35");
+        assert_eq!(usd(dec!(-3.5)), "-$3.50");
+    }
+
+    #[test]
+    fn test_decode_api_positions() {
+        let json = serde_json::json!({
+            "positions": [{
+                "address": "pos",
+                "pool_address": "pool",
+                "tick_lower": -100,
+                "tick_upper": 100,
+                "in_range": true,
+                "value_usd": "1250.5",
+                "pnl": {"net_pnl_usd": "-2.5", "net_pnl_pct": "-0.2"},
+                "status": "active"
+            }],
+            "total": 1
+        });
+        let list: PositionList = serde_json::from_value(json).unwrap();
+        assert_eq!(list.positions.len(), 1);
+        assert_eq!(list.positions[0].value_usd, dec!(1250.5));
+        assert_eq!(list.positions[0].pnl.net_pnl_usd, dec!(-2.5));
+        assert_eq!(list.positions[0].pnl.fees_earned_usd, Decimal::ZERO);
+    }
+}
@@ -8,6 +8,7 @@ use anyhow::Result;
 use clmm_lp_optimization::prelude::*;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use tracing::info;
 
 /// Arguments for the optimize command.
@@ -197,6 +198,35 @@ pub struct StrategyRecommendation {
     pub score: Decimal,
 }
 
+/// Progress of an `optimize` run, saved in the result of its job.
+///
+/// An interrupted run is saved as a cancelled job; `--resume` reuses its
+/// seed and market estimates and only simulates the widths not yet
+/// evaluated, so the resumed ranking matches an uninterrupted run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizeCheckpoint {
+    /// Seed of the Monte Carlo price paths.
+    pub seed: u64,
+    /// Annualized volatility the run started with.
+    pub volatility: f64,
+    /// Price the candidate ranges are centred on.
+    pub current_price: f64,
+    /// Candidate widths evaluated so far.
+    pub candidates: Vec<CandidateResult>,
+}
+
+impl OptimizeCheckpoint {
+    /// Returns the widths that still need to be evaluated.
+    #[must_use]
+    pub fn remaining_widths(&self, widths: &[Decimal]) -> Vec<Decimal> {
+        widths
+            .iter()
+            .copied()
+            .filter(|w| !self.candidates.iter().any(|c| c.range_width == *w))
+            .collect()
+    }
+}
+
 /// Prints optimization report in CSV format.
 fn print_csv_optimization(report: &OptimizationReport) {
     println!(
@@ -217,3 +247,39 @@ fn print_csv_optimization(report: &OptimizationReport) {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(width: Decimal) -> CandidateResult {
+        CandidateResult::new(
+            width,
+            Decimal::ONE,
+            Decimal::ZERO,
+            Decimal::ONE,
+            Decimal::ONE,
+            Decimal::ONE,
+        )
+    }
+
+    #[test]
+    fn test_checkpoint_remaining_widths() {
+        let checkpoint = OptimizeCheckpoint {
+            seed: 7,
+            volatility: 0.5,
+            current_price: 100.0,
+            candidates: vec![candidate(Decimal::new(1, 2)), candidate(Decimal::new(5, 2))],
+        };
+        let widths = [Decimal::new(1, 2), Decimal::new(2, 2), Decimal::new(5, 2)];
+        assert_eq!(
+            checkpoint.remaining_widths(&widths),
+            vec![Decimal::new(2, 2)]
+        );
+
+        let json = serde_json::to_value(&checkpoint).unwrap();
+        let restored: OptimizeCheckpoint = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.seed, 7);
+        assert_eq!(restored.candidates.len(), 2);
+    }
+}
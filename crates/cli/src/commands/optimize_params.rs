@@ -0,0 +1,294 @@
+//! Parameter optimization command implementation.
+//!
+//! Searches threshold, periodic and IL-limit rebalancing parameters for a
+//! pair with the [`ParameterOptimizer`], using volatility estimated from
+//! historical prices.
+
+use super::optimize::ObjectiveType;
+use crate::output;
+use crate::status;
+use anyhow::{Context, Result};
+use clmm_lp_data::prelude::*;
+use clmm_lp_domain::prelude::*;
+use clmm_lp_optimization::prelude::*;
+use prettytable::{Table, row};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Arguments for the optimize-params command.
+#[derive(Debug, Clone)]
+pub struct OptimizeParamsArgs {
+    /// Token A symbol.
+    pub symbol_a: String,
+    /// Token A mint address.
+    pub mint_a: String,
+    /// Days of history used to estimate volatility.
+    pub days: u64,
+    /// Horizon of the evaluated strategies, in days.
+    pub horizon_days: u64,
+    /// Range width as a fraction of price.
+    pub range_width: Decimal,
+    /// Transaction cost per rebalance in USD.
+    pub tx_cost: Decimal,
+    /// Optimization objective.
+    pub objective: ObjectiveType,
+    /// Number of candidates shown per strategy family.
+    pub top_n: usize,
+    /// Birdeye API key.
+    pub api_key: Option<String>,
+}
+
+impl Default for OptimizeParamsArgs {
+    fn default() -> Self {
+        Self {
+            symbol_a: "SOL".to_string(),
+            mint_a: "So11111111111111111111111111111111111111112".to_string(),
+            days: 30,
+            horizon_days: 30,
+            range_width: Decimal::new(10, 2),
+            tx_cost: Decimal::ONE,
+            objective: ObjectiveType::Pnl,
+            top_n: 5,
+            api_key: None,
+        }
+    }
+}
+
+/// Best candidates of every strategy family.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParameterCandidates {
+    /// Threshold strategy candidates, best first.
+    pub threshold: Vec<ThresholdCandidate>,
+    /// Periodic strategy candidates, best first.
+    pub periodic: Vec<PeriodicCandidate>,
+    /// IL-limit strategy candidates, best first.
+    pub il_limit: Vec<ILLimitCandidate>,
+}
+
+/// Runs every strategy family through the optimizer and keeps the top `n`.
+#[must_use]
+pub fn optimize_families(
+    config: &OptimizationConfig,
+    range_width: Decimal,
+    objective: ObjectiveType,
+    n: usize,
+) -> ParameterCandidates {
+    match objective {
+        ObjectiveType::Pnl => search(config, range_width, &MaximizeNetPnL, n),
+        ObjectiveType::Fees => search(config, range_width, &MaximizeFees, n),
+        ObjectiveType::Sharpe => search(config, range_width, &MaximizeSharpeRatio::default(), n),
+        ObjectiveType::MinIL => search(config, range_width, &MinimizeIL::default(), n),
+        ObjectiveType::TimeInRange => search(config, range_width, &MaximizeTimeInRange, n),
+    }
+}
+
+fn search<O: ObjectiveFunction>(
+    config: &OptimizationConfig,
+    range_width: Decimal,
+    objective: &O,
+    n: usize,
+) -> ParameterCandidates {
+    let optimizer = ParameterOptimizer::new();
+    let mut candidates = ParameterCandidates {
+        threshold: optimizer.optimize_threshold(config, range_width, objective),
+        periodic: optimizer.optimize_periodic(config, range_width, objective),
+        il_limit: optimizer.optimize_il_limit(config, range_width, objective),
+    };
+    candidates.threshold.truncate(n);
+    candidates.periodic.truncate(n);
+    candidates.il_limit.truncate(n);
+    candidates
+}
+
+/// Runs the optimize-params command.
+pub async fn run_optimize_params(args: OptimizeParamsArgs) -> Result<()> {
+    let api_key = args
+        .api_key
+        .clone()
+        .context("BIRDEYE_API_KEY must be set in .env, environment or config file")?;
+    let provider = BirdeyeProvider::new(api_key);
+    let token_a = Token::new(&args.mint_a, &args.symbol_a, 9, &args.symbol_a);
+    let token_b = Token::new(
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "USDC",
+        6,
+        "USD Coin",
+    );
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let start_time = now - (args.days * 24 * 3600);
+
+    status!(
+        "🔍 Fetching historical data for {}/USDC ({} days) to estimate volatility...",
+        args.symbol_a,
+        args.days
+    );
+    let candles = provider
+        .get_price_history(&token_a, &token_b, start_time, now, 3600)
+        .await?;
+    if candles.is_empty() {
+        status!("❌ No data found for the specified period.");
+        return Ok(());
+    }
+
+    let prices: Vec<f64> = candles
+        .iter()
+        .map(|c| c.close.value.to_f64().unwrap_or(0.0))
+        .collect();
+    let volatility = crate::calculate_volatility(&prices);
+    let current_price = candles
+        .last()
+        .map(|c| c.close.value)
+        .unwrap_or(Decimal::ONE);
+
+    // Strategy parameters are expressed in hourly steps.
+    let mut config = OptimizationConfig::new()
+        .with_steps((args.horizon_days * 24) as usize)
+        .with_volatility(volatility)
+        .with_price(current_price);
+    config.time_step_years = 1.0 / 8760.0;
+    config.tx_cost = args.tx_cost;
+
+    status!("📊 Market Analysis:");
+    status!("   Current Price: ${:.4}", current_price);
+    status!("   Volatility (annualized): {:.1}%", volatility * 100.0);
+    status!(
+        "   Range Width: ±{}% | Horizon: {} days | Objective: {:?}",
+        (args.range_width * Decimal::from(100)).normalize(),
+        args.horizon_days,
+        args.objective
+    );
+
+    let candidates = optimize_families(&config, args.range_width, args.objective, args.top_n);
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "pair": format!("{}/USDC", args.symbol_a),
+            "current_price": current_price,
+            "volatility": volatility,
+            "range_width": args.range_width,
+            "horizon_days": args.horizon_days,
+            "objective": format!("{:?}", args.objective),
+            "candidates": candidates,
+        }));
+    }
+    print_candidates(&candidates);
+    Ok(())
+}
+
+fn pct(value: Decimal) -> String {
+    format!("{:.1}%", value * Decimal::from(100))
+}
+
+fn print_candidates(candidates: &ParameterCandidates) {
+    println!();
+    println!("🎯 THRESHOLD REBALANCING");
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "Price Threshold",
+        "IL Threshold",
+        "Rebalance OOR",
+        "Net Fees",
+        "IL",
+        "Rebalances",
+        "Score"
+    ]);
+    for (i, c) in candidates.threshold.iter().enumerate() {
+        table.add_row(row![
+            i + 1,
+            pct(c.params.price_threshold),
+            pct(c.params.il_threshold),
+            if c.params.rebalance_on_out_of_range {
+                "yes"
+            } else {
+                "no"
+            },
+            format!("${:.2}", c.expected_fees),
+            pct(c.expected_il),
+            c.expected_rebalances,
+            format!("{:.4}", c.score)
+        ]);
+    }
+    table.printstd();
+
+    println!();
+    println!("⏱️  PERIODIC REBALANCING");
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "Interval",
+        "Only OOR",
+        "Net Fees",
+        "IL",
+        "Rebalances",
+        "Score"
+    ]);
+    for (i, c) in candidates.periodic.iter().enumerate() {
+        table.add_row(row![
+            i + 1,
+            format!("{}h", c.params.interval),
+            if c.params.only_when_out_of_range {
+                "yes"
+            } else {
+                "no"
+            },
+            format!("${:.2}", c.expected_fees),
+            pct(c.expected_il),
+            c.expected_rebalances,
+            format!("{:.4}", c.score)
+        ]);
+    }
+    table.printstd();
+
+    println!();
+    println!("🛡️  IL-LIMIT REBALANCING");
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "Max IL",
+        "Close IL",
+        "Grace",
+        "Net Fees",
+        "IL",
+        "Rebalances",
+        "Score"
+    ]);
+    for (i, c) in candidates.il_limit.iter().enumerate() {
+        table.add_row(row![
+            i + 1,
+            pct(c.params.max_il),
+            c.params.close_il.map_or_else(|| "-".to_string(), pct),
+            format!("{}h", c.params.grace_period),
+            format!("${:.2}", c.expected_fees),
+            pct(c.expected_il),
+            c.expected_rebalances,
+            format!("{:.4}", c.score)
+        ]);
+    }
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimize_families_keeps_top_candidates() {
+        let config = OptimizationConfig::new()
+            .with_steps(720)
+            .with_volatility(0.6);
+        let candidates = optimize_families(&config, Decimal::new(10, 2), ObjectiveType::Pnl, 3);
+
+        assert_eq!(candidates.threshold.len(), 3);
+        assert_eq!(candidates.periodic.len(), 3);
+        assert_eq!(candidates.il_limit.len(), 3);
+        assert!(
+            candidates
+                .periodic
+                .windows(2)
+                .all(|w| w[0].score >= w[1].score)
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! Position command implementation.
+//!
+//! Opens, closes and rebalances Orca Whirlpool positions. Every operation is
+//! planned from current on-chain state and printed first; transactions are
+//! only sent with `--execute`, after a confirmation prompt. Opens and
+//! rebalances are refused if the pool price diverges from the Pyth price.
+
+use super::wallet::load_wallet;
+use crate::output::{self, confirm};
+use crate::status;
+use anyhow::{Context, Result};
+use clmm_lp_execution::prelude::*;
+use clmm_lp_protocols::prelude::*;
+use prettytable::{Table, row};
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Arguments for the position command.
+#[derive(Debug, Clone)]
+pub struct PositionArgs {
+    /// Operation to perform.
+    pub action: PositionAction,
+    /// Solana RPC endpoint.
+    pub rpc_url: String,
+    /// Keypair or keystore file signing the transactions.
+    pub keypair: Option<PathBuf>,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+    /// Maximum divergence of the pool price from the Pyth price in basis
+    /// points; `0` disables the check.
+    pub max_oracle_divergence_bps: u32,
+    /// Send the transactions instead of only printing the plan.
+    pub execute: bool,
+    /// Skip the confirmation prompt.
+    pub yes: bool,
+}
+
+/// Position operations.
+#[derive(Debug, Clone)]
+pub enum PositionAction {
+    /// Open a position.
+    Open {
+        /// Pool address.
+        pool: String,
+        /// Lower price (raw token ratio).
+        lower_price: Decimal,
+        /// Upper price (raw token ratio).
+        upper_price: Decimal,
+        /// Maximum token A deposited, in base units.
+        amount_a: u64,
+        /// Maximum token B deposited, in base units.
+        amount_b: u64,
+    },
+    /// Close a position.
+    Close {
+        /// Position address.
+        position: String,
+    },
+    /// Move a position's liquidity into a new range.
+    Rebalance {
+        /// Position address.
+        position: String,
+        /// New lower price (raw token ratio).
+        lower_price: Decimal,
+        /// New upper price (raw token ratio).
+        upper_price: Decimal,
+    },
+}
+
+/// Formats a tick range with its prices.
+fn format_range(range: Option<(i32, i32)>) -> String {
+    match range {
+        Some((lower, upper)) => format!(
+            "[{}, {}] ({:.6} .. {:.6})",
+            lower,
+            upper,
+            tick_to_price(lower),
+            tick_to_price(upper)
+        ),
+        None => "-".to_string(),
+    }
+}
+
+/// Prints a plan.
+fn print_plan(plan: &OperationPlan) {
+    let mut table = Table::new();
+    table.add_row(row!["Operation", plan.kind.as_str()]);
+    table.add_row(row!["Pool", plan.pool]);
+    if let Some(position) = plan.position {
+        table.add_row(row!["Position", position]);
+    }
+    table.add_row(row!["Pool Tick", plan.pool_tick]);
+    table.add_row(row!["Current Range", format_range(plan.current_range)]);
+    table.add_row(row!["New Range", format_range(plan.new_range)]);
+    if plan.position.is_some() {
+        table.add_row(row!["Liquidity", plan.liquidity]);
+        table.add_row(row![
+            "Withdraw (A / B)",
+            format!("{} / {}", plan.withdraw_amounts.0, plan.withdraw_amounts.1)
+        ]);
+        table.add_row(row![
+            "Minimum (A / B)",
+            format!(
+                "{} / {}",
+                plan.withdraw_minimums.0, plan.withdraw_minimums.1
+            )
+        ]);
+        table.add_row(row![
+            "Fees Owed (A / B)",
+            format!("{} / {}", plan.fees_owed.0, plan.fees_owed.1)
+        ]);
+    }
+    if plan.new_range.is_some() {
+        table.add_row(row![
+            "Deposit Max (A / B)",
+            format!("{} / {}", plan.deposit_amounts.0, plan.deposit_amounts.1)
+        ]);
+    }
+    table.add_row(row!["Slippage", format!("{} bps", plan.slippage_bps)]);
+    table.printstd();
+
+    println!("Instructions:");
+    for (i, instruction) in plan.instructions.iter().enumerate() {
+        println!(
+            "  {}. {}::{} {}",
+            i + 1,
+            instruction.program,
+            instruction.instruction,
+            instruction.args
+        );
+    }
+}
+
+/// Converts a plan to JSON for `--output json`.
+pub(crate) fn plan_json(plan: &OperationPlan) -> serde_json::Value {
+    let range = |range: Option<(i32, i32)>| {
+        range.map(|(lower, upper)| {
+            serde_json::json!({
+                "tick_lower": lower,
+                "tick_upper": upper,
+                "price_lower": tick_to_price(lower),
+                "price_upper": tick_to_price(upper),
+            })
+        })
+    };
+    let rewards: Vec<_> = plan
+        .rewards
+        .iter()
+        .map(|r| {
+            serde_json::json!({
+                "index": r.index,
+                "mint": r.mint.to_string(),
+                "amount_owed": r.amount_owed,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "operation": plan.kind.as_str(),
+        "pool": plan.pool.to_string(),
+        "position": plan.position.map(|p| p.to_string()),
+        "pool_tick": plan.pool_tick,
+        "current_range": range(plan.current_range),
+        "new_range": range(plan.new_range),
+        "liquidity": plan.liquidity.to_string(),
+        "withdraw_amounts": [plan.withdraw_amounts.0, plan.withdraw_amounts.1],
+        "withdraw_minimums": [plan.withdraw_minimums.0, plan.withdraw_minimums.1],
+        "fees_owed": [plan.fees_owed.0, plan.fees_owed.1],
+        "rewards": rewards,
+        "deposit_amounts": [plan.deposit_amounts.0, plan.deposit_amounts.1],
+        "slippage_bps": plan.slippage_bps,
+        "instructions": plan.instructions,
+    })
+}
+
+/// Converts a transaction result to JSON for `--output json`.
+pub(crate) fn result_json(result: &ExecutionResult) -> serde_json::Value {
+    serde_json::json!({
+        "signature": result.signature.to_string(),
+        "success": result.success,
+        "slot": result.slot,
+        "error": result.error,
+    })
+}
+
+/// Runs the position command.
+pub async fn run_position(args: PositionArgs) -> Result<()> {
+    let provider = Arc::new(RpcProvider::new(RpcConfig::new(args.rpc_url.clone())));
+    let mut operator = PositionOperator::new(provider.clone()).with_slippage_bps(args.slippage_bps);
+    if args.max_oracle_divergence_bps > 0 {
+        let config = PriceGuardConfig {
+            max_divergence_bps: args.max_oracle_divergence_bps,
+            ..Default::default()
+        };
+        let guard = PriceGuard::new(provider, Arc::new(PythOracle::new()), config);
+        operator = operator.with_price_guard(Arc::new(guard));
+    }
+
+    let plan = match &args.action {
+        PositionAction::Open {
+            pool,
+            lower_price,
+            upper_price,
+            amount_a,
+            amount_b,
+        } => {
+            operator
+                .plan_open(
+                    pool,
+                    price_to_tick(*lower_price),
+                    price_to_tick(*upper_price),
+                    *amount_a,
+                    *amount_b,
+                )
+                .await?
+        }
+        PositionAction::Close { position } => operator.plan_close(position).await?,
+        PositionAction::Rebalance {
+            position,
+            lower_price,
+            upper_price,
+        } => {
+            operator
+                .plan_rebalance(
+                    position,
+                    price_to_tick(*lower_price),
+                    price_to_tick(*upper_price),
+                )
+                .await?
+        }
+    };
+
+    let dry_run_json =
+        || output::print_json(&serde_json::json!({ "plan": plan_json(&plan), "executed": false }));
+    if !output::is_json() {
+        print_plan(&plan);
+    }
+
+    if !args.execute {
+        if output::is_json() {
+            return dry_run_json();
+        }
+        println!("Dry run: nothing was sent. Re-run with --execute to send these transactions.");
+        return Ok(());
+    }
+
+    let keypair = args
+        .keypair
+        .context("--keypair (or CLMM_KEYPAIR) is required with --execute")?;
+    let wallet = load_wallet(&keypair, None)?;
+    status!("Signing with {}", wallet.pubkey());
+
+    if !args.yes && !confirm(&format!("Send the {} transactions?", plan.kind.as_str()))? {
+        status!("Aborted.");
+        return if output::is_json() {
+            dry_run_json()
+        } else {
+            Ok(())
+        };
+    }
+
+    let results = operator.execute(&plan, &wallet).await?;
+    if output::is_json() {
+        output::print_json(&serde_json::json!({
+            "plan": plan_json(&plan),
+            "executed": true,
+            "results": results.iter().map(result_json).collect::<Vec<_>>(),
+        }))?;
+    } else {
+        for result in &results {
+            match &result.error {
+                None => println!("✅ {} (slot {:?})", result.signature, result.slot),
+                Some(error) => println!("❌ {}: {}", result.signature, error),
+            }
+        }
+    }
+    if results.iter().any(|r| !r.success) {
+        anyhow::bail!("{} did not complete", plan.kind.as_str());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! Positions command implementation.
+//!
+//! Lists the Orca Whirlpool and Raydium CLMM positions held by a wallet,
+//! read directly from chain.
+
+use crate::output;
+use anyhow::Result;
+use clmm_lp_protocols::prelude::*;
+use prettytable::{Table, row};
+use std::sync::Arc;
+
+/// Arguments for the positions command.
+#[derive(Debug, Clone)]
+pub struct PositionsArgs {
+    /// Wallet address whose positions are listed.
+    pub wallet: String,
+    /// Solana RPC endpoint.
+    pub rpc_url: String,
+}
+
+/// Returns the display name of a protocol.
+fn protocol_name(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::OrcaWhirlpool => "Orca",
+        Protocol::RaydiumClmm => "Raydium",
+        Protocol::MeteoraDlmm => "Meteora",
+    }
+}
+
+/// Describes whether a position is in range.
+fn range_status(position: &WalletPosition) -> &'static str {
+    match position.in_range() {
+        Some(true) => "In range",
+        Some(false) => "Out of range",
+        None => "Unknown",
+    }
+}
+
+/// Runs the positions command.
+pub async fn run_positions(args: PositionsArgs) -> Result<()> {
+    let provider = Arc::new(RpcProvider::new(RpcConfig::new(args.rpc_url)));
+    let positions = WalletPositionScanner::new(provider)
+        .scan(&args.wallet)
+        .await?;
+
+    if output::is_json() {
+        let positions: Vec<_> = positions
+            .iter()
+            .map(|wallet_position| {
+                let position = &wallet_position.position;
+                serde_json::json!({
+                    "protocol": protocol_name(wallet_position.protocol),
+                    "address": position.address.to_string(),
+                    "pool": position.pool.to_string(),
+                    "tick_lower": position.tick_lower,
+                    "tick_upper": position.tick_upper,
+                    "price_lower": tick_to_price(position.tick_lower),
+                    "price_upper": tick_to_price(position.tick_upper),
+                    "liquidity": position.liquidity.to_string(),
+                    "fees_owed_a": position.fees_owed_a,
+                    "fees_owed_b": position.fees_owed_b,
+                    "in_range": wallet_position.in_range(),
+                })
+            })
+            .collect();
+        return output::print_json(&positions);
+    }
+
+    if positions.is_empty() {
+        println!("No Orca or Raydium positions found for {}", args.wallet);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Protocol",
+        "Position",
+        "Pool",
+        "Tick Range",
+        "Price Range",
+        "Liquidity",
+        "Unclaimed A",
+        "Unclaimed B",
+        "Status"
+    ]);
+    for wallet_position in &positions {
+        let position = &wallet_position.position;
+        table.add_row(row![
+            protocol_name(wallet_position.protocol),
+            position.address,
+            position.pool,
+            format!("{} .. {}", position.tick_lower, position.tick_upper),
+            format!(
+                "{:.6} .. {:.6}",
+                tick_to_price(position.tick_lower),
+                tick_to_price(position.tick_upper)
+            ),
+            position.liquidity,
+            position.fees_owed_a,
+            position.fees_owed_b,
+            range_status(wallet_position),
+        ]);
+    }
+    table.printstd();
+
+    let in_range = positions
+        .iter()
+        .filter(|p| p.in_range() == Some(true))
+        .count();
+    println!("{} positions, {} in range", positions.len(), in_range);
+    println!("Prices are raw token ratios; unclaimed fees are in base units.");
+
+    Ok(())
+}
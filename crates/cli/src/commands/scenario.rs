@@ -0,0 +1,221 @@
+//! Scenario command implementation.
+//!
+//! Runs a range and rebalancing strategy against the named stress
+//! scenarios (crash, pump, chop, depeg) and compares the outcomes, as a
+//! deterministic complement to Monte Carlo simulation.
+
+use super::backtest::StrategyType;
+use super::sweep::{SweepCase, simulate_case};
+use crate::output;
+use anyhow::{Result, bail};
+use clmm_lp_simulation::prelude::*;
+use prettytable::{Table, row};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// Arguments for the scenario command.
+#[derive(Debug, Clone)]
+pub struct ScenarioArgs {
+    /// Entry price of every scenario.
+    pub entry_price: Decimal,
+    /// Lower price bound.
+    pub lower: Decimal,
+    /// Upper price bound.
+    pub upper: Decimal,
+    /// Rebalancing strategy.
+    pub strategy: StrategyType,
+    /// Rebalance interval in hours (periodic strategy).
+    pub interval: u64,
+    /// Price threshold (threshold strategy).
+    pub threshold: Decimal,
+    /// Scenarios to run; the whole library when empty.
+    pub scenarios: Vec<Scenario>,
+    /// Hourly steps per scenario.
+    pub steps: usize,
+    /// Initial capital in USD.
+    pub capital: Decimal,
+    /// Transaction cost per rebalance in USD.
+    pub tx_cost: Decimal,
+}
+
+/// Outcome of one scenario.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioResult {
+    /// Scenario that was run.
+    pub scenario: Scenario,
+    /// Price at the end of the scenario.
+    pub final_price: Decimal,
+    /// Tracker summary at the end of the scenario.
+    pub summary: TrackerSummary,
+}
+
+/// Runs every selected scenario in library order.
+#[must_use]
+pub fn run_scenarios(args: &ScenarioArgs) -> Vec<ScenarioResult> {
+    let scenarios = if args.scenarios.is_empty() {
+        Scenario::library()
+    } else {
+        args.scenarios.clone()
+    };
+    let case = SweepCase {
+        lower: args.lower,
+        upper: args.upper,
+        strategy: args.strategy,
+        interval: matches!(args.strategy, StrategyType::Periodic).then_some(args.interval),
+        threshold: matches!(
+            args.strategy,
+            StrategyType::Threshold | StrategyType::ILLimit
+        )
+        .then_some(args.threshold),
+    };
+
+    scenarios
+        .into_iter()
+        .map(|scenario| {
+            let prices = scenario.price_path(args.entry_price, args.steps);
+            let final_price = prices.last().map_or(args.entry_price, |p| p.value);
+            ScenarioResult {
+                scenario,
+                final_price,
+                summary: simulate_case(&case, &prices, args.capital, args.tx_cost),
+            }
+        })
+        .collect()
+}
+
+/// Runs the scenario command.
+pub async fn run_scenario(args: ScenarioArgs) -> Result<()> {
+    if args.lower <= Decimal::ZERO || args.lower >= args.upper {
+        bail!("the range must satisfy 0 < lower < upper");
+    }
+    if args.steps == 0 {
+        bail!("scenarios need at least one step");
+    }
+
+    let results = run_scenarios(&args);
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "entry_price": args.entry_price,
+            "lower": args.lower,
+            "upper": args.upper,
+            "strategy": args.strategy,
+            "capital": args.capital,
+            "steps": args.steps,
+            "results": results,
+        }));
+    }
+
+    println!();
+    println!("🧪 STRESS SCENARIOS");
+    println!(
+        "   Entry: ${:.4} | Range: ${:.4} - ${:.4} | Strategy: {:?} | Capital: ${} | {} steps",
+        args.entry_price, args.lower, args.upper, args.strategy, args.capital, args.steps
+    );
+    println!();
+
+    let hundred = Decimal::from(100);
+    let mut table = Table::new();
+    table.add_row(row![
+        "Scenario",
+        "Shape",
+        "Final Price",
+        "PnL",
+        "Return",
+        "Fees",
+        "IL",
+        "In Range",
+        "Rebal.",
+        "Max DD",
+        "vs HODL"
+    ]);
+    for result in &results {
+        let summary = &result.summary;
+        let return_pct = if args.capital.is_zero() {
+            Decimal::ZERO
+        } else {
+            summary.final_pnl / args.capital * hundred
+        };
+        table.add_row(row![
+            result.scenario.name(),
+            result.scenario.description(),
+            format!("${:.4}", result.final_price),
+            format!("${:.2}", summary.final_pnl),
+            format!("{:.2}%", return_pct),
+            format!("${:.2}", summary.total_fees),
+            format!("{:.2}%", summary.final_il_pct * hundred),
+            format!("{:.1}%", summary.time_in_range_pct * hundred),
+            summary.rebalance_count,
+            format!("{:.2}%", summary.max_drawdown * hundred),
+            format!("${:.2}", summary.vs_hodl)
+        ]);
+    }
+    table.printstd();
+
+    if let Some(worst) = results.iter().min_by_key(|r| r.summary.final_pnl) {
+        println!();
+        println!(
+            "Worst case: {} (${:.2})",
+            worst.scenario.name(),
+            worst.summary.final_pnl
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> ScenarioArgs {
+        ScenarioArgs {
+            entry_price: Decimal::from(100),
+            lower: Decimal::from(90),
+            upper: Decimal::from(110),
+            strategy: StrategyType::Static,
+            interval: 24,
+            threshold: Decimal::new(5, 2),
+            scenarios: Vec::new(),
+            steps: 96,
+            capital: Decimal::from(1000),
+            tx_cost: Decimal::ONE,
+        }
+    }
+
+    #[test]
+    fn test_run_scenarios_covers_library() {
+        let results = run_scenarios(&args());
+        let names: Vec<_> = results.iter().map(|r| r.scenario.name()).collect();
+        assert_eq!(names, vec!["crash", "pump", "chop", "depeg"]);
+
+        let pnl = |name: &str| {
+            results
+                .iter()
+                .find(|r| r.scenario.name() == name)
+                .unwrap()
+                .summary
+                .final_pnl
+        };
+        // Chop stays in range and earns fees; a crash leaves the range with a loss
+        assert!(pnl("chop") > Decimal::ZERO);
+        assert!(pnl("crash") < pnl("chop"));
+    }
+
+    #[test]
+    fn test_run_selected_scenarios() {
+        let mut args = args();
+        args.scenarios = vec!["pump".parse().unwrap()];
+        let results = run_scenarios(&args);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].final_price, Decimal::from(150));
+    }
+
+    #[test]
+    fn test_scenario_results_serialize() {
+        let mut args = args();
+        args.scenarios = vec!["crash".parse().unwrap()];
+        let json = serde_json::to_value(run_scenarios(&args)).unwrap();
+        assert_eq!(json[0]["scenario"]["name"], "crash");
+        assert!(json[0]["summary"]["final_pnl"].is_string());
+    }
+}
@@ -0,0 +1,182 @@
+//! Pool swap history for backtests.
+//!
+//! Replays the swaps an Orca Whirlpool actually saw over the backtest
+//! window, so fees follow its real per-step volume, its fee rate and the
+//! position's share of its liquidity.
+
+use anyhow::{Context, Result, bail};
+use clmm_lp_domain::enums::Protocol as PoolProtocol;
+use clmm_lp_protocols::prelude::*;
+use clmm_lp_simulation::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Offset of the decimals byte in an SPL mint account.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Fee rate units in one (fee rates are in hundredths of a basis point).
+const FEE_RATE_DENOMINATOR: u32 = 1_000_000;
+
+/// Swaps and liquidity of a pool over a backtest window.
+#[derive(Debug, Clone)]
+pub struct SwapHistory {
+    /// Per-step volume in token B.
+    pub volume: SwapVolume,
+    /// Share of the fees earned by the position while in range.
+    pub fee_share: Decimal,
+    /// Fee rate of the pool.
+    pub fee_rate: Decimal,
+}
+
+/// Backtest window and position whose swap history is loaded.
+#[derive(Debug, Clone)]
+pub struct SwapWindow {
+    /// Unix timestamp of the first step.
+    pub start_time: u64,
+    /// Unix timestamp of the end of the window.
+    pub end_time: u64,
+    /// Length of a step in seconds.
+    pub step_seconds: u64,
+    /// Number of steps.
+    pub steps: usize,
+    /// Position capital in token B.
+    pub capital: f64,
+    /// Position range in token B per token A.
+    pub range: (f64, f64),
+    /// Price of token A in token B when the position opens.
+    pub entry_price: f64,
+}
+
+/// Loads the swaps of a pool over a backtest window.
+///
+/// The position's fee share is its liquidity over the pool's current active
+/// liquidity plus its own, since historical liquidity is not replayed.
+pub async fn load_swap_history(
+    rpc_url: &str,
+    pool: &str,
+    window: &SwapWindow,
+) -> Result<SwapHistory> {
+    let address = Pubkey::from_str(pool).context("Invalid pool address")?;
+    let provider = Arc::new(RpcProvider::new(RpcConfig::new(rpc_url.to_string())));
+    let registry = ProtocolRegistry::with_defaults(provider.clone());
+    let state = registry
+        .adapter_for_account(&address)
+        .await?
+        .fetch_pool_state(&address)
+        .await?;
+    if !matches!(state.protocol, PoolProtocol::OrcaWhirlpools) {
+        bail!("Swap replay only supports Orca Whirlpool pools");
+    }
+
+    let decimals = (
+        mint_decimals(&provider, &state.token_mint_a).await?,
+        mint_decimals(&provider, &state.token_mint_b).await?,
+    );
+    let swaps = EventFetcher::new(provider)
+        .with_config(FetchConfig {
+            batch_size: 1_000,
+            ..Default::default()
+        })
+        .fetch_swaps(pool, window.start_time, window.end_time)
+        .await?;
+
+    let scale = Decimal::from(10u64.pow(u32::from(decimals.1)));
+    let records = swaps.iter().map(|swap| SwapRecord {
+        timestamp: swap.timestamp,
+        volume: Decimal::from(swap.amount_b) / scale,
+    });
+    let volume = SwapVolume::from_swaps(
+        records,
+        window.start_time,
+        window.step_seconds,
+        window.steps,
+    );
+
+    let position = position_liquidity(window, decimals);
+    let fee_share = if position > 0.0 {
+        position / (position + state.liquidity as f64)
+    } else {
+        0.0
+    };
+
+    Ok(SwapHistory {
+        volume,
+        fee_share: Decimal::from_f64(fee_share).unwrap_or(Decimal::ZERO),
+        fee_rate: Decimal::from(state.fee_rate) / Decimal::from(FEE_RATE_DENOMINATOR),
+    })
+}
+
+/// Reads the decimals of a mint.
+async fn mint_decimals(provider: &RpcProvider, mint: &Pubkey) -> Result<u8> {
+    let account = provider.get_account(mint).await?;
+    account
+        .data
+        .get(MINT_DECIMALS_OFFSET)
+        .copied()
+        .with_context(|| format!("Account {} is not a token mint", mint))
+}
+
+/// Calculates the raw liquidity the capital buys in the range at the entry
+/// price.
+fn position_liquidity(window: &SwapWindow, (decimals_a, decimals_b): (u8, u8)) -> f64 {
+    let raw = 10f64.powi(i32::from(decimals_b) - i32::from(decimals_a));
+    let price = window.entry_price * raw;
+    let sqrt_lower = (window.range.0 * raw).sqrt();
+    let sqrt_upper = (window.range.1 * raw).sqrt();
+    if !(sqrt_lower > 0.0 && sqrt_upper > sqrt_lower && price > 0.0) {
+        return 0.0;
+    }
+
+    // Token amounts and their value in token B per unit of liquidity
+    let sqrt_price = price.sqrt().clamp(sqrt_lower, sqrt_upper);
+    let amount_a = 1.0 / sqrt_price - 1.0 / sqrt_upper;
+    let amount_b = sqrt_price - sqrt_lower;
+    let value = amount_a * price + amount_b;
+
+    window.capital * 10f64.powi(i32::from(decimals_b)) / value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(entry_price: f64, range: (f64, f64)) -> SwapWindow {
+        SwapWindow {
+            start_time: 0,
+            end_time: 3600,
+            step_seconds: 3600,
+            steps: 1,
+            capital: 1000.0,
+            range,
+            entry_price,
+        }
+    }
+
+    #[test]
+    fn test_position_liquidity() {
+        // SOL (9 decimals) in USDC (6 decimals)
+        let in_range = position_liquidity(&window(150.0, (140.0, 160.0)), (9, 6));
+        let wide = position_liquidity(&window(150.0, (100.0, 200.0)), (9, 6));
+        assert!(in_range > 0.0);
+        // Concentrating the same capital buys more liquidity
+        assert!(in_range > wide * 3.0);
+
+        // Out of range the position holds a single token
+        assert!(position_liquidity(&window(200.0, (140.0, 160.0)), (9, 6)) > 0.0);
+        assert!(position_liquidity(&window(100.0, (140.0, 160.0)), (9, 6)) > 0.0);
+        assert_eq!(
+            position_liquidity(&window(150.0, (160.0, 140.0)), (9, 6)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_position_liquidity_value() {
+        // One unit of liquidity at price 1 in [0.25, 4] is worth 2 * (1 - 0.5)
+        let liquidity = position_liquidity(&window(1.0, (0.25, 4.0)), (0, 0));
+        assert!((liquidity - 1000.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,560 @@
+//! Parameter sweep command implementation.
+//!
+//! Runs backtests over a grid of ranges, strategies and strategy
+//! parameters against a single price history, in parallel, and
+//! consolidates the results into one comparison table.
+
+use super::backtest::StrategyType;
+use crate::output;
+use crate::status;
+use anyhow::{Context, Result, bail};
+use clmm_lp_data::prelude::*;
+use clmm_lp_domain::prelude::*;
+use clmm_lp_simulation::prelude::*;
+use prettytable::{Table, row};
+use primitive_types::U256;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+/// Arguments for the sweep command.
+#[derive(Debug, Clone)]
+pub struct SweepArgs {
+    /// Token A symbol.
+    pub symbol_a: String,
+    /// Token A mint address.
+    pub mint_a: String,
+    /// Number of days of history.
+    pub days: u64,
+    /// Explicit price ranges as (lower, upper).
+    pub ranges: Vec<(Decimal, Decimal)>,
+    /// Range widths as fractions of the entry price, centered on it.
+    pub widths: Vec<Decimal>,
+    /// Strategies to evaluate.
+    pub strategies: Vec<StrategyType>,
+    /// Rebalance intervals in hours (periodic strategy).
+    pub intervals: Vec<u64>,
+    /// Price thresholds (threshold strategy) or maximum IL (IL-limit strategy).
+    pub thresholds: Vec<Decimal>,
+    /// Initial capital in USD.
+    pub capital: Decimal,
+    /// Transaction cost per rebalance in USD.
+    pub tx_cost: Decimal,
+    /// Number of worker threads.
+    pub jobs: usize,
+    /// CSV file receiving the consolidated results.
+    pub output: Option<PathBuf>,
+    /// Whether to reuse cached results of cases already simulated over the
+    /// same price history.
+    pub use_cache: bool,
+    /// Birdeye API key.
+    pub api_key: Option<String>,
+}
+
+impl Default for SweepArgs {
+    fn default() -> Self {
+        Self {
+            symbol_a: "SOL".to_string(),
+            mint_a: "So11111111111111111111111111111111111111112".to_string(),
+            days: 30,
+            ranges: Vec::new(),
+            widths: vec![Decimal::new(5, 2), Decimal::new(10, 2), Decimal::new(20, 2)],
+            strategies: vec![
+                StrategyType::Static,
+                StrategyType::Periodic,
+                StrategyType::Threshold,
+            ],
+            intervals: vec![24],
+            thresholds: vec![Decimal::new(5, 2)],
+            capital: Decimal::from(1000),
+            tx_cost: Decimal::ONE,
+            jobs: default_jobs(),
+            output: None,
+            use_cache: true,
+            api_key: None,
+        }
+    }
+}
+
+/// Returns the number of worker threads used by default.
+#[must_use]
+pub fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(4, |n| n.get())
+}
+
+/// A single point of the sweep grid.
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepCase {
+    /// Lower price bound.
+    pub lower: Decimal,
+    /// Upper price bound.
+    pub upper: Decimal,
+    /// Rebalancing strategy.
+    pub strategy: StrategyType,
+    /// Rebalance interval in hours, for periodic rebalancing.
+    pub interval: Option<u64>,
+    /// Strategy threshold, for threshold and IL-limit rebalancing.
+    pub threshold: Option<Decimal>,
+}
+
+impl SweepCase {
+    /// Human-readable strategy parameter.
+    fn parameter(&self) -> String {
+        match (self.interval, self.threshold) {
+            (Some(hours), _) => format!("{}h", hours),
+            (_, Some(threshold)) => format!("{}%", (threshold * Decimal::from(100)).normalize()),
+            _ => "-".to_string(),
+        }
+    }
+}
+
+/// Outcome of one sweep case.
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepResult {
+    /// Case that was simulated.
+    pub case: SweepCase,
+    /// Tracker summary at the end of the price path.
+    pub summary: TrackerSummary,
+}
+
+/// Expands the ranges, strategies and parameters into the full grid.
+///
+/// Ranges given as widths are centered on `entry_price`. Parameters only
+/// multiply the strategies that use them.
+#[must_use]
+pub fn build_grid(args: &SweepArgs, entry_price: Decimal) -> Vec<SweepCase> {
+    let ranges = args
+        .ranges
+        .iter()
+        .copied()
+        .chain(args.widths.iter().map(|width| {
+            let half = entry_price * *width / Decimal::TWO;
+            (entry_price - half, entry_price + half)
+        }));
+
+    let mut cases = Vec::new();
+    for (lower, upper) in ranges {
+        if lower <= Decimal::ZERO || lower >= upper {
+            continue;
+        }
+        for strategy in &args.strategies {
+            let case = SweepCase {
+                lower,
+                upper,
+                strategy: *strategy,
+                interval: None,
+                threshold: None,
+            };
+            match strategy {
+                StrategyType::Static => cases.push(case),
+                StrategyType::Periodic => {
+                    cases.extend(args.intervals.iter().map(|hours| SweepCase {
+                        interval: Some(*hours),
+                        ..case.clone()
+                    }))
+                }
+                StrategyType::Threshold | StrategyType::ILLimit => {
+                    cases.extend(args.thresholds.iter().map(|threshold| SweepCase {
+                        threshold: Some(*threshold),
+                        ..case.clone()
+                    }))
+                }
+            }
+        }
+    }
+    cases
+}
+
+/// Simulates one case over the price path.
+#[must_use]
+pub fn simulate_case(
+    case: &SweepCase,
+    prices: &[Price],
+    capital: Decimal,
+    tx_cost: Decimal,
+) -> TrackerSummary {
+    let entry_price = prices.first().copied().unwrap_or(Price::new(Decimal::ONE));
+    let range = PriceRange::new(Price::new(case.lower), Price::new(case.upper));
+    let mut tracker =
+        PositionTracker::new(capital, entry_price, range, tx_cost).with_capacity(prices.len());
+
+    let mut volume_model = ConstantVolume::from_amount(
+        Amount::new(U256::from(1_000_000_000_000u64), 6), // 1M USDC vol per step
+    );
+    let fee_share = Decimal::new(1, 2); // 1% of pool liquidity
+    let fee_rate = Decimal::new(3, 3);
+    let range_width_pct = (case.upper - case.lower) / ((case.upper + case.lower) / Decimal::TWO);
+    let threshold = case.threshold.unwrap_or(Decimal::new(5, 2));
+
+    for price in prices {
+        let in_range = price.value >= tracker.current_range.lower_price.value
+            && price.value <= tracker.current_range.upper_price.value;
+        let step_fees = if in_range {
+            volume_model.next_volume().to_decimal() * fee_share * fee_rate
+        } else {
+            Decimal::ZERO
+        };
+
+        match case.strategy {
+            StrategyType::Static => {
+                tracker.record_step(*price, step_fees, Some(&StaticRange::new()));
+            }
+            StrategyType::Periodic => {
+                let strat = PeriodicRebalance::new(case.interval.unwrap_or(24), range_width_pct);
+                tracker.record_step(*price, step_fees, Some(&strat));
+            }
+            StrategyType::Threshold => {
+                let strat = ThresholdRebalance::new(threshold, range_width_pct);
+                tracker.record_step(*price, step_fees, Some(&strat));
+            }
+            StrategyType::ILLimit => {
+                let strat = ILLimitStrategy::new(threshold, range_width_pct);
+                tracker.record_step(*price, step_fees, Some(&strat));
+            }
+        }
+    }
+
+    tracker.summary()
+}
+
+/// Runs every case on `jobs` worker threads and sorts the results by PnL.
+#[must_use]
+pub fn run_grid(
+    cases: Vec<SweepCase>,
+    prices: &[Price],
+    capital: Decimal,
+    tx_cost: Decimal,
+    jobs: usize,
+) -> Vec<SweepResult> {
+    let chunk_size = cases.len().div_ceil(jobs.max(1)).max(1);
+
+    let mut results: Vec<SweepResult> = std::thread::scope(|scope| {
+        let workers: Vec<_> = cases
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|case| SweepResult {
+                            case: case.clone(),
+                            summary: simulate_case(case, prices, capital, tx_cost),
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("sweep worker panicked"))
+            .collect()
+    });
+
+    rank_results(&mut results);
+    results
+}
+
+/// Sorts results by PnL, best first.
+fn rank_results(results: &mut [SweepResult]) {
+    results.sort_by_key(|r| std::cmp::Reverse(r.summary.final_pnl));
+}
+
+/// Result cache key of a case simulated over a price history.
+fn case_key(
+    data_hash: &str,
+    case: &SweepCase,
+    capital: Decimal,
+    tx_cost: Decimal,
+) -> Result<ResultKey> {
+    let config = content_hash(&serde_json::json!({
+        "case": case,
+        "capital": capital,
+        "tx_cost": tx_cost,
+    }))?;
+    Ok(ResultKey::new("sweep", data_hash, config))
+}
+
+/// Renders the results as CSV.
+#[must_use]
+pub fn results_to_csv(results: &[SweepResult], capital: Decimal) -> String {
+    let mut table = ExportTable::new([
+        "rank",
+        "lower",
+        "upper",
+        "strategy",
+        "parameter",
+        "final_value",
+        "pnl",
+        "return_pct",
+        "fees",
+        "il_pct",
+        "time_in_range_pct",
+        "rebalances",
+        "rebalance_cost",
+        "max_drawdown",
+        "vs_hodl",
+    ]);
+    for (rank, result) in results.iter().enumerate() {
+        let summary = &result.summary;
+        table.push_row(vec![
+            (rank + 1).into(),
+            result.case.lower.into(),
+            result.case.upper.into(),
+            format!("{:?}", result.case.strategy).into(),
+            result.case.parameter().into(),
+            summary.final_value.round_dp(4).into(),
+            summary.final_pnl.round_dp(4).into(),
+            return_pct(summary.final_pnl, capital).into(),
+            summary.total_fees.round_dp(4).into(),
+            (summary.final_il_pct * Decimal::from(100))
+                .round_dp(4)
+                .into(),
+            (summary.time_in_range_pct * Decimal::from(100))
+                .round_dp(2)
+                .into(),
+            summary.rebalance_count.into(),
+            summary.total_rebalance_cost.round_dp(4).into(),
+            summary.max_drawdown.round_dp(4).into(),
+            summary.vs_hodl.round_dp(4).into(),
+        ]);
+    }
+    table.to_csv()
+}
+
+fn return_pct(pnl: Decimal, capital: Decimal) -> Decimal {
+    if capital.is_zero() {
+        Decimal::ZERO
+    } else {
+        (pnl / capital * Decimal::from(100)).round_dp(2)
+    }
+}
+
+/// Runs the sweep command.
+pub async fn run_sweep(args: SweepArgs) -> Result<()> {
+    let api_key = args
+        .api_key
+        .clone()
+        .context("BIRDEYE_API_KEY must be set in .env, environment or config file")?;
+    if args.strategies.is_empty() {
+        bail!("at least one strategy is required");
+    }
+
+    let provider = BirdeyeProvider::new(api_key);
+    let token_a = Token::new(&args.mint_a, &args.symbol_a, 9, &args.symbol_a);
+    let token_b = Token::new(
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "USDC",
+        6,
+        "USD Coin",
+    );
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let start_time = now - (args.days * 24 * 3600);
+
+    status!(
+        "🔍 Fetching historical data for {}/USDC ({} days)...",
+        args.symbol_a,
+        args.days
+    );
+    let candles = provider
+        .get_price_history(&token_a, &token_b, start_time, now, 3600)
+        .await?;
+    if candles.is_empty() {
+        status!("❌ No data found for the specified period.");
+        return Ok(());
+    }
+
+    let prices: Vec<Price> = candles.iter().map(|c| c.close).collect();
+    let entry_price = prices[0].value;
+    let cases = build_grid(&args, entry_price);
+    if cases.is_empty() {
+        bail!("the sweep grid is empty; check --ranges and --widths");
+    }
+
+    // Cases already simulated over the same history are not run again
+    let (capital, tx_cost, jobs) = (args.capital, args.tx_cost, args.jobs);
+    let cache = super::data::result_cache(args.use_cache);
+    let data_hash = content_hash(&candles)?;
+    let mut results = Vec::new();
+    let mut pending = Vec::new();
+    for case in cases {
+        let cached = match &cache {
+            Some(cache) => cache.get(&case_key(&data_hash, &case, capital, tx_cost)?),
+            None => None,
+        };
+        match cached {
+            Some(summary) => results.push(SweepResult { case, summary }),
+            None => pending.push(case),
+        }
+    }
+    if !results.is_empty() {
+        status!(
+            "♻️  Reusing {} cached results (--no-cache to recompute)",
+            results.len()
+        );
+    }
+
+    if !pending.is_empty() {
+        status!(
+            "🚀 Running {} backtests over {} steps on {} threads...",
+            pending.len(),
+            prices.len(),
+            jobs.max(1)
+        );
+        info!(cases = pending.len(), jobs, "Starting sweep");
+
+        let computed =
+            tokio::task::spawn_blocking(move || run_grid(pending, &prices, capital, tx_cost, jobs))
+                .await?;
+        if let Some(cache) = &cache {
+            for result in &computed {
+                cache.put(
+                    &case_key(&data_hash, &result.case, capital, tx_cost)?,
+                    &result.summary,
+                );
+            }
+        }
+        results.extend(computed);
+    }
+    rank_results(&mut results);
+
+    if output::is_json() {
+        output::print_json(&serde_json::json!({
+            "pair": format!("{}/USDC", args.symbol_a),
+            "days": args.days,
+            "entry_price": entry_price,
+            "capital": args.capital,
+            "results": results,
+        }))?;
+    } else {
+        print_results(&args, entry_price, &results);
+    }
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, results_to_csv(&results, args.capital))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        status!("\n💾 Results written to {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn print_results(args: &SweepArgs, entry_price: Decimal, results: &[SweepResult]) {
+    println!();
+    println!("📊 SWEEP RESULTS: {}/USDC", args.symbol_a);
+    println!(
+        "   Period: {} days | Entry: ${:.4} | Capital: ${}",
+        args.days, entry_price, args.capital
+    );
+    println!();
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "#", "Range", "Strategy", "Param", "PnL", "Return", "Fees", "IL", "In Range", "Rebal.",
+        "vs HODL"
+    ]);
+    for (rank, result) in results.iter().enumerate() {
+        let summary = &result.summary;
+        table.add_row(row![
+            rank + 1,
+            format!("${:.2} - ${:.2}", result.case.lower, result.case.upper),
+            format!("{:?}", result.case.strategy),
+            result.case.parameter(),
+            format!("${:.2}", summary.final_pnl),
+            format!("{}%", return_pct(summary.final_pnl, args.capital)),
+            format!("${:.2}", summary.total_fees),
+            format!("{:.2}%", summary.final_il_pct * Decimal::from(100)),
+            format!("{:.1}%", summary.time_in_range_pct * Decimal::from(100)),
+            summary.rebalance_count,
+            format!("${:.2}", summary.vs_hodl)
+        ]);
+    }
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> SweepArgs {
+        SweepArgs {
+            ranges: vec![(Decimal::from(90), Decimal::from(110))],
+            widths: vec![Decimal::new(10, 2)],
+            strategies: vec![
+                StrategyType::Static,
+                StrategyType::Periodic,
+                StrategyType::Threshold,
+            ],
+            intervals: vec![12, 24],
+            thresholds: vec![Decimal::new(3, 2), Decimal::new(5, 2), Decimal::new(10, 2)],
+            ..SweepArgs::default()
+        }
+    }
+
+    #[test]
+    fn test_build_grid_multiplies_only_relevant_parameters() {
+        let cases = build_grid(&args(), Decimal::from(100));
+        // 2 ranges x (1 static + 2 periodic + 3 threshold)
+        assert_eq!(cases.len(), 12);
+        assert_eq!(
+            cases
+                .iter()
+                .filter(|c| matches!(c.strategy, StrategyType::Static))
+                .count(),
+            2
+        );
+        assert!(cases.iter().any(|c| c.lower == Decimal::from(95)
+            && c.upper == Decimal::from(105)
+            && c.interval == Some(12)));
+    }
+
+    #[test]
+    fn test_build_grid_skips_invalid_ranges() {
+        let args = SweepArgs {
+            ranges: vec![(Decimal::from(110), Decimal::from(90))],
+            widths: vec![Decimal::from(3)],
+            strategies: vec![StrategyType::Static],
+            ..SweepArgs::default()
+        };
+        assert!(build_grid(&args, Decimal::from(100)).is_empty());
+    }
+
+    #[test]
+    fn test_run_grid_sorts_by_pnl() {
+        let prices: Vec<Price> = (0..48)
+            .map(|i| Price::new(Decimal::from(100 + (i % 7))))
+            .collect();
+        let cases = build_grid(&args(), Decimal::from(100));
+        let count = cases.len();
+        let results = run_grid(cases, &prices, Decimal::from(1000), Decimal::ONE, 3);
+
+        assert_eq!(results.len(), count);
+        assert!(
+            results
+                .windows(2)
+                .all(|w| w[0].summary.final_pnl >= w[1].summary.final_pnl)
+        );
+
+        let csv = results_to_csv(&results, Decimal::from(1000));
+        assert_eq!(csv.lines().count(), count + 1);
+        assert!(csv.starts_with("rank,lower,upper,strategy"));
+    }
+    #[test]
+    fn test_case_key_covers_case_and_costs() {
+        let cases = build_grid(&args(), Decimal::from(100));
+        let key = |case, capital, tx_cost| {
+            case_key("data", case, Decimal::from(capital), Decimal::from(tx_cost))
+                .unwrap()
+                .cache_key()
+        };
+
+        let keys: std::collections::HashSet<_> =
+            cases.iter().map(|case| key(case, 1000, 1)).collect();
+        assert_eq!(keys.len(), cases.len());
+        assert_eq!(key(&cases[0], 1000, 1), key(&cases[0], 1000, 1));
+        assert_ne!(key(&cases[0], 1000, 1), key(&cases[0], 2000, 1));
+        assert_ne!(key(&cases[0], 1000, 1), key(&cases[0], 1000, 2));
+    }
+}
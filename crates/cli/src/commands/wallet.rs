@@ -0,0 +1,440 @@
+//! Wallet command implementation.
+//!
+//! Creates, imports and inspects the wallets used for live execution.
+//! Keypairs are stored as passphrase-encrypted keystores by default; plain
+//! `solana-keygen` files are supported for compatibility.
+
+use crate::output;
+use anyhow::{Context, Result, bail};
+use clmm_lp_execution::prelude::*;
+use clmm_lp_protocols::prelude::*;
+use prettytable::{Table, row};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the keystore passphrase.
+pub const PASSPHRASE_ENV: &str = "CLMM_WALLET_PASSPHRASE";
+
+/// Lamports per SOL.
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Arguments for the wallet command.
+#[derive(Debug, Clone)]
+pub struct WalletArgs {
+    /// Operation to perform.
+    pub action: WalletAction,
+}
+
+/// Wallet operations.
+#[derive(Debug, Clone)]
+pub enum WalletAction {
+    /// Generate a new keypair.
+    New {
+        /// Wallet label, used for the default file name.
+        label: String,
+        /// Output file; defaults to the wallet directory.
+        output: Option<PathBuf>,
+        /// Write an unencrypted `solana-keygen` file instead of a keystore.
+        plain: bool,
+        /// Overwrite an existing file.
+        force: bool,
+    },
+    /// Encrypt an existing keypair into a keystore.
+    Import {
+        /// Keypair file to import.
+        keypair: Option<PathBuf>,
+        /// Environment variable holding the keypair.
+        from_env: Option<String>,
+        /// Wallet label, used for the default file name.
+        label: String,
+        /// Output file; defaults to the wallet directory.
+        output: Option<PathBuf>,
+        /// Overwrite an existing file.
+        force: bool,
+    },
+    /// Show a wallet's public key and file format.
+    Show {
+        /// Wallet file or label.
+        wallet: String,
+    },
+    /// Show a wallet's SOL and token balances.
+    Balance {
+        /// Wallet file, label or address.
+        wallet: String,
+        /// Solana RPC endpoint.
+        rpc_url: String,
+    },
+}
+
+/// Format of a wallet file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletFileKind {
+    /// Passphrase-encrypted keystore.
+    Keystore,
+    /// Unencrypted `solana-keygen` byte array.
+    Keypair,
+}
+
+impl WalletFileKind {
+    /// Returns the display name.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keystore => "encrypted keystore",
+            Self::Keypair => "plain keypair",
+        }
+    }
+}
+
+/// Detects the format of a wallet file from its contents.
+///
+/// # Errors
+/// Returns an error if the contents are neither a keystore nor a keypair.
+pub fn wallet_file_kind(contents: &str) -> Result<WalletFileKind> {
+    match serde_json::from_str::<serde_json::Value>(contents)
+        .context("Wallet file is not valid JSON")?
+    {
+        serde_json::Value::Object(_) => Ok(WalletFileKind::Keystore),
+        serde_json::Value::Array(_) => Ok(WalletFileKind::Keypair),
+        _ => bail!("Wallet file is neither a keystore nor a keypair"),
+    }
+}
+
+/// Returns the directory holding wallets created by the CLI.
+fn wallet_dir() -> Result<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(".clmm-lp").join("wallets"))
+        .context("Could not determine the home directory")
+}
+
+/// Resolves a wallet argument to a file: an existing path, or a label in the
+/// wallet directory.
+fn wallet_path(wallet: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(wallet);
+    if path.exists() {
+        return Ok(path);
+    }
+    let labelled = wallet_dir()?.join(format!("{}.json", wallet));
+    if labelled.exists() {
+        return Ok(labelled);
+    }
+    bail!(
+        "No wallet file at {} or {}",
+        path.display(),
+        labelled.display()
+    )
+}
+
+/// Reads the keystore passphrase from the environment or a hidden prompt.
+///
+/// New passphrases are prompted for twice.
+fn read_passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+
+    let passphrase = rpassword::prompt_password("Keystore passphrase: ")?;
+    if confirm {
+        if passphrase.is_empty() {
+            bail!("Passphrase must not be empty");
+        }
+        let repeated = rpassword::prompt_password("Repeat passphrase: ")?;
+        if passphrase != repeated {
+            bail!("Passphrases do not match");
+        }
+    }
+    Ok(passphrase)
+}
+
+/// Loads a signing wallet from a keystore or plain keypair file.
+///
+/// Keystores are decrypted with `passphrase`, falling back to
+/// `CLMM_WALLET_PASSPHRASE` and then a prompt.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or decrypted.
+pub fn load_wallet(path: &Path, passphrase: Option<&str>) -> Result<Wallet> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read wallet {}", path.display()))?;
+
+    match wallet_file_kind(&contents)? {
+        WalletFileKind::Keypair => Wallet::from_file(path, "cli"),
+        WalletFileKind::Keystore => {
+            let passphrase = match passphrase {
+                Some(passphrase) => passphrase.to_string(),
+                None => read_passphrase(false)?,
+            };
+            Wallet::from_encrypted_file(path, &passphrase, "cli")
+        }
+    }
+}
+
+/// Returns the public key of a wallet file without decrypting it.
+fn wallet_pubkey(path: &Path) -> Result<(Pubkey, WalletFileKind)> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read wallet {}", path.display()))?;
+
+    let kind = wallet_file_kind(&contents)?;
+    let pubkey = match kind {
+        WalletFileKind::Keystore => Keystore::load(path)?.pubkey()?,
+        WalletFileKind::Keypair => Wallet::from_file(path, "cli")?.pubkey(),
+    };
+    Ok((pubkey, kind))
+}
+
+/// Refuses to overwrite an existing file unless forced.
+fn ensure_writable(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        bail!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Writes a secret file readable only by the current user.
+fn write_secret_file(path: &Path, contents: &str, force: bool) -> Result<()> {
+    ensure_writable(path, force)?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+/// Encrypts a keypair and saves it as a keystore.
+fn save_keystore(keypair: &Keypair, path: &Path, force: bool) -> Result<()> {
+    ensure_writable(path, force)?;
+    let passphrase = read_passphrase(true)?;
+    let keystore = Keystore::encrypt(keypair, &passphrase)?;
+    write_secret_file(path, &serde_json::to_string_pretty(&keystore)?, force)
+}
+
+/// Serializes a keypair in the `solana-keygen` format.
+fn keypair_json(keypair: &Keypair) -> Result<String> {
+    Ok(serde_json::to_string(&keypair.to_bytes().to_vec())?)
+}
+
+/// Runs the wallet command.
+pub async fn run_wallet(args: WalletArgs) -> Result<()> {
+    match args.action {
+        WalletAction::New {
+            label,
+            output,
+            plain,
+            force,
+        } => {
+            let path = match output {
+                Some(path) => path,
+                None => wallet_dir()?.join(format!("{}.json", label)),
+            };
+            let keypair = Keypair::new();
+
+            if plain {
+                write_secret_file(&path, &keypair_json(&keypair)?, force)?;
+            } else {
+                save_keystore(&keypair, &path, force)?;
+            }
+
+            if output::is_json() {
+                return output::print_json(&serde_json::json!({
+                    "label": label,
+                    "address": keypair.pubkey().to_string(),
+                    "file": path,
+                    "encrypted": !plain,
+                }));
+            }
+            println!("Created wallet '{}'", label);
+            println!("  Address: {}", keypair.pubkey());
+            println!("  File:    {}", path.display());
+            if plain {
+                println!("The keypair is unencrypted; keep the file private.");
+            }
+        }
+        WalletAction::Import {
+            keypair,
+            from_env,
+            label,
+            output,
+            force,
+        } => {
+            let wallet = match (keypair, from_env) {
+                (Some(path), _) => Wallet::from_file(&path, &label)?,
+                (None, Some(var)) => Wallet::from_env(&var, &label)?,
+                (None, None) => bail!("Pass a keypair file or --from-env"),
+            };
+            let keypair = wallet
+                .keypair()
+                .context("Only local keypairs can be imported")?;
+            let path = match output {
+                Some(path) => path,
+                None => wallet_dir()?.join(format!("{}.json", label)),
+            };
+
+            save_keystore(keypair, &path, force)?;
+
+            if output::is_json() {
+                return output::print_json(&serde_json::json!({
+                    "label": label,
+                    "address": wallet.pubkey().to_string(),
+                    "file": path,
+                    "encrypted": true,
+                }));
+            }
+            println!("Imported wallet '{}'", label);
+            println!("  Address: {}", wallet.pubkey());
+            println!("  File:    {}", path.display());
+        }
+        WalletAction::Show { wallet } => {
+            let path = wallet_path(&wallet)?;
+            let (pubkey, kind) = wallet_pubkey(&path)?;
+
+            if output::is_json() {
+                let keystore = match kind {
+                    WalletFileKind::Keystore => Some(Keystore::load(&path)?),
+                    WalletFileKind::Keypair => None,
+                };
+                return output::print_json(&serde_json::json!({
+                    "address": pubkey.to_string(),
+                    "file": path,
+                    "format": kind.as_str(),
+                    "cipher": keystore.as_ref().map(|k| k.cipher.clone()),
+                }));
+            }
+            println!("Address: {}", pubkey);
+            println!("File:    {}", path.display());
+            println!("Format:  {}", kind.as_str());
+            if kind == WalletFileKind::Keystore {
+                let keystore = Keystore::load(&path)?;
+                println!(
+                    "Cipher:  {} (scrypt log_n={}, r={}, p={})",
+                    keystore.cipher, keystore.kdf.log_n, keystore.kdf.r, keystore.kdf.p
+                );
+            }
+        }
+        WalletAction::Balance { wallet, rpc_url } => {
+            let owner = match wallet.parse::<Pubkey>() {
+                Ok(pubkey) => pubkey,
+                Err(_) => wallet_pubkey(&wallet_path(&wallet)?)?.0,
+            };
+
+            let provider = RpcProvider::new(RpcConfig::new(rpc_url));
+            let lamports = provider.get_balance(&owner).await?;
+            let balances = find_token_balances(&provider, &owner).await?;
+
+            if output::is_json() {
+                let tokens: Vec<_> = balances
+                    .iter()
+                    .map(|balance| {
+                        serde_json::json!({
+                            "mint": balance.mint.to_string(),
+                            "account": balance.account.to_string(),
+                            "amount": balance.amount,
+                            "decimals": balance.decimals,
+                            "ui_amount": balance.ui_amount(),
+                        })
+                    })
+                    .collect();
+                return output::print_json(&serde_json::json!({
+                    "address": owner.to_string(),
+                    "sol": lamports as f64 / LAMPORTS_PER_SOL,
+                    "tokens": tokens,
+                }));
+            }
+
+            println!("Address: {}", owner);
+            println!("SOL:     {:.9}", lamports as f64 / LAMPORTS_PER_SOL);
+
+            if balances.is_empty() {
+                println!("No token balances.");
+                return Ok(());
+            }
+
+            let mut table = Table::new();
+            table.add_row(row!["Mint", "Amount", "Decimals", "Token Account"]);
+            for balance in &balances {
+                table.add_row(row![
+                    balance.mint,
+                    balance
+                        .ui_amount()
+                        .map_or_else(|| balance.amount.to_string(), |amount| amount.to_string()),
+                    balance
+                        .decimals
+                        .map_or_else(|| "?".to_string(), |d| d.to_string()),
+                    balance.account
+                ]);
+            }
+            table.printstd();
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("clmm-wallet-{}-{}", uuid::Uuid::new_v4(), name))
+    }
+
+    #[test]
+    fn test_wallet_file_kind() {
+        assert_eq!(
+            wallet_file_kind("[1, 2, 3]").unwrap(),
+            WalletFileKind::Keypair
+        );
+        assert_eq!(
+            wallet_file_kind(r#"{"version": 1}"#).unwrap(),
+            WalletFileKind::Keystore
+        );
+        assert!(wallet_file_kind("\"key\"").is_err());
+        assert!(wallet_file_kind("not json").is_err());
+    }
+
+    #[test]
+    fn test_load_wallet_round_trips() {
+        let keypair = Keypair::new();
+        let expected = keypair.pubkey();
+
+        let plain = temp_path("plain.json");
+        write_secret_file(&plain, &keypair_json(&keypair).unwrap(), false).unwrap();
+        assert!(write_secret_file(&plain, "[]", false).is_err());
+        assert_eq!(load_wallet(&plain, None).unwrap().pubkey(), expected);
+
+        let encrypted = temp_path("keystore.json");
+        let params = KdfParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        };
+        let keystore = Keystore::encrypt_with_params(&keypair, "secret", params).unwrap();
+        keystore.save(&encrypted).unwrap();
+        assert_eq!(wallet_pubkey(&encrypted).unwrap().0, expected);
+        assert_eq!(
+            load_wallet(&encrypted, Some("secret")).unwrap().pubkey(),
+            expected
+        );
+        assert!(load_wallet(&encrypted, Some("wrong")).is_err());
+
+        fs::remove_file(plain).ok();
+        fs::remove_file(encrypted).ok();
+    }
+}
@@ -0,0 +1,113 @@
+//! CLI configuration.
+//!
+//! Settings come from the shared layered configuration: `--config` (or
+//! `CLMM_CONFIG`), falling back to `~/.clmm-lp/config.toml` when it exists,
+//! then environment variables. A profile overlays its sections on the
+//! file's base sections:
+//!
+//! ```toml
+//! default_profile = "mainnet"
+//!
+//! [providers]
+//! birdeye_api_key = "..."
+//!
+//! [profiles.mainnet.rpc]
+//! url = "https://api.mainnet-beta.solana.com"
+//!
+//! [profiles.mainnet.api]
+//! url = "https://lp.example.com/api/v1"
+//! key = "clmm_..."
+//!
+//! [profiles.mainnet.execution]
+//! keypair = "~/.config/solana/id.json"
+//!
+//! [profiles.mainnet.cli]
+//! symbol_a = "SOL"
+//! mint_a = "So11111111111111111111111111111111111111112"
+//! capital = 5000.0
+//! ```
+//!
+//! Configured values only fill in arguments left at their defaults: flags
+//! and the arguments' own environment variables always take precedence.
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use clmm_lp_config::prelude::{AppConfig, ConfigLoader};
+use std::path::Path;
+
+/// Loads the configuration file and profile, layered with the environment.
+pub fn load(path: Option<&Path>, profile: Option<&str>) -> Result<AppConfig> {
+    ConfigLoader::new()
+        .with_file(path)
+        .with_profile(profile)
+        .load()
+        .context("Failed to load configuration")
+}
+
+/// Checks whether an argument was set by a flag or environment variable.
+fn is_explicit(matches: &ArgMatches, id: &str) -> bool {
+    matches!(
+        matches.value_source(id),
+        Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+    )
+}
+
+/// Replaces an argument left at its default with a configured value.
+pub fn overlay<T: Clone>(matches: &ArgMatches, id: &str, field: &mut T, value: Option<&T>) {
+    if let Some(value) = value
+        && !is_explicit(matches, id)
+    {
+        *field = value.clone();
+    }
+}
+
+/// Replaces an unset optional argument with a configured value.
+pub fn overlay_option<T: Clone>(
+    matches: &ArgMatches,
+    id: &str,
+    field: &mut Option<T>,
+    value: Option<&T>,
+) {
+    if value.is_some() && !is_explicit(matches, id) {
+        *field = value.cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, Command};
+
+    #[test]
+    fn test_overlay_keeps_explicit_arguments() {
+        let command = Command::new("test")
+            .arg(Arg::new("rpc_url").long("rpc-url").default_value("default"))
+            .arg(Arg::new("symbol").long("symbol"));
+
+        let matches = command
+            .clone()
+            .get_matches_from(["test", "--rpc-url", "explicit"]);
+        let mut rpc_url = "explicit".to_string();
+        overlay(
+            &matches,
+            "rpc_url",
+            &mut rpc_url,
+            Some(&"config".to_string()),
+        );
+        assert_eq!(rpc_url, "explicit");
+
+        let matches = command.get_matches_from(["test"]);
+        let mut rpc_url = "default".to_string();
+        overlay(
+            &matches,
+            "rpc_url",
+            &mut rpc_url,
+            Some(&"config".to_string()),
+        );
+        assert_eq!(rpc_url, "config");
+        let mut symbol = None;
+        overlay_option(&matches, "symbol", &mut symbol, Some(&"SOL".to_string()));
+        assert_eq!(symbol.as_deref(), Some("SOL"));
+    }
+}
@@ -1,20 +1,22 @@
 //! Command Line Interface for the CLMM Liquidity Provider.
 
 pub mod commands;
+pub mod config;
 pub mod output;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use clmm_lp_config::prelude::AppConfig;
 use clmm_lp_data::prelude::*;
 use clmm_lp_domain::prelude::*;
 use clmm_lp_optimization::prelude::*;
 use clmm_lp_simulation::prelude::*;
+use config::{overlay, overlay_option};
 use dotenv::dotenv;
 use prettytable::{Table, row};
 use primitive_types::U256;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
-use std::env;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::info;
 use uuid::Uuid;
@@ -23,6 +25,24 @@ use uuid::Uuid;
 #[command(name = "clmm-lp-cli")]
 #[command(about = "CLMM Liquidity Provider Strategy Optimizer CLI", long_about = None)]
 struct Cli {
+    /// Configuration file (defaults to ~/.clmm-lp/config.toml)
+    #[arg(long, global = true, env = "CLMM_CONFIG")]
+    config: Option<std::path::PathBuf>,
+
+    /// Configuration profile to use
+    #[arg(long, global = true, env = "CLMM_PROFILE")]
+    profile: Option<String>,
+
+    /// Print results as tables or as one JSON document
+    #[arg(
+        id = "output_mode",
+        long = "output",
+        global = true,
+        value_enum,
+        default_value_t = output::OutputMode::Table
+    )]
+    output: output::OutputMode,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -38,6 +58,32 @@ enum OptimizationObjectiveArg {
     Sharpe,
 }
 
+/// Volatility estimator for the optimizer.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum VolatilityModelArg {
+    /// Standard deviation of hourly log returns
+    Stdev,
+    /// Exponentially weighted moving average, weighting recent returns more
+    Ewma,
+    /// GARCH(1,1) forecast over the optimization horizon
+    Garch,
+}
+
+impl VolatilityModelArg {
+    /// Builds the estimator, forecasting GARCH over `horizon_hours`.
+    fn estimator(self, horizon_hours: usize) -> VolatilityEstimator {
+        match self {
+            Self::Stdev => VolatilityEstimator::Stdev,
+            Self::Ewma => VolatilityEstimator::Ewma {
+                lambda: DEFAULT_EWMA_LAMBDA,
+            },
+            Self::Garch => VolatilityEstimator::Garch {
+                horizon: horizon_hours,
+            },
+        }
+    }
+}
+
 /// Rebalancing strategy for backtest.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 enum StrategyArg {
@@ -50,6 +96,29 @@ enum StrategyArg {
     Threshold,
 }
 
+impl StrategyArg {
+    fn strategy_type(self) -> commands::backtest::StrategyType {
+        match self {
+            Self::Static => commands::backtest::StrategyType::Static,
+            Self::Periodic => commands::backtest::StrategyType::Periodic,
+            Self::Threshold => commands::backtest::StrategyType::Threshold,
+        }
+    }
+}
+
+/// Parses a `LOWER:UPPER` price range.
+fn parse_range(value: &str) -> Result<(Decimal, Decimal), String> {
+    let (lower, upper) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected LOWER:UPPER, got '{}'", value))?;
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<Decimal>()
+            .map_err(|e| format!("invalid price '{}': {}", s, e))
+    };
+    Ok((parse(lower)?, parse(upper)?))
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Fetch recent market data
@@ -107,6 +176,203 @@ enum Commands {
         /// Transaction cost per rebalance in USD
         #[arg(long, default_value_t = 1.0)]
         tx_cost: f64,
+
+        /// Save the report to a file
+        #[arg(long)]
+        export: Option<std::path::PathBuf>,
+
+        /// Export format (inferred from the file extension by default)
+        #[arg(long, value_enum, requires = "export")]
+        format: Option<output::ExportFormat>,
+
+        /// Render equity, range and drawdown charts next to the export
+        #[arg(long, value_enum, requires = "export")]
+        charts: Option<output::ImageFormat>,
+
+        /// Replay candles stored in the database instead of fetching them from Birdeye
+        #[arg(long)]
+        replay: bool,
+
+        /// Pool whose stored candles are replayed (defaults to a stored pool of the pair)
+        /// or whose swaps are replayed with --swaps
+        #[arg(long)]
+        pool: Option<String>,
+
+        /// Earn fees on the pool's on-chain swap volume instead of a constant 1M USDC per step
+        #[arg(long, requires = "pool")]
+        swaps: bool,
+
+        /// Solana RPC endpoint used by --swaps
+        #[arg(
+            long,
+            env = "SOLANA_RPC_URL",
+            default_value = "https://api.mainnet-beta.solana.com"
+        )]
+        rpc_url: String,
+
+        /// Database connection URL used by --replay, --save and to save interrupted runs
+        #[arg(
+            long,
+            env = "DATABASE_URL",
+            default_value = "postgres://localhost/clmm_lp"
+        )]
+        database_url: String,
+
+        /// Write a run manifest (data window, provider, config hash) to this file
+        #[arg(long)]
+        manifest: Option<std::path::PathBuf>,
+
+        /// Recompute instead of reusing a cached result for the same data and configuration
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Save the simulation to the database, streaming its equity curve while it runs
+        #[arg(long)]
+        save: bool,
+    },
+    /// Backtest a grid of ranges, strategies and parameters in parallel
+    Sweep {
+        /// Token A Symbol (e.g., SOL)
+        #[arg(short, long, default_value = "SOL")]
+        symbol_a: String,
+
+        /// Token A Mint Address
+        #[arg(long, default_value = "So11111111111111111111111111111111111111112")]
+        mint_a: String,
+
+        /// Days of history to backtest
+        #[arg(short, long, default_value_t = 30)]
+        days: u64,
+
+        /// Explicit price ranges as LOWER:UPPER (comma separated)
+        #[arg(long, value_delimiter = ',', value_parser = parse_range)]
+        ranges: Vec<(Decimal, Decimal)>,
+
+        /// Range widths as fractions of the entry price (comma separated)
+        #[arg(long, value_delimiter = ',', default_value = "0.05,0.1,0.2")]
+        widths: Vec<Decimal>,
+
+        /// Rebalancing strategies to compare (comma separated)
+        #[arg(
+            long,
+            value_enum,
+            value_delimiter = ',',
+            default_value = "static,periodic,threshold"
+        )]
+        strategies: Vec<StrategyArg>,
+
+        /// Rebalance intervals in hours for the periodic strategy (comma separated)
+        #[arg(long, value_delimiter = ',', default_value = "24")]
+        intervals: Vec<u64>,
+
+        /// Price threshold percentages for the threshold strategy (comma separated)
+        #[arg(long, value_delimiter = ',', default_value = "0.05")]
+        thresholds: Vec<Decimal>,
+
+        /// Initial capital in USD
+        #[arg(long, default_value_t = 1000.0)]
+        capital: f64,
+
+        /// Transaction cost per rebalance in USD
+        #[arg(long, default_value_t = 1.0)]
+        tx_cost: f64,
+
+        /// Number of backtests run in parallel (defaults to the CPU count)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Write the consolidated results to a CSV file
+        #[arg(short, long = "out")]
+        output: Option<std::path::PathBuf>,
+
+        /// Recompute instead of reusing a cached result for the same data and configuration
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Stress-test a range and strategy against named scenarios (crash, pump, chop, depeg)
+    Scenario {
+        /// Entry price of every scenario
+        #[arg(long)]
+        price: f64,
+
+        /// Lower price bound (defaults to --width around the entry price)
+        #[arg(long, requires = "upper")]
+        lower: Option<f64>,
+
+        /// Upper price bound (defaults to --width around the entry price)
+        #[arg(long, requires = "lower")]
+        upper: Option<f64>,
+
+        /// Range width as a fraction of the entry price, used without --lower/--upper
+        #[arg(long, default_value_t = 0.2)]
+        width: f64,
+
+        /// Rebalancing strategy
+        #[arg(long, value_enum, default_value_t = StrategyArg::Static)]
+        strategy: StrategyArg,
+
+        /// Rebalance interval in hours (for periodic strategy)
+        #[arg(long, default_value_t = 24)]
+        rebalance_interval: u64,
+
+        /// Price threshold percentage for rebalance (for threshold strategy)
+        #[arg(long, default_value_t = 0.05)]
+        threshold_pct: f64,
+
+        /// Scenarios to run (comma separated; default: all)
+        #[arg(long, value_delimiter = ',')]
+        scenarios: Vec<Scenario>,
+
+        /// Hourly steps per scenario
+        #[arg(long, default_value_t = 168)]
+        hours: usize,
+
+        /// Initial capital in USD
+        #[arg(long, default_value_t = 1000.0)]
+        capital: f64,
+
+        /// Transaction cost per rebalance in USD
+        #[arg(long, default_value_t = 1.0)]
+        tx_cost: f64,
+    },
+    /// Compare saved simulations and inline configurations side by side
+    Compare {
+        /// Saved simulation IDs (or prefixes) and inline runs as
+        /// LOWER:UPPER[@STRATEGY[:PARAM]], e.g. 90:110@periodic:12
+        #[arg(required = true, num_args = 2..)]
+        runs: Vec<String>,
+
+        /// Token A Symbol (e.g., SOL)
+        #[arg(short, long, default_value = "SOL")]
+        symbol_a: String,
+
+        /// Token A Mint Address
+        #[arg(long, default_value = "So11111111111111111111111111111111111111112")]
+        mint_a: String,
+
+        /// Days of history for inline runs and reruns
+        #[arg(short, long, default_value_t = 30)]
+        days: u64,
+
+        /// Initial capital in USD for inline runs
+        #[arg(long, default_value_t = 1000.0)]
+        capital: f64,
+
+        /// Transaction cost per rebalance in USD for inline runs
+        #[arg(long, default_value_t = 1.0)]
+        tx_cost: f64,
+
+        /// Rerun saved simulations on fresh history instead of loading their results
+        #[arg(long)]
+        rerun: bool,
+
+        /// Database holding saved simulations
+        #[arg(
+            long,
+            env = "DATABASE_URL",
+            default_value = "postgres://localhost/clmm_lp"
+        )]
+        database_url: String,
     },
     /// Optimize price range for LP position
     Optimize {
@@ -130,17 +396,49 @@ enum Commands {
         #[arg(long, value_enum, default_value_t = OptimizationObjectiveArg::Pnl)]
         objective: OptimizationObjectiveArg,
 
+        /// Volatility estimator applied to the price history
+        #[arg(long, value_enum, default_value_t = VolatilityModelArg::Stdev)]
+        volatility_model: VolatilityModelArg,
+
         /// Number of Monte Carlo iterations
         #[arg(long, default_value_t = 100)]
         iterations: usize,
+
+        /// Seed of the Monte Carlo price paths (random by default, recorded in the manifest)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Write a run manifest (data window, provider, seed, config hash) to this file
+        #[arg(long)]
+        manifest: Option<std::path::PathBuf>,
+
+        /// Save the report to a file
+        #[arg(long)]
+        export: Option<std::path::PathBuf>,
+
+        /// Export format (inferred from the file extension by default)
+        #[arg(long, value_enum, requires = "export")]
+        format: Option<output::ExportFormat>,
+
+        /// Resume an interrupted optimization saved as this job ID
+        #[arg(long)]
+        resume: Option<Uuid>,
+
+        /// Database connection URL where interrupted runs are saved
+        #[arg(
+            long,
+            env = "DATABASE_URL",
+            default_value = "postgres://localhost/clmm_lp"
+        )]
+        database_url: String,
+
+        /// Recompute instead of reusing a cached result for the same data, configuration
+        /// and --seed
+        #[arg(long)]
+        no_cache: bool,
     },
-    /// Database management commands
-    Db {
-        #[command(subcommand)]
-        action: DbAction,
-    },
-    /// Analyze a token pair's historical data
-    Analyze {
+    /// Optimize threshold, periodic and IL-limit rebalancing parameters
+    OptimizeParams {
         /// Token A Symbol (e.g., SOL)
         #[arg(short, long, default_value = "SOL")]
         symbol_a: String,
@@ -149,222 +447,1474 @@ enum Commands {
         #[arg(long, default_value = "So11111111111111111111111111111111111111112")]
         mint_a: String,
 
-        /// Days of history to analyze
+        /// Days of history to analyze for volatility
         #[arg(short, long, default_value_t = 30)]
         days: u64,
-    },
-}
 
-/// Database management actions.
-#[derive(Subcommand)]
-enum DbAction {
-    /// Initialize the database with migrations
-    Init,
-    /// Show database connection status
-    Status,
-    /// List recent simulations
-    ListSimulations {
-        /// Maximum number of results
-        #[arg(short, long, default_value_t = 10)]
-        limit: i64,
+        /// Days the strategies are evaluated over
+        #[arg(long, default_value_t = 30)]
+        horizon: u64,
+
+        /// Range width as a fraction of price
+        #[arg(long, default_value = "0.1")]
+        range_width: Decimal,
+
+        /// Transaction cost per rebalance in USD
+        #[arg(long, default_value_t = 1.0)]
+        tx_cost: f64,
+
+        /// Optimization objective
+        #[arg(long, value_enum, default_value_t = OptimizationObjectiveArg::Pnl)]
+        objective: OptimizationObjectiveArg,
+
+        /// Number of candidates shown per strategy family
+        #[arg(long, default_value_t = 5)]
+        top: usize,
     },
-    /// List recent optimizations
-    ListOptimizations {
-        /// Maximum number of results
-        #[arg(short, long, default_value_t = 10)]
-        limit: i64,
+    /// Database management commands
+    #[cfg(feature = "postgres")]
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
     },
-}
+    /// Move market data between files and the database
+    #[cfg(feature = "postgres")]
+    Data {
+        /// Database connection URL
+        #[arg(
+            long,
+            env = "DATABASE_URL",
+            default_value = "postgres://localhost/clmm_lp"
+        )]
+        database_url: String,
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    dotenv().ok();
-    tracing_subscriber::fmt::init();
+        #[command(subcommand)]
+        action: DataAction,
+    },
+    /// Emergency controls for automated execution
+    Emergency {
+        /// Base URL of the API server
+        #[arg(
+            long,
+            env = "CLMM_API_URL",
+            default_value = "http://localhost:8080/api/v1"
+        )]
+        api_url: String,
 
-    let cli = Cli::parse();
+        /// API key sent in the X-API-Key header
+        #[arg(long, env = "CLMM_API_KEY")]
+        api_key: Option<String>,
 
-    match &cli.command {
-        Commands::MarketData {
-            symbol_a,
-            mint_a,
-            hours,
-        } => {
-            let api_key = env::var("BIRDEYE_API_KEY")
-                .expect("BIRDEYE_API_KEY must be set in .env or environment");
+        #[command(subcommand)]
+        action: EmergencyAction,
+    },
+    /// Manage custom alert rules on the API server
+    Alerts {
+        /// Base URL of the API server
+        #[arg(
+            long,
+            env = "CLMM_API_URL",
+            default_value = "http://localhost:8080/api/v1"
+        )]
+        api_url: String,
 
-            info!("📡 Initializing Birdeye Provider...");
-            let provider = BirdeyeProvider::new(api_key);
+        /// API key sent in the X-API-Key header
+        #[arg(long, env = "CLMM_API_KEY")]
+        api_key: Option<String>,
 
-            // Define Tokens (Token B assumed USDC for this demo)
-            let token_a = Token::new(mint_a, symbol_a, 9, symbol_a);
-            let token_b = Token::new(
-                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
-                "USDC",
-                6,
-                "USD Coin",
-            );
+        #[command(subcommand)]
+        action: AlertAction,
+    },
+    /// Download a strategy's dry-run transcript from the API server
+    Transcript {
+        /// Base URL of the API server
+        #[arg(
+            long,
+            env = "CLMM_API_URL",
+            default_value = "http://localhost:8080/api/v1"
+        )]
+        api_url: String,
 
-            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-            let start_time = now - (hours * 3600);
+        /// API key sent in the X-API-Key header
+        #[arg(long, env = "CLMM_API_KEY")]
+        api_key: Option<String>,
 
-            info!(
-                "🔍 Fetching data for {}/USDC from {} to {}...",
-                symbol_a, start_time, now
-            );
+        /// Strategy ID
+        strategy_id: String,
 
-            // Fetch 1-hour candles
-            let candles = provider
-                .get_price_history(
-                    &token_a, &token_b, start_time, now, 3600, // 1h resolution
-                )
-                .await?;
+        /// Maximum number of steps to fetch
+        #[arg(short, long, default_value_t = 100)]
+        limit: usize,
 
-            println!("✅ Fetched {} candles:", candles.len());
-            println!();
+        /// Write the full transcript as JSON to this file
+        #[arg(short, long = "out")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Export and import strategy definitions as YAML
+    Strategy {
+        /// Base URL of the API server
+        #[arg(
+            long,
+            env = "CLMM_API_URL",
+            default_value = "http://localhost:8080/api/v1"
+        )]
+        api_url: String,
 
-            let mut table = Table::new();
-            table.add_row(row!["Time", "Open", "High", "Low", "Close"]);
+        /// API key sent in the X-API-Key header
+        #[arg(long, env = "CLMM_API_KEY")]
+        api_key: Option<String>,
 
-            for candle in candles {
-                let datetime = chrono::DateTime::from_timestamp(candle.start_timestamp as i64, 0)
-                    .unwrap_or_default();
-                table.add_row(row![
-                    datetime.format("%Y-%m-%d %H:%M"),
-                    format!("{:.4}", candle.open.value),
-                    format!("{:.4}", candle.high.value),
-                    format!("{:.4}", candle.low.value),
-                    format!("{:.4}", candle.close.value)
-                ]);
-            }
-            table.printstd();
-        }
-        Commands::Backtest {
-            symbol_a,
-            mint_a,
-            days,
-            lower,
-            upper,
-            capital,
-            strategy,
-            rebalance_interval,
-            threshold_pct,
-            tx_cost,
-        } => {
-            let api_key = env::var("BIRDEYE_API_KEY")
-                .expect("BIRDEYE_API_KEY must be set in .env or environment");
+        #[command(subcommand)]
+        action: StrategyAction,
+    },
+    /// Discover and rank the pools of a token pair across protocols
+    Pools {
+        /// Base URL of the API server
+        #[arg(
+            long,
+            env = "CLMM_API_URL",
+            default_value = "http://localhost:8080/api/v1"
+        )]
+        api_url: String,
 
-            println!("📡 Initializing Backtest Engine...");
-            let provider = BirdeyeProvider::new(api_key);
+        /// API key sent in the X-API-Key header
+        #[arg(long, env = "CLMM_API_KEY")]
+        api_key: Option<String>,
 
-            // Define Tokens
-            let token_a = Token::new(mint_a, symbol_a, 9, symbol_a);
-            let token_b = Token::new(
-                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
-                "USDC",
-                6,
-                "USD Coin",
-            );
+        /// First token symbol or mint
+        token_a: String,
 
-            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-            let start_time = now - (days * 24 * 3600);
+        /// Second token symbol or mint
+        token_b: String,
 
-            println!(
-                "🔍 Fetching historical data for {}/USDC ({} days)...",
-                symbol_a, days
-            );
+        /// Only list pools of this protocol (e.g. orca, raydium)
+        #[arg(long)]
+        protocol: Option<String>,
 
-            let candles = provider
-                .get_price_history(&token_a, &token_b, start_time, now, 3600) // 1h resolution
-                .await?;
+        /// Metric to rank pools by
+        #[arg(short, long, value_enum, default_value_t = PoolSortArg::FeeApr)]
+        sort: PoolSortArg,
 
-            if candles.is_empty() {
-                println!("❌ No data found for the specified period.");
-                return Ok(());
-            }
+        /// Sort ascending instead of descending
+        #[arg(long)]
+        asc: bool,
 
-            // Prepare Price Path
-            let prices: Vec<Price> = candles.iter().map(|c| c.close).collect();
-            let entry_price = prices.first().cloned().unwrap_or(Price::new(Decimal::ONE));
-            let final_price = prices.last().cloned().unwrap_or(entry_price);
+        /// Minimum TVL in USD
+        #[arg(long)]
+        min_tvl: Option<Decimal>,
+
+        /// Maximum number of pools to list
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Recommend an action for a live position from the API server
+    Recommend {
+        /// Base URL of the API server
+        #[arg(
+            long,
+            env = "CLMM_API_URL",
+            default_value = "http://localhost:8080/api/v1"
+        )]
+        api_url: String,
+
+        /// API key sent in the X-API-Key header
+        #[arg(long, env = "CLMM_API_KEY")]
+        api_key: Option<String>,
+
+        /// Position address
+        position: String,
+
+        /// Hours to compare the current and proposed ranges over
+        #[arg(long)]
+        horizon_hours: Option<u64>,
+
+        /// Annualized volatility (0.8 = 80%), estimated from history if omitted
+        #[arg(long)]
+        volatility: Option<f64>,
+
+        /// Full-range fee APR in percent, estimated from history if omitted
+        #[arg(long)]
+        fee_apr: Option<Decimal>,
+
+        /// Width of a recentered range (0.1 = 10%)
+        #[arg(long)]
+        width: Option<Decimal>,
+    },
+    /// List a wallet's Orca and Raydium positions from chain
+    #[cfg(feature = "live")]
+    Positions {
+        /// Wallet address
+        wallet: String,
+
+        /// Solana RPC endpoint
+        #[arg(
+            long,
+            env = "SOLANA_RPC_URL",
+            default_value = "https://api.mainnet-beta.solana.com"
+        )]
+        rpc_url: String,
+    },
+    /// Open, close or rebalance an Orca Whirlpool position
+    #[cfg(feature = "live")]
+    Position {
+        /// Solana RPC endpoint
+        #[arg(
+            long,
+            env = "SOLANA_RPC_URL",
+            default_value = "https://api.mainnet-beta.solana.com"
+        )]
+        rpc_url: String,
+
+        /// Keypair or keystore file signing the transactions
+        #[arg(long, env = "CLMM_KEYPAIR")]
+        keypair: Option<std::path::PathBuf>,
+
+        /// Slippage tolerance in basis points
+        #[arg(long, default_value_t = 50)]
+        slippage_bps: u16,
+
+        /// Refuse to open or rebalance when the pool price diverges from the
+        /// Pyth price by more than this many basis points (0 disables)
+        #[arg(long, default_value_t = 200)]
+        max_oracle_divergence_bps: u32,
+
+        /// Send the transactions (default is a dry run that only prints the plan)
+        #[arg(long)]
+        execute: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+
+        #[command(subcommand)]
+        action: PositionAction,
+    },
+    /// Collect fees and rewards from a position or all positions of a wallet
+    #[cfg(feature = "live")]
+    CollectFees {
+        /// Position address; omit to collect from every position of the wallet
+        position: Option<String>,
+
+        /// Wallet whose positions are collected (defaults to the keypair's)
+        #[arg(long, conflicts_with = "position")]
+        wallet: Option<String>,
+
+        /// Solana RPC endpoint
+        #[arg(
+            long,
+            env = "SOLANA_RPC_URL",
+            default_value = "https://api.mainnet-beta.solana.com"
+        )]
+        rpc_url: String,
+
+        /// Keypair or keystore file signing the transactions
+        #[arg(long, env = "CLMM_KEYPAIR")]
+        keypair: Option<std::path::PathBuf>,
+
+        /// Send the transactions (default previews claimable amounts)
+        #[arg(long)]
+        execute: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Create, import and inspect execution wallets
+    #[cfg(feature = "live")]
+    Wallet {
+        #[command(subcommand)]
+        action: WalletAction,
+    },
+    /// Live terminal dashboard of monitored positions and alerts
+    Monitor {
+        /// Base URL of the API server
+        #[arg(
+            long,
+            env = "CLMM_API_URL",
+            default_value = "http://localhost:8080/api/v1"
+        )]
+        api_url: String,
+
+        /// API key sent in the X-API-Key header
+        #[arg(long, env = "CLMM_API_KEY")]
+        api_key: Option<String>,
+
+        /// Seconds between refreshes
+        #[arg(short, long, default_value_t = 5)]
+        interval: u64,
+
+        /// Number of recent alerts shown
+        #[arg(long, default_value_t = 8)]
+        alerts: usize,
+    },
+    /// Analyze a token pair's historical data
+    Analyze {
+        /// Token A Symbol (e.g., SOL)
+        #[arg(short, long, default_value = "SOL")]
+        symbol_a: String,
+
+        /// Token A Mint Address
+        #[arg(long, default_value = "So11111111111111111111111111111111111111112")]
+        mint_a: String,
+
+        /// Days of history to analyze
+        #[arg(short, long, default_value_t = 30)]
+        days: u64,
+
+        /// Refresh the analysis every N seconds until interrupted
+        #[arg(short, long, value_name = "SECONDS")]
+        watch: Option<u64>,
+
+        /// Highlight prices within this percentage of the range width from an edge
+        #[arg(long, default_value_t = 10.0)]
+        edge_margin: f64,
+
+        /// Save the report to a file
+        #[arg(long)]
+        export: Option<std::path::PathBuf>,
+
+        /// Export format (inferred from the file extension by default)
+        #[arg(long, value_enum, requires = "export")]
+        format: Option<output::ExportFormat>,
+    },
+    /// Analyze every pair of a watchlist concurrently and rank them
+    AnalyzeBatch {
+        /// Watchlist file, one `SYMBOL MINT [FEE_BPS [TVL_USD]]` pair per line
+        watchlist: std::path::PathBuf,
+
+        /// Days of history to analyze
+        #[arg(short, long, default_value_t = 30)]
+        days: u64,
+
+        /// Fee tier in basis points for pairs that do not set one
+        #[arg(long, default_value_t = 30)]
+        fee_bps: u32,
+
+        /// Number of pairs fetched at once
+        #[arg(short, long, default_value_t = 4)]
+        concurrency: usize,
+
+        /// Column the summary is ranked by
+        #[arg(long, value_enum, default_value_t = commands::analyze_batch::RankBy::FeeApr)]
+        sort: commands::analyze_batch::RankBy,
+
+        /// Only show the best N pairs
+        #[arg(long)]
+        top: Option<usize>,
+    },
+}
+
+/// Fills arguments left at their defaults from the configuration.
+fn apply_config(settings: &AppConfig, matches: &ArgMatches, command: &mut Commands) {
+    match command {
+        Commands::MarketData {
+            symbol_a, mint_a, ..
+        }
+        | Commands::OptimizeParams {
+            symbol_a, mint_a, ..
+        }
+        | Commands::Analyze {
+            symbol_a, mint_a, ..
+        } => {
+            overlay(
+                matches,
+                "symbol_a",
+                symbol_a,
+                settings.cli.symbol_a.as_ref(),
+            );
+            overlay(matches, "mint_a", mint_a, settings.cli.mint_a.as_ref());
+        }
+        Commands::Backtest {
+            symbol_a,
+            mint_a,
+            capital,
+            ..
+        }
+        | Commands::Sweep {
+            symbol_a,
+            mint_a,
+            capital,
+            ..
+        }
+        | Commands::Compare {
+            symbol_a,
+            mint_a,
+            capital,
+            ..
+        }
+        | Commands::Optimize {
+            symbol_a,
+            mint_a,
+            capital,
+            ..
+        } => {
+            overlay(
+                matches,
+                "symbol_a",
+                symbol_a,
+                settings.cli.symbol_a.as_ref(),
+            );
+            overlay(matches, "mint_a", mint_a, settings.cli.mint_a.as_ref());
+            overlay(matches, "capital", capital, settings.cli.capital.as_ref());
+        }
+        Commands::Scenario { capital, .. } => {
+            overlay(matches, "capital", capital, settings.cli.capital.as_ref());
+        }
+        #[cfg(feature = "live")]
+        Commands::Positions { rpc_url, .. } => {
+            overlay(matches, "rpc_url", rpc_url, Some(&settings.rpc.url));
+        }
+        #[cfg(feature = "live")]
+        Commands::Position {
+            rpc_url,
+            keypair,
+            max_oracle_divergence_bps,
+            ..
+        } => {
+            overlay(matches, "rpc_url", rpc_url, Some(&settings.rpc.url));
+            overlay_option(
+                matches,
+                "keypair",
+                keypair,
+                settings.execution.keypair.as_ref(),
+            );
+            overlay(
+                matches,
+                "max_oracle_divergence_bps",
+                max_oracle_divergence_bps,
+                Some(&settings.execution.max_oracle_divergence_bps),
+            );
+        }
+        #[cfg(feature = "live")]
+        Commands::CollectFees {
+            rpc_url, keypair, ..
+        } => {
+            overlay(matches, "rpc_url", rpc_url, Some(&settings.rpc.url));
+            overlay_option(
+                matches,
+                "keypair",
+                keypair,
+                settings.execution.keypair.as_ref(),
+            );
+        }
+        Commands::Emergency {
+            api_url, api_key, ..
+        }
+        | Commands::Alerts {
+            api_url, api_key, ..
+        }
+        | Commands::Transcript {
+            api_url, api_key, ..
+        }
+        | Commands::Strategy {
+            api_url, api_key, ..
+        }
+        | Commands::Pools {
+            api_url, api_key, ..
+        }
+        | Commands::Recommend {
+            api_url, api_key, ..
+        }
+        | Commands::Monitor {
+            api_url, api_key, ..
+        } => {
+            overlay(matches, "api_url", api_url, Some(&settings.api.url));
+            overlay_option(matches, "api_key", api_key, settings.api.key.as_ref());
+        }
+        #[cfg(feature = "postgres")]
+        Commands::Data {
+            action: DataAction::Import {
+                symbol_a, mint_a, ..
+            },
+            ..
+        } => {
+            overlay(
+                matches,
+                "symbol_a",
+                symbol_a,
+                settings.cli.symbol_a.as_ref(),
+            );
+            overlay(matches, "mint_a", mint_a, settings.cli.mint_a.as_ref());
+        }
+        #[cfg(feature = "live")]
+        Commands::Wallet {
+            action: WalletAction::Balance { rpc_url, .. },
+        } => {
+            overlay(matches, "rpc_url", rpc_url, Some(&settings.rpc.url));
+        }
+        #[cfg(feature = "live")]
+        Commands::Wallet { .. } => {}
+        #[cfg(feature = "postgres")]
+        Commands::Data {
+            action: DataAction::Export { .. },
+            ..
+        }
+        | Commands::Db { .. } => {}
+        Commands::AnalyzeBatch { .. } => {}
+    }
+
+    if let Commands::Backtest { database_url, .. }
+    | Commands::Compare { database_url, .. }
+    | Commands::Optimize { database_url, .. } = command
+    {
+        overlay(
+            matches,
+            "database_url",
+            database_url,
+            settings.database.url.as_ref(),
+        );
+    }
+    #[cfg(feature = "postgres")]
+    if let Commands::Data { database_url, .. } = command {
+        overlay(
+            matches,
+            "database_url",
+            database_url,
+            settings.database.url.as_ref(),
+        );
+    }
+
+    if let Commands::Backtest { rpc_url, .. } = command {
+        overlay(matches, "rpc_url", rpc_url, Some(&settings.rpc.url));
+    }
+}
+
+/// Market data actions.
+#[cfg(feature = "postgres")]
+#[derive(Subcommand)]
+enum DataAction {
+    /// Bulk-load CSV price files (timestamp,open,high,low,close[,volume[,liquidity]])
+    Import {
+        /// CSV files to import
+        #[arg(required = true)]
+        files: Vec<std::path::PathBuf>,
+
+        /// Address of the pool the prices belong to
+        #[arg(long)]
+        pool: String,
+
+        /// Protocol of the pool, if it is not registered yet
+        #[arg(long, default_value = "orca")]
+        protocol: String,
+
+        /// Token A Symbol, if the pool is not registered yet
+        #[arg(short, long, default_value = "SOL")]
+        symbol_a: String,
+
+        /// Token A Mint Address, if the pool is not registered yet
+        #[arg(long, default_value = "So11111111111111111111111111111111111111112")]
+        mint_a: String,
+
+        /// Fee tier in basis points, if the pool is not registered yet
+        #[arg(long, default_value_t = 30)]
+        fee_tier: i32,
+
+        /// Tick spacing, if the pool is not registered yet
+        #[arg(long, default_value_t = 64)]
+        tick_spacing: i32,
+
+        /// Import the valid rows even if some rows are invalid
+        #[arg(long)]
+        skip_invalid: bool,
+
+        /// Rows written per transaction
+        #[arg(long, default_value_t = 1000)]
+        batch_size: usize,
+    },
+    /// Dump stored price history of a pair or pool to CSV or Parquet
+    Export {
+        /// First token symbol or mint
+        #[arg(default_value = "SOL")]
+        token_a: String,
+
+        /// Second token symbol or mint
+        #[arg(default_value = "USDC")]
+        token_b: String,
+
+        /// Only export this pool instead of every pool of the pair
+        #[arg(long)]
+        pool: Option<String>,
+
+        /// Days of history to export
+        #[arg(short, long, default_value_t = 30)]
+        days: u64,
+
+        /// Output file
+        #[arg(short, long = "out")]
+        output: std::path::PathBuf,
+
+        /// Output format (inferred from the file extension by default)
+        #[arg(long, value_enum)]
+        format: Option<TableFormatArg>,
+    },
+}
+
+/// File format of exported tables.
+#[cfg(feature = "postgres")]
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum TableFormatArg {
+    /// Comma-separated values
+    Csv,
+    /// Apache Parquet
+    Parquet,
+}
+
+/// Database management actions.
+#[cfg(feature = "postgres")]
+#[derive(Subcommand)]
+enum DbAction {
+    /// Initialize the database with migrations
+    Init,
+    /// Show database connection status
+    Status,
+    /// List recent simulations
+    ListSimulations {
+        /// Maximum number of results
+        #[arg(short, long, default_value_t = 10)]
+        limit: i64,
+    },
+    /// Show the latest stored steps of a simulation, including one still running
+    Steps {
+        /// Simulation ID or ID prefix
+        id: String,
+
+        /// Maximum number of steps
+        #[arg(short, long, default_value_t = 20)]
+        limit: i64,
+    },
+    /// List recent optimizations
+    ListOptimizations {
+        /// Maximum number of results
+        #[arg(short, long, default_value_t = 10)]
+        limit: i64,
+    },
+    /// Show table sizes, row counts and vacuum times
+    Stats,
+    /// Delete rows older than a retention period
+    Prune {
+        /// Retention period in days
+        #[arg(long = "older-than", value_name = "DAYS")]
+        older_than_days: u32,
+
+        /// Comma-separated tables to prune (default: price, simulation,
+        /// optimization, history, job and request tables)
+        #[arg(long, value_delimiter = ',')]
+        tables: Vec<String>,
+
+        /// Only count the rows that would be deleted
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Vacuum and analyze tables to reclaim space
+    Vacuum {
+        /// Tables to vacuum (default: the whole database)
+        tables: Vec<String>,
+
+        /// Rewrite the tables to return space to the OS (locks them)
+        #[arg(long)]
+        full: bool,
+    },
+    /// Write every table to a JSON lines backup file
+    Backup {
+        /// Backup file
+        file: std::path::PathBuf,
+
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Load a backup file, skipping rows that already exist
+    Restore {
+        /// Backup file
+        file: std::path::PathBuf,
+
+        /// Rows inserted per statement
+        #[arg(long, default_value_t = 1000)]
+        batch_size: usize,
+    },
+}
+
+/// Position lifecycle actions.
+#[cfg(feature = "live")]
+#[derive(Subcommand)]
+enum PositionAction {
+    /// Open a new position
+    Open {
+        /// Pool address
+        pool: String,
+
+        /// Lower price of the range (raw token ratio)
+        #[arg(long)]
+        lower_price: Decimal,
+
+        /// Upper price of the range (raw token ratio)
+        #[arg(long)]
+        upper_price: Decimal,
+
+        /// Maximum token A to deposit, in base units
+        #[arg(long, default_value_t = 0)]
+        amount_a: u64,
+
+        /// Maximum token B to deposit, in base units
+        #[arg(long, default_value_t = 0)]
+        amount_b: u64,
+    },
+    /// Withdraw all liquidity, collect fees and close a position
+    Close {
+        /// Position address
+        position: String,
+    },
+    /// Close a position and reopen its liquidity in a new range
+    Rebalance {
+        /// Position address
+        position: String,
+
+        /// New lower price (raw token ratio)
+        #[arg(long)]
+        lower_price: Decimal,
+
+        /// New upper price (raw token ratio)
+        #[arg(long)]
+        upper_price: Decimal,
+    },
+}
+
+/// Wallet actions.
+#[cfg(feature = "live")]
+#[derive(Subcommand)]
+enum WalletAction {
+    /// Generate a new keypair, saved as an encrypted keystore by default
+    New {
+        /// Wallet label, used for the default file name
+        #[arg(short, long, default_value = "default")]
+        label: String,
+
+        /// Output file (default: ~/.clmm-lp/wallets/<LABEL>.json)
+        #[arg(short, long = "out")]
+        output: Option<std::path::PathBuf>,
+
+        /// Write an unencrypted solana-keygen file instead of a keystore
+        #[arg(long)]
+        plain: bool,
+
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt an existing keypair into a keystore
+    Import {
+        /// Keypair file (solana-keygen JSON) to import
+        #[arg(required_unless_present = "from_env", conflicts_with = "from_env")]
+        keypair: Option<std::path::PathBuf>,
+
+        /// Read the keypair (JSON array or base58) from an environment variable
+        #[arg(long, value_name = "VAR")]
+        from_env: Option<String>,
+
+        /// Wallet label, used for the default file name
+        #[arg(short, long, default_value = "default")]
+        label: String,
+
+        /// Output file (default: ~/.clmm-lp/wallets/<LABEL>.json)
+        #[arg(short, long = "out")]
+        output: Option<std::path::PathBuf>,
+
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show a wallet's address and file format without decrypting it
+    Show {
+        /// Wallet file or label
+        #[arg(default_value = "default")]
+        wallet: String,
+    },
+    /// Show a wallet's SOL and token balances
+    Balance {
+        /// Wallet file, label or address
+        #[arg(default_value = "default")]
+        wallet: String,
+
+        /// Solana RPC endpoint
+        #[arg(
+            long,
+            env = "SOLANA_RPC_URL",
+            default_value = "https://api.mainnet-beta.solana.com"
+        )]
+        rpc_url: String,
+    },
+}
+
+/// Emergency control actions.
+#[derive(Subcommand)]
+enum EmergencyAction {
+    /// Show kill switch and circuit breaker status
+    Status,
+    /// Engage the kill switch, halting all automated execution
+    Halt {
+        /// Reason for halting execution
+        #[arg(short, long)]
+        reason: String,
+    },
+    /// Release the kill switch and resume automated execution
+    Resume,
+    /// Show portfolio risk limits, exposure and portfolio stop status
+    Risk,
+    /// Engage the portfolio stop, pausing all strategies
+    Stop {
+        /// Reason for stopping
+        #[arg(short, long)]
+        reason: String,
+    },
+    /// Release the portfolio stop and resume all strategies
+    Release,
+}
+
+/// Strategy definition actions.
+#[derive(Subcommand)]
+enum StrategyAction {
+    /// Write a strategy's definition as YAML
+    Export {
+        /// Strategy ID
+        id: String,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long = "out")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Create a strategy from a YAML definition
+    Import {
+        /// YAML file written by `strategy export`
+        file: std::path::PathBuf,
+    },
+}
+
+/// Alert rule actions.
+#[derive(Subcommand)]
+enum AlertAction {
+    /// List configured alert rules
+    Rules,
+    /// Add or replace an expression-based alert rule
+    AddRule {
+        /// Rule name
+        name: String,
+
+        /// Condition expression (e.g. "il_pct > 3% && time_out_of_range > 2h")
+        condition: String,
+
+        /// Alert level
+        #[arg(short, long, value_enum, default_value_t = AlertLevelArg::Warning)]
+        level: AlertLevelArg,
+
+        /// Message template
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Cooldown between alerts in seconds
+        #[arg(long)]
+        cooldown: Option<u64>,
 
-            // Setup position tracker
-            let initial_range = PriceRange::new(
-                Price::new(Decimal::from_f64(*lower).unwrap()),
-                Price::new(Decimal::from_f64(*upper).unwrap()),
+        /// Fire only when the condition starts or stops holding
+        #[arg(long)]
+        on_change: bool,
+    },
+    /// Remove an alert rule
+    RemoveRule {
+        /// Rule name
+        name: String,
+    },
+    /// Send a test alert through the configured channels (Telegram, Slack, Discord, webhooks)
+    Test {
+        /// Only test this channel (e.g. telegram, discord, webhook)
+        #[arg(short, long)]
+        channel: Option<String>,
+
+        /// Alert level
+        #[arg(short, long, value_enum, default_value_t = AlertLevelArg::Info)]
+        level: AlertLevelArg,
+
+        /// Message text
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+}
+
+/// Alert level for custom rules.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum AlertLevelArg {
+    /// Informational
+    Info,
+    /// Warning
+    Warning,
+    /// Critical
+    Critical,
+}
+
+/// Metric to rank pools by.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PoolSortArg {
+    /// Estimated fee APR
+    FeeApr,
+    /// Total value locked
+    Tvl,
+    /// 24h volume
+    Volume,
+    /// Fee tier
+    FeeTier,
+}
+
+impl PoolSortArg {
+    /// Sort field name used by the pool search API.
+    fn as_query(self) -> &'static str {
+        match self {
+            Self::FeeApr => "fee_apr",
+            Self::Tvl => "tvl",
+            Self::Volume => "volume",
+            Self::FeeTier => "fee_tier",
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    output::set_output_mode(cli.output);
+    if output::is_json() {
+        // Keep stdout for the JSON document
+        tracing_subscriber::fmt()
+            .with_writer(std::io::stderr)
+            .init();
+    } else {
+        tracing_subscriber::fmt::init();
+    }
+    let settings = config::load(cli.config.as_deref(), cli.profile.as_deref())?;
+    if let Some((_, command_matches)) = matches.subcommand() {
+        apply_config(&settings, command_matches, &mut cli.command);
+    }
+    let birdeye_api_key = settings.providers.birdeye_api_key.clone();
+
+    match &cli.command {
+        Commands::MarketData {
+            symbol_a,
+            mint_a,
+            hours,
+        } => {
+            let api_key = birdeye_api_key
+                .clone()
+                .expect("BIRDEYE_API_KEY must be set in .env, environment or config file");
+
+            info!("📡 Initializing Birdeye Provider...");
+            let provider = BirdeyeProvider::new(api_key);
+
+            // Define Tokens (Token B assumed USDC for this demo)
+            let token_a = Token::new(mint_a, symbol_a, 9, symbol_a);
+            let token_b = Token::new(
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "USDC",
+                6,
+                "USD Coin",
             );
-            let capital_dec = Decimal::from_f64(*capital).unwrap();
-            let tx_cost_dec = Decimal::from_f64(*tx_cost).unwrap();
 
-            let mut tracker =
-                PositionTracker::new(capital_dec, entry_price, initial_range, tx_cost_dec);
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let start_time = now - (hours * 3600);
 
-            // Setup volume and liquidity models
-            let mut volume_model = ConstantVolume::from_amount(
-                Amount::new(U256::from(1_000_000_000_000u64), 6), // 1M USDC vol per step
+            info!(
+                "🔍 Fetching data for {}/USDC from {} to {}...",
+                symbol_a, start_time, now
             );
-            let liquidity_amount = (*capital as u128) * 10;
-            let global_liquidity = liquidity_amount * 100; // 1% share
-            let fee_rate = Decimal::from_f64(0.003).unwrap();
 
-            println!(
-                "🚀 Running backtest with {:?} strategy over {} steps...",
-                strategy,
-                prices.len()
+            // Fetch 1-hour candles
+            let candles = provider
+                .get_price_history(
+                    &token_a, &token_b, start_time, now, 3600, // 1h resolution
+                )
+                .await?;
+
+            if output::is_json() {
+                output::print_json(&candles)?;
+                return Ok(());
+            }
+
+            println!("✅ Fetched {} candles:", candles.len());
+            println!();
+
+            let mut table = Table::new();
+            table.add_row(row!["Time", "Open", "High", "Low", "Close"]);
+
+            for candle in candles {
+                let datetime = chrono::DateTime::from_timestamp(candle.start_timestamp as i64, 0)
+                    .unwrap_or_default();
+                table.add_row(row![
+                    datetime.format("%Y-%m-%d %H:%M"),
+                    format!("{:.4}", candle.open.value),
+                    format!("{:.4}", candle.high.value),
+                    format!("{:.4}", candle.low.value),
+                    format!("{:.4}", candle.close.value)
+                ]);
+            }
+            table.printstd();
+        }
+        Commands::Backtest {
+            symbol_a,
+            mint_a,
+            days,
+            lower,
+            upper,
+            capital,
+            strategy,
+            rebalance_interval,
+            threshold_pct,
+            tx_cost,
+            export,
+            format,
+            charts,
+            replay,
+            pool,
+            swaps,
+            rpc_url,
+            database_url,
+            manifest,
+            no_cache,
+            save,
+        } => {
+            status!("📡 Initializing Backtest Engine...");
+
+            // Define Tokens
+            let token_a = Token::new(mint_a, symbol_a, 9, symbol_a);
+            let token_b = Token::new(
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "USDC",
+                6,
+                "USD Coin",
             );
 
-            // Run simulation with strategy
-            let range_width_pct =
-                Decimal::from_f64((*upper - *lower) / ((*upper + *lower) / 2.0)).unwrap();
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let start_time = now - (days * 24 * 3600);
+
+            let (data_provider, candles) = if *replay {
+                status!(
+                    "💾 Replaying stored data for {}/USDC ({} days)...",
+                    symbol_a,
+                    days
+                );
+                replay_candles(
+                    database_url,
+                    pool.as_deref(),
+                    &token_a,
+                    &token_b,
+                    start_time,
+                    now,
+                )
+                .await?
+            } else {
+                let api_key = birdeye_api_key.clone().expect(
+                    "BIRDEYE_API_KEY must be set in .env, environment or config file \
+                     (or use --replay)",
+                );
+                let provider = BirdeyeProvider::new(api_key);
 
-            for price in &prices {
-                // Calculate fees for this step
-                let in_range = price.value >= tracker.current_range.lower_price.value
-                    && price.value <= tracker.current_range.upper_price.value;
+                status!(
+                    "🔍 Fetching historical data for {}/USDC ({} days)...",
+                    symbol_a,
+                    days
+                );
 
-                let step_fees = if in_range {
-                    let vol = volume_model.next_volume().to_decimal();
-                    let fee_share =
-                        Decimal::from(liquidity_amount) / Decimal::from(global_liquidity);
-                    vol * fee_share * fee_rate
-                } else {
-                    Decimal::ZERO
-                };
+                let candles = provider
+                    .get_price_history(&token_a, &token_b, start_time, now, 3600) // 1h resolution
+                    .await?;
+                ("birdeye".to_string(), candles)
+            };
+
+            if candles.is_empty() {
+                status!("❌ No data found for the specified period.");
+                return Ok(());
+            }
+
+            // Prepare Price Path
+            let prices: Vec<Price> = candles.iter().map(|c| c.close).collect();
+            let entry_price = prices.first().cloned().unwrap_or(Price::new(Decimal::ONE));
+
+            // Fees follow the pool's swaps, or a constant volume and share
+            let mut swap_fees = match pool.as_deref().filter(|_| *swaps) {
+                Some(pool) => {
+                    status!("🔁 Fetching swaps of pool {}...", pool);
+                    let (volume, fee_rate, fee_share) = backtest_swap_volume(
+                        rpc_url,
+                        pool,
+                        &candles,
+                        *capital,
+                        (*lower, *upper),
+                        entry_price.value,
+                    )
+                    .await?;
+                    status!(
+                        "   {} swaps, {:.0} USDC volume, {:.4}% fee share at a {}% fee",
+                        volume.swap_count(),
+                        volume.total(),
+                        fee_share * Decimal::from(100),
+                        fee_rate * Decimal::from(100)
+                    );
+                    Some((volume, fee_rate, fee_share))
+                }
+                None => None,
+            };
+
+            // Backtests replay history and have no random components to seed.
+            let config = serde_json::json!({
+                "symbol_a": symbol_a,
+                "mint_a": mint_a,
+                "days": days,
+                "lower": lower,
+                "upper": upper,
+                "capital": capital,
+                "strategy": format!("{:?}", strategy),
+                "rebalance_interval": rebalance_interval,
+                "threshold_pct": threshold_pct,
+                "tx_cost": tx_cost,
+                "swap_volume": swap_fees.as_ref().map(|(volume, ..)| volume.volumes()),
+            });
+            let cache = commands::data::result_cache(!*no_cache);
+            let cache_key = ResultKey::for_inputs("backtest", &candles, &config)?;
+            // Saved runs are always simulated so their steps can be streamed
+            let cached: Option<BacktestRun> = cache
+                .as_ref()
+                .filter(|_| !*save)
+                .and_then(|cache| cache.get(&cache_key));
+
+            let started_at = chrono::Utc::now();
+            let (run, steps_run) = match cached {
+                Some(run) => {
+                    status!(
+                        "♻️  Reusing cached result for the same data and configuration \
+                         (--no-cache to recompute)"
+                    );
+                    (run, prices.len())
+                }
+                None => {
+                    // Setup position tracker
+                    let initial_range = PriceRange::new(
+                        Price::new(Decimal::from_f64(*lower).unwrap()),
+                        Price::new(Decimal::from_f64(*upper).unwrap()),
+                    );
+                    let capital_dec = Decimal::from_f64(*capital).unwrap();
+                    let tx_cost_dec = Decimal::from_f64(*tx_cost).unwrap();
+
+                    let mut tracker =
+                        PositionTracker::new(capital_dec, entry_price, initial_range, tx_cost_dec)
+                            .with_capacity(prices.len());
+
+                    // Setup volume and liquidity models
+                    let mut volume_model = ConstantVolume::from_amount(
+                        Amount::new(U256::from(1_000_000_000_000u64), 6), // 1M USDC vol per step
+                    );
+                    let liquidity_amount = (*capital as u128) * 10;
+                    let global_liquidity = liquidity_amount * 100; // 1% share
+                    let fee_rate = swap_fees
+                        .as_ref()
+                        .map_or(Decimal::from_f64(0.003).unwrap(), |(_, fee_rate, _)| {
+                            *fee_rate
+                        });
+
+                    status!(
+                        "🚀 Running backtest with {:?} strategy over {} steps...",
+                        strategy,
+                        prices.len()
+                    );
+
+                    // Run simulation with strategy
+                    let range_width_pct =
+                        Decimal::from_f64((*upper - *lower) / ((*upper + *lower) / 2.0)).unwrap();
 
-                // Apply strategy
-                match strategy {
-                    StrategyArg::Static => {
-                        let strat = StaticRange::new();
-                        tracker.record_step(*price, step_fees, Some(&strat));
+                    let saved = if *save {
+                        start_saved_simulation(
+                            database_url,
+                            &format!("{:?}", strategy).to_lowercase(),
+                            config.clone(),
+                            (start_time, now),
+                            &tracker,
+                            fee_rate,
+                        )
+                        .await
+                    } else {
+                        None
+                    };
+                    let mut batcher = StepBatcher::new(STEP_BATCH_SIZE);
+
+                    let interrupt = output::Interrupt::install();
+                    let bar = output::progress_bar(prices.len() as u64, "Backtesting");
+                    let mut steps_run = 0;
+
+                    for price in &prices {
+                        if interrupt.is_set() {
+                            break;
+                        }
+
+                        // Calculate fees for this step
+                        let in_range = price.value >= tracker.current_range.lower_price.value
+                            && price.value <= tracker.current_range.upper_price.value;
+
+                        let step_fees = match &mut swap_fees {
+                            Some((volume, _, fee_share)) if in_range => {
+                                volume.get_volume(steps_run) * *fee_share * fee_rate
+                            }
+                            None if in_range => {
+                                let vol = volume_model.next_volume().to_decimal();
+                                let fee_share = Decimal::from(liquidity_amount)
+                                    / Decimal::from(global_liquidity);
+                                vol * fee_share * fee_rate
+                            }
+                            _ => Decimal::ZERO,
+                        };
+
+                        // Apply strategy
+                        match strategy {
+                            StrategyArg::Static => {
+                                let strat = StaticRange::new();
+                                tracker.record_step(*price, step_fees, Some(&strat));
+                            }
+                            StrategyArg::Periodic => {
+                                let strat =
+                                    PeriodicRebalance::new(*rebalance_interval, range_width_pct);
+                                tracker.record_step(*price, step_fees, Some(&strat));
+                            }
+                            StrategyArg::Threshold => {
+                                let strat = ThresholdRebalance::new(
+                                    Decimal::from_f64(*threshold_pct).unwrap(),
+                                    range_width_pct,
+                                );
+                                tracker.record_step(*price, step_fees, Some(&strat));
+                            }
+                        }
+
+                        steps_run += 1;
+                        bar.inc(1);
+
+                        if let Some(saved) = &saved
+                            && let Some(batch) = batcher.next_batch(&tracker)
+                        {
+                            saved.send(&batch);
+                        }
                     }
-                    StrategyArg::Periodic => {
-                        let strat = PeriodicRebalance::new(*rebalance_interval, range_width_pct);
-                        tracker.record_step(*price, step_fees, Some(&strat));
+                    bar.finish_and_clear();
+
+                    let snapshots: Vec<_> = tracker.snapshots().collect();
+                    let run = BacktestRun {
+                        summary: tracker.summary(),
+                        final_price: prices[..steps_run].last().copied().unwrap_or(entry_price),
+                        points: snapshots.iter().map(output::RangePoint::from).collect(),
+                        series: output::equity_points(&snapshots),
+                    };
+                    if let Some(saved) = saved {
+                        let rest = batcher.take_rest(&tracker);
+                        saved.send(&rest);
+                        // Interrupted runs keep their steps but get no result
+                        let complete = steps_run == prices.len();
+                        finish_saved_simulation(saved, capital_dec, complete.then_some(&run)).await;
                     }
-                    StrategyArg::Threshold => {
-                        let strat = ThresholdRebalance::new(
-                            Decimal::from_f64(*threshold_pct).unwrap(),
-                            range_width_pct,
-                        );
-                        tracker.record_step(*price, step_fees, Some(&strat));
+                    // Interrupted runs are partial and never cached
+                    if steps_run == prices.len()
+                        && let Some(cache) = &cache
+                    {
+                        cache.put(&cache_key, &run);
                     }
+                    (run, steps_run)
                 }
-            }
+            };
 
-            // Get summary
-            let summary = tracker.summary();
+            let interrupted = steps_run < prices.len();
 
-            // Print rich report
-            print_backtest_report(
+            let report = backtest_report(
                 symbol_a,
                 *days,
                 *capital,
                 entry_price.value,
-                final_price.value,
+                run.final_price.value,
                 *lower,
                 *upper,
-                &summary,
+                &run.summary,
                 *strategy,
             );
+
+            if output::is_json() {
+                output::print_json(&report)?;
+            } else {
+                // Print rich report
+                print_backtest_report(
+                    symbol_a,
+                    *days,
+                    *capital,
+                    entry_price.value,
+                    run.final_price.value,
+                    *lower,
+                    *upper,
+                    &run.summary,
+                    *strategy,
+                );
+
+                println!("📈 PRICE VS RANGE");
+                println!();
+                print!(
+                    "{}",
+                    output::render_range_chart(&run.points, &output::ChartConfig::default())
+                );
+                println!();
+            }
+
+            if let Some(path) = export {
+                let format = format.unwrap_or_else(|| output::ExportFormat::from_path(path));
+                let images = match charts {
+                    Some(image_format) => {
+                        let dir = path
+                            .parent()
+                            .filter(|dir| !dir.as_os_str().is_empty())
+                            .unwrap_or(std::path::Path::new("."));
+                        let stem = path
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .unwrap_or("backtest");
+                        output::render_backtest_charts(&run.series, dir, stem, *image_format)?
+                    }
+                    None => Vec::new(),
+                };
+                output::export_backtest_report_with_charts(
+                    &report,
+                    &run.series,
+                    path,
+                    format,
+                    &images,
+                )?;
+                status!("💾 Report exported to {}", path.display());
+                for image in &images {
+                    status!("🖼️  {} written to {}", image.title, image.path.display());
+                }
+            }
+
+            let run_manifest = output::RunManifest::new("backtest", config).with_data(
+                output::DataWindow::from_candles(
+                    data_provider,
+                    format!("{}/USDC", symbol_a),
+                    start_time,
+                    now,
+                    &candles,
+                ),
+            );
+            run_manifest.print_summary();
+            if let Some(path) = manifest {
+                run_manifest.write(path)?;
+                status!("🧾 Manifest written to {}", path.display());
+            }
+
+            if interrupted {
+                status!(
+                    "⏸️  Backtest stopped after {} of {} steps",
+                    steps_run,
+                    prices.len()
+                );
+                let record = saved_run(
+                    Uuid::new_v4(),
+                    "backtest",
+                    "cancelled",
+                    progress_pct(steps_run, prices.len()),
+                    run_manifest.config.clone(),
+                    serde_json::to_value(&report)?,
+                    started_at,
+                );
+                save_run(database_url, &record).await;
+            }
+        }
+        Commands::Sweep {
+            symbol_a,
+            mint_a,
+            days,
+            ranges,
+            widths,
+            strategies,
+            intervals,
+            thresholds,
+            capital,
+            tx_cost,
+            jobs,
+            output,
+            no_cache,
+        } => {
+            let args = commands::sweep::SweepArgs {
+                symbol_a: symbol_a.clone(),
+                mint_a: mint_a.clone(),
+                days: *days,
+                ranges: ranges.clone(),
+                widths: widths.clone(),
+                strategies: strategies.iter().map(|s| s.strategy_type()).collect(),
+                intervals: intervals.clone(),
+                thresholds: thresholds.clone(),
+                capital: Decimal::from_f64(*capital).unwrap(),
+                tx_cost: Decimal::from_f64(*tx_cost).unwrap(),
+                jobs: jobs.unwrap_or_else(commands::sweep::default_jobs),
+                output: output.clone(),
+                use_cache: !*no_cache,
+                api_key: birdeye_api_key.clone(),
+            };
+            commands::run_sweep(args).await?;
+        }
+        Commands::Scenario {
+            price,
+            lower,
+            upper,
+            width,
+            strategy,
+            rebalance_interval,
+            threshold_pct,
+            scenarios,
+            hours,
+            capital,
+            tx_cost,
+        } => {
+            let (lower, upper) = match (lower, upper) {
+                (Some(lower), Some(upper)) => (*lower, *upper),
+                _ => (price * (1.0 - width / 2.0), price * (1.0 + width / 2.0)),
+            };
+            let args = commands::scenario::ScenarioArgs {
+                entry_price: Decimal::from_f64(*price).unwrap_or_default(),
+                lower: Decimal::from_f64(lower).unwrap_or_default(),
+                upper: Decimal::from_f64(upper).unwrap_or_default(),
+                strategy: strategy.strategy_type(),
+                interval: *rebalance_interval,
+                threshold: Decimal::from_f64(*threshold_pct).unwrap_or_default(),
+                scenarios: scenarios.clone(),
+                steps: *hours,
+                capital: Decimal::from_f64(*capital).unwrap_or_default(),
+                tx_cost: Decimal::from_f64(*tx_cost).unwrap_or_default(),
+            };
+            commands::run_scenario(args).await?;
+        }
+        Commands::Compare {
+            runs,
+            symbol_a,
+            mint_a,
+            days,
+            capital,
+            tx_cost,
+            rerun,
+            database_url,
+        } => {
+            let args = commands::compare::CompareArgs {
+                runs: runs.clone(),
+                symbol_a: symbol_a.clone(),
+                mint_a: mint_a.clone(),
+                days: *days,
+                capital: Decimal::from_f64(*capital).unwrap(),
+                tx_cost: Decimal::from_f64(*tx_cost).unwrap(),
+                rerun: *rerun,
+                database_url: database_url.clone(),
+                api_key: birdeye_api_key.clone(),
+            };
+            commands::run_compare(args).await?;
         }
         Commands::Optimize {
             symbol_a,
@@ -372,12 +1922,61 @@ async fn main() -> Result<()> {
             days,
             capital,
             objective,
+            volatility_model,
             iterations,
+            seed,
+            manifest,
+            export,
+            format,
+            resume,
+            database_url,
+            no_cache,
         } => {
-            let api_key = env::var("BIRDEYE_API_KEY")
-                .expect("BIRDEYE_API_KEY must be set in .env or environment");
+            let api_key = birdeye_api_key
+                .clone()
+                .expect("BIRDEYE_API_KEY must be set in .env, environment or config file");
+
+            let checkpoint: Option<commands::optimize::OptimizeCheckpoint> = match resume {
+                #[cfg(not(feature = "postgres"))]
+                Some(_) => {
+                    anyhow::bail!("--resume needs the CLI built with the `postgres` feature")
+                }
+                #[cfg(feature = "postgres")]
+                Some(id) => {
+                    let db = Database::connect(database_url).await?;
+                    let job = db
+                        .jobs()
+                        .find_by_id(*id)
+                        .await?
+                        .filter(|job| job.kind == "optimization")
+                        .ok_or_else(|| anyhow::anyhow!("no saved optimization job {}", id))?;
+                    let saved_objective = job.params.get("objective").and_then(|v| v.as_str());
+                    let saved_iterations = job.params.get("iterations").and_then(|v| v.as_u64());
+                    if saved_objective != Some(format!("{:?}", objective).as_str())
+                        || saved_iterations != Some(*iterations as u64)
+                    {
+                        anyhow::bail!(
+                            "job {} used a different objective or iteration count; \
+                             rerun with the same --objective and --iterations",
+                            id
+                        );
+                    }
+                    let result = job.result.ok_or_else(|| {
+                        anyhow::anyhow!("job {} has no saved progress to resume", id)
+                    })?;
+                    let checkpoint: commands::optimize::OptimizeCheckpoint =
+                        serde_json::from_value(result)?;
+                    status!(
+                        "⏯️  Resuming job {} ({} ranges already evaluated)",
+                        id,
+                        checkpoint.candidates.len()
+                    );
+                    Some(checkpoint)
+                }
+                None => None,
+            };
 
-            println!("📡 Initializing Optimizer...");
+            status!("📡 Initializing Optimizer...");
             let provider = BirdeyeProvider::new(api_key);
 
             // Define Tokens
@@ -392,9 +1991,10 @@ async fn main() -> Result<()> {
             let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
             let start_time = now - (days * 24 * 3600);
 
-            println!(
+            status!(
                 "🔍 Fetching historical data for {}/USDC ({} days) to estimate volatility...",
-                symbol_a, days
+                symbol_a,
+                days
             );
 
             let candles = provider
@@ -402,7 +2002,7 @@ async fn main() -> Result<()> {
                 .await?;
 
             if candles.is_empty() {
-                println!("❌ No data found for the specified period.");
+                status!("❌ No data found for the specified period.");
                 return Ok(());
             }
 
@@ -412,17 +2012,42 @@ async fn main() -> Result<()> {
                 .map(|c| c.close.value.to_f64().unwrap_or(0.0))
                 .collect();
 
-            let volatility = calculate_volatility(&prices);
-            let current_price = *prices.last().unwrap_or(&100.0);
+            // GARCH forecasts over the 30 daily steps the optimizer simulates
+            let estimator = volatility_model.estimator(30 * 24);
+
+            // A resumed run keeps the estimates its first ranges were simulated with
+            let (volatility, current_price) = match &checkpoint {
+                Some(checkpoint) => (checkpoint.volatility, checkpoint.current_price),
+                None => (
+                    estimator.annualized(&prices, HOURS_PER_YEAR),
+                    *prices.last().unwrap_or(&100.0),
+                ),
+            };
             let current_price_dec = Decimal::from_f64(current_price).unwrap();
 
-            println!("📊 Market Analysis:");
-            println!("   Current Price: ${:.4}", current_price);
-            println!("   Volatility (annualized): {:.1}%", volatility * 100.0);
-            println!();
+            status!("📊 Market Analysis:");
+            status!("   Current Price: ${:.4}", current_price);
+            status!(
+                "   Volatility (annualized, {}): {:.1}%",
+                estimator.name(),
+                volatility * 100.0
+            );
+            status!();
+
+            // Only explicitly seeded runs are reproducible, and resumed runs
+            // continue their own checkpoint
+            let cacheable = seed.is_some() && checkpoint.is_none() && !*no_cache;
 
             // Setup optimizer
-            let optimizer = RangeOptimizer::new(*iterations, 30, 1.0 / 365.0);
+            let seed = match &checkpoint {
+                Some(checkpoint) => checkpoint.seed,
+                None => seed.unwrap_or_else(rand::random),
+            };
+            let mut optimizer = RangeOptimizer::new(*iterations, 30, 1.0 / 365.0).with_seed(seed);
+            let width_count = optimizer.range_widths.len();
+            if let Some(checkpoint) = &checkpoint {
+                optimizer.range_widths = checkpoint.remaining_widths(&optimizer.range_widths);
+            }
 
             let base_position = Position {
                 id: clmm_lp_domain::entities::position::PositionId(Uuid::new_v4()),
@@ -445,66 +2070,253 @@ async fn main() -> Result<()> {
             let pool_liquidity = (*capital as u128) * 1000;
             let fee_rate = Decimal::from_f64(0.003).unwrap();
 
-            println!(
-                "🔄 Running optimization with {:?} objective ({} iterations)...",
-                objective, iterations
-            );
+            let params = serde_json::json!({
+                "symbol_a": symbol_a,
+                "mint_a": mint_a,
+                "days": days,
+                "capital": capital,
+                "objective": format!("{:?}", objective),
+                "volatility_model": estimator.name(),
+                "iterations": iterations,
+                "steps": optimizer.steps,
+                "time_step": optimizer.time_step,
+            });
 
-            let result = match objective {
-                OptimizationObjectiveArg::Pnl => optimizer.optimize(
-                    base_position,
-                    current_price_dec,
-                    volatility,
-                    0.0,
-                    volume,
-                    pool_liquidity,
-                    fee_rate,
-                    MaximizeNetPnL,
-                ),
-                OptimizationObjectiveArg::Fees => optimizer.optimize(
-                    base_position,
-                    current_price_dec,
-                    volatility,
-                    0.0,
-                    volume,
-                    pool_liquidity,
-                    fee_rate,
-                    MaximizeFees,
-                ),
-                OptimizationObjectiveArg::Sharpe => optimizer.optimize(
-                    base_position,
-                    current_price_dec,
+            let cache = commands::data::result_cache(cacheable);
+            let cache_key = ResultKey::for_inputs("optimize", &candles, &params)?.with_seed(seed);
+            let cached: Option<commands::optimize::OptimizeCheckpoint> =
+                cache.as_ref().and_then(|cache| cache.get(&cache_key));
+            if cached.is_some() {
+                status!(
+                    "♻️  Reusing cached result for the same data, configuration and seed \
+                     (--no-cache to recompute)"
+                );
+            } else {
+                status!(
+                    "🔄 Running optimization with {:?} objective ({} iterations)...",
+                    objective,
+                    iterations
+                );
+            }
+
+            let interrupt = output::Interrupt::install();
+            let started_at = chrono::Utc::now();
+            let from_cache = cached.is_some();
+            let (evaluated, interrupted) = match cached {
+                Some(cached) => (cached.candidates, false),
+                None => match objective {
+                    OptimizationObjectiveArg::Pnl => rank_ranges(
+                        &optimizer,
+                        &base_position,
+                        current_price_dec,
+                        volatility,
+                        &volume,
+                        pool_liquidity,
+                        fee_rate,
+                        &MaximizeNetPnL,
+                        &interrupt,
+                    ),
+                    OptimizationObjectiveArg::Fees => rank_ranges(
+                        &optimizer,
+                        &base_position,
+                        current_price_dec,
+                        volatility,
+                        &volume,
+                        pool_liquidity,
+                        fee_rate,
+                        &MaximizeFees,
+                        &interrupt,
+                    ),
+                    OptimizationObjectiveArg::Sharpe => rank_ranges(
+                        &optimizer,
+                        &base_position,
+                        current_price_dec,
+                        volatility,
+                        &volume,
+                        pool_liquidity,
+                        fee_rate,
+                        &MaximizeSharpeRatio::new(Decimal::from_f64(0.05).unwrap()),
+                        &interrupt,
+                    ),
+                },
+            };
+
+            let mut candidates = checkpoint
+                .map(|checkpoint| checkpoint.candidates)
+                .unwrap_or_default();
+            candidates.extend(evaluated);
+            GridSearchOptimizer::rank_candidates(&mut candidates);
+
+            if !interrupted
+                && !from_cache
+                && let Some(cache) = &cache
+            {
+                cache.put(
+                    &cache_key,
+                    &commands::optimize::OptimizeCheckpoint {
+                        seed,
+                        volatility,
+                        current_price,
+                        candidates: candidates.clone(),
+                    },
+                );
+            }
+
+            if interrupted || resume.is_some() {
+                let job_id = resume.unwrap_or_else(Uuid::new_v4);
+                let status = if interrupted {
+                    "cancelled"
+                } else {
+                    "completed"
+                };
+                if interrupted {
+                    status!(
+                        "⏸️  Optimization stopped after {} of {} ranges",
+                        candidates.len(),
+                        width_count
+                    );
+                }
+                let progress = commands::optimize::OptimizeCheckpoint {
+                    seed,
                     volatility,
-                    0.0,
-                    volume,
-                    pool_liquidity,
-                    fee_rate,
-                    MaximizeSharpeRatio::new(Decimal::from_f64(0.05).unwrap()),
-                ),
+                    current_price,
+                    candidates: candidates.clone(),
+                };
+                let record = saved_run(
+                    job_id,
+                    "optimization",
+                    status,
+                    progress_pct(candidates.len(), width_count),
+                    params.clone(),
+                    serde_json::to_value(&progress)?,
+                    started_at,
+                );
+                if save_run(database_url, &record).await && interrupted {
+                    let objective_name = format!("{:?}", objective).to_lowercase();
+                    status!(
+                        "   Resume with: clmm-lp-cli optimize --resume {} --objective {} --iterations {}",
+                        job_id,
+                        objective_name,
+                        iterations
+                    );
+                }
+            }
+
+            let Some(best) = candidates.first() else {
+                status!("❌ Interrupted before any range was evaluated.");
+                return Ok(());
             };
+            let result = RangeOptimizer::recommendation(best, current_price_dec);
+
+            let in_range = prices
+                .iter()
+                .filter(|p| {
+                    let p = Decimal::from_f64(**p).unwrap_or_default();
+                    p >= result.recommended_range.lower_price.value
+                        && p <= result.recommended_range.upper_price.value
+                })
+                .count();
+            let time_in_range = Decimal::from(in_range * 100) / Decimal::from(prices.len().max(1));
+            let report = optimization_report(
+                symbol_a,
+                current_price,
+                volatility,
+                *capital,
+                *objective,
+                &result,
+                time_in_range,
+            );
 
             // Print optimization results
-            print_optimization_report(symbol_a, current_price, volatility, *capital, &result);
+            if output::is_json() {
+                output::print_json(&report)?;
+            } else {
+                print_optimization_report(symbol_a, current_price, volatility, *capital, &result);
+            }
+
+            if let Some(path) = export {
+                let format = format.unwrap_or_else(|| output::ExportFormat::from_path(path));
+                output::export_optimization_report(&report, path, format)?;
+                status!("💾 Report exported to {}", path.display());
+            }
+
+            let run_manifest = output::RunManifest::new("optimize", params)
+                .with_seed(seed)
+                .with_data(output::DataWindow::from_candles(
+                    "birdeye",
+                    format!("{}/USDC", symbol_a),
+                    start_time,
+                    now,
+                    &candles,
+                ));
+            run_manifest.print_summary();
+            if let Some(path) = manifest {
+                run_manifest.write(path)?;
+                status!("🧾 Manifest written to {}", path.display());
+            }
+        }
+        Commands::OptimizeParams {
+            symbol_a,
+            mint_a,
+            days,
+            horizon,
+            range_width,
+            tx_cost,
+            objective,
+            top,
+        } => {
+            let args = commands::optimize_params::OptimizeParamsArgs {
+                symbol_a: symbol_a.clone(),
+                mint_a: mint_a.clone(),
+                days: *days,
+                horizon_days: *horizon,
+                range_width: *range_width,
+                tx_cost: Decimal::from_f64(*tx_cost).unwrap(),
+                objective: match objective {
+                    OptimizationObjectiveArg::Pnl => commands::optimize::ObjectiveType::Pnl,
+                    OptimizationObjectiveArg::Fees => commands::optimize::ObjectiveType::Fees,
+                    OptimizationObjectiveArg::Sharpe => commands::optimize::ObjectiveType::Sharpe,
+                },
+                top_n: *top,
+                api_key: birdeye_api_key.clone(),
+            };
+            commands::run_optimize_params(args).await?;
         }
+        #[cfg(feature = "postgres")]
         Commands::Db { action } => {
-            let database_url = env::var("DATABASE_URL")
-                .unwrap_or_else(|_| "postgres://localhost/clmm_lp".to_string());
+            let database_url = settings
+                .database
+                .url
+                .clone()
+                .unwrap_or_else(|| "postgres://localhost/clmm_lp".to_string());
 
             match action {
                 DbAction::Init => {
-                    println!("🔧 Initializing database...");
+                    status!("🔧 Initializing database...");
                     let db = Database::connect(&database_url).await?;
                     db.migrate().await?;
-                    println!("✅ Database initialized successfully!");
+                    if output::is_json() {
+                        output::print_json(&serde_json::json!({ "initialized": true }))?;
+                    } else {
+                        println!("✅ Database initialized successfully!");
+                    }
                 }
                 DbAction::Status => {
-                    println!("🔍 Checking database connection...");
-                    match Database::connect(&database_url).await {
-                        Ok(_) => {
-                            println!("✅ Connected to database: {}", database_url);
-                        }
-                        Err(e) => {
-                            println!("❌ Failed to connect: {}", e);
+                    status!("🔍 Checking database connection...");
+                    let connection = Database::connect(&database_url).await;
+                    if output::is_json() {
+                        output::print_json(&serde_json::json!({
+                            "connected": connection.is_ok(),
+                            "error": connection.as_ref().err().map(ToString::to_string),
+                        }))?;
+                    } else {
+                        match connection {
+                            Ok(_) => {
+                                println!("✅ Connected to database: {}", database_url);
+                            }
+                            Err(e) => {
+                                println!("❌ Failed to connect: {}", e);
+                            }
                         }
                     }
                 }
@@ -512,7 +2324,9 @@ async fn main() -> Result<()> {
                     let db = Database::connect(&database_url).await?;
                     let simulations = db.simulations().find_recent(*limit).await?;
 
-                    if simulations.is_empty() {
+                    if output::is_json() {
+                        output::print_json(&simulations)?;
+                    } else if simulations.is_empty() {
                         println!("No simulations found.");
                     } else {
                         println!("📊 Recent Simulations:");
@@ -531,44 +2345,974 @@ async fn main() -> Result<()> {
                         table.printstd();
                     }
                 }
-                DbAction::ListOptimizations { limit } => {
-                    let db = Database::connect(&database_url).await?;
-                    let optimizations = db.simulations().find_recent_optimizations(*limit).await?;
+                DbAction::Steps { id, limit } => {
+                    let db = Database::connect(&database_url).await?;
+                    let repo = db.simulations();
+                    let mut matches = repo.find_by_id_prefix(id).await?;
+                    let simulation = match matches.len() {
+                        0 => anyhow::bail!("No saved simulation matches '{}'", id),
+                        1 => matches.remove(0),
+                        n => {
+                            anyhow::bail!("'{}' matches {} simulations; use a longer prefix", id, n)
+                        }
+                    };
+                    let total = repo.count_steps(simulation.id).await?;
+                    let complete = repo
+                        .find_result_by_simulation(simulation.id)
+                        .await?
+                        .is_some();
+                    let steps = repo.find_last_steps(simulation.id, *limit).await?;
+
+                    if output::is_json() {
+                        output::print_json(&serde_json::json!({
+                            "simulation": simulation,
+                            "complete": complete,
+                            "total_steps": total,
+                            "steps": steps,
+                        }))?;
+                    } else {
+                        println!(
+                            "📈 Simulation {} ({}, {} steps stored)",
+                            simulation.id,
+                            if complete { "complete" } else { "incomplete" },
+                            total
+                        );
+                        println!();
+                        let mut table = Table::new();
+                        table.add_row(row![
+                            "Step", "Price", "Range", "In Range", "Value", "Fees", "IL", "PnL"
+                        ]);
+                        for step in steps {
+                            table.add_row(row![
+                                step.step,
+                                format!("${:.4}", step.price),
+                                format!("${:.2} - ${:.2}", step.lower_price, step.upper_price),
+                                if step.rebalanced {
+                                    "🔄"
+                                } else if step.in_range {
+                                    "✅"
+                                } else {
+                                    "❌"
+                                },
+                                format!("${:.2}", step.position_value),
+                                format!("${:.2}", step.cumulative_fees),
+                                format!("{:.2}%", step.il_pct * Decimal::from(100)),
+                                format!("${:+.2}", step.net_pnl)
+                            ]);
+                        }
+                        table.printstd();
+                    }
+                }
+                DbAction::ListOptimizations { limit } => {
+                    let db = Database::connect(&database_url).await?;
+                    let optimizations = db.simulations().find_recent_optimizations(*limit).await?;
+
+                    if output::is_json() {
+                        output::print_json(&optimizations)?;
+                    } else if optimizations.is_empty() {
+                        println!("No optimizations found.");
+                    } else {
+                        println!("🎯 Recent Optimizations:");
+                        println!();
+                        let mut table = Table::new();
+                        table.add_row(row!["ID", "Objective", "Range", "Expected PnL", "Created"]);
+                        for opt in optimizations {
+                            table.add_row(row![
+                                opt.id.to_string()[..8].to_string(),
+                                opt.objective_type,
+                                format!(
+                                    "${:.2} - ${:.2}",
+                                    opt.recommended_lower, opt.recommended_upper
+                                ),
+                                format!("${:+.4}", opt.expected_pnl),
+                                opt.created_at.format("%Y-%m-%d %H:%M")
+                            ]);
+                        }
+                        table.printstd();
+                    }
+                }
+                DbAction::Stats
+                | DbAction::Prune { .. }
+                | DbAction::Vacuum { .. }
+                | DbAction::Backup { .. }
+                | DbAction::Restore { .. } => {
+                    let action = match action {
+                        DbAction::Prune {
+                            older_than_days,
+                            tables,
+                            dry_run,
+                            yes,
+                        } => commands::db::DbMaintenanceAction::Prune {
+                            older_than_days: *older_than_days,
+                            tables: tables.clone(),
+                            dry_run: *dry_run,
+                            yes: *yes,
+                        },
+                        DbAction::Vacuum { tables, full } => {
+                            commands::db::DbMaintenanceAction::Vacuum {
+                                tables: tables.clone(),
+                                full: *full,
+                            }
+                        }
+                        DbAction::Backup { file, force } => {
+                            commands::db::DbMaintenanceAction::Backup {
+                                output: file.clone(),
+                                force: *force,
+                            }
+                        }
+                        DbAction::Restore { file, batch_size } => {
+                            commands::db::DbMaintenanceAction::Restore {
+                                input: file.clone(),
+                                batch_size: *batch_size,
+                            }
+                        }
+                        DbAction::Stats => commands::db::DbMaintenanceAction::Stats,
+                        _ => unreachable!("listed in the enclosing pattern"),
+                    };
+                    commands::run_db_maintenance(commands::db::DbMaintenanceArgs {
+                        database_url,
+                        action,
+                    })
+                    .await?;
+                }
+            }
+        }
+        #[cfg(feature = "postgres")]
+        Commands::Data {
+            database_url,
+            action,
+        } => {
+            let action = match action {
+                DataAction::Import {
+                    files,
+                    pool,
+                    protocol,
+                    symbol_a,
+                    mint_a,
+                    fee_tier,
+                    tick_spacing,
+                    skip_invalid,
+                    batch_size,
+                } => commands::data::DataAction::Import(commands::data::ImportArgs {
+                    files: files.clone(),
+                    pool_address: pool.clone(),
+                    protocol: protocol.clone(),
+                    symbol_a: symbol_a.clone(),
+                    mint_a: mint_a.clone(),
+                    fee_tier: *fee_tier,
+                    tick_spacing: *tick_spacing,
+                    skip_invalid: *skip_invalid,
+                    batch_size: *batch_size,
+                    database_url: database_url.clone(),
+                }),
+                DataAction::Export {
+                    token_a,
+                    token_b,
+                    pool,
+                    days,
+                    output,
+                    format,
+                } => {
+                    let format = match format {
+                        Some(TableFormatArg::Csv) => TableFormat::Csv,
+                        Some(TableFormatArg::Parquet) => TableFormat::Parquet,
+                        None => output
+                            .extension()
+                            .and_then(|ext| ext.to_str())
+                            .and_then(|ext| ext.parse().ok())
+                            .unwrap_or_default(),
+                    };
+                    commands::data::DataAction::ExportStored(commands::data::ExportStoredArgs {
+                        pool_address: pool.clone(),
+                        token_a: token_a.clone(),
+                        token_b: token_b.clone(),
+                        days: *days,
+                        output: output.clone(),
+                        format,
+                        database_url: database_url.clone(),
+                    })
+                }
+            };
+            commands::run_data(commands::data::DataArgs { action }).await?;
+        }
+        Commands::Emergency {
+            api_url,
+            api_key,
+            action,
+        } => {
+            let portfolio = matches!(
+                action,
+                EmergencyAction::Risk | EmergencyAction::Stop { .. } | EmergencyAction::Release
+            );
+            let url = format!(
+                "{}/emergency/{}",
+                api_url.trim_end_matches('/'),
+                if portfolio {
+                    "portfolio-stop"
+                } else {
+                    "kill-switch"
+                }
+            );
+            let client = reqwest::Client::new();
+
+            let request = match action {
+                EmergencyAction::Status | EmergencyAction::Risk => client.get(&url),
+                EmergencyAction::Halt { reason } => {
+                    status!("🛑 Engaging kill switch...");
+                    client
+                        .post(&url)
+                        .json(&serde_json::json!({ "reason": reason }))
+                }
+                EmergencyAction::Resume => {
+                    status!("▶️  Releasing kill switch...");
+                    client.delete(&url)
+                }
+                EmergencyAction::Stop { reason } => {
+                    status!("🛑 Engaging portfolio stop...");
+                    client
+                        .post(&url)
+                        .json(&serde_json::json!({ "reason": reason }))
+                }
+                EmergencyAction::Release => {
+                    status!("▶️  Releasing portfolio stop...");
+                    client.delete(&url)
+                }
+            };
+            let request = match api_key {
+                Some(key) => request.header("X-API-Key", key),
+                None => request,
+            };
+
+            let response = request.send().await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API request failed ({}): {}", status, body);
+            }
+            let status: serde_json::Value = response.json().await?;
+            if output::is_json() {
+                output::print_json(&status)?;
+                return Ok(());
+            }
+
+            let field = |name: &str| match &status[name] {
+                serde_json::Value::Null => "-".to_string(),
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+
+            if portfolio {
+                let mut table = Table::new();
+                table.add_row(row!["Value (USD)", field("value_usd")]);
+                table.add_row(row!["Peak Value (USD)", field("peak_value_usd")]);
+                table.add_row(row![
+                    "Drawdown",
+                    format!(
+                        "{}% / {}%",
+                        field("drawdown_pct"),
+                        field("max_drawdown_pct")
+                    )
+                ]);
+                table.add_row(row![
+                    "Max Token Exposure (USD)",
+                    field("max_token_exposure_usd")
+                ]);
+                table.add_row(row!["Unwind On Stop", field("unwind_on_stop")]);
+                table.add_row(row!["Stopped", field("stopped")]);
+                table.add_row(row!["Reason", field("stop_reason")]);
+                table.add_row(row!["Stopped At", field("stopped_at")]);
+                table.add_row(row!["Unwound Positions", field("unwound_positions")]);
+                table.add_row(row!["Checked At", field("checked_at")]);
+                table.printstd();
+
+                if let Some(exposures) = status["exposures"].as_array()
+                    && !exposures.is_empty()
+                {
+                    let mut table = Table::new();
+                    table.add_row(row![
+                        "Token",
+                        "Amount",
+                        "Value (USD)",
+                        "Share",
+                        "Limit (USD)"
+                    ]);
+                    for exposure in exposures {
+                        let value = |name: &str| match &exposure[name] {
+                            serde_json::Value::Null => "-".to_string(),
+                            serde_json::Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        };
+                        table.add_row(row![
+                            value("mint"),
+                            value("amount"),
+                            value("value_usd"),
+                            format!("{}%", value("share_pct")),
+                            value("limit_usd")
+                        ]);
+                    }
+                    table.printstd();
+                }
+                for breach in status["breaches"].as_array().into_iter().flatten() {
+                    if let Some(breach) = breach.as_str() {
+                        println!("⚠️  {}", breach);
+                    }
+                }
+                return Ok(());
+            }
+
+            let mut table = Table::new();
+            table.add_row(row!["Circuit Breaker", field("circuit_breaker")]);
+            table.add_row(row!["Kill Switch Engaged", field("kill_switch_engaged")]);
+            table.add_row(row!["Execution Allowed", field("execution_allowed")]);
+            table.add_row(row![
+                "Failures",
+                format!("{} / {}", field("failure_count"), field("max_failures"))
+            ]);
+            table.add_row(row!["Reason", field("reason")]);
+            table.add_row(row!["Tripped At", field("tripped_at")]);
+            table.printstd();
+        }
+        Commands::Alerts {
+            api_url,
+            api_key,
+            action,
+        } => {
+            let url = format!("{}/alerts/rules", api_url.trim_end_matches('/'));
+            let client = reqwest::Client::new();
+            let level_name = |level: &AlertLevelArg| match level {
+                AlertLevelArg::Info => "info",
+                AlertLevelArg::Warning => "warning",
+                AlertLevelArg::Critical => "critical",
+            };
+
+            let request = match action {
+                AlertAction::Rules => client.get(&url),
+                AlertAction::AddRule {
+                    name,
+                    condition,
+                    level,
+                    message,
+                    cooldown,
+                    on_change,
+                } => client.post(&url).json(&serde_json::json!({
+                    "name": name,
+                    "condition": condition,
+                    "level": level_name(level),
+                    "message": message,
+                    "cooldown_secs": cooldown,
+                    "on_change": on_change,
+                })),
+                AlertAction::RemoveRule { name } => client.delete(format!("{}/{}", url, name)),
+                AlertAction::Test {
+                    channel,
+                    level,
+                    message,
+                } => client
+                    .post(format!("{}/alerts/test", api_url.trim_end_matches('/')))
+                    .json(&serde_json::json!({
+                        "channel": channel,
+                        "level": level_name(level),
+                        "message": message,
+                    })),
+            };
+            let request = match api_key {
+                Some(key) => request.header("X-API-Key", key),
+                None => request,
+            };
+
+            let response = request.send().await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API request failed ({}): {}", status, body);
+            }
+            let body: serde_json::Value = response.json().await?;
+            if output::is_json() {
+                output::print_json(&body)?;
+                let failed = body["failed"].as_u64().unwrap_or(0);
+                if matches!(action, AlertAction::Test { .. }) && failed > 0 {
+                    anyhow::bail!("{} alert channels failed", failed);
+                }
+                return Ok(());
+            }
+
+            let rules = match action {
+                AlertAction::Rules => body["rules"].as_array().cloned().unwrap_or_default(),
+                AlertAction::AddRule { .. } => {
+                    println!("✅ Alert rule saved");
+                    vec![body]
+                }
+                AlertAction::RemoveRule { name } => {
+                    println!("🗑️  Alert rule {} removed", name);
+                    Vec::new()
+                }
+                AlertAction::Test { .. } => {
+                    let channels = body["channels"].as_array().cloned().unwrap_or_default();
+                    let mut table = Table::new();
+                    table.add_row(row!["Channel", "Role", "Result"]);
+                    for channel in &channels {
+                        let role = if channel["primary"].as_bool().unwrap_or(false) {
+                            "primary"
+                        } else {
+                            "escalation"
+                        };
+                        let result = if channel["delivered"].as_bool().unwrap_or(false) {
+                            "✅ delivered".to_string()
+                        } else {
+                            format!("❌ {}", channel["error"].as_str().unwrap_or("failed"))
+                        };
+                        table.add_row(row![
+                            channel["channel"].as_str().unwrap_or("-"),
+                            role,
+                            result
+                        ]);
+                    }
+                    table.printstd();
+
+                    let failed = body["failed"].as_u64().unwrap_or(0);
+                    if failed > 0 {
+                        anyhow::bail!("{} of {} alert channels failed", failed, channels.len());
+                    }
+                    println!("Check each channel for the test message.");
+                    Vec::new()
+                }
+            };
+
+            if !rules.is_empty() {
+                let text = |value: &serde_json::Value| match value {
+                    serde_json::Value::Null => "-".to_string(),
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+
+                let mut table = Table::new();
+                table.add_row(row!["Name", "Level", "Condition", "Cooldown", "On Change"]);
+                for rule in &rules {
+                    table.add_row(row![
+                        text(&rule["name"]),
+                        text(&rule["level"]),
+                        text(&rule["condition"]),
+                        format!("{}s", text(&rule["cooldown_secs"])),
+                        text(&rule["on_change"]),
+                    ]);
+                }
+                table.printstd();
+            } else if matches!(action, AlertAction::Rules) {
+                println!("No alert rules configured");
+            }
+        }
+        Commands::Transcript {
+            api_url,
+            api_key,
+            strategy_id,
+            limit,
+            output,
+        } => {
+            let url = format!(
+                "{}/strategies/{}/transcript",
+                api_url.trim_end_matches('/'),
+                strategy_id
+            );
+            let request = reqwest::Client::new()
+                .get(&url)
+                .query(&[("limit", limit.to_string())]);
+            let request = match api_key {
+                Some(key) => request.header("X-API-Key", key),
+                None => request,
+            };
+
+            let response = request.send().await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API request failed ({}): {}", status, body);
+            }
+            let transcript: serde_json::Value = response.json().await?;
+
+            if let Some(path) = output {
+                std::fs::write(path, serde_json::to_string_pretty(&transcript)?)?;
+                status!("💾 Transcript written to {}", path.display());
+            }
+            if output::is_json() {
+                output::print_json(&transcript)?;
+                return Ok(());
+            }
+
+            let steps = transcript["steps"].as_array().cloned().unwrap_or_default();
+            if steps.is_empty() {
+                println!("No dry-run actions recorded for strategy {}", strategy_id);
+            } else {
+                let text = |value: &serde_json::Value| match value {
+                    serde_json::Value::Null => "-".to_string(),
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+
+                let mut table = Table::new();
+                table.add_row(row![
+                    "Time",
+                    "Position",
+                    "Action",
+                    "Instructions",
+                    "Amount A",
+                    "Amount B",
+                    "Cost (lamports)"
+                ]);
+                for step in &steps {
+                    let instructions: Vec<String> = step["instructions"]
+                        .as_array()
+                        .map(|list| list.iter().map(|i| text(&i["instruction"])).collect())
+                        .unwrap_or_default();
+                    table.add_row(row![
+                        text(&step["timestamp"]),
+                        text(&step["position_address"]),
+                        text(&step["action"]),
+                        instructions.join(" → "),
+                        text(&step["expected_amount_a"]),
+                        text(&step["expected_amount_b"]),
+                        text(&step["estimated_cost_lamports"]),
+                    ]);
+                }
+                table.printstd();
+                println!(
+                    "{} planned actions, estimated cost {} lamports",
+                    text(&transcript["total"]),
+                    text(&transcript["total_estimated_cost_lamports"])
+                );
+            }
+        }
+        Commands::Strategy {
+            api_url,
+            api_key,
+            action,
+        } => {
+            let base = api_url.trim_end_matches('/');
+            let client = reqwest::Client::new();
+
+            let request = match action {
+                StrategyAction::Export { id, .. } => {
+                    client.get(format!("{}/strategies/{}/export", base, id))
+                }
+                StrategyAction::Import { file } => {
+                    let document = std::fs::read_to_string(file).map_err(|e| {
+                        anyhow::anyhow!("Failed to read strategy file {}: {}", file.display(), e)
+                    })?;
+                    client
+                        .post(format!("{}/strategies/import", base))
+                        .header(reqwest::header::CONTENT_TYPE, "application/yaml")
+                        .body(document)
+                }
+            };
+            let request = match api_key {
+                Some(key) => request.header("X-API-Key", key),
+                None => request,
+            };
+
+            let response = request.send().await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API request failed ({}): {}", status, body);
+            }
+
+            match action {
+                StrategyAction::Export { id, output } => {
+                    let document = response.text().await?;
+                    match output {
+                        Some(path) => {
+                            std::fs::write(path, &document)?;
+                            status!("💾 Strategy {} written to {}", id, path.display());
+                        }
+                        None => print!("{}", document),
+                    }
+                }
+                StrategyAction::Import { file } => {
+                    let strategy: serde_json::Value = response.json().await?;
+                    if output::is_json() {
+                        output::print_json(&strategy)?;
+                        return Ok(());
+                    }
+                    println!(
+                        "✅ Imported {} as strategy {} ({}, stopped)",
+                        file.display(),
+                        strategy["id"].as_str().unwrap_or("-"),
+                        strategy["name"].as_str().unwrap_or("-")
+                    );
+                }
+            }
+        }
+        Commands::Pools {
+            api_url,
+            api_key,
+            token_a,
+            token_b,
+            protocol,
+            sort,
+            asc,
+            min_tvl,
+            limit,
+        } => {
+            let url = format!("{}/pools/search", api_url.trim_end_matches('/'));
+            let mut query = vec![
+                ("token_a", token_a.clone()),
+                ("token_b", token_b.clone()),
+                ("sort", sort.as_query().to_string()),
+                ("order", if *asc { "asc" } else { "desc" }.to_string()),
+                ("per_page", (*limit).clamp(1, 100).to_string()),
+            ];
+            if let Some(protocol) = protocol {
+                query.push(("protocol", protocol.clone()));
+            }
+            if let Some(min_tvl) = min_tvl {
+                query.push(("min_tvl", min_tvl.to_string()));
+            }
+            let request = reqwest::Client::new().get(&url).query(&query);
+            let request = match api_key {
+                Some(key) => request.header("X-API-Key", key),
+                None => request,
+            };
+
+            let response = request.send().await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API request failed ({}): {}", status, body);
+            }
+            let body: serde_json::Value = response.json().await?;
+            if output::is_json() {
+                output::print_json(&body)?;
+                return Ok(());
+            }
+
+            let pools = body["pools"].as_array().cloned().unwrap_or_default();
+            if pools.is_empty() {
+                println!("No pools found for {}/{}", token_a, token_b);
+            } else {
+                let decimal = |value: &serde_json::Value| -> Option<Decimal> {
+                    match value {
+                        serde_json::Value::String(s) => s.parse().ok(),
+                        serde_json::Value::Number(n) => n.to_string().parse().ok(),
+                        _ => None,
+                    }
+                };
+                let usd = |value: &serde_json::Value| {
+                    decimal(value).map_or_else(|| "-".to_string(), |v| format!("${:.0}", v))
+                };
+
+                println!(
+                    "🏊 {} of {} pools for {}/{}",
+                    pools.len(),
+                    body["total"],
+                    token_a,
+                    token_b
+                );
+                let mut table = Table::new();
+                table.add_row(row![
+                    "#",
+                    "Protocol",
+                    "Pool",
+                    "Pair",
+                    "Fee Tier",
+                    "TVL",
+                    "24h Volume",
+                    "Fee APR"
+                ]);
+                for (i, pool) in pools.iter().enumerate() {
+                    let fee_bps = pool["fee_rate_bps"].as_u64().unwrap_or_default();
+                    table.add_row(row![
+                        i + 1,
+                        pool["protocol"].as_str().unwrap_or("-"),
+                        pool["address"].as_str().unwrap_or("-"),
+                        format!(
+                            "{}/{}",
+                            pool["symbol_a"].as_str().unwrap_or("?"),
+                            pool["symbol_b"].as_str().unwrap_or("?")
+                        ),
+                        format!("{:.2}%", fee_bps as f64 / 100.0),
+                        usd(&pool["tvl_usd"]),
+                        usd(&pool["volume_24h_usd"]),
+                        decimal(&pool["fee_apr"]).map_or_else(
+                            || "-".to_string(),
+                            |apr| format!("{:.2}%", apr * Decimal::from(100))
+                        ),
+                    ]);
+                }
+                table.printstd();
+            }
+        }
+        Commands::Recommend {
+            api_url,
+            api_key,
+            position,
+            horizon_hours,
+            volatility,
+            fee_apr,
+            width,
+        } => {
+            let url = format!(
+                "{}/positions/{}/recommendation",
+                api_url.trim_end_matches('/'),
+                position
+            );
+            let mut query = Vec::new();
+            if let Some(hours) = horizon_hours {
+                query.push(("horizon_hours", hours.to_string()));
+            }
+            if let Some(volatility) = volatility {
+                query.push(("volatility", volatility.to_string()));
+            }
+            if let Some(apr) = fee_apr {
+                query.push(("fee_apr_pct", apr.to_string()));
+            }
+            if let Some(width) = width {
+                query.push(("range_width_pct", width.to_string()));
+            }
+            let request = reqwest::Client::new().get(&url).query(&query);
+            let request = match api_key {
+                Some(key) => request.header("X-API-Key", key),
+                None => request,
+            };
+
+            let response = request.send().await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("API request failed ({}): {}", status, body);
+            }
+            let body: serde_json::Value = response.json().await?;
+            if output::is_json() {
+                output::print_json(&body)?;
+                return Ok(());
+            }
+
+            let text = |value: &serde_json::Value| match value {
+                serde_json::Value::Null => "-".to_string(),
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let impact = &body["impact"];
+
+            println!(
+                "💡 {}: {}",
+                text(&body["action"]).to_uppercase(),
+                text(&body["reason"])
+            );
+            println!(
+                "   Pool price {} (tick {}), range [{}, {}] {}",
+                text(&body["price"]),
+                text(&body["tick_current"]),
+                text(&body["tick_lower"]),
+                text(&body["tick_upper"]),
+                if body["in_range"].as_bool().unwrap_or_default() {
+                    "in range"
+                } else {
+                    "out of range"
+                }
+            );
+            if !body["new_tick_lower"].is_null() {
+                println!(
+                    "   Proposed range [{}, {}] (price {} - {})",
+                    text(&body["new_tick_lower"]),
+                    text(&body["new_tick_upper"]),
+                    text(&body["new_price_lower"]),
+                    text(&body["new_price_upper"])
+                );
+            }
 
-                    if optimizations.is_empty() {
-                        println!("No optimizations found.");
-                    } else {
-                        println!("🎯 Recent Optimizations:");
-                        println!();
-                        let mut table = Table::new();
-                        table.add_row(row!["ID", "Objective", "Range", "Expected PnL", "Created"]);
-                        for opt in optimizations {
-                            table.add_row(row![
-                                opt.id.to_string()[..8].to_string(),
-                                opt.objective_type,
-                                format!(
-                                    "${:.2} - ${:.2}",
-                                    opt.recommended_lower, opt.recommended_upper
-                                ),
-                                format!("${:+.4}", opt.expected_pnl),
-                                opt.created_at.format("%Y-%m-%d %H:%M")
-                            ]);
-                        }
-                        table.printstd();
+            println!(
+                "\nExpected impact over {}h at {} volatility:",
+                text(&impact["horizon_hours"]),
+                text(&impact["volatility"])
+            );
+            let mut table = Table::new();
+            table.add_row(row!["", "Current", "Proposed"]);
+            table.add_row(row![
+                "Time in range %",
+                text(&impact["current_time_in_range_pct"]),
+                text(&impact["proposed_time_in_range_pct"])
+            ]);
+            table.add_row(row![
+                "Fee multiplier",
+                text(&impact["current_fee_multiplier"]),
+                text(&impact["proposed_fee_multiplier"])
+            ]);
+            table.add_row(row![
+                "Fees (USD)",
+                text(&impact["current_fees_usd"]),
+                text(&impact["proposed_fees_usd"])
+            ]);
+            table.printstd();
+            match &impact["net_benefit_usd"] {
+                serde_json::Value::Null => println!("Cost ${}", text(&impact["cost_usd"])),
+                net => println!(
+                    "Cost ${}, net benefit ${}",
+                    text(&impact["cost_usd"]),
+                    text(net)
+                ),
+            }
+        }
+        #[cfg(feature = "live")]
+        Commands::Positions { wallet, rpc_url } => {
+            commands::run_positions(commands::positions::PositionsArgs {
+                wallet: wallet.clone(),
+                rpc_url: rpc_url.clone(),
+            })
+            .await?;
+        }
+        #[cfg(feature = "live")]
+        Commands::Position {
+            rpc_url,
+            keypair,
+            slippage_bps,
+            max_oracle_divergence_bps,
+            execute,
+            yes,
+            action,
+        } => {
+            let action = match action {
+                PositionAction::Open {
+                    pool,
+                    lower_price,
+                    upper_price,
+                    amount_a,
+                    amount_b,
+                } => commands::position::PositionAction::Open {
+                    pool: pool.clone(),
+                    lower_price: *lower_price,
+                    upper_price: *upper_price,
+                    amount_a: *amount_a,
+                    amount_b: *amount_b,
+                },
+                PositionAction::Close { position } => commands::position::PositionAction::Close {
+                    position: position.clone(),
+                },
+                PositionAction::Rebalance {
+                    position,
+                    lower_price,
+                    upper_price,
+                } => commands::position::PositionAction::Rebalance {
+                    position: position.clone(),
+                    lower_price: *lower_price,
+                    upper_price: *upper_price,
+                },
+            };
+            commands::run_position(commands::position::PositionArgs {
+                action,
+                rpc_url: rpc_url.clone(),
+                keypair: keypair.clone(),
+                slippage_bps: *slippage_bps,
+                max_oracle_divergence_bps: *max_oracle_divergence_bps,
+                execute: *execute,
+                yes: *yes,
+            })
+            .await?;
+        }
+        #[cfg(feature = "live")]
+        Commands::CollectFees {
+            position,
+            wallet,
+            rpc_url,
+            keypair,
+            execute,
+            yes,
+        } => {
+            commands::run_collect_fees(commands::collect::CollectArgs {
+                position: position.clone(),
+                wallet: wallet.clone(),
+                rpc_url: rpc_url.clone(),
+                keypair: keypair.clone(),
+                execute: *execute,
+                yes: *yes,
+            })
+            .await?;
+        }
+        #[cfg(feature = "live")]
+        Commands::Wallet { action } => {
+            let action = match action {
+                WalletAction::New {
+                    label,
+                    output,
+                    plain,
+                    force,
+                } => commands::wallet::WalletAction::New {
+                    label: label.clone(),
+                    output: output.clone(),
+                    plain: *plain,
+                    force: *force,
+                },
+                WalletAction::Import {
+                    keypair,
+                    from_env,
+                    label,
+                    output,
+                    force,
+                } => commands::wallet::WalletAction::Import {
+                    keypair: keypair.clone(),
+                    from_env: from_env.clone(),
+                    label: label.clone(),
+                    output: output.clone(),
+                    force: *force,
+                },
+                WalletAction::Show { wallet } => commands::wallet::WalletAction::Show {
+                    wallet: wallet.clone(),
+                },
+                WalletAction::Balance { wallet, rpc_url } => {
+                    commands::wallet::WalletAction::Balance {
+                        wallet: wallet.clone(),
+                        rpc_url: rpc_url.clone(),
                     }
                 }
-            }
+            };
+            commands::run_wallet(commands::wallet::WalletArgs { action }).await?;
+        }
+        Commands::Monitor {
+            api_url,
+            api_key,
+            interval,
+            alerts,
+        } => {
+            commands::run_monitor(commands::monitor::MonitorArgs {
+                api_url: api_url.clone(),
+                api_key: api_key.clone(),
+                interval_secs: *interval,
+                alert_limit: *alerts,
+            })
+            .await?;
+        }
+        Commands::AnalyzeBatch {
+            watchlist,
+            days,
+            fee_bps,
+            concurrency,
+            sort,
+            top,
+        } => {
+            let args = commands::analyze_batch::AnalyzeBatchArgs {
+                watchlist: watchlist.clone(),
+                days: *days,
+                fee_bps: *fee_bps,
+                concurrency: *concurrency,
+                sort: *sort,
+                top: *top,
+                api_key: birdeye_api_key.clone(),
+            };
+            commands::run_analyze_batch(args).await?;
         }
         Commands::Analyze {
             symbol_a,
             mint_a,
             days,
+            export,
+            format,
+            watch,
+            edge_margin,
         } => {
-            let api_key = env::var("BIRDEYE_API_KEY")
-                .expect("BIRDEYE_API_KEY must be set in .env or environment");
+            let api_key = birdeye_api_key
+                .clone()
+                .expect("BIRDEYE_API_KEY must be set in .env, environment or config file");
 
-            println!("📊 Analyzing {}/USDC over {} days...", symbol_a, days);
-            println!();
+            status!("📊 Analyzing {}/USDC over {} days...", symbol_a, days);
+            status!();
 
             let provider = BirdeyeProvider::new(api_key);
 
@@ -580,165 +3324,288 @@ async fn main() -> Result<()> {
                 "USD Coin",
             );
 
-            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-            let start_time = now - (days * 24 * 3600);
+            let mut anchor: Option<(f64, f64)> = None;
+            loop {
+                if watch.is_some() && !output::is_json() {
+                    print!("\x1B[2J\x1B[H");
+                    println!(
+                        "🔄 Watching {}/USDC, updated {} (Ctrl+C to stop)",
+                        symbol_a,
+                        chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+                    );
+                    println!();
+                }
 
-            let candles = provider
-                .get_price_history(&token_a, &token_b, start_time, now, 3600)
-                .await?;
+                'analysis: {
+                    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                    let start_time = now - (days * 24 * 3600);
 
-            if candles.is_empty() {
-                println!("❌ No data available for the specified period.");
-                return Ok(());
-            }
+                    let candles = provider
+                        .get_price_history(&token_a, &token_b, start_time, now, 3600)
+                        .await?;
 
-            // Calculate statistics
-            let prices: Vec<f64> = candles
-                .iter()
-                .filter_map(|c| c.close.value.to_f64())
-                .collect();
+                    if candles.is_empty() {
+                        status!("❌ No data available for the specified period.");
+                        break 'analysis;
+                    }
 
-            let current_price = prices.last().copied().unwrap_or(0.0);
-            let first_price = prices.first().copied().unwrap_or(0.0);
-            let max_price = prices.iter().copied().fold(f64::MIN, f64::max);
-            let min_price = prices.iter().copied().fold(f64::MAX, f64::min);
-            let avg_price = prices.iter().sum::<f64>() / prices.len() as f64;
+                    // Calculate statistics
+                    let prices: Vec<f64> = candles
+                        .iter()
+                        .filter_map(|c| c.close.value.to_f64())
+                        .collect();
 
-            let price_change = if first_price > 0.0 {
-                (current_price - first_price) / first_price * 100.0
-            } else {
-                0.0
-            };
+                    let current_price = prices.last().copied().unwrap_or(0.0);
+                    let first_price = prices.first().copied().unwrap_or(0.0);
+                    let max_price = prices.iter().copied().fold(f64::MIN, f64::max);
+                    let min_price = prices.iter().copied().fold(f64::MAX, f64::min);
+                    let avg_price = prices.iter().sum::<f64>() / prices.len() as f64;
 
-            let volatility = calculate_volatility(&prices);
-            let volatility_daily = volatility / (365.0_f64).sqrt();
+                    let price_change = if first_price > 0.0 {
+                        (current_price - first_price) / first_price * 100.0
+                    } else {
+                        0.0
+                    };
 
-            // Calculate volume stats
-            let total_volume: f64 = candles
-                .iter()
-                .map(|c| c.volume_token_a.to_decimal().to_f64().unwrap_or(0.0))
-                .sum();
-            let avg_hourly_volume = total_volume / candles.len() as f64;
+                    let volatility = calculate_volatility(&prices);
+                    let volatility_daily = volatility / (365.0_f64).sqrt();
 
-            // Print analysis report
-            println!("🎯 ANALYSIS RESULTS: {}/USDC", symbol_a);
-            println!();
+                    // Calculate volume stats
+                    let total_volume: f64 = candles
+                        .iter()
+                        .map(|c| c.volume_token_a.to_decimal().to_f64().unwrap_or(0.0))
+                        .sum();
+                    let avg_hourly_volume = total_volume / candles.len() as f64;
 
-            // Price Statistics Table
-            let mut price_table = Table::new();
-            price_table.add_row(row!["PRICE STATISTICS", ""]);
-            price_table.add_row(row!["Current Price", format!("${:.4}", current_price)]);
-            price_table.add_row(row!["Period Start", format!("${:.4}", first_price)]);
-            price_table.add_row(row!["Period High", format!("${:.4}", max_price)]);
-            price_table.add_row(row!["Period Low", format!("${:.4}", min_price)]);
-            price_table.add_row(row!["Average Price", format!("${:.4}", avg_price)]);
-            price_table.add_row(row!["Price Change", format!("{:+.2}%", price_change)]);
-            price_table.add_row(row![
-                "Price Range",
-                format!("${:.4} - ${:.4}", min_price, max_price)
-            ]);
-            price_table.printstd();
+                    let range_2x = current_price * volatility_daily * 2.0;
+                    let recommended_lower = current_price - range_2x;
+                    let recommended_upper = current_price + range_2x;
+                    let in_range = prices
+                        .iter()
+                        .filter(|p| **p >= recommended_lower && **p <= recommended_upper)
+                        .count();
+                    let report = output::AnalysisReport {
+                        pair: format!("{}/USDC", symbol_a),
+                        period_days: *days,
+                        current_price: Decimal::from_f64(current_price).unwrap_or_default(),
+                        high_price: Decimal::from_f64(max_price).unwrap_or_default(),
+                        low_price: Decimal::from_f64(min_price).unwrap_or_default(),
+                        avg_price: Decimal::from_f64(avg_price).unwrap_or_default(),
+                        volatility_daily: Decimal::from_f64(volatility_daily).unwrap_or_default(),
+                        volatility_annual: Decimal::from_f64(volatility).unwrap_or_default(),
+                        recommended_lower: Decimal::from_f64(recommended_lower).unwrap_or_default(),
+                        recommended_upper: Decimal::from_f64(recommended_upper).unwrap_or_default(),
+                        recommended_width: Decimal::from_f64(volatility_daily * 2.0)
+                            .unwrap_or_default(),
+                        estimated_time_in_range: Decimal::from(in_range * 100)
+                            / Decimal::from(prices.len().max(1)),
+                        data_points: prices.len(),
+                    };
 
-            println!();
+                    if output::is_json() {
+                        output::print_json(&report)?;
+                    } else {
+                        // Print analysis report
+                        println!("🎯 ANALYSIS RESULTS: {}/USDC", symbol_a);
+                        println!();
 
-            // Volatility Table
-            let mut vol_table = Table::new();
-            vol_table.add_row(row!["VOLATILITY METRICS", ""]);
-            vol_table.add_row(row![
-                "Annualized Volatility",
-                format!("{:.1}%", volatility * 100.0)
-            ]);
-            vol_table.add_row(row![
-                "Daily Volatility",
-                format!("{:.2}%", volatility_daily * 100.0)
-            ]);
-            vol_table.add_row(row!["Data Points", format!("{} candles", candles.len())]);
-            vol_table.printstd();
+                        // Price Statistics Table
+                        let mut price_table = Table::new();
+                        price_table.add_row(row!["PRICE STATISTICS", ""]);
+                        price_table
+                            .add_row(row!["Current Price", format!("${:.4}", current_price)]);
+                        price_table.add_row(row!["Period Start", format!("${:.4}", first_price)]);
+                        price_table.add_row(row!["Period High", format!("${:.4}", max_price)]);
+                        price_table.add_row(row!["Period Low", format!("${:.4}", min_price)]);
+                        price_table.add_row(row!["Average Price", format!("${:.4}", avg_price)]);
+                        price_table.add_row(row!["Price Change", format!("{:+.2}%", price_change)]);
+                        price_table.add_row(row![
+                            "Price Range",
+                            format!("${:.4} - ${:.4}", min_price, max_price)
+                        ]);
+                        price_table.printstd();
 
-            println!();
+                        println!();
 
-            // Volume Table
-            let mut volume_table = Table::new();
-            volume_table.add_row(row!["VOLUME METRICS", ""]);
-            volume_table.add_row(row![
-                "Total Volume",
-                format!("{:.2} {}", total_volume, symbol_a)
-            ]);
-            volume_table.add_row(row![
-                "Avg Hourly Volume",
-                format!("{:.2} {}", avg_hourly_volume, symbol_a)
-            ]);
-            volume_table.add_row(row![
-                "Avg Daily Volume",
-                format!("{:.2} {}", avg_hourly_volume * 24.0, symbol_a)
-            ]);
-            volume_table.printstd();
+                        // Volatility Table
+                        let mut vol_table = Table::new();
+                        vol_table.add_row(row!["VOLATILITY METRICS", ""]);
+                        vol_table.add_row(row![
+                            "Annualized Volatility",
+                            format!("{:.1}%", volatility * 100.0)
+                        ]);
+                        vol_table.add_row(row![
+                            "Daily Volatility",
+                            format!("{:.2}%", volatility_daily * 100.0)
+                        ]);
+                        vol_table
+                            .add_row(row!["Data Points", format!("{} candles", candles.len())]);
+                        vol_table.printstd();
 
-            println!();
+                        println!();
 
-            // Suggested ranges based on volatility
-            let range_1x = current_price * volatility_daily;
-            let range_2x = current_price * volatility_daily * 2.0;
-
-            let mut suggest_table = Table::new();
-            suggest_table.add_row(row!["SUGGESTED LP RANGES", ""]);
-            suggest_table.add_row(row![
-                "Conservative (1σ daily)",
-                format!(
-                    "${:.2} - ${:.2}",
-                    current_price - range_1x,
-                    current_price + range_1x
-                )
-            ]);
-            suggest_table.add_row(row![
-                "Moderate (2σ daily)",
-                format!(
-                    "${:.2} - ${:.2}",
-                    current_price - range_2x,
-                    current_price + range_2x
-                )
-            ]);
-            suggest_table.add_row(row![
-                "Wide (period range)",
-                format!("${:.2} - ${:.2}", min_price * 0.95, max_price * 1.05)
-            ]);
-            suggest_table.printstd();
+                        // Volume Table
+                        let mut volume_table = Table::new();
+                        volume_table.add_row(row!["VOLUME METRICS", ""]);
+                        volume_table.add_row(row![
+                            "Total Volume",
+                            format!("{:.2} {}", total_volume, symbol_a)
+                        ]);
+                        volume_table.add_row(row![
+                            "Avg Hourly Volume",
+                            format!("{:.2} {}", avg_hourly_volume, symbol_a)
+                        ]);
+                        volume_table.add_row(row![
+                            "Avg Daily Volume",
+                            format!("{:.2} {}", avg_hourly_volume * 24.0, symbol_a)
+                        ]);
+                        volume_table.printstd();
 
-            println!();
-            println!("💡 Tip: Use these ranges with the backtest command:");
-            println!(
-                "   clmm-lp-cli backtest --lower {:.2} --upper {:.2} --days {}",
-                current_price - range_2x,
-                current_price + range_2x,
-                days
-            );
-            println!();
+                        println!();
+
+                        // Suggested ranges based on volatility
+                        let range_1x = current_price * volatility_daily;
+
+                        let mut suggest_table = Table::new();
+                        suggest_table.add_row(row!["SUGGESTED LP RANGES", ""]);
+                        suggest_table.add_row(row![
+                            "Conservative (1σ daily)",
+                            format!(
+                                "${:.2} - ${:.2}",
+                                current_price - range_1x,
+                                current_price + range_1x
+                            )
+                        ]);
+                        suggest_table.add_row(row![
+                            "Moderate (2σ daily)",
+                            format!(
+                                "${:.2} - ${:.2}",
+                                current_price - range_2x,
+                                current_price + range_2x
+                            )
+                        ]);
+                        suggest_table.add_row(row![
+                            "Wide (period range)",
+                            format!("${:.2} - ${:.2}", min_price * 0.95, max_price * 1.05)
+                        ]);
+                        suggest_table.printstd();
+
+                        // Watch mode keeps comparing against the first moderate range.
+                        let (watch_lower, watch_upper) = *anchor
+                            .get_or_insert((current_price - range_2x, current_price + range_2x));
+                        if watch.is_some() {
+                            println!();
+                            print_edge_status(
+                                current_price,
+                                watch_lower,
+                                watch_upper,
+                                *edge_margin,
+                            );
+                        }
+
+                        println!();
+                        println!("📈 PRICE VS MODERATE RANGE");
+                        println!();
+                        let lower = Decimal::from_f64(watch_lower).unwrap_or_default();
+                        let upper = Decimal::from_f64(watch_upper).unwrap_or_default();
+                        let points: Vec<output::RangePoint> = prices
+                            .iter()
+                            .map(|p| output::RangePoint {
+                                price: Decimal::from_f64(*p).unwrap_or_default(),
+                                lower,
+                                upper,
+                                rebalanced: false,
+                            })
+                            .collect();
+                        print!(
+                            "{}",
+                            output::render_range_chart(&points, &output::ChartConfig::default())
+                        );
+
+                        println!();
+                        println!("💡 Tip: Use these ranges with the backtest command:");
+                        println!(
+                            "   clmm-lp-cli backtest --lower {:.2} --upper {:.2} --days {}",
+                            current_price - range_2x,
+                            current_price + range_2x,
+                            days
+                        );
+                        println!();
+                    }
+
+                    if let Some(path) = export {
+                        let format =
+                            format.unwrap_or_else(|| output::ExportFormat::from_path(path));
+                        output::export_analysis_report(&report, path, format)?;
+                        status!("💾 Report exported to {}", path.display());
+                    }
+                }
+
+                let Some(interval) = watch else {
+                    break;
+                };
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => break,
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(*interval)) => {}
+                }
+            }
         }
     }
 
     Ok(())
 }
 
-/// Calculates annualized volatility from price series.
-fn calculate_volatility(prices: &[f64]) -> f64 {
-    if prices.len() < 2 {
-        return 0.0;
+/// Prints where the current price sits relative to a watched range.
+fn print_edge_status(price: f64, lower: f64, upper: f64, margin_pct: f64) {
+    let position = commands::analyze::range_position_pct(price, lower, upper);
+    let range = format!("${:.2} - ${:.2}", lower, upper);
+    match commands::analyze::edge_status(price, lower, upper, margin_pct) {
+        commands::analyze::EdgeStatus::Below => {
+            println!(
+                "🚨 Price ${:.4} is BELOW the watched range {}",
+                price, range
+            )
+        }
+        commands::analyze::EdgeStatus::Above => {
+            println!(
+                "🚨 Price ${:.4} is ABOVE the watched range {}",
+                price, range
+            )
+        }
+        commands::analyze::EdgeStatus::NearLower => println!(
+            "⚠️  Price ${:.4} is approaching the lower edge of {} ({:.1}% into the range)",
+            price, range, position
+        ),
+        commands::analyze::EdgeStatus::NearUpper => println!(
+            "⚠️  Price ${:.4} is approaching the upper edge of {} ({:.1}% into the range)",
+            price, range, position
+        ),
+        commands::analyze::EdgeStatus::Inside => println!(
+            "✅ Price ${:.4} sits {:.1}% into the watched range {}",
+            price, position, range
+        ),
     }
+}
 
-    // Calculate log returns
-    let returns: Vec<f64> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
-
-    if returns.is_empty() {
-        return 0.0;
-    }
+/// Hourly price samples per year.
+const HOURS_PER_YEAR: f64 = 8760.0;
 
-    // Calculate standard deviation
-    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
-    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64;
-    let std_dev = variance.sqrt();
+/// Calculates annualized volatility from hourly prices.
+fn calculate_volatility(prices: &[f64]) -> f64 {
+    VolatilityEstimator::Stdev.annualized(prices, HOURS_PER_YEAR)
+}
 
-    // Annualize (assuming hourly data, ~8760 hours/year)
-    std_dev * (8760.0_f64).sqrt()
+/// Outcome of a backtest, as kept in the result cache.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BacktestRun {
+    /// Tracker summary at the end of the price path.
+    summary: TrackerSummary,
+    /// Price at the last step run.
+    final_price: Price,
+    /// Price and active range at every step.
+    points: Vec<output::RangePoint>,
+    /// Position value and drawdown at every step.
+    series: Vec<output::EquityPoint>,
 }
 
 /// Prints a rich backtest report using prettytable.
@@ -839,6 +3706,514 @@ fn print_backtest_report(
 }
 
 /// Prints optimization results using prettytable.
+/// Builds the exportable report of a backtest.
+#[allow(clippy::too_many_arguments)]
+fn backtest_report(
+    symbol: &str,
+    days: u64,
+    capital: f64,
+    entry_price: Decimal,
+    final_price: Decimal,
+    lower: f64,
+    upper: f64,
+    summary: &TrackerSummary,
+    strategy: StrategyArg,
+) -> output::BacktestReport {
+    let capital = Decimal::from_f64(capital).unwrap_or_default();
+    let total_return = if capital.is_zero() {
+        Decimal::ZERO
+    } else {
+        (summary.final_pnl / capital * Decimal::from(100)).round_dp(2)
+    };
+    let vs_hodl = if summary.hodl_value.is_zero() {
+        Decimal::ZERO
+    } else {
+        (summary.vs_hodl / summary.hodl_value * Decimal::from(100)).round_dp(2)
+    };
+
+    output::BacktestReport {
+        pair: format!("{}/USDC", symbol),
+        period_days: days,
+        entry_price,
+        exit_price: final_price,
+        range_lower: Decimal::from_f64(lower).unwrap_or_default(),
+        range_upper: Decimal::from_f64(upper).unwrap_or_default(),
+        initial_capital: capital,
+        final_value: summary.final_value,
+        total_return,
+        fee_earnings: summary.total_fees,
+        impermanent_loss: summary.final_il_pct,
+        vs_hodl,
+        time_in_range: summary.time_in_range_pct * Decimal::from(100),
+        max_drawdown: summary.max_drawdown,
+        rebalance_count: summary.rebalance_count,
+        total_tx_costs: summary.total_rebalance_cost,
+        strategy: format!("{:?}", strategy),
+        sharpe_ratio: None,
+    }
+}
+
+/// Builds the exportable report of a range optimization.
+fn optimization_report(
+    symbol: &str,
+    current_price: f64,
+    volatility: f64,
+    capital: f64,
+    objective: OptimizationObjectiveArg,
+    result: &OptimizationResult,
+    time_in_range: Decimal,
+) -> output::OptimizationReport {
+    let current_price = Decimal::from_f64(current_price).unwrap_or_default();
+    let lower = result.recommended_range.lower_price.value;
+    let upper = result.recommended_range.upper_price.value;
+    let range_width_pct = if current_price.is_zero() {
+        Decimal::ZERO
+    } else {
+        ((upper - lower) / current_price * Decimal::from(100)).round_dp(1)
+    };
+    let score = match objective {
+        OptimizationObjectiveArg::Pnl => result.expected_pnl,
+        OptimizationObjectiveArg::Fees => result.expected_fees,
+        OptimizationObjectiveArg::Sharpe => result.sharpe_ratio.unwrap_or_default(),
+    };
+
+    output::OptimizationReport {
+        pair: format!("{}/USDC", symbol),
+        current_price,
+        volatility: Decimal::from_f64(volatility).unwrap_or_default(),
+        capital: Decimal::from_f64(capital).unwrap_or_default(),
+        objective: format!("{:?}", objective),
+        candidates: vec![output::RangeCandidate {
+            rank: 1,
+            range_width_pct,
+            lower_price: lower,
+            upper_price: upper,
+            expected_fees: result.expected_fees,
+            expected_il: result.expected_il,
+            expected_pnl: result.expected_pnl,
+            time_in_range,
+            score,
+        }],
+        strategy_recommendations: Vec::new(),
+    }
+}
+
+/// Ranks the optimizer's candidate widths behind a progress bar.
+///
+/// Stops at the next Monte Carlo iteration once Ctrl-C is pressed and
+/// returns only the widths that were fully evaluated, plus whether the run
+/// was interrupted.
+#[allow(clippy::too_many_arguments)]
+fn rank_ranges<O: ObjectiveFunction>(
+    optimizer: &RangeOptimizer,
+    base_position: &Position,
+    current_price: Decimal,
+    volatility: f64,
+    volume: &ConstantVolume,
+    pool_liquidity: u128,
+    fee_rate: Decimal,
+    objective: &O,
+    interrupt: &output::Interrupt,
+) -> (Vec<CandidateResult>, bool) {
+    let total = optimizer.range_widths.len() * optimizer.iterations;
+    let bar = output::progress_bar(total as u64, "Simulating");
+    let mut completed = 0;
+
+    // The optimizer is synchronous; keep the Ctrl-C listener running
+    let mut candidates = tokio::task::block_in_place(|| {
+        optimizer.rank_with_progress(
+            base_position,
+            current_price,
+            volatility,
+            0.0,
+            volume,
+            pool_liquidity,
+            fee_rate,
+            objective,
+            |done, _| {
+                completed = done;
+                bar.set_position(done as u64);
+                if interrupt.is_set() {
+                    std::ops::ControlFlow::Break(())
+                } else {
+                    std::ops::ControlFlow::Continue(())
+                }
+            },
+        )
+    });
+    bar.finish_and_clear();
+
+    let evaluated = completed
+        .checked_div(optimizer.iterations)
+        .unwrap_or(optimizer.range_widths.len());
+    let finished = &optimizer.range_widths[..evaluated];
+    candidates.retain(|c| finished.contains(&c.range_width));
+    (candidates, completed < total)
+}
+
+/// Returns `done` as a whole percentage of `total`.
+fn progress_pct(done: usize, total: usize) -> i16 {
+    (done * 100).checked_div(total).unwrap_or(100) as i16
+}
+
+/// A CLI run saved to the jobs table.
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+struct SavedRun {
+    /// Job ID.
+    id: Uuid,
+    /// Job kind.
+    kind: String,
+    /// Job status.
+    status: String,
+    /// Share of the run that completed.
+    progress_pct: i16,
+    /// Run parameters.
+    params: serde_json::Value,
+    /// Run result or progress.
+    result: serde_json::Value,
+    /// When the run started.
+    started_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Builds the saved form of a CLI run.
+fn saved_run(
+    id: Uuid,
+    kind: &str,
+    status: &str,
+    progress_pct: i16,
+    params: serde_json::Value,
+    result: serde_json::Value,
+    started_at: chrono::DateTime<chrono::Utc>,
+) -> SavedRun {
+    SavedRun {
+        id,
+        kind: kind.to_string(),
+        status: status.to_string(),
+        progress_pct,
+        params,
+        result,
+        started_at,
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl SavedRun {
+    /// Converts the run to its job record.
+    fn to_record(&self) -> JobRecord {
+        JobRecord {
+            id: self.id,
+            kind: self.kind.clone(),
+            status: self.status.clone(),
+            progress_pct: self.progress_pct,
+            params: self.params.clone(),
+            result: Some(self.result.clone()),
+            error: None,
+            tenant: None,
+            created_at: self.started_at,
+            started_at: Some(self.started_at),
+            finished_at: Some(chrono::Utc::now()),
+        }
+    }
+}
+
+/// Number of steps written to the database at a time by `backtest --save`.
+const STEP_BATCH_SIZE: usize = 250;
+
+/// A backtest being saved to the database while it runs.
+#[cfg(feature = "postgres")]
+struct SavedSimulation {
+    /// Simulation ID.
+    id: Uuid,
+    /// Repository the result is saved to.
+    repository: SimulationRepository,
+    /// Writer streaming the steps.
+    writer: SimulationStepWriter,
+}
+
+/// A backtest being saved to the database while it runs.
+///
+/// Without the `postgres` feature nothing is saved, so no value exists.
+#[cfg(not(feature = "postgres"))]
+enum SavedSimulation {}
+
+#[cfg(feature = "postgres")]
+impl SavedSimulation {
+    /// Queues tracked steps for writing.
+    fn send(&self, snapshots: &[PositionSnapshot]) {
+        self.writer
+            .send(snapshots.iter().map(step_record).collect());
+    }
+}
+
+#[cfg(not(feature = "postgres"))]
+impl SavedSimulation {
+    /// Queues tracked steps for writing.
+    fn send(&self, _snapshots: &[PositionSnapshot]) {
+        match *self {}
+    }
+}
+
+/// Saves a backtest's configuration and starts streaming its steps.
+///
+/// A failure is only reported and the backtest runs without being saved.
+#[cfg(feature = "postgres")]
+async fn start_saved_simulation(
+    database_url: &str,
+    strategy_type: &str,
+    strategy_config: serde_json::Value,
+    (start_time, end_time): (u64, u64),
+    tracker: &PositionTracker,
+    fee_rate: Decimal,
+) -> Option<SavedSimulation> {
+    let repository = match Database::connect(database_url).await {
+        Ok(db) => db.simulations(),
+        Err(e) => {
+            status!("⚠️  Could not save the simulation to the database: {}", e);
+            return None;
+        }
+    };
+
+    let saved = repository
+        .save_simulation(
+            Uuid::new_v4(),
+            None,
+            strategy_type,
+            strategy_config,
+            start_time as i64,
+            end_time as i64,
+            tracker.initial_capital,
+            tracker.entry_price.value,
+            tracker.current_range.lower_price.value,
+            tracker.current_range.upper_price.value,
+            fee_rate,
+            tracker.rebalance_cost,
+        )
+        .await;
+    match saved {
+        Ok(record) => {
+            status!("💾 Streaming steps to simulation {}", record.id);
+            Some(SavedSimulation {
+                id: record.id,
+                writer: SimulationStepWriter::spawn(repository.clone(), record.id),
+                repository,
+            })
+        }
+        Err(e) => {
+            status!("⚠️  Could not save the simulation to the database: {}", e);
+            None
+        }
+    }
+}
+
+/// Saving a backtest needs the `postgres` feature; the backtest runs without
+/// being saved.
+#[cfg(not(feature = "postgres"))]
+async fn start_saved_simulation(
+    _database_url: &str,
+    _strategy_type: &str,
+    _strategy_config: serde_json::Value,
+    _window: (u64, u64),
+    _tracker: &PositionTracker,
+    _fee_rate: Decimal,
+) -> Option<SavedSimulation> {
+    status!("⚠️  Could not save the simulation: the CLI was built without the `postgres` feature");
+    None
+}
+
+/// Waits for a saved backtest's steps to be written and saves its result.
+///
+/// Without a result the simulation is left incomplete, with the steps that
+/// were run. A failure is only reported, since the results were already
+/// computed.
+#[cfg(feature = "postgres")]
+async fn finish_saved_simulation(
+    saved: SavedSimulation,
+    initial_capital: Decimal,
+    run: Option<&BacktestRun>,
+) {
+    let SavedSimulation {
+        id,
+        repository,
+        writer,
+    } = saved;
+    let steps = match writer.finish().await {
+        Ok(steps) => steps,
+        Err(e) => {
+            status!("⚠️  Could not save all simulation steps: {}", e);
+            return;
+        }
+    };
+
+    let Some(run) = run else {
+        status!("💾 Saved {} steps of incomplete simulation {}", steps, id);
+        return;
+    };
+    let summary = &run.summary;
+    let saved = repository
+        .save_result(
+            Uuid::new_v4(),
+            id,
+            summary.final_value,
+            summary.final_pnl,
+            summary.total_fees,
+            initial_capital * summary.final_il_pct,
+            summary.final_il_pct,
+            summary.time_in_range_pct,
+            summary.max_drawdown,
+            summary.rebalance_count as i32,
+            summary.total_rebalance_cost,
+            summary.hodl_value,
+            summary.vs_hodl,
+            None,
+            run.final_price.value,
+        )
+        .await;
+    match saved {
+        Ok(_) => status!("💾 Saved simulation {} with {} steps", id, steps),
+        Err(e) => status!("⚠️  Could not save the simulation result: {}", e),
+    }
+}
+
+/// Waits for a saved backtest's steps to be written and saves its result.
+#[cfg(not(feature = "postgres"))]
+async fn finish_saved_simulation(
+    saved: SavedSimulation,
+    _initial_capital: Decimal,
+    _run: Option<&BacktestRun>,
+) {
+    match saved {}
+}
+
+/// Converts a tracked step to its database record.
+#[cfg(feature = "postgres")]
+fn step_record(snapshot: &PositionSnapshot) -> SimulationStepRecord {
+    SimulationStepRecord {
+        step: snapshot.step as i64,
+        price: snapshot.price.value,
+        lower_price: snapshot.range.lower_price.value,
+        upper_price: snapshot.range.upper_price.value,
+        in_range: snapshot.in_range,
+        position_value: snapshot.position_value_usd,
+        cumulative_fees: snapshot.cumulative_fees,
+        il_pct: snapshot.il_pct,
+        net_pnl: snapshot.net_pnl,
+        rebalanced: matches!(snapshot.action, Some(RebalanceAction::Rebalance { .. })),
+    }
+}
+
+/// Loads a pool's swap volume over the candles of a backtest, with the
+/// pool's fee rate and the position's share of its fees.
+#[cfg(feature = "live")]
+async fn backtest_swap_volume(
+    rpc_url: &str,
+    pool: &str,
+    candles: &[PriceCandle],
+    capital: f64,
+    range: (f64, f64),
+    entry_price: Decimal,
+) -> Result<(SwapVolume, Decimal, Decimal)> {
+    let (Some(first), Some(last)) = (candles.first(), candles.last()) else {
+        return Ok((SwapVolume::default(), Decimal::ZERO, Decimal::ZERO));
+    };
+    let window = commands::swaps::SwapWindow {
+        start_time: first.start_timestamp,
+        end_time: last.start_timestamp + last.duration_seconds,
+        step_seconds: first.duration_seconds,
+        steps: candles.len(),
+        capital,
+        range,
+        entry_price: entry_price.to_f64().unwrap_or(0.0),
+    };
+    let history = commands::swaps::load_swap_history(rpc_url, pool, &window).await?;
+    Ok((history.volume, history.fee_rate, history.fee_share))
+}
+
+/// Loads the stored candles replayed by `backtest --replay`, with the name of
+/// their data source.
+#[cfg(feature = "postgres")]
+async fn replay_candles(
+    database_url: &str,
+    pool: Option<&str>,
+    token_a: &Token,
+    token_b: &Token,
+    start_time: u64,
+    end_time: u64,
+) -> Result<(String, Vec<PriceCandle>)> {
+    let (pool, candles) = commands::data::load_stored_candles(
+        database_url,
+        pool,
+        token_a,
+        token_b,
+        start_time,
+        end_time,
+    )
+    .await?;
+    status!(
+        "   {} candles from {} pool {}",
+        candles.len(),
+        pool.protocol,
+        pool.address
+    );
+    Ok((format!("database:{}", pool.address), candles))
+}
+
+/// Replay reads the database, which needs the `postgres` feature.
+#[cfg(not(feature = "postgres"))]
+async fn replay_candles(
+    _database_url: &str,
+    _pool: Option<&str>,
+    _token_a: &Token,
+    _token_b: &Token,
+    _start_time: u64,
+    _end_time: u64,
+) -> Result<(String, Vec<PriceCandle>)> {
+    anyhow::bail!("--replay needs the CLI built with the `postgres` feature")
+}
+
+/// Swap replay reads the chain, which needs the `live` feature.
+#[cfg(not(feature = "live"))]
+async fn backtest_swap_volume(
+    _rpc_url: &str,
+    _pool: &str,
+    _candles: &[PriceCandle],
+    _capital: f64,
+    _range: (f64, f64),
+    _entry_price: Decimal,
+) -> Result<(SwapVolume, Decimal, Decimal)> {
+    anyhow::bail!("--swaps needs the CLI built with the `live` feature")
+}
+
+/// Saves a run to the jobs table.
+///
+/// A failure is only reported, since the results were already printed.
+#[cfg(feature = "postgres")]
+async fn save_run(database_url: &str, run: &SavedRun) -> bool {
+    let saved = match Database::connect(database_url).await {
+        Ok(db) => db.jobs().upsert(&run.to_record()).await,
+        Err(e) => Err(e),
+    };
+    match saved {
+        Ok(()) => {
+            status!("💾 Saved as job {}", run.id);
+            true
+        }
+        Err(e) => {
+            status!("⚠️  Could not save the run to the database: {}", e);
+            false
+        }
+    }
+}
+
+/// Saving a run needs the `postgres` feature; the failure is only reported.
+#[cfg(not(feature = "postgres"))]
+async fn save_run(_database_url: &str, run: &SavedRun) -> bool {
+    status!(
+        "⚠️  Could not save job {}: the CLI was built without the `postgres` feature",
+        run.id
+    );
+    false
+}
+
 fn print_optimization_report(
     symbol: &str,
     current_price: f64,
@@ -2,8 +2,10 @@
 //!
 //! Provides simple ASCII-based visualizations for terminal output.
 
+use clmm_lp_simulation::prelude::{PositionSnapshot, RebalanceAction};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
 
 /// Configuration for ASCII charts.
 #[derive(Debug, Clone)]
@@ -126,6 +128,132 @@ pub fn render_price_chart(prices: &[Decimal], config: &ChartConfig) -> String {
     output
 }
 
+/// A step of a price path with the LP range active at that step.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RangePoint {
+    /// Price at this step.
+    pub price: Decimal,
+    /// Lower bound of the active range.
+    pub lower: Decimal,
+    /// Upper bound of the active range.
+    pub upper: Decimal,
+    /// Whether the position was rebalanced at this step.
+    pub rebalanced: bool,
+}
+
+impl From<&PositionSnapshot> for RangePoint {
+    fn from(snapshot: &PositionSnapshot) -> Self {
+        Self {
+            price: snapshot.price.value,
+            lower: snapshot.range.lower_price.value,
+            upper: snapshot.range.upper_price.value,
+            rebalanced: matches!(snapshot.action, Some(RebalanceAction::Rebalance { .. })),
+        }
+    }
+}
+
+/// Renders a price chart with the LP range band and rebalances overlaid.
+///
+/// Steps are bucketed into at most `config.width` columns; each column
+/// shows the last price and range of its bucket and is marked as a
+/// rebalance if any of its steps rebalanced.
+pub fn render_range_chart(points: &[RangePoint], config: &ChartConfig) -> String {
+    if points.is_empty() {
+        return String::from("No data to display");
+    }
+
+    let columns = points.len().min(config.width.max(1));
+    let height = config.height.max(3);
+    let buckets: Vec<RangePoint> = (0..columns)
+        .map(|i| {
+            let start = i * points.len() / columns;
+            let end = ((i + 1) * points.len() / columns).max(start + 1);
+            let bucket = &points[start..end];
+            RangePoint {
+                rebalanced: bucket.iter().any(|p| p.rebalanced),
+                ..bucket[bucket.len() - 1]
+            }
+        })
+        .collect();
+
+    let min = buckets
+        .iter()
+        .map(|p| p.price.min(p.lower))
+        .min()
+        .unwrap_or(Decimal::ZERO);
+    let max = buckets
+        .iter()
+        .map(|p| p.price.max(p.upper))
+        .max()
+        .unwrap_or(Decimal::ONE);
+    let span = max - min;
+    let row_of = |value: Decimal| -> usize {
+        if span.is_zero() {
+            return height / 2;
+        }
+        let normalized = ((max - value) / span).to_f64().unwrap_or(0.0);
+        ((normalized * (height - 1) as f64).round() as usize).min(height - 1)
+    };
+
+    let mut grid = vec![vec![config.empty_char; columns]; height];
+    for (x, point) in buckets.iter().enumerate() {
+        let (top, bottom) = (row_of(point.upper), row_of(point.lower));
+        for row in grid.iter_mut().take(bottom + 1).skip(top) {
+            row[x] = '░';
+        }
+        grid[top][x] = '─';
+        grid[bottom][x] = '─';
+
+        let in_range = point.price >= point.lower && point.price <= point.upper;
+        grid[row_of(point.price)][x] = if point.rebalanced {
+            '◆'
+        } else if in_range {
+            '●'
+        } else {
+            '○'
+        };
+    }
+
+    let labels = [
+        (0, max),
+        (height / 2, max - span / Decimal::TWO),
+        (height - 1, min),
+    ];
+    let label_width = labels
+        .iter()
+        .map(|(_, v)| format!("{:.2}", v).len())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = String::new();
+    for (y, row) in grid.iter().enumerate() {
+        match labels.iter().find(|(label_row, _)| *label_row == y) {
+            Some((_, value)) => {
+                output.push_str(&format!("{:>width$.2} ┤", value, width = label_width))
+            }
+            None => output.push_str(&format!("{:>width$} │", "", width = label_width)),
+        }
+        output.extend(row.iter());
+        output.push('\n');
+    }
+
+    output.push_str(&format!("{:>width$} └", "", width = label_width));
+    output.push_str(&"─".repeat(columns));
+    output.push('\n');
+    if buckets.iter().any(|p| p.rebalanced) {
+        output.push_str(&format!("{:>width$}  ", "", width = label_width));
+        output.extend(buckets.iter().map(|p| if p.rebalanced { '▲' } else { ' ' }));
+        output.push('\n');
+    }
+    output.push_str(&format!(
+        "{:>width$}  ● in range  ○ out of range  ◆ rebalance  ░ LP range\n",
+        "",
+        width = label_width
+    ));
+
+    output
+}
+
 /// Renders a horizontal percentage bar.
 pub fn render_percentage_bar(value: Decimal, width: usize) -> String {
     let pct = value.to_f64().unwrap_or(0.0).clamp(0.0, 100.0);
@@ -200,6 +328,58 @@ mod tests {
         assert!(bar.contains("█"));
     }
 
+    #[test]
+    fn test_render_range_chart() {
+        let points: Vec<RangePoint> = (0..100)
+            .map(|i| RangePoint {
+                price: Decimal::from(95 + (i % 15)),
+                lower: if i < 50 { dec!(90) } else { dec!(100) },
+                upper: if i < 50 { dec!(105) } else { dec!(115) },
+                rebalanced: i == 50,
+            })
+            .collect();
+        let config = ChartConfig {
+            width: 40,
+            height: 8,
+            ..Default::default()
+        };
+
+        let chart = render_range_chart(&points, &config);
+        let lines: Vec<&str> = chart.lines().collect();
+        // 8 grid rows, the axis, the rebalance markers and the legend
+        assert_eq!(lines.len(), 11);
+        assert!(lines[0].starts_with("115.00 ┤"));
+        assert!(lines[7].starts_with(" 90.00 ┤"));
+        assert_eq!(lines[9].matches('▲').count(), 1);
+        assert!(chart.contains('◆'));
+        assert!(chart.contains('░'));
+    }
+
+    #[test]
+    fn test_render_range_chart_without_rebalances() {
+        let points = vec![
+            RangePoint {
+                price: dec!(100),
+                lower: dec!(90),
+                upper: dec!(110),
+                rebalanced: false,
+            };
+            5
+        ];
+        let chart = render_range_chart(&points, &ChartConfig::default());
+        assert!(!chart.contains('▲'));
+        assert_eq!(
+            chart
+                .lines()
+                .next()
+                .unwrap()
+                .chars()
+                .filter(|c| *c == '─')
+                .count(),
+            5
+        );
+    }
+
     #[test]
     fn test_render_sparkline() {
         let values = vec![dec!(1), dec!(2), dec!(3), dec!(2), dec!(1)];
@@ -0,0 +1,362 @@
+/*
+ * Minimal canvas charts for self-contained CLMM LP reports.
+ *
+ * Renders the line and scatter charts described by the report's embedded
+ * JSON, with hover tooltips. Has no dependencies so reports open offline.
+ */
+(function (global) {
+  "use strict";
+
+  var PADDING = { left: 72, right: 16, top: 16, bottom: 44 };
+  var COLORS = ["#2196F3", "#FF9800", "#9C27B0", "#009688", "#795548"];
+  var HIGHLIGHT = "#F44336";
+  var BAND = "rgba(76, 175, 80, 0.18)";
+
+  function niceStep(span, count) {
+    var raw = span / Math.max(count, 1);
+    var magnitude = Math.pow(10, Math.floor(Math.log10(raw)));
+    var residual = raw / magnitude;
+    var nice = residual > 5 ? 10 : residual > 2 ? 5 : residual > 1 ? 2 : 1;
+    return nice * magnitude;
+  }
+
+  function extent(values) {
+    var min = Infinity;
+    var max = -Infinity;
+    values.forEach(function (v) {
+      if (isFinite(v)) {
+        min = Math.min(min, v);
+        max = Math.max(max, v);
+      }
+    });
+    if (min === Infinity) {
+      return [0, 1];
+    }
+    if (min === max) {
+      var pad = Math.abs(min) * 0.05 || 1;
+      return [min - pad, max + pad];
+    }
+    return [min, max];
+  }
+
+  function format(value) {
+    var abs = Math.abs(value);
+    if (abs >= 1e6) {
+      return (value / 1e6).toFixed(2) + "M";
+    }
+    if (abs >= 1e4) {
+      return (value / 1e3).toFixed(1) + "k";
+    }
+    if (abs >= 1 || value === 0) {
+      return value.toFixed(2);
+    }
+    return value.toPrecision(3);
+  }
+
+  function Plot(canvas, chart, xs, ys) {
+    var ratio = global.devicePixelRatio || 1;
+    this.width = canvas.clientWidth || canvas.width;
+    this.height = canvas.clientHeight || canvas.height;
+    canvas.width = this.width * ratio;
+    canvas.height = this.height * ratio;
+    this.ctx = canvas.getContext("2d");
+    this.ctx.scale(ratio, ratio);
+    this.chart = chart;
+    this.x = extent(xs);
+    this.y = extent(ys);
+  }
+
+  Plot.prototype.px = function (x) {
+    var span = this.width - PADDING.left - PADDING.right;
+    return PADDING.left + ((x - this.x[0]) / (this.x[1] - this.x[0])) * span;
+  };
+
+  Plot.prototype.py = function (y) {
+    var span = this.height - PADDING.top - PADDING.bottom;
+    return this.height - PADDING.bottom - ((y - this.y[0]) / (this.y[1] - this.y[0])) * span;
+  };
+
+  Plot.prototype.axes = function () {
+    var ctx = this.ctx;
+    var right = this.width - PADDING.right;
+    var bottom = this.height - PADDING.bottom;
+    var value;
+    var step;
+
+    ctx.clearRect(0, 0, this.width, this.height);
+    ctx.font = "11px Arial, sans-serif";
+    ctx.lineWidth = 1;
+    ctx.strokeStyle = "#e0e0e0";
+    ctx.fillStyle = "#555";
+
+    step = niceStep(this.y[1] - this.y[0], 5);
+    ctx.textAlign = "right";
+    ctx.textBaseline = "middle";
+    for (value = Math.ceil(this.y[0] / step) * step; value <= this.y[1]; value += step) {
+      ctx.beginPath();
+      ctx.moveTo(PADDING.left, this.py(value));
+      ctx.lineTo(right, this.py(value));
+      ctx.stroke();
+      ctx.fillText(format(value), PADDING.left - 6, this.py(value));
+    }
+
+    step = niceStep(this.x[1] - this.x[0], 8);
+    ctx.textAlign = "center";
+    ctx.textBaseline = "top";
+    for (value = Math.ceil(this.x[0] / step) * step; value <= this.x[1]; value += step) {
+      ctx.fillText(format(value), this.px(value), bottom + 6);
+    }
+
+    ctx.strokeStyle = "#999";
+    ctx.strokeRect(PADDING.left, PADDING.top, right - PADDING.left, bottom - PADDING.top);
+    ctx.fillText(this.chart.x_label, (PADDING.left + right) / 2, this.height - 16);
+    ctx.save();
+    ctx.translate(14, (PADDING.top + bottom) / 2);
+    ctx.rotate(-Math.PI / 2);
+    ctx.textBaseline = "middle";
+    ctx.fillText(this.chart.y_label, 0, 0);
+    ctx.restore();
+  };
+
+  Plot.prototype.legend = function (entries) {
+    var ctx = this.ctx;
+    var x = PADDING.left + 10;
+    ctx.font = "11px Arial, sans-serif";
+    ctx.textAlign = "left";
+    ctx.textBaseline = "middle";
+    entries.forEach(function (entry) {
+      ctx.fillStyle = entry.color;
+      ctx.fillRect(x, PADDING.top + 8, 12, 8);
+      ctx.fillStyle = "#333";
+      ctx.fillText(entry.label, x + 16, PADDING.top + 12);
+      x += ctx.measureText(entry.label).width + 32;
+    });
+  };
+
+  Plot.prototype.path = function (points) {
+    var ctx = this.ctx;
+    var plot = this;
+    points.forEach(function (p, i) {
+      if (i === 0) {
+        ctx.moveTo(plot.px(p[0]), plot.py(p[1]));
+      } else {
+        ctx.lineTo(plot.px(p[0]), plot.py(p[1]));
+      }
+    });
+  };
+
+  function tooltip(canvas) {
+    var tip = document.createElement("div");
+    tip.style.cssText =
+      "position:absolute;pointer-events:none;display:none;white-space:pre;" +
+      "background:rgba(0,0,0,0.8);color:#fff;padding:4px 8px;border-radius:4px;" +
+      "font:12px Arial,sans-serif";
+    canvas.parentNode.style.position = "relative";
+    canvas.parentNode.appendChild(tip);
+    return {
+      show: function (x, y, text) {
+        tip.textContent = text;
+        tip.style.left = canvas.offsetLeft + x + 12 + "px";
+        tip.style.top = canvas.offsetTop + y + 12 + "px";
+        tip.style.display = "block";
+      },
+      hide: function () {
+        tip.style.display = "none";
+      },
+    };
+  }
+
+  function onHover(canvas, handler) {
+    canvas.addEventListener("mousemove", function (event) {
+      var rect = canvas.getBoundingClientRect();
+      handler(event.clientX - rect.left, event.clientY - rect.top);
+    });
+    canvas.addEventListener("mouseleave", function () {
+      handler(null, null);
+    });
+  }
+
+  function nearestIndex(points, x) {
+    var lo = 0;
+    var hi = points.length - 1;
+    while (hi - lo > 1) {
+      var mid = (lo + hi) >> 1;
+      if (points[mid][0] < x) {
+        lo = mid;
+      } else {
+        hi = mid;
+      }
+    }
+    return Math.abs(points[lo][0] - x) <= Math.abs(points[hi][0] - x) ? lo : hi;
+  }
+
+  function line(canvas, chart) {
+    var xs = [];
+    var ys = [];
+    var band = chart.band;
+    var sets = chart.series.map(function (s) {
+      return s.points;
+    });
+    if (band) {
+      sets.push(band.lower, band.upper);
+    }
+    sets.forEach(function (points) {
+      points.forEach(function (p) {
+        xs.push(p[0]);
+        ys.push(p[1]);
+      });
+    });
+    if (xs.length === 0) {
+      return;
+    }
+
+    var plot = new Plot(canvas, chart, xs, ys);
+    var tip = tooltip(canvas);
+    var colors = chart.series.map(function (s, i) {
+      return COLORS[i % COLORS.length];
+    });
+    var legend = chart.series.map(function (s, i) {
+      return { label: s.label, color: colors[i] };
+    });
+    if (band) {
+      legend.push({ label: band.label, color: BAND });
+    }
+
+    function draw(hover) {
+      var ctx = plot.ctx;
+      plot.axes();
+      if (band) {
+        ctx.fillStyle = BAND;
+        ctx.beginPath();
+        plot.path(band.upper);
+        for (var i = band.lower.length - 1; i >= 0; i--) {
+          ctx.lineTo(plot.px(band.lower[i][0]), plot.py(band.lower[i][1]));
+        }
+        ctx.closePath();
+        ctx.fill();
+      }
+      chart.series.forEach(function (s, i) {
+        ctx.strokeStyle = colors[i];
+        ctx.lineWidth = 1.5;
+        ctx.beginPath();
+        plot.path(s.points);
+        ctx.stroke();
+      });
+      plot.legend(legend);
+
+      if (hover !== null) {
+        var x = plot.px(chart.series[0].points[hover][0]);
+        ctx.strokeStyle = "#666";
+        ctx.lineWidth = 1;
+        ctx.beginPath();
+        ctx.moveTo(x, PADDING.top);
+        ctx.lineTo(x, plot.height - PADDING.bottom);
+        ctx.stroke();
+        chart.series.forEach(function (s, i) {
+          var p = s.points[hover];
+          if (p) {
+            ctx.fillStyle = colors[i];
+            ctx.beginPath();
+            ctx.arc(plot.px(p[0]), plot.py(p[1]), 3, 0, 2 * Math.PI);
+            ctx.fill();
+          }
+        });
+      }
+    }
+
+    draw(null);
+    onHover(canvas, function (x, y) {
+      if (x === null || chart.series[0].points.length === 0) {
+        tip.hide();
+        draw(null);
+        return;
+      }
+      var points = chart.series[0].points;
+      var span = plot.width - PADDING.left - PADDING.right;
+      var value = plot.x[0] + ((x - PADDING.left) / span) * (plot.x[1] - plot.x[0]);
+      var index = nearestIndex(points, value);
+      var lines = [chart.x_label + ": " + format(points[index][0])];
+      chart.series.forEach(function (s) {
+        if (s.points[index]) {
+          lines.push(s.label + ": " + format(s.points[index][1]));
+        }
+      });
+      if (band && band.lower[index] && band.upper[index]) {
+        lines.push(
+          band.label + ": " + format(band.lower[index][1]) + " - " + format(band.upper[index][1])
+        );
+      }
+      draw(index);
+      tip.show(x, y, lines.join("\n"));
+    });
+  }
+
+  function scatter(canvas, chart) {
+    if (chart.points.length === 0) {
+      return;
+    }
+    var xs = chart.points.map(function (p) {
+      return p.x;
+    });
+    var ys = chart.points.map(function (p) {
+      return p.y;
+    });
+    var plot = new Plot(canvas, chart, xs, ys);
+    var tip = tooltip(canvas);
+
+    function draw(hover) {
+      var ctx = plot.ctx;
+      plot.axes();
+      chart.points.forEach(function (p, i) {
+        ctx.fillStyle = p.highlight ? HIGHLIGHT : COLORS[0];
+        ctx.globalAlpha = hover === null || hover === i ? 0.9 : 0.5;
+        ctx.beginPath();
+        ctx.arc(plot.px(p.x), plot.py(p.y), p.highlight || hover === i ? 6 : 4, 0, 2 * Math.PI);
+        ctx.fill();
+      });
+      ctx.globalAlpha = 1;
+      plot.legend([
+        { label: "Candidate", color: COLORS[0] },
+        { label: "Best", color: HIGHLIGHT },
+      ]);
+    }
+
+    draw(null);
+    onHover(canvas, function (x, y) {
+      var best = null;
+      var bestDistance = 100;
+      if (x !== null) {
+        chart.points.forEach(function (p, i) {
+          var dx = plot.px(p.x) - x;
+          var dy = plot.py(p.y) - y;
+          if (dx * dx + dy * dy < bestDistance) {
+            best = i;
+            bestDistance = dx * dx + dy * dy;
+          }
+        });
+      }
+      draw(best);
+      if (best === null) {
+        tip.hide();
+        return;
+      }
+      var p = chart.points[best];
+      tip.show(
+        x,
+        y,
+        p.label + "\n" + chart.x_label + ": " + format(p.x) + "\n" + chart.y_label + ": " + format(p.y)
+      );
+    });
+  }
+
+  global.ClmmCharts = {
+    renderAll: function (charts) {
+      charts.forEach(function (chart) {
+        var canvas = document.getElementById(chart.id);
+        if (canvas) {
+          (chart.kind === "scatter" ? scatter : line)(canvas, chart);
+        }
+      });
+    },
+  };
+})(window);
@@ -1,15 +1,20 @@
 //! Export functionality for CLI output.
 //!
 //! Provides export to various formats including JSON, CSV, and HTML.
+//! HTML backtest and optimization reports embed interactive charts.
 
-use super::{AnalysisReport, BacktestReport, OptimizationReport};
+use super::interactive::{
+    attach_interactive_charts, backtest_charts, candidate_charts, insert_before_body_end,
+};
+use super::{AnalysisReport, BacktestReport, ChartImage, EquityPoint, OptimizationReport};
 use anyhow::Result;
+use clmm_lp_data::prelude::{ExportTable, ExportValue};
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
 /// Export format options.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum ExportFormat {
     /// JSON format.
     Json,
@@ -18,9 +23,28 @@ pub enum ExportFormat {
     /// HTML format.
     Html,
     /// Markdown format.
+    #[value(name = "md", alias = "markdown")]
     Markdown,
 }
 
+impl ExportFormat {
+    /// Infers the format from a file extension, defaulting to JSON.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("csv") => Self::Csv,
+            Some("html" | "htm") => Self::Html,
+            Some("md" | "markdown") => Self::Markdown,
+            _ => Self::Json,
+        }
+    }
+}
+
 /// Exports an analysis report to a file.
 pub fn export_analysis_report(
     report: &AnalysisReport,
@@ -45,12 +69,31 @@ pub fn export_backtest_report(
     report: &BacktestReport,
     path: &Path,
     format: ExportFormat,
+) -> Result<()> {
+    export_backtest_report_with_charts(report, &[], path, format, &[])
+}
+
+/// Exports a backtest report to a file with its per-step series and chart
+/// images attached.
+///
+/// HTML reports embed interactive charts of the series. HTML and Markdown
+/// reports link the images by file name, so they are expected next to the
+/// report; JSON and CSV ignore both.
+pub fn export_backtest_report_with_charts(
+    report: &BacktestReport,
+    series: &[EquityPoint],
+    path: &Path,
+    format: ExportFormat,
+    charts: &[ChartImage],
 ) -> Result<()> {
     let content = match format {
         ExportFormat::Json => serde_json::to_string_pretty(report)?,
         ExportFormat::Csv => backtest_to_csv(report),
-        ExportFormat::Html => backtest_to_html(report),
-        ExportFormat::Markdown => backtest_to_markdown(report),
+        ExportFormat::Html => attach_html_charts(
+            attach_interactive_charts(backtest_to_html(report), &backtest_charts(series)),
+            charts,
+        ),
+        ExportFormat::Markdown => attach_markdown_charts(backtest_to_markdown(report), charts),
     };
 
     let mut file = File::create(path)?;
@@ -68,7 +111,10 @@ pub fn export_optimization_report(
     let content = match format {
         ExportFormat::Json => serde_json::to_string_pretty(report)?,
         ExportFormat::Csv => optimization_to_csv(report),
-        ExportFormat::Html => optimization_to_html(report),
+        ExportFormat::Html => attach_interactive_charts(
+            optimization_to_html(report),
+            &candidate_charts(&report.candidates),
+        ),
         ExportFormat::Markdown => optimization_to_markdown(report),
     };
 
@@ -81,70 +127,115 @@ pub fn export_optimization_report(
 // CSV formatters
 
 fn analysis_to_csv(report: &AnalysisReport) -> String {
-    let mut csv = String::from("metric,value\n");
-    csv.push_str(&format!("pair,{}\n", report.pair));
-    csv.push_str(&format!("period_days,{}\n", report.period_days));
-    csv.push_str(&format!("current_price,{}\n", report.current_price));
-    csv.push_str(&format!("high_price,{}\n", report.high_price));
-    csv.push_str(&format!("low_price,{}\n", report.low_price));
-    csv.push_str(&format!("avg_price,{}\n", report.avg_price));
-    csv.push_str(&format!("volatility_daily,{}\n", report.volatility_daily));
-    csv.push_str(&format!("volatility_annual,{}\n", report.volatility_annual));
-    csv.push_str(&format!("recommended_lower,{}\n", report.recommended_lower));
-    csv.push_str(&format!("recommended_upper,{}\n", report.recommended_upper));
-    csv.push_str(&format!("recommended_width,{}\n", report.recommended_width));
-    csv.push_str(&format!(
-        "estimated_time_in_range,{}\n",
-        report.estimated_time_in_range
-    ));
-    csv.push_str(&format!("data_points,{}\n", report.data_points));
-    csv
+    ExportTable::metrics([
+        ("pair", ExportValue::from(report.pair.as_str())),
+        ("period_days", report.period_days.into()),
+        ("current_price", report.current_price.into()),
+        ("high_price", report.high_price.into()),
+        ("low_price", report.low_price.into()),
+        ("avg_price", report.avg_price.into()),
+        ("volatility_daily", report.volatility_daily.into()),
+        ("volatility_annual", report.volatility_annual.into()),
+        ("recommended_lower", report.recommended_lower.into()),
+        ("recommended_upper", report.recommended_upper.into()),
+        ("recommended_width", report.recommended_width.into()),
+        (
+            "estimated_time_in_range",
+            report.estimated_time_in_range.into(),
+        ),
+        ("data_points", report.data_points.into()),
+    ])
+    .to_csv()
 }
 
 fn backtest_to_csv(report: &BacktestReport) -> String {
-    let mut csv = String::from("metric,value\n");
-    csv.push_str(&format!("pair,{}\n", report.pair));
-    csv.push_str(&format!("period_days,{}\n", report.period_days));
-    csv.push_str(&format!("entry_price,{}\n", report.entry_price));
-    csv.push_str(&format!("exit_price,{}\n", report.exit_price));
-    csv.push_str(&format!("range_lower,{}\n", report.range_lower));
-    csv.push_str(&format!("range_upper,{}\n", report.range_upper));
-    csv.push_str(&format!("initial_capital,{}\n", report.initial_capital));
-    csv.push_str(&format!("final_value,{}\n", report.final_value));
-    csv.push_str(&format!("total_return,{}\n", report.total_return));
-    csv.push_str(&format!("fee_earnings,{}\n", report.fee_earnings));
-    csv.push_str(&format!("impermanent_loss,{}\n", report.impermanent_loss));
-    csv.push_str(&format!("vs_hodl,{}\n", report.vs_hodl));
-    csv.push_str(&format!("time_in_range,{}\n", report.time_in_range));
-    csv.push_str(&format!("max_drawdown,{}\n", report.max_drawdown));
-    csv.push_str(&format!("rebalance_count,{}\n", report.rebalance_count));
-    csv.push_str(&format!("total_tx_costs,{}\n", report.total_tx_costs));
-    csv.push_str(&format!("strategy,{}\n", report.strategy));
+    let mut table = ExportTable::metrics([
+        ("pair", ExportValue::from(report.pair.as_str())),
+        ("period_days", report.period_days.into()),
+        ("entry_price", report.entry_price.into()),
+        ("exit_price", report.exit_price.into()),
+        ("range_lower", report.range_lower.into()),
+        ("range_upper", report.range_upper.into()),
+        ("initial_capital", report.initial_capital.into()),
+        ("final_value", report.final_value.into()),
+        ("total_return", report.total_return.into()),
+        ("fee_earnings", report.fee_earnings.into()),
+        ("impermanent_loss", report.impermanent_loss.into()),
+        ("vs_hodl", report.vs_hodl.into()),
+        ("time_in_range", report.time_in_range.into()),
+        ("max_drawdown", report.max_drawdown.into()),
+        ("rebalance_count", report.rebalance_count.into()),
+        ("total_tx_costs", report.total_tx_costs.into()),
+        ("strategy", report.strategy.as_str().into()),
+    ]);
     if let Some(sharpe) = report.sharpe_ratio {
-        csv.push_str(&format!("sharpe_ratio,{}\n", sharpe));
+        table.push_row(vec!["sharpe_ratio".into(), sharpe.into()]);
     }
-    csv
+    table.to_csv()
 }
 
 fn optimization_to_csv(report: &OptimizationReport) -> String {
-    let mut csv = String::from(
-        "rank,width_pct,lower,upper,expected_fees,expected_il,expected_pnl,time_in_range,score\n",
-    );
+    let mut table = ExportTable::new([
+        "rank",
+        "width_pct",
+        "lower",
+        "upper",
+        "expected_fees",
+        "expected_il",
+        "expected_pnl",
+        "time_in_range",
+        "score",
+    ]);
     for c in &report.candidates {
-        csv.push_str(&format!(
-            "{},{},{},{},{},{},{},{},{}\n",
-            c.rank,
-            c.range_width_pct,
-            c.lower_price,
-            c.upper_price,
-            c.expected_fees,
-            c.expected_il,
-            c.expected_pnl,
-            c.time_in_range,
-            c.score
+        table.push_row(vec![
+            c.rank.into(),
+            c.range_width_pct.into(),
+            c.lower_price.into(),
+            c.upper_price.into(),
+            c.expected_fees.into(),
+            c.expected_il.into(),
+            c.expected_pnl.into(),
+            c.time_in_range.into(),
+            c.score.into(),
+        ]);
+    }
+    table.to_csv()
+}
+
+/// File name under which a chart is linked from its report.
+fn chart_link(chart: &ChartImage) -> String {
+    chart
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| chart.path.display().to_string())
+}
+
+fn attach_html_charts(html: String, charts: &[ChartImage]) -> String {
+    if charts.is_empty() {
+        return html;
+    }
+    let mut section = String::from("    <h2>Charts</h2>\n");
+    for chart in charts {
+        section.push_str(&format!(
+            "    <figure><img src=\"{}\" alt=\"{}\" style=\"max-width: 100%;\"><figcaption>{}</figcaption></figure>\n",
+            chart_link(chart),
+            chart.title,
+            chart.title
         ));
     }
-    csv
+    insert_before_body_end(html, &section)
+}
+
+fn attach_markdown_charts(mut markdown: String, charts: &[ChartImage]) -> String {
+    if charts.is_empty() {
+        return markdown;
+    }
+    markdown.push_str("\n## Charts\n\n");
+    for chart in charts {
+        markdown.push_str(&format!("![{}]({})\n\n", chart.title, chart_link(chart)));
+    }
+    markdown
 }
 
 // HTML formatters
@@ -416,3 +507,55 @@ fn optimization_to_markdown(report: &OptimizationReport) -> String {
         report.pair, rows
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn charts() -> Vec<ChartImage> {
+        vec![ChartImage {
+            title: "Equity Curve (USD)".to_string(),
+            path: Path::new("reports/sol-equity.svg").to_path_buf(),
+        }]
+    }
+
+    #[test]
+    fn test_attach_html_charts_before_body_end() {
+        let html = attach_html_charts(
+            "<html><body><h1>x</h1></body></html>".to_string(),
+            &charts(),
+        );
+        assert!(html.contains("<img src=\"sol-equity.svg\""));
+        assert!(html.ends_with("</body></html>"));
+    }
+
+    #[test]
+    fn test_attach_markdown_charts() {
+        let markdown = attach_markdown_charts("# Report\n".to_string(), &charts());
+        assert!(markdown.contains("![Equity Curve (USD)](sol-equity.svg)"));
+        assert_eq!(
+            attach_markdown_charts("# Report\n".to_string(), &[]),
+            "# Report\n"
+        );
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("r.csv")),
+            ExportFormat::Csv
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("r.HTML")),
+            ExportFormat::Html
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("r.md")),
+            ExportFormat::Markdown
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("report")),
+            ExportFormat::Json
+        );
+    }
+}
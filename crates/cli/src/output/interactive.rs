@@ -0,0 +1,355 @@
+//! Interactive charts for self-contained HTML reports.
+//!
+//! Charts are described as JSON embedded in the report and drawn by a small
+//! canvas chart script shipped inline, so reports need no network access or
+//! files next to them.
+
+use super::{EquityPoint, RangeCandidate};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+
+/// Chart script embedded in every interactive report.
+const CHART_SCRIPT: &str = include_str!("charts.js");
+
+/// Maximum number of points per line series embedded in a report.
+///
+/// Longer series are thinned to keep reports small and responsive.
+pub const MAX_SERIES_POINTS: usize = 2_000;
+
+/// A chart drawn by the embedded chart script.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InteractiveChart {
+    /// Line chart with an optional shaded band.
+    Line {
+        /// Element ID of the chart's canvas.
+        id: String,
+        /// Chart title.
+        title: String,
+        /// X axis label.
+        x_label: String,
+        /// Y axis label.
+        y_label: String,
+        /// Plotted lines.
+        series: Vec<LineSeries>,
+        /// Shaded area between two lines.
+        band: Option<Band>,
+    },
+    /// Scatter plot.
+    Scatter {
+        /// Element ID of the chart's canvas.
+        id: String,
+        /// Chart title.
+        title: String,
+        /// X axis label.
+        x_label: String,
+        /// Y axis label.
+        y_label: String,
+        /// Plotted points.
+        points: Vec<ScatterPoint>,
+    },
+}
+
+impl InteractiveChart {
+    /// Element ID of the chart's canvas.
+    fn id(&self) -> &str {
+        match self {
+            Self::Line { id, .. } | Self::Scatter { id, .. } => id,
+        }
+    }
+
+    /// Chart title.
+    fn title(&self) -> &str {
+        match self {
+            Self::Line { title, .. } | Self::Scatter { title, .. } => title,
+        }
+    }
+}
+
+/// A line of a line chart as `(x, y)` points ordered by x.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LineSeries {
+    /// Legend label.
+    pub label: String,
+    /// Points ordered by x.
+    pub points: Vec<(f64, f64)>,
+}
+
+/// A shaded area between a lower and an upper line.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Band {
+    /// Legend label.
+    pub label: String,
+    /// Lower edge, ordered by x.
+    pub lower: Vec<(f64, f64)>,
+    /// Upper edge, ordered by x.
+    pub upper: Vec<(f64, f64)>,
+}
+
+/// A point of a scatter plot.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScatterPoint {
+    /// X value.
+    pub x: f64,
+    /// Y value.
+    pub y: f64,
+    /// Tooltip label.
+    pub label: String,
+    /// Whether the point is drawn emphasized.
+    pub highlight: bool,
+}
+
+/// Builds the equity curve, range vs price and drawdown charts of a backtest.
+///
+/// Returns no charts for an empty series.
+#[must_use]
+pub fn backtest_charts(points: &[EquityPoint]) -> Vec<InteractiveChart> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let points = thin(points, MAX_SERIES_POINTS);
+    let series = |f: fn(&EquityPoint) -> f64| -> Vec<(f64, f64)> {
+        points.iter().map(|p| (p.step as f64, f(p))).collect()
+    };
+    let line = |id: &str, title: &str, y_label: &str, series, band| InteractiveChart::Line {
+        id: id.to_string(),
+        title: title.to_string(),
+        x_label: "Hour".to_string(),
+        y_label: y_label.to_string(),
+        series,
+        band,
+    };
+
+    vec![
+        line(
+            "chart-equity",
+            "Equity Curve",
+            "Value (USD)",
+            vec![LineSeries {
+                label: "Position value".to_string(),
+                points: series(|p| p.value),
+            }],
+            None,
+        ),
+        line(
+            "chart-range",
+            "Price vs Range",
+            "Price",
+            vec![LineSeries {
+                label: "Price".to_string(),
+                points: series(|p| p.price),
+            }],
+            Some(Band {
+                label: "Range".to_string(),
+                lower: series(|p| p.lower),
+                upper: series(|p| p.upper),
+            }),
+        ),
+        line(
+            "chart-drawdown",
+            "Drawdown",
+            "Drawdown (%)",
+            vec![LineSeries {
+                label: "Drawdown".to_string(),
+                points: series(|p| p.drawdown_pct),
+            }],
+            None,
+        ),
+    ]
+}
+
+/// Builds scatter plots of optimization candidates.
+///
+/// The best-ranked candidate is highlighted. Returns no charts without
+/// candidates.
+#[must_use]
+pub fn candidate_charts(candidates: &[RangeCandidate]) -> Vec<InteractiveChart> {
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+    let scatter = |id: &str,
+                   title: &str,
+                   x_label: &str,
+                   y_label: &str,
+                   axes: fn(&RangeCandidate) -> (f64, f64)| {
+        InteractiveChart::Scatter {
+            id: id.to_string(),
+            title: title.to_string(),
+            x_label: x_label.to_string(),
+            y_label: y_label.to_string(),
+            points: candidates
+                .iter()
+                .map(|c| {
+                    let (x, y) = axes(c);
+                    ScatterPoint {
+                        x,
+                        y,
+                        label: format!(
+                            "#{} ${} - ${} (score {})",
+                            c.rank,
+                            c.lower_price.round_dp(4),
+                            c.upper_price.round_dp(4),
+                            c.score.round_dp(4)
+                        ),
+                        highlight: c.rank == 1,
+                    }
+                })
+                .collect(),
+        }
+    };
+
+    vec![
+        scatter(
+            "chart-width-pnl",
+            "Range Width vs Expected PnL",
+            "Width (%)",
+            "Expected PnL",
+            |c| (f64_of(c.range_width_pct), f64_of(c.expected_pnl)),
+        ),
+        scatter(
+            "chart-il-fees",
+            "Expected IL vs Expected Fees",
+            "Expected IL",
+            "Expected fees",
+            |c| (f64_of(c.expected_il), f64_of(c.expected_fees)),
+        ),
+    ]
+}
+
+/// Inserts interactive charts before the end of an HTML document's body.
+#[must_use]
+pub fn attach_interactive_charts(html: String, charts: &[InteractiveChart]) -> String {
+    if charts.is_empty() {
+        return html;
+    }
+
+    let mut section = String::from("    <h2>Interactive Charts</h2>\n");
+    for chart in charts {
+        section.push_str(&format!(
+            "    <div class=\"chart\"><h3>{}</h3><canvas id=\"{}\" style=\"width: 100%; height: 360px;\"></canvas></div>\n",
+            escape_html(chart.title()),
+            chart.id()
+        ));
+    }
+    // `</` would end the script element early
+    let data = serde_json::to_string(charts)
+        .unwrap_or_else(|_| "[]".to_string())
+        .replace("</", "<\\/");
+    section.push_str(&format!(
+        "    <script type=\"application/json\" id=\"report-charts\">{}</script>\n    <script>\n{}\n    ClmmCharts.renderAll(JSON.parse(document.getElementById(\"report-charts\").textContent));\n    </script>\n",
+        data, CHART_SCRIPT
+    ));
+
+    insert_before_body_end(html, &section)
+}
+
+/// Inserts a section before `</body>`, or appends it if there is none.
+pub(super) fn insert_before_body_end(html: String, section: &str) -> String {
+    match html.rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], section, &html[end..]),
+        None => html + section,
+    }
+}
+
+/// Keeps at most `max` points, evenly spaced and always including the last.
+fn thin(points: &[EquityPoint], max: usize) -> Vec<EquityPoint> {
+    if points.len() <= max {
+        return points.to_vec();
+    }
+    let stride = points.len().div_ceil(max - 1);
+    let mut thinned: Vec<EquityPoint> = points.iter().step_by(stride).copied().collect();
+    if let Some(last) = points.last()
+        && thinned.last() != Some(last)
+    {
+        thinned.push(*last);
+    }
+    thinned
+}
+
+/// Converts a decimal to a float for plotting.
+fn f64_of(value: rust_decimal::Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+/// Escapes text for use in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn points(count: u64) -> Vec<EquityPoint> {
+        (0..count)
+            .map(|step| EquityPoint {
+                step,
+                price: 100.0 + step as f64,
+                lower: 90.0,
+                upper: 110.0,
+                value: 1_000.0,
+                drawdown_pct: 0.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_backtest_charts_thin_long_series() {
+        let charts = backtest_charts(&points(43_200));
+        assert_eq!(charts.len(), 3);
+        let InteractiveChart::Line { series, band, .. } = &charts[1] else {
+            panic!("expected a line chart");
+        };
+        let price = &series[0].points;
+        assert!(price.len() <= MAX_SERIES_POINTS);
+        assert_eq!(price.first(), Some(&(0.0, 100.0)));
+        assert_eq!(price.last(), Some(&(43_199.0, 43_299.0)));
+        assert_eq!(band.as_ref().unwrap().lower.len(), price.len());
+
+        assert!(backtest_charts(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_attach_embeds_data_and_script() {
+        let candidates = vec![RangeCandidate {
+            rank: 1,
+            range_width_pct: Decimal::from(10),
+            lower_price: Decimal::from(95),
+            upper_price: Decimal::from(105),
+            expected_fees: Decimal::from(12),
+            expected_il: Decimal::from(3),
+            expected_pnl: Decimal::from(9),
+            time_in_range: Decimal::from(80),
+            score: Decimal::ONE,
+        }];
+        let mut charts = backtest_charts(&points(10));
+        charts.extend(candidate_charts(&candidates));
+        let html =
+            attach_interactive_charts("<html><body><h1>x</h1></body></html>".to_string(), &charts);
+
+        assert!(html.contains("<canvas id=\"chart-equity\""));
+        assert!(html.contains("<canvas id=\"chart-width-pnl\""));
+        assert!(html.contains("\"kind\":\"scatter\""));
+        assert!(html.contains("\"highlight\":true"));
+        assert!(html.contains("ClmmCharts.renderAll"));
+        assert!(html.ends_with("</body></html>"));
+    }
+
+    #[test]
+    fn test_embedded_data_cannot_close_script() {
+        let chart = InteractiveChart::Scatter {
+            id: "c".to_string(),
+            title: "<b>".to_string(),
+            x_label: "</script><script>alert(1)".to_string(),
+            y_label: "y".to_string(),
+            points: Vec::new(),
+        };
+        let html = attach_interactive_charts(String::new(), &[chart]);
+        assert!(!html.contains("</script><script>alert"));
+        assert!(html.contains("<h3>&lt;b&gt;</h3>"));
+    }
+}
@@ -0,0 +1,195 @@
+//! Run manifests for reproducible results.
+//!
+//! A manifest records everything a result depends on: the command and its
+//! configuration, where the market data came from and which window it
+//! covered, and the random seed. Re-running the command with the manifest's
+//! configuration and seed over the same data reproduces the result.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clmm_lp_domain::entities::price_candle::PriceCandle;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Market data a run was computed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataWindow {
+    /// Data source, e.g. `birdeye` or `database:<pool address>`.
+    pub provider: String,
+    /// Trading pair.
+    pub pair: String,
+    /// Requested window start (Unix seconds).
+    pub start_time: u64,
+    /// Requested window end (Unix seconds).
+    pub end_time: u64,
+    /// Candle resolution in seconds.
+    pub resolution_secs: u64,
+    /// Number of candles used.
+    pub data_points: usize,
+    /// Timestamp of the first candle used.
+    pub first_timestamp: Option<u64>,
+    /// Timestamp of the last candle used.
+    pub last_timestamp: Option<u64>,
+}
+
+impl DataWindow {
+    /// Describes the candles a run used from a requested window.
+    #[must_use]
+    pub fn from_candles(
+        provider: impl Into<String>,
+        pair: impl Into<String>,
+        start_time: u64,
+        end_time: u64,
+        candles: &[PriceCandle],
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            pair: pair.into(),
+            start_time,
+            end_time,
+            resolution_secs: candles.first().map_or(0, |c| c.duration_seconds),
+            data_points: candles.len(),
+            first_timestamp: candles.first().map(|c| c.start_timestamp),
+            last_timestamp: candles.last().map(|c| c.start_timestamp),
+        }
+    }
+}
+
+/// Record of a run's inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// Command that produced the result.
+    pub command: String,
+    /// CLI version.
+    pub version: String,
+    /// When the run finished.
+    pub created_at: DateTime<Utc>,
+    /// Market data used.
+    pub data: Option<DataWindow>,
+    /// Seed of the random components, if any.
+    pub seed: Option<u64>,
+    /// Command configuration.
+    pub config: serde_json::Value,
+    /// SHA-256 of the configuration, for quick comparison of runs.
+    pub config_hash: String,
+}
+
+impl RunManifest {
+    /// Creates a manifest for a command and its configuration.
+    #[must_use]
+    pub fn new(command: impl Into<String>, config: serde_json::Value) -> Self {
+        Self {
+            command: command.into(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            data: None,
+            seed: None,
+            config_hash: config_hash(&config),
+            config,
+        }
+    }
+
+    /// Sets the market data window.
+    #[must_use]
+    pub fn with_data(mut self, data: DataWindow) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets the random seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Prints a one-line summary.
+    pub fn print_summary(&self) {
+        let seed = self
+            .seed
+            .map_or_else(|| "none".to_string(), |seed| seed.to_string());
+        let provider = self
+            .data
+            .as_ref()
+            .map_or("-", |data| data.provider.as_str());
+        crate::status!(
+            "🧾 Run manifest: seed {} | config {} | data {}",
+            seed,
+            &self.config_hash[..12],
+            provider
+        );
+    }
+
+    /// Writes the manifest as JSON.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Hashes a configuration independently of key order.
+fn config_hash(config: &serde_json::Value) -> String {
+    hex::encode(Sha256::digest(canonical(config).to_string().as_bytes()))
+}
+
+/// Rebuilds a value with object keys in sorted order.
+fn canonical(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), canonical(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(canonical).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_hash_ignores_key_order() {
+        let a: serde_json::Value = serde_json::from_str(r#"{"lower":80,"upper":120}"#).unwrap();
+        let b: serde_json::Value = serde_json::from_str(r#"{"upper":120,"lower":80}"#).unwrap();
+        let c: serde_json::Value = serde_json::from_str(r#"{"lower":80,"upper":121}"#).unwrap();
+
+        assert_eq!(config_hash(&a), config_hash(&b));
+        assert_ne!(config_hash(&a), config_hash(&c));
+        assert_eq!(config_hash(&a).len(), 64);
+    }
+
+    #[test]
+    fn test_manifest_round_trips() {
+        let manifest = RunManifest::new("backtest", serde_json::json!({ "days": 30 }))
+            .with_seed(42)
+            .with_data(DataWindow {
+                provider: "birdeye".to_string(),
+                pair: "SOL/USDC".to_string(),
+                start_time: 0,
+                end_time: 3600,
+                resolution_secs: 3600,
+                data_points: 2,
+                first_timestamp: Some(0),
+                last_timestamp: Some(3600),
+            });
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        let parsed: RunManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.seed, Some(42));
+        assert_eq!(parsed.config_hash, manifest.config_hash);
+        assert_eq!(parsed.data, manifest.data);
+    }
+}
@@ -5,10 +5,20 @@
 
 pub mod chart;
 pub mod export;
+pub mod interactive;
+pub mod manifest;
+pub mod mode;
+pub mod plot;
+pub mod progress;
 mod reports;
 pub mod table;
 
 pub use chart::*;
 pub use export::*;
+pub use interactive::*;
+pub use manifest::*;
+pub use mode::*;
+pub use plot::*;
+pub use progress::*;
 pub use reports::{AnalysisReport, BacktestReport, OptimizationReport, RangeCandidate};
 pub use table::*;
@@ -0,0 +1,101 @@
+//! Output mode selection.
+//!
+//! `--output json` switches every command from prettytable text to a single
+//! JSON document on stdout. Progress and status lines go to stderr in that
+//! mode, so the document can be piped straight into `jq` or a CI script.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::io::{self, BufRead, Write};
+use std::sync::OnceLock;
+
+/// How command results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputMode {
+    /// Human-readable tables
+    #[default]
+    Table,
+    /// One JSON document on stdout
+    Json,
+}
+
+static OUTPUT_MODE: OnceLock<OutputMode> = OnceLock::new();
+
+/// Sets the output mode for the rest of the process.
+///
+/// Only the first call takes effect.
+pub fn set_output_mode(mode: OutputMode) {
+    let _ = OUTPUT_MODE.set(mode);
+}
+
+/// Returns the output mode, `Table` until one is set.
+#[must_use]
+pub fn output_mode() -> OutputMode {
+    OUTPUT_MODE.get().copied().unwrap_or_default()
+}
+
+/// Returns true when results are printed as JSON.
+#[must_use]
+pub fn is_json() -> bool {
+    output_mode() == OutputMode::Json
+}
+
+/// Prints a value as pretty JSON on stdout.
+///
+/// # Errors
+/// Returns an error if the value cannot be serialized.
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Asks the user to confirm on standard input.
+///
+/// The prompt goes to stderr under `--output json`.
+///
+/// # Errors
+/// Returns an error if the terminal cannot be read or written.
+pub fn confirm(prompt: &str) -> Result<bool> {
+    if is_json() {
+        eprint!("{} [y/N] ", prompt);
+        io::stderr().flush()?;
+    } else {
+        print!("{} [y/N] ", prompt);
+        io::stdout().flush()?;
+    }
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+/// Prints a progress or status line.
+///
+/// Goes to stdout with table output and to stderr under `--output json`,
+/// keeping stdout for the JSON document.
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if $crate::output::is_json() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_mode_is_table() {
+        assert_eq!(OutputMode::default(), OutputMode::Table);
+        assert_eq!(
+            <OutputMode as clap::ValueEnum>::from_str("json", true).unwrap(),
+            OutputMode::Json
+        );
+    }
+}
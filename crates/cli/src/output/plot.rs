@@ -0,0 +1,257 @@
+//! Image chart rendering for exported reports.
+//!
+//! Renders PNG or SVG charts of a backtest with plotters so they can be
+//! attached to HTML and Markdown reports.
+
+use anyhow::{Result, anyhow};
+use clmm_lp_simulation::prelude::PositionSnapshot;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Image size of rendered charts in pixels.
+const CHART_SIZE: (u32, u32) = (960, 480);
+
+/// Image format of rendered charts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImageFormat {
+    /// Portable Network Graphics.
+    Png,
+    /// Scalable Vector Graphics.
+    Svg,
+}
+
+impl ImageFormat {
+    /// File extension of the format.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// A rendered chart file.
+#[derive(Debug, Clone)]
+pub struct ChartImage {
+    /// Chart title.
+    pub title: String,
+    /// Path of the image file.
+    pub path: PathBuf,
+}
+
+/// A step of a backtest used for charting.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EquityPoint {
+    /// Step number (hours since the start).
+    pub step: u64,
+    /// Price at this step.
+    pub price: f64,
+    /// Lower bound of the active range.
+    pub lower: f64,
+    /// Upper bound of the active range.
+    pub upper: f64,
+    /// Position value in USD.
+    pub value: f64,
+    /// Drawdown from the running peak value, as a percentage.
+    pub drawdown_pct: f64,
+}
+
+/// Converts position snapshots into chart points.
+#[must_use]
+pub fn equity_points(snapshots: &[PositionSnapshot]) -> Vec<EquityPoint> {
+    let mut peak = Decimal::ZERO;
+    snapshots
+        .iter()
+        .map(|s| {
+            peak = peak.max(s.position_value_usd);
+            let drawdown = if peak.is_zero() {
+                Decimal::ZERO
+            } else {
+                (s.position_value_usd - peak) / peak * Decimal::from(100)
+            };
+            EquityPoint {
+                step: s.step,
+                price: s.price.value.to_f64().unwrap_or_default(),
+                lower: s.range.lower_price.value.to_f64().unwrap_or_default(),
+                upper: s.range.upper_price.value.to_f64().unwrap_or_default(),
+                value: s.position_value_usd.to_f64().unwrap_or_default(),
+                drawdown_pct: drawdown.to_f64().unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Renders the equity curve, price vs range and drawdown charts.
+///
+/// Images are written to `dir` as `<stem>-equity`, `<stem>-range` and
+/// `<stem>-drawdown` with the extension of `format`.
+///
+/// # Errors
+/// Returns an error if there are no points or an image cannot be written.
+pub fn render_backtest_charts(
+    points: &[EquityPoint],
+    dir: &Path,
+    stem: &str,
+    format: ImageFormat,
+) -> Result<Vec<ChartImage>> {
+    if points.is_empty() {
+        return Err(anyhow!("no backtest steps to chart"));
+    }
+
+    let series = |f: fn(&EquityPoint) -> f64| -> Vec<(f64, f64)> {
+        points.iter().map(|p| (p.step as f64, f(p))).collect()
+    };
+    let charts: [(&str, &str, Vec<Series<'_>>); 3] = [
+        (
+            "equity",
+            "Equity Curve (USD)",
+            vec![("Position value", BLUE, series(|p| p.value))],
+        ),
+        (
+            "range",
+            "Price vs LP Range",
+            vec![
+                ("Price", BLACK, series(|p| p.price)),
+                ("Lower bound", RED, series(|p| p.lower)),
+                ("Upper bound", GREEN, series(|p| p.upper)),
+            ],
+        ),
+        (
+            "drawdown",
+            "Drawdown (%)",
+            vec![("Drawdown", RED, series(|p| p.drawdown_pct))],
+        ),
+    ];
+
+    let mut images = Vec::with_capacity(charts.len());
+    for (suffix, title, series) in charts {
+        let path = dir.join(format!("{}-{}.{}", stem, suffix, format.extension()));
+        match format {
+            ImageFormat::Png => draw_lines(
+                BitMapBackend::new(&path, CHART_SIZE).into_drawing_area(),
+                title,
+                &series,
+            )?,
+            ImageFormat::Svg => draw_lines(
+                SVGBackend::new(&path, CHART_SIZE).into_drawing_area(),
+                title,
+                &series,
+            )?,
+        }
+        images.push(ChartImage {
+            title: title.to_string(),
+            path,
+        });
+    }
+    Ok(images)
+}
+
+/// A labelled line of a chart.
+type Series<'a> = (&'a str, RGBColor, Vec<(f64, f64)>);
+
+/// Draws line series on a drawing area.
+fn draw_lines<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    title: &str,
+    series: &[Series<'_>],
+) -> Result<()> {
+    let plot_err = |e: DrawingAreaErrorKind<DB::ErrorType>| anyhow!("failed to draw chart: {}", e);
+
+    let points = || series.iter().flat_map(|(_, _, points)| points.iter());
+    let x_max = points().map(|(x, _)| *x).fold(1.0, f64::max);
+    let (mut y_min, mut y_max) = points().fold((f64::MAX, f64::MIN), |(lo, hi), (_, y)| {
+        (lo.min(*y), hi.max(*y))
+    });
+    let padding = ((y_max - y_min) * 0.05).max(1e-9);
+    y_min -= padding;
+    y_max += padding;
+
+    root.fill(&WHITE).map_err(plot_err)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 22))
+        .margin(12)
+        .x_label_area_size(36)
+        .y_label_area_size(64)
+        .build_cartesian_2d(0f64..x_max, y_min..y_max)
+        .map_err(plot_err)?;
+    chart
+        .configure_mesh()
+        .x_desc("Hours")
+        .draw()
+        .map_err(plot_err)?;
+
+    for (label, color, points) in series {
+        let color = *color;
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().copied(),
+                color.stroke_width(2),
+            ))
+            .map_err(plot_err)?
+            .label(*label)
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 16, y)], color.stroke_width(2))
+            });
+    }
+    if series.len() > 1 {
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(plot_err)?;
+    }
+
+    root.present().map_err(plot_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_domain::prelude::{Price, PriceRange};
+
+    fn snapshot(step: u64, value: i64) -> PositionSnapshot {
+        PositionSnapshot {
+            step,
+            price: Price::new(Decimal::from(100)),
+            range: PriceRange::new(
+                Price::new(Decimal::from(90)),
+                Price::new(Decimal::from(110)),
+            ),
+            in_range: true,
+            cumulative_fees: Decimal::ZERO,
+            il_pct: Decimal::ZERO,
+            position_value_usd: Decimal::from(value),
+            net_pnl: Decimal::ZERO,
+            action: None,
+        }
+    }
+
+    #[test]
+    fn test_equity_points_track_drawdown_from_peak() {
+        let snapshots = vec![
+            snapshot(1, 1000),
+            snapshot(2, 1100),
+            snapshot(3, 990),
+            snapshot(4, 1200),
+        ];
+        let points = equity_points(&snapshots);
+
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[1].drawdown_pct, 0.0);
+        assert!((points[2].drawdown_pct + 10.0).abs() < 1e-9);
+        assert_eq!(points[3].drawdown_pct, 0.0);
+        assert_eq!(points[0].lower, 90.0);
+    }
+
+    #[test]
+    fn test_render_without_points_fails() {
+        let result = render_backtest_charts(&[], Path::new("."), "empty", ImageFormat::Svg);
+        assert!(result.is_err());
+    }
+}
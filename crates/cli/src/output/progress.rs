@@ -0,0 +1,76 @@
+//! Progress bars and Ctrl-C handling for long-running commands.
+//!
+//! Bars are drawn on stderr, so they never mix with the JSON document of
+//! `--output json`, and disappear when stderr is not a terminal.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Creates a progress bar over `len` steps.
+#[must_use]
+pub fn progress_bar(len: u64, message: &'static str) -> ProgressBar {
+    let bar = ProgressBar::new(len);
+    bar.set_style(
+        ProgressStyle::with_template("{msg} [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
+            .expect("valid progress template")
+            .progress_chars("=> "),
+    );
+    bar.set_message(message);
+    bar
+}
+
+/// Flag raised by Ctrl-C.
+///
+/// The first Ctrl-C only raises the flag, letting the command stop at the
+/// next step and save what it has; a second one exits immediately.
+#[derive(Debug, Clone, Default)]
+pub struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+    /// Starts listening for Ctrl-C.
+    #[must_use]
+    pub fn install() -> Self {
+        let interrupt = Self::default();
+        let flag = interrupt.0.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                flag.store(true, Ordering::SeqCst);
+                eprintln!("\n⏸️  Interrupted, saving partial results (Ctrl-C again to abort)...");
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    std::process::exit(130);
+                }
+            }
+        });
+        interrupt
+    }
+
+    /// Returns true once Ctrl-C was pressed.
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interrupt_is_shared_between_clones() {
+        let interrupt = Interrupt::default();
+        let clone = interrupt.clone();
+        assert!(!clone.is_set());
+
+        interrupt.0.store(true, Ordering::SeqCst);
+        assert!(clone.is_set());
+    }
+
+    #[test]
+    fn test_progress_bar_length() {
+        let bar = progress_bar(12, "Simulating");
+        bar.inc(5);
+        assert_eq!(bar.length(), Some(12));
+        assert_eq!(bar.position(), 5);
+    }
+}
@@ -0,0 +1,43 @@
+//! Configuration errors.
+
+use std::path::PathBuf;
+
+/// Errors raised while loading configuration.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// The configuration file could not be read.
+    #[error("Failed to read config file {path}: {source}")]
+    Read {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying I/O error.
+        source: std::io::Error,
+    },
+    /// The configuration file is not valid TOML or has unknown keys.
+    #[error("Invalid config file {path}: {message}")]
+    Parse {
+        /// Path of the file, or `<inline>` for parsed strings.
+        path: String,
+        /// Parser message.
+        message: String,
+    },
+    /// The selected profile does not exist.
+    #[error("Profile '{0}' not found in config")]
+    UnknownProfile(String),
+    /// An override names a key that does not exist.
+    #[error("Unknown config key '{0}'")]
+    UnknownKey(String),
+    /// An environment variable or override has the wrong type.
+    #[error("Invalid value '{value}' for {key}: expected {expected}")]
+    InvalidValue {
+        /// Dotted key, e.g. `api.port`.
+        key: String,
+        /// Rejected value.
+        value: String,
+        /// Expected type.
+        expected: &'static str,
+    },
+}
+
+/// Result type for configuration operations.
+pub type ConfigResult<T> = Result<T, ConfigError>;
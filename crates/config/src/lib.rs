@@ -0,0 +1,17 @@
+//! Layered configuration shared by the CLI, API server and executors.
+//!
+//! [`ConfigLoader`](loader::ConfigLoader) builds an
+//! [`AppConfig`](settings::AppConfig) from built-in defaults, a TOML file
+//! with optional profiles, environment variables and command-line
+//! overrides, in increasing order of precedence. Each binary maps the typed
+//! sections onto its own runtime configuration.
+
+/// Prelude module for convenient imports.
+pub mod prelude;
+
+/// Configuration errors.
+pub mod error;
+/// Layered loading.
+pub mod loader;
+/// Typed configuration sections.
+pub mod settings;
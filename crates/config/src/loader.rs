@@ -0,0 +1,391 @@
+//! Layered configuration loading.
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::settings::{AppConfig, KEYS};
+use std::path::{Path, PathBuf};
+use toml::Table;
+
+/// Name of the profile used when none is selected.
+const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variables predating the `CLMM_<SECTION>_<KEY>` scheme, with
+/// the keys they set.
+pub const LEGACY_ENV_VARS: &[(&str, &str)] = &[
+    ("SOLANA_RPC_URL", "rpc.url"),
+    ("SOLANA_RPC_BACKUP_URLS", "rpc.fallback_urls"),
+    ("SOLANA_RPC_TIMEOUT_SECS", "rpc.timeout_secs"),
+    ("SOLANA_RPC_MAX_RETRIES", "rpc.max_retries"),
+    ("SOLANA_COMMITMENT", "rpc.commitment"),
+    ("BIRDEYE_API_KEY", "providers.birdeye_api_key"),
+    ("JUPITER_API_URL", "providers.jupiter_api_url"),
+    ("MARKET_CACHE_TTL_SECS", "providers.market_cache_ttl_secs"),
+    ("DATABASE_URL", "database.url"),
+    ("WALLET_KEYPAIR_PATH", "execution.keypair"),
+    ("CLMM_KEYPAIR", "execution.keypair"),
+    ("DRY_RUN", "execution.dry_run"),
+    ("MAX_SLIPPAGE_BPS", "execution.max_slippage_bps"),
+    (
+        "TX_CONFIRMATION_TIMEOUT_SECS",
+        "execution.confirmation_timeout_secs",
+    ),
+    ("API_HOST", "api.host"),
+    ("API_PORT", "api.port"),
+    ("API_CORS_ALLOW_ALL", "api.cors_allow_all"),
+    ("API_REQUEST_TIMEOUT_SECS", "api.request_timeout_secs"),
+    ("API_KEYS", "api.api_keys"),
+    ("API_REQUIRE_AUTH", "api.require_auth"),
+    ("API_PAPER_LEDGER_DIR", "api.paper_ledger_dir"),
+];
+
+/// Flat profile keys from before profiles were split into sections.
+const LEGACY_PROFILE_KEYS: &[(&str, &str, &str)] = &[
+    ("rpc_url", "rpc", "url"),
+    ("api_url", "api", "url"),
+    ("api_key", "api", "key"),
+    ("birdeye_api_key", "providers", "birdeye_api_key"),
+    ("keypair", "execution", "keypair"),
+    ("symbol_a", "cli", "symbol_a"),
+    ("mint_a", "cli", "mint_a"),
+    ("capital", "cli", "capital"),
+];
+
+/// Builds an [`AppConfig`] from layered sources.
+///
+/// Later layers win: built-in defaults, then the configuration file and the
+/// selected profile within it, then environment variables, then explicit
+/// overrides (typically command-line flags):
+///
+/// ```toml
+/// default_profile = "mainnet"
+///
+/// [api]
+/// port = 9000
+///
+/// [profiles.mainnet.rpc]
+/// url = "https://rpc.example.com"
+///
+/// [profiles.devnet.rpc]
+/// url = "https://api.devnet.solana.com"
+/// ```
+///
+/// Every key can be set from the environment as `CLMM_<SECTION>_<KEY>`,
+/// e.g. `CLMM_API_PORT`; the older names in [`LEGACY_ENV_VARS`] are also
+/// read, with the `CLMM_` form taking precedence.
+#[derive(Debug, Clone)]
+pub struct ConfigLoader {
+    /// Explicit configuration file, which must exist.
+    path: Option<PathBuf>,
+    /// Profile to apply over the file's base sections.
+    profile: Option<String>,
+    /// Whether to read environment variables.
+    env: bool,
+    /// Key-value overrides, applied last.
+    overrides: Vec<(String, String)>,
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigLoader {
+    /// Creates a loader reading the default file, if it exists, and the
+    /// environment.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            profile: None,
+            env: true,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Returns the default configuration file path,
+    /// `~/.clmm-lp/config.toml`.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".clmm-lp").join("config.toml"))
+    }
+
+    /// Reads an explicit configuration file instead of the default one.
+    #[must_use]
+    pub fn with_file(mut self, path: Option<impl Into<PathBuf>>) -> Self {
+        self.path = path.map(Into::into);
+        self
+    }
+
+    /// Selects a profile instead of the file's default one.
+    #[must_use]
+    pub fn with_profile(mut self, profile: Option<impl Into<String>>) -> Self {
+        self.profile = profile.map(Into::into);
+        self
+    }
+
+    /// Ignores environment variables.
+    #[must_use]
+    pub fn without_env(mut self) -> Self {
+        self.env = false;
+        self
+    }
+
+    /// Overrides a key after every other layer.
+    #[must_use]
+    pub fn with_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((key.into(), value.into()));
+        self
+    }
+
+    /// Loads the configuration.
+    ///
+    /// # Errors
+    /// Returns an error if an explicit file is missing, the file or a
+    /// value is invalid, or the selected profile does not exist.
+    pub fn load(&self) -> ConfigResult<AppConfig> {
+        let path = match &self.path {
+            Some(path) => Some(path.clone()),
+            None => Self::default_path().filter(|path| path.exists()),
+        };
+        let mut config = match path {
+            Some(path) => load_file(&path, self.profile.as_deref())?,
+            None => parse(&Table::new(), self.profile.as_deref(), "<default>")?,
+        };
+        if self.env {
+            apply_env(&mut config, |name| std::env::var(name).ok())?;
+        }
+        for (key, value) in &self.overrides {
+            config.set(key, value)?;
+        }
+        Ok(config)
+    }
+}
+
+/// Reads a configuration file and applies a profile from it.
+fn load_file(path: &Path, profile: Option<&str>) -> ConfigResult<AppConfig> {
+    let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_str(&contents, profile).map_err(|e| match e {
+        ConfigError::Parse { message, .. } => ConfigError::Parse {
+            path: path.display().to_string(),
+            message,
+        },
+        other => other,
+    })
+}
+
+/// Parses a configuration file's contents and applies a profile from it.
+///
+/// A named profile must exist. Without a name the file's
+/// `default_profile` is used, then a profile named `default`, then none.
+///
+/// # Errors
+/// Returns an error for invalid TOML, unknown keys or a missing profile.
+pub fn parse_str(contents: &str, profile: Option<&str>) -> ConfigResult<AppConfig> {
+    let table: Table = contents
+        .parse()
+        .map_err(|e: toml::de::Error| ConfigError::Parse {
+            path: "<inline>".to_string(),
+            message: e.message().to_string(),
+        })?;
+    parse(&table, profile, "<inline>")
+}
+
+/// Merges the selected profile over the base sections and deserializes them.
+fn parse(table: &Table, profile: Option<&str>, source: &str) -> ConfigResult<AppConfig> {
+    let mut base = table.clone();
+    let default_profile = match base.remove("default_profile") {
+        Some(toml::Value::String(name)) => Some(name),
+        Some(_) => {
+            return Err(ConfigError::Parse {
+                path: source.to_string(),
+                message: "default_profile must be a string".to_string(),
+            });
+        }
+        None => None,
+    };
+    let profiles = match base.remove("profiles") {
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => {
+            return Err(ConfigError::Parse {
+                path: source.to_string(),
+                message: "profiles must be a table".to_string(),
+            });
+        }
+        None => Table::new(),
+    };
+
+    let selected = match profile.map(str::to_string).or(default_profile) {
+        Some(name) => Some(
+            profiles
+                .get(&name)
+                .ok_or(ConfigError::UnknownProfile(name))?,
+        ),
+        None => profiles.get(DEFAULT_PROFILE),
+    };
+    if let Some(toml::Value::Table(overlay)) = selected {
+        merge(&mut base, sectioned(overlay));
+    }
+
+    let mut config: AppConfig =
+        toml::Value::Table(base)
+            .try_into()
+            .map_err(|e: toml::de::Error| ConfigError::Parse {
+                path: source.to_string(),
+                message: e.message().to_string(),
+            })?;
+    config.expand_paths();
+    Ok(config)
+}
+
+/// Moves flat legacy profile keys into their sections.
+fn sectioned(profile: &Table) -> Table {
+    let mut table = Table::new();
+    for (key, value) in profile {
+        match LEGACY_PROFILE_KEYS
+            .iter()
+            .find(|(legacy, _, _)| legacy == key)
+        {
+            Some((_, section, field)) => {
+                let mut entry = Table::new();
+                entry.insert((*field).to_string(), value.clone());
+                merge(
+                    &mut table,
+                    Table::from_iter([((*section).to_string(), toml::Value::Table(entry))]),
+                );
+            }
+            None => merge(&mut table, Table::from_iter([(key.clone(), value.clone())])),
+        }
+    }
+    table
+}
+
+/// Recursively merges `overlay` into `base`, overlay values winning.
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(value)) => {
+                merge(existing, value);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Returns the `CLMM_<SECTION>_<KEY>` environment variable of a key.
+#[must_use]
+pub fn env_var_name(key: &str) -> String {
+    format!("CLMM_{}", key.replace('.', "_").to_uppercase())
+}
+
+/// Applies environment variables found by `lookup`.
+///
+/// # Errors
+/// Returns an error naming the key if a variable has the wrong type.
+pub fn apply_env(
+    config: &mut AppConfig,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> ConfigResult<()> {
+    for (name, key) in LEGACY_ENV_VARS {
+        if let Some(value) = lookup(name) {
+            config.set(key, &value)?;
+        }
+    }
+    for key in KEYS {
+        if let Some(value) = lookup(&env_var_name(key)) {
+            config.set(key, &value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const CONFIG: &str = r#"
+        default_profile = "main"
+
+        [api]
+        port = 9000
+
+        [profiles.main.rpc]
+        url = "https://rpc.example.com"
+
+        [profiles.main.cli]
+        symbol_a = "JUP"
+
+        [profiles.legacy]
+        rpc_url = "https://api.devnet.solana.com"
+        keypair = "~/dev.json"
+        capital = 2500.0
+    "#;
+
+    #[test]
+    fn test_profile_layers_over_base_sections() {
+        let main = parse_str(CONFIG, None).unwrap();
+        assert_eq!(main.rpc.url, "https://rpc.example.com");
+        assert_eq!(main.api.port, 9000);
+        assert_eq!(main.cli.symbol_a.as_deref(), Some("JUP"));
+        // Defaults fill everything else
+        assert_eq!(main.rpc.timeout_secs, 30);
+
+        let legacy = parse_str(CONFIG, Some("legacy")).unwrap();
+        assert_eq!(legacy.rpc.url, "https://api.devnet.solana.com");
+        assert_eq!(legacy.cli.capital, Some(2500.0));
+        assert!(!legacy.execution.keypair.unwrap().starts_with("~"));
+
+        assert!(matches!(
+            parse_str(CONFIG, Some("missing")),
+            Err(ConfigError::UnknownProfile(_))
+        ));
+        assert_eq!(parse_str("", None).unwrap(), AppConfig::default());
+    }
+
+    #[test]
+    fn test_unknown_keys_rejected() {
+        assert!(parse_str("[api]\nprot = 80", None).is_err());
+        assert!(parse_str("[profiles.main]\nrpc = \"x\"", Some("main")).is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut config = parse_str(CONFIG, None).unwrap();
+        let env: HashMap<&str, &str> = HashMap::from([
+            ("API_PORT", "7000"),
+            ("SOLANA_RPC_URL", "https://legacy.example.com"),
+            ("CLMM_RPC_URL", "https://new.example.com"),
+            ("CLMM_EXECUTION_DRY_RUN", "false"),
+        ]);
+        apply_env(&mut config, |name| env.get(name).map(|v| v.to_string())).unwrap();
+
+        assert_eq!(config.api.port, 7000);
+        assert_eq!(config.rpc.url, "https://new.example.com");
+        assert!(!config.execution.dry_run);
+
+        let bad = HashMap::from([("API_PORT", "eighty")]);
+        assert!(apply_env(&mut config, |name| bad.get(name).map(|v| v.to_string())).is_err());
+    }
+
+    #[test]
+    fn test_overrides_apply_last() {
+        let config = ConfigLoader::new()
+            .with_file(None::<PathBuf>)
+            .without_env()
+            .with_override("api.port", "1234")
+            .load()
+            .unwrap();
+        assert_eq!(config.api.port, 1234);
+        assert!(
+            ConfigLoader::new()
+                .with_file(Some("/nonexistent/clmm.toml"))
+                .load()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Prelude module for convenient imports.
+//!
+//! This module re-exports the most commonly used types from the crate.
+//!
+//! # Example
+//!
+//! ```rust
+//! use clmm_lp_config::prelude::*;
+//! ```
+
+pub use crate::error::{ConfigError, ConfigResult};
+pub use crate::loader::{ConfigLoader, apply_env, env_var_name, parse_str};
+pub use crate::settings::{
+    ApiSettings, AppConfig, CliSettings, Commitment, DatabaseSettings, ExecutionSettings,
+    ProviderSettings, RpcSettings,
+};
@@ -0,0 +1,466 @@
+//! Typed configuration sections.
+
+use crate::error::{ConfigError, ConfigResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Complete configuration shared by the CLI, API server and executors.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    /// Solana RPC endpoints.
+    pub rpc: RpcSettings,
+    /// Market data providers.
+    pub providers: ProviderSettings,
+    /// PostgreSQL storage.
+    pub database: DatabaseSettings,
+    /// Strategy execution.
+    pub execution: ExecutionSettings,
+    /// API server, and the CLI's connection to it.
+    pub api: ApiSettings,
+    /// CLI argument defaults.
+    pub cli: CliSettings,
+}
+
+/// Solana RPC settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcSettings {
+    /// Primary RPC endpoint.
+    pub url: String,
+    /// Fallback endpoints, tried in order.
+    pub fallback_urls: Vec<String>,
+    /// Request timeout in seconds.
+    pub timeout_secs: u64,
+    /// Maximum retries per request.
+    pub max_retries: u32,
+    /// Commitment level of reads.
+    pub commitment: Commitment,
+}
+
+impl Default for RpcSettings {
+    fn default() -> Self {
+        Self {
+            url: "https://api.mainnet-beta.solana.com".to_string(),
+            fallback_urls: vec![
+                "https://solana-api.projectserum.com".to_string(),
+                "https://rpc.ankr.com/solana".to_string(),
+            ],
+            timeout_secs: 30,
+            max_retries: 3,
+            commitment: Commitment::Confirmed,
+        }
+    }
+}
+
+/// Commitment level of RPC reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+    /// Processed by the connected node.
+    Processed,
+    /// Voted on by a supermajority.
+    #[default]
+    Confirmed,
+    /// Rooted.
+    Finalized,
+}
+
+impl FromStr for Commitment {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "processed" => Ok(Self::Processed),
+            "confirmed" => Ok(Self::Confirmed),
+            "finalized" => Ok(Self::Finalized),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Market data provider settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProviderSettings {
+    /// Birdeye API key; market data is unavailable without it.
+    pub birdeye_api_key: Option<String>,
+    /// Jupiter API base URL, if not the public endpoint.
+    pub jupiter_api_url: Option<String>,
+    /// How long fetched candles stay cached, in seconds.
+    pub market_cache_ttl_secs: u64,
+}
+
+impl Default for ProviderSettings {
+    fn default() -> Self {
+        Self {
+            birdeye_api_key: None,
+            jupiter_api_url: None,
+            market_cache_ttl_secs: 60,
+        }
+    }
+}
+
+/// Database settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    /// PostgreSQL connection URL; state is kept in memory without it.
+    pub url: Option<String>,
+    /// Seconds between recorded snapshots of monitored positions.
+    pub snapshot_interval_secs: u64,
+    /// Seconds between recomputed pool and strategy leaderboards.
+    pub leaderboard_interval_secs: u64,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: None,
+            snapshot_interval_secs: 300,
+            leaderboard_interval_secs: 3600,
+        }
+    }
+}
+
+/// Strategy execution settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecutionSettings {
+    /// Keypair file signing transactions.
+    pub keypair: Option<PathBuf>,
+    /// Whether strategies only simulate unless they opt out.
+    pub dry_run: bool,
+    /// Whether strategies execute decisions without confirmation.
+    pub auto_execute: bool,
+    /// Seconds between strategy evaluations.
+    pub eval_interval_secs: u64,
+    /// Maximum slippage in basis points.
+    pub max_slippage_bps: u32,
+    /// Seconds to wait for a transaction to confirm.
+    pub confirmation_timeout_secs: u64,
+    /// Maximum divergence of a pool's price from the oracle price, in basis
+    /// points, before rebalances and opens are aborted; `0` disables the check.
+    pub max_oracle_divergence_bps: u32,
+    /// Maximum age of an oracle price in seconds.
+    pub max_oracle_staleness_secs: u64,
+    /// Maximum drop of the portfolio value from its peak, as a percentage,
+    /// before the portfolio stop engages; `0` disables the limit.
+    pub max_portfolio_drawdown_pct: f64,
+    /// Maximum USD exposure to any single token across all positions before
+    /// the portfolio stop engages; `0` disables the limit.
+    pub max_token_exposure_usd: f64,
+    /// Whether the portfolio stop closes all positions as well as pausing
+    /// strategies.
+    pub unwind_on_portfolio_stop: bool,
+    /// Seconds between portfolio risk checks.
+    pub risk_check_interval_secs: u64,
+}
+
+impl Default for ExecutionSettings {
+    fn default() -> Self {
+        Self {
+            keypair: None,
+            dry_run: true,
+            auto_execute: false,
+            eval_interval_secs: 300,
+            max_slippage_bps: 50,
+            confirmation_timeout_secs: 60,
+            max_oracle_divergence_bps: 200,
+            max_oracle_staleness_secs: 60,
+            max_portfolio_drawdown_pct: 0.0,
+            max_token_exposure_usd: 0.0,
+            unwind_on_portfolio_stop: false,
+            risk_check_interval_secs: 60,
+        }
+    }
+}
+
+/// API server settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiSettings {
+    /// Host the server binds to.
+    pub host: String,
+    /// Port the server binds to.
+    pub port: u16,
+    /// Whether to allow cross-origin requests from any origin.
+    pub cors_allow_all: bool,
+    /// Request timeout in seconds.
+    pub request_timeout_secs: u64,
+    /// Bootstrap admin API keys.
+    pub api_keys: Vec<String>,
+    /// Whether to require authentication even when no API keys exist.
+    pub require_auth: bool,
+    /// Directory for paper trading ledgers.
+    pub paper_ledger_dir: PathBuf,
+    /// Base URL the CLI sends API requests to.
+    pub url: String,
+    /// API key the CLI authenticates with.
+    pub key: Option<String>,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 8080,
+            cors_allow_all: true,
+            request_timeout_secs: 30,
+            api_keys: vec![],
+            require_auth: false,
+            paper_ledger_dir: PathBuf::from("data/paper"),
+            url: "http://localhost:8080/api/v1".to_string(),
+            key: None,
+        }
+    }
+}
+
+/// Defaults for CLI arguments.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliSettings {
+    /// Default token A symbol.
+    pub symbol_a: Option<String>,
+    /// Default token A mint address.
+    pub mint_a: Option<String>,
+    /// Default capital in USD.
+    pub capital: Option<f64>,
+}
+
+/// Every key accepted by [`AppConfig::set`].
+pub const KEYS: &[&str] = &[
+    "rpc.url",
+    "rpc.fallback_urls",
+    "rpc.timeout_secs",
+    "rpc.max_retries",
+    "rpc.commitment",
+    "providers.birdeye_api_key",
+    "providers.jupiter_api_url",
+    "providers.market_cache_ttl_secs",
+    "database.url",
+    "database.snapshot_interval_secs",
+    "database.leaderboard_interval_secs",
+    "execution.keypair",
+    "execution.dry_run",
+    "execution.auto_execute",
+    "execution.eval_interval_secs",
+    "execution.max_slippage_bps",
+    "execution.confirmation_timeout_secs",
+    "execution.max_oracle_divergence_bps",
+    "execution.max_oracle_staleness_secs",
+    "execution.max_portfolio_drawdown_pct",
+    "execution.max_token_exposure_usd",
+    "execution.unwind_on_portfolio_stop",
+    "execution.risk_check_interval_secs",
+    "api.host",
+    "api.port",
+    "api.cors_allow_all",
+    "api.request_timeout_secs",
+    "api.api_keys",
+    "api.require_auth",
+    "api.paper_ledger_dir",
+    "api.url",
+    "api.key",
+    "cli.symbol_a",
+    "cli.mint_a",
+    "cli.capital",
+];
+
+impl AppConfig {
+    /// Sets a key from its string form, as given in an environment
+    /// variable or command-line override.
+    ///
+    /// Lists are comma-separated and an empty string clears an optional
+    /// value.
+    ///
+    /// # Errors
+    /// Returns an error for an unknown key or a value of the wrong type.
+    pub fn set(&mut self, key: &str, value: &str) -> ConfigResult<()> {
+        match key {
+            "rpc.url" => self.rpc.url = value.to_string(),
+            "rpc.fallback_urls" => self.rpc.fallback_urls = list(value),
+            "rpc.timeout_secs" => self.rpc.timeout_secs = parse(key, value, "seconds")?,
+            "rpc.max_retries" => self.rpc.max_retries = parse(key, value, "an integer")?,
+            "rpc.commitment" => {
+                self.rpc.commitment = parse(key, value, "processed, confirmed or finalized")?;
+            }
+            "providers.birdeye_api_key" => self.providers.birdeye_api_key = optional(value),
+            "providers.jupiter_api_url" => self.providers.jupiter_api_url = optional(value),
+            "providers.market_cache_ttl_secs" => {
+                self.providers.market_cache_ttl_secs = parse(key, value, "seconds")?;
+            }
+            "database.url" => self.database.url = optional(value),
+            "database.snapshot_interval_secs" => {
+                self.database.snapshot_interval_secs = parse(key, value, "seconds")?;
+            }
+            "database.leaderboard_interval_secs" => {
+                self.database.leaderboard_interval_secs = parse(key, value, "seconds")?;
+            }
+            "execution.keypair" => {
+                self.execution.keypair = optional(value).map(|path| expand_home(Path::new(&path)));
+            }
+            "execution.dry_run" => self.execution.dry_run = boolean(key, value)?,
+            "execution.auto_execute" => self.execution.auto_execute = boolean(key, value)?,
+            "execution.eval_interval_secs" => {
+                self.execution.eval_interval_secs = parse(key, value, "seconds")?;
+            }
+            "execution.max_slippage_bps" => {
+                self.execution.max_slippage_bps = parse(key, value, "basis points")?;
+            }
+            "execution.confirmation_timeout_secs" => {
+                self.execution.confirmation_timeout_secs = parse(key, value, "seconds")?;
+            }
+            "execution.max_oracle_divergence_bps" => {
+                self.execution.max_oracle_divergence_bps = parse(key, value, "basis points")?;
+            }
+            "execution.max_oracle_staleness_secs" => {
+                self.execution.max_oracle_staleness_secs = parse(key, value, "seconds")?;
+            }
+            "execution.max_portfolio_drawdown_pct" => {
+                self.execution.max_portfolio_drawdown_pct = parse(key, value, "a percentage")?;
+            }
+            "execution.max_token_exposure_usd" => {
+                self.execution.max_token_exposure_usd = parse(key, value, "a USD amount")?;
+            }
+            "execution.unwind_on_portfolio_stop" => {
+                self.execution.unwind_on_portfolio_stop = boolean(key, value)?;
+            }
+            "execution.risk_check_interval_secs" => {
+                self.execution.risk_check_interval_secs = parse(key, value, "seconds")?;
+            }
+            "api.host" => self.api.host = value.to_string(),
+            "api.port" => self.api.port = parse(key, value, "a port number")?,
+            "api.cors_allow_all" => self.api.cors_allow_all = boolean(key, value)?,
+            "api.request_timeout_secs" => {
+                self.api.request_timeout_secs = parse(key, value, "seconds")?;
+            }
+            "api.api_keys" => self.api.api_keys = list(value),
+            "api.require_auth" => self.api.require_auth = boolean(key, value)?,
+            "api.paper_ledger_dir" => self.api.paper_ledger_dir = PathBuf::from(value),
+            "api.url" => self.api.url = value.to_string(),
+            "api.key" => self.api.key = optional(value),
+            "cli.symbol_a" => self.cli.symbol_a = optional(value),
+            "cli.mint_a" => self.cli.mint_a = optional(value),
+            "cli.capital" => self.cli.capital = Some(parse(key, value, "a number")?),
+            _ => return Err(ConfigError::UnknownKey(key.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Expands `~` in configured paths to the home directory.
+    pub(crate) fn expand_paths(&mut self) {
+        self.execution.keypair = self.execution.keypair.as_deref().map(expand_home);
+    }
+}
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Processed => "processed",
+            Self::Confirmed => "confirmed",
+            Self::Finalized => "finalized",
+        })
+    }
+}
+
+/// Parses a value, naming the key and expected type on failure.
+fn parse<T: FromStr>(key: &str, value: &str, expected: &'static str) -> ConfigResult<T> {
+    value.trim().parse().map_err(|_| ConfigError::InvalidValue {
+        key: key.to_string(),
+        value: value.to_string(),
+        expected,
+    })
+}
+
+/// Parses `true`/`false`, also accepting `1`/`0` and `yes`/`no`.
+fn boolean(key: &str, value: &str) -> ConfigResult<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => Ok(true),
+        "false" | "0" | "no" => Ok(false),
+        _ => Err(ConfigError::InvalidValue {
+            key: key.to_string(),
+            value: value.to_string(),
+            expected: "true or false",
+        }),
+    }
+}
+
+/// Splits a comma-separated list, dropping empty entries.
+fn list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Treats an empty string as unset.
+fn optional(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Expands a leading `~` to the home directory.
+pub(crate) fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_parses_typed_values() {
+        let mut config = AppConfig::default();
+        config.set("api.port", "9090").unwrap();
+        config.set("api.api_keys", "a, b,,c").unwrap();
+        config.set("execution.dry_run", "false").unwrap();
+        config.set("rpc.commitment", "Finalized").unwrap();
+        config.set("providers.birdeye_api_key", "").unwrap();
+
+        assert_eq!(config.api.port, 9090);
+        assert_eq!(config.api.api_keys, vec!["a", "b", "c"]);
+        assert!(!config.execution.dry_run);
+        assert_eq!(config.rpc.commitment, Commitment::Finalized);
+        assert!(config.providers.birdeye_api_key.is_none());
+    }
+
+    #[test]
+    fn test_set_rejects_bad_input() {
+        let mut config = AppConfig::default();
+        assert!(matches!(
+            config.set("api.port", "http"),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            config.set("api.prot", "80"),
+            Err(ConfigError::UnknownKey(_))
+        ));
+        assert!(config.set("rpc.commitment", "max").is_err());
+    }
+
+    #[test]
+    fn test_every_key_is_settable() {
+        let mut config = AppConfig::default();
+        for key in KEYS {
+            let value = if key.ends_with("commitment") {
+                "processed"
+            } else if key.ends_with("dry_run")
+                || key.ends_with("auto_execute")
+                || key.ends_with("cors_allow_all")
+                || key.ends_with("require_auth")
+            {
+                "true"
+            } else {
+                "1"
+            };
+            config.set(key, value).unwrap();
+        }
+    }
+}
@@ -0,0 +1,34 @@
+-- Migration: 003_position_state
+-- Persists monitor and lifecycle state so PnL survives restarts
+
+-- Monitored positions table: latest monitor snapshot per position
+CREATE TABLE IF NOT EXISTS monitored_positions (
+    position_address VARCHAR(64) PRIMARY KEY,
+    pool_address VARCHAR(64) NOT NULL,
+    in_range BOOLEAN NOT NULL DEFAULT true,
+    state JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for pool queries
+CREATE INDEX IF NOT EXISTS idx_monitored_positions_pool ON monitored_positions(pool_address);
+
+-- Lifecycle events table: append-only position history
+CREATE TABLE IF NOT EXISTS lifecycle_events (
+    id UUID PRIMARY KEY,
+    position_address VARCHAR(64) NOT NULL,
+    pool_address VARCHAR(64) NOT NULL,
+    event_type VARCHAR(50) NOT NULL,  -- 'PositionOpened', 'Rebalanced', 'FeesCollected', ...
+    event JSONB NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Indexes for event replay
+CREATE INDEX IF NOT EXISTS idx_lifecycle_events_position ON lifecycle_events(position_address);
+CREATE INDEX IF NOT EXISTS idx_lifecycle_events_timestamp ON lifecycle_events(timestamp);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (3, '003_position_state')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,32 @@
+-- Migration: 004_audit_log
+-- Append-only audit log of decisions and transactions
+
+-- Audit log table: one row per evaluation, proposal, transaction or outcome
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    kind VARCHAR(20) NOT NULL,  -- 'evaluation', 'proposal', 'transaction', 'outcome'
+    action VARCHAR(50) NOT NULL,
+    strategy_id VARCHAR(64),
+    position_address VARCHAR(64),
+    tx_signature VARCHAR(128),
+    success BOOLEAN,
+    error TEXT,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Indexes for post-mortem queries
+CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_strategy ON audit_log(strategy_id, timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_position ON audit_log(position_address, timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_signature ON audit_log(tx_signature);
+
+-- Enforce append-only: updates and deletes are silently discarded
+CREATE OR REPLACE RULE audit_log_no_update AS ON UPDATE TO audit_log DO INSTEAD NOTHING;
+CREATE OR REPLACE RULE audit_log_no_delete AS ON DELETE TO audit_log DO INSTEAD NOTHING;
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (4, '004_audit_log')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,25 @@
+-- Migration: 005_jobs
+-- Background jobs (backtests, optimizations) and their results
+
+-- Jobs table: one row per submitted job, updated as it runs
+CREATE TABLE IF NOT EXISTS jobs (
+    id UUID PRIMARY KEY,
+    kind VARCHAR(32) NOT NULL,    -- 'backtest', 'optimization'
+    status VARCHAR(16) NOT NULL,  -- 'queued', 'running', 'completed', 'failed', 'cancelled'
+    progress_pct SMALLINT NOT NULL DEFAULT 0,
+    params JSONB NOT NULL DEFAULT '{}',
+    result JSONB,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+-- Indexes for listing jobs
+CREATE INDEX IF NOT EXISTS idx_jobs_kind ON jobs(kind, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (5, '005_jobs')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,24 @@
+-- Migration: 006_position_history
+-- Time series of monitor snapshots for historical portfolio analytics
+
+-- Position history table: latest monitor snapshot per position per bucket
+CREATE TABLE IF NOT EXISTS position_history (
+    position_address VARCHAR(64) NOT NULL,
+    pool_address VARCHAR(64) NOT NULL,
+    bucket TIMESTAMPTZ NOT NULL,
+    in_range BOOLEAN NOT NULL,
+    value_usd DECIMAL(30, 8) NOT NULL,
+    fees_usd DECIMAL(30, 8) NOT NULL,
+    il_pct DECIMAL(20, 8) NOT NULL,
+    net_pnl_usd DECIMAL(30, 8) NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (position_address, bucket)
+);
+
+-- Index for time range queries
+CREATE INDEX IF NOT EXISTS idx_position_history_bucket ON position_history(bucket);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (6, '006_position_history')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,20 @@
+-- Migration: 007_idempotency_keys
+-- Stored responses of mutating requests, replayed on client retries
+
+-- Idempotency keys table: one row per client key
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key VARCHAR(64) PRIMARY KEY,  -- SHA-256 of the client and its Idempotency-Key
+    fingerprint VARCHAR(64) NOT NULL,  -- SHA-256 of method, path and body
+    status INTEGER,  -- NULL while the request runs, or if it never completed
+    content_type VARCHAR(255),
+    body BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+-- Index for expiry
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created ON idempotency_keys(created_at);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (7, '007_idempotency_keys')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,19 @@
+-- Migration: 008_strategy_tenants
+-- Tenant owning each strategy and job, for deployments serving several users
+
+-- Strategy tenants table: strategies without a row belong to the operator
+CREATE TABLE IF NOT EXISTS strategy_tenants (
+    strategy_id UUID PRIMARY KEY REFERENCES strategies(id) ON DELETE CASCADE,
+    tenant VARCHAR(100) NOT NULL
+);
+
+-- Index for per-tenant queries
+CREATE INDEX IF NOT EXISTS idx_strategy_tenants_tenant ON strategy_tenants(tenant);
+
+-- Tenant submitting each job: jobs without one belong to the operator
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS tenant VARCHAR(100);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (8, '008_strategy_tenants')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,30 @@
+-- Migration: 009_request_audit_log
+-- Append-only log of mutating API requests
+
+-- Request audit log table: one row per mutating API call
+CREATE TABLE IF NOT EXISTS request_audit_log (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    subject VARCHAR(255) NOT NULL,  -- API key ID or token subject; empty when unauthenticated
+    role VARCHAR(20),
+    tenant VARCHAR(100),
+    method VARCHAR(10) NOT NULL,
+    path TEXT NOT NULL,
+    payload_hash VARCHAR(64),  -- SHA-256 of the request body; NULL when empty
+    status INTEGER NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Indexes for traceability queries
+CREATE INDEX IF NOT EXISTS idx_request_audit_log_timestamp ON request_audit_log(timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_request_audit_log_subject ON request_audit_log(subject, timestamp DESC);
+
+-- Enforce append-only: updates and deletes are silently discarded
+CREATE OR REPLACE RULE request_audit_log_no_update AS ON UPDATE TO request_audit_log DO INSTEAD NOTHING;
+CREATE OR REPLACE RULE request_audit_log_no_delete AS ON DELETE TO request_audit_log DO INSTEAD NOTHING;
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (9, '009_request_audit_log')
+ON CONFLICT (version) DO NOTHING;
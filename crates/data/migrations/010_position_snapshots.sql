@@ -0,0 +1,36 @@
+-- Migration: 010_position_snapshots
+-- Periodic monitor snapshots for historical portfolio analytics and
+-- post-hoc strategy evaluation, replacing position_history
+
+-- Position snapshots table: one row per position per recording
+CREATE TABLE IF NOT EXISTS position_snapshots (
+    position_address VARCHAR(64) NOT NULL,
+    pool_address VARCHAR(64) NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    tick_lower INTEGER,  -- NULL for points carried over from position_history
+    tick_upper INTEGER,
+    in_range BOOLEAN NOT NULL,
+    entry_value_usd DECIMAL(30, 8),  -- NULL for points carried over from position_history
+    value_usd DECIMAL(30, 8) NOT NULL,
+    fees_usd DECIMAL(30, 8) NOT NULL,
+    il_pct DECIMAL(20, 8) NOT NULL,
+    net_pnl_usd DECIMAL(30, 8) NOT NULL,
+    PRIMARY KEY (position_address, recorded_at)
+);
+
+-- Index for time range queries
+CREATE INDEX IF NOT EXISTS idx_position_snapshots_recorded_at ON position_snapshots(recorded_at);
+
+INSERT INTO position_snapshots (position_address, pool_address, recorded_at, in_range,
+                                value_usd, fees_usd, il_pct, net_pnl_usd)
+SELECT position_address, pool_address, recorded_at, in_range,
+       value_usd, fees_usd, il_pct, net_pnl_usd
+FROM position_history
+ON CONFLICT (position_address, recorded_at) DO NOTHING;
+
+DROP TABLE IF EXISTS position_history;
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (10, '010_position_snapshots')
+ON CONFLICT (version) DO NOTHING;
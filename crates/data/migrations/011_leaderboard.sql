@@ -0,0 +1,29 @@
+-- Migration: 011_leaderboard
+-- Periodically computed rankings of pools and strategies by realized
+-- fee APR and performance against holding
+
+-- Leaderboard entries table: the latest ranking per kind and window
+CREATE TABLE IF NOT EXISTS leaderboard_entries (
+    kind VARCHAR(16) NOT NULL,  -- pool or strategy
+    window_days INTEGER NOT NULL,
+    subject VARCHAR(64) NOT NULL,  -- pool address or strategy ID
+    name VARCHAR(255) NOT NULL,
+    pool_address VARCHAR(64) NOT NULL,
+    rank INTEGER NOT NULL,
+    positions INTEGER NOT NULL,
+    avg_value_usd DECIMAL(30, 8) NOT NULL,
+    fees_usd DECIMAL(30, 8) NOT NULL,
+    fee_apr_pct DECIMAL(20, 8) NOT NULL,
+    vs_hodl_pct DECIMAL(20, 8) NOT NULL,
+    time_in_range_pct DECIMAL(20, 8) NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (kind, window_days, subject)
+);
+
+-- Index for ranked reads
+CREATE INDEX IF NOT EXISTS idx_leaderboard_entries_rank ON leaderboard_entries(kind, window_days, rank);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (11, '011_leaderboard')
+ON CONFLICT (version) DO NOTHING;
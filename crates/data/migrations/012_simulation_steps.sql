@@ -0,0 +1,23 @@
+-- Migration: 012_simulation_steps
+-- Per-step equity curves of simulations, written in batches while they run
+
+-- Simulation steps table: one row per simulated step
+CREATE TABLE IF NOT EXISTS simulation_steps (
+    simulation_id UUID NOT NULL REFERENCES simulations(id) ON DELETE CASCADE,
+    step BIGINT NOT NULL,
+    price DECIMAL(20, 8) NOT NULL,
+    lower_price DECIMAL(20, 8) NOT NULL,
+    upper_price DECIMAL(20, 8) NOT NULL,
+    in_range BOOLEAN NOT NULL,
+    position_value DECIMAL(20, 8) NOT NULL,
+    cumulative_fees DECIMAL(20, 8) NOT NULL,
+    il_pct DECIMAL(10, 6) NOT NULL,
+    net_pnl DECIMAL(20, 8) NOT NULL,
+    rebalanced BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (simulation_id, step)
+);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name) 
+VALUES (12, '012_simulation_steps')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,72 @@
+-- Migration: 013_position_activity
+-- Rebalances and fee collections of live positions as queryable rows,
+-- alongside the JSON lifecycle events they are recorded from
+
+-- Position rebalances table: one row per executed rebalance
+CREATE TABLE IF NOT EXISTS position_rebalances (
+    id UUID PRIMARY KEY,
+    position_address VARCHAR(64) NOT NULL,
+    pool_address VARCHAR(64) NOT NULL,
+    old_tick_lower INTEGER NOT NULL,
+    old_tick_upper INTEGER NOT NULL,
+    new_tick_lower INTEGER NOT NULL,
+    new_tick_upper INTEGER NOT NULL,
+    tx_cost_lamports BIGINT NOT NULL DEFAULT 0,
+    il_pct DECIMAL(20, 8) NOT NULL,
+    reason VARCHAR(32) NOT NULL,  -- 'RangeExit', 'ILThreshold', 'Periodic', 'Manual', 'Optimization'
+    tx_signature VARCHAR(128),
+    timestamp TIMESTAMPTZ NOT NULL
+);
+
+-- Indexes for per-position and time range queries
+CREATE INDEX IF NOT EXISTS idx_position_rebalances_position ON position_rebalances(position_address, timestamp);
+CREATE INDEX IF NOT EXISTS idx_position_rebalances_timestamp ON position_rebalances(timestamp);
+
+-- Fee collections table: one row per fee claim
+CREATE TABLE IF NOT EXISTS fee_collections (
+    id UUID PRIMARY KEY,
+    position_address VARCHAR(64) NOT NULL,
+    pool_address VARCHAR(64) NOT NULL,
+    fees_a BIGINT NOT NULL,
+    fees_b BIGINT NOT NULL,
+    fees_usd DECIMAL(30, 8) NOT NULL,
+    tx_signature VARCHAR(128),
+    timestamp TIMESTAMPTZ NOT NULL
+);
+
+-- Indexes for per-position and time range queries
+CREATE INDEX IF NOT EXISTS idx_fee_collections_position ON fee_collections(position_address, timestamp);
+CREATE INDEX IF NOT EXISTS idx_fee_collections_timestamp ON fee_collections(timestamp);
+
+-- Carry over the rebalances and fee collections already recorded as events
+INSERT INTO position_rebalances (id, position_address, pool_address, old_tick_lower,
+                                 old_tick_upper, new_tick_lower, new_tick_upper,
+                                 tx_cost_lamports, il_pct, reason, timestamp)
+SELECT id, position_address, pool_address,
+       (event->'data'->'Rebalance'->>'old_tick_lower')::INTEGER,
+       (event->'data'->'Rebalance'->>'old_tick_upper')::INTEGER,
+       (event->'data'->'Rebalance'->>'new_tick_lower')::INTEGER,
+       (event->'data'->'Rebalance'->>'new_tick_upper')::INTEGER,
+       (event->'data'->'Rebalance'->>'tx_cost_lamports')::BIGINT,
+       (event->'data'->'Rebalance'->>'il_at_rebalance')::DECIMAL,
+       event->'data'->'Rebalance'->>'reason',
+       timestamp
+FROM lifecycle_events
+WHERE event_type = 'Rebalanced' AND event->'data' ? 'Rebalance'
+ON CONFLICT (id) DO NOTHING;
+
+INSERT INTO fee_collections (id, position_address, pool_address, fees_a, fees_b, fees_usd,
+                             timestamp)
+SELECT id, position_address, pool_address,
+       (event->'data'->'FeesCollected'->>'fees_a')::BIGINT,
+       (event->'data'->'FeesCollected'->>'fees_b')::BIGINT,
+       (event->'data'->'FeesCollected'->>'fees_usd')::DECIMAL,
+       timestamp
+FROM lifecycle_events
+WHERE event_type = 'FeesCollected' AND event->'data' ? 'FeesCollected'
+ON CONFLICT (id) DO NOTHING;
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (13, '013_position_activity')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,13 @@
+-- Migration: 014_strategy_wallets
+-- Signing wallet bound to each strategy, restored with the strategy on startup
+
+-- Strategy wallets table: strategies without a row have no bound wallet
+CREATE TABLE IF NOT EXISTS strategy_wallets (
+    strategy_id UUID PRIMARY KEY REFERENCES strategies(id) ON DELETE CASCADE,
+    wallet_label VARCHAR(100) NOT NULL
+);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (14, '014_strategy_wallets')
+ON CONFLICT (version) DO NOTHING;
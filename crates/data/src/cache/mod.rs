@@ -5,8 +5,10 @@
 
 mod memory;
 mod persistent;
+mod results;
 mod types;
 
 pub use memory::MemoryCache;
 pub use persistent::FileCache;
+pub use results::{ResultCache, ResultKey, content_hash};
 pub use types::{Cache, CacheEntry, CacheKeyBuilder, CachedProvider};
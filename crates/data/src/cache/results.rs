@@ -0,0 +1,303 @@
+//! Content-addressed cache of backtest and optimization results.
+//!
+//! Results are keyed by hashes of the market data and configuration they
+//! were computed from, plus the random seed, so a repeated run over the
+//! same inputs is answered from the cache instead of being recomputed.
+
+use super::{Cache, CacheKeyBuilder, FileCache, MemoryCache};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Default lifetime of cached results (30 days).
+const DEFAULT_TTL: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Hashes a value's JSON form independently of object key order.
+///
+/// # Errors
+/// Returns an error if the value cannot be serialized.
+pub fn content_hash<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<String> {
+    let value = serde_json::to_value(value)?;
+    Ok(hex::encode(Sha256::digest(
+        canonical(&value).to_string().as_bytes(),
+    )))
+}
+
+/// Rebuilds a value with object keys in sorted order.
+fn canonical(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), canonical(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(canonical).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// Inputs a cached result was computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultKey {
+    /// Kind of result, e.g. `backtest` or `optimization`.
+    pub kind: String,
+    /// Hash of the market data.
+    pub data_hash: String,
+    /// Hash of the run configuration.
+    pub config_hash: String,
+    /// Seed of the random components, if any.
+    pub seed: Option<u64>,
+}
+
+impl ResultKey {
+    /// Creates a key from precomputed hashes.
+    #[must_use]
+    pub fn new(
+        kind: impl Into<String>,
+        data_hash: impl Into<String>,
+        config_hash: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind: kind.into(),
+            data_hash: data_hash.into(),
+            config_hash: config_hash.into(),
+            seed: None,
+        }
+    }
+
+    /// Creates a key by hashing the market data and configuration.
+    ///
+    /// # Errors
+    /// Returns an error if either cannot be serialized.
+    pub fn for_inputs<D, C>(kind: impl Into<String>, data: &D, config: &C) -> anyhow::Result<Self>
+    where
+        D: Serialize + ?Sized,
+        C: Serialize + ?Sized,
+    {
+        Ok(Self::new(kind, content_hash(data)?, content_hash(config)?))
+    }
+
+    /// Sets the random seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Builds the cache key.
+    ///
+    /// The key holds only filename-safe characters so file cache entries
+    /// are found again after a restart, and includes the crate version so
+    /// results are recomputed after an upgrade.
+    #[must_use]
+    pub fn cache_key(&self) -> String {
+        let inputs = CacheKeyBuilder::new()
+            .with(self.kind.as_str())
+            .with(env!("CARGO_PKG_VERSION"))
+            .with(self.data_hash.as_str())
+            .with(self.config_hash.as_str())
+            .with(
+                self.seed
+                    .map_or_else(|| "unseeded".to_string(), |seed| seed.to_string()),
+            )
+            .build();
+        format!("result-{}", hex::encode(Sha256::digest(inputs.as_bytes())))
+    }
+}
+
+/// Cache of computed results keyed by their inputs.
+#[derive(Clone)]
+pub struct ResultCache {
+    /// Underlying cache.
+    cache: Arc<dyn Cache>,
+    /// Lifetime of cached results.
+    ttl: Duration,
+}
+
+impl ResultCache {
+    /// Creates a result cache on top of any cache.
+    #[must_use]
+    pub fn new(cache: Arc<dyn Cache>) -> Self {
+        Self {
+            cache,
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// Creates an in-memory result cache holding up to `max_entries` results.
+    #[must_use]
+    pub fn in_memory(max_entries: usize) -> Self {
+        Self::new(Arc::new(MemoryCache::with_max_entries(max_entries)))
+    }
+
+    /// Creates a result cache persisted to a directory, one file per result.
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be created.
+    pub fn on_disk(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        Ok(Self::new(Arc::new(FileCache::multi_file(dir.into())?)))
+    }
+
+    /// Sets the lifetime of cached results.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Gets a cached result.
+    ///
+    /// Entries that no longer deserialize as `T` are treated as missing.
+    #[must_use]
+    pub fn get<T: DeserializeOwned>(&self, key: &ResultKey) -> Option<T> {
+        let data = self.cache.get(&key.cache_key())?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Stores a result.
+    pub fn put<T: Serialize>(&self, key: &ResultKey, result: &T) {
+        if let Ok(data) = serde_json::to_vec(result) {
+            self.cache.set(&key.cache_key(), data, self.ttl);
+        }
+    }
+
+    /// Gets a cached result or computes and stores it.
+    ///
+    /// # Errors
+    /// Returns the error of `compute`; failed runs are not cached.
+    pub fn get_or_compute<T, F>(&self, key: &ResultKey, compute: F) -> anyhow::Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> anyhow::Result<T>,
+    {
+        if let Some(result) = self.get(key) {
+            debug!(kind = %key.kind, "Result cache hit");
+            return Ok(result);
+        }
+
+        let result = compute()?;
+        self.put(key, &result);
+        Ok(result)
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
+    /// Returns the number of cached results.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns true if no results are cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_content_hash_ignores_key_order() {
+        let a = json!({ "lower": 80, "upper": 120, "nested": { "a": 1, "b": 2 } });
+        let b = json!({ "nested": { "b": 2, "a": 1 }, "upper": 120, "lower": 80 });
+        let c = json!({ "lower": 80, "upper": 121, "nested": { "a": 1, "b": 2 } });
+
+        assert_eq!(content_hash(&a).unwrap(), content_hash(&b).unwrap());
+        assert_ne!(content_hash(&a).unwrap(), content_hash(&c).unwrap());
+        assert_eq!(content_hash(&a).unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_result_key_depends_on_all_inputs() {
+        let prices = [100.0, 101.5, 99.0];
+        let key = ResultKey::for_inputs("backtest", &prices[..], &json!({ "days": 30 })).unwrap();
+
+        let other_data =
+            ResultKey::for_inputs("backtest", &prices[..2], &json!({ "days": 30 })).unwrap();
+        let other_config =
+            ResultKey::for_inputs("backtest", &prices[..], &json!({ "days": 31 })).unwrap();
+        let other_kind =
+            ResultKey::for_inputs("optimization", &prices[..], &json!({ "days": 30 })).unwrap();
+        let seeded = key.clone().with_seed(7);
+
+        let keys = [
+            key.cache_key(),
+            other_data.cache_key(),
+            other_config.cache_key(),
+            other_kind.cache_key(),
+            seeded.cache_key(),
+            seeded.clone().with_seed(8).cache_key(),
+        ];
+        for (i, a) in keys.iter().enumerate() {
+            for b in &keys[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert_eq!(seeded.cache_key(), key.with_seed(7).cache_key());
+        assert!(
+            keys[0]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        );
+    }
+
+    #[test]
+    fn test_get_or_compute_reuses_results() {
+        let cache = ResultCache::in_memory(10);
+        let key = ResultKey::new("backtest", "data", "config");
+        let mut runs = 0;
+
+        for _ in 0..3 {
+            let result: Vec<u32> = cache
+                .get_or_compute(&key, || {
+                    runs += 1;
+                    Ok(vec![1, 2, 3])
+                })
+                .unwrap();
+            assert_eq!(result, vec![1, 2, 3]);
+        }
+        assert_eq!(runs, 1);
+        assert_eq!(cache.len(), 1);
+
+        // Failed runs are not cached
+        let failing = ResultKey::new("backtest", "data", "other");
+        assert!(
+            cache
+                .get_or_compute::<u32, _>(&failing, || anyhow::bail!("no data"))
+                .is_err()
+        );
+        assert!(cache.get::<u32>(&failing).is_none());
+    }
+
+    #[test]
+    fn test_disk_cache_survives_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = ResultKey::new("optimization", "data", "config").with_seed(42);
+
+        ResultCache::on_disk(dir.path())
+            .unwrap()
+            .put(&key, &json!({ "score": 1.5 }));
+
+        let reopened = ResultCache::on_disk(dir.path()).unwrap();
+        let result: serde_json::Value = reopened.get(&key).unwrap();
+        assert_eq!(result["score"], 1.5);
+    }
+}
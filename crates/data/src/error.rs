@@ -0,0 +1,129 @@
+//! Error types for market data providers.
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+/// Result type returned by market data providers.
+pub type DataResult<T> = Result<T, DataError>;
+
+/// Errors raised while fetching or loading market data.
+#[derive(Debug, Error)]
+pub enum DataError {
+    /// The provider rejected the request because of its rate limit.
+    #[error("{provider} rate limit exceeded")]
+    RateLimited {
+        /// Provider name.
+        provider: &'static str,
+        /// Seconds to wait before retrying, when the provider sent one.
+        retry_after_secs: Option<u64>,
+    },
+    /// The requested token, price or file does not exist.
+    #[error("Not found: {0}")]
+    NotFound(String),
+    /// The provider rejected the API key.
+    #[error("{provider} rejected the API key")]
+    Unauthorized {
+        /// Provider name.
+        provider: &'static str,
+    },
+    /// The provider answered with an unexpected status or payload.
+    #[error("{provider} API error: {status} - {message}")]
+    Provider {
+        /// Provider name.
+        provider: &'static str,
+        /// HTTP status code.
+        status: u16,
+        /// Response body or error description.
+        message: String,
+    },
+    /// The HTTP request failed before a response was received.
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// A local file could not be read.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data was received but could not be interpreted.
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+}
+
+impl DataError {
+    /// Classifies a non-success HTTP response from `provider`.
+    pub fn from_status(
+        provider: &'static str,
+        status: StatusCode,
+        retry_after_secs: Option<u64>,
+        body: String,
+    ) -> Self {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                provider,
+                retry_after_secs,
+            },
+            StatusCode::NOT_FOUND => Self::NotFound(body),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Unauthorized { provider },
+            _ => Self::Provider {
+                provider,
+                status: status.as_u16(),
+                message: body,
+            },
+        }
+    }
+
+    /// Returns true for failures that are worth retrying later.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::Http(_) => true,
+            Self::Provider { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+/// Reads the `Retry-After` header of a response, in seconds.
+pub(crate) fn retry_after(response: &reqwest::Response) -> Option<u64> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status_classifies_responses() {
+        let error = DataError::from_status(
+            "Birdeye",
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(30),
+            String::new(),
+        );
+        assert!(matches!(
+            error,
+            DataError::RateLimited {
+                retry_after_secs: Some(30),
+                ..
+            }
+        ));
+        assert!(error.is_transient());
+
+        let error = DataError::from_status("Jupiter", StatusCode::NOT_FOUND, None, "x".into());
+        assert!(matches!(error, DataError::NotFound(_)));
+        assert!(!error.is_transient());
+
+        let error = DataError::from_status("Birdeye", StatusCode::FORBIDDEN, None, String::new());
+        assert!(matches!(error, DataError::Unauthorized { .. }));
+
+        let error =
+            DataError::from_status("Birdeye", StatusCode::BAD_GATEWAY, None, "upstream".into());
+        assert_eq!(error.to_string(), "Birdeye API error: 502 - upstream");
+        assert!(error.is_transient());
+    }
+}
@@ -0,0 +1,440 @@
+//! Tabular export to CSV and Parquet.
+//!
+//! Reports and API downloads build an [`ExportTable`] and render it in the
+//! requested [`TableFormat`], so the CLI and the API produce identical
+//! files. Parquet output requires the `parquet` feature.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+/// File format of an exported table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableFormat {
+    /// Comma-separated values.
+    #[default]
+    Csv,
+    /// Apache Parquet.
+    Parquet,
+}
+
+impl TableFormat {
+    /// Returns the file extension.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    /// Returns the MIME type.
+    #[must_use]
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+impl FromStr for TableFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            other => anyhow::bail!("Unknown table format: {}", other),
+        }
+    }
+}
+
+/// A cell of an exported table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValue {
+    /// Missing value.
+    Null,
+    /// Text.
+    Text(String),
+    /// Decimal number.
+    Decimal(Decimal),
+    /// Integer.
+    Integer(i64),
+    /// Boolean.
+    Bool(bool),
+    /// UTC timestamp.
+    Timestamp(DateTime<Utc>),
+}
+
+impl fmt::Display for ExportValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => Ok(()),
+            Self::Text(text) => write!(f, "{}", text),
+            Self::Decimal(value) => write!(f, "{}", value),
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Timestamp(time) => write!(f, "{}", time.to_rfc3339()),
+        }
+    }
+}
+
+impl From<&str> for ExportValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for ExportValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Decimal> for ExportValue {
+    fn from(value: Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
+
+impl From<i64> for ExportValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<i32> for ExportValue {
+    fn from(value: i32) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<u32> for ExportValue {
+    fn from(value: u32) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<u64> for ExportValue {
+    fn from(value: u64) -> Self {
+        i64::try_from(value).map_or_else(|_| Self::Text(value.to_string()), Self::Integer)
+    }
+}
+
+impl From<usize> for ExportValue {
+    fn from(value: usize) -> Self {
+        (value as u64).into()
+    }
+}
+
+impl From<bool> for ExportValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<DateTime<Utc>> for ExportValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::Timestamp(value)
+    }
+}
+
+impl<T: Into<ExportValue>> From<Option<T>> for ExportValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+/// A table of named columns.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportTable {
+    /// Column names.
+    columns: Vec<String>,
+    /// Rows, each with one value per column.
+    rows: Vec<Vec<ExportValue>>,
+}
+
+impl ExportTable {
+    /// Creates an empty table with columns.
+    pub fn new(columns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Creates a two-column `metric,value` table.
+    pub fn metrics(
+        metrics: impl IntoIterator<Item = (impl Into<String>, impl Into<ExportValue>)>,
+    ) -> Self {
+        let mut table = Self::new(["metric", "value"]);
+        for (name, value) in metrics {
+            table.push_row(vec![ExportValue::Text(name.into()), value.into()]);
+        }
+        table
+    }
+
+    /// Appends a row, padding or truncating it to the column count.
+    pub fn push_row(&mut self, mut row: Vec<ExportValue>) {
+        row.resize(self.columns.len(), ExportValue::Null);
+        self.rows.push(row);
+    }
+
+    /// Returns the column names.
+    #[must_use]
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the rows.
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<ExportValue>] {
+        &self.rows
+    }
+
+    /// Renders the table as CSV with a header row.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        push_csv_line(&mut csv, self.columns.iter().map(String::as_str));
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(ToString::to_string).collect();
+            push_csv_line(&mut csv, cells.iter().map(String::as_str));
+        }
+        csv
+    }
+
+    /// Renders the table in a format.
+    ///
+    /// # Errors
+    /// Returns an error if encoding fails or Parquet support is not
+    /// compiled in.
+    pub fn render(&self, format: TableFormat) -> Result<Vec<u8>> {
+        match format {
+            TableFormat::Csv => Ok(self.to_csv().into_bytes()),
+            TableFormat::Parquet => self.to_parquet(),
+        }
+    }
+
+    /// Encodes the table as Parquet.
+    ///
+    /// Each column's type is taken from its values: decimals become
+    /// doubles, and columns with mixed types are written as text.
+    ///
+    /// # Errors
+    /// Returns an error if encoding fails.
+    #[cfg(feature = "parquet")]
+    pub fn to_parquet(&self) -> Result<Vec<u8>> {
+        parquet_writer::encode(self)
+    }
+
+    /// Parquet support is not compiled in.
+    ///
+    /// # Errors
+    /// Always returns an error.
+    #[cfg(not(feature = "parquet"))]
+    pub fn to_parquet(&self) -> Result<Vec<u8>> {
+        anyhow::bail!("Parquet export requires the `parquet` feature")
+    }
+}
+
+/// Appends a CSV line, quoting fields that need it.
+fn push_csv_line<'a>(csv: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push('\n');
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_writer {
+    use super::{ExportTable, ExportValue};
+    use anyhow::Result;
+    use arrow_array::{
+        ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray,
+        TimestampMillisecondArray,
+    };
+    use arrow_schema::{DataType, Field, Schema, TimeUnit};
+    use parquet::arrow::ArrowWriter;
+    use rust_decimal::prelude::ToPrimitive;
+    use std::sync::Arc;
+
+    /// Column type inferred from its values.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum ColumnKind {
+        Text,
+        Float,
+        Integer,
+        Bool,
+        Timestamp,
+    }
+
+    /// Returns the kind of a value, or `None` for nulls.
+    fn kind(value: &ExportValue) -> Option<ColumnKind> {
+        match value {
+            ExportValue::Null => None,
+            ExportValue::Text(_) => Some(ColumnKind::Text),
+            ExportValue::Decimal(_) => Some(ColumnKind::Float),
+            ExportValue::Integer(_) => Some(ColumnKind::Integer),
+            ExportValue::Bool(_) => Some(ColumnKind::Bool),
+            ExportValue::Timestamp(_) => Some(ColumnKind::Timestamp),
+        }
+    }
+
+    /// Infers a column's kind; mixed columns are text.
+    fn column_kind(table: &ExportTable, column: usize) -> ColumnKind {
+        let mut kinds = table.rows().iter().filter_map(|row| kind(&row[column]));
+        let Some(first) = kinds.next() else {
+            return ColumnKind::Text;
+        };
+        if kinds.all(|k| k == first) {
+            first
+        } else {
+            ColumnKind::Text
+        }
+    }
+
+    /// Builds the Arrow array of a column.
+    fn column_array(table: &ExportTable, column: usize, kind: ColumnKind) -> ArrayRef {
+        let values = table.rows().iter().map(|row| &row[column]);
+        match kind {
+            ColumnKind::Text => Arc::new(StringArray::from(
+                values
+                    .map(|v| (*v != ExportValue::Null).then(|| v.to_string()))
+                    .collect::<Vec<_>>(),
+            )),
+            ColumnKind::Float => Arc::new(Float64Array::from(
+                values
+                    .map(|v| match v {
+                        ExportValue::Decimal(d) => d.to_f64(),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            ColumnKind::Integer => Arc::new(Int64Array::from(
+                values
+                    .map(|v| match v {
+                        ExportValue::Integer(i) => Some(*i),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            ColumnKind::Bool => Arc::new(BooleanArray::from(
+                values
+                    .map(|v| match v {
+                        ExportValue::Bool(b) => Some(*b),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            ColumnKind::Timestamp => Arc::new(
+                TimestampMillisecondArray::from(
+                    values
+                        .map(|v| match v {
+                            ExportValue::Timestamp(t) => Some(t.timestamp_millis()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .with_timezone("UTC"),
+            ),
+        }
+    }
+
+    /// Returns the Arrow type of a column kind.
+    fn data_type(kind: ColumnKind) -> DataType {
+        match kind {
+            ColumnKind::Text => DataType::Utf8,
+            ColumnKind::Float => DataType::Float64,
+            ColumnKind::Integer => DataType::Int64,
+            ColumnKind::Bool => DataType::Boolean,
+            ColumnKind::Timestamp => DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+        }
+    }
+
+    /// Encodes a table as a single-row-group Parquet file.
+    pub(super) fn encode(table: &ExportTable) -> Result<Vec<u8>> {
+        let kinds: Vec<ColumnKind> = (0..table.columns().len())
+            .map(|column| column_kind(table, column))
+            .collect();
+        let schema = Arc::new(Schema::new(
+            table
+                .columns()
+                .iter()
+                .zip(&kinds)
+                .map(|(name, kind)| Field::new(name, data_type(*kind), true))
+                .collect::<Vec<_>>(),
+        ));
+        let arrays: Vec<ArrayRef> = kinds
+            .iter()
+            .enumerate()
+            .map(|(column, kind)| column_array(table, column, *kind))
+            .collect();
+        let batch = RecordBatch::try_new(schema.clone(), arrays)?;
+
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, schema, None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        let mut table = ExportTable::new(["name", "value"]);
+        table.push_row(vec!["SOL/USDC".into(), dec!(1.5).into()]);
+        table.push_row(vec!["a,\"b\"".into()]);
+        assert_eq!(
+            table.to_csv(),
+            "name,value\nSOL/USDC,1.5\n\"a,\"\"b\"\"\",\n"
+        );
+    }
+
+    #[test]
+    fn test_metrics_table() {
+        let table = ExportTable::metrics([("steps", ExportValue::from(10u64))]);
+        assert_eq!(table.columns(), ["metric", "value"]);
+        assert_eq!(table.to_csv(), "metric,value\nsteps,10\n");
+        assert_eq!(
+            "PARQUET".parse::<TableFormat>().unwrap(),
+            TableFormat::Parquet
+        );
+        assert!("xlsx".parse::<TableFormat>().is_err());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_roundtrip() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let mut table = ExportTable::new(["address", "value_usd", "in_range"]);
+        table.push_row(vec!["pos1".into(), dec!(100.5).into(), true.into()]);
+        table.push_row(vec!["pos2".into(), ExportValue::Null, false.into()]);
+
+        let bytes = table.render(TableFormat::Parquet).unwrap();
+        let mut file = tempfile::tempfile().unwrap();
+        std::io::Write::write_all(&mut file, &bytes).unwrap();
+        let reader = SerializedFileReader::new(file).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert_eq!(metadata.file_metadata().schema_descr().num_columns(), 3);
+    }
+}
@@ -5,21 +5,26 @@ pub mod prelude;
 
 /// Caching layer for market data.
 pub mod cache;
+/// Error types for market data providers.
+pub mod error;
+/// Tabular export to CSV and Parquet.
+pub mod export;
 /// Historical pool state structures.
 pub mod pool_state;
 /// Data providers.
 pub mod providers;
 /// Database repositories.
+#[cfg(feature = "postgres")]
 pub mod repositories;
 /// In-memory data repository for simulation.
 pub mod repository;
 /// Time series data structures.
 pub mod timeseries;
 
-use anyhow::Result;
 use async_trait::async_trait;
 use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
+use error::DataResult;
 
 /// Trait for providing market data.
 #[async_trait]
@@ -32,5 +37,12 @@ pub trait MarketDataProvider {
         start_time: u64,
         end_time: u64,
         resolution: u64, // seconds
-    ) -> Result<Vec<PriceCandle>>;
+    ) -> DataResult<Vec<PriceCandle>>;
+
+    /// Checks that the provider's backend is reachable.
+    ///
+    /// Providers without a remote backend are always reachable.
+    async fn health_check(&self) -> DataResult<()> {
+        Ok(())
+    }
 }
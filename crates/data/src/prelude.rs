@@ -13,9 +13,16 @@ pub use crate::MarketDataProvider;
 
 // Cache
 pub use crate::cache::{
-    Cache, CacheEntry, CacheKeyBuilder, CachedProvider, FileCache, MemoryCache,
+    Cache, CacheEntry, CacheKeyBuilder, CachedProvider, FileCache, MemoryCache, ResultCache,
+    ResultKey, content_hash,
 };
 
+// Errors
+pub use crate::error::{DataError, DataResult};
+
+// Export
+pub use crate::export::{ExportTable, ExportValue, TableFormat};
+
 // Pool state
 pub use crate::pool_state::{PoolStateHistory, PoolStateSnapshot};
 
@@ -24,9 +31,16 @@ pub use crate::providers::csv_provider::write_candles_to_csv;
 pub use crate::providers::{BirdeyeProvider, CsvProvider, JupiterProvider, MockMarketDataProvider};
 
 // Database repositories
+#[cfg(feature = "postgres")]
 pub use crate::repositories::{
-    Database, OptimizationRecord, PoolRecord, PoolRepository, PriceRecord, PriceRepository,
-    SimulationRecord, SimulationRepository, SimulationResultRecord,
+    AuditFilter, AuditRecord, AuditRepository, Database, FeeCollectionRecord, IdempotencyRecord,
+    IdempotencyRepository, JobRecord, JobRepository, LeaderboardRecord, LeaderboardRepository,
+    LifecycleEventRecord, MAX_STRATEGY_NAME_LEN, MaintenanceRepository, OptimizationRecord,
+    PoolRecord, PoolRepository, PoolStatsRecord, PositionRepository, PositionSnapshotRecord,
+    PositionSnapshotRepository, PositionStateRecord, PositionStateRepository, PriceBar,
+    PriceRecord, PriceRepository, RebalanceRecord, RequestAuditFilter, RequestAuditRecord,
+    RequestAuditRepository, SimulationRecord, SimulationRepository, SimulationResultRecord,
+    SimulationStepRecord, SimulationStepWriter, StrategyRecord, StrategyRepository, TableStats,
 };
 
 // In-memory repository
@@ -1,7 +1,7 @@
 //! Birdeye API provider for market data.
 
 use crate::MarketDataProvider;
-use anyhow::Result;
+use crate::error::{DataError, DataResult, retry_after};
 use async_trait::async_trait;
 use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
@@ -11,6 +11,9 @@ use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use serde::Deserialize;
 
+/// Provider name used in errors.
+const PROVIDER: &str = "Birdeye";
+
 #[derive(Deserialize, Debug)]
 struct BirdeyeOhlcvResponse {
     data: BirdeyeData,
@@ -76,7 +79,7 @@ impl MarketDataProvider for BirdeyeProvider {
         start_time: u64,
         end_time: u64,
         resolution: u64,
-    ) -> Result<Vec<PriceCandle>> {
+    ) -> DataResult<Vec<PriceCandle>> {
         let is_token_b_usd = token_b.symbol.to_uppercase().contains("USD");
 
         if !is_token_b_usd {
@@ -102,14 +105,17 @@ impl MarketDataProvider for BirdeyeProvider {
 
         if !resp.status().is_success() {
             let status = resp.status();
+            let retry_after = retry_after(&resp);
             let text = resp.text().await?;
-            return Err(anyhow::anyhow!("Birdeye API error: {} - {}", status, text));
+            return Err(DataError::from_status(PROVIDER, status, retry_after, text));
         }
 
         let data: BirdeyeOhlcvResponse = resp.json().await?;
 
         if !data.success {
-            return Err(anyhow::anyhow!("Birdeye API returned success=false"));
+            return Err(DataError::InvalidData(
+                "Birdeye API returned success=false".to_string(),
+            ));
         }
 
         let candles = data
@@ -147,4 +153,27 @@ impl MarketDataProvider for BirdeyeProvider {
 
         Ok(candles)
     }
+
+    /// Checks reachability and the API key against the networks endpoint.
+    async fn health_check(&self) -> DataResult<()> {
+        let resp = self
+            .client
+            .get("https://public-api.birdeye.so/defi/networks")
+            .header("X-API-KEY", &self.api_key)
+            .header("accept", "application/json")
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let retry_after = retry_after(&resp);
+            return Err(DataError::from_status(
+                PROVIDER,
+                status,
+                retry_after,
+                String::new(),
+            ));
+        }
+        Ok(())
+    }
 }
@@ -4,6 +4,7 @@
 //! and backtesting without requiring API access.
 
 use crate::MarketDataProvider;
+use crate::error::{DataError, DataResult};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use clmm_lp_domain::entities::price_candle::PriceCandle;
@@ -87,17 +88,21 @@ impl MarketDataProvider for CsvProvider {
         start_time: u64,
         end_time: u64,
         resolution: u64,
-    ) -> Result<Vec<PriceCandle>> {
+    ) -> DataResult<Vec<PriceCandle>> {
         let filepath = self.get_filename(token_a, token_b);
 
-        let file = File::open(&filepath)
-            .with_context(|| format!("Failed to open CSV file: {}", filepath.display()))?;
+        let file = File::open(&filepath).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                DataError::NotFound(format!("CSV file {}", filepath.display()))
+            }
+            _ => DataError::Io(e),
+        })?;
 
         let reader = BufReader::new(file);
         let mut candles = Vec::new();
 
         for (line_num, line_result) in reader.lines().enumerate() {
-            let line = line_result.with_context(|| format!("Failed to read line {}", line_num))?;
+            let line = line_result?;
 
             // Skip header line
             if line_num == 0 && line.to_lowercase().contains("timestamp") {
@@ -270,6 +275,25 @@ mod tests {
         assert_eq!(candles.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_csv_provider_reports_missing_file_as_not_found() {
+        let dir = tempdir().unwrap();
+        let provider = CsvProvider::new(dir.path().to_path_buf());
+
+        let err = provider
+            .get_price_history(
+                &create_test_token("SOL"),
+                &create_test_token("USDC"),
+                0,
+                u64::MAX,
+                3600,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DataError::NotFound(_)));
+    }
+
     #[test]
     fn test_write_candles_to_csv() {
         let dir = tempdir().unwrap();
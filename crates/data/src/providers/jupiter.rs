@@ -4,7 +4,7 @@
 //! for fetching token prices on Solana.
 
 use crate::MarketDataProvider;
-use anyhow::{Result, anyhow};
+use crate::error::{DataError, DataResult, retry_after};
 use async_trait::async_trait;
 use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
@@ -19,6 +19,9 @@ use std::collections::HashMap;
 /// Base URL for Jupiter Price API v2.
 const JUPITER_PRICE_API_V2: &str = "https://api.jup.ag/price/v2";
 
+/// Provider name used in errors.
+const PROVIDER: &str = "Jupiter";
+
 /// Response from Jupiter Price API.
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
@@ -112,12 +115,12 @@ impl JupiterProvider {
     ///
     /// # Returns
     /// The price in USD as a Decimal
-    pub async fn get_price(&self, mint_address: &str) -> Result<Decimal> {
+    pub async fn get_price(&self, mint_address: &str) -> DataResult<Decimal> {
         let prices = self.get_prices(&[mint_address.to_string()]).await?;
         prices
             .get(mint_address)
             .copied()
-            .ok_or_else(|| anyhow!("Price not found for {}", mint_address))
+            .ok_or_else(|| DataError::NotFound(format!("Price for {}", mint_address)))
     }
 
     /// Fetches current prices for multiple tokens.
@@ -127,7 +130,10 @@ impl JupiterProvider {
     ///
     /// # Returns
     /// HashMap of mint address to price in USD
-    pub async fn get_prices(&self, mint_addresses: &[String]) -> Result<HashMap<String, Decimal>> {
+    pub async fn get_prices(
+        &self,
+        mint_addresses: &[String],
+    ) -> DataResult<HashMap<String, Decimal>> {
         if mint_addresses.is_empty() {
             return Ok(HashMap::new());
         }
@@ -144,11 +150,10 @@ impl JupiterProvider {
         let response = request.send().await?;
 
         if !response.status().is_success() {
-            return Err(anyhow!(
-                "Jupiter API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            ));
+            let status = response.status();
+            let retry_after = retry_after(&response);
+            let body = response.text().await.unwrap_or_default();
+            return Err(DataError::from_status(PROVIDER, status, retry_after, body));
         }
 
         let data: JupiterPriceResponse = response.json().await?;
@@ -173,21 +178,23 @@ impl JupiterProvider {
     ///
     /// # Returns
     /// Price of base token in terms of quote token
-    pub async fn get_price_ratio(&self, base_mint: &str, quote_mint: &str) -> Result<Decimal> {
+    pub async fn get_price_ratio(&self, base_mint: &str, quote_mint: &str) -> DataResult<Decimal> {
         let prices = self
             .get_prices(&[base_mint.to_string(), quote_mint.to_string()])
             .await?;
 
         let base_price = prices
             .get(base_mint)
-            .ok_or_else(|| anyhow!("Price not found for base token {}", base_mint))?;
+            .ok_or_else(|| DataError::NotFound(format!("Price for base token {}", base_mint)))?;
 
         let quote_price = prices
             .get(quote_mint)
-            .ok_or_else(|| anyhow!("Price not found for quote token {}", quote_mint))?;
+            .ok_or_else(|| DataError::NotFound(format!("Price for quote token {}", quote_mint)))?;
 
         if quote_price.is_zero() {
-            return Err(anyhow!("Quote token price is zero"));
+            return Err(DataError::InvalidData(
+                "Quote token price is zero".to_string(),
+            ));
         }
 
         Ok(*base_price / *quote_price)
@@ -212,7 +219,7 @@ impl JupiterProvider {
         start_time: u64,
         end_time: u64,
         resolution: u64,
-    ) -> Result<Vec<PriceCandle>> {
+    ) -> DataResult<Vec<PriceCandle>> {
         let current_price = self.get_price(mint_address).await?;
 
         let mut candles = Vec::new();
@@ -258,7 +265,7 @@ impl MarketDataProvider for JupiterProvider {
         start_time: u64,
         end_time: u64,
         resolution: u64,
-    ) -> Result<Vec<PriceCandle>> {
+    ) -> DataResult<Vec<PriceCandle>> {
         // Get current prices for both tokens
         let prices = self
             .get_prices(&[token_a.mint_address.clone(), token_b.mint_address.clone()])
@@ -266,11 +273,11 @@ impl MarketDataProvider for JupiterProvider {
 
         let price_a = prices
             .get(&token_a.mint_address)
-            .ok_or_else(|| anyhow!("Price not found for {}", token_a.symbol))?;
+            .ok_or_else(|| DataError::NotFound(format!("Price for {}", token_a.symbol)))?;
 
         let price_b = prices
             .get(&token_b.mint_address)
-            .ok_or_else(|| anyhow!("Price not found for {}", token_b.symbol))?;
+            .ok_or_else(|| DataError::NotFound(format!("Price for {}", token_b.symbol)))?;
 
         // Calculate price ratio (A in terms of B)
         let price_ratio = if price_b.is_zero() {
@@ -301,6 +308,11 @@ impl MarketDataProvider for JupiterProvider {
 
         Ok(candles)
     }
+
+    /// Checks reachability by fetching the SOL price.
+    async fn health_check(&self) -> DataResult<()> {
+        self.get_price(known_mints::SOL).await.map(|_| ())
+    }
 }
 
 /// Well-known Solana token mint addresses.
@@ -1,7 +1,7 @@
 //! Mock market data provider for testing.
 
 use crate::MarketDataProvider;
-use anyhow::Result;
+use crate::error::DataResult;
 use async_trait::async_trait;
 use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
@@ -21,7 +21,7 @@ impl MarketDataProvider for MockMarketDataProvider {
         start_time: u64,
         _end_time: u64,
         resolution: u64,
-    ) -> Result<Vec<PriceCandle>> {
+    ) -> DataResult<Vec<PriceCandle>> {
         Ok(vec![PriceCandle {
             token_a: token_a.clone(),
             token_b: token_b.clone(),
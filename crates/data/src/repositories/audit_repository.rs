@@ -0,0 +1,165 @@
+//! Audit log repository for decisions and transactions.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Database record for an audit log entry.
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    /// Entry ID.
+    pub id: Uuid,
+    /// When the event occurred.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Event kind ('evaluation', 'proposal', 'transaction', 'outcome').
+    pub kind: String,
+    /// Action name.
+    pub action: String,
+    /// Strategy the event belongs to.
+    pub strategy_id: Option<String>,
+    /// Position the event concerns.
+    pub position_address: Option<String>,
+    /// Transaction signature.
+    pub tx_signature: Option<String>,
+    /// Whether the action succeeded.
+    pub success: Option<bool>,
+    /// Error message if the action failed.
+    pub error: Option<String>,
+    /// Event-specific details.
+    pub details: serde_json::Value,
+}
+
+impl AuditRecord {
+    /// Creates an AuditRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            timestamp: row.try_get("timestamp")?,
+            kind: row.try_get("kind")?,
+            action: row.try_get("action")?,
+            strategy_id: row.try_get("strategy_id")?,
+            position_address: row.try_get("position_address")?,
+            tx_signature: row.try_get("tx_signature")?,
+            success: row.try_get("success")?,
+            error: row.try_get("error")?,
+            details: row.try_get("details")?,
+        })
+    }
+}
+
+/// Filter for audit log queries.
+///
+/// Unset fields match every entry.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// Only entries for this strategy.
+    pub strategy_id: Option<String>,
+    /// Only entries for this position.
+    pub position_address: Option<String>,
+    /// Only entries of this kind.
+    pub kind: Option<String>,
+    /// Only entries with this transaction signature.
+    pub tx_signature: Option<String>,
+    /// Only entries at or after this time.
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only entries before this time.
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Repository for the append-only audit log.
+///
+/// Exposes no update or delete operations; the table itself discards
+/// them as well.
+#[derive(Clone)]
+pub struct AuditRepository {
+    pool: Arc<PgPool>,
+}
+
+impl AuditRepository {
+    /// Creates a new AuditRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Appends an entry.
+    ///
+    /// Appending an entry that already exists is a no-op.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn append(&self, record: &AuditRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (id, timestamp, kind, action, strategy_id, position_address,
+                                   tx_signature, success, error, details)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(record.id)
+        .bind(record.timestamp)
+        .bind(&record.kind)
+        .bind(&record.action)
+        .bind(&record.strategy_id)
+        .bind(&record.position_address)
+        .bind(&record.tx_signature)
+        .bind(record.success)
+        .bind(&record.error)
+        .bind(&record.details)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds entries matching a filter, newest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn query(
+        &self,
+        filter: &AuditFilter,
+        limit: i64,
+    ) -> Result<Vec<AuditRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM audit_log
+            WHERE ($1::text IS NULL OR strategy_id = $1)
+              AND ($2::text IS NULL OR position_address = $2)
+              AND ($3::text IS NULL OR kind = $3)
+              AND ($4::text IS NULL OR tx_signature = $4)
+              AND ($5::timestamptz IS NULL OR timestamp >= $5)
+              AND ($6::timestamptz IS NULL OR timestamp < $6)
+            ORDER BY timestamp DESC
+            LIMIT $7
+            "#,
+        )
+        .bind(&filter.strategy_id)
+        .bind(&filter.position_address)
+        .bind(&filter.kind)
+        .bind(&filter.tx_signature)
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(AuditRecord::from_row).collect()
+    }
+
+    /// Finds entries for a transaction signature.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_signature(
+        &self,
+        tx_signature: &str,
+    ) -> Result<Vec<AuditRecord>, sqlx::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM audit_log WHERE tx_signature = $1 ORDER BY timestamp ASC")
+                .bind(tx_signature)
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        rows.iter().map(AuditRecord::from_row).collect()
+    }
+}
@@ -3,10 +3,33 @@
 //! Provides a unified interface for database operations including
 //! connection management, repository access, and schema migrations.
 
-use super::{PoolRepository, PriceRepository, SimulationRepository};
+use super::{
+    AuditRepository, IdempotencyRepository, JobRepository, LeaderboardRepository,
+    MaintenanceRepository, PoolRepository, PositionRepository, PositionSnapshotRepository,
+    PositionStateRepository, PriceRepository, RequestAuditRepository, SimulationRepository,
+    StrategyRepository,
+};
 use sqlx::PgPool;
 use std::sync::Arc;
 
+/// Schema migrations, in the order they are applied.
+const MIGRATIONS: [&str; 14] = [
+    include_str!("../../migrations/001_initial_schema.sql"),
+    include_str!("../../migrations/002_add_positions.sql"),
+    include_str!("../../migrations/003_position_state.sql"),
+    include_str!("../../migrations/004_audit_log.sql"),
+    include_str!("../../migrations/005_jobs.sql"),
+    include_str!("../../migrations/006_position_history.sql"),
+    include_str!("../../migrations/007_idempotency_keys.sql"),
+    include_str!("../../migrations/008_strategy_tenants.sql"),
+    include_str!("../../migrations/009_request_audit_log.sql"),
+    include_str!("../../migrations/010_position_snapshots.sql"),
+    include_str!("../../migrations/011_leaderboard.sql"),
+    include_str!("../../migrations/012_simulation_steps.sql"),
+    include_str!("../../migrations/013_position_activity.sql"),
+    include_str!("../../migrations/014_strategy_wallets.sql"),
+];
+
 /// Database connection wrapper for repositories.
 ///
 /// Manages the PostgreSQL connection pool and provides factory methods
@@ -67,26 +90,267 @@ impl Database {
         PriceRepository::new(self.pool.clone())
     }
 
+    /// Creates a PositionStateRepository instance.
+    #[must_use]
+    pub fn position_states(&self) -> PositionStateRepository {
+        PositionStateRepository::new(self.pool.clone())
+    }
+
+    /// Creates a PositionRepository instance.
+    #[must_use]
+    pub fn positions(&self) -> PositionRepository {
+        PositionRepository::new(self.pool.clone())
+    }
+
+    /// Creates a PositionSnapshotRepository instance.
+    #[must_use]
+    pub fn position_snapshots(&self) -> PositionSnapshotRepository {
+        PositionSnapshotRepository::new(self.pool.clone())
+    }
+
+    /// Creates a LeaderboardRepository instance.
+    #[must_use]
+    pub fn leaderboard(&self) -> LeaderboardRepository {
+        LeaderboardRepository::new(self.pool.clone())
+    }
+
+    /// Creates an AuditRepository instance.
+    #[must_use]
+    pub fn audit(&self) -> AuditRepository {
+        AuditRepository::new(self.pool.clone())
+    }
+
+    /// Creates an IdempotencyRepository instance.
+    #[must_use]
+    pub fn idempotency_keys(&self) -> IdempotencyRepository {
+        IdempotencyRepository::new(self.pool.clone())
+    }
+
+    /// Creates a RequestAuditRepository instance.
+    #[must_use]
+    pub fn request_audit(&self) -> RequestAuditRepository {
+        RequestAuditRepository::new(self.pool.clone())
+    }
+
+    /// Creates a JobRepository instance.
+    #[must_use]
+    pub fn jobs(&self) -> JobRepository {
+        JobRepository::new(self.pool.clone())
+    }
+
+    /// Creates a StrategyRepository instance.
+    #[must_use]
+    pub fn strategies(&self) -> StrategyRepository {
+        StrategyRepository::new(self.pool.clone())
+    }
+
+    /// Creates a MaintenanceRepository instance.
+    #[must_use]
+    pub fn maintenance(&self) -> MaintenanceRepository {
+        MaintenanceRepository::new(self.pool.clone())
+    }
+
+    /// Checks that the database answers a trivial query.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn ping(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1").execute(self.pool.as_ref()).await?;
+        Ok(())
+    }
+
     /// Runs database migrations.
     ///
-    /// Executes the initial schema migration. Splits the migration file
-    /// by semicolons and executes each statement separately to support
-    /// multiple SQL commands.
+    /// Executes every schema migration in order, one statement at a time.
+    /// Statements are split on semicolons once comments are stripped, so a
+    /// statement following a comment, or a comment holding a semicolon, is
+    /// never lost or cut short.
     ///
     /// # Errors
     /// Returns an error if any migration statement fails.
     pub async fn migrate(&self) -> Result<(), sqlx::Error> {
-        let migration_sql = include_str!("../../migrations/001_initial_schema.sql");
-
-        // Split by semicolons and execute each statement separately
-        for statement in migration_sql.split(';') {
-            let trimmed = statement.trim();
-            // Skip empty statements and comments-only blocks
-            if trimmed.is_empty() || trimmed.starts_with("--") && !trimmed.contains("CREATE") {
-                continue;
+        self.run_migrations(&MIGRATIONS).await
+    }
+
+    /// Executes the statements of the given migrations in order.
+    async fn run_migrations(&self, migrations: &[&str]) -> Result<(), sqlx::Error> {
+        for migration_sql in migrations {
+            for statement in split_statements(migration_sql) {
+                sqlx::query(&statement).execute(self.pool.as_ref()).await?;
             }
-            sqlx::query(trimmed).execute(self.pool.as_ref()).await?;
         }
         Ok(())
     }
 }
+
+/// Splits a migration into its statements, dropping `--` comments and
+/// blank statements. Semicolons and dashes inside quoted strings are kept.
+fn split_statements(sql: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = sql.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_string = !in_string;
+                current.push(c);
+            }
+            '-' if !in_string && chars.peek() == Some(&'-') => {
+                // Skip the comment up to the end of its line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        current.push(c);
+                        break;
+                    }
+                }
+            }
+            ';' if !in_string => {
+                statements.push(std::mem::take(&mut current));
+            }
+            _ => current.push(c),
+        }
+    }
+    statements.push(current);
+
+    statements
+        .into_iter()
+        .map(|statement| statement.trim().to_string())
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_statements() {
+        let sql = "-- Leading comment; with a semicolon\n\
+                   CREATE TABLE t (\n\
+                       a TEXT NOT NULL,  -- trailing; comment\n\
+                       b TEXT DEFAULT 'x;--y'\n\
+                   );\n\
+                   \n\
+                   -- Comment before an insert\n\
+                   INSERT INTO t (a) VALUES ('1');\n\
+                   -- Trailing comment only\n";
+        let statements = split_statements(sql);
+
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].starts_with("CREATE TABLE t"));
+        assert!(statements[0].contains("b TEXT DEFAULT 'x;--y'"));
+        assert!(!statements[0].contains("trailing"));
+        assert_eq!(statements[1], "INSERT INTO t (a) VALUES ('1')");
+    }
+
+    #[test]
+    fn test_migrations_keep_every_statement() {
+        // Every migration after the first, which predates the
+        // schema_migrations table, records itself last
+        for migration in &MIGRATIONS[1..] {
+            let statements = split_statements(migration);
+            assert!(
+                statements
+                    .last()
+                    .is_some_and(|s| s.starts_with("INSERT INTO schema_migrations")),
+                "{}",
+                migration.lines().next().unwrap_or_default()
+            );
+            assert!(statements.iter().all(|s| !s.contains("--")));
+        }
+
+        // The position activity backfill follows a comment
+        let statements = split_statements(MIGRATIONS[12]);
+        let backfills = statements
+            .iter()
+            .filter(|s| s.contains("FROM lifecycle_events"))
+            .count();
+        assert_eq!(backfills, 2);
+    }
+
+    /// Runs against the database at `TEST_DATABASE_URL`, in a schema of its
+    /// own; skipped when the variable is not set.
+    #[tokio::test]
+    async fn test_migrate_backfills_position_activity() {
+        let Ok(url) = std::env::var("TEST_DATABASE_URL") else {
+            return;
+        };
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(1)
+            .connect(&url)
+            .await
+            .unwrap();
+        let schema = format!("migrate_{}", uuid::Uuid::new_v4().simple());
+        for statement in [
+            format!("CREATE SCHEMA {}", schema),
+            format!("SET search_path TO {}", schema),
+        ] {
+            sqlx::query(&statement).execute(&pool).await.unwrap();
+        }
+        let db = Database::new(pool);
+
+        // Events recorded before the position activity tables existed
+        db.run_migrations(&MIGRATIONS[..12]).await.unwrap();
+        let events = [
+            (
+                "Rebalanced",
+                r#"{"Rebalance": {"old_tick_lower": -128, "old_tick_upper": 128,
+                    "new_tick_lower": -64, "new_tick_upper": 192, "old_liquidity": 1,
+                    "new_liquidity": 1, "tx_cost_lamports": 15000,
+                    "il_at_rebalance": "0.5", "reason": "RangeExit"}}"#,
+            ),
+            (
+                "Rebalanced",
+                r#"{"Rebalance": {"old_tick_lower": -64, "old_tick_upper": 192,
+                    "new_tick_lower": 0, "new_tick_upper": 256, "old_liquidity": 1,
+                    "new_liquidity": 1, "tx_cost_lamports": 12000,
+                    "il_at_rebalance": "0.25", "reason": "Manual"}}"#,
+            ),
+            (
+                "FeesCollected",
+                r#"{"FeesCollected": {"fees_a": 10, "fees_b": 20, "fees_usd": "1.5"}}"#,
+            ),
+            (
+                "PositionOpened",
+                r#"{"PositionOpened": {"tick_lower": -128, "tick_upper": 128}}"#,
+            ),
+        ];
+        for (event_type, data) in events {
+            sqlx::query(
+                "INSERT INTO lifecycle_events (id, position_address, pool_address, event_type, \
+                 event, timestamp) VALUES ($1, 'position', 'pool', $2, $3::JSONB, NOW())",
+            )
+            .bind(uuid::Uuid::new_v4())
+            .bind(event_type)
+            .bind(format!(r#"{{"data": {}}}"#, data))
+            .execute(db.pool())
+            .await
+            .unwrap();
+        }
+
+        // Migrating again is a no-op for the applied migrations
+        db.migrate().await.unwrap();
+        db.migrate().await.unwrap();
+
+        let count = |table: &str| {
+            let sql = format!("SELECT COUNT(*) FROM {}", table);
+            let pool = db.pool().clone();
+            async move {
+                sqlx::query_scalar::<_, i64>(&sql)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(count("position_rebalances").await, 2);
+        assert_eq!(count("fee_collections").await, 1);
+        assert_eq!(count("schema_migrations").await, 13);
+
+        sqlx::query(&format!("DROP SCHEMA {} CASCADE", schema))
+            .execute(db.pool())
+            .await
+            .unwrap();
+    }
+}
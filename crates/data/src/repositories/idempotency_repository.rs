@@ -0,0 +1,142 @@
+//! Idempotency repository for stored responses of mutating requests.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Database record for a stored response.
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    /// Scoped idempotency key.
+    pub key: String,
+    /// Fingerprint of the original request.
+    pub fingerprint: String,
+    /// HTTP status code; `None` while the request runs.
+    pub status: Option<i32>,
+    /// Response content type.
+    pub content_type: Option<String>,
+    /// Response body.
+    pub body: Vec<u8>,
+    /// When the response was stored.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl IdempotencyRecord {
+    /// Creates an IdempotencyRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            key: row.try_get("key")?,
+            fingerprint: row.try_get("fingerprint")?,
+            status: row.try_get("status")?,
+            content_type: row.try_get("content_type")?,
+            body: row.try_get("body")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Repository for idempotency keys.
+#[derive(Clone)]
+pub struct IdempotencyRepository {
+    pool: Arc<PgPool>,
+}
+
+impl IdempotencyRepository {
+    /// Creates a new IdempotencyRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts a record unless an unexpired one, stored at or after the
+    /// cutoff, exists under the same key.
+    ///
+    /// Returns whether the record was inserted.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn reserve(
+        &self,
+        record: &IdempotencyRecord,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, status, content_type, body, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (key) DO UPDATE SET
+                fingerprint = EXCLUDED.fingerprint,
+                status = EXCLUDED.status,
+                content_type = EXCLUDED.content_type,
+                body = EXCLUDED.body,
+                created_at = EXCLUDED.created_at
+            WHERE idempotency_keys.created_at < $7
+            "#,
+        )
+        .bind(&record.key)
+        .bind(&record.fingerprint)
+        .bind(record.status)
+        .bind(&record.content_type)
+        .bind(&record.body)
+        .bind(record.created_at)
+        .bind(cutoff)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stores a response, replacing any record under the same key.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn upsert(&self, record: &IdempotencyRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, status, content_type, body, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (key) DO UPDATE SET
+                fingerprint = EXCLUDED.fingerprint,
+                status = EXCLUDED.status,
+                content_type = EXCLUDED.content_type,
+                body = EXCLUDED.body,
+                created_at = EXCLUDED.created_at
+            "#,
+        )
+        .bind(&record.key)
+        .bind(&record.fingerprint)
+        .bind(record.status)
+        .bind(&record.content_type)
+        .bind(&record.body)
+        .bind(record.created_at)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds a stored response by key.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find(&self, key: &str) -> Result<Option<IdempotencyRecord>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM idempotency_keys WHERE key = $1")
+            .bind(key)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(IdempotencyRecord::from_row).transpose()
+    }
+
+    /// Deletes responses stored before a time.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn delete_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE created_at < $1")
+            .bind(cutoff)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,132 @@
+//! Job repository for background jobs and their results.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Database record for a background job.
+#[derive(Debug, Clone)]
+pub struct JobRecord {
+    /// Job ID.
+    pub id: Uuid,
+    /// Job kind ('backtest', 'optimization').
+    pub kind: String,
+    /// Status ('queued', 'running', 'completed', 'failed', 'cancelled').
+    pub status: String,
+    /// Progress percentage.
+    pub progress_pct: i16,
+    /// Job input.
+    pub params: serde_json::Value,
+    /// Result of a completed job.
+    pub result: Option<serde_json::Value>,
+    /// Error of a failed job.
+    pub error: Option<String>,
+    /// Tenant that submitted the job, if any.
+    pub tenant: Option<String>,
+    /// When the job was submitted.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// When the job started running.
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the job finished.
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl JobRecord {
+    /// Creates a JobRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            kind: row.try_get("kind")?,
+            status: row.try_get("status")?,
+            progress_pct: row.try_get("progress_pct")?,
+            params: row.try_get("params")?,
+            result: row.try_get("result")?,
+            error: row.try_get("error")?,
+            tenant: row.try_get("tenant")?,
+            created_at: row.try_get("created_at")?,
+            started_at: row.try_get("started_at")?,
+            finished_at: row.try_get("finished_at")?,
+        })
+    }
+}
+
+/// Repository for background jobs.
+#[derive(Clone)]
+pub struct JobRepository {
+    pool: Arc<PgPool>,
+}
+
+impl JobRepository {
+    /// Creates a new JobRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts or updates a job.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn upsert(&self, record: &JobRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO jobs (id, kind, status, progress_pct, params, result, error,
+                              tenant, created_at, started_at, finished_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (id) DO UPDATE SET
+                status = EXCLUDED.status,
+                progress_pct = EXCLUDED.progress_pct,
+                result = EXCLUDED.result,
+                error = EXCLUDED.error,
+                started_at = EXCLUDED.started_at,
+                finished_at = EXCLUDED.finished_at
+            "#,
+        )
+        .bind(record.id)
+        .bind(&record.kind)
+        .bind(&record.status)
+        .bind(record.progress_pct)
+        .bind(&record.params)
+        .bind(&record.result)
+        .bind(&record.error)
+        .bind(&record.tenant)
+        .bind(record.created_at)
+        .bind(record.started_at)
+        .bind(record.finished_at)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds a job by ID.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<JobRecord>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM jobs WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(JobRecord::from_row).transpose()
+    }
+
+    /// Marks queued and running jobs as failed.
+    ///
+    /// Used at startup, since jobs do not survive a restart.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn fail_unfinished(&self, error: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs SET status = 'failed', error = $1, finished_at = NOW()
+            WHERE status IN ('queued', 'running')
+            "#,
+        )
+        .bind(error)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
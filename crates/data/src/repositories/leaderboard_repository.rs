@@ -0,0 +1,145 @@
+//! Leaderboard repository for computed pool and strategy rankings.
+
+use rust_decimal::Decimal;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Database record for a ranked pool or strategy.
+#[derive(Debug, Clone)]
+pub struct LeaderboardRecord {
+    /// What is ranked: `pool` or `strategy`.
+    pub kind: String,
+    /// Length of the window the metrics cover, in days.
+    pub window_days: i32,
+    /// Pool address or strategy ID.
+    pub subject: String,
+    /// Display name.
+    pub name: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Rank within the kind and window, starting at 1.
+    pub rank: i32,
+    /// Number of positions the metrics cover.
+    pub positions: i32,
+    /// Average capital deployed in USD.
+    pub avg_value_usd: Decimal,
+    /// Fees earned over the window in USD.
+    pub fees_usd: Decimal,
+    /// Realized fee APR (as percentage).
+    pub fee_apr_pct: Decimal,
+    /// Return relative to holding the tokens (as percentage).
+    pub vs_hodl_pct: Decimal,
+    /// Share of snapshots in range (as percentage).
+    pub time_in_range_pct: Decimal,
+    /// When the ranking was computed.
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl LeaderboardRecord {
+    /// Creates a LeaderboardRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            kind: row.try_get("kind")?,
+            window_days: row.try_get("window_days")?,
+            subject: row.try_get("subject")?,
+            name: row.try_get("name")?,
+            pool_address: row.try_get("pool_address")?,
+            rank: row.try_get("rank")?,
+            positions: row.try_get("positions")?,
+            avg_value_usd: row.try_get("avg_value_usd")?,
+            fees_usd: row.try_get("fees_usd")?,
+            fee_apr_pct: row.try_get("fee_apr_pct")?,
+            vs_hodl_pct: row.try_get("vs_hodl_pct")?,
+            time_in_range_pct: row.try_get("time_in_range_pct")?,
+            computed_at: row.try_get("computed_at")?,
+        })
+    }
+}
+
+/// Repository for leaderboard rankings.
+#[derive(Clone)]
+pub struct LeaderboardRepository {
+    pool: Arc<PgPool>,
+}
+
+impl LeaderboardRepository {
+    /// Creates a new LeaderboardRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Replaces the ranking of a kind and window in a single transaction.
+    ///
+    /// # Errors
+    /// Returns an error if any statement fails; the previous ranking is kept
+    /// in that case.
+    pub async fn replace(
+        &self,
+        kind: &str,
+        window_days: i32,
+        records: &[LeaderboardRecord],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM leaderboard_entries WHERE kind = $1 AND window_days = $2")
+            .bind(kind)
+            .bind(window_days)
+            .execute(&mut *tx)
+            .await?;
+        for record in records {
+            sqlx::query(
+                r#"
+                INSERT INTO leaderboard_entries (kind, window_days, subject, name, pool_address,
+                                                 rank, positions, avg_value_usd, fees_usd,
+                                                 fee_apr_pct, vs_hodl_pct, time_in_range_pct,
+                                                 computed_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                "#,
+            )
+            .bind(kind)
+            .bind(window_days)
+            .bind(&record.subject)
+            .bind(&record.name)
+            .bind(&record.pool_address)
+            .bind(record.rank)
+            .bind(record.positions)
+            .bind(record.avg_value_usd)
+            .bind(record.fees_usd)
+            .bind(record.fee_apr_pct)
+            .bind(record.vs_hodl_pct)
+            .bind(record.time_in_range_pct)
+            .bind(record.computed_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Finds the top entries of a kind and window in rank order.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_ranked(
+        &self,
+        kind: &str,
+        window_days: i32,
+        limit: i64,
+    ) -> Result<Vec<LeaderboardRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM leaderboard_entries
+            WHERE kind = $1 AND window_days = $2
+            ORDER BY rank ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(kind)
+        .bind(window_days)
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        rows.iter().map(LeaderboardRecord::from_row).collect()
+    }
+}
@@ -0,0 +1,312 @@
+//! Maintenance repository for table statistics, retention and backups.
+
+use serde::Serialize;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Every application table, in dependency order: each table follows the
+/// tables it references, so restoring in this order satisfies foreign keys.
+pub const TABLES: [&str; 22] = [
+    "pools",
+    "simulations",
+    "simulation_results",
+    "simulation_steps",
+    "price_history",
+    "optimization_results",
+    "positions",
+    "position_events",
+    "strategies",
+    "strategy_tenants",
+    "strategy_wallets",
+    "strategy_executions",
+    "monitored_positions",
+    "lifecycle_events",
+    "position_snapshots",
+    "position_rebalances",
+    "fee_collections",
+    "audit_log",
+    "jobs",
+    "idempotency_keys",
+    "request_audit_log",
+    "leaderboard_entries",
+];
+
+/// Tables pruned when no table is named.
+///
+/// The execution audit log is left out; it is only pruned on request.
+pub const DEFAULT_PRUNE_TABLES: [&str; 10] = [
+    "price_history",
+    "simulations",
+    "optimization_results",
+    "position_snapshots",
+    "position_rebalances",
+    "fee_collections",
+    "lifecycle_events",
+    "jobs",
+    "idempotency_keys",
+    "request_audit_log",
+];
+
+/// Rows fetched per round trip while exporting.
+const EXPORT_BATCH: i64 = 1000;
+
+/// Returns the condition selecting rows older than the `$1` cutoff, for
+/// tables that support retention.
+#[must_use]
+pub fn retention_condition(table: &str) -> Option<&'static str> {
+    match table {
+        "price_history" => Some("timestamp < EXTRACT(EPOCH FROM $1::timestamptz)"),
+        "simulations" | "optimization_results" | "idempotency_keys" => Some("created_at < $1"),
+        "audit_log" | "lifecycle_events" | "request_audit_log" => Some("timestamp < $1"),
+        "position_snapshots" => Some("recorded_at < $1"),
+        "position_rebalances" | "fee_collections" => Some("timestamp < $1"),
+        // Queued and running jobs are kept regardless of age
+        "jobs" => Some("finished_at < $1"),
+        _ => None,
+    }
+}
+
+/// Resolves a table name against the known tables.
+fn known_table(table: &str) -> Result<&'static str, sqlx::Error> {
+    TABLES
+        .iter()
+        .find(|known| **known == table)
+        .copied()
+        .ok_or_else(|| sqlx::Error::Configuration(format!("unknown table '{}'", table).into()))
+}
+
+/// Size and upkeep statistics of a table.
+#[derive(Debug, Clone, Serialize)]
+pub struct TableStats {
+    /// Table name.
+    pub table: String,
+    /// Estimated number of live rows.
+    pub rows: i64,
+    /// Size of the table including indexes and TOAST, in bytes.
+    pub total_bytes: i64,
+    /// Size of the table's indexes, in bytes.
+    pub index_bytes: i64,
+    /// Estimated number of dead rows awaiting vacuum.
+    pub dead_rows: i64,
+    /// Last manual or automatic vacuum.
+    pub last_vacuum: Option<chrono::DateTime<chrono::Utc>>,
+    /// Last manual or automatic analyze.
+    pub last_analyze: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TableStats {
+    /// Creates a TableStats from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            table: row.try_get("table_name")?,
+            rows: row.try_get("live_rows")?,
+            total_bytes: row.try_get("total_bytes")?,
+            index_bytes: row.try_get("index_bytes")?,
+            dead_rows: row.try_get("dead_rows")?,
+            last_vacuum: row.try_get("last_vacuum")?,
+            last_analyze: row.try_get("last_analyze")?,
+        })
+    }
+}
+
+/// Repository for database maintenance.
+pub struct MaintenanceRepository {
+    pool: Arc<PgPool>,
+}
+
+impl MaintenanceRepository {
+    /// Creates a new MaintenanceRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the size of the current database in bytes.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn database_size(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT pg_database_size(current_database())")
+            .fetch_one(self.pool.as_ref())
+            .await
+    }
+
+    /// Returns statistics of every user table, largest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn table_stats(&self) -> Result<Vec<TableStats>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT relname::text AS table_name,
+                   n_live_tup AS live_rows,
+                   n_dead_tup AS dead_rows,
+                   pg_total_relation_size(relid) AS total_bytes,
+                   pg_indexes_size(relid) AS index_bytes,
+                   GREATEST(last_vacuum, last_autovacuum) AS last_vacuum,
+                   GREATEST(last_analyze, last_autoanalyze) AS last_analyze
+            FROM pg_stat_user_tables
+            ORDER BY pg_total_relation_size(relid) DESC
+            "#,
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(TableStats::from_row).collect()
+    }
+
+    /// Counts the rows of a table older than a cutoff.
+    ///
+    /// # Errors
+    /// Returns an error if the table does not support retention or the
+    /// query fails.
+    pub async fn count_older_than(
+        &self,
+        table: &str,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let (table, condition) = Self::retention(table)?;
+        let count: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            table, condition
+        ))
+        .bind(cutoff)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(count.max(0) as u64)
+    }
+
+    /// Deletes the rows of a table older than a cutoff.
+    ///
+    /// Rows referencing deleted rows are removed by their cascades.
+    ///
+    /// # Errors
+    /// Returns an error if the table does not support retention or the
+    /// query fails.
+    pub async fn delete_older_than(
+        &self,
+        table: &str,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let (table, condition) = Self::retention(table)?;
+        let result = sqlx::query(&format!("DELETE FROM {} WHERE {}", table, condition))
+            .bind(cutoff)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Vacuums and analyzes a table, or the whole database.
+    ///
+    /// `full` rewrites the table to return space to the operating system
+    /// and locks it exclusively while it runs.
+    ///
+    /// # Errors
+    /// Returns an error if the table is unknown or the command fails.
+    pub async fn vacuum(&self, table: Option<&str>, full: bool) -> Result<(), sqlx::Error> {
+        let options = if full { "(FULL, ANALYZE)" } else { "(ANALYZE)" };
+        let sql = match table {
+            Some(table) => format!("VACUUM {} {}", options, known_table(table)?),
+            None => format!("VACUUM {}", options),
+        };
+        sqlx::query(&sql).execute(self.pool.as_ref()).await?;
+        Ok(())
+    }
+
+    /// Exports every table as JSON rows, in [`TABLES`] order.
+    ///
+    /// Reads from a single repeatable-read snapshot, so the backup is
+    /// consistent across tables, and passes each row to `write` with its
+    /// table name. Returns the number of rows of each table.
+    ///
+    /// # Errors
+    /// Returns an error if a query or `write` fails.
+    pub async fn export<F>(&self, mut write: F) -> Result<Vec<(&'static str, u64)>, sqlx::Error>
+    where
+        F: FnMut(&'static str, &str) -> std::io::Result<()>,
+    {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let mut counts = Vec::with_capacity(TABLES.len());
+        for table in TABLES {
+            sqlx::query(&format!(
+                "DECLARE export_rows NO SCROLL CURSOR FOR SELECT row_to_json(t)::text FROM {} t",
+                table
+            ))
+            .execute(&mut *tx)
+            .await?;
+
+            let mut count = 0;
+            loop {
+                let rows: Vec<String> =
+                    sqlx::query_scalar(&format!("FETCH {} FROM export_rows", EXPORT_BATCH))
+                        .fetch_all(&mut *tx)
+                        .await?;
+                if rows.is_empty() {
+                    break;
+                }
+                for row in &rows {
+                    write(table, row)?;
+                }
+                count += rows.len() as u64;
+            }
+
+            sqlx::query("CLOSE export_rows").execute(&mut *tx).await?;
+            counts.push((table, count));
+        }
+
+        tx.commit().await?;
+        Ok(counts)
+    }
+
+    /// Inserts JSON rows, as written by [`Self::export`], into a table.
+    ///
+    /// Rows whose key already exists are skipped. Returns the number of
+    /// rows inserted.
+    ///
+    /// # Errors
+    /// Returns an error if the table is unknown or the insert fails.
+    pub async fn import(&self, table: &str, rows: &[String]) -> Result<u64, sqlx::Error> {
+        let table = known_table(table)?;
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let result = sqlx::query(&format!(
+            "INSERT INTO {0} SELECT * FROM jsonb_populate_recordset(NULL::{0}, $1::jsonb) \
+             ON CONFLICT DO NOTHING",
+            table
+        ))
+        .bind(format!("[{}]", rows.join(",")))
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Resolves a table and its retention condition.
+    fn retention(table: &str) -> Result<(&'static str, &'static str), sqlx::Error> {
+        let table = known_table(table)?;
+        let condition = retention_condition(table).ok_or_else(|| {
+            sqlx::Error::Configuration(format!("table '{}' has no retention rule", table).into())
+        })?;
+        Ok((table, condition))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_rules() {
+        for table in DEFAULT_PRUNE_TABLES {
+            assert!(TABLES.contains(&table));
+            assert!(retention_condition(table).is_some(), "{}", table);
+        }
+        assert!(retention_condition("audit_log").is_some());
+        assert!(retention_condition("pools").is_none());
+        assert!(known_table("pools; DROP TABLE pools").is_err());
+    }
+}
@@ -1,16 +1,46 @@
 //! Repository implementations for database persistence.
 //!
 //! This module provides repository patterns for storing and retrieving
-//! simulation data, pool configurations, and price history.
+//! simulation data and streamed equity curves, pool configurations, price history, and live
+//! position state, snapshots, rebalances and fee collections, the audit log, background jobs, strategies,
+//! idempotency keys, the request audit log, and pool and strategy
+//! leaderboards, plus database maintenance.
 
+mod audit_repository;
 mod database;
+mod idempotency_repository;
+mod job_repository;
+mod leaderboard_repository;
+mod maintenance_repository;
 mod pool_repository;
+mod position_repository;
+mod position_snapshot_repository;
+mod position_state_repository;
 mod price_repository;
+mod request_audit_repository;
 mod simulation_repository;
+mod strategy_repository;
 
+pub use audit_repository::{AuditFilter, AuditRecord, AuditRepository};
 pub use database::Database;
-pub use pool_repository::{PoolRecord, PoolRepository};
-pub use price_repository::{PriceRecord, PriceRepository};
+pub use idempotency_repository::{IdempotencyRecord, IdempotencyRepository};
+pub use job_repository::{JobRecord, JobRepository};
+pub use leaderboard_repository::{LeaderboardRecord, LeaderboardRepository};
+pub use maintenance_repository::{
+    DEFAULT_PRUNE_TABLES, MaintenanceRepository, TABLES, TableStats, retention_condition,
+};
+pub use pool_repository::{PoolRecord, PoolRepository, PoolStatsRecord};
+pub use position_repository::{FeeCollectionRecord, PositionRepository, RebalanceRecord};
+pub use position_snapshot_repository::{PositionSnapshotRecord, PositionSnapshotRepository};
+pub use position_state_repository::{
+    LifecycleEventRecord, PositionStateRecord, PositionStateRepository,
+};
+pub use price_repository::{PriceBar, PriceRecord, PriceRepository};
+pub use request_audit_repository::{
+    RequestAuditFilter, RequestAuditRecord, RequestAuditRepository,
+};
 pub use simulation_repository::{
     OptimizationRecord, SimulationRecord, SimulationRepository, SimulationResultRecord,
+    SimulationStepRecord, SimulationStepWriter,
 };
+pub use strategy_repository::{MAX_STRATEGY_NAME_LEN, StrategyRecord, StrategyRepository};
@@ -1,5 +1,6 @@
 //! Pool repository for CLMM pool persistence.
 
+use rust_decimal::Decimal;
 use sqlx::postgres::PgRow;
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
@@ -57,6 +58,28 @@ impl PoolRecord {
     }
 }
 
+/// Pool record with market statistics from its price history.
+#[derive(Debug, Clone)]
+pub struct PoolStatsRecord {
+    /// The pool.
+    pub pool: PoolRecord,
+    /// Latest recorded liquidity in USD.
+    pub tvl: Option<Decimal>,
+    /// Volume recorded since the requested timestamp.
+    pub volume: Decimal,
+}
+
+impl PoolStatsRecord {
+    /// Creates a PoolStatsRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            pool: PoolRecord::from_row(row)?,
+            tvl: row.try_get("tvl")?,
+            volume: row.try_get("volume_window")?,
+        })
+    }
+}
+
 /// Repository for pool CRUD operations.
 #[derive(Clone)]
 pub struct PoolRepository {
@@ -117,6 +140,33 @@ impl PoolRepository {
         rows.iter().map(PoolRecord::from_row).collect()
     }
 
+    /// Finds all pools with their latest liquidity and the volume recorded
+    /// since `since` (Unix timestamp).
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all_with_stats(
+        &self,
+        since: i64,
+    ) -> Result<Vec<PoolStatsRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT p.*,
+                (SELECT ph.liquidity FROM price_history ph
+                 WHERE ph.pool_id = p.id AND ph.liquidity IS NOT NULL
+                 ORDER BY ph.timestamp DESC LIMIT 1) AS tvl,
+                COALESCE((SELECT SUM(ph.volume) FROM price_history ph
+                          WHERE ph.pool_id = p.id AND ph.timestamp >= $1), 0) AS volume_window
+            FROM pools p
+            ORDER BY p.created_at DESC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(PoolStatsRecord::from_row).collect()
+    }
+
     /// Creates or updates a pool record.
     ///
     /// # Errors
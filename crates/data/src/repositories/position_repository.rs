@@ -0,0 +1,256 @@
+//! Position repository for the history of live positions.
+//!
+//! Covers everything the monitor records about a position over time:
+//! periodic snapshots, executed rebalances and fee collections.
+
+use super::{PositionSnapshotRecord, PositionSnapshotRepository};
+use rust_decimal::Decimal;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Database record for an executed rebalance.
+#[derive(Debug, Clone)]
+pub struct RebalanceRecord {
+    /// Lifecycle event ID.
+    pub id: Uuid,
+    /// On-chain position address.
+    pub position_address: String,
+    /// On-chain pool address.
+    pub pool_address: String,
+    /// Lower tick before the rebalance.
+    pub old_tick_lower: i32,
+    /// Upper tick before the rebalance.
+    pub old_tick_upper: i32,
+    /// Lower tick after the rebalance.
+    pub new_tick_lower: i32,
+    /// Upper tick after the rebalance.
+    pub new_tick_upper: i32,
+    /// Transaction cost in lamports.
+    pub tx_cost_lamports: i64,
+    /// Impermanent loss percentage at the rebalance.
+    pub il_pct: Decimal,
+    /// Reason for the rebalance.
+    pub reason: String,
+    /// Transaction signature, if known.
+    pub tx_signature: Option<String>,
+    /// When the rebalance happened.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl RebalanceRecord {
+    /// Creates a RebalanceRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            position_address: row.try_get("position_address")?,
+            pool_address: row.try_get("pool_address")?,
+            old_tick_lower: row.try_get("old_tick_lower")?,
+            old_tick_upper: row.try_get("old_tick_upper")?,
+            new_tick_lower: row.try_get("new_tick_lower")?,
+            new_tick_upper: row.try_get("new_tick_upper")?,
+            tx_cost_lamports: row.try_get("tx_cost_lamports")?,
+            il_pct: row.try_get("il_pct")?,
+            reason: row.try_get("reason")?,
+            tx_signature: row.try_get("tx_signature")?,
+            timestamp: row.try_get("timestamp")?,
+        })
+    }
+}
+
+/// Database record for a fee collection.
+#[derive(Debug, Clone)]
+pub struct FeeCollectionRecord {
+    /// Lifecycle event ID.
+    pub id: Uuid,
+    /// On-chain position address.
+    pub position_address: String,
+    /// On-chain pool address.
+    pub pool_address: String,
+    /// Token A fees collected, in base units.
+    pub fees_a: i64,
+    /// Token B fees collected, in base units.
+    pub fees_b: i64,
+    /// Fees value in USD.
+    pub fees_usd: Decimal,
+    /// Transaction signature, if known.
+    pub tx_signature: Option<String>,
+    /// When the fees were collected.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl FeeCollectionRecord {
+    /// Creates a FeeCollectionRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            position_address: row.try_get("position_address")?,
+            pool_address: row.try_get("pool_address")?,
+            fees_a: row.try_get("fees_a")?,
+            fees_b: row.try_get("fees_b")?,
+            fees_usd: row.try_get("fees_usd")?,
+            tx_signature: row.try_get("tx_signature")?,
+            timestamp: row.try_get("timestamp")?,
+        })
+    }
+}
+
+/// Repository for the snapshots, rebalances and fee collections of live
+/// positions.
+#[derive(Clone)]
+pub struct PositionRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PositionRepository {
+    /// Creates a new PositionRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Saves snapshots in a single transaction.
+    ///
+    /// See [`PositionSnapshotRepository::insert_batch`].
+    ///
+    /// # Errors
+    /// Returns an error if any insert fails; nothing is written in that case.
+    pub async fn insert_snapshots(
+        &self,
+        records: &[PositionSnapshotRecord],
+    ) -> Result<u64, sqlx::Error> {
+        self.snapshots().insert_batch(records).await
+    }
+
+    /// Finds snapshots since a time in chronological order, optionally for
+    /// a single position.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_snapshots(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        position_address: Option<&str>,
+    ) -> Result<Vec<PositionSnapshotRecord>, sqlx::Error> {
+        self.snapshots().find_since(since, position_address).await
+    }
+
+    /// Records a rebalance.
+    ///
+    /// Recording a rebalance that already exists is a no-op.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn insert_rebalance(&self, record: &RebalanceRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO position_rebalances (id, position_address, pool_address, old_tick_lower,
+                                             old_tick_upper, new_tick_lower, new_tick_upper,
+                                             tx_cost_lamports, il_pct, reason, tx_signature,
+                                             timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(record.id)
+        .bind(&record.position_address)
+        .bind(&record.pool_address)
+        .bind(record.old_tick_lower)
+        .bind(record.old_tick_upper)
+        .bind(record.new_tick_lower)
+        .bind(record.new_tick_upper)
+        .bind(record.tx_cost_lamports)
+        .bind(record.il_pct)
+        .bind(&record.reason)
+        .bind(&record.tx_signature)
+        .bind(record.timestamp)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds rebalances since a time in chronological order, optionally for
+    /// a single position.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_rebalances(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        position_address: Option<&str>,
+    ) -> Result<Vec<RebalanceRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM position_rebalances
+            WHERE timestamp >= $1 AND ($2::VARCHAR IS NULL OR position_address = $2)
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(since)
+        .bind(position_address)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(RebalanceRecord::from_row).collect()
+    }
+
+    /// Records a fee collection.
+    ///
+    /// Recording a fee collection that already exists is a no-op.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn insert_fee_collection(
+        &self,
+        record: &FeeCollectionRecord,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO fee_collections (id, position_address, pool_address, fees_a, fees_b,
+                                         fees_usd, tx_signature, timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(record.id)
+        .bind(&record.position_address)
+        .bind(&record.pool_address)
+        .bind(record.fees_a)
+        .bind(record.fees_b)
+        .bind(record.fees_usd)
+        .bind(&record.tx_signature)
+        .bind(record.timestamp)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds fee collections since a time in chronological order, optionally
+    /// for a single position.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_fee_collections(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        position_address: Option<&str>,
+    ) -> Result<Vec<FeeCollectionRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM fee_collections
+            WHERE timestamp >= $1 AND ($2::VARCHAR IS NULL OR position_address = $2)
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(since)
+        .bind(position_address)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(FeeCollectionRecord::from_row).collect()
+    }
+
+    /// Returns the snapshot repository sharing this connection pool.
+    fn snapshots(&self) -> PositionSnapshotRepository {
+        PositionSnapshotRepository::new(self.pool.clone())
+    }
+}
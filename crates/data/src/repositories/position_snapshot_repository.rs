@@ -0,0 +1,132 @@
+//! Position snapshot repository for historical portfolio analytics.
+
+use rust_decimal::Decimal;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Database record for a recorded snapshot of a monitored position.
+#[derive(Debug, Clone)]
+pub struct PositionSnapshotRecord {
+    /// On-chain position address.
+    pub position_address: String,
+    /// On-chain pool address.
+    pub pool_address: String,
+    /// When the snapshot was taken.
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    /// Lower tick of the range, if recorded.
+    pub tick_lower: Option<i32>,
+    /// Upper tick of the range, if recorded.
+    pub tick_upper: Option<i32>,
+    /// Whether the position was in range.
+    pub in_range: bool,
+    /// Entry value in USD, if recorded.
+    pub entry_value_usd: Option<Decimal>,
+    /// Position value in USD.
+    pub value_usd: Decimal,
+    /// Fees earned in USD.
+    pub fees_usd: Decimal,
+    /// Impermanent loss percentage.
+    pub il_pct: Decimal,
+    /// Net PnL in USD.
+    pub net_pnl_usd: Decimal,
+}
+
+impl PositionSnapshotRecord {
+    /// Creates a PositionSnapshotRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            position_address: row.try_get("position_address")?,
+            pool_address: row.try_get("pool_address")?,
+            recorded_at: row.try_get("recorded_at")?,
+            tick_lower: row.try_get("tick_lower")?,
+            tick_upper: row.try_get("tick_upper")?,
+            in_range: row.try_get("in_range")?,
+            entry_value_usd: row.try_get("entry_value_usd")?,
+            value_usd: row.try_get("value_usd")?,
+            fees_usd: row.try_get("fees_usd")?,
+            il_pct: row.try_get("il_pct")?,
+            net_pnl_usd: row.try_get("net_pnl_usd")?,
+        })
+    }
+}
+
+/// Repository for periodic snapshots of monitored positions.
+#[derive(Clone)]
+pub struct PositionSnapshotRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PositionSnapshotRepository {
+    /// Creates a new PositionSnapshotRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Saves snapshots in a single transaction.
+    ///
+    /// Snapshots already stored for a position at the same time are kept.
+    /// Returns the number of snapshots written.
+    ///
+    /// # Errors
+    /// Returns an error if any insert fails; nothing is written in that case.
+    pub async fn insert_batch(
+        &self,
+        records: &[PositionSnapshotRecord],
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut written = 0;
+        for record in records {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO position_snapshots (position_address, pool_address, recorded_at,
+                                                tick_lower, tick_upper, in_range, entry_value_usd,
+                                                value_usd, fees_usd, il_pct, net_pnl_usd)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (position_address, recorded_at) DO NOTHING
+                "#,
+            )
+            .bind(&record.position_address)
+            .bind(&record.pool_address)
+            .bind(record.recorded_at)
+            .bind(record.tick_lower)
+            .bind(record.tick_upper)
+            .bind(record.in_range)
+            .bind(record.entry_value_usd)
+            .bind(record.value_usd)
+            .bind(record.fees_usd)
+            .bind(record.il_pct)
+            .bind(record.net_pnl_usd)
+            .execute(&mut *tx)
+            .await?;
+            written += result.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(written)
+    }
+
+    /// Finds snapshots since a time in chronological order, optionally for
+    /// a single position.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_since(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        position_address: Option<&str>,
+    ) -> Result<Vec<PositionSnapshotRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM position_snapshots
+            WHERE recorded_at >= $1 AND ($2::VARCHAR IS NULL OR position_address = $2)
+            ORDER BY recorded_at ASC, position_address ASC
+            "#,
+        )
+        .bind(since)
+        .bind(position_address)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(PositionSnapshotRecord::from_row).collect()
+    }
+}
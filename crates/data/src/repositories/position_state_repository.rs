@@ -0,0 +1,187 @@
+//! Position state repository for monitor and lifecycle persistence.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Database record for a monitored position snapshot.
+#[derive(Debug, Clone)]
+pub struct PositionStateRecord {
+    /// On-chain position address.
+    pub position_address: String,
+    /// On-chain pool address.
+    pub pool_address: String,
+    /// Whether the position was in range at the last update.
+    pub in_range: bool,
+    /// Serialized monitor state.
+    pub state: serde_json::Value,
+    /// Record update timestamp.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PositionStateRecord {
+    /// Creates a PositionStateRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            position_address: row.try_get("position_address")?,
+            pool_address: row.try_get("pool_address")?,
+            in_range: row.try_get("in_range")?,
+            state: row.try_get("state")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Database record for a position lifecycle event.
+#[derive(Debug, Clone)]
+pub struct LifecycleEventRecord {
+    /// Event ID.
+    pub id: Uuid,
+    /// On-chain position address.
+    pub position_address: String,
+    /// On-chain pool address.
+    pub pool_address: String,
+    /// Event type name.
+    pub event_type: String,
+    /// Serialized event.
+    pub event: serde_json::Value,
+    /// When the event occurred.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl LifecycleEventRecord {
+    /// Creates a LifecycleEventRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            position_address: row.try_get("position_address")?,
+            pool_address: row.try_get("pool_address")?,
+            event_type: row.try_get("event_type")?,
+            event: row.try_get("event")?,
+            timestamp: row.try_get("timestamp")?,
+        })
+    }
+}
+
+/// Repository for monitored position state and lifecycle history.
+#[derive(Clone)]
+pub struct PositionStateRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PositionStateRepository {
+    /// Creates a new PositionStateRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Creates or updates a position snapshot.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn upsert(
+        &self,
+        position_address: &str,
+        pool_address: &str,
+        in_range: bool,
+        state: &serde_json::Value,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO monitored_positions (position_address, pool_address, in_range, state)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (position_address) DO UPDATE SET
+                pool_address = EXCLUDED.pool_address,
+                in_range = EXCLUDED.in_range,
+                state = EXCLUDED.state,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(position_address)
+        .bind(pool_address)
+        .bind(in_range)
+        .bind(state)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds all position snapshots.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all(&self) -> Result<Vec<PositionStateRecord>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM monitored_positions ORDER BY updated_at DESC")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(PositionStateRecord::from_row).collect()
+    }
+
+    /// Deletes a position snapshot.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn delete(&self, position_address: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM monitored_positions WHERE position_address = $1")
+            .bind(position_address)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Appends a lifecycle event.
+    ///
+    /// Inserting an event that already exists is a no-op.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn insert_event(&self, record: &LifecycleEventRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO lifecycle_events (id, position_address, pool_address, event_type,
+                                          event, timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(record.id)
+        .bind(&record.position_address)
+        .bind(&record.pool_address)
+        .bind(&record.event_type)
+        .bind(&record.event)
+        .bind(record.timestamp)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds all lifecycle events in chronological order.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all_events(&self) -> Result<Vec<LifecycleEventRecord>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM lifecycle_events ORDER BY timestamp ASC")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(LifecycleEventRecord::from_row).collect()
+    }
+
+    /// Finds lifecycle events for a position in chronological order.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_events(
+        &self,
+        position_address: &str,
+    ) -> Result<Vec<LifecycleEventRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM lifecycle_events WHERE position_address = $1 ORDER BY timestamp ASC",
+        )
+        .bind(position_address)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(LifecycleEventRecord::from_row).collect()
+    }
+}
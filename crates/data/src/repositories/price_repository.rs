@@ -1,5 +1,8 @@
 //! Price history repository for caching market data.
 
+use clmm_lp_domain::entities::price_candle::PriceCandle;
+use clmm_lp_domain::entities::token::Token;
+use clmm_lp_domain::value_objects::{amount::Amount, price::Price};
 use rust_decimal::Decimal;
 use sqlx::postgres::PgRow;
 use sqlx::{PgPool, Row};
@@ -47,6 +50,52 @@ impl PriceRecord {
             created_at: row.try_get("created_at")?,
         })
     }
+
+    /// Converts the record into a price candle of a token pair.
+    ///
+    /// Volume is read as token A volume, the unit `write_candles_to_csv`
+    /// writes; missing volume becomes zero.
+    #[must_use]
+    pub fn to_candle(
+        &self,
+        token_a: &Token,
+        token_b: &Token,
+        duration_seconds: u64,
+    ) -> PriceCandle {
+        PriceCandle {
+            token_a: token_a.clone(),
+            token_b: token_b.clone(),
+            start_timestamp: self.timestamp.max(0) as u64,
+            duration_seconds,
+            open: Price::new(self.open_price),
+            high: Price::new(self.high_price),
+            low: Price::new(self.low_price),
+            close: Price::new(self.close_price),
+            volume_token_a: Amount::from_decimal(
+                self.volume.unwrap_or_default().max(Decimal::ZERO),
+                token_a.decimals,
+            ),
+        }
+    }
+}
+
+/// A price bar to store with [`PriceRepository::save_batch`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceBar {
+    /// Timestamp in seconds.
+    pub timestamp: i64,
+    /// Open price.
+    pub open: Decimal,
+    /// High price.
+    pub high: Decimal,
+    /// Low price.
+    pub low: Decimal,
+    /// Close price.
+    pub close: Decimal,
+    /// Trading volume.
+    pub volume: Option<Decimal>,
+    /// Pool liquidity.
+    pub liquidity: Option<Decimal>,
 }
 
 /// Repository for price history CRUD operations.
@@ -108,6 +157,52 @@ impl PriceRepository {
         PriceRecord::from_row(&row)
     }
 
+    /// Saves price bars for a pool in a single transaction.
+    ///
+    /// Bars at timestamps already stored for the pool are overwritten.
+    /// Returns the number of bars written.
+    ///
+    /// # Errors
+    /// Returns an error if any insert fails; nothing is written in that case.
+    pub async fn save_batch(
+        &self,
+        pool_id: Option<Uuid>,
+        bars: &[PriceBar],
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut written = 0;
+        for bar in bars {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO price_history (id, pool_id, timestamp, open_price, high_price,
+                                           low_price, close_price, volume, liquidity)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (pool_id, timestamp) DO UPDATE SET
+                    open_price = EXCLUDED.open_price,
+                    high_price = EXCLUDED.high_price,
+                    low_price = EXCLUDED.low_price,
+                    close_price = EXCLUDED.close_price,
+                    volume = EXCLUDED.volume,
+                    liquidity = EXCLUDED.liquidity
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(pool_id)
+            .bind(bar.timestamp)
+            .bind(bar.open)
+            .bind(bar.high)
+            .bind(bar.low)
+            .bind(bar.close)
+            .bind(bar.volume)
+            .bind(bar.liquidity)
+            .execute(&mut *tx)
+            .await?;
+            written += result.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(written)
+    }
+
     /// Finds price history for a pool within a time range.
     ///
     /// # Errors
@@ -0,0 +1,153 @@
+//! Request audit log repository for mutating API calls.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Database record for a mutating API request.
+#[derive(Debug, Clone)]
+pub struct RequestAuditRecord {
+    /// Entry ID.
+    pub id: Uuid,
+    /// When the request was received.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// API key ID or token subject; empty when unauthenticated.
+    pub subject: String,
+    /// Caller's role.
+    pub role: Option<String>,
+    /// Caller's tenant.
+    pub tenant: Option<String>,
+    /// HTTP method.
+    pub method: String,
+    /// Request path.
+    pub path: String,
+    /// SHA-256 of the request body; `None` when empty.
+    pub payload_hash: Option<String>,
+    /// Response status code.
+    pub status: i32,
+    /// Time to respond in milliseconds.
+    pub duration_ms: i64,
+}
+
+impl RequestAuditRecord {
+    /// Creates a RequestAuditRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            timestamp: row.try_get("timestamp")?,
+            subject: row.try_get("subject")?,
+            role: row.try_get("role")?,
+            tenant: row.try_get("tenant")?,
+            method: row.try_get("method")?,
+            path: row.try_get("path")?,
+            payload_hash: row.try_get("payload_hash")?,
+            status: row.try_get("status")?,
+            duration_ms: row.try_get("duration_ms")?,
+        })
+    }
+}
+
+/// Filter for request audit log queries.
+///
+/// Unset fields match every entry.
+#[derive(Debug, Clone, Default)]
+pub struct RequestAuditFilter {
+    /// Only requests by this subject.
+    pub subject: Option<String>,
+    /// Only requests by this tenant.
+    pub tenant: Option<String>,
+    /// Only requests with this method.
+    pub method: Option<String>,
+    /// Only requests whose path starts with this prefix.
+    pub path_prefix: Option<String>,
+    /// Only requests answered with this status code.
+    pub status: Option<i32>,
+    /// Only requests at or after this time.
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only requests before this time.
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Repository for the append-only request audit log.
+///
+/// Exposes no update or delete operations; the table itself discards
+/// them as well.
+#[derive(Clone)]
+pub struct RequestAuditRepository {
+    pool: Arc<PgPool>,
+}
+
+impl RequestAuditRepository {
+    /// Creates a new RequestAuditRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Appends an entry.
+    ///
+    /// Appending an entry that already exists is a no-op.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn append(&self, record: &RequestAuditRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO request_audit_log (id, timestamp, subject, role, tenant, method, path,
+                                           payload_hash, status, duration_ms)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(record.id)
+        .bind(record.timestamp)
+        .bind(&record.subject)
+        .bind(&record.role)
+        .bind(&record.tenant)
+        .bind(&record.method)
+        .bind(&record.path)
+        .bind(&record.payload_hash)
+        .bind(record.status)
+        .bind(record.duration_ms)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Finds entries matching a filter, newest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn query(
+        &self,
+        filter: &RequestAuditFilter,
+        limit: i64,
+    ) -> Result<Vec<RequestAuditRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM request_audit_log
+            WHERE ($1::text IS NULL OR subject = $1)
+              AND ($2::text IS NULL OR tenant = $2)
+              AND ($3::text IS NULL OR method = $3)
+              AND ($4::text IS NULL OR starts_with(path, $4))
+              AND ($5::int IS NULL OR status = $5)
+              AND ($6::timestamptz IS NULL OR timestamp >= $6)
+              AND ($7::timestamptz IS NULL OR timestamp < $7)
+            ORDER BY timestamp DESC
+            LIMIT $8
+            "#,
+        )
+        .bind(&filter.subject)
+        .bind(&filter.tenant)
+        .bind(&filter.method)
+        .bind(&filter.path_prefix)
+        .bind(filter.status)
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(RequestAuditRecord::from_row).collect()
+    }
+}
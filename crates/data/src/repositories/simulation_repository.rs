@@ -1,13 +1,16 @@
 //! Simulation repository for backtest and optimization persistence.
 
 use rust_decimal::Decimal;
+use serde::Serialize;
 use sqlx::postgres::PgRow;
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 /// Database record for a simulation configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SimulationRecord {
     /// Unique identifier.
     pub id: Uuid,
@@ -119,8 +122,51 @@ impl SimulationResultRecord {
     }
 }
 
+/// Database record for one step of a simulation's equity curve.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationStepRecord {
+    /// Step number, starting at 0.
+    pub step: i64,
+    /// Price at this step.
+    pub price: Decimal,
+    /// Lower bound of the active range.
+    pub lower_price: Decimal,
+    /// Upper bound of the active range.
+    pub upper_price: Decimal,
+    /// Whether the price was in range.
+    pub in_range: bool,
+    /// Position value.
+    pub position_value: Decimal,
+    /// Fees earned up to this step.
+    pub cumulative_fees: Decimal,
+    /// Impermanent loss as a fraction.
+    pub il_pct: Decimal,
+    /// Net PnL.
+    pub net_pnl: Decimal,
+    /// Whether the position was rebalanced at this step.
+    pub rebalanced: bool,
+}
+
+impl SimulationStepRecord {
+    /// Creates a SimulationStepRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            step: row.try_get("step")?,
+            price: row.try_get("price")?,
+            lower_price: row.try_get("lower_price")?,
+            upper_price: row.try_get("upper_price")?,
+            in_range: row.try_get("in_range")?,
+            position_value: row.try_get("position_value")?,
+            cumulative_fees: row.try_get("cumulative_fees")?,
+            il_pct: row.try_get("il_pct")?,
+            net_pnl: row.try_get("net_pnl")?,
+            rebalanced: row.try_get("rebalanced")?,
+        })
+    }
+}
+
 /// Database record for optimization results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OptimizationRecord {
     /// Unique identifier.
     pub id: Uuid,
@@ -289,6 +335,113 @@ impl SimulationRepository {
         SimulationResultRecord::from_row(&row)
     }
 
+    /// Saves steps of a simulation in a single transaction.
+    ///
+    /// Steps already stored are kept, so a batch can be retried. Returns
+    /// the number of steps written.
+    ///
+    /// # Errors
+    /// Returns an error if any insert fails; nothing is written in that case.
+    pub async fn save_steps(
+        &self,
+        simulation_id: Uuid,
+        steps: &[SimulationStepRecord],
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut written = 0;
+        for step in steps {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO simulation_steps (simulation_id, step, price, lower_price, upper_price,
+                                              in_range, position_value, cumulative_fees, il_pct,
+                                              net_pnl, rebalanced)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (simulation_id, step) DO NOTHING
+                "#,
+            )
+            .bind(simulation_id)
+            .bind(step.step)
+            .bind(step.price)
+            .bind(step.lower_price)
+            .bind(step.upper_price)
+            .bind(step.in_range)
+            .bind(step.position_value)
+            .bind(step.cumulative_fees)
+            .bind(step.il_pct)
+            .bind(step.net_pnl)
+            .bind(step.rebalanced)
+            .execute(&mut *tx)
+            .await?;
+            written += result.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(written)
+    }
+
+    /// Finds steps of a simulation after a step number, in step order.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_steps(
+        &self,
+        simulation_id: Uuid,
+        after_step: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<SimulationStepRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM simulation_steps
+            WHERE simulation_id = $1 AND ($2::BIGINT IS NULL OR step > $2)
+            ORDER BY step ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(simulation_id)
+        .bind(after_step)
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(SimulationStepRecord::from_row).collect()
+    }
+
+    /// Finds the last steps of a simulation, in step order.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_last_steps(
+        &self,
+        simulation_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<SimulationStepRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT * FROM simulation_steps
+                WHERE simulation_id = $1
+                ORDER BY step DESC
+                LIMIT $2
+            ) last_steps
+            ORDER BY step ASC
+            "#,
+        )
+        .bind(simulation_id)
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(SimulationStepRecord::from_row).collect()
+    }
+
+    /// Counts the stored steps of a simulation.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn count_steps(&self, simulation_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM simulation_steps WHERE simulation_id = $1")
+            .bind(simulation_id)
+            .fetch_one(self.pool.as_ref())
+            .await
+    }
+
     /// Finds a simulation by ID.
     ///
     /// # Errors
@@ -333,6 +486,25 @@ impl SimulationRepository {
         rows.iter().map(SimulationRecord::from_row).collect()
     }
 
+    /// Finds simulations whose ID starts with a prefix.
+    ///
+    /// Lets callers resolve the shortened IDs shown in listings.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_id_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<SimulationRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM simulations WHERE id::text LIKE $1 || '%' ORDER BY created_at DESC",
+        )
+        .bind(prefix.to_lowercase())
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(SimulationRecord::from_row).collect()
+    }
+
     /// Saves an optimization result.
     ///
     /// # Errors
@@ -413,3 +585,51 @@ impl SimulationRepository {
         Ok(result.rows_affected() > 0)
     }
 }
+
+/// Writes simulation steps in the background as batches arrive.
+///
+/// Batches are written in order by a single task, so a long simulation's
+/// equity curve can be inspected while it runs and survives a crash up to
+/// the last written batch.
+pub struct SimulationStepWriter {
+    /// Queue of batches to write.
+    batches: mpsc::UnboundedSender<Vec<SimulationStepRecord>>,
+    /// Task writing the batches, returning the number of steps written.
+    task: JoinHandle<Result<u64, sqlx::Error>>,
+}
+
+impl SimulationStepWriter {
+    /// Starts writing steps of a simulation.
+    ///
+    /// Must be called from within a Tokio runtime.
+    #[must_use]
+    pub fn spawn(repository: SimulationRepository, simulation_id: Uuid) -> Self {
+        let (batches, mut queue) = mpsc::unbounded_channel::<Vec<SimulationStepRecord>>();
+        let task = tokio::spawn(async move {
+            let mut written = 0;
+            while let Some(batch) = queue.recv().await {
+                written += repository.save_steps(simulation_id, &batch).await?;
+            }
+            Ok(written)
+        });
+        Self { batches, task }
+    }
+
+    /// Queues a batch of steps.
+    ///
+    /// Returns false if the writer stopped after a failed write.
+    pub fn send(&self, steps: Vec<SimulationStepRecord>) -> bool {
+        steps.is_empty() || self.batches.send(steps).is_ok()
+    }
+
+    /// Waits until every queued batch is written.
+    ///
+    /// Returns the number of steps written.
+    ///
+    /// # Errors
+    /// Returns the first failed write.
+    pub async fn finish(self) -> anyhow::Result<u64> {
+        drop(self.batches);
+        Ok(self.task.await??)
+    }
+}
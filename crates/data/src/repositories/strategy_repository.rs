@@ -0,0 +1,246 @@
+//! Strategy repository for strategy definitions and running state.
+//!
+//! Uses the `strategies` table from migration 002; `is_active` records
+//! whether the strategy was running. Owning tenants are kept in the
+//! `strategy_tenants` table from migration 008 and bound wallets in the
+//! `strategy_wallets` table from migration 014.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Longest strategy name the `strategies` table holds.
+pub const MAX_STRATEGY_NAME_LEN: usize = 100;
+
+/// Longest strategy type the `strategies` table holds.
+const MAX_STRATEGY_TYPE_LEN: usize = 50;
+
+/// Database record for a strategy.
+#[derive(Debug, Clone)]
+pub struct StrategyRecord {
+    /// Strategy ID.
+    pub id: Uuid,
+    /// Strategy name.
+    pub name: String,
+    /// Strategy type.
+    pub strategy_type: String,
+    /// Strategy configuration.
+    pub config: serde_json::Value,
+    /// Whether the strategy was running.
+    pub running: bool,
+    /// Owning tenant; `None` for operator strategies.
+    pub tenant: Option<String>,
+    /// Label of the bound signing wallet, if any.
+    pub wallet: Option<String>,
+    /// When the strategy was created.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// When the strategy was last updated.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl StrategyRecord {
+    /// Creates a StrategyRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            strategy_type: row.try_get("strategy_type")?,
+            config: row.try_get("config")?,
+            running: row.try_get("is_active")?,
+            tenant: row.try_get("tenant")?,
+            wallet: row.try_get("wallet_label")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    /// Checks that the name and type fit the `strategies` table.
+    ///
+    /// # Errors
+    /// Returns an error if the name or type is empty or too long.
+    pub fn validate(&self) -> Result<(), sqlx::Error> {
+        let invalid = |reason: String| Err(sqlx::Error::Configuration(reason.into()));
+        if self.name.trim().is_empty() {
+            return invalid(format!("strategy {} has no name", self.id));
+        }
+        if self.name.chars().count() > MAX_STRATEGY_NAME_LEN {
+            return invalid(format!(
+                "strategy name is longer than {} characters",
+                MAX_STRATEGY_NAME_LEN
+            ));
+        }
+        if self.strategy_type.is_empty() {
+            return invalid(format!("strategy {} has no type", self.id));
+        }
+        if self.strategy_type.chars().count() > MAX_STRATEGY_TYPE_LEN {
+            return invalid(format!(
+                "strategy type is longer than {} characters",
+                MAX_STRATEGY_TYPE_LEN
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Repository for strategies.
+#[derive(Clone)]
+pub struct StrategyRepository {
+    pool: Arc<PgPool>,
+}
+
+impl StrategyRepository {
+    /// Creates a new StrategyRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts or updates a strategy, its tenant and its bound wallet.
+    ///
+    /// # Errors
+    /// Returns an error if the record is invalid or the query fails.
+    pub async fn upsert(&self, record: &StrategyRecord) -> Result<(), sqlx::Error> {
+        record.validate()?;
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO strategies
+                (id, name, strategy_type, config, is_active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE SET
+                name = EXCLUDED.name,
+                strategy_type = EXCLUDED.strategy_type,
+                config = EXCLUDED.config,
+                is_active = EXCLUDED.is_active,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(record.id)
+        .bind(&record.name)
+        .bind(&record.strategy_type)
+        .bind(&record.config)
+        .bind(record.running)
+        .bind(record.created_at)
+        .bind(record.updated_at)
+        .execute(&mut *tx)
+        .await?;
+
+        match &record.tenant {
+            Some(tenant) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO strategy_tenants (strategy_id, tenant)
+                    VALUES ($1, $2)
+                    ON CONFLICT (strategy_id) DO UPDATE SET tenant = EXCLUDED.tenant
+                    "#,
+                )
+                .bind(record.id)
+                .bind(tenant)
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM strategy_tenants WHERE strategy_id = $1")
+                    .bind(record.id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        match &record.wallet {
+            Some(label) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO strategy_wallets (strategy_id, wallet_label)
+                    VALUES ($1, $2)
+                    ON CONFLICT (strategy_id) DO UPDATE SET wallet_label = EXCLUDED.wallet_label
+                    "#,
+                )
+                .bind(record.id)
+                .bind(label)
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM strategy_wallets WHERE strategy_id = $1")
+                    .bind(record.id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await
+    }
+
+    /// Finds all strategies, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all(&self) -> Result<Vec<StrategyRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.*, t.tenant, w.wallet_label
+            FROM strategies s
+            LEFT JOIN strategy_tenants t ON t.strategy_id = s.id
+            LEFT JOIN strategy_wallets w ON w.strategy_id = s.id
+            ORDER BY s.created_at
+            "#,
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(StrategyRecord::from_row).collect()
+    }
+
+    /// Deletes a strategy.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM strategies WHERE id = $1")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, strategy_type: &str) -> StrategyRecord {
+        let now = chrono::Utc::now();
+        StrategyRecord {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            strategy_type: strategy_type.to_string(),
+            config: serde_json::json!({}),
+            running: false,
+            tenant: None,
+            wallet: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(record("SOL range", "threshold").validate().is_ok());
+        assert!(
+            record(&"é".repeat(MAX_STRATEGY_NAME_LEN), "static")
+                .validate()
+                .is_ok()
+        );
+
+        assert!(record("", "static").validate().is_err());
+        assert!(record("  ", "static").validate().is_err());
+        assert!(
+            record(&"a".repeat(MAX_STRATEGY_NAME_LEN + 1), "static")
+                .validate()
+                .is_err()
+        );
+        assert!(record("SOL range", "").validate().is_err());
+        assert!(record("SOL range", &"a".repeat(51)).validate().is_err());
+    }
+}
@@ -13,6 +13,8 @@ pub enum Protocol {
     MeteoraDLMM,
     /// Meteora Stable protocol.
     MeteoraStable,
+    /// Uniswap v3 and its EVM deployments.
+    UniswapV3,
 }
 
 /// Types of pools.
@@ -1,4 +1,5 @@
 use crate::token::TokenAmount;
+use primitive_types::U256;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
@@ -112,6 +113,111 @@ pub fn get_liquidity_for_amount1(
     liquidity.to_u128().ok_or("Overflow")
 }
 
+/// Orders two Q64.64 sqrt prices.
+fn ordered(sqrt_price_a: u128, sqrt_price_b: u128) -> (u128, u128) {
+    if sqrt_price_a < sqrt_price_b {
+        (sqrt_price_a, sqrt_price_b)
+    } else {
+        (sqrt_price_b, sqrt_price_a)
+    }
+}
+
+/// Calculates the token A amount of liquidity between two Q64.64 sqrt
+/// prices, as the Whirlpool program's `get_amount_delta_a`.
+/// delta_x = L * (sqrt(P_b) - sqrt(P_a)) / (sqrt(P_a) * sqrt(P_b))
+pub fn get_amount_a_delta_x64(
+    liquidity: u128,
+    sqrt_price_a: u128,
+    sqrt_price_b: u128,
+    round_up: bool,
+) -> Result<u64, &'static str> {
+    let (lower, upper) = ordered(sqrt_price_a, sqrt_price_b);
+    if lower == 0 {
+        return Err("Sqrt price must be positive");
+    }
+
+    let numerator = (U256::from(liquidity) * U256::from(upper - lower)) << 64;
+    let denominator = U256::from(lower) * U256::from(upper);
+    let (mut amount, remainder) = numerator.div_mod(denominator);
+    if round_up && !remainder.is_zero() {
+        amount += U256::one();
+    }
+    u64::try_from(amount).map_err(|_| "Overflow converting amount")
+}
+
+/// Calculates the token B amount of liquidity between two Q64.64 sqrt
+/// prices, as the Whirlpool program's `get_amount_delta_b`.
+/// delta_y = L * (sqrt(P_b) - sqrt(P_a))
+pub fn get_amount_b_delta_x64(
+    liquidity: u128,
+    sqrt_price_a: u128,
+    sqrt_price_b: u128,
+    round_up: bool,
+) -> Result<u64, &'static str> {
+    let (lower, upper) = ordered(sqrt_price_a, sqrt_price_b);
+
+    let product = U256::from(liquidity) * U256::from(upper - lower);
+    let mut amount = product >> 64;
+    if round_up && product.low_u64() != 0 {
+        amount += U256::one();
+    }
+    u64::try_from(amount).map_err(|_| "Overflow converting amount")
+}
+
+/// Calculates the token amounts liquidity in a range is worth at a pool's
+/// Q64.64 sqrt price.
+///
+/// Rounding down gives what withdrawing the liquidity returns; rounding up
+/// gives what depositing it costs.
+pub fn get_amounts_for_liquidity_x64(
+    liquidity: u128,
+    sqrt_price: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    round_up: bool,
+) -> Result<(u64, u64), &'static str> {
+    let (lower, upper) = ordered(sqrt_price_lower, sqrt_price_upper);
+    let sqrt_price = sqrt_price.clamp(lower, upper);
+    Ok((
+        get_amount_a_delta_x64(liquidity, sqrt_price, upper, round_up)?,
+        get_amount_b_delta_x64(liquidity, lower, sqrt_price, round_up)?,
+    ))
+}
+
+/// Calculates the most liquidity token amounts can fund in a range at a
+/// pool's Q64.64 sqrt price.
+///
+/// Rounds down, so depositing the liquidity never needs more than the
+/// amounts. Returns 0 for an empty range.
+pub fn get_liquidity_for_amounts_x64(
+    (amount_a, amount_b): (u64, u64),
+    sqrt_price: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+) -> u128 {
+    let (lower, upper) = ordered(sqrt_price_lower, sqrt_price_upper);
+    if lower == 0 || lower == upper {
+        return 0;
+    }
+
+    // L = amount_a * sqrt(P_a) * sqrt(P_b) / (sqrt(P_b) - sqrt(P_a))
+    let for_a = |lower: u128, upper: u128| {
+        let product = (U256::from(lower) * U256::from(upper)) >> 64;
+        let liquidity = U256::from(amount_a) * product / U256::from(upper - lower);
+        u128::try_from(liquidity).unwrap_or(u128::MAX)
+    };
+    // L = amount_b / (sqrt(P_b) - sqrt(P_a))
+    let for_b = |lower: u128, upper: u128| (u128::from(amount_b) << 64) / (upper - lower);
+
+    if sqrt_price <= lower {
+        for_a(lower, upper)
+    } else if sqrt_price >= upper {
+        for_b(lower, upper)
+    } else {
+        for_a(sqrt_price, upper).min(for_b(lower, sqrt_price))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,6 +240,55 @@ mod tests {
         assert_eq!(dx.as_u256().as_u64(), 500);
     }
 
+    #[test]
+    fn test_amount_deltas_x64() {
+        // Liquidity 1000 from sqrt price 1 to 2: 500 token A and 1000 token B
+        let (one, two) = (1u128 << 64, 2u128 << 64);
+        assert_eq!(get_amount_a_delta_x64(1000, one, two, false), Ok(500));
+        assert_eq!(get_amount_b_delta_x64(1000, two, one, false), Ok(1000));
+
+        // Rounding only matters for inexact amounts
+        let three = 3u128 << 64;
+        assert_eq!(get_amount_a_delta_x64(1000, one, three, false), Ok(666));
+        assert_eq!(get_amount_a_delta_x64(1000, one, three, true), Ok(667));
+        assert_eq!(get_amount_b_delta_x64(1, one, one + 1, false), Ok(0));
+        assert_eq!(get_amount_b_delta_x64(1, one, one + 1, true), Ok(1));
+
+        assert!(get_amount_a_delta_x64(1000, 0, one, false).is_err());
+        assert!(get_amount_b_delta_x64(u128::MAX, one, three, false).is_err());
+    }
+
+    #[test]
+    fn test_liquidity_for_amounts_x64() {
+        let (lower, price, upper) = (1u128 << 64, 2u128 << 64, 3u128 << 64);
+
+        // In range the scarcer token limits the liquidity
+        let liquidity = get_liquidity_for_amounts_x64((1000, 1000), price, lower, upper);
+        let (a, b) = get_amounts_for_liquidity_x64(liquidity, price, lower, upper, true).unwrap();
+        assert!(a <= 1000 && b <= 1000);
+        assert!(a.max(b) >= 999);
+
+        // Below the range only token A counts, above it only token B
+        assert_eq!(
+            get_liquidity_for_amounts_x64((0, 1000), lower, lower, upper),
+            0
+        );
+        assert_eq!(
+            get_liquidity_for_amounts_x64((1000, 0), upper, lower, upper),
+            0
+        );
+        let liquidity = get_liquidity_for_amounts_x64((0, 1000), upper, lower, upper);
+        assert_eq!(
+            get_amounts_for_liquidity_x64(liquidity, upper, lower, upper, false),
+            Ok((0, 1000))
+        );
+
+        assert_eq!(
+            get_liquidity_for_amounts_x64((1000, 1000), price, upper, upper),
+            0
+        );
+    }
+
     #[test]
     fn test_get_liquidity() {
         let sqrt_p_a = Decimal::from(1);
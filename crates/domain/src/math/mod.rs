@@ -6,6 +6,7 @@
 //! - Price/tick conversions
 //! - Fee calculations
 //! - Price impact estimation
+//! - Verification against Whirlpool reference vectors
 
 /// Concentrated liquidity math.
 pub mod concentrated_liquidity;
@@ -17,3 +18,5 @@ pub mod fee_math;
 pub mod price_impact;
 /// Price tick conversions.
 pub mod price_tick;
+/// Verification against Whirlpool reference vectors.
+pub mod verification;
@@ -1,6 +1,63 @@
+use primitive_types::U256;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
+/// Lowest tick of a Whirlpool.
+pub const MIN_TICK_INDEX: i32 = -443_636;
+
+/// Highest tick of a Whirlpool.
+pub const MAX_TICK_INDEX: i32 = 443_636;
+
+/// Q64.64 sqrt price at [`MIN_TICK_INDEX`].
+pub const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+
+/// Q64.64 sqrt price at [`MAX_TICK_INDEX`].
+pub const MAX_SQRT_PRICE_X64: u128 = 79_226_673_515_401_279_992_447_579_055;
+
+/// `sqrt(1.0001^(2^i))` in Q32.96 for each bit `i` of a positive tick.
+const POSITIVE_TICK_FACTORS_X96: [u128; 18] = [
+    79_236_085_330_515_764_027_303_304_731,
+    79_244_008_939_048_815_603_706_035_061,
+    79_259_858_533_276_714_757_314_932_305,
+    79_291_567_232_598_584_799_939_703_904,
+    79_355_022_692_464_371_645_785_046_466,
+    79_482_085_999_252_804_386_437_311_141,
+    79_736_823_300_114_093_921_829_183_326,
+    80_248_749_790_819_932_309_965_073_892,
+    81_282_483_887_344_747_381_513_967_011,
+    83_390_072_131_320_151_908_154_831_281,
+    87_770_609_709_833_776_024_991_924_138,
+    97_234_110_755_111_693_312_479_820_773,
+    119_332_217_159_966_728_226_237_229_890,
+    179_736_315_981_702_064_433_883_588_727,
+    407_748_233_172_238_350_107_850_275_304,
+    2_098_478_828_474_011_932_436_660_412_517,
+    55_581_415_166_113_811_149_459_800_483_533,
+    38_992_368_544_603_139_932_233_054_999_993_551,
+];
+
+/// `1 / sqrt(1.0001^(2^i))` in Q64.64 for each bit `i` of a negative tick.
+const NEGATIVE_TICK_FACTORS_X64: [u128; 18] = [
+    18_444_899_583_751_176_498,
+    18_443_055_278_223_354_162,
+    18_439_367_220_385_604_838,
+    18_431_993_317_065_449_817,
+    18_417_254_355_718_160_513,
+    18_387_811_781_193_591_352,
+    18_329_067_761_203_520_168,
+    18_212_142_134_806_087_854,
+    17_980_523_815_641_551_639,
+    17_526_086_738_831_147_013,
+    16_651_378_430_235_024_244,
+    15_030_750_278_693_429_944,
+    12_247_334_978_882_834_399,
+    8_131_365_268_884_726_200,
+    3_584_323_654_723_342_297,
+    696_457_651_847_595_233,
+    26_294_789_957_452_057,
+    37_481_735_321_082,
+];
+
 /// Returns the price corresponding to a given tick.
 /// P = 1.0001 ^ tick
 pub fn tick_to_price(tick: i32) -> Result<Decimal, &'static str> {
@@ -9,6 +66,45 @@ pub fn tick_to_price(tick: i32) -> Result<Decimal, &'static str> {
     Decimal::from_f64(price_f64).ok_or("Overflow converting price")
 }
 
+/// Returns the Q64.64 sqrt price of a tick, computed exactly as the
+/// Whirlpool program's `sqrt_price_from_tick_index`.
+///
+/// The price is built from one precomputed factor per bit of the tick, so it
+/// matches on-chain values bit for bit where `tick_to_price` only
+/// approximates them.
+pub fn tick_to_sqrt_price_x64(tick: i32) -> Result<u128, &'static str> {
+    if !(MIN_TICK_INDEX..=MAX_TICK_INDEX).contains(&tick) {
+        return Err("Tick out of bounds");
+    }
+
+    if tick >= 0 {
+        let mut ratio: u128 = if tick & 1 != 0 {
+            79_232_123_823_359_799_118_286_999_567
+        } else {
+            1 << 96
+        };
+        for (bit, factor) in POSITIVE_TICK_FACTORS_X96.iter().enumerate() {
+            if tick & (2 << bit) != 0 {
+                ratio = ((U256::from(ratio) * U256::from(*factor)) >> 96).as_u128();
+            }
+        }
+        Ok(ratio >> 32)
+    } else {
+        let abs_tick = tick.unsigned_abs();
+        let mut ratio: u128 = if abs_tick & 1 != 0 {
+            18_445_821_805_675_392_311
+        } else {
+            1 << 64
+        };
+        for (bit, factor) in NEGATIVE_TICK_FACTORS_X64.iter().enumerate() {
+            if abs_tick & (2 << bit) != 0 {
+                ratio = (ratio * factor) >> 64;
+            }
+        }
+        Ok(ratio)
+    }
+}
+
 /// Returns the tick corresponding to a given price.
 /// tick = log_1.0001(P)
 pub fn price_to_tick(price: Decimal) -> Result<i32, &'static str> {
@@ -39,6 +135,26 @@ mod tests {
         assert!(diff < 0.000001);
     }
 
+    #[test]
+    fn test_tick_to_sqrt_price_x64() {
+        assert_eq!(tick_to_sqrt_price_x64(0), Ok(1 << 64));
+        assert_eq!(
+            tick_to_sqrt_price_x64(MIN_TICK_INDEX),
+            Ok(MIN_SQRT_PRICE_X64)
+        );
+        assert_eq!(
+            tick_to_sqrt_price_x64(MAX_TICK_INDEX),
+            Ok(MAX_SQRT_PRICE_X64)
+        );
+        assert!(tick_to_sqrt_price_x64(MAX_TICK_INDEX + 1).is_err());
+        assert!(tick_to_sqrt_price_x64(MIN_TICK_INDEX - 1).is_err());
+
+        // Strictly increasing across the sign change
+        let below = tick_to_sqrt_price_x64(-1).unwrap();
+        let above = tick_to_sqrt_price_x64(1).unwrap();
+        assert!(below < 1 << 64 && 1 << 64 < above);
+    }
+
     #[test]
     fn test_price_to_tick() {
         let t = price_to_tick(Decimal::from(1)).unwrap();
@@ -0,0 +1,372 @@
+//! Verification of the domain math against Orca Whirlpool reference vectors.
+//!
+//! `fixtures/whirlpool_vectors.txt` holds outputs of the Whirlpool SDK's
+//! Q64.64 fixed-point math for tick, price, liquidity and fee calculations.
+//! [`verify_whirlpool_vectors`] runs the domain's implementations against
+//! them and reports every divergence beyond tolerance. The integer sqrt
+//! price and amount routines execution relies on are held to within a few
+//! units of the reference. Executors call
+//! [`debug_verify`] at startup so that debug builds flag divergence before
+//! real execution relies on this math.
+
+use super::concentrated_liquidity::{
+    get_amount_a_delta_x64, get_amount_b_delta_x64, get_amount0_delta, get_amount1_delta,
+};
+use super::fee_math::{calculate_effective_fee_rate, calculate_fee_amount};
+use super::price_tick::{price_to_tick, tick_to_price, tick_to_sqrt_price_x64};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Reference vectors shipped with the crate.
+const WHIRLPOOL_VECTORS: &str = include_str!("../../fixtures/whirlpool_vectors.txt");
+
+/// Relative tolerance for floating point prices and amounts.
+pub const RELATIVE_TOLERANCE: f64 = 1e-9;
+
+/// 2^64, the scale of Q64.64 sqrt prices.
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Units a Q64.64 sqrt price computed from a tick may differ from the exact
+/// floor in the fixture, as the Whirlpool SDK's does.
+pub const SQRT_PRICE_TOLERANCE_X64: u128 = 8;
+
+/// A reference vector the domain math does not reproduce.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// Name of the check that failed.
+    pub check: &'static str,
+    /// Line of the vector in the fixture.
+    pub line: usize,
+    /// Reference value.
+    pub expected: String,
+    /// Value computed by the domain.
+    pub actual: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (line {}): expected {}, got {}",
+            self.check, self.line, self.expected, self.actual
+        )
+    }
+}
+
+/// Result of checking the domain math against reference vectors.
+#[derive(Debug, Clone, Default)]
+pub struct VerificationReport {
+    /// Number of checks run.
+    pub checked: usize,
+    /// Checks whose result diverged from the reference.
+    pub divergences: Vec<Divergence>,
+}
+
+impl VerificationReport {
+    /// Returns true if every check matched its reference.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Records a check of `actual` against `expected`.
+    fn check(
+        &mut self,
+        check: &'static str,
+        line: usize,
+        matches: bool,
+        expected: impl ToString,
+        actual: impl ToString,
+    ) {
+        self.checked += 1;
+        if !matches {
+            self.divergences.push(Divergence {
+                check,
+                line,
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+    }
+}
+
+/// Checks the domain math against the shipped Whirlpool reference vectors.
+#[must_use]
+pub fn verify_whirlpool_vectors() -> VerificationReport {
+    verify(WHIRLPOOL_VECTORS)
+}
+
+/// Checks the shipped vectors once per process in debug builds.
+///
+/// Returns `None` in release builds, where the check is skipped.
+#[must_use]
+pub fn debug_verify() -> Option<&'static VerificationReport> {
+    static REPORT: OnceLock<VerificationReport> = OnceLock::new();
+    cfg!(debug_assertions).then(|| REPORT.get_or_init(verify_whirlpool_vectors))
+}
+
+/// Checks the domain math against vectors in the fixture format.
+#[must_use]
+pub fn verify(vectors: &str) -> VerificationReport {
+    let mut report = VerificationReport::default();
+
+    for (index, line) in vectors.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let kind = fields.next().unwrap_or_default();
+        let values: Vec<&str> = fields.collect();
+        let checked = match (kind, values.as_slice()) {
+            ("tick", [tick, sqrt_price]) => parse(tick)
+                .zip(parse(sqrt_price))
+                .map(|(tick, sqrt_price)| check_tick(&mut report, line_number, tick, sqrt_price)),
+            ("amount", [lower, upper, liquidity, amount_a, amount_b]) => parse(lower)
+                .zip(parse(upper))
+                .zip(parse(liquidity))
+                .zip(parse(amount_a).zip(parse(amount_b)))
+                .map(|(((lower, upper), liquidity), amounts)| {
+                    check_amounts(&mut report, line_number, lower, upper, liquidity, amounts)
+                }),
+            ("pool", [sqrt_price, tick]) => parse(sqrt_price)
+                .zip(parse(tick))
+                .map(|(sqrt_price, tick)| check_pool(&mut report, line_number, sqrt_price, tick)),
+            ("fee", [fee_rate, protocol_fee_rate, amount, fee, protocol_fee]) => parse(fee_rate)
+                .zip(parse(protocol_fee_rate))
+                .zip(parse(amount))
+                .zip(parse(fee).zip(parse(protocol_fee)))
+                .map(|(((fee_rate, protocol_fee_rate), amount), fees)| {
+                    check_fee(
+                        &mut report,
+                        line_number,
+                        fee_rate,
+                        protocol_fee_rate,
+                        amount,
+                        fees,
+                    )
+                }),
+            _ => None,
+        };
+
+        if checked.is_none() {
+            report.check("fixture", line_number, false, "a valid vector", line);
+        }
+    }
+
+    report
+}
+
+/// Checks tick to price conversion in both directions.
+fn check_tick(report: &mut VerificationReport, line: usize, tick: i32, sqrt_price_x64: u128) {
+    let expected = sqrt_price_to_price(sqrt_price_x64);
+    let actual = tick_to_price(tick).ok().and_then(|p| p.to_f64());
+    report.check(
+        "tick_to_price",
+        line,
+        actual.is_some_and(|actual| close(actual, expected)),
+        expected,
+        format!("{actual:?}"),
+    );
+
+    let round_trip = Decimal::from_f64(expected)
+        .ok_or("Overflow")
+        .and_then(price_to_tick);
+    report.check(
+        "price_to_tick",
+        line,
+        round_trip == Ok(tick),
+        tick,
+        format!("{round_trip:?}"),
+    );
+
+    let actual = tick_to_sqrt_price_x64(tick);
+    report.check(
+        "tick_to_sqrt_price_x64",
+        line,
+        actual.is_ok_and(|actual| actual.abs_diff(sqrt_price_x64) <= SQRT_PRICE_TOLERANCE_X64),
+        sqrt_price_x64,
+        format!("{actual:?}"),
+    );
+}
+
+/// Checks token amounts for liquidity over a range.
+fn check_amounts(
+    report: &mut VerificationReport,
+    line: usize,
+    lower_x64: u128,
+    upper_x64: u128,
+    liquidity: u128,
+    (amount_a, amount_b): (u128, u128),
+) {
+    let lower = sqrt_price_to_decimal(lower_x64);
+    let upper = sqrt_price_to_decimal(upper_x64);
+
+    let actual = get_amount0_delta(liquidity, lower, upper).map(|a| a.0.as_u128());
+    report.check(
+        "get_amount0_delta",
+        line,
+        actual.is_ok_and(|actual| close_amount(actual, amount_a)),
+        amount_a,
+        format!("{actual:?}"),
+    );
+
+    let actual = get_amount1_delta(liquidity, lower, upper).map(|a| a.0.as_u128());
+    report.check(
+        "get_amount1_delta",
+        line,
+        actual.is_ok_and(|actual| close_amount(actual, amount_b)),
+        amount_b,
+        format!("{actual:?}"),
+    );
+
+    // The integer routines reproduce the program's rounding exactly
+    let actual = get_amount_a_delta_x64(liquidity, lower_x64, upper_x64, false);
+    report.check(
+        "get_amount_a_delta_x64",
+        line,
+        actual.is_ok_and(|actual| u128::from(actual) == amount_a),
+        amount_a,
+        format!("{actual:?}"),
+    );
+
+    let actual = get_amount_b_delta_x64(liquidity, lower_x64, upper_x64, false);
+    report.check(
+        "get_amount_b_delta_x64",
+        line,
+        actual.is_ok_and(|actual| u128::from(actual) == amount_b),
+        amount_b,
+        format!("{actual:?}"),
+    );
+}
+
+/// Checks that a pool's current tick brackets its sqrt price.
+fn check_pool(report: &mut VerificationReport, line: usize, sqrt_price_x64: u128, tick: i32) {
+    let price = sqrt_price_to_price(sqrt_price_x64);
+    let lower = tick_to_price(tick).ok().and_then(|p| p.to_f64());
+    let upper = tick_to_price(tick + 1).ok().and_then(|p| p.to_f64());
+    let brackets = lower
+        .zip(upper)
+        .is_some_and(|(lower, upper)| lower <= price * (1.0 + RELATIVE_TOLERANCE) && price < upper);
+    report.check(
+        "tick_current_index",
+        line,
+        brackets,
+        format!("[{lower:?}, {upper:?})"),
+        price,
+    );
+
+    // The domain rounds to the nearest tick where the pool floors
+    let nearest = Decimal::from_f64(price)
+        .ok_or("Overflow")
+        .and_then(price_to_tick);
+    report.check(
+        "price_to_tick",
+        line,
+        nearest.is_ok_and(|nearest| (nearest - tick).abs() <= 1),
+        tick,
+        format!("{nearest:?}"),
+    );
+}
+
+/// Checks the swap fee and the LP's share after protocol fees.
+fn check_fee(
+    report: &mut VerificationReport,
+    line: usize,
+    fee_rate: u32,
+    protocol_fee_rate: u32,
+    amount: u64,
+    (fee, protocol_fee): (u64, u64),
+) {
+    // Whirlpool fee rates are in hundredths of a basis point
+    let rate = Decimal::from(fee_rate) / Decimal::from(1_000_000);
+    let protocol_pct = Decimal::from(protocol_fee_rate) / Decimal::from(10_000);
+
+    let actual = calculate_fee_amount(Decimal::from(amount), rate).ceil();
+    report.check(
+        "calculate_fee_amount",
+        line,
+        actual == Decimal::from(fee),
+        fee,
+        actual,
+    );
+
+    let expected = Decimal::from(fee - protocol_fee);
+    let actual = Decimal::from(amount) * calculate_effective_fee_rate(rate, protocol_pct);
+    report.check(
+        "calculate_effective_fee_rate",
+        line,
+        (actual - expected).abs() <= Decimal::ONE,
+        expected,
+        actual,
+    );
+}
+
+/// Parses a fixture field.
+fn parse<T: FromStr>(value: &str) -> Option<T> {
+    value.parse().ok()
+}
+
+/// Converts a Q64.64 sqrt price to a price.
+fn sqrt_price_to_price(sqrt_price_x64: u128) -> f64 {
+    let sqrt_price = sqrt_price_x64 as f64 / Q64;
+    sqrt_price * sqrt_price
+}
+
+/// Converts a Q64.64 sqrt price to a decimal sqrt price.
+fn sqrt_price_to_decimal(sqrt_price_x64: u128) -> Decimal {
+    Decimal::from(sqrt_price_x64) / Decimal::from(1u128 << 64)
+}
+
+/// Returns true if two values agree within [`RELATIVE_TOLERANCE`].
+fn close(actual: f64, expected: f64) -> bool {
+    (actual - expected).abs() <= expected.abs() * RELATIVE_TOLERANCE
+}
+
+/// Returns true if two token amounts agree within one unit or
+/// [`RELATIVE_TOLERANCE`].
+fn close_amount(actual: u128, expected: u128) -> bool {
+    actual.abs_diff(expected) <= 1 || close(actual as f64, expected as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_vectors_match() {
+        let report = verify_whirlpool_vectors();
+        assert!(report.checked > 50);
+        assert!(report.is_ok(), "{:#?}", report.divergences);
+        assert!(debug_verify().is_some_and(VerificationReport::is_ok));
+    }
+
+    #[test]
+    fn test_divergence_is_reported() {
+        let report = verify(
+            "# wrong sqrt price for tick 0\n\
+             tick 0 18539204128674405812\n\
+             fee 3000 300 1000000 2999 90\n\
+             bogus 1 2\n",
+        );
+
+        assert_eq!(report.checked, 6);
+        let checks: Vec<_> = report.divergences.iter().map(|d| d.check).collect();
+        assert_eq!(
+            checks,
+            [
+                "tick_to_price",
+                "price_to_tick",
+                "tick_to_sqrt_price_x64",
+                "calculate_fee_amount",
+                "fixture"
+            ]
+        );
+        assert_eq!(report.divergences[0].line, 2);
+    }
+}
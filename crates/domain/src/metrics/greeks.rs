@@ -0,0 +1,131 @@
+//! Position greeks for concentrated liquidity.
+//!
+//! Delta and gamma describe how a position's value responds to price moves
+//! and are used to size hedges.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+
+/// Price sensitivities of a concentrated liquidity position.
+///
+/// Amounts are in the same units as the liquidity and prices passed in, so
+/// raw on-chain values give raw token amounts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionGreeks {
+    /// Change in position value per unit change in price (token0 held).
+    pub delta: Decimal,
+    /// Change in delta per unit change in price (zero or negative).
+    pub gamma: Decimal,
+    /// Position value in token1.
+    pub value: Decimal,
+}
+
+impl PositionGreeks {
+    /// Returns the share of position value exposed to token0 price moves.
+    ///
+    /// This is `delta * price / value`: 1.0 when the position is all token0
+    /// and 0.0 when it is all token1. It does not depend on token decimals,
+    /// so it can be computed from raw prices and applied to a USD value.
+    #[must_use]
+    pub fn delta_share(&self, price: Decimal) -> Decimal {
+        if self.value.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.delta * price / self.value
+    }
+}
+
+/// Calculates the delta and gamma of a concentrated liquidity position.
+///
+/// # Arguments
+///
+/// * `liquidity` - The position liquidity
+/// * `current_price` - The current price (token1/token0)
+/// * `price_lower` - Lower bound of the range
+/// * `price_upper` - Upper bound of the range
+///
+/// # Returns
+///
+/// * `PositionGreeks` - Delta is the token0 amount held, which is zero above
+///   the range and constant below it; gamma is only non-zero in range.
+pub fn calculate_position_greeks(
+    liquidity: Decimal,
+    current_price: Decimal,
+    price_lower: Decimal,
+    price_upper: Decimal,
+) -> Result<PositionGreeks, &'static str> {
+    if current_price <= Decimal::ZERO || price_lower <= Decimal::ZERO {
+        return Err("Prices must be positive");
+    }
+    if price_lower >= price_upper {
+        return Err("Invalid range");
+    }
+
+    let l = liquidity.to_f64().ok_or("Overflow converting to f64")?;
+    let p = current_price.to_f64().ok_or("Overflow converting to f64")?;
+    let sqrt_p = p.sqrt();
+    let sqrt_a = price_lower
+        .to_f64()
+        .ok_or("Overflow converting to f64")?
+        .sqrt();
+    let sqrt_b = price_upper
+        .to_f64()
+        .ok_or("Overflow converting to f64")?
+        .sqrt();
+
+    // x = token0 amount, y = token1 amount
+    let (x, y, gamma) = if sqrt_p <= sqrt_a {
+        (l * (1.0 / sqrt_a - 1.0 / sqrt_b), 0.0, 0.0)
+    } else if sqrt_p >= sqrt_b {
+        (0.0, l * (sqrt_b - sqrt_a), 0.0)
+    } else {
+        (
+            l * (1.0 / sqrt_p - 1.0 / sqrt_b),
+            l * (sqrt_p - sqrt_a),
+            // d/dP of L(1/sqrt(P) - 1/sqrt(Pb))
+            -l / (2.0 * p * sqrt_p),
+        )
+    };
+
+    let to_decimal = |v: f64| Decimal::from_f64(v).ok_or("Overflow converting result");
+
+    Ok(PositionGreeks {
+        delta: to_decimal(x)?,
+        gamma: to_decimal(gamma)?,
+        value: to_decimal(x * p + y)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_position_greeks() {
+        let l = Decimal::from(1000);
+        let lower = Decimal::from(81);
+        let upper = Decimal::from(121);
+
+        // In range at P = 100: x = 1000 * (1/10 - 1/11), y = 1000 * (10 - 9)
+        let greeks = calculate_position_greeks(l, Decimal::from(100), lower, upper).unwrap();
+        let expected_delta = 1000.0 * (0.1 - 1.0 / 11.0);
+        assert!((greeks.delta.to_f64().unwrap() - expected_delta).abs() < 1e-9);
+        // gamma = -L / (2 * P^1.5) = -1000 / 2000
+        assert!((greeks.gamma.to_f64().unwrap() + 0.5).abs() < 1e-9);
+        let share = greeks.delta_share(Decimal::from(100)).to_f64().unwrap();
+        assert!(share > 0.45 && share < 0.48);
+
+        // Below range: all token0, constant delta
+        let below = calculate_position_greeks(l, Decimal::from(50), lower, upper).unwrap();
+        assert!(below.gamma.is_zero());
+        let share = below.delta_share(Decimal::from(50)).to_f64().unwrap();
+        assert!((share - 1.0).abs() < 1e-9);
+
+        // Above range: all token1, no delta
+        let above = calculate_position_greeks(l, Decimal::from(200), lower, upper).unwrap();
+        assert!(above.delta.is_zero());
+        assert!(above.delta_share(Decimal::from(200)).is_zero());
+
+        assert!(calculate_position_greeks(l, Decimal::from(100), upper, lower).is_err());
+    }
+}
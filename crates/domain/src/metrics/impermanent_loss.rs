@@ -1,4 +1,5 @@
 use crate::math::concentrated_liquidity;
+use crate::token::TokenAmount;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
@@ -48,87 +49,128 @@ pub fn calculate_il_concentrated(
     price_lower: Decimal,
     price_upper: Decimal,
 ) -> Result<Decimal, &'static str> {
-    if entry_price.is_zero() || price_lower.is_zero() || price_upper.is_zero() {
-        return Err("Prices must be non-zero");
+    ConcentratedIl::new(entry_price, price_lower, price_upper)?.at(current_price)
+}
+
+/// Arbitrary liquidity to simulate amounts.
+/// Using a large number to avoid small number precision issues with integer TokenAmount.
+const IL_LIQUIDITY: u128 = 1_000_000_000_000_000_000; // 1e18
+
+/// Impermanent loss of a concentrated position with a fixed entry and range.
+///
+/// Everything that does not depend on the current price is computed once,
+/// so evaluating many prices against the same range is cheap. Results are
+/// identical to [`calculate_il_concentrated`].
+#[derive(Debug, Clone)]
+pub struct ConcentratedIl {
+    sqrt_lower: Decimal,
+    sqrt_upper: Decimal,
+    /// Amounts held at entry.
+    held: (Decimal, Decimal),
+    /// Amount of token X when the price is below the range.
+    below: Decimal,
+    /// Amount of token Y when the price is above the range.
+    above: Decimal,
+}
+
+impl ConcentratedIl {
+    /// Prepares the calculation for a position opened at `entry_price`.
+    ///
+    /// # Errors
+    /// Returns an error if a price is zero or the range is empty.
+    pub fn new(
+        entry_price: Decimal,
+        price_lower: Decimal,
+        price_upper: Decimal,
+    ) -> Result<Self, &'static str> {
+        if entry_price.is_zero() || price_lower.is_zero() || price_upper.is_zero() {
+            return Err("Prices must be non-zero");
+        }
+        if price_lower >= price_upper {
+            return Err("Invalid range");
+        }
+
+        let sqrt_lower = sqrt(price_lower)?;
+        let sqrt_upper = sqrt(price_upper)?;
+
+        // If P < Lower: The curve segment is "above" us. We hold amount0 required to
+        // cross [Lower, Upper], i.e., we are full in X. If P > Upper we are full in Y.
+        let below = amount_to_decimal(concentrated_liquidity::get_amount0_delta(
+            IL_LIQUIDITY,
+            sqrt_lower,
+            sqrt_upper,
+        )?)?;
+        let above = amount_to_decimal(concentrated_liquidity::get_amount1_delta(
+            IL_LIQUIDITY,
+            sqrt_lower,
+            sqrt_upper,
+        )?)?;
+
+        let mut il = Self {
+            sqrt_lower,
+            sqrt_upper,
+            held: (Decimal::ZERO, Decimal::ZERO),
+            below,
+            above,
+        };
+        // For IL calculation, we assume the position was created *at* entry_price.
+        il.held = il.amounts(sqrt(entry_price)?)?;
+        Ok(il)
     }
-    if price_lower >= price_upper {
-        return Err("Invalid range");
+
+    /// Returns the impermanent loss at `current_price`.
+    ///
+    /// # Errors
+    /// Returns an error if the price cannot be converted for the square root.
+    pub fn at(&self, current_price: Decimal) -> Result<Decimal, &'static str> {
+        let (x0, y0) = self.held;
+        let (x1, y1) = self.amounts(sqrt(current_price)?)?;
+
+        // Value Held: The initial bundle (x0, y0) valued at current_price
+        let value_held = x0 * current_price + y0;
+
+        // Value LP: The current bundle (x1, y1) valued at current_price
+        let value_lp = x1 * current_price + y1;
+
+        if value_held.is_zero() {
+            // If we held nothing, no loss/gain reference. (Should not happen with non-zero liq)
+            return Ok(Decimal::ZERO);
+        }
+
+        let il = (value_lp - value_held) / value_held;
+        Ok(il)
     }
 
-    // Arbitrary liquidity to simulate amounts.
-    // Using a large number to avoid small number precision issues with integer TokenAmount.
-    let liquidity = 1_000_000_000_000_000_000u128; // 1e18
-
-    let sqrt = |p: Decimal| -> Result<Decimal, &'static str> {
-        let f = p.to_f64().ok_or("Overflow")?;
-        Decimal::from_f64(f.sqrt()).ok_or("Overflow")
-    };
-
-    let sqrt_entry = sqrt(entry_price)?;
-    let sqrt_curr = sqrt(current_price)?;
-    let sqrt_lower = sqrt(price_lower)?;
-    let sqrt_upper = sqrt(price_upper)?;
-
-    // 1. Calculate Initial Amounts (Held)
-    // We need to know "active price" at entry to determine amounts.
-    // If entry < lower, all X. If entry > upper, all Y. If in range, mix.
-    // However, for IL calculation, we assume the position was created *at* entry_price.
-    // So we use the standard liquidity formulas with entry_price as the "current" price for initial state.
-
-    // BUT wait: get_amount functions take a range.
-    // For amount0: range is [max(current, lower), upper]? No.
-    // Uniswap logic:
-    // if P < lower: amounts are determined by range [lower, upper] assuming P is below. All X.
-    // actually, standard delta formulas work if we pass the range correctly.
-
-    // Let's use a helper to get amounts at a specific price P for range [Lower, Upper]
-    let get_amounts = |p_sqrt: Decimal| -> Result<(Decimal, Decimal), &'static str> {
-        let mut amt0 = Decimal::ZERO;
-        let mut amt1 = Decimal::ZERO;
-
-        // If P < Lower: Price is below range. Position is all Token0 (X).
-        // Effectively P_current = Lower for the purpose of logic? No.
-        // Standard logic:
-        // Liquidity is active only in [Lower, Upper].
-        // If P < Lower: The curve segment is "above" us. We hold amount0 required to cross [Lower, Upper].
-        // i.e., we are full in X.
-
-        if p_sqrt < sqrt_lower {
-            // Full range crossing for X
-            let a0 = concentrated_liquidity::get_amount0_delta(liquidity, sqrt_lower, sqrt_upper)?;
-            amt0 = Decimal::from_str(&a0.0.to_string()).unwrap();
-        } else if p_sqrt >= sqrt_upper {
-            // Price > Upper. Position is all Token1 (Y).
-            let a1 = concentrated_liquidity::get_amount1_delta(liquidity, sqrt_lower, sqrt_upper)?;
-            amt1 = Decimal::from_str(&a1.0.to_string()).unwrap();
+    /// Returns the token amounts at a specific price P for range [Lower, Upper].
+    fn amounts(&self, p_sqrt: Decimal) -> Result<(Decimal, Decimal), &'static str> {
+        if p_sqrt < self.sqrt_lower {
+            Ok((self.below, Decimal::ZERO))
+        } else if p_sqrt >= self.sqrt_upper {
+            Ok((Decimal::ZERO, self.above))
         } else {
-            // In range.
             // X part: from P to Upper
-            let a0 = concentrated_liquidity::get_amount0_delta(liquidity, p_sqrt, sqrt_upper)?;
-            amt0 = Decimal::from_str(&a0.0.to_string()).unwrap();
+            let a0 =
+                concentrated_liquidity::get_amount0_delta(IL_LIQUIDITY, p_sqrt, self.sqrt_upper)?;
             // Y part: from Lower to P
-            let a1 = concentrated_liquidity::get_amount1_delta(liquidity, sqrt_lower, p_sqrt)?;
-            amt1 = Decimal::from_str(&a1.0.to_string()).unwrap();
+            let a1 =
+                concentrated_liquidity::get_amount1_delta(IL_LIQUIDITY, self.sqrt_lower, p_sqrt)?;
+            Ok((amount_to_decimal(a0)?, amount_to_decimal(a1)?))
         }
-        Ok((amt0, amt1))
-    };
-
-    let (x0, y0) = get_amounts(sqrt_entry)?;
-    let (x1, y1) = get_amounts(sqrt_curr)?;
-
-    // Value Held: The initial bundle (x0, y0) valued at current_price
-    let value_held = x0 * current_price + y0;
-
-    // Value LP: The current bundle (x1, y1) valued at current_price
-    let value_lp = x1 * current_price + y1;
-
-    if value_held.is_zero() {
-        // If we held nothing, no loss/gain reference. (Should not happen with non-zero liq)
-        return Ok(Decimal::ZERO);
     }
+}
+
+/// Square root through `f64`, as IL is an estimation.
+fn sqrt(p: Decimal) -> Result<Decimal, &'static str> {
+    let f = p.to_f64().ok_or("Overflow")?;
+    Decimal::from_f64(f.sqrt()).ok_or("Overflow")
+}
 
-    let il = (value_lp - value_held) / value_held;
-    Ok(il)
+/// Converts a token amount to a `Decimal` without formatting it as a string.
+fn amount_to_decimal(amount: TokenAmount) -> Result<Decimal, &'static str> {
+    u128::try_from(amount.0)
+        .ok()
+        .and_then(Decimal::from_u128)
+        .ok_or("Overflow converting amount")
 }
 
 #[cfg(test)]
@@ -174,4 +216,21 @@ mod tests {
         let il_up = calculate_il_concentrated(entry, curr_up, lower, upper).unwrap();
         assert!(il_up < Decimal::ZERO);
     }
+
+    #[test]
+    fn test_concentrated_il_matches_function() {
+        let entry = Decimal::from(100);
+        let lower = Decimal::from(90);
+        let upper = Decimal::from(110);
+        let il = ConcentratedIl::new(entry, lower, upper).unwrap();
+
+        for price in [80, 90, 95, 100, 105, 110, 130] {
+            let price = Decimal::from(price);
+            assert_eq!(
+                il.at(price).unwrap(),
+                calculate_il_concentrated(entry, price, lower, upper).unwrap()
+            );
+        }
+        assert!(ConcentratedIl::new(entry, upper, lower).is_err());
+    }
 }
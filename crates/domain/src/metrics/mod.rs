@@ -2,9 +2,13 @@
 
 /// Fee related metrics.
 pub mod fees;
+/// Position greeks.
+pub mod greeks;
 /// Impermanent loss metrics.
 pub mod impermanent_loss;
 /// Metric types.
 mod types;
+/// Volatility estimators and cones.
+pub mod volatility;
 
 pub use types::{APY, ImpermanentLoss, PnL};
@@ -0,0 +1,389 @@
+//! Volatility estimation from price history.
+//!
+//! Besides the plain standard deviation of log returns, EWMA and GARCH(1,1)
+//! estimators weight recent returns more heavily, so they react to volatility
+//! regimes that a long flat window averages away. The volatility cone puts
+//! the current realized volatility in the context of its own history.
+//!
+//! Returns are per period of the price series and volatilities are
+//! annualized with `periods_per_year` (8,760 for hourly prices).
+
+use crate::value_objects::VolatilityEstimate;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// RiskMetrics decay factor for daily returns.
+pub const DEFAULT_EWMA_LAMBDA: f64 = 0.94;
+
+/// Method used to estimate volatility.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "method")]
+pub enum VolatilityEstimator {
+    /// Standard deviation of all log returns.
+    #[default]
+    Stdev,
+    /// Exponentially weighted moving average of squared returns.
+    Ewma {
+        /// Decay factor between 0 and 1; higher values remember longer.
+        lambda: f64,
+    },
+    /// GARCH(1,1) fitted to the returns, forecast over a horizon.
+    Garch {
+        /// Forecast horizon in periods.
+        horizon: usize,
+    },
+}
+
+impl VolatilityEstimator {
+    /// Estimates the annualized volatility of a price series.
+    ///
+    /// Returns zero for fewer than two prices. GARCH falls back to the
+    /// standard deviation when it cannot be fitted.
+    #[must_use]
+    pub fn annualized(&self, prices: &[f64], periods_per_year: f64) -> f64 {
+        let returns = log_returns(prices);
+        let volatility = match *self {
+            Self::Stdev => stdev(&returns),
+            Self::Ewma { lambda } => ewma_volatility(&returns, lambda),
+            Self::Garch { horizon } => match Garch11::fit(&returns) {
+                Some(garch) => garch.forecast_volatility(&returns, horizon),
+                None => stdev(&returns),
+            },
+        };
+        volatility * periods_per_year.max(0.0).sqrt()
+    }
+
+    /// Estimates the annualized volatility of a price series, labelled with
+    /// the method used.
+    #[must_use]
+    pub fn estimate(&self, prices: &[f64], periods_per_year: f64) -> VolatilityEstimate {
+        VolatilityEstimate {
+            annualized_volatility: Decimal::from_f64(self.annualized(prices, periods_per_year))
+                .unwrap_or(Decimal::ZERO),
+            method: self.name(),
+        }
+    }
+
+    /// Describes the method and its parameters.
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self {
+            Self::Stdev => "stdev".to_string(),
+            Self::Ewma { lambda } => format!("ewma(lambda={})", lambda),
+            Self::Garch { horizon } => format!("garch(1,1, horizon={})", horizon),
+        }
+    }
+}
+
+/// Calculates the log returns of a price series, skipping non-positive
+/// prices.
+#[must_use]
+pub fn log_returns(prices: &[f64]) -> Vec<f64> {
+    prices
+        .windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect()
+}
+
+/// Calculates the standard deviation of returns per period.
+#[must_use]
+pub fn stdev(returns: &[f64]) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt()
+}
+
+/// Calculates the EWMA volatility per period after the last return.
+///
+/// The variance starts at the first squared return and is updated as
+/// `lambda * variance + (1 - lambda) * return^2`.
+#[must_use]
+pub fn ewma_volatility(returns: &[f64], lambda: f64) -> f64 {
+    let lambda = lambda.clamp(0.0, 1.0);
+    let Some((first, rest)) = returns.split_first() else {
+        return 0.0;
+    };
+    rest.iter()
+        .fold(first * first, |variance, r| {
+            lambda * variance + (1.0 - lambda) * r * r
+        })
+        .sqrt()
+}
+
+/// A GARCH(1,1) model of per-period variance:
+/// `variance = omega + alpha * return^2 + beta * previous variance`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Garch11 {
+    /// Constant term.
+    pub omega: f64,
+    /// Weight of the last squared return.
+    pub alpha: f64,
+    /// Weight of the last variance.
+    pub beta: f64,
+}
+
+impl Garch11 {
+    /// Minimum number of returns to fit a model.
+    pub const MIN_RETURNS: usize = 30;
+
+    /// Fits a model to returns by maximum likelihood.
+    ///
+    /// `omega` is targeted so the long-run variance equals the sample
+    /// variance, and `alpha` and `beta` are searched on a grid keeping the
+    /// model stationary. Returns `None` for fewer than
+    /// [`MIN_RETURNS`](Self::MIN_RETURNS) returns or constant returns.
+    #[must_use]
+    pub fn fit(returns: &[f64]) -> Option<Self> {
+        if returns.len() < Self::MIN_RETURNS {
+            return None;
+        }
+        let variance = stdev(returns).powi(2);
+        if variance <= 0.0 {
+            return None;
+        }
+
+        let mut best: Option<(f64, Self)> = None;
+        for alpha in (1..=30).map(|i| f64::from(i) * 0.01) {
+            for beta in (50..=98).map(|i| f64::from(i) * 0.01) {
+                if alpha + beta >= 0.999 {
+                    continue;
+                }
+                let model = Self {
+                    omega: variance * (1.0 - alpha - beta),
+                    alpha,
+                    beta,
+                };
+                let likelihood = model.log_likelihood(returns, variance);
+                if best.is_none_or(|(best, _)| likelihood > best) {
+                    best = Some((likelihood, model));
+                }
+            }
+        }
+        best.map(|(_, model)| model)
+    }
+
+    /// Calculates the long-run variance per period the model reverts to.
+    #[must_use]
+    pub fn long_run_variance(&self) -> f64 {
+        let persistence = self.alpha + self.beta;
+        if persistence >= 1.0 {
+            return f64::INFINITY;
+        }
+        self.omega / (1.0 - persistence)
+    }
+
+    /// Calculates the variance forecast for the period after the returns.
+    #[must_use]
+    pub fn next_variance(&self, returns: &[f64]) -> f64 {
+        self.variances(returns, self.long_run_variance())
+            .last()
+            .copied()
+            .unwrap_or_else(|| self.long_run_variance())
+    }
+
+    /// Forecasts the average volatility per period over the next `horizon`
+    /// periods.
+    ///
+    /// Variance forecasts decay geometrically from the next period's towards
+    /// the long-run variance.
+    #[must_use]
+    pub fn forecast_volatility(&self, returns: &[f64], horizon: usize) -> f64 {
+        let long_run = self.long_run_variance();
+        let persistence = self.alpha + self.beta;
+        let mut deviation = self.next_variance(returns) - long_run;
+        let horizon = horizon.max(1);
+
+        let mut total = 0.0;
+        for _ in 0..horizon {
+            total += long_run + deviation;
+            deviation *= persistence;
+        }
+        (total / horizon as f64).max(0.0).sqrt()
+    }
+
+    /// Calculates the conditional variance after each return, starting from
+    /// `initial`.
+    fn variances(&self, returns: &[f64], initial: f64) -> Vec<f64> {
+        let mut variance = initial;
+        returns
+            .iter()
+            .map(|r| {
+                variance = self.omega + self.alpha * r * r + self.beta * variance;
+                variance
+            })
+            .collect()
+    }
+
+    /// Calculates the Gaussian log-likelihood of returns, up to a constant.
+    fn log_likelihood(&self, returns: &[f64], initial: f64) -> f64 {
+        let mut variance = initial;
+        let mut likelihood = 0.0;
+        for r in returns {
+            likelihood -= variance.ln() + r * r / variance;
+            variance = self.omega + self.alpha * r * r + self.beta * variance;
+        }
+        likelihood
+    }
+}
+
+/// Realized volatility over one window length in a volatility cone.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConePoint {
+    /// Window length in periods.
+    pub window: usize,
+    /// Lowest annualized volatility of any window.
+    pub min: f64,
+    /// 25th percentile.
+    pub p25: f64,
+    /// Median.
+    pub median: f64,
+    /// 75th percentile.
+    pub p75: f64,
+    /// Highest annualized volatility of any window.
+    pub max: f64,
+    /// Annualized volatility of the most recent window.
+    pub current: f64,
+}
+
+/// Calculates a realized volatility cone.
+///
+/// For each window length, the annualized volatility of every rolling window
+/// of returns is summarized by its range and quartiles. Window lengths longer
+/// than the history are skipped.
+#[must_use]
+pub fn volatility_cone(prices: &[f64], windows: &[usize], periods_per_year: f64) -> Vec<ConePoint> {
+    let returns = log_returns(prices);
+    let annualize = periods_per_year.max(0.0).sqrt();
+
+    windows
+        .iter()
+        .filter(|&&window| window >= 2 && window <= returns.len())
+        .map(|&window| {
+            let volatilities: Vec<f64> = returns
+                .windows(window)
+                .map(|w| stdev(w) * annualize)
+                .collect();
+            let current = volatilities.last().copied().unwrap_or(0.0);
+            let mut sorted = volatilities;
+            sorted.sort_by(f64::total_cmp);
+
+            ConePoint {
+                window,
+                min: sorted[0],
+                p25: percentile(&sorted, 25),
+                median: percentile(&sorted, 50),
+                p75: percentile(&sorted, 75),
+                max: sorted[sorted.len() - 1],
+                current,
+            }
+        })
+        .collect()
+}
+
+/// Returns the value at a percentile of sorted values.
+fn percentile(sorted: &[f64], percentile: usize) -> f64 {
+    sorted[(sorted.len() - 1) * percentile.min(100) / 100]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Prices following the given log returns from 100.
+    fn prices(returns: &[f64]) -> Vec<f64> {
+        let mut price = 100.0;
+        let mut prices = vec![price];
+        for r in returns {
+            price *= r.exp();
+            prices.push(price);
+        }
+        prices
+    }
+
+    /// Alternating returns of a size, with a standard deviation of that size.
+    fn alternating(size: f64, count: usize) -> Vec<f64> {
+        (0..count)
+            .map(|i| if i % 2 == 0 { size } else { -size })
+            .collect()
+    }
+
+    #[test]
+    fn test_stdev_estimator() {
+        let series = prices(&alternating(0.01, 100));
+        let volatility = VolatilityEstimator::Stdev.annualized(&series, 8760.0);
+        assert!((volatility - 0.01 * 8760f64.sqrt()).abs() < 1e-9);
+        assert_eq!(VolatilityEstimator::Stdev.annualized(&[100.0], 8760.0), 0.0);
+    }
+
+    #[test]
+    fn test_ewma_weights_recent_returns() {
+        // Calm, then a volatile regime
+        let mut returns = alternating(0.005, 200);
+        returns.extend(alternating(0.03, 20));
+
+        let ewma = ewma_volatility(&returns, DEFAULT_EWMA_LAMBDA);
+        assert!(ewma > stdev(&returns));
+        assert!(ewma < 0.03);
+        assert_eq!(ewma_volatility(&[], 0.94), 0.0);
+    }
+
+    #[test]
+    fn test_garch_fit_and_forecast() {
+        let mut returns = alternating(0.01, 300);
+        returns.extend(alternating(0.04, 10));
+
+        let garch = Garch11::fit(&returns).unwrap();
+        assert!(garch.alpha > 0.0 && garch.beta > 0.0);
+        assert!(garch.alpha + garch.beta < 1.0);
+        assert!((garch.long_run_variance() - stdev(&returns).powi(2)).abs() < 1e-12);
+
+        // After a shock the forecast starts high and reverts to the long run
+        let next = garch.forecast_volatility(&returns, 1);
+        let long = garch.forecast_volatility(&returns, 10_000);
+        assert!(next > long);
+        assert!((long - garch.long_run_variance().sqrt()).abs() < next - long);
+
+        assert!(Garch11::fit(&returns[..10]).is_none());
+        assert!(Garch11::fit(&[0.0; 50]).is_none());
+    }
+
+    #[test]
+    fn test_garch_estimator_falls_back_to_stdev() {
+        let series = prices(&alternating(0.01, 10));
+        assert_eq!(
+            VolatilityEstimator::Garch { horizon: 24 }.annualized(&series, 1.0),
+            VolatilityEstimator::Stdev.annualized(&series, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_volatility_cone() {
+        let mut returns = alternating(0.01, 100);
+        returns.extend(alternating(0.02, 24));
+        let series = prices(&returns);
+
+        let cone = volatility_cone(&series, &[24, 96, 1000], 1.0);
+        assert_eq!(cone.len(), 2);
+
+        let day = cone[0];
+        assert_eq!(day.window, 24);
+        assert!((day.min - 0.01).abs() < 1e-9);
+        assert!((day.max - 0.02).abs() < 1e-9);
+        assert!((day.current - 0.02).abs() < 1e-9);
+        assert!(day.min <= day.p25 && day.p25 <= day.median);
+        assert!(day.median <= day.p75 && day.p75 <= day.max);
+    }
+
+    #[test]
+    fn test_estimate_names_method() {
+        let series = prices(&alternating(0.01, 10));
+        let estimate = VolatilityEstimator::Ewma { lambda: 0.94 }.estimate(&series, 1.0);
+        assert_eq!(estimate.method, "ewma(lambda=0.94)");
+        assert!(estimate.annualized_volatility > Decimal::ZERO);
+    }
+}
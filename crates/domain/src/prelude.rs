@@ -41,8 +41,13 @@ pub use crate::metrics::fees::{
     calculate_breakeven_days, calculate_fee_efficiency, calculate_pool_fees,
     calculate_required_fee_rate, project_fees,
 };
+pub use crate::metrics::greeks::{PositionGreeks, calculate_position_greeks};
 pub use crate::metrics::impermanent_loss::{
-    calculate_il_concentrated, calculate_il_constant_product,
+    ConcentratedIl, calculate_il_concentrated, calculate_il_constant_product,
+};
+pub use crate::metrics::volatility::{
+    ConePoint, DEFAULT_EWMA_LAMBDA, Garch11, VolatilityEstimator, ewma_volatility, log_returns,
+    volatility_cone,
 };
 pub use crate::metrics::{APY, ImpermanentLoss, PnL};
 
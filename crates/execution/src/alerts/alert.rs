@@ -1,11 +1,12 @@
 //! Alert types and structures.
 
+use super::EscalationPolicy;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
 /// Alert severity level.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum AlertLevel {
     /// Informational alert.
     Info,
@@ -16,6 +17,16 @@ pub enum AlertLevel {
 }
 
 impl AlertLevel {
+    /// Returns a human-readable name for this alert level.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Warning => "Warning",
+            Self::Critical => "Critical",
+        }
+    }
+
     /// Returns the emoji for this alert level.
     #[must_use]
     pub fn emoji(&self) -> &'static str {
@@ -52,6 +63,12 @@ pub enum AlertType {
     FeesMilestone,
     /// Position needs rebalancing.
     RebalanceNeeded,
+    /// Stop-loss limit breached.
+    StopLoss,
+    /// Pool price diverged from the oracle price.
+    PriceDivergence,
+    /// Portfolio risk limit breached and the portfolio stop engaged.
+    PortfolioStop,
     /// System error occurred.
     SystemError,
     /// Connection issue.
@@ -71,6 +88,9 @@ impl AlertType {
             Self::PnLTarget => "PnL Target",
             Self::FeesMilestone => "Fees Milestone",
             Self::RebalanceNeeded => "Rebalance Needed",
+            Self::StopLoss => "Stop Loss",
+            Self::PriceDivergence => "Price Divergence",
+            Self::PortfolioStop => "Portfolio Stop",
             Self::SystemError => "System Error",
             Self::ConnectionIssue => "Connection Issue",
             Self::Custom(name) => name,
@@ -99,6 +119,9 @@ pub struct Alert {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     /// Whether the alert has been acknowledged.
     pub acknowledged: bool,
+    /// Escalation policy if the alert goes unacknowledged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation: Option<EscalationPolicy>,
 }
 
 impl Alert {
@@ -114,6 +137,7 @@ impl Alert {
             data: None,
             timestamp: chrono::Utc::now(),
             acknowledged: false,
+            escalation: None,
         }
     }
 
@@ -138,6 +162,13 @@ impl Alert {
         self
     }
 
+    /// Sets the escalation policy for this alert.
+    #[must_use]
+    pub fn with_escalation(mut self, policy: EscalationPolicy) -> Self {
+        self.escalation = Some(policy);
+        self
+    }
+
     /// Acknowledges this alert.
     pub fn acknowledge(&mut self) {
         self.acknowledged = true;
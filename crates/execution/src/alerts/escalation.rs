@@ -0,0 +1,418 @@
+//! Alert dispatch with acknowledgement-based escalation.
+
+use super::{Alert, AlertLevel, Notifier};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+/// Escalation policy for unacknowledged alerts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscalationPolicy {
+    /// Seconds to wait for acknowledgement before escalating.
+    pub after_secs: u64,
+    /// Name of the channel to escalate through.
+    pub channel: String,
+}
+
+impl EscalationPolicy {
+    /// Creates a new escalation policy.
+    pub fn new(after_secs: u64, channel: impl Into<String>) -> Self {
+        Self {
+            after_secs,
+            channel: channel.into(),
+        }
+    }
+}
+
+/// An alert tracked until it is acknowledged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedAlert {
+    /// The alert.
+    pub alert: Alert,
+    /// Escalation policy in effect.
+    pub policy: Option<EscalationPolicy>,
+    /// When the alert was escalated, if it has been.
+    pub escalated_at: Option<DateTime<Utc>>,
+    /// When the alert was acknowledged, if it has been.
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    /// Who acknowledged the alert.
+    pub acknowledged_by: Option<String>,
+}
+
+impl TrackedAlert {
+    /// Returns true if the alert is due for escalation.
+    #[must_use]
+    pub fn needs_escalation(&self, now: DateTime<Utc>) -> bool {
+        let Some(policy) = &self.policy else {
+            return false;
+        };
+        !self.alert.acknowledged
+            && self.escalated_at.is_none()
+            && (now - self.alert.timestamp).num_seconds() >= policy.after_secs as i64
+    }
+}
+
+/// Outcome of sending a test alert through a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelTestResult {
+    /// Channel name.
+    pub channel: String,
+    /// Whether the channel receives every alert or only escalations.
+    pub primary: bool,
+    /// Delivery error, if the alert could not be sent.
+    pub error: Option<String>,
+}
+
+/// Dispatches alerts to named channels and escalates unacknowledged ones.
+///
+/// Alerts are sent to every primary channel. Alerts with an escalation
+/// policy (their own, or the default for critical alerts) are re-sent
+/// through the policy's channel if not acknowledged in time.
+pub struct AlertDispatcher {
+    /// Notification channels by name.
+    channels: RwLock<HashMap<String, Arc<dyn Notifier>>>,
+    /// Channels that receive every alert.
+    primary: RwLock<Vec<String>>,
+    /// Policy for critical alerts without their own.
+    default_policy: RwLock<Option<EscalationPolicy>>,
+    /// Tracked alerts by ID.
+    alerts: RwLock<HashMap<String, TrackedAlert>>,
+    /// Maximum number of tracked alerts kept.
+    max_tracked: usize,
+}
+
+impl AlertDispatcher {
+    /// Creates a new dispatcher with no channels.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            channels: RwLock::new(HashMap::new()),
+            primary: RwLock::new(Vec::new()),
+            default_policy: RwLock::new(None),
+            alerts: RwLock::new(HashMap::new()),
+            max_tracked: 1000,
+        }
+    }
+
+    /// Registers a channel that receives every alert.
+    pub async fn add_primary(&self, name: impl Into<String>, notifier: Arc<dyn Notifier>) {
+        let name = name.into();
+        self.channels.write().await.insert(name.clone(), notifier);
+        self.primary.write().await.push(name);
+    }
+
+    /// Registers a channel used only for escalations.
+    pub async fn add_escalation_channel(
+        &self,
+        name: impl Into<String>,
+        notifier: Arc<dyn Notifier>,
+    ) {
+        self.channels.write().await.insert(name.into(), notifier);
+    }
+
+    /// Sets the escalation policy for critical alerts without their own.
+    pub async fn set_default_policy(&self, policy: EscalationPolicy) {
+        *self.default_policy.write().await = Some(policy);
+    }
+
+    /// Sends an alert to the primary channels and tracks it for acknowledgement.
+    pub async fn dispatch(&self, alert: Alert) {
+        let policy = match &alert.escalation {
+            Some(policy) => Some(policy.clone()),
+            None if alert.level == AlertLevel::Critical => self.default_policy.read().await.clone(),
+            None => None,
+        };
+
+        let primary = self.primary.read().await.clone();
+        for name in &primary {
+            self.send(name, &alert).await;
+        }
+
+        let mut alerts = self.alerts.write().await;
+        if alerts.len() >= self.max_tracked {
+            // Drop the oldest acknowledged alerts first
+            let mut done: Vec<_> = alerts
+                .values()
+                .filter(|t| t.alert.acknowledged)
+                .map(|t| (t.alert.timestamp, t.alert.id.clone()))
+                .collect();
+            done.sort();
+            for (_, id) in done.into_iter().take(alerts.len() + 1 - self.max_tracked) {
+                alerts.remove(&id);
+            }
+        }
+
+        alerts.insert(
+            alert.id.clone(),
+            TrackedAlert {
+                alert,
+                policy,
+                escalated_at: None,
+                acknowledged_at: None,
+                acknowledged_by: None,
+            },
+        );
+    }
+
+    /// Acknowledges an alert, stopping any pending escalation.
+    ///
+    /// Returns the updated alert, or `None` if it is not tracked.
+    pub async fn acknowledge(&self, id: &str, by: Option<String>) -> Option<TrackedAlert> {
+        let mut alerts = self.alerts.write().await;
+        let tracked = alerts.get_mut(id)?;
+
+        if !tracked.alert.acknowledged {
+            tracked.alert.acknowledge();
+            tracked.acknowledged_at = Some(Utc::now());
+            tracked.acknowledged_by = by;
+            info!(alert_id = %id, "Alert acknowledged");
+        }
+
+        Some(tracked.clone())
+    }
+
+    /// Gets a tracked alert.
+    pub async fn get(&self, id: &str) -> Option<TrackedAlert> {
+        self.alerts.read().await.get(id).cloned()
+    }
+
+    /// Gets tracked alerts, newest first.
+    pub async fn list(&self, unacknowledged_only: bool) -> Vec<TrackedAlert> {
+        let mut alerts: Vec<_> = self
+            .alerts
+            .read()
+            .await
+            .values()
+            .filter(|t| !unacknowledged_only || !t.alert.acknowledged)
+            .cloned()
+            .collect();
+        alerts.sort_by(|a, b| b.alert.timestamp.cmp(&a.alert.timestamp));
+        alerts
+    }
+
+    /// Escalates alerts whose acknowledgement window has passed.
+    ///
+    /// Returns the number of alerts escalated.
+    pub async fn check_escalations(&self, now: DateTime<Utc>) -> usize {
+        let due: Vec<(Alert, EscalationPolicy)> = {
+            let mut alerts = self.alerts.write().await;
+            alerts
+                .values_mut()
+                .filter(|t| t.needs_escalation(now))
+                .filter_map(|t| {
+                    t.escalated_at = Some(now);
+                    t.policy.clone().map(|p| (t.alert.clone(), p))
+                })
+                .collect()
+        };
+
+        for (alert, policy) in &due {
+            warn!(
+                alert_id = %alert.id,
+                channel = %policy.channel,
+                after_secs = policy.after_secs,
+                "Alert unacknowledged, escalating"
+            );
+
+            let mut escalated = alert.clone();
+            escalated.message = format!(
+                "[ESCALATED - unacknowledged for {}m] {}",
+                policy.after_secs / 60,
+                alert.message
+            );
+            self.send(&policy.channel, &escalated).await;
+        }
+
+        due.len()
+    }
+
+    /// Sends an alert through every channel, or one named channel, without
+    /// tracking it, and reports each delivery.
+    ///
+    /// Used to verify channel configuration. Results are sorted by channel
+    /// name; an unknown channel name yields no results.
+    pub async fn test_channels(
+        &self,
+        channel: Option<&str>,
+        alert: &Alert,
+    ) -> Vec<ChannelTestResult> {
+        let channels: Vec<(String, Arc<dyn Notifier>)> = self
+            .channels
+            .read()
+            .await
+            .iter()
+            .filter(|(name, _)| channel.is_none_or(|c| c == name.as_str()))
+            .map(|(name, notifier)| (name.clone(), notifier.clone()))
+            .collect();
+        let primary = self.primary.read().await.clone();
+
+        let mut results = Vec::with_capacity(channels.len());
+        for (name, notifier) in channels {
+            let error = notifier.notify(alert).await.err().map(|e| e.to_string());
+            info!(channel = %name, delivered = error.is_none(), "Test alert sent");
+            results.push(ChannelTestResult {
+                primary: primary.contains(&name),
+                channel: name,
+                error,
+            });
+        }
+        results.sort_by(|a, b| a.channel.cmp(&b.channel));
+        results
+    }
+
+    /// Runs escalation checks on an interval until the task is dropped.
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            self.check_escalations(Utc::now()).await;
+        }
+    }
+
+    /// Sends an alert through a named channel.
+    async fn send(&self, name: &str, alert: &Alert) {
+        let notifier = self.channels.read().await.get(name).cloned();
+        let Some(notifier) = notifier else {
+            error!(channel = %name, "Unknown alert channel");
+            return;
+        };
+
+        if let Err(e) = notifier.notify(alert).await {
+            error!(
+                channel = %name,
+                notifier = notifier.name(),
+                alert_id = %alert.id,
+                error = %e,
+                "Failed to send notification"
+            );
+        }
+    }
+}
+
+impl Default for AlertDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertType;
+    use async_trait::async_trait;
+
+    /// Notifier that records received messages.
+    #[derive(Default)]
+    struct RecordingNotifier {
+        messages: RwLock<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Notifier for RecordingNotifier {
+        async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+            self.messages.write().await.push(alert.message.clone());
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+    }
+
+    async fn dispatcher() -> (
+        AlertDispatcher,
+        Arc<RecordingNotifier>,
+        Arc<RecordingNotifier>,
+    ) {
+        let primary = Arc::new(RecordingNotifier::default());
+        let pager = Arc::new(RecordingNotifier::default());
+        let dispatcher = AlertDispatcher::new();
+        dispatcher.add_primary("telegram", primary.clone()).await;
+        dispatcher
+            .add_escalation_channel("pager", pager.clone())
+            .await;
+        (dispatcher, primary, pager)
+    }
+
+    #[tokio::test]
+    async fn test_unacknowledged_alert_escalates_once() {
+        let (dispatcher, primary, pager) = dispatcher().await;
+        let alert = Alert::new(AlertLevel::Warning, AlertType::RangeExit, "Out of range")
+            .with_escalation(EscalationPolicy::new(600, "pager"));
+        let sent_at = alert.timestamp;
+        dispatcher.dispatch(alert).await;
+
+        assert_eq!(primary.messages.read().await.len(), 1);
+        assert_eq!(
+            dispatcher
+                .check_escalations(sent_at + chrono::Duration::seconds(60))
+                .await,
+            0
+        );
+
+        let later = sent_at + chrono::Duration::seconds(601);
+        assert_eq!(dispatcher.check_escalations(later).await, 1);
+        assert_eq!(dispatcher.check_escalations(later).await, 0);
+
+        let pages = pager.messages.read().await;
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].starts_with("[ESCALATED"));
+    }
+
+    #[tokio::test]
+    async fn test_test_channels_reports_each_channel() {
+        let (dispatcher, primary, pager) = dispatcher().await;
+        let alert = Alert::new(AlertLevel::Info, AlertType::SystemError, "Test");
+
+        let results = dispatcher.test_channels(None, &alert).await;
+        let names: Vec<_> = results.iter().map(|r| r.channel.as_str()).collect();
+        assert_eq!(names, vec!["pager", "telegram"]);
+        assert!(!results[0].primary && results[1].primary);
+        assert!(results.iter().all(|r| r.error.is_none()));
+        assert_eq!(primary.messages.read().await.len(), 1);
+        assert_eq!(pager.messages.read().await.len(), 1);
+
+        assert_eq!(
+            dispatcher.test_channels(Some("pager"), &alert).await.len(),
+            1
+        );
+        assert!(
+            dispatcher
+                .test_channels(Some("sms"), &alert)
+                .await
+                .is_empty()
+        );
+        // Test alerts are not tracked
+        assert!(dispatcher.list(false).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_acknowledged_alert_does_not_escalate() {
+        let (dispatcher, _, pager) = dispatcher().await;
+        dispatcher
+            .set_default_policy(EscalationPolicy::new(60, "pager"))
+            .await;
+
+        let alert = Alert::new(AlertLevel::Critical, AlertType::SystemError, "Failed");
+        let id = alert.id.clone();
+        let sent_at = alert.timestamp;
+        dispatcher.dispatch(alert).await;
+
+        let tracked = dispatcher
+            .acknowledge(&id, Some("ops".to_string()))
+            .await
+            .unwrap();
+        assert!(tracked.alert.acknowledged);
+        assert_eq!(tracked.acknowledged_by.as_deref(), Some("ops"));
+        assert!(dispatcher.list(true).await.is_empty());
+
+        let later = sent_at + chrono::Duration::seconds(120);
+        assert_eq!(dispatcher.check_escalations(later).await, 0);
+        assert!(pager.messages.read().await.is_empty());
+        assert!(dispatcher.acknowledge("missing", None).await.is_none());
+    }
+}
@@ -0,0 +1,485 @@
+//! Expression language for custom alert conditions.
+//!
+//! Expressions compare monitor state against literals and combine the
+//! comparisons with boolean operators:
+//!
+//! ```text
+//! il_pct > 3% && time_out_of_range > 2h
+//! !in_range || (fees_usd >= 50 && pnl_pct < -0.05)
+//! ```
+//!
+//! Numbers may carry a `%` suffix (divided by 100) or a duration suffix
+//! (`s`, `m`, `h`, `d`, converted to seconds).
+
+use super::RuleContext;
+use anyhow::{Context, bail};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed alert condition expression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Expression {
+    /// Original source text.
+    source: String,
+    /// Parsed expression tree.
+    root: Node,
+}
+
+impl Expression {
+    /// Parses an expression.
+    pub fn parse(source: &str) -> anyhow::Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            bail!("unexpected {} in expression", token);
+        }
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            root,
+        })
+    }
+
+    /// Returns the source text.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the expression against a rule context.
+    #[must_use]
+    pub fn evaluate(&self, context: &RuleContext) -> bool {
+        self.root.evaluate(context)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Expression {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<Expression> for String {
+    fn from(expr: Expression) -> Self {
+        expr.source
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Variables available to expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variable {
+    /// Impermanent loss (fraction, absolute value).
+    IlPct,
+    /// Net PnL (fraction).
+    PnlPct,
+    /// Net PnL in USD.
+    PnlUsd,
+    /// Fees earned in USD.
+    FeesUsd,
+    /// Current position value in USD.
+    ValueUsd,
+    /// Annualized return.
+    Apy,
+    /// Seconds the position has been out of range.
+    TimeOutOfRange,
+    /// Hours since the last rebalance.
+    HoursSinceRebalance,
+    /// Whether the position is in range.
+    InRange,
+    /// Whether the position was in range at the previous evaluation.
+    WasInRange,
+}
+
+impl Variable {
+    /// All variables with their names.
+    pub const ALL: [(&'static str, Variable); 10] = [
+        ("il_pct", Self::IlPct),
+        ("pnl_pct", Self::PnlPct),
+        ("pnl_usd", Self::PnlUsd),
+        ("fees_usd", Self::FeesUsd),
+        ("value_usd", Self::ValueUsd),
+        ("apy", Self::Apy),
+        ("time_out_of_range", Self::TimeOutOfRange),
+        ("hours_since_rebalance", Self::HoursSinceRebalance),
+        ("in_range", Self::InRange),
+        ("was_in_range", Self::WasInRange),
+    ];
+
+    /// Looks up a variable by name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+    }
+
+    /// Returns true for boolean variables.
+    #[must_use]
+    pub fn is_flag(self) -> bool {
+        matches!(self, Self::InRange | Self::WasInRange)
+    }
+
+    /// Reads a numeric variable from the context.
+    fn value(self, context: &RuleContext) -> Decimal {
+        let pnl = &context.pnl;
+        match self {
+            Self::IlPct => pnl.il_pct.abs(),
+            Self::PnlPct => pnl.net_pnl_pct,
+            Self::PnlUsd => pnl.net_pnl_usd,
+            Self::FeesUsd => pnl.fees_usd,
+            Self::ValueUsd => pnl.current_value_usd,
+            Self::Apy => pnl.apy,
+            Self::TimeOutOfRange => Decimal::from(context.time_out_of_range_secs),
+            Self::HoursSinceRebalance => Decimal::from(context.hours_since_rebalance),
+            Self::InRange => Decimal::from(u8::from(context.in_range)),
+            Self::WasInRange => Decimal::from(u8::from(context.was_in_range)),
+        }
+    }
+
+    /// Reads a boolean variable from the context.
+    fn flag(self, context: &RuleContext) -> bool {
+        match self {
+            Self::InRange => context.in_range,
+            Self::WasInRange => context.was_in_range,
+            _ => self.value(context) != Decimal::ZERO,
+        }
+    }
+}
+
+/// Comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    fn apply(self, lhs: Decimal, rhs: Decimal) -> bool {
+        match self {
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+            Self::Eq => lhs == rhs,
+            Self::Ne => lhs != rhs,
+        }
+    }
+}
+
+/// Comparison operand.
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Number(Decimal),
+    Var(Variable),
+}
+
+impl Operand {
+    fn value(&self, context: &RuleContext) -> Decimal {
+        match self {
+            Self::Number(n) => *n,
+            Self::Var(v) => v.value(context),
+        }
+    }
+}
+
+/// Expression tree node.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Bool(bool),
+    Flag(Variable),
+    Compare(Operand, Comparison, Operand),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+}
+
+impl Node {
+    fn evaluate(&self, context: &RuleContext) -> bool {
+        match self {
+            Self::Bool(b) => *b,
+            Self::Flag(v) => v.flag(context),
+            Self::Compare(lhs, op, rhs) => op.apply(lhs.value(context), rhs.value(context)),
+            Self::Not(inner) => !inner.evaluate(context),
+            Self::And(a, b) => a.evaluate(context) && b.evaluate(context),
+            Self::Or(a, b) => a.evaluate(context) || b.evaluate(context),
+        }
+    }
+}
+
+/// Lexical token.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Decimal),
+    Ident(String),
+    Compare(Comparison),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "number {}", n),
+            Self::Ident(name) => write!(f, "'{}'", name),
+            Self::Compare(op) => write!(f, "operator {:?}", op),
+            Self::And => f.write_str("'&&'"),
+            Self::Or => f.write_str("'||'"),
+            Self::Not => f.write_str("'!'"),
+            Self::LParen => f.write_str("'('"),
+            Self::RParen => f.write_str("')'"),
+        }
+    }
+}
+
+/// Splits an expression into tokens.
+fn tokenize(source: &str) -> anyhow::Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        let (token, len) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('<', Some('=')) => (Token::Compare(Comparison::Le), 2),
+            ('>', Some('=')) => (Token::Compare(Comparison::Ge), 2),
+            ('=', Some('=')) => (Token::Compare(Comparison::Eq), 2),
+            ('!', Some('=')) => (Token::Compare(Comparison::Ne), 2),
+            ('<', _) => (Token::Compare(Comparison::Lt), 1),
+            ('>', _) => (Token::Compare(Comparison::Gt), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            (c, _)
+                if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let mut value = Decimal::from_str(&text)
+                    .with_context(|| format!("invalid number '{}'", text))?;
+
+                // Unit suffix
+                let multiplier = match chars.get(i) {
+                    Some('%') => Some(Decimal::new(1, 2)),
+                    Some('s') => Some(Decimal::ONE),
+                    Some('m') => Some(Decimal::from(60)),
+                    Some('h') => Some(Decimal::from(3600)),
+                    Some('d') => Some(Decimal::from(86400)),
+                    _ => None,
+                };
+                if let Some(multiplier) = multiplier {
+                    value *= multiplier;
+                    i += 1;
+                }
+                if chars.get(i).is_some_and(|c| c.is_alphanumeric()) {
+                    bail!("invalid number suffix after '{}'", text);
+                }
+
+                tokens.push(Token::Number(value));
+                continue;
+            }
+            (c, _) if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+                continue;
+            }
+            (c, _) => bail!("unexpected character '{}' in expression", c),
+        };
+
+        tokens.push(token);
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over tokens.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> anyhow::Result<Node> {
+        let mut node = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            node = Node::Or(Box::new(node), Box::new(self.parse_and()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> anyhow::Result<Node> {
+        let mut node = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            node = Node::And(Box::new(node), Box::new(self.parse_unary()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_unary(&mut self) -> anyhow::Result<Node> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Node::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> anyhow::Result<Node> {
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let node = self.parse_or()?;
+            match self.advance() {
+                Some(Token::RParen) => return Ok(node),
+                Some(token) => bail!("expected ')' but found {}", token),
+                None => bail!("missing closing ')'"),
+            }
+        }
+
+        let lhs = match self.advance() {
+            Some(Token::Ident(name)) if name == "true" => return Ok(Node::Bool(true)),
+            Some(Token::Ident(name)) if name == "false" => return Ok(Node::Bool(false)),
+            Some(Token::Ident(name)) => {
+                let var = Variable::from_name(&name)
+                    .with_context(|| format!("unknown variable '{}'", name))?;
+                if var.is_flag() && !matches!(self.peek(), Some(Token::Compare(_))) {
+                    return Ok(Node::Flag(var));
+                }
+                Operand::Var(var)
+            }
+            Some(Token::Number(n)) => Operand::Number(n),
+            Some(token) => bail!("unexpected {} in expression", token),
+            None => bail!("unexpected end of expression"),
+        };
+
+        let op = match self.advance() {
+            Some(Token::Compare(op)) => op,
+            Some(token) => bail!("expected comparison but found {}", token),
+            None => bail!("expected comparison at end of expression"),
+        };
+
+        let rhs = match self.advance() {
+            Some(Token::Number(n)) => Operand::Number(n),
+            Some(Token::Ident(name)) => Operand::Var(
+                Variable::from_name(&name)
+                    .with_context(|| format!("unknown variable '{}'", name))?,
+            ),
+            Some(token) => bail!("unexpected {} in expression", token),
+            None => bail!("unexpected end of expression"),
+        };
+
+        Ok(Node::Compare(lhs, op, rhs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::PositionPnL;
+    use rust_decimal_macros::dec;
+
+    fn context(il_pct: Decimal, in_range: bool, time_out_of_range_secs: u64) -> RuleContext {
+        RuleContext {
+            in_range,
+            was_in_range: in_range,
+            pnl: PositionPnL {
+                il_pct,
+                ..Default::default()
+            },
+            hours_since_rebalance: 0,
+            time_out_of_range_secs,
+            position: None,
+            pool: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_and_evaluate() {
+        let expr = Expression::parse("il_pct > 0.03 && time_out_of_range > 2h").unwrap();
+
+        assert!(expr.evaluate(&context(dec!(0.05), false, 3 * 3600)));
+        assert!(!expr.evaluate(&context(dec!(0.05), false, 3600)));
+        assert!(!expr.evaluate(&context(dec!(0.01), false, 3 * 3600)));
+    }
+
+    #[test]
+    fn test_precedence_and_flags() {
+        let expr = Expression::parse("!in_range || il_pct >= 5% && false").unwrap();
+        assert!(expr.evaluate(&context(dec!(0.10), false, 0)));
+        assert!(!expr.evaluate(&context(dec!(0.10), true, 0)));
+
+        let grouped = Expression::parse("(!in_range || il_pct >= 5%) && true").unwrap();
+        assert!(grouped.evaluate(&context(dec!(0.10), true, 0)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Expression::parse("il_pct >").is_err());
+        assert!(Expression::parse("unknown > 1").is_err());
+        assert!(Expression::parse("(il_pct > 1").is_err());
+        assert!(Expression::parse("il_pct > 1 fees_usd").is_err());
+        assert!(Expression::parse("il_pct > 2x").is_err());
+        assert!(Expression::parse("il_pct").is_err());
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let expr = Expression::parse("fees_usd > 50").unwrap();
+        let json = serde_json::to_string(&expr).unwrap();
+        assert_eq!(json, "\"fees_usd > 50\"");
+
+        let parsed: Expression = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, expr);
+        assert!(serde_json::from_str::<Expression>("\"fees_usd >\"").is_err());
+    }
+}
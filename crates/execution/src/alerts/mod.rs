@@ -5,11 +5,22 @@
 //! - IL thresholds
 //! - PnL targets
 //! - System errors
+//! - Custom expressions over monitor state
+//!
+//! Alerts can be delivered to the console, a log file, a signed webhook,
+//! Telegram, Slack or Discord, and escalated through a secondary channel when
+//! they go unacknowledged.
 
 mod alert;
+mod escalation;
+mod expression;
 mod notifier;
 mod rules;
+mod webhook;
 
 pub use alert::*;
+pub use escalation::*;
+pub use expression::*;
 pub use notifier::*;
 pub use rules::*;
+pub use webhook::*;
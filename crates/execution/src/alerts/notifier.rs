@@ -1,11 +1,14 @@
 //! Alert notification channels.
 
-use super::Alert;
+use super::{Alert, AlertLevel};
 use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::PathBuf;
-use tracing::{error, info};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, error, warn};
 
 /// Trait for alert notification channels.
 #[async_trait]
@@ -63,50 +66,496 @@ impl Notifier for FileNotifier {
     }
 }
 
-/// Webhook notifier - sends alerts to an HTTP endpoint.
-pub struct WebhookNotifier {
-    /// Webhook URL.
-    url: String,
+/// Default Telegram message template.
+pub const DEFAULT_TELEGRAM_TEMPLATE: &str = "{emoji} {level} - {type}\n{message}\n{position}";
+
+/// Configuration for the Telegram notifier.
+#[derive(Clone)]
+pub struct TelegramConfig {
+    /// Bot token from @BotFather.
+    pub bot_token: String,
+    /// Target chat ID (user, group or channel).
+    pub chat_id: String,
+    /// Message template.
+    ///
+    /// Supports `{emoji}`, `{level}`, `{type}`, `{message}`, `{position}`,
+    /// `{pool}` and `{timestamp}` placeholders.
+    pub template: String,
+    /// Minimum level of alerts to send.
+    pub min_level: AlertLevel,
+    /// Maximum messages per minute (critical alerts are never dropped).
+    pub max_per_minute: u32,
+    /// Bot API base URL.
+    pub api_url: String,
+}
+
+impl TelegramConfig {
+    /// Creates a configuration for a bot and chat with default settings.
+    pub fn new(bot_token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            bot_token: bot_token.into(),
+            chat_id: chat_id.into(),
+            template: DEFAULT_TELEGRAM_TEMPLATE.to_string(),
+            min_level: AlertLevel::Info,
+            max_per_minute: 20, // Telegram's per-group limit
+            api_url: "https://api.telegram.org".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TelegramConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramConfig")
+            .field("bot_token", &"<redacted>")
+            .field("chat_id", &self.chat_id)
+            .field("template", &self.template)
+            .field("min_level", &self.min_level)
+            .field("max_per_minute", &self.max_per_minute)
+            .field("api_url", &self.api_url)
+            .finish()
+    }
+}
+
+/// Renders an alert into a message template.
+#[must_use]
+pub fn render_template(template: &str, alert: &Alert) -> String {
+    template
+        .replace("{emoji}", alert.level.emoji())
+        .replace("{level}", alert.level.name())
+        .replace("{type}", alert.alert_type.name())
+        .replace("{message}", &alert.message)
+        .replace("{position}", alert.position.as_deref().unwrap_or(""))
+        .replace("{pool}", alert.pool.as_deref().unwrap_or(""))
+        .replace(
+            "{timestamp}",
+            &alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        )
+        .trim_end()
+        .to_string()
+}
+
+/// Sliding-window rate limiter for outgoing messages.
+#[derive(Debug)]
+struct SendWindow {
+    /// Send times within the current window.
+    sent: VecDeque<Instant>,
+    /// Maximum sends per window.
+    limit: usize,
+    /// Window length.
+    window: Duration,
+}
+
+impl SendWindow {
+    fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            sent: VecDeque::new(),
+            limit: limit as usize,
+            window,
+        }
+    }
+
+    /// Records a send if under the limit, returning false if it should be dropped.
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        while let Some(first) = self.sent.front()
+            && now.duration_since(*first) >= self.window
+        {
+            self.sent.pop_front();
+        }
+
+        if self.sent.len() >= self.limit {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+
+    /// Records a send regardless of the limit.
+    fn force(&mut self, now: Instant) {
+        self.sent.push_back(now);
+    }
+}
+
+/// Telegram notifier - sends alerts to a chat via the Bot API.
+pub struct TelegramNotifier {
+    /// Configuration.
+    config: TelegramConfig,
     /// HTTP client.
-    #[allow(dead_code)]
     client: reqwest::Client,
+    /// Rate limiter.
+    window: Mutex<SendWindow>,
 }
 
-impl WebhookNotifier {
-    /// Creates a new webhook notifier.
-    pub fn new(url: impl Into<String>) -> Self {
+impl TelegramNotifier {
+    /// Creates a new Telegram notifier.
+    pub fn new(config: TelegramConfig) -> Self {
+        let window = SendWindow::new(config.max_per_minute, Duration::from_secs(60));
         Self {
-            url: url.into(),
+            config,
             client: reqwest::Client::new(),
+            window: Mutex::new(window),
         }
     }
 }
 
 #[async_trait]
-impl Notifier for WebhookNotifier {
+impl Notifier for TelegramNotifier {
     async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
-        // Build webhook payload
+        if alert.level < self.config.min_level {
+            debug!(alert_id = %alert.id, "Alert below Telegram min level, skipping");
+            return Ok(());
+        }
+
+        {
+            let mut window = self.window.lock().await;
+            let now = Instant::now();
+            if alert.level == AlertLevel::Critical {
+                window.force(now);
+            } else if !window.try_acquire(now) {
+                warn!(alert_id = %alert.id, "Telegram rate limit reached, dropping alert");
+                return Ok(());
+            }
+        }
+
+        let url = format!(
+            "{}/bot{}/sendMessage",
+            self.config.api_url, self.config.bot_token
+        );
         let payload = serde_json::json!({
-            "text": alert.format(),
-            "alert": alert,
+            "chat_id": self.config.chat_id,
+            "text": render_template(&self.config.template, alert),
+            "disable_web_page_preview": true,
         });
 
-        // Note: In a real implementation, we would use the client to send
-        // For now, just log the attempt
-        info!(
-            url = %self.url,
-            alert_id = %alert.id,
-            "Would send webhook notification"
-        );
+        // Strip the URL from errors so the bot token is never logged
+        let response = self
+            .client
+            .post(&url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Telegram request failed: {}", e.without_url()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Telegram API returned {}: {}", status, body);
+        }
+
+        debug!(alert_id = %alert.id, chat_id = %self.config.chat_id, "Telegram notification sent");
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "telegram"
+    }
+}
+
+/// Destination for Slack messages.
+#[derive(Clone, PartialEq, Eq)]
+pub enum SlackTarget {
+    /// Incoming webhook URL (bound to a single channel).
+    Webhook(String),
+    /// Channel name or ID, posted to via the Slack app bot token.
+    Channel(String),
+}
+
+impl std::fmt::Debug for SlackTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // Webhook URLs embed a secret
+            Self::Webhook(_) => f.write_str("Webhook(<redacted>)"),
+            Self::Channel(channel) => f.debug_tuple("Channel").field(channel).finish(),
+        }
+    }
+}
+
+/// Configuration for the Slack notifier.
+#[derive(Clone)]
+pub struct SlackConfig {
+    /// Bot token for posting via the Slack app (required for channel targets).
+    pub bot_token: Option<String>,
+    /// Target for alerts without a level-specific route.
+    pub default_target: SlackTarget,
+    /// Level-specific targets, e.g. critical alerts to an on-call channel.
+    pub level_targets: HashMap<AlertLevel, SlackTarget>,
+    /// Minimum level of alerts to send.
+    pub min_level: AlertLevel,
+    /// Slack Web API base URL.
+    pub api_url: String,
+}
+
+impl SlackConfig {
+    /// Creates a configuration that posts to an incoming webhook.
+    pub fn webhook(url: impl Into<String>) -> Self {
+        Self::with_target(None, SlackTarget::Webhook(url.into()))
+    }
+
+    /// Creates a configuration that posts to a channel via the Slack app.
+    pub fn app(bot_token: impl Into<String>, channel: impl Into<String>) -> Self {
+        Self::with_target(Some(bot_token.into()), SlackTarget::Channel(channel.into()))
+    }
+
+    fn with_target(bot_token: Option<String>, default_target: SlackTarget) -> Self {
+        Self {
+            bot_token,
+            default_target,
+            level_targets: HashMap::new(),
+            min_level: AlertLevel::Info,
+            api_url: "https://slack.com/api".to_string(),
+        }
+    }
+
+    /// Routes alerts of a level to a specific target.
+    #[must_use]
+    pub fn with_route(mut self, level: AlertLevel, target: SlackTarget) -> Self {
+        self.level_targets.insert(level, target);
+        self
+    }
+
+    /// Gets the target for an alert level.
+    #[must_use]
+    pub fn target_for(&self, level: AlertLevel) -> &SlackTarget {
+        self.level_targets
+            .get(&level)
+            .unwrap_or(&self.default_target)
+    }
+}
+
+impl std::fmt::Debug for SlackConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlackConfig")
+            .field("bot_token", &self.bot_token.as_ref().map(|_| "<redacted>"))
+            .field("default_target", &self.default_target)
+            .field("level_targets", &self.level_targets)
+            .field("min_level", &self.min_level)
+            .field("api_url", &self.api_url)
+            .finish()
+    }
+}
+
+/// Slack notifier - posts alerts via incoming webhooks or the Slack app.
+pub struct SlackNotifier {
+    /// Configuration.
+    config: SlackConfig,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl SlackNotifier {
+    /// Creates a new Slack notifier.
+    pub fn new(config: SlackConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Builds the message payload for an alert.
+    fn build_payload(alert: &Alert, channel: Option<&str>) -> serde_json::Value {
+        let color = match alert.level {
+            AlertLevel::Info => "#439FE0",
+            AlertLevel::Warning => "warning",
+            AlertLevel::Critical => "danger",
+        };
+
+        let mut fields = vec![serde_json::json!({
+            "title": "Type",
+            "value": alert.alert_type.name(),
+            "short": true,
+        })];
+        if let Some(position) = &alert.position {
+            fields.push(serde_json::json!({
+                "title": "Position",
+                "value": position,
+                "short": true,
+            }));
+        }
+        if let Some(pool) = &alert.pool {
+            fields.push(serde_json::json!({
+                "title": "Pool",
+                "value": pool,
+                "short": true,
+            }));
+        }
+
+        let mut payload = serde_json::json!({
+            "text": format!("{} {}", alert.level.emoji(), alert.message),
+            "attachments": [{
+                "color": color,
+                "fields": fields,
+                "footer": alert.level.name(),
+                "ts": alert.timestamp.timestamp(),
+            }],
+        });
+        if let Some(channel) = channel {
+            payload["channel"] = serde_json::Value::from(channel);
+        }
+        payload
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        if alert.level < self.config.min_level {
+            debug!(alert_id = %alert.id, "Alert below Slack min level, skipping");
+            return Ok(());
+        }
+
+        match self.config.target_for(alert.level) {
+            SlackTarget::Webhook(url) => {
+                let response = self
+                    .client
+                    .post(url)
+                    .json(&Self::build_payload(alert, None))
+                    .send()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Slack request failed: {}", e.without_url()))?;
+
+                let status = response.status();
+                if !status.is_success() {
+                    let body = response.text().await.unwrap_or_default();
+                    anyhow::bail!("Slack webhook returned {}: {}", status, body);
+                }
+            }
+            SlackTarget::Channel(channel) => {
+                let Some(token) = &self.config.bot_token else {
+                    anyhow::bail!("Slack bot token required to post to channel {}", channel);
+                };
+
+                let response: serde_json::Value = self
+                    .client
+                    .post(format!("{}/chat.postMessage", self.config.api_url))
+                    .bearer_auth(token)
+                    .json(&Self::build_payload(alert, Some(channel)))
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+
+                // The Web API reports failures in the body with a 200 status
+                if !response["ok"].as_bool().unwrap_or(false) {
+                    anyhow::bail!(
+                        "Slack API error: {}",
+                        response["error"].as_str().unwrap_or("unknown")
+                    );
+                }
+            }
+        }
+
+        debug!(alert_id = %alert.id, level = alert.level.name(), "Slack notification sent");
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "slack"
+    }
+}
+
+/// Discord notifier - posts alerts to a channel webhook as embeds.
+pub struct DiscordNotifier {
+    /// Webhook URL (embeds a secret token).
+    webhook_url: String,
+    /// Minimum level of alerts to send.
+    min_level: AlertLevel,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl DiscordNotifier {
+    /// Creates a new Discord notifier for a channel webhook.
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self {
+            webhook_url: webhook_url.into(),
+            min_level: AlertLevel::Info,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Sets the minimum level of alerts to send.
+    #[must_use]
+    pub fn with_min_level(mut self, level: AlertLevel) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Builds the message payload for an alert.
+    fn build_payload(alert: &Alert) -> serde_json::Value {
+        let color = match alert.level {
+            AlertLevel::Info => 0x439FE0,
+            AlertLevel::Warning => 0xFFA500,
+            AlertLevel::Critical => 0xE01E5A,
+        };
+
+        let mut fields = vec![serde_json::json!({
+            "name": "Type",
+            "value": alert.alert_type.name(),
+            "inline": true,
+        })];
+        if let Some(position) = &alert.position {
+            fields.push(serde_json::json!({
+                "name": "Position",
+                "value": position,
+                "inline": true,
+            }));
+        }
+        if let Some(pool) = &alert.pool {
+            fields.push(serde_json::json!({
+                "name": "Pool",
+                "value": pool,
+                "inline": true,
+            }));
+        }
+
+        serde_json::json!({
+            "embeds": [{
+                "title": format!("{} {}", alert.level.emoji(), alert.level.name()),
+                "description": alert.message,
+                "color": color,
+                "fields": fields,
+                "timestamp": alert.timestamp.to_rfc3339(),
+            }],
+        })
+    }
+}
+
+impl std::fmt::Debug for DiscordNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiscordNotifier")
+            .field("webhook_url", &"<redacted>")
+            .field("min_level", &self.min_level)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        if alert.level < self.min_level {
+            debug!(alert_id = %alert.id, "Alert below Discord min level, skipping");
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .json(&Self::build_payload(alert))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Discord request failed: {}", e.without_url()))?;
 
-        // Placeholder for actual HTTP request
-        let _ = payload;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Discord webhook returned {}: {}", status, body);
+        }
 
+        debug!(alert_id = %alert.id, level = alert.level.name(), "Discord notification sent");
         Ok(())
     }
 
     fn name(&self) -> &str {
-        "webhook"
+        "discord"
     }
 }
 
@@ -163,4 +612,102 @@ mod tests {
         let result = notifier.notify(&alert).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_render_template() {
+        let alert = Alert::new(AlertLevel::Warning, AlertType::RangeExit, "Out of range")
+            .with_position(&solana_sdk::pubkey::Pubkey::default());
+
+        let text = render_template(DEFAULT_TELEGRAM_TEMPLATE, &alert);
+        assert!(text.starts_with("⚠️ Warning - Range Exit\nOut of range\n"));
+        assert!(text.ends_with(&solana_sdk::pubkey::Pubkey::default().to_string()));
+
+        let alert = Alert::new(AlertLevel::Info, AlertType::SystemError, "Boom");
+        assert_eq!(
+            render_template("{type}: {message} {pool}", &alert),
+            "System Error: Boom"
+        );
+    }
+
+    #[test]
+    fn test_send_window_limits_rate() {
+        let mut window = SendWindow::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(window.try_acquire(start));
+        assert!(window.try_acquire(start));
+        assert!(!window.try_acquire(start + Duration::from_secs(30)));
+        assert!(window.try_acquire(start + Duration::from_secs(61)));
+    }
+
+    #[tokio::test]
+    async fn test_telegram_skips_below_min_level() {
+        let config = TelegramConfig {
+            min_level: AlertLevel::Critical,
+            api_url: "http://127.0.0.1:9".to_string(),
+            ..TelegramConfig::new("123:secret", "chat")
+        };
+        let notifier = TelegramNotifier::new(config);
+        let alert = Alert::new(AlertLevel::Warning, AlertType::RangeExit, "Out of range");
+
+        assert!(notifier.notify(&alert).await.is_ok());
+        assert!(!format!("{:?}", notifier.config).contains("secret"));
+    }
+
+    #[test]
+    fn test_slack_routes_by_level() {
+        let config = SlackConfig::app("xoxb-secret", "#lp-alerts").with_route(
+            AlertLevel::Critical,
+            SlackTarget::Channel("#on-call".into()),
+        );
+
+        assert_eq!(
+            config.target_for(AlertLevel::Info),
+            &SlackTarget::Channel("#lp-alerts".into())
+        );
+        assert_eq!(
+            config.target_for(AlertLevel::Critical),
+            &SlackTarget::Channel("#on-call".into())
+        );
+        assert!(!format!("{:?}", config).contains("secret"));
+    }
+
+    #[test]
+    fn test_slack_payload() {
+        let alert = Alert::new(
+            AlertLevel::Critical,
+            AlertType::SystemError,
+            "Execution failed",
+        )
+        .with_pool(&solana_sdk::pubkey::Pubkey::default());
+
+        let payload = SlackNotifier::build_payload(&alert, Some("#on-call"));
+        assert_eq!(payload["channel"], "#on-call");
+        assert_eq!(payload["attachments"][0]["color"], "danger");
+        assert_eq!(
+            payload["attachments"][0]["fields"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let payload = SlackNotifier::build_payload(&alert, None);
+        assert!(payload.get("channel").is_none());
+    }
+
+    #[test]
+    fn test_discord_payload() {
+        let alert = Alert::new(AlertLevel::Warning, AlertType::RangeExit, "Out of range")
+            .with_position(&solana_sdk::pubkey::Pubkey::default());
+
+        let payload = DiscordNotifier::build_payload(&alert);
+        let embed = &payload["embeds"][0];
+        assert_eq!(embed["description"], "Out of range");
+        assert_eq!(embed["color"], 0xFFA500);
+        assert_eq!(embed["fields"][1]["name"], "Position");
+
+        let notifier = DiscordNotifier::new("https://discord.com/api/webhooks/1/secret");
+        assert!(!format!("{:?}", notifier).contains("secret"));
+    }
 }
@@ -1,9 +1,25 @@
 //! Alert rules engine.
 
-use super::{Alert, AlertLevel, AlertType};
+use super::{Alert, AlertLevel, AlertType, EscalationPolicy, Expression};
 use crate::monitor::PositionPnL;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+
+/// Default deduplication key template.
+pub const DEFAULT_DEDUP_KEY: &str = "{rule}:{position}";
+
+/// When a rule fires while its condition holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerMode {
+    /// Fire on every evaluation where the condition holds, subject to the cooldown.
+    #[default]
+    Always,
+    /// Fire once when the condition starts holding, and once more when it clears.
+    OnChange,
+}
 
 /// Alert rule configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,8 +36,20 @@ pub struct AlertRule {
     pub message_template: String,
     /// Whether the rule is enabled.
     pub enabled: bool,
-    /// Cooldown between alerts in seconds.
+    /// Cooldown between alerts with the same dedup key in seconds.
     pub cooldown_secs: u64,
+    /// When the rule fires while its condition holds.
+    #[serde(default)]
+    pub trigger_mode: TriggerMode,
+    /// Deduplication key template (`{rule}`, `{position}` and `{pool}` placeholders).
+    ///
+    /// Alerts sharing a key share a cooldown and condition state. Defaults to
+    /// one key per rule and position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<String>,
+    /// Escalation policy if alerts from this rule go unacknowledged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation: Option<EscalationPolicy>,
 }
 
 impl AlertRule {
@@ -40,6 +68,9 @@ impl AlertRule {
             message_template: String::new(),
             enabled: true,
             cooldown_secs: 300, // 5 minutes default
+            trigger_mode: TriggerMode::Always,
+            dedup_key: None,
+            escalation: None,
         }
     }
 
@@ -57,6 +88,39 @@ impl AlertRule {
         self
     }
 
+    /// Fires only when the condition starts or stops holding.
+    #[must_use]
+    pub fn on_change(mut self) -> Self {
+        self.trigger_mode = TriggerMode::OnChange;
+        self
+    }
+
+    /// Sets the deduplication key template.
+    #[must_use]
+    pub fn with_dedup_key(mut self, template: impl Into<String>) -> Self {
+        self.dedup_key = Some(template.into());
+        self
+    }
+
+    /// Resolves the deduplication key for a context.
+    #[must_use]
+    pub fn dedup_key_for(&self, context: &RuleContext) -> String {
+        let format_key = |key: Option<Pubkey>| key.map(|k| k.to_string()).unwrap_or_default();
+        self.dedup_key
+            .as_deref()
+            .unwrap_or(DEFAULT_DEDUP_KEY)
+            .replace("{rule}", &self.name)
+            .replace("{position}", &format_key(context.position))
+            .replace("{pool}", &format_key(context.pool))
+    }
+
+    /// Escalates unacknowledged alerts from this rule.
+    #[must_use]
+    pub fn with_escalation(mut self, policy: EscalationPolicy) -> Self {
+        self.escalation = Some(policy);
+        self
+    }
+
     /// Disables the rule.
     #[must_use]
     pub fn disabled(mut self) -> Self {
@@ -70,6 +134,8 @@ impl AlertRule {
 pub enum RuleCondition {
     /// Position exits range.
     RangeExit,
+    /// Position is out of range.
+    OutOfRange,
     /// Position enters range.
     RangeEntry,
     /// IL exceeds threshold.
@@ -86,6 +152,8 @@ pub enum RuleCondition {
     And(Box<RuleCondition>, Box<RuleCondition>),
     /// Compound condition (OR).
     Or(Box<RuleCondition>, Box<RuleCondition>),
+    /// Custom expression, e.g. `il_pct > 3% && time_out_of_range > 2h`.
+    Expression(Expression),
 }
 
 /// Context for evaluating rules.
@@ -99,14 +167,22 @@ pub struct RuleContext {
     pub pnl: PositionPnL,
     /// Hours since last rebalance.
     pub hours_since_rebalance: u64,
+    /// Seconds the position has been out of range (zero while in range).
+    pub time_out_of_range_secs: u64,
+    /// Position being evaluated.
+    pub position: Option<Pubkey>,
+    /// Pool of the position.
+    pub pool: Option<Pubkey>,
 }
 
 /// Rules engine for evaluating alert conditions.
 pub struct RulesEngine {
     /// Configured rules.
     rules: Vec<AlertRule>,
-    /// Last trigger times for cooldown.
-    last_triggers: std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
+    /// Last trigger times by dedup key, for cooldowns.
+    last_triggers: HashMap<String, chrono::DateTime<chrono::Utc>>,
+    /// Dedup keys whose condition held at the last evaluation.
+    active: HashSet<String>,
 }
 
 impl RulesEngine {
@@ -115,7 +191,8 @@ impl RulesEngine {
     pub fn new() -> Self {
         Self {
             rules: Vec::new(),
-            last_triggers: std::collections::HashMap::new(),
+            last_triggers: HashMap::new(),
+            active: HashSet::new(),
         }
     }
 
@@ -129,6 +206,12 @@ impl RulesEngine {
         self.rules.retain(|r| r.name != name);
     }
 
+    /// Gets the configured rules.
+    #[must_use]
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
     /// Evaluates all rules and returns triggered alerts.
     pub fn evaluate(&mut self, context: &RuleContext) -> Vec<Alert> {
         let mut alerts = Vec::new();
@@ -139,34 +222,83 @@ impl RulesEngine {
                 continue;
             }
 
+            let key = rule.dedup_key_for(context);
+            let met = self.evaluate_condition(&rule.condition, context);
+            let was_active = if met {
+                !self.active.insert(key.clone())
+            } else {
+                self.active.remove(&key)
+            };
+
+            let message = self.format_message(&rule.message_template, context);
+            let alert = match rule.trigger_mode {
+                TriggerMode::Always if met => {
+                    Alert::new(rule.level, rule.alert_type.clone(), message)
+                }
+                TriggerMode::OnChange if met && !was_active => {
+                    Alert::new(rule.level, rule.alert_type.clone(), message)
+                }
+                // Condition cleared: resolutions bypass the cooldown
+                TriggerMode::OnChange if !met && was_active => {
+                    alerts.push(self.build_alert(
+                        rule,
+                        context,
+                        Alert::new(
+                            AlertLevel::Info,
+                            rule.alert_type.clone(),
+                            format!("Resolved: {}", message),
+                        ),
+                    ));
+                    continue;
+                }
+                _ => continue,
+            };
+
             // Check cooldown
-            if let Some(last) = self.last_triggers.get(&rule.name) {
-                let elapsed = (now - *last).num_seconds() as u64;
+            if let Some(last) = self.last_triggers.get(&key) {
+                let elapsed = (now - *last).num_seconds().max(0) as u64;
                 if elapsed < rule.cooldown_secs {
                     continue;
                 }
             }
 
-            // Evaluate condition
-            if self.evaluate_condition(&rule.condition, context) {
-                let message = self.format_message(&rule.message_template, context);
-                let alert = Alert::new(rule.level, rule.alert_type.clone(), message);
-                alerts.push(alert);
+            alerts.push(self.build_alert(rule, context, alert));
 
-                // Update last trigger time
-                self.last_triggers.insert(rule.name.clone(), now);
-            }
+            // Update last trigger time
+            self.last_triggers.insert(key, now);
         }
 
         alerts
     }
 
+    /// Attaches rule and context details to an alert.
+    fn build_alert(&self, rule: &AlertRule, context: &RuleContext, mut alert: Alert) -> Alert {
+        if let Some(position) = &context.position {
+            alert = alert.with_position(position);
+        }
+        if let Some(pool) = &context.pool {
+            alert = alert.with_pool(pool);
+        }
+        if let Some(policy) = &rule.escalation {
+            alert = alert.with_escalation(policy.clone());
+        }
+        alert
+    }
+
+    /// Clears cooldown and condition state for a position.
+    pub fn clear_position(&mut self, position: &Pubkey) {
+        let position = position.to_string();
+        self.last_triggers.retain(|key, _| !key.contains(&position));
+        self.active.retain(|key| !key.contains(&position));
+    }
+
     /// Evaluates a single condition.
     #[allow(clippy::only_used_in_recursion)]
     fn evaluate_condition(&self, condition: &RuleCondition, context: &RuleContext) -> bool {
         match condition {
             RuleCondition::RangeExit => context.was_in_range && !context.in_range,
             RuleCondition::RangeEntry => !context.was_in_range && context.in_range,
+            RuleCondition::OutOfRange => !context.in_range,
             RuleCondition::ILExceeds(threshold) => context.pnl.il_pct.abs() > *threshold,
             RuleCondition::PnLExceeds(threshold) => context.pnl.net_pnl_pct > *threshold,
             RuleCondition::PnLBelow(threshold) => context.pnl.net_pnl_pct < *threshold,
@@ -178,6 +310,7 @@ impl RulesEngine {
             RuleCondition::Or(a, b) => {
                 self.evaluate_condition(a, context) || self.evaluate_condition(b, context)
             }
+            RuleCondition::Expression(expr) => expr.evaluate(context),
         }
     }
 
@@ -189,6 +322,10 @@ impl RulesEngine {
             .replace("{pnl_usd}", &format!("${:.2}", context.pnl.net_pnl_usd))
             .replace("{fees_usd}", &format!("${:.2}", context.pnl.fees_usd))
             .replace("{in_range}", if context.in_range { "yes" } else { "no" })
+            .replace(
+                "{position}",
+                &context.position.map(|p| p.to_string()).unwrap_or_default(),
+            )
     }
 
     /// Creates default rules.
@@ -272,10 +409,125 @@ mod tests {
             was_in_range: true,
             pnl: PositionPnL::default(),
             hours_since_rebalance: 0,
+            time_out_of_range_secs: 0,
+            position: None,
+            pool: None,
         };
 
         let alerts = engine.evaluate(&context);
         assert_eq!(alerts.len(), 1);
         assert_eq!(alerts[0].level, AlertLevel::Warning);
     }
+
+    fn out_of_range_context(position: Pubkey, in_range: bool) -> RuleContext {
+        RuleContext {
+            in_range,
+            was_in_range: in_range,
+            pnl: PositionPnL::default(),
+            hours_since_rebalance: 0,
+            time_out_of_range_secs: 0,
+            position: Some(position),
+            pool: None,
+        }
+    }
+
+    #[test]
+    fn test_on_change_fires_on_enter_and_exit() {
+        let mut engine = RulesEngine::new();
+        engine.add_rule(
+            AlertRule::new(
+                "out_of_range",
+                RuleCondition::OutOfRange,
+                AlertLevel::Warning,
+                AlertType::RangeExit,
+            )
+            .with_message("Position out of range")
+            .with_cooldown(0)
+            .on_change(),
+        );
+        let position = Pubkey::new_unique();
+
+        let alerts = engine.evaluate(&out_of_range_context(position, false));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].position, Some(position.to_string()));
+
+        // Still out of range: no repeat
+        assert!(
+            engine
+                .evaluate(&out_of_range_context(position, false))
+                .is_empty()
+        );
+
+        let alerts = engine.evaluate(&out_of_range_context(position, true));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].level, AlertLevel::Info);
+        assert!(alerts[0].message.starts_with("Resolved"));
+
+        assert!(
+            engine
+                .evaluate(&out_of_range_context(position, true))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cooldown_is_per_dedup_key() {
+        let mut engine = RulesEngine::new();
+        engine.add_rule(AlertRule::new(
+            "out_of_range",
+            RuleCondition::OutOfRange,
+            AlertLevel::Warning,
+            AlertType::RangeExit,
+        ));
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        assert_eq!(
+            engine.evaluate(&out_of_range_context(first, false)).len(),
+            1
+        );
+        // Same position within the cooldown is suppressed
+        assert!(
+            engine
+                .evaluate(&out_of_range_context(first, false))
+                .is_empty()
+        );
+        // A different position has its own key
+        assert_eq!(
+            engine.evaluate(&out_of_range_context(second, false)).len(),
+            1
+        );
+
+        engine.clear_position(&first);
+        assert_eq!(
+            engine.evaluate(&out_of_range_context(first, false)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_shared_dedup_key() {
+        let mut engine = RulesEngine::new();
+        engine.add_rule(
+            AlertRule::new(
+                "out_of_range",
+                RuleCondition::OutOfRange,
+                AlertLevel::Warning,
+                AlertType::RangeExit,
+            )
+            .with_dedup_key("{rule}"),
+        );
+
+        assert_eq!(
+            engine
+                .evaluate(&out_of_range_context(Pubkey::new_unique(), false))
+                .len(),
+            1
+        );
+        assert!(
+            engine
+                .evaluate(&out_of_range_context(Pubkey::new_unique(), false))
+                .is_empty()
+        );
+    }
 }
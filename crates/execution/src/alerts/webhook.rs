@@ -0,0 +1,416 @@
+//! Signed HTTP webhook notifier.
+//!
+//! Events are POSTed as JSON. When a secret is configured each request
+//! carries an HMAC-SHA256 signature of `"{timestamp}.{body}"` so receivers
+//! can verify the sender and reject replays. Failed deliveries are retried
+//! with exponential backoff and finally appended to a dead-letter log.
+
+use super::{Alert, Notifier};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Header carrying the request signature.
+pub const SIGNATURE_HEADER: &str = "X-CLMM-Signature";
+/// Header carrying the signing timestamp (Unix seconds).
+pub const TIMESTAMP_HEADER: &str = "X-CLMM-Timestamp";
+/// Header carrying the event kind.
+pub const EVENT_HEADER: &str = "X-CLMM-Event";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Kind of webhook event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    /// Alert raised.
+    Alert,
+    /// Position state changed.
+    Position,
+    /// Position rebalanced.
+    Rebalance,
+    /// Strategy state changed.
+    Strategy,
+}
+
+impl WebhookEventKind {
+    /// Returns the wire name of this event kind.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Alert => "alert",
+            Self::Position => "position",
+            Self::Rebalance => "rebalance",
+            Self::Strategy => "strategy",
+        }
+    }
+}
+
+/// An event delivered to webhook receivers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Unique event ID, usable by receivers for deduplication.
+    pub id: String,
+    /// Event kind.
+    pub kind: WebhookEventKind,
+    /// When the event occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Event payload.
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    /// Creates a new event.
+    pub fn new(kind: WebhookEventKind, data: serde_json::Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            timestamp: Utc::now(),
+            data,
+        }
+    }
+
+    /// Creates an alert event.
+    pub fn alert(alert: &Alert) -> Self {
+        Self::new(
+            WebhookEventKind::Alert,
+            serde_json::to_value(alert).unwrap_or_default(),
+        )
+    }
+}
+
+/// Configuration for the webhook notifier.
+#[derive(Clone)]
+pub struct WebhookConfig {
+    /// Endpoint URL.
+    pub url: String,
+    /// Shared secret for HMAC signatures (unsigned if unset).
+    pub secret: Option<String>,
+    /// Retries after the first failed attempt.
+    pub max_retries: u32,
+    /// Backoff before the first retry in milliseconds (doubles per retry).
+    pub initial_backoff_ms: u64,
+    /// Maximum backoff in milliseconds.
+    pub max_backoff_ms: u64,
+    /// Per-request timeout in seconds.
+    pub timeout_secs: u64,
+    /// JSON-lines file for events that could not be delivered.
+    pub dead_letter_path: Option<PathBuf>,
+}
+
+impl WebhookConfig {
+    /// Creates a configuration for an endpoint with default settings.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret: None,
+            max_retries: 3,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+            timeout_secs: 10,
+            dead_letter_path: None,
+        }
+    }
+
+    /// Signs requests with a shared secret.
+    #[must_use]
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Writes undeliverable events to a dead-letter file.
+    #[must_use]
+    pub fn with_dead_letter(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dead_letter_path = Some(path.into());
+        self
+    }
+
+    /// Backoff before the given retry (1-based).
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+        let ms = self
+            .initial_backoff_ms
+            .saturating_mul(factor)
+            .min(self.max_backoff_ms);
+        Duration::from_millis(ms)
+    }
+}
+
+impl std::fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookConfig")
+            .field("url", &self.url)
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff_ms", &self.initial_backoff_ms)
+            .field("max_backoff_ms", &self.max_backoff_ms)
+            .field("timeout_secs", &self.timeout_secs)
+            .field("dead_letter_path", &self.dead_letter_path)
+            .finish()
+    }
+}
+
+/// Computes the signature header value for a request body.
+#[must_use]
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Verifies a signature header value in constant time.
+#[must_use]
+pub fn verify_signature(secret: &str, timestamp: i64, body: &[u8], signature: &str) -> bool {
+    let Some(hex_sig) = signature.strip_prefix("sha256=") else {
+        return false;
+    };
+    let Ok(expected) = hex::decode(hex_sig) else {
+        return false;
+    };
+
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// An event that could not be delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Endpoint URL.
+    pub url: String,
+    /// Undelivered event.
+    pub event: WebhookEvent,
+    /// Delivery attempts made.
+    pub attempts: u32,
+    /// Last error.
+    pub error: String,
+    /// When delivery was abandoned.
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Outcome of a single delivery attempt.
+enum Attempt {
+    /// Delivered.
+    Delivered,
+    /// Failed, may succeed on retry.
+    Retry(String),
+    /// Failed permanently.
+    Fatal(String),
+}
+
+/// Webhook notifier - POSTs signed events to an HTTP endpoint.
+pub struct WebhookNotifier {
+    /// Configuration.
+    config: WebhookConfig,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    /// Creates a new unsigned webhook notifier with default settings.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_config(WebhookConfig::new(url))
+    }
+
+    /// Creates a new webhook notifier with the given configuration.
+    pub fn with_config(config: WebhookConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+
+    /// Delivers an event, retrying with backoff and dead-lettering on failure.
+    pub async fn send_event(&self, event: &WebhookEvent) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(event)?;
+        let mut attempts = 0;
+
+        let error = loop {
+            attempts += 1;
+            match self.attempt(event, &body).await {
+                Attempt::Delivered => {
+                    debug!(
+                        event_id = %event.id,
+                        attempts = attempts,
+                        "Webhook event delivered"
+                    );
+                    return Ok(());
+                }
+                Attempt::Fatal(e) => break e,
+                Attempt::Retry(e) if attempts > self.config.max_retries => break e,
+                Attempt::Retry(e) => {
+                    let backoff = self.config.backoff(attempts);
+                    warn!(
+                        event_id = %event.id,
+                        attempt = attempts,
+                        backoff_ms = backoff.as_millis() as u64,
+                        error = %e,
+                        "Webhook delivery failed, retrying"
+                    );
+                    tokio::time::sleep(backoff).await;
+                }
+            }
+        };
+
+        error!(
+            event_id = %event.id,
+            attempts = attempts,
+            error = %error,
+            "Webhook delivery abandoned"
+        );
+        self.dead_letter(event, attempts, &error);
+
+        anyhow::bail!(
+            "webhook delivery failed after {} attempts: {}",
+            attempts,
+            error
+        )
+    }
+
+    /// Makes a single delivery attempt.
+    async fn attempt(&self, event: &WebhookEvent, body: &[u8]) -> Attempt {
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event.kind.as_str())
+            .body(body.to_vec());
+
+        if let Some(secret) = &self.config.secret {
+            let timestamp = Utc::now().timestamp();
+            request = request
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, sign_payload(secret, timestamp, body));
+        }
+
+        match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    Attempt::Delivered
+                } else if status.is_server_error()
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                {
+                    Attempt::Retry(format!("endpoint returned {}", status))
+                } else {
+                    Attempt::Fatal(format!("endpoint returned {}", status))
+                }
+            }
+            Err(e) => Attempt::Retry(e.to_string()),
+        }
+    }
+
+    /// Appends an undeliverable event to the dead-letter log.
+    fn dead_letter(&self, event: &WebhookEvent, attempts: u32, error: &str) {
+        let Some(path) = &self.config.dead_letter_path else {
+            return;
+        };
+
+        let letter = DeadLetter {
+            url: self.config.url.clone(),
+            event: event.clone(),
+            attempts,
+            error: error.to_string(),
+            failed_at: Utc::now(),
+        };
+
+        let result = (|| -> anyhow::Result<()> {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(&letter)?)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            error!(
+                event_id = %event.id,
+                path = %path.display(),
+                error = %e,
+                "Failed to write webhook dead letter"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        self.send_event(&WebhookEvent::alert(alert)).await
+    }
+
+    fn name(&self) -> &str {
+        "webhook"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::{AlertLevel, AlertType};
+
+    #[test]
+    fn test_sign_and_verify() {
+        let body = br#"{"hello":"world"}"#;
+        let signature = sign_payload("secret", 1_700_000_000, body);
+        assert_eq!(
+            signature,
+            "sha256=654f06c856baf080af3fa272934823257a542d35cf1f88099338f850a60601a4"
+        );
+
+        assert!(verify_signature("secret", 1_700_000_000, body, &signature));
+        assert!(!verify_signature("secret", 1_700_000_001, body, &signature));
+        assert!(!verify_signature("other", 1_700_000_000, body, &signature));
+        assert!(!verify_signature("secret", 1_700_000_000, body, "md5=abc"));
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let config = WebhookConfig {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 350,
+            ..WebhookConfig::new("http://localhost")
+        };
+
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(350));
+    }
+
+    #[tokio::test]
+    async fn test_undeliverable_event_is_dead_lettered() {
+        let path = std::env::temp_dir().join(format!("dead_letter_{}.jsonl", uuid::Uuid::new_v4()));
+        let config = WebhookConfig {
+            max_retries: 1,
+            initial_backoff_ms: 1,
+            ..WebhookConfig::new("http://127.0.0.1:9/hook")
+                .with_secret("secret")
+                .with_dead_letter(&path)
+        };
+        let notifier = WebhookNotifier::with_config(config);
+        let alert = Alert::new(AlertLevel::Critical, AlertType::SystemError, "Boom");
+
+        assert!(notifier.notify(&alert).await.is_err());
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let letter: DeadLetter = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+        assert_eq!(letter.attempts, 2);
+        assert_eq!(letter.event.kind, WebhookEventKind::Alert);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
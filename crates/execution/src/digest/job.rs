@@ -0,0 +1,216 @@
+//! Scheduled job that sends the daily digest.
+
+use super::{DailyDigest, DigestSnapshot};
+use crate::alerts::Alert;
+use crate::lifecycle::LifecycleTracker;
+use crate::monitor::PositionMonitor;
+use crate::scheduler::{
+    CronSchedule, Schedule, ScheduledTask, Scheduler, SchedulerHandle, TaskKind,
+};
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+/// Name of the scheduled digest task.
+pub const DIGEST_TASK: &str = "daily-digest";
+
+/// Configuration for the daily digest.
+#[derive(Debug, Clone)]
+pub struct DigestConfig {
+    /// When to send the digest.
+    pub schedule: Schedule,
+    /// Distance from a range edge, as a percentage of the pool price, at
+    /// which a position is reported as near the edge.
+    pub edge_threshold_pct: Decimal,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            schedule: Schedule::Cron(
+                CronSchedule::parse("0 8 * * *").expect("valid cron expression"),
+            ),
+            edge_threshold_pct: Decimal::from(5), // 5%
+        }
+    }
+}
+
+impl DigestConfig {
+    /// Sets when to send the digest.
+    #[must_use]
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Sets the range edge threshold as a percentage of the pool price.
+    #[must_use]
+    pub fn with_edge_threshold_pct(mut self, edge_threshold_pct: Decimal) -> Self {
+        self.edge_threshold_pct = edge_threshold_pct;
+        self
+    }
+}
+
+/// Sends a daily summary of the monitored portfolio.
+///
+/// Each digest covers the period since the previous one, or since the job
+/// started for the first digest.
+pub struct DigestJob {
+    /// Pool reader for current ticks.
+    pool_reader: WhirlpoolReader,
+    /// Position monitor.
+    monitor: Arc<PositionMonitor>,
+    /// Lifecycle tracker.
+    lifecycle: Arc<LifecycleTracker>,
+    /// Configuration.
+    config: DigestConfig,
+    /// Snapshot at the start of the current period.
+    baseline: RwLock<Option<DigestSnapshot>>,
+    /// Alert callback receiving each digest.
+    alert_callback: Option<Box<dyn Fn(Alert) + Send + Sync>>,
+    /// Handle of the running scheduler.
+    scheduler: Mutex<Option<SchedulerHandle>>,
+}
+
+impl DigestJob {
+    /// Creates a new digest job.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        monitor: Arc<PositionMonitor>,
+        lifecycle: Arc<LifecycleTracker>,
+        config: DigestConfig,
+    ) -> Self {
+        Self {
+            pool_reader: WhirlpoolReader::new(provider),
+            monitor,
+            lifecycle,
+            config,
+            baseline: RwLock::new(None),
+            alert_callback: None,
+            scheduler: Mutex::new(None),
+        }
+    }
+
+    /// Sets the callback receiving each digest as an alert.
+    pub fn set_alert_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(Alert) + Send + Sync + 'static,
+    {
+        self.alert_callback = Some(Box::new(callback));
+    }
+
+    /// Builds the digest for the period since the last one without sending it.
+    pub async fn preview(&self) -> DailyDigest {
+        self.build().await.0
+    }
+
+    /// Builds and sends the digest, starting a new period.
+    pub async fn send(&self) -> DailyDigest {
+        let (digest, current) = self.build().await;
+
+        info!(
+            positions = digest.positions,
+            value_change_usd = %digest.value_change_usd,
+            fees_earned_usd = %digest.fees_earned_usd,
+            rebalances = digest.rebalances,
+            near_edge = digest.near_edge.len(),
+            "Sending daily digest"
+        );
+        if let Some(callback) = &self.alert_callback {
+            callback(digest.to_alert());
+        }
+
+        *self.baseline.write().await = Some(current);
+        digest
+    }
+
+    /// Builds the digest and the snapshot that ends its period.
+    async fn build(&self) -> (DailyDigest, DigestSnapshot) {
+        let positions = self.monitor.get_positions().await;
+        let stats = self.lifecycle.get_aggregate_stats().await;
+        let current = DigestSnapshot::capture(&positions, &stats, chrono::Utc::now());
+        let previous = self
+            .baseline
+            .read()
+            .await
+            .clone()
+            .unwrap_or_else(|| current.clone());
+
+        let mut pool_ticks = HashMap::new();
+        for position in &positions {
+            if pool_ticks.contains_key(&position.pool) {
+                continue;
+            }
+            match self
+                .pool_reader
+                .get_pool_state(&position.pool.to_string())
+                .await
+            {
+                Ok(state) => {
+                    pool_ticks.insert(position.pool, state.tick_current);
+                }
+                Err(e) => {
+                    warn!(pool = %position.pool, error = %e, "Failed to read pool for digest")
+                }
+            }
+        }
+
+        let digest = DailyDigest::build(
+            &previous,
+            &current,
+            &positions,
+            &pool_ticks,
+            self.config.edge_threshold_pct,
+        );
+        (digest, current)
+    }
+
+    /// Runs the job, sending a digest on each scheduled run until stopped.
+    pub async fn start(&self) {
+        if self.baseline.read().await.is_none() {
+            let positions = self.monitor.get_positions().await;
+            let stats = self.lifecycle.get_aggregate_stats().await;
+            *self.baseline.write().await = Some(DigestSnapshot::capture(
+                &positions,
+                &stats,
+                chrono::Utc::now(),
+            ));
+        }
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_task(
+            ScheduledTask::new(DIGEST_TASK, self.config.schedule.clone())
+                .with_kind(TaskKind::Digest),
+        );
+        let Some(mut events) = scheduler.take_receiver() else {
+            return;
+        };
+        *self.scheduler.lock().await = Some(scheduler.handle());
+        tokio::spawn(async move { scheduler.start().await });
+
+        info!(schedule = ?self.config.schedule, "Starting daily digest job");
+
+        while let Some(event) = events.recv().await {
+            if event.kind == TaskKind::Digest {
+                self.send().await;
+            }
+        }
+
+        info!("Daily digest job stopped");
+    }
+
+    /// Stops the job.
+    pub async fn stop(&self) {
+        if let Some(handle) = self.scheduler.lock().await.take() {
+            handle.stop();
+        }
+    }
+
+    /// Gets the configuration.
+    pub fn config(&self) -> &DigestConfig {
+        &self.config
+    }
+}
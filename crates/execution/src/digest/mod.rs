@@ -0,0 +1,13 @@
+//! Daily portfolio digest.
+//!
+//! Summarizes the monitored portfolio once a day:
+//! - Portfolio value change
+//! - Fees earned and impermanent loss
+//! - Rebalances executed and their transaction costs
+//! - Positions near or past their range edges
+
+mod job;
+mod report;
+
+pub use job::{DIGEST_TASK, DigestConfig, DigestJob};
+pub use report::{DailyDigest, DigestSnapshot, EdgeProximity, RangeEdge};
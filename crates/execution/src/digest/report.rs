@@ -0,0 +1,452 @@
+//! Daily digest contents.
+
+use crate::alerts::{Alert, AlertData, AlertLevel, AlertType};
+use crate::lifecycle::AggregateStats;
+use crate::monitor::MonitoredPosition;
+use chrono::{DateTime, Utc};
+use clmm_lp_protocols::prelude::tick_to_price;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Lamports per SOL.
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Portfolio totals captured at a point in time.
+///
+/// A digest reports the change between the snapshot taken when the previous
+/// digest was sent and the current one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DigestSnapshot {
+    /// When the snapshot was taken.
+    pub taken_at: DateTime<Utc>,
+    /// Total value of monitored positions in USD.
+    pub value_usd: Decimal,
+    /// Uncollected fees of monitored positions in USD.
+    pub outstanding_fees_usd: Decimal,
+    /// Fees collected over all tracked positions in USD.
+    pub collected_fees_usd: Decimal,
+    /// Rebalances over all tracked positions.
+    pub rebalances: u32,
+    /// Transaction costs over all tracked positions in lamports.
+    pub tx_costs_lamports: u64,
+}
+
+impl DigestSnapshot {
+    /// Captures monitored positions and lifecycle totals.
+    #[must_use]
+    pub fn capture(
+        positions: &[MonitoredPosition],
+        stats: &AggregateStats,
+        taken_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            taken_at,
+            value_usd: positions.iter().map(|p| p.pnl.current_value_usd).sum(),
+            outstanding_fees_usd: positions.iter().map(|p| p.pnl.fees_usd).sum(),
+            collected_fees_usd: stats.total_fees_usd,
+            rebalances: stats.total_rebalances,
+            tx_costs_lamports: stats.total_tx_costs_lamports,
+        }
+    }
+}
+
+/// Edge of a position's price range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RangeEdge {
+    /// Lower bound of the range.
+    Lower,
+    /// Upper bound of the range.
+    Upper,
+}
+
+impl fmt::Display for RangeEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lower => write!(f, "lower"),
+            Self::Upper => write!(f, "upper"),
+        }
+    }
+}
+
+/// Distance between the pool price and the nearest edge of a position's range.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeProximity {
+    /// Position address.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Whether the pool price is inside the range.
+    pub in_range: bool,
+    /// Nearest edge, or the edge the price has crossed if out of range.
+    pub edge: RangeEdge,
+    /// Distance from the edge as a percentage of the pool price.
+    pub distance_pct: Decimal,
+}
+
+impl EdgeProximity {
+    /// Measures how close the pool tick is to a position's range edges.
+    #[must_use]
+    pub fn measure(position: &MonitoredPosition, tick_current: i32) -> Self {
+        let (tick_lower, tick_upper) = (position.on_chain.tick_lower, position.on_chain.tick_upper);
+        let price = tick_to_price(tick_current);
+        let lower = tick_to_price(tick_lower);
+        let upper = tick_to_price(tick_upper);
+
+        let in_range = tick_current >= tick_lower && tick_current < tick_upper;
+        let edge = if tick_current < tick_lower || (in_range && price - lower <= upper - price) {
+            RangeEdge::Lower
+        } else {
+            RangeEdge::Upper
+        };
+        let edge_price = match edge {
+            RangeEdge::Lower => lower,
+            RangeEdge::Upper => upper,
+        };
+        let distance_pct = if price.is_zero() {
+            Decimal::ZERO
+        } else {
+            (price - edge_price).abs() / price * Decimal::from(100)
+        };
+
+        Self {
+            position: position.address,
+            pool: position.pool,
+            in_range,
+            edge,
+            distance_pct,
+        }
+    }
+}
+
+/// Daily summary of portfolio performance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyDigest {
+    /// Start of the period.
+    pub period_start: DateTime<Utc>,
+    /// End of the period.
+    pub period_end: DateTime<Utc>,
+    /// Monitored positions.
+    pub positions: u32,
+    /// Monitored positions in range.
+    pub positions_in_range: u32,
+    /// Portfolio value in USD at the end of the period.
+    pub value_usd: Decimal,
+    /// Change in portfolio value in USD, including deposits and withdrawals.
+    pub value_change_usd: Decimal,
+    /// Change in portfolio value as a percentage.
+    pub value_change_pct: Decimal,
+    /// Fees earned over the period in USD, collected or not.
+    pub fees_earned_usd: Decimal,
+    /// Current impermanent loss in USD.
+    pub il_usd: Decimal,
+    /// Current impermanent loss as a percentage of entry value.
+    pub il_pct: Decimal,
+    /// Rebalances executed over the period.
+    pub rebalances: u32,
+    /// Transaction costs over the period in lamports.
+    pub tx_costs_lamports: u64,
+    /// Positions out of range or within the edge threshold, nearest first.
+    pub near_edge: Vec<EdgeProximity>,
+}
+
+impl DailyDigest {
+    /// Builds a digest from the snapshots at the start and end of the period.
+    ///
+    /// Positions whose pool tick is missing from `pool_ticks` are left out of
+    /// the range edge check. A position is near an edge when it is out of
+    /// range or the pool price is within `edge_threshold_pct` of an edge.
+    #[must_use]
+    pub fn build(
+        previous: &DigestSnapshot,
+        current: &DigestSnapshot,
+        positions: &[MonitoredPosition],
+        pool_ticks: &HashMap<Pubkey, i32>,
+        edge_threshold_pct: Decimal,
+    ) -> Self {
+        let hundred = Decimal::from(100);
+
+        let value_change_usd = current.value_usd - previous.value_usd;
+        let value_change_pct = if previous.value_usd.is_zero() {
+            Decimal::ZERO
+        } else {
+            value_change_usd / previous.value_usd * hundred
+        };
+
+        // Collecting moves fees from outstanding to collected
+        let fees_earned_usd = (current.collected_fees_usd - previous.collected_fees_usd
+            + current.outstanding_fees_usd
+            - previous.outstanding_fees_usd)
+            .max(Decimal::ZERO);
+
+        let entry_value: Decimal = positions.iter().map(|p| p.pnl.entry_value_usd).sum();
+        let il_usd: Decimal = positions
+            .iter()
+            .map(|p| p.pnl.entry_value_usd * p.pnl.il_pct.abs())
+            .sum();
+        let il_pct = if entry_value.is_zero() {
+            Decimal::ZERO
+        } else {
+            il_usd / entry_value * hundred
+        };
+
+        let mut near_edge: Vec<EdgeProximity> = positions
+            .iter()
+            .filter_map(|p| {
+                let tick = pool_ticks.get(&p.pool)?;
+                let proximity = EdgeProximity::measure(p, *tick);
+                (!proximity.in_range || proximity.distance_pct <= edge_threshold_pct)
+                    .then_some(proximity)
+            })
+            .collect();
+        near_edge.sort_by(|a, b| {
+            a.in_range
+                .cmp(&b.in_range)
+                .then(a.distance_pct.cmp(&b.distance_pct))
+        });
+
+        Self {
+            period_start: previous.taken_at,
+            period_end: current.taken_at,
+            positions: positions.len() as u32,
+            positions_in_range: positions.iter().filter(|p| p.in_range).count() as u32,
+            value_usd: current.value_usd,
+            value_change_usd,
+            value_change_pct,
+            fees_earned_usd,
+            il_usd,
+            il_pct,
+            rebalances: current.rebalances.saturating_sub(previous.rebalances),
+            tx_costs_lamports: current
+                .tx_costs_lamports
+                .saturating_sub(previous.tx_costs_lamports),
+            near_edge,
+        }
+    }
+
+    /// Transaction costs over the period in SOL.
+    #[must_use]
+    pub fn tx_costs_sol(&self) -> Decimal {
+        Decimal::from(self.tx_costs_lamports) / Decimal::from(LAMPORTS_PER_SOL)
+    }
+
+    /// Formats the digest as a plain-text message.
+    #[must_use]
+    pub fn format(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "Daily digest {} - {}",
+                self.period_start.format("%Y-%m-%d %H:%M"),
+                self.period_end.format("%Y-%m-%d %H:%M UTC")
+            ),
+            format!(
+                "Portfolio value: ${} ({}${} / {}%)",
+                self.value_usd.round_dp(2),
+                if self.value_change_usd.is_sign_negative() {
+                    "-"
+                } else {
+                    "+"
+                },
+                self.value_change_usd.abs().round_dp(2),
+                self.value_change_pct.round_dp(2)
+            ),
+            format!("Fees earned: ${}", self.fees_earned_usd.round_dp(2)),
+            format!(
+                "Impermanent loss: ${} ({}%)",
+                self.il_usd.round_dp(2),
+                self.il_pct.round_dp(2)
+            ),
+            format!(
+                "Rebalances: {} (tx costs {} SOL)",
+                self.rebalances,
+                self.tx_costs_sol().round_dp(6)
+            ),
+            format!(
+                "Positions in range: {}/{}",
+                self.positions_in_range, self.positions
+            ),
+        ];
+
+        if !self.near_edge.is_empty() {
+            lines.push("Near range edge:".to_string());
+            for p in &self.near_edge {
+                lines.push(if p.in_range {
+                    format!(
+                        "- {} {}% from {} edge",
+                        p.position,
+                        p.distance_pct.round_dp(2),
+                        p.edge
+                    )
+                } else {
+                    format!(
+                        "- {} out of range, {}% past {} edge",
+                        p.position,
+                        p.distance_pct.round_dp(2),
+                        p.edge
+                    )
+                });
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    /// Converts the digest into an informational alert.
+    #[must_use]
+    pub fn to_alert(&self) -> Alert {
+        let custom = HashMap::from([
+            ("rebalances".to_string(), self.rebalances.to_string()),
+            (
+                "tx_costs_lamports".to_string(),
+                self.tx_costs_lamports.to_string(),
+            ),
+            ("near_edge".to_string(), self.near_edge.len().to_string()),
+        ]);
+
+        Alert::new(
+            AlertLevel::Info,
+            AlertType::Custom("Daily Digest".to_string()),
+            self.format(),
+        )
+        .with_data(AlertData {
+            il_pct: Some(self.il_pct),
+            pnl: Some(self.value_change_usd),
+            fees: Some(self.fees_earned_usd),
+            custom: Some(custom),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::PositionPnL;
+    use clmm_lp_protocols::prelude::OnChainPosition;
+
+    fn position(
+        pool: Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+        pnl: PositionPnL,
+    ) -> MonitoredPosition {
+        MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool,
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool,
+                owner: Pubkey::new_unique(),
+                tick_lower,
+                tick_upper,
+                liquidity: 1_000_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+            },
+            pnl,
+            in_range: true,
+            out_of_range_since: None,
+            last_updated: Utc::now(),
+        }
+    }
+
+    fn snapshot(
+        value: i64,
+        outstanding: i64,
+        collected: i64,
+        rebalances: u32,
+        lamports: u64,
+    ) -> DigestSnapshot {
+        DigestSnapshot {
+            taken_at: Utc::now(),
+            value_usd: Decimal::from(value),
+            outstanding_fees_usd: Decimal::from(outstanding),
+            collected_fees_usd: Decimal::from(collected),
+            rebalances,
+            tx_costs_lamports: lamports,
+        }
+    }
+
+    #[test]
+    fn test_build_reports_period_changes() {
+        let pool = Pubkey::new_unique();
+        let positions = vec![
+            position(
+                pool,
+                -1000,
+                1000,
+                PositionPnL {
+                    entry_value_usd: Decimal::from(1_000),
+                    il_pct: Decimal::new(-2, 2), // 2%
+                    ..Default::default()
+                },
+            ),
+            position(
+                pool,
+                -1000,
+                1000,
+                PositionPnL {
+                    entry_value_usd: Decimal::from(3_000),
+                    ..Default::default()
+                },
+            ),
+        ];
+        // $30 outstanding collected during the day, then $5 more accrued
+        let previous = snapshot(4_000, 30, 100, 2, 10_000);
+        let current = snapshot(4_100, 5, 130, 3, 25_000);
+
+        let digest = DailyDigest::build(
+            &previous,
+            &current,
+            &positions,
+            &HashMap::new(),
+            Decimal::from(5),
+        );
+
+        assert_eq!(digest.value_change_usd, Decimal::from(100));
+        assert_eq!(digest.value_change_pct, Decimal::new(25, 1));
+        assert_eq!(digest.fees_earned_usd, Decimal::from(5));
+        assert_eq!(digest.il_usd, Decimal::from(20));
+        assert_eq!(digest.il_pct, Decimal::new(5, 1));
+        assert_eq!(digest.rebalances, 1);
+        assert_eq!(digest.tx_costs_lamports, 15_000);
+        assert!(digest.near_edge.is_empty());
+
+        let alert = digest.to_alert();
+        assert_eq!(alert.level, AlertLevel::Info);
+        assert!(alert.message.contains("Rebalances: 1"));
+    }
+
+    #[test]
+    fn test_near_edge_positions() {
+        let pool = Pubkey::new_unique();
+        let unknown_pool = Pubkey::new_unique();
+        let centered = position(pool, -1000, 1000, PositionPnL::default());
+        let near_upper = position(pool, -2000, 200, PositionPnL::default());
+        let out_of_range = position(pool, 500, 1500, PositionPnL::default());
+        let no_tick = position(unknown_pool, 500, 1500, PositionPnL::default());
+        let positions = vec![centered, near_upper.clone(), out_of_range.clone(), no_tick];
+        let ticks = HashMap::from([(pool, 0)]);
+        let now = snapshot(0, 0, 0, 0, 0);
+
+        let digest = DailyDigest::build(&now, &now, &positions, &ticks, Decimal::from(5));
+
+        assert_eq!(digest.near_edge.len(), 2);
+        let first = &digest.near_edge[0];
+        assert_eq!(first.position, out_of_range.address);
+        assert!(!first.in_range);
+        assert_eq!(first.edge, RangeEdge::Lower);
+
+        let second = &digest.near_edge[1];
+        assert_eq!(second.position, near_upper.address);
+        assert_eq!(second.edge, RangeEdge::Upper);
+        // 200 ticks is about 2%
+        assert!(second.distance_pct > Decimal::from(1) && second.distance_pct < Decimal::from(3));
+        assert!(digest.format().contains("out of range"));
+    }
+}
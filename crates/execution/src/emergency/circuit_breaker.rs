@@ -1,5 +1,6 @@
 //! Circuit breaker for automated trading safety.
 
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
@@ -59,6 +60,10 @@ pub struct CircuitBreaker {
     opened_at: Arc<RwLock<Option<Instant>>>,
     /// Manual trip flag.
     manually_tripped: AtomicBool,
+    /// Reason for the most recent trip.
+    trip_reason: Arc<RwLock<Option<String>>>,
+    /// Wall-clock time of the most recent trip.
+    tripped_at: Arc<RwLock<Option<DateTime<Utc>>>>,
     /// Callback for state changes.
     #[allow(dead_code)]
     on_state_change: Option<Box<dyn Fn(CircuitState) + Send + Sync>>,
@@ -74,6 +79,8 @@ impl CircuitBreaker {
             success_count: AtomicU32::new(0),
             opened_at: Arc::new(RwLock::new(None)),
             manually_tripped: AtomicBool::new(false),
+            trip_reason: Arc::new(RwLock::new(None)),
+            tripped_at: Arc::new(RwLock::new(None)),
             on_state_change: None,
         }
     }
@@ -183,11 +190,23 @@ impl CircuitBreaker {
         info!("Manual trip flag reset");
     }
 
+    /// Returns whether the operator kill switch is engaged.
+    pub fn is_manually_tripped(&self) -> bool {
+        self.manually_tripped.load(Ordering::SeqCst)
+    }
+
+    /// Returns the configuration.
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
     /// Trips the circuit breaker.
     async fn trip(&self, reason: &str) {
         error!(reason = reason, "Circuit breaker tripped");
         self.transition_to(CircuitState::Open).await;
         *self.opened_at.write().await = Some(Instant::now());
+        *self.trip_reason.write().await = Some(reason.to_string());
+        *self.tripped_at.write().await = Some(Utc::now());
         self.failure_count.store(0, Ordering::SeqCst);
     }
 
@@ -218,6 +237,8 @@ impl CircuitBreaker {
         self.success_count.store(0, Ordering::SeqCst);
         self.manually_tripped.store(false, Ordering::SeqCst);
         *self.opened_at.write().await = None;
+        *self.trip_reason.write().await = None;
+        *self.tripped_at.write().await = None;
         info!("Circuit breaker reset");
     }
 
@@ -229,6 +250,8 @@ impl CircuitBreaker {
             success_count: self.success_count.load(Ordering::SeqCst),
             manually_tripped: self.manually_tripped.load(Ordering::SeqCst),
             opened_at: *self.opened_at.read().await,
+            trip_reason: self.trip_reason.read().await.clone(),
+            tripped_at: *self.tripped_at.read().await,
         }
     }
 }
@@ -252,6 +275,10 @@ pub struct CircuitBreakerStats {
     pub manually_tripped: bool,
     /// When circuit was opened.
     pub opened_at: Option<Instant>,
+    /// Reason for the most recent trip.
+    pub trip_reason: Option<String>,
+    /// Wall-clock time of the most recent trip.
+    pub tripped_at: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
@@ -303,4 +330,24 @@ mod tests {
         cb.reset().await;
         assert!(cb.is_allowed().await);
     }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_records_trip_reason() {
+        let cb = CircuitBreaker::default();
+
+        cb.manual_trip("operator halt").await;
+        let stats = cb.stats().await;
+        assert!(cb.is_manually_tripped());
+        assert_eq!(
+            stats.trip_reason.as_deref(),
+            Some("manual trip: operator halt")
+        );
+        assert!(stats.tripped_at.is_some());
+
+        cb.reset().await;
+        let stats = cb.stats().await;
+        assert!(!cb.is_manually_tripped());
+        assert!(stats.trip_reason.is_none());
+        assert!(stats.tripped_at.is_none());
+    }
 }
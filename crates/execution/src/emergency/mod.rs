@@ -4,9 +4,12 @@
 //! - Circuit breaker for consecutive failures
 //! - Emergency position exit
 //! - Loss threshold protection
+//! - Portfolio drawdown and token exposure limits with a portfolio stop
 
 mod circuit_breaker;
 mod emergency_exit;
+mod risk_limits;
 
 pub use circuit_breaker::*;
 pub use emergency_exit::*;
+pub use risk_limits::*;
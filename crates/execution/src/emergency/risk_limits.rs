@@ -0,0 +1,791 @@
+//! Portfolio-wide risk limits and stop.
+
+use super::{CircuitBreaker, EmergencyExitManager, ExitStatus};
+use crate::alerts::{Alert, AlertLevel, AlertType};
+use crate::monitor::PositionMonitor;
+use crate::oracle::{MINT_DECIMALS_OFFSET, PriceOracle, adjust_for_decimals};
+use chrono::{DateTime, Utc};
+use clmm_lp_config::prelude::ExecutionSettings;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+/// Configuration for portfolio risk limits.
+#[derive(Debug, Clone)]
+pub struct RiskLimitsConfig {
+    /// Maximum drop of the portfolio value from its peak, as a percentage.
+    pub max_drawdown_pct: Option<Decimal>,
+    /// Maximum USD exposure to any single token.
+    pub max_token_exposure_usd: Option<Decimal>,
+    /// Per-token USD exposure limits, overriding the default limit.
+    pub token_exposure_limits: HashMap<Pubkey, Decimal>,
+    /// Whether to close all positions when the portfolio stop triggers.
+    pub unwind_on_stop: bool,
+    /// Seconds between checks.
+    pub check_interval_secs: u64,
+}
+
+impl Default for RiskLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_drawdown_pct: None,
+            max_token_exposure_usd: None,
+            token_exposure_limits: HashMap::new(),
+            unwind_on_stop: false,
+            check_interval_secs: 60,
+        }
+    }
+}
+
+impl RiskLimitsConfig {
+    /// Creates a configuration from the shared execution settings.
+    ///
+    /// Limits set to 0 are disabled.
+    #[must_use]
+    pub fn from_settings(settings: &ExecutionSettings) -> Self {
+        let positive = |value: f64| Decimal::from_f64(value).filter(|v| *v > Decimal::ZERO);
+        Self {
+            max_drawdown_pct: positive(settings.max_portfolio_drawdown_pct),
+            max_token_exposure_usd: positive(settings.max_token_exposure_usd),
+            unwind_on_stop: settings.unwind_on_portfolio_stop,
+            check_interval_secs: settings.risk_check_interval_secs,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the USD exposure limit of a token.
+    #[must_use]
+    pub fn with_token_limit(mut self, mint: Pubkey, max_usd: Decimal) -> Self {
+        self.token_exposure_limits.insert(mint, max_usd);
+        self
+    }
+
+    /// Returns true if any limit is set.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.max_drawdown_pct.is_some()
+            || self.max_token_exposure_usd.is_some()
+            || !self.token_exposure_limits.is_empty()
+    }
+
+    /// Gets the USD exposure limit of a token.
+    #[must_use]
+    pub fn token_limit(&self, mint: &Pubkey) -> Option<Decimal> {
+        self.token_exposure_limits
+            .get(mint)
+            .copied()
+            .or(self.max_token_exposure_usd)
+    }
+}
+
+/// Holdings of one token across the portfolio.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenHolding {
+    /// Amount in whole tokens.
+    pub amount: Decimal,
+    /// Value in USD.
+    pub value_usd: Decimal,
+}
+
+/// Value of the portfolio by position and by token.
+#[derive(Debug, Clone, Default)]
+pub struct PortfolioValuation {
+    /// Value of each position in USD.
+    pub positions: HashMap<Pubkey, Decimal>,
+    /// Holdings by token mint.
+    pub tokens: HashMap<Pubkey, TokenHolding>,
+}
+
+impl PortfolioValuation {
+    /// Adds a position's token holdings.
+    pub fn add_position(&mut self, position: Pubkey, holdings: [(Pubkey, TokenHolding); 2]) {
+        let value: Decimal = holdings.iter().map(|(_, h)| h.value_usd).sum();
+        *self.positions.entry(position).or_default() += value;
+        for (mint, holding) in holdings {
+            let total = self.tokens.entry(mint).or_default();
+            total.amount += holding.amount;
+            total.value_usd += holding.value_usd;
+        }
+    }
+
+    /// Gets the total value in USD.
+    #[must_use]
+    pub fn value_usd(&self) -> Decimal {
+        self.positions.values().sum()
+    }
+}
+
+/// Exposure to one token against its limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenExposure {
+    /// Token mint.
+    pub mint: Pubkey,
+    /// Amount in whole tokens.
+    pub amount: Decimal,
+    /// Value in USD.
+    pub value_usd: Decimal,
+    /// Share of the portfolio value, as a percentage.
+    pub share_pct: Decimal,
+    /// USD limit, if any.
+    pub limit_usd: Option<Decimal>,
+}
+
+/// A breached portfolio limit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskBreach {
+    /// The portfolio fell too far from its peak value.
+    Drawdown {
+        /// Drawdown from the peak, as a percentage.
+        drawdown_pct: Decimal,
+        /// Maximum drawdown, as a percentage.
+        max_pct: Decimal,
+    },
+    /// Too much value is held in one token.
+    TokenExposure {
+        /// Token mint.
+        mint: Pubkey,
+        /// Exposure in USD.
+        value_usd: Decimal,
+        /// Maximum exposure in USD.
+        max_usd: Decimal,
+    },
+}
+
+impl fmt::Display for RiskBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Drawdown {
+                drawdown_pct,
+                max_pct,
+            } => write!(
+                f,
+                "portfolio drawdown {}% exceeds {}%",
+                drawdown_pct.round_dp(2),
+                max_pct
+            ),
+            Self::TokenExposure {
+                mint,
+                value_usd,
+                max_usd,
+            } => write!(
+                f,
+                "exposure to {} of ${} exceeds ${}",
+                mint,
+                value_usd.round_dp(2),
+                max_usd
+            ),
+        }
+    }
+}
+
+/// Current portfolio risk against the limits.
+#[derive(Debug, Clone, Default)]
+pub struct RiskStatus {
+    /// Portfolio value in USD.
+    pub value_usd: Decimal,
+    /// Peak portfolio value in USD, adjusted for positions added and removed.
+    pub peak_value_usd: Decimal,
+    /// Drawdown from the peak, as a percentage.
+    pub drawdown_pct: Decimal,
+    /// Exposure by token, largest first.
+    pub exposures: Vec<TokenExposure>,
+    /// Limits breached at the last check.
+    pub breaches: Vec<RiskBreach>,
+    /// Whether the portfolio stop is engaged.
+    pub stopped: bool,
+    /// Why the portfolio stop was engaged.
+    pub stop_reason: Option<String>,
+    /// When the portfolio stop was engaged.
+    pub stopped_at: Option<DateTime<Utc>>,
+    /// Positions closed after the stop, if unwinding was requested.
+    pub unwound_positions: Option<usize>,
+    /// When the limits were last checked.
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl RiskStatus {
+    /// Updates the status with a new valuation.
+    ///
+    /// The peak moves with positions added to or removed from the
+    /// portfolio, so closing a position or moving liquidity to a new one
+    /// is not mistaken for a loss.
+    fn update(
+        &mut self,
+        config: &RiskLimitsConfig,
+        previous: &HashMap<Pubkey, Decimal>,
+        valuation: &PortfolioValuation,
+        now: DateTime<Utc>,
+    ) {
+        for (position, value) in previous {
+            if !valuation.positions.contains_key(position) {
+                self.peak_value_usd -= *value;
+            }
+        }
+        for (position, value) in &valuation.positions {
+            if !previous.contains_key(position) {
+                self.peak_value_usd += *value;
+            }
+        }
+
+        self.value_usd = valuation.value_usd();
+        self.peak_value_usd = self.peak_value_usd.max(self.value_usd).max(Decimal::ZERO);
+        self.drawdown_pct = if self.peak_value_usd.is_zero() {
+            Decimal::ZERO
+        } else {
+            (self.peak_value_usd - self.value_usd) / self.peak_value_usd * Decimal::from(100)
+        };
+
+        self.exposures = valuation
+            .tokens
+            .iter()
+            .map(|(mint, holding)| TokenExposure {
+                mint: *mint,
+                amount: holding.amount,
+                value_usd: holding.value_usd,
+                share_pct: if self.value_usd.is_zero() {
+                    Decimal::ZERO
+                } else {
+                    holding.value_usd / self.value_usd * Decimal::from(100)
+                },
+                limit_usd: config.token_limit(mint),
+            })
+            .collect();
+        self.exposures
+            .sort_by(|a, b| b.value_usd.cmp(&a.value_usd).then(a.mint.cmp(&b.mint)));
+
+        self.breaches.clear();
+        if let Some(max_pct) = config.max_drawdown_pct
+            && self.drawdown_pct > max_pct
+        {
+            self.breaches.push(RiskBreach::Drawdown {
+                drawdown_pct: self.drawdown_pct,
+                max_pct,
+            });
+        }
+        for exposure in &self.exposures {
+            if let Some(max_usd) = exposure.limit_usd
+                && exposure.value_usd > max_usd
+            {
+                self.breaches.push(RiskBreach::TokenExposure {
+                    mint: exposure.mint,
+                    value_usd: exposure.value_usd,
+                    max_usd,
+                });
+            }
+        }
+        self.checked_at = Some(now);
+    }
+}
+
+/// Enforces portfolio-wide risk limits.
+///
+/// Values the monitored positions by token at each check and engages the
+/// portfolio stop when the portfolio falls too far from its peak or holds
+/// too much of one token. The stop trips the shared circuit breaker, which
+/// pauses every strategy executor, and optionally closes all positions. It
+/// stays engaged until [`PortfolioRiskManager::resume`] is called.
+pub struct PortfolioRiskManager {
+    /// RPC provider for reading mint decimals.
+    provider: Arc<RpcProvider>,
+    /// Pool reader for current prices.
+    pool_reader: WhirlpoolReader,
+    /// Position reader for token amounts.
+    position_reader: PositionReader,
+    /// Position monitor.
+    monitor: Arc<PositionMonitor>,
+    /// Oracle of USD token prices.
+    oracle: Arc<dyn PriceOracle>,
+    /// Circuit breaker shared with the strategy executors.
+    circuit_breaker: Arc<CircuitBreaker>,
+    /// Exit manager closing positions when unwinding.
+    exit_manager: Option<Arc<EmergencyExitManager>>,
+    /// Configuration.
+    config: RiskLimitsConfig,
+    /// Current status.
+    status: RwLock<RiskStatus>,
+    /// Position values at the last check.
+    position_values: RwLock<HashMap<Pubkey, Decimal>>,
+    /// Decimals by mint, read once.
+    decimals: RwLock<HashMap<Pubkey, u8>>,
+    /// Alert callback.
+    alert_callback: Option<Box<dyn Fn(Alert) + Send + Sync>>,
+    /// Callback receiving the status when the portfolio stop engages.
+    stop_callback: Option<Box<dyn Fn(&RiskStatus) + Send + Sync>>,
+}
+
+impl PortfolioRiskManager {
+    /// Creates a risk manager for the monitored portfolio.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        monitor: Arc<PositionMonitor>,
+        oracle: Arc<dyn PriceOracle>,
+        circuit_breaker: Arc<CircuitBreaker>,
+        config: RiskLimitsConfig,
+    ) -> Self {
+        Self {
+            pool_reader: WhirlpoolReader::new(provider.clone()),
+            position_reader: PositionReader::new(provider.clone()),
+            provider,
+            monitor,
+            oracle,
+            circuit_breaker,
+            exit_manager: None,
+            config,
+            status: RwLock::new(RiskStatus::default()),
+            position_values: RwLock::new(HashMap::new()),
+            decimals: RwLock::new(HashMap::new()),
+            alert_callback: None,
+            stop_callback: None,
+        }
+    }
+
+    /// Sets the exit manager closing positions when the stop unwinds them.
+    pub fn set_exit_manager(&mut self, exit_manager: Arc<EmergencyExitManager>) {
+        self.exit_manager = Some(exit_manager);
+    }
+
+    /// Sets the alert callback.
+    pub fn set_alert_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(Alert) + Send + Sync + 'static,
+    {
+        self.alert_callback = Some(Box::new(callback));
+    }
+
+    /// Sets the callback receiving the status when the portfolio stop engages.
+    pub fn set_stop_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&RiskStatus) + Send + Sync + 'static,
+    {
+        self.stop_callback = Some(Box::new(callback));
+    }
+
+    /// Gets the configuration.
+    pub fn config(&self) -> &RiskLimitsConfig {
+        &self.config
+    }
+
+    /// Gets the status at the last check.
+    pub async fn status(&self) -> RiskStatus {
+        self.status.read().await.clone()
+    }
+
+    /// Values the portfolio and checks it against the limits.
+    ///
+    /// Engages the portfolio stop on the first breach.
+    ///
+    /// # Errors
+    /// Returns an error if the portfolio cannot be valued.
+    pub async fn check(&self) -> anyhow::Result<RiskStatus> {
+        let valuation = self.value_portfolio().await?;
+        self.record(valuation, Utc::now()).await
+    }
+
+    /// Checks a valuation against the limits.
+    ///
+    /// Engages the portfolio stop on the first breach.
+    ///
+    /// # Errors
+    /// Returns an error if unwinding positions fails to start.
+    pub async fn record(
+        &self,
+        valuation: PortfolioValuation,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<RiskStatus> {
+        let breaches = {
+            let mut previous = self.position_values.write().await;
+            let mut status = self.status.write().await;
+            status.update(&self.config, &previous, &valuation, now);
+            *previous = valuation.positions;
+            (!status.stopped).then(|| status.breaches.clone())
+        };
+
+        if let Some(breaches) = breaches.filter(|b| !b.is_empty()) {
+            let reason = breaches
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ");
+            self.stop(&reason).await;
+        }
+        Ok(self.status().await)
+    }
+
+    /// Engages the portfolio stop.
+    ///
+    /// Pauses all strategies through the circuit breaker, raises a critical
+    /// alert and closes all positions if configured to unwind.
+    pub async fn stop(&self, reason: &str) {
+        {
+            let mut status = self.status.write().await;
+            if status.stopped {
+                return;
+            }
+            status.stopped = true;
+            status.stop_reason = Some(reason.to_string());
+            status.stopped_at = Some(Utc::now());
+            status.unwound_positions = None;
+        }
+
+        error!(reason = %reason, "Portfolio stop engaged");
+        self.circuit_breaker
+            .manual_trip(&format!("portfolio stop: {}", reason))
+            .await;
+
+        let unwind = self.config.unwind_on_stop && self.exit_manager.is_some();
+        if let Some(callback) = &self.alert_callback {
+            let action = if unwind {
+                "closing all positions"
+            } else {
+                "strategies paused"
+            };
+            callback(Alert::new(
+                AlertLevel::Critical,
+                AlertType::PortfolioStop,
+                format!("Portfolio stop engaged, {}: {}", action, reason),
+            ));
+        }
+        if let Some(callback) = &self.stop_callback {
+            callback(&self.status().await);
+        }
+
+        if unwind && let Some(exit_manager) = &self.exit_manager {
+            let results = exit_manager.exit_all().await;
+            let closed = results
+                .iter()
+                .filter(|r| r.status == ExitStatus::Completed)
+                .count();
+            if closed < results.len() {
+                warn!(
+                    closed = closed,
+                    total = results.len(),
+                    "Portfolio stop could not close every position"
+                );
+            }
+            self.status.write().await.unwound_positions = Some(closed);
+        }
+    }
+
+    /// Releases the portfolio stop and resumes strategies.
+    ///
+    /// The peak is reset to the current value, so the drawdown counts from
+    /// the moment of resuming.
+    pub async fn resume(&self) {
+        {
+            let mut status = self.status.write().await;
+            status.stopped = false;
+            status.stop_reason = None;
+            status.stopped_at = None;
+            status.unwound_positions = None;
+            status.breaches.clear();
+            status.peak_value_usd = status.value_usd;
+            status.drawdown_pct = Decimal::ZERO;
+        }
+        self.circuit_breaker.reset().await;
+        info!("Portfolio stop released");
+    }
+
+    /// Runs the checks on the configured interval until stopped.
+    pub async fn start(&self) {
+        let mut ticker = interval(Duration::from_secs(self.config.check_interval_secs.max(1)));
+        info!(
+            interval_secs = self.config.check_interval_secs,
+            "Starting portfolio risk checks"
+        );
+
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check().await {
+                error!(error = %e, "Portfolio risk check failed");
+            }
+        }
+    }
+
+    /// Values the monitored positions by token.
+    ///
+    /// Positions whose tokens have no price are skipped with a warning.
+    ///
+    /// # Errors
+    /// Returns an error if a pool cannot be read.
+    pub async fn value_portfolio(&self) -> anyhow::Result<PortfolioValuation> {
+        let mut valuation = PortfolioValuation::default();
+        let mut pools = HashMap::new();
+
+        for position in self.monitor.get_positions().await {
+            if !pools.contains_key(&position.pool) {
+                let pool = self
+                    .pool_reader
+                    .get_pool_state(&position.pool.to_string())
+                    .await?;
+                pools.insert(position.pool, pool);
+            }
+            let pool = &pools[&position.pool];
+
+            let (amount_a, amount_b) = self.position_reader.calculate_token_amounts(
+                &position.on_chain,
+                pool.tick_current,
+                pool.sqrt_price,
+            );
+            let decimals_a = self.mint_decimals(&pool.token_mint_a).await?;
+            let decimals_b = self.mint_decimals(&pool.token_mint_b).await?;
+            let Some((price_a, price_b)) = self.token_prices(pool, decimals_a, decimals_b).await
+            else {
+                warn!(
+                    position = %position.address,
+                    pool = %position.pool,
+                    "No USD price for pool tokens, position left out of risk limits"
+                );
+                continue;
+            };
+
+            let whole = |amount: u64, decimals: u8| {
+                Decimal::from(amount) / Decimal::from(10u64.pow(u32::from(decimals)))
+            };
+            let amount_a = whole(
+                amount_a.saturating_add(position.on_chain.fees_owed_a),
+                decimals_a,
+            );
+            let amount_b = whole(
+                amount_b.saturating_add(position.on_chain.fees_owed_b),
+                decimals_b,
+            );
+            valuation.add_position(
+                position.address,
+                [
+                    (
+                        pool.token_mint_a,
+                        TokenHolding {
+                            amount: amount_a,
+                            value_usd: amount_a * price_a,
+                        },
+                    ),
+                    (
+                        pool.token_mint_b,
+                        TokenHolding {
+                            amount: amount_b,
+                            value_usd: amount_b * price_b,
+                        },
+                    ),
+                ],
+            );
+        }
+
+        Ok(valuation)
+    }
+
+    /// Gets the USD prices of a pool's tokens.
+    ///
+    /// A token without an oracle feed is priced through the pool from the
+    /// other token.
+    async fn token_prices(
+        &self,
+        pool: &WhirlpoolState,
+        decimals_a: u8,
+        decimals_b: u8,
+    ) -> Option<(Decimal, Decimal)> {
+        let usd = |mint: Pubkey| async move {
+            match self.oracle.usd_price(&mint).await {
+                Ok(price) => price.map(|p| p.price).filter(|p| *p > Decimal::ZERO),
+                Err(e) => {
+                    warn!(mint = %mint, error = %e, "Failed to read oracle price");
+                    None
+                }
+            }
+        };
+        let pool_price = adjust_for_decimals(pool.price, decimals_a, decimals_b);
+
+        match (usd(pool.token_mint_a).await, usd(pool.token_mint_b).await) {
+            (Some(a), Some(b)) => Some((a, b)),
+            (Some(a), None) if !pool_price.is_zero() => Some((a, a / pool_price)),
+            (None, Some(b)) => Some((pool_price * b, b)),
+            _ => None,
+        }
+    }
+
+    /// Gets the decimals of a mint, reading the mint account on first use.
+    async fn mint_decimals(&self, mint: &Pubkey) -> anyhow::Result<u8> {
+        if let Some(decimals) = self.decimals.read().await.get(mint) {
+            return Ok(*decimals);
+        }
+
+        let account = self.provider.get_account(mint).await?;
+        let decimals = *account
+            .data
+            .get(MINT_DECIMALS_OFFSET)
+            .ok_or_else(|| anyhow::anyhow!("Account {} is not a token mint", mint))?;
+        self.decimals.write().await.insert(*mint, decimals);
+        Ok(decimals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn holding(amount: Decimal, value_usd: Decimal) -> TokenHolding {
+        TokenHolding { amount, value_usd }
+    }
+
+    #[test]
+    fn test_valuation_sums_tokens_across_positions() {
+        let (sol, usdc) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut valuation = PortfolioValuation::default();
+        valuation.add_position(
+            Pubkey::new_unique(),
+            [
+                (sol, holding(dec!(10), dec!(1500))),
+                (usdc, holding(dec!(500), dec!(500))),
+            ],
+        );
+        valuation.add_position(
+            Pubkey::new_unique(),
+            [
+                (sol, holding(dec!(2), dec!(300))),
+                (usdc, holding(dec!(700), dec!(700))),
+            ],
+        );
+
+        assert_eq!(valuation.value_usd(), dec!(3000));
+        assert_eq!(valuation.tokens[&sol], holding(dec!(12), dec!(1800)));
+        assert_eq!(valuation.tokens[&usdc].value_usd, dec!(1200));
+    }
+
+    #[test]
+    fn test_drawdown_breach() {
+        let config = RiskLimitsConfig {
+            max_drawdown_pct: Some(dec!(10)),
+            ..Default::default()
+        };
+        let (position, sol, usdc) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let valued = |value: Decimal| {
+            let mut valuation = PortfolioValuation::default();
+            valuation.add_position(
+                position,
+                [
+                    (sol, holding(Decimal::ONE, value / dec!(2))),
+                    (usdc, holding(value / dec!(2), value / dec!(2))),
+                ],
+            );
+            valuation
+        };
+
+        let mut status = RiskStatus::default();
+        let mut previous = HashMap::new();
+        for (value, breached) in [
+            (dec!(1000), false),
+            (dec!(1200), false),
+            (dec!(1100), false),
+            (dec!(1000), true),
+        ] {
+            let valuation = valued(value);
+            status.update(&config, &previous, &valuation, Utc::now());
+            previous = valuation.positions;
+            assert_eq!(!status.breaches.is_empty(), breached, "value {}", value);
+        }
+
+        assert_eq!(status.peak_value_usd, dec!(1200));
+        assert_eq!(status.drawdown_pct.round_dp(2), dec!(16.67));
+        assert!(matches!(
+            status.breaches[0],
+            RiskBreach::Drawdown { max_pct, .. } if max_pct == dec!(10)
+        ));
+    }
+
+    #[test]
+    fn test_peak_follows_positions_added_and_removed() {
+        let config = RiskLimitsConfig {
+            max_drawdown_pct: Some(dec!(10)),
+            ..Default::default()
+        };
+        let (a, b, sol, usdc) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let add = |valuation: &mut PortfolioValuation, position| {
+            valuation.add_position(
+                position,
+                [
+                    (sol, holding(dec!(5), dec!(500))),
+                    (usdc, holding(dec!(500), dec!(500))),
+                ],
+            );
+        };
+        let mut status = RiskStatus::default();
+
+        let mut both = PortfolioValuation::default();
+        add(&mut both, a);
+        add(&mut both, b);
+        status.update(&config, &HashMap::new(), &both, Utc::now());
+        assert_eq!(status.peak_value_usd, dec!(2000));
+
+        // Closing a position halves the value but is not a drawdown
+        let mut one = PortfolioValuation::default();
+        add(&mut one, a);
+        status.update(&config, &both.positions, &one, Utc::now());
+        assert_eq!(status.peak_value_usd, dec!(1000));
+        assert!(status.drawdown_pct.is_zero());
+        assert!(status.breaches.is_empty());
+    }
+
+    #[test]
+    fn test_token_exposure_breach() {
+        let (sol, usdc) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let config = RiskLimitsConfig {
+            max_token_exposure_usd: Some(dec!(1000)),
+            ..Default::default()
+        }
+        .with_token_limit(usdc, dec!(5000));
+
+        let mut valuation = PortfolioValuation::default();
+        valuation.add_position(
+            Pubkey::new_unique(),
+            [
+                (sol, holding(dec!(10), dec!(1500))),
+                (usdc, holding(dec!(1500), dec!(1500))),
+            ],
+        );
+        let mut status = RiskStatus::default();
+        status.update(&config, &HashMap::new(), &valuation, Utc::now());
+
+        assert_eq!(status.exposures.len(), 2);
+        assert_eq!(status.exposures[0].share_pct, dec!(50));
+        assert_eq!(
+            status.breaches,
+            vec![RiskBreach::TokenExposure {
+                mint: sol,
+                value_usd: dec!(1500),
+                max_usd: dec!(1000),
+            }]
+        );
+        assert!(status.breaches[0].to_string().contains("$1500"));
+    }
+
+    #[test]
+    fn test_config_from_settings() {
+        let settings = ExecutionSettings {
+            max_portfolio_drawdown_pct: 15.0,
+            max_token_exposure_usd: 0.0,
+            ..Default::default()
+        };
+        let config = RiskLimitsConfig::from_settings(&settings);
+        assert_eq!(config.max_drawdown_pct, Some(dec!(15)));
+        assert!(config.max_token_exposure_usd.is_none());
+        assert!(config.is_enabled());
+        assert!(!RiskLimitsConfig::from_settings(&ExecutionSettings::default()).is_enabled());
+    }
+}
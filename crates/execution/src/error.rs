@@ -0,0 +1,112 @@
+//! Error types for the execution engine.
+//!
+//! Like the protocol adapters, the engine returns `anyhow::Result` and raises
+//! the failures callers need to tell apart as [`ExecutionError`].
+
+use clmm_lp_protocols::prelude::ProtocolError;
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+/// Result type for execution operations.
+pub type ExecutionResult<T> = Result<T, ExecutionError>;
+
+/// Errors raised while executing strategies and transactions.
+#[derive(Debug, Error)]
+pub enum ExecutionError {
+    /// The circuit breaker is open and execution is paused.
+    #[error("Circuit breaker is open")]
+    CircuitBreakerOpen,
+    /// The position is not tracked by the engine.
+    #[error("Position {0} not found")]
+    PositionNotFound(String),
+    /// The position exists but is not in a state that allows the operation.
+    #[error("Position {position} is {state}")]
+    InvalidPositionState {
+        /// Position address or identifier.
+        position: String,
+        /// Description of the current state.
+        state: String,
+    },
+    /// The wallet does not hold the NFT of the position.
+    #[error("Position {position} is not held by wallet {wallet}")]
+    NotPositionOwner {
+        /// Position address.
+        position: String,
+        /// Wallet public key.
+        wallet: String,
+    },
+    /// No emergency exit is awaiting confirmation for the position.
+    #[error("No pending exit for position {0}")]
+    NoPendingExit(String),
+    /// No shadow-mode record has the ID.
+    #[error("Shadow record {0} not found")]
+    ShadowRecordNotFound(String),
+    /// The requested price range is invalid.
+    #[error("Invalid range: {0}")]
+    InvalidRange(String),
+    /// The transaction was not confirmed in time.
+    #[error("Confirmation timed out after {0}s")]
+    ConfirmationTimeout(u64),
+    /// The transaction failed on chain.
+    #[error("Transaction failed: {0}")]
+    TransactionFailed(String),
+    /// The transaction failed in simulation and was not sent.
+    #[error("Simulation failed: {0}")]
+    SimulationFailed(String),
+    /// The pool price diverges from the oracle price by more than allowed.
+    #[error(
+        "Pool {pool} price {pool_price} diverges {divergence_bps} bps from oracle price {oracle_price} (max {max_bps} bps)"
+    )]
+    PriceDivergence {
+        /// Pool address.
+        pool: String,
+        /// Pool price of token A in token B.
+        pool_price: Decimal,
+        /// Oracle price of token A in token B.
+        oracle_price: Decimal,
+        /// Divergence in basis points.
+        divergence_bps: u32,
+        /// Maximum allowed divergence in basis points.
+        max_bps: u32,
+    },
+    /// A swap route moves the price more than allowed.
+    #[error("Swap price impact {impact_bps} bps exceeds {max_bps} bps")]
+    PriceImpactTooHigh {
+        /// Price impact of the route in basis points.
+        impact_bps: u32,
+        /// Maximum allowed price impact in basis points.
+        max_bps: u32,
+    },
+    /// No usable oracle price is available.
+    #[error("Oracle price unavailable: {0}")]
+    OracleUnavailable(String),
+    /// A protocol or RPC error.
+    #[error(transparent)]
+    Protocol(#[from] ProtocolError),
+}
+
+impl ExecutionError {
+    /// Finds an execution error anywhere in an error chain.
+    #[must_use]
+    pub fn find(err: &anyhow::Error) -> Option<&Self> {
+        err.downcast_ref::<Self>()
+            .or_else(|| err.chain().find_map(|e| e.downcast_ref::<Self>()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_display() {
+        let err = anyhow::Error::from(ExecutionError::NoPendingExit("pos".to_string()));
+        assert!(matches!(
+            ExecutionError::find(&err),
+            Some(ExecutionError::NoPendingExit(_))
+        ));
+
+        let err = ExecutionError::from(ProtocolError::AccountNotFound("abc".to_string()));
+        assert_eq!(err.to_string(), "Account not found: abc");
+    }
+}
@@ -0,0 +1,196 @@
+//! Drift perpetuals venue.
+
+use super::{HedgeMarket, PerpVenue};
+use crate::wallet::Wallet;
+use async_trait::async_trait;
+use clmm_lp_protocols::drift::PRICE_PRECISION;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use solana_sdk::signature::Signature;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+/// Default Drift DLOB server URL.
+pub const DRIFT_DLOB_URL: &str = "https://dlob.drift.trade";
+
+/// Default Drift data API URL.
+pub const DRIFT_DATA_API_URL: &str = "https://data.api.drift.trade";
+
+/// Precision of Drift funding rates (1e9).
+const FUNDING_RATE_PRECISION: u64 = 1_000_000_000;
+
+/// Hedges on Drift perpetual markets.
+///
+/// Orders are placed on-chain with the wallet as the Drift user authority;
+/// oracle prices and funding rates are read from Drift's public APIs.
+pub struct DriftVenue {
+    /// Order executor.
+    executor: DriftExecutor,
+    /// Wallet of the Drift user authority.
+    wallet: Arc<Wallet>,
+    /// HTTP client.
+    client: reqwest::Client,
+    /// DLOB server URL.
+    dlob_url: String,
+    /// Data API URL.
+    data_api_url: String,
+}
+
+impl DriftVenue {
+    /// Creates a Drift venue for the wallet's subaccount 0.
+    pub fn new(provider: Arc<RpcProvider>, wallet: Arc<Wallet>) -> Self {
+        Self {
+            executor: DriftExecutor::new(provider),
+            wallet,
+            client: reqwest::Client::new(),
+            dlob_url: DRIFT_DLOB_URL.to_string(),
+            data_api_url: DRIFT_DATA_API_URL.to_string(),
+        }
+    }
+
+    /// Sets the Drift subaccount ID.
+    #[must_use]
+    pub fn with_sub_account(mut self, sub_account_id: u16) -> Self {
+        self.executor = self.executor.with_sub_account(sub_account_id);
+        self
+    }
+
+    /// Sets the DLOB server and data API URLs.
+    #[must_use]
+    pub fn with_api_urls(
+        mut self,
+        dlob_url: impl Into<String>,
+        data_api_url: impl Into<String>,
+    ) -> Self {
+        self.dlob_url = dlob_url.into();
+        self.data_api_url = data_api_url.into();
+        self
+    }
+
+    /// Fetches a JSON document.
+    async fn get_json(&self, url: &str) -> anyhow::Result<serde_json::Value> {
+        let response = self.client.get(url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Drift API returned {} for {}", status, url);
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// Reads a decimal from a JSON number or string.
+fn json_decimal(value: &serde_json::Value) -> Option<Decimal> {
+    match value {
+        serde_json::Value::String(s) => Decimal::from_str(s).ok(),
+        serde_json::Value::Number(n) => Decimal::from_str(&n.to_string()).ok(),
+        _ => None,
+    }
+}
+
+/// Converts a Drift funding rate record to an hourly fraction of notional.
+///
+/// Drift reports the hourly funding payment per base unit (1e9 precision)
+/// alongside the oracle TWAP (1e6 precision).
+fn hourly_rate_from_record(record: &serde_json::Value) -> Option<Decimal> {
+    let funding = json_decimal(&record["fundingRate"])? / Decimal::from(FUNDING_RATE_PRECISION);
+    let twap = json_decimal(&record["oraclePriceTwap"])? / Decimal::from(PRICE_PRECISION);
+    if twap.is_zero() {
+        return None;
+    }
+    Some(funding / twap)
+}
+
+#[async_trait]
+impl PerpVenue for DriftVenue {
+    fn name(&self) -> &str {
+        "drift"
+    }
+
+    async fn position_size(&self, market: &HedgeMarket) -> anyhow::Result<Decimal> {
+        self.executor
+            .get_perp_position_size(&self.wallet.pubkey(), market.index)
+            .await
+    }
+
+    async fn oracle_price(&self, market: &HedgeMarket) -> anyhow::Result<Decimal> {
+        let url = format!("{}/l2?marketName={}&depth=1", self.dlob_url, market.name);
+        let book = self.get_json(&url).await?;
+        let oracle = json_decimal(&book["oracle"])
+            .ok_or_else(|| anyhow::anyhow!("Drift orderbook has no oracle price"))?;
+        Ok(oracle / Decimal::from(PRICE_PRECISION))
+    }
+
+    async fn hourly_funding_rate(&self, market: &HedgeMarket) -> anyhow::Result<Decimal> {
+        let url = format!(
+            "{}/fundingRates?marketName={}",
+            self.data_api_url, market.name
+        );
+        let body = self.get_json(&url).await?;
+        body["fundingRates"]
+            .as_array()
+            .and_then(|rates| rates.last())
+            .and_then(hourly_rate_from_record)
+            .ok_or_else(|| anyhow::anyhow!("No funding rate for {}", market.name))
+    }
+
+    async fn adjust_position(
+        &self,
+        market: &HedgeMarket,
+        delta_base: Decimal,
+        reduce_only: bool,
+    ) -> anyhow::Result<Option<Signature>> {
+        if delta_base.is_zero() {
+            return Ok(None);
+        }
+
+        let params = PerpOrderParams {
+            market_index: market.index,
+            oracle: market.oracle,
+            direction: if delta_base > Decimal::ZERO {
+                PositionDirection::Long
+            } else {
+                PositionDirection::Short
+            },
+            base_amount: delta_base.abs(),
+            reduce_only,
+        };
+
+        let result = self
+            .executor
+            .place_perp_order(&params, self.wallet.as_ref())
+            .await?;
+        if !result.success {
+            anyhow::bail!(
+                "Drift order failed: {}",
+                result.error.unwrap_or_else(|| "unknown error".to_string())
+            );
+        }
+
+        info!(
+            market = %market.name,
+            delta_base = %delta_base,
+            signature = %result.signature,
+            "Drift hedge adjusted"
+        );
+        Ok(Some(result.signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hourly_rate_from_record() {
+        // 0.0015 USD per SOL per hour at a $150 TWAP
+        let record = serde_json::json!({
+            "fundingRate": "1500000",
+            "oraclePriceTwap": "150000000",
+        });
+        assert_eq!(hourly_rate_from_record(&record), Some(Decimal::new(1, 5)));
+
+        let record = serde_json::json!({ "fundingRate": 1, "oraclePriceTwap": 0 });
+        assert_eq!(hourly_rate_from_record(&record), None);
+    }
+}
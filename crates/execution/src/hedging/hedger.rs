@@ -0,0 +1,528 @@
+//! Delta hedger keeping LP positions neutral with perp positions.
+
+use super::{HedgeMarket, PerpVenue};
+use crate::lifecycle::{HedgeFundingData, LifecycleTracker};
+use crate::strategy::DecisionContext;
+use chrono::{DateTime, Utc};
+use clmm_lp_domain::prelude::calculate_position_greeks;
+use clmm_lp_protocols::prelude::tick_to_price;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+/// Configuration for delta hedging.
+#[derive(Debug, Clone)]
+pub struct HedgeConfig {
+    /// Perp market to hedge on.
+    pub market: HedgeMarket,
+    /// Fraction of the position delta to offset (1.0 is fully neutral).
+    pub hedge_ratio: Decimal,
+    /// Smallest order size in base units.
+    pub min_adjustment_base: Decimal,
+    /// Drift from the target, as a percentage of it, before adjusting.
+    pub rebalance_threshold_pct: Decimal,
+    /// Maximum hedge size in base units.
+    pub max_position_base: Option<Decimal>,
+}
+
+impl HedgeConfig {
+    /// Creates a fully neutral hedge configuration for a market.
+    pub fn new(market: HedgeMarket) -> Self {
+        Self {
+            market,
+            hedge_ratio: Decimal::ONE,
+            min_adjustment_base: Decimal::new(1, 2), // 0.01
+            rebalance_threshold_pct: Decimal::from(5),
+            max_position_base: None,
+        }
+    }
+}
+
+/// Hedge state of a single LP position.
+#[derive(Debug, Clone)]
+pub struct HedgeState {
+    /// Target hedge size in base units (negative is short).
+    pub target_base: Decimal,
+    /// Hedge size held since the last funding accrual.
+    pub hedge_base: Decimal,
+    /// Position delta as a share of its value.
+    pub delta_share: Decimal,
+    /// Total funding paid in USD (negative if received).
+    pub funding_paid_usd: Decimal,
+    /// When funding was last accrued.
+    pub last_funding_at: DateTime<Utc>,
+}
+
+/// Result of a hedge update.
+#[derive(Debug, Clone)]
+pub struct HedgeUpdate {
+    /// Target hedge size for the position in base units.
+    pub target_base: Decimal,
+    /// Size of the order sent to the venue, if any.
+    pub order_base: Option<Decimal>,
+    /// Order transaction signature.
+    pub signature: Option<Signature>,
+    /// Funding accrued since the previous update in USD.
+    pub funding_usd: Decimal,
+}
+
+/// Maintains perp positions offsetting the delta of LP positions.
+///
+/// Each LP position has its own hedge target; the venue holds a single
+/// perp position equal to their sum. Funding on each position's share of
+/// the hedge is recorded in the lifecycle history, so it is deducted from
+/// that position's net PnL.
+pub struct DeltaHedger {
+    /// Venue holding the perp position.
+    venue: Arc<dyn PerpVenue>,
+    /// Configuration.
+    config: HedgeConfig,
+    /// Hedge state by LP position.
+    states: RwLock<HashMap<Pubkey, HedgeState>>,
+}
+
+impl DeltaHedger {
+    /// Creates a new delta hedger.
+    pub fn new(venue: Arc<dyn PerpVenue>, config: HedgeConfig) -> Self {
+        Self {
+            venue,
+            config,
+            states: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the configuration.
+    pub fn config(&self) -> &HedgeConfig {
+        &self.config
+    }
+
+    /// Gets the hedge state of a position.
+    pub async fn state(&self, position: &Pubkey) -> Option<HedgeState> {
+        self.states.read().await.get(position).cloned()
+    }
+
+    /// Gets the total target hedge size across positions.
+    pub async fn total_target(&self) -> Decimal {
+        self.states
+            .read()
+            .await
+            .values()
+            .map(|s| s.target_base)
+            .sum()
+    }
+
+    /// Calculates the hedge target for a position in base units.
+    ///
+    /// The position's delta share is computed from raw tick prices, so it
+    /// does not depend on token decimals, and is applied to the position's
+    /// USD value at the oracle price. Returns the target and the delta share.
+    ///
+    /// # Errors
+    /// Returns an error if the position's range is invalid.
+    pub fn target_for(
+        &self,
+        context: &DecisionContext,
+        oracle_price: Decimal,
+    ) -> anyhow::Result<(Decimal, Decimal)> {
+        let position = &context.position;
+        let price = tick_to_price(context.pool.tick_current);
+        // Delta share does not depend on liquidity
+        let greeks = calculate_position_greeks(
+            Decimal::ONE,
+            price,
+            tick_to_price(position.on_chain.tick_lower),
+            tick_to_price(position.on_chain.tick_upper),
+        )
+        .map_err(|e| anyhow::anyhow!(e))?;
+        let delta_share = greeks.delta_share(price);
+
+        if oracle_price <= Decimal::ZERO {
+            anyhow::bail!("oracle price must be positive");
+        }
+        let mut target = -(self.config.hedge_ratio * delta_share * position.pnl.current_value_usd
+            / oracle_price);
+        if let Some(max) = self.config.max_position_base {
+            target = target.clamp(-max, max);
+        }
+
+        Ok((target, delta_share))
+    }
+
+    /// Updates the hedge of a position and accrues its funding.
+    pub async fn rebalance(
+        &self,
+        context: &DecisionContext,
+        lifecycle: &LifecycleTracker,
+    ) -> anyhow::Result<HedgeUpdate> {
+        let market = &self.config.market;
+        let position = &context.position;
+        let oracle_price = self.venue.oracle_price(market).await?;
+        let (target_base, delta_share) = self.target_for(context, oracle_price)?;
+
+        let funding_usd = self
+            .accrue_funding(&position.address, &position.pool, oracle_price, lifecycle)
+            .await?;
+
+        {
+            let mut states = self.states.write().await;
+            let state = states
+                .entry(position.address)
+                .or_insert_with(|| HedgeState {
+                    target_base,
+                    hedge_base: Decimal::ZERO,
+                    delta_share,
+                    funding_paid_usd: Decimal::ZERO,
+                    last_funding_at: Utc::now(),
+                });
+            state.target_base = target_base;
+            state.delta_share = delta_share;
+        }
+
+        debug!(
+            position = %position.address,
+            delta_share = %delta_share,
+            target_base = %target_base,
+            "Hedge target updated"
+        );
+
+        let (order_base, signature) = self.sync(false).await?;
+
+        Ok(HedgeUpdate {
+            target_base,
+            order_base,
+            signature,
+            funding_usd,
+        })
+    }
+
+    /// Closes the hedge of a position after the position is closed.
+    ///
+    /// Settles its outstanding funding and reduces the venue position by
+    /// the position's share.
+    pub async fn close(
+        &self,
+        position: &Pubkey,
+        pool: &Pubkey,
+        lifecycle: &LifecycleTracker,
+    ) -> anyhow::Result<HedgeUpdate> {
+        if !self.states.read().await.contains_key(position) {
+            return Ok(HedgeUpdate {
+                target_base: Decimal::ZERO,
+                order_base: None,
+                signature: None,
+                funding_usd: Decimal::ZERO,
+            });
+        }
+
+        let oracle_price = self.venue.oracle_price(&self.config.market).await?;
+        let funding_usd = self
+            .accrue_funding(position, pool, oracle_price, lifecycle)
+            .await?;
+        self.states.write().await.remove(position);
+
+        info!(position = %position, "Closing position hedge");
+        let (order_base, signature) = self.sync(true).await?;
+
+        Ok(HedgeUpdate {
+            target_base: Decimal::ZERO,
+            order_base,
+            signature,
+            funding_usd,
+        })
+    }
+
+    /// Accrues funding on a position's hedge since the last accrual.
+    ///
+    /// Funding is the hedge notional times the current hourly rate times the
+    /// hours elapsed; longs pay when the rate is positive.
+    async fn accrue_funding(
+        &self,
+        position: &Pubkey,
+        pool: &Pubkey,
+        oracle_price: Decimal,
+        lifecycle: &LifecycleTracker,
+    ) -> anyhow::Result<Decimal> {
+        let Some((hedge_base, last_funding_at)) = self
+            .states
+            .read()
+            .await
+            .get(position)
+            .map(|s| (s.hedge_base, s.last_funding_at))
+        else {
+            return Ok(Decimal::ZERO);
+        };
+
+        let now = Utc::now();
+        let funding_usd = if hedge_base.is_zero() {
+            Decimal::ZERO
+        } else {
+            let rate = self.venue.hourly_funding_rate(&self.config.market).await?;
+            let seconds = (now - last_funding_at).num_seconds().max(0);
+            let hours = Decimal::from(seconds) / Decimal::from(3600);
+            hedge_base * oracle_price * rate * hours
+        };
+
+        if let Some(state) = self.states.write().await.get_mut(position) {
+            state.funding_paid_usd += funding_usd;
+            state.last_funding_at = now;
+        }
+
+        if !funding_usd.is_zero() {
+            lifecycle
+                .record_hedge_funding(
+                    *position,
+                    *pool,
+                    HedgeFundingData {
+                        market: self.config.market.name.clone(),
+                        hedge_size: hedge_base,
+                        funding_usd,
+                    },
+                )
+                .await;
+        }
+
+        Ok(funding_usd)
+    }
+
+    /// Brings the venue position to the total target if it has drifted
+    /// past the threshold.
+    async fn sync(
+        &self,
+        reduce_only: bool,
+    ) -> anyhow::Result<(Option<Decimal>, Option<Signature>)> {
+        let market = &self.config.market;
+        let target = self.total_target().await;
+        let current = self.venue.position_size(market).await?;
+        let diff = target - current;
+
+        let threshold = target.abs() * self.config.rebalance_threshold_pct / Decimal::from(100);
+        if diff.abs() < self.config.min_adjustment_base || diff.abs() <= threshold {
+            debug!(
+                market = %market.name,
+                target = %target,
+                current = %current,
+                "Hedge within threshold"
+            );
+            self.mark_hedged().await;
+            return Ok((None, None));
+        }
+
+        info!(
+            venue = self.venue.name(),
+            market = %market.name,
+            target = %target,
+            current = %current,
+            order = %diff,
+            "Adjusting hedge"
+        );
+        let signature = self
+            .venue
+            .adjust_position(market, diff, reduce_only)
+            .await?;
+        self.mark_hedged().await;
+
+        Ok((Some(diff), signature))
+    }
+
+    /// Records each position's target as its held hedge.
+    async fn mark_hedged(&self) {
+        for state in self.states.write().await.values_mut() {
+            state.hedge_base = state.target_base;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lifecycle::PositionOpenedData;
+    use crate::monitor::{MonitoredPosition, PositionPnL};
+    use async_trait::async_trait;
+    use clmm_lp_protocols::prelude::{OnChainPosition, WhirlpoolState};
+
+    /// Venue that fills every order immediately.
+    struct MockVenue {
+        position: RwLock<Decimal>,
+        orders: RwLock<Vec<Decimal>>,
+    }
+
+    #[async_trait]
+    impl PerpVenue for MockVenue {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn position_size(&self, _market: &HedgeMarket) -> anyhow::Result<Decimal> {
+            Ok(*self.position.read().await)
+        }
+
+        async fn oracle_price(&self, _market: &HedgeMarket) -> anyhow::Result<Decimal> {
+            Ok(Decimal::from(100))
+        }
+
+        async fn hourly_funding_rate(&self, _market: &HedgeMarket) -> anyhow::Result<Decimal> {
+            Ok(Decimal::new(1, 4)) // 0.01% per hour
+        }
+
+        async fn adjust_position(
+            &self,
+            _market: &HedgeMarket,
+            delta_base: Decimal,
+            _reduce_only: bool,
+        ) -> anyhow::Result<Option<Signature>> {
+            *self.position.write().await += delta_base;
+            self.orders.write().await.push(delta_base);
+            Ok(Some(Signature::default()))
+        }
+    }
+
+    fn create_test_context(tick_current: i32) -> DecisionContext {
+        let position = MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                tick_lower: -1000,
+                tick_upper: 1000,
+                liquidity: 1000000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+            },
+            pnl: PositionPnL {
+                current_value_usd: Decimal::from(10_000),
+                ..Default::default()
+            },
+            in_range: true,
+            out_of_range_since: None,
+            last_updated: Utc::now(),
+        };
+
+        let pool = WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current,
+            tick_spacing: 64,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 1000000,
+            fee_rate_bps: 30,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+        };
+
+        DecisionContext {
+            position,
+            pool,
+            hours_since_rebalance: 0,
+            peak_value_usd: Decimal::ZERO,
+        }
+    }
+
+    fn create_test_context_for(base: &DecisionContext, tick_current: i32) -> DecisionContext {
+        let mut context = base.clone();
+        context.pool.tick_current = tick_current;
+        context
+    }
+
+    fn create_hedger() -> (Arc<MockVenue>, DeltaHedger) {
+        let venue = Arc::new(MockVenue {
+            position: RwLock::new(Decimal::ZERO),
+            orders: RwLock::new(Vec::new()),
+        });
+        let market = HedgeMarket::new(0, "SOL-PERP", Pubkey::new_unique());
+        (
+            venue.clone(),
+            DeltaHedger::new(venue, HedgeConfig::new(market)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_offsets_delta() {
+        let (venue, hedger) = create_hedger();
+        let lifecycle = LifecycleTracker::new();
+        let context = create_test_context(0);
+
+        // At the middle of a symmetric range about half the value is token0
+        let update = hedger.rebalance(&context, &lifecycle).await.unwrap();
+        assert!(update.target_base < Decimal::from(-48) && update.target_base > Decimal::from(-52));
+        assert_eq!(*venue.position.read().await, update.target_base);
+
+        // A small move stays within the threshold
+        let update = hedger
+            .rebalance(&create_test_context_for(&context, 10), &lifecycle)
+            .await
+            .unwrap();
+        assert!(update.order_base.is_none());
+
+        // Above the range the position holds no token0
+        let update = hedger
+            .rebalance(&create_test_context_for(&context, 2000), &lifecycle)
+            .await
+            .unwrap();
+        assert!(update.target_base.is_zero());
+        assert!(venue.position.read().await.is_zero());
+        assert_eq!(venue.orders.read().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_funding_folded_into_pnl() {
+        let (venue, hedger) = create_hedger();
+        let lifecycle = LifecycleTracker::new();
+        let context = create_test_context(0);
+        let position = context.position.address;
+        let pool = context.position.pool;
+
+        lifecycle
+            .record_position_opened(
+                position,
+                pool,
+                PositionOpenedData {
+                    tick_lower: -1000,
+                    tick_upper: 1000,
+                    liquidity: 1000000,
+                    amount_a: 0,
+                    amount_b: 0,
+                    entry_price: Decimal::ONE,
+                    entry_value_usd: Decimal::from(10_000),
+                },
+            )
+            .await;
+
+        let hedge = hedger
+            .rebalance(&context, &lifecycle)
+            .await
+            .unwrap()
+            .target_base;
+
+        // Two hours pass with a short hedge and positive funding
+        hedger
+            .states
+            .write()
+            .await
+            .get_mut(&position)
+            .unwrap()
+            .last_funding_at -= chrono::Duration::hours(2);
+        let update = hedger.close(&position, &pool, &lifecycle).await.unwrap();
+
+        // Shorts receive funding: hedge * price * rate * hours
+        let expected = hedge * Decimal::from(100) * Decimal::new(1, 4) * Decimal::from(2);
+        assert!(update.funding_usd < Decimal::ZERO);
+        assert!((update.funding_usd - expected).abs() < Decimal::new(1, 6));
+        assert!(venue.position.read().await.is_zero());
+        assert!(hedger.state(&position).await.is_none());
+
+        let summary = lifecycle.get_summary(&position).await.unwrap();
+        assert_eq!(summary.total_funding_usd, update.funding_usd);
+        assert_eq!(summary.net_pnl_usd, -update.funding_usd);
+    }
+}
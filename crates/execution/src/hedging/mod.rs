@@ -0,0 +1,14 @@
+//! Delta hedging of LP positions with perpetual futures.
+//!
+//! Provides hedging functionality including:
+//! - Position delta from concentrated liquidity greeks
+//! - Offsetting perp positions on Drift
+//! - Funding cost tracking folded into position PnL
+
+mod drift;
+mod hedger;
+mod venue;
+
+pub use drift::*;
+pub use hedger::*;
+pub use venue::*;
@@ -0,0 +1,56 @@
+//! Perpetual futures venues used for hedging.
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+/// A perp market to hedge on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HedgeMarket {
+    /// Market index on the venue.
+    pub index: u16,
+    /// Market name (e.g. `SOL-PERP`).
+    pub name: String,
+    /// Oracle account of the market.
+    pub oracle: Pubkey,
+}
+
+impl HedgeMarket {
+    /// Creates a new hedge market.
+    pub fn new(index: u16, name: impl Into<String>, oracle: Pubkey) -> Self {
+        Self {
+            index,
+            name: name.into(),
+            oracle,
+        }
+    }
+}
+
+/// A venue for holding perp positions.
+#[async_trait]
+pub trait PerpVenue: Send + Sync {
+    /// Returns the venue name.
+    fn name(&self) -> &str;
+
+    /// Gets the signed position size in base units (positive is long).
+    async fn position_size(&self, market: &HedgeMarket) -> anyhow::Result<Decimal>;
+
+    /// Gets the oracle price of the market's base asset in USD.
+    async fn oracle_price(&self, market: &HedgeMarket) -> anyhow::Result<Decimal>;
+
+    /// Gets the current hourly funding rate as a fraction of notional.
+    ///
+    /// Longs pay shorts when the rate is positive.
+    async fn hourly_funding_rate(&self, market: &HedgeMarket) -> anyhow::Result<Decimal>;
+
+    /// Changes the position by `delta_base` base units (negative sells).
+    ///
+    /// Returns the order transaction signature, if one was sent.
+    async fn adjust_position(
+        &self,
+        market: &HedgeMarket,
+        delta_base: Decimal,
+        reduce_only: bool,
+    ) -> anyhow::Result<Option<Signature>>;
+}
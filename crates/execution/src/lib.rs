@@ -6,8 +6,15 @@
 //! - Wallet management for transaction signing
 //! - Transaction building and lifecycle management
 //! - Automated strategy execution
-//! - Emergency controls and circuit breaker
+//! - Manual open, close and rebalance operations
+//! - Jupiter swaps to a new range's token ratio
+//! - Daily portfolio digest
+//! - Paper trading against live pool quotes
+//! - Emergency controls, circuit breaker and portfolio risk limits
+//! - Oracle price checks against manipulated or depegged pools
 //! - Position lifecycle tracking
+//! - Delta hedging with perpetual futures
+//! - State persistence across restarts
 //! - State synchronization
 
 /// Prelude module for convenient imports.
@@ -15,16 +22,32 @@ pub mod prelude;
 
 /// Alert system.
 pub mod alerts;
-/// Emergency controls and circuit breaker.
+/// Daily portfolio digest.
+pub mod digest;
+/// Emergency controls, circuit breaker and portfolio risk limits.
 pub mod emergency;
+/// Error types for the execution engine.
+pub mod error;
+/// Delta hedging with perpetual futures.
+pub mod hedging;
 /// Position lifecycle tracking.
 pub mod lifecycle;
 /// Position monitoring.
 pub mod monitor;
+/// Manual position operations.
+pub mod operations;
+/// Oracle price checks before execution.
+pub mod oracle;
+/// Paper trading execution backend.
+pub mod paper;
+/// State persistence across restarts.
+pub mod persistence;
 /// Scheduler for strategy timing.
 pub mod scheduler;
 /// Strategy execution.
 pub mod strategy;
+/// Token swaps for rebalancing.
+pub mod swap;
 /// State synchronization.
 pub mod sync;
 /// Transaction building and sending.
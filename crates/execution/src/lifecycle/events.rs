@@ -20,6 +20,8 @@ pub enum LifecycleEventType {
     FeesCollected,
     /// Position was closed.
     PositionClosed,
+    /// Funding was settled on the position's hedge.
+    HedgeFunding,
 }
 
 /// A lifecycle event for a position.
@@ -81,6 +83,8 @@ pub enum EventData {
     FeesCollected(FeesCollectedData),
     /// Position closed data.
     PositionClosed(PositionClosedData),
+    /// Hedge funding data.
+    HedgeFunding(HedgeFundingData),
 }
 
 /// Data for position opened event.
@@ -166,6 +170,17 @@ pub struct FeesCollectedData {
     pub fees_usd: Decimal,
 }
 
+/// Data for hedge funding event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HedgeFundingData {
+    /// Perp market the hedge is held on (e.g. `SOL-PERP`).
+    pub market: String,
+    /// Signed hedge size in base units (negative is short).
+    pub hedge_size: Decimal,
+    /// Funding paid in USD (negative if received).
+    pub funding_usd: Decimal,
+}
+
 /// Data for position closed event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionClosedData {
@@ -204,6 +219,8 @@ pub enum CloseReason {
     Emergency,
     /// Strategy ended.
     StrategyEnded,
+    /// Closed to reopen in a new range.
+    Rebalance,
 }
 
 #[cfg(test)]
@@ -1,15 +1,16 @@
 //! Lifecycle tracker for position history.
 
 use super::{
-    EventData, FeesCollectedData, LifecycleEvent, LifecycleEventType, LiquidityChangeData,
-    PositionClosedData, PositionOpenedData, RebalanceData,
+    EventData, FeesCollectedData, HedgeFundingData, LifecycleEvent, LifecycleEventType,
+    LiquidityChangeData, PositionClosedData, PositionOpenedData, RebalanceData,
 };
+use crate::persistence::StateStore;
 use rust_decimal::Decimal;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 
 /// Summary of a position's lifecycle.
 #[derive(Debug, Clone)]
@@ -34,7 +35,9 @@ pub struct PositionSummary {
     pub total_tx_costs_lamports: u64,
     /// Total IL percentage.
     pub total_il_pct: Decimal,
-    /// Net PnL in USD.
+    /// Total hedge funding paid in USD (negative if received).
+    pub total_funding_usd: Decimal,
+    /// Net PnL in USD, after hedge funding.
     pub net_pnl_usd: Decimal,
     /// Net PnL percentage.
     pub net_pnl_pct: Decimal,
@@ -48,6 +51,8 @@ pub struct LifecycleTracker {
     events: Arc<RwLock<HashMap<Pubkey, Vec<LifecycleEvent>>>>,
     /// Position summaries.
     summaries: Arc<RwLock<HashMap<Pubkey, PositionSummary>>>,
+    /// Durable store for events.
+    store: RwLock<Option<Arc<dyn StateStore>>>,
 }
 
 impl LifecycleTracker {
@@ -57,9 +62,49 @@ impl LifecycleTracker {
         Self {
             events: Arc::new(RwLock::new(HashMap::new())),
             summaries: Arc::new(RwLock::new(HashMap::new())),
+            store: RwLock::new(None),
         }
     }
 
+    /// Sets the store that events are persisted to.
+    pub async fn set_state_store(&self, store: Arc<dyn StateStore>) {
+        *self.store.write().await = Some(store);
+    }
+
+    /// Restores events from the state store and rebuilds position summaries.
+    ///
+    /// Events already tracked are skipped. Returns the number of events restored.
+    pub async fn restore(&self) -> anyhow::Result<usize> {
+        let Some(store) = self.store.read().await.clone() else {
+            return Ok(0);
+        };
+
+        let mut restored = 0;
+        for event in store.load_events().await? {
+            let known = self
+                .events
+                .read()
+                .await
+                .get(&event.position)
+                .is_some_and(|events| events.iter().any(|e| e.id == event.id));
+            if known {
+                continue;
+            }
+
+            self.apply_event(&event).await;
+            self.events
+                .write()
+                .await
+                .entry(event.position)
+                .or_default()
+                .push(event);
+            restored += 1;
+        }
+
+        info!(events = restored, "Restored lifecycle history");
+        Ok(restored)
+    }
+
     /// Records a position opened event.
     pub async fn record_position_opened(
         &self,
@@ -74,26 +119,7 @@ impl LifecycleTracker {
             EventData::PositionOpened(data.clone()),
         );
 
-        self.add_event(position, event.clone()).await;
-
-        // Create summary
-        let summary = PositionSummary {
-            position,
-            pool,
-            opened_at: event.timestamp,
-            closed_at: None,
-            entry_value_usd: data.entry_value_usd,
-            current_value_usd: data.entry_value_usd,
-            total_fees_usd: Decimal::ZERO,
-            rebalance_count: 0,
-            total_tx_costs_lamports: 0,
-            total_il_pct: Decimal::ZERO,
-            net_pnl_usd: Decimal::ZERO,
-            net_pnl_pct: Decimal::ZERO,
-            is_open: true,
-        };
-
-        self.summaries.write().await.insert(position, summary);
+        self.add_event(position, event).await;
 
         info!(
             position = %position,
@@ -144,12 +170,6 @@ impl LifecycleTracker {
 
         self.add_event(position, event).await;
 
-        // Update summary
-        if let Some(summary) = self.summaries.write().await.get_mut(&position) {
-            summary.rebalance_count += 1;
-            summary.total_tx_costs_lamports += data.tx_cost_lamports;
-        }
-
         info!(
             position = %position,
             old_range = format!("[{}, {}]", data.old_tick_lower, data.old_tick_upper),
@@ -175,11 +195,6 @@ impl LifecycleTracker {
 
         self.add_event(position, event).await;
 
-        // Update summary
-        if let Some(summary) = self.summaries.write().await.get_mut(&position) {
-            summary.total_fees_usd += data.fees_usd;
-        }
-
         info!(
             position = %position,
             fees_a = data.fees_a,
@@ -203,16 +218,7 @@ impl LifecycleTracker {
             EventData::PositionClosed(data.clone()),
         );
 
-        self.add_event(position, event.clone()).await;
-
-        // Update summary
-        if let Some(summary) = self.summaries.write().await.get_mut(&position) {
-            summary.closed_at = Some(event.timestamp);
-            summary.is_open = false;
-            summary.net_pnl_usd = data.final_pnl_usd;
-            summary.net_pnl_pct = data.final_pnl_pct;
-            summary.total_il_pct = data.total_il_pct;
-        }
+        self.add_event(position, event).await;
 
         info!(
             position = %position,
@@ -224,12 +230,117 @@ impl LifecycleTracker {
         );
     }
 
-    /// Adds an event to the tracker.
+    /// Records funding settled on a position's hedge.
+    ///
+    /// Funding paid reduces the position's net PnL.
+    pub async fn record_hedge_funding(
+        &self,
+        position: Pubkey,
+        pool: Pubkey,
+        data: HedgeFundingData,
+    ) {
+        let event = LifecycleEvent::new(
+            LifecycleEventType::HedgeFunding,
+            position,
+            pool,
+            EventData::HedgeFunding(data.clone()),
+        );
+
+        self.add_event(position, event).await;
+
+        debug!(
+            position = %position,
+            market = %data.market,
+            hedge_size = %data.hedge_size,
+            funding_usd = %data.funding_usd,
+            "Hedge funding settled"
+        );
+    }
+
+    /// Records an event built by the caller, such as one carrying the
+    /// signature of the transaction behind it.
+    pub async fn record_event(&self, event: LifecycleEvent) {
+        debug!(
+            position = %event.position,
+            event_type = ?event.event_type,
+            "Recording lifecycle event"
+        );
+        self.add_event(event.position, event).await;
+    }
+
+    /// Adds an event to the tracker, updating its summary and persisting it.
     async fn add_event(&self, position: Pubkey, event: LifecycleEvent) {
+        self.apply_event(&event).await;
+
+        let store = self.store.read().await.clone();
+        if let Some(store) = store
+            && let Err(e) = store.append_event(&event).await
+        {
+            error!(
+                position = %position,
+                event_id = %event.id,
+                error = %e,
+                "Failed to persist lifecycle event"
+            );
+        }
+
         let mut events = self.events.write().await;
         events.entry(position).or_default().push(event);
     }
 
+    /// Updates the position summary for an event.
+    async fn apply_event(&self, event: &LifecycleEvent) {
+        let mut summaries = self.summaries.write().await;
+
+        if let EventData::PositionOpened(data) = &event.data {
+            summaries.insert(
+                event.position,
+                PositionSummary {
+                    position: event.position,
+                    pool: event.pool,
+                    opened_at: event.timestamp,
+                    closed_at: None,
+                    entry_value_usd: data.entry_value_usd,
+                    current_value_usd: data.entry_value_usd,
+                    total_fees_usd: Decimal::ZERO,
+                    rebalance_count: 0,
+                    total_tx_costs_lamports: 0,
+                    total_il_pct: Decimal::ZERO,
+                    total_funding_usd: Decimal::ZERO,
+                    net_pnl_usd: Decimal::ZERO,
+                    net_pnl_pct: Decimal::ZERO,
+                    is_open: true,
+                },
+            );
+            return;
+        }
+
+        let Some(summary) = summaries.get_mut(&event.position) else {
+            return;
+        };
+        match &event.data {
+            EventData::Rebalance(data) => {
+                summary.rebalance_count += 1;
+                summary.total_tx_costs_lamports += data.tx_cost_lamports;
+            }
+            EventData::FeesCollected(data) => {
+                summary.total_fees_usd += data.fees_usd;
+            }
+            EventData::PositionClosed(data) => {
+                summary.closed_at = Some(event.timestamp);
+                summary.is_open = false;
+                summary.net_pnl_usd = data.final_pnl_usd - summary.total_funding_usd;
+                summary.net_pnl_pct = data.final_pnl_pct;
+                summary.total_il_pct = data.total_il_pct;
+            }
+            EventData::HedgeFunding(data) => {
+                summary.total_funding_usd += data.funding_usd;
+                summary.net_pnl_usd -= data.funding_usd;
+            }
+            EventData::PositionOpened(_) | EventData::LiquidityChange(_) => {}
+        }
+    }
+
     /// Gets all events for a position.
     pub async fn get_events(&self, position: &Pubkey) -> Vec<LifecycleEvent> {
         self.events
@@ -287,6 +398,7 @@ impl LifecycleTracker {
             }
 
             stats.total_fees_usd += summary.total_fees_usd;
+            stats.total_funding_usd += summary.total_funding_usd;
             stats.total_pnl_usd += summary.net_pnl_usd;
             stats.total_rebalances += summary.rebalance_count;
             stats.total_tx_costs_lamports += summary.total_tx_costs_lamports;
@@ -318,6 +430,8 @@ pub struct AggregateStats {
     pub closed_positions: u32,
     /// Total fees earned in USD.
     pub total_fees_usd: Decimal,
+    /// Total hedge funding paid in USD.
+    pub total_funding_usd: Decimal,
     /// Total PnL in USD.
     pub total_pnl_usd: Decimal,
     /// Average PnL percentage.
@@ -331,6 +445,8 @@ pub struct AggregateStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lifecycle::{CloseReason, RebalanceReason};
+    use crate::persistence::MemoryStateStore;
 
     #[tokio::test]
     async fn test_lifecycle_tracker() {
@@ -362,4 +478,133 @@ mod tests {
         assert!(summary.is_some());
         assert!(summary.unwrap().is_open);
     }
+
+    #[tokio::test]
+    async fn test_restore_rebuilds_summaries() {
+        let store = Arc::new(MemoryStateStore::new());
+        let position = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        let tracker = LifecycleTracker::new();
+        tracker.set_state_store(store.clone()).await;
+        tracker
+            .record_position_opened(
+                position,
+                pool,
+                PositionOpenedData {
+                    tick_lower: -1000,
+                    tick_upper: 1000,
+                    liquidity: 1000000,
+                    amount_a: 1000000000,
+                    amount_b: 100000000,
+                    entry_price: Decimal::new(100, 0),
+                    entry_value_usd: Decimal::new(1000, 0),
+                },
+            )
+            .await;
+        tracker
+            .record_rebalance(
+                position,
+                pool,
+                RebalanceData {
+                    old_tick_lower: -1000,
+                    old_tick_upper: 1000,
+                    new_tick_lower: -500,
+                    new_tick_upper: 1500,
+                    old_liquidity: 1000000,
+                    new_liquidity: 1000000,
+                    tx_cost_lamports: 5000,
+                    il_at_rebalance: Decimal::ZERO,
+                    reason: RebalanceReason::RangeExit,
+                },
+            )
+            .await;
+        tracker
+            .record_fees_collected(
+                position,
+                pool,
+                FeesCollectedData {
+                    fees_a: 100,
+                    fees_b: 200,
+                    fees_usd: Decimal::new(12, 0),
+                },
+            )
+            .await;
+
+        // A fresh tracker (as after a restart) rebuilds state from the store
+        let restarted = LifecycleTracker::new();
+        restarted.set_state_store(store).await;
+        assert_eq!(restarted.restore().await.unwrap(), 3);
+        assert_eq!(restarted.restore().await.unwrap(), 0);
+
+        let summary = restarted.get_summary(&position).await.unwrap();
+        assert_eq!(summary.entry_value_usd, Decimal::new(1000, 0));
+        assert_eq!(summary.rebalance_count, 1);
+        assert_eq!(summary.total_tx_costs_lamports, 5000);
+        assert_eq!(summary.total_fees_usd, Decimal::new(12, 0));
+        assert_eq!(restarted.get_events(&position).await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_funding_reduces_pnl() {
+        let tracker = LifecycleTracker::new();
+        let position = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        tracker
+            .record_position_opened(
+                position,
+                pool,
+                PositionOpenedData {
+                    tick_lower: -1000,
+                    tick_upper: 1000,
+                    liquidity: 1000000,
+                    amount_a: 1000000000,
+                    amount_b: 100000000,
+                    entry_price: Decimal::new(100, 0),
+                    entry_value_usd: Decimal::new(1000, 0),
+                },
+            )
+            .await;
+        for funding_usd in [Decimal::new(3, 0), Decimal::new(-1, 0)] {
+            tracker
+                .record_hedge_funding(
+                    position,
+                    pool,
+                    HedgeFundingData {
+                        market: "SOL-PERP".to_string(),
+                        hedge_size: Decimal::new(-5, 0),
+                        funding_usd,
+                    },
+                )
+                .await;
+        }
+
+        let summary = tracker.get_summary(&position).await.unwrap();
+        assert_eq!(summary.total_funding_usd, Decimal::new(2, 0));
+        assert_eq!(summary.net_pnl_usd, Decimal::new(-2, 0));
+
+        tracker
+            .record_position_closed(
+                position,
+                pool,
+                PositionClosedData {
+                    liquidity_removed: 1000000,
+                    amount_a: 0,
+                    amount_b: 0,
+                    total_fees_a: 0,
+                    total_fees_b: 0,
+                    final_pnl_usd: Decimal::new(50, 0),
+                    final_pnl_pct: Decimal::new(5, 0),
+                    total_il_pct: Decimal::ZERO,
+                    duration_hours: 24,
+                    reason: CloseReason::Manual,
+                },
+            )
+            .await;
+
+        let stats = tracker.get_aggregate_stats().await;
+        assert_eq!(stats.total_funding_usd, Decimal::new(2, 0));
+        assert_eq!(stats.total_pnl_usd, Decimal::new(48, 0));
+    }
 }
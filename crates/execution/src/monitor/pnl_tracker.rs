@@ -48,10 +48,82 @@ pub struct PnLResult {
     pub apy: Decimal,
 }
 
+/// Claimed reward token totals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RewardAccrual {
+    /// Raw amount claimed.
+    pub claimed_amount: u64,
+    /// Value of claimed rewards in USD at claim time.
+    pub claimed_usd: Decimal,
+}
+
+/// Cost-basis ledger for a position.
+///
+/// Survives rebalances: moving liquidity to a new range (or a new position
+/// account) carries the ledger over instead of resetting it.
+#[derive(Debug, Clone, Default)]
+pub struct CostBasis {
+    /// Total capital deposited in USD.
+    pub total_deposited_usd: Decimal,
+    /// Total capital withdrawn in USD.
+    pub total_withdrawn_usd: Decimal,
+    /// Remaining cost basis of the capital still in the position.
+    pub cost_basis_usd: Decimal,
+    /// Gains (or losses) realized on withdrawals, against average cost.
+    pub realized_capital_pnl_usd: Decimal,
+    /// Cumulative transaction costs in USD.
+    pub tx_costs_usd: Decimal,
+    /// Fees collected (realized) in USD.
+    pub collected_fees_usd: Decimal,
+    /// Claimed rewards by reward token mint.
+    pub rewards: HashMap<String, RewardAccrual>,
+    /// Number of rebalances.
+    pub rebalance_count: u32,
+}
+
+impl CostBasis {
+    /// Returns the total value of claimed rewards in USD.
+    #[must_use]
+    pub fn claimed_rewards_usd(&self) -> Decimal {
+        self.rewards.values().map(|r| r.claimed_usd).sum()
+    }
+}
+
+/// Realized vs unrealized PnL breakdown.
+#[derive(Debug, Clone, Default)]
+pub struct PnLBreakdown {
+    /// Remaining cost basis in USD.
+    pub cost_basis_usd: Decimal,
+    /// Current position value in USD (excluding unclaimed fees and rewards).
+    pub current_value_usd: Decimal,
+    /// Fees earned but not yet collected, in USD.
+    pub unclaimed_fees_usd: Decimal,
+    /// Rewards earned but not yet claimed, in USD.
+    pub unclaimed_rewards_usd: Decimal,
+    /// Fees collected, in USD.
+    pub realized_fees_usd: Decimal,
+    /// Rewards claimed, in USD.
+    pub realized_rewards_usd: Decimal,
+    /// Gains realized on withdrawals, in USD.
+    pub realized_capital_pnl_usd: Decimal,
+    /// Cumulative transaction costs, in USD.
+    pub tx_costs_usd: Decimal,
+    /// Realized PnL (collected fees + claimed rewards + withdrawal gains - tx costs).
+    pub realized_pnl_usd: Decimal,
+    /// Unrealized PnL (value over cost basis + unclaimed fees and rewards).
+    pub unrealized_pnl_usd: Decimal,
+    /// Total PnL in USD.
+    pub total_pnl_usd: Decimal,
+    /// Total PnL as a percentage of capital deposited.
+    pub total_pnl_pct: Decimal,
+}
+
 /// Tracks PnL for multiple positions.
 pub struct PnLTracker {
     /// Entry states for positions.
     entries: HashMap<String, PositionEntry>,
+    /// Cost-basis ledgers for positions.
+    ledgers: HashMap<String, CostBasis>,
 }
 
 impl PnLTracker {
@@ -60,6 +132,7 @@ impl PnLTracker {
     pub fn new() -> Self {
         Self {
             entries: HashMap::new(),
+            ledgers: HashMap::new(),
         }
     }
 
@@ -86,6 +159,7 @@ impl PnLTracker {
         };
 
         self.entries.insert(position_address.to_string(), entry);
+        self.record_deposit(position_address, entry_value_usd);
 
         debug!(
             position = position_address,
@@ -170,6 +244,149 @@ impl PnLTracker {
         })
     }
 
+    /// Records capital added to a position.
+    pub fn record_deposit(&mut self, position_address: &str, value_usd: Decimal) {
+        let ledger = self.ledger_mut(position_address);
+        ledger.total_deposited_usd += value_usd;
+        ledger.cost_basis_usd += value_usd;
+    }
+
+    /// Records capital removed from a position.
+    ///
+    /// The withdrawn share of the cost basis is released at average cost,
+    /// realizing the difference as capital PnL.
+    pub fn record_withdrawal(
+        &mut self,
+        position_address: &str,
+        value_usd: Decimal,
+        position_value_usd: Decimal,
+    ) {
+        let ledger = self.ledger_mut(position_address);
+        let fraction = if position_value_usd.is_zero() {
+            Decimal::ONE
+        } else {
+            (value_usd / position_value_usd).min(Decimal::ONE)
+        };
+        let released = ledger.cost_basis_usd * fraction;
+
+        ledger.total_withdrawn_usd += value_usd;
+        ledger.cost_basis_usd -= released;
+        ledger.realized_capital_pnl_usd += value_usd - released;
+    }
+
+    /// Records a transaction cost.
+    pub fn record_tx_cost(&mut self, position_address: &str, cost_usd: Decimal) {
+        self.ledger_mut(position_address).tx_costs_usd += cost_usd;
+    }
+
+    /// Records collected (realized) fees.
+    pub fn record_fees_collected(&mut self, position_address: &str, fees_usd: Decimal) {
+        self.ledger_mut(position_address).collected_fees_usd += fees_usd;
+    }
+
+    /// Records claimed reward tokens.
+    pub fn record_reward_claimed(
+        &mut self,
+        position_address: &str,
+        reward_mint: &str,
+        amount: u64,
+        value_usd: Decimal,
+    ) {
+        let reward = self
+            .ledger_mut(position_address)
+            .rewards
+            .entry(reward_mint.to_string())
+            .or_default();
+        reward.claimed_amount += amount;
+        reward.claimed_usd += value_usd;
+    }
+
+    /// Records a rebalance into a new range.
+    ///
+    /// The entry and ledger move to `new_address` (which may equal the old
+    /// address) so cost basis and realized PnL carry over.
+    pub fn record_rebalance(
+        &mut self,
+        old_address: &str,
+        new_address: &str,
+        tick_lower: i32,
+        tick_upper: i32,
+        tx_cost_usd: Decimal,
+    ) {
+        if let Some(mut entry) = self.entries.remove(old_address) {
+            entry.tick_lower = tick_lower;
+            entry.tick_upper = tick_upper;
+            self.entries.insert(new_address.to_string(), entry);
+        }
+
+        let mut ledger = self.ledgers.remove(old_address).unwrap_or_default();
+        ledger.tx_costs_usd += tx_cost_usd;
+        ledger.rebalance_count += 1;
+        self.ledgers.insert(new_address.to_string(), ledger);
+
+        debug!(
+            old_position = old_address,
+            new_position = new_address,
+            tick_lower = tick_lower,
+            tick_upper = tick_upper,
+            "Carried cost basis over rebalance"
+        );
+    }
+
+    /// Calculates the realized vs unrealized PnL breakdown for a position.
+    ///
+    /// Returns `None` if the position has no cost-basis ledger.
+    pub fn calculate_breakdown(
+        &self,
+        position_address: &str,
+        current_value_usd: Decimal,
+        unclaimed_fees_usd: Decimal,
+        unclaimed_rewards_usd: Decimal,
+    ) -> Option<PnLBreakdown> {
+        let ledger = self.ledgers.get(position_address)?;
+
+        let realized_rewards_usd = ledger.claimed_rewards_usd();
+        let realized_pnl_usd =
+            ledger.collected_fees_usd + realized_rewards_usd + ledger.realized_capital_pnl_usd
+                - ledger.tx_costs_usd;
+        let unrealized_pnl_usd =
+            current_value_usd - ledger.cost_basis_usd + unclaimed_fees_usd + unclaimed_rewards_usd;
+        let total_pnl_usd = realized_pnl_usd + unrealized_pnl_usd;
+
+        let total_pnl_pct = if ledger.total_deposited_usd.is_zero() {
+            Decimal::ZERO
+        } else {
+            total_pnl_usd / ledger.total_deposited_usd * Decimal::from(100)
+        };
+
+        Some(PnLBreakdown {
+            cost_basis_usd: ledger.cost_basis_usd,
+            current_value_usd,
+            unclaimed_fees_usd,
+            unclaimed_rewards_usd,
+            realized_fees_usd: ledger.collected_fees_usd,
+            realized_rewards_usd,
+            realized_capital_pnl_usd: ledger.realized_capital_pnl_usd,
+            tx_costs_usd: ledger.tx_costs_usd,
+            realized_pnl_usd,
+            unrealized_pnl_usd,
+            total_pnl_usd,
+            total_pnl_pct,
+        })
+    }
+
+    /// Gets the cost-basis ledger for a position.
+    pub fn get_cost_basis(&self, position_address: &str) -> Option<&CostBasis> {
+        self.ledgers.get(position_address)
+    }
+
+    /// Gets or creates the ledger for a position.
+    fn ledger_mut(&mut self, position_address: &str) -> &mut CostBasis {
+        self.ledgers
+            .entry(position_address.to_string())
+            .or_default()
+    }
+
     /// Gets the entry for a position.
     pub fn get_entry(&self, position_address: &str) -> Option<&PositionEntry> {
         self.entries.get(position_address)
@@ -178,6 +395,7 @@ impl PnLTracker {
     /// Removes a position entry.
     pub fn remove_entry(&mut self, position_address: &str) {
         self.entries.remove(position_address);
+        self.ledgers.remove(position_address);
     }
 
     /// Gets all tracked positions.
@@ -218,4 +436,51 @@ mod tests {
         assert_eq!(entry.entry_price, dec!(100));
         assert_eq!(entry.entry_value_usd, dec!(1000));
     }
+
+    #[test]
+    fn test_cost_basis_survives_rebalance() {
+        let mut tracker = PnLTracker::new();
+        tracker.record_entry("old", dec!(100), dec!(1000), 0, 0, -1000, 1000);
+        tracker.record_fees_collected("old", dec!(20));
+        tracker.record_tx_cost("old", dec!(1));
+
+        tracker.record_rebalance("old", "new", -500, 1500, dec!(2));
+        assert!(tracker.get_entry("old").is_none());
+        assert_eq!(tracker.get_entry("new").unwrap().tick_lower, -500);
+
+        tracker.record_reward_claimed("new", "ORCA", 5_000_000, dec!(4));
+
+        let breakdown = tracker
+            .calculate_breakdown("new", dec!(990), dec!(6), dec!(1))
+            .unwrap();
+        assert_eq!(breakdown.cost_basis_usd, dec!(1000));
+        // 20 fees + 4 rewards - 3 tx costs
+        assert_eq!(breakdown.realized_pnl_usd, dec!(21));
+        // -10 value + 6 fees + 1 reward
+        assert_eq!(breakdown.unrealized_pnl_usd, dec!(-3));
+        assert_eq!(breakdown.total_pnl_usd, dec!(18));
+        assert_eq!(breakdown.total_pnl_pct, dec!(1.8));
+        assert_eq!(tracker.get_cost_basis("new").unwrap().rebalance_count, 1);
+    }
+
+    #[test]
+    fn test_withdrawal_realizes_at_average_cost() {
+        let mut tracker = PnLTracker::new();
+        tracker.record_deposit("pos", dec!(1000));
+        tracker.record_deposit("pos", dec!(1000));
+
+        // Position grew to 2400; withdraw a quarter of it
+        tracker.record_withdrawal("pos", dec!(600), dec!(2400));
+
+        let ledger = tracker.get_cost_basis("pos").unwrap();
+        assert_eq!(ledger.cost_basis_usd, dec!(1500));
+        assert_eq!(ledger.realized_capital_pnl_usd, dec!(100));
+
+        let breakdown = tracker
+            .calculate_breakdown("pos", dec!(1800), Decimal::ZERO, Decimal::ZERO)
+            .unwrap();
+        assert_eq!(breakdown.realized_pnl_usd, dec!(100));
+        assert_eq!(breakdown.unrealized_pnl_usd, dec!(300));
+        assert_eq!(breakdown.total_pnl_usd, dec!(400));
+    }
 }
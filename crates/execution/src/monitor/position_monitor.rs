@@ -1,10 +1,14 @@
 //! Position monitor for real-time tracking.
 
-use crate::alerts::{Alert, AlertRule};
+use crate::alerts::{
+    Alert, AlertLevel, AlertRule, AlertType, RuleCondition, RuleContext, RulesEngine,
+};
+use crate::persistence::{PositionSnapshot, StateStore};
 use clmm_lp_protocols::prelude::*;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -51,12 +55,14 @@ pub struct MonitoredPosition {
     pub pnl: PositionPnL,
     /// Whether position is currently in range.
     pub in_range: bool,
+    /// When the position last left its range, if currently out of range.
+    pub out_of_range_since: Option<chrono::DateTime<chrono::Utc>>,
     /// Last update timestamp.
     pub last_updated: chrono::DateTime<chrono::Utc>,
 }
 
 /// PnL data for a position.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PositionPnL {
     /// Entry value in USD.
     pub entry_value_usd: Decimal,
@@ -91,11 +97,12 @@ pub struct PositionMonitor {
     positions: Arc<RwLock<HashMap<Pubkey, MonitoredPosition>>>,
     /// Configuration.
     config: MonitorConfig,
-    /// Alert rules.
-    alert_rules: Vec<AlertRule>,
+    /// Alert rules with their deduplication state.
+    rules: RwLock<RulesEngine>,
     /// Alert callback.
-    #[allow(dead_code)]
     alert_callback: Option<Box<dyn Fn(Alert) + Send + Sync>>,
+    /// Durable store for position snapshots.
+    store: RwLock<Option<Arc<dyn StateStore>>>,
 }
 
 impl PositionMonitor {
@@ -104,14 +111,97 @@ impl PositionMonitor {
         let pool_reader = WhirlpoolReader::new(provider.clone());
         let position_reader = PositionReader::new(provider.clone());
 
+        let mut rules = RulesEngine::new();
+        if config.range_exit_alert {
+            // Fire once on leaving the range and once on returning
+            rules.add_rule(
+                AlertRule::new(
+                    "out_of_range",
+                    RuleCondition::OutOfRange,
+                    AlertLevel::Warning,
+                    AlertType::RangeExit,
+                )
+                .with_message("Position {position} is out of range")
+                .on_change(),
+            );
+        }
+
         Self {
             provider,
             pool_reader,
             position_reader,
             positions: Arc::new(RwLock::new(HashMap::new())),
             config,
-            alert_rules: Vec::new(),
+            rules: RwLock::new(rules),
             alert_callback: None,
+            store: RwLock::new(None),
+        }
+    }
+
+    /// Sets the store that position snapshots are persisted to.
+    pub async fn set_state_store(&self, store: Arc<dyn StateStore>) {
+        *self.store.write().await = Some(store);
+    }
+
+    /// Restores monitored positions from the state store.
+    ///
+    /// On-chain state is re-fetched for each position; PnL baselines and
+    /// range history come from the stored snapshot. Positions that can no
+    /// longer be fetched are skipped. Returns the number of positions restored.
+    pub async fn restore(&self) -> anyhow::Result<usize> {
+        let Some(store) = self.store.read().await.clone() else {
+            return Ok(0);
+        };
+
+        let mut restored = 0;
+        for snapshot in store.load_positions().await? {
+            let on_chain = match self
+                .position_reader
+                .get_position(&snapshot.address.to_string())
+                .await
+            {
+                Ok(position) => position,
+                Err(e) => {
+                    warn!(
+                        position = %snapshot.address,
+                        error = %e,
+                        "Skipping stored position that could not be fetched"
+                    );
+                    continue;
+                }
+            };
+
+            let monitored = MonitoredPosition {
+                address: snapshot.address,
+                pool: snapshot.pool,
+                on_chain,
+                pnl: snapshot.pnl,
+                in_range: snapshot.in_range,
+                out_of_range_since: snapshot.out_of_range_since,
+                last_updated: snapshot.last_updated,
+            };
+            self.positions
+                .write()
+                .await
+                .insert(monitored.address, monitored);
+            restored += 1;
+        }
+
+        info!(positions = restored, "Restored monitored positions");
+        Ok(restored)
+    }
+
+    /// Persists a position snapshot if a store is configured.
+    async fn persist(&self, snapshot: PositionSnapshot) {
+        let store = self.store.read().await.clone();
+        if let Some(store) = store
+            && let Err(e) = store.save_position(&snapshot).await
+        {
+            error!(
+                position = %snapshot.address,
+                error = %e,
+                "Failed to persist position state"
+            );
         }
     }
 
@@ -125,11 +215,16 @@ impl PositionMonitor {
             on_chain: position.clone(),
             pnl: PositionPnL::default(),
             in_range: true,
+            out_of_range_since: None,
             last_updated: chrono::Utc::now(),
         };
 
-        let mut positions = self.positions.write().await;
-        positions.insert(position.address, monitored);
+        let snapshot = PositionSnapshot::from(&monitored);
+        self.positions
+            .write()
+            .await
+            .insert(position.address, monitored);
+        self.persist(snapshot).await;
 
         info!(position = position_address, "Added position to monitor");
 
@@ -140,6 +235,19 @@ impl PositionMonitor {
     pub async fn remove_position(&self, position_address: &Pubkey) {
         let mut positions = self.positions.write().await;
         positions.remove(position_address);
+        drop(positions);
+        self.rules.write().await.clear_position(position_address);
+
+        let store = self.store.read().await.clone();
+        if let Some(store) = store
+            && let Err(e) = store.remove_position(position_address).await
+        {
+            error!(
+                position = %position_address,
+                error = %e,
+                "Failed to remove persisted position state"
+            );
+        }
 
         info!(
             position = %position_address,
@@ -190,24 +298,73 @@ impl PositionMonitor {
             .get_pool_state(&position.pool.to_string())
             .await?;
 
+        self.apply_pool_state(
+            address,
+            position,
+            pool_state.tick_current,
+            pool_state.sqrt_price,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Applies a streamed pool price update to the positions in its pool.
+    ///
+    /// Range status and token amounts follow the new price using the last
+    /// fetched position state; fees are refreshed by the next poll.
+    pub async fn apply_pool_update(&self, update: &PoolPriceUpdate) {
+        let positions: Vec<(Pubkey, OnChainPosition)> = {
+            let positions = self.positions.read().await;
+            positions
+                .values()
+                .filter(|p| p.pool == update.pool)
+                .map(|p| (p.address, p.on_chain.clone()))
+                .collect()
+        };
+
+        for (address, position) in positions {
+            self.apply_pool_state(&address, position, update.tick_current, update.sqrt_price)
+                .await;
+        }
+    }
+
+    /// Gets the pools of the monitored positions.
+    pub async fn monitored_pools(&self) -> HashSet<Pubkey> {
+        let positions = self.positions.read().await;
+        positions.values().map(|p| p.pool).collect()
+    }
+
+    /// Updates a position's state, PnL and alerts at its pool's price.
+    async fn apply_pool_state(
+        &self,
+        address: &Pubkey,
+        position: OnChainPosition,
+        tick_current: i32,
+        sqrt_price: u128,
+    ) {
         // Check if in range
-        let in_range = pool_state.is_tick_in_range(position.tick_lower, position.tick_upper);
+        let in_range = tick_current >= position.tick_lower && tick_current < position.tick_upper;
 
         // Calculate token amounts
-        let (amount_a, amount_b) = self.position_reader.calculate_token_amounts(
-            &position,
-            pool_state.tick_current,
-            pool_state.sqrt_price,
-        );
+        let (amount_a, amount_b) =
+            self.position_reader
+                .calculate_token_amounts(&position, tick_current, sqrt_price);
 
         // Update position state
         let mut positions = self.positions.write().await;
         if let Some(monitored) = positions.get_mut(address) {
             let was_in_range = monitored.in_range;
 
+            let now = chrono::Utc::now();
             monitored.on_chain = position.clone();
             monitored.in_range = in_range;
-            monitored.last_updated = chrono::Utc::now();
+            monitored.last_updated = now;
+            monitored.out_of_range_since = match (in_range, monitored.out_of_range_since) {
+                (true, _) => None,
+                (false, Some(since)) => Some(since),
+                (false, None) => Some(now),
+            };
 
             // Update PnL
             monitored.pnl.fees_earned_a = position.fees_owed_a;
@@ -227,11 +384,33 @@ impl PositionMonitor {
                     position = %address,
                     "Position exited range"
                 );
-                // TODO: Trigger alert
             }
-        }
 
-        Ok(())
+            if self.config.alerts_enabled {
+                let context = RuleContext {
+                    in_range,
+                    was_in_range,
+                    pnl: monitored.pnl.clone(),
+                    hours_since_rebalance: 0,
+                    time_out_of_range_secs: monitored
+                        .out_of_range_since
+                        .map(|since| (now - since).num_seconds().max(0) as u64)
+                        .unwrap_or_default(),
+                    position: Some(*address),
+                    pool: Some(monitored.pool),
+                };
+                let alerts = self.rules.write().await.evaluate(&context);
+                if let Some(callback) = &self.alert_callback {
+                    for alert in alerts {
+                        callback(alert);
+                    }
+                }
+            }
+
+            let snapshot = PositionSnapshot::from(&*monitored);
+            drop(positions);
+            self.persist(snapshot).await;
+        }
     }
 
     /// Starts the monitoring loop.
@@ -253,9 +432,67 @@ impl PositionMonitor {
         }
     }
 
-    /// Adds an alert rule.
-    pub fn add_alert_rule(&mut self, rule: AlertRule) {
-        self.alert_rules.push(rule);
+    /// Starts the monitoring loop with pool prices streamed over WebSocket.
+    ///
+    /// Range status and token amounts update on every pool account change;
+    /// the poll still runs every `poll_interval_secs` to refresh fees and to
+    /// resubscribe when the set of monitored pools changes.
+    pub async fn start_streaming(&self, subscription: SubscriptionConfig) {
+        let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+        let mut ticker = interval(poll_interval);
+        let mut pools = HashSet::new();
+        let mut updates: Option<PoolSubscription> = None;
+
+        info!(
+            interval_secs = self.config.poll_interval_secs,
+            ws_url = %subscription.ws_url,
+            "Starting streaming position monitor"
+        );
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    if let Err(e) = self.update_all().await {
+                        error!(error = %e, "Monitor update failed");
+                    }
+
+                    let monitored = self.monitored_pools().await;
+                    if monitored != pools {
+                        pools = monitored;
+                        updates = (!pools.is_empty()).then(|| {
+                            PoolSubscriber::new(subscription.clone())
+                                .with_pools(pools.iter().copied())
+                                .spawn()
+                        });
+                    }
+                }
+                Some(update) = next_update(&mut updates) => {
+                    self.apply_pool_update(&update).await;
+                }
+            }
+        }
+    }
+
+    /// Adds an alert rule, replacing any rule with the same name.
+    pub async fn add_alert_rule(&self, rule: AlertRule) {
+        let mut rules = self.rules.write().await;
+        rules.remove_rule(&rule.name);
+        rules.add_rule(rule);
+    }
+
+    /// Removes an alert rule by name.
+    ///
+    /// Returns false if no rule had that name.
+    pub async fn remove_alert_rule(&self, name: &str) -> bool {
+        let mut rules = self.rules.write().await;
+        let exists = rules.rules().iter().any(|r| r.name == name);
+        rules.remove_rule(name);
+        exists
+    }
+
+    /// Gets the configured alert rules.
+    pub async fn alert_rules(&self) -> Vec<AlertRule> {
+        self.rules.read().await.rules().to_vec()
     }
 
     /// Sets the alert callback.
@@ -292,6 +529,14 @@ impl PositionMonitor {
     }
 }
 
+/// Receives the next update of a subscription, or waits forever without one.
+async fn next_update(subscription: &mut Option<PoolSubscription>) -> Option<PoolPriceUpdate> {
+    match subscription {
+        Some(subscription) => subscription.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
 /// Aggregate portfolio metrics.
 #[derive(Debug, Clone, Default)]
 pub struct PortfolioMetrics {
@@ -0,0 +1,331 @@
+//! Closing positions through the protocol adapters.
+
+use super::position::{RewardClaim, reward_claims, with_slippage};
+use super::submit::Submitter;
+use crate::error::ExecutionError;
+use crate::lifecycle::{
+    CloseReason, EventData, LifecycleEvent, LifecycleEventType, LifecycleTracker,
+    PositionClosedData,
+};
+use crate::monitor::PositionPnL;
+use crate::transaction::{PriorityLevel, TransactionManager};
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clmm_lp_domain::enums::Protocol;
+use clmm_lp_domain::math::concentrated_liquidity::get_amounts_for_liquidity_x64;
+use clmm_lp_domain::math::price_tick::tick_to_sqrt_price_x64;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::sync::Arc;
+use tracing::info;
+
+/// A position to close.
+#[derive(Debug, Clone)]
+pub struct CloseRequest {
+    /// Position account.
+    pub position: Pubkey,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+    /// Reason for closing.
+    pub reason: CloseReason,
+    /// Latest PnL of the position, realized by closing it.
+    pub pnl: Option<PositionPnL>,
+}
+
+/// A position closed on chain.
+///
+/// Token amounts are raw base units.
+#[derive(Debug, Clone)]
+pub struct ClosedPosition {
+    /// Position account.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Liquidity withdrawn.
+    pub liquidity_removed: u128,
+    /// Token amounts expected from the withdrawal.
+    pub amounts: (u64, u64),
+    /// Minimum token amounts accepted from the withdrawal.
+    pub minimums: (u64, u64),
+    /// Fees collected, as last recorded on the position.
+    pub fees_collected: (u64, u64),
+    /// Rewards collected, as last recorded on the position.
+    pub rewards_collected: Vec<RewardClaim>,
+    /// PnL realized by closing, in USD.
+    pub realized_pnl_usd: Decimal,
+    /// PnL realized by closing, as a percentage of the entry value.
+    pub realized_pnl_pct: Decimal,
+    /// Compute unit price paid, in micro-lamports.
+    pub priority_fee: u64,
+    /// Compute units used in simulation.
+    pub compute_units: u64,
+    /// Transaction signature.
+    pub signature: Signature,
+    /// Slot the transaction was confirmed in.
+    pub slot: u64,
+}
+
+/// Closes positions in any registered Solana CLMM.
+///
+/// Liquidity is withdrawn to zero, owed fees and rewards are collected and
+/// the position account is closed and its NFT burned, all in one transaction
+/// priced and simulated like an open. Withdrawing first settles the fees and
+/// rewards earned up to the close, so the collects leave nothing behind.
+/// Rewards are read for Orca Whirlpool positions only, so Raydium CLMM
+/// positions, whose rewards would block the close, are refused.
+pub struct PositionCloser {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Protocol adapters building the instructions.
+    protocols: Arc<ProtocolRegistry>,
+    /// Sender of close transactions.
+    submitter: Submitter,
+    /// Lifecycle tracker recording closed positions.
+    lifecycle: Option<Arc<LifecycleTracker>>,
+}
+
+impl PositionCloser {
+    /// Creates a closer paying a medium priority fee.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        protocols: Arc<ProtocolRegistry>,
+        transactions: Arc<TransactionManager>,
+    ) -> Self {
+        Self {
+            protocols,
+            submitter: Submitter::new(provider.clone(), transactions),
+            provider,
+            lifecycle: None,
+        }
+    }
+
+    /// Sets the priority of close transactions.
+    #[must_use]
+    pub fn with_priority(mut self, priority: PriorityLevel) -> Self {
+        self.submitter.priority = priority;
+        self
+    }
+
+    /// Records closed positions in a lifecycle tracker.
+    #[must_use]
+    pub fn with_lifecycle(mut self, lifecycle: Arc<LifecycleTracker>) -> Self {
+        self.lifecycle = Some(lifecycle);
+        self
+    }
+
+    /// Withdraws a position's liquidity and fees and closes it, signed by
+    /// the wallet holding its NFT.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::NotPositionOwner`] if the wallet does not
+    /// hold the position NFT, [`ProtocolError::Unsupported`] for a Raydium
+    /// CLMM position, and [`ExecutionError::SimulationFailed`] if the
+    /// transaction would fail, in which case nothing is sent.
+    pub async fn close(&self, request: &CloseRequest, wallet: &Wallet) -> Result<ClosedPosition> {
+        let adapter = self
+            .protocols
+            .adapter_for_account(&request.position)
+            .await?;
+        let mut position = adapter.fetch_position(&request.position).await?;
+        if position.protocol == Protocol::Raydium {
+            return Err(ProtocolError::Unsupported(format!(
+                "closing Raydium CLMM position {}: its rewards cannot be collected",
+                request.position
+            ))
+            .into());
+        }
+        ensure_owner(&self.provider, &position, wallet).await?;
+        position.on_chain.owner = wallet.pubkey();
+        let on_chain = &position.on_chain;
+        let pool = adapter.fetch_pool_state(&on_chain.pool).await?;
+
+        let amounts = amounts_for_liquidity(
+            on_chain.liquidity,
+            pool.sqrt_price,
+            on_chain.tick_lower,
+            on_chain.tick_upper,
+        )?;
+        let minimums = (
+            with_slippage(amounts.0, request.slippage_bps),
+            with_slippage(amounts.1, request.slippage_bps),
+        );
+
+        let owner = wallet.pubkey();
+        let mut instructions = Vec::new();
+        if on_chain.liquidity > 0 {
+            instructions.push(adapter.build_decrease_liquidity_instruction(
+                &pool,
+                &position,
+                &owner,
+                on_chain.liquidity,
+                minimums.0,
+                minimums.1,
+            )?);
+        }
+        instructions.push(adapter.build_collect_fees_instruction(&pool, &position, &owner)?);
+        let rewards = read_rewards(&self.provider, &position).await?;
+        for reward in &rewards {
+            instructions.push(adapter.build_collect_reward_instruction(
+                &pool,
+                &position,
+                &owner,
+                reward.index,
+                &reward.mint,
+                &reward.vault,
+            )?);
+        }
+        instructions.push(adapter.build_close_position_instruction(&position, &owner)?);
+
+        let submitted = self
+            .submitter
+            .submit(
+                "close_position",
+                instructions,
+                wallet,
+                None,
+                &[pool.address],
+            )
+            .await?;
+
+        let pnl = request.pnl.clone().unwrap_or_default();
+        let closed = ClosedPosition {
+            position: request.position,
+            pool: pool.address,
+            liquidity_removed: on_chain.liquidity,
+            amounts,
+            minimums,
+            fees_collected: (on_chain.fees_owed_a, on_chain.fees_owed_b),
+            rewards_collected: rewards,
+            realized_pnl_usd: pnl.net_pnl_usd,
+            realized_pnl_pct: pnl.net_pnl_pct,
+            priority_fee: submitted.priority_fee,
+            compute_units: submitted.compute_units,
+            signature: submitted.signature,
+            slot: submitted.slot,
+        };
+        info!(
+            position = %closed.position,
+            pool = %closed.pool,
+            liquidity = closed.liquidity_removed,
+            pnl_usd = %closed.realized_pnl_usd,
+            signature = %closed.signature,
+            "Position closed"
+        );
+
+        if let Some(lifecycle) = &self.lifecycle {
+            let duration_hours = lifecycle
+                .get_summary(&closed.position)
+                .await
+                .map(|summary| (chrono::Utc::now() - summary.opened_at).num_hours().max(0) as u64)
+                .unwrap_or(0);
+            let event = LifecycleEvent::new(
+                LifecycleEventType::PositionClosed,
+                closed.position,
+                closed.pool,
+                EventData::PositionClosed(PositionClosedData {
+                    liquidity_removed: closed.liquidity_removed,
+                    amount_a: closed.amounts.0,
+                    amount_b: closed.amounts.1,
+                    total_fees_a: pnl.fees_earned_a.max(closed.fees_collected.0),
+                    total_fees_b: pnl.fees_earned_b.max(closed.fees_collected.1),
+                    final_pnl_usd: closed.realized_pnl_usd,
+                    final_pnl_pct: closed.realized_pnl_pct,
+                    total_il_pct: pnl.il_pct,
+                    duration_hours,
+                    reason: request.reason.clone(),
+                }),
+            )
+            .with_signature(closed.signature);
+            lifecycle.record_event(event).await;
+        }
+
+        Ok(closed)
+    }
+}
+
+/// Calculates the token amounts withdrawing liquidity from a range returns
+/// at the pool's Q64.64 sqrt price, with the Whirlpool program's integer
+/// math.
+///
+/// # Errors
+/// Returns [`ExecutionError::InvalidRange`] for a range outside the valid
+/// ticks.
+pub(super) fn amounts_for_liquidity(
+    liquidity: u128,
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<(u64, u64)> {
+    let invalid =
+        |e: &str| ExecutionError::InvalidRange(format!("[{}, {}]: {}", tick_lower, tick_upper, e));
+    let sqrt_lower = tick_to_sqrt_price_x64(tick_lower).map_err(invalid)?;
+    let sqrt_upper = tick_to_sqrt_price_x64(tick_upper).map_err(invalid)?;
+    let amounts =
+        get_amounts_for_liquidity_x64(liquidity, sqrt_price, sqrt_lower, sqrt_upper, false)
+            .map_err(invalid)?;
+    Ok(amounts)
+}
+
+/// Checks that a wallet holds the NFT of a position.
+///
+/// # Errors
+/// Returns [`ExecutionError::NotPositionOwner`] if it does not.
+pub(crate) async fn ensure_owner(
+    provider: &RpcProvider,
+    position: &ProtocolPosition,
+    wallet: &Wallet,
+) -> Result<()> {
+    let mints = find_position_mints(provider, &wallet.pubkey()).await?;
+    if !mints.contains(&position.position_mint) {
+        return Err(ExecutionError::NotPositionOwner {
+            position: position.on_chain.address.to_string(),
+            wallet: wallet.pubkey().to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Reads the initialized pool rewards of a position, which must all be
+/// collected before it can be closed.
+///
+/// Only Orca Whirlpool rewards are read; other protocols return none.
+pub(crate) async fn read_rewards(
+    provider: &Arc<RpcProvider>,
+    position: &ProtocolPosition,
+) -> Result<Vec<RewardClaim>> {
+    if position.protocol != Protocol::OrcaWhirlpools {
+        return Ok(Vec::new());
+    }
+    let account = PositionReader::new(provider.clone())
+        .get_position_account(&position.on_chain.address.to_string())
+        .await?;
+    let whirlpool = WhirlpoolReader::new(provider.clone())
+        .get_whirlpool(&account.whirlpool.to_string())
+        .await?;
+    Ok(reward_claims(&account, &whirlpool))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amounts_for_liquidity() {
+        let sqrt_price = |tick| tick_to_sqrt_price_x64(tick).unwrap();
+
+        // In range both tokens are withdrawn
+        let (a, b) = amounts_for_liquidity(1_000_000_000, sqrt_price(0), -1000, 1000).unwrap();
+        assert!(a > 0 && b > 0);
+
+        // Below the range only token A, above it only token B
+        let (a, b) = amounts_for_liquidity(1_000_000_000, sqrt_price(-2000), -1000, 1000).unwrap();
+        assert!(a > 0 && b == 0);
+        let (a, b) = amounts_for_liquidity(1_000_000_000, sqrt_price(2000), -1000, 1000).unwrap();
+        assert!(a == 0 && b > 0);
+
+        assert!(amounts_for_liquidity(1, sqrt_price(0), -500_000, 1000).is_err());
+    }
+}
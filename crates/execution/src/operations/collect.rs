@@ -0,0 +1,160 @@
+//! Collecting position fees and rewards through the protocol adapters.
+
+use super::close::{ensure_owner, read_rewards};
+use super::position::RewardClaim;
+use super::submit::Submitter;
+use crate::lifecycle::{
+    EventData, FeesCollectedData, LifecycleEvent, LifecycleEventType, LifecycleTracker,
+};
+use crate::transaction::{PriorityLevel, TransactionManager};
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::sync::Arc;
+use tracing::info;
+
+/// Fees and rewards collected on chain.
+///
+/// Token amounts are raw base units.
+#[derive(Debug, Clone)]
+pub struct CollectedFees {
+    /// Position account.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Fees collected, as last recorded on the position.
+    pub fees: (u64, u64),
+    /// Rewards collected, as last recorded on the position.
+    pub rewards: Vec<RewardClaim>,
+    /// Compute unit price paid, in micro-lamports.
+    pub priority_fee: u64,
+    /// Compute units used in simulation.
+    pub compute_units: u64,
+    /// Transaction signature.
+    pub signature: Signature,
+    /// Slot the transaction was confirmed in.
+    pub slot: u64,
+}
+
+/// Collects the owed fees and rewards of positions in any registered
+/// Solana CLMM, leaving their liquidity in place.
+///
+/// `collect_fees` and a `collect_reward` per initialized reward go out in
+/// one transaction priced and simulated like an open. Rewards are read for
+/// Orca Whirlpool positions only.
+pub struct PositionCollector {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Protocol adapters building the instructions.
+    protocols: Arc<ProtocolRegistry>,
+    /// Sender of collect transactions.
+    submitter: Submitter,
+    /// Lifecycle tracker recording collected fees.
+    lifecycle: Option<Arc<LifecycleTracker>>,
+}
+
+impl PositionCollector {
+    /// Creates a collector paying a medium priority fee.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        protocols: Arc<ProtocolRegistry>,
+        transactions: Arc<TransactionManager>,
+    ) -> Self {
+        Self {
+            protocols,
+            submitter: Submitter::new(provider.clone(), transactions),
+            provider,
+            lifecycle: None,
+        }
+    }
+
+    /// Sets the priority of collect transactions.
+    #[must_use]
+    pub fn with_priority(mut self, priority: PriorityLevel) -> Self {
+        self.submitter.priority = priority;
+        self
+    }
+
+    /// Records collected fees in a lifecycle tracker.
+    #[must_use]
+    pub fn with_lifecycle(mut self, lifecycle: Arc<LifecycleTracker>) -> Self {
+        self.lifecycle = Some(lifecycle);
+        self
+    }
+
+    /// Collects a position's owed fees and rewards, signed by the wallet
+    /// holding its NFT.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::NotPositionOwner`] if the wallet does not
+    /// hold the position NFT, and [`ExecutionError::SimulationFailed`] if
+    /// the transaction would fail, in which case nothing is sent.
+    ///
+    /// [`ExecutionError::NotPositionOwner`]: crate::error::ExecutionError::NotPositionOwner
+    /// [`ExecutionError::SimulationFailed`]: crate::error::ExecutionError::SimulationFailed
+    pub async fn collect(&self, position: &Pubkey, wallet: &Wallet) -> Result<CollectedFees> {
+        let adapter = self.protocols.adapter_for_account(position).await?;
+        let mut position = adapter.fetch_position(position).await?;
+        ensure_owner(&self.provider, &position, wallet).await?;
+        position.on_chain.owner = wallet.pubkey();
+        let pool = adapter.fetch_pool_state(&position.on_chain.pool).await?;
+
+        let owner = wallet.pubkey();
+        let mut instructions =
+            vec![adapter.build_collect_fees_instruction(&pool, &position, &owner)?];
+        let rewards = read_rewards(&self.provider, &position).await?;
+        for reward in &rewards {
+            instructions.push(adapter.build_collect_reward_instruction(
+                &pool,
+                &position,
+                &owner,
+                reward.index,
+                &reward.mint,
+                &reward.vault,
+            )?);
+        }
+
+        let submitted = self
+            .submitter
+            .submit("collect_fees", instructions, wallet, None, &[pool.address])
+            .await?;
+
+        let collected = CollectedFees {
+            position: position.on_chain.address,
+            pool: pool.address,
+            fees: (position.on_chain.fees_owed_a, position.on_chain.fees_owed_b),
+            rewards,
+            priority_fee: submitted.priority_fee,
+            compute_units: submitted.compute_units,
+            signature: submitted.signature,
+            slot: submitted.slot,
+        };
+        info!(
+            position = %collected.position,
+            fees_a = collected.fees.0,
+            fees_b = collected.fees.1,
+            signature = %collected.signature,
+            "Fees collected"
+        );
+
+        if let Some(lifecycle) = &self.lifecycle {
+            let event = LifecycleEvent::new(
+                LifecycleEventType::FeesCollected,
+                collected.position,
+                collected.pool,
+                EventData::FeesCollected(FeesCollectedData {
+                    fees_a: collected.fees.0,
+                    fees_b: collected.fees.1,
+                    fees_usd: Decimal::ZERO, // Would need price oracle
+                }),
+            )
+            .with_signature(collected.signature);
+            lifecycle.record_event(event).await;
+        }
+
+        Ok(collected)
+    }
+}
@@ -0,0 +1,328 @@
+//! Adding and withdrawing position liquidity through the protocol adapters.
+
+use super::close::{amounts_for_liquidity, ensure_owner};
+use super::open::{liquidity_for_amounts, with_slippage_up};
+use super::position::with_slippage;
+use super::submit::Submitter;
+use crate::error::ExecutionError;
+use crate::lifecycle::{
+    EventData, LifecycleEvent, LifecycleEventType, LifecycleTracker, LiquidityChangeData,
+};
+use crate::oracle::PriceGuard;
+use crate::transaction::{PriorityLevel, TransactionManager};
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clmm_lp_protocols::prelude::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::sync::Arc;
+use tracing::info;
+
+/// Liquidity to add to a position.
+///
+/// Token amounts are raw base units.
+#[derive(Debug, Clone)]
+pub struct IncreaseRequest {
+    /// Position account.
+    pub position: Pubkey,
+    /// Amount of token A to deposit.
+    pub amount_a: u64,
+    /// Amount of token B to deposit.
+    pub amount_b: u64,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+}
+
+/// Liquidity to withdraw from a position.
+#[derive(Debug, Clone)]
+pub struct DecreaseRequest {
+    /// Position account.
+    pub position: Pubkey,
+    /// Liquidity to withdraw, at most the position's.
+    pub liquidity: u128,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+}
+
+/// A change of position liquidity made on chain.
+///
+/// Token amounts are raw base units.
+#[derive(Debug, Clone)]
+pub struct LiquidityChange {
+    /// Position account.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Whether liquidity was added.
+    pub is_increase: bool,
+    /// Liquidity added or withdrawn.
+    pub liquidity_delta: u128,
+    /// Liquidity of the position after the change.
+    pub new_liquidity: u128,
+    /// Token amounts expected to be deposited or withdrawn.
+    pub amounts: (u64, u64),
+    /// Maximum token amounts a deposit could spend, or minimum token
+    /// amounts a withdrawal accepted.
+    pub limits: (u64, u64),
+    /// Compute unit price paid, in micro-lamports.
+    pub priority_fee: u64,
+    /// Compute units used in simulation.
+    pub compute_units: u64,
+    /// Transaction signature.
+    pub signature: Signature,
+    /// Slot the transaction was confirmed in.
+    pub slot: u64,
+}
+
+/// Adds liquidity to and withdraws it from positions in any registered
+/// Solana CLMM, keeping them open.
+///
+/// Each change goes out in one transaction priced and simulated like an
+/// open, signed by the wallet holding the position NFT.
+pub struct LiquidityAdjuster {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Protocol adapters building the instructions.
+    protocols: Arc<ProtocolRegistry>,
+    /// Sender of liquidity transactions.
+    submitter: Submitter,
+    /// Oracle price guard checked before depositing.
+    price_guard: Option<Arc<PriceGuard>>,
+    /// Lifecycle tracker recording liquidity changes.
+    lifecycle: Option<Arc<LifecycleTracker>>,
+}
+
+impl LiquidityAdjuster {
+    /// Creates an adjuster paying a medium priority fee.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        protocols: Arc<ProtocolRegistry>,
+        transactions: Arc<TransactionManager>,
+    ) -> Self {
+        Self {
+            protocols,
+            submitter: Submitter::new(provider.clone(), transactions),
+            provider,
+            price_guard: None,
+            lifecycle: None,
+        }
+    }
+
+    /// Sets the priority of liquidity transactions.
+    #[must_use]
+    pub fn with_priority(mut self, priority: PriorityLevel) -> Self {
+        self.submitter.priority = priority;
+        self
+    }
+
+    /// Checks the pool price against an oracle before depositing.
+    #[must_use]
+    pub fn with_price_guard(mut self, guard: Arc<PriceGuard>) -> Self {
+        self.price_guard = Some(guard);
+        self
+    }
+
+    /// Records liquidity changes in a lifecycle tracker.
+    #[must_use]
+    pub fn with_lifecycle(mut self, lifecycle: Arc<LifecycleTracker>) -> Self {
+        self.lifecycle = Some(lifecycle);
+        self
+    }
+
+    /// Deposits token amounts into a position at the pool's current price.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::NotPositionOwner`] if the wallet does not
+    /// hold the position NFT, [`ExecutionError::InvalidRange`] if the
+    /// amounts deposit no liquidity in the position's range, and
+    /// [`ExecutionError::SimulationFailed`] if the transaction would fail,
+    /// in which case nothing is sent.
+    pub async fn increase(
+        &self,
+        request: &IncreaseRequest,
+        wallet: &Wallet,
+    ) -> Result<LiquidityChange> {
+        let adapter = self
+            .protocols
+            .adapter_for_account(&request.position)
+            .await?;
+        let mut position = adapter.fetch_position(&request.position).await?;
+        ensure_owner(&self.provider, &position, wallet).await?;
+        position.on_chain.owner = wallet.pubkey();
+        let on_chain = &position.on_chain;
+        let pool = adapter.fetch_pool_state(&on_chain.pool).await?;
+
+        if let Some(guard) = &self.price_guard {
+            guard.check_pool(&pool).await?;
+        }
+
+        let liquidity = liquidity_for_amounts(
+            (request.amount_a, request.amount_b),
+            pool.sqrt_price,
+            on_chain.tick_lower,
+            on_chain.tick_upper,
+        );
+        if liquidity == 0 {
+            return Err(ExecutionError::InvalidRange(format!(
+                "amounts deposit no liquidity in [{}, {}] at tick {}",
+                on_chain.tick_lower, on_chain.tick_upper, pool.tick_current
+            ))
+            .into());
+        }
+        let token_max = (
+            with_slippage_up(request.amount_a, request.slippage_bps),
+            with_slippage_up(request.amount_b, request.slippage_bps),
+        );
+
+        let instruction = adapter.build_increase_liquidity_instruction(
+            &pool,
+            &position,
+            &wallet.pubkey(),
+            liquidity,
+            token_max.0,
+            token_max.1,
+        )?;
+        let submitted = self
+            .submitter
+            .submit(
+                "increase_liquidity",
+                vec![instruction],
+                wallet,
+                None,
+                &[pool.address],
+            )
+            .await?;
+
+        let change = LiquidityChange {
+            position: on_chain.address,
+            pool: pool.address,
+            is_increase: true,
+            liquidity_delta: liquidity,
+            new_liquidity: on_chain.liquidity.saturating_add(liquidity),
+            amounts: (request.amount_a, request.amount_b),
+            limits: token_max,
+            priority_fee: submitted.priority_fee,
+            compute_units: submitted.compute_units,
+            signature: submitted.signature,
+            slot: submitted.slot,
+        };
+        self.record(&change).await;
+        Ok(change)
+    }
+
+    /// Withdraws liquidity from a position, leaving it open.
+    ///
+    /// Withdrawn tokens are owed to the position until its fees are
+    /// collected or it is closed.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::NotPositionOwner`] if the wallet does not
+    /// hold the position NFT, [`ExecutionError::InvalidPositionState`] if
+    /// the position holds less liquidity than requested, and
+    /// [`ExecutionError::SimulationFailed`] if the transaction would fail,
+    /// in which case nothing is sent.
+    pub async fn decrease(
+        &self,
+        request: &DecreaseRequest,
+        wallet: &Wallet,
+    ) -> Result<LiquidityChange> {
+        let adapter = self
+            .protocols
+            .adapter_for_account(&request.position)
+            .await?;
+        let mut position = adapter.fetch_position(&request.position).await?;
+        ensure_owner(&self.provider, &position, wallet).await?;
+        position.on_chain.owner = wallet.pubkey();
+        let on_chain = &position.on_chain;
+        if request.liquidity == 0 || request.liquidity > on_chain.liquidity {
+            return Err(ExecutionError::InvalidPositionState {
+                position: request.position.to_string(),
+                state: format!(
+                    "holding liquidity {}, cannot withdraw {}",
+                    on_chain.liquidity, request.liquidity
+                ),
+            }
+            .into());
+        }
+        let pool = adapter.fetch_pool_state(&on_chain.pool).await?;
+
+        let amounts = amounts_for_liquidity(
+            request.liquidity,
+            pool.sqrt_price,
+            on_chain.tick_lower,
+            on_chain.tick_upper,
+        )?;
+        let minimums = (
+            with_slippage(amounts.0, request.slippage_bps),
+            with_slippage(amounts.1, request.slippage_bps),
+        );
+
+        let instruction = adapter.build_decrease_liquidity_instruction(
+            &pool,
+            &position,
+            &wallet.pubkey(),
+            request.liquidity,
+            minimums.0,
+            minimums.1,
+        )?;
+        let submitted = self
+            .submitter
+            .submit(
+                "decrease_liquidity",
+                vec![instruction],
+                wallet,
+                None,
+                &[pool.address],
+            )
+            .await?;
+
+        let change = LiquidityChange {
+            position: on_chain.address,
+            pool: pool.address,
+            is_increase: false,
+            liquidity_delta: request.liquidity,
+            new_liquidity: on_chain.liquidity - request.liquidity,
+            amounts,
+            limits: minimums,
+            priority_fee: submitted.priority_fee,
+            compute_units: submitted.compute_units,
+            signature: submitted.signature,
+            slot: submitted.slot,
+        };
+        self.record(&change).await;
+        Ok(change)
+    }
+
+    /// Logs a liquidity change and records it in the lifecycle.
+    async fn record(&self, change: &LiquidityChange) {
+        info!(
+            position = %change.position,
+            is_increase = change.is_increase,
+            delta = change.liquidity_delta,
+            signature = %change.signature,
+            "Position liquidity changed"
+        );
+
+        if let Some(lifecycle) = &self.lifecycle {
+            let event_type = if change.is_increase {
+                LifecycleEventType::LiquidityIncreased
+            } else {
+                LifecycleEventType::LiquidityDecreased
+            };
+            let event = LifecycleEvent::new(
+                event_type,
+                change.position,
+                change.pool,
+                EventData::LiquidityChange(LiquidityChangeData {
+                    is_increase: change.is_increase,
+                    liquidity_delta: change.liquidity_delta,
+                    amount_a: change.amounts.0,
+                    amount_b: change.amounts.1,
+                    new_liquidity: change.new_liquidity,
+                }),
+            )
+            .with_signature(change.signature);
+            lifecycle.record_event(event).await;
+        }
+    }
+}
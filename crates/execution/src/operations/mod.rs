@@ -0,0 +1,25 @@
+//! Manual position operations.
+//!
+//! Provides operator-initiated position changes:
+//! - Opening a position in a pool of any registered protocol
+//! - Closing a position, withdrawing its liquidity, fees and rewards
+//! - Collecting a position's fees and rewards
+//! - Adding liquidity to or withdrawing it from an open position
+//! - Rebalancing a position into a new range
+//!
+//! Each operation is planned from current on-chain state first, so it can
+//! be reviewed (or only reviewed, in a dry run) before it is sent.
+
+mod close;
+mod collect;
+mod liquidity;
+mod open;
+mod position;
+mod submit;
+
+pub use close::{CloseRequest, ClosedPosition, PositionCloser};
+pub use collect::{CollectedFees, PositionCollector};
+pub use liquidity::{DecreaseRequest, IncreaseRequest, LiquidityAdjuster, LiquidityChange};
+pub use open::{OpenRequest, OpenedPosition, PositionOpener};
+pub use position::*;
+pub(crate) use submit::{Submitter, transaction_fee};
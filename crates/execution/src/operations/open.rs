@@ -0,0 +1,346 @@
+//! Opening positions through the protocol adapters.
+
+use super::submit::Submitter;
+use crate::error::ExecutionError;
+use crate::lifecycle::{
+    EventData, LifecycleEvent, LifecycleEventType, LifecycleTracker, PositionOpenedData,
+};
+use crate::oracle::PriceGuard;
+use crate::transaction::{PriorityLevel, TransactionManager};
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clmm_lp_domain::math::concentrated_liquidity::get_liquidity_for_amounts_x64;
+use clmm_lp_domain::math::price_tick::tick_to_sqrt_price_x64;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+use std::sync::Arc;
+use tracing::info;
+
+/// Basis points in one.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// A position to open.
+///
+/// Token amounts are raw base units.
+#[derive(Debug, Clone)]
+pub struct OpenRequest {
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Lower tick, a multiple of the pool's tick spacing.
+    pub tick_lower: i32,
+    /// Upper tick, a multiple of the pool's tick spacing.
+    pub tick_upper: i32,
+    /// Amount of token A to deposit.
+    pub amount_a: u64,
+    /// Amount of token B to deposit.
+    pub amount_b: u64,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+}
+
+/// A position opened on chain.
+#[derive(Debug, Clone)]
+pub struct OpenedPosition {
+    /// Position account.
+    pub position: Pubkey,
+    /// Mint of the position NFT.
+    pub position_mint: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Lower tick.
+    pub tick_lower: i32,
+    /// Upper tick.
+    pub tick_upper: i32,
+    /// Liquidity deposited.
+    pub liquidity: u128,
+    /// Maximum token amounts the deposit could spend.
+    pub token_max: (u64, u64),
+    /// Compute unit price paid, in micro-lamports.
+    pub priority_fee: u64,
+    /// Compute units used in simulation.
+    pub compute_units: u64,
+    /// Transaction signature.
+    pub signature: Signature,
+    /// Slot the transaction was confirmed in.
+    pub slot: u64,
+}
+
+/// Opens positions in any registered Solana CLMM.
+///
+/// The position NFT mint, `open_position` and `increase_liquidity` go out in
+/// one transaction signed by the wallet and a fresh mint keypair, priced
+/// from recent priority fees and simulated before it is sent.
+pub struct PositionOpener {
+    /// Protocol adapters building the instructions.
+    protocols: Arc<ProtocolRegistry>,
+    /// Sender of open transactions.
+    submitter: Submitter,
+    /// Oracle price guard checked before opening.
+    price_guard: Option<Arc<PriceGuard>>,
+    /// Lifecycle tracker recording opened positions.
+    lifecycle: Option<Arc<LifecycleTracker>>,
+}
+
+impl PositionOpener {
+    /// Creates an opener paying a medium priority fee.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        protocols: Arc<ProtocolRegistry>,
+        transactions: Arc<TransactionManager>,
+    ) -> Self {
+        Self {
+            protocols,
+            submitter: Submitter::new(provider, transactions),
+            price_guard: None,
+            lifecycle: None,
+        }
+    }
+
+    /// Sets the priority of open transactions.
+    #[must_use]
+    pub fn with_priority(mut self, priority: PriorityLevel) -> Self {
+        self.submitter.priority = priority;
+        self
+    }
+
+    /// Checks the pool price against an oracle before opening.
+    #[must_use]
+    pub fn with_price_guard(mut self, guard: Arc<PriceGuard>) -> Self {
+        self.price_guard = Some(guard);
+        self
+    }
+
+    /// Records opened positions in a lifecycle tracker.
+    #[must_use]
+    pub fn with_lifecycle(mut self, lifecycle: Arc<LifecycleTracker>) -> Self {
+        self.lifecycle = Some(lifecycle);
+        self
+    }
+
+    /// Opens a position and deposits liquidity, signed by a wallet.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::InvalidRange`] for a range off the pool's
+    /// tick spacing or too small for the amounts, and
+    /// [`ExecutionError::SimulationFailed`] if the transaction would fail,
+    /// in which case nothing is sent.
+    pub async fn open(&self, request: &OpenRequest, wallet: &Wallet) -> Result<OpenedPosition> {
+        let adapter = self.protocols.adapter_for_account(&request.pool).await?;
+        let pool = adapter.fetch_pool_state(&request.pool).await?;
+        validate_range(request.tick_lower, request.tick_upper, pool.tick_spacing)?;
+
+        if let Some(guard) = &self.price_guard {
+            guard.check_pool(&pool).await?;
+        }
+
+        let liquidity = liquidity_for_amounts(
+            (request.amount_a, request.amount_b),
+            pool.sqrt_price,
+            request.tick_lower,
+            request.tick_upper,
+        );
+        if liquidity == 0 {
+            return Err(ExecutionError::InvalidRange(format!(
+                "amounts deposit no liquidity in [{}, {}] at tick {}",
+                request.tick_lower, request.tick_upper, pool.tick_current
+            ))
+            .into());
+        }
+        let token_max = (
+            with_slippage_up(request.amount_a, request.slippage_bps),
+            with_slippage_up(request.amount_b, request.slippage_bps),
+        );
+
+        let owner = wallet.pubkey();
+        let position_mint = Keypair::new();
+        let position = ProtocolPosition {
+            protocol: pool.protocol,
+            position_mint: position_mint.pubkey(),
+            on_chain: OnChainPosition {
+                address: adapter.position_address(&position_mint.pubkey()),
+                pool: pool.address,
+                owner,
+                tick_lower: request.tick_lower,
+                tick_upper: request.tick_upper,
+                liquidity: 0,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+            },
+        };
+        let instructions = vec![
+            adapter.build_open_position_instruction(
+                &pool,
+                &owner,
+                &position.position_mint,
+                request.tick_lower,
+                request.tick_upper,
+            )?,
+            adapter.build_increase_liquidity_instruction(
+                &pool,
+                &position,
+                &owner,
+                liquidity,
+                token_max.0,
+                token_max.1,
+            )?,
+        ];
+
+        let submitted = self
+            .submitter
+            .submit(
+                "open_position",
+                instructions,
+                wallet,
+                Some(&position_mint),
+                &[pool.address],
+            )
+            .await?;
+
+        let opened = OpenedPosition {
+            position: position.on_chain.address,
+            position_mint: position.position_mint,
+            pool: pool.address,
+            tick_lower: request.tick_lower,
+            tick_upper: request.tick_upper,
+            liquidity,
+            token_max,
+            priority_fee: submitted.priority_fee,
+            compute_units: submitted.compute_units,
+            signature: submitted.signature,
+            slot: submitted.slot,
+        };
+        info!(
+            position = %opened.position,
+            pool = %opened.pool,
+            liquidity = opened.liquidity,
+            signature = %opened.signature,
+            "Position opened"
+        );
+
+        if let Some(lifecycle) = &self.lifecycle {
+            // USD value is unknown here; the monitor values the position
+            // once it tracks it
+            let event = LifecycleEvent::new(
+                LifecycleEventType::PositionOpened,
+                opened.position,
+                opened.pool,
+                EventData::PositionOpened(PositionOpenedData {
+                    tick_lower: opened.tick_lower,
+                    tick_upper: opened.tick_upper,
+                    liquidity,
+                    amount_a: request.amount_a,
+                    amount_b: request.amount_b,
+                    entry_price: pool.price,
+                    entry_value_usd: Decimal::ZERO,
+                }),
+            )
+            .with_signature(opened.signature);
+            lifecycle.record_event(event).await;
+        }
+
+        Ok(opened)
+    }
+}
+
+/// Checks that a range is ordered and on the pool's tick spacing.
+fn validate_range(tick_lower: i32, tick_upper: i32, tick_spacing: u16) -> Result<()> {
+    let spacing = i32::from(tick_spacing.max(1));
+    if tick_lower >= tick_upper {
+        return Err(ExecutionError::InvalidRange(format!(
+            "lower tick {} must be below upper tick {}",
+            tick_lower, tick_upper
+        ))
+        .into());
+    }
+    if tick_lower % spacing != 0 || tick_upper % spacing != 0 {
+        return Err(ExecutionError::InvalidRange(format!(
+            "ticks [{}, {}] must be multiples of tick spacing {}",
+            tick_lower, tick_upper, spacing
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Raises an amount by a slippage tolerance.
+pub(super) fn with_slippage_up(amount: u64, slippage_bps: u16) -> u64 {
+    let allowed = BPS_DENOMINATOR + u64::from(slippage_bps);
+    (u128::from(amount) * u128::from(allowed) / u128::from(BPS_DENOMINATOR)).min(u64::MAX as u128)
+        as u64
+}
+
+/// Calculates the most liquidity the raw token amounts can fund in a range
+/// at the pool's Q64.64 sqrt price, with the Whirlpool program's integer
+/// math.
+///
+/// Returns 0 for a range outside the valid ticks.
+pub(super) fn liquidity_for_amounts(
+    amounts: (u64, u64),
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> u128 {
+    match (
+        tick_to_sqrt_price_x64(tick_lower),
+        tick_to_sqrt_price_x64(tick_upper),
+    ) {
+        (Ok(sqrt_lower), Ok(sqrt_upper)) if sqrt_lower < sqrt_upper => {
+            get_liquidity_for_amounts_x64(amounts, sqrt_price, sqrt_lower, sqrt_upper)
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::expected_amounts;
+
+    fn sqrt_price(tick: i32) -> u128 {
+        tick_to_sqrt_price_x64(tick).unwrap()
+    }
+
+    #[test]
+    fn test_validate_range() {
+        assert!(validate_range(-128, 128, 64).is_ok());
+        assert!(validate_range(-130, 128, 64).is_err());
+        assert!(validate_range(128, 128, 64).is_err());
+        assert!(validate_range(5, 7, 0).is_ok());
+    }
+
+    #[test]
+    fn test_with_slippage_up() {
+        assert_eq!(with_slippage_up(10_000, 50), 10_050);
+        assert_eq!(with_slippage_up(u64::MAX, 100), u64::MAX);
+        assert_eq!(with_slippage_up(1_000, 0), 1_000);
+    }
+
+    #[test]
+    fn test_liquidity_for_amounts() {
+        // In range, the scarcer token limits the liquidity
+        let liquidity = liquidity_for_amounts((1_000_000, 1_000_000), sqrt_price(0), -1000, 1000);
+        let (a, b) = expected_amounts(liquidity, 0, -1000, 1000);
+        assert!(a <= 1_000_000 && b <= 1_000_000);
+        assert!(a.max(b) > 999_000);
+
+        // Below the range only token A is deposited
+        let only_a = liquidity_for_amounts((1_000_000, 0), sqrt_price(-2000), -1000, 1000);
+        assert!(only_a > 0);
+        assert_eq!(
+            liquidity_for_amounts((0, 1_000_000), sqrt_price(-2000), -1000, 1000),
+            0
+        );
+
+        // Above the range only token B is deposited
+        assert!(liquidity_for_amounts((0, 1_000_000), sqrt_price(2000), -1000, 1000) > 0);
+        assert_eq!(
+            liquidity_for_amounts((1_000_000, 1_000_000), sqrt_price(0), 1000, 1000),
+            0
+        );
+    }
+}
@@ -0,0 +1,532 @@
+//! Planning and execution of manual position operations.
+
+use crate::error::ExecutionError;
+use crate::oracle::PriceGuard;
+use crate::strategy::{PlannedInstruction, expected_amounts};
+use crate::wallet::Wallet;
+use anyhow::{Context, Result};
+use clmm_lp_protocols::prelude::*;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+/// Basis points in one.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Kind of position operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    /// Open a new position.
+    Open,
+    /// Withdraw all liquidity and close a position.
+    Close,
+    /// Close a position and reopen its liquidity in a new range.
+    Rebalance,
+    /// Collect a position's fees and rewards.
+    Collect,
+}
+
+/// A reward claimable from a position.
+#[derive(Debug, Clone)]
+pub struct RewardClaim {
+    /// Pool reward index.
+    pub index: u8,
+    /// Reward token mint.
+    pub mint: Pubkey,
+    /// Pool vault holding the reward.
+    pub vault: Pubkey,
+    /// Reward owed as last recorded on the position.
+    pub amount_owed: u64,
+}
+
+impl OperationKind {
+    /// Returns the operation name.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Close => "close",
+            Self::Rebalance => "rebalance",
+            Self::Collect => "collect",
+        }
+    }
+}
+
+/// A position operation planned against current on-chain state.
+///
+/// Token amounts are raw base units.
+#[derive(Debug, Clone)]
+pub struct OperationPlan {
+    /// Kind of operation.
+    pub kind: OperationKind,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Position closed by the operation, if any.
+    pub position: Option<Pubkey>,
+    /// Pool tick when planned.
+    pub pool_tick: i32,
+    /// Range of the closed position.
+    pub current_range: Option<(i32, i32)>,
+    /// Range of the opened position.
+    pub new_range: Option<(i32, i32)>,
+    /// Liquidity withdrawn from the closed position.
+    pub liquidity: u128,
+    /// Token amounts expected from the closed position.
+    pub withdraw_amounts: (u64, u64),
+    /// Minimum token amounts accepted from the closed position.
+    pub withdraw_minimums: (u64, u64),
+    /// Uncollected fees of the position, as last recorded on-chain.
+    pub fees_owed: (u64, u64),
+    /// Rewards collected from the position.
+    pub rewards: Vec<RewardClaim>,
+    /// Tick spacing of the pool.
+    pub tick_spacing: u16,
+    /// Maximum token amounts deposited into the opened position.
+    pub deposit_amounts: (u64, u64),
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+    /// Instructions that will be sent, in order.
+    pub instructions: Vec<PlannedInstruction>,
+}
+
+/// Reduces an amount by a slippage tolerance.
+pub(super) fn with_slippage(amount: u64, slippage_bps: u16) -> u64 {
+    let kept = BPS_DENOMINATOR.saturating_sub(u64::from(slippage_bps));
+    (u128::from(amount) * u128::from(kept) / u128::from(BPS_DENOMINATOR)) as u64
+}
+
+/// Aligns a range outwards to the pool's tick spacing.
+fn align_range(tick_lower: i32, tick_upper: i32, tick_spacing: u16) -> Result<(i32, i32)> {
+    let spacing = i32::from(tick_spacing.max(1));
+    let lower = tick_lower.div_euclid(spacing) * spacing;
+    let upper = -((-tick_upper).div_euclid(spacing) * spacing);
+    if lower >= upper {
+        return Err(ExecutionError::InvalidRange(format!(
+            "lower tick {} must be below upper tick {}",
+            tick_lower, tick_upper
+        ))
+        .into());
+    }
+    Ok((lower, upper))
+}
+
+/// Plans and executes manual operations on Orca Whirlpool positions.
+pub struct PositionOperator {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Slippage tolerance in basis points.
+    slippage_bps: u16,
+    /// Oracle price guard for operations opening positions.
+    price_guard: Option<Arc<PriceGuard>>,
+}
+
+impl PositionOperator {
+    /// Creates an operator with a 0.5% slippage tolerance.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            provider,
+            slippage_bps: 50,
+            price_guard: None,
+        }
+    }
+
+    /// Sets the slippage tolerance in basis points.
+    #[must_use]
+    pub fn with_slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = slippage_bps.min(BPS_DENOMINATOR as u16);
+        self
+    }
+
+    /// Checks the pool price against an oracle before opening or rebalancing.
+    #[must_use]
+    pub fn with_price_guard(mut self, guard: Arc<PriceGuard>) -> Self {
+        self.price_guard = Some(guard);
+        self
+    }
+
+    /// Plans opening a position with up to the given token amounts.
+    ///
+    /// The range is widened to the pool's tick spacing.
+    pub async fn plan_open(
+        &self,
+        pool: &str,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<OperationPlan> {
+        let pool_address = Pubkey::from_str(pool).context("Invalid pool address")?;
+        let state = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(pool)
+            .await?;
+        let range = align_range(tick_lower, tick_upper, state.tick_spacing)?;
+
+        Ok(OperationPlan {
+            kind: OperationKind::Open,
+            pool: pool_address,
+            position: None,
+            pool_tick: state.tick_current,
+            current_range: None,
+            new_range: Some(range),
+            liquidity: 0,
+            withdraw_amounts: (0, 0),
+            withdraw_minimums: (0, 0),
+            fees_owed: (0, 0),
+            rewards: Vec::new(),
+            tick_spacing: state.tick_spacing,
+            deposit_amounts: (amount_a, amount_b),
+            slippage_bps: self.slippage_bps,
+            instructions: open_instructions(range, (amount_a, amount_b), self.slippage_bps),
+        })
+    }
+
+    /// Plans closing a position.
+    pub async fn plan_close(&self, position: &str) -> Result<OperationPlan> {
+        let (on_chain, state) = self.read_position(position).await?;
+        let withdraw = expected_amounts(
+            on_chain.liquidity,
+            state.tick_current,
+            on_chain.tick_lower,
+            on_chain.tick_upper,
+        );
+        let minimums = (
+            with_slippage(withdraw.0, self.slippage_bps),
+            with_slippage(withdraw.1, self.slippage_bps),
+        );
+
+        Ok(OperationPlan {
+            kind: OperationKind::Close,
+            pool: on_chain.pool,
+            position: Some(on_chain.address),
+            pool_tick: state.tick_current,
+            current_range: Some((on_chain.tick_lower, on_chain.tick_upper)),
+            new_range: None,
+            liquidity: on_chain.liquidity,
+            withdraw_amounts: withdraw,
+            withdraw_minimums: minimums,
+            fees_owed: (on_chain.fees_owed_a, on_chain.fees_owed_b),
+            rewards: Vec::new(),
+            tick_spacing: state.tick_spacing,
+            deposit_amounts: (0, 0),
+            slippage_bps: self.slippage_bps,
+            instructions: close_instructions(&on_chain, minimums),
+        })
+    }
+
+    /// Plans closing a position and reopening it in a new range.
+    ///
+    /// At most the minimum withdrawn amounts are deposited; whatever the new
+    /// range does not use stays in the wallet.
+    pub async fn plan_rebalance(
+        &self,
+        position: &str,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<OperationPlan> {
+        let mut plan = self.plan_close(position).await?;
+        let state = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(&plan.pool.to_string())
+            .await?;
+        let range = align_range(tick_lower, tick_upper, state.tick_spacing)?;
+        anyhow::ensure!(
+            plan.current_range != Some(range),
+            "Position already uses range [{}, {}]",
+            range.0,
+            range.1
+        );
+
+        plan.kind = OperationKind::Rebalance;
+        plan.new_range = Some(range);
+        plan.deposit_amounts = plan.withdraw_minimums;
+        plan.instructions.extend(open_instructions(
+            range,
+            plan.deposit_amounts,
+            self.slippage_bps,
+        ));
+        Ok(plan)
+    }
+
+    /// Plans collecting a position's fees and rewards.
+    pub async fn plan_collect(&self, position: &str) -> Result<OperationPlan> {
+        let account = PositionReader::new(self.provider.clone())
+            .get_position_account(position)
+            .await?;
+        let address = Pubkey::from_str(position).context("Invalid position address")?;
+        let whirlpool = WhirlpoolReader::new(self.provider.clone())
+            .get_whirlpool(&account.whirlpool.to_string())
+            .await?;
+        Ok(collect_plan(
+            account.to_on_chain(address, Pubkey::default()),
+            &account,
+            &whirlpool,
+        ))
+    }
+
+    /// Plans collecting the fees and rewards of every Orca position of a
+    /// wallet, skipping positions with nothing recorded as owed and no
+    /// liquidity to earn more.
+    pub async fn plan_collect_all(&self, owner: &str) -> Result<Vec<OperationPlan>> {
+        let positions = WalletPositionScanner::new(self.provider.clone())
+            .scan(owner)
+            .await?;
+
+        let mut plans = Vec::new();
+        for wallet_position in positions {
+            if wallet_position.protocol != Protocol::OrcaWhirlpool {
+                continue;
+            }
+            let plan = self
+                .plan_collect(&wallet_position.position.address.to_string())
+                .await?;
+            let owes = plan.fees_owed != (0, 0) || plan.rewards.iter().any(|r| r.amount_owed > 0);
+            if owes || plan.liquidity > 0 {
+                plans.push(plan);
+            }
+        }
+        Ok(plans)
+    }
+
+    /// Sends the transactions of a plan, signed by a wallet.
+    ///
+    /// A rebalance stops after the close if the close fails. With a price
+    /// guard, opens and rebalances are refused before anything is sent if the
+    /// pool price diverges from the oracle.
+    pub async fn execute(
+        &self,
+        plan: &OperationPlan,
+        wallet: &Wallet,
+    ) -> Result<Vec<ExecutionResult>> {
+        info!(
+            kind = plan.kind.as_str(),
+            pool = %plan.pool,
+            wallet = %wallet.pubkey(),
+            "Executing position operation"
+        );
+
+        if let Some(guard) = &self.price_guard
+            && plan.new_range.is_some()
+        {
+            let state = WhirlpoolReader::new(self.provider.clone())
+                .get_pool_state(&plan.pool.to_string())
+                .await?;
+            guard.check(&state).await?;
+        }
+
+        let executor = WhirlpoolExecutor::new(self.provider.clone());
+        let mut results = Vec::new();
+
+        if plan.kind == OperationKind::Collect {
+            let (position, (tick_lower, tick_upper)) = plan
+                .position
+                .zip(plan.current_range)
+                .context("Collect plan has no position")?;
+            let on_chain = OnChainPosition {
+                address: position,
+                pool: plan.pool,
+                owner: wallet.pubkey(),
+                tick_lower,
+                tick_upper,
+                liquidity: plan.liquidity,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: plan.fees_owed.0,
+                fees_owed_b: plan.fees_owed.1,
+            };
+            let rewards: Vec<(u8, Pubkey, Pubkey)> = plan
+                .rewards
+                .iter()
+                .map(|r| (r.index, r.mint, r.vault))
+                .collect();
+            results.push(
+                executor
+                    .collect_fees_and_rewards(&on_chain, plan.tick_spacing, &rewards, wallet)
+                    .await?,
+            );
+            return Ok(results);
+        }
+
+        if let Some(position) = plan.position {
+            let result = executor
+                .close_position_with_minimums(
+                    &position,
+                    &plan.pool,
+                    plan.withdraw_minimums.0,
+                    plan.withdraw_minimums.1,
+                    wallet,
+                )
+                .await?;
+            let failed = !result.success;
+            results.push(result);
+            if failed {
+                return Ok(results);
+            }
+        }
+
+        if let Some((tick_lower, tick_upper)) = plan.new_range {
+            let params = OpenPositionParams {
+                pool: plan.pool,
+                tick_lower,
+                tick_upper,
+                amount_a: plan.deposit_amounts.0,
+                amount_b: plan.deposit_amounts.1,
+                slippage_bps: plan.slippage_bps,
+            };
+            results.push(executor.open_position(&params, wallet).await?);
+        }
+
+        Ok(results)
+    }
+
+    /// Reads a position and the state of its pool.
+    async fn read_position(&self, position: &str) -> Result<(OnChainPosition, WhirlpoolState)> {
+        let on_chain = PositionReader::new(self.provider.clone())
+            .get_position(position)
+            .await?;
+        let state = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(&on_chain.pool.to_string())
+            .await?;
+        Ok((on_chain, state))
+    }
+}
+
+/// Lists the rewards of a position in the pool's initialized reward slots.
+pub(super) fn reward_claims(
+    account: &WhirlpoolPosition,
+    whirlpool: &Whirlpool,
+) -> Vec<RewardClaim> {
+    whirlpool
+        .reward_infos
+        .iter()
+        .zip(&account.reward_infos)
+        .enumerate()
+        .filter(|(_, (pool_reward, _))| pool_reward.is_initialized())
+        .map(|(index, (pool_reward, position_reward))| RewardClaim {
+            index: index as u8,
+            mint: pool_reward.mint,
+            vault: pool_reward.vault,
+            amount_owed: position_reward.amount_owed,
+        })
+        .collect()
+}
+
+/// Plans collecting the fees and initialized rewards of a position.
+fn collect_plan(
+    on_chain: OnChainPosition,
+    account: &WhirlpoolPosition,
+    whirlpool: &Whirlpool,
+) -> OperationPlan {
+    let rewards = reward_claims(account, whirlpool);
+
+    let address = on_chain.address.to_string();
+    let mut instructions = vec![
+        PlannedInstruction::whirlpool(
+            "update_fees_and_rewards",
+            serde_json::json!({ "position": address }),
+        ),
+        PlannedInstruction::whirlpool(
+            "collect_fees",
+            serde_json::json!({
+                "position": address,
+                "fees_owed_a": on_chain.fees_owed_a,
+                "fees_owed_b": on_chain.fees_owed_b,
+            }),
+        ),
+    ];
+    instructions.extend(rewards.iter().map(|reward| {
+        PlannedInstruction::whirlpool(
+            "collect_reward",
+            serde_json::json!({
+                "position": address,
+                "reward_index": reward.index,
+                "mint": reward.mint.to_string(),
+                "amount_owed": reward.amount_owed,
+            }),
+        )
+    }));
+
+    OperationPlan {
+        kind: OperationKind::Collect,
+        pool: on_chain.pool,
+        position: Some(on_chain.address),
+        pool_tick: whirlpool.tick_current_index,
+        current_range: Some((on_chain.tick_lower, on_chain.tick_upper)),
+        new_range: None,
+        liquidity: on_chain.liquidity,
+        withdraw_amounts: (0, 0),
+        withdraw_minimums: (0, 0),
+        fees_owed: (on_chain.fees_owed_a, on_chain.fees_owed_b),
+        rewards,
+        tick_spacing: whirlpool.tick_spacing,
+        deposit_amounts: (0, 0),
+        slippage_bps: 0,
+        instructions,
+    }
+}
+
+/// Plans the instructions opening a position.
+fn open_instructions(
+    range: (i32, i32),
+    amounts: (u64, u64),
+    slippage_bps: u16,
+) -> Vec<PlannedInstruction> {
+    vec![
+        PlannedInstruction::whirlpool(
+            "open_position",
+            serde_json::json!({ "tick_lower": range.0, "tick_upper": range.1 }),
+        ),
+        PlannedInstruction::whirlpool(
+            "increase_liquidity",
+            serde_json::json!({
+                "token_max_a": amounts.0,
+                "token_max_b": amounts.1,
+                "slippage_bps": slippage_bps,
+            }),
+        ),
+    ]
+}
+
+/// Plans the instructions closing a position.
+fn close_instructions(position: &OnChainPosition, minimums: (u64, u64)) -> Vec<PlannedInstruction> {
+    let address = position.address.to_string();
+    vec![
+        PlannedInstruction::whirlpool(
+            "decrease_liquidity",
+            serde_json::json!({
+                "position": address,
+                "liquidity": position.liquidity.to_string(),
+                "token_min_a": minimums.0,
+                "token_min_b": minimums.1,
+            }),
+        ),
+        PlannedInstruction::whirlpool(
+            "collect_fees",
+            serde_json::json!({
+                "position": address,
+                "fees_owed_a": position.fees_owed_a,
+                "fees_owed_b": position.fees_owed_b,
+            }),
+        ),
+        PlannedInstruction::whirlpool("close_position", serde_json::json!({ "position": address })),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_slippage() {
+        assert_eq!(with_slippage(10_000, 50), 9_950);
+        assert_eq!(with_slippage(u64::MAX, 0), u64::MAX);
+        assert_eq!(with_slippage(1_000, 10_000), 0);
+    }
+
+    #[test]
+    fn test_align_range() {
+        assert_eq!(align_range(-130, 130, 64).unwrap(), (-192, 192));
+        assert_eq!(align_range(-128, 128, 64).unwrap(), (-128, 128));
+        assert_eq!(align_range(5, 60, 64).unwrap(), (0, 64));
+        assert!(align_range(100, 100, 1).is_err());
+    }
+}
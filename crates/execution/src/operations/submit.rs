@@ -0,0 +1,162 @@
+//! Pricing, simulating and sending the transaction of an operation.
+
+use crate::error::ExecutionError;
+use crate::transaction::{
+    PriorityFeeEstimator, PriorityLevel, TransactionBuilder, TransactionManager,
+};
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clmm_lp_protocols::prelude::RpcProvider;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Compute unit limit used until simulation measures the actual usage.
+const DEFAULT_COMPUTE_UNITS: u32 = 400_000;
+
+/// Headroom added to the simulated compute units, in percent.
+const COMPUTE_UNIT_MARGIN_PCT: u64 = 20;
+
+/// Base fee per transaction signature, in lamports.
+const SIGNATURE_FEE_LAMPORTS: u64 = 5_000;
+
+/// A transaction confirmed on chain.
+#[derive(Debug, Clone)]
+pub(crate) struct Submitted {
+    /// Transaction signature.
+    pub signature: Signature,
+    /// Slot the transaction was confirmed in.
+    pub slot: u64,
+    /// Compute unit price paid, in micro-lamports.
+    pub priority_fee: u64,
+    /// Compute units used in simulation.
+    pub compute_units: u64,
+}
+
+/// Estimates the fee of a transaction in lamports from its signatures, its
+/// compute unit price in micro-lamports and the compute units it used.
+pub(crate) fn transaction_fee(signatures: u64, priority_fee: u64, compute_units: u64) -> u64 {
+    let priority = u128::from(priority_fee) * u128::from(compute_units) / 1_000_000;
+    signatures * SIGNATURE_FEE_LAMPORTS + priority as u64
+}
+
+/// Sends operation transactions with an estimated priority fee, after a
+/// simulation that also sizes their compute unit limit.
+pub(crate) struct Submitter {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Transaction manager for simulating, sending and confirming.
+    transactions: Arc<TransactionManager>,
+    /// Priority fee estimator.
+    fees: PriorityFeeEstimator,
+    /// Priority of the transactions.
+    pub priority: PriorityLevel,
+}
+
+impl Submitter {
+    /// Creates a submitter paying a medium priority fee.
+    pub fn new(provider: Arc<RpcProvider>, transactions: Arc<TransactionManager>) -> Self {
+        Self {
+            fees: PriorityFeeEstimator::new(provider.clone()),
+            provider,
+            transactions,
+            priority: PriorityLevel::Medium,
+        }
+    }
+
+    /// Sends instructions in one transaction paid and signed by a wallet,
+    /// and co-signed by `extra_signer` if given.
+    ///
+    /// The priority fee is estimated from recent fees paid to write
+    /// `fee_accounts`.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::SimulationFailed`] without sending if the
+    /// transaction fails in simulation.
+    pub async fn submit(
+        &self,
+        operation: &str,
+        instructions: Vec<Instruction>,
+        wallet: &Wallet,
+        extra_signer: Option<&Keypair>,
+        fee_accounts: &[Pubkey],
+    ) -> Result<Submitted> {
+        let priority_fee = self
+            .fees
+            .estimate(fee_accounts, self.priority)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Priority fee estimation failed, paying no priority fee");
+                0
+            });
+        let blockhash = self.provider.get_latest_blockhash().await?;
+        let build = |compute_units: u32| -> Result<Transaction> {
+            let builder = TransactionBuilder::new()
+                .add_instructions(instructions.clone())
+                .with_compute_units(compute_units)
+                .with_compute_unit_price(priority_fee)
+                .with_priority(self.priority)
+                .with_fee_payer(wallet.pubkey())
+                .with_blockhash(blockhash);
+            match extra_signer {
+                Some(extra) => builder.build(&[wallet as &dyn Signer, extra]),
+                None => builder.build(&[wallet]),
+            }
+        };
+
+        let simulation = self
+            .transactions
+            .simulate(&build(DEFAULT_COMPUTE_UNITS)?)
+            .await?;
+        if !simulation.success {
+            warn!(operation, logs = ?simulation.logs, "Simulation failed");
+            return Err(ExecutionError::SimulationFailed(
+                simulation
+                    .error
+                    .unwrap_or_else(|| "unknown error".to_string()),
+            )
+            .into());
+        }
+
+        let transaction = build(compute_limit(simulation.compute_units))?;
+        let result = self.transactions.send_and_confirm(&transaction).await?;
+
+        Ok(Submitted {
+            signature: result.signature,
+            slot: result.slot,
+            priority_fee,
+            compute_units: simulation.compute_units,
+        })
+    }
+}
+
+/// Returns the compute unit limit for a simulated usage.
+fn compute_limit(simulated: u64) -> u32 {
+    if simulated == 0 {
+        return DEFAULT_COMPUTE_UNITS;
+    }
+    let limit = simulated * (100 + COMPUTE_UNIT_MARGIN_PCT) / 100;
+    u32::try_from(limit).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_limit() {
+        assert_eq!(compute_limit(0), DEFAULT_COMPUTE_UNITS);
+        assert_eq!(compute_limit(100_000), 120_000);
+    }
+
+    #[test]
+    fn test_transaction_fee() {
+        assert_eq!(transaction_fee(1, 0, 200_000), 5_000);
+        // 10,000 micro-lamports per unit over 200,000 units is 2,000 lamports
+        assert_eq!(transaction_fee(2, 10_000, 200_000), 12_000);
+    }
+}
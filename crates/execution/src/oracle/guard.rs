@@ -0,0 +1,357 @@
+//! Guard against acting on pool prices that diverge from an oracle.
+
+use super::{OraclePrice, PriceOracle};
+use crate::error::ExecutionError;
+use chrono::{DateTime, Utc};
+use clmm_lp_config::prelude::ExecutionSettings;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+/// Offset of the decimals byte in an SPL mint account.
+pub(crate) const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Basis points in one.
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Configuration for the oracle price guard.
+#[derive(Debug, Clone)]
+pub struct PriceGuardConfig {
+    /// Maximum divergence between pool and oracle price in basis points.
+    pub max_divergence_bps: u32,
+    /// Maximum age of an oracle price in seconds.
+    pub max_staleness_secs: u64,
+    /// Maximum oracle confidence interval relative to price, in basis points.
+    pub max_confidence_bps: u32,
+    /// Whether to block pools whose tokens have no oracle feed.
+    pub require_feed: bool,
+}
+
+impl Default for PriceGuardConfig {
+    fn default() -> Self {
+        Self {
+            max_divergence_bps: 200, // 2%
+            max_staleness_secs: 60,
+            max_confidence_bps: 100, // 1%
+            require_feed: false,
+        }
+    }
+}
+
+impl PriceGuardConfig {
+    /// Creates a configuration from the shared execution settings.
+    #[must_use]
+    pub fn from_settings(settings: &ExecutionSettings) -> Self {
+        Self {
+            max_divergence_bps: settings.max_oracle_divergence_bps,
+            max_staleness_secs: settings.max_oracle_staleness_secs,
+            ..Default::default()
+        }
+    }
+}
+
+/// A pool price that passed the oracle check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DivergenceCheck {
+    /// Pool price of token A in token B.
+    pub pool_price: Decimal,
+    /// Oracle price of token A in token B.
+    pub oracle_price: Decimal,
+    /// Divergence between the two in basis points.
+    pub divergence_bps: u32,
+}
+
+/// Compares pool prices with oracle prices before liquidity is deployed.
+///
+/// Rebalances and opens are aborted when a pool's instantaneous price strays
+/// too far from the oracle, as it does when the pool is being manipulated or
+/// one of its tokens has depegged.
+pub struct PriceGuard {
+    /// RPC provider for reading mint decimals.
+    provider: Arc<RpcProvider>,
+    /// Oracle of USD token prices.
+    oracle: Arc<dyn PriceOracle>,
+    /// Configuration.
+    config: PriceGuardConfig,
+    /// Decimals by mint, read once.
+    decimals: RwLock<HashMap<Pubkey, u8>>,
+}
+
+impl PriceGuard {
+    /// Creates a guard checking prices against an oracle.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        oracle: Arc<dyn PriceOracle>,
+        config: PriceGuardConfig,
+    ) -> Self {
+        Self {
+            provider,
+            oracle,
+            config,
+            decimals: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the configuration.
+    pub fn config(&self) -> &PriceGuardConfig {
+        &self.config
+    }
+
+    /// Checks a pool's price against the oracle.
+    ///
+    /// Returns `None` without checking if the oracle has no feed for one of
+    /// the pool's tokens and feeds are not required.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::PriceDivergence`] if the prices diverge by
+    /// more than allowed, or [`ExecutionError::OracleUnavailable`] if the
+    /// oracle cannot be read or its price is stale or too uncertain.
+    pub async fn check(&self, pool: &WhirlpoolState) -> anyhow::Result<Option<DivergenceCheck>> {
+        self.check_price(
+            &pool.address,
+            (&pool.token_mint_a, &pool.token_mint_b),
+            pool.price,
+        )
+        .await
+    }
+
+    /// Checks the price of a pool of any protocol against the oracle.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`PriceGuard::check`].
+    pub async fn check_pool(
+        &self,
+        pool: &ProtocolPoolState,
+    ) -> anyhow::Result<Option<DivergenceCheck>> {
+        self.check_price(
+            &pool.address.to_string(),
+            (&pool.token_mint_a, &pool.token_mint_b),
+            pool.price,
+        )
+        .await
+    }
+
+    /// Compares a raw pool price with the oracle prices of its tokens.
+    async fn check_price(
+        &self,
+        pool: &str,
+        (mint_a, mint_b): (&Pubkey, &Pubkey),
+        raw_price: Decimal,
+    ) -> anyhow::Result<Option<DivergenceCheck>> {
+        let base = self.usd_price(mint_a).await?;
+        let quote = self.usd_price(mint_b).await?;
+        let (Some(base), Some(quote)) = (base, quote) else {
+            if self.config.require_feed {
+                return Err(ExecutionError::OracleUnavailable(format!(
+                    "{} has no feed for a token of pool {}",
+                    self.oracle.name(),
+                    pool
+                ))
+                .into());
+            }
+            debug!(pool = %pool, "No oracle feed for pool tokens, skipping price check");
+            return Ok(None);
+        };
+
+        let decimals = (
+            self.mint_decimals(mint_a).await?,
+            self.mint_decimals(mint_b).await?,
+        );
+        let check = compare_prices(
+            pool,
+            raw_price,
+            decimals,
+            &base,
+            &quote,
+            &self.config,
+            Utc::now(),
+        )?;
+        debug!(
+            pool = %pool,
+            pool_price = %check.pool_price,
+            oracle_price = %check.oracle_price,
+            divergence_bps = check.divergence_bps,
+            "Pool price matches oracle"
+        );
+        Ok(Some(check))
+    }
+
+    /// Gets a token's USD price, reporting oracle failures as unavailable.
+    async fn usd_price(&self, mint: &Pubkey) -> anyhow::Result<Option<OraclePrice>> {
+        self.oracle.usd_price(mint).await.map_err(|e| {
+            ExecutionError::OracleUnavailable(format!(
+                "{} failed for {}: {}",
+                self.oracle.name(),
+                mint,
+                e
+            ))
+            .into()
+        })
+    }
+
+    /// Gets the decimals of a mint, reading the mint account on first use.
+    async fn mint_decimals(&self, mint: &Pubkey) -> anyhow::Result<u8> {
+        if let Some(decimals) = self.decimals.read().await.get(mint) {
+            return Ok(*decimals);
+        }
+
+        let account = self.provider.get_account(mint).await?;
+        let decimals = *account
+            .data
+            .get(MINT_DECIMALS_OFFSET)
+            .ok_or_else(|| anyhow::anyhow!("Account {} is not a token mint", mint))?;
+        self.decimals.write().await.insert(*mint, decimals);
+        Ok(decimals)
+    }
+}
+
+/// Compares a raw pool price (base units of B per base unit of A) with the
+/// oracle's USD prices of both tokens.
+fn compare_prices(
+    pool: &str,
+    raw_price: Decimal,
+    (decimals_a, decimals_b): (u8, u8),
+    base: &OraclePrice,
+    quote: &OraclePrice,
+    config: &PriceGuardConfig,
+    now: DateTime<Utc>,
+) -> Result<DivergenceCheck, ExecutionError> {
+    for (token, price) in [("token A", base), ("token B", quote)] {
+        let age = price.age_secs(now);
+        if age > config.max_staleness_secs as i64 {
+            return Err(ExecutionError::OracleUnavailable(format!(
+                "{} price of pool {} is {}s old",
+                token, pool, age
+            )));
+        }
+        match price.confidence_bps() {
+            Some(bps) if bps <= config.max_confidence_bps => {}
+            Some(bps) => {
+                return Err(ExecutionError::OracleUnavailable(format!(
+                    "{} price of pool {} is uncertain by {} bps",
+                    token, pool, bps
+                )));
+            }
+            None => {
+                return Err(ExecutionError::OracleUnavailable(format!(
+                    "{} price of pool {} is not positive",
+                    token, pool
+                )));
+            }
+        }
+    }
+
+    let pool_price = adjust_for_decimals(raw_price, decimals_a, decimals_b);
+    let oracle_price = base.price / quote.price;
+    let divergence_bps = ((pool_price - oracle_price).abs() / oracle_price
+        * Decimal::from(BPS_DENOMINATOR))
+    .round()
+    .to_u32()
+    .unwrap_or(u32::MAX);
+
+    if divergence_bps > config.max_divergence_bps {
+        return Err(ExecutionError::PriceDivergence {
+            pool: pool.to_string(),
+            pool_price: pool_price.round_dp(6),
+            oracle_price: oracle_price.round_dp(6),
+            divergence_bps,
+            max_bps: config.max_divergence_bps,
+        });
+    }
+
+    Ok(DivergenceCheck {
+        pool_price,
+        oracle_price,
+        divergence_bps,
+    })
+}
+
+/// Converts a price in base units to a price in whole tokens.
+pub(crate) fn adjust_for_decimals(raw_price: Decimal, decimals_a: u8, decimals_b: u8) -> Decimal {
+    let factor = Decimal::from(10u64.pow(u32::from(decimals_a.abs_diff(decimals_b))));
+    if decimals_a >= decimals_b {
+        raw_price * factor
+    } else {
+        raw_price / factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn price(usd: Decimal, published_at: DateTime<Utc>) -> OraclePrice {
+        OraclePrice {
+            price: usd,
+            confidence: usd / dec!(1000),
+            published_at,
+        }
+    }
+
+    #[test]
+    fn test_adjust_for_decimals() {
+        // SOL (9 decimals) in USDC (6 decimals) at $150
+        assert_eq!(adjust_for_decimals(dec!(0.15), 9, 6), dec!(150));
+        assert_eq!(adjust_for_decimals(dec!(150000), 6, 9), dec!(150));
+        assert_eq!(adjust_for_decimals(dec!(1.5), 6, 6), dec!(1.5));
+    }
+
+    #[test]
+    fn test_compare_prices() {
+        let now = Utc::now();
+        let config = PriceGuardConfig::default();
+        let sol = price(dec!(150), now);
+        let usdc = price(dec!(1), now);
+
+        let check =
+            compare_prices("pool", dec!(0.1512), (9, 6), &sol, &usdc, &config, now).unwrap();
+        assert_eq!(check.pool_price, dec!(151.2));
+        assert_eq!(check.oracle_price, dec!(150));
+        assert_eq!(check.divergence_bps, 80);
+
+        // Pool pushed 5% above the oracle
+        assert!(matches!(
+            compare_prices("pool", dec!(0.1575), (9, 6), &sol, &usdc, &config, now),
+            Err(ExecutionError::PriceDivergence {
+                divergence_bps: 500,
+                max_bps: 200,
+                ..
+            })
+        ));
+
+        // USDC depegged to $0.95 while the pool still prices it at $1
+        let depegged = price(dec!(0.95), now);
+        assert!(matches!(
+            compare_prices("pool", dec!(0.15), (9, 6), &sol, &depegged, &config, now),
+            Err(ExecutionError::PriceDivergence { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unusable_oracle_prices() {
+        let now = Utc::now();
+        let config = PriceGuardConfig::default();
+        let usdc = price(dec!(1), now);
+
+        let stale = price(dec!(150), now - chrono::Duration::seconds(120));
+        assert!(matches!(
+            compare_prices("pool", dec!(0.15), (9, 6), &stale, &usdc, &config, now),
+            Err(ExecutionError::OracleUnavailable(_))
+        ));
+
+        let uncertain = OraclePrice {
+            confidence: dec!(3),
+            ..price(dec!(150), now)
+        };
+        assert!(matches!(
+            compare_prices("pool", dec!(0.15), (9, 6), &uncertain, &usdc, &config, now),
+            Err(ExecutionError::OracleUnavailable(_))
+        ));
+    }
+}
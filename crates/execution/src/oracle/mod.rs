@@ -0,0 +1,13 @@
+//! Oracle price checks before execution.
+//!
+//! Provides protection against acting on manipulated or depegged pools:
+//! - USD prices from Pyth feeds
+//! - Guard comparing a pool's price with the oracle price
+
+mod guard;
+mod pyth;
+mod source;
+
+pub use guard::*;
+pub use pyth::*;
+pub use source::*;
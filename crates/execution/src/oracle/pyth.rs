@@ -0,0 +1,168 @@
+//! Pyth price feeds read from the Hermes API.
+
+use super::{OraclePrice, PriceOracle};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Default Pyth Hermes API URL.
+pub const PYTH_HERMES_URL: &str = "https://hermes.pyth.network";
+
+/// Pyth USD feed IDs of well-known tokens by mint.
+const KNOWN_FEEDS: [(&str, &str); 3] = [
+    // SOL
+    (
+        "So11111111111111111111111111111111111111112",
+        "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+    ),
+    // USDC
+    (
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "eaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a",
+    ),
+    // USDT
+    (
+        "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+        "2b89b9dc8fdf9f34709a5b106b472f0f39bb6ca9ce04b0fd7f2e971688e2e53b",
+    ),
+];
+
+/// USD prices from Pyth, with feeds for SOL, USDC and USDT built in.
+pub struct PythOracle {
+    /// HTTP client.
+    client: reqwest::Client,
+    /// Hermes API URL.
+    url: String,
+    /// Feed IDs by token mint, as lowercase hex without a `0x` prefix.
+    feeds: HashMap<Pubkey, String>,
+}
+
+impl Default for PythOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PythOracle {
+    /// Creates an oracle reading the public Hermes API.
+    #[must_use]
+    pub fn new() -> Self {
+        let feeds = KNOWN_FEEDS
+            .iter()
+            .filter_map(|(mint, feed)| Some((Pubkey::from_str(mint).ok()?, feed.to_string())))
+            .collect();
+        Self {
+            client: reqwest::Client::new(),
+            url: PYTH_HERMES_URL.to_string(),
+            feeds,
+        }
+    }
+
+    /// Sets the Hermes API URL.
+    #[must_use]
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Adds or replaces the USD feed of a token.
+    #[must_use]
+    pub fn with_feed(mut self, mint: Pubkey, feed_id: &str) -> Self {
+        self.feeds.insert(mint, normalize_feed_id(feed_id));
+        self
+    }
+}
+
+/// Lowercases a feed ID and strips its `0x` prefix.
+fn normalize_feed_id(feed_id: &str) -> String {
+    feed_id.trim_start_matches("0x").to_ascii_lowercase()
+}
+
+/// Reads a Hermes price object (`price`, `conf`, `expo`, `publish_time`).
+fn parse_price(value: &serde_json::Value) -> Option<OraclePrice> {
+    let price: i64 = value["price"].as_str()?.parse().ok()?;
+    let confidence: u64 = value["conf"].as_str()?.parse().ok()?;
+    let expo = i32::try_from(value["expo"].as_i64()?).ok()?;
+    let published_at = DateTime::<Utc>::from_timestamp(value["publish_time"].as_i64()?, 0)?;
+
+    let scale = |mantissa: i128| -> Option<Decimal> {
+        if expo <= 0 {
+            Decimal::try_from_i128_with_scale(mantissa, expo.unsigned_abs()).ok()
+        } else {
+            Decimal::from_i128_with_scale(mantissa, 0)
+                .checked_mul(Decimal::from(10u64.checked_pow(expo.unsigned_abs())?))
+        }
+    };
+
+    Some(OraclePrice {
+        price: scale(i128::from(price))?,
+        confidence: scale(i128::from(confidence))?,
+        published_at,
+    })
+}
+
+#[async_trait]
+impl PriceOracle for PythOracle {
+    fn name(&self) -> &str {
+        "pyth"
+    }
+
+    async fn usd_price(&self, mint: &Pubkey) -> anyhow::Result<Option<OraclePrice>> {
+        let Some(feed) = self.feeds.get(mint) else {
+            return Ok(None);
+        };
+
+        let url = format!("{}/v2/updates/price/latest?ids[]={}", self.url, feed);
+        let response = self.client.get(&url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Pyth Hermes returned {} for feed {}", status, feed);
+        }
+        let body: serde_json::Value = response.json().await?;
+
+        let price = body["parsed"]
+            .as_array()
+            .and_then(|updates| {
+                updates.iter().find(|update| {
+                    update["id"].as_str().map(normalize_feed_id).as_ref() == Some(feed)
+                })
+            })
+            .and_then(|update| parse_price(&update["price"]))
+            .ok_or_else(|| anyhow::anyhow!("Pyth Hermes returned no price for feed {}", feed))?;
+        Ok(Some(price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_price() {
+        let price = parse_price(&json!({
+            "price": "14523100000",
+            "conf": "7250000",
+            "expo": -8,
+            "publish_time": 1_700_000_000
+        }))
+        .unwrap();
+
+        assert_eq!(price.price, Decimal::new(1_452_310, 4));
+        assert_eq!(price.confidence, Decimal::new(725, 4));
+        assert_eq!(price.published_at.timestamp(), 1_700_000_000);
+        assert_eq!(price.confidence_bps(), Some(5));
+
+        assert!(parse_price(&json!({ "price": 1, "conf": "1", "expo": -8 })).is_none());
+    }
+
+    #[test]
+    fn test_known_feeds() {
+        let oracle = PythOracle::new().with_feed(Pubkey::new_unique(), "0xABCD");
+        assert_eq!(oracle.feeds.len(), KNOWN_FEEDS.len() + 1);
+        assert!(oracle.feeds.values().any(|feed| feed == "abcd"));
+    }
+}
@@ -0,0 +1,51 @@
+//! Oracle price sources.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use solana_sdk::pubkey::Pubkey;
+
+/// A USD price reported by an oracle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OraclePrice {
+    /// Price in USD.
+    pub price: Decimal,
+    /// Confidence interval around the price in USD.
+    pub confidence: Decimal,
+    /// When the price was published.
+    pub published_at: DateTime<Utc>,
+}
+
+impl OraclePrice {
+    /// Gets the confidence interval relative to the price, in basis points.
+    ///
+    /// Returns `None` for non-positive prices.
+    #[must_use]
+    pub fn confidence_bps(&self) -> Option<u32> {
+        if self.price <= Decimal::ZERO {
+            return None;
+        }
+        (self.confidence.abs() / self.price * Decimal::from(10_000))
+            .round()
+            .to_u32()
+    }
+
+    /// Gets the age of the price in seconds at `now`.
+    #[must_use]
+    pub fn age_secs(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.published_at).num_seconds()
+    }
+}
+
+/// A source of token prices.
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+    /// Returns the oracle name.
+    fn name(&self) -> &str;
+
+    /// Gets the USD price of a token.
+    ///
+    /// Returns `None` if the oracle has no feed for the mint.
+    async fn usd_price(&self, mint: &Pubkey) -> anyhow::Result<Option<OraclePrice>>;
+}
@@ -0,0 +1,381 @@
+//! Paper trading execution backend.
+
+use super::{PaperFill, PaperLedger, PaperPnL};
+use crate::lifecycle::{
+    CloseReason, FeesCollectedData, LifecycleTracker, PositionClosedData, PositionOpenedData,
+    RebalanceData,
+};
+use crate::strategy::{DecisionContext, ExecutionBackend, RebalanceParams, RebalanceResult};
+use async_trait::async_trait;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Configuration for paper trading.
+#[derive(Debug, Clone)]
+pub struct PaperTradingConfig {
+    /// Simulated slippage in basis points, applied to the value moved by a fill.
+    pub slippage_bps: u16,
+    /// Simulated cost per transaction in USD.
+    pub tx_cost_usd: Decimal,
+    /// Path of the JSON ledger file (in-memory only if unset).
+    pub ledger_path: Option<PathBuf>,
+}
+
+impl Default for PaperTradingConfig {
+    fn default() -> Self {
+        Self {
+            slippage_bps: 10,                // 0.1%
+            tx_cost_usd: Decimal::new(5, 2), // $0.05
+            ledger_path: None,
+        }
+    }
+}
+
+/// Execution backend that fills against live pool quotes without sending transactions.
+///
+/// Each monitored position is shadowed by a paper position seeded from its
+/// current value. Fills are persisted to the ledger file after every change.
+pub struct PaperExecutor {
+    /// Pool reader for live quotes.
+    pool_reader: WhirlpoolReader,
+    /// Lifecycle tracker.
+    lifecycle: Arc<LifecycleTracker>,
+    /// Ledger of fills and positions.
+    ledger: Arc<RwLock<PaperLedger>>,
+    /// Configuration.
+    config: PaperTradingConfig,
+}
+
+impl PaperExecutor {
+    /// Creates a new paper executor, loading an existing ledger if configured.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        lifecycle: Arc<LifecycleTracker>,
+        config: PaperTradingConfig,
+    ) -> anyhow::Result<Self> {
+        let ledger = match &config.ledger_path {
+            Some(path) => PaperLedger::load(path)?,
+            None => PaperLedger::new(),
+        };
+
+        info!(
+            fills = ledger.fills.len(),
+            positions = ledger.positions.len(),
+            "Paper trading ledger loaded"
+        );
+
+        Ok(Self {
+            pool_reader: WhirlpoolReader::new(provider),
+            lifecycle,
+            ledger: Arc::new(RwLock::new(ledger)),
+            config,
+        })
+    }
+
+    /// Gets a snapshot of the ledger.
+    pub async fn ledger(&self) -> PaperLedger {
+        self.ledger.read().await.clone()
+    }
+
+    /// Gets aggregate paper PnL.
+    pub async fn pnl(&self) -> PaperPnL {
+        self.ledger.read().await.pnl()
+    }
+
+    /// Gets the fill history.
+    pub async fn fills(&self) -> Vec<PaperFill> {
+        self.ledger.read().await.fills.clone()
+    }
+
+    /// Fetches the live tick for a pool.
+    async fn quote(&self, ctx: &DecisionContext) -> anyhow::Result<i32> {
+        let state = self
+            .pool_reader
+            .get_pool_state(&ctx.position.pool.to_string())
+            .await?;
+        Ok(state.tick_current)
+    }
+
+    /// Simulated slippage for moving the given value.
+    fn slippage(&self, value_usd: Decimal) -> Decimal {
+        value_usd.abs() * Decimal::from(self.config.slippage_bps) / Decimal::from(10_000)
+    }
+
+    /// Simulated cost of the given number of transactions.
+    fn tx_cost(&self, tx_count: u32) -> Decimal {
+        self.config.tx_cost_usd * Decimal::from(tx_count)
+    }
+
+    /// Seeds a paper position from a monitored position if not yet tracked.
+    async fn ensure_position(&self, ledger: &mut PaperLedger, ctx: &DecisionContext, tick: i32) {
+        let key = ctx.position.address.to_string();
+        if ledger.get(&key).is_some() {
+            return;
+        }
+
+        let position = &ctx.position;
+        ledger.open(
+            &key,
+            &position.pool.to_string(),
+            tick,
+            position.on_chain.tick_lower,
+            position.on_chain.tick_upper,
+            position.pnl.current_value_usd,
+            position.pnl.fees_usd,
+        );
+
+        if self
+            .lifecycle
+            .get_summary(&position.address)
+            .await
+            .is_some()
+        {
+            return;
+        }
+
+        self.lifecycle
+            .record_position_opened(
+                position.address,
+                position.pool,
+                PositionOpenedData {
+                    tick_lower: position.on_chain.tick_lower,
+                    tick_upper: position.on_chain.tick_upper,
+                    liquidity: position.on_chain.liquidity,
+                    amount_a: 0,
+                    amount_b: 0,
+                    entry_price: tick_to_price(tick),
+                    entry_value_usd: position.pnl.current_value_usd,
+                },
+            )
+            .await;
+
+        info!(
+            position = %position.address,
+            value_usd = %position.pnl.current_value_usd,
+            "Paper position opened"
+        );
+    }
+
+    /// Persists the ledger if a path is configured.
+    fn persist(&self, ledger: &PaperLedger) -> anyhow::Result<()> {
+        if let Some(path) = &self.config.ledger_path {
+            ledger.save(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ExecutionBackend for PaperExecutor {
+    fn name(&self) -> &str {
+        "paper"
+    }
+
+    async fn rebalance(&self, ctx: &DecisionContext, params: RebalanceParams) -> RebalanceResult {
+        let mut result = RebalanceResult {
+            success: false,
+            old_position: params.position,
+            new_position: None,
+            fees_collected: None,
+            liquidity_removed: 0,
+            old_position_closed: false,
+            liquidity_added: 0,
+            swap: None,
+            tx_cost_lamports: 0,
+            skipped: false,
+            error: None,
+        };
+
+        let tick = match self.quote(ctx).await {
+            Ok(tick) => tick,
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch live quote for paper rebalance");
+                result.error = Some(e.to_string());
+                return result;
+            }
+        };
+
+        let mut ledger = self.ledger.write().await;
+        self.ensure_position(&mut ledger, ctx, tick).await;
+
+        let key = params.position.to_string();
+        let value = ledger.mark(&key, tick).unwrap_or(Decimal::ZERO);
+        // Decrease, close, open and increase
+        let cost = self.slippage(value) + self.tx_cost(4);
+
+        let fill = match ledger.rebalance(
+            &key,
+            tick,
+            params.new_tick_lower,
+            params.new_tick_upper,
+            cost,
+        ) {
+            Ok(fill) => fill.clone(),
+            Err(e) => {
+                result.error = Some(e.to_string());
+                return result;
+            }
+        };
+
+        if let Err(e) = self.persist(&ledger) {
+            warn!(error = %e, "Failed to persist paper ledger");
+        }
+        drop(ledger);
+
+        self.lifecycle
+            .record_rebalance(
+                params.position,
+                params.pool,
+                RebalanceData {
+                    old_tick_lower: params.current_tick_lower,
+                    old_tick_upper: params.current_tick_upper,
+                    new_tick_lower: params.new_tick_lower,
+                    new_tick_upper: params.new_tick_upper,
+                    old_liquidity: params.current_liquidity,
+                    new_liquidity: params.current_liquidity,
+                    tx_cost_lamports: 0,
+                    il_at_rebalance: params.current_il_pct,
+                    reason: params.reason,
+                },
+            )
+            .await;
+
+        info!(
+            position = %params.position,
+            tick = tick,
+            value_usd = %fill.value_usd,
+            cost_usd = %fill.cost_usd,
+            "Paper rebalance filled"
+        );
+
+        result.success = true;
+        result.liquidity_removed = params.current_liquidity;
+        result.liquidity_added = params.current_liquidity;
+        result
+    }
+
+    async fn close_position(&self, ctx: &DecisionContext) -> anyhow::Result<()> {
+        let tick = self.quote(ctx).await?;
+        let mut ledger = self.ledger.write().await;
+        self.ensure_position(&mut ledger, ctx, tick).await;
+
+        let key = ctx.position.address.to_string();
+        let value = ledger.mark(&key, tick).unwrap_or(Decimal::ZERO);
+        // Collect, decrease and close
+        let cost = self.slippage(value) + self.tx_cost(3);
+        let fill = ledger.close(&key, tick, cost)?.clone();
+        self.persist(&ledger)?;
+
+        let closed = ledger.get(&key).cloned();
+        drop(ledger);
+
+        if let Some(closed) = closed {
+            let duration = chrono::Utc::now() - closed.opened_at;
+            self.lifecycle
+                .record_position_closed(
+                    ctx.position.address,
+                    ctx.position.pool,
+                    PositionClosedData {
+                        liquidity_removed: ctx.position.on_chain.liquidity,
+                        amount_a: 0,
+                        amount_b: 0,
+                        total_fees_a: 0,
+                        total_fees_b: 0,
+                        final_pnl_usd: closed.net_pnl_usd(),
+                        final_pnl_pct: closed.net_pnl_pct(),
+                        total_il_pct: ctx.position.pnl.il_pct,
+                        duration_hours: duration.num_hours().max(0) as u64,
+                        reason: CloseReason::StrategyEnded,
+                    },
+                )
+                .await;
+        }
+
+        info!(
+            position = %ctx.position.address,
+            proceeds_usd = %fill.cash_delta_usd,
+            "Paper close filled"
+        );
+        Ok(())
+    }
+
+    async fn swap_to_quote(&self, ctx: &DecisionContext) -> anyhow::Result<()> {
+        let tick = self.quote(ctx).await?;
+        let mut ledger = self.ledger.write().await;
+
+        let key = ctx.position.address.to_string();
+        let proceeds = ledger
+            .get(&key)
+            .map(|p| p.cash_usd)
+            .unwrap_or(Decimal::ZERO);
+        let cost = self.slippage(proceeds) + self.tx_cost(1);
+        let fill = ledger.swap_to_quote(&key, tick, cost)?.clone();
+        self.persist(&ledger)?;
+
+        info!(
+            position = %ctx.position.address,
+            cost_usd = %fill.cost_usd,
+            "Paper swap to quote filled"
+        );
+        Ok(())
+    }
+
+    async fn collect_fees(&self, ctx: &DecisionContext) -> anyhow::Result<()> {
+        let tick = self.quote(ctx).await?;
+        let mut ledger = self.ledger.write().await;
+        self.ensure_position(&mut ledger, ctx, tick).await;
+
+        let key = ctx.position.address.to_string();
+        let fill = ledger
+            .collect_fees(&key, tick, ctx.position.pnl.fees_usd, self.tx_cost(1))?
+            .clone();
+        self.persist(&ledger)?;
+        drop(ledger);
+
+        self.lifecycle
+            .record_fees_collected(
+                ctx.position.address,
+                ctx.position.pool,
+                FeesCollectedData {
+                    fees_a: 0,
+                    fees_b: 0,
+                    fees_usd: fill.cash_delta_usd + fill.cost_usd,
+                },
+            )
+            .await;
+
+        info!(
+            position = %ctx.position.address,
+            net_usd = %fill.cash_delta_usd,
+            "Paper fee collection filled"
+        );
+        Ok(())
+    }
+
+    async fn adjust_liquidity(
+        &self,
+        ctx: &DecisionContext,
+        delta_usd: Decimal,
+    ) -> anyhow::Result<()> {
+        let tick = self.quote(ctx).await?;
+        let mut ledger = self.ledger.write().await;
+        self.ensure_position(&mut ledger, ctx, tick).await;
+
+        let key = ctx.position.address.to_string();
+        let cost = self.slippage(delta_usd) + self.tx_cost(1);
+        let fill = ledger.adjust(&key, tick, delta_usd, cost)?.clone();
+        self.persist(&ledger)?;
+
+        info!(
+            position = %ctx.position.address,
+            delta_usd = %delta_usd,
+            value_usd = %fill.value_usd,
+            "Paper liquidity adjustment filled"
+        );
+        Ok(())
+    }
+}
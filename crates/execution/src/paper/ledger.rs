@@ -0,0 +1,524 @@
+//! Paper trading ledger of simulated fills and positions.
+
+use crate::error::ExecutionError;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Kind of simulated fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillKind {
+    /// Position opened (or first observed) in paper mode.
+    Open,
+    /// Position moved into a new range.
+    Rebalance,
+    /// Liquidity added.
+    IncreaseLiquidity,
+    /// Liquidity removed.
+    DecreaseLiquidity,
+    /// Fees collected.
+    CollectFees,
+    /// Position closed.
+    Close,
+    /// Close proceeds swapped to the quote token.
+    SwapToQuote,
+}
+
+/// A simulated fill against a live pool quote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperFill {
+    /// Fill ID.
+    pub id: String,
+    /// Position address.
+    pub position: String,
+    /// Pool address.
+    pub pool: String,
+    /// Fill kind.
+    pub kind: FillKind,
+    /// Pool tick at fill time.
+    pub tick: i32,
+    /// Pool price at fill time.
+    pub price: Decimal,
+    /// Lower tick after the fill.
+    pub tick_lower: i32,
+    /// Upper tick after the fill.
+    pub tick_upper: i32,
+    /// Position value after the fill in USD.
+    pub value_usd: Decimal,
+    /// Cash moved by the fill in USD (positive into the ledger).
+    pub cash_delta_usd: Decimal,
+    /// Simulated slippage and transaction costs in USD.
+    pub cost_usd: Decimal,
+    /// Timestamp.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// A simulated liquidity position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperPosition {
+    /// Position address.
+    pub position: String,
+    /// Pool address.
+    pub pool: String,
+    /// Lower tick.
+    pub tick_lower: i32,
+    /// Upper tick.
+    pub tick_upper: i32,
+    /// Virtual liquidity used to mark the position to the pool price.
+    pub liquidity: Decimal,
+    /// Capital committed in USD.
+    pub entry_value_usd: Decimal,
+    /// Last marked position value in USD.
+    pub value_usd: Decimal,
+    /// Cash returned to the ledger (fees, withdrawals, close proceeds) in USD.
+    pub cash_usd: Decimal,
+    /// Total fees collected in USD.
+    pub fees_collected_usd: Decimal,
+    /// Total simulated costs in USD.
+    pub costs_usd: Decimal,
+    /// Fees already booked from the shadowed on-chain position.
+    pub fees_baseline_usd: Decimal,
+    /// Whether the position is open.
+    pub is_open: bool,
+    /// When the position was opened.
+    pub opened_at: chrono::DateTime<chrono::Utc>,
+    /// When the position was last updated.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PaperPosition {
+    /// Net PnL in USD.
+    #[must_use]
+    pub fn net_pnl_usd(&self) -> Decimal {
+        self.value_usd + self.cash_usd - self.entry_value_usd
+    }
+
+    /// Net PnL as a percentage of committed capital.
+    #[must_use]
+    pub fn net_pnl_pct(&self) -> Decimal {
+        if self.entry_value_usd.is_zero() {
+            Decimal::ZERO
+        } else {
+            self.net_pnl_usd() / self.entry_value_usd * Decimal::from(100)
+        }
+    }
+}
+
+/// Aggregate paper trading PnL.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaperPnL {
+    /// Total capital committed in USD.
+    pub entry_value_usd: Decimal,
+    /// Current value of open positions in USD.
+    pub value_usd: Decimal,
+    /// Cash returned to the ledger in USD.
+    pub cash_usd: Decimal,
+    /// Total fees collected in USD.
+    pub fees_collected_usd: Decimal,
+    /// Total simulated costs in USD.
+    pub costs_usd: Decimal,
+    /// Net PnL in USD.
+    pub net_pnl_usd: Decimal,
+    /// Net PnL percentage.
+    pub net_pnl_pct: Decimal,
+    /// Number of open positions.
+    pub open_positions: usize,
+    /// Number of fills.
+    pub fill_count: usize,
+}
+
+/// Ledger of paper positions and fills.
+///
+/// Positions are valued with the concentrated liquidity formulas so that
+/// marks follow the live pool price the same way a real position would.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaperLedger {
+    /// Positions by address.
+    pub positions: HashMap<String, PaperPosition>,
+    /// Fill history.
+    pub fills: Vec<PaperFill>,
+}
+
+impl PaperLedger {
+    /// Creates an empty ledger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a ledger from a JSON file, returning an empty ledger if it does not exist.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Saves the ledger to a JSON file.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Gets a paper position.
+    pub fn get(&self, position: &str) -> Option<&PaperPosition> {
+        self.positions.get(position)
+    }
+
+    /// Opens a paper position with the given capital at the current tick.
+    #[allow(clippy::too_many_arguments)]
+    pub fn open(
+        &mut self,
+        position: &str,
+        pool: &str,
+        tick: i32,
+        tick_lower: i32,
+        tick_upper: i32,
+        value_usd: Decimal,
+        fees_baseline_usd: Decimal,
+    ) -> &PaperFill {
+        let now = chrono::Utc::now();
+        let liquidity = liquidity_for_value(value_usd, tick, tick_lower, tick_upper);
+
+        self.positions.insert(
+            position.to_string(),
+            PaperPosition {
+                position: position.to_string(),
+                pool: pool.to_string(),
+                tick_lower,
+                tick_upper,
+                liquidity,
+                entry_value_usd: value_usd,
+                value_usd,
+                cash_usd: Decimal::ZERO,
+                fees_collected_usd: Decimal::ZERO,
+                costs_usd: Decimal::ZERO,
+                fees_baseline_usd,
+                is_open: true,
+                opened_at: now,
+                updated_at: now,
+            },
+        );
+
+        self.push_fill(position, FillKind::Open, tick, Decimal::ZERO, Decimal::ZERO)
+    }
+
+    /// Marks a position to the current tick, returning its value.
+    pub fn mark(&mut self, position: &str, tick: i32) -> Option<Decimal> {
+        let pos = self.positions.get_mut(position)?;
+        if pos.is_open {
+            pos.value_usd =
+                pos.liquidity * range_value_per_liquidity(tick, pos.tick_lower, pos.tick_upper);
+            pos.updated_at = chrono::Utc::now();
+        }
+        Some(pos.value_usd)
+    }
+
+    /// Moves a position into a new range, paying the given costs.
+    pub fn rebalance(
+        &mut self,
+        position: &str,
+        tick: i32,
+        new_tick_lower: i32,
+        new_tick_upper: i32,
+        cost_usd: Decimal,
+    ) -> anyhow::Result<&PaperFill> {
+        self.mark(position, tick);
+        let pos = self.open_position_mut(position)?;
+
+        let value = (pos.value_usd - cost_usd).max(Decimal::ZERO);
+        pos.tick_lower = new_tick_lower;
+        pos.tick_upper = new_tick_upper;
+        pos.liquidity = liquidity_for_value(value, tick, new_tick_lower, new_tick_upper);
+        pos.value_usd = value;
+        pos.costs_usd += cost_usd;
+
+        Ok(self.push_fill(position, FillKind::Rebalance, tick, Decimal::ZERO, cost_usd))
+    }
+
+    /// Adds (positive) or removes (negative) value from a position.
+    pub fn adjust(
+        &mut self,
+        position: &str,
+        tick: i32,
+        delta_usd: Decimal,
+        cost_usd: Decimal,
+    ) -> anyhow::Result<&PaperFill> {
+        self.mark(position, tick);
+        let pos = self.open_position_mut(position)?;
+
+        let (kind, cash_delta) = if delta_usd.is_sign_negative() {
+            let removed = delta_usd.abs().min(pos.value_usd);
+            pos.value_usd -= removed;
+            (FillKind::DecreaseLiquidity, removed - cost_usd)
+        } else {
+            pos.entry_value_usd += delta_usd;
+            pos.value_usd += delta_usd;
+            (FillKind::IncreaseLiquidity, -cost_usd)
+        };
+        pos.cash_usd += cash_delta;
+        pos.costs_usd += cost_usd;
+        pos.liquidity = liquidity_for_value(pos.value_usd, tick, pos.tick_lower, pos.tick_upper);
+
+        Ok(self.push_fill(position, kind, tick, cash_delta, cost_usd))
+    }
+
+    /// Books fees accrued since the last collection.
+    pub fn collect_fees(
+        &mut self,
+        position: &str,
+        tick: i32,
+        fees_total_usd: Decimal,
+        cost_usd: Decimal,
+    ) -> anyhow::Result<&PaperFill> {
+        self.mark(position, tick);
+        let pos = self.open_position_mut(position)?;
+
+        let fees = (fees_total_usd - pos.fees_baseline_usd).max(Decimal::ZERO);
+        pos.fees_baseline_usd = pos.fees_baseline_usd.max(fees_total_usd);
+        pos.fees_collected_usd += fees;
+        pos.costs_usd += cost_usd;
+        let cash_delta = fees - cost_usd;
+        pos.cash_usd += cash_delta;
+
+        Ok(self.push_fill(position, FillKind::CollectFees, tick, cash_delta, cost_usd))
+    }
+
+    /// Closes a position, returning its value to cash.
+    pub fn close(
+        &mut self,
+        position: &str,
+        tick: i32,
+        cost_usd: Decimal,
+    ) -> anyhow::Result<&PaperFill> {
+        self.mark(position, tick);
+        let pos = self.open_position_mut(position)?;
+
+        let cash_delta = pos.value_usd - cost_usd;
+        pos.cash_usd += cash_delta;
+        pos.costs_usd += cost_usd;
+        pos.value_usd = Decimal::ZERO;
+        pos.liquidity = Decimal::ZERO;
+        pos.is_open = false;
+
+        Ok(self.push_fill(position, FillKind::Close, tick, cash_delta, cost_usd))
+    }
+
+    /// Swaps the proceeds of a closed position to the quote token.
+    ///
+    /// Proceeds are already held in USD, so only the swap cost is booked.
+    pub fn swap_to_quote(
+        &mut self,
+        position: &str,
+        tick: i32,
+        cost_usd: Decimal,
+    ) -> anyhow::Result<&PaperFill> {
+        let pos = match self.positions.get_mut(position) {
+            Some(pos) if !pos.is_open => pos,
+            Some(_) => return Err(invalid_state(position, "still open").into()),
+            None => return Err(ExecutionError::PositionNotFound(position.to_string()).into()),
+        };
+
+        pos.cash_usd -= cost_usd;
+        pos.costs_usd += cost_usd;
+        pos.updated_at = chrono::Utc::now();
+
+        Ok(self.push_fill(position, FillKind::SwapToQuote, tick, -cost_usd, cost_usd))
+    }
+
+    /// Calculates aggregate PnL across all positions.
+    #[must_use]
+    pub fn pnl(&self) -> PaperPnL {
+        let mut pnl = PaperPnL {
+            fill_count: self.fills.len(),
+            ..Default::default()
+        };
+
+        for pos in self.positions.values() {
+            pnl.entry_value_usd += pos.entry_value_usd;
+            pnl.value_usd += pos.value_usd;
+            pnl.cash_usd += pos.cash_usd;
+            pnl.fees_collected_usd += pos.fees_collected_usd;
+            pnl.costs_usd += pos.costs_usd;
+            if pos.is_open {
+                pnl.open_positions += 1;
+            }
+        }
+
+        pnl.net_pnl_usd = pnl.value_usd + pnl.cash_usd - pnl.entry_value_usd;
+        if !pnl.entry_value_usd.is_zero() {
+            pnl.net_pnl_pct = pnl.net_pnl_usd / pnl.entry_value_usd * Decimal::from(100);
+        }
+
+        pnl
+    }
+
+    /// Gets an open position for mutation.
+    fn open_position_mut(&mut self, position: &str) -> anyhow::Result<&mut PaperPosition> {
+        match self.positions.get_mut(position) {
+            Some(pos) if pos.is_open => Ok(pos),
+            Some(_) => Err(invalid_state(position, "closed").into()),
+            None => Err(ExecutionError::PositionNotFound(position.to_string()).into()),
+        }
+    }
+
+    /// Records a fill for a position.
+    fn push_fill(
+        &mut self,
+        position: &str,
+        kind: FillKind,
+        tick: i32,
+        cash_delta_usd: Decimal,
+        cost_usd: Decimal,
+    ) -> &PaperFill {
+        let pos = &self.positions[position];
+        self.fills.push(PaperFill {
+            id: uuid::Uuid::new_v4().to_string(),
+            position: position.to_string(),
+            pool: pos.pool.clone(),
+            kind,
+            tick,
+            price: clmm_lp_protocols::prelude::tick_to_price(tick),
+            tick_lower: pos.tick_lower,
+            tick_upper: pos.tick_upper,
+            value_usd: pos.value_usd,
+            cash_delta_usd,
+            cost_usd,
+            timestamp: chrono::Utc::now(),
+        });
+        self.fills.last().expect("fill was just pushed")
+    }
+}
+
+/// Value of one unit of liquidity in a tick range at the given tick.
+///
+/// Uses the concentrated liquidity token amounts
+/// `x = L(1/sqrt(p) - 1/sqrt(pb))` and `y = L(sqrt(p) - sqrt(pa))`
+/// valued in token B. Prices are clamped to the range bounds.
+fn range_value_per_liquidity(tick: i32, tick_lower: i32, tick_upper: i32) -> Decimal {
+    let sqrt_at = |t: i32| 1.0001_f64.powf(f64::from(t) / 2.0);
+    let sa = sqrt_at(tick_lower);
+    let sb = sqrt_at(tick_upper);
+    let sp = sqrt_at(tick.clamp(tick_lower, tick_upper));
+
+    let amount_a = 1.0 / sp - 1.0 / sb;
+    let amount_b = sp - sa;
+    let value = amount_a * sp * sp + amount_b;
+
+    Decimal::from_f64(value).unwrap_or(Decimal::ZERO)
+}
+
+/// Virtual liquidity that gives a position the requested value at the given tick.
+fn liquidity_for_value(value_usd: Decimal, tick: i32, tick_lower: i32, tick_upper: i32) -> Decimal {
+    let unit = range_value_per_liquidity(tick, tick_lower, tick_upper);
+    if unit <= Decimal::ZERO {
+        Decimal::ZERO
+    } else {
+        value_usd / unit
+    }
+}
+
+/// Builds the error for an operation on a position in the wrong state.
+fn invalid_state(position: &str, state: &str) -> ExecutionError {
+    ExecutionError::InvalidPositionState {
+        position: position.to_string(),
+        state: state.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn ledger_with_position() -> PaperLedger {
+        let mut ledger = PaperLedger::new();
+        ledger.open("pos", "pool", 0, -1000, 1000, dec!(1000), Decimal::ZERO);
+        ledger
+    }
+
+    #[test]
+    fn test_open_marks_at_entry_value() {
+        let mut ledger = ledger_with_position();
+        let value = ledger.mark("pos", 0).unwrap();
+        assert!((value - dec!(1000)).abs() < dec!(0.01));
+        assert_eq!(ledger.fills.len(), 1);
+        assert_eq!(ledger.fills[0].kind, FillKind::Open);
+    }
+
+    #[test]
+    fn test_price_move_creates_il() {
+        let mut ledger = ledger_with_position();
+        // Price moves out of range above: position is all token B
+        let up = ledger.mark("pos", 2000).unwrap();
+        let mut ledger = ledger_with_position();
+        let down = ledger.mark("pos", -2000).unwrap();
+        assert!(up > dec!(1000));
+        assert!(down < dec!(1000));
+    }
+
+    #[test]
+    fn test_rebalance_charges_costs() {
+        let mut ledger = ledger_with_position();
+        ledger.rebalance("pos", 0, -500, 500, dec!(5)).unwrap();
+
+        let pos = ledger.get("pos").unwrap();
+        assert_eq!(pos.tick_lower, -500);
+        assert!((pos.value_usd - dec!(995)).abs() < dec!(0.01));
+        assert_eq!(pos.costs_usd, dec!(5));
+        assert!((pos.net_pnl_usd() + dec!(5)).abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn test_collect_fees_uses_baseline() {
+        let mut ledger = ledger_with_position();
+        ledger.collect_fees("pos", 0, dec!(10), dec!(1)).unwrap();
+        ledger
+            .collect_fees("pos", 0, dec!(10), Decimal::ZERO)
+            .unwrap();
+
+        let pos = ledger.get("pos").unwrap();
+        assert_eq!(pos.fees_collected_usd, dec!(10));
+        assert_eq!(pos.cash_usd, dec!(9));
+    }
+
+    #[test]
+    fn test_close_moves_value_to_cash() {
+        let mut ledger = ledger_with_position();
+        ledger.close("pos", 0, dec!(2)).unwrap();
+
+        let pos = ledger.get("pos").unwrap();
+        assert!(!pos.is_open);
+        assert_eq!(pos.value_usd, Decimal::ZERO);
+        assert!(ledger.close("pos", 0, Decimal::ZERO).is_err());
+
+        let pnl = ledger.pnl();
+        assert_eq!(pnl.open_positions, 0);
+        assert!((pnl.net_pnl_usd + dec!(2)).abs() < dec!(0.01));
+
+        assert!(ledger.swap_to_quote("pos", 0, dec!(1)).is_ok());
+        assert_eq!(ledger.get("pos").unwrap().costs_usd, dec!(3));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let mut ledger = ledger_with_position();
+        ledger.adjust("pos", 0, dec!(-100), dec!(1)).unwrap();
+
+        let path = std::env::temp_dir().join(format!("paper_{}.json", uuid::Uuid::new_v4()));
+        ledger.save(&path).unwrap();
+        let loaded = PaperLedger::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.fills.len(), 2);
+        assert_eq!(loaded.get("pos").unwrap().cash_usd, dec!(99));
+    }
+}
@@ -0,0 +1,12 @@
+//! Paper trading execution backend.
+//!
+//! Runs strategies against live pool quotes without sending transactions:
+//! - Simulated fills with slippage and transaction costs
+//! - Concentrated liquidity marking of paper positions
+//! - JSON-persisted ledger of fills and PnL
+
+mod executor;
+mod ledger;
+
+pub use executor::*;
+pub use ledger::*;
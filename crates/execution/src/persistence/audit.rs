@@ -0,0 +1,281 @@
+//! Append-only audit log of decisions and transactions.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::VecDeque;
+use tokio::sync::RwLock;
+
+/// Kind of audited event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    /// A decision-engine evaluation of a position.
+    Evaluation,
+    /// An action proposed by the decision engine.
+    Proposal,
+    /// A transaction sent on-chain.
+    Transaction,
+    /// The outcome of executing an action.
+    Outcome,
+    /// An action planned in dry run mode, with its transcript.
+    DryRun,
+    /// A decision recorded in shadow mode with its simulated outcome.
+    Shadow,
+    /// A shadow decision reconciled against its actual outcome.
+    Reconciliation,
+}
+
+impl AuditKind {
+    /// Returns the kind's name as stored.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Evaluation => "evaluation",
+            Self::Proposal => "proposal",
+            Self::Transaction => "transaction",
+            Self::Outcome => "outcome",
+            Self::DryRun => "dry_run",
+            Self::Shadow => "shadow",
+            Self::Reconciliation => "reconciliation",
+        }
+    }
+
+    /// Parses a stored kind name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "evaluation" => Some(Self::Evaluation),
+            "proposal" => Some(Self::Proposal),
+            "transaction" => Some(Self::Transaction),
+            "outcome" => Some(Self::Outcome),
+            "dry_run" => Some(Self::DryRun),
+            "shadow" => Some(Self::Shadow),
+            "reconciliation" => Some(Self::Reconciliation),
+            _ => None,
+        }
+    }
+}
+
+/// An audit log entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Entry ID.
+    pub id: String,
+    /// When the event occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Kind of event.
+    pub kind: AuditKind,
+    /// Action name (e.g. `rebalance`, `hold`, `send_transaction`).
+    pub action: String,
+    /// Strategy the event belongs to.
+    pub strategy_id: Option<String>,
+    /// Position the event concerns.
+    pub position: Option<String>,
+    /// Transaction signature.
+    pub signature: Option<String>,
+    /// Whether the action succeeded, for outcomes and transactions.
+    pub success: Option<bool>,
+    /// Error message if the action failed.
+    pub error: Option<String>,
+    /// Event-specific details.
+    pub details: serde_json::Value,
+}
+
+impl AuditEntry {
+    /// Creates a new audit entry.
+    pub fn new(kind: AuditKind, action: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            kind,
+            action: action.into(),
+            strategy_id: None,
+            position: None,
+            signature: None,
+            success: None,
+            error: None,
+            details: serde_json::Value::Null,
+        }
+    }
+
+    /// Sets the strategy.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy_id: Option<&str>) -> Self {
+        self.strategy_id = strategy_id.map(str::to_string);
+        self
+    }
+
+    /// Sets the position.
+    #[must_use]
+    pub fn with_position(mut self, position: &Pubkey) -> Self {
+        self.position = Some(position.to_string());
+        self
+    }
+
+    /// Sets the transaction signature.
+    #[must_use]
+    pub fn with_signature(mut self, signature: &Signature) -> Self {
+        self.signature = Some(signature.to_string());
+        self
+    }
+
+    /// Sets the outcome from a result.
+    #[must_use]
+    pub fn with_result<T>(mut self, result: &anyhow::Result<T>) -> Self {
+        self.success = Some(result.is_ok());
+        self.error = result.as_ref().err().map(|e| e.to_string());
+        self
+    }
+
+    /// Sets event-specific details.
+    #[must_use]
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// Filter for querying the audit log.
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Only entries for this strategy.
+    pub strategy_id: Option<String>,
+    /// Only entries for this position.
+    pub position: Option<String>,
+    /// Only entries of this kind.
+    pub kind: Option<AuditKind>,
+    /// Only entries at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only entries before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Maximum number of entries returned.
+    pub limit: usize,
+}
+
+impl AuditQuery {
+    /// Returns true if an entry matches the filter (ignoring the limit).
+    #[must_use]
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.strategy_id
+            .as_ref()
+            .is_none_or(|id| entry.strategy_id.as_ref() == Some(id))
+            && self
+                .position
+                .as_ref()
+                .is_none_or(|p| entry.position.as_ref() == Some(p))
+            && self.kind.is_none_or(|k| entry.kind == k)
+            && self.since.is_none_or(|t| entry.timestamp >= t)
+            && self.until.is_none_or(|t| entry.timestamp < t)
+    }
+}
+
+/// Append-only audit log.
+///
+/// Entries can be appended and queried but never modified or removed.
+#[async_trait]
+pub trait AuditLog: Send + Sync {
+    /// Appends an entry.
+    async fn append(&self, entry: &AuditEntry) -> anyhow::Result<()>;
+
+    /// Queries entries, newest first.
+    async fn query(&self, query: &AuditQuery) -> anyhow::Result<Vec<AuditEntry>>;
+}
+
+/// In-memory audit log keeping the most recent entries.
+pub struct MemoryAuditLog {
+    /// Entries, oldest first.
+    entries: RwLock<VecDeque<AuditEntry>>,
+    /// Maximum number of entries kept.
+    max_entries: usize,
+}
+
+impl MemoryAuditLog {
+    /// Creates an in-memory log keeping up to `max_entries` entries.
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: RwLock::new(VecDeque::new()),
+            max_entries,
+        }
+    }
+}
+
+impl Default for MemoryAuditLog {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+#[async_trait]
+impl AuditLog for MemoryAuditLog {
+    async fn append(&self, entry: &AuditEntry) -> anyhow::Result<()> {
+        let mut entries = self.entries.write().await;
+        if entries.len() >= self.max_entries {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+        Ok(())
+    }
+
+    async fn query(&self, query: &AuditQuery) -> anyhow::Result<Vec<AuditEntry>> {
+        Ok(self
+            .entries
+            .read()
+            .await
+            .iter()
+            .rev()
+            .filter(|e| query.matches(e))
+            .take(query.limit)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_audit_log_query() {
+        let log = MemoryAuditLog::new(3);
+        let position = Pubkey::new_unique();
+
+        for action in ["hold", "rebalance", "close", "hold"] {
+            let entry = AuditEntry::new(AuditKind::Evaluation, action)
+                .with_strategy(Some("s1"))
+                .with_position(&position);
+            log.append(&entry).await.unwrap();
+        }
+        let outcome = AuditEntry::new(AuditKind::Outcome, "close")
+            .with_result(&Err::<(), _>(anyhow::anyhow!("rpc down")));
+        log.append(&outcome).await.unwrap();
+
+        // Oldest entries are evicted beyond the cap
+        let all = log
+            .query(&AuditQuery {
+                limit: 10,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].kind, AuditKind::Outcome);
+        assert_eq!(all[0].success, Some(false));
+        assert_eq!(all[0].error.as_deref(), Some("rpc down"));
+
+        let evaluations = log
+            .query(&AuditQuery {
+                strategy_id: Some("s1".to_string()),
+                kind: Some(AuditKind::Evaluation),
+                limit: 1,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(evaluations.len(), 1);
+        assert_eq!(evaluations[0].action, "hold");
+    }
+}
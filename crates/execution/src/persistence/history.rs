@@ -0,0 +1,215 @@
+//! Periodic recording of monitored position snapshots.
+
+use crate::monitor::{MonitoredPosition, PositionMonitor, PositionPnL};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tracing::{debug, error, info};
+
+/// Recorded state of a monitored position at a point in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryPoint {
+    /// Position address.
+    pub address: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Lower tick of the range.
+    pub tick_lower: i32,
+    /// Upper tick of the range.
+    pub tick_upper: i32,
+    /// Whether the position was in range.
+    pub in_range: bool,
+    /// PnL state.
+    pub pnl: PositionPnL,
+    /// When the point was recorded.
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl HistoryPoint {
+    /// Records a monitored position's current state.
+    #[must_use]
+    pub fn new(position: &MonitoredPosition, recorded_at: DateTime<Utc>) -> Self {
+        Self {
+            address: position.address,
+            pool: position.pool,
+            tick_lower: position.on_chain.tick_lower,
+            tick_upper: position.on_chain.tick_upper,
+            in_range: position.in_range,
+            pnl: position.pnl.clone(),
+            recorded_at,
+        }
+    }
+}
+
+/// Durable storage for position history.
+///
+/// Implementations must tolerate recording the same point twice.
+#[async_trait]
+pub trait HistoryStore: Send + Sync {
+    /// Records points, all taken at the same time.
+    async fn record(&self, points: &[HistoryPoint]) -> anyhow::Result<()>;
+}
+
+/// In-memory history store keeping the most recent points.
+pub struct MemoryHistoryStore {
+    /// Points, oldest first.
+    points: RwLock<Vec<HistoryPoint>>,
+    /// Maximum number of points kept.
+    max_points: usize,
+}
+
+impl MemoryHistoryStore {
+    /// Creates an in-memory store keeping up to `max_points` points.
+    #[must_use]
+    pub fn new(max_points: usize) -> Self {
+        Self {
+            points: RwLock::new(Vec::new()),
+            max_points,
+        }
+    }
+
+    /// Gets the recorded points in chronological order.
+    pub async fn points(&self) -> Vec<HistoryPoint> {
+        self.points.read().await.clone()
+    }
+}
+
+impl Default for MemoryHistoryStore {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+
+#[async_trait]
+impl HistoryStore for MemoryHistoryStore {
+    async fn record(&self, points: &[HistoryPoint]) -> anyhow::Result<()> {
+        let mut stored = self.points.write().await;
+        for point in points {
+            // Points arrive in chronological order, so duplicates are at the end
+            let duplicate = stored
+                .iter()
+                .rev()
+                .take_while(|p| p.recorded_at >= point.recorded_at)
+                .any(|p| p.address == point.address && p.recorded_at == point.recorded_at);
+            if !duplicate {
+                stored.push(point.clone());
+            }
+        }
+        if stored.len() > self.max_points {
+            let excess = stored.len() - self.max_points;
+            stored.drain(..excess);
+        }
+        Ok(())
+    }
+}
+
+/// Periodically records every monitored position into a history store.
+pub struct SnapshotRecorder {
+    /// Position monitor.
+    monitor: Arc<PositionMonitor>,
+    /// History store.
+    store: Arc<dyn HistoryStore>,
+    /// Interval between recordings.
+    interval: Duration,
+}
+
+impl SnapshotRecorder {
+    /// Creates a recorder writing every `interval`.
+    pub fn new(
+        monitor: Arc<PositionMonitor>,
+        store: Arc<dyn HistoryStore>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            monitor,
+            store,
+            interval,
+        }
+    }
+
+    /// Records every monitored position once.
+    ///
+    /// Returns the number of positions recorded.
+    ///
+    /// # Errors
+    /// Returns an error if the store fails.
+    pub async fn record_once(&self) -> anyhow::Result<usize> {
+        let recorded_at = Utc::now();
+        let points: Vec<HistoryPoint> = self
+            .monitor
+            .get_positions()
+            .await
+            .iter()
+            .map(|p| HistoryPoint::new(p, recorded_at))
+            .collect();
+        if !points.is_empty() {
+            self.store.record(&points).await?;
+        }
+        Ok(points.len())
+    }
+
+    /// Runs the recording loop.
+    pub async fn run(self) {
+        info!(
+            interval_secs = self.interval.as_secs(),
+            "Starting position snapshot recorder"
+        );
+
+        let mut ticker = interval(self.interval);
+        loop {
+            ticker.tick().await;
+            match self.record_once().await {
+                Ok(count) => debug!(positions = count, "Recorded position snapshots"),
+                Err(e) => error!(error = %e, "Failed to record position snapshots"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(address: Pubkey, recorded_at: DateTime<Utc>) -> HistoryPoint {
+        HistoryPoint {
+            address,
+            pool: Pubkey::new_unique(),
+            tick_lower: -100,
+            tick_upper: 100,
+            in_range: true,
+            pnl: PositionPnL::default(),
+            recorded_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_history_store() {
+        let store = MemoryHistoryStore::new(3);
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let start = Utc::now();
+        let later = start + chrono::Duration::minutes(5);
+
+        store
+            .record(&[point(a, start), point(b, start)])
+            .await
+            .unwrap();
+        // Recording the same point again is a no-op
+        store.record(&[point(a, start)]).await.unwrap();
+        assert_eq!(store.points().await.len(), 2);
+
+        // The oldest points are evicted beyond the cap
+        store
+            .record(&[point(a, later), point(b, later)])
+            .await
+            .unwrap();
+        let points = store.points().await;
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].address, b);
+        assert_eq!(points[2].recorded_at, later);
+    }
+}
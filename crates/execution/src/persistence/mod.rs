@@ -0,0 +1,15 @@
+//! State persistence across restarts.
+//!
+//! Provides a storage abstraction for:
+//! - Monitored position snapshots (entry and fee baselines, range status)
+//! - Lifecycle events (opens, rebalances, fee collections, closes)
+//! - Periodic position history for historical analytics
+//! - An append-only audit log of decisions and transactions
+
+mod audit;
+mod history;
+mod store;
+
+pub use audit::*;
+pub use history::*;
+pub use store::*;
@@ -0,0 +1,115 @@
+//! Storage backends for monitor and lifecycle state.
+
+use crate::lifecycle::LifecycleEvent;
+use crate::monitor::{MonitoredPosition, PositionPnL};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// Persisted state of a monitored position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSnapshot {
+    /// Position address.
+    pub address: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// PnL state, including entry value and fee baselines.
+    pub pnl: PositionPnL,
+    /// Whether the position was in range.
+    pub in_range: bool,
+    /// When the position last left its range, if out of range.
+    pub out_of_range_since: Option<DateTime<Utc>>,
+    /// When the snapshot was taken.
+    pub last_updated: DateTime<Utc>,
+}
+
+impl From<&MonitoredPosition> for PositionSnapshot {
+    fn from(position: &MonitoredPosition) -> Self {
+        Self {
+            address: position.address,
+            pool: position.pool,
+            pnl: position.pnl.clone(),
+            in_range: position.in_range,
+            out_of_range_since: position.out_of_range_since,
+            last_updated: position.last_updated,
+        }
+    }
+}
+
+/// Durable storage for monitor and lifecycle state.
+///
+/// Implementations must tolerate repeated saves of the same snapshot and
+/// repeated appends of the same event.
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    /// Saves or replaces a position snapshot.
+    async fn save_position(&self, snapshot: &PositionSnapshot) -> anyhow::Result<()>;
+
+    /// Removes a position snapshot.
+    async fn remove_position(&self, position: &Pubkey) -> anyhow::Result<()>;
+
+    /// Loads all position snapshots.
+    async fn load_positions(&self) -> anyhow::Result<Vec<PositionSnapshot>>;
+
+    /// Appends a lifecycle event.
+    async fn append_event(&self, event: &LifecycleEvent) -> anyhow::Result<()>;
+
+    /// Loads all lifecycle events in chronological order.
+    async fn load_events(&self) -> anyhow::Result<Vec<LifecycleEvent>>;
+}
+
+/// In-memory state store.
+///
+/// Useful for tests and dry runs; state is lost when the process exits.
+#[derive(Default)]
+pub struct MemoryStateStore {
+    /// Snapshots by position.
+    positions: RwLock<HashMap<Pubkey, PositionSnapshot>>,
+    /// Events in insertion order.
+    events: RwLock<Vec<LifecycleEvent>>,
+}
+
+impl MemoryStateStore {
+    /// Creates an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStateStore {
+    async fn save_position(&self, snapshot: &PositionSnapshot) -> anyhow::Result<()> {
+        self.positions
+            .write()
+            .await
+            .insert(snapshot.address, snapshot.clone());
+        Ok(())
+    }
+
+    async fn remove_position(&self, position: &Pubkey) -> anyhow::Result<()> {
+        self.positions.write().await.remove(position);
+        Ok(())
+    }
+
+    async fn load_positions(&self) -> anyhow::Result<Vec<PositionSnapshot>> {
+        Ok(self.positions.read().await.values().cloned().collect())
+    }
+
+    async fn append_event(&self, event: &LifecycleEvent) -> anyhow::Result<()> {
+        let mut events = self.events.write().await;
+        if !events.iter().any(|e| e.id == event.id) {
+            events.push(event.clone());
+        }
+        Ok(())
+    }
+
+    async fn load_events(&self) -> anyhow::Result<Vec<LifecycleEvent>> {
+        let mut events = self.events.read().await.clone();
+        events.sort_by_key(|e| e.timestamp);
+        Ok(events)
+    }
+}
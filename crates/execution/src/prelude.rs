@@ -10,36 +10,89 @@
 
 // Alerts
 pub use crate::alerts::{
-    Alert, AlertData, AlertLevel, AlertRule, AlertType, ConsoleNotifier, FileNotifier,
-    MultiNotifier, Notifier, RuleCondition, RuleContext, RulesEngine, WebhookNotifier,
+    Alert, AlertData, AlertDispatcher, AlertLevel, AlertRule, AlertType, ChannelTestResult,
+    ConsoleNotifier, DiscordNotifier, EscalationPolicy, FileNotifier, MultiNotifier, Notifier,
+    RuleCondition, RuleContext, RulesEngine, SlackConfig, SlackNotifier, SlackTarget,
+    TelegramConfig, TelegramNotifier, TrackedAlert, TriggerMode, WebhookConfig, WebhookEvent,
+    WebhookEventKind, WebhookNotifier,
+};
+
+// Digest
+pub use crate::digest::{
+    DailyDigest, DigestConfig, DigestJob, DigestSnapshot, EdgeProximity, RangeEdge,
 };
 
 // Emergency
 pub use crate::emergency::{
     CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState, EmergencyExitConfig,
-    EmergencyExitManager, ExitResult, ExitStatus,
+    EmergencyExitManager, ExitResult, ExitStatus, PortfolioRiskManager, PortfolioValuation,
+    RiskBreach, RiskLimitsConfig, RiskStatus, TokenExposure, TokenHolding,
+};
+
+// Errors
+pub use crate::error::{ExecutionError, ExecutionResult};
+
+// Hedging
+pub use crate::hedging::{
+    DeltaHedger, DriftVenue, HedgeConfig, HedgeMarket, HedgeState, HedgeUpdate, PerpVenue,
 };
 
 // Lifecycle
 pub use crate::lifecycle::{
-    AggregateStats, CloseReason, EventData, FeesCollectedData, LifecycleEvent, LifecycleEventType,
-    LifecycleTracker, LiquidityChangeData, PositionClosedData, PositionOpenedData, PositionSummary,
-    RebalanceData, RebalanceReason,
+    AggregateStats, CloseReason, EventData, FeesCollectedData, HedgeFundingData, LifecycleEvent,
+    LifecycleEventType, LifecycleTracker, LiquidityChangeData, PositionClosedData,
+    PositionOpenedData, PositionSummary, RebalanceData, RebalanceReason,
 };
 
 // Monitor
 pub use crate::monitor::{
-    MonitorConfig, MonitoredPosition, PnLResult, PnLTracker, PortfolioMetrics, PositionEntry,
-    PositionMonitor, PositionPnL, ReconcileResult, StateSynchronizer, SyncState,
+    CostBasis, MonitorConfig, MonitoredPosition, PnLBreakdown, PnLResult, PnLTracker,
+    PortfolioMetrics, PositionEntry, PositionMonitor, PositionPnL, ReconcileResult, RewardAccrual,
+    StateSynchronizer, SyncState,
+};
+
+// Operations
+pub use crate::operations::{
+    CloseRequest, ClosedPosition, CollectedFees, DecreaseRequest, IncreaseRequest,
+    LiquidityAdjuster, LiquidityChange, OpenRequest, OpenedPosition, OperationKind, OperationPlan,
+    PositionCloser, PositionCollector, PositionOpener, PositionOperator, RewardClaim,
+};
+
+// Oracle
+pub use crate::oracle::{
+    DivergenceCheck, OraclePrice, PriceGuard, PriceGuardConfig, PriceOracle, PythOracle,
+};
+
+// Paper trading
+pub use crate::paper::{
+    FillKind, PaperExecutor, PaperFill, PaperLedger, PaperPnL, PaperPosition, PaperTradingConfig,
+};
+
+// Persistence
+pub use crate::persistence::{
+    AuditEntry, AuditKind, AuditLog, AuditQuery, HistoryPoint, HistoryStore, MemoryAuditLog,
+    MemoryHistoryStore, MemoryStateStore, PositionSnapshot, SnapshotRecorder, StateStore,
 };
 
 // Scheduler
-pub use crate::scheduler::{Schedule, ScheduleBuilder, ScheduledTask, Scheduler, TaskEvent};
+pub use crate::scheduler::{
+    CronSchedule, Schedule, ScheduleBuilder, ScheduledTask, Scheduler, SchedulerHandle, TaskEvent,
+    TaskKind,
+};
 
 // Strategy
 pub use crate::strategy::{
-    Decision, DecisionConfig, DecisionContext, DecisionEngine, ExecutorConfig, ProfitabilityCheck,
-    RebalanceConfig, RebalanceExecutor, RebalanceParams, RebalanceResult, StrategyExecutor,
+    ActualOutcome, AutoCompoundConfig, CompoundCheck, Decision, DecisionConfig, DecisionContext,
+    DecisionEngine, ExecutionBackend, ExecutorConfig, ExitTrigger, ExpectedImpact, PendingExit,
+    PlannedInstruction, ProfitabilityCheck, RebalanceConfig, RebalanceExecutor, RebalanceParams,
+    RebalanceResult, Recommendation, RecommendationConfig, RecommendedAction, Recommender,
+    ShadowEntry, ShadowReconciliation, ShadowRecord, ShadowReport, ShadowSummary, StopLossConfig,
+    StrategyExecutor, TranscriptStep, reconcile_shadow, shadow_report,
+};
+
+// Swap
+pub use crate::swap::{
+    JupiterSwapper, QuoteRequest, SwapInstructions, SwapPlan, SwapQuote, amounts_for_liquidity,
 };
 
 // Sync
@@ -50,9 +103,9 @@ pub use crate::sync::{
 
 // Transaction
 pub use crate::transaction::{
-    PriorityLevel, SimulationResult, TransactionBuilder, TransactionConfig, TransactionManager,
-    TransactionResult, TransactionStatus,
+    PriorityFeeEstimator, PriorityLevel, SimulationResult, TransactionBuilder, TransactionConfig,
+    TransactionManager, TransactionResult, TransactionStatus,
 };
 
 // Wallet
-pub use crate::wallet::{Wallet, WalletManager};
+pub use crate::wallet::{KdfParams, Keystore, RemoteSigner, Wallet, WalletManager};
@@ -0,0 +1,336 @@
+//! Cron expressions evaluated in a timezone.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use std::fmt;
+
+/// Month names accepted in the month field.
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// Weekday names accepted in the day-of-week field.
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// How far ahead to search for the next matching time.
+const MAX_LOOKAHEAD_DAYS: i64 = 5 * 366;
+
+/// A five-field cron schedule (`minute hour day-of-month month day-of-week`).
+///
+/// Fields accept `*`, values, ranges (`1-5`), lists (`0,30`) and steps
+/// (`*/15`, `10-50/20`). Months and weekdays also accept names (`JAN`,
+/// `MON`), and Sunday is either `0` or `7`. As in standard cron, when both
+/// day-of-month and day-of-week are restricted a day matching either runs.
+///
+/// The macros `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly` are
+/// also accepted.
+///
+/// Times are matched in the schedule's timezone. Local times skipped by a
+/// daylight-saving transition do not run, and repeated local times run once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    /// Original expression.
+    expression: String,
+    /// Timezone the expression is evaluated in.
+    timezone: Tz,
+    /// Matching minutes (bits 0-59).
+    minutes: u64,
+    /// Matching hours (bits 0-23).
+    hours: u64,
+    /// Matching days of the month (bits 1-31).
+    days: u64,
+    /// Matching months (bits 1-12).
+    months: u64,
+    /// Matching weekdays (bits 0-6, Sunday is 0).
+    weekdays: u64,
+    /// Whether the day-of-month field is `*`.
+    any_day: bool,
+    /// Whether the day-of-week field is `*`.
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    /// Parses a cron expression evaluated in UTC.
+    ///
+    /// # Errors
+    /// Returns an error if the expression is invalid.
+    pub fn parse(expression: &str) -> anyhow::Result<Self> {
+        Self::parse_in(expression, "UTC")
+    }
+
+    /// Parses a cron expression evaluated in an IANA timezone
+    /// (e.g. `America/New_York`).
+    ///
+    /// # Errors
+    /// Returns an error if the expression or timezone is invalid.
+    pub fn parse_in(expression: &str, timezone: &str) -> anyhow::Result<Self> {
+        let timezone: Tz = timezone
+            .parse()
+            .map_err(|_| anyhow::anyhow!("unknown timezone: {}", timezone))?;
+
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other if other.starts_with('@') => {
+                anyhow::bail!("unknown cron macro: {}", other)
+            }
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            anyhow::bail!(
+                "cron expression must have 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            );
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7, &WEEKDAY_NAMES, 0)?;
+        // Sunday may be written as 7
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            timezone,
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)?,
+            days: parse_field(day, 1, 31, &[], 0)?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, 1)?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    /// Gets the original expression.
+    #[must_use]
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Gets the timezone the expression is evaluated in.
+    #[must_use]
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Returns the first matching time strictly after `after`.
+    ///
+    /// Returns `None` if nothing matches within five years (e.g. `0 0 30 2 *`).
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let local = after.with_timezone(&self.timezone).naive_local();
+        let mut t = local.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = t + Duration::days(MAX_LOOKAHEAD_DAYS);
+
+        while t < limit {
+            if !has_bit(self.months, t.month()) {
+                t = start_of_next_month(t)?;
+                continue;
+            }
+            if !self.day_matches(t.date()) {
+                t = t.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !has_bit(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !has_bit(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+                continue;
+            }
+
+            // Skipped local times yield no result; repeated ones use the first
+            if let Some(utc) = self
+                .timezone
+                .from_local_datetime(&t)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+                && utc > after
+            {
+                return Some(utc);
+            }
+            t += Duration::minutes(1);
+        }
+
+        None
+    }
+
+    /// Checks whether a local date matches the day fields.
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = has_bit(self.days, date.day());
+        let weekday = has_bit(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.expression, self.timezone)
+    }
+}
+
+/// Checks whether bit `n` is set.
+fn has_bit(bits: u64, n: u32) -> bool {
+    bits & (1 << n) != 0
+}
+
+/// Returns midnight on the first day of the following month.
+fn start_of_next_month(t: NaiveDateTime) -> Option<NaiveDateTime> {
+    let (year, month) = if t.month() == 12 {
+        (t.year() + 1, 1)
+    } else {
+        (t.year(), t.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+/// Parses one cron field into a bit set of matching values.
+///
+/// `names[i]` is accepted as an alias for `name_base + i`.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    name_base: u32,
+) -> anyhow::Result<u64> {
+    let value = |s: &str| -> anyhow::Result<u32> {
+        let n = match names.iter().position(|n| n.eq_ignore_ascii_case(s)) {
+            Some(i) => i as u32 + name_base,
+            None => s
+                .parse()
+                .map_err(|_| anyhow::anyhow!("invalid cron value '{}' in '{}'", s, field))?,
+        };
+        if n < min || n > max {
+            anyhow::bail!(
+                "cron value {} out of range {}-{} in '{}'",
+                n,
+                min,
+                max,
+                field
+            );
+        }
+        Ok(n)
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid cron step in '{}'", field))?;
+                if step == 0 {
+                    anyhow::bail!("cron step must be positive in '{}'", field);
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // A single value with a step runs from the value to the max
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let n = value(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if start > end {
+            anyhow::bail!("invalid cron range '{}'", range);
+        }
+
+        for n in (start..=end).step_by(step as usize) {
+            bits |= 1 << n;
+        }
+    }
+
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let cron = CronSchedule::parse("*/15 9-17 * JAN-MAR MON-FRI").unwrap();
+        assert!(has_bit(cron.minutes, 45));
+        assert!(!has_bit(cron.minutes, 50));
+        assert!(has_bit(cron.months, 3));
+        assert!(!has_bit(cron.weekdays, 0));
+
+        assert!(CronSchedule::parse("* * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse_in("* * * * *", "Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_next_after_utc() {
+        // 5 minutes after each 8-hour funding time
+        let cron = CronSchedule::parse("5 0,8,16 * * *").unwrap();
+        assert_eq!(
+            cron.next_after(utc("2024-03-01T07:59:00Z")),
+            Some(utc("2024-03-01T08:05:00Z"))
+        );
+        assert_eq!(
+            cron.next_after(utc("2024-03-01T16:05:00Z")),
+            Some(utc("2024-03-02T00:05:00Z"))
+        );
+
+        // Either day field matches when both are restricted
+        let cron = CronSchedule::parse("0 12 1 * FRI").unwrap();
+        assert_eq!(
+            cron.next_after(utc("2024-03-01T12:00:00Z")),
+            Some(utc("2024-03-08T12:00:00Z"))
+        );
+
+        assert_eq!(
+            CronSchedule::parse("0 0 30 2 *")
+                .unwrap()
+                .next_after(utc("2024-01-01T00:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_after_in_timezone() {
+        let cron = CronSchedule::parse_in("@daily", "America/New_York").unwrap();
+        // EST is UTC-5 in winter
+        assert_eq!(
+            cron.next_after(utc("2024-01-15T12:00:00Z")),
+            Some(utc("2024-01-16T05:00:00Z"))
+        );
+        // EDT is UTC-4 in summer
+        assert_eq!(
+            cron.next_after(utc("2024-07-15T12:00:00Z")),
+            Some(utc("2024-07-16T04:00:00Z"))
+        );
+
+        // 02:30 does not exist on the spring-forward day
+        let cron = CronSchedule::parse_in("30 2 * * *", "America/New_York").unwrap();
+        assert_eq!(
+            cron.next_after(utc("2024-03-10T00:00:00Z")),
+            Some(utc("2024-03-11T06:30:00Z"))
+        );
+    }
+}
@@ -3,10 +3,13 @@
 //! Provides flexible scheduling for:
 //! - Periodic evaluations
 //! - Time-based triggers
-//! - Cron-like scheduling
+//! - Cron expressions evaluated in a timezone
+//! - Auto-compounding tasks
 
+mod cron;
 mod runner;
 mod types;
 
-pub use runner::Scheduler;
-pub use types::{Schedule, ScheduleBuilder, ScheduledTask, TaskEvent};
+pub use cron::CronSchedule;
+pub use runner::{Scheduler, SchedulerHandle};
+pub use types::{Schedule, ScheduleBuilder, ScheduledTask, TaskEvent, TaskKind};
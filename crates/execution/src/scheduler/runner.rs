@@ -97,6 +97,7 @@ impl Scheduler {
                     // Task should run
                     let event = TaskEvent {
                         task_name: task.name.clone(),
+                        kind: task.kind,
                         scheduled_at: next_run,
                         triggered_at: now,
                     };
@@ -131,6 +132,13 @@ impl Scheduler {
         self.running.store(false, Ordering::SeqCst);
     }
 
+    /// Gets a handle that can stop the scheduler while it is running.
+    pub fn handle(&self) -> SchedulerHandle {
+        SchedulerHandle {
+            running: self.running.clone(),
+        }
+    }
+
     /// Calculates the next run time for a schedule (static version).
     fn calculate_next_run_static(schedule: &Schedule, from: Instant) -> Instant {
         match schedule {
@@ -141,10 +149,15 @@ impl Scheduler {
                 // A real implementation would calculate based on wall clock time
                 from + Duration::from_secs(24 * 60 * 60)
             }
-            Schedule::Cron(_expr) => {
-                // Simplified: just run in 1 hour
-                // A real implementation would parse the cron expression
-                from + Duration::from_secs(60 * 60)
+            Schedule::Cron(cron) => {
+                let now = chrono::Utc::now();
+                match cron.next_after(now) {
+                    Some(next) => from + (next - now).to_std().unwrap_or_default(),
+                    None => {
+                        warn!(schedule = %cron, "Cron schedule never matches");
+                        from + Duration::from_secs(365 * 24 * 60 * 60)
+                    }
+                }
             }
         }
     }
@@ -160,6 +173,25 @@ impl Scheduler {
     }
 }
 
+/// Handle for stopping a running scheduler.
+#[derive(Debug, Clone)]
+pub struct SchedulerHandle {
+    /// Shared running flag.
+    running: Arc<AtomicBool>,
+}
+
+impl SchedulerHandle {
+    /// Stops the scheduler.
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    /// Checks if the scheduler is running.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
 impl Default for Scheduler {
     fn default() -> Self {
         Self::new()
@@ -1,5 +1,6 @@
 //! Types for the scheduler module.
 
+use super::CronSchedule;
 use std::time::Duration;
 use tokio::time::Instant;
 
@@ -12,8 +13,22 @@ pub enum Schedule {
     Daily(Vec<(u8, u8)>),
     /// Run once after delay.
     Once(Duration),
-    /// Custom schedule with cron-like expression.
-    Cron(String),
+    /// Run at times matching a cron expression.
+    Cron(CronSchedule),
+}
+
+/// Kind of work performed by a scheduled task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskKind {
+    /// Custom task handled by the event consumer.
+    #[default]
+    Custom,
+    /// Evaluate positions against the strategy.
+    Evaluate,
+    /// Collect fees and reinvest them into the position.
+    AutoCompound,
+    /// Send the daily portfolio digest.
+    Digest,
 }
 
 /// A scheduled task.
@@ -23,6 +38,8 @@ pub struct ScheduledTask {
     pub name: String,
     /// Schedule.
     pub schedule: Schedule,
+    /// Kind of work the task performs.
+    pub kind: TaskKind,
     /// Whether task is enabled.
     pub enabled: bool,
     /// Last run time.
@@ -37,12 +54,20 @@ impl ScheduledTask {
         Self {
             name: name.into(),
             schedule,
+            kind: TaskKind::Custom,
             enabled: true,
             last_run: None,
             next_run: None,
         }
     }
 
+    /// Sets the kind of work the task performs.
+    #[must_use]
+    pub fn with_kind(mut self, kind: TaskKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
     /// Disables the task.
     #[must_use]
     pub fn disabled(mut self) -> Self {
@@ -56,6 +81,8 @@ impl ScheduledTask {
 pub struct TaskEvent {
     /// Task name.
     pub task_name: String,
+    /// Kind of work the task performs.
+    pub kind: TaskKind,
     /// Scheduled time.
     pub scheduled_at: Instant,
     /// Actual trigger time.
@@ -86,6 +113,25 @@ impl ScheduleBuilder {
         Schedule::Interval(Duration::from_secs(hours * 60 * 60))
     }
 
+    /// Creates a schedule from a cron expression evaluated in UTC.
+    ///
+    /// # Errors
+    /// Returns an error if the expression is invalid.
+    pub fn cron(expression: &str) -> anyhow::Result<Schedule> {
+        Ok(Schedule::Cron(CronSchedule::parse(expression)?))
+    }
+
+    /// Creates a schedule from a cron expression evaluated in an IANA
+    /// timezone (e.g. `Europe/London`).
+    ///
+    /// # Errors
+    /// Returns an error if the expression or timezone is invalid.
+    pub fn cron_in(expression: &str, timezone: &str) -> anyhow::Result<Schedule> {
+        Ok(Schedule::Cron(CronSchedule::parse_in(
+            expression, timezone,
+        )?))
+    }
+
     /// Creates a one-time schedule.
     pub fn once_after(delay: Duration) -> Schedule {
         Schedule::Once(delay)
@@ -111,10 +157,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cron_schedule_builder() {
+        let schedule = ScheduleBuilder::cron_in("5 */8 * * *", "Asia/Singapore").unwrap();
+        let Schedule::Cron(cron) = schedule else {
+            panic!("expected a cron schedule");
+        };
+        assert_eq!(cron.expression(), "5 */8 * * *");
+        assert_eq!(cron.timezone(), chrono_tz::Asia::Singapore);
+
+        assert!(ScheduleBuilder::cron("not a cron").is_err());
+    }
+
     #[test]
     fn test_scheduled_task() {
         let task = ScheduledTask::new("test", ScheduleBuilder::every_secs(60));
         assert!(task.enabled);
         assert_eq!(task.name, "test");
+        assert_eq!(task.kind, TaskKind::Custom);
+
+        let task = task.with_kind(TaskKind::AutoCompound);
+        assert_eq!(task.kind, TaskKind::AutoCompound);
     }
 }
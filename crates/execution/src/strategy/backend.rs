@@ -0,0 +1,96 @@
+//! Execution backends for strategy decisions.
+
+use super::{DecisionContext, RebalanceExecutor, RebalanceParams, RebalanceResult};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use tracing::info;
+
+/// Backend that carries out strategy decisions.
+///
+/// The live backend sends transactions on-chain; alternative backends
+/// (such as paper trading) can simulate fills without touching the chain.
+#[async_trait]
+pub trait ExecutionBackend: Send + Sync {
+    /// Returns the backend name.
+    fn name(&self) -> &str;
+
+    /// Rebalances a position into a new range.
+    async fn rebalance(&self, ctx: &DecisionContext, params: RebalanceParams) -> RebalanceResult;
+
+    /// Closes a position.
+    async fn close_position(&self, ctx: &DecisionContext) -> anyhow::Result<()>;
+
+    /// Swaps the proceeds of a closed position to the pool's quote token.
+    async fn swap_to_quote(&self, ctx: &DecisionContext) -> anyhow::Result<()>;
+
+    /// Collects accumulated fees from a position.
+    async fn collect_fees(&self, ctx: &DecisionContext) -> anyhow::Result<()>;
+
+    /// Adjusts position liquidity by a USD amount (positive to add, negative to remove).
+    async fn adjust_liquidity(
+        &self,
+        ctx: &DecisionContext,
+        delta_usd: Decimal,
+    ) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+impl ExecutionBackend for RebalanceExecutor {
+    fn name(&self) -> &str {
+        "live"
+    }
+
+    async fn rebalance(&self, _ctx: &DecisionContext, params: RebalanceParams) -> RebalanceResult {
+        self.execute(params).await
+    }
+
+    async fn close_position(&self, ctx: &DecisionContext) -> anyhow::Result<()> {
+        if self.is_dry_run() {
+            info!(position = %ctx.position.address, "Dry run mode - would close position");
+            return Ok(());
+        }
+        self.exit(&ctx.position).await.map(|_| ())
+    }
+
+    async fn swap_to_quote(&self, ctx: &DecisionContext) -> anyhow::Result<()> {
+        if self.is_dry_run() {
+            info!(
+                position = %ctx.position.address,
+                quote_mint = %ctx.pool.token_mint_b,
+                "Dry run mode - would swap to quote"
+            );
+            return Ok(());
+        }
+        self.sell_proceeds(&ctx.position.address, &ctx.pool)
+            .await
+            .map(|_| ())
+    }
+
+    async fn collect_fees(&self, ctx: &DecisionContext) -> anyhow::Result<()> {
+        if self.is_dry_run() {
+            info!(position = %ctx.position.address, "Dry run mode - would collect fees");
+            return Ok(());
+        }
+        self.collect(&ctx.position.address).await.map(|_| ())
+    }
+
+    async fn adjust_liquidity(
+        &self,
+        ctx: &DecisionContext,
+        delta_usd: Decimal,
+    ) -> anyhow::Result<()> {
+        if self.is_dry_run() {
+            info!(
+                position = %ctx.position.address,
+                delta = %delta_usd,
+                "Dry run mode - would adjust liquidity"
+            );
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Liquidity adjustment of {} USD for position {} is not supported by the live backend",
+            delta_usd,
+            ctx.position.address
+        )
+    }
+}
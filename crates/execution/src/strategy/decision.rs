@@ -1,9 +1,13 @@
 //! Decision engine for strategy execution.
 
-use super::Decision;
+use super::{Decision, StopLossConfig};
 use crate::monitor::MonitoredPosition;
-use clmm_lp_protocols::prelude::WhirlpoolState;
+use chrono::{DateTime, Utc};
+use clmm_lp_protocols::prelude::{WhirlpoolState, tick_to_price};
 use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
 use tracing::debug;
 
 /// Configuration for the decision engine.
@@ -15,12 +19,24 @@ pub struct DecisionConfig {
     pub il_close_threshold: Decimal,
     /// Minimum time between rebalances in hours.
     pub min_rebalance_interval_hours: u64,
+    /// How far the price must move beyond the range boundary before an
+    /// out-of-range rebalance is considered (as percentage of the boundary price).
+    pub hysteresis_margin_pct: Decimal,
+    /// Consecutive evaluations the rebalance condition must hold before acting.
+    pub confirm_evaluations: u32,
+    /// Hard cooldown after an executed rebalance in seconds.
+    ///
+    /// Unlike `min_rebalance_interval_hours`, this is tracked by the engine
+    /// itself and applies even when no lifecycle history is available.
+    pub rebalance_cooldown_secs: u64,
     /// Range width for new positions (as percentage).
     pub range_width_pct: Decimal,
     /// Whether to auto-collect fees.
     pub auto_collect_fees: bool,
     /// Minimum fees to collect in USD.
     pub min_fees_to_collect: Decimal,
+    /// Stop-loss rules for emergency exits (disabled if unset).
+    pub stop_loss: Option<StopLossConfig>,
 }
 
 impl Default for DecisionConfig {
@@ -29,9 +45,13 @@ impl Default for DecisionConfig {
             il_rebalance_threshold: Decimal::new(5, 2), // 5%
             il_close_threshold: Decimal::new(15, 2),    // 15%
             min_rebalance_interval_hours: 24,
+            hysteresis_margin_pct: Decimal::ZERO,
+            confirm_evaluations: 1,
+            rebalance_cooldown_secs: 60 * 60,     // 1 hour
             range_width_pct: Decimal::new(10, 2), // 10%
             auto_collect_fees: true,
             min_fees_to_collect: Decimal::new(10, 0), // $10
+            stop_loss: None,
         }
     }
 }
@@ -45,19 +65,35 @@ pub struct DecisionContext {
     pub pool: WhirlpoolState,
     /// Hours since last rebalance.
     pub hours_since_rebalance: u64,
+    /// Highest observed position value in USD.
+    pub peak_value_usd: Decimal,
+}
+
+/// Per-position state used for hysteresis and cooldowns.
+#[derive(Debug, Clone, Default)]
+struct RebalanceGuard {
+    /// Consecutive evaluations where the rebalance condition held.
+    breach_streak: u32,
+    /// When the last rebalance was executed.
+    last_rebalance: Option<DateTime<Utc>>,
 }
 
 /// Decision engine for automated strategy execution.
 pub struct DecisionEngine {
     /// Configuration.
     config: DecisionConfig,
+    /// Hysteresis and cooldown state by position.
+    guards: Mutex<HashMap<Pubkey, RebalanceGuard>>,
 }
 
 impl DecisionEngine {
     /// Creates a new decision engine.
     #[must_use]
     pub fn new(config: DecisionConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            guards: Mutex::new(HashMap::new()),
+        }
     }
 
     /// Makes a decision for a position.
@@ -72,12 +108,29 @@ impl DecisionEngine {
             "Evaluating position"
         );
 
+        // Check stop-loss limits - exit before any other rule
+        if let Some(stop_loss) = &self.config.stop_loss
+            && let Some(trigger) = stop_loss.check(context)
+        {
+            debug!(trigger = %trigger.description(), "Stop-loss breached, recommending exit");
+            return Decision::EmergencyExit {
+                trigger,
+                swap_to_quote: stop_loss.swap_to_quote,
+            };
+        }
+
         // Check for critical IL - close position
         if position.pnl.il_pct.abs() > self.config.il_close_threshold {
             debug!("IL exceeds close threshold, recommending close");
             return Decision::Close;
         }
 
+        // Track how long the rebalance condition has held
+        let out_of_range = self.is_out_of_range(context);
+        let il_breached = position.pnl.il_pct.abs() > self.config.il_rebalance_threshold;
+        let breach_streak =
+            self.update_breach_streak(&position.address, out_of_range || il_breached);
+
         // Check for fee collection
         if self.config.auto_collect_fees && position.pnl.fees_usd > self.config.min_fees_to_collect
         {
@@ -85,40 +138,109 @@ impl DecisionEngine {
             return Decision::CollectFees;
         }
 
-        // Check if out of range
-        if !position.in_range {
-            // Check if enough time has passed since last rebalance
-            if context.hours_since_rebalance >= self.config.min_rebalance_interval_hours {
-                let (new_lower, new_upper) = self.calculate_new_range(pool);
-                debug!(
-                    new_lower = new_lower,
-                    new_upper = new_upper,
-                    "Position out of range, recommending rebalance"
-                );
-                return Decision::Rebalance {
-                    new_tick_lower: new_lower,
-                    new_tick_upper: new_upper,
-                };
-            }
+        if !out_of_range && !il_breached {
+            return Decision::Hold;
         }
 
-        // Check for IL-based rebalancing
-        if position.pnl.il_pct.abs() > self.config.il_rebalance_threshold
-            && context.hours_since_rebalance >= self.config.min_rebalance_interval_hours
-        {
-            let (new_lower, new_upper) = self.calculate_new_range(pool);
+        // Require the condition to persist before acting
+        if breach_streak < self.config.confirm_evaluations {
             debug!(
-                il_pct = %position.pnl.il_pct,
-                "IL exceeds threshold, recommending rebalance"
+                breach_streak = breach_streak,
+                required = self.config.confirm_evaluations,
+                "Rebalance condition not yet confirmed"
             );
-            return Decision::Rebalance {
-                new_tick_lower: new_lower,
-                new_tick_upper: new_upper,
-            };
+            return Decision::Hold;
+        }
+
+        // Check if enough time has passed since last rebalance
+        if context.hours_since_rebalance < self.config.min_rebalance_interval_hours {
+            return Decision::Hold;
+        }
+
+        if let Some(remaining) = self.cooldown_remaining(&position.address) {
+            debug!(
+                remaining_secs = remaining,
+                "Rebalance cooldown active, holding"
+            );
+            return Decision::Hold;
         }
 
-        // Default: hold
-        Decision::Hold
+        let (new_lower, new_upper) = self.calculate_new_range(pool);
+        debug!(
+            new_lower = new_lower,
+            new_upper = new_upper,
+            out_of_range = out_of_range,
+            il_pct = %position.pnl.il_pct,
+            "Rebalance condition confirmed, recommending rebalance"
+        );
+        Decision::Rebalance {
+            new_tick_lower: new_lower,
+            new_tick_upper: new_upper,
+        }
+    }
+
+    /// Returns true if the position is out of range by more than the hysteresis margin.
+    fn is_out_of_range(&self, context: &DecisionContext) -> bool {
+        if context.position.in_range {
+            return false;
+        }
+        if self.config.hysteresis_margin_pct.is_zero() {
+            return true;
+        }
+
+        let tick = context.pool.tick_current;
+        let on_chain = &context.position.on_chain;
+        let boundary = if tick < on_chain.tick_lower {
+            on_chain.tick_lower
+        } else if tick >= on_chain.tick_upper {
+            on_chain.tick_upper
+        } else {
+            return false;
+        };
+
+        let boundary_price = tick_to_price(boundary);
+        if boundary_price.is_zero() {
+            return false;
+        }
+        let distance = ((tick_to_price(tick) - boundary_price) / boundary_price).abs();
+        distance > self.config.hysteresis_margin_pct
+    }
+
+    /// Updates the breach streak for a position and returns the new value.
+    fn update_breach_streak(&self, position: &Pubkey, breached: bool) -> u32 {
+        let mut guards = self.guards.lock().unwrap_or_else(PoisonError::into_inner);
+        let guard = guards.entry(*position).or_default();
+        guard.breach_streak = if breached {
+            guard.breach_streak.saturating_add(1)
+        } else {
+            0
+        };
+        guard.breach_streak
+    }
+
+    /// Returns the remaining cooldown in seconds, if a cooldown is active.
+    fn cooldown_remaining(&self, position: &Pubkey) -> Option<i64> {
+        let guards = self.guards.lock().unwrap_or_else(PoisonError::into_inner);
+        let last = guards.get(position)?.last_rebalance?;
+        let elapsed = (Utc::now() - last).num_seconds();
+        let remaining = self.config.rebalance_cooldown_secs as i64 - elapsed;
+        (remaining > 0).then_some(remaining)
+    }
+
+    /// Records an executed rebalance, starting the cooldown and resetting hysteresis.
+    pub fn record_rebalance(&self, position: &Pubkey) {
+        let mut guards = self.guards.lock().unwrap_or_else(PoisonError::into_inner);
+        let guard = guards.entry(*position).or_default();
+        guard.breach_streak = 0;
+        guard.last_rebalance = Some(Utc::now());
+    }
+
+    /// Clears hysteresis and cooldown state for a position that is no longer managed.
+    pub fn forget(&self, position: &Pubkey) {
+        self.guards
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(position);
     }
 
     /// Calculates a new range centered on current price.
@@ -175,6 +297,7 @@ mod tests {
                 ..Default::default()
             },
             in_range,
+            out_of_range_since: None,
             last_updated: chrono::Utc::now(),
         };
 
@@ -197,6 +320,7 @@ mod tests {
             position,
             pool,
             hours_since_rebalance: 48,
+            peak_value_usd: Decimal::ZERO,
         }
     }
 
@@ -226,4 +350,88 @@ mod tests {
         let decision = engine.decide(&context);
         assert!(matches!(decision, Decision::Close));
     }
+
+    #[test]
+    fn test_stop_loss_precedes_close() {
+        let engine = DecisionEngine::new(DecisionConfig {
+            stop_loss: Some(StopLossConfig {
+                max_il_pct: Some(Decimal::new(10, 2)), // 10%
+                swap_to_quote: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let context = create_test_context(true, Decimal::new(20, 2)); // 20% IL
+
+        let decision = engine.decide(&context);
+        assert!(matches!(
+            decision,
+            Decision::EmergencyExit {
+                swap_to_quote: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_confirm_evaluations_delays_rebalance() {
+        let engine = DecisionEngine::new(DecisionConfig {
+            confirm_evaluations: 3,
+            ..Default::default()
+        });
+        let context = create_test_context(false, Decimal::ZERO);
+
+        assert!(matches!(engine.decide(&context), Decision::Hold));
+        assert!(matches!(engine.decide(&context), Decision::Hold));
+        assert!(matches!(
+            engine.decide(&context),
+            Decision::Rebalance { .. }
+        ));
+
+        // Streak resets once the condition clears
+        let recovered = DecisionContext {
+            position: MonitoredPosition {
+                in_range: true,
+                ..context.position.clone()
+            },
+            ..context.clone()
+        };
+        assert!(matches!(engine.decide(&recovered), Decision::Hold));
+        assert!(matches!(engine.decide(&context), Decision::Hold));
+    }
+
+    #[test]
+    fn test_hysteresis_margin() {
+        let engine = DecisionEngine::new(DecisionConfig {
+            hysteresis_margin_pct: Decimal::new(2, 2), // 2%
+            ..Default::default()
+        });
+
+        // 100 ticks (~1%) above the upper bound stays within the margin
+        let mut context = create_test_context(false, Decimal::ZERO);
+        context.pool.tick_current = 1100;
+        assert!(matches!(engine.decide(&context), Decision::Hold));
+
+        // 500 ticks (~5%) above the upper bound exceeds it
+        context.pool.tick_current = 1500;
+        assert!(matches!(
+            engine.decide(&context),
+            Decision::Rebalance { .. }
+        ));
+    }
+
+    #[test]
+    fn test_cooldown_after_rebalance() {
+        let engine = DecisionEngine::default();
+        let context = create_test_context(false, Decimal::ZERO);
+
+        engine.record_rebalance(&context.position.address);
+        assert!(matches!(engine.decide(&context), Decision::Hold));
+
+        engine.forget(&context.position.address);
+        assert!(matches!(
+            engine.decide(&context),
+            Decision::Rebalance { .. }
+        ));
+    }
 }
@@ -1,26 +1,40 @@
 //! Strategy executor for automated position management.
 
 use super::{
-    Decision, DecisionConfig, DecisionContext, DecisionEngine, RebalanceConfig, RebalanceExecutor,
-    RebalanceParams,
+    AUTO_COMPOUND_TASK, AutoCompoundConfig, Decision, DecisionConfig, DecisionContext,
+    DecisionEngine, ExecutionBackend, ExitTrigger, PendingExit, RebalanceConfig, RebalanceExecutor,
+    RebalanceParams, ShadowRecord, TranscriptStep,
 };
+use crate::alerts::{Alert, AlertLevel, AlertType};
 use crate::emergency::CircuitBreaker;
+use crate::error::ExecutionError;
+use crate::hedging::DeltaHedger;
 use crate::lifecycle::{LifecycleTracker, RebalanceReason};
-use crate::monitor::PositionMonitor;
+use crate::monitor::{MonitoredPosition, PositionMonitor};
+use crate::oracle::PriceGuard;
+use crate::persistence::{AuditEntry, AuditKind, AuditLog};
+use crate::scheduler::{CronSchedule, Schedule, ScheduledTask, Scheduler, TaskEvent, TaskKind};
+use crate::swap::JupiterSwapper;
 use crate::transaction::TransactionManager;
 use crate::wallet::Wallet;
+use clmm_lp_config::prelude::ExecutionSettings;
 use clmm_lp_protocols::prelude::*;
 use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{RwLock, mpsc};
 use tokio::time::interval;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, instrument, warn};
 
 /// Configuration for strategy execution.
 #[derive(Debug, Clone)]
 pub struct ExecutorConfig {
     /// Evaluation interval in seconds.
     pub eval_interval_secs: u64,
+    /// Cron schedule for evaluations, overriding the interval.
+    pub eval_cron: Option<CronSchedule>,
     /// Whether to execute decisions automatically.
     pub auto_execute: bool,
     /// Whether to require confirmation before executing.
@@ -28,21 +42,51 @@ pub struct ExecutorConfig {
     /// Maximum slippage tolerance (as percentage).
     pub max_slippage_pct: Decimal,
     /// Dry run mode - simulate but don't execute.
+    ///
+    /// Planned actions are recorded as a transcript in the audit log.
     pub dry_run: bool,
+    /// Shadow mode - record decisions but leave them to an operator.
+    ///
+    /// Every decision is recorded with its simulated outcome so it can later
+    /// be reconciled against what actually happened on-chain. Takes
+    /// precedence over auto-execution.
+    pub shadow: bool,
 }
 
 impl Default for ExecutorConfig {
     fn default() -> Self {
         Self {
             eval_interval_secs: 300, // 5 minutes
-            auto_execute: false,     // Require manual confirmation by default
+            eval_cron: None,
+            auto_execute: false, // Require manual confirmation by default
             require_confirmation: true,
             max_slippage_pct: Decimal::new(5, 3), // 0.5%
             dry_run: false,
+            shadow: false,
         }
     }
 }
 
+impl ExecutorConfig {
+    /// Creates an executor config from the shared configuration's
+    /// execution section.
+    #[must_use]
+    pub fn from_settings(settings: &ExecutionSettings) -> Self {
+        Self {
+            eval_interval_secs: settings.eval_interval_secs,
+            eval_cron: None,
+            auto_execute: settings.auto_execute,
+            require_confirmation: !settings.auto_execute,
+            max_slippage_pct: Decimal::new(i64::from(settings.max_slippage_bps), 4),
+            dry_run: settings.dry_run,
+            shadow: false,
+        }
+    }
+}
+
+/// Name of the scheduled evaluation task.
+const EVALUATE_TASK: &str = "evaluate";
+
 /// Strategy executor for automated position management.
 pub struct StrategyExecutor {
     /// Position monitor.
@@ -54,10 +98,28 @@ pub struct StrategyExecutor {
     tx_manager: Arc<TransactionManager>,
     /// Rebalance executor.
     rebalance_executor: RebalanceExecutor,
+    /// Alternative execution backend (e.g. paper trading).
+    backend: Option<Arc<dyn ExecutionBackend>>,
+    /// Auto-compounding configuration.
+    auto_compound: Option<AutoCompoundConfig>,
     /// Circuit breaker.
     circuit_breaker: Arc<CircuitBreaker>,
     /// Lifecycle tracker.
     lifecycle: Arc<LifecycleTracker>,
+    /// Delta hedger for positions.
+    hedger: Option<Arc<DeltaHedger>>,
+    /// Oracle price guard for actions deploying liquidity.
+    price_guard: Option<Arc<PriceGuard>>,
+    /// Audit log for decisions and outcomes.
+    audit: Option<Arc<dyn AuditLog>>,
+    /// Strategy ID recorded in audit entries.
+    strategy_id: Option<String>,
+    /// Peak observed value per position, for drawdown limits.
+    peak_values: RwLock<HashMap<Pubkey, Decimal>>,
+    /// Emergency exits awaiting confirmation.
+    pending_exits: RwLock<HashMap<Pubkey, PendingExit>>,
+    /// Alert callback.
+    alert_callback: Option<Box<dyn Fn(Alert) + Send + Sync>>,
     /// Wallet for signing.
     wallet: Option<Arc<Wallet>>,
     /// Configuration.
@@ -93,8 +155,17 @@ impl StrategyExecutor {
             decision_engine: DecisionEngine::default(),
             tx_manager,
             rebalance_executor,
+            backend: None,
+            auto_compound: None,
             circuit_breaker,
             lifecycle,
+            hedger: None,
+            price_guard: None,
+            audit: None,
+            strategy_id: None,
+            peak_values: RwLock::new(HashMap::new()),
+            pending_exits: RwLock::new(HashMap::new()),
+            alert_callback: None,
             wallet: None,
             config,
             running: std::sync::atomic::AtomicBool::new(false),
@@ -108,6 +179,12 @@ impl StrategyExecutor {
         self.rebalance_executor.set_wallet(wallet);
     }
 
+    /// Sets the Jupiter client used to swap to a new range's token ratio
+    /// when rebalancing.
+    pub fn set_swapper(&mut self, swapper: Arc<JupiterSwapper>) {
+        self.rebalance_executor.set_swapper(swapper);
+    }
+
     /// Sets the decision engine configuration.
     pub fn set_decision_config(&mut self, config: DecisionConfig) {
         self.decision_engine.set_config(config);
@@ -119,17 +196,101 @@ impl StrategyExecutor {
         self.rebalance_executor.set_dry_run(dry_run);
     }
 
+    /// Routes decisions through an alternative execution backend.
+    ///
+    /// When unset, decisions are executed live via the rebalance executor.
+    pub fn set_backend(&mut self, backend: Arc<dyn ExecutionBackend>) {
+        self.backend = Some(backend);
+    }
+
+    /// Gets the name of the active execution backend.
+    pub fn backend_name(&self) -> &str {
+        self.backend().name()
+    }
+
+    /// Gets the active execution backend.
+    fn backend(&self) -> &dyn ExecutionBackend {
+        match &self.backend {
+            Some(backend) => backend.as_ref(),
+            None => &self.rebalance_executor,
+        }
+    }
+
+    /// Enables auto-compounding of accrued fees.
+    pub fn set_auto_compound(&mut self, config: AutoCompoundConfig) {
+        self.auto_compound = Some(config);
+    }
+
+    /// Replaces the circuit breaker with a shared instance.
+    ///
+    /// Executors sharing one breaker are halted together when it trips,
+    /// either after consecutive failures or via the operator kill switch.
+    pub fn set_circuit_breaker(&mut self, circuit_breaker: Arc<CircuitBreaker>) {
+        self.circuit_breaker = circuit_breaker;
+    }
+
+    /// Replaces the lifecycle tracker with a shared instance.
+    ///
+    /// Rebalances and fee collections are then recorded in the shared
+    /// history, which is persisted when the tracker has a state store.
+    pub fn set_lifecycle(&mut self, lifecycle: Arc<LifecycleTracker>) {
+        self.rebalance_executor.set_lifecycle(lifecycle.clone());
+        self.lifecycle = lifecycle;
+    }
+
+    /// Hedges position delta with a perp position after each evaluation.
+    ///
+    /// The hedge is closed when its position is closed. Hedging only runs
+    /// when executing live: it is skipped in dry run mode and with an
+    /// alternative backend. Funding is recorded in the lifecycle history.
+    pub fn set_hedger(&mut self, hedger: Arc<DeltaHedger>) {
+        self.hedger = Some(hedger);
+    }
+
+    /// Checks pool prices against an oracle before rebalancing or adding
+    /// liquidity.
+    ///
+    /// The action is aborted, and an alert raised, when the pool price
+    /// diverges from the oracle or no usable oracle price is available.
+    pub fn set_price_guard(&mut self, guard: Arc<PriceGuard>) {
+        self.price_guard = Some(guard);
+    }
+
+    /// Gets the delta hedger, if hedging is enabled.
+    pub fn hedger(&self) -> Option<&Arc<DeltaHedger>> {
+        self.hedger.as_ref()
+    }
+
+    /// Records evaluations, proposed actions and outcomes in an audit log.
+    ///
+    /// Entries are tagged with `strategy_id` when given.
+    pub fn set_audit_log(&mut self, audit: Arc<dyn AuditLog>, strategy_id: Option<String>) {
+        self.audit = Some(audit);
+        self.strategy_id = strategy_id;
+    }
+
     /// Gets the circuit breaker.
     pub fn circuit_breaker(&self) -> &Arc<CircuitBreaker> {
         &self.circuit_breaker
     }
 
+    /// Sets the alert callback for stop-loss and oracle guard notifications.
+    pub fn set_alert_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(Alert) + Send + Sync + 'static,
+    {
+        self.alert_callback = Some(Box::new(callback));
+    }
+
     /// Gets the lifecycle tracker.
     pub fn lifecycle(&self) -> &Arc<LifecycleTracker> {
         &self.lifecycle
     }
 
     /// Starts the strategy execution loop.
+    ///
+    /// Positions are evaluated on the configured interval, or at the times
+    /// matching the evaluation cron schedule when one is set.
     pub async fn start(&self) {
         self.running
             .store(true, std::sync::atomic::Ordering::SeqCst);
@@ -139,43 +300,113 @@ impl StrategyExecutor {
 
         info!(
             interval_secs = self.config.eval_interval_secs,
+            eval_cron = ?self.config.eval_cron.as_ref().map(ToString::to_string),
             auto_execute = self.config.auto_execute,
             dry_run = self.config.dry_run,
+            shadow = self.config.shadow,
+            auto_compound = self.auto_compound.is_some(),
             "Starting strategy executor"
         );
 
-        while self.running.load(std::sync::atomic::Ordering::SeqCst) {
-            ticker.tick().await;
-
-            // Check circuit breaker
-            if !self.circuit_breaker.is_allowed().await {
-                warn!("Circuit breaker open, skipping evaluation");
-                continue;
+        // Debug builds check the domain math before executing on it
+        if let Some(report) = clmm_lp_domain::math::verification::debug_verify() {
+            for divergence in &report.divergences {
+                warn!(%divergence, "Domain math diverges from Whirlpool reference vectors");
             }
+        }
+
+        // Schedule cron evaluations and auto-compounding
+        let mut scheduler = Scheduler::new();
+        if let Some(cron) = &self.config.eval_cron {
+            scheduler.add_task(
+                ScheduledTask::new(EVALUATE_TASK, Schedule::Cron(cron.clone()))
+                    .with_kind(TaskKind::Evaluate),
+            );
+        }
+        if let Some(config) = &self.auto_compound {
+            scheduler.add_task(
+                ScheduledTask::new(AUTO_COMPOUND_TASK, config.schedule())
+                    .with_kind(TaskKind::AutoCompound),
+            );
+        }
 
-            if let Err(e) = self.evaluate_all().await {
-                error!(error = %e, "Strategy evaluation failed");
-                self.circuit_breaker.record_failure().await;
-            } else {
-                self.circuit_breaker.record_success().await;
+        let mut scheduled_events = None;
+        let mut scheduler_handle = None;
+        if !scheduler.tasks().is_empty() {
+            scheduled_events = scheduler.take_receiver();
+            scheduler_handle = Some(scheduler.handle());
+            tokio::spawn(async move { scheduler.start().await });
+        }
+
+        let use_interval = self.config.eval_cron.is_none();
+        while self.running.load(std::sync::atomic::Ordering::SeqCst) {
+            tokio::select! {
+                _ = ticker.tick(), if use_interval => {
+                    self.run_evaluation().await;
+                }
+                Some(event) = Self::next_event(&mut scheduled_events) => {
+                    match event.kind {
+                        TaskKind::Evaluate => self.run_evaluation().await,
+                        TaskKind::AutoCompound => {
+                            if let Err(e) = self.compound_all().await {
+                                error!(error = %e, "Auto-compound failed");
+                                self.circuit_breaker.record_failure().await;
+                            }
+                        }
+                        TaskKind::Custom | TaskKind::Digest => {}
+                    }
+                }
             }
         }
 
+        if let Some(handle) = scheduler_handle {
+            handle.stop();
+        }
+
         info!("Strategy executor stopped");
     }
 
+    /// Evaluates all positions unless the circuit breaker is open.
+    async fn run_evaluation(&self) {
+        if !self.circuit_breaker.is_allowed().await {
+            warn!("Circuit breaker open, skipping evaluation");
+        } else if let Err(e) = self.evaluate_all().await {
+            error!(error = %e, "Strategy evaluation failed");
+            self.circuit_breaker.record_failure().await;
+        } else {
+            self.circuit_breaker.record_success().await;
+        }
+    }
+
+    /// Receives the next scheduler event, waiting forever if there is no scheduler.
+    async fn next_event(events: &mut Option<mpsc::Receiver<TaskEvent>>) -> Option<TaskEvent> {
+        match events {
+            Some(rx) => rx.recv().await,
+            None => std::future::pending().await,
+        }
+    }
+
     /// Stops the strategy execution loop.
     pub fn stop(&self) {
         self.running
             .store(false, std::sync::atomic::Ordering::SeqCst);
     }
 
+    /// Returns whether the execution loop is running.
+    pub fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
     /// Evaluates all monitored positions.
+    #[instrument(skip_all)]
     async fn evaluate_all(&self) -> anyhow::Result<()> {
         let positions = self.monitor.get_positions().await;
 
         debug!(count = positions.len(), "Evaluating positions");
 
+        let total = positions.len();
+        let mut failed = 0;
+
         for position in positions {
             if let Err(e) = self.evaluate_position(&position).await {
                 warn!(
@@ -183,17 +414,372 @@ impl StrategyExecutor {
                     error = %e,
                     "Failed to evaluate position"
                 );
+                failed += 1;
             }
         }
 
+        if failed > 0 {
+            anyhow::bail!("{} of {} position evaluations failed", failed, total);
+        }
+
         Ok(())
     }
 
     /// Evaluates a single position.
-    async fn evaluate_position(
+    #[instrument(skip_all, fields(position = %position.address))]
+    async fn evaluate_position(&self, position: &MonitoredPosition) -> anyhow::Result<()> {
+        let context = self.build_context(position).await;
+        let decision = self.decision_engine.decide(&context);
+
+        self.audit(
+            AuditEntry::new(AuditKind::Evaluation, decision.name())
+                .with_position(&position.address)
+                .with_details(serde_json::json!({
+                    "decision": decision.description(),
+                    "in_range": position.in_range,
+                    "pool_tick": context.pool.tick_current,
+                    "tick_lower": position.on_chain.tick_lower,
+                    "tick_upper": position.on_chain.tick_upper,
+                    "il_pct": position.pnl.il_pct,
+                    "value_usd": position.pnl.current_value_usd,
+                    "hours_since_rebalance": context.hours_since_rebalance,
+                })),
+        )
+        .await;
+
+        if let Decision::EmergencyExit {
+            trigger,
+            swap_to_quote,
+        } = &decision
+        {
+            return self
+                .handle_exit(&context, &decision, trigger, *swap_to_quote)
+                .await;
+        }
+
+        // The breach has cleared before the exit was confirmed
+        if self
+            .pending_exits
+            .write()
+            .await
+            .remove(&position.address)
+            .is_some()
+        {
+            info!(position = %position.address, "Stop-loss condition cleared");
+        }
+
+        if decision.requires_transaction() {
+            info!(
+                position = %position.address,
+                decision = %decision.description(),
+                dry_run = self.config.dry_run,
+                shadow = self.config.shadow,
+                "Decision requires action"
+            );
+
+            self.audit(
+                AuditEntry::new(AuditKind::Proposal, decision.name())
+                    .with_position(&position.address)
+                    .with_details(serde_json::json!({
+                        "decision": decision.description(),
+                        "auto_execute": self.config.auto_execute,
+                        "dry_run": self.config.dry_run,
+                        "shadow": self.config.shadow,
+                    })),
+            )
+            .await;
+
+            if self.config.shadow {
+                self.record_shadow(&context, &decision).await;
+            } else if self.config.auto_execute {
+                self.execute_decision(&context, &decision).await?;
+            } else if self.config.dry_run {
+                self.record_transcript(&context, &decision).await;
+            }
+        }
+
+        // An executed close has already unwound the position's hedge
+        if !matches!(decision, Decision::Close) || !self.executes() {
+            self.hedge(&context).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the hedger if hedges should be adjusted live.
+    fn live_hedger(&self) -> Option<&Arc<DeltaHedger>> {
+        if self.config.dry_run || self.config.shadow || self.backend.is_some() {
+            return None;
+        }
+        self.hedger.as_ref()
+    }
+
+    /// Updates the hedge of a position.
+    async fn hedge(&self, context: &DecisionContext) -> anyhow::Result<()> {
+        let Some(hedger) = self.live_hedger() else {
+            return Ok(());
+        };
+
+        let update = hedger.rebalance(context, &self.lifecycle).await?;
+        if let Some(order_base) = update.order_base {
+            let mut entry = AuditEntry::new(AuditKind::Transaction, "hedge")
+                .with_position(&context.position.address)
+                .with_details(serde_json::json!({
+                    "market": hedger.config().market.name,
+                    "order_base": order_base,
+                    "target_base": update.target_base,
+                }));
+            if let Some(signature) = &update.signature {
+                entry = entry.with_signature(signature);
+            }
+            self.audit(entry).await;
+        }
+
+        Ok(())
+    }
+
+    /// Closes the hedge of a closed position.
+    async fn close_hedge(&self, position: &MonitoredPosition) -> anyhow::Result<()> {
+        let Some(hedger) = self.live_hedger() else {
+            return Ok(());
+        };
+
+        let update = hedger
+            .close(&position.address, &position.pool, &self.lifecycle)
+            .await?;
+        if let Some(order_base) = update.order_base {
+            let mut entry = AuditEntry::new(AuditKind::Transaction, "close_hedge")
+                .with_position(&position.address)
+                .with_details(serde_json::json!({
+                    "market": hedger.config().market.name,
+                    "order_base": order_base,
+                    "funding_usd": update.funding_usd,
+                }));
+            if let Some(signature) = &update.signature {
+                entry = entry.with_signature(signature);
+            }
+            self.audit(entry).await;
+        }
+
+        Ok(())
+    }
+
+    /// Handles a breached stop-loss limit.
+    ///
+    /// Exits immediately when auto-execution is enabled and neither the executor
+    /// nor the stop-loss requires confirmation. Otherwise the exit is held for an operator and
+    /// its alert is escalated to critical if it stays unconfirmed. In shadow mode
+    /// a newly held exit is also recorded for reconciliation.
+    #[instrument(skip_all, fields(position = %context.position.address))]
+    async fn handle_exit(
         &self,
-        position: &crate::monitor::MonitoredPosition,
+        context: &DecisionContext,
+        decision: &Decision,
+        trigger: &ExitTrigger,
+        swap_to_quote: bool,
     ) -> anyhow::Result<()> {
+        let position = &context.position;
+        let Some(stop_loss) = &self.decision_engine.config().stop_loss else {
+            return Ok(());
+        };
+
+        let confirmed = !self.config.require_confirmation && !stop_loss.require_confirmation;
+        if self.executes() && confirmed {
+            warn!(
+                position = %position.address,
+                trigger = %trigger.description(),
+                "Stop-loss breached, exiting position"
+            );
+            self.raise_alert(
+                AlertLevel::Critical,
+                AlertType::StopLoss,
+                position,
+                format!("Stop-loss exit executing: {}", trigger.description()),
+            );
+            return self.execute_decision(context, decision).await;
+        }
+
+        let mut pending_exits = self.pending_exits.write().await;
+        match pending_exits.get_mut(&position.address) {
+            None => {
+                warn!(
+                    position = %position.address,
+                    trigger = %trigger.description(),
+                    "Stop-loss breached, awaiting exit confirmation"
+                );
+                pending_exits.insert(
+                    position.address,
+                    PendingExit::new(position.address, trigger.clone(), swap_to_quote),
+                );
+                if self.config.shadow {
+                    self.record_shadow(context, decision).await;
+                }
+                self.raise_alert(
+                    AlertLevel::Warning,
+                    AlertType::StopLoss,
+                    position,
+                    format!(
+                        "Stop-loss breached ({}), exit awaiting confirmation",
+                        trigger.description()
+                    ),
+                );
+            }
+            Some(pending) => {
+                pending.trigger = trigger.clone();
+                if pending.needs_escalation(chrono::Utc::now(), stop_loss.escalate_after_secs) {
+                    pending.escalated = true;
+                    self.raise_alert(
+                        AlertLevel::Critical,
+                        AlertType::StopLoss,
+                        position,
+                        format!(
+                            "Stop-loss exit unconfirmed since {} ({})",
+                            pending.detected_at.format("%Y-%m-%d %H:%M:%S"),
+                            trigger.description()
+                        ),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends an alert to the callback, if set.
+    fn raise_alert(
+        &self,
+        level: AlertLevel,
+        alert_type: AlertType,
+        position: &MonitoredPosition,
+        message: String,
+    ) {
+        if let Some(callback) = &self.alert_callback {
+            callback(
+                Alert::new(level, alert_type, message)
+                    .with_position(&position.address)
+                    .with_pool(&position.pool),
+            );
+        }
+    }
+
+    /// Checks the pool price against the oracle, if a guard is set.
+    ///
+    /// Raises a critical alert on divergence and a warning when the oracle
+    /// cannot be used, then returns the error to abort the action.
+    async fn check_oracle_price(&self, context: &DecisionContext) -> anyhow::Result<()> {
+        let Some(guard) = &self.price_guard else {
+            return Ok(());
+        };
+
+        let Err(e) = guard.check(&context.pool).await else {
+            return Ok(());
+        };
+        let level = match ExecutionError::find(&e) {
+            Some(ExecutionError::PriceDivergence { .. }) => AlertLevel::Critical,
+            _ => AlertLevel::Warning,
+        };
+        warn!(
+            position = %context.position.address,
+            error = %e,
+            "Oracle price check failed, aborting"
+        );
+        self.raise_alert(
+            level,
+            AlertType::PriceDivergence,
+            &context.position,
+            format!("Action aborted by oracle price check: {}", e),
+        );
+        Err(e)
+    }
+
+    /// Gets emergency exits awaiting confirmation.
+    pub async fn pending_exits(&self) -> Vec<PendingExit> {
+        self.pending_exits.read().await.values().cloned().collect()
+    }
+
+    /// Confirms and executes a pending emergency exit.
+    #[instrument(skip_all, fields(position = %position))]
+    pub async fn confirm_exit(&self, position: &Pubkey) -> anyhow::Result<()> {
+        let Some(pending) = self.pending_exits.write().await.remove(position) else {
+            return Err(ExecutionError::NoPendingExit(position.to_string()).into());
+        };
+
+        let Some(monitored) = self.monitor.get_position(position).await else {
+            return Err(ExecutionError::PositionNotFound(position.to_string()).into());
+        };
+
+        info!(
+            position = %position,
+            trigger = %pending.trigger.description(),
+            "Emergency exit confirmed"
+        );
+
+        let context = self.build_context(&monitored).await;
+        let decision = Decision::EmergencyExit {
+            trigger: pending.trigger,
+            swap_to_quote: pending.swap_to_quote,
+        };
+        self.execute_decision(&context, &decision).await
+    }
+
+    /// Cancels a pending emergency exit.
+    ///
+    /// Returns false if no exit was pending for the position.
+    pub async fn cancel_exit(&self, position: &Pubkey) -> bool {
+        let cancelled = self.pending_exits.write().await.remove(position).is_some();
+        if cancelled {
+            info!(position = %position, "Emergency exit cancelled");
+        }
+        cancelled
+    }
+
+    /// Collects and reinvests fees for positions where it beats the transaction cost.
+    #[instrument(skip_all)]
+    async fn compound_all(&self) -> anyhow::Result<()> {
+        let Some(config) = &self.auto_compound else {
+            return Ok(());
+        };
+
+        if !self.circuit_breaker.is_allowed().await {
+            warn!("Circuit breaker open, skipping auto-compound");
+            return Ok(());
+        }
+
+        let backend = self.backend();
+
+        for position in self.monitor.get_positions().await {
+            let check = config.check(position.pnl.fees_usd);
+            if !check.should_compound {
+                debug!(
+                    position = %position.address,
+                    fees_usd = %check.fees_usd,
+                    min_required = %check.min_required_usd,
+                    "Fees below compounding threshold"
+                );
+                continue;
+            }
+
+            info!(
+                position = %position.address,
+                fees_usd = %check.fees_usd,
+                backend = backend.name(),
+                "Compounding fees"
+            );
+
+            let context = self.build_context(&position).await;
+            if self.config.shadow {
+                self.record_shadow(&context, &Decision::CollectFees).await;
+                continue;
+            }
+            backend.collect_fees(&context).await?;
+            backend.adjust_liquidity(&context, check.fees_usd).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Builds a decision context for a position.
+    async fn build_context(&self, position: &MonitoredPosition) -> DecisionContext {
         // Fetch current pool state
         let pool = self
             .pool_reader
@@ -201,8 +787,8 @@ impl StrategyExecutor {
             .await
             .unwrap_or_else(|_| WhirlpoolState {
                 address: position.pool.to_string(),
-                token_mint_a: solana_sdk::pubkey::Pubkey::default(),
-                token_mint_b: solana_sdk::pubkey::Pubkey::default(),
+                token_mint_a: Pubkey::default(),
+                token_mint_b: Pubkey::default(),
                 tick_current: 0,
                 tick_spacing: 64,
                 sqrt_price: 1 << 64,
@@ -219,32 +805,24 @@ impl StrategyExecutor {
             .calculate_hours_since_rebalance(&position.address)
             .await;
 
-        let context = DecisionContext {
-            position: position.clone(),
-            pool: pool.clone(),
-            hours_since_rebalance,
+        // Track the peak value for drawdown limits
+        let peak_value_usd = {
+            let mut peaks = self.peak_values.write().await;
+            let peak = peaks.entry(position.address).or_insert(Decimal::ZERO);
+            *peak = (*peak).max(position.pnl.current_value_usd);
+            *peak
         };
 
-        let decision = self.decision_engine.decide(&context);
-
-        if decision.requires_transaction() {
-            info!(
-                position = %position.address,
-                decision = %decision.description(),
-                dry_run = self.config.dry_run,
-                "Decision requires action"
-            );
-
-            if self.config.auto_execute {
-                self.execute_decision(position, &decision, &pool).await?;
-            }
+        DecisionContext {
+            position: position.clone(),
+            pool,
+            hours_since_rebalance,
+            peak_value_usd,
         }
-
-        Ok(())
     }
 
     /// Calculates hours since last rebalance.
-    async fn calculate_hours_since_rebalance(&self, position: &solana_sdk::pubkey::Pubkey) -> u64 {
+    async fn calculate_hours_since_rebalance(&self, position: &Pubkey) -> u64 {
         let events = self.lifecycle.get_events(position).await;
 
         // Find the last rebalance event
@@ -265,16 +843,131 @@ impl StrategyExecutor {
         u64::MAX
     }
 
-    /// Executes a decision.
+    /// Appends an entry to the audit log, if set.
+    ///
+    /// Audit failures are logged but never interrupt execution.
+    async fn audit(&self, entry: AuditEntry) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let entry = entry.with_strategy(self.strategy_id.as_deref());
+        if let Err(e) = audit.append(&entry).await {
+            error!(action = %entry.action, error = %e, "Failed to write audit entry");
+        }
+    }
+
+    /// Records the actions a decision would take in the dry-run transcript.
+    ///
+    /// Transcript steps are appended to the audit log, so they are persisted
+    /// with it and can be queried by strategy.
+    async fn record_transcript(&self, context: &DecisionContext, decision: &Decision) {
+        let step = TranscriptStep::plan(context, decision);
+        info!(
+            position = %context.position.address,
+            action = %step.action,
+            instructions = step.instructions.len(),
+            estimated_cost_lamports = step.estimated_cost_lamports,
+            "Dry run: recorded planned action"
+        );
+
+        let details = serde_json::to_value(&step).unwrap_or_default();
+        self.audit(
+            AuditEntry::new(AuditKind::DryRun, decision.name())
+                .with_position(&context.position.address)
+                .with_details(details),
+        )
+        .await;
+    }
+
+    /// Records a decision and its simulated outcome in shadow mode.
+    ///
+    /// The record is appended to the audit log, where it awaits
+    /// reconciliation against the operator's actual transaction.
+    async fn record_shadow(&self, context: &DecisionContext, decision: &Decision) {
+        let record = ShadowRecord::plan(context, decision);
+        info!(
+            position = %context.position.address,
+            action = %record.planned.action,
+            expected_value_usd = %record.planned.position_value_usd,
+            "Shadow: recorded decision for manual execution"
+        );
+
+        let details = serde_json::to_value(&record).unwrap_or_default();
+        self.audit(
+            AuditEntry::new(AuditKind::Shadow, decision.name())
+                .with_position(&context.position.address)
+                .with_details(details),
+        )
+        .await;
+    }
+
+    /// Returns true if decisions are executed automatically.
+    fn executes(&self) -> bool {
+        self.config.auto_execute && !self.config.shadow
+    }
+
+    /// Executes a decision and records its outcome.
+    #[instrument(
+        skip_all,
+        fields(position = %context.position.address, decision = decision.name())
+    )]
     async fn execute_decision(
         &self,
-        position: &crate::monitor::MonitoredPosition,
+        context: &DecisionContext,
+        decision: &Decision,
+    ) -> anyhow::Result<()> {
+        if self.config.dry_run {
+            self.record_transcript(context, decision).await;
+        }
+
+        let result = self.run_decision(context, decision).await;
+
+        self.audit(
+            AuditEntry::new(AuditKind::Outcome, decision.name())
+                .with_position(&context.position.address)
+                .with_result(&result)
+                .with_details(serde_json::json!({
+                    "decision": decision.description(),
+                    "backend": self.backend_name(),
+                    "dry_run": self.config.dry_run,
+                })),
+        )
+        .await;
+
+        result
+    }
+
+    /// Runs a decision through the active backend.
+    async fn run_decision(
+        &self,
+        context: &DecisionContext,
         decision: &Decision,
-        _pool: &WhirlpoolState,
     ) -> anyhow::Result<()> {
+        let position = &context.position;
+
+        // Re-check the breaker: the kill switch may have been engaged mid-cycle
+        if !self.circuit_breaker.is_allowed().await {
+            warn!(
+                position = %position.address,
+                "Circuit breaker open, skipping execution"
+            );
+            return Ok(());
+        }
+
+        // Never deploy liquidity into a pool priced away from the market
+        if matches!(
+            decision,
+            Decision::Rebalance { .. } | Decision::IncreaseLiquidity { .. }
+        ) {
+            self.check_oracle_price(context).await?;
+        }
+
+        let backend = self.backend();
+
         info!(
             position = %position.address,
             decision = %decision.description(),
+            backend = backend.name(),
             "Executing decision"
         );
 
@@ -302,26 +995,40 @@ impl StrategyExecutor {
                     current_il_pct: position.pnl.il_pct,
                 };
 
-                let result = self.rebalance_executor.execute(params).await;
+                let result = backend.rebalance(context, params).await;
 
-                if !result.success
-                    && let Some(err) = result.error
-                {
+                if !result.success && !result.skipped {
+                    let err = result.error.unwrap_or_else(|| "unknown error".to_string());
                     error!(error = %err, "Rebalance failed");
+                    anyhow::bail!("rebalance failed: {}", err);
+                }
+
+                if result.success {
+                    self.decision_engine.record_rebalance(&position.address);
                 }
             }
             Decision::Close => {
-                info!("Would execute close");
-                // TODO: Implement close via emergency exit manager
+                backend.close_position(context).await?;
+                self.decision_engine.forget(&position.address);
+                self.close_hedge(position).await?;
             }
             Decision::IncreaseLiquidity { amount } => {
-                info!(amount = %amount, "Would execute increase liquidity");
+                backend.adjust_liquidity(context, *amount).await?;
             }
             Decision::DecreaseLiquidity { amount } => {
-                info!(amount = %amount, "Would execute decrease liquidity");
+                backend.adjust_liquidity(context, -*amount).await?;
             }
             Decision::CollectFees => {
-                info!("Would execute collect fees");
+                backend.collect_fees(context).await?;
+            }
+            Decision::EmergencyExit { swap_to_quote, .. } => {
+                backend.close_position(context).await?;
+                if *swap_to_quote {
+                    backend.swap_to_quote(context).await?;
+                }
+                self.peak_values.write().await.remove(&position.address);
+                self.decision_engine.forget(&position.address);
+                self.close_hedge(position).await?;
             }
         }
 
@@ -3,14 +3,32 @@
 //! Provides automated strategy execution including:
 //! - Decision engine
 //! - Rebalancing logic
+//! - Pluggable execution backends (live or paper)
+//! - Auto-compounding of accrued fees
+//! - Stop-loss emergency exits
+//! - Dry-run transcripts of planned actions
+//! - Shadow mode reconciling recorded decisions with actual outcomes
+//! - Point-in-time recommendations for live positions
 //! - Position lifecycle management
 
+mod backend;
+mod compound;
 mod decision;
 mod executor;
 mod rebalance;
+mod recommend;
+mod shadow;
+mod stop_loss;
+mod transcript;
 mod types;
 
+pub use backend::*;
+pub use compound::*;
 pub use decision::*;
 pub use executor::*;
 pub use rebalance::*;
+pub use recommend::*;
+pub use shadow::*;
+pub use stop_loss::*;
+pub use transcript::*;
 pub use types::Decision;
@@ -1,12 +1,23 @@
 //! Rebalancing execution logic.
 
-use crate::lifecycle::{FeesCollectedData, LifecycleTracker, RebalanceData, RebalanceReason};
+use crate::lifecycle::{CloseReason, LifecycleTracker, RebalanceData, RebalanceReason};
+use crate::monitor::{MonitoredPosition, PositionPnL};
+use crate::operations::{
+    CloseRequest, ClosedPosition, OpenRequest, PositionCloser, PositionCollector, PositionOpener,
+    Submitter, transaction_fee,
+};
+use crate::oracle::PriceGuard;
+use crate::swap::{JupiterSwapper, QuoteRequest, SwapPlan};
 use crate::transaction::TransactionManager;
 use crate::wallet::Wallet;
 use clmm_lp_protocols::prelude::*;
 use rust_decimal::Decimal;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 /// Configuration for rebalancing.
@@ -18,6 +29,9 @@ pub struct RebalanceConfig {
     pub min_profit_multiplier: Decimal,
     /// Whether to collect fees before rebalancing.
     pub collect_fees_first: bool,
+    /// Imbalance from the new range's token ratio, in basis points of the
+    /// position value, below which no swap is made.
+    pub min_swap_bps: u16,
     /// Priority fee level.
     pub priority_level: crate::transaction::PriorityLevel,
 }
@@ -28,6 +42,7 @@ impl Default for RebalanceConfig {
             max_slippage_bps: 50,                      // 0.5%
             min_profit_multiplier: Decimal::new(2, 0), // 2x tx cost
             collect_fees_first: true,
+            min_swap_bps: 50, // 0.5%
             priority_level: crate::transaction::PriorityLevel::Medium,
         }
     }
@@ -69,24 +84,57 @@ pub struct RebalanceResult {
     pub fees_collected: Option<(u64, u64)>,
     /// Liquidity removed from old position.
     pub liquidity_removed: u128,
+    /// Whether the old position was closed on chain, even if the rebalance
+    /// then failed.
+    pub old_position_closed: bool,
     /// Liquidity added to new position.
     pub liquidity_added: u128,
+    /// Swap to the new range's token ratio, if one was made.
+    pub swap: Option<RebalanceSwap>,
     /// Transaction cost in lamports.
     pub tx_cost_lamports: u64,
+    /// Whether the rebalance was skipped without attempting execution.
+    pub skipped: bool,
     /// Error message if failed.
     pub error: Option<String>,
 }
 
+/// A swap made to reach the new range's token ratio.
+///
+/// Amounts are raw base units.
+#[derive(Debug, Clone)]
+pub struct RebalanceSwap {
+    /// Whether token A was sold for token B.
+    pub a_to_b: bool,
+    /// Amount sold.
+    pub amount_in: u64,
+    /// Quoted amount bought.
+    pub expected_out: u64,
+    /// Minimum amount bought after slippage.
+    pub min_out: u64,
+    /// Transaction fee paid, in lamports.
+    pub fee_lamports: u64,
+    /// Transaction signature.
+    pub signature: Signature,
+}
+
 /// Executor for rebalancing operations.
 pub struct RebalanceExecutor {
     /// RPC provider.
-    #[allow(dead_code)]
     provider: Arc<RpcProvider>,
     /// Transaction manager.
-    #[allow(dead_code)]
     tx_manager: Arc<TransactionManager>,
+    /// Protocol adapters building position instructions.
+    protocols: Arc<ProtocolRegistry>,
+    /// Token amounts returned by closed positions, kept for a swap to the
+    /// quote token.
+    proceeds: RwLock<HashMap<Pubkey, (u64, u64)>>,
     /// Wallet for signing.
     wallet: Option<Arc<Wallet>>,
+    /// Jupiter client for swaps to the new range's token ratio.
+    swapper: Option<Arc<JupiterSwapper>>,
+    /// Oracle price guard checked before the old position is closed.
+    price_guard: Option<Arc<PriceGuard>>,
     /// Lifecycle tracker.
     lifecycle: Arc<LifecycleTracker>,
     /// Configuration.
@@ -104,9 +152,13 @@ impl RebalanceExecutor {
         config: RebalanceConfig,
     ) -> Self {
         Self {
+            protocols: Arc::new(ProtocolRegistry::with_defaults(provider.clone())),
             provider,
             tx_manager,
+            proceeds: RwLock::new(HashMap::new()),
             wallet: None,
+            swapper: None,
+            price_guard: None,
             lifecycle,
             config,
             dry_run: false,
@@ -118,11 +170,34 @@ impl RebalanceExecutor {
         self.wallet = Some(wallet);
     }
 
+    /// Sets the Jupiter client that swaps withdrawn tokens to the new
+    /// range's token ratio; without one, no swap is made.
+    pub fn set_swapper(&mut self, swapper: Arc<JupiterSwapper>) {
+        self.swapper = Some(swapper);
+    }
+
+    /// Sets the oracle price guard checked against the pool before the old
+    /// position is closed, so a rebalance never starts in a mispriced pool.
+    pub fn set_price_guard(&mut self, guard: Arc<PriceGuard>) {
+        self.price_guard = Some(guard);
+    }
+
     /// Enables or disables dry run mode.
     pub fn set_dry_run(&mut self, dry_run: bool) {
         self.dry_run = dry_run;
     }
 
+    /// Returns whether dry run mode is enabled.
+    #[must_use]
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Replaces the lifecycle tracker with a shared instance.
+    pub fn set_lifecycle(&mut self, lifecycle: Arc<LifecycleTracker>) {
+        self.lifecycle = lifecycle;
+    }
+
     /// Checks if a rebalance is profitable.
     pub async fn is_profitable(&self, params: &RebalanceParams) -> ProfitabilityCheck {
         // Estimate transaction costs
@@ -160,6 +235,9 @@ impl RebalanceExecutor {
     }
 
     /// Executes a rebalance operation.
+    ///
+    /// Manual rebalances skip the profitability check; the operator asking
+    /// for one has already judged it worthwhile.
     pub async fn execute(&self, params: RebalanceParams) -> RebalanceResult {
         info!(
             position = %params.position,
@@ -176,19 +254,23 @@ impl RebalanceExecutor {
             new_position: None,
             fees_collected: None,
             liquidity_removed: 0,
+            old_position_closed: false,
             liquidity_added: 0,
+            swap: None,
             tx_cost_lamports: 0,
+            skipped: false,
             error: None,
         };
 
         // Check profitability
         let profitability = self.is_profitable(&params).await;
-        if !profitability.is_profitable {
+        if params.reason != RebalanceReason::Manual && !profitability.is_profitable {
             warn!(
                 expected_benefit = %profitability.expected_benefit,
                 min_required = %profitability.min_required_benefit,
                 "Rebalance not profitable, skipping"
             );
+            result.skipped = true;
             result.error = Some("Rebalance not profitable".to_string());
             return result;
         }
@@ -201,25 +283,28 @@ impl RebalanceExecutor {
             return result;
         }
 
+        let wallet = match self.wallet() {
+            Ok(wallet) => wallet,
+            Err(e) => {
+                result.error = Some(e.to_string());
+                return result;
+            }
+        };
+
+        if let Err(e) = self.check_price(&params.pool).await {
+            warn!(error = %e, "Pool price check failed, not rebalancing");
+            result.error = Some(e.to_string());
+            return result;
+        }
+
         // Step 1: Collect fees if configured
+        let mut fees = (0, 0);
         if self.config.collect_fees_first {
-            match self.collect_fees(&params.position).await {
-                Ok(fees) => {
-                    result.fees_collected = Some(fees);
+            match self.collect(&params.position).await {
+                Ok(collected) => {
+                    fees = collected;
+                    result.fees_collected = Some(collected);
                     result.tx_cost_lamports += 5000; // Approximate
-
-                    // Record in lifecycle
-                    self.lifecycle
-                        .record_fees_collected(
-                            params.position,
-                            params.pool,
-                            FeesCollectedData {
-                                fees_a: fees.0,
-                                fees_b: fees.1,
-                                fees_usd: Decimal::ZERO, // Would need price oracle
-                            },
-                        )
-                        .await;
                 }
                 Err(e) => {
                     warn!(error = %e, "Failed to collect fees, continuing");
@@ -227,60 +312,93 @@ impl RebalanceExecutor {
             }
         }
 
-        // Step 2: Decrease liquidity from current position
-        match self
-            .decrease_liquidity(&params.position, params.current_liquidity)
+        // Step 2: Withdraw the liquidity and close the old position
+        let closed = match self
+            .close(params.position, CloseReason::Rebalance, None)
             .await
         {
-            Ok(liquidity) => {
-                result.liquidity_removed = liquidity;
-                result.tx_cost_lamports += 5000;
-            }
+            Ok(closed) => closed,
             Err(e) => {
-                error!(error = %e, "Failed to decrease liquidity");
+                error!(error = %e, "Failed to close position");
                 result.error = Some(e.to_string());
                 return result;
             }
-        }
+        };
+        result.liquidity_removed = closed.liquidity_removed;
+        result.old_position_closed = true;
+        result.fees_collected = Some((
+            fees.0.saturating_add(closed.fees_collected.0),
+            fees.1.saturating_add(closed.fees_collected.1),
+        ));
+        result.tx_cost_lamports += transaction_fee(1, closed.priority_fee, closed.compute_units);
+
+        let withdrawn = withdrawn(&closed);
+        let mut holdings = (
+            withdrawn.0.saturating_add(fees.0),
+            withdrawn.1.saturating_add(fees.1),
+        );
 
-        // Step 3: Close old position
-        if let Err(e) = self.close_position(&params.position).await {
-            error!(error = %e, "Failed to close position");
-            result.error = Some(e.to_string());
-            return result;
+        // Step 3: Swap to the token ratio of the new range
+        if let Some(swapper) = &self.swapper {
+            match self.swap_to_range(swapper, &params, holdings).await {
+                Ok(swap) => {
+                    if let Some(swap) = &swap {
+                        result.tx_cost_lamports += swap.fee_lamports;
+                        holdings = if swap.a_to_b {
+                            (
+                                holdings.0.saturating_sub(swap.amount_in),
+                                holdings.1.saturating_add(swap.min_out),
+                            )
+                        } else {
+                            (
+                                holdings.0.saturating_add(swap.min_out),
+                                holdings.1.saturating_sub(swap.amount_in),
+                            )
+                        };
+                    }
+                    result.swap = swap;
+                }
+                Err(e) => {
+                    error!(error = %e, "Failed to swap to the new range's token ratio");
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            }
         }
-        result.tx_cost_lamports += 5000;
 
-        // Step 4: Open new position
-        let new_position = match self
-            .open_position(&params.pool, params.new_tick_lower, params.new_tick_upper)
-            .await
+        // Step 4: Open the new position with the holdings
+        let opened = match PositionOpener::new(
+            self.provider.clone(),
+            self.protocols.clone(),
+            self.tx_manager.clone(),
+        )
+        .with_priority(self.config.priority_level)
+        .with_lifecycle(self.lifecycle.clone())
+        .open(
+            &OpenRequest {
+                pool: params.pool,
+                tick_lower: params.new_tick_lower,
+                tick_upper: params.new_tick_upper,
+                amount_a: holdings.0,
+                amount_b: holdings.1,
+                slippage_bps: self.config.max_slippage_bps,
+            },
+            wallet,
+        )
+        .await
         {
-            Ok(pos) => pos,
+            Ok(opened) => opened,
             Err(e) => {
                 error!(error = %e, "Failed to open new position");
                 result.error = Some(e.to_string());
                 return result;
             }
         };
+        let new_position = opened.position;
         result.new_position = Some(new_position);
-        result.tx_cost_lamports += 5000;
-
-        // Step 5: Increase liquidity in new position
-        match self
-            .increase_liquidity(&new_position, params.current_liquidity)
-            .await
-        {
-            Ok(liquidity) => {
-                result.liquidity_added = liquidity;
-                result.tx_cost_lamports += 5000;
-            }
-            Err(e) => {
-                error!(error = %e, "Failed to increase liquidity");
-                result.error = Some(e.to_string());
-                return result;
-            }
-        }
+        result.liquidity_added = opened.liquidity;
+        // The position mint co-signs the open
+        result.tx_cost_lamports += transaction_fee(2, opened.priority_fee, opened.compute_units);
 
         // Record rebalance in lifecycle
         self.lifecycle
@@ -312,57 +430,234 @@ impl RebalanceExecutor {
         result
     }
 
-    /// Collects fees from a position.
-    async fn collect_fees(&self, _position: &Pubkey) -> anyhow::Result<(u64, u64)> {
-        // TODO: Implement actual fee collection via Whirlpool instruction
-        debug!("Would collect fees");
-        Ok((0, 0))
+    /// Checks the pool price against the oracle, if a price guard is set.
+    async fn check_price(&self, pool: &Pubkey) -> anyhow::Result<()> {
+        let Some(guard) = &self.price_guard else {
+            return Ok(());
+        };
+        let pool_state = self
+            .protocols
+            .adapter_for_account(pool)
+            .await?
+            .fetch_pool_state(pool)
+            .await?;
+        guard.check_pool(&pool_state).await?;
+        Ok(())
     }
 
-    /// Decreases liquidity from a position.
-    async fn decrease_liquidity(
+    /// Swaps the tokens held after closing the old range to the token ratio
+    /// of the new range at the pool's current price.
+    ///
+    /// Returns `None` if the holdings are within `min_swap_bps` of it.
+    async fn swap_to_range(
         &self,
-        _position: &Pubkey,
-        liquidity: u128,
-    ) -> anyhow::Result<u128> {
-        // TODO: Implement actual liquidity decrease via Whirlpool instruction
-        debug!(liquidity = liquidity, "Would decrease liquidity");
-        Ok(liquidity)
+        swapper: &JupiterSwapper,
+        params: &RebalanceParams,
+        holdings: (u64, u64),
+    ) -> anyhow::Result<Option<RebalanceSwap>> {
+        let pool = self
+            .protocols
+            .adapter_for_account(&params.pool)
+            .await?
+            .fetch_pool_state(&params.pool)
+            .await?;
+
+        let Some(plan) = SwapPlan::for_range(
+            holdings.0,
+            holdings.1,
+            pool.sqrt_price,
+            params.new_tick_lower,
+            params.new_tick_upper,
+            self.config.min_swap_bps,
+        ) else {
+            debug!("Token ratio already matches the new range, no swap needed");
+            return Ok(None);
+        };
+
+        self.swap(
+            swapper,
+            plan.a_to_b,
+            plan.amount_in,
+            (pool.token_mint_a, pool.token_mint_b),
+            params.pool,
+        )
+        .await
+        .map(Some)
     }
 
-    /// Closes a position.
-    async fn close_position(&self, _position: &Pubkey) -> anyhow::Result<()> {
-        // TODO: Implement actual position close via Whirlpool instruction
-        debug!("Would close position");
-        Ok(())
+    /// Sells the token A returned by a closed position for token B.
+    ///
+    /// Returns `None` if the close returned no token A.
+    pub(super) async fn sell_proceeds(
+        &self,
+        position: &Pubkey,
+        pool: &WhirlpoolState,
+    ) -> anyhow::Result<Option<RebalanceSwap>> {
+        let swapper = self
+            .swapper
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No Jupiter client configured for swaps"))?;
+        let (amount_a, _) = self
+            .proceeds
+            .write()
+            .await
+            .remove(position)
+            .ok_or_else(|| anyhow::anyhow!("No closed proceeds of position {}", position))?;
+        if amount_a == 0 {
+            return Ok(None);
+        }
+
+        let pool_address = Pubkey::from_str(&pool.address)?;
+        self.swap(
+            swapper,
+            true,
+            amount_a,
+            (pool.token_mint_a, pool.token_mint_b),
+            pool_address,
+        )
+        .await
+        .map(Some)
     }
 
-    /// Opens a new position.
-    async fn open_position(
+    /// Swaps an amount of one pool token for the other through Jupiter.
+    async fn swap(
         &self,
-        _pool: &Pubkey,
-        tick_lower: i32,
-        tick_upper: i32,
-    ) -> anyhow::Result<Pubkey> {
-        // TODO: Implement actual position open via Whirlpool instruction
-        debug!(
-            tick_lower = tick_lower,
-            tick_upper = tick_upper,
-            "Would open position"
+        swapper: &JupiterSwapper,
+        a_to_b: bool,
+        amount_in: u64,
+        (token_mint_a, token_mint_b): (Pubkey, Pubkey),
+        pool: Pubkey,
+    ) -> anyhow::Result<RebalanceSwap> {
+        let wallet = self.wallet()?;
+        let (input_mint, output_mint) = if a_to_b {
+            (token_mint_a, token_mint_b)
+        } else {
+            (token_mint_b, token_mint_a)
+        };
+        let quote = swapper
+            .quote(&QuoteRequest {
+                input_mint,
+                output_mint,
+                amount: amount_in,
+                slippage_bps: self.config.max_slippage_bps,
+            })
+            .await?;
+        let instructions = swapper.swap_instructions(&quote, &wallet.pubkey()).await?;
+
+        let submitted = self
+            .submitter()
+            .submit(
+                "swap",
+                instructions.into_instructions(),
+                wallet,
+                None,
+                &[pool],
+            )
+            .await?;
+
+        info!(
+            a_to_b,
+            amount_in = quote.in_amount,
+            expected_out = quote.out_amount,
+            signature = %submitted.signature,
+            "Swapped pool tokens"
         );
-        Ok(Pubkey::new_unique())
+
+        Ok(RebalanceSwap {
+            a_to_b,
+            amount_in: quote.in_amount,
+            expected_out: quote.out_amount,
+            min_out: quote.min_out_amount,
+            fee_lamports: transaction_fee(1, submitted.priority_fee, submitted.compute_units),
+            signature: submitted.signature,
+        })
+    }
+
+    /// Closes a monitored position at the end of a strategy, keeping the
+    /// tokens it returns for [`Self::sell_proceeds`].
+    pub(super) async fn exit(
+        &self,
+        position: &MonitoredPosition,
+    ) -> anyhow::Result<ClosedPosition> {
+        let closed = self
+            .close(
+                position.address,
+                CloseReason::StrategyEnded,
+                Some(position.pnl.clone()),
+            )
+            .await?;
+        self.proceeds
+            .write()
+            .await
+            .insert(closed.position, withdrawn(&closed));
+        Ok(closed)
     }
 
-    /// Increases liquidity in a position.
-    async fn increase_liquidity(
+    /// Closes a position, recording it in the lifecycle.
+    async fn close(
         &self,
-        _position: &Pubkey,
-        liquidity: u128,
-    ) -> anyhow::Result<u128> {
-        // TODO: Implement actual liquidity increase via Whirlpool instruction
-        debug!(liquidity = liquidity, "Would increase liquidity");
-        Ok(liquidity)
+        position: Pubkey,
+        reason: CloseReason,
+        pnl: Option<PositionPnL>,
+    ) -> anyhow::Result<ClosedPosition> {
+        PositionCloser::new(
+            self.provider.clone(),
+            self.protocols.clone(),
+            self.tx_manager.clone(),
+        )
+        .with_priority(self.config.priority_level)
+        .with_lifecycle(self.lifecycle.clone())
+        .close(
+            &CloseRequest {
+                position,
+                slippage_bps: self.config.max_slippage_bps,
+                reason,
+                pnl,
+            },
+            self.wallet()?,
+        )
+        .await
     }
+
+    /// Returns the signing wallet.
+    fn wallet(&self) -> anyhow::Result<&Arc<Wallet>> {
+        self.wallet
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No wallet configured for live execution"))
+    }
+
+    /// Creates a sender of transactions at the configured priority.
+    fn submitter(&self) -> Submitter {
+        let mut submitter = Submitter::new(self.provider.clone(), self.tx_manager.clone());
+        submitter.priority = self.config.priority_level;
+        submitter
+    }
+
+    /// Collects a position's owed fees and rewards, signed by the wallet
+    /// holding it, and records them in the lifecycle.
+    ///
+    /// Returns the fees as last recorded on the position.
+    pub(super) async fn collect(&self, position: &Pubkey) -> anyhow::Result<(u64, u64)> {
+        let collected = PositionCollector::new(
+            self.provider.clone(),
+            self.protocols.clone(),
+            self.tx_manager.clone(),
+        )
+        .with_priority(self.config.priority_level)
+        .with_lifecycle(self.lifecycle.clone())
+        .collect(position, self.wallet()?)
+        .await?;
+        Ok(collected.fees)
+    }
+}
+
+/// Token amounts a closed position returned at least: the withdrawal's
+/// minimums and the collected fees.
+fn withdrawn(closed: &ClosedPosition) -> (u64, u64) {
+    (
+        closed.minimums.0.saturating_add(closed.fees_collected.0),
+        closed.minimums.1.saturating_add(closed.fees_collected.1),
+    )
 }
 
 /// Result of profitability check.
@@ -386,6 +681,7 @@ mod tests {
     async fn test_rebalance_config_default() {
         let config = RebalanceConfig::default();
         assert_eq!(config.max_slippage_bps, 50);
+        assert_eq!(config.min_swap_bps, 50);
         assert!(config.collect_fees_first);
     }
 }
@@ -0,0 +1,628 @@
+//! Point-in-time recommendations for live positions.
+//!
+//! Runs the decision engine against the current pool state without the
+//! confirmation and cooldown gates used during automated execution, then
+//! compares the current range with the proposed one over a short horizon.
+
+use super::{Decision, DecisionConfig, DecisionContext, DecisionEngine};
+use crate::monitor::MonitoredPosition;
+use clmm_lp_protocols::prelude::{WhirlpoolState, calculate_tick_range, tick_to_price};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+
+/// Hours in a year, for annualized volatility and APR.
+const HOURS_PER_YEAR: f64 = 8_760.0;
+
+/// Points sampled over the horizon when estimating time in range.
+const TIME_IN_RANGE_SAMPLES: u32 = 24;
+
+/// Range width multiples tried when the strategy width is too narrow.
+const WIDEN_FACTORS: [i64; 4] = [2, 3, 4, 6];
+
+/// Recommended action for a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendedAction {
+    /// Keep the position as it is.
+    Hold,
+    /// Move the range to the strategy width around the current price.
+    Recenter,
+    /// Move to a wider range around the current price.
+    Widen,
+    /// Close the position.
+    Close,
+}
+
+impl fmt::Display for RecommendedAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Hold => "hold",
+            Self::Recenter => "recenter",
+            Self::Widen => "widen",
+            Self::Close => "close",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Configuration for recommendations.
+#[derive(Debug, Clone)]
+pub struct RecommendationConfig {
+    /// Decision rules and the strategy range width.
+    pub decision: DecisionConfig,
+    /// Horizon the current and proposed ranges are compared over, in hours.
+    pub horizon_hours: u64,
+    /// Annualized price volatility (0.8 = 80%).
+    pub volatility: f64,
+    /// Expected time in range below which a wider range is proposed
+    /// (as percentage).
+    pub min_time_in_range_pct: Decimal,
+    /// Fee APR of a full-range position in the pool (as percentage), if
+    /// known. Enables USD fee estimates.
+    pub fee_apr_pct: Option<Decimal>,
+    /// Cost per transaction in USD.
+    pub tx_cost_usd: Decimal,
+}
+
+impl Default for RecommendationConfig {
+    fn default() -> Self {
+        Self {
+            decision: DecisionConfig::default(),
+            horizon_hours: 24,
+            volatility: 0.8,
+            min_time_in_range_pct: Decimal::from(50),
+            fee_apr_pct: None,
+            tx_cost_usd: Decimal::new(5, 1), // $0.50
+        }
+    }
+}
+
+impl RecommendationConfig {
+    /// Sets the decision rules.
+    #[must_use]
+    pub fn with_decision(mut self, decision: DecisionConfig) -> Self {
+        self.decision = decision;
+        self
+    }
+
+    /// Sets the comparison horizon in hours.
+    #[must_use]
+    pub fn with_horizon_hours(mut self, horizon_hours: u64) -> Self {
+        self.horizon_hours = horizon_hours.max(1);
+        self
+    }
+
+    /// Sets the annualized volatility.
+    #[must_use]
+    pub fn with_volatility(mut self, volatility: f64) -> Self {
+        self.volatility = volatility.max(0.0);
+        self
+    }
+
+    /// Sets the full-range fee APR of the pool.
+    #[must_use]
+    pub fn with_fee_apr_pct(mut self, fee_apr_pct: Decimal) -> Self {
+        self.fee_apr_pct = Some(fee_apr_pct);
+        self
+    }
+
+    /// Sets the cost per transaction in USD.
+    #[must_use]
+    pub fn with_tx_cost_usd(mut self, tx_cost_usd: Decimal) -> Self {
+        self.tx_cost_usd = tx_cost_usd;
+        self
+    }
+}
+
+/// Expected effect of following a recommendation over the horizon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedImpact {
+    /// Horizon in hours.
+    pub horizon_hours: u64,
+    /// Annualized volatility used.
+    pub volatility: f64,
+    /// Expected time in range of the current range (as percentage).
+    pub current_time_in_range_pct: Decimal,
+    /// Expected time in range after the action (as percentage).
+    pub proposed_time_in_range_pct: Decimal,
+    /// Expected fee yield of the current range relative to a full-range
+    /// position.
+    pub current_fee_multiplier: Decimal,
+    /// Expected fee yield after the action relative to a full-range position.
+    pub proposed_fee_multiplier: Decimal,
+    /// Expected fees of the current range in USD, if the pool APR is known.
+    pub current_fees_usd: Option<Decimal>,
+    /// Expected fees after the action in USD, if the pool APR is known.
+    pub proposed_fees_usd: Option<Decimal>,
+    /// Estimated cost of the action in USD.
+    pub cost_usd: Decimal,
+    /// Expected fees gained minus cost in USD, if the pool APR is known.
+    pub net_benefit_usd: Option<Decimal>,
+}
+
+/// Recommendation for a position at the current pool state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recommendation {
+    /// Position address.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Recommended action.
+    pub action: RecommendedAction,
+    /// Why the action is recommended.
+    pub reason: String,
+    /// Current pool tick.
+    pub tick_current: i32,
+    /// Current pool price.
+    pub price: Decimal,
+    /// Whether the position is in range.
+    pub in_range: bool,
+    /// Current lower tick.
+    pub tick_lower: i32,
+    /// Current upper tick.
+    pub tick_upper: i32,
+    /// Proposed lower tick for recenter and widen.
+    pub new_tick_lower: Option<i32>,
+    /// Proposed upper tick for recenter and widen.
+    pub new_tick_upper: Option<i32>,
+    /// Expected impact.
+    pub impact: ExpectedImpact,
+}
+
+impl Recommendation {
+    /// Gets the proposed price range for recenter and widen.
+    #[must_use]
+    pub fn new_price_range(&self) -> Option<(Decimal, Decimal)> {
+        Some((
+            tick_to_price(self.new_tick_lower?),
+            tick_to_price(self.new_tick_upper?),
+        ))
+    }
+}
+
+/// Recommends what to do with a live position right now.
+#[derive(Debug, Clone, Default)]
+pub struct Recommender {
+    /// Configuration.
+    config: RecommendationConfig,
+}
+
+impl Recommender {
+    /// Creates a new recommender.
+    #[must_use]
+    pub fn new(config: RecommendationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Gets the configuration.
+    #[must_use]
+    pub fn config(&self) -> &RecommendationConfig {
+        &self.config
+    }
+
+    /// Recommends an action for a position at the current pool state.
+    #[must_use]
+    pub fn recommend(&self, position: &MonitoredPosition, pool: &WhirlpoolState) -> Recommendation {
+        let (tick_lower, tick_upper) = (position.on_chain.tick_lower, position.on_chain.tick_upper);
+        let current = self.estimate(pool, tick_lower, tick_upper);
+
+        let recommendation = |action, reason: String, proposed: Option<RangeEstimate>| {
+            let cost = self.cost(action, position, pool);
+            Recommendation {
+                position: position.address,
+                pool: position.pool,
+                action,
+                reason,
+                tick_current: pool.tick_current,
+                price: pool.price,
+                in_range: position.in_range,
+                tick_lower,
+                tick_upper,
+                new_tick_lower: proposed.map(|p| p.tick_lower),
+                new_tick_upper: proposed.map(|p| p.tick_upper),
+                impact: self.impact(position, action, &current, proposed.as_ref(), cost),
+            }
+        };
+
+        let decision = self.decide(position, pool);
+        let recenter = match decision {
+            Decision::Close => {
+                return recommendation(
+                    RecommendedAction::Close,
+                    format!(
+                        "Impermanent loss of {}% exceeds the close threshold",
+                        il_display_pct(position)
+                    ),
+                    None,
+                );
+            }
+            Decision::EmergencyExit { .. } => {
+                return recommendation(RecommendedAction::Close, decision.description(), None);
+            }
+            Decision::Rebalance {
+                new_tick_lower,
+                new_tick_upper,
+            } => Some(self.estimate(pool, new_tick_lower, new_tick_upper)),
+            _ => None,
+        };
+
+        let min_time_in_range = self.config.min_time_in_range_pct;
+        let proposal = match recenter {
+            Some(range) if range.time_in_range_pct >= min_time_in_range => Some((
+                RecommendedAction::Recenter,
+                range,
+                if position.in_range {
+                    format!(
+                        "Impermanent loss of {}% exceeds the rebalance threshold",
+                        il_display_pct(position)
+                    )
+                } else {
+                    "Position is out of range".to_string()
+                },
+            )),
+            Some(range) => self.widened(pool).map(|wide| {
+                (
+                    RecommendedAction::Widen,
+                    wide,
+                    format!(
+                        "A recentered range would be in range only {}% of the next {}h",
+                        range.time_in_range_pct.round_dp(1),
+                        self.config.horizon_hours
+                    ),
+                )
+            }),
+            None if current.time_in_range_pct < min_time_in_range => {
+                self.widened(pool).map(|wide| {
+                    (
+                        RecommendedAction::Widen,
+                        wide,
+                        format!(
+                            "Current range is expected in range only {}% of the next {}h",
+                            current.time_in_range_pct.round_dp(1),
+                            self.config.horizon_hours
+                        ),
+                    )
+                })
+            }
+            None => None,
+        };
+
+        let Some((action, proposed, reason)) = proposal else {
+            return recommendation(
+                RecommendedAction::Hold,
+                format!(
+                    "Range is expected in range {}% of the next {}h",
+                    current.time_in_range_pct.round_dp(1),
+                    self.config.horizon_hours
+                ),
+                None,
+            );
+        };
+
+        let candidate = recommendation(action, reason, Some(proposed));
+        let worthwhile = match candidate.impact.net_benefit_usd {
+            Some(net) => net > Decimal::ZERO,
+            None => proposed.fee_multiplier > current.fee_multiplier,
+        };
+        if worthwhile {
+            candidate
+        } else {
+            recommendation(
+                RecommendedAction::Hold,
+                format!(
+                    "Moving the range is not expected to pay for itself over the next {}h",
+                    self.config.horizon_hours
+                ),
+                None,
+            )
+        }
+    }
+
+    /// Runs the decision engine without confirmation, cooldown or fee rules.
+    fn decide(&self, position: &MonitoredPosition, pool: &WhirlpoolState) -> Decision {
+        let engine = DecisionEngine::new(DecisionConfig {
+            confirm_evaluations: 1,
+            min_rebalance_interval_hours: 0,
+            rebalance_cooldown_secs: 0,
+            auto_collect_fees: false,
+            ..self.config.decision.clone()
+        });
+        engine.decide(&DecisionContext {
+            position: position.clone(),
+            pool: pool.clone(),
+            hours_since_rebalance: u64::MAX,
+            peak_value_usd: position
+                .pnl
+                .entry_value_usd
+                .max(position.pnl.current_value_usd),
+        })
+    }
+
+    /// Finds the narrowest wider range meeting the time in range target,
+    /// or the widest candidate if none does.
+    fn widened(&self, pool: &WhirlpoolState) -> Option<RangeEstimate> {
+        let base = self.config.decision.range_width_pct;
+        let mut widest = None;
+        for factor in WIDEN_FACTORS {
+            let (lower, upper) = calculate_tick_range(
+                pool.tick_current,
+                base * Decimal::from(factor),
+                pool.tick_spacing,
+            );
+            let estimate = self.estimate(pool, lower, upper);
+            if estimate.time_in_range_pct >= self.config.min_time_in_range_pct {
+                return Some(estimate);
+            }
+            widest = Some(estimate);
+        }
+        widest
+    }
+
+    /// Estimates time in range and fee yield of a tick range.
+    fn estimate(&self, pool: &WhirlpoolState, tick_lower: i32, tick_upper: i32) -> RangeEstimate {
+        let price = tick_to_price(pool.tick_current).to_f64().unwrap_or(0.0);
+        let lower = tick_to_price(tick_lower).to_f64().unwrap_or(0.0);
+        let upper = tick_to_price(tick_upper).to_f64().unwrap_or(0.0);
+        let horizon_years = self.config.horizon_hours as f64 / HOURS_PER_YEAR;
+
+        let time_in_range =
+            expected_time_in_range(price, lower, upper, self.config.volatility, horizon_years);
+        let fee_multiplier = time_in_range * capital_efficiency(lower, upper);
+
+        RangeEstimate {
+            tick_lower,
+            tick_upper,
+            time_in_range_pct: to_decimal(time_in_range * 100.0).round_dp(2),
+            fee_multiplier: to_decimal(fee_multiplier).round_dp(4),
+        }
+    }
+
+    /// Estimates the cost of an action in USD.
+    ///
+    /// Moving the range pays the transaction cost of closing and reopening
+    /// plus swapping about half the position at the pool fee.
+    fn cost(
+        &self,
+        action: RecommendedAction,
+        position: &MonitoredPosition,
+        pool: &WhirlpoolState,
+    ) -> Decimal {
+        match action {
+            RecommendedAction::Hold => Decimal::ZERO,
+            RecommendedAction::Close => self.config.tx_cost_usd,
+            RecommendedAction::Recenter | RecommendedAction::Widen => {
+                let swap_fee = position.pnl.current_value_usd / Decimal::TWO * pool.fee_rate();
+                (self.config.tx_cost_usd * Decimal::TWO + swap_fee).round_dp(2)
+            }
+        }
+    }
+
+    /// Builds the expected impact of an action.
+    fn impact(
+        &self,
+        position: &MonitoredPosition,
+        action: RecommendedAction,
+        current: &RangeEstimate,
+        proposed: Option<&RangeEstimate>,
+        cost_usd: Decimal,
+    ) -> ExpectedImpact {
+        let (proposed_time_in_range, proposed_multiplier) = match (action, proposed) {
+            (RecommendedAction::Close, _) => (Decimal::ZERO, Decimal::ZERO),
+            (_, Some(p)) => (p.time_in_range_pct, p.fee_multiplier),
+            (_, None) => (current.time_in_range_pct, current.fee_multiplier),
+        };
+
+        // Fees of a full-range position of the same value over the horizon
+        let full_range_fees = self.config.fee_apr_pct.map(|apr| {
+            position.pnl.current_value_usd * apr / Decimal::ONE_HUNDRED
+                * Decimal::from(self.config.horizon_hours)
+                / to_decimal(HOURS_PER_YEAR)
+        });
+        let current_fees = full_range_fees.map(|f| (f * current.fee_multiplier).round_dp(2));
+        let proposed_fees = full_range_fees.map(|f| (f * proposed_multiplier).round_dp(2));
+
+        ExpectedImpact {
+            horizon_hours: self.config.horizon_hours,
+            volatility: self.config.volatility,
+            current_time_in_range_pct: current.time_in_range_pct,
+            proposed_time_in_range_pct: proposed_time_in_range,
+            current_fee_multiplier: current.fee_multiplier,
+            proposed_fee_multiplier: proposed_multiplier,
+            current_fees_usd: current_fees,
+            proposed_fees_usd: proposed_fees,
+            cost_usd,
+            net_benefit_usd: current_fees
+                .zip(proposed_fees)
+                .map(|(current, proposed)| proposed - current - cost_usd),
+        }
+    }
+}
+
+/// Time in range and fee yield estimated for a tick range.
+#[derive(Debug, Clone, Copy)]
+struct RangeEstimate {
+    /// Lower tick.
+    tick_lower: i32,
+    /// Upper tick.
+    tick_upper: i32,
+    /// Expected time in range over the horizon (as percentage).
+    time_in_range_pct: Decimal,
+    /// Expected fee yield relative to a full-range position.
+    fee_multiplier: Decimal,
+}
+
+/// Expected fraction of the horizon a driftless GBM price spends in range.
+fn expected_time_in_range(
+    price: f64,
+    lower: f64,
+    upper: f64,
+    volatility: f64,
+    horizon_years: f64,
+) -> f64 {
+    if price <= 0.0 || lower <= 0.0 || lower >= upper {
+        return 0.0;
+    }
+    let (a, b) = ((lower / price).ln(), (upper / price).ln());
+
+    let total: f64 = (1..=TIME_IN_RANGE_SAMPLES)
+        .map(|i| {
+            let t = horizon_years * f64::from(i) / f64::from(TIME_IN_RANGE_SAMPLES);
+            let sd = volatility * t.sqrt();
+            if sd <= 0.0 {
+                return if a <= 0.0 && b > 0.0 { 1.0 } else { 0.0 };
+            }
+            normal_cdf(b / sd) - normal_cdf(a / sd)
+        })
+        .sum();
+    total / f64::from(TIME_IN_RANGE_SAMPLES)
+}
+
+/// Liquidity per unit of value relative to a full-range position, at the
+/// geometric center of the range.
+fn capital_efficiency(lower: f64, upper: f64) -> f64 {
+    if lower <= 0.0 || lower >= upper {
+        return 0.0;
+    }
+    1.0 / (1.0 - (lower / upper).powf(0.25))
+}
+
+/// Standard normal CDF (Abramowitz and Stegun 7.1.26, error below 1.5e-7).
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * z);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Formats the impermanent loss of a position as a percentage.
+fn il_display_pct(position: &MonitoredPosition) -> Decimal {
+    (position.pnl.il_pct.abs() * Decimal::ONE_HUNDRED).round_dp(2)
+}
+
+/// Converts an estimate to a decimal, mapping non-finite values to zero.
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::PositionPnL;
+    use clmm_lp_protocols::prelude::OnChainPosition;
+
+    fn position(tick_lower: i32, tick_upper: i32, tick_current: i32) -> MonitoredPosition {
+        MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                tick_lower,
+                tick_upper,
+                liquidity: 1_000_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+            },
+            pnl: PositionPnL {
+                entry_value_usd: Decimal::from(10_000),
+                current_value_usd: Decimal::from(10_000),
+                ..Default::default()
+            },
+            in_range: tick_current >= tick_lower && tick_current < tick_upper,
+            out_of_range_since: None,
+            last_updated: chrono::Utc::now(),
+        }
+    }
+
+    fn pool(tick_current: i32) -> WhirlpoolState {
+        WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current,
+            tick_spacing: 64,
+            sqrt_price: 1 << 64,
+            price: tick_to_price(tick_current),
+            liquidity: 1_000_000,
+            fee_rate_bps: 30,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+        }
+    }
+
+    #[test]
+    fn test_time_in_range_model() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-3);
+
+        let day = 24.0 / HOURS_PER_YEAR;
+        let narrow = expected_time_in_range(1.0, 0.99, 1.01, 0.8, day);
+        let wide = expected_time_in_range(1.0, 0.8, 1.25, 0.8, day);
+        assert!(narrow < wide && wide > 0.99);
+        assert_eq!(expected_time_in_range(1.0, 1.1, 1.2, 0.0, day), 0.0);
+        assert!(capital_efficiency(0.99, 1.01) > capital_efficiency(0.8, 1.25));
+    }
+
+    #[test]
+    fn test_hold_in_range() {
+        let recommender = Recommender::default();
+        let recommendation = recommender.recommend(&position(-2000, 2000, 0), &pool(0));
+
+        assert_eq!(recommendation.action, RecommendedAction::Hold);
+        assert!(recommendation.new_tick_lower.is_none());
+        assert_eq!(recommendation.impact.cost_usd, Decimal::ZERO);
+        assert!(recommendation.impact.current_time_in_range_pct > Decimal::from(90));
+    }
+
+    #[test]
+    fn test_recenter_out_of_range() {
+        let recommender = Recommender::new(
+            RecommendationConfig::default()
+                .with_volatility(0.5)
+                .with_fee_apr_pct(Decimal::from(20)),
+        );
+        let recommendation = recommender.recommend(&position(-3000, -1000, 0), &pool(0));
+
+        assert_eq!(recommendation.action, RecommendedAction::Recenter);
+        let (lower, upper) = (
+            recommendation.new_tick_lower.unwrap(),
+            recommendation.new_tick_upper.unwrap(),
+        );
+        assert!(lower < 0 && upper > 0);
+        let impact = &recommendation.impact;
+        assert!(impact.proposed_time_in_range_pct > impact.current_time_in_range_pct);
+        assert!(impact.net_benefit_usd.unwrap() > Decimal::ZERO);
+        assert!(impact.cost_usd > Decimal::ONE);
+    }
+
+    #[test]
+    fn test_widen_and_close() {
+        // At 300% volatility a 10% range is rarely in range over a day
+        let recommender = Recommender::new(RecommendationConfig::default().with_volatility(3.0));
+        let recommendation = recommender.recommend(&position(-3000, -1000, 0), &pool(0));
+        assert_eq!(recommendation.action, RecommendedAction::Widen);
+        let (lower, upper) = recommendation.new_price_range().unwrap();
+        assert!(upper / lower > Decimal::new(115, 2));
+
+        let mut losing = position(-2000, 2000, 0);
+        losing.pnl.il_pct = Decimal::new(20, 2);
+        let recommendation = Recommender::default().recommend(&losing, &pool(0));
+        assert_eq!(recommendation.action, RecommendedAction::Close);
+        assert_eq!(recommendation.impact.proposed_fee_multiplier, Decimal::ZERO);
+    }
+}
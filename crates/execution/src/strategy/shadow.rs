@@ -0,0 +1,464 @@
+//! Shadow-mode records of decisions and their reconciliation.
+//!
+//! In shadow mode the executor records every decision with its simulated
+//! outcome and executes nothing, leaving the action to an operator. Once the
+//! operator has acted, the record is reconciled against the actual on-chain
+//! outcome to measure how far the model's expectations were off before
+//! auto-execution is enabled.
+
+use super::{Decision, DecisionContext, TranscriptStep};
+use crate::error::ExecutionError;
+use crate::persistence::{AuditEntry, AuditKind, AuditLog, AuditQuery};
+use chrono::{DateTime, Utc};
+use clmm_lp_protocols::prelude::ProtocolEvent;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of shadow entries read from the audit log.
+const MAX_SHADOW_ENTRIES: usize = 10_000;
+
+/// A decision recorded in shadow mode with its simulated outcome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowRecord {
+    /// Planned instructions, expected amounts and costs.
+    #[serde(flatten)]
+    pub planned: TranscriptStep,
+    /// Pool price when the decision was made.
+    pub pool_price: Decimal,
+}
+
+impl ShadowRecord {
+    /// Simulates the outcome of a decision without executing it.
+    #[must_use]
+    pub fn plan(context: &DecisionContext, decision: &Decision) -> Self {
+        Self {
+            planned: TranscriptStep::plan(context, decision),
+            pool_price: context.pool.price,
+        }
+    }
+}
+
+/// What actually happened on-chain after a shadow decision.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActualOutcome {
+    /// Signature of the transaction that carried out the action.
+    pub signature: Option<String>,
+    /// Token A amount withdrawn, deposited or collected.
+    pub amount_a: u64,
+    /// Token B amount withdrawn, deposited or collected.
+    pub amount_b: u64,
+    /// Position value in USD after the action.
+    pub value_usd: Decimal,
+    /// Transaction cost paid in lamports.
+    pub cost_lamports: u64,
+}
+
+impl ActualOutcome {
+    /// Builds the outcome of `action` from a position's protocol events.
+    ///
+    /// Fee collections are summed for `collect_fees`, liquidity increases
+    /// for `increase_liquidity` and liquidity decreases for every other
+    /// action. The signature is taken from the first matching event.
+    #[must_use]
+    pub fn from_events(
+        action: &str,
+        position: &str,
+        events: &[ProtocolEvent],
+        value_usd: Decimal,
+    ) -> Self {
+        let mut outcome = Self {
+            value_usd,
+            ..Default::default()
+        };
+
+        for event in events {
+            let (signature, amount_a, amount_b) = match (action, event) {
+                ("collect_fees", ProtocolEvent::CollectFees(e)) if e.position == position => {
+                    (&e.signature, e.fee_a, e.fee_b)
+                }
+                ("increase_liquidity", ProtocolEvent::IncreaseLiquidity(e))
+                    if e.position == position =>
+                {
+                    (&e.signature, e.token_a_amount, e.token_b_amount)
+                }
+                ("collect_fees" | "increase_liquidity", _) => continue,
+                (_, ProtocolEvent::DecreaseLiquidity(e)) if e.position == position => {
+                    (&e.signature, e.token_a_amount, e.token_b_amount)
+                }
+                _ => continue,
+            };
+            outcome.signature.get_or_insert_with(|| signature.clone());
+            outcome.amount_a += amount_a;
+            outcome.amount_b += amount_b;
+        }
+
+        outcome
+    }
+}
+
+/// Comparison of a shadow decision with its actual outcome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowReconciliation {
+    /// Audit entry ID of the shadow record.
+    pub record_id: String,
+    /// Position the decision concerned.
+    pub position: Option<String>,
+    /// Action name (e.g. `rebalance`).
+    pub action: String,
+    /// When the decision was recorded.
+    pub decided_at: DateTime<Utc>,
+    /// Expected token A amount.
+    pub expected_amount_a: u64,
+    /// Expected token B amount.
+    pub expected_amount_b: u64,
+    /// Actual token A amount.
+    pub actual_amount_a: u64,
+    /// Actual token B amount.
+    pub actual_amount_b: u64,
+    /// Token A shortfall (negative) or surplus as a percentage of the expected amount.
+    pub slippage_a_pct: Decimal,
+    /// Token B shortfall (negative) or surplus as a percentage of the expected amount.
+    pub slippage_b_pct: Decimal,
+    /// Position value in USD when the decision was made.
+    pub expected_value_usd: Decimal,
+    /// Position value in USD after the action.
+    pub actual_value_usd: Decimal,
+    /// Difference between the actual and expected value in USD.
+    pub value_slippage_usd: Decimal,
+    /// Value difference as a percentage of the expected value.
+    pub value_slippage_pct: Decimal,
+    /// Estimated transaction cost in lamports.
+    pub expected_cost_lamports: u64,
+    /// Transaction cost paid in lamports.
+    pub actual_cost_lamports: u64,
+    /// Signature of the transaction that carried out the action.
+    pub signature: Option<String>,
+}
+
+impl ShadowReconciliation {
+    /// Compares a shadow record with the actual outcome.
+    #[must_use]
+    pub fn new(
+        record_id: impl Into<String>,
+        position: Option<String>,
+        decided_at: DateTime<Utc>,
+        record: &ShadowRecord,
+        outcome: &ActualOutcome,
+    ) -> Self {
+        let planned = &record.planned;
+        let value_slippage_usd = outcome.value_usd - planned.position_value_usd;
+
+        Self {
+            record_id: record_id.into(),
+            position,
+            action: planned.action.clone(),
+            decided_at,
+            expected_amount_a: planned.expected_amount_a,
+            expected_amount_b: planned.expected_amount_b,
+            actual_amount_a: outcome.amount_a,
+            actual_amount_b: outcome.amount_b,
+            slippage_a_pct: pct_change(
+                Decimal::from(planned.expected_amount_a),
+                Decimal::from(outcome.amount_a),
+            ),
+            slippage_b_pct: pct_change(
+                Decimal::from(planned.expected_amount_b),
+                Decimal::from(outcome.amount_b),
+            ),
+            expected_value_usd: planned.position_value_usd,
+            actual_value_usd: outcome.value_usd,
+            value_slippage_usd,
+            value_slippage_pct: pct_change(planned.position_value_usd, outcome.value_usd),
+            expected_cost_lamports: planned.estimated_cost_lamports,
+            actual_cost_lamports: outcome.cost_lamports,
+            signature: outcome.signature.clone(),
+        }
+    }
+}
+
+/// A shadow decision and its reconciliation, if any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowEntry {
+    /// Audit entry ID of the record.
+    pub id: String,
+    /// When the decision was recorded.
+    pub timestamp: DateTime<Utc>,
+    /// Position the decision concerned.
+    pub position: Option<String>,
+    /// The recorded decision.
+    pub record: ShadowRecord,
+    /// Comparison with the actual outcome, once reconciled.
+    pub reconciliation: Option<ShadowReconciliation>,
+}
+
+/// Aggregate model slippage over reconciled shadow decisions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShadowSummary {
+    /// Number of recorded decisions.
+    pub decisions: usize,
+    /// Number of decisions reconciled against an outcome.
+    pub reconciled: usize,
+    /// Mean absolute value slippage as a percentage.
+    pub mean_abs_value_slippage_pct: Decimal,
+    /// Largest absolute value slippage as a percentage.
+    pub max_abs_value_slippage_pct: Decimal,
+    /// Sum of value differences in USD.
+    pub total_value_slippage_usd: Decimal,
+    /// Sum of actual minus estimated transaction costs in lamports.
+    pub cost_difference_lamports: i64,
+}
+
+impl ShadowSummary {
+    /// Summarizes shadow entries.
+    #[must_use]
+    pub fn from_entries(entries: &[ShadowEntry]) -> Self {
+        let mut summary = Self {
+            decisions: entries.len(),
+            ..Default::default()
+        };
+        let mut total_abs_pct = Decimal::ZERO;
+
+        for reconciliation in entries.iter().filter_map(|e| e.reconciliation.as_ref()) {
+            let abs_pct = reconciliation.value_slippage_pct.abs();
+            summary.reconciled += 1;
+            total_abs_pct += abs_pct;
+            summary.max_abs_value_slippage_pct = summary.max_abs_value_slippage_pct.max(abs_pct);
+            summary.total_value_slippage_usd += reconciliation.value_slippage_usd;
+            summary.cost_difference_lamports += reconciliation.actual_cost_lamports as i64
+                - reconciliation.expected_cost_lamports as i64;
+        }
+        if summary.reconciled > 0 {
+            summary.mean_abs_value_slippage_pct = total_abs_pct / Decimal::from(summary.reconciled);
+        }
+
+        summary
+    }
+}
+
+/// Shadow decisions of a strategy with their reconciliations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShadowReport {
+    /// Decisions, newest first.
+    pub entries: Vec<ShadowEntry>,
+    /// Aggregate model slippage.
+    pub summary: ShadowSummary,
+}
+
+/// Loads the shadow decisions recorded in an audit log.
+///
+/// Only decisions of `strategy_id` are included when given.
+///
+/// # Errors
+/// Returns an error if the audit log cannot be queried.
+pub async fn shadow_report(
+    audit: &dyn AuditLog,
+    strategy_id: Option<&str>,
+) -> anyhow::Result<ShadowReport> {
+    let query = |kind| AuditQuery {
+        strategy_id: strategy_id.map(str::to_string),
+        kind: Some(kind),
+        limit: MAX_SHADOW_ENTRIES,
+        ..Default::default()
+    };
+    let reconciliations: Vec<ShadowReconciliation> = audit
+        .query(&query(AuditKind::Reconciliation))
+        .await?
+        .into_iter()
+        .filter_map(|entry| serde_json::from_value(entry.details).ok())
+        .collect();
+
+    let entries: Vec<ShadowEntry> = audit
+        .query(&query(AuditKind::Shadow))
+        .await?
+        .into_iter()
+        .filter_map(|entry| {
+            let record = serde_json::from_value(entry.details).ok()?;
+            // The newest reconciliation of a record wins
+            let reconciliation = reconciliations
+                .iter()
+                .find(|r| r.record_id == entry.id)
+                .cloned();
+            Some(ShadowEntry {
+                id: entry.id,
+                timestamp: entry.timestamp,
+                position: entry.position,
+                record,
+                reconciliation,
+            })
+        })
+        .collect();
+
+    Ok(ShadowReport {
+        summary: ShadowSummary::from_entries(&entries),
+        entries,
+    })
+}
+
+/// Reconciles a shadow decision with its actual outcome.
+///
+/// Only records of `strategy_id` are considered when given. The
+/// reconciliation is appended to the audit log under the record's strategy
+/// and position.
+///
+/// # Errors
+/// Returns [`ExecutionError::ShadowRecordNotFound`] if no shadow record has
+/// the ID, or an error if the audit log fails.
+pub async fn reconcile_shadow(
+    audit: &dyn AuditLog,
+    strategy_id: Option<&str>,
+    record_id: &str,
+    outcome: &ActualOutcome,
+) -> anyhow::Result<ShadowReconciliation> {
+    let entry = audit
+        .query(&AuditQuery {
+            strategy_id: strategy_id.map(str::to_string),
+            kind: Some(AuditKind::Shadow),
+            limit: MAX_SHADOW_ENTRIES,
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .find(|entry| entry.id == record_id)
+        .ok_or_else(|| ExecutionError::ShadowRecordNotFound(record_id.to_string()))?;
+    let record: ShadowRecord = serde_json::from_value(entry.details)
+        .map_err(|_| ExecutionError::ShadowRecordNotFound(record_id.to_string()))?;
+
+    let reconciliation = ShadowReconciliation::new(
+        record_id,
+        entry.position.clone(),
+        entry.timestamp,
+        &record,
+        outcome,
+    );
+
+    let mut audit_entry = AuditEntry::new(AuditKind::Reconciliation, &reconciliation.action)
+        .with_strategy(entry.strategy_id.as_deref())
+        .with_details(serde_json::to_value(&reconciliation)?);
+    audit_entry.position = entry.position;
+    audit_entry.signature = outcome.signature.clone();
+    audit.append(&audit_entry).await?;
+
+    Ok(reconciliation)
+}
+
+/// Returns the change from `expected` to `actual` as a percentage.
+fn pct_change(expected: Decimal, actual: Decimal) -> Decimal {
+    if expected.is_zero() {
+        return Decimal::ZERO;
+    }
+    (actual - expected) / expected * Decimal::ONE_HUNDRED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::MemoryAuditLog;
+    use clmm_lp_protocols::prelude::{CollectFeesEvent, LiquidityEvent};
+
+    fn record(expected_a: u64, value_usd: Decimal) -> ShadowRecord {
+        ShadowRecord {
+            planned: TranscriptStep {
+                action: "close".to_string(),
+                description: "Close position".to_string(),
+                pool_tick: 0,
+                instructions: Vec::new(),
+                expected_amount_a: expected_a,
+                expected_amount_b: 0,
+                position_value_usd: value_usd,
+                estimated_cost_lamports: 15_000,
+            },
+            pool_price: Decimal::ONE,
+        }
+    }
+
+    fn liquidity_event(position: &str, signature: &str, amount_a: u64) -> LiquidityEvent {
+        LiquidityEvent {
+            signature: signature.to_string(),
+            pool: "pool".to_string(),
+            position: position.to_string(),
+            timestamp: 0,
+            slot: 0,
+            liquidity_delta: 1,
+            token_a_amount: amount_a,
+            token_b_amount: 0,
+            tick_lower: -10,
+            tick_upper: 10,
+        }
+    }
+
+    #[test]
+    fn test_outcome_from_events() {
+        let events = vec![
+            ProtocolEvent::DecreaseLiquidity(liquidity_event("pos", "sig1", 600)),
+            ProtocolEvent::DecreaseLiquidity(liquidity_event("other", "sig2", 1_000)),
+            ProtocolEvent::DecreaseLiquidity(liquidity_event("pos", "sig3", 390)),
+            ProtocolEvent::CollectFees(CollectFeesEvent {
+                signature: "sig4".to_string(),
+                pool: "pool".to_string(),
+                position: "pos".to_string(),
+                timestamp: 0,
+                slot: 0,
+                fee_a: 7,
+                fee_b: 3,
+            }),
+        ];
+
+        let close = ActualOutcome::from_events("close", "pos", &events, Decimal::from(990));
+        assert_eq!(close.amount_a, 990);
+        assert_eq!(close.signature.as_deref(), Some("sig1"));
+
+        let fees = ActualOutcome::from_events("collect_fees", "pos", &events, Decimal::ZERO);
+        assert_eq!((fees.amount_a, fees.amount_b), (7, 3));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_and_report() {
+        let audit = MemoryAuditLog::default();
+        let entry = AuditEntry::new(AuditKind::Shadow, "close")
+            .with_strategy(Some("s1"))
+            .with_details(serde_json::to_value(record(1_000, Decimal::from(1_000))).unwrap());
+        audit.append(&entry).await.unwrap();
+        let unexecuted = AuditEntry::new(AuditKind::Shadow, "close")
+            .with_strategy(Some("s1"))
+            .with_details(serde_json::to_value(record(500, Decimal::from(500))).unwrap());
+        audit.append(&unexecuted).await.unwrap();
+
+        let outcome = ActualOutcome {
+            signature: Some("sig".to_string()),
+            amount_a: 990,
+            amount_b: 0,
+            value_usd: Decimal::from(980),
+            cost_lamports: 20_000,
+        };
+        let reconciliation = reconcile_shadow(&audit, Some("s1"), &entry.id, &outcome)
+            .await
+            .unwrap();
+        assert_eq!(reconciliation.slippage_a_pct, Decimal::from(-1));
+        assert_eq!(reconciliation.value_slippage_usd, Decimal::from(-20));
+        assert_eq!(reconciliation.value_slippage_pct, Decimal::from(-2));
+
+        let report = shadow_report(&audit, Some("s1")).await.unwrap();
+        assert_eq!(report.summary.decisions, 2);
+        assert_eq!(report.summary.reconciled, 1);
+        assert_eq!(report.summary.max_abs_value_slippage_pct, Decimal::from(2));
+        assert_eq!(report.summary.cost_difference_lamports, 5_000);
+        assert!(report.entries[0].reconciliation.is_none());
+        assert_eq!(
+            report.entries[1]
+                .reconciliation
+                .as_ref()
+                .unwrap()
+                .signature
+                .as_deref(),
+            Some("sig")
+        );
+
+        let other_strategy = reconcile_shadow(&audit, Some("s2"), &entry.id, &outcome)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            ExecutionError::find(&other_strategy),
+            Some(ExecutionError::ShadowRecordNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+//! Stop-loss and emergency-exit rules.
+
+use super::DecisionContext;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// Configuration for stop-loss exits.
+///
+/// Each limit is optional; an exit triggers as soon as any configured
+/// limit is breached.
+#[derive(Debug, Clone)]
+pub struct StopLossConfig {
+    /// Maximum impermanent loss before exiting (as percentage).
+    pub max_il_pct: Option<Decimal>,
+    /// Maximum drawdown from the peak position value (as percentage).
+    pub max_drawdown_pct: Option<Decimal>,
+    /// Exit if the pool price falls below this level.
+    pub min_price: Option<Decimal>,
+    /// Exit if the pool price rises above this level.
+    pub max_price: Option<Decimal>,
+    /// Whether to swap withdrawn tokens to the quote token after closing.
+    pub swap_to_quote: bool,
+    /// Whether exits must be confirmed by an operator before executing.
+    pub require_confirmation: bool,
+    /// Seconds an exit may await confirmation before the alert is escalated.
+    pub escalate_after_secs: u64,
+}
+
+impl Default for StopLossConfig {
+    fn default() -> Self {
+        Self {
+            max_il_pct: Some(Decimal::new(20, 2)),       // 20%
+            max_drawdown_pct: Some(Decimal::new(25, 2)), // 25%
+            min_price: None,
+            max_price: None,
+            swap_to_quote: false,
+            require_confirmation: true,
+            escalate_after_secs: 15 * 60, // 15 minutes
+        }
+    }
+}
+
+impl StopLossConfig {
+    /// Checks a position against the configured limits.
+    ///
+    /// Returns the first breached limit, checking IL, drawdown and then price.
+    #[must_use]
+    pub fn check(&self, context: &DecisionContext) -> Option<ExitTrigger> {
+        let pnl = &context.position.pnl;
+
+        if let Some(limit) = self.max_il_pct
+            && pnl.il_pct.abs() > limit
+        {
+            return Some(ExitTrigger::ImpermanentLoss {
+                il_pct: pnl.il_pct,
+                limit,
+            });
+        }
+
+        if let Some(limit) = self.max_drawdown_pct
+            && context.peak_value_usd > Decimal::ZERO
+        {
+            let drawdown_pct =
+                (context.peak_value_usd - pnl.current_value_usd) / context.peak_value_usd;
+            if drawdown_pct > limit {
+                return Some(ExitTrigger::Drawdown {
+                    drawdown_pct,
+                    limit,
+                });
+            }
+        }
+
+        let price = context.pool.price;
+
+        if let Some(limit) = self.min_price
+            && price < limit
+        {
+            return Some(ExitTrigger::PriceBelow { price, limit });
+        }
+
+        if let Some(limit) = self.max_price
+            && price > limit
+        {
+            return Some(ExitTrigger::PriceAbove { price, limit });
+        }
+
+        None
+    }
+}
+
+/// Limit that triggered an emergency exit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitTrigger {
+    /// Impermanent loss exceeded its limit.
+    ImpermanentLoss {
+        /// Current IL.
+        il_pct: Decimal,
+        /// Configured limit.
+        limit: Decimal,
+    },
+    /// Position value fell too far from its peak.
+    Drawdown {
+        /// Current drawdown from peak.
+        drawdown_pct: Decimal,
+        /// Configured limit.
+        limit: Decimal,
+    },
+    /// Pool price fell below the lower limit.
+    PriceBelow {
+        /// Current price.
+        price: Decimal,
+        /// Configured limit.
+        limit: Decimal,
+    },
+    /// Pool price rose above the upper limit.
+    PriceAbove {
+        /// Current price.
+        price: Decimal,
+        /// Configured limit.
+        limit: Decimal,
+    },
+}
+
+impl ExitTrigger {
+    /// Returns a human-readable description.
+    #[must_use]
+    pub fn description(&self) -> String {
+        let hundred = Decimal::ONE_HUNDRED;
+        match self {
+            Self::ImpermanentLoss { il_pct, limit } => format!(
+                "IL {:.2}% exceeds {:.2}% limit",
+                il_pct.abs() * hundred,
+                limit * hundred
+            ),
+            Self::Drawdown {
+                drawdown_pct,
+                limit,
+            } => format!(
+                "drawdown {:.2}% exceeds {:.2}% limit",
+                drawdown_pct * hundred,
+                limit * hundred
+            ),
+            Self::PriceBelow { price, limit } => {
+                format!("price {} below {} limit", price, limit)
+            }
+            Self::PriceAbove { price, limit } => {
+                format!("price {} above {} limit", price, limit)
+            }
+        }
+    }
+}
+
+/// Emergency exit awaiting operator confirmation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingExit {
+    /// Position to exit.
+    pub position: Pubkey,
+    /// Limit that triggered the exit.
+    pub trigger: ExitTrigger,
+    /// Whether to swap to the quote token after closing.
+    pub swap_to_quote: bool,
+    /// When the breach was first detected.
+    pub detected_at: DateTime<Utc>,
+    /// Whether the alert has been escalated.
+    pub escalated: bool,
+}
+
+impl PendingExit {
+    /// Creates a new pending exit.
+    #[must_use]
+    pub fn new(position: Pubkey, trigger: ExitTrigger, swap_to_quote: bool) -> Self {
+        Self {
+            position,
+            trigger,
+            swap_to_quote,
+            detected_at: Utc::now(),
+            escalated: false,
+        }
+    }
+
+    /// Returns true if the exit has waited long enough to escalate.
+    #[must_use]
+    pub fn needs_escalation(&self, now: DateTime<Utc>, escalate_after_secs: u64) -> bool {
+        !self.escalated && (now - self.detected_at).num_seconds() >= escalate_after_secs as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::{MonitoredPosition, PositionPnL};
+    use clmm_lp_protocols::prelude::{OnChainPosition, WhirlpoolState};
+    use rust_decimal_macros::dec;
+
+    fn create_test_context(pnl: PositionPnL, peak: Decimal, price: Decimal) -> DecisionContext {
+        let on_chain = OnChainPosition {
+            address: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            tick_lower: -1000,
+            tick_upper: 1000,
+            liquidity: 1000000,
+            fee_growth_inside_a: 0,
+            fee_growth_inside_b: 0,
+            fees_owed_a: 0,
+            fees_owed_b: 0,
+        };
+
+        DecisionContext {
+            position: MonitoredPosition {
+                address: on_chain.address,
+                pool: on_chain.pool,
+                on_chain,
+                pnl,
+                in_range: true,
+                out_of_range_since: None,
+                last_updated: Utc::now(),
+            },
+            pool: WhirlpoolState {
+                address: String::new(),
+                token_mint_a: Pubkey::new_unique(),
+                token_mint_b: Pubkey::new_unique(),
+                tick_current: 0,
+                tick_spacing: 64,
+                sqrt_price: 1 << 64,
+                price,
+                liquidity: 1000000,
+                fee_rate_bps: 30,
+                protocol_fee_rate_bps: 0,
+                fee_growth_global_a: 0,
+                fee_growth_global_b: 0,
+            },
+            hours_since_rebalance: 48,
+            peak_value_usd: peak,
+        }
+    }
+
+    #[test]
+    fn test_no_trigger_within_limits() {
+        let config = StopLossConfig::default();
+        let pnl = PositionPnL {
+            il_pct: dec!(0.05),
+            current_value_usd: dec!(900),
+            ..Default::default()
+        };
+        let context = create_test_context(pnl, dec!(1000), Decimal::ONE);
+        assert!(config.check(&context).is_none());
+    }
+
+    #[test]
+    fn test_drawdown_trigger() {
+        let config = StopLossConfig::default();
+        let pnl = PositionPnL {
+            current_value_usd: dec!(700),
+            ..Default::default()
+        };
+        let context = create_test_context(pnl, dec!(1000), Decimal::ONE);
+        assert_eq!(
+            config.check(&context),
+            Some(ExitTrigger::Drawdown {
+                drawdown_pct: dec!(0.3),
+                limit: dec!(0.25),
+            })
+        );
+    }
+
+    #[test]
+    fn test_price_triggers() {
+        let config = StopLossConfig {
+            min_price: Some(dec!(90)),
+            max_price: Some(dec!(110)),
+            ..Default::default()
+        };
+
+        let below = create_test_context(PositionPnL::default(), Decimal::ZERO, dec!(85));
+        assert!(matches!(
+            config.check(&below),
+            Some(ExitTrigger::PriceBelow { .. })
+        ));
+
+        let above = create_test_context(PositionPnL::default(), Decimal::ZERO, dec!(120));
+        assert!(matches!(
+            config.check(&above),
+            Some(ExitTrigger::PriceAbove { .. })
+        ));
+
+        let inside = create_test_context(PositionPnL::default(), Decimal::ZERO, dec!(100));
+        assert!(config.check(&inside).is_none());
+    }
+
+    #[test]
+    fn test_pending_exit_escalation() {
+        let trigger = ExitTrigger::PriceBelow {
+            price: dec!(85),
+            limit: dec!(90),
+        };
+        let mut pending = PendingExit::new(Pubkey::new_unique(), trigger, true);
+
+        assert!(!pending.needs_escalation(pending.detected_at, 60));
+        let later = pending.detected_at + chrono::Duration::seconds(61);
+        assert!(pending.needs_escalation(later, 60));
+
+        pending.escalated = true;
+        assert!(!pending.needs_escalation(later, 60));
+    }
+}
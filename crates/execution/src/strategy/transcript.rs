@@ -0,0 +1,319 @@
+//! Dry-run transcripts of the actions a strategy would take.
+
+use super::{Decision, DecisionContext};
+use clmm_lp_protocols::prelude::tick_to_price;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// Program name of Orca Whirlpool instructions.
+const WHIRLPOOL_PROGRAM: &str = "whirlpool";
+
+/// Estimated base fee per transaction in lamports.
+const BASE_FEE_LAMPORTS: u64 = 5000;
+
+/// An instruction a dry run would have sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedInstruction {
+    /// Program the instruction targets.
+    pub program: String,
+    /// Instruction name.
+    pub instruction: String,
+    /// Instruction arguments.
+    pub args: serde_json::Value,
+}
+
+impl PlannedInstruction {
+    /// Creates a Whirlpool instruction.
+    pub(crate) fn whirlpool(instruction: &str, args: serde_json::Value) -> Self {
+        Self {
+            program: WHIRLPOOL_PROGRAM.to_string(),
+            instruction: instruction.to_string(),
+            args,
+        }
+    }
+}
+
+/// One action in a dry-run transcript.
+///
+/// Amounts are raw token amounts expected from the position at the current
+/// pool price; costs assume one transaction per instruction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptStep {
+    /// Action name (e.g. `rebalance`).
+    pub action: String,
+    /// Human-readable description.
+    pub description: String,
+    /// Pool tick when the action was planned.
+    pub pool_tick: i32,
+    /// Instructions that would have been sent, in order.
+    pub instructions: Vec<PlannedInstruction>,
+    /// Expected token A amount withdrawn (or deposited for increases).
+    pub expected_amount_a: u64,
+    /// Expected token B amount withdrawn (or deposited for increases).
+    pub expected_amount_b: u64,
+    /// Position value in USD when planned.
+    pub position_value_usd: Decimal,
+    /// Estimated transaction cost in lamports.
+    pub estimated_cost_lamports: u64,
+}
+
+impl TranscriptStep {
+    /// Plans the instructions for a decision without executing them.
+    #[must_use]
+    pub fn plan(context: &DecisionContext, decision: &Decision) -> Self {
+        let position = &context.position;
+        let on_chain = &position.on_chain;
+        let position_address = position.address.to_string();
+        let (amount_a, amount_b) = expected_amounts(
+            on_chain.liquidity,
+            context.pool.tick_current,
+            on_chain.tick_lower,
+            on_chain.tick_upper,
+        );
+
+        let collect = PlannedInstruction::whirlpool(
+            "collect_fees",
+            serde_json::json!({
+                "position": position_address,
+                "fees_owed_a": on_chain.fees_owed_a,
+                "fees_owed_b": on_chain.fees_owed_b,
+            }),
+        );
+        let decrease_all = PlannedInstruction::whirlpool(
+            "decrease_liquidity",
+            serde_json::json!({
+                "position": position_address,
+                "liquidity_amount": on_chain.liquidity.to_string(),
+                "token_min_a": amount_a,
+                "token_min_b": amount_b,
+            }),
+        );
+        let close = PlannedInstruction::whirlpool(
+            "close_position",
+            serde_json::json!({ "position": position_address }),
+        );
+
+        let (instructions, expected_amount_a, expected_amount_b) = match decision {
+            Decision::Hold => (Vec::new(), 0, 0),
+            Decision::Rebalance {
+                new_tick_lower,
+                new_tick_upper,
+            } => {
+                let (new_a, new_b) = expected_amounts(
+                    on_chain.liquidity,
+                    context.pool.tick_current,
+                    *new_tick_lower,
+                    *new_tick_upper,
+                );
+                let instructions = vec![
+                    collect,
+                    decrease_all,
+                    close,
+                    PlannedInstruction::whirlpool(
+                        "open_position",
+                        serde_json::json!({
+                            "pool": position.pool.to_string(),
+                            "tick_lower": new_tick_lower,
+                            "tick_upper": new_tick_upper,
+                        }),
+                    ),
+                    PlannedInstruction::whirlpool(
+                        "increase_liquidity",
+                        serde_json::json!({
+                            "liquidity_amount": on_chain.liquidity.to_string(),
+                            "token_max_a": new_a,
+                            "token_max_b": new_b,
+                        }),
+                    ),
+                ];
+                (instructions, amount_a, amount_b)
+            }
+            Decision::Close => (vec![collect, decrease_all, close], amount_a, amount_b),
+            Decision::EmergencyExit { swap_to_quote, .. } => {
+                let mut instructions = vec![collect, decrease_all, close];
+                if *swap_to_quote {
+                    instructions.push(PlannedInstruction {
+                        program: "swap".to_string(),
+                        instruction: "swap_to_quote".to_string(),
+                        args: serde_json::json!({
+                            "input_mint": context.pool.token_mint_a.to_string(),
+                            "output_mint": context.pool.token_mint_b.to_string(),
+                            "amount": amount_a,
+                        }),
+                    });
+                }
+                (instructions, amount_a, amount_b)
+            }
+            Decision::IncreaseLiquidity { amount } | Decision::DecreaseLiquidity { amount } => {
+                let is_increase = matches!(decision, Decision::IncreaseLiquidity { .. });
+                let (a, b) = scale_amounts(
+                    (amount_a, amount_b),
+                    *amount,
+                    position.pnl.current_value_usd,
+                );
+                let name = if is_increase {
+                    "increase_liquidity"
+                } else {
+                    "decrease_liquidity"
+                };
+                let instruction = PlannedInstruction::whirlpool(
+                    name,
+                    serde_json::json!({
+                        "position": position_address,
+                        "amount_usd": amount,
+                    }),
+                );
+                (vec![instruction], a, b)
+            }
+            Decision::CollectFees => (vec![collect], on_chain.fees_owed_a, on_chain.fees_owed_b),
+        };
+
+        Self {
+            action: decision.name().to_string(),
+            description: decision.description(),
+            pool_tick: context.pool.tick_current,
+            estimated_cost_lamports: BASE_FEE_LAMPORTS * instructions.len() as u64,
+            instructions,
+            expected_amount_a,
+            expected_amount_b,
+            position_value_usd: position.pnl.current_value_usd,
+        }
+    }
+}
+
+/// Calculates the raw token amounts of a liquidity range at the current tick.
+pub(crate) fn expected_amounts(
+    liquidity: u128,
+    tick_current: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> (u64, u64) {
+    let sqrt = |tick: i32| tick_to_price(tick).to_f64().unwrap_or(0.0).sqrt();
+    let l = liquidity as f64;
+    let sqrt_a = sqrt(tick_lower);
+    let sqrt_b = sqrt(tick_upper);
+    if sqrt_a <= 0.0 || sqrt_b <= sqrt_a {
+        return (0, 0);
+    }
+    let sqrt_p = sqrt(tick_current).clamp(sqrt_a, sqrt_b);
+
+    let amount_a = l * (1.0 / sqrt_p - 1.0 / sqrt_b);
+    let amount_b = l * (sqrt_p - sqrt_a);
+    (amount_a as u64, amount_b as u64)
+}
+
+/// Scales token amounts by the fraction `amount_usd / value_usd`.
+fn scale_amounts(amounts: (u64, u64), amount_usd: Decimal, value_usd: Decimal) -> (u64, u64) {
+    if value_usd <= Decimal::ZERO {
+        return (0, 0);
+    }
+    let fraction = (amount_usd.abs() / value_usd).to_f64().unwrap_or(0.0);
+    (
+        (amounts.0 as f64 * fraction) as u64,
+        (amounts.1 as f64 * fraction) as u64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::{MonitoredPosition, PositionPnL};
+    use clmm_lp_protocols::prelude::{OnChainPosition, WhirlpoolState};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn create_test_context() -> DecisionContext {
+        let position = MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                tick_lower: -1000,
+                tick_upper: 1000,
+                liquidity: 1_000_000_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 250,
+                fees_owed_b: 500,
+            },
+            pnl: PositionPnL {
+                current_value_usd: Decimal::from(1000),
+                ..Default::default()
+            },
+            in_range: true,
+            out_of_range_since: None,
+            last_updated: chrono::Utc::now(),
+        };
+
+        let pool = WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 64,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 1_000_000_000,
+            fee_rate_bps: 30,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+        };
+
+        DecisionContext {
+            position,
+            pool,
+            hours_since_rebalance: 48,
+            peak_value_usd: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_plan_rebalance() {
+        let context = create_test_context();
+        let step = TranscriptStep::plan(
+            &context,
+            &Decision::Rebalance {
+                new_tick_lower: 500,
+                new_tick_upper: 1500,
+            },
+        );
+
+        let names: Vec<&str> = step
+            .instructions
+            .iter()
+            .map(|i| i.instruction.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "collect_fees",
+                "decrease_liquidity",
+                "close_position",
+                "open_position",
+                "increase_liquidity"
+            ]
+        );
+        assert_eq!(step.estimated_cost_lamports, 25_000);
+        // Symmetric range at its midpoint holds about equal raw amounts
+        assert!(step.expected_amount_a > 0);
+        assert!(
+            step.expected_amount_a.abs_diff(step.expected_amount_b) < step.expected_amount_a / 100
+        );
+    }
+
+    #[test]
+    fn test_plan_collect_and_hold() {
+        let context = create_test_context();
+
+        let step = TranscriptStep::plan(&context, &Decision::CollectFees);
+        assert_eq!(step.instructions.len(), 1);
+        assert_eq!((step.expected_amount_a, step.expected_amount_b), (250, 500));
+
+        let step = TranscriptStep::plan(&context, &Decision::Hold);
+        assert!(step.instructions.is_empty());
+        assert_eq!(step.estimated_cost_lamports, 0);
+    }
+}
@@ -1,5 +1,6 @@
 //! Strategy decision types.
 
+use super::ExitTrigger;
 use rust_decimal::Decimal;
 
 /// Decision made by the strategy engine.
@@ -28,6 +29,13 @@ pub enum Decision {
     },
     /// Collect fees.
     CollectFees,
+    /// Close the position after a stop-loss limit was breached.
+    EmergencyExit {
+        /// Limit that triggered the exit.
+        trigger: ExitTrigger,
+        /// Whether to swap to the quote token after closing.
+        swap_to_quote: bool,
+    },
 }
 
 impl Decision {
@@ -49,6 +57,33 @@ impl Decision {
             Self::IncreaseLiquidity { amount } => format!("Increase liquidity by {}", amount),
             Self::DecreaseLiquidity { amount } => format!("Decrease liquidity by {}", amount),
             Self::CollectFees => "Collect accumulated fees".to_string(),
+            Self::EmergencyExit {
+                trigger,
+                swap_to_quote,
+            } => {
+                if *swap_to_quote {
+                    format!(
+                        "Emergency exit ({}) and swap to quote",
+                        trigger.description()
+                    )
+                } else {
+                    format!("Emergency exit ({})", trigger.description())
+                }
+            }
+        }
+    }
+
+    /// Returns a short machine-readable name.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hold => "hold",
+            Self::Rebalance { .. } => "rebalance",
+            Self::Close => "close",
+            Self::IncreaseLiquidity { .. } => "increase_liquidity",
+            Self::DecreaseLiquidity { .. } => "decrease_liquidity",
+            Self::CollectFees => "collect_fees",
+            Self::EmergencyExit { .. } => "emergency_exit",
         }
     }
 
@@ -0,0 +1,404 @@
+//! Jupiter v6 swap quotes and instructions.
+
+use crate::error::ExecutionError;
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde_json::{Value, json};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use tracing::{debug, instrument};
+
+/// Default Jupiter v6 swap API URL.
+pub const JUPITER_SWAP_API_URL: &str = "https://quote-api.jup.ag/v6";
+
+/// A swap to quote.
+///
+/// The amount is in raw base units of the input token.
+#[derive(Debug, Clone)]
+pub struct QuoteRequest {
+    /// Mint of the sold token.
+    pub input_mint: Pubkey,
+    /// Mint of the bought token.
+    pub output_mint: Pubkey,
+    /// Amount of the sold token.
+    pub amount: u64,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+}
+
+/// A Jupiter route for a swap.
+#[derive(Debug, Clone)]
+pub struct SwapQuote {
+    /// Mint of the sold token.
+    pub input_mint: Pubkey,
+    /// Mint of the bought token.
+    pub output_mint: Pubkey,
+    /// Amount sold.
+    pub in_amount: u64,
+    /// Expected amount bought.
+    pub out_amount: u64,
+    /// Minimum amount bought after slippage; the swap fails below it.
+    pub min_out_amount: u64,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+    /// Price impact of the route as a fraction (0.01 = 1%).
+    pub price_impact: Decimal,
+    /// Quote as returned by the API, sent back to build the swap.
+    response: Value,
+}
+
+impl SwapQuote {
+    /// Parses a quote response.
+    fn parse(response: Value) -> Result<Self> {
+        let pubkey = |field: &str| -> Result<Pubkey> {
+            let value = response[field]
+                .as_str()
+                .with_context(|| format!("Jupiter quote has no {}", field))?;
+            Pubkey::from_str(value).with_context(|| format!("Invalid {} in Jupiter quote", field))
+        };
+        let amount = |field: &str| -> Result<u64> {
+            response[field]
+                .as_str()
+                .and_then(|v| v.parse().ok())
+                .with_context(|| format!("Invalid {} in Jupiter quote", field))
+        };
+
+        Ok(Self {
+            input_mint: pubkey("inputMint")?,
+            output_mint: pubkey("outputMint")?,
+            in_amount: amount("inAmount")?,
+            out_amount: amount("outAmount")?,
+            min_out_amount: amount("otherAmountThreshold")?,
+            slippage_bps: response["slippageBps"]
+                .as_u64()
+                .and_then(|v| u16::try_from(v).ok())
+                .unwrap_or_default(),
+            price_impact: response["priceImpactPct"]
+                .as_str()
+                .and_then(|v| Decimal::from_str(v).ok())
+                .unwrap_or_default(),
+            response,
+        })
+    }
+
+    /// Gets the price impact in basis points.
+    #[must_use]
+    pub fn price_impact_bps(&self) -> u32 {
+        (self.price_impact.abs() * Decimal::from(10_000))
+            .ceil()
+            .to_u32()
+            .unwrap_or(u32::MAX)
+    }
+}
+
+/// Instructions performing a quoted swap.
+///
+/// Compute budget instructions are left out; the sender prices and sizes
+/// the transaction itself.
+#[derive(Debug, Clone)]
+pub struct SwapInstructions {
+    /// Token account creation and SOL wrapping.
+    pub setup: Vec<Instruction>,
+    /// The swap.
+    pub swap: Instruction,
+    /// SOL unwrapping, if needed.
+    pub cleanup: Option<Instruction>,
+}
+
+impl SwapInstructions {
+    /// Gets the instructions in execution order.
+    #[must_use]
+    pub fn into_instructions(self) -> Vec<Instruction> {
+        let mut instructions = self.setup;
+        instructions.push(self.swap);
+        instructions.extend(self.cleanup);
+        instructions
+    }
+}
+
+/// Client for the Jupiter v6 swap API.
+///
+/// Routes are requested as legacy transactions so they fit the
+/// transactions the engine builds, without address lookup tables.
+pub struct JupiterSwapper {
+    /// HTTP client.
+    client: reqwest::Client,
+    /// Swap API URL.
+    url: String,
+    /// API key, sent as `x-api-key`.
+    api_key: Option<String>,
+    /// Maximum accepted price impact in basis points.
+    max_price_impact_bps: u32,
+}
+
+impl Default for JupiterSwapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JupiterSwapper {
+    /// Creates a client for the public API accepting up to 1% price impact.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: JUPITER_SWAP_API_URL.to_string(),
+            api_key: None,
+            max_price_impact_bps: 100,
+        }
+    }
+
+    /// Sets the swap API URL.
+    #[must_use]
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Sets the API key.
+    #[must_use]
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sets the maximum accepted price impact in basis points.
+    #[must_use]
+    pub fn with_max_price_impact_bps(mut self, max_bps: u32) -> Self {
+        self.max_price_impact_bps = max_bps;
+        self
+    }
+
+    /// Gets the best route for a swap.
+    ///
+    /// # Errors
+    /// Returns [`ExecutionError::PriceImpactTooHigh`] if the route moves the
+    /// price more than allowed.
+    #[instrument(skip_all, fields(input = %request.input_mint, output = %request.output_mint))]
+    pub async fn quote(&self, request: &QuoteRequest) -> Result<SwapQuote> {
+        let query = [
+            ("inputMint", request.input_mint.to_string()),
+            ("outputMint", request.output_mint.to_string()),
+            ("amount", request.amount.to_string()),
+            ("slippageBps", request.slippage_bps.to_string()),
+            ("asLegacyTransaction", "true".to_string()),
+        ];
+        let response = self
+            .authorize(self.client.get(format!("{}/quote", self.url)))
+            .query(&query)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Jupiter quote returned {}: {}", status, body);
+        }
+
+        let quote = SwapQuote::parse(response.json().await?)?;
+        debug!(
+            in_amount = quote.in_amount,
+            out_amount = quote.out_amount,
+            price_impact = %quote.price_impact,
+            "Received Jupiter quote"
+        );
+
+        let impact_bps = quote.price_impact_bps();
+        if impact_bps > self.max_price_impact_bps {
+            return Err(ExecutionError::PriceImpactTooHigh {
+                impact_bps,
+                max_bps: self.max_price_impact_bps,
+            }
+            .into());
+        }
+        Ok(quote)
+    }
+
+    /// Builds the instructions of a quoted swap for a wallet.
+    #[instrument(skip_all, fields(user = %user))]
+    pub async fn swap_instructions(
+        &self,
+        quote: &SwapQuote,
+        user: &Pubkey,
+    ) -> Result<SwapInstructions> {
+        let body = json!({
+            "quoteResponse": quote.response,
+            "userPublicKey": user.to_string(),
+            "wrapAndUnwrapSol": true,
+            "asLegacyTransaction": true,
+        });
+        let response = self
+            .authorize(self.client.post(format!("{}/swap-instructions", self.url)))
+            .json(&body)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Jupiter swap instructions returned {}: {}", status, body);
+        }
+
+        parse_swap_instructions(&response.json().await?)
+    }
+
+    /// Adds the API key header if one is set.
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.api_key {
+            Some(key) => request.header("x-api-key", key),
+            None => request,
+        }
+    }
+}
+
+/// Parses a `/swap-instructions` response.
+fn parse_swap_instructions(body: &Value) -> Result<SwapInstructions> {
+    if let Some(error) = body["error"].as_str() {
+        anyhow::bail!("Jupiter swap instructions failed: {}", error);
+    }
+    if body["addressLookupTableAddresses"]
+        .as_array()
+        .is_some_and(|tables| !tables.is_empty())
+    {
+        anyhow::bail!("Jupiter route needs address lookup tables");
+    }
+
+    let setup = body["setupInstructions"]
+        .as_array()
+        .map(|instructions| {
+            instructions
+                .iter()
+                .map(parse_instruction)
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let cleanup = match &body["cleanupInstruction"] {
+        Value::Null => None,
+        instruction => Some(parse_instruction(instruction)?),
+    };
+
+    Ok(SwapInstructions {
+        setup,
+        swap: parse_instruction(&body["swapInstruction"])?,
+        cleanup,
+    })
+}
+
+/// Parses an instruction (`programId`, `accounts`, base64 `data`).
+fn parse_instruction(value: &Value) -> Result<Instruction> {
+    let program_id = value["programId"]
+        .as_str()
+        .and_then(|v| Pubkey::from_str(v).ok())
+        .context("Invalid program ID in Jupiter instruction")?;
+    let accounts = value["accounts"]
+        .as_array()
+        .context("Jupiter instruction has no accounts")?
+        .iter()
+        .map(|account| {
+            let pubkey = account["pubkey"]
+                .as_str()
+                .and_then(|v| Pubkey::from_str(v).ok())
+                .context("Invalid account in Jupiter instruction")?;
+            let is_signer = account["isSigner"].as_bool().unwrap_or_default();
+            Ok(if account["isWritable"].as_bool().unwrap_or_default() {
+                AccountMeta::new(pubkey, is_signer)
+            } else {
+                AccountMeta::new_readonly(pubkey, is_signer)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let data = STANDARD
+        .decode(value["data"].as_str().unwrap_or_default())
+        .context("Invalid data in Jupiter instruction")?;
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn instruction(program: &Pubkey, account: &Pubkey, data: &[u8]) -> Value {
+        json!({
+            "programId": program.to_string(),
+            "accounts": [
+                { "pubkey": account.to_string(), "isSigner": true, "isWritable": true },
+                { "pubkey": program.to_string(), "isSigner": false, "isWritable": false },
+            ],
+            "data": STANDARD.encode(data),
+        })
+    }
+
+    #[test]
+    fn test_parse_quote() {
+        let (input, output) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let quote = SwapQuote::parse(json!({
+            "inputMint": input.to_string(),
+            "outputMint": output.to_string(),
+            "inAmount": "1000000",
+            "outAmount": "150000",
+            "otherAmountThreshold": "149250",
+            "slippageBps": 50,
+            "priceImpactPct": "0.00123",
+            "routePlan": [],
+        }))
+        .unwrap();
+
+        assert_eq!(quote.input_mint, input);
+        assert_eq!(quote.output_mint, output);
+        assert_eq!(quote.in_amount, 1_000_000);
+        assert_eq!(quote.out_amount, 150_000);
+        assert_eq!(quote.min_out_amount, 149_250);
+        assert_eq!(quote.slippage_bps, 50);
+        assert_eq!(quote.price_impact, dec!(0.00123));
+        assert_eq!(quote.price_impact_bps(), 13);
+
+        assert!(SwapQuote::parse(json!({ "inputMint": input.to_string() })).is_err());
+    }
+
+    #[test]
+    fn test_parse_swap_instructions() {
+        let (program, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let body = json!({
+            "computeBudgetInstructions": [instruction(&program, &user, &[9])],
+            "setupInstructions": [instruction(&program, &user, &[1])],
+            "swapInstruction": instruction(&program, &user, &[2, 3]),
+            "cleanupInstruction": null,
+            "addressLookupTableAddresses": [],
+        });
+
+        let instructions = parse_swap_instructions(&body).unwrap();
+        assert_eq!(instructions.setup.len(), 1);
+        assert!(instructions.cleanup.is_none());
+        assert_eq!(instructions.swap.data, vec![2, 3]);
+        assert!(instructions.swap.accounts[0].is_signer);
+        assert!(instructions.swap.accounts[0].is_writable);
+        assert!(!instructions.swap.accounts[1].is_writable);
+
+        let all = instructions.into_instructions();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].data, vec![1]);
+    }
+
+    #[test]
+    fn test_parse_swap_instructions_rejects_lookup_tables() {
+        let (program, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let body = json!({
+            "setupInstructions": [],
+            "swapInstruction": instruction(&program, &user, &[2]),
+            "addressLookupTableAddresses": [Pubkey::new_unique().to_string()],
+        });
+
+        assert!(parse_swap_instructions(&body).is_err());
+        assert!(parse_swap_instructions(&json!({ "error": "no route" })).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+//! Token swaps for rebalancing.
+//!
+//! A new range usually needs a different token ratio than the liquidity
+//! withdrawn from the old one. This module provides:
+//! - Planning the swap that matches holdings to a range's ratio
+//! - Jupiter v6 quotes and swap instructions with slippage limits
+
+mod jupiter;
+mod ratio;
+
+pub use jupiter::*;
+pub use ratio::*;
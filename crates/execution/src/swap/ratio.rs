@@ -0,0 +1,172 @@
+//! Token ratio of a range and the swap that reaches it.
+//!
+//! Amounts are raw base units and prices are raw token B units per token A
+//! unit, as read from a pool's Q64.64 sqrt price.
+
+/// Basis points in one.
+const BPS_DENOMINATOR: f64 = 10_000.0;
+
+/// A swap between the two tokens of a pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapPlan {
+    /// Whether token A is sold for token B.
+    pub a_to_b: bool,
+    /// Amount of the sold token.
+    pub amount_in: u64,
+}
+
+impl SwapPlan {
+    /// Plans the swap that converts `amount_a` and `amount_b` to the token
+    /// ratio a range needs at the pool's current sqrt price.
+    ///
+    /// The swap is sized at the pool price, ignoring fees and price impact,
+    /// so the deposit leaves a small remainder of one token. Returns `None`
+    /// if the holdings are already within `min_swap_bps` of their value from
+    /// the target ratio, or the range or price is invalid.
+    #[must_use]
+    pub fn for_range(
+        amount_a: u64,
+        amount_b: u64,
+        sqrt_price_x64: u128,
+        tick_lower: i32,
+        tick_upper: i32,
+        min_swap_bps: u16,
+    ) -> Option<Self> {
+        let sqrt_price = sqrt_price_from_x64(sqrt_price_x64);
+        let price = sqrt_price * sqrt_price;
+        let share_a = value_share_a(sqrt_price, tick_lower, tick_upper)?;
+
+        let value_a = amount_a as f64 * price;
+        let total = value_a + amount_b as f64;
+        if total <= 0.0 {
+            return None;
+        }
+
+        // Value of token A held beyond what the range needs
+        let excess = value_a - total * share_a;
+        if excess.abs() < total * f64::from(min_swap_bps) / BPS_DENOMINATOR {
+            return None;
+        }
+
+        let plan = if excess > 0.0 {
+            Self {
+                a_to_b: true,
+                amount_in: (excess / price) as u64,
+            }
+        } else {
+            Self {
+                a_to_b: false,
+                amount_in: (-excess) as u64,
+            }
+        };
+        (plan.amount_in > 0).then_some(plan)
+    }
+}
+
+/// Calculates the token amounts `liquidity` is worth in a range at the
+/// pool's current sqrt price.
+#[must_use]
+pub fn amounts_for_liquidity(
+    liquidity: u128,
+    sqrt_price_x64: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> (u64, u64) {
+    let (sqrt_lower, sqrt_upper) = (tick_sqrt_price(tick_lower), tick_sqrt_price(tick_upper));
+    let sqrt_price = sqrt_price_from_x64(sqrt_price_x64).clamp(sqrt_lower, sqrt_upper);
+    let liquidity = liquidity as f64;
+
+    let amount_a = liquidity * (1.0 / sqrt_price - 1.0 / sqrt_upper);
+    let amount_b = liquidity * (sqrt_price - sqrt_lower);
+    (amount_a.max(0.0) as u64, amount_b.max(0.0) as u64)
+}
+
+/// Calculates the share of a range position's value held in token A.
+///
+/// Returns `None` for an empty or inverted range.
+fn value_share_a(sqrt_price: f64, tick_lower: i32, tick_upper: i32) -> Option<f64> {
+    if tick_lower >= tick_upper || sqrt_price <= 0.0 {
+        return None;
+    }
+    let (sqrt_lower, sqrt_upper) = (tick_sqrt_price(tick_lower), tick_sqrt_price(tick_upper));
+    let sqrt_price = sqrt_price.clamp(sqrt_lower, sqrt_upper);
+
+    // Token amounts and their value in token B per unit of liquidity
+    let value_a = (1.0 / sqrt_price - 1.0 / sqrt_upper) * sqrt_price * sqrt_price;
+    let value_b = sqrt_price - sqrt_lower;
+    Some(value_a / (value_a + value_b))
+}
+
+/// Converts a Q64.64 sqrt price to a float.
+fn sqrt_price_from_x64(sqrt_price_x64: u128) -> f64 {
+    sqrt_price_x64 as f64 / (1u128 << 64) as f64
+}
+
+/// Calculates the sqrt price at a tick.
+fn tick_sqrt_price(tick: i32) -> f64 {
+    1.0001_f64.powf(f64::from(tick) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Q64.64 sqrt price of a price.
+    fn x64(price: f64) -> u128 {
+        (price.sqrt() * (1u128 << 64) as f64) as u128
+    }
+
+    #[test]
+    fn test_symmetric_range_holds_equal_values() {
+        // Around price 1 a symmetric range holds half its value in each token
+        let share = value_share_a(1.0, -1000, 1000).unwrap();
+        assert!((share - 0.5).abs() < 1e-9);
+
+        // Above and below the range it holds a single token
+        assert_eq!(value_share_a(1.0, -2000, -1000), Some(0.0));
+        assert_eq!(value_share_a(1.0, 1000, 2000), Some(1.0));
+        assert_eq!(value_share_a(1.0, 1000, 1000), None);
+    }
+
+    #[test]
+    fn test_plan_sells_excess_token() {
+        // All token A, moving into a range around the price: sell half
+        let plan = SwapPlan::for_range(1_000_000, 0, x64(1.0), -1000, 1000, 10).unwrap();
+        assert!(plan.a_to_b);
+        assert!((plan.amount_in as i64 - 500_000).abs() < 10);
+
+        // All token B, moving into a range below the price: no swap needed
+        assert_eq!(
+            SwapPlan::for_range(0, 1_000_000, x64(1.0), -2000, -1000, 10),
+            None
+        );
+
+        // All token B, moving into a range above the price: buy token A
+        let plan = SwapPlan::for_range(0, 2_000_000, x64(2.0), 8000, 9000, 10).unwrap();
+        assert!(!plan.a_to_b);
+        assert!((plan.amount_in as i64 - 2_000_000).abs() < 10);
+    }
+
+    #[test]
+    fn test_plan_skips_small_imbalance() {
+        // 0.2% of the value away from the target ratio
+        assert_eq!(
+            SwapPlan::for_range(502_000, 498_000, x64(1.0), -1000, 1000, 50),
+            None
+        );
+        assert!(SwapPlan::for_range(502_000, 498_000, x64(1.0), -1000, 1000, 10).is_some());
+    }
+
+    #[test]
+    fn test_amounts_for_liquidity() {
+        // Liquidity at price 1 in about [0.25, 4] holds half of it in each token
+        let (a, b) = amounts_for_liquidity(1_000_000, x64(1.0), -13863, 13863);
+        assert!((a as i64 - 500_000).abs() < 100);
+        assert!((b as i64 - 500_000).abs() < 100);
+
+        // Below the range the liquidity is all token A
+        let (a, b) = amounts_for_liquidity(1_000_000, x64(0.1), -13863, 13863);
+        assert!(a > 0);
+        assert_eq!(b, 0);
+    }
+}
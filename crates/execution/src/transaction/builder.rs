@@ -5,9 +5,20 @@ use anyhow::{Context, Result};
 use solana_sdk::hash::Hash;
 use solana_sdk::instruction::Instruction;
 use solana_sdk::message::Message;
-use solana_sdk::signature::Keypair;
-use solana_sdk::signer::Signer;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::signers::Signers;
 use solana_sdk::transaction::Transaction;
+use tracing::instrument;
+
+/// Compute budget program.
+const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("ComputeBudget111111111111111111111111111111");
+
+/// Compute budget instruction setting the compute unit limit.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// Compute budget instruction setting the compute unit price.
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
 
 /// Builder for constructing transactions.
 pub struct TransactionBuilder {
@@ -15,6 +26,8 @@ pub struct TransactionBuilder {
     instructions: Vec<Instruction>,
     /// Compute unit limit.
     compute_units: Option<u32>,
+    /// Compute unit price in micro-lamports.
+    compute_unit_price: Option<u64>,
     /// Priority level.
     priority: PriorityLevel,
     /// Recent blockhash.
@@ -30,6 +43,7 @@ impl TransactionBuilder {
         Self {
             instructions: Vec::new(),
             compute_units: None,
+            compute_unit_price: None,
             priority: PriorityLevel::default(),
             blockhash: None,
             fee_payer: None,
@@ -57,6 +71,13 @@ impl TransactionBuilder {
         self
     }
 
+    /// Sets the compute unit price in micro-lamports, paying a priority fee.
+    #[must_use]
+    pub fn with_compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
     /// Sets the priority level.
     #[must_use]
     pub fn with_priority(mut self, priority: PriorityLevel) -> Self {
@@ -78,23 +99,26 @@ impl TransactionBuilder {
         self
     }
 
-    /// Builds the transaction.
-    pub fn build(self, signers: &[&Keypair]) -> Result<Transaction> {
+    /// Builds the transaction, signing it with the given signers.
+    ///
+    /// Signers missing from the set can sign the returned transaction later.
+    #[instrument(skip_all, fields(instructions = self.instructions.len()))]
+    pub fn build<T: Signers + ?Sized>(self, signers: &T) -> Result<Transaction> {
         let blockhash = self.blockhash.context("Blockhash not set")?;
 
         let fee_payer = self
             .fee_payer
-            .or_else(|| signers.first().map(|s| s.pubkey()))
+            .or_else(|| signers.pubkeys().first().copied())
             .context("Fee payer not set")?;
 
-        // Build instructions with compute budget
+        // Compute budget instructions come first
         let mut all_instructions = Vec::new();
-
-        // Note: Compute budget instructions would be added here
-        // In solana-sdk 3.x, these are in a separate crate
-        // For now, we skip compute budget instructions
-        let _ = self.compute_units;
-        let _ = self.priority;
+        if let Some(units) = self.compute_units {
+            all_instructions.push(set_compute_unit_limit(units));
+        }
+        if let Some(micro_lamports) = self.compute_unit_price {
+            all_instructions.push(set_compute_unit_price(micro_lamports));
+        }
 
         // Add user instructions
         all_instructions.extend(self.instructions);
@@ -104,11 +128,17 @@ impl TransactionBuilder {
 
         // Create and sign transaction
         let mut transaction = Transaction::new_unsigned(message);
-        transaction.partial_sign(signers, blockhash);
+        transaction.try_partial_sign(signers, blockhash)?;
 
         Ok(transaction)
     }
 
+    /// Returns the priority level.
+    #[must_use]
+    pub fn priority(&self) -> PriorityLevel {
+        self.priority
+    }
+
     /// Returns the estimated compute units.
     #[must_use]
     pub fn estimated_compute_units(&self) -> u32 {
@@ -116,6 +146,20 @@ impl TransactionBuilder {
     }
 }
 
+/// Builds the compute budget instruction limiting compute units.
+fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// Builds the compute budget instruction pricing compute units.
+fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
 impl Default for TransactionBuilder {
     fn default() -> Self {
         Self::new()
@@ -125,6 +169,7 @@ impl Default for TransactionBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_sdk::signer::Signer;
 
     #[test]
     fn test_builder_creation() {
@@ -145,4 +190,32 @@ mod tests {
 
         assert_eq!(builder.instructions.len(), 1);
     }
+
+    #[test]
+    fn test_compute_budget_instructions() {
+        let payer = solana_sdk::signature::Keypair::new();
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+
+        let transaction = TransactionBuilder::new()
+            .add_instruction(instruction)
+            .with_compute_units(300_000)
+            .with_compute_unit_price(25_000)
+            .with_blockhash(Hash::default())
+            .build(&[&payer])
+            .unwrap();
+
+        let message = &transaction.message;
+        assert_eq!(message.instructions.len(), 3);
+        assert_eq!(
+            message.account_keys[usize::from(message.instructions[0].program_id_index)],
+            COMPUTE_BUDGET_PROGRAM_ID
+        );
+        assert_eq!(message.instructions[0].data, [2, 0xe0, 0x93, 0x04, 0x00]);
+        assert_eq!(
+            message.instructions[1].data,
+            [3, 0xa8, 0x61, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(message.account_keys[0], payer.pubkey());
+        assert!(transaction.is_signed());
+    }
 }
@@ -0,0 +1,87 @@
+//! Priority fee estimation from recent fees.
+
+use super::PriorityLevel;
+use anyhow::Result;
+use clmm_lp_protocols::prelude::RpcProvider;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Estimates compute unit prices from the fees recently paid to write the
+/// same accounts.
+pub struct PriorityFeeEstimator {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Lowest price returned, in micro-lamports per compute unit.
+    min_price: u64,
+    /// Highest price returned, in micro-lamports per compute unit.
+    max_price: u64,
+}
+
+impl PriorityFeeEstimator {
+    /// Creates an estimator with a 1,000 micro-lamport floor and a
+    /// 5,000,000 micro-lamport cap.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            provider,
+            min_price: 1_000,
+            max_price: 5_000_000,
+        }
+    }
+
+    /// Sets the lowest and highest price returned.
+    #[must_use]
+    pub fn with_bounds(mut self, min_price: u64, max_price: u64) -> Self {
+        self.min_price = min_price.min(max_price);
+        self.max_price = max_price;
+        self
+    }
+
+    /// Estimates the compute unit price in micro-lamports for a transaction
+    /// writing the given accounts.
+    pub async fn estimate(&self, accounts: &[Pubkey], priority: PriorityLevel) -> Result<u64> {
+        let fees = self
+            .provider
+            .get_recent_prioritization_fees(accounts)
+            .await?;
+        let price = fee_at_percentile(&fees, priority.fee_percentile())
+            .clamp(self.min_price, self.max_price);
+
+        debug!(
+            samples = fees.len(),
+            priority = ?priority,
+            micro_lamports = price,
+            "Estimated priority fee"
+        );
+        Ok(price)
+    }
+}
+
+/// Returns the fee at a percentile of recent fees, ignoring slots without
+/// priority fees.
+fn fee_at_percentile(fees: &[u64], percentile: u8) -> u64 {
+    let mut paid: Vec<u64> = fees.iter().copied().filter(|fee| *fee > 0).collect();
+    if paid.is_empty() {
+        return 0;
+    }
+    paid.sort_unstable();
+    let index = (paid.len() - 1) * usize::from(percentile.min(100)) / 100;
+    paid[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_at_percentile() {
+        let fees = [0, 500, 100, 0, 300, 200, 400];
+
+        assert_eq!(fee_at_percentile(&fees, 0), 100);
+        assert_eq!(fee_at_percentile(&fees, 50), 300);
+        assert_eq!(fee_at_percentile(&fees, 75), 400);
+        assert_eq!(fee_at_percentile(&fees, 100), 500);
+        assert_eq!(fee_at_percentile(&[0, 0], 50), 0);
+        assert_eq!(fee_at_percentile(&[], 50), 0);
+    }
+}
@@ -1,14 +1,18 @@
 //! Transaction manager for lifecycle handling.
 
 use super::TransactionResult;
+use crate::error::ExecutionError;
+use crate::persistence::{AuditEntry, AuditKind, AuditLog};
 use anyhow::Result;
-use clmm_lp_protocols::prelude::RpcProvider;
+use clmm_lp_config::prelude::ExecutionSettings;
+use clmm_lp_protocols::prelude::{CommitmentLevel, RpcProvider};
 use solana_sdk::signature::Signature;
 use solana_sdk::transaction::Transaction;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 use tokio::time::sleep;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, instrument, warn};
 
 /// Configuration for transaction management.
 #[derive(Debug, Clone)]
@@ -34,22 +38,76 @@ impl Default for TransactionConfig {
     }
 }
 
+impl TransactionConfig {
+    /// Creates a transaction config from the shared configuration's
+    /// execution section.
+    #[must_use]
+    pub fn from_settings(settings: &ExecutionSettings) -> Self {
+        Self {
+            confirmation_timeout_secs: settings.confirmation_timeout_secs,
+            ..Default::default()
+        }
+    }
+}
+
 /// Manages transaction lifecycle.
 pub struct TransactionManager {
     /// RPC provider.
     provider: Arc<RpcProvider>,
     /// Configuration.
     config: TransactionConfig,
+    /// Audit log for sent transactions.
+    audit: RwLock<Option<Arc<dyn AuditLog>>>,
 }
 
 impl TransactionManager {
     /// Creates a new transaction manager.
     pub fn new(provider: Arc<RpcProvider>, config: TransactionConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            audit: RwLock::new(None),
+        }
+    }
+
+    /// Records every sent transaction and its signature in an audit log.
+    #[must_use]
+    pub fn with_audit_log(mut self, audit: Arc<dyn AuditLog>) -> Self {
+        self.audit = RwLock::new(Some(audit));
+        self
+    }
+
+    /// Replaces the audit log for sent transactions.
+    pub async fn set_audit_log(&self, audit: Arc<dyn AuditLog>) {
+        *self.audit.write().await = Some(audit);
     }
 
     /// Sends a transaction with retry logic.
+    #[instrument(skip_all, fields(instructions = transaction.message.instructions.len()))]
     pub async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let result = self.send_with_retries(transaction).await;
+
+        let audit = self.audit.read().await.clone();
+        if let Some(audit) = audit {
+            let mut entry = AuditEntry::new(AuditKind::Transaction, "send_transaction")
+                .with_result(&result)
+                .with_details(serde_json::json!({
+                    "instructions": transaction.message.instructions.len(),
+                    "signers": transaction.message.header.num_required_signatures,
+                }));
+            if let Ok(signature) = &result {
+                entry = entry.with_signature(signature);
+            }
+            if let Err(e) = audit.append(&entry).await {
+                error!(error = %e, "Failed to write audit entry");
+            }
+        }
+
+        result
+    }
+
+    /// Sends a transaction, retrying with exponential backoff.
+    async fn send_with_retries(&self, transaction: &Transaction) -> Result<Signature> {
         let mut last_error = None;
 
         for attempt in 0..=self.config.max_retries {
@@ -79,13 +137,12 @@ impl TransactionManager {
     }
 
     /// Tries to send a transaction once.
-    async fn try_send_transaction(&self, _transaction: &Transaction) -> Result<Signature> {
-        // TODO: Implement actual transaction sending
-        // For now, return a placeholder
-        Err(anyhow::anyhow!("Transaction sending not implemented"))
+    async fn try_send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        self.provider.send_transaction(transaction).await
     }
 
     /// Waits for transaction confirmation.
+    #[instrument(skip_all, fields(signature = %signature))]
     pub async fn wait_for_confirmation(&self, signature: &Signature) -> Result<TransactionResult> {
         let start = Instant::now();
         let timeout = Duration::from_secs(self.config.confirmation_timeout_secs);
@@ -94,11 +151,15 @@ impl TransactionManager {
 
         loop {
             if start.elapsed() > timeout {
-                return Err(anyhow::anyhow!("Confirmation timeout"));
+                return Err(ExecutionError::ConfirmationTimeout(
+                    self.config.confirmation_timeout_secs,
+                )
+                .into());
             }
 
             match self.check_confirmation(signature).await {
-                Ok(Some(result)) => {
+                Ok(Some(mut result)) => {
+                    result.confirmation_time = start.elapsed();
                     info!(
                         signature = %signature,
                         slot = result.slot,
@@ -121,19 +182,24 @@ impl TransactionManager {
 
     /// Checks if a transaction is confirmed.
     async fn check_confirmation(&self, signature: &Signature) -> Result<Option<TransactionResult>> {
-        let status = self.provider.get_signature_status(signature).await?;
+        let Some(status) = self.provider.get_transaction_status(signature).await? else {
+            return Ok(None);
+        };
 
-        match status {
-            Some(err) => {
-                // Transaction failed
-                Err(anyhow::anyhow!("Transaction failed: {:?}", err))
-            }
-            None => {
-                // Check if confirmed by getting slot
-                // For now, assume not confirmed
-                Ok(None)
-            }
+        if let Some(err) = status.err {
+            return Err(ExecutionError::TransactionFailed(format!("{:?}", err)).into());
         }
+        if status.commitment < CommitmentLevel::Confirmed {
+            return Ok(None);
+        }
+
+        Ok(Some(TransactionResult {
+            signature: *signature,
+            slot: status.slot,
+            confirmation_time: Duration::ZERO,
+            compute_units: None,
+            fee: 0,
+        }))
     }
 
     /// Sends and confirms a transaction.
@@ -143,14 +209,25 @@ impl TransactionManager {
     }
 
     /// Simulates a transaction.
-    pub async fn simulate(&self, _transaction: &Transaction) -> Result<SimulationResult> {
-        // TODO: Implement transaction simulation
-        Ok(SimulationResult {
-            success: true,
-            logs: vec![],
-            compute_units: 0,
-            error: None,
-        })
+    ///
+    /// A transaction the program rejects is reported as an unsuccessful
+    /// result; only RPC failures are returned as errors.
+    #[instrument(skip_all)]
+    pub async fn simulate(&self, transaction: &Transaction) -> Result<SimulationResult> {
+        let result = self.provider.simulate_transaction(transaction).await?;
+
+        let simulation = SimulationResult {
+            success: result.err.is_none(),
+            logs: result.logs.unwrap_or_default(),
+            compute_units: result.units_consumed.unwrap_or(0),
+            error: result.err.map(|e| format!("{:?}", e)),
+        };
+        debug!(
+            success = simulation.success,
+            compute_units = simulation.compute_units,
+            "Simulated transaction"
+        );
+        Ok(simulation)
     }
 }
 
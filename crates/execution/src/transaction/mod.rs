@@ -7,9 +7,11 @@
 //! - Confirmation tracking
 
 mod builder;
+mod fees;
 mod manager;
 mod types;
 
 pub use builder::*;
+pub use fees::PriorityFeeEstimator;
 pub use manager::*;
 pub use types::{PriorityLevel, TransactionResult, TransactionStatus};
@@ -60,4 +60,15 @@ impl PriorityLevel {
             Self::Urgent => 1000,
         }
     }
+
+    /// Returns the percentile of recent priority fees paid at this level.
+    #[must_use]
+    pub fn fee_percentile(&self) -> u8 {
+        match self {
+            Self::Low => 25,
+            Self::Medium => 50,
+            Self::High => 75,
+            Self::Urgent => 95,
+        }
+    }
 }
@@ -1,17 +1,27 @@
 //! Wallet implementation for transaction signing.
 
+use super::{Keystore, RemoteSigner};
 use anyhow::{Context, Result};
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::signer::SignerError;
 use std::fs;
 use std::path::Path;
 use tracing::info;
 use zeroize::Zeroizing;
 
+/// Source of a wallet's signatures.
+enum WalletSigner {
+    /// Keypair held in memory.
+    Local(Keypair),
+    /// External signing service.
+    Remote(RemoteSigner),
+}
+
 /// Wallet abstraction for signing transactions.
 pub struct Wallet {
-    /// The keypair.
-    keypair: Keypair,
+    /// The signer.
+    signer: WalletSigner,
     /// Wallet label.
     label: String,
 }
@@ -20,11 +30,37 @@ impl Wallet {
     /// Creates a wallet from a keypair.
     pub fn from_keypair(keypair: Keypair, label: impl Into<String>) -> Self {
         Self {
-            keypair,
+            signer: WalletSigner::Local(keypair),
+            label: label.into(),
+        }
+    }
+
+    /// Creates a wallet backed by a remote signer.
+    pub fn from_remote_signer(signer: RemoteSigner, label: impl Into<String>) -> Self {
+        Self {
+            signer: WalletSigner::Remote(signer),
             label: label.into(),
         }
     }
 
+    /// Loads a wallet from a passphrase-encrypted keystore file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or the passphrase is wrong.
+    pub fn from_encrypted_file(
+        path: impl AsRef<Path>,
+        passphrase: &str,
+        label: impl Into<String>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let label = label.into();
+
+        info!(path = %path.display(), label = %label, "Loading wallet from keystore");
+
+        let keypair = Keystore::load(path)?.decrypt(passphrase)?;
+        Ok(Self::from_keypair(keypair, label))
+    }
+
     /// Loads a wallet from a JSON file.
     ///
     /// # Arguments
@@ -48,7 +84,7 @@ impl Wallet {
         let bytes_array: [u8; 32] = bytes[..32].try_into().context("Invalid keypair length")?;
         let keypair = Keypair::new_from_array(bytes_array);
 
-        Ok(Self { keypair, label })
+        Ok(Self::from_keypair(keypair, label))
     }
 
     /// Loads a wallet from an environment variable.
@@ -73,19 +109,31 @@ impl Wallet {
         if let Ok(bytes) = serde_json::from_str::<Vec<u8>>(&value) {
             let bytes_array: [u8; 32] = bytes[..32].try_into().context("Invalid keypair length")?;
             let keypair = Keypair::new_from_array(bytes_array);
-            return Ok(Self { keypair, label });
+            return Ok(Self::from_keypair(keypair, label));
         }
 
         // Try to parse as base58
         let keypair = Keypair::from_base58_string(&value);
 
-        Ok(Self { keypair, label })
+        Ok(Self::from_keypair(keypair, label))
     }
 
     /// Returns the public key.
     #[must_use]
     pub fn pubkey(&self) -> Pubkey {
-        self.keypair.pubkey()
+        match &self.signer {
+            WalletSigner::Local(keypair) => keypair.pubkey(),
+            WalletSigner::Remote(remote) => remote.pubkey(),
+        }
+    }
+
+    /// Returns the remote signer URL, if signing is delegated.
+    #[must_use]
+    pub fn remote_signer_url(&self) -> Option<&str> {
+        match &self.signer {
+            WalletSigner::Local(_) => None,
+            WalletSigner::Remote(remote) => Some(remote.url()),
+        }
     }
 
     /// Returns the wallet label.
@@ -95,12 +143,38 @@ impl Wallet {
     }
 
     /// Signs a message.
-    pub fn sign(&self, message: &[u8]) -> solana_sdk::signature::Signature {
-        self.keypair.sign_message(message)
+    ///
+    /// # Panics
+    /// Panics if a remote signer fails; use [`Signer::try_sign_message`]
+    /// to handle the error.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_message(message)
+    }
+
+    /// Returns the keypair, unless signing is delegated to a remote signer.
+    pub fn keypair(&self) -> Option<&Keypair> {
+        match &self.signer {
+            WalletSigner::Local(keypair) => Some(keypair),
+            WalletSigner::Remote(_) => None,
+        }
+    }
+}
+
+impl Signer for Wallet {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey())
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        match &self.signer {
+            WalletSigner::Local(keypair) => keypair.try_sign_message(message),
+            WalletSigner::Remote(remote) => remote
+                .sign_blocking(message)
+                .map_err(|e| SignerError::Connection(format!("{:#}", e))),
+        }
     }
 
-    /// Returns a reference to the keypair for signing transactions.
-    pub fn keypair(&self) -> &Keypair {
-        &self.keypair
+    fn is_interactive(&self) -> bool {
+        false
     }
 }
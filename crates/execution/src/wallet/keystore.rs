@@ -0,0 +1,221 @@
+//! Passphrase-encrypted keystore files.
+//!
+//! The secret key is encrypted with AES-256-GCM under a key derived from
+//! the passphrase with scrypt. The public key is stored in the clear so a
+//! keystore can be identified without decrypting it.
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::fs;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+/// Current keystore format version.
+const KEYSTORE_VERSION: u32 = 1;
+
+/// Cipher name stored in keystores.
+const CIPHER: &str = "aes-256-gcm";
+
+/// Length of the scrypt salt in bytes.
+const SALT_LEN: usize = 32;
+
+/// Length of a secret key in bytes.
+const SECRET_KEY_LEN: usize = 32;
+
+/// scrypt key derivation parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Log2 of the CPU/memory cost.
+    pub log_n: u8,
+    /// Block size.
+    pub r: u32,
+    /// Parallelization.
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// An encrypted keypair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    /// Format version.
+    pub version: u32,
+    /// Base58 public key.
+    pub pubkey: String,
+    /// Key derivation parameters.
+    pub kdf: KdfParams,
+    /// Hex scrypt salt.
+    pub salt: String,
+    /// Cipher name.
+    pub cipher: String,
+    /// Hex cipher nonce.
+    pub nonce: String,
+    /// Hex encrypted secret key.
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypts a keypair with a passphrase using the default parameters.
+    ///
+    /// # Errors
+    /// Returns an error if encryption fails.
+    pub fn encrypt(keypair: &Keypair, passphrase: &str) -> Result<Self> {
+        Self::encrypt_with_params(keypair, passphrase, KdfParams::default())
+    }
+
+    /// Encrypts a keypair with a passphrase and key derivation parameters.
+    ///
+    /// # Errors
+    /// Returns an error if the parameters are invalid or encryption fails.
+    pub fn encrypt_with_params(
+        keypair: &Keypair,
+        passphrase: &str,
+        kdf: KdfParams,
+    ) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = derive_key(passphrase, &salt, kdf)?;
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let secret = Zeroizing::new(keypair.to_bytes());
+        let ciphertext = Aes256Gcm::new(&key)
+            .encrypt(&nonce, &secret[..SECRET_KEY_LEN])
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt keypair"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey: keypair.pubkey().to_string(),
+            kdf,
+            salt: hex::encode(salt),
+            cipher: CIPHER.to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the keypair.
+    ///
+    /// # Errors
+    /// Returns an error if the passphrase is wrong or the keystore is
+    /// malformed.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Keypair> {
+        anyhow::ensure!(
+            self.version == KEYSTORE_VERSION,
+            "Unsupported keystore version {}",
+            self.version
+        );
+        anyhow::ensure!(
+            self.cipher == CIPHER,
+            "Unsupported keystore cipher {}",
+            self.cipher
+        );
+
+        let salt = hex::decode(&self.salt).context("Invalid keystore salt")?;
+        let nonce = hex::decode(&self.nonce).context("Invalid keystore nonce")?;
+        let ciphertext = hex::decode(&self.ciphertext).context("Invalid keystore ciphertext")?;
+        anyhow::ensure!(nonce.len() == 12, "Invalid keystore nonce length");
+
+        let key = derive_key(passphrase, &salt, self.kdf)?;
+        let secret = Zeroizing::new(
+            Aes256Gcm::new(&key)
+                .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                .map_err(|_| anyhow::anyhow!("Wrong passphrase or corrupted keystore"))?,
+        );
+
+        let secret: [u8; SECRET_KEY_LEN] = secret
+            .as_slice()
+            .try_into()
+            .context("Invalid secret key length")?;
+        let keypair = Keypair::new_from_array(secret);
+        anyhow::ensure!(
+            keypair.pubkey().to_string() == self.pubkey,
+            "Keystore public key does not match its secret key"
+        );
+
+        Ok(keypair)
+    }
+
+    /// Returns the public key.
+    ///
+    /// # Errors
+    /// Returns an error if the stored public key is invalid.
+    pub fn pubkey(&self) -> Result<Pubkey> {
+        self.pubkey.parse().context("Invalid keystore public key")
+    }
+
+    /// Loads a keystore from a JSON file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+        serde_json::from_str(&contents).context("Failed to parse keystore JSON")
+    }
+
+    /// Saves the keystore as a JSON file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write keystore {}", path.display()))
+    }
+}
+
+/// Derives the encryption key from a passphrase.
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<Key<Aes256Gcm>> {
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, 32)
+        .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, key.as_mut_slice())
+        .map_err(|e| anyhow::anyhow!("Key derivation failed: {}", e))?;
+    Ok(*Key::<Aes256Gcm>::from_slice(key.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so tests run quickly.
+    const TEST_KDF: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let keypair = Keypair::new();
+        let keystore = Keystore::encrypt_with_params(&keypair, "correct horse", TEST_KDF).unwrap();
+        assert_eq!(keystore.pubkey().unwrap(), keypair.pubkey());
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        let loaded: Keystore = serde_json::from_str(&json).unwrap();
+        let decrypted = loaded.decrypt("correct horse").unwrap();
+        assert_eq!(decrypted.pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_keystore_wrong_passphrase() {
+        let keypair = Keypair::new();
+        let keystore = Keystore::encrypt_with_params(&keypair, "correct horse", TEST_KDF).unwrap();
+        assert!(keystore.decrypt("battery staple").is_err());
+    }
+}
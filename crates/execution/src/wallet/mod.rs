@@ -3,10 +3,16 @@
 //! Provides secure wallet handling including:
 //! - Keypair loading from files
 //! - Environment variable support
+//! - Passphrase-encrypted keystores
+//! - Remote signing services
 //! - Memory safety with zeroize
 
 mod keypair;
+mod keystore;
 mod manager;
+mod remote;
 
 pub use keypair::Wallet;
+pub use keystore::{KdfParams, Keystore};
 pub use manager::WalletManager;
+pub use remote::RemoteSigner;
@@ -0,0 +1,128 @@
+//! Remote signer client.
+//!
+//! Delegates signing to an external service (an HSM gateway or signing
+//! daemon) so the secret key never enters this process. The service
+//! receives `{"pubkey", "message"}` with the message base58 encoded and
+//! answers `{"signature"}` in base58.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::time::Duration;
+
+/// Timeout of a signing request.
+const SIGN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Signing request body.
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    /// Base58 public key to sign with.
+    pubkey: String,
+    /// Base58 message bytes.
+    message: String,
+}
+
+/// Signing response body.
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    /// Base58 signature.
+    signature: String,
+}
+
+/// Client of a remote signing service.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    /// Signing endpoint URL.
+    url: String,
+    /// Public key the service signs for.
+    pubkey: Pubkey,
+    /// Optional bearer token.
+    auth_token: Option<String>,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl RemoteSigner {
+    /// Creates a client for a signing endpoint.
+    pub fn new(url: impl Into<String>, pubkey: Pubkey) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(SIGN_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            url: url.into(),
+            pubkey,
+            auth_token: None,
+            client,
+        }
+    }
+
+    /// Sets the bearer token sent with signing requests.
+    #[must_use]
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Returns the signing endpoint URL.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the public key.
+    #[must_use]
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    /// Requests a signature over a message.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails or the returned signature does
+    /// not verify against the public key.
+    pub async fn sign(&self, message: &[u8]) -> Result<Signature> {
+        let body = SignRequest {
+            pubkey: self.pubkey.to_string(),
+            message: bs58::encode(message).into_string(),
+        };
+        let mut request = self.client.post(&self.url).json(&body);
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response: SignResponse = request
+            .send()
+            .await
+            .context("Remote signer request failed")?
+            .error_for_status()
+            .context("Remote signer rejected the request")?
+            .json()
+            .await
+            .context("Invalid remote signer response")?;
+
+        let signature: Signature = response
+            .signature
+            .parse()
+            .context("Invalid signature from remote signer")?;
+        anyhow::ensure!(
+            signature.verify(self.pubkey.as_ref(), message),
+            "Remote signer returned a signature that does not verify"
+        );
+
+        Ok(signature)
+    }
+
+    /// Requests a signature from synchronous code.
+    ///
+    /// Must be called from a multi-threaded Tokio runtime.
+    ///
+    /// # Errors
+    /// Returns an error if signing fails.
+    pub fn sign_blocking(&self, message: &[u8]) -> Result<Signature> {
+        let handle = tokio::runtime::Handle::try_current()
+            .context("Remote signing requires a Tokio runtime")?;
+        tokio::task::block_in_place(|| handle.block_on(self.sign(message)))
+    }
+}
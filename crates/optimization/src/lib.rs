@@ -13,3 +13,5 @@ pub mod optimizer;
 pub mod parameter_optimizer;
 /// Range optimization logic.
 pub mod range_optimizer;
+/// Walk-forward optimization.
+pub mod walk_forward;
@@ -5,12 +5,14 @@
 
 use crate::constraints::OptimizationConstraints;
 use crate::objective::ObjectiveFunction;
+use clmm_lp_domain::metrics::volatility::VolatilityEstimator;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 /// Result of a single optimization candidate evaluation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CandidateResult {
     /// The range width parameter.
     pub range_width: Decimal,
@@ -193,6 +195,21 @@ impl OptimizationConfig {
         self
     }
 
+    /// Sets the volatility estimated from historical prices.
+    ///
+    /// `periods_per_year` is the number of price samples per year, e.g. 8760
+    /// for hourly closes.
+    #[must_use]
+    pub fn with_estimated_volatility(
+        mut self,
+        prices: &[f64],
+        estimator: VolatilityEstimator,
+        periods_per_year: f64,
+    ) -> Self {
+        self.volatility = estimator.annualized(prices, periods_per_year);
+        self
+    }
+
     /// Sets the current price.
     #[must_use]
     pub fn with_price(mut self, price: Decimal) -> Self {
@@ -200,6 +217,14 @@ impl OptimizationConfig {
         self
     }
 
+    /// Sets the pool liquidity, e.g. the average liquidity of a tick range
+    /// instead of the pool-wide default.
+    #[must_use]
+    pub fn with_pool_liquidity(mut self, pool_liquidity: u128) -> Self {
+        self.pool_liquidity = pool_liquidity;
+        self
+    }
+
     /// Sets the fee rate.
     #[must_use]
     pub fn with_fee_rate(mut self, fee_rate: Decimal) -> Self {
@@ -457,4 +482,26 @@ mod tests {
         assert_eq!(config.volatility, 0.4);
         assert_eq!(config.current_price, Decimal::from(150));
     }
+
+    #[test]
+    fn test_optimization_config_estimated_volatility() {
+        // Alternating 1% moves
+        let prices: Vec<f64> = (0..100)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 101.0 })
+            .collect();
+
+        let stdev = OptimizationConfig::new().with_estimated_volatility(
+            &prices,
+            VolatilityEstimator::Stdev,
+            8760.0,
+        );
+        let ewma = OptimizationConfig::new().with_estimated_volatility(
+            &prices,
+            VolatilityEstimator::Ewma { lambda: 0.94 },
+            8760.0,
+        );
+
+        assert!(stdev.volatility > 0.5 && stdev.volatility < 1.5);
+        assert!((stdev.volatility - ewma.volatility).abs() < 0.1);
+    }
 }
@@ -8,9 +8,10 @@ use crate::objective::ObjectiveFunction;
 use crate::optimizer::OptimizationConfig;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::Serialize;
 
 /// Parameters for a threshold-based rebalancing strategy.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ThresholdParams {
     /// Price deviation threshold to trigger rebalance.
     pub price_threshold: Decimal,
@@ -31,7 +32,7 @@ impl Default for ThresholdParams {
 }
 
 /// Parameters for a periodic rebalancing strategy.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PeriodicParams {
     /// Interval between rebalances in steps.
     pub interval: u64,
@@ -49,7 +50,7 @@ impl Default for PeriodicParams {
 }
 
 /// Parameters for an IL-limit strategy.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ILLimitParams {
     /// Maximum IL before rebalancing.
     pub max_il: Decimal,
@@ -396,7 +397,7 @@ impl ParameterOptimizer {
 }
 
 /// Candidate result for threshold optimization.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ThresholdCandidate {
     /// The parameters.
     pub params: ThresholdParams,
@@ -411,7 +412,7 @@ pub struct ThresholdCandidate {
 }
 
 /// Candidate result for periodic optimization.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PeriodicCandidate {
     /// The parameters.
     pub params: PeriodicParams,
@@ -426,7 +427,7 @@ pub struct PeriodicCandidate {
 }
 
 /// Candidate result for IL limit optimization.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ILLimitCandidate {
     /// The parameters.
     pub params: ILLimitParams,
@@ -30,3 +30,8 @@ pub use crate::parameter_optimizer::{
 
 // Range optimizer
 pub use crate::range_optimizer::RangeOptimizer;
+
+// Walk-forward optimization
+pub use crate::walk_forward::{
+    WalkForwardConfig, WalkForwardOptimizer, WalkForwardReport, WalkForwardWindow, WindowResult,
+};
@@ -1,4 +1,5 @@
 use crate::objective::ObjectiveFunction;
+use crate::optimizer::{CandidateResult, GridSearchOptimizer};
 use clmm_lp_domain::entities::position::Position;
 use clmm_lp_domain::value_objects::OptimizationResult;
 use clmm_lp_domain::value_objects::price::Price;
@@ -9,6 +10,7 @@ use clmm_lp_simulation::monte_carlo::MonteCarloRunner;
 use clmm_lp_simulation::volume::ConstantVolume;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
+use std::ops::ControlFlow;
 
 /// Optimizer for finding the best price range.
 pub struct RangeOptimizer {
@@ -18,19 +20,46 @@ pub struct RangeOptimizer {
     pub steps: usize,
     /// Time step in years.
     pub time_step: f64,
+    /// Candidate range widths as fractions of the current price.
+    pub range_widths: Vec<Decimal>,
+    /// Seed of the Monte Carlo price paths; unseeded runs use OS entropy.
+    pub seed: Option<u64>,
 }
 
 impl RangeOptimizer {
     /// Creates a new RangeOptimizer.
     pub fn new(iterations: usize, steps: usize, time_step: f64) -> Self {
+        // Candidate widths: 1%, 2%, 5%, 10%, 20%, 50%
+        let widths = vec![0.01, 0.02, 0.05, 0.10, 0.20, 0.50];
         Self {
             iterations,
             steps,
             time_step,
+            range_widths: widths.into_iter().filter_map(Decimal::from_f64).collect(),
+            seed: None,
         }
     }
 
+    /// Seeds the Monte Carlo simulations so results are reproducible.
+    ///
+    /// Every candidate width is simulated on the same price paths.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the candidate range widths.
+    #[must_use]
+    pub fn with_widths(mut self, widths: Vec<Decimal>) -> Self {
+        self.range_widths = widths;
+        self
+    }
+
     /// Optimizes the price range for a given position.
+    ///
+    /// # Panics
+    /// Panics if no candidate widths are configured.
     #[allow(clippy::too_many_arguments)]
     pub fn optimize<O: ObjectiveFunction>(
         &self,
@@ -43,79 +72,231 @@ impl RangeOptimizer {
         fee_rate: Decimal,
         objective: O,
     ) -> OptimizationResult {
-        // Candidate widths: 1%, 2%, 5%, 10%, 20%, 50%
-        let widths = vec![0.01, 0.02, 0.05, 0.10, 0.20, 0.50];
-
-        let mut best_result: Option<(SimulationResult, PriceRange)> = None;
-        let mut best_score = Decimal::MIN;
-
-        // Assume 1000 USD capital for estimation
-        let _capital = Decimal::from(1000);
-        let liquidity_model = ConstantLiquidity::new(pool_liquidity);
+        let best = self
+            .rank(
+                &base_position,
+                current_price,
+                volatility,
+                drift,
+                &volume,
+                pool_liquidity,
+                fee_rate,
+                &objective,
+            )
+            .into_iter()
+            .next()
+            .expect("No candidates evaluated");
 
-        for width in widths {
-            let lower_mult = Decimal::from_f64(1.0 - width).unwrap();
-            let upper_mult = Decimal::from_f64(1.0 + width).unwrap();
+        Self::recommendation(&best, current_price)
+    }
 
-            let lower_price = current_price * lower_mult;
-            let upper_price = current_price * upper_mult;
+    /// Builds the recommended range of a candidate around the current price.
+    #[must_use]
+    pub fn recommendation(
+        candidate: &CandidateResult,
+        current_price: Decimal,
+    ) -> OptimizationResult {
+        let lower_price = current_price * (Decimal::ONE - candidate.range_width);
+        let upper_price = current_price * (Decimal::ONE + candidate.range_width);
 
-            let range = PriceRange::new(Price::new(lower_price), Price::new(upper_price));
+        OptimizationResult {
+            recommended_range: PriceRange::new(Price::new(lower_price), Price::new(upper_price)),
+            expected_pnl: candidate.net_pnl,
+            expected_fees: candidate.expected_fees,
+            expected_il: candidate.expected_il,
+            sharpe_ratio: None,
+        }
+    }
 
-            // Estimate Liquidity L for this range given Capital
-            // Narrower range -> Higher L
-            // Approximation: L = Capital / (Width_factor)
-            // For simplicity, let's use L = 1 / width (relative to 1000 base)
-            // Real calc is complex, this proxy ensures narrower ranges get higher fees.
-            let width_dec = Decimal::from_f64(width).unwrap();
-            let liquidity_proxy = (Decimal::from(1000) / width_dec).to_u128().unwrap_or(1000);
+    /// Evaluates every candidate width and returns them ranked by score.
+    #[allow(clippy::too_many_arguments)]
+    pub fn rank<O: ObjectiveFunction>(
+        &self,
+        base_position: &Position,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: &ConstantVolume,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+        objective: &O,
+    ) -> Vec<CandidateResult> {
+        self.rank_with_progress(
+            base_position,
+            current_price,
+            volatility,
+            drift,
+            volume,
+            pool_liquidity,
+            fee_rate,
+            objective,
+            |_, _| ControlFlow::Continue(()),
+        )
+    }
 
-            let mut candidate_position = base_position.clone();
-            candidate_position.range = Some(range.clone());
-            candidate_position.liquidity_amount = liquidity_proxy;
+    /// Evaluates the candidate widths in order, reporting progress after
+    /// every Monte Carlo iteration, and returns them ranked by score.
+    ///
+    /// `on_progress` receives the number of completed iterations across all
+    /// widths and the total. Returning `ControlFlow::Break` stops the run:
+    /// the widths evaluated so far are returned, the interrupted one
+    /// averaged over the iterations it completed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn rank_with_progress<O, F>(
+        &self,
+        base_position: &Position,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: &ConstantVolume,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+        objective: &O,
+        mut on_progress: F,
+    ) -> Vec<CandidateResult>
+    where
+        O: ObjectiveFunction,
+        F: FnMut(usize, usize) -> ControlFlow<()>,
+    {
+        let total = self.range_widths.len() * self.iterations;
+        let mut candidates = Vec::with_capacity(self.range_widths.len());
+        let mut stopped = false;
 
-            let mut runner = MonteCarloRunner {
-                position: candidate_position,
-                volume_model: volume.clone(),
-                liquidity_model: liquidity_model.clone(),
-                fee_rate,
-                initial_price: current_price,
-                drift,
+        for (index, &width) in self.range_widths.iter().enumerate() {
+            let done = index * self.iterations;
+            let candidate = self.evaluate_width_with_progress(
+                width,
+                base_position,
+                current_price,
                 volatility,
-                time_step: self.time_step,
-                steps: self.steps,
-                iterations: self.iterations,
-            };
-
-            let agg_result = runner.run();
-
-            let sim_result = SimulationResult {
-                final_position_value: Decimal::ZERO,
-                total_fees_earned: agg_result.mean_fees,
-                total_il: agg_result.mean_il,
-                net_pnl: agg_result.mean_net_pnl,
-                max_drawdown: Decimal::ZERO,
-                time_in_range_percentage: Decimal::ZERO,
-                sharpe_ratio: None,
-            };
-
-            let score = objective.evaluate(&sim_result);
-
-            if score > best_score {
-                best_score = score;
-                best_result = Some((sim_result, range));
+                drift,
+                volume,
+                pool_liquidity,
+                fee_rate,
+                objective,
+                |completed, _| {
+                    let flow = on_progress(done + completed, total);
+                    stopped = flow.is_break();
+                    flow
+                },
+            );
+            candidates.push(candidate);
+            if stopped {
+                break;
             }
         }
 
-        let (best_sim, best_range) = best_result.expect("No candidates evaluated");
+        GridSearchOptimizer::rank_candidates(&mut candidates);
+        candidates
+    }
 
-        OptimizationResult {
-            recommended_range: best_range,
-            expected_pnl: best_sim.net_pnl,
-            expected_fees: best_sim.total_fees_earned,
-            expected_il: best_sim.total_il,
-            sharpe_ratio: best_sim.sharpe_ratio,
-        }
+    /// Runs the Monte Carlo simulation for a single range width.
+    #[allow(clippy::too_many_arguments)]
+    pub fn evaluate_width<O: ObjectiveFunction>(
+        &self,
+        width: Decimal,
+        base_position: &Position,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: &ConstantVolume,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+        objective: &O,
+    ) -> CandidateResult {
+        self.evaluate_width_with_progress(
+            width,
+            base_position,
+            current_price,
+            volatility,
+            drift,
+            volume,
+            pool_liquidity,
+            fee_rate,
+            objective,
+            |_, _| ControlFlow::Continue(()),
+        )
+    }
+
+    /// Runs the Monte Carlo simulation for a single range width, reporting
+    /// progress after every iteration.
+    ///
+    /// See [`MonteCarloRunner::run_with_progress`] for how `on_iteration`
+    /// can stop the run early.
+    #[allow(clippy::too_many_arguments)]
+    pub fn evaluate_width_with_progress<O, F>(
+        &self,
+        width: Decimal,
+        base_position: &Position,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: &ConstantVolume,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+        objective: &O,
+        on_iteration: F,
+    ) -> CandidateResult
+    where
+        O: ObjectiveFunction,
+        F: FnMut(usize, usize) -> ControlFlow<()>,
+    {
+        let lower_price = current_price * (Decimal::ONE - width);
+        let upper_price = current_price * (Decimal::ONE + width);
+
+        let range = PriceRange::new(Price::new(lower_price), Price::new(upper_price));
+
+        // Estimate Liquidity L for this range given Capital
+        // Narrower range -> Higher L
+        // Approximation: L = Capital / (Width_factor)
+        // For simplicity, let's use L = 1 / width (relative to 1000 base)
+        // Real calc is complex, this proxy ensures narrower ranges get higher fees.
+        let liquidity_proxy = if width.is_zero() {
+            1000
+        } else {
+            (Decimal::from(1000) / width).to_u128().unwrap_or(1000)
+        };
+
+        let mut candidate_position = base_position.clone();
+        candidate_position.range = Some(range);
+        candidate_position.liquidity_amount = liquidity_proxy;
+
+        let mut runner = MonteCarloRunner {
+            position: candidate_position,
+            volume_model: volume.clone(),
+            liquidity_model: ConstantLiquidity::new(pool_liquidity),
+            fee_rate,
+            initial_price: current_price,
+            drift,
+            volatility,
+            time_step: self.time_step,
+            steps: self.steps,
+            iterations: self.iterations,
+            seed: self.seed,
+        };
+
+        let agg_result = runner.run_with_progress(on_iteration);
+
+        let sim_result = SimulationResult {
+            final_position_value: Decimal::ZERO,
+            total_fees_earned: agg_result.mean_fees,
+            total_il: agg_result.mean_il,
+            net_pnl: agg_result.mean_net_pnl,
+            max_drawdown: Decimal::ZERO,
+            time_in_range_percentage: Decimal::ZERO,
+            sharpe_ratio: None,
+        };
+
+        let score = objective.evaluate(&sim_result);
+
+        CandidateResult::new(
+            width,
+            sim_result.total_fees_earned,
+            sim_result.total_il,
+            sim_result.net_pnl,
+            sim_result.time_in_range_percentage,
+            score,
+        )
     }
 }
 
@@ -175,4 +356,91 @@ mod tests {
         assert!(result.recommended_range.lower_price.value < current_price);
         assert!(result.recommended_range.upper_price.value > current_price);
     }
+
+    #[test]
+    fn test_rank_orders_candidates() {
+        let optimizer = RangeOptimizer::new(5, 5, 1.0 / 365.0).with_widths(vec![
+            Decimal::from_f64(0.05).unwrap(),
+            Decimal::from_f64(0.20).unwrap(),
+        ]);
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+
+        let candidates = optimizer.rank(
+            &create_dummy_position(),
+            Decimal::from(100),
+            0.1,
+            0.0,
+            &volume,
+            100_000_000,
+            Decimal::from_f64(0.003).unwrap(),
+            &MaximizeNetPnL,
+        );
+
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates[0].score >= candidates[1].score);
+    }
+
+    #[test]
+    fn test_seeded_optimization_is_reproducible() {
+        let optimizer = RangeOptimizer::new(5, 10, 1.0 / 365.0).with_seed(7);
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let run = || {
+            optimizer.optimize(
+                create_dummy_position(),
+                Decimal::from(100),
+                0.8,
+                0.0,
+                volume.clone(),
+                100_000_000,
+                Decimal::from_f64(0.003).unwrap(),
+                MaximizeNetPnL,
+            )
+        };
+
+        let (first, second) = (run(), run());
+        assert_eq!(first.expected_pnl, second.expected_pnl);
+        assert_eq!(first.recommended_range, second.recommended_range);
+    }
+
+    #[test]
+    fn test_rank_with_progress_stops_early() {
+        let optimizer = RangeOptimizer::new(4, 5, 1.0 / 365.0)
+            .with_seed(3)
+            .with_widths(vec![
+                Decimal::new(5, 2),
+                Decimal::new(10, 2),
+                Decimal::new(20, 2),
+            ]);
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+
+        let mut reports = Vec::new();
+        let candidates = optimizer.rank_with_progress(
+            &create_dummy_position(),
+            Decimal::from(100),
+            0.1,
+            0.0,
+            &volume,
+            100_000_000,
+            Decimal::from_f64(0.003).unwrap(),
+            &MaximizeNetPnL,
+            |completed, total| {
+                reports.push((completed, total));
+                if completed == 6 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+
+        // Stopped halfway through the second width
+        assert_eq!(reports.len(), 6);
+        assert_eq!(reports.last(), Some(&(6, 12)));
+        assert_eq!(candidates.len(), 2);
+        assert!(
+            candidates
+                .iter()
+                .all(|c| c.range_width != Decimal::new(20, 2))
+        );
+    }
 }
@@ -0,0 +1,484 @@
+//! Walk-forward optimization over historical candles.
+//!
+//! The candle series is split into rolling in-sample/out-of-sample windows.
+//! Each in-sample window picks the best range width for an objective, which
+//! is then backtested unchanged on the out-of-sample window that follows.
+//! Parameters that only fit the data they were chosen on lose most of their
+//! edge out of sample, which the report measures.
+
+use crate::objective::ObjectiveFunction;
+use clmm_lp_domain::entities::price_candle::PriceCandle;
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
+use clmm_lp_simulation::position_tracker::PositionTracker;
+use clmm_lp_simulation::strategies::StaticRange;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Window layout of a walk-forward run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalkForwardConfig {
+    /// Candles per in-sample window.
+    pub in_sample: usize,
+    /// Candles per out-of-sample window; windows advance by this much.
+    pub out_of_sample: usize,
+    /// Whether in-sample windows grow from the first candle instead of rolling.
+    pub anchored: bool,
+}
+
+impl WalkForwardConfig {
+    /// Creates a rolling layout of `in_sample` then `out_of_sample` candles.
+    #[must_use]
+    pub fn new(in_sample: usize, out_of_sample: usize) -> Self {
+        Self {
+            in_sample: in_sample.max(1),
+            out_of_sample: out_of_sample.max(1),
+            anchored: false,
+        }
+    }
+
+    /// Sets whether in-sample windows start at the first candle.
+    #[must_use]
+    pub fn with_anchored(mut self, anchored: bool) -> Self {
+        self.anchored = anchored;
+        self
+    }
+
+    /// Splits a series of `len` candles into windows.
+    ///
+    /// Only complete out-of-sample windows are returned, so trailing candles
+    /// that do not fill one are left out.
+    #[must_use]
+    pub fn windows(&self, len: usize) -> Vec<WalkForwardWindow> {
+        let mut windows = Vec::new();
+        let mut split = self.in_sample;
+        while split + self.out_of_sample <= len {
+            let start = if self.anchored {
+                0
+            } else {
+                split - self.in_sample
+            };
+            windows.push(WalkForwardWindow {
+                in_sample: start..split,
+                out_of_sample: split..split + self.out_of_sample,
+            });
+            split += self.out_of_sample;
+        }
+        windows
+    }
+}
+
+/// Candle indices of one walk-forward window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalkForwardWindow {
+    /// Candles the parameters are optimized on.
+    pub in_sample: Range<usize>,
+    /// Candles the optimized parameters are tested on.
+    pub out_of_sample: Range<usize>,
+}
+
+/// Walk-forward optimizer of static range widths.
+///
+/// Every candidate width is backtested as a range centered on the first
+/// close of a window, earning fees on each in-range candle's volume at
+/// `fee_rate`. The same capital in a narrower range provides more liquidity,
+/// so the share of the volume earned scales inversely with the width.
+#[derive(Debug, Clone)]
+pub struct WalkForwardOptimizer {
+    /// Window layout.
+    pub config: WalkForwardConfig,
+    /// Candidate range widths as fractions of the price.
+    pub range_widths: Vec<Decimal>,
+    /// Capital backtested in each window in USD.
+    pub initial_capital: Decimal,
+    /// Pool fee rate.
+    pub fee_rate: Decimal,
+    /// Share of the pool's volume a range of ±10% earns fees on.
+    pub fee_share: Decimal,
+}
+
+impl WalkForwardOptimizer {
+    /// Creates a walk-forward optimizer with the default candidate widths.
+    #[must_use]
+    pub fn new(config: WalkForwardConfig) -> Self {
+        // Candidate widths: 1%, 2%, 5%, 10%, 20%, 50%
+        let widths = vec![0.01, 0.02, 0.05, 0.10, 0.20, 0.50];
+        Self {
+            config,
+            range_widths: widths.into_iter().filter_map(Decimal::from_f64).collect(),
+            initial_capital: Decimal::from(1000),
+            fee_rate: Decimal::new(3, 3),
+            fee_share: Decimal::new(1, 2),
+        }
+    }
+
+    /// Sets the candidate range widths.
+    #[must_use]
+    pub fn with_widths(mut self, widths: Vec<Decimal>) -> Self {
+        self.range_widths = widths;
+        self
+    }
+
+    /// Sets the capital backtested in each window.
+    #[must_use]
+    pub fn with_capital(mut self, capital: Decimal) -> Self {
+        self.initial_capital = capital;
+        self
+    }
+
+    /// Sets the pool fee rate.
+    #[must_use]
+    pub fn with_fee_rate(mut self, fee_rate: Decimal) -> Self {
+        self.fee_rate = fee_rate;
+        self
+    }
+
+    /// Sets the share of the pool's volume a range of ±10% earns fees on.
+    #[must_use]
+    pub fn with_fee_share(mut self, fee_share: Decimal) -> Self {
+        self.fee_share = fee_share;
+        self
+    }
+
+    /// Runs the walk-forward optimization over `candles`.
+    ///
+    /// Returns a report without windows if the series is shorter than one
+    /// in-sample and one out-of-sample window, or no widths are configured.
+    pub fn run<O: ObjectiveFunction>(
+        &self,
+        candles: &[PriceCandle],
+        objective: &O,
+    ) -> WalkForwardReport {
+        let windows = if self.range_widths.is_empty() {
+            Vec::new()
+        } else {
+            self.config.windows(candles.len())
+        };
+
+        let results = windows
+            .into_iter()
+            .map(|window| self.run_window(candles, window, objective))
+            .collect();
+        WalkForwardReport::new(objective.name(), self.config, results)
+    }
+
+    /// Optimizes one in-sample window and tests its winner out of sample.
+    fn run_window<O: ObjectiveFunction>(
+        &self,
+        candles: &[PriceCandle],
+        window: WalkForwardWindow,
+        objective: &O,
+    ) -> WindowResult {
+        let in_sample = &candles[window.in_sample.clone()];
+        let out_of_sample = &candles[window.out_of_sample.clone()];
+
+        let scored = |window: &[PriceCandle]| -> Vec<(Decimal, SimulationResult, Decimal)> {
+            self.range_widths
+                .iter()
+                .map(|&width| {
+                    let result = self.backtest(window, width);
+                    let score = objective.evaluate(&result);
+                    (width, result, score)
+                })
+                .collect()
+        };
+
+        let in_sample_results = scored(in_sample);
+        let (best_width, best_in_sample, in_sample_score) = in_sample_results
+            .into_iter()
+            .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+            .expect("at least one candidate width");
+
+        // Every width out of sample, to rank the in-sample winner among them
+        let out_of_sample_results = scored(out_of_sample);
+        let (_, best_out_of_sample, out_of_sample_score) = out_of_sample_results
+            .iter()
+            .find(|(width, _, _)| *width == best_width)
+            .cloned()
+            .expect("in-sample winner evaluated out of sample");
+        let out_of_sample_rank = 1 + out_of_sample_results
+            .iter()
+            .filter(|(_, _, score)| *score > out_of_sample_score)
+            .count();
+
+        WindowResult {
+            in_sample_pnl_per_step: per_step(best_in_sample.net_pnl, in_sample.len()),
+            out_of_sample_pnl_per_step: per_step(best_out_of_sample.net_pnl, out_of_sample.len()),
+            window,
+            best_width,
+            in_sample_score,
+            out_of_sample_score,
+            out_of_sample_rank,
+            candidates: self.range_widths.len(),
+        }
+    }
+
+    /// Backtests a static range of `width` around the first close of `candles`.
+    #[must_use]
+    pub fn backtest(&self, candles: &[PriceCandle], width: Decimal) -> SimulationResult {
+        let entry_price = candles
+            .first()
+            .map_or(Price::new(Decimal::ONE), |c| c.close);
+        let range = PriceRange::new(
+            Price::new(entry_price.value * (Decimal::ONE - width)),
+            Price::new(entry_price.value * (Decimal::ONE + width)),
+        );
+        let mut tracker =
+            PositionTracker::new(self.initial_capital, entry_price, range, Decimal::ZERO)
+                .with_capacity(candles.len());
+        // Share of a ±10% range, scaled by the liquidity this width provides
+        let fee_share = if width.is_zero() {
+            Decimal::ONE
+        } else {
+            (self.fee_share * Decimal::new(1, 1) / width).min(Decimal::ONE)
+        };
+
+        for candle in candles {
+            let price = candle.close;
+            let in_range = price.value >= tracker.current_range.lower_price.value
+                && price.value <= tracker.current_range.upper_price.value;
+            let step_fees = if in_range {
+                // Volume is in token A, valued at the candle's close
+                candle.volume_token_a.to_decimal() * price.value * self.fee_rate * fee_share
+            } else {
+                Decimal::ZERO
+            };
+            tracker.record_step::<StaticRange>(price, step_fees, None);
+        }
+
+        let summary = tracker.summary();
+        SimulationResult {
+            final_position_value: summary.final_value,
+            total_fees_earned: summary.total_fees,
+            total_il: self.initial_capital * summary.final_il_pct,
+            net_pnl: summary.final_pnl,
+            max_drawdown: summary.max_drawdown,
+            time_in_range_percentage: summary.time_in_range_pct,
+            sharpe_ratio: None,
+        }
+    }
+}
+
+/// Outcome of one walk-forward window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowResult {
+    /// Candle indices of the window.
+    pub window: WalkForwardWindow,
+    /// Range width that scored best in sample.
+    pub best_width: Decimal,
+    /// Objective score of the best width in sample.
+    pub in_sample_score: Decimal,
+    /// Objective score of the same width out of sample.
+    pub out_of_sample_score: Decimal,
+    /// Net PnL per candle of the best width in sample.
+    pub in_sample_pnl_per_step: Decimal,
+    /// Net PnL per candle of the same width out of sample.
+    pub out_of_sample_pnl_per_step: Decimal,
+    /// Rank of the in-sample winner among all widths out of sample (1 = best).
+    pub out_of_sample_rank: usize,
+    /// Number of candidate widths.
+    pub candidates: usize,
+}
+
+/// Result of a walk-forward run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardReport {
+    /// Name of the objective function optimized.
+    pub objective: String,
+    /// Window layout.
+    pub config: WalkForwardConfig,
+    /// Per-window results in chronological order.
+    pub windows: Vec<WindowResult>,
+    /// Mean in-sample score of the winners.
+    pub mean_in_sample_score: Decimal,
+    /// Mean out-of-sample score of the winners.
+    pub mean_out_of_sample_score: Decimal,
+    /// Out-of-sample over in-sample PnL per candle (1 = no degradation).
+    ///
+    /// `None` if the winners lost money in sample, where the ratio is
+    /// meaningless.
+    pub efficiency: Option<Decimal>,
+    /// Mean out-of-sample rank of the winners as a fraction of the candidates,
+    /// from 0 (always the best) to 1 (always the worst).
+    pub mean_rank_percentile: Decimal,
+    /// Number of distinct widths chosen across windows.
+    pub distinct_widths: usize,
+}
+
+impl WalkForwardReport {
+    /// Aggregates the window results of a run.
+    fn new(objective: &str, config: WalkForwardConfig, windows: Vec<WindowResult>) -> Self {
+        let mean = |values: Vec<Decimal>| {
+            if values.is_empty() {
+                Decimal::ZERO
+            } else {
+                let count = Decimal::from(values.len());
+                values.into_iter().sum::<Decimal>() / count
+            }
+        };
+
+        let in_sample_pnl = mean(windows.iter().map(|w| w.in_sample_pnl_per_step).collect());
+        let out_of_sample_pnl = mean(
+            windows
+                .iter()
+                .map(|w| w.out_of_sample_pnl_per_step)
+                .collect(),
+        );
+        let efficiency = (in_sample_pnl > Decimal::ZERO).then(|| out_of_sample_pnl / in_sample_pnl);
+
+        let mean_rank_percentile = mean(
+            windows
+                .iter()
+                .map(|w| {
+                    if w.candidates > 1 {
+                        Decimal::from(w.out_of_sample_rank - 1) / Decimal::from(w.candidates - 1)
+                    } else {
+                        Decimal::ZERO
+                    }
+                })
+                .collect(),
+        );
+
+        let mut widths: Vec<Decimal> = windows.iter().map(|w| w.best_width).collect();
+        widths.sort();
+        widths.dedup();
+
+        Self {
+            objective: objective.to_string(),
+            config,
+            mean_in_sample_score: mean(windows.iter().map(|w| w.in_sample_score).collect()),
+            mean_out_of_sample_score: mean(windows.iter().map(|w| w.out_of_sample_score).collect()),
+            efficiency,
+            mean_rank_percentile,
+            distinct_widths: widths.len(),
+            windows,
+        }
+    }
+
+    /// Returns the share of in-sample PnL per candle lost out of sample.
+    #[must_use]
+    pub fn degradation(&self) -> Option<Decimal> {
+        self.efficiency.map(|efficiency| Decimal::ONE - efficiency)
+    }
+
+    /// Returns true if the out-of-sample PnL per candle fell below
+    /// `min_efficiency` of the in-sample one, or the winners lost money in
+    /// sample.
+    #[must_use]
+    pub fn is_overfit(&self, min_efficiency: Decimal) -> bool {
+        self.efficiency
+            .is_none_or(|efficiency| efficiency < min_efficiency)
+    }
+}
+
+/// Divides a PnL by a number of candles.
+fn per_step(pnl: Decimal, steps: usize) -> Decimal {
+    if steps == 0 {
+        Decimal::ZERO
+    } else {
+        pnl / Decimal::from(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::MaximizeNetPnL;
+    use clmm_lp_domain::entities::token::Token;
+    use clmm_lp_domain::value_objects::amount::Amount;
+    use primitive_types::U256;
+
+    fn candles(closes: &[f64]) -> Vec<PriceCandle> {
+        let token = Token::new(
+            "So11111111111111111111111111111111111111112",
+            "SOL",
+            9,
+            "Solana",
+        );
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| {
+                let price = Price::new(Decimal::from_f64(close).unwrap());
+                PriceCandle {
+                    token_a: token.clone(),
+                    token_b: token.clone(),
+                    start_timestamp: i as u64 * 3600,
+                    duration_seconds: 3600,
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    // 1000 tokens per candle
+                    volume_token_a: Amount::new(U256::from(1_000_000_000_000u64), 9),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rolling_windows() {
+        let windows = WalkForwardConfig::new(4, 2).windows(11);
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0].in_sample, 0..4);
+        assert_eq!(windows[0].out_of_sample, 4..6);
+        assert_eq!(windows[2].in_sample, 4..8);
+        assert_eq!(windows[2].out_of_sample, 8..10);
+
+        assert!(WalkForwardConfig::new(4, 2).windows(5).is_empty());
+    }
+
+    #[test]
+    fn test_anchored_windows() {
+        let windows = WalkForwardConfig::new(4, 2).with_anchored(true).windows(10);
+        assert_eq!(windows.len(), 3);
+        assert!(windows.iter().all(|w| w.in_sample.start == 0));
+        assert_eq!(windows[2].in_sample, 0..8);
+    }
+
+    #[test]
+    fn test_stable_market_keeps_its_edge() {
+        // Small oscillations: the narrowest range earns the most everywhere
+        let closes: Vec<f64> = (0..40)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 100.5 })
+            .collect();
+        let optimizer = WalkForwardOptimizer::new(WalkForwardConfig::new(10, 10));
+        let report = optimizer.run(&candles(&closes), &MaximizeNetPnL);
+
+        assert_eq!(report.windows.len(), 3);
+        assert_eq!(report.objective, "MaximizeNetPnL");
+        assert_eq!(report.distinct_widths, 1);
+        assert_eq!(report.mean_rank_percentile, Decimal::ZERO);
+        assert!(!report.is_overfit(Decimal::new(5, 1)));
+    }
+
+    #[test]
+    fn test_regime_change_degrades_out_of_sample() {
+        // Calm in sample, then a trend that leaves narrow ranges
+        let mut closes: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 100.5 })
+            .collect();
+        closes.extend((1..=20).map(|i| 100.0 * 1.02_f64.powi(i)));
+        let optimizer = WalkForwardOptimizer::new(WalkForwardConfig::new(20, 20));
+        let report = optimizer.run(&candles(&closes), &MaximizeNetPnL);
+
+        let window = &report.windows[0];
+        assert_eq!(window.best_width, Decimal::from_f64(0.01).unwrap());
+        assert!(window.out_of_sample_rank > 1);
+        assert!(report.degradation().unwrap() > Decimal::ONE);
+        assert!(report.is_overfit(Decimal::new(5, 1)));
+    }
+
+    #[test]
+    fn test_short_series_has_no_windows() {
+        let optimizer = WalkForwardOptimizer::new(WalkForwardConfig::new(10, 10));
+        let report = optimizer.run(&candles(&[100.0; 15]), &MaximizeNetPnL);
+
+        assert!(report.windows.is_empty());
+        assert_eq!(report.efficiency, None);
+    }
+}
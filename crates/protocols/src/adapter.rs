@@ -0,0 +1,286 @@
+//! Protocol-agnostic access to Solana CLMM pools and positions.
+//!
+//! Each supported program implements [`ProtocolAdapter`], which reads pool
+//! and position state in a common form and builds the instructions that
+//! manage a position. [`ProtocolRegistry`] finds the adapter of an account
+//! from the program that owns it, so callers never name a protocol.
+
+use crate::error::ProtocolError;
+use crate::events::OnChainPosition;
+use crate::orca::adapter::OrcaAdapter;
+use crate::raydium::adapter::RaydiumAdapter;
+use crate::rpc::RpcProvider;
+use anyhow::Result;
+use async_trait::async_trait;
+use clmm_lp_domain::enums::Protocol;
+use rust_decimal::Decimal;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Pool state read from any supported Solana CLMM.
+#[derive(Debug, Clone)]
+pub struct ProtocolPoolState {
+    /// Protocol of the pool.
+    pub protocol: Protocol,
+    /// Pool address.
+    pub address: Pubkey,
+    /// Token A (token 0) mint.
+    pub token_mint_a: Pubkey,
+    /// Token B (token 1) mint.
+    pub token_mint_b: Pubkey,
+    /// Vault holding token A.
+    pub token_vault_a: Pubkey,
+    /// Vault holding token B.
+    pub token_vault_b: Pubkey,
+    /// Current tick index.
+    pub tick_current: i32,
+    /// Tick spacing.
+    pub tick_spacing: u16,
+    /// Current sqrt price (Q64.64).
+    pub sqrt_price: u128,
+    /// Current price of token A in token B, in raw units.
+    pub price: Decimal,
+    /// Liquidity at the current tick.
+    pub liquidity: u128,
+    /// Fee rate in hundredths of a basis point (3000 = 0.3%).
+    pub fee_rate: u32,
+    /// Fee growth global for token A (Q64.64).
+    pub fee_growth_global_a: u128,
+    /// Fee growth global for token B (Q64.64).
+    pub fee_growth_global_b: u128,
+}
+
+impl ProtocolPoolState {
+    /// Returns the fee rate as a decimal.
+    #[must_use]
+    pub fn fee_fraction(&self) -> Decimal {
+        Decimal::from(self.fee_rate) / Decimal::from(1_000_000)
+    }
+
+    /// Checks if a tick is within the current range.
+    #[must_use]
+    pub fn is_tick_in_range(&self, tick_lower: i32, tick_upper: i32) -> bool {
+        self.tick_current >= tick_lower && self.tick_current < tick_upper
+    }
+}
+
+/// A position in any supported Solana CLMM.
+#[derive(Debug, Clone)]
+pub struct ProtocolPosition {
+    /// Protocol of the position.
+    pub protocol: Protocol,
+    /// Mint of the NFT representing the position.
+    pub position_mint: Pubkey,
+    /// Position state.
+    pub on_chain: OnChainPosition,
+}
+
+/// Reads and manages positions of one Solana CLMM program.
+#[async_trait]
+pub trait ProtocolAdapter: Send + Sync {
+    /// Protocol served by the adapter.
+    fn protocol(&self) -> Protocol;
+
+    /// Program owning the protocol's pool and position accounts.
+    fn program_id(&self) -> Pubkey;
+
+    /// Reads a pool's current state.
+    async fn fetch_pool_state(&self, pool: &Pubkey) -> Result<ProtocolPoolState>;
+
+    /// Reads a position by its account address.
+    ///
+    /// The owner of the position NFT is not read and is left as the default
+    /// pubkey.
+    async fn fetch_position(&self, position: &Pubkey) -> Result<ProtocolPosition>;
+
+    /// Derives the position account of a position NFT mint.
+    fn position_address(&self, position_mint: &Pubkey) -> Pubkey;
+
+    /// Builds the instruction opening an empty position.
+    ///
+    /// `position_mint` is a fresh keypair that must sign the transaction.
+    fn build_open_position_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        owner: &Pubkey,
+        position_mint: &Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<Instruction>;
+
+    /// Builds the instruction adding liquidity to a position, spending at
+    /// most the given token amounts.
+    fn build_increase_liquidity_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<Instruction>;
+
+    /// Builds the instruction removing liquidity from a position, receiving
+    /// at least the given token amounts.
+    fn build_decrease_liquidity_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<Instruction>;
+
+    /// Builds the instruction collecting a position's owed fees.
+    fn build_collect_fees_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+    ) -> Result<Instruction>;
+
+    /// Builds the instruction collecting a position's owed reward from one
+    /// of the pool's reward slots.
+    ///
+    /// Protocols without per-slot reward collection return
+    /// [`ProtocolError::Unsupported`].
+    fn build_collect_reward_instruction(
+        &self,
+        _pool: &ProtocolPoolState,
+        _position: &ProtocolPosition,
+        _owner: &Pubkey,
+        reward_index: u8,
+        _reward_mint: &Pubkey,
+        _reward_vault: &Pubkey,
+    ) -> Result<Instruction> {
+        Err(ProtocolError::Unsupported(format!(
+            "{:?} reward {} collection",
+            self.protocol(),
+            reward_index
+        ))
+        .into())
+    }
+
+    /// Builds the instruction closing an empty position and burning its NFT.
+    fn build_close_position_instruction(
+        &self,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+    ) -> Result<Instruction>;
+}
+
+/// Protocol adapters keyed by program ID.
+pub struct ProtocolRegistry {
+    /// RPC provider for resolving account owners.
+    provider: Arc<RpcProvider>,
+    /// Adapters by program ID.
+    adapters: HashMap<Pubkey, Arc<dyn ProtocolAdapter>>,
+}
+
+impl ProtocolRegistry {
+    /// Creates an empty registry.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            provider,
+            adapters: HashMap::new(),
+        }
+    }
+
+    /// Creates a registry with the Orca Whirlpool and Raydium CLMM adapters.
+    pub fn with_defaults(provider: Arc<RpcProvider>) -> Self {
+        let mut registry = Self::new(provider.clone());
+        registry.register(Arc::new(OrcaAdapter::new(provider.clone())));
+        registry.register(Arc::new(RaydiumAdapter::new(provider)));
+        registry
+    }
+
+    /// Registers an adapter, replacing any adapter of the same program.
+    pub fn register(&mut self, adapter: Arc<dyn ProtocolAdapter>) {
+        self.adapters.insert(adapter.program_id(), adapter);
+    }
+
+    /// Gets the adapter of a program.
+    #[must_use]
+    pub fn get(&self, program_id: &Pubkey) -> Option<Arc<dyn ProtocolAdapter>> {
+        self.adapters.get(program_id).cloned()
+    }
+
+    /// Gets the adapter of a protocol.
+    #[must_use]
+    pub fn for_protocol(&self, protocol: Protocol) -> Option<Arc<dyn ProtocolAdapter>> {
+        self.adapters
+            .values()
+            .find(|adapter| adapter.protocol() == protocol)
+            .cloned()
+    }
+
+    /// Returns the program IDs with a registered adapter.
+    #[must_use]
+    pub fn program_ids(&self) -> Vec<Pubkey> {
+        self.adapters.keys().copied().collect()
+    }
+
+    /// Finds the adapter of a pool or position account from its owner.
+    ///
+    /// # Errors
+    /// Returns [`ProtocolError::UnsupportedProgram`] if no adapter serves the
+    /// program owning the account.
+    pub async fn adapter_for_account(&self, address: &Pubkey) -> Result<Arc<dyn ProtocolAdapter>> {
+        let account = self.provider.get_account(address).await?;
+        self.get(&account.owner)
+            .ok_or_else(|| ProtocolError::UnsupportedProgram(account.owner.to_string()).into())
+    }
+
+    /// Reads a pool of any registered protocol.
+    pub async fn fetch_pool_state(&self, pool: &Pubkey) -> Result<ProtocolPoolState> {
+        self.adapter_for_account(pool)
+            .await?
+            .fetch_pool_state(pool)
+            .await
+    }
+
+    /// Reads a position of any registered protocol.
+    pub async fn fetch_position(&self, position: &Pubkey) -> Result<ProtocolPosition> {
+        self.adapter_for_account(position)
+            .await?
+            .fetch_position(position)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orca::pool_reader::WHIRLPOOL_PROGRAM_ID;
+    use crate::raydium::accounts::RAYDIUM_CLMM_PROGRAM_ID;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_registry_lookup() {
+        let registry = ProtocolRegistry::with_defaults(Arc::new(RpcProvider::localhost()));
+
+        let orca = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).unwrap();
+        let raydium = Pubkey::from_str(RAYDIUM_CLMM_PROGRAM_ID).unwrap();
+        assert_eq!(registry.program_ids().len(), 2);
+        assert_eq!(
+            registry.get(&orca).unwrap().protocol(),
+            Protocol::OrcaWhirlpools
+        );
+        assert_eq!(
+            registry.get(&raydium).unwrap().protocol(),
+            Protocol::Raydium
+        );
+        assert_eq!(
+            registry
+                .for_protocol(Protocol::Raydium)
+                .unwrap()
+                .program_id(),
+            raydium
+        );
+        assert!(registry.get(&Pubkey::new_unique()).is_none());
+        assert!(registry.for_protocol(Protocol::MeteoraDLMM).is_none());
+    }
+}
@@ -0,0 +1,274 @@
+//! Chain abstraction for CLMM adapters.
+//!
+//! Orca, Raydium and Uniswap v3 share the same tick, liquidity and fee
+//! math. Adapters expose pool and position state in a chain-agnostic form
+//! so the optimizer and simulator can serve LPs on any supported chain.
+
+use crate::error::ProtocolError;
+use anyhow::Result;
+use async_trait::async_trait;
+use clmm_lp_domain::entities::pool::Pool;
+use clmm_lp_domain::entities::token::Token;
+use clmm_lp_domain::enums::{PoolType, Protocol};
+use clmm_lp_domain::value_objects::amount::Amount;
+use primitive_types::U256;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Blockchains with supported CLMM deployments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+    /// Solana mainnet.
+    Solana,
+    /// Ethereum mainnet.
+    Ethereum,
+    /// Arbitrum One.
+    Arbitrum,
+    /// OP Mainnet.
+    Optimism,
+    /// Base.
+    Base,
+    /// Polygon PoS.
+    Polygon,
+}
+
+impl Chain {
+    /// All supported chains.
+    pub const ALL: [Chain; 6] = [
+        Chain::Solana,
+        Chain::Ethereum,
+        Chain::Arbitrum,
+        Chain::Optimism,
+        Chain::Base,
+        Chain::Polygon,
+    ];
+
+    /// Gets the lowercase chain name.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Chain::Solana => "solana",
+            Chain::Ethereum => "ethereum",
+            Chain::Arbitrum => "arbitrum",
+            Chain::Optimism => "optimism",
+            Chain::Base => "base",
+            Chain::Polygon => "polygon",
+        }
+    }
+
+    /// Gets the EVM chain ID, or `None` for non-EVM chains.
+    #[must_use]
+    pub fn chain_id(self) -> Option<u64> {
+        match self {
+            Chain::Solana => None,
+            Chain::Ethereum => Some(1),
+            Chain::Arbitrum => Some(42_161),
+            Chain::Optimism => Some(10),
+            Chain::Base => Some(8_453),
+            Chain::Polygon => Some(137),
+        }
+    }
+
+    /// Finds the chain with an EVM chain ID.
+    #[must_use]
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|chain| chain.chain_id() == Some(chain_id))
+    }
+
+    /// Returns true for EVM chains.
+    #[must_use]
+    pub fn is_evm(self) -> bool {
+        self.chain_id().is_some()
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Chain {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        match name.as_str() {
+            "mainnet" | "eth" => Ok(Chain::Ethereum),
+            "arbitrum-one" | "arb" => Ok(Chain::Arbitrum),
+            "op" => Ok(Chain::Optimism),
+            "matic" => Ok(Chain::Polygon),
+            _ => Self::ALL
+                .into_iter()
+                .find(|chain| chain.name() == name)
+                .ok_or_else(|| ProtocolError::UnsupportedChain(s.to_string())),
+        }
+    }
+}
+
+/// Pool state read from any supported chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmPoolState {
+    /// Chain the pool lives on.
+    pub chain: Chain,
+    /// Protocol of the pool.
+    pub protocol: Protocol,
+    /// Pool address.
+    pub address: String,
+    /// Token 0 (token A) address.
+    pub token0: String,
+    /// Token 1 (token B) address.
+    pub token1: String,
+    /// Current tick index.
+    pub tick_current: i32,
+    /// Tick spacing.
+    pub tick_spacing: i32,
+    /// Current price of token 0 in token 1, in raw units.
+    pub price: Decimal,
+    /// Liquidity at the current tick.
+    pub liquidity: u128,
+    /// Fee rate in hundredths of a basis point (3000 = 0.3%).
+    pub fee_rate: u32,
+}
+
+impl ClmmPoolState {
+    /// Returns the fee rate as a decimal.
+    #[must_use]
+    pub fn fee_fraction(&self) -> Decimal {
+        Decimal::from(self.fee_rate) / Decimal::from(1_000_000)
+    }
+
+    /// Checks if a tick is within the current range.
+    #[must_use]
+    pub fn is_tick_in_range(&self, tick_lower: i32, tick_upper: i32) -> bool {
+        self.tick_current >= tick_lower && self.tick_current < tick_upper
+    }
+
+    /// Converts the state into a domain pool with the given token metadata.
+    ///
+    /// Reserves are not part of the state and are left at zero.
+    #[must_use]
+    pub fn to_pool(&self, token0: Token, token1: Token) -> Pool {
+        Pool {
+            address: self.address.clone(),
+            protocol: self.protocol,
+            pool_type: PoolType::ConcentratedLiquidity,
+            reserve_a: Amount::new(U256::zero(), token0.decimals),
+            reserve_b: Amount::new(U256::zero(), token1.decimals),
+            token_a: token0,
+            token_b: token1,
+            fee_rate: self.fee_rate / 100,
+            tick_spacing: Some(self.tick_spacing),
+            current_tick: Some(self.tick_current),
+            liquidity: Some(self.liquidity),
+            amplification_coefficient: None,
+            created_at: 0,
+        }
+    }
+}
+
+/// Position state read from any supported chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClmmPositionState {
+    /// Chain the position lives on.
+    pub chain: Chain,
+    /// Protocol of the position.
+    pub protocol: Protocol,
+    /// Position identifier: the account address on Solana, the NFT token ID
+    /// on EVM chains.
+    pub id: String,
+    /// Pool address.
+    pub pool: String,
+    /// Owner address, if known.
+    pub owner: Option<String>,
+    /// Lower tick.
+    pub tick_lower: i32,
+    /// Upper tick.
+    pub tick_upper: i32,
+    /// Liquidity amount.
+    pub liquidity: u128,
+    /// Uncollected fees for token 0.
+    pub fees_owed_0: u128,
+    /// Uncollected fees for token 1.
+    pub fees_owed_1: u128,
+}
+
+impl ClmmPositionState {
+    /// Checks if the position is in range at a tick.
+    #[must_use]
+    pub fn is_in_range(&self, tick: i32) -> bool {
+        tick >= self.tick_lower && tick < self.tick_upper
+    }
+}
+
+/// Reads CLMM pools and positions on one chain.
+#[async_trait]
+pub trait ClmmAdapter: Send + Sync {
+    /// Chain served by the adapter.
+    fn chain(&self) -> Chain;
+
+    /// Protocol served by the adapter.
+    fn protocol(&self) -> Protocol;
+
+    /// Reads a pool's current state.
+    async fn pool_state(&self, pool_address: &str) -> Result<ClmmPoolState>;
+
+    /// Reads a position by its identifier.
+    async fn position(&self, position_id: &str) -> Result<ClmmPositionState>;
+
+    /// Reads every position held by an owner.
+    async fn positions_by_owner(&self, owner: &str) -> Result<Vec<ClmmPositionState>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_names_and_ids() {
+        for chain in Chain::ALL {
+            assert_eq!(chain.to_string().parse::<Chain>().unwrap(), chain);
+            if let Some(id) = chain.chain_id() {
+                assert_eq!(Chain::from_chain_id(id), Some(chain));
+            }
+        }
+        assert_eq!("Arb".parse::<Chain>().unwrap(), Chain::Arbitrum);
+        assert!(!Chain::Solana.is_evm());
+        assert!(Chain::Base.is_evm());
+        assert!(matches!(
+            "cosmos".parse::<Chain>(),
+            Err(ProtocolError::UnsupportedChain(_))
+        ));
+    }
+
+    #[test]
+    fn test_pool_state_to_pool() {
+        let state = ClmmPoolState {
+            chain: Chain::Arbitrum,
+            protocol: Protocol::UniswapV3,
+            address: "0xc6962004f452be9203591991d15f6b388e09e8d0".to_string(),
+            token0: "0x82af49447d8a07e3bd95bd0d56f35241523fbab1".to_string(),
+            token1: "0xaf88d065e77c8cc2239327c5edb3a432268e5831".to_string(),
+            tick_current: -196_000,
+            tick_spacing: 10,
+            price: Decimal::ZERO,
+            liquidity: 1_000,
+            fee_rate: 500,
+        };
+        let pool = state.to_pool(
+            Token::new(&state.token0, "WETH", 18, "Wrapped Ether"),
+            Token::new(&state.token1, "USDC", 6, "USD Coin"),
+        );
+
+        assert_eq!(pool.fee_rate, 5);
+        assert_eq!(state.fee_fraction(), Decimal::new(5, 4));
+        assert_eq!(pool.current_tick, Some(-196_000));
+        assert_eq!(pool.reserve_b.decimals, 6);
+        assert!(state.is_tick_in_range(-196_010, -195_990));
+    }
+}
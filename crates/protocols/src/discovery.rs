@@ -0,0 +1,326 @@
+//! Discovery of the LP positions held by a wallet.
+//!
+//! Orca and Raydium represent a position as an NFT held by its owner. The
+//! wallet's token accounts are scanned for NFTs, the position account of each
+//! mint is derived for every protocol, and the accounts that exist are read
+//! together with the current tick of their pools.
+
+use crate::events::{OnChainPosition, Protocol};
+use crate::orca::position_reader::PositionReader;
+use crate::orca::whirlpool::Whirlpool;
+use crate::raydium::accounts::RaydiumPool;
+use crate::raydium::position_reader::RaydiumPositionReader;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// SPL Token program ID.
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// SPL Token-2022 program ID.
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PeR5ChwV5vkRW5jB";
+
+/// Size of an SPL token account without extensions.
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Offset of the owner in an SPL token account.
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+
+/// Offset of the decimals in an SPL mint account.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Returns the mint of a token account holding exactly one token.
+fn nft_mint(data: &[u8]) -> Option<Pubkey> {
+    let mint = Pubkey::try_from(data.get(0..32)?).ok()?;
+    let amount = u64::from_le_bytes(data.get(64..72)?.try_into().ok()?);
+    (amount == 1).then_some(mint)
+}
+
+/// Returns the mint and raw amount of a token account.
+fn token_amount(data: &[u8]) -> Option<(Pubkey, u64)> {
+    let mint = Pubkey::try_from(data.get(0..32)?).ok()?;
+    let amount = u64::from_le_bytes(data.get(64..72)?.try_into().ok()?);
+    Some((mint, amount))
+}
+
+/// A token balance held by a wallet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBalance {
+    /// Token account address.
+    pub account: Pubkey,
+    /// Token mint.
+    pub mint: Pubkey,
+    /// Raw amount in the mint's smallest unit.
+    pub amount: u64,
+    /// Decimals of the mint; `None` when the mint could not be read.
+    pub decimals: Option<u8>,
+}
+
+impl TokenBalance {
+    /// Returns the amount in whole tokens, if the decimals are known.
+    #[must_use]
+    pub fn ui_amount(&self) -> Option<f64> {
+        self.decimals
+            .map(|decimals| self.amount as f64 / 10f64.powi(i32::from(decimals)))
+    }
+}
+
+/// Finds the non-zero token balances of a wallet, largest raw amount first.
+///
+/// Both SPL Token and Token-2022 accounts are scanned, and the decimals of
+/// every mint are read in one batch.
+pub async fn find_token_balances(
+    provider: &RpcProvider,
+    owner: &Pubkey,
+) -> Result<Vec<TokenBalance>> {
+    let mut balances: Vec<TokenBalance> = owned_token_accounts(provider, owner)
+        .await?
+        .iter()
+        .filter_map(|(account, data)| {
+            let (mint, amount) = token_amount(&data.data)?;
+            (amount > 0).then_some(TokenBalance {
+                account: *account,
+                mint,
+                amount,
+                decimals: None,
+            })
+        })
+        .collect();
+
+    let mints: Vec<Pubkey> = balances.iter().map(|balance| balance.mint).collect();
+    let mint_accounts = provider.get_multiple_accounts(&mints).await?;
+    for (balance, mint) in balances.iter_mut().zip(mint_accounts) {
+        balance.decimals = mint.and_then(|mint| mint.data.get(MINT_DECIMALS_OFFSET).copied());
+    }
+
+    balances.sort_by(|a, b| b.amount.cmp(&a.amount));
+    Ok(balances)
+}
+
+/// Finds the mints of the NFTs held by a wallet.
+///
+/// Both SPL Token and Token-2022 accounts are scanned, as newer Orca
+/// positions are minted with Token-2022.
+pub async fn find_position_mints(provider: &RpcProvider, owner: &Pubkey) -> Result<Vec<Pubkey>> {
+    let mints: Vec<Pubkey> = owned_token_accounts(provider, owner)
+        .await?
+        .iter()
+        .filter_map(|(_, account)| nft_mint(&account.data))
+        .collect();
+
+    debug!(owner = %owner, count = mints.len(), "Found NFT mints");
+    Ok(mints)
+}
+
+/// Loads the SPL Token and Token-2022 accounts owned by a wallet.
+async fn owned_token_accounts(
+    provider: &RpcProvider,
+    owner: &Pubkey,
+) -> Result<Vec<(Pubkey, Account)>> {
+    let owner_filter = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        TOKEN_ACCOUNT_OWNER_OFFSET,
+        owner.as_ref(),
+    ));
+
+    let token_accounts = provider
+        .get_program_accounts(
+            &Pubkey::from_str(TOKEN_PROGRAM_ID)?,
+            vec![
+                RpcFilterType::DataSize(TOKEN_ACCOUNT_LEN as u64),
+                owner_filter.clone(),
+            ],
+        )
+        .await?;
+    let token_2022_accounts = provider
+        .get_program_accounts(
+            &Pubkey::from_str(TOKEN_2022_PROGRAM_ID)?,
+            vec![owner_filter],
+        )
+        .await?;
+
+    Ok(token_accounts
+        .into_iter()
+        .chain(token_2022_accounts)
+        .collect())
+}
+
+/// A position held by a wallet.
+#[derive(Debug, Clone)]
+pub struct WalletPosition {
+    /// Protocol of the position.
+    pub protocol: Protocol,
+    /// Position state.
+    pub position: OnChainPosition,
+    /// Current tick of the pool; `None` when the pool could not be read.
+    pub tick_current: Option<i32>,
+}
+
+impl WalletPosition {
+    /// Checks whether the pool's current tick is inside the position's range.
+    ///
+    /// Returns `None` when the current tick is unknown.
+    #[must_use]
+    pub fn in_range(&self) -> Option<bool> {
+        self.tick_current
+            .map(|tick| tick >= self.position.tick_lower && tick < self.position.tick_upper)
+    }
+}
+
+/// Finds the Orca and Raydium positions of a wallet.
+pub struct WalletPositionScanner {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+}
+
+impl WalletPositionScanner {
+    /// Creates a new scanner.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Scans a wallet for positions, ordered by protocol and pool.
+    pub async fn scan(&self, owner: &str) -> Result<Vec<WalletPosition>> {
+        let owner = Pubkey::from_str(owner).context("Invalid owner address")?;
+
+        info!(owner = %owner, "Scanning wallet for positions");
+
+        let mints = find_position_mints(&self.provider, &owner).await?;
+        if mints.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let orca = PositionReader::new(self.provider.clone())
+            .get_positions_for_mints(&owner, &mints)
+            .await?;
+        let raydium = RaydiumPositionReader::new(self.provider.clone())
+            .get_positions_for_mints(&owner, &mints)
+            .await?;
+
+        let mut positions: Vec<WalletPosition> = orca
+            .into_iter()
+            .map(|position| (Protocol::OrcaWhirlpool, position))
+            .chain(
+                raydium
+                    .into_iter()
+                    .map(|position| (Protocol::RaydiumClmm, position)),
+            )
+            .map(|(protocol, position)| WalletPosition {
+                protocol,
+                position,
+                tick_current: None,
+            })
+            .collect();
+
+        let ticks = self.current_ticks(&positions).await?;
+        for wallet_position in &mut positions {
+            wallet_position.tick_current = ticks.get(&wallet_position.position.pool).copied();
+        }
+        positions.sort_by_key(|p| (p.protocol as u8, p.position.pool.to_string()));
+
+        Ok(positions)
+    }
+
+    /// Reads the current tick of the pools of the given positions.
+    async fn current_ticks(&self, positions: &[WalletPosition]) -> Result<HashMap<Pubkey, i32>> {
+        let mut pools: Vec<(Pubkey, Protocol)> = positions
+            .iter()
+            .map(|p| (p.position.pool, p.protocol))
+            .collect();
+        pools.sort_by_key(|(pool, _)| *pool);
+        pools.dedup_by_key(|(pool, _)| *pool);
+
+        let addresses: Vec<Pubkey> = pools.iter().map(|(pool, _)| *pool).collect();
+        let accounts = self.provider.get_multiple_accounts(&addresses).await?;
+
+        let mut ticks = HashMap::new();
+        for ((pool, protocol), account) in pools.into_iter().zip(accounts) {
+            let Some(account) = account else { continue };
+            let tick = match protocol {
+                Protocol::OrcaWhirlpool => Whirlpool::deserialize(&mut account.data.as_slice())
+                    .ok()
+                    .map(|wp| wp.tick_current_index),
+                Protocol::RaydiumClmm => RaydiumPool::parse(&account.data)
+                    .ok()
+                    .map(|p| p.tick_current),
+                Protocol::MeteoraDlmm => None,
+            };
+            match tick {
+                Some(tick) => {
+                    ticks.insert(pool, tick);
+                }
+                None => debug!(pool = %pool, "Could not read pool tick"),
+            }
+        }
+        Ok(ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_account(mint: &Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+        data[0..32].copy_from_slice(mint.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_nft_mint() {
+        let mint = Pubkey::new_unique();
+        assert_eq!(nft_mint(&token_account(&mint, 1)), Some(mint));
+        assert_eq!(nft_mint(&token_account(&mint, 0)), None);
+        assert_eq!(nft_mint(&token_account(&mint, 5_000_000)), None);
+        assert_eq!(nft_mint(&[0u8; 40]), None);
+    }
+
+    #[test]
+    fn test_token_balance_ui_amount() {
+        let mint = Pubkey::new_unique();
+        assert_eq!(token_amount(&token_account(&mint, 42)), Some((mint, 42)));
+
+        let mut balance = TokenBalance {
+            account: Pubkey::new_unique(),
+            mint,
+            amount: 1_500_000,
+            decimals: Some(6),
+        };
+        assert_eq!(balance.ui_amount(), Some(1.5));
+        balance.decimals = None;
+        assert_eq!(balance.ui_amount(), None);
+    }
+
+    #[test]
+    fn test_in_range() {
+        let mut position = WalletPosition {
+            protocol: Protocol::OrcaWhirlpool,
+            position: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                tick_lower: -100,
+                tick_upper: 100,
+                liquidity: 1,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+            },
+            tick_current: None,
+        };
+        assert_eq!(position.in_range(), None);
+
+        position.tick_current = Some(-100);
+        assert_eq!(position.in_range(), Some(true));
+        position.tick_current = Some(100);
+        assert_eq!(position.in_range(), Some(false));
+    }
+}
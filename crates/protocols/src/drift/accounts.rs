@@ -0,0 +1,168 @@
+//! Drift account layouts.
+
+use super::{BASE_PRECISION, DRIFT_PROGRAM_ID, PRICE_PRECISION};
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Number of perp position slots in a user account.
+const PERP_POSITION_SLOTS: usize = 8;
+
+/// Offset of the perp positions in a user account.
+///
+/// discriminator (8) + authority (32) + delegate (32) + name (32)
+/// + 8 spot positions (40 bytes each).
+const PERP_POSITIONS_OFFSET: usize = 8 + 32 + 32 + 32 + 8 * 40;
+
+/// Size of a serialized perp position.
+const PERP_POSITION_SIZE: usize = 96;
+
+/// A perp position slot in a Drift user account.
+#[derive(BorshDeserialize, Debug, Clone, Default)]
+pub struct PerpPosition {
+    /// Cumulative funding rate when the position last settled funding.
+    pub last_cumulative_funding_rate: i64,
+    /// Signed position size (positive is long), in base precision.
+    pub base_asset_amount: i64,
+    /// Quote asset amount, in quote precision.
+    pub quote_asset_amount: i64,
+    /// Quote amount to break even, in quote precision.
+    pub quote_break_even_amount: i64,
+    /// Quote amount paid on entry, in quote precision.
+    pub quote_entry_amount: i64,
+    /// Open bid size, in base precision.
+    pub open_bids: i64,
+    /// Open ask size, in base precision.
+    pub open_asks: i64,
+    /// Settled PnL, in quote precision.
+    pub settled_pnl: i64,
+    /// LP shares.
+    pub lp_shares: u64,
+    /// Base amount per LP share at last settlement.
+    pub last_base_asset_amount_per_lp: i64,
+    /// Quote amount per LP share at last settlement.
+    pub last_quote_asset_amount_per_lp: i64,
+    /// Remainder base amount.
+    pub remainder_base_asset_amount: i32,
+    /// Perp market index.
+    pub market_index: u16,
+    /// Number of open orders.
+    pub open_orders: u8,
+    /// LP base precision exponent.
+    pub per_lp_base: i8,
+}
+
+impl PerpPosition {
+    /// Returns true if the slot holds no position and no orders.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.base_asset_amount == 0 && self.open_orders == 0 && self.lp_shares == 0
+    }
+
+    /// Gets the signed position size in base units (positive is long).
+    #[must_use]
+    pub fn base_size(&self) -> Decimal {
+        Decimal::from(self.base_asset_amount) / Decimal::from(BASE_PRECISION)
+    }
+
+    /// Gets the quote asset amount in quote units.
+    #[must_use]
+    pub fn quote_amount(&self) -> Decimal {
+        Decimal::from(self.quote_asset_amount) / Decimal::from(PRICE_PRECISION)
+    }
+}
+
+/// Parses the non-empty perp positions from a Drift user account.
+///
+/// # Errors
+/// Returns an error if the account data is too short.
+pub fn parse_perp_positions(data: &[u8]) -> Result<Vec<PerpPosition>> {
+    let end = PERP_POSITIONS_OFFSET + PERP_POSITION_SLOTS * PERP_POSITION_SIZE;
+    let slots = data
+        .get(PERP_POSITIONS_OFFSET..end)
+        .context("Drift user account data too short")?;
+
+    let mut positions = Vec::new();
+    for chunk in slots.chunks_exact(PERP_POSITION_SIZE) {
+        let position = PerpPosition::try_from_slice(chunk)
+            .context("Failed to deserialize Drift perp position")?;
+        if !position.is_empty() {
+            positions.push(position);
+        }
+    }
+    Ok(positions)
+}
+
+/// Gets the Drift program ID.
+#[must_use]
+pub fn program_id() -> Pubkey {
+    Pubkey::from_str(DRIFT_PROGRAM_ID).expect("valid Drift program ID")
+}
+
+/// Derives the global state account address.
+#[must_use]
+pub fn state_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"drift_state"], &program_id()).0
+}
+
+/// Derives a user (sub)account address.
+#[must_use]
+pub fn user_address(authority: &Pubkey, sub_account_id: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user", authority.as_ref(), &sub_account_id.to_le_bytes()],
+        &program_id(),
+    )
+    .0
+}
+
+/// Derives a perp market account address.
+#[must_use]
+pub fn perp_market_address(market_index: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"perp_market", &market_index.to_le_bytes()],
+        &program_id(),
+    )
+    .0
+}
+
+/// Derives a spot market account address.
+#[must_use]
+pub fn spot_market_address(market_index: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"spot_market", &market_index.to_le_bytes()],
+        &program_id(),
+    )
+    .0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_perp_positions() {
+        let mut data = vec![0u8; PERP_POSITIONS_OFFSET + PERP_POSITION_SLOTS * PERP_POSITION_SIZE];
+
+        // Short 2.5 SOL-PERP (market 0) in the second slot
+        let slot = PERP_POSITIONS_OFFSET + PERP_POSITION_SIZE;
+        data[slot + 8..slot + 16].copy_from_slice(&(-2_500_000_000i64).to_le_bytes());
+        data[slot + 16..slot + 24].copy_from_slice(&(375_000_000i64).to_le_bytes());
+        data[slot + 92..slot + 94].copy_from_slice(&0u16.to_le_bytes());
+
+        let positions = parse_perp_positions(&data).unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].base_size(), Decimal::new(-25, 1));
+        assert_eq!(positions[0].quote_amount(), Decimal::from(375));
+
+        assert!(parse_perp_positions(&data[..100]).is_err());
+    }
+
+    #[test]
+    fn test_addresses_are_distinct() {
+        let authority = Pubkey::new_unique();
+        assert_ne!(user_address(&authority, 0), user_address(&authority, 1));
+        assert_ne!(perp_market_address(0), spot_market_address(0));
+    }
+}
@@ -0,0 +1,278 @@
+//! Drift executor for perp orders.
+
+use super::accounts::{
+    PerpPosition, parse_perp_positions, perp_market_address, program_id, spot_market_address,
+    state_address, user_address,
+};
+use super::{BASE_PRECISION, QUOTE_SPOT_MARKET_INDEX};
+use crate::orca::executor::ExecutionResult;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use borsh::BorshSerialize;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Direction of a perp order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize)]
+pub enum PositionDirection {
+    /// Buy (increase long / reduce short).
+    Long,
+    /// Sell (increase short / reduce long).
+    Short,
+}
+
+/// Parameters for a perp market order.
+#[derive(Debug, Clone)]
+pub struct PerpOrderParams {
+    /// Perp market index.
+    pub market_index: u16,
+    /// Oracle account of the market.
+    pub oracle: Pubkey,
+    /// Order direction.
+    pub direction: PositionDirection,
+    /// Order size in base units.
+    pub base_amount: Decimal,
+    /// Only reduce an existing position.
+    pub reduce_only: bool,
+}
+
+/// Drift `OrderParams` as serialized on-chain.
+#[derive(BorshSerialize)]
+struct OrderParams {
+    order_type: u8,
+    market_type: u8,
+    direction: PositionDirection,
+    user_order_id: u8,
+    base_asset_amount: u64,
+    price: u64,
+    market_index: u16,
+    reduce_only: bool,
+    post_only: u8,
+    bit_flags: u8,
+    max_ts: Option<i64>,
+    trigger_price: Option<u64>,
+    trigger_condition: u8,
+    oracle_price_offset: Option<i32>,
+    auction_duration: Option<u8>,
+    auction_start_price: Option<i64>,
+    auction_end_price: Option<i64>,
+}
+
+/// Market order type.
+const ORDER_TYPE_MARKET: u8 = 0;
+
+/// Perp market type.
+const MARKET_TYPE_PERP: u8 = 1;
+
+/// Executes perp orders on Drift for a user subaccount.
+pub struct DriftExecutor {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Drift program ID.
+    program_id: Pubkey,
+    /// User subaccount ID.
+    sub_account_id: u16,
+}
+
+impl DriftExecutor {
+    /// Creates a new Drift executor for subaccount 0.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            provider,
+            program_id: program_id(),
+            sub_account_id: 0,
+        }
+    }
+
+    /// Sets the user subaccount ID.
+    #[must_use]
+    pub fn with_sub_account(mut self, sub_account_id: u16) -> Self {
+        self.sub_account_id = sub_account_id;
+        self
+    }
+
+    /// Gets the open perp positions of an authority's subaccount.
+    pub async fn get_perp_positions(&self, authority: &Pubkey) -> Result<Vec<PerpPosition>> {
+        let user = user_address(authority, self.sub_account_id);
+        let account = self
+            .provider
+            .get_account(&user)
+            .await
+            .context("Failed to fetch Drift user account")?;
+        parse_perp_positions(&account.data)
+    }
+
+    /// Gets the signed perp position size in a market (zero if none).
+    pub async fn get_perp_position_size(
+        &self,
+        authority: &Pubkey,
+        market_index: u16,
+    ) -> Result<Decimal> {
+        Ok(self
+            .get_perp_positions(authority)
+            .await?
+            .iter()
+            .find(|p| p.market_index == market_index)
+            .map(PerpPosition::base_size)
+            .unwrap_or(Decimal::ZERO))
+    }
+
+    /// Places a perp market order.
+    ///
+    /// # Returns
+    /// Execution result with transaction signature.
+    pub async fn place_perp_order<S: Signer>(
+        &self,
+        params: &PerpOrderParams,
+        authority: &S,
+    ) -> Result<ExecutionResult> {
+        info!(
+            market_index = params.market_index,
+            direction = ?params.direction,
+            base_amount = %params.base_amount,
+            "Placing Drift perp order"
+        );
+
+        let ix = self.build_place_perp_order_instruction(params, &authority.pubkey())?;
+
+        let recent_blockhash = self
+            .provider
+            .get_latest_blockhash()
+            .await
+            .context("Failed to get recent blockhash")?;
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            recent_blockhash,
+        );
+
+        debug!("Sending Drift order transaction...");
+
+        match self
+            .provider
+            .send_and_confirm_transaction(&transaction)
+            .await
+        {
+            Ok(signature) => {
+                info!(signature = %signature, "Drift order confirmed");
+                let slot = self.provider.get_slot().await.unwrap_or(0);
+                Ok(ExecutionResult::success(signature, slot))
+            }
+            Err(e) => {
+                let signature = transaction.signatures.first().copied().unwrap_or_default();
+                Ok(ExecutionResult::failure(signature, e.to_string()))
+            }
+        }
+    }
+
+    /// Builds a `place_perp_order` instruction.
+    ///
+    /// # Errors
+    /// Returns an error if the order size is not positive or out of range.
+    pub fn build_place_perp_order_instruction(
+        &self,
+        params: &PerpOrderParams,
+        authority: &Pubkey,
+    ) -> Result<Instruction> {
+        // Drift PlacePerpOrder instruction discriminator
+        let discriminator: [u8; 8] = [0x45, 0xa1, 0x5d, 0xca, 0x78, 0x7e, 0x4c, 0xb9];
+
+        let base_asset_amount = (params.base_amount * Decimal::from(BASE_PRECISION))
+            .trunc()
+            .to_u64()
+            .filter(|amount| *amount > 0)
+            .context("Order size must be positive")?;
+
+        let order = OrderParams {
+            order_type: ORDER_TYPE_MARKET,
+            market_type: MARKET_TYPE_PERP,
+            direction: params.direction,
+            user_order_id: 0,
+            base_asset_amount,
+            price: 0,
+            market_index: params.market_index,
+            reduce_only: params.reduce_only,
+            post_only: 0,
+            bit_flags: 0,
+            max_ts: None,
+            trigger_price: None,
+            trigger_condition: 0,
+            oracle_price_offset: None,
+            auction_duration: None,
+            auction_start_price: None,
+            auction_end_price: None,
+        };
+
+        let mut data = discriminator.to_vec();
+        order
+            .serialize(&mut data)
+            .context("Failed to serialize order params")?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(state_address(), false), // state
+            AccountMeta::new(user_address(authority, self.sub_account_id), false), // user
+            AccountMeta::new_readonly(*authority, true),       // authority
+            // Remaining accounts: oracle, quote spot market, perp market
+            AccountMeta::new_readonly(params.oracle, false),
+            AccountMeta::new_readonly(spot_market_address(QUOTE_SPOT_MARKET_INDEX), false),
+            AccountMeta::new(perp_market_address(params.market_index), false),
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_place_perp_order_instruction() {
+        let executor = DriftExecutor::new(Arc::new(RpcProvider::devnet()));
+        let authority = Pubkey::new_unique();
+        let params = PerpOrderParams {
+            market_index: 0,
+            oracle: Pubkey::new_unique(),
+            direction: PositionDirection::Short,
+            base_amount: Decimal::new(15, 1),
+            reduce_only: false,
+        };
+
+        let ix = executor
+            .build_place_perp_order_instruction(&params, &authority)
+            .unwrap();
+        assert_eq!(ix.accounts.len(), 6);
+        assert!(ix.accounts[2].is_signer);
+        // discriminator + fixed-size fields + six `None` options and trigger condition
+        assert_eq!(ix.data.len(), 8 + 25 + 7);
+        assert_eq!(ix.data[10], 1); // short
+        assert_eq!(
+            u64::from_le_bytes(ix.data[12..20].try_into().unwrap()),
+            1_500_000_000
+        );
+
+        let zero = PerpOrderParams {
+            base_amount: Decimal::ZERO,
+            ..params
+        };
+        assert!(
+            executor
+                .build_place_perp_order_instruction(&zero, &authority)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Drift Protocol perpetuals adapter.
+//!
+//! This module provides functionality to hedge on Drift perpetual markets:
+//! - Derive Drift account addresses
+//! - Read perp positions from a user account
+//! - Place perp market orders
+
+/// Drift account structures.
+pub mod accounts;
+/// Executor for perp orders.
+#[cfg(feature = "rpc")]
+pub mod executor;
+
+/// Drift v2 program ID (mainnet and devnet).
+pub const DRIFT_PROGRAM_ID: &str = "dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH";
+
+/// Precision of base asset amounts (1e9).
+pub const BASE_PRECISION: u64 = 1_000_000_000;
+
+/// Precision of prices and quote amounts (1e6).
+pub const PRICE_PRECISION: u64 = 1_000_000;
+
+/// Spot market index of the USDC quote asset.
+pub const QUOTE_SPOT_MARKET_INDEX: u16 = 0;
@@ -0,0 +1,93 @@
+//! Error types for protocol adapters and RPC access.
+//!
+//! Adapters still return `anyhow::Result`, but the failures callers need to
+//! tell apart are raised as [`ProtocolError`] and can be recovered with
+//! [`ProtocolError::find`].
+
+use thiserror::Error;
+
+/// Result type for protocol operations.
+pub type ProtocolResult<T> = Result<T, ProtocolError>;
+
+/// Errors raised while talking to Solana or EVM chains and decoding protocol
+/// accounts.
+#[derive(Debug, Error)]
+pub enum ProtocolError {
+    /// The string is not a valid Solana address.
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    /// The account does not exist on chain.
+    #[error("Account not found: {0}")]
+    AccountNotFound(String),
+    /// The account exists but its data could not be decoded.
+    #[error("Invalid account data for {account}: {message}")]
+    InvalidAccountData {
+        /// Account address.
+        account: String,
+        /// Decoding failure.
+        message: String,
+    },
+    /// Every attempt against every endpoint failed.
+    #[error("RPC unavailable after {attempts} attempts")]
+    RpcUnavailable {
+        /// Number of attempts made.
+        attempts: u32,
+    },
+    /// The transaction was rejected or failed on chain.
+    #[error("Transaction failed: {0}")]
+    Transaction(String),
+    /// The chain name or ID is not supported.
+    #[error("Unsupported chain: {0}")]
+    UnsupportedChain(String),
+    /// No adapter serves the program owning an account.
+    #[error("Unsupported program: {0}")]
+    UnsupportedProgram(String),
+    /// The adapter does not support the operation.
+    #[error("Unsupported operation: {0}")]
+    Unsupported(String),
+    /// An EVM contract call reverted or returned malformed data.
+    #[error("Contract call to {contract} failed: {message}")]
+    ContractCall {
+        /// Contract address.
+        contract: String,
+        /// Revert reason or decoding failure.
+        message: String,
+    },
+}
+
+impl ProtocolError {
+    /// Finds a protocol error anywhere in an error chain.
+    #[must_use]
+    pub fn find(err: &anyhow::Error) -> Option<&Self> {
+        // `anyhow::Error::downcast_ref` also matches errors attached as context
+        err.downcast_ref::<Self>()
+            .or_else(|| err.chain().find_map(|e| e.downcast_ref::<Self>()))
+    }
+
+    /// Returns true for failures that are worth retrying later.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::RpcUnavailable { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_find_through_context() {
+        let err: anyhow::Result<()> =
+            Err(ProtocolError::AccountNotFound("abc".to_string())).context("Failed to read pool");
+        let err = err.unwrap_err();
+
+        assert!(matches!(
+            ProtocolError::find(&err),
+            Some(ProtocolError::AccountNotFound(_))
+        ));
+        let err = anyhow::anyhow!("timeout").context(ProtocolError::RpcUnavailable { attempts: 3 });
+        assert!(ProtocolError::find(&err).is_some_and(ProtocolError::is_transient));
+        assert!(ProtocolError::find(&anyhow::anyhow!("other")).is_none());
+    }
+}
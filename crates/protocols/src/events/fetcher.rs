@@ -1,10 +1,9 @@
 //! Event fetcher for retrieving historical transactions.
 
-use super::ProtocolEvent;
-use crate::rpc::RpcProvider;
+use super::{EventParser, Protocol, ProtocolEvent, SwapEvent};
+use crate::rpc::{AccountSignature, RpcProvider};
 use anyhow::{Context, Result};
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::Signature;
 use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{debug, info};
@@ -36,22 +35,30 @@ impl Default for FetchConfig {
 /// Fetches events from on-chain transactions.
 pub struct EventFetcher {
     /// RPC provider.
-    #[allow(dead_code)]
     provider: Arc<RpcProvider>,
     /// Fetch configuration.
-    #[allow(dead_code)]
     config: FetchConfig,
+    /// Parser of the program logs.
+    parser: EventParser,
 }
 
 impl EventFetcher {
-    /// Creates a new event fetcher.
+    /// Creates a new event fetcher for Orca Whirlpool transactions.
     pub fn new(provider: Arc<RpcProvider>) -> Self {
         Self {
             provider,
             config: FetchConfig::default(),
+            parser: EventParser::new(Protocol::OrcaWhirlpool),
         }
     }
 
+    /// Sets the protocol whose logs are parsed.
+    #[must_use]
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.parser = EventParser::new(protocol);
+        self
+    }
+
     /// Sets the fetch configuration.
     #[must_use]
     pub fn with_config(mut self, config: FetchConfig) -> Self {
@@ -83,8 +90,8 @@ impl EventFetcher {
 
         // Parse events from transactions
         let mut events = Vec::new();
-        for sig in signatures {
-            if let Ok(parsed) = self.parse_transaction(&sig).await {
+        for sig in &signatures {
+            if let Ok(parsed) = self.parse_transaction(sig).await {
                 events.extend(parsed);
             }
         }
@@ -92,6 +99,65 @@ impl EventFetcher {
         Ok(events)
     }
 
+    /// Fetches the swaps of a pool between two Unix timestamps, oldest
+    /// first.
+    ///
+    /// Transactions are paged back from the most recent until one is older
+    /// than `start_time`; transactions that cannot be read are skipped.
+    pub async fn fetch_swaps(
+        &self,
+        pool_address: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<SwapEvent>> {
+        let pubkey = Pubkey::from_str(pool_address).context("Invalid pool address")?;
+
+        info!(
+            pool = pool_address,
+            start_time, end_time, "Fetching pool swaps"
+        );
+
+        let mut swaps = Vec::new();
+        let mut before = None;
+        'pages: loop {
+            let page = self
+                .provider
+                .get_signatures_for_address(&pubkey, before, self.config.batch_size)
+                .await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(last.signature);
+            let exhausted = page.len() < self.config.batch_size;
+
+            for sig in page.iter().filter(|sig| self.wanted(sig)) {
+                let Some(time) = sig.block_time.and_then(|t| u64::try_from(t).ok()) else {
+                    continue;
+                };
+                if time < start_time {
+                    break 'pages;
+                }
+                if time > end_time {
+                    continue;
+                }
+                if let Ok(parsed) = self.parse_transaction(sig).await {
+                    swaps.extend(parsed.into_iter().filter_map(|event| match event {
+                        ProtocolEvent::Swap(swap) if swap.pool == pool_address => Some(swap),
+                        _ => None,
+                    }));
+                }
+            }
+
+            if exhausted {
+                break;
+            }
+        }
+
+        swaps.reverse();
+        debug!(count = swaps.len(), "Fetched pool swaps");
+        Ok(swaps)
+    }
+
     /// Fetches events for a position address.
     pub async fn fetch_position_events(
         &self,
@@ -109,8 +175,8 @@ impl EventFetcher {
         let signatures = self.get_signatures_for_address(&pubkey, limit).await?;
         let mut events = Vec::new();
 
-        for sig in signatures {
-            if let Ok(parsed) = self.parse_transaction(&sig).await {
+        for sig in &signatures {
+            if let Ok(parsed) = self.parse_transaction(sig).await {
                 events.extend(parsed);
             }
         }
@@ -118,24 +184,61 @@ impl EventFetcher {
         Ok(events)
     }
 
-    /// Gets transaction signatures for an address.
+    /// Gets up to `limit` transaction signatures for an address, newest
+    /// first, within the configured slots.
     async fn get_signatures_for_address(
         &self,
-        _address: &Pubkey,
-        _limit: usize,
-    ) -> Result<Vec<Signature>> {
-        // TODO: Implement using RPC getSignaturesForAddress
-        // This requires additional RPC methods in the provider
-        Ok(vec![])
+        address: &Pubkey,
+        limit: usize,
+    ) -> Result<Vec<AccountSignature>> {
+        let mut signatures = Vec::new();
+        let mut before = None;
+
+        while signatures.len() < limit {
+            let page = self
+                .provider
+                .get_signatures_for_address(address, before, self.config.batch_size)
+                .await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(last.signature);
+            let exhausted = page.len() < self.config.batch_size;
+            let passed_min_slot = self.config.min_slot.is_some_and(|min| last.slot < min);
+
+            signatures.extend(page.into_iter().filter(|sig| self.wanted(sig)));
+            if exhausted || passed_min_slot {
+                break;
+            }
+        }
+
+        signatures.truncate(limit);
+        Ok(signatures)
+    }
+
+    /// Checks whether a transaction passes the slot and failure filters.
+    fn wanted(&self, signature: &AccountSignature) -> bool {
+        (self.config.include_failed || !signature.failed)
+            && self.config.min_slot.is_none_or(|min| signature.slot >= min)
+            && self.config.max_slot.is_none_or(|max| signature.slot <= max)
     }
 
-    /// Parses a transaction for events.
-    async fn parse_transaction(&self, _signature: &Signature) -> Result<Vec<ProtocolEvent>> {
-        // TODO: Implement transaction parsing
-        // 1. Fetch transaction details
-        // 2. Parse instruction data
-        // 3. Extract events from logs
-        Ok(vec![])
+    /// Parses a transaction's logs for events.
+    async fn parse_transaction(&self, signature: &AccountSignature) -> Result<Vec<ProtocolEvent>> {
+        let logs = self
+            .provider
+            .get_transaction_logs(&signature.signature)
+            .await?;
+        let timestamp = signature
+            .block_time
+            .and_then(|t| u64::try_from(t).ok())
+            .unwrap_or(0);
+        self.parser.parse_logs(
+            &logs,
+            &signature.signature.to_string(),
+            signature.slot,
+            timestamp,
+        )
     }
 }
 
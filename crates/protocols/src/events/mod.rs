@@ -3,10 +3,12 @@
 //! This module provides functionality to fetch and parse on-chain events
 //! from CLMM protocol transactions.
 
+#[cfg(feature = "rpc")]
 mod fetcher;
 mod parser;
 mod types;
 
+#[cfg(feature = "rpc")]
 pub use fetcher::*;
 pub use parser::*;
 pub use types::*;
@@ -2,8 +2,17 @@
 
 use super::{CollectFeesEvent, LiquidityEvent, ProtocolEvent, SwapEvent};
 use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use solana_sdk::pubkey::Pubkey;
 use tracing::debug;
 
+/// Anchor discriminator of the Whirlpool `Traded` event.
+const TRADED_DISCRIMINATOR: [u8; 8] = [225, 202, 73, 175, 147, 43, 160, 150];
+
+/// Length of an encoded Whirlpool `Traded` event.
+const TRADED_LEN: usize = 121;
+
 /// Protocol type for parsing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Protocol {
@@ -63,6 +72,15 @@ impl EventParser {
         let mut events = Vec::new();
 
         for log in logs {
+            // Swaps are reported by Anchor events, emitted as base64 program data
+            if let Some(data) = log.strip_prefix("Program data:") {
+                if let Some(event) = parse_whirlpool_traded(data.trim(), signature, slot, timestamp)
+                {
+                    events.push(ProtocolEvent::Swap(event));
+                }
+                continue;
+            }
+
             // Whirlpool program logs start with "Program log:"
             if !log.starts_with("Program log:") {
                 continue;
@@ -71,12 +89,7 @@ impl EventParser {
             let log_data = log.trim_start_matches("Program log:").trim();
 
             // Parse different event types based on log content
-            if log_data.contains("Swap") {
-                if let Some(event) = self.parse_whirlpool_swap(log_data, signature, slot, timestamp)
-                {
-                    events.push(ProtocolEvent::Swap(event));
-                }
-            } else if log_data.contains("IncreaseLiquidity") {
+            if log_data.contains("IncreaseLiquidity") {
                 if let Some(event) =
                     self.parse_whirlpool_liquidity(log_data, signature, slot, timestamp, true)
                 {
@@ -105,30 +118,6 @@ impl EventParser {
         Ok(events)
     }
 
-    /// Parses a Whirlpool swap log.
-    fn parse_whirlpool_swap(
-        &self,
-        _log_data: &str,
-        signature: &str,
-        slot: u64,
-        timestamp: u64,
-    ) -> Option<SwapEvent> {
-        // TODO: Implement actual parsing based on Whirlpool log format
-        // For now, return a placeholder
-        Some(SwapEvent {
-            signature: signature.to_string(),
-            pool: String::new(),
-            timestamp,
-            slot,
-            amount_a: 0,
-            amount_b: 0,
-            is_buy: true,
-            sqrt_price_after: 0,
-            tick_after: 0,
-            fee_amount: 0,
-        })
-    }
-
     /// Parses a Whirlpool liquidity log.
     fn parse_whirlpool_liquidity(
         &self,
@@ -196,6 +185,58 @@ impl EventParser {
     }
 }
 
+/// Parses a base64 Whirlpool `Traded` event into a swap.
+///
+/// Returns `None` for other events.
+fn parse_whirlpool_traded(
+    data: &str,
+    signature: &str,
+    slot: u64,
+    timestamp: u64,
+) -> Option<SwapEvent> {
+    let bytes = STANDARD.decode(data).ok()?;
+    if bytes.len() < TRADED_LEN || bytes[..8] != TRADED_DISCRIMINATOR {
+        return None;
+    }
+
+    let u64_at = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+    let u128_at =
+        |offset: usize| u128::from_le_bytes(bytes[offset..offset + 16].try_into().unwrap());
+    let pool = Pubkey::new_from_array(bytes[8..40].try_into().ok()?);
+    let a_to_b = bytes[40] != 0;
+    let sqrt_price_after = u128_at(57);
+    let input_amount = u64_at(73);
+    let output_amount = u64_at(81);
+    let (amount_a, amount_b) = if a_to_b {
+        (input_amount, output_amount)
+    } else {
+        (output_amount, input_amount)
+    };
+
+    Some(SwapEvent {
+        signature: signature.to_string(),
+        pool: pool.to_string(),
+        timestamp,
+        slot,
+        amount_a,
+        amount_b,
+        is_buy: a_to_b,
+        sqrt_price_after,
+        tick_after: sqrt_price_to_tick(sqrt_price_after),
+        // LP and protocol fees
+        fee_amount: u64_at(105).saturating_add(u64_at(113)),
+    })
+}
+
+/// Converts a Q64.64 sqrt price to the tick at or below it.
+fn sqrt_price_to_tick(sqrt_price: u128) -> i32 {
+    let sqrt = sqrt_price as f64 / (1u128 << 64) as f64;
+    if sqrt <= 0.0 {
+        return 0;
+    }
+    (2.0 * sqrt.ln() / 1.0001_f64.ln()).floor() as i32
+}
+
 /// Parses instruction data for Whirlpool operations.
 pub fn parse_whirlpool_instruction(_data: &[u8]) -> Option<WhirlpoolInstruction> {
     // Whirlpool uses Anchor, so first 8 bytes are discriminator
@@ -256,4 +297,45 @@ mod tests {
         let events = parser.parse_logs(&[], "sig123", 100, 1234567890).unwrap();
         assert!(events.is_empty());
     }
+
+    #[test]
+    fn test_parse_whirlpool_traded() {
+        let pool = Pubkey::new_unique();
+        let mut data = TRADED_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(pool.as_ref());
+        data.push(0); // B to A
+        data.extend_from_slice(&(1u128 << 64).to_le_bytes());
+        data.extend_from_slice(&(1u128 << 64).to_le_bytes());
+        for amount in [2_000_000u64, 1_000_000, 0, 0, 5_000, 600] {
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        let logs = vec![
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [1]".to_string(),
+            "Program log: Instruction: Swap".to_string(),
+            format!("Program data: {}", STANDARD.encode(&data)),
+        ];
+
+        let parser = EventParser::new(Protocol::OrcaWhirlpool);
+        let events = parser.parse_logs(&logs, "sig123", 100, 1234567890).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let ProtocolEvent::Swap(swap) = &events[0] else {
+            panic!("expected a swap");
+        };
+        assert_eq!(swap.pool, pool.to_string());
+        assert_eq!(swap.amount_a, 1_000_000);
+        assert_eq!(swap.amount_b, 2_000_000);
+        assert!(!swap.is_buy);
+        assert_eq!(swap.tick_after, 0);
+        assert_eq!(swap.fee_amount, 5_600);
+    }
+
+    #[test]
+    fn test_sqrt_price_to_tick() {
+        assert_eq!(sqrt_price_to_tick(1u128 << 64), 0);
+        assert_eq!(sqrt_price_to_tick(0), 0);
+        // sqrt(1.0001^100) in Q64.64
+        let sqrt_price = (1.0001_f64.powi(50) * (1u128 << 64) as f64) as u128;
+        assert!((99..=100).contains(&sqrt_price_to_tick(sqrt_price)));
+    }
 }
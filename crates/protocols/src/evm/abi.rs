@@ -0,0 +1,256 @@
+//! Minimal Solidity ABI encoding for read-only contract calls.
+//!
+//! The adapters only call view functions with static arguments, so calls
+//! are a selector followed by 32-byte words. Return data is decoded word by
+//! word, plus the dynamic `string` returned by ERC-20 `symbol()`.
+
+use crate::error::ProtocolError;
+use anyhow::{Result, bail};
+use primitive_types::U256;
+
+/// A 32-byte ABI word.
+pub type Word = [u8; 32];
+
+/// Encodes a call to a function taking static arguments.
+#[must_use]
+pub fn encode_call(selector: [u8; 4], args: &[Word]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(4 + 32 * args.len());
+    data.extend_from_slice(&selector);
+    for arg in args {
+        data.extend_from_slice(arg);
+    }
+    data
+}
+
+/// Encodes an address argument.
+///
+/// # Errors
+/// Returns [`ProtocolError::InvalidAddress`] if the address is not 20 bytes
+/// of hex.
+pub fn address_word(address: &str) -> Result<Word> {
+    let bytes = parse_address(address)?;
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(&bytes);
+    Ok(word)
+}
+
+/// Encodes an unsigned integer argument.
+#[must_use]
+pub fn uint_word(value: U256) -> Word {
+    value.to_big_endian()
+}
+
+/// Normalizes an address to lowercase `0x`-prefixed hex.
+///
+/// # Errors
+/// Returns [`ProtocolError::InvalidAddress`] if the address is not 20 bytes
+/// of hex.
+pub fn normalize_address(address: &str) -> Result<String> {
+    Ok(format!("0x{}", hex::encode(parse_address(address)?)))
+}
+
+/// Parses a `0x`-prefixed or bare hex address.
+fn parse_address(address: &str) -> Result<[u8; 20]> {
+    let digits = address.strip_prefix("0x").unwrap_or(address);
+    let mut bytes = [0u8; 20];
+    hex::decode_to_slice(digits, &mut bytes)
+        .map_err(|_| ProtocolError::InvalidAddress(address.to_string()))?;
+    Ok(bytes)
+}
+
+/// Decodes the return data of a contract call.
+#[derive(Debug, Clone, Copy)]
+pub struct AbiDecoder<'a> {
+    /// Raw return data.
+    data: &'a [u8],
+}
+
+impl<'a> AbiDecoder<'a> {
+    /// Creates a decoder over return data.
+    #[must_use]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Gets the number of complete words.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len() / 32
+    }
+
+    /// Returns true if the data holds no complete word.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the word at an index.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short.
+    pub fn word(&self, index: usize) -> Result<&'a [u8]> {
+        let start = index * 32;
+        match self.data.get(start..start + 32) {
+            Some(word) => Ok(word),
+            None => bail!(
+                "Return data has {} bytes, expected at least {}",
+                self.data.len(),
+                start + 32
+            ),
+        }
+    }
+
+    /// Decodes an unsigned integer of any width.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short.
+    pub fn uint(&self, index: usize) -> Result<U256> {
+        Ok(U256::from_big_endian(self.word(index)?))
+    }
+
+    /// Decodes an unsigned integer of at most 128 bits.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short or the value overflows.
+    pub fn u128(&self, index: usize) -> Result<u128> {
+        let value = self.uint(index)?;
+        if value.bits() > 128 {
+            bail!("Word {index} does not fit in 128 bits");
+        }
+        Ok(value.as_u128())
+    }
+
+    /// Decodes an unsigned integer of at most 32 bits, such as a `uint24`.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short or the value overflows.
+    pub fn u32(&self, index: usize) -> Result<u32> {
+        u32::try_from(self.u128(index)?)
+            .map_err(|_| anyhow::anyhow!("Word {index} does not fit in 32 bits"))
+    }
+
+    /// Decodes a signed integer of at most 32 bits, such as an `int24` tick.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short or the value is not a
+    /// sign-extended 32-bit integer.
+    pub fn i32(&self, index: usize) -> Result<i32> {
+        let word = self.word(index)?;
+        let value = i32::from_be_bytes([word[28], word[29], word[30], word[31]]);
+        let fill = if value < 0 { 0xff } else { 0x00 };
+        if word[..28].iter().any(|&b| b != fill) {
+            bail!("Word {index} does not fit in 32 bits");
+        }
+        Ok(value)
+    }
+
+    /// Decodes an address as lowercase `0x`-prefixed hex.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short or the word has dirty high
+    /// bytes.
+    pub fn address(&self, index: usize) -> Result<String> {
+        let word = self.word(index)?;
+        if word[..12].iter().any(|&b| b != 0) {
+            bail!("Word {index} is not an address");
+        }
+        Ok(format!("0x{}", hex::encode(&word[12..])))
+    }
+
+    /// Decodes a boolean.
+    ///
+    /// # Errors
+    /// Returns an error if the data is too short.
+    pub fn bool(&self, index: usize) -> Result<bool> {
+        Ok(!self.uint(index)?.is_zero())
+    }
+
+    /// Decodes a string returned as the only value.
+    ///
+    /// Some older tokens return `bytes32` instead of `string`; those are
+    /// decoded with trailing zero bytes removed.
+    ///
+    /// # Errors
+    /// Returns an error if the data is malformed.
+    pub fn string(&self) -> Result<String> {
+        let bytes = if self.data.len() == 32 {
+            let word = self.word(0)?;
+            let end = word.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+            &word[..end]
+        } else {
+            let offset = self.offset(0)?;
+            let len = self.offset(offset / 32)?;
+            match self.data.get(offset + 32..offset + 32 + len) {
+                Some(bytes) => bytes,
+                None => bail!("String length {len} exceeds return data"),
+            }
+        };
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Decodes an offset or length word.
+    fn offset(&self, index: usize) -> Result<usize> {
+        usize::try_from(self.u128(index)?)
+            .ok()
+            .filter(|&n| n <= self.data.len())
+            .ok_or_else(|| anyhow::anyhow!("Word {index} is not a valid offset"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_call() {
+        let data = encode_call(
+            [0x70, 0xa0, 0x82, 0x31],
+            &[address_word("0xC36442b4a4522E871399CD717aBDD847Ab11FE88").unwrap()],
+        );
+        assert_eq!(data.len(), 36);
+        assert_eq!(
+            hex::encode(&data),
+            "70a08231000000000000000000000000c36442b4a4522e871399cd717abdd847ab11fe88"
+        );
+        assert!(matches!(
+            address_word("0x1234").unwrap_err().downcast_ref(),
+            Some(ProtocolError::InvalidAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_words() {
+        let mut data = vec![0u8; 96];
+        data[31] = 1;
+        data[32..64].fill(0xff);
+        data[60..64].copy_from_slice(&(-887_272i32).to_be_bytes());
+        data[95] = 0xaa;
+        let decoder = AbiDecoder::new(&data);
+
+        assert_eq!(decoder.len(), 3);
+        assert!(decoder.bool(0).unwrap());
+        assert_eq!(decoder.i32(1).unwrap(), -887_272);
+        assert_eq!(decoder.u32(2).unwrap(), 0xaa);
+        assert_eq!(
+            decoder.address(2).unwrap(),
+            "0x00000000000000000000000000000000000000aa"
+        );
+        assert!(decoder.u32(1).is_err());
+        assert!(decoder.word(3).is_err());
+    }
+
+    #[test]
+    fn test_decode_string() {
+        // abi.encode("USDC")
+        let mut data = vec![0u8; 96];
+        data[31] = 0x20;
+        data[63] = 4;
+        data[64..68].copy_from_slice(b"USDC");
+        assert_eq!(AbiDecoder::new(&data).string().unwrap(), "USDC");
+
+        // bytes32("MKR")
+        let mut data = vec![0u8; 32];
+        data[..3].copy_from_slice(b"MKR");
+        assert_eq!(AbiDecoder::new(&data).string().unwrap(), "MKR");
+    }
+}
@@ -0,0 +1,10 @@
+//! EVM chain access.
+//!
+//! ABI encoding is always built; the JSON-RPC client is behind the `evm`
+//! feature.
+
+/// Solidity ABI encoding and decoding.
+pub mod abi;
+/// JSON-RPC client.
+#[cfg(feature = "evm")]
+pub mod rpc;
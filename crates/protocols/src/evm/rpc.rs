@@ -0,0 +1,133 @@
+//! Minimal EVM JSON-RPC client for read-only contract calls.
+
+use crate::error::ProtocolError;
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::debug;
+
+/// JSON-RPC client for an EVM node.
+pub struct EvmRpcClient {
+    /// HTTP client.
+    http: reqwest::Client,
+    /// Node URL.
+    url: String,
+    /// Next request ID.
+    next_id: AtomicU64,
+}
+
+impl EvmRpcClient {
+    /// Creates a client for a node URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Gets the node URL.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Gets the chain ID reported by the node.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn chain_id(&self) -> Result<u64> {
+        let result = self.request("eth_chainId", json!([])).await?;
+        parse_quantity(&result)
+    }
+
+    /// Gets the latest block number.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn block_number(&self) -> Result<u64> {
+        let result = self.request("eth_blockNumber", json!([])).await?;
+        parse_quantity(&result)
+    }
+
+    /// Calls a view function at the latest block and returns its raw output.
+    ///
+    /// # Errors
+    /// Returns [`ProtocolError::ContractCall`] if the call reverts, or an
+    /// error if the node is unreachable.
+    pub async fn call(&self, contract: &str, data: &[u8]) -> Result<Vec<u8>> {
+        let params = json!([
+            { "to": contract, "data": format!("0x{}", hex::encode(data)) },
+            "latest"
+        ]);
+        let result =
+            self.request("eth_call", params)
+                .await
+                .map_err(|e| match ProtocolError::find(&e) {
+                    Some(ProtocolError::RpcUnavailable { .. }) => e,
+                    _ => ProtocolError::ContractCall {
+                        contract: contract.to_string(),
+                        message: e.to_string(),
+                    }
+                    .into(),
+                })?;
+
+        let output = result.as_str().unwrap_or_default();
+        hex::decode(output.strip_prefix("0x").unwrap_or(output)).map_err(|e| {
+            ProtocolError::ContractCall {
+                contract: contract.to_string(),
+                message: format!("Invalid return data: {e}"),
+            }
+            .into()
+        })
+    }
+
+    /// Sends a JSON-RPC request and returns its result.
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        debug!(method, id, "Sending EVM RPC request");
+
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .context(ProtocolError::RpcUnavailable { attempts: 1 })?
+            .json()
+            .await
+            .context(ProtocolError::RpcUnavailable { attempts: 1 })?;
+
+        if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("{method} failed: {message}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .with_context(|| format!("{method} returned no result"))
+    }
+}
+
+/// Parses a hex quantity such as `0xa4b1`.
+fn parse_quantity(value: &Value) -> Result<u64> {
+    let text = value.as_str().context("Quantity is not a string")?;
+    u64::from_str_radix(text.trim_start_matches("0x"), 16)
+        .with_context(|| format!("Invalid quantity {text}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity(&json!("0xa4b1")).unwrap(), 42_161);
+        assert!(parse_quantity(&json!(1)).is_err());
+        assert!(parse_quantity(&json!("0xzz")).is_err());
+    }
+}
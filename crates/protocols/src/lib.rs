@@ -4,12 +4,41 @@
 //! - Orca Whirlpools
 //! - Raydium CLMM
 //! - Meteora DLMM (planned)
+//!
+//! - Uniswap v3 on Ethereum and its L2s
+//!
+//! It also provides a Drift perpetuals adapter for hedging.
+//!
+//! Pool and position readers implement [`chain::ClmmAdapter`], which exposes
+//! their state in a chain-agnostic form. Solana CLMMs also implement
+//! `adapter::ProtocolAdapter`, which reads pools and positions and builds
+//! position instructions for any program found in the
+//! `adapter::ProtocolRegistry`.
+//!
+//! RPC access, on-chain readers and executors are behind the default `rpc`
+//! feature, and the EVM JSON-RPC client and Uniswap v3 adapter behind the
+//! default `evm` feature. Without them only account layouts, ABI decoding,
+//! parsers and events are built.
 
 /// Prelude module for convenient imports.
 pub mod prelude;
 
+/// Protocol-agnostic Solana CLMM adapters and their registry.
+#[cfg(feature = "rpc")]
+pub mod adapter;
+/// Chain abstraction for CLMM adapters.
+pub mod chain;
+/// Wallet position discovery.
+#[cfg(feature = "rpc")]
+pub mod discovery;
+/// Drift perpetuals adapter.
+pub mod drift;
+/// Error types for protocol adapters.
+pub mod error;
 /// Event fetching and parsing.
 pub mod events;
+/// EVM chain access.
+pub mod evm;
 /// Orca protocol adapter.
 pub mod orca;
 /// Data parsers.
@@ -17,9 +46,13 @@ pub mod parsers;
 /// Raydium protocol adapter.
 pub mod raydium;
 /// RPC provider with health checks and fallback.
+#[cfg(feature = "rpc")]
 pub mod rpc;
 /// Solana client wrapper.
+#[cfg(feature = "rpc")]
 pub mod solana_client;
+/// Uniswap v3 protocol adapter.
+pub mod uniswap_v3;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -0,0 +1,523 @@
+//! Adapters over the Whirlpool readers.
+//!
+//! [`OrcaAdapter`] exposes Whirlpool state through the chain-agnostic
+//! [`ClmmAdapter`] and manages positions through [`ProtocolAdapter`].
+
+use super::executor::{ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use super::pool_reader::{WHIRLPOOL_PROGRAM_ID, WhirlpoolReader, sqrt_price_to_price};
+use super::position_reader::{self, PositionReader, WhirlpoolPosition};
+use super::whirlpool::Whirlpool;
+use crate::adapter::{ProtocolAdapter, ProtocolPoolState, ProtocolPosition};
+use crate::chain::{Chain, ClmmAdapter, ClmmPoolState, ClmmPositionState};
+use crate::error::ProtocolError;
+use crate::events::OnChainPosition;
+use crate::rpc::RpcProvider;
+use anyhow::Result;
+use async_trait::async_trait;
+use borsh::BorshDeserialize;
+use clmm_lp_domain::enums::Protocol;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Number of ticks in a Whirlpool tick array.
+const TICK_ARRAY_SIZE: i32 = 88;
+
+/// Anchor discriminators of the Whirlpool instructions.
+const OPEN_POSITION: [u8; 8] = [0x87, 0x80, 0x2f, 0x4d, 0x0f, 0x98, 0xf0, 0x31];
+const INCREASE_LIQUIDITY: [u8; 8] = [0x2e, 0x9c, 0xf3, 0x76, 0x0d, 0xcd, 0xfb, 0xb2];
+const DECREASE_LIQUIDITY: [u8; 8] = [0xa0, 0x26, 0xd0, 0x6f, 0x68, 0x5b, 0x2c, 0x01];
+const COLLECT_FEES: [u8; 8] = [0xa4, 0x98, 0xcf, 0x63, 0x1e, 0xba, 0x13, 0xb6];
+const COLLECT_REWARD: [u8; 8] = [0x46, 0x05, 0x84, 0x57, 0x56, 0xeb, 0xb1, 0x22];
+const CLOSE_POSITION: [u8; 8] = [0x7b, 0x86, 0x51, 0x00, 0x31, 0x44, 0x62, 0x62];
+
+/// Reads and manages Orca Whirlpool pools and positions.
+pub struct OrcaAdapter {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Pool reader.
+    pool_reader: WhirlpoolReader,
+    /// Position reader.
+    position_reader: PositionReader,
+    /// Whirlpool program ID.
+    program_id: Pubkey,
+    /// Token program ID.
+    token_program: Pubkey,
+    /// Associated token program ID.
+    ata_program: Pubkey,
+}
+
+impl OrcaAdapter {
+    /// Creates a new Orca adapter.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            pool_reader: WhirlpoolReader::new(provider.clone()),
+            position_reader: PositionReader::new(provider.clone()),
+            provider,
+            program_id: Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).expect("valid program id"),
+            token_program: Pubkey::from_str(TOKEN_PROGRAM_ID).expect("valid program id"),
+            ata_program: Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).expect("valid program id"),
+        }
+    }
+
+    /// Derives the associated token account of an owner for a mint.
+    fn associated_token_address(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[owner.as_ref(), self.token_program.as_ref(), mint.as_ref()],
+            &self.ata_program,
+        )
+        .0
+    }
+
+    /// Derives the tick array containing a tick.
+    fn tick_array_address(&self, pool: &Pubkey, tick: i32, tick_spacing: u16) -> Pubkey {
+        let ticks_per_array = i32::from(tick_spacing.max(1)) * TICK_ARRAY_SIZE;
+        let start_index = tick.div_euclid(ticks_per_array) * ticks_per_array;
+        Pubkey::find_program_address(
+            &[
+                b"tick_array",
+                pool.as_ref(),
+                start_index.to_string().as_bytes(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    /// Builds an increase or decrease liquidity instruction, which share
+    /// their accounts and argument layout.
+    #[allow(clippy::too_many_arguments)]
+    fn modify_liquidity_instruction(
+        &self,
+        discriminator: [u8; 8],
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_a: u64,
+        token_b: u64,
+    ) -> Instruction {
+        let mut data = Vec::with_capacity(40);
+        data.extend_from_slice(&discriminator);
+        data.extend_from_slice(&liquidity.to_le_bytes());
+        data.extend_from_slice(&token_a.to_le_bytes());
+        data.extend_from_slice(&token_b.to_le_bytes());
+
+        let on_chain = &position.on_chain;
+        let accounts = vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new_readonly(*owner, true), // position_authority
+            AccountMeta::new(on_chain.address, false),
+            AccountMeta::new_readonly(
+                self.associated_token_address(owner, &position.position_mint),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_a),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_b),
+                false,
+            ),
+            AccountMeta::new(pool.token_vault_a, false),
+            AccountMeta::new(pool.token_vault_b, false),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, on_chain.tick_lower, pool.tick_spacing),
+                false,
+            ),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, on_chain.tick_upper, pool.tick_spacing),
+                false,
+            ),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[async_trait]
+impl ClmmAdapter for OrcaAdapter {
+    fn chain(&self) -> Chain {
+        Chain::Solana
+    }
+
+    fn protocol(&self) -> Protocol {
+        Protocol::OrcaWhirlpools
+    }
+
+    async fn pool_state(&self, pool_address: &str) -> Result<ClmmPoolState> {
+        let state = self.pool_reader.get_pool_state(pool_address).await?;
+        Ok(ClmmPoolState {
+            chain: Chain::Solana,
+            protocol: Protocol::OrcaWhirlpools,
+            address: state.address,
+            token0: state.token_mint_a.to_string(),
+            token1: state.token_mint_b.to_string(),
+            tick_current: state.tick_current,
+            tick_spacing: i32::from(state.tick_spacing),
+            price: state.price,
+            liquidity: state.liquidity,
+            // Whirlpool fee rates are stored in hundredths of a basis point
+            fee_rate: u32::from(state.fee_rate_bps),
+        })
+    }
+
+    async fn position(&self, position_id: &str) -> Result<ClmmPositionState> {
+        let position = self.position_reader.get_position(position_id).await?;
+        Ok(to_position_state(&position))
+    }
+
+    async fn positions_by_owner(&self, owner: &str) -> Result<Vec<ClmmPositionState>> {
+        let positions = self.position_reader.get_positions_by_owner(owner).await?;
+        Ok(positions.iter().map(to_position_state).collect())
+    }
+}
+
+#[async_trait]
+impl ProtocolAdapter for OrcaAdapter {
+    fn protocol(&self) -> Protocol {
+        Protocol::OrcaWhirlpools
+    }
+
+    fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    async fn fetch_pool_state(&self, pool: &Pubkey) -> Result<ProtocolPoolState> {
+        let account = self.provider.get_account(pool).await?;
+        let whirlpool = Whirlpool::try_from_slice(&account.data).map_err(|e| {
+            ProtocolError::InvalidAccountData {
+                account: pool.to_string(),
+                message: e.to_string(),
+            }
+        })?;
+
+        Ok(ProtocolPoolState {
+            protocol: Protocol::OrcaWhirlpools,
+            address: *pool,
+            token_mint_a: whirlpool.token_mint_a,
+            token_mint_b: whirlpool.token_mint_b,
+            token_vault_a: whirlpool.token_vault_a,
+            token_vault_b: whirlpool.token_vault_b,
+            tick_current: whirlpool.tick_current_index,
+            tick_spacing: whirlpool.tick_spacing,
+            sqrt_price: whirlpool.sqrt_price,
+            price: sqrt_price_to_price(whirlpool.sqrt_price),
+            liquidity: whirlpool.liquidity,
+            fee_rate: u32::from(whirlpool.fee_rate),
+            fee_growth_global_a: whirlpool.fee_growth_global_a,
+            fee_growth_global_b: whirlpool.fee_growth_global_b,
+        })
+    }
+
+    async fn fetch_position(&self, position: &Pubkey) -> Result<ProtocolPosition> {
+        let account = self.provider.get_account(position).await?;
+        let parsed = WhirlpoolPosition::parse(&account.data).map_err(|e| {
+            ProtocolError::InvalidAccountData {
+                account: position.to_string(),
+                message: e.to_string(),
+            }
+        })?;
+
+        Ok(ProtocolPosition {
+            protocol: Protocol::OrcaWhirlpools,
+            position_mint: parsed.position_mint,
+            on_chain: parsed.to_on_chain(*position, Pubkey::default()),
+        })
+    }
+
+    fn position_address(&self, position_mint: &Pubkey) -> Pubkey {
+        position_reader::position_address(position_mint)
+    }
+
+    fn build_open_position_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        owner: &Pubkey,
+        position_mint: &Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<Instruction> {
+        let (position, position_bump) =
+            Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &self.program_id);
+
+        let mut data = Vec::with_capacity(17);
+        data.extend_from_slice(&OPEN_POSITION);
+        data.push(position_bump);
+        data.extend_from_slice(&tick_lower.to_le_bytes());
+        data.extend_from_slice(&tick_upper.to_le_bytes());
+
+        let accounts = vec![
+            AccountMeta::new(*owner, true),           // funder
+            AccountMeta::new_readonly(*owner, false), // owner
+            AccountMeta::new(position, false),
+            AccountMeta::new(*position_mint, true),
+            AccountMeta::new(self.associated_token_address(owner, position_mint), false),
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID)?, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(self.ata_program, false),
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    fn build_increase_liquidity_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<Instruction> {
+        Ok(self.modify_liquidity_instruction(
+            INCREASE_LIQUIDITY,
+            pool,
+            position,
+            owner,
+            liquidity,
+            token_max_a,
+            token_max_b,
+        ))
+    }
+
+    fn build_decrease_liquidity_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<Instruction> {
+        Ok(self.modify_liquidity_instruction(
+            DECREASE_LIQUIDITY,
+            pool,
+            position,
+            owner,
+            liquidity,
+            token_min_a,
+            token_min_b,
+        ))
+    }
+
+    fn build_collect_fees_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+    ) -> Result<Instruction> {
+        let accounts = vec![
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new_readonly(*owner, true), // position_authority
+            AccountMeta::new(position.on_chain.address, false),
+            AccountMeta::new_readonly(
+                self.associated_token_address(owner, &position.position_mint),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_a),
+                false,
+            ),
+            AccountMeta::new(pool.token_vault_a, false),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_b),
+                false,
+            ),
+            AccountMeta::new(pool.token_vault_b, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: COLLECT_FEES.to_vec(),
+        })
+    }
+
+    fn build_collect_reward_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        reward_index: u8,
+        reward_mint: &Pubkey,
+        reward_vault: &Pubkey,
+    ) -> Result<Instruction> {
+        let accounts = vec![
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new_readonly(*owner, true), // position_authority
+            AccountMeta::new(position.on_chain.address, false),
+            AccountMeta::new_readonly(
+                self.associated_token_address(owner, &position.position_mint),
+                false,
+            ),
+            AccountMeta::new(self.associated_token_address(owner, reward_mint), false),
+            AccountMeta::new(*reward_vault, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+
+        let mut data = COLLECT_REWARD.to_vec();
+        data.push(reward_index);
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    fn build_close_position_instruction(
+        &self,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+    ) -> Result<Instruction> {
+        let accounts = vec![
+            AccountMeta::new_readonly(*owner, true), // position_authority
+            AccountMeta::new(*owner, false),         // receiver
+            AccountMeta::new(position.on_chain.address, false),
+            AccountMeta::new(position.position_mint, false),
+            AccountMeta::new(
+                self.associated_token_address(owner, &position.position_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: CLOSE_POSITION.to_vec(),
+        })
+    }
+}
+
+/// Converts a Whirlpool position to the chain-agnostic state.
+fn to_position_state(position: &OnChainPosition) -> ClmmPositionState {
+    ClmmPositionState {
+        chain: Chain::Solana,
+        protocol: Protocol::OrcaWhirlpools,
+        id: position.address.to_string(),
+        pool: position.pool.to_string(),
+        // Single position reads leave the owner unset
+        owner: (position.owner != Pubkey::default()).then(|| position.owner.to_string()),
+        tick_lower: position.tick_lower,
+        tick_upper: position.tick_upper,
+        liquidity: position.liquidity,
+        fees_owed_0: u128::from(position.fees_owed_a),
+        fees_owed_1: u128::from(position.fees_owed_b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use solana_sdk::hash::hash;
+
+    fn pool() -> ProtocolPoolState {
+        ProtocolPoolState {
+            protocol: Protocol::OrcaWhirlpools,
+            address: Pubkey::new_unique(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 64,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_rate: 3000,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+        }
+    }
+
+    #[test]
+    fn test_discriminators() {
+        for (name, discriminator) in [
+            ("open_position", OPEN_POSITION),
+            ("increase_liquidity", INCREASE_LIQUIDITY),
+            ("decrease_liquidity", DECREASE_LIQUIDITY),
+            ("collect_fees", COLLECT_FEES),
+            ("collect_reward", COLLECT_REWARD),
+            ("close_position", CLOSE_POSITION),
+        ] {
+            let expected = hash(format!("global:{}", name).as_bytes());
+            assert_eq!(&expected.to_bytes()[..8], &discriminator, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_position_instructions() {
+        let adapter = OrcaAdapter::new(Arc::new(RpcProvider::localhost()));
+        let pool = pool();
+        let owner = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+
+        let open = adapter
+            .build_open_position_instruction(&pool, &owner, &position_mint, -128, 128)
+            .unwrap();
+        let position_address = adapter.position_address(&position_mint);
+        assert_eq!(open.accounts[2].pubkey, position_address);
+        assert!(open.accounts[3].is_signer);
+        assert_eq!(&open.data[9..13], &(-128i32).to_le_bytes());
+
+        let position = ProtocolPosition {
+            protocol: Protocol::OrcaWhirlpools,
+            position_mint,
+            on_chain: OnChainPosition {
+                address: position_address,
+                pool: pool.address,
+                owner,
+                tick_lower: -128,
+                tick_upper: 128,
+                liquidity: 1_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+            },
+        };
+        let increase = adapter
+            .build_increase_liquidity_instruction(&pool, &position, &owner, 1_000, 10, 20)
+            .unwrap();
+        assert_eq!(increase.accounts.len(), 11);
+        assert_eq!(increase.data.len(), 40);
+        // Ticks -128 and 128 lie in the tick arrays starting at -5632 and 0
+        assert_ne!(increase.accounts[9].pubkey, increase.accounts[10].pubkey);
+
+        let reward_mint = Pubkey::new_unique();
+        let reward = adapter
+            .build_collect_reward_instruction(
+                &pool,
+                &position,
+                &owner,
+                1,
+                &reward_mint,
+                &Pubkey::new_unique(),
+            )
+            .unwrap();
+        assert_eq!(reward.accounts.len(), 7);
+        assert_eq!(reward.data, [&COLLECT_REWARD[..], &[1]].concat());
+
+        let close = adapter
+            .build_close_position_instruction(&position, &owner)
+            .unwrap();
+        assert_eq!(close.accounts[3].pubkey, position_mint);
+    }
+}
@@ -6,6 +6,7 @@
 //! - Collect fees
 //! - Close positions
 
+use crate::events::OnChainPosition;
 use crate::rpc::RpcProvider;
 use anyhow::{Context, Result};
 use solana_sdk::{
@@ -31,6 +32,9 @@ pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25e
 /// System program ID.
 pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
 
+/// Number of ticks in a Whirlpool tick array.
+const TICK_ARRAY_SIZE: i32 = 88;
+
 /// Parameters for opening a new position.
 #[derive(Debug, Clone)]
 pub struct OpenPositionParams {
@@ -253,12 +257,65 @@ impl WhirlpoolExecutor {
         self.send_transaction(&[ix], payer).await
     }
 
+    /// Collects fees and the given rewards from a position in one transaction.
+    ///
+    /// The position's owed amounts are brought up to date first. Each reward
+    /// is its pool reward index with the reward mint and vault.
+    pub async fn collect_fees_and_rewards<S: Signer>(
+        &self,
+        position: &OnChainPosition,
+        tick_spacing: u16,
+        rewards: &[(u8, Pubkey, Pubkey)],
+        payer: &S,
+    ) -> Result<ExecutionResult> {
+        info!(
+            position = %position.address,
+            rewards = rewards.len(),
+            "Collecting fees and rewards"
+        );
+
+        let mut instructions = vec![
+            self.build_update_fees_and_rewards_instruction(position, tick_spacing),
+            self.build_collect_fees_instruction(
+                &position.address,
+                &position.pool,
+                &payer.pubkey(),
+            )?,
+        ];
+        for (index, mint, vault) in rewards {
+            instructions.push(self.build_collect_reward_instruction(
+                &position.address,
+                &position.pool,
+                &payer.pubkey(),
+                *index,
+                mint,
+                vault,
+            )?);
+        }
+
+        self.send_transaction(&instructions, payer).await
+    }
+
     /// Closes a position.
     pub async fn close_position<S: Signer>(
         &self,
         position: &Pubkey,
         pool: &Pubkey,
         payer: &S,
+    ) -> Result<ExecutionResult> {
+        self.close_position_with_minimums(position, pool, 0, 0, payer)
+            .await
+    }
+
+    /// Closes a position, failing if less than the minimum amounts would be
+    /// withdrawn.
+    pub async fn close_position_with_minimums<S: Signer>(
+        &self,
+        position: &Pubkey,
+        pool: &Pubkey,
+        token_min_a: u64,
+        token_min_b: u64,
+        payer: &S,
     ) -> Result<ExecutionResult> {
         info!(position = %position, "Closing position");
 
@@ -268,8 +325,8 @@ impl WhirlpoolExecutor {
             pool,
             &payer.pubkey(),
             u128::MAX, // All liquidity
-            0,         // Min token A
-            0,         // Min token B
+            token_min_a,
+            token_min_b,
         )?;
 
         // Collect any remaining fees
@@ -452,7 +509,7 @@ impl WhirlpoolExecutor {
         owner: &Pubkey,
     ) -> Result<Instruction> {
         // Whirlpool CollectFees instruction discriminator
-        let discriminator: [u8; 8] = [0xa4, 0x98, 0xcf, 0x63, 0x1e, 0xba, 0x13, 0x7a];
+        let discriminator: [u8; 8] = [0xa4, 0x98, 0xcf, 0x63, 0x1e, 0xba, 0x13, 0xb6];
 
         let data = discriminator.to_vec();
 
@@ -471,6 +528,82 @@ impl WhirlpoolExecutor {
         })
     }
 
+    fn build_update_fees_and_rewards_instruction(
+        &self,
+        position: &OnChainPosition,
+        tick_spacing: u16,
+    ) -> Instruction {
+        // Whirlpool UpdateFeesAndRewards instruction discriminator
+        let discriminator: [u8; 8] = [0x9a, 0xe6, 0xfa, 0x0d, 0xec, 0xd1, 0x4b, 0xdf];
+
+        let tick_array_lower =
+            self.derive_tick_array(&position.pool, position.tick_lower, tick_spacing);
+        let tick_array_upper =
+            self.derive_tick_array(&position.pool, position.tick_upper, tick_spacing);
+
+        let accounts = vec![
+            AccountMeta::new(position.pool, false),    // whirlpool
+            AccountMeta::new(position.address, false), // position
+            AccountMeta::new_readonly(tick_array_lower, false), // tick_array_lower
+            AccountMeta::new_readonly(tick_array_upper, false), // tick_array_upper
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: discriminator.to_vec(),
+        }
+    }
+
+    /// Derives the tick array containing a tick.
+    fn derive_tick_array(&self, pool: &Pubkey, tick: i32, tick_spacing: u16) -> Pubkey {
+        let ticks_per_array = i32::from(tick_spacing.max(1)) * TICK_ARRAY_SIZE;
+        let start_index = tick.div_euclid(ticks_per_array) * ticks_per_array;
+        let (tick_array, _bump) = Pubkey::find_program_address(
+            &[
+                b"tick_array",
+                pool.as_ref(),
+                start_index.to_string().as_bytes(),
+            ],
+            &self.program_id,
+        );
+        tick_array
+    }
+
+    fn build_collect_reward_instruction(
+        &self,
+        position: &Pubkey,
+        pool: &Pubkey,
+        owner: &Pubkey,
+        reward_index: u8,
+        reward_mint: &Pubkey,
+        reward_vault: &Pubkey,
+    ) -> Result<Instruction> {
+        // Whirlpool CollectReward instruction discriminator
+        let discriminator: [u8; 8] = [0x46, 0x05, 0x84, 0x57, 0x56, 0xeb, 0xb1, 0x22];
+
+        let mut data = discriminator.to_vec();
+        data.push(reward_index);
+
+        let reward_owner_account = self.derive_ata(owner, reward_mint)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*pool, false),       // whirlpool
+            AccountMeta::new_readonly(*owner, true),       // position_authority
+            AccountMeta::new(*position, false),            // position
+            AccountMeta::new(reward_owner_account, false), // reward_owner_account
+            AccountMeta::new(*reward_vault, false),        // reward_vault
+            AccountMeta::new_readonly(self.token_program, false), // token_program
+                                                           // position_token_account
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
     fn build_close_position_instruction(
         &self,
         position: &Pubkey,
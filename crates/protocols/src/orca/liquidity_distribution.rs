@@ -0,0 +1,382 @@
+//! Liquidity distribution of a Whirlpool across its ticks.
+//!
+//! Reads every initialized tick array of a pool and rebuilds the active
+//! liquidity at each tick from the ticks' net liquidity, so the fee share
+//! of a range can be estimated from the liquidity it actually competes
+//! with instead of a single pool-wide figure.
+
+use super::pool_reader::{WHIRLPOOL_PROGRAM_ID, tick_to_price};
+use super::whirlpool::Whirlpool;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use rust_decimal::Decimal;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, info, instrument};
+
+/// Number of ticks in a Whirlpool tick array.
+const TICK_ARRAY_SIZE: usize = 88;
+
+/// Size of a tick array account.
+const TICK_ARRAY_LEN: usize = 9988;
+
+/// Offset of the whirlpool address in a tick array account.
+const TICK_ARRAY_WHIRLPOOL_OFFSET: usize = 9956;
+
+/// A tick of a Whirlpool tick array.
+#[derive(BorshDeserialize, Debug, Clone, Copy)]
+pub struct Tick {
+    /// Whether any position uses the tick as a bound.
+    pub initialized: bool,
+    /// Liquidity added when the price crosses the tick upwards.
+    pub liquidity_net: i128,
+    /// Total liquidity referencing the tick.
+    pub liquidity_gross: u128,
+    /// Fee growth of token A on the other side of the tick (Q64.64).
+    pub fee_growth_outside_a: u128,
+    /// Fee growth of token B on the other side of the tick (Q64.64).
+    pub fee_growth_outside_b: u128,
+    /// Reward growths on the other side of the tick (Q64.64).
+    pub reward_growths_outside: [u128; 3],
+}
+
+/// A Whirlpool tick array account of 88 consecutive ticks.
+#[derive(BorshDeserialize, Debug, Clone)]
+pub struct TickArray {
+    /// Account discriminator.
+    pub discriminator: [u8; 8],
+    /// Index of the first tick in the array.
+    pub start_tick_index: i32,
+    /// The ticks, `tick_spacing` apart.
+    pub ticks: [Tick; TICK_ARRAY_SIZE],
+    /// The whirlpool the array belongs to.
+    pub whirlpool: Pubkey,
+}
+
+impl TickArray {
+    /// Parses a tick array account.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::deserialize(&mut &data[..]).context("Failed to deserialize tick array")
+    }
+
+    /// Returns the index and net liquidity of the initialized ticks.
+    #[must_use]
+    pub fn initialized_ticks(&self, tick_spacing: u16) -> Vec<(i32, i128)> {
+        self.ticks
+            .iter()
+            .enumerate()
+            .filter(|(_, tick)| tick.initialized)
+            .map(|(i, tick)| {
+                (
+                    self.start_tick_index + i as i32 * i32::from(tick_spacing),
+                    tick.liquidity_net,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Active liquidity over a span of ticks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidityBucket {
+    /// First tick of the bucket.
+    pub tick_lower: i32,
+    /// Tick after the last tick of the bucket.
+    pub tick_upper: i32,
+    /// Active liquidity averaged over the bucket's ticks.
+    pub liquidity: u128,
+}
+
+/// Active liquidity of a pool per tick bucket.
+#[derive(Debug, Clone)]
+pub struct LiquidityProfile {
+    /// Current tick of the pool.
+    pub tick_current: i32,
+    /// Tick spacing of the pool.
+    pub tick_spacing: u16,
+    /// Width of each bucket in ticks.
+    pub bucket_ticks: i32,
+    /// Contiguous buckets in tick order, from the lowest to the highest
+    /// initialized tick; there is no liquidity outside them.
+    pub buckets: Vec<LiquidityBucket>,
+}
+
+impl LiquidityProfile {
+    /// Builds a profile from the index and net liquidity of a pool's
+    /// initialized ticks.
+    ///
+    /// `bucket_ticks` is rounded up to a multiple of the tick spacing. A
+    /// running liquidity below zero, from ticks missing in the input, is
+    /// treated as zero.
+    #[must_use]
+    pub fn from_ticks(
+        tick_current: i32,
+        tick_spacing: u16,
+        ticks: &[(i32, i128)],
+        bucket_ticks: i32,
+    ) -> Self {
+        let spacing = i32::from(tick_spacing.max(1));
+        let bucket_ticks = ((bucket_ticks.max(1) + spacing - 1) / spacing) * spacing;
+
+        let mut ticks = ticks.to_vec();
+        ticks.sort_by_key(|(index, _)| *index);
+
+        // Spans between consecutive initialized ticks with their liquidity
+        let mut segments = Vec::with_capacity(ticks.len());
+        let mut running: i128 = 0;
+        for pair in ticks.windows(2) {
+            running += pair[0].1;
+            if pair[1].0 > pair[0].0 {
+                segments.push((pair[0].0, pair[1].0, running.max(0) as u128));
+            }
+        }
+
+        let mut buckets = Vec::new();
+        if let (Some(first), Some(last)) = (segments.first(), segments.last()) {
+            let start = first.0.div_euclid(bucket_ticks) * bucket_ticks;
+            let end = last.1;
+            let mut segment = 0;
+            let mut lower = start;
+            while lower < end {
+                let upper = lower + bucket_ticks;
+                while segment < segments.len() && segments[segment].1 <= lower {
+                    segment += 1;
+                }
+                let mut weighted: u128 = 0;
+                for &(seg_lower, seg_upper, liquidity) in &segments[segment..] {
+                    if seg_lower >= upper {
+                        break;
+                    }
+                    let overlap = seg_upper.min(upper) - seg_lower.max(lower);
+                    weighted = weighted.saturating_add(liquidity.saturating_mul(overlap as u128));
+                }
+                buckets.push(LiquidityBucket {
+                    tick_lower: lower,
+                    tick_upper: upper,
+                    liquidity: weighted / bucket_ticks as u128,
+                });
+                lower = upper;
+            }
+        }
+
+        Self {
+            tick_current,
+            tick_spacing,
+            bucket_ticks,
+            buckets,
+        }
+    }
+
+    /// Returns the liquidity of the bucket containing a tick.
+    #[must_use]
+    pub fn liquidity_at_tick(&self, tick: i32) -> u128 {
+        let index = self.buckets.partition_point(|b| b.tick_upper <= tick);
+        self.buckets
+            .get(index)
+            .filter(|b| b.tick_lower <= tick)
+            .map_or(0, |b| b.liquidity)
+    }
+
+    /// Returns the liquidity at the current tick.
+    #[must_use]
+    pub fn active_liquidity(&self) -> u128 {
+        self.liquidity_at_tick(self.tick_current)
+    }
+
+    /// Returns the liquidity averaged over the ticks of a range.
+    ///
+    /// This is the liquidity a position in the range shares fees with while
+    /// the price is spread evenly across it.
+    #[must_use]
+    pub fn average_liquidity(&self, tick_lower: i32, tick_upper: i32) -> u128 {
+        if tick_upper <= tick_lower {
+            return self.liquidity_at_tick(tick_lower);
+        }
+        let weighted = self
+            .buckets
+            .iter()
+            .filter(|b| b.tick_upper > tick_lower && b.tick_lower < tick_upper)
+            .fold(0u128, |sum, b| {
+                let overlap = b.tick_upper.min(tick_upper) - b.tick_lower.max(tick_lower);
+                sum.saturating_add(b.liquidity.saturating_mul(overlap as u128))
+            });
+        weighted / (tick_upper - tick_lower) as u128
+    }
+
+    /// Returns the share of fees a position with the given liquidity earns
+    /// in a range, against the liquidity already there.
+    #[must_use]
+    pub fn fee_share(&self, position_liquidity: u128, tick_lower: i32, tick_upper: i32) -> Decimal {
+        let total =
+            position_liquidity.saturating_add(self.average_liquidity(tick_lower, tick_upper));
+        if total == 0 {
+            return Decimal::ZERO;
+        }
+        // Scale down so both fit in a Decimal
+        let scale = (total >> 64).max(1);
+        Decimal::from(position_liquidity / scale) / Decimal::from(total / scale)
+    }
+
+    /// Returns the buckets as `(price_lower, price_upper, liquidity)` in raw
+    /// token units.
+    #[must_use]
+    pub fn price_buckets(&self) -> Vec<(Decimal, Decimal, u128)> {
+        self.buckets
+            .iter()
+            .map(|b| {
+                (
+                    tick_to_price(b.tick_lower),
+                    tick_to_price(b.tick_upper),
+                    b.liquidity,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Reads the liquidity distribution of Whirlpools from on-chain.
+///
+/// Only fixed-size tick arrays are read.
+pub struct LiquidityDistributionReader {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Whirlpool program ID.
+    program_id: Pubkey,
+}
+
+impl LiquidityDistributionReader {
+    /// Creates a new liquidity distribution reader.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            provider,
+            program_id: Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).expect("valid program id"),
+        }
+    }
+
+    /// Fetches all initialized tick arrays of a pool, ordered by start tick.
+    #[instrument(skip(self), fields(pool = %pool))]
+    pub async fn get_tick_arrays(&self, pool: &Pubkey) -> Result<Vec<TickArray>> {
+        let accounts = self
+            .provider
+            .get_program_accounts(
+                &self.program_id,
+                vec![
+                    RpcFilterType::DataSize(TICK_ARRAY_LEN as u64),
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                        TICK_ARRAY_WHIRLPOOL_OFFSET,
+                        pool.as_ref(),
+                    )),
+                ],
+            )
+            .await?;
+
+        let mut arrays = Vec::with_capacity(accounts.len());
+        for (address, account) in accounts {
+            match TickArray::parse(&account.data) {
+                Ok(array) => arrays.push(array),
+                Err(e) => {
+                    debug!(tick_array = %address, error = %e, "Skipping unreadable tick array")
+                }
+            }
+        }
+        arrays.sort_by_key(|array| array.start_tick_index);
+
+        debug!(count = arrays.len(), "Fetched tick arrays");
+        Ok(arrays)
+    }
+
+    /// Gets the liquidity profile of a pool with buckets of `bucket_ticks`
+    /// ticks.
+    pub async fn get_profile(
+        &self,
+        pool_address: &str,
+        bucket_ticks: i32,
+    ) -> Result<LiquidityProfile> {
+        let pool = Pubkey::from_str(pool_address).context("Invalid pool address")?;
+
+        info!(
+            pool = pool_address,
+            "Fetching Whirlpool liquidity distribution"
+        );
+
+        let account = self.provider.get_account(&pool).await?;
+        let whirlpool = Whirlpool::try_from_slice(&account.data)
+            .context("Failed to deserialize Whirlpool account")?;
+
+        let ticks: Vec<(i32, i128)> = self
+            .get_tick_arrays(&pool)
+            .await?
+            .iter()
+            .flat_map(|array| array.initialized_ticks(whirlpool.tick_spacing))
+            .collect();
+
+        Ok(LiquidityProfile::from_ticks(
+            whirlpool.tick_current_index,
+            whirlpool.tick_spacing,
+            &ticks,
+            bucket_ticks,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_array_layout() {
+        let mut data = vec![0u8; TICK_ARRAY_LEN];
+        data[8..12].copy_from_slice(&(-5632i32).to_le_bytes());
+        // Tick 2 (at -5632 + 2 * 64) is initialized with a net of 500
+        let tick = 12 + 2 * 113;
+        data[tick] = 1;
+        data[tick + 1..tick + 17].copy_from_slice(&500i128.to_le_bytes());
+        let pool = Pubkey::new_unique();
+        data[TICK_ARRAY_WHIRLPOOL_OFFSET..].copy_from_slice(pool.as_ref());
+
+        let array = TickArray::parse(&data).unwrap();
+        assert_eq!(array.whirlpool, pool);
+        assert_eq!(array.initialized_ticks(64), vec![(-5504, 500)]);
+    }
+
+    #[test]
+    fn test_profile_from_ticks() {
+        // Two positions: [-100, 100] with 1000 and [0, 200] with 500
+        let ticks = [(-100, 1000), (100, -1000), (0, 500), (200, -500)];
+        let profile = LiquidityProfile::from_ticks(50, 10, &ticks, 100);
+
+        assert_eq!(profile.buckets.len(), 3);
+        assert_eq!(profile.liquidity_at_tick(-50), 1000);
+        assert_eq!(profile.liquidity_at_tick(0), 1500);
+        assert_eq!(profile.active_liquidity(), 1500);
+        assert_eq!(profile.liquidity_at_tick(150), 500);
+        assert_eq!(profile.liquidity_at_tick(200), 0);
+        assert_eq!(profile.liquidity_at_tick(-101), 0);
+
+        assert_eq!(profile.average_liquidity(-100, 100), 1250);
+        assert_eq!(profile.average_liquidity(100, 300), 250);
+    }
+
+    #[test]
+    fn test_profile_buckets_average_partial_spans() {
+        let ticks = [(-64, 900), (64, -900)];
+        // Bucket width is rounded up to a multiple of the tick spacing
+        let profile = LiquidityProfile::from_ticks(0, 64, &ticks, 100);
+        assert_eq!(profile.bucket_ticks, 128);
+        assert_eq!(profile.buckets[0].tick_lower, -128);
+        assert_eq!(profile.liquidity_at_tick(-128), 450);
+        assert_eq!(profile.liquidity_at_tick(0), 450);
+    }
+
+    #[test]
+    fn test_fee_share() {
+        let profile = LiquidityProfile::from_ticks(0, 1, &[(-10, 3000), (10, -3000)], 10);
+        assert_eq!(profile.fee_share(1000, -10, 10), Decimal::new(25, 2));
+        // Outside the existing liquidity the position earns every fee
+        assert_eq!(profile.fee_share(1000, 20, 30), Decimal::ONE);
+        assert_eq!(profile.fee_share(0, 20, 30), Decimal::ZERO);
+    }
+}
@@ -5,14 +5,25 @@
 //! - Read position state
 //! - Execute LP operations
 //! - Calculate token amounts
+//! - Read the liquidity distribution across ticks
 
+/// Chain-agnostic and protocol adapters.
+#[cfg(feature = "rpc")]
+pub mod adapter;
 /// Executor for on-chain operations.
+#[cfg(feature = "rpc")]
 pub mod executor;
+/// Liquidity distribution across ticks.
+#[cfg(feature = "rpc")]
+pub mod liquidity_distribution;
 /// Pool reader for on-chain state.
+#[cfg(feature = "rpc")]
 pub mod pool_reader;
 /// Position reader for on-chain state.
+#[cfg(feature = "rpc")]
 pub mod position_reader;
 /// Orca pool provider.
+#[cfg(feature = "rpc")]
 pub mod provider;
 /// Orca whirlpool account structures.
 pub mod whirlpool;
@@ -11,7 +11,7 @@ use rust_decimal::prelude::FromPrimitive;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, instrument};
 
 /// Orca Whirlpool program ID.
 pub const WHIRLPOOL_PROGRAM_ID: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
@@ -35,6 +35,7 @@ impl WhirlpoolReader {
     ///
     /// # Returns
     /// The deserialized Whirlpool state
+    #[instrument(skip(self))]
     pub async fn get_pool_state(&self, pool_address: &str) -> Result<WhirlpoolState> {
         let pubkey = Pubkey::from_str(pool_address).context("Invalid pool address")?;
 
@@ -53,6 +54,13 @@ impl WhirlpoolReader {
         Ok(WhirlpoolState::from_whirlpool(&whirlpool, pool_address))
     }
 
+    /// Gets the full Whirlpool account, including its reward emissions.
+    pub async fn get_whirlpool(&self, pool_address: &str) -> Result<Whirlpool> {
+        let pubkey = Pubkey::from_str(pool_address).context("Invalid pool address")?;
+        let account = self.provider.get_account(&pubkey).await?;
+        Whirlpool::try_from_slice(&account.data).context("Failed to deserialize Whirlpool account")
+    }
+
     /// Gets the current price from a pool.
     pub async fn get_current_price(&self, pool_address: &str) -> Result<Decimal> {
         let state = self.get_pool_state(pool_address).await?;
@@ -158,7 +166,7 @@ impl WhirlpoolState {
 ///
 /// sqrt_price is stored as a Q64.64 fixed-point number.
 /// price = (sqrt_price / 2^64)^2
-fn sqrt_price_to_price(sqrt_price: u128) -> Decimal {
+pub(crate) fn sqrt_price_to_price(sqrt_price: u128) -> Decimal {
     // sqrt_price is Q64.64, so we need to divide by 2^64
     let sqrt_price_f64 = sqrt_price as f64 / (1u128 << 64) as f64;
     let price = sqrt_price_f64 * sqrt_price_f64;
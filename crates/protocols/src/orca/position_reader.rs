@@ -2,6 +2,8 @@
 //!
 //! Reads position state from on-chain accounts.
 
+use super::pool_reader::WHIRLPOOL_PROGRAM_ID;
+use crate::discovery::find_position_mints;
 use crate::events::OnChainPosition;
 use crate::rpc::RpcProvider;
 use anyhow::{Context, Result};
@@ -9,7 +11,7 @@ use borsh::BorshDeserialize;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, instrument};
 
 /// Whirlpool position account structure.
 #[derive(BorshDeserialize, Debug, Clone)]
@@ -34,7 +36,48 @@ pub struct WhirlpoolPosition {
     pub fee_growth_checkpoint_b: u128,
     /// Fee owed for token B.
     pub fee_owed_b: u64,
-    // Reward fields omitted for simplicity
+    /// Rewards accrued by the position, by pool reward index.
+    pub reward_infos: [PositionRewardInfo; 3],
+}
+
+/// A reward accrued by a Whirlpool position.
+#[derive(BorshDeserialize, Debug, Clone, Copy)]
+pub struct PositionRewardInfo {
+    /// Reward growth checkpoint (Q64.64).
+    pub growth_inside_checkpoint: u128,
+    /// Reward owed.
+    pub amount_owed: u64,
+}
+
+impl WhirlpoolPosition {
+    /// Parses a position account.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::deserialize(&mut &data[..]).context("Failed to deserialize position account")
+    }
+
+    /// Converts the account into an on-chain position.
+    #[must_use]
+    pub fn to_on_chain(&self, address: Pubkey, owner: Pubkey) -> OnChainPosition {
+        OnChainPosition {
+            address,
+            pool: self.whirlpool,
+            owner,
+            tick_lower: self.tick_lower_index,
+            tick_upper: self.tick_upper_index,
+            liquidity: self.liquidity,
+            fee_growth_inside_a: self.fee_growth_checkpoint_a,
+            fee_growth_inside_b: self.fee_growth_checkpoint_b,
+            fees_owed_a: self.fee_owed_a,
+            fees_owed_b: self.fee_owed_b,
+        }
+    }
+}
+
+/// Derives the position account of a position NFT mint.
+#[must_use]
+pub fn position_address(position_mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).expect("valid program id");
+    Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &program_id).0
 }
 
 /// Reads Orca Whirlpool positions from on-chain.
@@ -49,15 +92,22 @@ impl PositionReader {
         Self { provider }
     }
 
+    /// Gets the raw position account by its address.
+    pub async fn get_position_account(&self, position_address: &str) -> Result<WhirlpoolPosition> {
+        let pubkey = Pubkey::from_str(position_address).context("Invalid position address")?;
+        let account = self.provider.get_account(&pubkey).await?;
+        WhirlpoolPosition::parse(&account.data)
+    }
+
     /// Gets a position by its address.
+    #[instrument(skip(self))]
     pub async fn get_position(&self, position_address: &str) -> Result<OnChainPosition> {
         let pubkey = Pubkey::from_str(position_address).context("Invalid position address")?;
 
         info!(position = position_address, "Fetching position state");
 
         let account = self.provider.get_account(&pubkey).await?;
-        let position = WhirlpoolPosition::try_from_slice(&account.data)
-            .context("Failed to deserialize position account")?;
+        let position = WhirlpoolPosition::parse(&account.data)?;
 
         debug!(
             liquidity = %position.liquidity,
@@ -66,45 +116,61 @@ impl PositionReader {
             "Parsed position state"
         );
 
-        Ok(OnChainPosition {
-            address: pubkey,
-            pool: position.whirlpool,
-            owner: Pubkey::default(), // Owner needs to be fetched from token account
-            tick_lower: position.tick_lower_index,
-            tick_upper: position.tick_upper_index,
-            liquidity: position.liquidity,
-            fee_growth_inside_a: position.fee_growth_checkpoint_a,
-            fee_growth_inside_b: position.fee_growth_checkpoint_b,
-            fees_owed_a: position.fee_owed_a,
-            fees_owed_b: position.fee_owed_b,
-        })
+        // Owner needs to be fetched from token account
+        Ok(position.to_on_chain(pubkey, Pubkey::default()))
     }
 
     /// Gets all positions for a given owner.
     ///
-    /// This requires scanning token accounts for position NFTs.
+    /// Scans the owner's token accounts for position NFTs and reads the
+    /// position accounts derived from their mints.
     pub async fn get_positions_by_owner(&self, owner: &str) -> Result<Vec<OnChainPosition>> {
-        let _owner_pubkey = Pubkey::from_str(owner).context("Invalid owner address")?;
+        let owner_pubkey = Pubkey::from_str(owner).context("Invalid owner address")?;
 
         info!(owner = owner, "Fetching positions for owner");
 
-        // TODO: Implement by:
-        // 1. Get all token accounts for owner
-        // 2. Filter for position NFT mints
-        // 3. Derive position PDAs from mints
-        // 4. Fetch position accounts
+        let mints = find_position_mints(&self.provider, &owner_pubkey).await?;
+        self.get_positions_for_mints(&owner_pubkey, &mints).await
+    }
+
+    /// Gets the positions of the given NFT mints held by an owner.
+    ///
+    /// Mints that are not Whirlpool position NFTs are skipped.
+    pub async fn get_positions_for_mints(
+        &self,
+        owner: &Pubkey,
+        mints: &[Pubkey],
+    ) -> Result<Vec<OnChainPosition>> {
+        let program_id = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID)?;
+        let addresses: Vec<Pubkey> = mints.iter().map(position_address).collect();
+        let accounts = self.provider.get_multiple_accounts(&addresses).await?;
+
+        let mut positions = Vec::new();
+        for (address, account) in addresses.into_iter().zip(accounts) {
+            let Some(account) = account else { continue };
+            if account.owner != program_id {
+                continue;
+            }
+            match WhirlpoolPosition::parse(&account.data) {
+                Ok(position) => positions.push(position.to_on_chain(address, *owner)),
+                Err(e) => debug!(position = %address, error = %e, "Skipping unreadable position"),
+            }
+        }
 
-        Ok(vec![])
+        debug!(count = positions.len(), "Found Whirlpool positions");
+        Ok(positions)
     }
 
     /// Gets positions for a specific pool.
     pub async fn get_positions_for_pool(
         &self,
-        _pool_address: &str,
-        _owner: &str,
+        pool_address: &str,
+        owner: &str,
     ) -> Result<Vec<OnChainPosition>> {
-        // TODO: Implement by filtering owner positions by pool
-        Ok(vec![])
+        let pool = Pubkey::from_str(pool_address).context("Invalid pool address")?;
+        let mut positions = self.get_positions_by_owner(owner).await?;
+        positions.retain(|p| p.pool == pool);
+        Ok(positions)
     }
 
     /// Calculates the token amounts for a position.
@@ -46,11 +46,31 @@ pub struct Whirlpool {
     pub fee_growth_global_b: u128,
     /// The last updated timestamp for rewards.
     pub reward_last_updated_timestamp: u64,
-    // ... there are more fields (rewards, etc.)
-    // Borsh deserialization fails if struct doesn't match exact bytes.
-    // So we usually need the FULL struct or use a manual parser (unsafe pointer cast or byte slicing).
-    // For safety in Rust, using the Anchor deserializer is best if we have the IDL.
-    // Or we can skip bytes if we know offsets.
+    /// The reward emissions of the pool.
+    pub reward_infos: [WhirlpoolRewardInfo; 3],
+}
+
+/// A reward emitted by a Whirlpool.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy)]
+pub struct WhirlpoolRewardInfo {
+    /// The reward token mint; the default pubkey when uninitialized.
+    pub mint: Pubkey,
+    /// The vault holding the reward tokens.
+    pub vault: Pubkey,
+    /// The authority allowed to change the emissions.
+    pub authority: Pubkey,
+    /// Reward tokens emitted per second (Q64.64).
+    pub emissions_per_second_x64: u128,
+    /// Reward growth per unit of liquidity (Q64.64).
+    pub growth_global_x64: u128,
+}
+
+impl WhirlpoolRewardInfo {
+    /// Checks whether the reward slot is in use.
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
 }
 
 /// Helper for parsing Whirlpool data.
@@ -10,28 +10,90 @@
 
 // Traits
 pub use crate::PoolFetcher;
+#[cfg(feature = "rpc")]
+pub use crate::adapter::ProtocolAdapter;
+pub use crate::chain::ClmmAdapter;
+
+// Protocol adapters
+#[cfg(feature = "rpc")]
+pub use crate::adapter::{ProtocolPoolState, ProtocolPosition, ProtocolRegistry};
+
+// Chains
+pub use crate::chain::{Chain, ClmmPoolState, ClmmPositionState};
+
+// Errors
+pub use crate::error::{ProtocolError, ProtocolResult};
 
 // RPC provider
-pub use crate::rpc::{CommitmentLevel, EndpointHealth, HealthChecker, RpcConfig, RpcProvider};
+#[cfg(feature = "rpc")]
+pub use crate::rpc::{
+    AccountSignature, CommitmentLevel, EndpointHealth, EndpointProbe, HealthChecker,
+    PoolPriceUpdate, PoolSubscriber, PoolSubscription, RpcConfig, RpcProvider, SignatureStatus,
+    SubscriptionConfig, fill_slot_lag,
+};
 
 // Events
 pub use crate::events::{
-    ClosePositionEvent, CollectFeesEvent, EventFetcher, EventParser, FetchConfig, LiquidityEvent,
-    OnChainPosition, OpenPositionEvent, Protocol, ProtocolEvent, SwapEvent, VolumeData,
-    WhirlpoolInstruction,
+    ClosePositionEvent, CollectFeesEvent, EventParser, LiquidityEvent, OnChainPosition,
+    OpenPositionEvent, Protocol, ProtocolEvent, SwapEvent, VolumeData, WhirlpoolInstruction,
 };
+#[cfg(feature = "rpc")]
+pub use crate::events::{EventFetcher, FetchConfig};
+
+// Discovery
+#[cfg(feature = "rpc")]
+pub use crate::discovery::{
+    TokenBalance, WalletPosition, WalletPositionScanner, find_position_mints, find_token_balances,
+};
+
+// Drift
+pub use crate::drift::accounts::{PerpPosition, parse_perp_positions};
+#[cfg(feature = "rpc")]
+pub use crate::drift::executor::{DriftExecutor, PerpOrderParams, PositionDirection};
+
+// EVM
+pub use crate::evm::abi::AbiDecoder;
+#[cfg(feature = "evm")]
+pub use crate::evm::rpc::EvmRpcClient;
 
 // Orca
+#[cfg(feature = "rpc")]
+pub use crate::orca::adapter::OrcaAdapter;
+#[cfg(feature = "rpc")]
 pub use crate::orca::executor::{
     DecreaseLiquidityParams, ExecutionResult, IncreaseLiquidityParams, OpenPositionParams,
     WhirlpoolExecutor,
 };
+#[cfg(feature = "rpc")]
+pub use crate::orca::liquidity_distribution::{
+    LiquidityBucket, LiquidityDistributionReader, LiquidityProfile, Tick, TickArray,
+};
+#[cfg(feature = "rpc")]
 pub use crate::orca::pool_reader::{
     WhirlpoolReader, WhirlpoolState, calculate_tick_range, price_to_tick, tick_to_price,
 };
-pub use crate::orca::position_reader::{PositionReader, WhirlpoolPosition};
+#[cfg(feature = "rpc")]
+pub use crate::orca::position_reader::{PositionReader, PositionRewardInfo, WhirlpoolPosition};
+#[cfg(feature = "rpc")]
 pub use crate::orca::provider::OrcaPoolProvider;
-pub use crate::orca::whirlpool::{Whirlpool, WhirlpoolParser};
+pub use crate::orca::whirlpool::{Whirlpool, WhirlpoolParser, WhirlpoolRewardInfo};
+
+// Raydium
+pub use crate::raydium::accounts::{
+    AmmConfig, PersonalPosition, RAYDIUM_CLMM_PROGRAM_ID, RaydiumPool,
+};
+#[cfg(feature = "rpc")]
+pub use crate::raydium::adapter::RaydiumAdapter;
+#[cfg(feature = "rpc")]
+pub use crate::raydium::position_reader::RaydiumPositionReader;
 
 // Solana client
+#[cfg(feature = "rpc")]
 pub use crate::solana_client::SolanaRpcAdapter;
+
+// Uniswap v3
+#[cfg(feature = "evm")]
+pub use crate::uniswap_v3::adapter::UniswapV3Adapter;
+pub use crate::uniswap_v3::contracts::{
+    NftPosition, Slot0, UniswapV3Deployment, sqrt_price_x96_to_price,
+};
@@ -0,0 +1,121 @@
+//! Raydium CLMM account layouts.
+//!
+//! Only the leading fields needed to read positions and the current price
+//! are declared; the remaining bytes of each account are ignored.
+
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Raydium CLMM program ID.
+pub const RAYDIUM_CLMM_PROGRAM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+
+/// Raydium CLMM personal position account.
+#[derive(BorshDeserialize, Debug, Clone)]
+pub struct PersonalPosition {
+    /// Account discriminator.
+    pub discriminator: [u8; 8],
+    /// PDA bump.
+    pub bump: [u8; 1],
+    /// The position mint (NFT).
+    pub nft_mint: Pubkey,
+    /// The pool this position belongs to.
+    pub pool_id: Pubkey,
+    /// Lower tick index.
+    pub tick_lower_index: i32,
+    /// Upper tick index.
+    pub tick_upper_index: i32,
+    /// The liquidity in this position.
+    pub liquidity: u128,
+    /// Fee growth checkpoint for token 0 (Q64.64).
+    pub fee_growth_inside_0_last_x64: u128,
+    /// Fee growth checkpoint for token 1 (Q64.64).
+    pub fee_growth_inside_1_last_x64: u128,
+    /// Fees owed for token 0.
+    pub token_fees_owed_0: u64,
+    /// Fees owed for token 1.
+    pub token_fees_owed_1: u64,
+    // Reward fields omitted
+}
+
+impl PersonalPosition {
+    /// Parses a personal position account.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::deserialize(&mut &data[..]).context("Failed to deserialize Raydium position")
+    }
+}
+
+/// Leading fields of a Raydium CLMM pool account.
+#[derive(BorshDeserialize, Debug, Clone)]
+pub struct RaydiumPool {
+    /// Account discriminator.
+    pub discriminator: [u8; 8],
+    /// PDA bump.
+    pub bump: [u8; 1],
+    /// The AMM config account.
+    pub amm_config: Pubkey,
+    /// The pool creator.
+    pub owner: Pubkey,
+    /// The mint of token 0.
+    pub token_mint_0: Pubkey,
+    /// The mint of token 1.
+    pub token_mint_1: Pubkey,
+    /// The vault for token 0.
+    pub token_vault_0: Pubkey,
+    /// The vault for token 1.
+    pub token_vault_1: Pubkey,
+    /// The observation account.
+    pub observation_key: Pubkey,
+    /// Decimals of token 0.
+    pub mint_decimals_0: u8,
+    /// Decimals of token 1.
+    pub mint_decimals_1: u8,
+    /// The tick spacing.
+    pub tick_spacing: u16,
+    /// The liquidity at the current tick.
+    pub liquidity: u128,
+    /// The square root price (Q64.64).
+    pub sqrt_price_x64: u128,
+    /// The current tick index.
+    pub tick_current: i32,
+    /// Unused padding.
+    pub padding: [u16; 2],
+    /// Fee growth global for token 0 (Q64.64).
+    pub fee_growth_global_0_x64: u128,
+    /// Fee growth global for token 1 (Q64.64).
+    pub fee_growth_global_1_x64: u128,
+}
+
+impl RaydiumPool {
+    /// Parses the leading fields of a pool account.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::deserialize(&mut &data[..]).context("Failed to deserialize Raydium pool")
+    }
+}
+
+/// Leading fields of a Raydium CLMM AMM config account, which holds the fee
+/// rates shared by its pools.
+#[derive(BorshDeserialize, Debug, Clone)]
+pub struct AmmConfig {
+    /// Account discriminator.
+    pub discriminator: [u8; 8],
+    /// PDA bump.
+    pub bump: u8,
+    /// Config index.
+    pub index: u16,
+    /// The config owner.
+    pub owner: Pubkey,
+    /// Protocol share of trade fees, in hundredths of a basis point.
+    pub protocol_fee_rate: u32,
+    /// Trade fee rate in hundredths of a basis point (2500 = 0.25%).
+    pub trade_fee_rate: u32,
+    /// The tick spacing of pools using the config.
+    pub tick_spacing: u16,
+}
+
+impl AmmConfig {
+    /// Parses the leading fields of an AMM config account.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::deserialize(&mut &data[..]).context("Failed to deserialize Raydium AMM config")
+    }
+}
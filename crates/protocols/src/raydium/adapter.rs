@@ -0,0 +1,527 @@
+//! Raydium CLMM protocol adapter.
+//!
+//! Instructions use the original (v1) Raydium CLMM layouts, which take SPL
+//! Token accounts only; pool reward vaults are not passed, so collecting
+//! fees does not claim rewards.
+
+use super::accounts::{AmmConfig, PersonalPosition, RAYDIUM_CLMM_PROGRAM_ID, RaydiumPool};
+use super::position_reader;
+use crate::adapter::{ProtocolAdapter, ProtocolPoolState, ProtocolPosition};
+use crate::error::ProtocolError;
+use crate::events::OnChainPosition;
+use crate::orca::executor::{ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use crate::orca::pool_reader::sqrt_price_to_price;
+use crate::rpc::RpcProvider;
+use anyhow::Result;
+use async_trait::async_trait;
+use clmm_lp_domain::enums::Protocol;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Metaplex token metadata program ID.
+const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// Number of ticks in a Raydium CLMM tick array.
+const TICK_ARRAY_SIZE: i32 = 60;
+
+/// Anchor discriminators of the Raydium CLMM instructions. The instruction
+/// names match Whirlpool's, and so do the discriminators.
+const OPEN_POSITION: [u8; 8] = [0x87, 0x80, 0x2f, 0x4d, 0x0f, 0x98, 0xf0, 0x31];
+const INCREASE_LIQUIDITY: [u8; 8] = [0x2e, 0x9c, 0xf3, 0x76, 0x0d, 0xcd, 0xfb, 0xb2];
+const DECREASE_LIQUIDITY: [u8; 8] = [0xa0, 0x26, 0xd0, 0x6f, 0x68, 0x5b, 0x2c, 0x01];
+const CLOSE_POSITION: [u8; 8] = [0x7b, 0x86, 0x51, 0x00, 0x31, 0x44, 0x62, 0x62];
+
+/// Reads and manages Raydium CLMM pools and positions through
+/// [`ProtocolAdapter`].
+pub struct RaydiumAdapter {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Raydium CLMM program ID.
+    program_id: Pubkey,
+    /// Token program ID.
+    token_program: Pubkey,
+    /// Associated token program ID.
+    ata_program: Pubkey,
+}
+
+impl RaydiumAdapter {
+    /// Creates a new Raydium adapter.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            provider,
+            program_id: Pubkey::from_str(RAYDIUM_CLMM_PROGRAM_ID).expect("valid program id"),
+            token_program: Pubkey::from_str(TOKEN_PROGRAM_ID).expect("valid program id"),
+            ata_program: Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).expect("valid program id"),
+        }
+    }
+
+    /// Derives the associated token account of an owner for a mint.
+    fn associated_token_address(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[owner.as_ref(), self.token_program.as_ref(), mint.as_ref()],
+            &self.ata_program,
+        )
+        .0
+    }
+
+    /// Returns the start index of the tick array containing a tick.
+    fn tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+        let ticks_per_array = i32::from(tick_spacing.max(1)) * TICK_ARRAY_SIZE;
+        tick.div_euclid(ticks_per_array) * ticks_per_array
+    }
+
+    /// Derives the tick array containing a tick.
+    fn tick_array_address(&self, pool: &Pubkey, tick: i32, tick_spacing: u16) -> Pubkey {
+        let start_index = Self::tick_array_start_index(tick, tick_spacing);
+        Pubkey::find_program_address(
+            &[b"tick_array", pool.as_ref(), &start_index.to_be_bytes()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    /// Derives the pool-wide position account shared by all positions with
+    /// the same range.
+    fn protocol_position_address(&self, pool: &Pubkey, tick_lower: i32, tick_upper: i32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"position",
+                pool.as_ref(),
+                &tick_lower.to_be_bytes(),
+                &tick_upper.to_be_bytes(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    /// Builds a decrease liquidity instruction; with zero liquidity it only
+    /// collects the owed fees.
+    fn decrease_liquidity_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Instruction {
+        let mut data = Vec::with_capacity(40);
+        data.extend_from_slice(&DECREASE_LIQUIDITY);
+        data.extend_from_slice(&liquidity.to_le_bytes());
+        data.extend_from_slice(&token_min_a.to_le_bytes());
+        data.extend_from_slice(&token_min_b.to_le_bytes());
+
+        let on_chain = &position.on_chain;
+        let accounts = vec![
+            AccountMeta::new_readonly(*owner, true), // nft_owner
+            AccountMeta::new_readonly(
+                self.associated_token_address(owner, &position.position_mint),
+                false,
+            ),
+            AccountMeta::new(on_chain.address, false),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(
+                self.protocol_position_address(
+                    &pool.address,
+                    on_chain.tick_lower,
+                    on_chain.tick_upper,
+                ),
+                false,
+            ),
+            AccountMeta::new(pool.token_vault_a, false),
+            AccountMeta::new(pool.token_vault_b, false),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, on_chain.tick_lower, pool.tick_spacing),
+                false,
+            ),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, on_chain.tick_upper, pool.tick_spacing),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_a),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_b),
+                false,
+            ),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Reads and parses an account of the Raydium program.
+    async fn read_account<T>(
+        &self,
+        address: &Pubkey,
+        parse: impl FnOnce(&[u8]) -> Result<T>,
+    ) -> Result<T> {
+        let account = self.provider.get_account(address).await?;
+        if account.owner != self.program_id {
+            return Err(ProtocolError::InvalidAccountData {
+                account: address.to_string(),
+                message: format!("owned by {}, not the Raydium CLMM program", account.owner),
+            }
+            .into());
+        }
+        parse(&account.data).map_err(|e| {
+            ProtocolError::InvalidAccountData {
+                account: address.to_string(),
+                message: e.to_string(),
+            }
+            .into()
+        })
+    }
+}
+
+#[async_trait]
+impl ProtocolAdapter for RaydiumAdapter {
+    fn protocol(&self) -> Protocol {
+        Protocol::Raydium
+    }
+
+    fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    async fn fetch_pool_state(&self, pool: &Pubkey) -> Result<ProtocolPoolState> {
+        let state = self.read_account(pool, RaydiumPool::parse).await?;
+        let config = self
+            .read_account(&state.amm_config, AmmConfig::parse)
+            .await?;
+
+        Ok(ProtocolPoolState {
+            protocol: Protocol::Raydium,
+            address: *pool,
+            token_mint_a: state.token_mint_0,
+            token_mint_b: state.token_mint_1,
+            token_vault_a: state.token_vault_0,
+            token_vault_b: state.token_vault_1,
+            tick_current: state.tick_current,
+            tick_spacing: state.tick_spacing,
+            sqrt_price: state.sqrt_price_x64,
+            price: sqrt_price_to_price(state.sqrt_price_x64),
+            liquidity: state.liquidity,
+            fee_rate: config.trade_fee_rate,
+            fee_growth_global_a: state.fee_growth_global_0_x64,
+            fee_growth_global_b: state.fee_growth_global_1_x64,
+        })
+    }
+
+    async fn fetch_position(&self, position: &Pubkey) -> Result<ProtocolPosition> {
+        let parsed = self.read_account(position, PersonalPosition::parse).await?;
+
+        Ok(ProtocolPosition {
+            protocol: Protocol::Raydium,
+            position_mint: parsed.nft_mint,
+            on_chain: OnChainPosition {
+                address: *position,
+                pool: parsed.pool_id,
+                owner: Pubkey::default(),
+                tick_lower: parsed.tick_lower_index,
+                tick_upper: parsed.tick_upper_index,
+                liquidity: parsed.liquidity,
+                fee_growth_inside_a: parsed.fee_growth_inside_0_last_x64,
+                fee_growth_inside_b: parsed.fee_growth_inside_1_last_x64,
+                fees_owed_a: parsed.token_fees_owed_0,
+                fees_owed_b: parsed.token_fees_owed_1,
+            },
+        })
+    }
+
+    fn position_address(&self, position_mint: &Pubkey) -> Pubkey {
+        position_reader::position_address(position_mint)
+    }
+
+    fn build_open_position_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        owner: &Pubkey,
+        position_mint: &Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<Instruction> {
+        // Opened empty; liquidity is added with a separate increase
+        let mut data = Vec::with_capacity(56);
+        data.extend_from_slice(&OPEN_POSITION);
+        data.extend_from_slice(&tick_lower.to_le_bytes());
+        data.extend_from_slice(&tick_upper.to_le_bytes());
+        data.extend_from_slice(
+            &Self::tick_array_start_index(tick_lower, pool.tick_spacing).to_le_bytes(),
+        );
+        data.extend_from_slice(
+            &Self::tick_array_start_index(tick_upper, pool.tick_spacing).to_le_bytes(),
+        );
+        data.extend_from_slice(&0u128.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+
+        let metadata_program = Pubkey::from_str(METADATA_PROGRAM_ID)?;
+        let metadata = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                metadata_program.as_ref(),
+                position_mint.as_ref(),
+            ],
+            &metadata_program,
+        )
+        .0;
+
+        let accounts = vec![
+            AccountMeta::new(*owner, true),           // payer
+            AccountMeta::new_readonly(*owner, false), // position_nft_owner
+            AccountMeta::new(*position_mint, true),
+            AccountMeta::new(self.associated_token_address(owner, position_mint), false),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(
+                self.protocol_position_address(&pool.address, tick_lower, tick_upper),
+                false,
+            ),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, tick_lower, pool.tick_spacing),
+                false,
+            ),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, tick_upper, pool.tick_spacing),
+                false,
+            ),
+            AccountMeta::new(self.position_address(position_mint), false),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_a),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_b),
+                false,
+            ),
+            AccountMeta::new(pool.token_vault_a, false),
+            AccountMeta::new(pool.token_vault_b, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID)?, false),
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new_readonly(self.ata_program, false),
+            AccountMeta::new_readonly(metadata_program, false),
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    fn build_increase_liquidity_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<Instruction> {
+        let mut data = Vec::with_capacity(40);
+        data.extend_from_slice(&INCREASE_LIQUIDITY);
+        data.extend_from_slice(&liquidity.to_le_bytes());
+        data.extend_from_slice(&token_max_a.to_le_bytes());
+        data.extend_from_slice(&token_max_b.to_le_bytes());
+
+        let on_chain = &position.on_chain;
+        let accounts = vec![
+            AccountMeta::new_readonly(*owner, true), // nft_owner
+            AccountMeta::new_readonly(
+                self.associated_token_address(owner, &position.position_mint),
+                false,
+            ),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(
+                self.protocol_position_address(
+                    &pool.address,
+                    on_chain.tick_lower,
+                    on_chain.tick_upper,
+                ),
+                false,
+            ),
+            AccountMeta::new(on_chain.address, false),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, on_chain.tick_lower, pool.tick_spacing),
+                false,
+            ),
+            AccountMeta::new(
+                self.tick_array_address(&pool.address, on_chain.tick_upper, pool.tick_spacing),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_a),
+                false,
+            ),
+            AccountMeta::new(
+                self.associated_token_address(owner, &pool.token_mint_b),
+                false,
+            ),
+            AccountMeta::new(pool.token_vault_a, false),
+            AccountMeta::new(pool.token_vault_b, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    fn build_decrease_liquidity_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+        liquidity: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<Instruction> {
+        Ok(self.decrease_liquidity_instruction(
+            pool,
+            position,
+            owner,
+            liquidity,
+            token_min_a,
+            token_min_b,
+        ))
+    }
+
+    fn build_collect_fees_instruction(
+        &self,
+        pool: &ProtocolPoolState,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+    ) -> Result<Instruction> {
+        // Raydium has no separate collect instruction; removing no liquidity
+        // transfers the owed fees
+        Ok(self.decrease_liquidity_instruction(pool, position, owner, 0, 0, 0))
+    }
+
+    fn build_close_position_instruction(
+        &self,
+        position: &ProtocolPosition,
+        owner: &Pubkey,
+    ) -> Result<Instruction> {
+        let accounts = vec![
+            AccountMeta::new(*owner, true), // nft_owner, receives the rent
+            AccountMeta::new(position.position_mint, false),
+            AccountMeta::new(
+                self.associated_token_address(owner, &position.position_mint),
+                false,
+            ),
+            AccountMeta::new(position.on_chain.address, false),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM_ID)?, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: CLOSE_POSITION.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn pool() -> ProtocolPoolState {
+        ProtocolPoolState {
+            protocol: Protocol::Raydium,
+            address: Pubkey::new_unique(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 10,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_rate: 2500,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+        }
+    }
+
+    #[test]
+    fn test_tick_array_start_index() {
+        // Arrays of 60 ticks at spacing 10 start every 600 ticks
+        assert_eq!(RaydiumAdapter::tick_array_start_index(0, 10), 0);
+        assert_eq!(RaydiumAdapter::tick_array_start_index(599, 10), 0);
+        assert_eq!(RaydiumAdapter::tick_array_start_index(600, 10), 600);
+        assert_eq!(RaydiumAdapter::tick_array_start_index(-1, 10), -600);
+    }
+
+    #[test]
+    fn test_position_instructions() {
+        let adapter = RaydiumAdapter::new(Arc::new(RpcProvider::localhost()));
+        let pool = pool();
+        let owner = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+
+        let open = adapter
+            .build_open_position_instruction(&pool, &owner, &position_mint, -120, 1200)
+            .unwrap();
+        let position_address = adapter.position_address(&position_mint);
+        assert_eq!(open.accounts.len(), 19);
+        assert_eq!(open.accounts[9].pubkey, position_address);
+        assert!(open.accounts[2].is_signer);
+        assert_eq!(open.data.len(), 56);
+        assert_eq!(&open.data[16..20], &(-600i32).to_le_bytes());
+        assert_eq!(&open.data[20..24], &1200i32.to_le_bytes());
+
+        let position = ProtocolPosition {
+            protocol: Protocol::Raydium,
+            position_mint,
+            on_chain: OnChainPosition {
+                address: position_address,
+                pool: pool.address,
+                owner,
+                tick_lower: -120,
+                tick_upper: 1200,
+                liquidity: 1_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+            },
+        };
+        let increase = adapter
+            .build_increase_liquidity_instruction(&pool, &position, &owner, 1_000, 10, 20)
+            .unwrap();
+        assert_eq!(increase.accounts.len(), 12);
+        assert_eq!(increase.data.len(), 40);
+        // The protocol position is shared with the open instruction
+        assert_eq!(increase.accounts[3].pubkey, open.accounts[6].pubkey);
+        assert_eq!(increase.accounts[5].pubkey, open.accounts[7].pubkey);
+        assert_ne!(increase.accounts[5].pubkey, increase.accounts[6].pubkey);
+
+        let collect = adapter
+            .build_collect_fees_instruction(&pool, &position, &owner)
+            .unwrap();
+        assert_eq!(&collect.data[..8], &DECREASE_LIQUIDITY);
+        assert!(collect.data[8..].iter().all(|&b| b == 0));
+
+        let close = adapter
+            .build_close_position_instruction(&position, &owner)
+            .unwrap();
+        assert_eq!(close.accounts[1].pubkey, position_mint);
+        assert_eq!(close.accounts[3].pubkey, position_address);
+    }
+}
@@ -1,2 +1,17 @@
-// Raydium adapter module
-// TODO: Implement Raydium V4 (CLMM) and CLMM parsing logic
+//! Raydium CLMM protocol adapter.
+//!
+//! This module provides functionality to interact with Raydium CLMM pools:
+//! - Read pool state
+//! - Read position state
+//! - Build LP instructions
+//!
+//! Swap instruction building is not implemented yet.
+
+/// Raydium CLMM account structures.
+pub mod accounts;
+/// Protocol adapter.
+#[cfg(feature = "rpc")]
+pub mod adapter;
+/// Position reader for on-chain state.
+#[cfg(feature = "rpc")]
+pub mod position_reader;
@@ -0,0 +1,82 @@
+//! Raydium CLMM position reader.
+//!
+//! Reads position state from on-chain accounts.
+
+use super::accounts::{PersonalPosition, RAYDIUM_CLMM_PROGRAM_ID};
+use crate::discovery::find_position_mints;
+use crate::events::OnChainPosition;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Derives the personal position account of a position NFT mint.
+#[must_use]
+pub fn position_address(position_mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(RAYDIUM_CLMM_PROGRAM_ID).expect("valid program id");
+    Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &program_id).0
+}
+
+/// Reads Raydium CLMM positions from on-chain.
+pub struct RaydiumPositionReader {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+}
+
+impl RaydiumPositionReader {
+    /// Creates a new position reader.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Gets all positions for a given owner.
+    pub async fn get_positions_by_owner(&self, owner: &str) -> Result<Vec<OnChainPosition>> {
+        let owner_pubkey = Pubkey::from_str(owner).context("Invalid owner address")?;
+
+        info!(owner = owner, "Fetching Raydium positions for owner");
+
+        let mints = find_position_mints(&self.provider, &owner_pubkey).await?;
+        self.get_positions_for_mints(&owner_pubkey, &mints).await
+    }
+
+    /// Gets the positions of the given NFT mints held by an owner.
+    ///
+    /// Mints that are not Raydium position NFTs are skipped.
+    pub async fn get_positions_for_mints(
+        &self,
+        owner: &Pubkey,
+        mints: &[Pubkey],
+    ) -> Result<Vec<OnChainPosition>> {
+        let program_id = Pubkey::from_str(RAYDIUM_CLMM_PROGRAM_ID)?;
+        let addresses: Vec<Pubkey> = mints.iter().map(position_address).collect();
+        let accounts = self.provider.get_multiple_accounts(&addresses).await?;
+
+        let mut positions = Vec::new();
+        for (address, account) in addresses.into_iter().zip(accounts) {
+            let Some(account) = account else { continue };
+            if account.owner != program_id {
+                continue;
+            }
+            match PersonalPosition::parse(&account.data) {
+                Ok(position) => positions.push(OnChainPosition {
+                    address,
+                    pool: position.pool_id,
+                    owner: *owner,
+                    tick_lower: position.tick_lower_index,
+                    tick_upper: position.tick_upper_index,
+                    liquidity: position.liquidity,
+                    fee_growth_inside_a: position.fee_growth_inside_0_last_x64,
+                    fee_growth_inside_b: position.fee_growth_inside_1_last_x64,
+                    fees_owed_a: position.token_fees_owed_0,
+                    fees_owed_b: position.token_fees_owed_1,
+                }),
+                Err(e) => debug!(position = %address, error = %e, "Skipping unreadable position"),
+            }
+        }
+
+        debug!(count = positions.len(), "Found Raydium positions");
+        Ok(positions)
+    }
+}
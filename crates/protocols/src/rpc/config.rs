@@ -1,5 +1,6 @@
 //! RPC configuration for Solana endpoints.
 
+use clmm_lp_config::prelude::{Commitment, RpcSettings};
 use std::time::Duration;
 
 /// Configuration for RPC endpoints.
@@ -51,6 +52,23 @@ impl RpcConfig {
         }
     }
 
+    /// Creates an RPC config from the shared configuration's RPC section.
+    #[must_use]
+    pub fn from_settings(settings: &RpcSettings) -> Self {
+        Self {
+            primary_url: settings.url.clone(),
+            fallback_urls: settings.fallback_urls.clone(),
+            timeout: Duration::from_secs(settings.timeout_secs),
+            max_retries: settings.max_retries,
+            commitment: match settings.commitment {
+                Commitment::Processed => CommitmentLevel::Processed,
+                Commitment::Confirmed => CommitmentLevel::Confirmed,
+                Commitment::Finalized => CommitmentLevel::Finalized,
+            },
+            ..Default::default()
+        }
+    }
+
     /// Adds a fallback URL.
     #[must_use]
     pub fn with_fallback(mut self, url: impl Into<String>) -> Self {
@@ -118,8 +136,8 @@ impl RpcConfig {
     }
 }
 
-/// Commitment level for RPC requests.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Commitment level for RPC requests, ordered from least to most final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum CommitmentLevel {
     /// Processed commitment (fastest, least reliable).
     Processed,
@@ -164,6 +182,20 @@ mod tests {
         assert_eq!(endpoints[0], "https://primary.com");
     }
 
+    #[test]
+    fn test_from_settings() {
+        let settings = RpcSettings {
+            url: "https://rpc.example.com".to_string(),
+            fallback_urls: vec![],
+            commitment: Commitment::Finalized,
+            ..Default::default()
+        };
+        let config = RpcConfig::from_settings(&settings);
+        assert_eq!(config.all_endpoints(), vec!["https://rpc.example.com"]);
+        assert_eq!(config.commitment, CommitmentLevel::Finalized);
+        assert_eq!(config.timeout, Duration::from_secs(settings.timeout_secs));
+    }
+
     #[test]
     fn test_devnet_config() {
         let config = RpcConfig::devnet();
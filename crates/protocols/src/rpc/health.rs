@@ -40,6 +40,39 @@ impl Default for EndpointHealth {
     }
 }
 
+/// Result of probing an RPC endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointProbe {
+    /// Endpoint URL.
+    pub endpoint: String,
+    /// Slot reported by the endpoint, if it answered.
+    pub slot: Option<u64>,
+    /// Round-trip time of the probe in milliseconds, if it answered.
+    pub latency_ms: Option<f64>,
+    /// Slots behind the most advanced endpoint, if it answered.
+    pub slot_lag: Option<u64>,
+    /// Error of a failed probe.
+    pub error: Option<String>,
+}
+
+impl EndpointProbe {
+    /// Returns whether the endpoint answered.
+    #[must_use]
+    pub fn is_reachable(&self) -> bool {
+        self.slot.is_some()
+    }
+}
+
+/// Sets each probe's slot lag relative to the highest observed slot.
+pub fn fill_slot_lag(probes: &mut [EndpointProbe]) {
+    let Some(tip) = probes.iter().filter_map(|p| p.slot).max() else {
+        return;
+    };
+    for probe in probes {
+        probe.slot_lag = probe.slot.map(|slot| tip.saturating_sub(slot));
+    }
+}
+
 impl EndpointHealth {
     /// Records a successful request.
     pub fn record_success(&mut self, response_time_ms: f64) {
@@ -279,4 +312,22 @@ mod tests {
         checker.record_failure("https://test.com").await;
         assert!(!checker.is_healthy("https://test.com").await);
     }
+
+    #[test]
+    fn test_fill_slot_lag() {
+        let probe = |slot: Option<u64>| EndpointProbe {
+            endpoint: "http://rpc".to_string(),
+            slot,
+            latency_ms: slot.map(|_| 10.0),
+            slot_lag: None,
+            error: None,
+        };
+        let mut probes = vec![probe(Some(100)), probe(Some(95)), probe(None)];
+        fill_slot_lag(&mut probes);
+
+        assert_eq!(probes[0].slot_lag, Some(0));
+        assert_eq!(probes[1].slot_lag, Some(5));
+        assert_eq!(probes[2].slot_lag, None);
+        assert!(!probes[2].is_reachable());
+    }
 }
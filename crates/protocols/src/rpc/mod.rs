@@ -5,11 +5,14 @@
 //! - Health checking and endpoint rotation
 //! - Rate limiting
 //! - Retry logic with exponential backoff
+//! - Streaming pool price subscriptions over WebSocket
 
 mod config;
 mod health;
 mod provider;
+mod subscription;
 
 pub use config::*;
 pub use health::*;
 pub use provider::*;
+pub use subscription::*;
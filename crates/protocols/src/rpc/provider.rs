@@ -1,17 +1,53 @@
 //! RPC provider with automatic failover and retry logic.
 
-use super::{HealthChecker, RpcConfig};
+use super::{CommitmentLevel, EndpointProbe, HealthChecker, RpcConfig, fill_slot_lag};
+use crate::error::ProtocolError;
 use anyhow::{Context, Result};
+use solana_account_decoder_client_types::UiAccountEncoding;
 use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig,
+};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_client::rpc_response::TransactionConfirmationStatus;
 use solana_sdk::account::Account;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
+use solana_sdk::transaction::TransactionError;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
-use tracing::{debug, info, warn};
+use tracing::{Instrument, debug, info, info_span, instrument, warn};
+
+/// Maximum number of accounts in one `getMultipleAccounts` request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Status of a sent transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureStatus {
+    /// Slot the transaction was processed in.
+    pub slot: u64,
+    /// Commitment the transaction has reached.
+    pub commitment: CommitmentLevel,
+    /// Error of a failed transaction.
+    pub err: Option<TransactionError>,
+}
+
+/// A transaction that referenced an account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountSignature {
+    /// Transaction signature.
+    pub signature: Signature,
+    /// Slot the transaction was processed in.
+    pub slot: u64,
+    /// Block time in Unix seconds, if known.
+    pub block_time: Option<i64>,
+    /// Whether the transaction failed.
+    pub failed: bool,
+}
 
 /// RPC provider with automatic failover and health checking.
 pub struct RpcProvider {
@@ -91,6 +127,10 @@ impl RpcProvider {
     }
 
     /// Executes a request with retry and failover logic.
+    ///
+    /// Missing accounts are returned immediately since another endpoint
+    /// would give the same answer. Once retries are exhausted the last
+    /// error is wrapped in [`ProtocolError::RpcUnavailable`].
     async fn execute_with_retry<T, F, Fut>(&self, operation: F) -> Result<T>
     where
         F: Fn(RpcClient) -> Fut,
@@ -104,12 +144,21 @@ impl RpcProvider {
             let client = self.get_client().await;
             let start = Instant::now();
 
-            match operation(client).await {
+            let attempt = info_span!("rpc_attempt", attempt = retry_count);
+            match operation(client).instrument(attempt).await {
                 Ok(result) => {
                     let elapsed = start.elapsed().as_millis() as f64;
                     self.health.record_success(&endpoint, elapsed).await;
                     return Ok(result);
                 }
+                Err(e)
+                    if matches!(
+                        ProtocolError::find(&e),
+                        Some(ProtocolError::AccountNotFound(_))
+                    ) =>
+                {
+                    return Err(e);
+                }
                 Err(e) => {
                     warn!(
                         endpoint = endpoint,
@@ -139,10 +188,17 @@ impl RpcProvider {
             }
         }
 
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Unknown error")))
+        let attempts = ProtocolError::RpcUnavailable {
+            attempts: retry_count,
+        };
+        Err(match last_error {
+            Some(e) => e.context(attempts),
+            None => attempts.into(),
+        })
     }
 
     /// Gets the current slot.
+    #[instrument(skip_all)]
     pub async fn get_slot(&self) -> Result<u64> {
         self.execute_with_retry(|client| async move {
             client.get_slot().await.context("Failed to get slot")
@@ -151,6 +207,7 @@ impl RpcProvider {
     }
 
     /// Gets the current block height.
+    #[instrument(skip_all)]
     pub async fn get_block_height(&self) -> Result<u64> {
         self.execute_with_retry(|client| async move {
             client
@@ -162,42 +219,85 @@ impl RpcProvider {
     }
 
     /// Gets account data for a given address.
+    #[instrument(skip_all, fields(address = %address))]
     pub async fn get_account(&self, address: &Pubkey) -> Result<Account> {
         let addr = *address;
         self.execute_with_retry(|client| async move {
-            client
-                .get_account(&addr)
-                .await
-                .context("Failed to get account")
+            client.get_account(&addr).await.map_err(|e| {
+                // The RPC client reports missing accounts as a generic error
+                if e.to_string().contains("AccountNotFound") {
+                    ProtocolError::AccountNotFound(addr.to_string()).into()
+                } else {
+                    anyhow::Error::new(e).context("Failed to get account")
+                }
+            })
         })
         .await
     }
 
     /// Gets account data by address string.
     pub async fn get_account_by_address(&self, address: &str) -> Result<Account> {
-        let pubkey = Pubkey::from_str(address).context("Invalid pubkey")?;
+        let pubkey = Pubkey::from_str(address)
+            .map_err(|_| ProtocolError::InvalidAddress(address.to_string()))?;
         self.get_account(&pubkey).await
     }
 
     /// Gets multiple accounts.
+    ///
+    /// Requests are split into batches of the RPC limit of 100 accounts.
+    #[instrument(skip_all, fields(count = addresses.len()))]
     pub async fn get_multiple_accounts(
         &self,
         addresses: &[Pubkey],
     ) -> Result<Vec<Option<Account>>> {
-        let addrs = addresses.to_vec();
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addrs = chunk.to_vec();
+            let batch = self
+                .execute_with_retry(|client| {
+                    let addrs = addrs.clone();
+                    async move {
+                        client
+                            .get_multiple_accounts(&addrs)
+                            .await
+                            .context("Failed to get multiple accounts")
+                    }
+                })
+                .await?;
+            accounts.extend(batch);
+        }
+        Ok(accounts)
+    }
+
+    /// Gets the accounts owned by a program that match all filters.
+    #[instrument(skip_all, fields(program = %program_id))]
+    pub async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        let program = *program_id;
         self.execute_with_retry(|client| {
-            let addrs = addrs.clone();
+            let config = RpcProgramAccountsConfig {
+                filters: Some(filters.clone()),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
             async move {
                 client
-                    .get_multiple_accounts(&addrs)
+                    .get_program_accounts_with_config(&program, config)
                     .await
-                    .context("Failed to get multiple accounts")
+                    .context("Failed to get program accounts")
             }
         })
         .await
     }
 
     /// Gets the balance of an account in lamports.
+    #[instrument(skip_all, fields(address = %address))]
     pub async fn get_balance(&self, address: &Pubkey) -> Result<u64> {
         let addr = *address;
         self.execute_with_retry(|client| async move {
@@ -210,6 +310,7 @@ impl RpcProvider {
     }
 
     /// Gets the latest blockhash.
+    #[instrument(skip_all)]
     pub async fn get_latest_blockhash(&self) -> Result<solana_sdk::hash::Hash> {
         self.execute_with_retry(|client| async move {
             client
@@ -221,6 +322,7 @@ impl RpcProvider {
     }
 
     /// Gets transaction status.
+    #[instrument(skip_all, fields(signature = %signature))]
     pub async fn get_signature_status(
         &self,
         signature: &Signature,
@@ -240,6 +342,123 @@ impl RpcProvider {
         .await
     }
 
+    /// Gets the slot, commitment and error of a sent transaction.
+    ///
+    /// Returns `None` while no node has processed the transaction.
+    #[instrument(skip_all, fields(signature = %signature))]
+    pub async fn get_transaction_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<SignatureStatus>> {
+        let sig = *signature;
+        self.execute_with_retry(|client| async move {
+            let statuses = client
+                .get_signature_statuses(&[sig])
+                .await
+                .context("Failed to get signature status")?;
+
+            Ok(statuses.value.into_iter().next().flatten().map(|status| {
+                let commitment = match status.confirmation_status {
+                    Some(TransactionConfirmationStatus::Processed) => CommitmentLevel::Processed,
+                    Some(TransactionConfirmationStatus::Confirmed) => CommitmentLevel::Confirmed,
+                    Some(TransactionConfirmationStatus::Finalized) => CommitmentLevel::Finalized,
+                    // Older nodes only report confirmations, which are absent once rooted
+                    None if status.confirmations.is_none() => CommitmentLevel::Finalized,
+                    None => CommitmentLevel::Processed,
+                };
+                SignatureStatus {
+                    slot: status.slot,
+                    commitment,
+                    err: status.err,
+                }
+            }))
+        })
+        .await
+    }
+
+    /// Gets the priority fees paid in recent slots by transactions writing
+    /// to any of the given accounts, in micro-lamports per compute unit.
+    #[instrument(skip_all, fields(accounts = accounts.len()))]
+    pub async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> Result<Vec<u64>> {
+        let accounts = accounts.to_vec();
+        self.execute_with_retry(|client| {
+            let accounts = accounts.clone();
+            async move {
+                let fees = client
+                    .get_recent_prioritization_fees(&accounts)
+                    .await
+                    .context("Failed to get recent prioritization fees")?;
+                Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+            }
+        })
+        .await
+    }
+
+    /// Gets the transactions that referenced an account, newest first.
+    ///
+    /// Returns up to `limit` (at most 1,000) transactions older than
+    /// `before`, or the most recent ones without it.
+    #[instrument(skip_all, fields(address = %address, limit))]
+    pub async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<Signature>,
+        limit: usize,
+    ) -> Result<Vec<AccountSignature>> {
+        let address = *address;
+        self.execute_with_retry(|client| async move {
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                limit: Some(limit),
+                ..Default::default()
+            };
+            let statuses = client
+                .get_signatures_for_address_with_config(&address, config)
+                .await
+                .context("Failed to get signatures for address")?;
+
+            statuses
+                .into_iter()
+                .map(|status| {
+                    Ok(AccountSignature {
+                        signature: Signature::from_str(&status.signature)
+                            .context("Invalid signature in response")?,
+                        slot: status.slot,
+                        block_time: status.block_time,
+                        failed: status.err.is_some(),
+                    })
+                })
+                .collect()
+        })
+        .await
+    }
+
+    /// Gets the log messages of a confirmed transaction.
+    ///
+    /// Returns an empty list for transactions recorded without logs.
+    #[instrument(skip_all, fields(signature = %signature))]
+    pub async fn get_transaction_logs(&self, signature: &Signature) -> Result<Vec<String>> {
+        let sig = *signature;
+        self.execute_with_retry(|client| async move {
+            let config = RpcTransactionConfig {
+                encoding: None,
+                commitment: None,
+                max_supported_transaction_version: Some(0),
+            };
+            let transaction = client
+                .get_transaction_with_config(&sig, config)
+                .await
+                .context("Failed to get transaction")?;
+
+            let logs: Option<Vec<String>> = transaction
+                .transaction
+                .meta
+                .and_then(|meta| meta.log_messages.into());
+            Ok(logs.unwrap_or_default())
+        })
+        .await
+    }
+
     /// Gets the health status of all endpoints.
     pub async fn get_health_status(
         &self,
@@ -255,7 +474,50 @@ impl RpcProvider {
         }
     }
 
+    /// Probes every endpoint once for its slot and latency.
+    ///
+    /// Endpoints are queried concurrently without retries, and the results
+    /// are recorded in the health checker.
+    pub async fn probe_endpoints(&self) -> Vec<EndpointProbe> {
+        let probes = self
+            .config
+            .all_endpoints()
+            .into_iter()
+            .map(|endpoint| async move {
+                let client = RpcClient::new_with_timeout(endpoint.to_string(), self.config.timeout);
+                let start = Instant::now();
+                match client.get_slot().await {
+                    Ok(slot) => {
+                        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+                        self.health.record_success(endpoint, latency_ms).await;
+                        EndpointProbe {
+                            endpoint: endpoint.to_string(),
+                            slot: Some(slot),
+                            latency_ms: Some(latency_ms),
+                            slot_lag: None,
+                            error: None,
+                        }
+                    }
+                    Err(e) => {
+                        self.health.record_failure(endpoint).await;
+                        EndpointProbe {
+                            endpoint: endpoint.to_string(),
+                            slot: None,
+                            latency_ms: None,
+                            slot_lag: None,
+                            error: Some(e.to_string()),
+                        }
+                    }
+                }
+            });
+
+        let mut probes = futures::future::join_all(probes).await;
+        fill_slot_lag(&mut probes);
+        probes
+    }
+
     /// Simulates a transaction without broadcasting.
+    #[instrument(skip_all)]
     pub async fn simulate_transaction(
         &self,
         transaction: &solana_sdk::transaction::Transaction,
@@ -275,6 +537,7 @@ impl RpcProvider {
     }
 
     /// Sends and confirms a transaction.
+    #[instrument(skip_all)]
     pub async fn send_and_confirm_transaction(
         &self,
         transaction: &solana_sdk::transaction::Transaction,
@@ -293,6 +556,7 @@ impl RpcProvider {
     }
 
     /// Sends a transaction without waiting for confirmation.
+    #[instrument(skip_all)]
     pub async fn send_transaction(
         &self,
         transaction: &solana_sdk::transaction::Transaction,
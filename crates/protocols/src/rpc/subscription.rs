@@ -0,0 +1,389 @@
+//! Streaming pool price subscriptions over the Solana WebSocket API.
+//!
+//! Subscribes to pool accounts with `accountSubscribe` and decodes every
+//! notification into the pool's sqrt price, tick and liquidity, so consumers
+//! see price changes as soon as the node does instead of on their next poll.
+
+use super::{CommitmentLevel, RpcConfig};
+use crate::orca::pool_reader::{WHIRLPOOL_PROGRAM_ID, sqrt_price_to_price};
+use crate::orca::whirlpool::Whirlpool;
+use crate::raydium::accounts::{RAYDIUM_CLMM_PROGRAM_ID, RaydiumPool};
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use clmm_lp_domain::enums::Protocol;
+use futures::StreamExt;
+use futures::stream::select_all;
+use rust_decimal::Decimal;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Price state of a pool pushed by an account notification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolPriceUpdate {
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Protocol of the pool.
+    pub protocol: Protocol,
+    /// Slot the account changed in.
+    pub slot: u64,
+    /// Current sqrt price (Q64.64).
+    pub sqrt_price: u128,
+    /// Current tick index.
+    pub tick_current: i32,
+    /// Liquidity at the current tick.
+    pub liquidity: u128,
+}
+
+impl PoolPriceUpdate {
+    /// Decodes a pool account owned by a supported CLMM program.
+    ///
+    /// Returns `None` for accounts of other programs or that are not pools.
+    #[must_use]
+    pub fn decode(pool: Pubkey, slot: u64, owner: &Pubkey, data: &[u8]) -> Option<Self> {
+        let owner = owner.to_string();
+        if owner == WHIRLPOOL_PROGRAM_ID {
+            let whirlpool = Whirlpool::try_from_slice(data).ok()?;
+            Some(Self {
+                pool,
+                protocol: Protocol::OrcaWhirlpools,
+                slot,
+                sqrt_price: whirlpool.sqrt_price,
+                tick_current: whirlpool.tick_current_index,
+                liquidity: whirlpool.liquidity,
+            })
+        } else if owner == RAYDIUM_CLMM_PROGRAM_ID {
+            let state = RaydiumPool::parse(data).ok()?;
+            Some(Self {
+                pool,
+                protocol: Protocol::Raydium,
+                slot,
+                sqrt_price: state.sqrt_price_x64,
+                tick_current: state.tick_current,
+                liquidity: state.liquidity,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Gets the price of token A in token B, unadjusted for decimals.
+    #[must_use]
+    pub fn price(&self) -> Decimal {
+        sqrt_price_to_price(self.sqrt_price)
+    }
+
+    /// Checks if a tick is within the current range.
+    #[must_use]
+    pub fn is_tick_in_range(&self, tick_lower: i32, tick_upper: i32) -> bool {
+        self.tick_current >= tick_lower && self.tick_current < tick_upper
+    }
+}
+
+/// Configuration for pool subscriptions.
+#[derive(Debug, Clone)]
+pub struct SubscriptionConfig {
+    /// WebSocket endpoint URL.
+    pub ws_url: String,
+    /// Commitment level of notifications.
+    pub commitment: CommitmentLevel,
+    /// Delay before reconnecting after the connection drops.
+    pub reconnect_delay: Duration,
+    /// Updates buffered before the subscriber waits for the consumer.
+    pub channel_capacity: usize,
+}
+
+impl Default for SubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            ws_url: "wss://api.mainnet-beta.solana.com".to_string(),
+            commitment: CommitmentLevel::Confirmed,
+            reconnect_delay: Duration::from_secs(5),
+            channel_capacity: 1000,
+        }
+    }
+}
+
+impl SubscriptionConfig {
+    /// Creates a subscription config for the given WebSocket URL.
+    #[must_use]
+    pub fn new(ws_url: impl Into<String>) -> Self {
+        Self {
+            ws_url: ws_url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a subscription config for the WebSocket endpoint of an RPC
+    /// config's primary URL, with the same commitment.
+    #[must_use]
+    pub fn from_rpc_config(config: &RpcConfig) -> Self {
+        Self {
+            ws_url: ws_url_for(&config.primary_url),
+            commitment: config.commitment,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the commitment level.
+    #[must_use]
+    pub fn with_commitment(mut self, commitment: CommitmentLevel) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Sets the reconnect delay.
+    #[must_use]
+    pub fn with_reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+}
+
+/// Derives the WebSocket URL of an HTTP RPC endpoint, which Solana nodes
+/// serve on the same host.
+#[must_use]
+pub fn ws_url_for(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+/// Subscribes to pool accounts and streams their price updates.
+pub struct PoolSubscriber {
+    /// Configuration.
+    config: SubscriptionConfig,
+    /// Pools to subscribe to.
+    pools: Vec<Pubkey>,
+}
+
+impl PoolSubscriber {
+    /// Creates a subscriber without pools.
+    #[must_use]
+    pub fn new(config: SubscriptionConfig) -> Self {
+        Self {
+            config,
+            pools: Vec::new(),
+        }
+    }
+
+    /// Adds pools to subscribe to.
+    #[must_use]
+    pub fn with_pools(mut self, pools: impl IntoIterator<Item = Pubkey>) -> Self {
+        for pool in pools {
+            if !self.pools.contains(&pool) {
+                self.pools.push(pool);
+            }
+        }
+        self
+    }
+
+    /// Gets the pools subscribed to.
+    #[must_use]
+    pub fn pools(&self) -> &[Pubkey] {
+        &self.pools
+    }
+
+    /// Connects and streams updates in a background task.
+    ///
+    /// The task reconnects and resubscribes whenever the connection drops,
+    /// and stops once the returned subscription is dropped.
+    #[must_use]
+    pub fn spawn(self) -> PoolSubscription {
+        let (tx, rx) = mpsc::channel(self.config.channel_capacity);
+        let task = tokio::spawn(async move {
+            info!(
+                ws_url = %self.config.ws_url,
+                pools = self.pools.len(),
+                "Starting pool subscriptions"
+            );
+            loop {
+                match self.stream(&tx).await {
+                    Ok(()) if tx.is_closed() => break,
+                    Ok(()) => warn!("Pool subscription stream ended"),
+                    Err(e) => warn!(error = %e, "Pool subscription failed"),
+                }
+                tokio::time::sleep(self.config.reconnect_delay).await;
+            }
+        });
+
+        PoolSubscription { updates: rx, task }
+    }
+
+    /// Subscribes to every pool and forwards updates until the connection
+    /// drops or the receiver is closed.
+    async fn stream(&self, tx: &mpsc::Sender<PoolPriceUpdate>) -> Result<()> {
+        let client = PubsubClient::new(self.config.ws_url.as_str())
+            .await
+            .context("Failed to connect to WebSocket endpoint")?;
+        let account_config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig {
+                commitment: match self.config.commitment {
+                    CommitmentLevel::Processed => {
+                        solana_commitment_config::CommitmentLevel::Processed
+                    }
+                    CommitmentLevel::Confirmed => {
+                        solana_commitment_config::CommitmentLevel::Confirmed
+                    }
+                    CommitmentLevel::Finalized => {
+                        solana_commitment_config::CommitmentLevel::Finalized
+                    }
+                },
+            }),
+            ..Default::default()
+        };
+
+        let mut streams = Vec::with_capacity(self.pools.len());
+        let mut unsubscribes = Vec::with_capacity(self.pools.len());
+        for pool in &self.pools {
+            let (stream, unsubscribe) = client
+                .account_subscribe(pool, Some(account_config.clone()))
+                .await
+                .with_context(|| format!("Failed to subscribe to pool {}", pool))?;
+            let pool = *pool;
+            streams.push(stream.map(move |response| (pool, response)));
+            unsubscribes.push(unsubscribe);
+        }
+        debug!(pools = streams.len(), "Subscribed to pools");
+
+        let mut notifications = select_all(streams);
+        while let Some((pool, response)) = notifications.next().await {
+            let Some(account) = response.value.decode::<Account>() else {
+                continue;
+            };
+            let Some(update) =
+                PoolPriceUpdate::decode(pool, response.context.slot, &account.owner, &account.data)
+            else {
+                warn!(pool = %pool, "Skipping undecodable pool notification");
+                continue;
+            };
+            if tx.send(update).await.is_err() {
+                break;
+            }
+        }
+
+        drop(notifications);
+        for unsubscribe in unsubscribes {
+            unsubscribe().await;
+        }
+        Ok(())
+    }
+}
+
+/// Running pool subscription; dropping it stops the background task.
+pub struct PoolSubscription {
+    /// Decoded updates.
+    updates: mpsc::Receiver<PoolPriceUpdate>,
+    /// Background task.
+    task: JoinHandle<()>,
+}
+
+impl PoolSubscription {
+    /// Receives the next update.
+    ///
+    /// Returns `None` if the background task stopped.
+    pub async fn recv(&mut self) -> Option<PoolPriceUpdate> {
+        self.updates.recv().await
+    }
+}
+
+impl Drop for PoolSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orca::whirlpool::WhirlpoolRewardInfo;
+    use borsh::BorshSerialize;
+    use std::str::FromStr;
+
+    fn whirlpool_data(sqrt_price: u128, tick: i32, liquidity: u128) -> Vec<u8> {
+        let reward = WhirlpoolRewardInfo {
+            mint: Pubkey::default(),
+            vault: Pubkey::default(),
+            authority: Pubkey::default(),
+            emissions_per_second_x64: 0,
+            growth_global_x64: 0,
+        };
+        let whirlpool = Whirlpool {
+            discriminator: [0; 8],
+            whirlpools_config: Pubkey::default(),
+            whirlpool_bump: [0],
+            tick_spacing: 64,
+            tick_spacing_seed: [64, 0],
+            fee_rate: 3000,
+            protocol_fee_rate: 300,
+            liquidity,
+            sqrt_price,
+            tick_current_index: tick,
+            protocol_fee_owed_a: 0,
+            protocol_fee_owed_b: 0,
+            token_mint_a: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            fee_growth_global_a: 0,
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            fee_growth_global_b: 0,
+            reward_last_updated_timestamp: 0,
+            reward_infos: [reward; 3],
+        };
+        let mut data = Vec::new();
+        whirlpool.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_decode_whirlpool_update() {
+        let pool = Pubkey::new_unique();
+        let owner = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).unwrap();
+        let data = whirlpool_data(1u128 << 64, -5, 1_000_000);
+
+        let update = PoolPriceUpdate::decode(pool, 42, &owner, &data).unwrap();
+
+        assert_eq!(update.pool, pool);
+        assert_eq!(update.protocol, Protocol::OrcaWhirlpools);
+        assert_eq!(update.slot, 42);
+        assert_eq!(update.tick_current, -5);
+        assert_eq!(update.liquidity, 1_000_000);
+        assert_eq!(update.price(), Decimal::ONE);
+        assert!(update.is_tick_in_range(-10, 0));
+        assert!(!update.is_tick_in_range(0, 10));
+    }
+
+    #[test]
+    fn test_decode_rejects_other_programs() {
+        let data = whirlpool_data(1u128 << 64, 0, 1);
+
+        assert!(
+            PoolPriceUpdate::decode(Pubkey::new_unique(), 1, &Pubkey::new_unique(), &data)
+                .is_none()
+        );
+        let owner = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).unwrap();
+        assert!(PoolPriceUpdate::decode(Pubkey::new_unique(), 1, &owner, &data[..100]).is_none());
+    }
+
+    #[test]
+    fn test_ws_url_for() {
+        assert_eq!(
+            ws_url_for("https://api.mainnet-beta.solana.com"),
+            "wss://api.mainnet-beta.solana.com"
+        );
+        assert_eq!(ws_url_for("http://localhost:8899"), "ws://localhost:8899");
+        assert_eq!(ws_url_for("wss://node.example"), "wss://node.example");
+    }
+}
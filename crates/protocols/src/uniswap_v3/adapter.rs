@@ -0,0 +1,276 @@
+//! Uniswap v3 adapter reading pools and positions over JSON-RPC.
+
+use super::contracts::{NftPosition, Slot0, UniswapV3Deployment, selectors};
+use crate::PoolFetcher;
+use crate::chain::{Chain, ClmmAdapter, ClmmPoolState, ClmmPositionState};
+use crate::error::ProtocolError;
+use crate::evm::abi::{AbiDecoder, address_word, encode_call, normalize_address, uint_word};
+use crate::evm::rpc::EvmRpcClient;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clmm_lp_domain::entities::pool::Pool;
+use clmm_lp_domain::entities::token::Token;
+use clmm_lp_domain::enums::Protocol;
+use futures::future::try_join_all;
+use primitive_types::U256;
+use tracing::{debug, info, instrument};
+
+/// Reads Uniswap v3 pools and positions on an EVM chain.
+pub struct UniswapV3Adapter {
+    /// Chain served.
+    chain: Chain,
+    /// JSON-RPC client.
+    client: EvmRpcClient,
+    /// Contract addresses.
+    deployment: UniswapV3Deployment,
+}
+
+impl UniswapV3Adapter {
+    /// Creates an adapter for the official deployment on a chain.
+    ///
+    /// # Errors
+    /// Returns [`ProtocolError::UnsupportedChain`] if Uniswap v3 is not
+    /// deployed on the chain.
+    pub fn new(chain: Chain, client: EvmRpcClient) -> Result<Self> {
+        let deployment = UniswapV3Deployment::for_chain(chain)
+            .ok_or_else(|| ProtocolError::UnsupportedChain(chain.to_string()))?;
+        Ok(Self::with_deployment(chain, client, deployment))
+    }
+
+    /// Creates an adapter for a specific deployment, such as a fork.
+    pub fn with_deployment(
+        chain: Chain,
+        client: EvmRpcClient,
+        deployment: UniswapV3Deployment,
+    ) -> Self {
+        Self {
+            chain,
+            client,
+            deployment,
+        }
+    }
+
+    /// Gets the contract addresses used.
+    #[must_use]
+    pub fn deployment(&self) -> UniswapV3Deployment {
+        self.deployment
+    }
+
+    /// Reads a pool's `slot0()`.
+    pub async fn slot0(&self, pool_address: &str) -> Result<Slot0> {
+        let data = self
+            .client
+            .call(pool_address, &encode_call(selectors::SLOT0, &[]))
+            .await?;
+        Slot0::decode(&data).map_err(|e| invalid_data(pool_address, &e))
+    }
+
+    /// Reads a position from the position manager.
+    pub async fn nft_position(&self, token_id: U256) -> Result<NftPosition> {
+        let manager = self.deployment.position_manager;
+        let data = self
+            .client
+            .call(
+                manager,
+                &encode_call(selectors::POSITIONS, &[uint_word(token_id)]),
+            )
+            .await?;
+        NftPosition::decode(&data).map_err(|e| invalid_data(manager, &e))
+    }
+
+    /// Finds the pool for a token pair and fee tier.
+    pub async fn pool_address(&self, token0: &str, token1: &str, fee: u32) -> Result<String> {
+        let args = [
+            address_word(token0)?,
+            address_word(token1)?,
+            uint_word(U256::from(fee)),
+        ];
+        let address = self
+            .call_word(self.deployment.factory, selectors::GET_POOL, &args)
+            .await
+            .and_then(|data| AbiDecoder::new(&data).address(0))?;
+        if address.trim_start_matches("0x").bytes().all(|b| b == b'0') {
+            return Err(ProtocolError::AccountNotFound(format!(
+                "{token0}/{token1} pool with fee {fee}"
+            ))
+            .into());
+        }
+        Ok(address)
+    }
+
+    /// Reads an ERC-20 token's metadata.
+    pub async fn token(&self, address: &str) -> Result<Token> {
+        let (decimals, symbol) = futures::try_join!(
+            self.call_word(address, selectors::DECIMALS, &[]),
+            self.call_word(address, selectors::SYMBOL, &[]),
+        )?;
+        let decimals = AbiDecoder::new(&decimals)
+            .u32(0)
+            .ok()
+            .and_then(|d| u8::try_from(d).ok())
+            .ok_or_else(|| invalid_data(address, &anyhow::anyhow!("Invalid decimals")))?;
+        let symbol = AbiDecoder::new(&symbol)
+            .string()
+            .map_err(|e| invalid_data(address, &e))?;
+        Ok(Token::new(
+            normalize_address(address)?,
+            &symbol,
+            decimals,
+            &symbol,
+        ))
+    }
+
+    /// Lists the position token IDs held by an owner.
+    pub async fn token_ids(&self, owner: &str) -> Result<Vec<U256>> {
+        let manager = self.deployment.position_manager;
+        let owner_word = address_word(owner)?;
+        let balance = self
+            .call_word(manager, selectors::BALANCE_OF, &[owner_word])
+            .await
+            .and_then(|data| AbiDecoder::new(&data).u128(0))?;
+
+        try_join_all((0..balance).map(|index| async move {
+            let data = self
+                .call_word(
+                    manager,
+                    selectors::TOKEN_OF_OWNER_BY_INDEX,
+                    &[owner_word, uint_word(U256::from(index))],
+                )
+                .await?;
+            AbiDecoder::new(&data).uint(0)
+        }))
+        .await
+    }
+
+    /// Reads a position and resolves its pool.
+    async fn position_state(
+        &self,
+        token_id: U256,
+        owner: Option<String>,
+    ) -> Result<ClmmPositionState> {
+        let position = self.nft_position(token_id).await?;
+        let pool = self
+            .pool_address(&position.token0, &position.token1, position.fee)
+            .await?;
+
+        Ok(ClmmPositionState {
+            chain: self.chain,
+            protocol: Protocol::UniswapV3,
+            id: token_id.to_string(),
+            pool,
+            owner,
+            tick_lower: position.tick_lower,
+            tick_upper: position.tick_upper,
+            liquidity: position.liquidity,
+            fees_owed_0: position.tokens_owed_0,
+            fees_owed_1: position.tokens_owed_1,
+        })
+    }
+
+    /// Calls a view function.
+    async fn call_word(
+        &self,
+        contract: &str,
+        selector: [u8; 4],
+        args: &[[u8; 32]],
+    ) -> Result<Vec<u8>> {
+        self.client
+            .call(contract, &encode_call(selector, args))
+            .await
+    }
+}
+
+#[async_trait]
+impl ClmmAdapter for UniswapV3Adapter {
+    fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    fn protocol(&self) -> Protocol {
+        Protocol::UniswapV3
+    }
+
+    #[instrument(skip(self), fields(chain = %self.chain))]
+    async fn pool_state(&self, pool_address: &str) -> Result<ClmmPoolState> {
+        let address = normalize_address(pool_address)?;
+        info!(pool = %address, "Fetching Uniswap v3 pool state");
+
+        let (slot0, liquidity, fee, tick_spacing, token0, token1) = futures::try_join!(
+            self.slot0(&address),
+            self.call_word(&address, selectors::LIQUIDITY, &[]),
+            self.call_word(&address, selectors::FEE, &[]),
+            self.call_word(&address, selectors::TICK_SPACING, &[]),
+            self.call_word(&address, selectors::TOKEN0, &[]),
+            self.call_word(&address, selectors::TOKEN1, &[]),
+        )?;
+        let decode = || -> Result<ClmmPoolState> {
+            Ok(ClmmPoolState {
+                chain: self.chain,
+                protocol: Protocol::UniswapV3,
+                address: address.clone(),
+                token0: AbiDecoder::new(&token0).address(0)?,
+                token1: AbiDecoder::new(&token1).address(0)?,
+                tick_current: slot0.tick,
+                tick_spacing: AbiDecoder::new(&tick_spacing).i32(0)?,
+                price: slot0.price(),
+                liquidity: AbiDecoder::new(&liquidity).u128(0)?,
+                fee_rate: AbiDecoder::new(&fee).u32(0)?,
+            })
+        };
+        let state = decode().map_err(|e| invalid_data(&address, &e))?;
+
+        debug!(
+            tick = state.tick_current,
+            liquidity = %state.liquidity,
+            "Parsed Uniswap v3 pool state"
+        );
+        Ok(state)
+    }
+
+    async fn position(&self, position_id: &str) -> Result<ClmmPositionState> {
+        let token_id = U256::from_dec_str(position_id)
+            .map_err(|_| ProtocolError::InvalidAddress(position_id.to_string()))?;
+        let owner = self
+            .call_word(
+                self.deployment.position_manager,
+                selectors::OWNER_OF,
+                &[uint_word(token_id)],
+            )
+            .await
+            .and_then(|data| AbiDecoder::new(&data).address(0))
+            .context("Failed to read position owner")?;
+        self.position_state(token_id, Some(owner)).await
+    }
+
+    async fn positions_by_owner(&self, owner: &str) -> Result<Vec<ClmmPositionState>> {
+        let owner = normalize_address(owner)?;
+        info!(owner = %owner, chain = %self.chain, "Fetching Uniswap v3 positions");
+
+        let token_ids = self.token_ids(&owner).await?;
+        try_join_all(
+            token_ids
+                .into_iter()
+                .map(|id| self.position_state(id, Some(owner.clone()))),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl PoolFetcher for UniswapV3Adapter {
+    async fn fetch_pool(&self, pool_address: &str) -> Result<Pool> {
+        let state = self.pool_state(pool_address).await?;
+        let (token0, token1) =
+            futures::try_join!(self.token(&state.token0), self.token(&state.token1))?;
+        Ok(state.to_pool(token0, token1))
+    }
+}
+
+/// Wraps a decoding failure for a contract.
+fn invalid_data(contract: &str, err: &anyhow::Error) -> anyhow::Error {
+    ProtocolError::InvalidAccountData {
+        account: contract.to_string(),
+        message: err.to_string(),
+    }
+    .into()
+}
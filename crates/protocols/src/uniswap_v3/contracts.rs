@@ -0,0 +1,254 @@
+//! Uniswap v3 contract addresses, selectors and return data layouts.
+
+use crate::chain::Chain;
+use crate::evm::abi::AbiDecoder;
+use anyhow::Result;
+use primitive_types::U256;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+
+/// Function selectors of the calls made by the adapter.
+pub mod selectors {
+    /// `slot0()` on a pool.
+    pub const SLOT0: [u8; 4] = [0x38, 0x50, 0xc7, 0xbd];
+    /// `liquidity()` on a pool.
+    pub const LIQUIDITY: [u8; 4] = [0x1a, 0x68, 0x65, 0x02];
+    /// `fee()` on a pool.
+    pub const FEE: [u8; 4] = [0xdd, 0xca, 0x3f, 0x43];
+    /// `tickSpacing()` on a pool.
+    pub const TICK_SPACING: [u8; 4] = [0xd0, 0xc9, 0x3a, 0x7c];
+    /// `token0()` on a pool.
+    pub const TOKEN0: [u8; 4] = [0x0d, 0xfe, 0x16, 0x81];
+    /// `token1()` on a pool.
+    pub const TOKEN1: [u8; 4] = [0xd2, 0x12, 0x20, 0xa7];
+    /// `getPool(address,address,uint24)` on the factory.
+    pub const GET_POOL: [u8; 4] = [0x16, 0x98, 0xee, 0x82];
+    /// `positions(uint256)` on the position manager.
+    pub const POSITIONS: [u8; 4] = [0x99, 0xfb, 0xab, 0x88];
+    /// `ownerOf(uint256)` on the position manager.
+    pub const OWNER_OF: [u8; 4] = [0x63, 0x52, 0x21, 0x1e];
+    /// `balanceOf(address)` on the position manager.
+    pub const BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+    /// `tokenOfOwnerByIndex(address,uint256)` on the position manager.
+    pub const TOKEN_OF_OWNER_BY_INDEX: [u8; 4] = [0x2f, 0x74, 0x5c, 0x59];
+    /// `decimals()` on an ERC-20 token.
+    pub const DECIMALS: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+    /// `symbol()` on an ERC-20 token.
+    pub const SYMBOL: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+}
+
+/// Contract addresses of a Uniswap v3 deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniswapV3Deployment {
+    /// UniswapV3Factory address.
+    pub factory: &'static str,
+    /// NonfungiblePositionManager address.
+    pub position_manager: &'static str,
+}
+
+impl UniswapV3Deployment {
+    /// Canonical deployment on Ethereum, Arbitrum, Optimism and Polygon.
+    pub const CANONICAL: Self = Self {
+        factory: "0x1F98431c8aD98523631AE4a59f267346ea31F984",
+        position_manager: "0xC36442b4a4522E871399CD717aBDD847Ab11FE88",
+    };
+
+    /// Deployment on Base.
+    pub const BASE: Self = Self {
+        factory: "0x33128a8fC17869897dcE68Ed026d694621f6FDfD",
+        position_manager: "0x03a520b32C04BF3bEEf7BEb72E919cf822Ed34f1",
+    };
+
+    /// Gets the official deployment on a chain.
+    #[must_use]
+    pub fn for_chain(chain: Chain) -> Option<Self> {
+        match chain {
+            Chain::Ethereum | Chain::Arbitrum | Chain::Optimism | Chain::Polygon => {
+                Some(Self::CANONICAL)
+            }
+            Chain::Base => Some(Self::BASE),
+            Chain::Solana => None,
+        }
+    }
+}
+
+/// Decoded `slot0()` of a pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot0 {
+    /// Current sqrt price (Q64.96).
+    pub sqrt_price_x96: U256,
+    /// Current tick.
+    pub tick: i32,
+    /// Whether the pool is unlocked.
+    pub unlocked: bool,
+}
+
+impl Slot0 {
+    /// Decodes `slot0()` return data.
+    ///
+    /// # Errors
+    /// Returns an error if the data is malformed.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let decoder = AbiDecoder::new(data);
+        Ok(Self {
+            sqrt_price_x96: decoder.uint(0)?,
+            tick: decoder.i32(1)?,
+            unlocked: decoder.bool(6)?,
+        })
+    }
+
+    /// Gets the price of token 0 in token 1, in raw units.
+    #[must_use]
+    pub fn price(&self) -> Decimal {
+        sqrt_price_x96_to_price(self.sqrt_price_x96)
+    }
+}
+
+/// Decoded `positions(uint256)` of the position manager.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftPosition {
+    /// Token 0 address.
+    pub token0: String,
+    /// Token 1 address.
+    pub token1: String,
+    /// Pool fee in hundredths of a basis point.
+    pub fee: u32,
+    /// Lower tick.
+    pub tick_lower: i32,
+    /// Upper tick.
+    pub tick_upper: i32,
+    /// Liquidity amount.
+    pub liquidity: u128,
+    /// Fee growth inside the range for token 0 at the last update (Q128.128).
+    pub fee_growth_inside_0_last: U256,
+    /// Fee growth inside the range for token 1 at the last update (Q128.128).
+    pub fee_growth_inside_1_last: U256,
+    /// Uncollected token 0, as of the last update.
+    pub tokens_owed_0: u128,
+    /// Uncollected token 1, as of the last update.
+    pub tokens_owed_1: u128,
+}
+
+impl NftPosition {
+    /// Decodes `positions(uint256)` return data.
+    ///
+    /// # Errors
+    /// Returns an error if the data is malformed.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        // (nonce, operator, token0, token1, fee, tickLower, tickUpper,
+        //  liquidity, feeGrowthInside0LastX128, feeGrowthInside1LastX128,
+        //  tokensOwed0, tokensOwed1)
+        let decoder = AbiDecoder::new(data);
+        Ok(Self {
+            token0: decoder.address(2)?,
+            token1: decoder.address(3)?,
+            fee: decoder.u32(4)?,
+            tick_lower: decoder.i32(5)?,
+            tick_upper: decoder.i32(6)?,
+            liquidity: decoder.u128(7)?,
+            fee_growth_inside_0_last: decoder.uint(8)?,
+            fee_growth_inside_1_last: decoder.uint(9)?,
+            tokens_owed_0: decoder.u128(10)?,
+            tokens_owed_1: decoder.u128(11)?,
+        })
+    }
+}
+
+/// Converts a Q64.96 sqrt price to a price in raw units.
+///
+/// price = (sqrt_price / 2^96)^2
+#[must_use]
+pub fn sqrt_price_x96_to_price(sqrt_price_x96: U256) -> Decimal {
+    let high = (sqrt_price_x96 >> 128).low_u128() as f64;
+    let low = sqrt_price_x96.low_u128() as f64;
+    let sqrt_price = (high * 2f64.powi(128) + low) / 2f64.powi(96);
+    Decimal::from_f64(sqrt_price * sqrt_price).unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: i64) -> [u8; 32] {
+        let fill = if value < 0 { 0xff } else { 0x00 };
+        let mut word = [fill; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    fn address(byte: u8) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[12..].fill(byte);
+        word
+    }
+
+    #[test]
+    fn test_decode_slot0() {
+        // Price 1 at tick 0
+        let mut data = Vec::new();
+        data.extend_from_slice(&(U256::one() << 96).to_big_endian());
+        for value in [0, 1, 1, 1, 0, 1] {
+            data.extend_from_slice(&word(value));
+        }
+        let slot0 = Slot0::decode(&data).unwrap();
+        assert_eq!(slot0.tick, 0);
+        assert!(slot0.unlocked);
+        assert_eq!(slot0.price(), Decimal::ONE);
+
+        data[32..64].copy_from_slice(&word(-200_000));
+        // floor(sqrt(1.0001^-200_000) * 2^96)
+        data[..32]
+            .copy_from_slice(&U256::from(3_598_751_819_609_688_046_946_418u128).to_big_endian());
+        let slot0 = Slot0::decode(&data).unwrap();
+        assert_eq!(slot0.tick, -200_000);
+        let expected = 1.0001f64.powi(-200_000);
+        let price: f64 = slot0.price().to_string().parse().unwrap();
+        assert!((price - expected).abs() / expected < 1e-6);
+
+        assert!(Slot0::decode(&data[..64]).is_err());
+    }
+
+    #[test]
+    fn test_decode_position() {
+        let mut data = Vec::new();
+        for w in [
+            word(0),
+            address(0),
+            address(0x11),
+            address(0x22),
+            word(500),
+            word(-887_270),
+            word(887_270),
+            word(1_000_000),
+            word(7),
+            word(9),
+            word(123),
+            word(456),
+        ] {
+            data.extend_from_slice(&w);
+        }
+
+        let position = NftPosition::decode(&data).unwrap();
+        assert_eq!(position.token0, format!("0x{}", "11".repeat(20)));
+        assert_eq!(position.fee, 500);
+        assert_eq!(position.tick_lower, -887_270);
+        assert_eq!(position.tick_upper, 887_270);
+        assert_eq!(position.liquidity, 1_000_000);
+        assert_eq!(position.fee_growth_inside_1_last, U256::from(9));
+        assert_eq!(position.tokens_owed_0, 123);
+        assert_eq!(position.tokens_owed_1, 456);
+    }
+
+    #[test]
+    fn test_deployments() {
+        assert_eq!(
+            UniswapV3Deployment::for_chain(Chain::Arbitrum),
+            Some(UniswapV3Deployment::CANONICAL)
+        );
+        assert_eq!(
+            UniswapV3Deployment::for_chain(Chain::Base),
+            Some(UniswapV3Deployment::BASE)
+        );
+        assert_eq!(UniswapV3Deployment::for_chain(Chain::Solana), None);
+    }
+}
@@ -0,0 +1,11 @@
+//! Uniswap v3 adapter.
+//!
+//! Reads pools directly and positions through the NonfungiblePositionManager,
+//! which holds every position as an ERC-721 token. The same contracts are
+//! deployed on Ethereum and its L2s.
+
+/// JSON-RPC adapter.
+#[cfg(feature = "evm")]
+pub mod adapter;
+/// Contract addresses, selectors and return data layouts.
+pub mod contracts;
@@ -0,0 +1,37 @@
+//! Conversions between Python floats and `Decimal`.
+
+use pyo3::PyResult;
+use pyo3::exceptions::PyValueError;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Converts a Python float argument to a `Decimal`.
+///
+/// # Errors
+/// Returns a `ValueError` naming the argument if it is NaN or infinite.
+pub(crate) fn decimal(name: &str, value: f64) -> PyResult<Decimal> {
+    Decimal::from_f64(value)
+        .ok_or_else(|| PyValueError::new_err(format!("{} must be a finite number", name)))
+}
+
+/// Converts a `Decimal` result to a Python float.
+pub(crate) fn float(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Converts a list of Python floats to `Decimal`s.
+///
+/// # Errors
+/// Returns a `ValueError` if any value is NaN or infinite.
+pub(crate) fn decimals(name: &str, values: &[f64]) -> PyResult<Vec<Decimal>> {
+    values.iter().map(|v| decimal(name, *v)).collect()
+}
+
+/// Returns a `ValueError` unless `lower < upper`.
+pub(crate) fn check_range(lower: Decimal, upper: Decimal) -> PyResult<()> {
+    if lower < upper {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err("lower must be below upper"))
+    }
+}
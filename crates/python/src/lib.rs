@@ -0,0 +1,41 @@
+//! Python bindings for the CLMM Liquidity Provider engine.
+//!
+//! Exposes price-path generation, position simulation, backtesting and the
+//! range and parameter optimizers as the `clmm_lp_py` extension module.
+//! Prices and amounts cross the boundary as floats and are converted to
+//! `Decimal` on the Rust side.
+//!
+//! ```python
+//! import clmm_lp_py as clmm
+//!
+//! prices = clmm.gbm_price_path(100.0, volatility=0.6, steps=720, seed=42)
+//! result = clmm.backtest(prices, lower=90.0, upper=110.0, strategy="threshold")
+//! ranges = clmm.optimize_range(100.0, volatility=0.6, objective="sharpe")
+//! ```
+
+/// Float and `Decimal` conversions.
+mod convert;
+/// Range and parameter optimizer bindings.
+pub mod optimization;
+/// Price path, simulation and backtest bindings.
+pub mod simulation;
+
+use pyo3::prelude::*;
+
+/// The `clmm_lp_py` Python module.
+#[pymodule]
+fn clmm_lp_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+
+    m.add_function(wrap_pyfunction!(simulation::gbm_price_path, m)?)?;
+    m.add_function(wrap_pyfunction!(simulation::simulate_position, m)?)?;
+    m.add_function(wrap_pyfunction!(simulation::backtest, m)?)?;
+    m.add_class::<simulation::PositionSimulation>()?;
+    m.add_class::<simulation::BacktestResult>()?;
+
+    m.add_function(wrap_pyfunction!(optimization::optimize_range, m)?)?;
+    m.add_function(wrap_pyfunction!(optimization::optimize_params, m)?)?;
+    m.add_class::<optimization::RangeCandidate>()?;
+    m.add_class::<optimization::ParameterCandidate>()?;
+    Ok(())
+}
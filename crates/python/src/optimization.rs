@@ -0,0 +1,418 @@
+//! Range and parameter optimizer bindings.
+
+use crate::convert::{decimal, decimals, float};
+use clmm_lp_domain::prelude::{Amount, Position, PositionId, PositionStatus};
+use clmm_lp_optimization::prelude::{
+    MaximizeFees, MaximizeNetPnL, MaximizeSharpeRatio, MaximizeTimeInRange, MinimizeIL,
+    ObjectiveFunction, OptimizationConfig, ParameterOptimizer, RangeOptimizer, RiskAdjustedReturn,
+};
+use clmm_lp_simulation::prelude::ConstantVolume;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Objectives accepted by the optimizers.
+const OBJECTIVES: &str = "pnl, fees, sharpe, min_il, time_in_range or risk_adjusted";
+
+/// Evaluates `$body` with `$objective` bound to the objective named by
+/// `$name`, returning a `ValueError` from the function for unknown names.
+macro_rules! with_objective {
+    ($name:expr, |$objective:ident| $body:expr) => {
+        match $name {
+            "pnl" => {
+                let $objective = &MaximizeNetPnL;
+                $body
+            }
+            "fees" => {
+                let $objective = &MaximizeFees;
+                $body
+            }
+            "sharpe" => {
+                let $objective = &MaximizeSharpeRatio::default();
+                $body
+            }
+            "min_il" => {
+                let $objective = &MinimizeIL::default();
+                $body
+            }
+            "time_in_range" => {
+                let $objective = &MaximizeTimeInRange;
+                $body
+            }
+            "risk_adjusted" => {
+                let $objective = &RiskAdjustedReturn::default();
+                $body
+            }
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown objective '{}', expected {}",
+                    other, OBJECTIVES
+                )));
+            }
+        }
+    };
+}
+
+/// A candidate price range ranked by the range optimizer.
+#[pyclass(get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct RangeCandidate {
+    /// Rank, starting at 1.
+    pub rank: usize,
+    /// Half-width of the range as a fraction of the price.
+    pub range_width: f64,
+    /// Lower bound around the current price.
+    pub lower_price: f64,
+    /// Upper bound around the current price.
+    pub upper_price: f64,
+    /// Expected fees.
+    pub expected_fees: f64,
+    /// Expected impermanent loss.
+    pub expected_il: f64,
+    /// Expected net PnL.
+    pub net_pnl: f64,
+    /// Objective score.
+    pub score: f64,
+}
+
+#[pymethods]
+impl RangeCandidate {
+    fn __repr__(&self) -> String {
+        format!(
+            "RangeCandidate(rank={}, lower={:.4}, upper={:.4}, score={:.4})",
+            self.rank, self.lower_price, self.upper_price, self.score
+        )
+    }
+}
+
+/// Ranks candidate range widths by Monte Carlo simulation.
+///
+/// Every width is simulated over `iterations` GBM paths of `steps` steps
+/// of `time_step` years. `widths` overrides the default 1%-50% grid and a
+/// `seed` makes the ranking reproducible.
+///
+/// # Errors
+/// Returns a `ValueError` for an unknown objective, an empty width list,
+/// or arguments that are not finite.
+#[pyfunction]
+#[pyo3(signature = (
+    current_price,
+    volatility,
+    objective = "pnl",
+    iterations = 100,
+    steps = 30,
+    time_step = 1.0 / 365.0,
+    drift = 0.0,
+    volume = 1_000_000.0,
+    pool_liquidity = 1_000_000,
+    fee_rate = 0.003,
+    widths = None,
+    seed = None,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn optimize_range(
+    current_price: f64,
+    volatility: f64,
+    objective: &str,
+    iterations: usize,
+    steps: usize,
+    time_step: f64,
+    drift: f64,
+    volume: f64,
+    pool_liquidity: u128,
+    fee_rate: f64,
+    widths: Option<Vec<f64>>,
+    seed: Option<u64>,
+) -> PyResult<Vec<RangeCandidate>> {
+    let price = decimal("current_price", current_price)?;
+    let volume = ConstantVolume::new(decimal("volume", volume)?);
+    let fee_rate = decimal("fee_rate", fee_rate)?;
+
+    let mut optimizer = RangeOptimizer::new(iterations, steps, time_step);
+    if let Some(widths) = widths {
+        optimizer = optimizer.with_widths(decimals("widths", &widths)?);
+    }
+    if optimizer.range_widths.is_empty() {
+        return Err(PyValueError::new_err("widths must not be empty"));
+    }
+    if let Some(seed) = seed {
+        optimizer = optimizer.with_seed(seed);
+    }
+    let position = base_position();
+
+    let candidates = with_objective!(objective, |objective| {
+        optimizer.rank(
+            &position,
+            price,
+            volatility,
+            drift,
+            &volume,
+            pool_liquidity,
+            fee_rate,
+            objective,
+        )
+    });
+    Ok(candidates
+        .iter()
+        .enumerate()
+        .map(|(i, c)| RangeCandidate {
+            rank: i + 1,
+            range_width: float(c.range_width),
+            lower_price: float(price * (Decimal::ONE - c.range_width)),
+            upper_price: float(price * (Decimal::ONE + c.range_width)),
+            expected_fees: float(c.expected_fees),
+            expected_il: float(c.expected_il),
+            net_pnl: float(c.net_pnl),
+            score: float(c.score),
+        })
+        .collect())
+}
+
+/// A candidate set of rebalancing parameters.
+#[pyclass(get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct ParameterCandidate {
+    /// Rank, starting at 1.
+    pub rank: usize,
+    /// Strategy the parameters apply to.
+    pub strategy: String,
+    /// Strategy parameters by name.
+    pub params: HashMap<String, f64>,
+    /// Expected fees.
+    pub expected_fees: f64,
+    /// Expected impermanent loss.
+    pub expected_il: f64,
+    /// Expected number of rebalances.
+    pub expected_rebalances: u32,
+    /// Objective score.
+    pub score: f64,
+}
+
+#[pymethods]
+impl ParameterCandidate {
+    fn __repr__(&self) -> String {
+        format!(
+            "ParameterCandidate(rank={}, strategy='{}', score={:.4})",
+            self.rank, self.strategy, self.score
+        )
+    }
+}
+
+/// Ranks rebalancing parameters for a range width.
+///
+/// `strategy` is `threshold`, `periodic` or `il_limit`. Strategy steps are
+/// `time_step` years long, so intervals and grace periods are in steps.
+///
+/// # Errors
+/// Returns a `ValueError` for an unknown strategy or objective, or
+/// arguments that are not finite.
+#[pyfunction]
+#[pyo3(signature = (
+    current_price,
+    volatility,
+    range_width,
+    strategy = "threshold",
+    objective = "pnl",
+    iterations = 100,
+    steps = 720,
+    time_step = 1.0 / 8760.0,
+    pool_liquidity = 1_000_000,
+    fee_rate = 0.003,
+    tx_cost = 1.0,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn optimize_params(
+    current_price: f64,
+    volatility: f64,
+    range_width: f64,
+    strategy: &str,
+    objective: &str,
+    iterations: usize,
+    steps: usize,
+    time_step: f64,
+    pool_liquidity: u128,
+    fee_rate: f64,
+    tx_cost: f64,
+) -> PyResult<Vec<ParameterCandidate>> {
+    let mut config = OptimizationConfig::new()
+        .with_iterations(iterations)
+        .with_steps(steps)
+        .with_volatility(volatility)
+        .with_price(decimal("current_price", current_price)?)
+        .with_fee_rate(decimal("fee_rate", fee_rate)?);
+    config.time_step_years = time_step;
+    config.pool_liquidity = pool_liquidity;
+    config.tx_cost = decimal("tx_cost", tx_cost)?;
+    let width = decimal("range_width", range_width)?;
+    let optimizer = ParameterOptimizer::new();
+
+    let candidates = with_objective!(objective, |objective| {
+        rank_params(&optimizer, &config, width, strategy, objective)?
+    });
+    Ok(candidates
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| ParameterCandidate { rank: i + 1, ..c })
+        .collect())
+}
+
+/// Runs the parameter search of a strategy, unranked.
+fn rank_params<O: ObjectiveFunction>(
+    optimizer: &ParameterOptimizer,
+    config: &OptimizationConfig,
+    width: Decimal,
+    strategy: &str,
+    objective: &O,
+) -> PyResult<Vec<ParameterCandidate>> {
+    let candidate = |params: Vec<(&str, f64)>,
+                     expected_fees: Decimal,
+                     expected_il: Decimal,
+                     expected_rebalances: u32,
+                     score: Decimal| ParameterCandidate {
+        rank: 0,
+        strategy: strategy.to_string(),
+        params: params
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+        expected_fees: float(expected_fees),
+        expected_il: float(expected_il),
+        expected_rebalances,
+        score: float(score),
+    };
+
+    let candidates = match strategy {
+        "threshold" => optimizer
+            .optimize_threshold(config, width, objective)
+            .into_iter()
+            .map(|c| {
+                let params = vec![
+                    ("price_threshold", float(c.params.price_threshold)),
+                    ("il_threshold", float(c.params.il_threshold)),
+                ];
+                candidate(
+                    params,
+                    c.expected_fees,
+                    c.expected_il,
+                    c.expected_rebalances,
+                    c.score,
+                )
+            })
+            .collect(),
+        "periodic" => optimizer
+            .optimize_periodic(config, width, objective)
+            .into_iter()
+            .map(|c| {
+                let params = vec![("interval", c.params.interval as f64)];
+                candidate(
+                    params,
+                    c.expected_fees,
+                    c.expected_il,
+                    c.expected_rebalances,
+                    c.score,
+                )
+            })
+            .collect(),
+        "il_limit" => optimizer
+            .optimize_il_limit(config, width, objective)
+            .into_iter()
+            .map(|c| {
+                let mut params = vec![
+                    ("max_il", float(c.params.max_il)),
+                    ("grace_period", c.params.grace_period as f64),
+                ];
+                if let Some(close_il) = c.params.close_il {
+                    params.push(("close_il", float(close_il)));
+                }
+                candidate(
+                    params,
+                    c.expected_fees,
+                    c.expected_il,
+                    c.expected_rebalances,
+                    c.score,
+                )
+            })
+            .collect(),
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "unknown strategy '{}', expected threshold, periodic or il_limit",
+                other
+            )));
+        }
+    };
+    Ok(candidates)
+}
+
+/// Returns the empty position the optimizers size ranges for.
+fn base_position() -> Position {
+    let zero = Amount::from_decimal(Decimal::ZERO, 6);
+    Position {
+        id: PositionId(Uuid::new_v4()),
+        pool_address: "python".to_string(),
+        owner_address: "python".to_string(),
+        liquidity_amount: 0,
+        deposited_amount_a: zero,
+        deposited_amount_b: zero,
+        current_amount_a: zero,
+        current_amount_b: zero,
+        unclaimed_fees_a: zero,
+        unclaimed_fees_b: zero,
+        range: None,
+        opened_at: 0,
+        status: PositionStatus::Open,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimize_range_ranks_widths() {
+        let candidates = optimize_range(
+            100.0,
+            0.5,
+            "pnl",
+            5,
+            5,
+            1.0 / 365.0,
+            0.0,
+            1_000_000.0,
+            1_000_000,
+            0.003,
+            Some(vec![0.05, 0.1, 0.2]),
+            Some(1),
+        )
+        .unwrap();
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].rank, 1);
+        assert!(candidates[0].score >= candidates[2].score);
+        assert!(candidates[0].lower_price < 100.0 && candidates[0].upper_price > 100.0);
+    }
+
+    #[test]
+    fn test_optimize_params_rejects_unknown_names() {
+        let run = |strategy, objective| {
+            optimize_params(
+                100.0,
+                0.5,
+                0.1,
+                strategy,
+                objective,
+                5,
+                24,
+                1.0 / 8760.0,
+                1_000_000,
+                0.003,
+                1.0,
+            )
+        };
+        let periodic = run("periodic", "fees").unwrap();
+        assert!(!periodic.is_empty());
+        assert!(periodic[0].params.contains_key("interval"));
+        assert!(run("grid", "pnl").is_err());
+        assert!(run("threshold", "luck").is_err());
+    }
+}
@@ -0,0 +1,436 @@
+//! Price path, position simulation and backtest bindings.
+
+use crate::convert::{check_range, decimal, decimals, float};
+use clmm_lp_domain::prelude::{Price, PriceRange};
+use clmm_lp_simulation::prelude::{
+    ConstantLiquidity, ConstantVolume, DeterministicPricePath, GeometricBrownianMotion,
+    ILLimitStrategy, PeriodicRebalance, PositionTracker, PricePathGenerator, RebalanceAction,
+    RebalanceStrategy, SimulationConfig, StaticRange, ThresholdRebalance,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+
+/// Generates a geometric Brownian motion price path.
+///
+/// `volatility` and `drift` are annualized; `time_step` is the length of a
+/// step in years. Returns `steps + 1` prices starting at `initial_price`;
+/// a `seed` makes the path reproducible.
+///
+/// # Errors
+/// Returns a `ValueError` if `initial_price` is not a finite number.
+#[pyfunction]
+#[pyo3(signature = (initial_price, volatility, steps, drift = 0.0, time_step = 1.0 / 365.0, seed = None))]
+pub fn gbm_price_path(
+    initial_price: f64,
+    volatility: f64,
+    steps: usize,
+    drift: f64,
+    time_step: f64,
+    seed: Option<u64>,
+) -> PyResult<Vec<f64>> {
+    let mut gbm = GeometricBrownianMotion::new(
+        decimal("initial_price", initial_price)?,
+        drift,
+        volatility,
+        time_step,
+    );
+    if let Some(seed) = seed {
+        gbm = gbm.with_seed(seed);
+    }
+    Ok(gbm
+        .generate(steps)
+        .into_iter()
+        .map(|p| float(p.value))
+        .collect())
+}
+
+/// Result of a static position simulation.
+#[pyclass(get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct PositionSimulation {
+    /// Number of simulated steps.
+    pub total_steps: u64,
+    /// Steps spent inside the range.
+    pub steps_in_range: u64,
+    /// Final position value.
+    pub final_value: f64,
+    /// Fees earned.
+    pub total_fees: f64,
+    /// Final impermanent loss as a fraction.
+    pub final_il_pct: f64,
+    /// Worst impermanent loss as a fraction.
+    pub max_il_pct: f64,
+    /// Net PnL (fees minus IL).
+    pub net_pnl: f64,
+    /// Net PnL as a fraction of capital.
+    pub net_pnl_pct: f64,
+    /// Maximum drawdown as a fraction.
+    pub max_drawdown_pct: f64,
+    /// Value of holding the initial tokens.
+    pub hodl_value: f64,
+    /// Final value minus the HODL value.
+    pub vs_hodl: f64,
+    /// Net PnL after every step.
+    pub pnl_history: Vec<f64>,
+    /// Impermanent loss after every step.
+    pub il_history: Vec<f64>,
+    /// Cumulative fees after every step.
+    pub fee_history: Vec<f64>,
+}
+
+#[pymethods]
+impl PositionSimulation {
+    fn __repr__(&self) -> String {
+        format!(
+            "PositionSimulation(steps={}, net_pnl={:.4}, total_fees={:.4}, final_il_pct={:.4})",
+            self.total_steps, self.net_pnl, self.total_fees, self.final_il_pct
+        )
+    }
+}
+
+/// Simulates a static LP position over a price path.
+///
+/// Each in-range step earns `volume * fee_rate` scaled by the position's
+/// share of pool liquidity, `position_liquidity / pool_liquidity`.
+///
+/// # Errors
+/// Returns a `ValueError` if a price is not finite or the range is empty.
+#[pyfunction]
+#[pyo3(signature = (
+    prices,
+    lower,
+    upper,
+    capital = 1000.0,
+    fee_rate = 0.003,
+    volume = 1_000_000.0,
+    position_liquidity = 10_000,
+    pool_liquidity = 1_000_000,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_position(
+    prices: Vec<f64>,
+    lower: f64,
+    upper: f64,
+    capital: f64,
+    fee_rate: f64,
+    volume: f64,
+    position_liquidity: u128,
+    pool_liquidity: u128,
+) -> PyResult<PositionSimulation> {
+    let prices = decimals("prices", &prices)?;
+    let (lower, upper) = (decimal("lower", lower)?, decimal("upper", upper)?);
+    check_range(lower, upper)?;
+
+    let mut config = SimulationConfig::new(
+        decimal("capital", capital)?,
+        PriceRange::new(Price::new(lower), Price::new(upper)),
+    );
+    config.fee_rate = decimal("fee_rate", fee_rate)?;
+    config.pool_liquidity = position_liquidity;
+    config.steps = prices.len();
+
+    let result = clmm_lp_simulation::prelude::simulate_position(
+        &config,
+        &mut DeterministicPricePath::new(prices),
+        &mut ConstantVolume::new(decimal("volume", volume)?),
+        &ConstantLiquidity::new(pool_liquidity),
+    );
+    let summary = &result.summary;
+    let floats = |values: &[Decimal]| values.iter().copied().map(float).collect();
+
+    Ok(PositionSimulation {
+        total_steps: summary.total_steps,
+        steps_in_range: summary.steps_in_range,
+        final_value: float(summary.final_value),
+        total_fees: float(summary.total_fees),
+        final_il_pct: float(summary.final_il_pct),
+        max_il_pct: float(summary.max_il_pct),
+        net_pnl: float(summary.net_pnl),
+        net_pnl_pct: float(summary.net_pnl_pct),
+        max_drawdown_pct: float(summary.max_drawdown_pct),
+        hodl_value: float(summary.hodl_value),
+        vs_hodl: float(summary.vs_hodl),
+        pnl_history: floats(&result.pnl_history),
+        il_history: floats(&result.il_history),
+        fee_history: floats(&result.fee_history),
+    })
+}
+
+/// Result of a backtest.
+#[pyclass(get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    /// Number of replayed steps.
+    pub total_steps: u64,
+    /// Final position value.
+    pub final_value: f64,
+    /// Final PnL.
+    pub final_pnl: f64,
+    /// Final impermanent loss as a fraction.
+    pub final_il_pct: f64,
+    /// Fees earned.
+    pub total_fees: f64,
+    /// Share of steps spent inside the range.
+    pub time_in_range_pct: f64,
+    /// Number of rebalances.
+    pub rebalance_count: u32,
+    /// Cost of all rebalances.
+    pub total_rebalance_cost: f64,
+    /// Maximum drawdown as a fraction.
+    pub max_drawdown: f64,
+    /// Value of holding the initial tokens.
+    pub hodl_value: f64,
+    /// Final value minus the HODL value.
+    pub vs_hodl: f64,
+    /// Position value after every step.
+    pub equity_curve: Vec<f64>,
+    /// Steps at which the position was rebalanced.
+    pub rebalance_steps: Vec<u64>,
+}
+
+#[pymethods]
+impl BacktestResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "BacktestResult(steps={}, final_pnl={:.4}, total_fees={:.4}, rebalances={})",
+            self.total_steps, self.final_pnl, self.total_fees, self.rebalance_count
+        )
+    }
+}
+
+/// Backtests a position with a rebalancing strategy over a price path.
+///
+/// `strategy` is one of `static`, `periodic` (every `rebalance_interval`
+/// steps), `threshold` (price moves beyond `threshold_pct`) or `il_limit`
+/// (IL beyond `max_il_pct`); rebalances keep the initial range width.
+/// Each in-range step earns its volume times `fee_rate` and
+/// `liquidity_share`. `volumes` gives one volume per step; otherwise every
+/// step trades `volume`.
+///
+/// # Errors
+/// Returns a `ValueError` for an unknown strategy, an empty range, or
+/// `volumes` of a different length than `prices`.
+#[pyfunction]
+#[pyo3(signature = (
+    prices,
+    lower,
+    upper,
+    capital = 1000.0,
+    strategy = "static",
+    rebalance_interval = 24,
+    threshold_pct = 0.05,
+    max_il_pct = 0.05,
+    fee_rate = 0.003,
+    volume = 1_000_000.0,
+    volumes = None,
+    liquidity_share = 0.01,
+    tx_cost = 1.0,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn backtest(
+    prices: Vec<f64>,
+    lower: f64,
+    upper: f64,
+    capital: f64,
+    strategy: &str,
+    rebalance_interval: u64,
+    threshold_pct: f64,
+    max_il_pct: f64,
+    fee_rate: f64,
+    volume: f64,
+    volumes: Option<Vec<f64>>,
+    liquidity_share: f64,
+    tx_cost: f64,
+) -> PyResult<BacktestResult> {
+    let prices = decimals("prices", &prices)?;
+    let (lower, upper) = (decimal("lower", lower)?, decimal("upper", upper)?);
+    check_range(lower, upper)?;
+    let volumes = match volumes {
+        Some(volumes) if volumes.len() != prices.len() => {
+            return Err(PyValueError::new_err(
+                "volumes must have one entry per price",
+            ));
+        }
+        Some(volumes) => decimals("volumes", &volumes)?,
+        None => vec![decimal("volume", volume)?; prices.len()],
+    };
+    let Some(entry_price) = prices.first() else {
+        return Err(PyValueError::new_err("prices must not be empty"));
+    };
+
+    let range_width_pct = (upper - lower) / ((upper + lower) / Decimal::TWO);
+    let mut tracker = PositionTracker::new(
+        decimal("capital", capital)?,
+        Price::new(*entry_price),
+        PriceRange::new(Price::new(lower), Price::new(upper)),
+        decimal("tx_cost", tx_cost)?,
+    )
+    .with_capacity(prices.len());
+    let fee_per_volume =
+        decimal("fee_rate", fee_rate)? * decimal("liquidity_share", liquidity_share)?;
+
+    match strategy {
+        "static" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &StaticRange::new(),
+        ),
+        "periodic" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &PeriodicRebalance::new(rebalance_interval, range_width_pct),
+        ),
+        "threshold" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &ThresholdRebalance::new(decimal("threshold_pct", threshold_pct)?, range_width_pct),
+        ),
+        "il_limit" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &ILLimitStrategy::new(decimal("max_il_pct", max_il_pct)?, range_width_pct),
+        ),
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "unknown strategy '{}', expected static, periodic, threshold or il_limit",
+                other
+            )));
+        }
+    }
+
+    let summary = tracker.summary();
+    Ok(BacktestResult {
+        total_steps: summary.total_steps,
+        final_value: float(summary.final_value),
+        final_pnl: float(summary.final_pnl),
+        final_il_pct: float(summary.final_il_pct),
+        total_fees: float(summary.total_fees),
+        time_in_range_pct: float(summary.time_in_range_pct),
+        rebalance_count: summary.rebalance_count,
+        total_rebalance_cost: float(summary.total_rebalance_cost),
+        max_drawdown: float(summary.max_drawdown),
+        hodl_value: float(summary.hodl_value),
+        vs_hodl: float(summary.vs_hodl),
+        equity_curve: tracker.equity_curve().iter().copied().map(float).collect(),
+        rebalance_steps: tracker
+            .actions()
+            .iter()
+            .filter(|(_, action)| matches!(action, RebalanceAction::Rebalance { .. }))
+            .map(|(step, _)| *step)
+            .collect(),
+    })
+}
+
+/// Steps the tracker through every price with a strategy.
+fn replay<S: RebalanceStrategy>(
+    tracker: &mut PositionTracker,
+    prices: &[Decimal],
+    volumes: &[Decimal],
+    fee_per_volume: Decimal,
+    strategy: &S,
+) {
+    for (price, volume) in prices.iter().zip(volumes) {
+        let in_range = *price >= tracker.current_range.lower_price.value
+            && *price <= tracker.current_range.upper_price.value;
+        let fees = if in_range {
+            *volume * fee_per_volume
+        } else {
+            Decimal::ZERO
+        };
+        tracker.record_step(Price::new(*price), fees, Some(strategy));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gbm_price_path_is_seeded() {
+        let first = gbm_price_path(100.0, 0.5, 50, 0.0, 1.0 / 365.0, Some(7)).unwrap();
+        let second = gbm_price_path(100.0, 0.5, 50, 0.0, 1.0 / 365.0, Some(7)).unwrap();
+        assert_eq!(first.len(), 51);
+        assert_eq!(first, second);
+        assert!(gbm_price_path(f64::NAN, 0.5, 50, 0.0, 1.0, None).is_err());
+    }
+
+    #[test]
+    fn test_simulate_position_in_range_earns_fees() {
+        let result = simulate_position(
+            vec![100.0; 10],
+            90.0,
+            110.0,
+            1000.0,
+            0.003,
+            1000.0,
+            10,
+            1000,
+        )
+        .unwrap();
+        assert_eq!(result.steps_in_range, 10);
+        assert!(result.total_fees > 0.0);
+        assert_eq!(result.fee_history.len(), 10);
+    }
+
+    #[test]
+    fn test_backtest_strategies() {
+        let prices: Vec<f64> = (0..48).map(|i| 100.0 + f64::from(i)).collect();
+
+        let fixed = backtest(
+            prices.clone(),
+            95.0,
+            105.0,
+            1000.0,
+            "static",
+            24,
+            0.05,
+            0.05,
+            0.003,
+            1_000_000.0,
+            None,
+            0.01,
+            1.0,
+        )
+        .unwrap();
+        assert_eq!(fixed.total_steps, 48);
+        assert_eq!(fixed.rebalance_count, 0);
+        assert_eq!(fixed.equity_curve.len(), 48);
+
+        let threshold = backtest(
+            prices.clone(),
+            95.0,
+            105.0,
+            1000.0,
+            "threshold",
+            24,
+            0.05,
+            0.05,
+            0.003,
+            1_000_000.0,
+            None,
+            0.01,
+            1.0,
+        )
+        .unwrap();
+        assert!(threshold.rebalance_count > 0);
+        assert_eq!(
+            threshold.rebalance_steps.len(),
+            threshold.rebalance_count as usize
+        );
+        assert!(threshold.total_fees > fixed.total_fees);
+
+        let unknown = backtest(
+            prices, 95.0, 105.0, 1000.0, "yolo", 24, 0.05, 0.05, 0.003, 1.0, None, 0.01, 1.0,
+        );
+        assert!(unknown.is_err());
+    }
+}
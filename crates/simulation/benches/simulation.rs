@@ -0,0 +1,166 @@
+//! Benchmarks of the `Decimal` and `f64` simulation paths.
+//!
+//! Run with `cargo bench -p clmm-lp-simulation`.
+
+use clmm_lp_domain::prelude::{Amount, Position, PositionId, PositionStatus, Price, PriceRange};
+use clmm_lp_simulation::prelude::*;
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Steps per simulated path: 30 days of hourly prices.
+const STEPS: usize = 720;
+
+/// Steps per backtest: 30 days of minute prices.
+const MINUTE_STEPS: usize = 43_200;
+
+fn position() -> Position {
+    let zero = Amount::from_decimal(Decimal::ZERO, 6);
+    Position {
+        id: PositionId(Uuid::new_v4()),
+        pool_address: "bench".to_string(),
+        owner_address: "bench".to_string(),
+        liquidity_amount: 1_000,
+        deposited_amount_a: zero,
+        deposited_amount_b: zero,
+        current_amount_a: zero,
+        current_amount_b: zero,
+        unclaimed_fees_a: zero,
+        unclaimed_fees_b: zero,
+        range: Some(PriceRange::new(
+            Price::new(Decimal::from(80)),
+            Price::new(Decimal::from(120)),
+        )),
+        opened_at: 0,
+        status: PositionStatus::Open,
+    }
+}
+
+fn gbm(seed: u64) -> GeometricBrownianMotion {
+    GeometricBrownianMotion::new(Decimal::from(100), 0.0, 0.6, 1.0 / 8760.0).with_seed(seed)
+}
+
+fn engine(
+    seed: u64,
+) -> SimulationEngine<GeometricBrownianMotion, ConstantVolume, ConstantLiquidity> {
+    SimulationEngine::new(
+        position(),
+        gbm(seed),
+        ConstantVolume::new(Decimal::from(50_000)),
+        ConstantLiquidity::new(100_000),
+        Decimal::new(3, 3),
+        STEPS,
+    )
+}
+
+fn bench_price_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gbm_path");
+    group.bench_function("decimal", |b| {
+        let mut gbm = gbm(1);
+        b.iter(|| black_box(gbm.generate(STEPS)));
+    });
+    group.bench_function("f64", |b| {
+        let mut gbm = gbm(1);
+        b.iter(|| black_box(gbm.generate_f64(STEPS)));
+    });
+    group.finish();
+}
+
+fn bench_engine(c: &mut Criterion) {
+    let mut group = c.benchmark_group("engine");
+    group.bench_function("decimal", |b| {
+        let mut engine = engine(1);
+        b.iter(|| black_box(engine.run()));
+    });
+    group.bench_function("f64", |b| {
+        let mut engine = engine(1);
+        b.iter(|| black_box(engine.run_fast()));
+    });
+    group.finish();
+}
+
+fn bench_monte_carlo(c: &mut Criterion) {
+    let mut group = c.benchmark_group("monte_carlo");
+    group.sample_size(20);
+    for iterations in [100, 500] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(iterations),
+            &iterations,
+            |b, &iterations| {
+                b.iter(|| {
+                    let mut runner = MonteCarloRunner {
+                        position: position(),
+                        volume_model: ConstantVolume::new(Decimal::from(50_000)),
+                        liquidity_model: ConstantLiquidity::new(100_000),
+                        fee_rate: Decimal::new(3, 3),
+                        initial_price: Decimal::from(100),
+                        drift: 0.0,
+                        volatility: 0.6,
+                        time_step: 1.0 / 8760.0,
+                        steps: STEPS,
+                        iterations,
+                        seed: Some(1),
+                    };
+                    black_box(runner.run())
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn minute_prices() -> Vec<Price> {
+    GeometricBrownianMotion::new(Decimal::from(100), 0.0, 0.6, 1.0 / 525_600.0)
+        .with_seed(1)
+        .generate(MINUTE_STEPS - 1)
+}
+
+fn bench_backtest(c: &mut Criterion) {
+    let prices = minute_prices();
+    let range = PriceRange::new(
+        Price::new(Decimal::from(95)),
+        Price::new(Decimal::from(105)),
+    );
+    let strategy = ThresholdRebalance::new(Decimal::new(5, 2), Decimal::new(10, 2));
+
+    let mut group = c.benchmark_group("backtest_30d_minutes");
+    group.sample_size(10);
+    group.bench_function("position_tracker", |b| {
+        b.iter(|| {
+            let mut tracker = PositionTracker::new(
+                Decimal::from(10_000),
+                prices[0],
+                range.clone(),
+                Decimal::ONE,
+            );
+            for price in &prices {
+                tracker.record_step(*price, Decimal::new(1, 2), Some(&strategy));
+            }
+            black_box(tracker.summary())
+        });
+    });
+    group.bench_function("strategy_simulator", |b| {
+        let config = SimulationConfig::new(Decimal::from(10_000), range.clone())
+            .with_steps(prices.len())
+            .with_rebalance_cost(Decimal::ONE);
+        b.iter(|| {
+            black_box(simulate_with_strategy(
+                &config,
+                &mut DeterministicPricePath::from_prices(prices.clone()),
+                &mut ConstantVolume::new(Decimal::from(50_000)),
+                &ConstantLiquidity::new(100_000),
+                &strategy,
+            ))
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_price_path,
+    bench_engine,
+    bench_monte_carlo,
+    bench_backtest
+);
+criterion_main!(benches);
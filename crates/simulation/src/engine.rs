@@ -6,6 +6,7 @@ use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
 use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 
 /// Engine for running simulations.
 pub struct SimulationEngine<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> {
@@ -55,18 +56,8 @@ impl<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> SimulationEngine<
             .value;
         let mut current_price = initial_price;
 
-        // Initial value (approximate for simulation)
-        // Real implementation would calculate exact amounts held at initial price
-        let initial_value_usd = Decimal::from(1000); // Placeholder, should compute from position.liquidity
-
         // We assume position range is fixed for this basic simulation
-        let range = self
-            .position
-            .range
-            .clone()
-            .expect("CLMM position needs range");
-        let lower = range.lower_price.value;
-        let upper = range.upper_price.value;
+        let (lower, upper) = self.range();
 
         let mut time_in_range_count = 0;
 
@@ -105,8 +96,85 @@ impl<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> SimulationEngine<
             }
         }
 
+        self.finish(
+            initial_price,
+            current_price,
+            total_fees_usd,
+            time_in_range_count,
+        )
+    }
+
+    /// Runs the simulation with `f64` arithmetic in the inner loop.
+    ///
+    /// Produces the same result as [`run`](Self::run) up to floating point
+    /// rounding, several times faster. Prices, fee shares and fees stay in
+    /// `f64` per step and are converted to `Decimal` once at the end.
+    pub fn run_fast(&mut self) -> SimulationResult {
+        let prices = self.price_path_generator.generate_f64(self.steps);
+
+        let (lower, upper) = self.range();
+        let lower_f64 = lower.to_f64().unwrap_or(0.0);
+        let upper_f64 = upper.to_f64().unwrap_or(f64::MAX);
+        let fee_rate = self.fee_rate.to_f64().unwrap_or(0.0);
+        let position_liquidity = self.position.liquidity_amount as f64;
+
+        let mut total_fees = 0.0;
+        let mut time_in_range_count = 0;
+
+        for &price in &prices {
+            if price < lower_f64 || price > upper_f64 {
+                continue;
+            }
+            time_in_range_count += 1;
+
+            let volume = self.volume_model.next_volume_f64();
+            let global_liquidity = self.liquidity_model.get_liquidity_at_price_f64(price);
+            let fee_share = if global_liquidity > 0 {
+                (position_liquidity / global_liquidity as f64).min(1.0)
+            } else {
+                0.0
+            };
+            total_fees += volume * fee_share * fee_rate;
+        }
+
+        let to_decimal = |value: f64| Decimal::from_f64(value).unwrap_or(Decimal::ZERO);
+        let initial_price = prices.first().map_or(Decimal::ONE, |p| to_decimal(*p));
+        let final_price = prices.last().map_or(initial_price, |p| to_decimal(*p));
+
+        self.finish(
+            initial_price,
+            final_price,
+            to_decimal(total_fees),
+            time_in_range_count,
+        )
+    }
+
+    /// Returns the bounds of the position's range.
+    fn range(&self) -> (Decimal, Decimal) {
+        let range = self
+            .position
+            .range
+            .as_ref()
+            .expect("CLMM position needs range");
+        (range.lower_price.value, range.upper_price.value)
+    }
+
+    /// Builds the result from the path's end points and accrued fees.
+    fn finish(
+        &self,
+        initial_price: Decimal,
+        final_price: Decimal,
+        total_fees_usd: Decimal,
+        time_in_range_count: usize,
+    ) -> SimulationResult {
+        let (lower, upper) = self.range();
+
+        // Initial value (approximate for simulation)
+        // Real implementation would calculate exact amounts held at initial price
+        let initial_value_usd = Decimal::from(1000); // Placeholder, should compute from position.liquidity
+
         // 3. Calculate Final IL
-        let il_pct = calculate_il_concentrated(initial_price, current_price, lower, upper)
+        let il_pct = calculate_il_concentrated(initial_price, final_price, lower, upper)
             .unwrap_or(Decimal::ZERO);
 
         let il_amount = initial_value_usd * il_pct; // Negative value
@@ -224,4 +292,38 @@ mod tests {
         // IL should be negative (price moved)
         assert!(result.total_il < Decimal::ZERO);
     }
+
+    #[test]
+    fn test_fast_path_matches_decimal_path() {
+        use crate::price_path::GeometricBrownianMotion;
+
+        let run = |fast: bool| {
+            let gbm = GeometricBrownianMotion::new(Decimal::from(100), 0.0, 0.6, 1.0 / 365.0)
+                .with_seed(11);
+            let mut engine = SimulationEngine::new(
+                create_dummy_position(),
+                gbm,
+                ConstantVolume::new(Decimal::from(1_000_000)),
+                ConstantLiquidity::new(10000),
+                Decimal::from_f64(0.003).unwrap(),
+                60,
+            );
+            if fast {
+                engine.run_fast()
+            } else {
+                engine.run()
+            }
+        };
+        let exact = run(false);
+        let fast = run(true);
+
+        assert_eq!(
+            exact.time_in_range_percentage,
+            fast.time_in_range_percentage
+        );
+        let tolerance = Decimal::new(1, 6);
+        assert!((exact.total_fees_earned - fast.total_fees_earned).abs() < tolerance);
+        assert!((exact.total_il - fast.total_il).abs() < tolerance);
+        assert!((exact.net_pnl - fast.net_pnl).abs() < tolerance);
+    }
 }
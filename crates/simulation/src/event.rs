@@ -240,6 +240,12 @@ impl EventLog {
         &self.events
     }
 
+    /// Consumes the log and returns its events.
+    #[must_use]
+    pub fn into_events(self) -> Vec<SimulationEvent> {
+        self.events
+    }
+
     /// Returns events of a specific type.
     #[must_use]
     pub fn events_of_type(&self, event_type: SimulationEventType) -> Vec<&SimulationEvent> {
@@ -11,12 +11,16 @@ pub mod event;
 pub mod liquidity;
 /// Monte Carlo simulation logic.
 pub mod monte_carlo;
+/// Multi-position portfolio tracking.
+pub mod portfolio_tracker;
 /// Position simulation logic.
 pub mod position_simulator;
 /// Position tracking logic.
 pub mod position_tracker;
 /// Price path generation.
 pub mod price_path;
+/// Named stress scenarios.
+pub mod scenario;
 /// Simulation state management.
 pub mod state;
 /// Rebalancing strategies.
@@ -1,4 +1,5 @@
 use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
 
 /// Trait to model the global liquidity of a pool.
 pub trait LiquidityModel {
@@ -10,6 +11,14 @@ pub trait LiquidityModel {
     fn get_liquidity(&self, _step: usize) -> u128 {
         self.get_liquidity_at_price(Decimal::ZERO)
     }
+
+    /// Returns the global active liquidity at an `f64` price.
+    ///
+    /// Used by the fast simulation path. The default converts the price to
+    /// `Decimal`; models that ignore the price should override it.
+    fn get_liquidity_at_price_f64(&self, price: f64) -> u128 {
+        self.get_liquidity_at_price(Decimal::from_f64(price).unwrap_or(Decimal::ZERO))
+    }
 }
 
 /// A simple model with constant global liquidity.
@@ -30,4 +39,8 @@ impl LiquidityModel for ConstantLiquidity {
     fn get_liquidity_at_price(&self, _price: Decimal) -> u128 {
         self.liquidity
     }
+
+    fn get_liquidity_at_price_f64(&self, _price: f64) -> u128 {
+        self.liquidity
+    }
 }
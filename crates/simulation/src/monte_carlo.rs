@@ -5,8 +5,13 @@ use crate::volume::VolumeModel;
 use clmm_lp_domain::entities::position::Position;
 use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
 use rust_decimal::Decimal;
+use std::ops::ControlFlow;
 
 /// Runner for Monte Carlo simulations.
+///
+/// Iterations run on [`SimulationEngine::run_fast`], so paths and fee
+/// accrual are computed in `f64` and only each iteration's result is
+/// converted to `Decimal`.
 pub struct MonteCarloRunner<V: VolumeModel + Clone, L: LiquidityModel + Clone> {
     /// The position to simulate.
     pub position: Position,
@@ -28,6 +33,8 @@ pub struct MonteCarloRunner<V: VolumeModel + Clone, L: LiquidityModel + Clone> {
     pub steps: usize,
     /// The number of iterations.
     pub iterations: usize,
+    /// Seed of the first iteration's price path; unseeded runs use OS entropy.
+    pub seed: Option<u64>,
 }
 
 /// Result of a Monte Carlo simulation run.
@@ -49,15 +56,31 @@ pub struct AggregateResult {
 impl<V: VolumeModel + Clone, L: LiquidityModel + Clone> MonteCarloRunner<V, L> {
     /// Runs the Monte Carlo simulation.
     pub fn run(&mut self) -> AggregateResult {
+        self.run_with_progress(|_, _| ControlFlow::Continue(()))
+    }
+
+    /// Runs the Monte Carlo simulation, reporting progress after each
+    /// iteration.
+    ///
+    /// `on_iteration` receives the number of completed iterations and the
+    /// total. Returning `ControlFlow::Break` stops the run; the result then
+    /// aggregates the iterations completed so far.
+    pub fn run_with_progress<F>(&mut self, mut on_iteration: F) -> AggregateResult
+    where
+        F: FnMut(usize, usize) -> ControlFlow<()>,
+    {
         let mut results: Vec<SimulationResult> = Vec::with_capacity(self.iterations);
 
-        for _ in 0..self.iterations {
-            let gbm = GeometricBrownianMotion::new(
+        for i in 0..self.iterations {
+            let mut gbm = GeometricBrownianMotion::new(
                 self.initial_price,
                 self.drift,
                 self.volatility,
                 self.time_step,
             );
+            if let Some(seed) = self.seed {
+                gbm = gbm.with_seed(seed.wrapping_add(i as u64));
+            }
 
             // Create a fresh volume model for each run if it has state
             let vol = self.volume_model.clone();
@@ -72,7 +95,10 @@ impl<V: VolumeModel + Clone, L: LiquidityModel + Clone> MonteCarloRunner<V, L> {
                 self.steps,
             );
 
-            results.push(engine.run());
+            results.push(engine.run_fast());
+            if on_iteration(i + 1, self.iterations).is_break() {
+                break;
+            }
         }
 
         self.aggregate(results)
@@ -0,0 +1,533 @@
+//! Portfolio tracking for multi-position simulations.
+//!
+//! A portfolio runs several positions side by side, each in its own pool or
+//! range and with its own strategy, funded from one pool of capital. Capital
+//! not allocated to a position is held as cash at its nominal value.
+
+use crate::position_tracker::{PositionTracker, TrackerSummary};
+use crate::strategies::{RebalanceAction, RebalanceStrategy};
+use clmm_lp_domain::value_objects::price::Price;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// A position held in a portfolio.
+struct PortfolioPosition {
+    /// Label identifying the position in reports.
+    label: String,
+    /// Tracker of the position, funded with its initial capital.
+    tracker: PositionTracker,
+    /// Strategy evaluated for the position at each step.
+    strategy: Option<Box<dyn RebalanceStrategy>>,
+}
+
+/// Tracks several positions sharing one pool of capital.
+pub struct PortfolioTracker {
+    /// Total capital in USD, deployed and undeployed.
+    pub initial_capital: Decimal,
+    /// Positions in the order they were added.
+    positions: Vec<PortfolioPosition>,
+    /// Portfolio value at each step.
+    equity: Vec<Decimal>,
+    /// Highest portfolio value seen.
+    peak_value: Decimal,
+    /// Largest drawdown from the peak.
+    max_drawdown: Decimal,
+}
+
+impl PortfolioTracker {
+    /// Creates an empty portfolio with `initial_capital` USD of cash.
+    #[must_use]
+    pub fn new(initial_capital: Decimal) -> Self {
+        Self {
+            initial_capital,
+            positions: Vec::new(),
+            equity: Vec::new(),
+            peak_value: initial_capital,
+            max_drawdown: Decimal::ZERO,
+        }
+    }
+
+    /// Adds a position funded with the tracker's initial capital.
+    ///
+    /// Positions must be added before the first step, and together may not
+    /// allocate more than the portfolio's capital.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if steps were already recorded, the allocation is not
+    /// positive or exceeds the undeployed capital.
+    pub fn add_position(
+        &mut self,
+        label: impl Into<String>,
+        tracker: PositionTracker,
+        strategy: Option<Box<dyn RebalanceStrategy>>,
+    ) -> Result<(), String> {
+        let label = label.into();
+        if !self.equity.is_empty() {
+            return Err(format!(
+                "cannot add position '{}' after the simulation started",
+                label
+            ));
+        }
+        let allocation = tracker.initial_capital;
+        if allocation <= Decimal::ZERO {
+            return Err(format!("position '{}' has no capital", label));
+        }
+        if allocation > self.undeployed_capital() {
+            return Err(format!(
+                "position '{}' needs {} but only {} is undeployed",
+                label,
+                allocation,
+                self.undeployed_capital()
+            ));
+        }
+        self.positions.push(PortfolioPosition {
+            label,
+            tracker,
+            strategy,
+        });
+        Ok(())
+    }
+
+    /// Returns the capital allocated to positions.
+    #[must_use]
+    pub fn deployed_capital(&self) -> Decimal {
+        self.positions
+            .iter()
+            .map(|p| p.tracker.initial_capital)
+            .sum()
+    }
+
+    /// Returns the capital held as cash.
+    #[must_use]
+    pub fn undeployed_capital(&self) -> Decimal {
+        self.initial_capital - self.deployed_capital()
+    }
+
+    /// Returns the number of positions.
+    #[must_use]
+    pub fn position_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the tracker of the position at `index`.
+    #[must_use]
+    pub fn position(&self, index: usize) -> Option<&PositionTracker> {
+        self.positions.get(index).map(|p| &p.tracker)
+    }
+
+    /// Records a step for every position.
+    ///
+    /// `steps` holds the price of each position's pool and the fees the
+    /// position earned this step, in the order the positions were added.
+    ///
+    /// # Returns
+    ///
+    /// The action taken for each position (if any)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` does not hold one entry per position.
+    pub fn record_step(&mut self, steps: &[(Price, Decimal)]) -> Vec<Option<RebalanceAction>> {
+        assert_eq!(
+            steps.len(),
+            self.positions.len(),
+            "expected one price and fee per position"
+        );
+
+        let actions = self
+            .positions
+            .iter_mut()
+            .zip(steps)
+            .map(|(position, &(price, fees))| {
+                position
+                    .tracker
+                    .record_step(price, fees, position.strategy.as_deref())
+            })
+            .collect();
+
+        let value = self.undeployed_capital()
+            + self
+                .positions
+                .iter()
+                .filter_map(|p| p.tracker.equity_curve().last())
+                .sum::<Decimal>();
+        self.equity.push(value);
+
+        if value > self.peak_value {
+            self.peak_value = value;
+        }
+        if self.peak_value > Decimal::ZERO {
+            let drawdown = (self.peak_value - value) / self.peak_value;
+            if drawdown > self.max_drawdown {
+                self.max_drawdown = drawdown;
+            }
+        }
+
+        actions
+    }
+
+    /// Returns the number of recorded steps.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.equity.len()
+    }
+
+    /// Returns true if no steps have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.equity.is_empty()
+    }
+
+    /// Returns the portfolio value at each step.
+    #[must_use]
+    pub fn equity_curve(&self) -> &[Decimal] {
+        &self.equity
+    }
+
+    /// Returns the correlation of the positions' step returns.
+    ///
+    /// Row and column `i` belong to the position at index `i`. Positions
+    /// whose value never changed are uncorrelated with every other one.
+    #[must_use]
+    pub fn correlation_matrix(&self) -> Vec<Vec<f64>> {
+        let returns: Vec<Vec<f64>> = self
+            .positions
+            .iter()
+            .map(|p| step_returns(&p.tracker))
+            .collect();
+
+        (0..returns.len())
+            .map(|i| {
+                (0..returns.len())
+                    .map(|j| {
+                        if i == j {
+                            1.0
+                        } else {
+                            pearson(&returns[i], &returns[j])
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns summary statistics for the portfolio and each position.
+    #[must_use]
+    pub fn summary(&self) -> PortfolioSummary {
+        let final_value = self.equity.last().copied().unwrap_or(self.initial_capital);
+        let final_pnl = final_value - self.initial_capital;
+        let correlation = self.correlation_matrix();
+
+        let summaries: Vec<TrackerSummary> =
+            self.positions.iter().map(|p| p.tracker.summary()).collect();
+        let weights: Vec<f64> = self
+            .positions
+            .iter()
+            .map(|p| ratio(p.tracker.initial_capital, self.initial_capital))
+            .collect();
+        let drawdowns: Vec<f64> = summaries
+            .iter()
+            .map(|s| s.max_drawdown.to_f64().unwrap_or(0.0))
+            .collect();
+
+        // Weighted drawdowns combined like volatilities: perfectly correlated
+        // positions add up, uncorrelated ones partly offset each other
+        let mut variance = 0.0;
+        for i in 0..weights.len() {
+            for j in 0..weights.len() {
+                variance +=
+                    weights[i] * weights[j] * correlation[i][j] * drawdowns[i] * drawdowns[j];
+            }
+        }
+        let correlation_adjusted_drawdown = variance.max(0.0).sqrt();
+        let weighted_drawdown: f64 = weights.iter().zip(&drawdowns).map(|(w, d)| w * d).sum();
+        let diversification_ratio = if correlation_adjusted_drawdown > 0.0 {
+            weighted_drawdown / correlation_adjusted_drawdown
+        } else {
+            1.0
+        };
+
+        let positions = self
+            .positions
+            .iter()
+            .zip(summaries)
+            .zip(weights)
+            .map(|((position, summary), weight)| PositionAttribution {
+                label: position.label.clone(),
+                allocation: position.tracker.initial_capital,
+                weight: to_decimal(weight),
+                pnl_share: if final_pnl.is_zero() {
+                    Decimal::ZERO
+                } else {
+                    summary.final_pnl / final_pnl
+                },
+                summary,
+            })
+            .collect::<Vec<_>>();
+
+        PortfolioSummary {
+            total_steps: self.len() as u64,
+            initial_capital: self.initial_capital,
+            deployed_capital: self.deployed_capital(),
+            final_value,
+            final_pnl,
+            total_fees: positions.iter().map(|p| p.summary.total_fees).sum(),
+            total_rebalance_cost: positions
+                .iter()
+                .map(|p| p.summary.total_rebalance_cost)
+                .sum(),
+            max_drawdown: self.max_drawdown,
+            correlation_adjusted_drawdown: to_decimal(correlation_adjusted_drawdown),
+            diversification_ratio: to_decimal(diversification_ratio),
+            correlation: correlation
+                .into_iter()
+                .map(|row| row.into_iter().map(to_decimal).collect())
+                .collect(),
+            positions,
+        }
+    }
+}
+
+/// Summary statistics from portfolio tracking.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioSummary {
+    /// Total simulation steps.
+    pub total_steps: u64,
+    /// Total capital in USD.
+    pub initial_capital: Decimal,
+    /// Capital allocated to positions.
+    pub deployed_capital: Decimal,
+    /// Final portfolio value including undeployed capital.
+    pub final_value: Decimal,
+    /// Final net PnL.
+    pub final_pnl: Decimal,
+    /// Total fees earned across positions.
+    pub total_fees: Decimal,
+    /// Total cost of rebalancing across positions.
+    pub total_rebalance_cost: Decimal,
+    /// Maximum drawdown percentage of the portfolio value.
+    pub max_drawdown: Decimal,
+    /// Position drawdowns weighted by allocation and combined through the
+    /// correlation of their returns.
+    pub correlation_adjusted_drawdown: Decimal,
+    /// Weighted sum of position drawdowns over the correlation-adjusted
+    /// drawdown (1 = no diversification benefit).
+    pub diversification_ratio: Decimal,
+    /// Correlation of the positions' step returns.
+    pub correlation: Vec<Vec<Decimal>>,
+    /// Per-position attribution, in the order the positions were added.
+    pub positions: Vec<PositionAttribution>,
+}
+
+/// Contribution of one position to the portfolio.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionAttribution {
+    /// Label of the position.
+    pub label: String,
+    /// Capital allocated to the position.
+    pub allocation: Decimal,
+    /// Share of the portfolio capital allocated to the position.
+    pub weight: Decimal,
+    /// Share of the portfolio PnL earned by the position.
+    pub pnl_share: Decimal,
+    /// Summary of the position on its own.
+    pub summary: TrackerSummary,
+}
+
+/// Returns the step returns of a position, starting from its initial capital.
+fn step_returns(tracker: &PositionTracker) -> Vec<f64> {
+    let mut previous = tracker.initial_capital;
+    tracker
+        .equity_curve()
+        .iter()
+        .map(|&value| {
+            let step_return = if previous.is_zero() {
+                0.0
+            } else {
+                ratio(value - previous, previous)
+            };
+            previous = value;
+            step_return
+        })
+        .collect()
+}
+
+/// Calculates the Pearson correlation of two series.
+///
+/// Returns 0 for series without variance.
+fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len().min(y.len());
+    if n < 2 {
+        return 0.0;
+    }
+    let mean_x = x[..n].iter().sum::<f64>() / n as f64;
+    let mean_y = y[..n].iter().sum::<f64>() / n as f64;
+
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (a, b) in x[..n].iter().zip(&y[..n]) {
+        let (dx, dy) = (a - mean_x, b - mean_y);
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x <= f64::EPSILON || var_y <= f64::EPSILON {
+        return 0.0;
+    }
+    (cov / (var_x.sqrt() * var_y.sqrt())).clamp(-1.0, 1.0)
+}
+
+/// Divides two decimals as a float.
+fn ratio(numerator: Decimal, denominator: Decimal) -> f64 {
+    if denominator.is_zero() {
+        return 0.0;
+    }
+    (numerator / denominator).to_f64().unwrap_or(0.0)
+}
+
+/// Converts a float to a decimal, mapping non-finite values to zero.
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::{StaticRange, ThresholdRebalance};
+    use clmm_lp_domain::value_objects::price_range::PriceRange;
+    use rust_decimal_macros::dec;
+
+    fn tracker(capital: Decimal, price: Decimal, width: Decimal) -> PositionTracker {
+        PositionTracker::new(
+            capital,
+            Price::new(price),
+            PriceRange::new(Price::new(price - width), Price::new(price + width)),
+            dec!(1),
+        )
+    }
+
+    #[test]
+    fn test_add_position_limits_capital() {
+        let mut portfolio = PortfolioTracker::new(dec!(1000));
+        portfolio
+            .add_position("sol", tracker(dec!(600), dec!(100), dec!(10)), None)
+            .unwrap();
+        assert_eq!(portfolio.undeployed_capital(), dec!(400));
+
+        // More than the undeployed capital
+        assert!(
+            portfolio
+                .add_position("eth", tracker(dec!(500), dec!(100), dec!(10)), None)
+                .is_err()
+        );
+        portfolio
+            .add_position("eth", tracker(dec!(400), dec!(100), dec!(10)), None)
+            .unwrap();
+
+        // No positions once the simulation started
+        portfolio.record_step(&[
+            (Price::new(dec!(100)), dec!(0)),
+            (Price::new(dec!(100)), dec!(0)),
+        ]);
+        let mut late = PortfolioTracker::new(dec!(1000));
+        late.record_step(&[]);
+        assert!(
+            late.add_position("sol", tracker(dec!(100), dec!(100), dec!(10)), None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_portfolio_value_includes_cash() {
+        let mut portfolio = PortfolioTracker::new(dec!(1000));
+        portfolio
+            .add_position(
+                "sol",
+                tracker(dec!(500), dec!(100), dec!(10)),
+                Some(Box::new(StaticRange::new())),
+            )
+            .unwrap();
+
+        portfolio.record_step(&[(Price::new(dec!(100)), dec!(10))]);
+        // 500 undeployed plus the position with its fees
+        assert_eq!(portfolio.equity_curve(), &[dec!(1010)]);
+
+        let summary = portfolio.summary();
+        assert_eq!(summary.final_pnl, dec!(10));
+        assert_eq!(summary.total_fees, dec!(10));
+        assert_eq!(summary.positions[0].weight, dec!(0.5));
+        assert_eq!(summary.positions[0].pnl_share, Decimal::ONE);
+    }
+
+    #[test]
+    fn test_strategies_run_per_position() {
+        let mut portfolio = PortfolioTracker::new(dec!(2000));
+        portfolio
+            .add_position(
+                "narrow",
+                tracker(dec!(1000), dec!(100), dec!(5)),
+                Some(Box::new(ThresholdRebalance::new(dec!(0.05), dec!(0.1)))),
+            )
+            .unwrap();
+        portfolio
+            .add_position("wide", tracker(dec!(1000), dec!(100), dec!(50)), None)
+            .unwrap();
+
+        let actions = portfolio.record_step(&[
+            (Price::new(dec!(120)), dec!(0)),
+            (Price::new(dec!(120)), dec!(0)),
+        ]);
+        assert!(matches!(
+            actions[0],
+            Some(RebalanceAction::Rebalance { .. })
+        ));
+        assert!(actions[1].is_none());
+
+        let summary = portfolio.summary();
+        assert_eq!(summary.positions[0].summary.rebalance_count, 1);
+        assert_eq!(summary.positions[1].summary.rebalance_count, 0);
+        assert_eq!(summary.total_rebalance_cost, dec!(1));
+    }
+
+    #[test]
+    fn test_uncorrelated_positions_diversify_drawdown() {
+        let mut portfolio = PortfolioTracker::new(dec!(2000));
+        portfolio
+            .add_position("a", tracker(dec!(1000), dec!(100), dec!(20)), None)
+            .unwrap();
+        portfolio
+            .add_position("b", tracker(dec!(1000), dec!(100), dec!(20)), None)
+            .unwrap();
+
+        // Each pool moves away and back while the other one is still
+        let a = [dec!(90), dec!(100), dec!(100), dec!(100)];
+        let b = [dec!(100), dec!(100), dec!(90), dec!(100)];
+        for (price_a, price_b) in a.into_iter().zip(b) {
+            portfolio.record_step(&[
+                (Price::new(price_a), dec!(0)),
+                (Price::new(price_b), dec!(0)),
+            ]);
+        }
+
+        let correlation = portfolio.correlation_matrix();
+        assert!((correlation[0][0] - 1.0).abs() < 1e-12);
+        assert!(correlation[0][1] < 0.5);
+
+        let summary = portfolio.summary();
+        let weighted: Decimal = summary
+            .positions
+            .iter()
+            .map(|p| p.weight * p.summary.max_drawdown)
+            .sum();
+        assert!(summary.correlation_adjusted_drawdown < weighted);
+        assert!(summary.diversification_ratio > Decimal::ONE);
+    }
+
+    #[test]
+    fn test_pearson() {
+        assert!((pearson(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]) - 1.0).abs() < 1e-12);
+        assert!((pearson(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]) + 1.0).abs() < 1e-12);
+        assert_eq!(pearson(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]), 0.0);
+    }
+}
@@ -4,10 +4,11 @@
 //! recording snapshots and computing metrics at each step.
 
 use crate::strategies::{RebalanceAction, RebalanceStrategy, StrategyContext};
-use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
+use clmm_lp_domain::metrics::impermanent_loss::ConcentratedIl;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 /// A snapshot of position state at a point in time.
 #[derive(Debug, Clone)]
@@ -33,6 +34,10 @@ pub struct PositionSnapshot {
 }
 
 /// Tracks position state throughout a simulation.
+///
+/// Per-step state is stored column-wise, one preallocated array per metric,
+/// so stepping only appends plain values. Ranges and actions change rarely
+/// and are stored once per change rather than once per step.
 #[derive(Debug)]
 pub struct PositionTracker {
     /// Initial capital in USD.
@@ -41,8 +46,6 @@ pub struct PositionTracker {
     pub entry_price: Price,
     /// Current range.
     pub current_range: PriceRange,
-    /// All recorded snapshots.
-    pub snapshots: Vec<PositionSnapshot>,
     /// Steps since last rebalance.
     pub steps_since_rebalance: u64,
     /// Total rebalance count.
@@ -55,6 +58,28 @@ pub struct PositionTracker {
     cumulative_fees: Decimal,
     /// Current step.
     current_step: u64,
+    /// Price at each step.
+    prices: Vec<Price>,
+    /// Whether the price was in range at each step.
+    in_range: Vec<bool>,
+    /// Cumulative fees at each step.
+    fees: Vec<Decimal>,
+    /// Impermanent loss at each step.
+    il: Vec<Decimal>,
+    /// Position value at each step.
+    equity: Vec<Decimal>,
+    /// Ranges keyed by the first step they apply to.
+    ranges: Vec<(u64, PriceRange)>,
+    /// Rebalance and close actions keyed by step.
+    actions: Vec<(u64, RebalanceAction)>,
+    /// Steps recorded in range.
+    in_range_steps: u64,
+    /// Highest position value seen.
+    peak_value: Decimal,
+    /// Largest drawdown from the peak.
+    max_drawdown: Decimal,
+    /// IL calculation for the entry price and range it was prepared for.
+    il_cache: Option<(Price, PriceRange, ConcentratedIl)>,
 }
 
 impl PositionTracker {
@@ -76,17 +101,43 @@ impl PositionTracker {
         Self {
             initial_capital,
             entry_price,
+            ranges: vec![(0, initial_range.clone())],
             current_range: initial_range,
-            snapshots: Vec::new(),
             steps_since_rebalance: 0,
             rebalance_count: 0,
             total_rebalance_cost: Decimal::ZERO,
             rebalance_cost,
             cumulative_fees: Decimal::ZERO,
             current_step: 0,
+            prices: Vec::new(),
+            in_range: Vec::new(),
+            fees: Vec::new(),
+            il: Vec::new(),
+            equity: Vec::new(),
+            actions: Vec::new(),
+            in_range_steps: 0,
+            peak_value: initial_capital,
+            max_drawdown: Decimal::ZERO,
+            il_cache: None,
         }
     }
 
+    /// Preallocates storage for `steps` steps.
+    #[must_use]
+    pub fn with_capacity(mut self, steps: usize) -> Self {
+        self.reserve(steps);
+        self
+    }
+
+    /// Reserves storage for at least `additional` more steps.
+    pub fn reserve(&mut self, additional: usize) {
+        self.prices.reserve(additional);
+        self.in_range.reserve(additional);
+        self.fees.reserve(additional);
+        self.il.reserve(additional);
+        self.equity.reserve(additional);
+    }
+
     /// Records a step in the simulation.
     ///
     /// # Arguments
@@ -98,7 +149,7 @@ impl PositionTracker {
     /// # Returns
     ///
     /// The action taken (if any)
-    pub fn record_step<S: RebalanceStrategy>(
+    pub fn record_step<S: RebalanceStrategy + ?Sized>(
         &mut self,
         price: Price,
         step_fees: Decimal,
@@ -109,26 +160,19 @@ impl PositionTracker {
         self.cumulative_fees += step_fees;
 
         // Calculate current IL
-        let il_pct = calculate_il_concentrated(
-            self.entry_price.value,
-            price.value,
-            self.current_range.lower_price.value,
-            self.current_range.upper_price.value,
-        )
-        .unwrap_or(Decimal::ZERO);
+        let il_pct = self.il_at(price);
 
         // Calculate position value
         let il_amount = self.initial_capital * il_pct;
         let position_value =
             self.initial_capital + il_amount + self.cumulative_fees - self.total_rebalance_cost;
-        let net_pnl = position_value - self.initial_capital;
 
         // Check if in range
         let in_range = price.value >= self.current_range.lower_price.value
             && price.value <= self.current_range.upper_price.value;
 
         // Evaluate strategy if provided
-        let action = strategy.map(|s| {
+        let action = strategy.and_then(|s| {
             let context = StrategyContext {
                 current_price: price,
                 current_range: self.current_range.clone(),
@@ -138,84 +182,178 @@ impl PositionTracker {
                 current_il_pct: il_pct,
                 total_fees_earned: self.cumulative_fees,
             };
-            s.evaluate(&context)
+            match s.evaluate(&context) {
+                RebalanceAction::Hold => None,
+                action => Some(action),
+            }
         });
 
         // Handle rebalance action
-        let final_action = if let Some(ref act) = action {
-            match act {
-                RebalanceAction::Rebalance { new_range, .. } => {
-                    self.execute_rebalance(new_range.clone());
-                    action.clone()
-                }
-                RebalanceAction::Close { .. } => action.clone(),
-                RebalanceAction::Hold => None,
+        if let Some(action) = &action {
+            if let RebalanceAction::Rebalance { new_range, .. } = action {
+                self.execute_rebalance(new_range.clone());
             }
-        } else {
-            None
-        };
+            self.actions.push((self.current_step, action.clone()));
+        }
 
-        // Record snapshot
-        let snapshot = PositionSnapshot {
-            step: self.current_step,
-            price,
-            range: self.current_range.clone(),
-            in_range,
-            cumulative_fees: self.cumulative_fees,
-            il_pct,
-            position_value_usd: position_value,
-            net_pnl,
-            action: final_action.clone(),
-        };
-        self.snapshots.push(snapshot);
+        // Record step
+        self.prices.push(price);
+        self.in_range.push(in_range);
+        self.fees.push(self.cumulative_fees);
+        self.il.push(il_pct);
+        self.equity.push(position_value);
 
-        final_action
+        if in_range {
+            self.in_range_steps += 1;
+        }
+        if position_value > self.peak_value {
+            self.peak_value = position_value;
+        }
+        let drawdown = (self.peak_value - position_value) / self.peak_value;
+        if drawdown > self.max_drawdown {
+            self.max_drawdown = drawdown;
+        }
+
+        action
+    }
+
+    /// Returns the IL at `price`, preparing the calculation again only when
+    /// the entry price or range changed since the previous step.
+    fn il_at(&mut self, price: Price) -> Decimal {
+        let stale = self.il_cache.as_ref().is_none_or(|(entry, range, _)| {
+            *entry != self.entry_price || *range != self.current_range
+        });
+        if stale {
+            self.il_cache = ConcentratedIl::new(
+                self.entry_price.value,
+                self.current_range.lower_price.value,
+                self.current_range.upper_price.value,
+            )
+            .ok()
+            .map(|il| (self.entry_price, self.current_range.clone(), il));
+        }
+        self.il_cache
+            .as_ref()
+            .and_then(|(_, _, il)| il.at(price.value).ok())
+            .unwrap_or(Decimal::ZERO)
     }
 
     /// Executes a rebalance to a new range.
     fn execute_rebalance(&mut self, new_range: PriceRange) {
+        self.ranges.push((self.current_step, new_range.clone()));
         self.current_range = new_range;
         self.steps_since_rebalance = 0;
         self.rebalance_count += 1;
         self.total_rebalance_cost += self.rebalance_cost;
     }
 
+    /// Returns the number of recorded steps.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.equity.len()
+    }
+
+    /// Returns true if no steps have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.equity.is_empty()
+    }
+
+    /// Returns the price at each step.
+    #[must_use]
+    pub fn prices(&self) -> &[Price] {
+        &self.prices
+    }
+
+    /// Returns the position value in USD at each step.
+    #[must_use]
+    pub fn equity_curve(&self) -> &[Decimal] {
+        &self.equity
+    }
+
+    /// Returns the cumulative fees at each step.
+    #[must_use]
+    pub fn fee_curve(&self) -> &[Decimal] {
+        &self.fees
+    }
+
+    /// Returns the impermanent loss percentage at each step.
+    #[must_use]
+    pub fn il_curve(&self) -> &[Decimal] {
+        &self.il
+    }
+
+    /// Returns each range with the first step it applies to.
+    #[must_use]
+    pub fn range_history(&self) -> &[(u64, PriceRange)] {
+        &self.ranges
+    }
+
+    /// Returns the rebalance and close actions with the step they were taken.
+    #[must_use]
+    pub fn actions(&self) -> &[(u64, RebalanceAction)] {
+        &self.actions
+    }
+
+    /// Builds the snapshot of the step at `index`.
+    #[must_use]
+    pub fn snapshot(&self, index: usize) -> Option<PositionSnapshot> {
+        (index < self.len()).then(|| self.snapshot_at(index))
+    }
+
+    /// Returns the snapshot of the most recent step.
+    #[must_use]
+    pub fn last_snapshot(&self) -> Option<PositionSnapshot> {
+        self.len().checked_sub(1).map(|i| self.snapshot_at(i))
+    }
+
+    /// Iterates over a snapshot of every recorded step.
+    pub fn snapshots(&self) -> impl ExactSizeIterator<Item = PositionSnapshot> + '_ {
+        (0..self.len()).map(|i| self.snapshot_at(i))
+    }
+
+    /// Builds a snapshot from the columns at a recorded `index`.
+    fn snapshot_at(&self, index: usize) -> PositionSnapshot {
+        let step = index as u64 + 1;
+        let range = &self.ranges[self.ranges.partition_point(|(from, _)| *from <= step) - 1].1;
+        let action = self
+            .actions
+            .binary_search_by_key(&step, |(at, _)| *at)
+            .ok()
+            .map(|i| self.actions[i].1.clone());
+
+        PositionSnapshot {
+            step,
+            price: self.prices[index],
+            range: range.clone(),
+            in_range: self.in_range[index],
+            cumulative_fees: self.fees[index],
+            il_pct: self.il[index],
+            position_value_usd: self.equity[index],
+            net_pnl: self.equity[index] - self.initial_capital,
+            action,
+        }
+    }
+
     /// Returns summary statistics for the tracked position.
     #[must_use]
     pub fn summary(&self) -> TrackerSummary {
-        let total_steps = self.snapshots.len() as u64;
-        let in_range_steps = self.snapshots.iter().filter(|s| s.in_range).count() as u64;
+        let total_steps = self.len() as u64;
 
         let time_in_range_pct = if total_steps > 0 {
-            Decimal::from(in_range_steps) / Decimal::from(total_steps)
+            Decimal::from(self.in_range_steps) / Decimal::from(total_steps)
         } else {
             Decimal::ZERO
         };
 
-        let final_snapshot = self.snapshots.last();
-        let final_value = final_snapshot
-            .map(|s| s.position_value_usd)
-            .unwrap_or(self.initial_capital);
-        let final_pnl = final_snapshot.map(|s| s.net_pnl).unwrap_or(Decimal::ZERO);
-        let final_il = final_snapshot.map(|s| s.il_pct).unwrap_or(Decimal::ZERO);
-
-        // Calculate max drawdown
-        let mut peak = self.initial_capital;
-        let mut max_drawdown = Decimal::ZERO;
-        for snapshot in &self.snapshots {
-            if snapshot.position_value_usd > peak {
-                peak = snapshot.position_value_usd;
-            }
-            let drawdown = (peak - snapshot.position_value_usd) / peak;
-            if drawdown > max_drawdown {
-                max_drawdown = drawdown;
-            }
-        }
+        let final_value = self.equity.last().copied().unwrap_or(self.initial_capital);
+        let final_pnl = final_value - self.initial_capital;
+        let final_il = self.il.last().copied().unwrap_or(Decimal::ZERO);
 
         // Calculate HODL comparison
-        let hodl_value = if let Some(final_snap) = final_snapshot {
+        let hodl_value = if let Some(final_price) = self.prices.last() {
             // Simple HODL: assume 50/50 split at entry, track price change
-            let price_ratio = final_snap.price.value / self.entry_price.value;
+            let price_ratio = final_price.value / self.entry_price.value;
             // HODL value = initial * (1 + price_change) / 2 + initial / 2
             // Simplified: assume quote token is stable
             self.initial_capital * (Decimal::ONE + price_ratio) / Decimal::from(2)
@@ -233,7 +371,7 @@ impl PositionTracker {
             time_in_range_pct,
             rebalance_count: self.rebalance_count,
             total_rebalance_cost: self.total_rebalance_cost,
-            max_drawdown,
+            max_drawdown: self.max_drawdown,
             hodl_value,
             vs_hodl,
         }
@@ -241,7 +379,7 @@ impl PositionTracker {
 }
 
 /// Summary statistics from position tracking.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackerSummary {
     /// Total simulation steps.
     pub total_steps: u64,
@@ -267,6 +405,55 @@ pub struct TrackerSummary {
     pub vs_hodl: Decimal,
 }
 
+/// Splits a running simulation's steps into batches for incremental persistence.
+///
+/// Each call hands out the steps recorded since the previous batch once
+/// enough have accumulated, so a caller can stream the equity curve while
+/// the simulation runs.
+#[derive(Debug, Clone)]
+pub struct StepBatcher {
+    /// Minimum number of new steps per batch.
+    batch_size: usize,
+    /// Number of steps already handed out.
+    taken: usize,
+}
+
+impl StepBatcher {
+    /// Creates a batcher handing out at least `batch_size` steps at a time.
+    #[must_use]
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            taken: 0,
+        }
+    }
+
+    /// Returns the new steps once at least a full batch has been recorded.
+    #[must_use]
+    pub fn next_batch(&mut self, tracker: &PositionTracker) -> Option<Vec<PositionSnapshot>> {
+        if tracker.len() - self.taken < self.batch_size {
+            return None;
+        }
+        Some(self.take_rest(tracker))
+    }
+
+    /// Returns every step not handed out yet.
+    #[must_use]
+    pub fn take_rest(&mut self, tracker: &PositionTracker) -> Vec<PositionSnapshot> {
+        let batch = (self.taken..tracker.len())
+            .map(|i| tracker.snapshot_at(i))
+            .collect();
+        self.taken = tracker.len();
+        batch
+    }
+
+    /// Returns the number of steps handed out so far.
+    #[must_use]
+    pub fn taken(&self) -> usize {
+        self.taken
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,7 +474,7 @@ mod tests {
         tracker.record_step::<StaticRange>(Price::new(dec!(102)), dec!(10), None);
         tracker.record_step::<StaticRange>(Price::new(dec!(98)), dec!(10), None);
 
-        assert_eq!(tracker.snapshots.len(), 3);
+        assert_eq!(tracker.len(), 3);
         assert_eq!(tracker.cumulative_fees, dec!(30));
 
         let summary = tracker.summary();
@@ -323,6 +510,44 @@ mod tests {
         assert_eq!(tracker.current_range.upper_price.value, dec!(132)); // 120 + 12
     }
 
+    #[test]
+    fn test_tracker_snapshots_from_columns() {
+        use crate::strategies::ThresholdRebalance;
+
+        let mut tracker = PositionTracker::new(
+            dec!(1000),
+            Price::new(dec!(100)),
+            PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            dec!(5),
+        )
+        .with_capacity(3);
+        let strategy = ThresholdRebalance::new(dec!(0.05), dec!(0.2));
+
+        tracker.record_step(Price::new(dec!(100)), dec!(10), Some(&strategy));
+        tracker.record_step(Price::new(dec!(120)), dec!(5), Some(&strategy));
+        tracker.record_step(Price::new(dec!(121)), dec!(5), Some(&strategy));
+
+        let snapshots: Vec<_> = tracker.snapshots().collect();
+        assert_eq!(snapshots.len(), 3);
+        assert_eq!(snapshots[0].range.upper_price.value, dec!(110));
+        assert!(snapshots[0].action.is_none());
+        // The rebalance step reports the new range but the old range's in-range flag
+        assert!(!snapshots[1].in_range);
+        assert_eq!(snapshots[1].range.upper_price.value, dec!(132));
+        assert!(matches!(
+            snapshots[1].action,
+            Some(RebalanceAction::Rebalance { .. })
+        ));
+        assert_eq!(snapshots[2].cumulative_fees, dec!(20));
+        assert_eq!(tracker.range_history().len(), 2);
+        assert_eq!(tracker.actions()[0].0, 2);
+
+        let last = tracker.last_snapshot().unwrap();
+        assert_eq!(last.step, 3);
+        assert_eq!(last.net_pnl, tracker.summary().final_pnl);
+        assert!(tracker.snapshot(3).is_none());
+    }
+
     #[test]
     fn test_tracker_time_in_range() {
         let mut tracker = PositionTracker::new(
@@ -342,4 +567,35 @@ mod tests {
         assert!(summary.time_in_range_pct > dec!(0.66));
         assert!(summary.time_in_range_pct < dec!(0.67));
     }
+
+    #[test]
+    fn test_step_batcher() {
+        let mut tracker = PositionTracker::new(
+            dec!(1000),
+            Price::new(dec!(100)),
+            PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            dec!(5),
+        );
+        let mut batcher = StepBatcher::new(2);
+
+        tracker.record_step::<StaticRange>(Price::new(dec!(100)), dec!(10), None);
+        assert!(batcher.next_batch(&tracker).is_none());
+
+        tracker.record_step::<StaticRange>(Price::new(dec!(101)), dec!(10), None);
+        tracker.record_step::<StaticRange>(Price::new(dec!(102)), dec!(10), None);
+        let batch = batcher.next_batch(&tracker).unwrap();
+        assert_eq!(
+            batch.iter().map(|s| s.step).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(batcher.next_batch(&tracker).is_none());
+
+        tracker.record_step::<StaticRange>(Price::new(dec!(103)), dec!(10), None);
+        let rest = batcher.take_rest(&tracker);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].step, 4);
+        assert_eq!(rest[0].cumulative_fees, dec!(40));
+        assert_eq!(batcher.taken(), tracker.len());
+        assert!(batcher.take_rest(&tracker).is_empty());
+    }
 }
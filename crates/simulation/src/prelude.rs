@@ -20,17 +20,23 @@ pub use crate::liquidity::{ConstantLiquidity, LiquidityModel};
 // Monte Carlo
 pub use crate::monte_carlo::{AggregateResult, MonteCarloRunner};
 
+// Portfolio tracking
+pub use crate::portfolio_tracker::{PortfolioSummary, PortfolioTracker, PositionAttribution};
+
 // Position simulator
 pub use crate::position_simulator::{PositionSimulationResult, simulate_position};
 
 // Position tracking
-pub use crate::position_tracker::{PositionSnapshot, PositionTracker, TrackerSummary};
+pub use crate::position_tracker::{PositionSnapshot, PositionTracker, StepBatcher, TrackerSummary};
 
 // Price path generators
 pub use crate::price_path::{
     DeterministicPricePath, GeometricBrownianMotion, HistoricalPricePath, PricePathGenerator,
 };
 
+// Stress scenarios
+pub use crate::scenario::Scenario;
+
 // State management
 pub use crate::state::{
     PoolState, PositionState, SimulationConfig, SimulationState, SimulationSummary,
@@ -46,4 +52,4 @@ pub use crate::strategies::{
 pub use crate::strategy_simulator::{StrategySimulationResult, simulate_with_strategy};
 
 // Volume models
-pub use crate::volume::{ConstantVolume, VolumeModel};
+pub use crate::volume::{ConstantVolume, SwapRecord, SwapVolume, VolumeModel};
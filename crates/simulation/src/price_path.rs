@@ -1,4 +1,6 @@
 use clmm_lp_domain::value_objects::price::Price;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use rand_distr::{Distribution, Normal};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
@@ -7,6 +9,17 @@ use rust_decimal::prelude::*;
 pub trait PricePathGenerator {
     /// Generates a price path with the specified number of steps.
     fn generate(&mut self, steps: usize) -> Vec<Price>;
+
+    /// Generates a price path as `f64` values for the fast simulation path.
+    ///
+    /// The default converts [`generate`](Self::generate); generators that
+    /// work in floating point override it to skip the `Decimal` round trip.
+    fn generate_f64(&mut self, steps: usize) -> Vec<f64> {
+        self.generate(steps)
+            .iter()
+            .map(|p| p.value.to_f64().unwrap_or(0.0))
+            .collect()
+    }
 }
 
 /// Geometric Brownian Motion price path generator.
@@ -19,6 +32,8 @@ pub struct GeometricBrownianMotion {
     pub volatility: f64, // annualized volatility (sigma)
     /// Time step in years (dt).
     pub time_step: f64, // time step in years (dt) e.g. 1/365 for daily
+    /// Seed of the next generated path; unseeded paths use OS entropy.
+    pub seed: Option<u64>,
 }
 
 impl GeometricBrownianMotion {
@@ -29,16 +44,44 @@ impl GeometricBrownianMotion {
             drift,
             volatility,
             time_step,
+            seed: None,
         }
     }
+
+    /// Seeds the generator so its paths are reproducible.
+    ///
+    /// Each generated path advances the seed by one, so consecutive paths
+    /// differ but the sequence repeats for the same seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
 }
 
 impl PricePathGenerator for GeometricBrownianMotion {
     fn generate(&mut self, steps: usize) -> Vec<Price> {
-        let mut prices = Vec::with_capacity(steps + 1);
+        let path = self.generate_f64(steps);
+
+        // The first price is kept exact; the rest are converted from f64
+        let mut prices = Vec::with_capacity(path.len());
         prices.push(Price::new(self.initial_price));
+        prices.extend(
+            path[1..]
+                .iter()
+                .map(|p| Price::new(Decimal::from_f64(*p).unwrap_or(Decimal::ZERO))),
+        );
+        prices
+    }
 
-        let mut rng = rand::rng();
+    fn generate_f64(&mut self, steps: usize) -> Vec<f64> {
+        let mut rng = match self.seed {
+            Some(seed) => {
+                self.seed = Some(seed.wrapping_add(1));
+                StdRng::seed_from_u64(seed)
+            }
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
         let normal = Normal::new(0.0, 1.0).unwrap();
 
         let dt = self.time_step;
@@ -46,18 +89,13 @@ impl PricePathGenerator for GeometricBrownianMotion {
         let vol_term = self.volatility * dt.sqrt();
 
         let mut current_price = self.initial_price.to_f64().unwrap_or(0.0);
+        let mut prices = Vec::with_capacity(steps + 1);
+        prices.push(current_price);
 
         for _ in 0..steps {
             let z = normal.sample(&mut rng);
-            let change = (drift_term + vol_term * z).exp();
-            current_price *= change;
-
-            // Convert back to Decimal
-            // Note: Standard f64 precision might drift from Decimal over simulated time,
-            // but for Monte Carlo high performance, f64 is standard.
-            // We cast back to Decimal for the domain object.
-            let p = Decimal::from_f64(current_price).unwrap_or(Decimal::ZERO);
-            prices.push(Price::new(p));
+            current_price *= (drift_term + vol_term * z).exp();
+            prices.push(current_price);
         }
 
         prices
@@ -138,4 +176,32 @@ mod tests {
         let all_same = path.iter().all(|p| p.value == initial);
         assert!(!all_same);
     }
+
+    #[test]
+    fn test_seeded_gbm_is_reproducible() {
+        let gbm = || GeometricBrownianMotion::new(Decimal::from(100), 0.0, 0.5, 1.0 / 365.0);
+
+        let mut first = gbm().with_seed(42);
+        let mut second = gbm().with_seed(42);
+        let path = first.generate(20);
+        assert_eq!(path, second.generate(20));
+
+        // Later paths of the same generator differ but repeat across generators
+        let next = first.generate(20);
+        assert_ne!(path, next);
+        assert_eq!(next, second.generate(20));
+    }
+
+    #[test]
+    fn test_gbm_f64_path_matches_decimal_path() {
+        let gbm = || GeometricBrownianMotion::new(Decimal::from(100), 0.1, 0.5, 1.0 / 365.0);
+
+        let path = gbm().with_seed(7).generate(50);
+        let fast = gbm().with_seed(7).generate_f64(50);
+
+        assert_eq!(path.len(), fast.len());
+        for (price, fast) in path.iter().zip(&fast) {
+            assert!((price.value.to_f64().unwrap() - fast).abs() < 1e-9);
+        }
+    }
 }
@@ -0,0 +1,238 @@
+//! Named stress scenarios.
+//!
+//! Each scenario is a deterministic price path shaped after a market event,
+//! so a range or strategy can be checked against specific what-ifs rather
+//! than only against random paths.
+
+use clmm_lp_domain::value_objects::price::Price;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use std::f64::consts::PI;
+use std::str::FromStr;
+
+/// A deterministic stress scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "name", rename_all = "lowercase")]
+pub enum Scenario {
+    /// Steady decline by `drop` (fraction) over `over` steps, then flat.
+    Crash {
+        /// Total decline as a fraction of the entry price.
+        drop: f64,
+        /// Steps over which the decline happens.
+        over: usize,
+    },
+    /// Steady rise by `rise` (fraction) over `over` steps, then flat.
+    Pump {
+        /// Total rise as a fraction of the entry price.
+        rise: f64,
+        /// Steps over which the rise happens.
+        over: usize,
+    },
+    /// Oscillation around the entry price without a trend.
+    Chop {
+        /// Swing as a fraction of the entry price.
+        amplitude: f64,
+        /// Steps per full oscillation.
+        period: usize,
+    },
+    /// Sudden drop a quarter of the way in, held for `duration` steps, then
+    /// a linear recovery to the entry price over as many steps.
+    Depeg {
+        /// Size of the drop as a fraction of the entry price.
+        drop: f64,
+        /// Steps the price stays off peg before recovering.
+        duration: usize,
+    },
+}
+
+impl Scenario {
+    /// Returns the default crash, pump, chop and depeg scenarios, sized for
+    /// hourly steps.
+    #[must_use]
+    pub fn library() -> Vec<Self> {
+        vec![
+            Self::Crash {
+                drop: 0.4,
+                over: 24,
+            },
+            Self::Pump {
+                rise: 0.5,
+                over: 24,
+            },
+            Self::Chop {
+                amplitude: 0.05,
+                period: 12,
+            },
+            Self::Depeg {
+                drop: 0.15,
+                duration: 48,
+            },
+        ]
+    }
+
+    /// Returns the scenario name.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Crash { .. } => "crash",
+            Self::Pump { .. } => "pump",
+            Self::Chop { .. } => "chop",
+            Self::Depeg { .. } => "depeg",
+        }
+    }
+
+    /// Returns a one-line description of the scenario.
+    #[must_use]
+    pub fn description(&self) -> String {
+        match self {
+            Self::Crash { drop, over } => format!("-{:.0}% over {} steps", drop * 100.0, over),
+            Self::Pump { rise, over } => format!("+{:.0}% over {} steps", rise * 100.0, over),
+            Self::Chop { amplitude, period } => {
+                format!("±{:.0}% every {} steps", amplitude * 100.0, period)
+            }
+            Self::Depeg { drop, duration } => {
+                format!(
+                    "-{:.0}% for {} steps, then recovery",
+                    drop * 100.0,
+                    duration
+                )
+            }
+        }
+    }
+
+    /// Price multiplier relative to entry at a step.
+    fn multiplier(&self, step: usize, steps: usize) -> f64 {
+        match *self {
+            Self::Crash { drop, over } => 1.0 - drop * ramp(step, over),
+            Self::Pump { rise, over } => 1.0 + rise * ramp(step, over),
+            Self::Chop { amplitude, period } => {
+                let phase = step as f64 / period.max(1) as f64;
+                1.0 + amplitude * (2.0 * PI * phase).sin()
+            }
+            Self::Depeg { drop, duration } => {
+                let start = steps / 4;
+                if step <= start {
+                    1.0
+                } else if step <= start + duration {
+                    1.0 - drop
+                } else {
+                    let recovery = ramp(step - start - duration, duration);
+                    1.0 - drop * (1.0 - recovery)
+                }
+            }
+        }
+    }
+
+    /// Generates the scenario's price path of `steps + 1` prices starting at
+    /// `entry`.
+    #[must_use]
+    pub fn price_path(&self, entry: Decimal, steps: usize) -> Vec<Price> {
+        let entry = entry.to_f64().unwrap_or(0.0);
+        (0..=steps)
+            .map(|step| {
+                let price = (entry * self.multiplier(step, steps)).max(0.0);
+                Price::new(Decimal::from_f64(price).unwrap_or(Decimal::ZERO))
+            })
+            .collect()
+    }
+}
+
+/// Progress of a linear move over `over` steps, from 0 to 1.
+fn ramp(step: usize, over: usize) -> f64 {
+    if over == 0 {
+        return 1.0;
+    }
+    (step as f64 / over as f64).min(1.0)
+}
+
+impl FromStr for Scenario {
+    type Err = String;
+
+    /// Parses a scenario name into the library scenario of that name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::library()
+            .into_iter()
+            .find(|scenario| scenario.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown scenario '{}' (expected one of: crash, pump, chop, depeg)",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(scenario: Scenario, steps: usize) -> Vec<f64> {
+        scenario
+            .price_path(Decimal::from(100), steps)
+            .iter()
+            .map(|p| p.value.to_f64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_crash_and_pump_paths() {
+        let crash = values(
+            Scenario::Crash {
+                drop: 0.4,
+                over: 10,
+            },
+            20,
+        );
+        assert_eq!(crash.len(), 21);
+        assert_eq!(crash[0], 100.0);
+        assert!((crash[5] - 80.0).abs() < 1e-9);
+        assert!((crash[20] - 60.0).abs() < 1e-9);
+
+        let pump = values(
+            Scenario::Pump {
+                rise: 0.5,
+                over: 10,
+            },
+            20,
+        );
+        assert!((pump[20] - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_chop_has_no_trend() {
+        let chop = values(
+            Scenario::Chop {
+                amplitude: 0.05,
+                period: 4,
+            },
+            8,
+        );
+        assert!((chop[1] - 105.0).abs() < 1e-9);
+        assert!((chop[3] - 95.0).abs() < 1e-9);
+        assert!((chop[8] - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_depeg_drops_and_recovers() {
+        let depeg = values(
+            Scenario::Depeg {
+                drop: 0.1,
+                duration: 10,
+            },
+            40,
+        );
+        assert_eq!(depeg[10], 100.0);
+        assert!((depeg[11] - 90.0).abs() < 1e-9);
+        assert!((depeg[20] - 90.0).abs() < 1e-9);
+        assert!((depeg[25] - 95.0).abs() < 1e-9);
+        assert!((depeg[40] - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_scenario() {
+        assert_eq!("Crash".parse::<Scenario>().unwrap().name(), "crash");
+        assert!("meltdown".parse::<Scenario>().is_err());
+        assert_eq!(Scenario::library().len(), 4);
+    }
+}
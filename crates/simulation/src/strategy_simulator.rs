@@ -9,7 +9,7 @@ use crate::price_path::PricePathGenerator;
 use crate::state::{SimulationConfig, SimulationSummary};
 use crate::strategies::{RebalanceAction, RebalanceReason, RebalanceStrategy, StrategyContext};
 use crate::volume::VolumeModel;
-use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
+use clmm_lp_domain::metrics::impermanent_loss::{ConcentratedIl, calculate_il_concentrated};
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
@@ -76,6 +76,7 @@ where
     let mut rebalance_count: u32 = 0;
     let mut total_rebalance_cost = Decimal::ZERO;
     let mut steps_since_rebalance: u64 = 0;
+    let mut closed = false;
 
     let mut pnl_history = Vec::with_capacity(prices.len());
     let mut il_history = Vec::with_capacity(prices.len());
@@ -83,6 +84,11 @@ where
     let mut range_history = Vec::new();
 
     let mut was_in_range = is_in_range(&entry_price, &current_range);
+    let mut il_model = concentrated_il(entry_price, &current_range);
+
+    // The LP share only depends on pool liquidity, so it is cached between steps
+    let mut pool_liquidity = 0;
+    let mut lp_share = Decimal::ZERO;
 
     // Record initial range
     range_history.push((0, current_range.clone()));
@@ -115,13 +121,10 @@ where
         was_in_range = in_range;
 
         // Calculate current IL for strategy context
-        let il_decimal = calculate_il_concentrated(
-            entry_price.value,
-            price.value,
-            current_range.lower_price.value,
-            current_range.upper_price.value,
-        )
-        .unwrap_or(Decimal::ZERO);
+        let il_decimal = il_model
+            .as_ref()
+            .and_then(|il| il.at(price.value).ok())
+            .unwrap_or(Decimal::ZERO);
 
         if il_decimal < max_il {
             max_il = il_decimal;
@@ -145,6 +148,7 @@ where
             RebalanceAction::Rebalance { new_range, reason } => {
                 let old_range = current_range.clone();
                 current_range = new_range.clone();
+                il_model = concentrated_il(entry_price, &current_range);
                 rebalance_count += 1;
                 total_rebalance_cost += config.rebalance_cost;
                 steps_since_rebalance = 0;
@@ -178,6 +182,7 @@ where
                         - total_rebalance_cost,
                 ));
                 // Position is closed, skip remaining steps
+                closed = true;
                 break;
             }
             RebalanceAction::Hold => {
@@ -191,10 +196,13 @@ where
             steps_in_range += 1;
 
             let volume = volume_model.get_volume(step);
-            let pool_liquidity = liquidity_model.get_liquidity(step);
+            let liquidity = liquidity_model.get_liquidity(step);
+            if liquidity != pool_liquidity && liquidity > 0 {
+                pool_liquidity = liquidity;
+                lp_share = Decimal::from(config.pool_liquidity) / Decimal::from(liquidity);
+            }
 
-            let step_fees = if pool_liquidity > 0 {
-                let lp_share = Decimal::from(config.pool_liquidity) / Decimal::from(pool_liquidity);
+            let step_fees = if liquidity > 0 {
                 volume * config.fee_rate * lp_share
             } else {
                 Decimal::ZERO
@@ -269,11 +277,7 @@ where
     let vs_hodl = final_value - hodl_value;
 
     // Record position closed if not already closed
-    if !event_log
-        .events()
-        .iter()
-        .any(|e| e.event_type == crate::event::SimulationEventType::PositionClosed)
-    {
+    if !closed {
         event_log.record(SimulationEvent::position_closed(
             prices.len() as u64,
             final_price,
@@ -305,7 +309,7 @@ where
 
     StrategySimulationResult {
         summary,
-        events: event_log.events().to_vec(),
+        events: event_log.into_events(),
         prices,
         pnl_history,
         il_history,
@@ -333,6 +337,16 @@ fn format_reason(reason: &RebalanceReason) -> String {
     }
 }
 
+/// Prepares the IL calculation for a range, if the range is valid.
+fn concentrated_il(entry_price: Price, range: &PriceRange) -> Option<ConcentratedIl> {
+    ConcentratedIl::new(
+        entry_price.value,
+        range.lower_price.value,
+        range.upper_price.value,
+    )
+    .ok()
+}
+
 /// Checks if a price is within a range.
 fn is_in_range(price: &Price, range: &PriceRange) -> bool {
     price.value >= range.lower_price.value && price.value <= range.upper_price.value
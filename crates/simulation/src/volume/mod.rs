@@ -1,5 +1,15 @@
+//! Volume models for fee estimation.
+//!
+//! Volumes are in the quote token (or USD) per simulation step, either a
+//! constant or replayed from a pool's historical swaps.
+
+mod swaps;
+
+pub use swaps::{SwapRecord, SwapVolume};
+
 use clmm_lp_domain::value_objects::amount::Amount;
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 
 /// Trait for modeling volume.
 pub trait VolumeModel {
@@ -8,6 +18,14 @@ pub trait VolumeModel {
 
     /// Returns the volume for a specific step as Decimal.
     fn get_volume(&mut self, step: usize) -> Decimal;
+
+    /// Returns the volume for the next step as `f64`.
+    ///
+    /// Used by the fast simulation path. The default converts
+    /// [`next_volume`](Self::next_volume).
+    fn next_volume_f64(&mut self) -> f64 {
+        self.next_volume().to_decimal().to_f64().unwrap_or(0.0)
+    }
 }
 
 /// Constant volume model.
@@ -17,15 +35,19 @@ pub struct ConstantVolume {
     pub amount: Amount,
     /// Volume as decimal for convenience.
     volume_decimal: Decimal,
+    /// Volume as f64 for the fast simulation path.
+    volume_f64: f64,
 }
 
 impl ConstantVolume {
     /// Creates a new constant volume model from an Amount.
     #[must_use]
     pub fn from_amount(amount: Amount) -> Self {
+        let volume_decimal = amount.to_decimal();
         Self {
             amount,
-            volume_decimal: amount.to_decimal(),
+            volume_decimal,
+            volume_f64: volume_decimal.to_f64().unwrap_or(0.0),
         }
     }
 
@@ -35,6 +57,7 @@ impl ConstantVolume {
         Self {
             amount: Amount::from_decimal(volume, 6),
             volume_decimal: volume,
+            volume_f64: volume.to_f64().unwrap_or(0.0),
         }
     }
 }
@@ -47,6 +70,8 @@ impl VolumeModel for ConstantVolume {
     fn get_volume(&mut self, _step: usize) -> Decimal {
         self.volume_decimal
     }
-}
 
-// Could add StochasticVolume later
+    fn next_volume_f64(&mut self) -> f64 {
+        self.volume_f64
+    }
+}
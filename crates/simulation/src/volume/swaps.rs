@@ -0,0 +1,155 @@
+//! Volume replayed from historical swaps.
+
+use super::VolumeModel;
+use clmm_lp_domain::value_objects::amount::Amount;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// A historical swap in a pool.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapRecord {
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Swapped volume in the quote token.
+    pub volume: Decimal,
+}
+
+/// Volume model replaying a pool's historical swaps.
+///
+/// Swaps are summed per simulation step, so each step sees the volume the
+/// pool actually traded over its period and quiet steps earn little or no
+/// fees.
+#[derive(Debug, Clone, Default)]
+pub struct SwapVolume {
+    /// Volume of each step.
+    volumes: Vec<Decimal>,
+    /// Volume of each step as `f64` for the fast simulation path.
+    volumes_f64: Vec<f64>,
+    /// Swaps within the replayed window.
+    swap_count: usize,
+    /// Next step returned by [`VolumeModel::next_volume`].
+    next_step: usize,
+}
+
+impl SwapVolume {
+    /// Sums swaps into `steps` steps of `step_seconds` each, the first
+    /// starting at `start`.
+    ///
+    /// Swaps outside the window are ignored.
+    #[must_use]
+    pub fn from_swaps(
+        swaps: impl IntoIterator<Item = SwapRecord>,
+        start: u64,
+        step_seconds: u64,
+        steps: usize,
+    ) -> Self {
+        let step_seconds = step_seconds.max(1);
+        let mut volumes = vec![Decimal::ZERO; steps];
+        let mut swap_count = 0;
+
+        for swap in swaps {
+            let Some(offset) = swap.timestamp.checked_sub(start) else {
+                continue;
+            };
+            let Some(volume) = usize::try_from(offset / step_seconds)
+                .ok()
+                .and_then(|step| volumes.get_mut(step))
+            else {
+                continue;
+            };
+            *volume += swap.volume.abs();
+            swap_count += 1;
+        }
+
+        let mut model = Self::from_volumes(volumes);
+        model.swap_count = swap_count;
+        model
+    }
+
+    /// Creates a model from volumes already summed per step, such as stored
+    /// candle volumes.
+    #[must_use]
+    pub fn from_volumes(volumes: Vec<Decimal>) -> Self {
+        let volumes_f64 = volumes.iter().map(|v| v.to_f64().unwrap_or(0.0)).collect();
+        Self {
+            swap_count: 0,
+            volumes,
+            volumes_f64,
+            next_step: 0,
+        }
+    }
+
+    /// Gets the volume of each step.
+    #[must_use]
+    pub fn volumes(&self) -> &[Decimal] {
+        &self.volumes
+    }
+
+    /// Gets the number of swaps within the replayed window.
+    #[must_use]
+    pub fn swap_count(&self) -> usize {
+        self.swap_count
+    }
+
+    /// Calculates the volume over all steps.
+    #[must_use]
+    pub fn total(&self) -> Decimal {
+        self.volumes.iter().copied().sum()
+    }
+}
+
+impl VolumeModel for SwapVolume {
+    fn next_volume(&mut self) -> Amount {
+        let volume = self.get_volume(self.next_step);
+        self.next_step += 1;
+        Amount::from_decimal(volume, 6)
+    }
+
+    fn get_volume(&mut self, step: usize) -> Decimal {
+        self.volumes.get(step).copied().unwrap_or(Decimal::ZERO)
+    }
+
+    fn next_volume_f64(&mut self) -> f64 {
+        let volume = self.volumes_f64.get(self.next_step).copied().unwrap_or(0.0);
+        self.next_step += 1;
+        volume
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn swap(timestamp: u64, volume: Decimal) -> SwapRecord {
+        SwapRecord { timestamp, volume }
+    }
+
+    #[test]
+    fn test_from_swaps_buckets_by_step() {
+        let swaps = [
+            swap(999, dec!(500)),
+            swap(1000, dec!(100)),
+            swap(3599 + 1000, dec!(50)),
+            swap(3600 + 1000, dec!(25)),
+            swap(3 * 3600 + 1000, dec!(-10)),
+            swap(4 * 3600 + 1000, dec!(1000)),
+        ];
+        let mut model = SwapVolume::from_swaps(swaps, 1000, 3600, 4);
+
+        assert_eq!(model.volumes(), &[dec!(150), dec!(25), dec!(0), dec!(10)]);
+        assert_eq!(model.swap_count(), 4);
+        assert_eq!(model.total(), dec!(185));
+        assert_eq!(model.get_volume(1), dec!(25));
+        assert_eq!(model.get_volume(10), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_next_volume_steps_through_history() {
+        let mut model = SwapVolume::from_volumes(vec![dec!(1), dec!(2)]);
+
+        assert_eq!(model.next_volume().to_decimal(), dec!(1));
+        assert_eq!(model.next_volume_f64(), 2.0);
+        assert_eq!(model.next_volume().to_decimal(), Decimal::ZERO);
+    }
+}
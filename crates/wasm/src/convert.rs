@@ -0,0 +1,36 @@
+//! Conversions between JavaScript numbers and `Decimal`.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Converts a JavaScript number argument to a `Decimal`.
+///
+/// # Errors
+/// Returns a message naming the argument if it is NaN or infinite.
+pub(crate) fn decimal(name: &str, value: f64) -> Result<Decimal, String> {
+    Decimal::from_f64(value).ok_or_else(|| format!("{} must be a finite number", name))
+}
+
+/// Converts a `Decimal` result to a JavaScript number.
+pub(crate) fn float(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Converts an array of JavaScript numbers to `Decimal`s.
+///
+/// # Errors
+/// Returns a message if any value is NaN or infinite.
+pub(crate) fn decimals(name: &str, values: &[f64]) -> Result<Vec<Decimal>, String> {
+    values.iter().map(|v| decimal(name, *v)).collect()
+}
+
+/// Returns an error unless `0 < lower < upper`.
+pub(crate) fn check_range(lower: Decimal, upper: Decimal) -> Result<(), String> {
+    if lower <= Decimal::ZERO {
+        Err("lower must be positive".to_string())
+    } else if lower >= upper {
+        Err("lower must be below upper".to_string())
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! WebAssembly bindings for the CLMM Liquidity Provider engine.
+//!
+//! Exposes range previews, price-path generation and quick backtests to
+//! JavaScript so a web UI can run them client-side. Build with
+//! [wasm-pack](https://rustwasm.github.io/wasm-pack/):
+//!
+//! ```bash
+//! wasm-pack build crates/wasm --target web
+//! ```
+//!
+//! ```js
+//! import init, { rangePreview, gbmPricePath, backtest } from "clmm-lp-wasm";
+//!
+//! await init();
+//! const preview = rangePreview(100, 90, 110, 1000);
+//! const prices = gbmPricePath(100, 0.6, 720, 0, 1 / 8760, 42);
+//! const result = backtest(prices, { lower: 90, upper: 110, strategy: "threshold" });
+//! ```
+//!
+//! Numbers cross the boundary as `f64` and are converted to `Decimal` on the
+//! Rust side; results are plain objects with camelCase fields and invalid
+//! arguments throw an `Error`.
+
+/// Number and `Decimal` conversions.
+mod convert;
+/// Range previews.
+pub mod preview;
+/// Price paths and backtests.
+pub mod simulation;
+
+use wasm_bindgen::prelude::*;
+
+/// Previews a range around the current price for a deposit of `capital`.
+///
+/// # Errors
+/// Throws if a price is not positive or the range is empty.
+#[wasm_bindgen(js_name = rangePreview)]
+pub fn range_preview(
+    current_price: f64,
+    lower: f64,
+    upper: f64,
+    capital: f64,
+) -> Result<JsValue, JsError> {
+    let preview = preview::range_preview(current_price, lower, upper, capital).map_err(js_error)?;
+    Ok(serde_wasm_bindgen::to_value(&preview)?)
+}
+
+/// Generates a geometric Brownian motion price path as a `Float64Array`.
+///
+/// # Errors
+/// Throws if `initialPrice` is not a finite number.
+#[wasm_bindgen(js_name = gbmPricePath)]
+pub fn gbm_price_path(
+    initial_price: f64,
+    volatility: f64,
+    steps: usize,
+    drift: f64,
+    time_step: f64,
+    seed: Option<u32>,
+) -> Result<Vec<f64>, JsError> {
+    simulation::gbm_price_path(
+        initial_price,
+        volatility,
+        steps,
+        drift,
+        time_step,
+        seed.map(u64::from),
+    )
+    .map_err(js_error)
+}
+
+/// Backtests a position over a price path.
+///
+/// `options` is a plain object with the fields of
+/// [`simulation::BacktestOptions`] in camelCase; only `lower` and `upper`
+/// are required.
+///
+/// # Errors
+/// Throws for malformed options, an unknown strategy or an empty range.
+#[wasm_bindgen]
+pub fn backtest(prices: &[f64], options: JsValue) -> Result<JsValue, JsError> {
+    let options: simulation::BacktestOptions = serde_wasm_bindgen::from_value(options)?;
+    let report = simulation::backtest(prices, &options).map_err(js_error)?;
+    Ok(serde_wasm_bindgen::to_value(&report)?)
+}
+
+/// Wraps an argument error for JavaScript.
+fn js_error(message: String) -> JsError {
+    JsError::new(&message)
+}
@@ -0,0 +1,110 @@
+//! Range previews built from the domain math.
+
+use crate::convert::{check_range, decimal, float};
+use clmm_lp_domain::prelude::{
+    calculate_il_concentrated, calculate_position_greeks, price_to_tick,
+};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// What a price range looks like before a position is opened.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RangePreview {
+    /// Tick of the lower bound.
+    pub lower_tick: i32,
+    /// Tick of the upper bound.
+    pub upper_tick: i32,
+    /// Whether the current price is inside the range.
+    pub in_range: bool,
+    /// Share of the deposit held as token0, by value.
+    pub token0_share: f64,
+    /// Token0 to deposit for `capital`.
+    pub amount0: f64,
+    /// Token1 to deposit for `capital`.
+    pub amount1: f64,
+    /// Impermanent loss if the price falls to the lower bound.
+    pub il_at_lower: f64,
+    /// Impermanent loss if the price rises to the upper bound.
+    pub il_at_upper: f64,
+    /// Price points of the IL curve, from half the lower bound to
+    /// 1.5x the upper bound.
+    pub curve_prices: Vec<f64>,
+    /// Impermanent loss at each curve price.
+    pub curve_il: Vec<f64>,
+}
+
+/// Number of points on the preview IL curve.
+const CURVE_POINTS: u32 = 50;
+
+/// Previews a range around the current price for a deposit of `capital`
+/// (in token1).
+///
+/// # Errors
+/// Returns a message if a price is not finite or positive, or the range is
+/// empty.
+pub fn range_preview(
+    current_price: f64,
+    lower: f64,
+    upper: f64,
+    capital: f64,
+) -> Result<RangePreview, String> {
+    let price = decimal("currentPrice", current_price)?;
+    let (lower, upper) = (decimal("lower", lower)?, decimal("upper", upper)?);
+    let capital = decimal("capital", capital)?;
+    check_range(lower, upper)?;
+    if price <= Decimal::ZERO {
+        return Err("currentPrice must be positive".to_string());
+    }
+
+    let greeks = calculate_position_greeks(Decimal::ONE, price, lower, upper)?;
+    let token0_share = greeks.delta_share(price);
+    let il = |at: Decimal| calculate_il_concentrated(price, at, lower, upper).map(float);
+
+    let start = lower / Decimal::TWO;
+    let step = (upper * Decimal::new(15, 1) - start) / Decimal::from(CURVE_POINTS - 1);
+    let curve_prices: Vec<Decimal> = (0..CURVE_POINTS)
+        .map(|i| start + step * Decimal::from(i))
+        .collect();
+
+    Ok(RangePreview {
+        lower_tick: price_to_tick(lower)?,
+        upper_tick: price_to_tick(upper)?,
+        in_range: price >= lower && price <= upper,
+        token0_share: float(token0_share),
+        amount0: float(capital * token0_share / price),
+        amount1: float(capital * (Decimal::ONE - token0_share)),
+        il_at_lower: il(lower)?,
+        il_at_upper: il(upper)?,
+        curve_il: curve_prices
+            .iter()
+            .map(|p| il(*p))
+            .collect::<Result<_, _>>()?,
+        curve_prices: curve_prices.into_iter().map(float).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_preview_splits_capital() {
+        let preview = range_preview(100.0, 90.0, 110.0, 1000.0).unwrap();
+        assert!(preview.in_range);
+        assert!(preview.lower_tick < preview.upper_tick);
+        assert!(preview.token0_share > 0.4 && preview.token0_share < 0.6);
+        let value = preview.amount0 * 100.0 + preview.amount1;
+        assert!((value - 1000.0).abs() < 1e-6);
+        assert!(preview.il_at_lower < 0.0 && preview.il_at_upper < 0.0);
+        assert_eq!(preview.curve_prices.len(), CURVE_POINTS as usize);
+        assert_eq!(preview.curve_il.len(), CURVE_POINTS as usize);
+
+        let above = range_preview(120.0, 90.0, 110.0, 1000.0).unwrap();
+        assert!(!above.in_range);
+        assert_eq!(above.amount0, 0.0);
+
+        assert!(range_preview(100.0, 110.0, 90.0, 1000.0).is_err());
+        assert!(range_preview(f64::NAN, 90.0, 110.0, 1000.0).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Price paths and quick backtests.
+
+use crate::convert::{check_range, decimal, decimals, float};
+use clmm_lp_domain::prelude::{Price, PriceRange};
+use clmm_lp_simulation::prelude::{
+    GeometricBrownianMotion, ILLimitStrategy, PeriodicRebalance, PositionTracker,
+    PricePathGenerator, RebalanceAction, RebalanceStrategy, StaticRange, ThresholdRebalance,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Generates a geometric Brownian motion price path.
+///
+/// `volatility` and `drift` are annualized; `time_step` is the length of a
+/// step in years. Returns `steps + 1` prices starting at `initial_price`.
+///
+/// # Errors
+/// Returns a message if `initial_price` is not a finite number.
+pub fn gbm_price_path(
+    initial_price: f64,
+    volatility: f64,
+    steps: usize,
+    drift: f64,
+    time_step: f64,
+    seed: Option<u64>,
+) -> Result<Vec<f64>, String> {
+    let mut gbm = GeometricBrownianMotion::new(
+        decimal("initialPrice", initial_price)?,
+        drift,
+        volatility,
+        time_step,
+    );
+    if let Some(seed) = seed {
+        gbm = gbm.with_seed(seed);
+    }
+    Ok(gbm
+        .generate(steps)
+        .into_iter()
+        .map(|p| float(p.value))
+        .collect())
+}
+
+/// Backtest settings, passed from JavaScript as a plain object.
+///
+/// Every field except the range bounds is optional.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BacktestOptions {
+    /// Lower bound of the initial range.
+    pub lower: f64,
+    /// Upper bound of the initial range.
+    pub upper: f64,
+    /// Capital deployed, in token1.
+    pub capital: f64,
+    /// `static`, `periodic`, `threshold` or `il_limit`.
+    pub strategy: String,
+    /// Steps between periodic rebalances.
+    pub rebalance_interval: u64,
+    /// Price move that triggers a threshold rebalance.
+    pub threshold_pct: f64,
+    /// Impermanent loss that triggers an IL-limit rebalance.
+    pub max_il_pct: f64,
+    /// Pool fee rate.
+    pub fee_rate: f64,
+    /// Volume traded every step, unless `volumes` is given.
+    pub volume: f64,
+    /// Volume traded at each step, one entry per price.
+    pub volumes: Option<Vec<f64>>,
+    /// Share of in-range fees earned by the position.
+    pub liquidity_share: f64,
+    /// Cost of each rebalance.
+    pub tx_cost: f64,
+}
+
+impl Default for BacktestOptions {
+    fn default() -> Self {
+        Self {
+            lower: 0.0,
+            upper: 0.0,
+            capital: 1000.0,
+            strategy: "static".to_string(),
+            rebalance_interval: 24,
+            threshold_pct: 0.05,
+            max_il_pct: 0.05,
+            fee_rate: 0.003,
+            volume: 1_000_000.0,
+            volumes: None,
+            liquidity_share: 0.01,
+            tx_cost: 1.0,
+        }
+    }
+}
+
+/// Result of a backtest.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestReport {
+    /// Number of replayed steps.
+    pub total_steps: u64,
+    /// Final position value.
+    pub final_value: f64,
+    /// Final PnL.
+    pub final_pnl: f64,
+    /// Final impermanent loss as a fraction.
+    pub final_il_pct: f64,
+    /// Fees earned.
+    pub total_fees: f64,
+    /// Share of steps spent inside the range.
+    pub time_in_range_pct: f64,
+    /// Number of rebalances.
+    pub rebalance_count: u32,
+    /// Cost of all rebalances.
+    pub total_rebalance_cost: f64,
+    /// Maximum drawdown as a fraction.
+    pub max_drawdown: f64,
+    /// Value of holding the initial tokens.
+    pub hodl_value: f64,
+    /// Final value minus the HODL value.
+    pub vs_hodl: f64,
+    /// Position value after every step.
+    pub equity_curve: Vec<f64>,
+    /// Steps at which the position was rebalanced.
+    pub rebalance_steps: Vec<u64>,
+}
+
+/// Backtests a position with a rebalancing strategy over a price path.
+///
+/// Rebalances keep the initial range width. Each in-range step earns its
+/// volume times `fee_rate` and `liquidity_share`.
+///
+/// # Errors
+/// Returns a message for an unknown strategy, an empty range or price
+/// path, or `volumes` of a different length than `prices`.
+pub fn backtest(prices: &[f64], options: &BacktestOptions) -> Result<BacktestReport, String> {
+    let prices = decimals("prices", prices)?;
+    let (lower, upper) = (
+        decimal("lower", options.lower)?,
+        decimal("upper", options.upper)?,
+    );
+    check_range(lower, upper)?;
+    let volumes = match &options.volumes {
+        Some(volumes) if volumes.len() != prices.len() => {
+            return Err("volumes must have one entry per price".to_string());
+        }
+        Some(volumes) => decimals("volumes", volumes)?,
+        None => vec![decimal("volume", options.volume)?; prices.len()],
+    };
+    let Some(entry_price) = prices.first() else {
+        return Err("prices must not be empty".to_string());
+    };
+
+    let range_width_pct = (upper - lower) / ((upper + lower) / Decimal::TWO);
+    let mut tracker = PositionTracker::new(
+        decimal("capital", options.capital)?,
+        Price::new(*entry_price),
+        PriceRange::new(Price::new(lower), Price::new(upper)),
+        decimal("txCost", options.tx_cost)?,
+    )
+    .with_capacity(prices.len());
+    let fee_per_volume =
+        decimal("feeRate", options.fee_rate)? * decimal("liquidityShare", options.liquidity_share)?;
+
+    match options.strategy.as_str() {
+        "static" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &StaticRange::new(),
+        ),
+        "periodic" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &PeriodicRebalance::new(options.rebalance_interval, range_width_pct),
+        ),
+        "threshold" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &ThresholdRebalance::new(
+                decimal("thresholdPct", options.threshold_pct)?,
+                range_width_pct,
+            ),
+        ),
+        "il_limit" => replay(
+            &mut tracker,
+            &prices,
+            &volumes,
+            fee_per_volume,
+            &ILLimitStrategy::new(decimal("maxIlPct", options.max_il_pct)?, range_width_pct),
+        ),
+        other => {
+            return Err(format!(
+                "unknown strategy '{}', expected static, periodic, threshold or il_limit",
+                other
+            ));
+        }
+    }
+
+    let summary = tracker.summary();
+    Ok(BacktestReport {
+        total_steps: summary.total_steps,
+        final_value: float(summary.final_value),
+        final_pnl: float(summary.final_pnl),
+        final_il_pct: float(summary.final_il_pct),
+        total_fees: float(summary.total_fees),
+        time_in_range_pct: float(summary.time_in_range_pct),
+        rebalance_count: summary.rebalance_count,
+        total_rebalance_cost: float(summary.total_rebalance_cost),
+        max_drawdown: float(summary.max_drawdown),
+        hodl_value: float(summary.hodl_value),
+        vs_hodl: float(summary.vs_hodl),
+        equity_curve: tracker.equity_curve().iter().copied().map(float).collect(),
+        rebalance_steps: tracker
+            .actions()
+            .iter()
+            .filter(|(_, action)| matches!(action, RebalanceAction::Rebalance { .. }))
+            .map(|(step, _)| *step)
+            .collect(),
+    })
+}
+
+/// Steps the tracker through every price with a strategy.
+fn replay<S: RebalanceStrategy>(
+    tracker: &mut PositionTracker,
+    prices: &[Decimal],
+    volumes: &[Decimal],
+    fee_per_volume: Decimal,
+    strategy: &S,
+) {
+    for (price, volume) in prices.iter().zip(volumes) {
+        let in_range = *price >= tracker.current_range.lower_price.value
+            && *price <= tracker.current_range.upper_price.value;
+        let fees = if in_range {
+            *volume * fee_per_volume
+        } else {
+            Decimal::ZERO
+        };
+        tracker.record_step(Price::new(*price), fees, Some(strategy));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gbm_price_path_is_seeded() {
+        let first = gbm_price_path(100.0, 0.5, 50, 0.0, 1.0 / 365.0, Some(7)).unwrap();
+        let second = gbm_price_path(100.0, 0.5, 50, 0.0, 1.0 / 365.0, Some(7)).unwrap();
+        assert_eq!(first.len(), 51);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_backtest_strategies() {
+        let prices: Vec<f64> = (0..48).map(|i| 100.0 + f64::from(i)).collect();
+        let options = BacktestOptions {
+            lower: 95.0,
+            upper: 105.0,
+            ..BacktestOptions::default()
+        };
+
+        let fixed = backtest(&prices, &options).unwrap();
+        assert_eq!(fixed.total_steps, 48);
+        assert_eq!(fixed.rebalance_count, 0);
+        assert_eq!(fixed.equity_curve.len(), 48);
+
+        let threshold = backtest(
+            &prices,
+            &BacktestOptions {
+                strategy: "threshold".to_string(),
+                ..options.clone()
+            },
+        )
+        .unwrap();
+        assert!(threshold.rebalance_count > 0);
+        assert!(threshold.total_fees > fixed.total_fees);
+
+        let unknown = BacktestOptions {
+            strategy: "yolo".to_string(),
+            ..options
+        };
+        assert!(backtest(&prices, &unknown).is_err());
+        assert!(backtest(&[], &BacktestOptions::default()).is_err());
+    }
+}